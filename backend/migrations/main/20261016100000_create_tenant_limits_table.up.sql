@@ -0,0 +1,34 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+CREATE TABLE tenant_limits (
+    tenant_id       uuid        primary key,
+    max_users       integer,
+    max_products    integer,
+    max_storage_mb  bigint,
+    created_at      timestamptz not null default now(),
+    updated_at      timestamptz not null default now(),
+    foreign key (tenant_id) references tenants (id)
+);
+
+CREATE TRIGGER update_updated_at_on_tenant_limits_table
+    BEFORE UPDATE
+    ON tenant_limits
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
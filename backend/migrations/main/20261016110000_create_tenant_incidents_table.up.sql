@@ -0,0 +1,29 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+CREATE TABLE tenant_incidents (
+    id              uuid        primary key default uuid_generate_v4(),
+    tenant_id       uuid        not null,
+    incident_type   varchar(50) not null,
+    message         text        not null,
+    created_at      timestamptz not null default now(),
+    foreign key (tenant_id) references tenants (id)
+);
+
+CREATE INDEX idx_tenant_incidents_tenant_id ON tenant_incidents (tenant_id);
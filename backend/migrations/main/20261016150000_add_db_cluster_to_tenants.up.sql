@@ -0,0 +1,25 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE tenants ADD COLUMN db_cluster varchar(100);
+
+-- NOTE: managed tenants created before multi-cluster placement live on the implicit default cluster
+UPDATE tenants SET db_cluster = 'default' WHERE is_self_hosted = false;
+
+CREATE INDEX idx_tenants_db_cluster ON tenants (db_cluster) WHERE db_cluster IS NOT NULL;
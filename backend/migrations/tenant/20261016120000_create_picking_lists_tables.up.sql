@@ -0,0 +1,83 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE inventory
+    ADD COLUMN zone         varchar(50),
+    ADD COLUMN bin_location varchar(50);
+
+create table picking_lists
+(
+    id             uuid primary key     default uuid_generate_v4(),
+    warehouse_id   uuid        not null,
+    zone           varchar(50),
+    status         varchar(20) not null default 'open' check (status IN ('open', 'in_progress', 'completed', 'cancelled')),
+    assigned_to_id uuid,
+    completed_at   timestamptz,
+    created_by_id  uuid        not null,
+    created_at     timestamptz not null default now(),
+    updated_at     timestamptz not null default now(),
+    foreign key (warehouse_id) references warehouses (id),
+    foreign key (assigned_to_id) references users (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_picking_lists_warehouse_id ON picking_lists (warehouse_id);
+CREATE INDEX idx_picking_lists_status ON picking_lists (status);
+CREATE INDEX idx_picking_lists_assigned_to_id ON picking_lists (assigned_to_id);
+
+CREATE TRIGGER update_updated_at_on_picking_lists_table
+    BEFORE UPDATE
+    ON picking_lists
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table picking_list_items
+(
+    id                       uuid primary key        default uuid_generate_v4(),
+    picking_list_id          uuid           not null,
+    inventory_reservation_id uuid           not null,
+    inventory_id             uuid           not null,
+    zone                     varchar(50),
+    bin_location             varchar(50),
+    walk_order               integer        not null,
+    quantity_requested       numeric(15, 2) not null check (quantity_requested > 0),
+    quantity_picked          numeric(15, 2) check (quantity_picked IS NULL OR
+                                                   (quantity_picked >= 0 AND quantity_picked <= quantity_requested)),
+    short_reason             text,
+    picked_by_id             uuid,
+    picked_at                timestamptz,
+    created_at               timestamptz    not null default now(),
+    updated_at               timestamptz    not null default now(),
+    foreign key (picking_list_id) references picking_lists (id) on delete cascade,
+    foreign key (inventory_reservation_id) references inventory_reservations (id),
+    foreign key (inventory_id) references inventory (id),
+    foreign key (picked_by_id) references users (id),
+    constraint check_short_pick_reason check (
+        quantity_picked IS NULL OR quantity_picked = quantity_requested OR short_reason IS NOT NULL
+        )
+);
+
+CREATE INDEX idx_picking_list_items_picking_list_id ON picking_list_items (picking_list_id, walk_order);
+CREATE INDEX idx_picking_list_items_inventory_reservation_id ON picking_list_items (inventory_reservation_id);
+
+CREATE TRIGGER update_updated_at_on_picking_list_items_table
+    BEFORE UPDATE
+    ON picking_list_items
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,50 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE products
+    ADD COLUMN weight_g  integer check (weight_g IS NULL OR weight_g >= 0),
+    ADD COLUMN length_mm integer check (length_mm IS NULL OR length_mm > 0),
+    ADD COLUMN width_mm  integer check (width_mm IS NULL OR width_mm > 0),
+    ADD COLUMN height_mm integer check (height_mm IS NULL OR height_mm > 0);
+
+create table package_types
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    name          varchar(255) not null,
+    length_mm     integer      not null check (length_mm > 0),
+    width_mm      integer      not null check (width_mm > 0),
+    height_mm     integer      not null check (height_mm > 0),
+    tare_weight_g integer      not null default 0 check (tare_weight_g >= 0),
+    max_weight_g  integer check (max_weight_g IS NULL OR max_weight_g > 0),
+    status        varchar(50)  not null default 'active' check (status IN ('active', 'inactive')),
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_package_types_status ON package_types (status);
+CREATE INDEX idx_package_types_deleted_at ON package_types (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_package_types_table
+    BEFORE UPDATE
+    ON package_types
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
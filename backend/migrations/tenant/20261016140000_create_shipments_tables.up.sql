@@ -0,0 +1,114 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table carrier_accounts
+(
+    id                    uuid primary key      default uuid_generate_v4(),
+    carrier               varchar(20)  not null check (carrier IN ('gls')),
+    name                  varchar(255) not null,
+    api_url               varchar(255) not null,
+    username              varchar(255) not null,
+    password              varchar(255) not null,
+    client_number         varchar(50)  not null,
+    sender_name           varchar(255) not null,
+    sender_street         varchar(255) not null,
+    sender_house_number   varchar(50)  not null,
+    sender_city           varchar(100) not null,
+    sender_zip_code       varchar(20)  not null,
+    sender_country_code   varchar(2)   not null,
+    sender_contact_name   varchar(255),
+    sender_contact_phone  varchar(50),
+    sender_contact_email  varchar(255),
+    status                varchar(50)  not null default 'active' check (status IN ('active', 'inactive')),
+    created_by_id         uuid         not null,
+    created_at            timestamptz  not null default now(),
+    updated_at            timestamptz  not null default now(),
+    deleted_at            timestamptz,
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE TRIGGER update_updated_at_on_carrier_accounts_table
+    BEFORE UPDATE
+    ON carrier_accounts
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table shipments
+(
+    id                     uuid primary key      default uuid_generate_v4(),
+    carrier_account_id     uuid         not null,
+    picking_list_id        uuid,
+    package_type_id        uuid,
+    reference              varchar(100),
+    recipient_name         varchar(255) not null,
+    recipient_street       varchar(255) not null,
+    recipient_house_number varchar(50)  not null,
+    recipient_city         varchar(100) not null,
+    recipient_zip_code     varchar(20)  not null,
+    recipient_country_code varchar(2)   not null,
+    recipient_phone        varchar(50),
+    recipient_email        varchar(255),
+    parcel_count           integer      not null default 1 check (parcel_count > 0),
+    weight_g               integer check (weight_g IS NULL OR weight_g > 0),
+    cod_amount             numeric(15, 2) check (cod_amount IS NULL OR cod_amount > 0),
+    status                 varchar(20)  not null default 'draft'
+        check (status IN ('draft', 'booked', 'in_transit', 'delivered', 'cancelled')),
+    carrier_parcel_id      varchar(50),
+    tracking_number        varchar(50),
+    label_pdf              bytea,
+    booked_at              timestamptz,
+    delivered_at           timestamptz,
+    tracking_checked_at    timestamptz,
+    created_by_id          uuid         not null,
+    created_at             timestamptz  not null default now(),
+    updated_at             timestamptz  not null default now(),
+    foreign key (carrier_account_id) references carrier_accounts (id),
+    foreign key (picking_list_id) references picking_lists (id),
+    foreign key (package_type_id) references package_types (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_booked_shipment_has_tracking check (
+        status IN ('draft', 'cancelled') OR tracking_number IS NOT NULL
+        )
+);
+
+CREATE INDEX idx_shipments_status ON shipments (status);
+CREATE INDEX idx_shipments_picking_list_id ON shipments (picking_list_id);
+CREATE INDEX idx_shipments_tracking_number ON shipments (tracking_number);
+
+CREATE TRIGGER update_updated_at_on_shipments_table
+    BEFORE UPDATE
+    ON shipments
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table shipment_events
+(
+    id          uuid primary key     default uuid_generate_v4(),
+    shipment_id uuid        not null,
+    status      varchar(20) not null,
+    code        varchar(20) not null,
+    description text,
+    location    varchar(255),
+    occurred_at timestamptz not null,
+    created_at  timestamptz not null default now(),
+    foreign key (shipment_id) references shipments (id) on delete cascade,
+    unique (shipment_id, code, occurred_at)
+);
+
+CREATE INDEX idx_shipment_events_shipment_id ON shipment_events (shipment_id);
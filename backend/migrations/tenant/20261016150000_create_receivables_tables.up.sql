@@ -0,0 +1,81 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE customers
+    ADD COLUMN sales_rep_id uuid REFERENCES users (id);
+
+CREATE INDEX idx_customers_sales_rep_id ON customers (sales_rep_id);
+
+create table receivables
+(
+    id              uuid primary key       default uuid_generate_v4(),
+    customer_id     uuid          not null,
+    document_number varchar(100)  not null,
+    issue_date      date          not null,
+    due_date        date          not null,
+    currency_code   varchar(3)    not null,
+    amount          numeric(15, 2) not null check (amount > 0),
+    paid_amount     numeric(15, 2) not null default 0 check (paid_amount >= 0),
+    status          varchar(50)   not null default 'open' check (status IN ('open', 'paid', 'written_off')),
+    created_by_id   uuid          not null,
+    created_at      timestamptz   not null default now(),
+    updated_at      timestamptz   not null default now(),
+    deleted_at      timestamptz,
+    foreign key (customer_id) references customers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    unique nulls not distinct (document_number, deleted_at),
+    constraint check_receivable_due_date check (due_date >= issue_date),
+    constraint check_receivable_paid_amount check (paid_amount <= amount)
+);
+
+CREATE INDEX idx_receivables_customer_id ON receivables (customer_id);
+CREATE INDEX idx_receivables_due_date ON receivables (due_date);
+CREATE INDEX idx_receivables_status ON receivables (status);
+CREATE INDEX idx_receivables_deleted_at ON receivables (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_receivables_table
+    BEFORE UPDATE
+    ON receivables
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table collection_activities
+(
+    id              uuid primary key      default uuid_generate_v4(),
+    customer_id     uuid         not null,
+    receivable_id   uuid,
+    activity_type   varchar(50)  not null check (activity_type IN ('call', 'email', 'letter', 'visit', 'promise_to_pay', 'note')),
+    note            text,
+    promised_amount numeric(15, 2) check (promised_amount IS NULL OR promised_amount > 0),
+    promised_date   date,
+    created_by_id   uuid         not null,
+    created_at      timestamptz  not null default now(),
+    foreign key (customer_id) references customers (id),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_promise_to_pay check (
+        activity_type <> 'promise_to_pay' OR promised_date IS NOT NULL
+        )
+);
+
+CREATE INDEX idx_collection_activities_customer_id ON collection_activities (customer_id);
+CREATE INDEX idx_collection_activities_receivable_id ON collection_activities (receivable_id);
+CREATE INDEX idx_collection_activities_created_by_id ON collection_activities (created_by_id);
+CREATE INDEX idx_collection_activities_created_at ON collection_activities (created_at);
@@ -0,0 +1,57 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE inventory_movements
+    ALTER COLUMN tax_id DROP NOT NULL;
+
+create table user_permissions
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    user_id       uuid         not null,
+    permission    varchar(100) not null,
+    granted_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    foreign key (user_id) references users (id),
+    foreign key (granted_by_id) references users (id),
+    unique (user_id, permission)
+);
+
+CREATE INDEX idx_user_permissions_user_id ON user_permissions (user_id);
+
+create table inventory_adjustments
+(
+    id                    uuid primary key        default uuid_generate_v4(),
+    inventory_id          uuid           not null,
+    inventory_movement_id uuid           not null,
+    quantity              numeric(15, 2) not null check (quantity <> 0),
+    reason_code           varchar(50)    not null check (reason_code IN ('damage', 'loss', 'correction', 'initial_load')),
+    note                  text,
+    created_by_id         uuid           not null,
+    created_at            timestamptz    not null default now(),
+    foreign key (inventory_id) references inventory (id),
+    foreign key (inventory_movement_id) references inventory_movements (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_adjustment_direction check (
+        reason_code NOT IN ('damage', 'loss') OR quantity < 0
+        )
+);
+
+CREATE INDEX idx_inventory_adjustments_inventory_id ON inventory_adjustments (inventory_id);
+CREATE INDEX idx_inventory_adjustments_reason_code ON inventory_adjustments (reason_code);
+CREATE INDEX idx_inventory_adjustments_created_at ON inventory_adjustments (created_at);
@@ -0,0 +1,27 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TABLE IF EXISTS worksheet_planned_materials;
+DROP TABLE IF EXISTS worksheet_checklist_items;
+ALTER TABLE worksheets
+    DROP COLUMN IF EXISTS estimated_duration_minutes,
+    DROP COLUMN IF EXISTS worksheet_template_id;
+DROP TABLE IF EXISTS worksheet_template_materials;
+DROP TABLE IF EXISTS worksheet_template_services;
+DROP TABLE IF EXISTS worksheet_templates;
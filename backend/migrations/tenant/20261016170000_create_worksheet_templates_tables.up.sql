@@ -0,0 +1,99 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table worksheet_templates
+(
+    id                         uuid primary key      default uuid_generate_v4(),
+    name                       varchar(255) not null,
+    description                text,
+    estimated_duration_minutes integer check (estimated_duration_minutes IS NULL OR estimated_duration_minutes > 0),
+    checklist                  text[]       not null default '{}',
+    status                     varchar(50)  not null default 'active' check (status IN ('active', 'inactive')),
+    created_by_id              uuid         not null,
+    created_at                 timestamptz  not null default now(),
+    updated_at                 timestamptz  not null default now(),
+    deleted_at                 timestamptz,
+    foreign key (created_by_id) references users (id),
+    unique nulls not distinct (name, deleted_at)
+);
+
+CREATE INDEX idx_worksheet_templates_status ON worksheet_templates (status);
+CREATE INDEX idx_worksheet_templates_deleted_at ON worksheet_templates (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_worksheet_templates_table
+    BEFORE UPDATE
+    ON worksheet_templates
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table worksheet_template_services
+(
+    id                    uuid primary key        default uuid_generate_v4(),
+    worksheet_template_id uuid           not null,
+    service_id            uuid           not null,
+    quantity              numeric(15, 2) not null check (quantity > 0),
+    position              integer        not null,
+    foreign key (worksheet_template_id) references worksheet_templates (id) on delete cascade,
+    foreign key (service_id) references services (id)
+);
+
+CREATE INDEX idx_worksheet_template_services_template_id ON worksheet_template_services (worksheet_template_id);
+
+create table worksheet_template_materials
+(
+    id                    uuid primary key        default uuid_generate_v4(),
+    worksheet_template_id uuid           not null,
+    product_id            uuid           not null,
+    quantity              numeric(15, 2) not null check (quantity > 0),
+    position              integer        not null,
+    foreign key (worksheet_template_id) references worksheet_templates (id) on delete cascade,
+    foreign key (product_id) references products (id)
+);
+
+CREATE INDEX idx_worksheet_template_materials_template_id ON worksheet_template_materials (worksheet_template_id);
+
+ALTER TABLE worksheets
+    ADD COLUMN worksheet_template_id      uuid REFERENCES worksheet_templates (id),
+    ADD COLUMN estimated_duration_minutes integer;
+
+create table worksheet_checklist_items
+(
+    id              uuid primary key      default uuid_generate_v4(),
+    worksheet_id    uuid         not null,
+    position        integer      not null,
+    title           text         not null,
+    completed_at    timestamptz,
+    completed_by_id uuid,
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (completed_by_id) references users (id)
+);
+
+CREATE INDEX idx_worksheet_checklist_items_worksheet_id ON worksheet_checklist_items (worksheet_id);
+
+create table worksheet_planned_materials
+(
+    id           uuid primary key        default uuid_generate_v4(),
+    worksheet_id uuid           not null,
+    product_id   uuid           not null,
+    quantity     numeric(15, 2) not null check (quantity > 0),
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (product_id) references products (id)
+);
+
+CREATE INDEX idx_worksheet_planned_materials_worksheet_id ON worksheet_planned_materials (worksheet_id);
@@ -0,0 +1,69 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table stock_transfers
+(
+    id                       uuid primary key     default uuid_generate_v4(),
+    source_warehouse_id      uuid        not null,
+    destination_warehouse_id uuid        not null,
+    status                   varchar(20) not null default 'draft' check (status IN ('draft', 'dispatched', 'received', 'cancelled')),
+    note                     text,
+    dispatched_at            timestamptz,
+    dispatched_by_id         uuid,
+    received_at              timestamptz,
+    received_by_id           uuid,
+    created_by_id            uuid        not null,
+    created_at               timestamptz not null default now(),
+    updated_at               timestamptz not null default now(),
+    foreign key (source_warehouse_id) references warehouses (id),
+    foreign key (destination_warehouse_id) references warehouses (id),
+    foreign key (dispatched_by_id) references users (id),
+    foreign key (received_by_id) references users (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_stock_transfer_warehouses check (source_warehouse_id <> destination_warehouse_id)
+);
+
+CREATE INDEX idx_stock_transfers_source_warehouse_id ON stock_transfers (source_warehouse_id);
+CREATE INDEX idx_stock_transfers_destination_warehouse_id ON stock_transfers (destination_warehouse_id);
+CREATE INDEX idx_stock_transfers_status ON stock_transfers (status);
+CREATE INDEX idx_stock_transfers_created_at ON stock_transfers (created_at);
+
+CREATE TRIGGER update_updated_at_on_stock_transfers_table
+    BEFORE UPDATE
+    ON stock_transfers
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table stock_transfer_items
+(
+    id                       uuid primary key        default uuid_generate_v4(),
+    stock_transfer_id        uuid           not null,
+    product_id               uuid           not null,
+    quantity                 numeric(15, 2) not null check (quantity > 0),
+    source_inventory_id      uuid,
+    destination_inventory_id uuid,
+    foreign key (stock_transfer_id) references stock_transfers (id) on delete cascade,
+    foreign key (product_id) references products (id),
+    foreign key (source_inventory_id) references inventory (id),
+    foreign key (destination_inventory_id) references inventory (id),
+    unique (stock_transfer_id, product_id)
+);
+
+CREATE INDEX idx_stock_transfer_items_stock_transfer_id ON stock_transfer_items (stock_transfer_id);
+CREATE INDEX idx_stock_transfer_items_product_id ON stock_transfer_items (product_id);
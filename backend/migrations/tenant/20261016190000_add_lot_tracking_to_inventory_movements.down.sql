@@ -0,0 +1,25 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP INDEX IF EXISTS idx_inventory_movements_inventory_id_lot_number;
+DROP INDEX IF EXISTS idx_inventory_movements_lot_number;
+ALTER TABLE inventory_movements
+    DROP CONSTRAINT IF EXISTS check_expiry_date_requires_lot,
+    DROP COLUMN IF EXISTS expiry_date,
+    DROP COLUMN IF EXISTS lot_number;
@@ -0,0 +1,43 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TRIGGER IF EXISTS watch_assigned_record_on_project_assignments_table ON project_assignments;
+DROP TRIGGER IF EXISTS watch_assigned_record_on_task_assignments_table ON task_assignments;
+DROP TRIGGER IF EXISTS notify_comment_watchers_on_comments_table ON comments;
+
+DO
+$$
+DECLARE
+    watchable_table text;
+BEGIN
+    FOREACH watchable_table IN ARRAY ARRAY ['customers', 'projects', 'worksheets', 'tasks', 'products', 'warehouses', 'services']
+        LOOP
+            EXECUTE format('DROP TRIGGER IF EXISTS notify_record_watchers_on_%1$s_table ON %1$s', watchable_table);
+            EXECUTE format('DROP TRIGGER IF EXISTS watch_created_record_on_%1$s_table ON %1$s', watchable_table);
+        END LOOP;
+END;
+$$;
+
+DROP FUNCTION IF EXISTS watch_assigned_record();
+DROP FUNCTION IF EXISTS notify_comment_watchers();
+DROP FUNCTION IF EXISTS notify_record_watchers();
+DROP FUNCTION IF EXISTS watch_created_record();
+
+DROP TABLE IF EXISTS watch_notifications;
+DROP TABLE IF EXISTS watches;
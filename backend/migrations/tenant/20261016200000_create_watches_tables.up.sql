@@ -0,0 +1,152 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table watches
+(
+    id             uuid primary key     default uuid_generate_v4(),
+    user_id        uuid        not null,
+    watchable_type varchar(50) not null,
+    watchable_id   uuid        not null,
+    created_at     timestamptz not null default now(),
+    unique (user_id, watchable_type, watchable_id),
+    foreign key (user_id) references users (id)
+);
+
+CREATE INDEX idx_watches_watchable ON watches (watchable_type, watchable_id);
+CREATE INDEX idx_watches_user_id ON watches (user_id);
+
+create table watch_notifications
+(
+    id             uuid primary key     default uuid_generate_v4(),
+    user_id        uuid        not null,
+    watchable_type varchar(50) not null,
+    watchable_id   uuid        not null,
+    event          varchar(20) not null check (event IN ('updated', 'deleted', 'commented', 'assigned')),
+    created_at     timestamptz not null default now(),
+    read_at        timestamptz,
+    foreign key (user_id) references users (id)
+);
+
+CREATE INDEX idx_watch_notifications_user_id ON watch_notifications (user_id);
+CREATE INDEX idx_watch_notifications_unread ON watch_notifications (user_id, created_at) WHERE read_at IS NULL;
+
+CREATE OR REPLACE FUNCTION watch_created_record()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    INSERT INTO watches (user_id, watchable_type, watchable_id)
+    VALUES (NEW.created_by_id, TG_TABLE_NAME, NEW.id)
+    ON CONFLICT DO NOTHING;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION notify_record_watchers()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+    SELECT user_id,
+           TG_TABLE_NAME,
+           NEW.id,
+           CASE WHEN NEW.deleted_at IS NOT NULL AND OLD.deleted_at IS NULL THEN 'deleted' ELSE 'updated' END
+    FROM watches
+    WHERE watchable_type = TG_TABLE_NAME
+      AND watchable_id = NEW.id;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION notify_comment_watchers()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+    SELECT user_id, watchable_type, watchable_id, 'commented'
+    FROM watches
+    WHERE watchable_type = NEW.commentable_type
+      AND watchable_id = NEW.commentable_id
+      AND user_id <> NEW.created_by_id;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION watch_assigned_record()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    target_type varchar(50);
+    target_id   uuid;
+BEGIN
+    IF TG_TABLE_NAME = 'task_assignments' THEN
+        target_type := 'tasks';
+        target_id := NEW.task_id;
+    ELSE
+        target_type := 'projects';
+        target_id := NEW.project_id;
+    END IF;
+
+    INSERT INTO watches (user_id, watchable_type, watchable_id)
+    VALUES (NEW.user_id, target_type, target_id)
+    ON CONFLICT DO NOTHING;
+
+    INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+    VALUES (NEW.user_id, target_type, target_id, 'assigned');
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DO
+$$
+DECLARE
+    watchable_table text;
+BEGIN
+    FOREACH watchable_table IN ARRAY ARRAY ['customers', 'projects', 'worksheets', 'tasks', 'products', 'warehouses', 'services']
+        LOOP
+            EXECUTE format(
+                    'CREATE TRIGGER watch_created_record_on_%1$s_table AFTER INSERT ON %1$s FOR EACH ROW EXECUTE FUNCTION watch_created_record()',
+                    watchable_table);
+            EXECUTE format(
+                    'CREATE TRIGGER notify_record_watchers_on_%1$s_table AFTER UPDATE ON %1$s FOR EACH ROW EXECUTE FUNCTION notify_record_watchers()',
+                    watchable_table);
+        END LOOP;
+END;
+$$;
+
+CREATE TRIGGER notify_comment_watchers_on_comments_table
+    AFTER INSERT
+    ON comments
+    FOR EACH ROW
+EXECUTE FUNCTION notify_comment_watchers();
+
+CREATE TRIGGER watch_assigned_record_on_task_assignments_table
+    AFTER INSERT
+    ON task_assignments
+    FOR EACH ROW
+EXECUTE FUNCTION watch_assigned_record();
+
+CREATE TRIGGER watch_assigned_record_on_project_assignments_table
+    AFTER INSERT
+    ON project_assignments
+    FOR EACH ROW
+EXECUTE FUNCTION watch_assigned_record();
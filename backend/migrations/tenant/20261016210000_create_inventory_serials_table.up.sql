@@ -0,0 +1,49 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table inventory_serials
+(
+    id                  uuid primary key      default uuid_generate_v4(),
+    product_id          uuid         not null,
+    inventory_id        uuid         not null,
+    serial_number       varchar(100) not null,
+    status              varchar(20)  not null default 'in_stock' check (status IN ('in_stock', 'reserved', 'sold', 'returned')),
+    receipt_movement_id uuid         not null,
+    issue_movement_id   uuid,
+    created_by_id       uuid         not null,
+    created_at          timestamptz  not null default now(),
+    updated_at          timestamptz  not null default now(),
+    unique (product_id, serial_number),
+    foreign key (product_id) references products (id),
+    foreign key (inventory_id) references inventory (id),
+    foreign key (receipt_movement_id) references inventory_movements (id),
+    foreign key (issue_movement_id) references inventory_movements (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_inventory_serials_serial_number ON inventory_serials (serial_number);
+CREATE INDEX idx_inventory_serials_inventory_id_status ON inventory_serials (inventory_id, status);
+CREATE INDEX idx_inventory_serials_receipt_movement_id ON inventory_serials (receipt_movement_id);
+CREATE INDEX idx_inventory_serials_issue_movement_id ON inventory_serials (issue_movement_id);
+
+CREATE TRIGGER update_updated_at_on_inventory_serials_table
+    BEFORE UPDATE
+    ON inventory_serials
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
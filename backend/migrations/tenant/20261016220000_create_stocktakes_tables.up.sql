@@ -0,0 +1,64 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table stocktakes
+(
+    id             uuid primary key     default uuid_generate_v4(),
+    warehouse_id   uuid        not null,
+    status         varchar(20) not null default 'open' check (status IN ('open', 'approved', 'cancelled')),
+    note           text,
+    approved_at    timestamptz,
+    approved_by_id uuid,
+    created_by_id  uuid        not null,
+    created_at     timestamptz not null default now(),
+    updated_at     timestamptz not null default now(),
+    foreign key (warehouse_id) references warehouses (id),
+    foreign key (approved_by_id) references users (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_stocktakes_warehouse_id ON stocktakes (warehouse_id);
+CREATE INDEX idx_stocktakes_status ON stocktakes (status);
+CREATE INDEX idx_stocktakes_created_at ON stocktakes (created_at);
+
+CREATE TRIGGER update_updated_at_on_stocktakes_table
+    BEFORE UPDATE
+    ON stocktakes
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table stocktake_lines
+(
+    id                      uuid primary key        default uuid_generate_v4(),
+    stocktake_id            uuid           not null,
+    inventory_id            uuid           not null,
+    expected_quantity       numeric(15, 2) not null,
+    counted_quantity        numeric(15, 2) check (counted_quantity >= 0),
+    counted_at              timestamptz,
+    counted_by_id           uuid,
+    inventory_adjustment_id uuid,
+    unique (stocktake_id, inventory_id),
+    foreign key (stocktake_id) references stocktakes (id) on delete cascade,
+    foreign key (inventory_id) references inventory (id),
+    foreign key (counted_by_id) references users (id),
+    foreign key (inventory_adjustment_id) references inventory_adjustments (id)
+);
+
+CREATE INDEX idx_stocktake_lines_stocktake_id ON stocktake_lines (stocktake_id);
+CREATE INDEX idx_stocktake_lines_inventory_id ON stocktake_lines (inventory_id);
@@ -0,0 +1,31 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE products ADD COLUMN minimum_stock numeric(15, 2) CHECK (minimum_stock >= 0);
+
+create table low_stock_alert_subscriptions
+(
+    user_id    uuid primary key,
+    created_at timestamptz not null default now(),
+    foreign key (user_id) references users (id)
+);
+
+ALTER TABLE watch_notifications DROP CONSTRAINT watch_notifications_event_check;
+ALTER TABLE watch_notifications ADD CONSTRAINT watch_notifications_event_check
+    CHECK (event IN ('updated', 'deleted', 'commented', 'assigned', 'low_stock'));
@@ -0,0 +1,26 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TRIGGER IF EXISTS apply_cost_on_inventory_movement ON inventory_movements;
+DROP FUNCTION IF EXISTS apply_inventory_movement_cost();
+DROP TABLE IF EXISTS inventory_cost_layers;
+ALTER TABLE inventory_movements DROP COLUMN IF EXISTS total_cost;
+ALTER TABLE inventory_movements DROP COLUMN IF EXISTS unit_cost;
+ALTER TABLE inventory DROP COLUMN IF EXISTS average_cost;
+DROP TABLE IF EXISTS costing_settings;
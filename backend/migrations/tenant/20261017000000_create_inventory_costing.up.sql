@@ -0,0 +1,176 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table costing_settings
+(
+    id            boolean primary key  default true check (id),
+    method        varchar(20) not null default 'weighted_average' check (method IN ('weighted_average', 'fifo')),
+    updated_by_id uuid,
+    updated_at    timestamptz not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO costing_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_costing_settings_table
+    BEFORE UPDATE
+    ON costing_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+ALTER TABLE inventory ADD COLUMN average_cost numeric(15, 4) not null default 0;
+ALTER TABLE inventory_movements ADD COLUMN unit_cost numeric(15, 4);
+ALTER TABLE inventory_movements ADD COLUMN total_cost numeric(15, 2);
+
+create table inventory_cost_layers
+(
+    id                 uuid primary key        default uuid_generate_v4(),
+    inventory_id       uuid           not null,
+    movement_id        uuid,
+    unit_cost          numeric(15, 4) not null check (unit_cost >= 0),
+    quantity_received  numeric(15, 2) not null check (quantity_received > 0),
+    quantity_remaining numeric(15, 2) not null check (quantity_remaining >= 0),
+    received_at        timestamptz    not null default now(),
+    foreign key (inventory_id) references inventory (id),
+    foreign key (movement_id) references inventory_movements (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX idx_inventory_cost_layers_open ON inventory_cost_layers (inventory_id, received_at) WHERE quantity_remaining > 0;
+CREATE INDEX idx_inventory_cost_layers_movement_id ON inventory_cost_layers (movement_id);
+
+-- Existing stock is opened as a single layer at the average purchase price
+UPDATE inventory
+SET average_cost = costs.average_cost
+FROM (SELECT inventory_id,
+             round(SUM(unit_price * quantity) / SUM(quantity), 4) AS average_cost
+      FROM inventory_movements
+      WHERE movement_type = 'in'
+        AND unit_price IS NOT NULL
+      GROUP BY inventory_id) costs
+WHERE inventory.id = costs.inventory_id;
+
+UPDATE inventory_movements
+SET unit_cost  = CASE
+                     WHEN inventory_movements.movement_type = 'in' AND inventory_movements.unit_price IS NOT NULL
+                         THEN inventory_movements.unit_price
+                     ELSE inventory.average_cost
+    END,
+    total_cost = round(CASE
+                           WHEN inventory_movements.movement_type = 'in' AND inventory_movements.unit_price IS NOT NULL
+                               THEN inventory_movements.unit_price
+                           ELSE inventory.average_cost
+                           END * inventory_movements.quantity, 2)
+FROM inventory
+WHERE inventory.id = inventory_movements.inventory_id;
+
+INSERT INTO inventory_cost_layers (inventory_id, unit_cost, quantity_received, quantity_remaining)
+SELECT id, average_cost, quantity_on_hand, quantity_on_hand
+FROM inventory
+WHERE quantity_on_hand > 0;
+
+-- Costs every movement in the inserting transaction, the inventory row lock serializes
+-- concurrent movements of the same stock
+CREATE OR REPLACE FUNCTION apply_inventory_movement_cost()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    costing_method   varchar(20);
+    current_quantity numeric(15, 2);
+    current_cost     numeric(15, 4);
+    remaining        numeric(15, 2);
+    taken            numeric(15, 2);
+    issued_cost      numeric := 0;
+    layer            record;
+BEGIN
+    SELECT quantity_on_hand, average_cost
+    INTO current_quantity, current_cost
+    FROM inventory
+    WHERE id = NEW.inventory_id
+        FOR UPDATE;
+
+    IF NEW.quantity > 0 THEN
+        NEW.unit_cost := COALESCE(
+                NEW.unit_cost,
+                CASE WHEN NEW.movement_type = 'in' THEN NEW.unit_price END,
+                current_cost
+                         );
+        NEW.total_cost := round(NEW.unit_cost * NEW.quantity, 2);
+
+        INSERT INTO inventory_cost_layers (inventory_id, movement_id, unit_cost, quantity_received,
+                                           quantity_remaining, received_at)
+        VALUES (NEW.inventory_id, NEW.id, NEW.unit_cost, NEW.quantity, NEW.quantity, NEW.movement_date);
+
+        UPDATE inventory
+        SET average_cost = round(
+                (GREATEST(current_quantity, 0) * current_cost + NEW.quantity * NEW.unit_cost)
+                    / (GREATEST(current_quantity, 0) + NEW.quantity), 4)
+        WHERE id = NEW.inventory_id;
+    ELSIF NEW.quantity < 0 THEN
+        SELECT method INTO costing_method FROM costing_settings;
+
+        remaining := -NEW.quantity;
+        FOR layer IN
+            SELECT id, unit_cost, quantity_remaining
+            FROM inventory_cost_layers
+            WHERE inventory_id = NEW.inventory_id
+              AND quantity_remaining > 0
+            ORDER BY received_at, id
+                FOR UPDATE
+            LOOP
+                EXIT WHEN remaining <= 0;
+                taken := LEAST(remaining, layer.quantity_remaining);
+                UPDATE inventory_cost_layers
+                SET quantity_remaining = quantity_remaining - taken
+                WHERE id = layer.id;
+                issued_cost := issued_cost + taken * layer.unit_cost;
+                remaining := remaining - taken;
+            END LOOP;
+        -- Quantity not covered by layers is issued at the current average cost
+        issued_cost := issued_cost + remaining * current_cost;
+
+        IF costing_method = 'fifo' THEN
+            NEW.unit_cost := round(issued_cost / -NEW.quantity, 4);
+            NEW.total_cost := -round(issued_cost, 2);
+
+            UPDATE inventory
+            SET average_cost = COALESCE(
+                    (SELECT round(SUM(quantity_remaining * unit_cost) / NULLIF(SUM(quantity_remaining), 0), 4)
+                     FROM inventory_cost_layers
+                     WHERE inventory_id = NEW.inventory_id
+                       AND quantity_remaining > 0),
+                    current_cost)
+            WHERE id = NEW.inventory_id;
+        ELSE
+            NEW.unit_cost := current_cost;
+            NEW.total_cost := round(current_cost * NEW.quantity, 2);
+        END IF;
+    ELSE
+        NEW.unit_cost := current_cost;
+        NEW.total_cost := 0;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER apply_cost_on_inventory_movement
+    BEFORE INSERT
+    ON inventory_movements
+    FOR EACH ROW
+EXECUTE FUNCTION apply_inventory_movement_cost();
@@ -0,0 +1,24 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE products ADD COLUMN sku varchar(64);
+ALTER TABLE products ADD COLUMN barcode varchar(48);
+
+CREATE UNIQUE INDEX idx_products_sku ON products (sku) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX idx_products_barcode ON products (barcode) WHERE deleted_at IS NULL;
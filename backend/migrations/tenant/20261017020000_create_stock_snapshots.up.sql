@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table stock_snapshots
+(
+    id            uuid primary key     default uuid_generate_v4(),
+    taken_at      timestamptz not null,
+    created_by_id uuid, -- null for scheduled snapshots
+    created_at    timestamptz not null default now(),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_stock_snapshots_taken_at ON stock_snapshots (taken_at);
+
+create table stock_snapshot_lines
+(
+    snapshot_id  uuid           not null,
+    inventory_id uuid           not null,
+    product_id   uuid           not null,
+    warehouse_id uuid           not null,
+    quantity     numeric(15, 2) not null,
+    total_cost   numeric(15, 2) not null,
+    primary key (snapshot_id, inventory_id),
+    foreign key (snapshot_id) references stock_snapshots (id) on delete cascade,
+    foreign key (inventory_id) references inventory (id),
+    foreign key (product_id) references products (id),
+    foreign key (warehouse_id) references warehouses (id)
+);
+
+CREATE INDEX idx_stock_snapshot_lines_inventory_id ON stock_snapshot_lines (inventory_id);
+
+-- A movement booked before an existing snapshot makes that snapshot stale
+CREATE OR REPLACE FUNCTION invalidate_stock_snapshots()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    DELETE FROM stock_snapshots WHERE taken_at > NEW.movement_date;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER invalidate_stock_snapshots_on_inventory_movement
+    AFTER INSERT
+    ON inventory_movements
+    FOR EACH ROW
+EXECUTE FUNCTION invalidate_stock_snapshots();
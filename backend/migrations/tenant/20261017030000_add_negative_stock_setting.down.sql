@@ -0,0 +1,59 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+CREATE OR REPLACE FUNCTION update_inventory_quantities()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    calculated_quantity numeric(15, 2);
+    target_inventory_id uuid;
+BEGIN
+    -- Determine which inventory_id to use based on operation
+    target_inventory_id := COALESCE(NEW.inventory_id, OLD.inventory_id);
+
+    -- Calculate the new quantity
+    SELECT COALESCE(SUM(quantity), 0)
+    INTO calculated_quantity
+    FROM inventory_movements
+    WHERE inventory_id = target_inventory_id;
+
+    -- Validate that the calculated quantity is not negative
+    IF calculated_quantity < 0 THEN
+        RAISE EXCEPTION 'Inventory quantity cannot be negative. Calculated quantity: %', calculated_quantity;
+    END IF;
+
+    -- Update the inventory quantities based on movements
+    UPDATE inventory
+    SET quantity_on_hand = calculated_quantity,
+        updated_at       = now()
+    WHERE id = target_inventory_id;
+
+    -- Log a warning if quantity goes below minimum stock
+    IF calculated_quantity <= (SELECT minimum_stock FROM inventory WHERE id = target_inventory_id) THEN
+        RAISE NOTICE 'Inventory % is at or below minimum stock level', target_inventory_id;
+    END IF;
+
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE inventory ADD CONSTRAINT check_quantity_available CHECK (quantity_available >= 0);
+ALTER TABLE inventory ADD CONSTRAINT inventory_quantity_on_hand_check CHECK (quantity_on_hand >= 0);
+ALTER TABLE warehouses DROP COLUMN IF EXISTS allow_negative_stock;
+DROP TABLE IF EXISTS inventory_settings;
@@ -0,0 +1,85 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table inventory_settings
+(
+    id                   boolean primary key  default true check (id),
+    allow_negative_stock boolean     not null default false,
+    updated_by_id        uuid,
+    updated_at           timestamptz not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO inventory_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_inventory_settings_table
+    BEFORE UPDATE
+    ON inventory_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- null follows the tenant setting
+ALTER TABLE warehouses ADD COLUMN allow_negative_stock boolean;
+
+-- The movement trigger enforces the policy from now on
+ALTER TABLE inventory DROP CONSTRAINT inventory_quantity_on_hand_check;
+ALTER TABLE inventory DROP CONSTRAINT check_quantity_available;
+
+CREATE OR REPLACE FUNCTION update_inventory_quantities()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    calculated_quantity  numeric(15, 2);
+    target_inventory_id  uuid;
+    allow_negative_stock boolean;
+    reserved_quantity    numeric(15, 2);
+BEGIN
+    target_inventory_id := COALESCE(NEW.inventory_id, OLD.inventory_id);
+
+    SELECT COALESCE(warehouses.allow_negative_stock, inventory_settings.allow_negative_stock),
+           inventory.quantity_reserved
+    INTO allow_negative_stock, reserved_quantity
+    FROM inventory
+             JOIN warehouses ON inventory.warehouse_id = warehouses.id
+             CROSS JOIN inventory_settings
+    WHERE inventory.id = target_inventory_id
+        FOR UPDATE OF inventory;
+
+    SELECT COALESCE(SUM(quantity), 0)
+    INTO calculated_quantity
+    FROM inventory_movements
+    WHERE inventory_id = target_inventory_id;
+
+    IF NOT allow_negative_stock AND calculated_quantity < reserved_quantity THEN
+        RAISE EXCEPTION 'Inventory quantity cannot go below zero. Calculated quantity: %', calculated_quantity
+            USING ERRCODE = 'check_violation', CONSTRAINT = 'inventory_negative_stock';
+    END IF;
+
+    UPDATE inventory
+    SET quantity_on_hand = calculated_quantity,
+        updated_at       = now()
+    WHERE id = target_inventory_id;
+
+    IF calculated_quantity <= (SELECT minimum_stock FROM inventory WHERE id = target_inventory_id) THEN
+        RAISE NOTICE 'Inventory % is at or below minimum stock level', target_inventory_id;
+    END IF;
+
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,31 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TRIGGER update_inventory_on_movement ON inventory_movements;
+CREATE TRIGGER update_inventory_on_movement
+    AFTER INSERT OR UPDATE OR DELETE
+    ON inventory_movements
+    FOR EACH ROW
+EXECUTE FUNCTION update_inventory_quantities();
+
+DROP INDEX IF EXISTS idx_inventory_movements_reverses_id;
+
+ALTER TABLE inventory_movements
+    DROP COLUMN IF EXISTS reversed_by_id,
+    DROP COLUMN IF EXISTS reverses_id;
@@ -0,0 +1,34 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE inventory_movements
+    ADD COLUMN reverses_id    uuid REFERENCES inventory_movements (id),
+    ADD COLUMN reversed_by_id uuid REFERENCES inventory_movements (id);
+
+-- A movement can be reversed at most once
+CREATE UNIQUE INDEX idx_inventory_movements_reverses_id ON inventory_movements (reverses_id)
+    WHERE reverses_id IS NOT NULL;
+
+-- Linking a reversal must not re-run the stock check on the original movement
+DROP TRIGGER update_inventory_on_movement ON inventory_movements;
+CREATE TRIGGER update_inventory_on_movement
+    AFTER INSERT OR DELETE OR UPDATE OF inventory_id, quantity
+    ON inventory_movements
+    FOR EACH ROW
+EXECUTE FUNCTION update_inventory_quantities();
@@ -0,0 +1,54 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Capacity of a whole warehouse (zone is null) or of one of its zones, in cubic meters
+create table warehouse_capacities
+(
+    id                      uuid primary key        default uuid_generate_v4(),
+    warehouse_id            uuid           not null,
+    zone                    varchar(50),
+    capacity_m3             numeric(12, 3) not null check (capacity_m3 > 0),
+    alert_threshold_percent numeric(5, 2)  not null default 90 check (alert_threshold_percent > 0 AND alert_threshold_percent <= 100),
+    above_threshold         boolean        not null default false,
+    created_at              timestamptz    not null default now(),
+    updated_at              timestamptz    not null default now(),
+    foreign key (warehouse_id) references warehouses (id)
+);
+
+CREATE UNIQUE INDEX idx_warehouse_capacities_warehouse_zone ON warehouse_capacities (warehouse_id, COALESCE(zone, ''));
+
+-- Products without dimensions cannot be measured, they are counted separately
+CREATE VIEW warehouse_capacity_utilization AS
+SELECT warehouse_capacities.id,
+       COALESCE(SUM(GREATEST(inventory.quantity_on_hand, 0) * products.length_mm * products.width_mm
+                    * products.height_mm / 1000000000.0), 0)::numeric(15, 3) AS used_m3,
+       COUNT(inventory.id) FILTER (
+           WHERE inventory.quantity_on_hand > 0
+               AND (products.length_mm IS NULL OR products.width_mm IS NULL OR products.height_mm IS NULL)
+           )                                                             AS unmeasured_items
+FROM warehouse_capacities
+         LEFT JOIN inventory ON inventory.warehouse_id = warehouse_capacities.warehouse_id
+    AND inventory.deleted_at IS NULL
+    AND (warehouse_capacities.zone IS NULL OR inventory.zone = warehouse_capacities.zone)
+         LEFT JOIN products ON inventory.product_id = products.id
+GROUP BY warehouse_capacities.id;
+
+ALTER TABLE watch_notifications DROP CONSTRAINT watch_notifications_event_check;
+ALTER TABLE watch_notifications ADD CONSTRAINT watch_notifications_event_check
+    CHECK (event IN ('updated', 'deleted', 'commented', 'assigned', 'low_stock', 'capacity'));
@@ -0,0 +1,40 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE product_category
+    ADD COLUMN slug varchar(255),
+    ADD COLUMN sort_order integer not null default 0,
+    ADD COLUMN updated_at timestamptz not null default now(),
+    ADD COLUMN deleted_at timestamptz;
+
+UPDATE product_category
+SET slug = trim(both '-' from lower(regexp_replace(name, '[^[:alnum:]]+', '-', 'g'))) || '-' || left(id::text, 8);
+
+ALTER TABLE product_category
+    ALTER COLUMN slug SET NOT NULL,
+    ADD CONSTRAINT product_category_parent_check CHECK (parent_id IS NULL OR parent_id <> id);
+
+CREATE UNIQUE INDEX idx_product_category_slug ON product_category (slug) WHERE deleted_at IS NULL;
+CREATE INDEX idx_product_category_deleted_at ON product_category (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_product_category_table
+    BEFORE UPDATE
+    ON product_category
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,30 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP INDEX IF EXISTS idx_products_variant_attributes;
+DROP INDEX IF EXISTS idx_products_parent_id;
+
+ALTER TABLE products
+    DROP CONSTRAINT IF EXISTS products_price_check,
+    DROP CONSTRAINT IF EXISTS products_variant_attributes_check,
+    DROP CONSTRAINT IF EXISTS products_parent_check,
+    DROP COLUMN IF EXISTS currency_code,
+    DROP COLUMN IF EXISTS price,
+    DROP COLUMN IF EXISTS variant_attributes,
+    DROP COLUMN IF EXISTS parent_id;
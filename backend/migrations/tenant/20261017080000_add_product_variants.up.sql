@@ -0,0 +1,32 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE products
+    ADD COLUMN parent_id uuid REFERENCES products (id),
+    ADD COLUMN variant_attributes jsonb,
+    ADD COLUMN price numeric(15, 2),
+    ADD COLUMN currency_code varchar(3) REFERENCES currencies (code),
+    ADD CONSTRAINT products_parent_check CHECK (parent_id IS NULL OR parent_id <> id),
+    ADD CONSTRAINT products_variant_attributes_check
+        CHECK ((parent_id IS NULL) = (variant_attributes IS NULL)),
+    ADD CONSTRAINT products_price_check CHECK (price IS NULL OR price >= 0);
+
+CREATE INDEX idx_products_parent_id ON products (parent_id);
+CREATE UNIQUE INDEX idx_products_variant_attributes ON products (parent_id, variant_attributes)
+    WHERE parent_id IS NOT NULL AND deleted_at IS NULL;
@@ -0,0 +1,40 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table product_attachments
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    product_id    uuid         not null,
+    kind          varchar(20)  not null check (kind IN ('image', 'document')),
+    file_name     varchar(255) not null,
+    content_type  varchar(100) not null,
+    size_bytes    bigint       not null check (size_bytes >= 0),
+    storage_key   varchar(512) not null,
+    thumbnail_key varchar(512),
+    sort_order    integer      not null default 0,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (product_id) references products (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_product_attachments_product_id ON product_attachments (product_id);
+CREATE INDEX idx_product_attachments_created_by_id ON product_attachments (created_by_id);
+CREATE INDEX idx_product_attachments_deleted_at ON product_attachments (deleted_at);
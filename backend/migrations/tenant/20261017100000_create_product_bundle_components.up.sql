@@ -0,0 +1,36 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table product_bundle_components
+(
+    id            uuid primary key        default uuid_generate_v4(),
+    bundle_id     uuid           not null,
+    component_id  uuid           not null,
+    quantity      numeric(15, 2) not null check (quantity > 0),
+    created_by_id uuid           not null,
+    created_at    timestamptz    not null default now(),
+    foreign key (bundle_id) references products (id),
+    foreign key (component_id) references products (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_bundle_component_not_self check (bundle_id <> component_id),
+    unique (bundle_id, component_id)
+);
+
+CREATE INDEX idx_product_bundle_components_component_id ON product_bundle_components (component_id);
+CREATE INDEX idx_product_bundle_components_created_by_id ON product_bundle_components (created_by_id);
@@ -0,0 +1,79 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table service_rates
+(
+    id            uuid primary key        default uuid_generate_v4(),
+    service_id    uuid           not null,
+    customer_id   uuid           not null,
+    rate          numeric(15, 2) not null check (rate >= 0),
+    unit          varchar(16)    not null check (unit in ('hour', 'unit')),
+    currency_code varchar(3)     not null,
+    valid_from    date           not null,
+    valid_until   date,
+    created_by_id uuid           not null,
+    created_at    timestamptz    not null default now(),
+    deleted_at    timestamptz,
+    foreign key (service_id) references services (id),
+    foreign key (customer_id) references customers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_service_rate_validity check (valid_until is null or valid_until >= valid_from)
+);
+
+CREATE INDEX idx_service_rates_service_id_customer_id ON service_rates (service_id, customer_id);
+CREATE INDEX idx_service_rates_customer_id ON service_rates (customer_id);
+CREATE INDEX idx_service_rates_created_by_id ON service_rates (created_by_id);
+CREATE INDEX idx_service_rates_deleted_at ON service_rates (deleted_at);
+
+-- Customer rate valid on the given day, otherwise the global service default
+CREATE OR REPLACE FUNCTION resolve_service_rate(p_service_id uuid, p_customer_id uuid, p_on date)
+    RETURNS TABLE
+            (
+                service_rate_id uuid,
+                price           numeric(15, 2),
+                currency_code   varchar(3)
+            )
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT resolved.service_rate_id, resolved.price, resolved.currency_code
+FROM (SELECT service_rates.id AS service_rate_id,
+             service_rates.rate AS price,
+             service_rates.currency_code AS currency_code,
+             0 AS priority,
+             service_rates.valid_from
+      FROM service_rates
+      WHERE service_rates.service_id = p_service_id
+        AND service_rates.customer_id = p_customer_id
+        AND service_rates.deleted_at IS NULL
+        AND service_rates.valid_from <= p_on
+        AND (service_rates.valid_until IS NULL OR service_rates.valid_until >= p_on)
+      UNION ALL
+      SELECT NULL,
+             services.default_price,
+             services.currency_code,
+             1,
+             NULL
+      FROM services
+      WHERE services.id = p_service_id) AS resolved
+ORDER BY resolved.priority, resolved.valid_from DESC
+LIMIT 1
+$$;
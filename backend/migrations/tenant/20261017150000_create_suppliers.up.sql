@@ -0,0 +1,94 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table suppliers
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    name          varchar(255) not null,
+    contact_name  varchar(255),
+    email         varchar(255),
+    phone_number  varchar(50),
+    tax_number    varchar(50),
+    status        varchar(50)  not null default 'active' check (status IN ('active', 'inactive')),
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_suppliers_name ON suppliers (name);
+CREATE INDEX idx_suppliers_status ON suppliers (status);
+CREATE INDEX idx_suppliers_deleted_at ON suppliers (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_suppliers_table
+    BEFORE UPDATE
+    ON suppliers
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table product_suppliers
+(
+    id             uuid primary key        default uuid_generate_v4(),
+    product_id     uuid           not null,
+    supplier_id    uuid           not null,
+    supplier_sku   varchar(100),
+    purchase_price numeric(15, 2) check (purchase_price IS NULL OR purchase_price >= 0),
+    currency_code  varchar(3),
+    lead_time_days integer check (lead_time_days IS NULL OR lead_time_days >= 0),
+    is_preferred   boolean        not null default false,
+    created_by_id  uuid           not null,
+    created_at     timestamptz    not null default now(),
+    updated_at     timestamptz    not null default now(),
+    foreign key (product_id) references products (id),
+    foreign key (supplier_id) references suppliers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_product_supplier_price_currency check (purchase_price IS NULL OR currency_code IS NOT NULL),
+    unique (product_id, supplier_id)
+);
+
+CREATE INDEX idx_product_suppliers_supplier_id ON product_suppliers (supplier_id);
+CREATE UNIQUE INDEX idx_product_suppliers_preferred ON product_suppliers (product_id) WHERE is_preferred;
+
+CREATE TRIGGER update_updated_at_on_product_suppliers_table
+    BEFORE UPDATE
+    ON product_suppliers
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- The explicitly preferred supplier wins, otherwise the fastest then the cheapest active one
+CREATE VIEW product_preferred_suppliers AS
+SELECT DISTINCT ON (product_suppliers.product_id) product_suppliers.product_id,
+                                                  product_suppliers.id AS product_supplier_id,
+                                                  product_suppliers.supplier_id,
+                                                  suppliers.name       AS supplier,
+                                                  product_suppliers.supplier_sku,
+                                                  product_suppliers.purchase_price,
+                                                  product_suppliers.currency_code,
+                                                  product_suppliers.lead_time_days
+FROM product_suppliers
+         JOIN suppliers ON product_suppliers.supplier_id = suppliers.id
+WHERE suppliers.deleted_at IS NULL
+  AND suppliers.status = 'active'
+ORDER BY product_suppliers.product_id,
+         product_suppliers.is_preferred DESC,
+         product_suppliers.lead_time_days NULLS LAST,
+         product_suppliers.purchase_price NULLS LAST,
+         suppliers.name;
@@ -0,0 +1,48 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table product_custom_fields
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    key           varchar(64)  not null check (key ~ '^[a-z][a-z0-9_]*$'),
+    label         varchar(255) not null,
+    field_type    varchar(16)  not null check (field_type IN ('text', 'number', 'date', 'select')),
+    options       text[]       not null default '{}',
+    is_required   boolean      not null default false,
+    position      integer      not null default 0,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (created_by_id) references users (id),
+    unique nulls not distinct (key, deleted_at)
+);
+
+CREATE INDEX idx_product_custom_fields_deleted_at ON product_custom_fields (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_product_custom_fields_table
+    BEFORE UPDATE
+    ON product_custom_fields
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+ALTER TABLE products
+    ADD COLUMN custom_fields jsonb not null default '{}';
+
+CREATE INDEX idx_products_custom_fields ON products USING gin (custom_fields);
@@ -0,0 +1,76 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+CREATE SEQUENCE quote_number_seq;
+
+create table quotes
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    quote_number  varchar(50)  not null,
+    customer_id   uuid         not null,
+    title         varchar(255) not null,
+    notes         text,
+    currency_code varchar(3)   not null,
+    valid_until   date         not null,
+    status        varchar(50)  not null default 'draft' check (status IN ('draft', 'sent', 'accepted', 'rejected', 'converted')),
+    responded_at  timestamptz,
+    worksheet_id  uuid,
+    receivable_id uuid,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (customer_id) references customers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (created_by_id) references users (id),
+    unique (quote_number)
+);
+
+CREATE INDEX idx_quotes_customer_id ON quotes (customer_id);
+CREATE INDEX idx_quotes_status ON quotes (status);
+CREATE INDEX idx_quotes_valid_until ON quotes (valid_until);
+CREATE INDEX idx_quotes_deleted_at ON quotes (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_quotes_table
+    BEFORE UPDATE
+    ON quotes
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table quote_lines
+(
+    id          uuid primary key        default uuid_generate_v4(),
+    quote_id    uuid           not null,
+    service_id  uuid,
+    product_id  uuid,
+    description text           not null,
+    quantity    numeric(15, 2) not null check (quantity > 0),
+    unit_price  numeric(15, 2) not null check (unit_price >= 0),
+    tax_id      uuid           not null,
+    position    integer        not null,
+    foreign key (quote_id) references quotes (id) on delete cascade,
+    foreign key (service_id) references services (id),
+    foreign key (product_id) references products (id),
+    foreign key (tax_id) references taxes (id),
+    constraint check_quote_line_item check (num_nonnulls(service_id, product_id) = 1)
+);
+
+CREATE INDEX idx_quote_lines_quote_id ON quote_lines (quote_id);
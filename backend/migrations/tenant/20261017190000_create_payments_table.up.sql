@@ -0,0 +1,53 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table payments
+(
+    id               uuid primary key       default uuid_generate_v4(),
+    customer_id      uuid           not null,
+    receivable_id    uuid,
+    payment_method   varchar(50)    not null check (payment_method IN ('bank_transfer', 'cash', 'card')),
+    amount           numeric(15, 2) not null check (amount > 0),
+    allocated_amount numeric(15, 2) not null default 0 check (allocated_amount >= 0),
+    currency_code    varchar(3)     not null,
+    paid_on          date           not null,
+    reference        varchar(255),
+    note             text,
+    created_by_id    uuid           not null,
+    created_at       timestamptz    not null default now(),
+    updated_at       timestamptz    not null default now(),
+    deleted_at       timestamptz,
+    foreign key (customer_id) references customers (id),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_payment_allocated_amount check (allocated_amount <= amount),
+    constraint check_payment_allocation check (receivable_id IS NOT NULL OR allocated_amount = 0)
+);
+
+CREATE INDEX idx_payments_customer_id ON payments (customer_id);
+CREATE INDEX idx_payments_receivable_id ON payments (receivable_id);
+CREATE INDEX idx_payments_paid_on ON payments (paid_on);
+CREATE INDEX idx_payments_deleted_at ON payments (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_payments_table
+    BEFORE UPDATE
+    ON payments
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
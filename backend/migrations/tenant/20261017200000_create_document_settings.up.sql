@@ -0,0 +1,43 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table document_settings
+(
+    id                boolean primary key  default true check (id),
+    company_name      varchar(255),
+    tax_number        varchar(50),
+    address           varchar(500),
+    bank_account      varchar(100),
+    email             varchar(255),
+    phone_number      varchar(50),
+    footer_note       text,
+    logo_storage_key  varchar(500),
+    logo_content_type varchar(100),
+    updated_by_id     uuid,
+    updated_at        timestamptz not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO document_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_document_settings_table
+    BEFORE UPDATE
+    ON document_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,75 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table nav_settings
+(
+    id             boolean primary key default true check (id),
+    environment    varchar(20)  not null default 'test' check (environment IN ('test', 'production')),
+    login          varchar(15)  not null,
+    password_hash  varchar(128) not null,
+    signature_key  text         not null,
+    exchange_key   text         not null,
+    tax_number     varchar(13)  not null,
+    company_name   varchar(255) not null,
+    country_code   varchar(2)   not null default 'HU',
+    postal_code    varchar(10)  not null,
+    city           varchar(255) not null,
+    street_address varchar(255) not null,
+    updated_by_id  uuid         not null,
+    updated_at     timestamptz  not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+CREATE TRIGGER update_updated_at_on_nav_settings_table
+    BEFORE UPDATE
+    ON nav_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table nav_invoice_submissions
+(
+    id              uuid primary key     default uuid_generate_v4(),
+    receivable_id   uuid        not null,
+    operation       varchar(20) not null default 'CREATE' check (operation IN ('CREATE', 'MODIFY', 'STORNO')),
+    invoice_xml     text        not null,
+    status          varchar(20) not null default 'queued' check (status IN ('queued', 'submitted', 'done', 'aborted', 'failed')),
+    transaction_id  varchar(30),
+    nav_status      varchar(20),
+    messages        jsonb       not null default '[]',
+    attempts        integer     not null default 0,
+    next_attempt_at timestamptz not null default now(),
+    last_error      text,
+    submitted_at    timestamptz,
+    completed_at    timestamptz,
+    created_by_id   uuid        not null,
+    created_at      timestamptz not null default now(),
+    updated_at      timestamptz not null default now(),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE UNIQUE INDEX idx_nav_invoice_submissions_active ON nav_invoice_submissions (receivable_id, operation)
+    WHERE status IN ('queued', 'submitted', 'done');
+CREATE INDEX idx_nav_invoice_submissions_status ON nav_invoice_submissions (status, next_attempt_at);
+
+CREATE TRIGGER update_updated_at_on_nav_invoice_submissions_table
+    BEFORE UPDATE
+    ON nav_invoice_submissions
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,146 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+CREATE SEQUENCE invoice_number_seq;
+
+create table receivable_lines
+(
+    id            uuid primary key        default uuid_generate_v4(),
+    receivable_id uuid           not null,
+    service_id    uuid,
+    product_id    uuid,
+    description   text           not null,
+    quantity      numeric(15, 2) not null,
+    unit_price    numeric(15, 2) not null,
+    tax_id        uuid           not null,
+    tax_rate      numeric(5, 2)  not null,
+    net_amount    numeric(15, 2) not null,
+    tax_amount    numeric(15, 2) not null,
+    position      integer        not null,
+    foreign key (receivable_id) references receivables (id) on delete cascade,
+    foreign key (service_id) references services (id),
+    foreign key (product_id) references products (id),
+    foreign key (tax_id) references taxes (id),
+    constraint check_receivable_line_item check (num_nonnulls(service_id, product_id) = 1)
+);
+
+CREATE INDEX idx_receivable_lines_receivable_id ON receivable_lines (receivable_id);
+
+INSERT INTO receivable_lines (receivable_id, service_id, product_id, description, quantity,
+                              unit_price, tax_id, tax_rate, net_amount, tax_amount, position)
+SELECT quotes.receivable_id,
+       quote_lines.service_id,
+       quote_lines.product_id,
+       quote_lines.description,
+       quote_lines.quantity,
+       quote_lines.unit_price,
+       quote_lines.tax_id,
+       COALESCE(taxes.rate, 0),
+       round(quote_lines.quantity * quote_lines.unit_price, 2),
+       CASE
+           WHEN taxes.is_rate_applicable
+               THEN round(quote_lines.quantity * quote_lines.unit_price
+                              * COALESCE(taxes.rate, 0) / 100, 2)
+           ELSE 0
+       END,
+       quote_lines.position
+FROM quotes
+JOIN quote_lines ON quote_lines.quote_id = quotes.id
+JOIN taxes ON quote_lines.tax_id = taxes.id
+WHERE quotes.receivable_id IS NOT NULL;
+
+create table recurring_invoices
+(
+    id                uuid primary key      default uuid_generate_v4(),
+    customer_id       uuid         not null,
+    title             varchar(255) not null,
+    currency_code     varchar(3)   not null,
+    recurrence        varchar(50)  not null check (recurrence IN ('monthly', 'quarterly', 'cron')),
+    cron_expression   varchar(255),
+    start_date        date         not null,
+    end_date          date,
+    next_issue_date   date,
+    payment_term_days integer      not null default 8 check (payment_term_days between 0 and 365),
+    auto_issue        boolean      not null default false,
+    send_email        boolean      not null default true,
+    status            varchar(50)  not null default 'active' check (status IN ('active', 'paused', 'ended')),
+    created_by_id     uuid         not null,
+    created_at        timestamptz  not null default now(),
+    updated_at        timestamptz  not null default now(),
+    deleted_at        timestamptz,
+    foreign key (customer_id) references customers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_recurring_invoice_cron check ((recurrence = 'cron') = (cron_expression IS NOT NULL)),
+    constraint check_recurring_invoice_next check ((status = 'ended') = (next_issue_date IS NULL))
+);
+
+CREATE INDEX idx_recurring_invoices_customer_id ON recurring_invoices (customer_id);
+CREATE INDEX idx_recurring_invoices_next_issue_date ON recurring_invoices (next_issue_date);
+CREATE INDEX idx_recurring_invoices_deleted_at ON recurring_invoices (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_recurring_invoices_table
+    BEFORE UPDATE
+    ON recurring_invoices
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table recurring_invoice_lines
+(
+    id                   uuid primary key        default uuid_generate_v4(),
+    recurring_invoice_id uuid           not null,
+    service_id           uuid,
+    product_id           uuid,
+    description          text           not null,
+    quantity             numeric(15, 2) not null check (quantity > 0),
+    unit_price           numeric(15, 2) not null check (unit_price >= 0),
+    tax_id               uuid           not null,
+    position             integer        not null,
+    foreign key (recurring_invoice_id) references recurring_invoices (id) on delete cascade,
+    foreign key (service_id) references services (id),
+    foreign key (product_id) references products (id),
+    foreign key (tax_id) references taxes (id),
+    constraint check_recurring_invoice_line_item check (num_nonnulls(service_id, product_id) = 1)
+);
+
+CREATE INDEX idx_recurring_invoice_lines_recurring_invoice_id ON recurring_invoice_lines (recurring_invoice_id);
+
+create table recurring_invoice_runs
+(
+    id                   uuid primary key     default uuid_generate_v4(),
+    recurring_invoice_id uuid        not null,
+    issue_date           date        not null,
+    status               varchar(50) not null check (status IN ('draft', 'issued', 'skipped')),
+    receivable_id        uuid,
+    emailed_at           timestamptz,
+    created_at           timestamptz not null default now(),
+    updated_at           timestamptz not null default now(),
+    foreign key (recurring_invoice_id) references recurring_invoices (id) on delete cascade,
+    foreign key (receivable_id) references receivables (id),
+    unique (recurring_invoice_id, issue_date),
+    constraint check_recurring_invoice_run_receivable check ((status = 'issued') = (receivable_id IS NOT NULL))
+);
+
+CREATE INDEX idx_recurring_invoice_runs_status ON recurring_invoice_runs (status);
+
+CREATE TRIGGER update_updated_at_on_recurring_invoice_runs_table
+    BEFORE UPDATE
+    ON recurring_invoice_runs
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,36 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+DROP INDEX IF EXISTS idx_nav_invoice_submissions_active_credit_note;
+DROP INDEX IF EXISTS idx_nav_invoice_submissions_active;
+ALTER TABLE nav_invoice_submissions
+    DROP COLUMN IF EXISTS credit_note_id;
+CREATE UNIQUE INDEX idx_nav_invoice_submissions_active ON nav_invoice_submissions (receivable_id, operation)
+    WHERE status IN ('queued', 'submitted', 'done');
+
+DROP TABLE IF EXISTS credit_note_lines;
+DROP TABLE IF EXISTS credit_notes;
+
+ALTER TABLE receivables
+    DROP CONSTRAINT check_receivable_paid_amount,
+    DROP COLUMN IF EXISTS credited_amount,
+    ADD CONSTRAINT check_receivable_paid_amount check (paid_amount <= amount);
+
+DROP SEQUENCE IF EXISTS credit_note_number_seq;
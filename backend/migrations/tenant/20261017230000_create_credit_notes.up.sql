@@ -0,0 +1,91 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+CREATE SEQUENCE credit_note_number_seq;
+
+ALTER TABLE receivables
+    ADD COLUMN credited_amount numeric(15, 2) not null default 0 check (credited_amount >= 0),
+    DROP CONSTRAINT check_receivable_paid_amount,
+    ADD CONSTRAINT check_receivable_paid_amount check (paid_amount + credited_amount <= amount);
+
+create table credit_notes
+(
+    id              uuid primary key       default uuid_generate_v4(),
+    receivable_id   uuid           not null,
+    customer_id     uuid           not null,
+    document_number varchar(100)   not null unique,
+    issue_date      date           not null,
+    currency_code   varchar(3)     not null,
+    reason          text           not null,
+    net_amount      numeric(15, 2) not null check (net_amount <= 0),
+    tax_amount      numeric(15, 2) not null check (tax_amount <= 0),
+    amount          numeric(15, 2) not null check (amount < 0),
+    applied_amount  numeric(15, 2) not null default 0 check (applied_amount >= 0),
+    created_by_id   uuid           not null,
+    created_at      timestamptz    not null default now(),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (customer_id) references customers (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_credit_note_applied_amount check (applied_amount <= -amount)
+);
+
+CREATE INDEX idx_credit_notes_receivable_id ON credit_notes (receivable_id);
+CREATE INDEX idx_credit_notes_customer_id ON credit_notes (customer_id);
+
+create table credit_note_lines
+(
+    id                    uuid primary key        default uuid_generate_v4(),
+    credit_note_id        uuid           not null,
+    receivable_line_id    uuid           not null,
+    service_id            uuid,
+    product_id            uuid,
+    description           text           not null,
+    quantity              numeric(15, 2) not null check (quantity < 0),
+    unit_price            numeric(15, 2) not null,
+    tax_id                uuid           not null,
+    tax_rate              numeric(5, 2)  not null,
+    net_amount            numeric(15, 2) not null,
+    tax_amount            numeric(15, 2) not null,
+    position              integer        not null,
+    inventory_id          uuid,
+    inventory_movement_id uuid,
+    foreign key (credit_note_id) references credit_notes (id) on delete cascade,
+    foreign key (receivable_line_id) references receivable_lines (id),
+    foreign key (service_id) references services (id),
+    foreign key (product_id) references products (id),
+    foreign key (tax_id) references taxes (id),
+    foreign key (inventory_id) references inventory (id),
+    foreign key (inventory_movement_id) references inventory_movements (id),
+    constraint check_credit_note_line_item check (num_nonnulls(service_id, product_id) = 1),
+    constraint check_credit_note_line_return check (inventory_id IS NULL OR product_id IS NOT NULL)
+);
+
+CREATE INDEX idx_credit_note_lines_credit_note_id ON credit_note_lines (credit_note_id);
+CREATE INDEX idx_credit_note_lines_receivable_line_id ON credit_note_lines (receivable_line_id);
+
+ALTER TABLE nav_invoice_submissions
+    ADD COLUMN credit_note_id uuid REFERENCES credit_notes (id);
+
+DROP INDEX idx_nav_invoice_submissions_active;
+CREATE UNIQUE INDEX idx_nav_invoice_submissions_active ON nav_invoice_submissions (receivable_id, operation)
+    WHERE credit_note_id IS NULL AND status IN ('queued', 'submitted', 'done');
+CREATE UNIQUE INDEX idx_nav_invoice_submissions_active_credit_note ON nav_invoice_submissions (credit_note_id)
+    WHERE credit_note_id IS NOT NULL AND status IN ('queued', 'submitted', 'done');
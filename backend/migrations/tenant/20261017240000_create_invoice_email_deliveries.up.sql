@@ -0,0 +1,50 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+ALTER TABLE document_settings
+    ADD COLUMN invoice_email_cc  varchar(1000),
+    ADD COLUMN invoice_email_bcc varchar(1000);
+
+create table invoice_email_deliveries
+(
+    id            uuid primary key       default uuid_generate_v4(),
+    receivable_id uuid          not null,
+    recipient     varchar(255)  not null,
+    cc            varchar(1000),
+    bcc           varchar(1000),
+    language      varchar(2)    not null default 'hu' check (language IN ('hu', 'en')),
+    subject       varchar(500)  not null,
+    status        varchar(20)   not null default 'queued' check (status IN ('queued', 'sent', 'failed')),
+    error         text,
+    sent_at       timestamptz,
+    created_by_id uuid          not null,
+    created_at    timestamptz   not null default now(),
+    updated_at    timestamptz   not null default now(),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_invoice_email_deliveries_receivable_id ON invoice_email_deliveries (receivable_id, created_at);
+
+CREATE TRIGGER update_updated_at_on_invoice_email_deliveries_table
+    BEFORE UPDATE
+    ON invoice_email_deliveries
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
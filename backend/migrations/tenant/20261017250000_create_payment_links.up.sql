@@ -0,0 +1,70 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table payment_provider_settings
+(
+    id             boolean primary key default true check (id),
+    provider       varchar(20)  not null check (provider IN ('stripe', 'barion')),
+    environment    varchar(20)  not null default 'test' check (environment IN ('test', 'production')),
+    secret_key     text         not null,
+    webhook_secret text,
+    payee_email    varchar(255),
+    enabled        boolean      not null default true,
+    updated_by_id  uuid         not null,
+    updated_at     timestamptz  not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+CREATE TRIGGER update_updated_at_on_payment_provider_settings_table
+    BEFORE UPDATE
+    ON payment_provider_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table payment_links
+(
+    id            uuid primary key        default uuid_generate_v4(),
+    receivable_id uuid           not null,
+    provider      varchar(20)    not null check (provider IN ('stripe', 'barion')),
+    external_id   varchar(255)   not null,
+    checkout_url  text           not null,
+    amount        numeric(15, 2) not null check (amount > 0),
+    currency_code varchar(3)     not null,
+    status        varchar(20)    not null default 'pending' check (status IN ('pending', 'paid', 'expired', 'failed')),
+    expires_at    timestamptz    not null,
+    paid_at       timestamptz,
+    payment_id    uuid,
+    created_by_id uuid           not null,
+    created_at    timestamptz    not null default now(),
+    updated_at    timestamptz    not null default now(),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (payment_id) references payments (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE UNIQUE INDEX idx_payment_links_provider_external_id ON payment_links (provider, external_id);
+CREATE INDEX idx_payment_links_receivable_id ON payment_links (receivable_id, created_at);
+
+CREATE TRIGGER update_updated_at_on_payment_links_table
+    BEFORE UPDATE
+    ON payment_links
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
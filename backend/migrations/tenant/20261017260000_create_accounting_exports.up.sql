@@ -0,0 +1,66 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table accounting_connections
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    adapter       varchar(20)  not null check (adapter IN ('szamlazz_hu', 'billingo', 'csv')),
+    name          varchar(255) not null,
+    api_key       text,
+    field_mapping jsonb        not null default '{}',
+    enabled       boolean      not null default true,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (created_by_id) references users (id),
+    constraint check_accounting_connection_api_key check (adapter = 'csv' OR api_key IS NOT NULL)
+);
+
+CREATE TRIGGER update_updated_at_on_accounting_connections_table
+    BEFORE UPDATE
+    ON accounting_connections
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table accounting_exports
+(
+    id              uuid primary key      default uuid_generate_v4(),
+    connection_id   uuid         not null,
+    adapter         varchar(20)  not null,
+    receivable_id   uuid,
+    period_from     date,
+    period_to       date,
+    invoice_count   integer      not null default 0,
+    status          varchar(20)  not null check (status IN ('exported', 'failed')),
+    external_id     varchar(255),
+    external_number varchar(100),
+    error           text,
+    created_by_id   uuid         not null,
+    created_at      timestamptz  not null default now(),
+    foreign key (connection_id) references accounting_connections (id),
+    foreign key (receivable_id) references receivables (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_accounting_exports_connection_id ON accounting_exports (connection_id, created_at);
+CREATE INDEX idx_accounting_exports_receivable_id ON accounting_exports (receivable_id);
+CREATE UNIQUE INDEX idx_accounting_exports_pushed_once ON accounting_exports (connection_id, receivable_id)
+    WHERE status = 'exported' AND receivable_id IS NOT NULL;
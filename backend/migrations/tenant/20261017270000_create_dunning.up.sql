@@ -0,0 +1,63 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+ALTER TABLE customers
+    ADD COLUMN dunning_opt_out boolean not null default false;
+
+create table dunning_levels
+(
+    id             uuid primary key      default uuid_generate_v4(),
+    days_overdue   integer      not null check (days_overdue > 0),
+    template       varchar(50)  not null check (template IN ('reminder', 'second_reminder', 'final_notice')),
+    custom_subject text,
+    custom_body    text,
+    enabled        boolean      not null default true,
+    created_by_id  uuid         not null,
+    created_at     timestamptz  not null default now(),
+    updated_at     timestamptz  not null default now(),
+    deleted_at     timestamptz,
+    foreign key (created_by_id) references users (id),
+    unique nulls not distinct (days_overdue, deleted_at)
+);
+
+CREATE TRIGGER update_updated_at_on_dunning_levels_table
+    BEFORE UPDATE
+    ON dunning_levels
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table dunning_reminders
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    receivable_id uuid         not null,
+    level_id      uuid         not null,
+    days_overdue  integer      not null,
+    recipient     varchar(255) not null,
+    subject       text         not null,
+    status        varchar(20)  not null check (status IN ('sent', 'failed')),
+    error         text,
+    created_at    timestamptz  not null default now(),
+    foreign key (receivable_id) references receivables (id) on delete cascade,
+    foreign key (level_id) references dunning_levels (id)
+);
+
+CREATE INDEX idx_dunning_reminders_receivable_id ON dunning_reminders (receivable_id, created_at);
+CREATE UNIQUE INDEX idx_dunning_reminders_sent_once ON dunning_reminders (receivable_id, level_id)
+    WHERE status = 'sent';
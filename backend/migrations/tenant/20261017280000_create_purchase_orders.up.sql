@@ -0,0 +1,78 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+CREATE SEQUENCE purchase_order_number_seq;
+
+create table purchase_orders
+(
+    id                     uuid primary key      default uuid_generate_v4(),
+    order_number           varchar(50)  not null,
+    supplier_id            uuid         not null,
+    warehouse_id           uuid         not null,
+    currency_code          varchar(3)   not null,
+    order_date             date         not null default current_date,
+    expected_delivery_date date,
+    supplier_reference     varchar(100),
+    notes                  text,
+    status                 varchar(50)  not null default 'draft' check (status IN ('draft', 'sent', 'partially_received', 'closed')),
+    sent_at                timestamptz,
+    closed_at              timestamptz,
+    created_by_id          uuid         not null,
+    created_at             timestamptz  not null default now(),
+    updated_at             timestamptz  not null default now(),
+    deleted_at             timestamptz,
+    foreign key (supplier_id) references suppliers (id),
+    foreign key (warehouse_id) references warehouses (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    constraint check_purchase_order_delivery_date check (expected_delivery_date IS NULL OR expected_delivery_date >= order_date),
+    unique (order_number)
+);
+
+CREATE INDEX idx_purchase_orders_supplier_id ON purchase_orders (supplier_id);
+CREATE INDEX idx_purchase_orders_status ON purchase_orders (status);
+CREATE INDEX idx_purchase_orders_expected_delivery_date ON purchase_orders (expected_delivery_date);
+CREATE INDEX idx_purchase_orders_deleted_at ON purchase_orders (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_purchase_orders_table
+    BEFORE UPDATE
+    ON purchase_orders
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table purchase_order_lines
+(
+    id                     uuid primary key        default uuid_generate_v4(),
+    purchase_order_id      uuid           not null,
+    product_id             uuid           not null,
+    supplier_sku           varchar(100),
+    description            text           not null,
+    quantity               numeric(15, 2) not null check (quantity > 0),
+    unit_price             numeric(15, 2) not null check (unit_price >= 0),
+    tax_id                 uuid           not null,
+    expected_delivery_date date,
+    position               integer        not null,
+    foreign key (purchase_order_id) references purchase_orders (id) on delete cascade,
+    foreign key (product_id) references products (id),
+    foreign key (tax_id) references taxes (id)
+);
+
+CREATE INDEX idx_purchase_order_lines_purchase_order_id ON purchase_order_lines (purchase_order_id);
+CREATE INDEX idx_purchase_order_lines_product_id ON purchase_order_lines (product_id);
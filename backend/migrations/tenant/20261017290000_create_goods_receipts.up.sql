@@ -0,0 +1,61 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+CREATE SEQUENCE goods_receipt_number_seq;
+
+create table goods_receipts
+(
+    id                   uuid primary key      default uuid_generate_v4(),
+    receipt_number       varchar(50)  not null,
+    purchase_order_id    uuid         not null,
+    warehouse_id         uuid         not null,
+    receipt_date         date         not null default current_date,
+    delivery_note_number varchar(100),
+    notes                text,
+    created_by_id        uuid         not null,
+    created_at           timestamptz  not null default now(),
+    foreign key (purchase_order_id) references purchase_orders (id),
+    foreign key (warehouse_id) references warehouses (id),
+    foreign key (created_by_id) references users (id),
+    unique (receipt_number)
+);
+
+CREATE INDEX idx_goods_receipts_purchase_order_id ON goods_receipts (purchase_order_id);
+CREATE INDEX idx_goods_receipts_receipt_date ON goods_receipts (receipt_date);
+
+create table goods_receipt_lines
+(
+    id                     uuid primary key        default uuid_generate_v4(),
+    goods_receipt_id       uuid           not null,
+    purchase_order_line_id uuid           not null,
+    inventory_movement_id  uuid           not null,
+    quantity               numeric(15, 2) not null check (quantity > 0),
+    unit_cost              numeric(15, 4) not null check (unit_cost >= 0),
+    currency_code          varchar(3)     not null,
+    lot_number             varchar(100),
+    expiry_date            date,
+    foreign key (goods_receipt_id) references goods_receipts (id) on delete cascade,
+    foreign key (purchase_order_line_id) references purchase_order_lines (id),
+    foreign key (inventory_movement_id) references inventory_movements (id),
+    foreign key (currency_code) references currencies (code)
+);
+
+CREATE INDEX idx_goods_receipt_lines_goods_receipt_id ON goods_receipt_lines (goods_receipt_id);
+CREATE INDEX idx_goods_receipt_lines_purchase_order_line_id ON goods_receipt_lines (purchase_order_line_id);
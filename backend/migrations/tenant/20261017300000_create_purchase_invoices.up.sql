@@ -0,0 +1,90 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table purchase_invoice_settings
+(
+    id                         boolean primary key    default true check (id),
+    quantity_tolerance_percent numeric(5, 2) not null default 0 check (quantity_tolerance_percent >= 0),
+    price_tolerance_percent    numeric(5, 2) not null default 0 check (price_tolerance_percent >= 0),
+    updated_by_id              uuid,
+    updated_at                 timestamptz   not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO purchase_invoice_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_purchase_invoice_settings_table
+    BEFORE UPDATE
+    ON purchase_invoice_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table purchase_invoices
+(
+    id                uuid primary key      default uuid_generate_v4(),
+    invoice_number    varchar(100) not null,
+    supplier_id       uuid         not null,
+    purchase_order_id uuid         not null,
+    currency_code     varchar(3)   not null,
+    issue_date        date         not null,
+    due_date          date         not null,
+    notes             text,
+    status            varchar(50)  not null default 'recorded' check (status IN ('recorded', 'approved', 'rejected')),
+    decided_by_id     uuid,
+    decided_at        timestamptz,
+    decision_note     text,
+    created_by_id     uuid         not null,
+    created_at        timestamptz  not null default now(),
+    updated_at        timestamptz  not null default now(),
+    deleted_at        timestamptz,
+    foreign key (supplier_id) references suppliers (id),
+    foreign key (purchase_order_id) references purchase_orders (id),
+    foreign key (currency_code) references currencies (code),
+    foreign key (decided_by_id) references users (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_purchase_invoice_due_date check (due_date >= issue_date)
+);
+
+-- The supplier's invoice number identifies the invoice, the same one must not be booked twice
+CREATE UNIQUE INDEX idx_purchase_invoices_supplier_invoice_number ON purchase_invoices (supplier_id, invoice_number)
+    WHERE deleted_at IS NULL;
+CREATE INDEX idx_purchase_invoices_purchase_order_id ON purchase_invoices (purchase_order_id);
+CREATE INDEX idx_purchase_invoices_status ON purchase_invoices (status);
+CREATE INDEX idx_purchase_invoices_deleted_at ON purchase_invoices (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_purchase_invoices_table
+    BEFORE UPDATE
+    ON purchase_invoices
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+create table purchase_invoice_lines
+(
+    id                     uuid primary key        default uuid_generate_v4(),
+    purchase_invoice_id    uuid           not null,
+    purchase_order_line_id uuid           not null,
+    quantity               numeric(15, 2) not null check (quantity > 0),
+    unit_price             numeric(15, 2) not null check (unit_price >= 0),
+    foreign key (purchase_invoice_id) references purchase_invoices (id) on delete cascade,
+    foreign key (purchase_order_line_id) references purchase_order_lines (id),
+    unique (purchase_invoice_id, purchase_order_line_id)
+);
+
+CREATE INDEX idx_purchase_invoice_lines_purchase_order_line_id ON purchase_invoice_lines (purchase_order_line_id);
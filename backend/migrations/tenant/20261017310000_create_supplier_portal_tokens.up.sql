@@ -0,0 +1,42 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+-- Only the SHA-256 hash of the token is stored, the token itself is shown once when issued
+create table supplier_portal_tokens
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    supplier_id   uuid         not null,
+    token_hash    varchar(64)  not null,
+    description   varchar(255),
+    scopes        text[]       not null check (scopes <@ ARRAY ['orders.read', 'orders.confirm'] AND cardinality(scopes) > 0),
+    expires_at    timestamptz  not null,
+    revoked_at    timestamptz,
+    last_used_at  timestamptz,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    foreign key (supplier_id) references suppliers (id),
+    foreign key (created_by_id) references users (id),
+    unique (token_hash)
+);
+
+CREATE INDEX idx_supplier_portal_tokens_supplier_id ON supplier_portal_tokens (supplier_id);
+
+-- Set when the supplier confirms the order, either through the portal or as recorded by a buyer
+ALTER TABLE purchase_orders ADD COLUMN confirmed_at timestamptz;
@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+-- An entry belongs to exactly one task, worksheet or project, a running timer has no ended_at
+-- and an approved entry is locked
+create table time_entries
+(
+    id             uuid primary key     default uuid_generate_v4(),
+    user_id        uuid        not null,
+    task_id        uuid,
+    worksheet_id   uuid,
+    project_id     uuid,
+    description    text,
+    started_at     timestamptz not null,
+    ended_at       timestamptz,
+    billable       boolean     not null default true,
+    approved_by_id uuid,
+    approved_at    timestamptz,
+    created_by_id  uuid        not null,
+    created_at     timestamptz not null default now(),
+    updated_at     timestamptz not null default now(),
+    deleted_at     timestamptz,
+    foreign key (user_id) references users (id),
+    foreign key (task_id) references tasks (id),
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (project_id) references projects (id),
+    foreign key (approved_by_id) references users (id),
+    foreign key (created_by_id) references users (id),
+    constraint check_time_entry_target check (num_nonnulls(task_id, worksheet_id, project_id) = 1),
+    constraint check_time_entry_period check (ended_at IS NULL OR ended_at > started_at),
+    constraint check_time_entry_approval check (approved_at IS NULL OR ended_at IS NOT NULL)
+);
+
+CREATE UNIQUE INDEX idx_time_entries_running ON time_entries (user_id) WHERE ended_at IS NULL AND deleted_at IS NULL;
+CREATE INDEX idx_time_entries_user_id_started_at ON time_entries (user_id, started_at);
+CREATE INDEX idx_time_entries_task_id ON time_entries (task_id);
+CREATE INDEX idx_time_entries_worksheet_id ON time_entries (worksheet_id);
+CREATE INDEX idx_time_entries_project_id ON time_entries (project_id);
+CREATE INDEX idx_time_entries_deleted_at ON time_entries (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_time_entries_table
+    BEFORE UPDATE
+    ON time_entries
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,34 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+-- Replies point to the comment they answer, edited_at is set when the author changes the text
+ALTER TABLE comments
+    ADD COLUMN parent_id uuid,
+    ADD COLUMN edited_at timestamptz,
+    ADD CONSTRAINT comments_parent_id_fkey FOREIGN KEY (parent_id) REFERENCES comments (id);
+
+CREATE INDEX idx_comments_commentable ON comments (commentable_type, commentable_id);
+CREATE INDEX idx_comments_parent_id ON comments (parent_id);
+
+-- Users mentioned as @name in a comment are notified even if they do not watch the record
+ALTER TABLE watch_notifications
+    DROP CONSTRAINT watch_notifications_event_check,
+    ADD CONSTRAINT watch_notifications_event_check
+        CHECK (event IN ('updated', 'deleted', 'commented', 'assigned', 'mentioned'));
@@ -0,0 +1,38 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table attachments
+(
+    id              uuid primary key      default uuid_generate_v4(),
+    attachable_type varchar(20)  not null check (attachable_type IN ('tasks', 'worksheets')),
+    attachable_id   uuid         not null,
+    file_name       varchar(255) not null,
+    content_type    varchar(100) not null,
+    size_bytes      bigint       not null check (size_bytes >= 0),
+    storage_key     varchar(512) not null,
+    created_by_id   uuid         not null,
+    created_at      timestamptz  not null default now(),
+    deleted_at      timestamptz,
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_attachments_attachable ON attachments (attachable_type, attachable_id);
+CREATE INDEX idx_attachments_created_by_id ON attachments (created_by_id);
+CREATE INDEX idx_attachments_deleted_at ON attachments (deleted_at);
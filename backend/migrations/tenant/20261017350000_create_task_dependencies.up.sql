@@ -0,0 +1,34 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table task_dependencies
+(
+    task_id       uuid        not null,
+    blocked_by_id uuid        not null,
+    created_by_id uuid        not null,
+    created_at    timestamptz not null default now(),
+    primary key (task_id, blocked_by_id),
+    check (task_id <> blocked_by_id),
+    foreign key (task_id) references tasks (id),
+    foreign key (blocked_by_id) references tasks (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked_by_id ON task_dependencies (blocked_by_id);
@@ -0,0 +1,53 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+create table task_recurrences
+(
+    id                   uuid primary key      default uuid_generate_v4(),
+    template_task_id     uuid         not null,
+    rule                 varchar(255) not null,
+    generate_on          varchar(20)  not null check (generate_on IN ('schedule', 'completion')),
+    start_date           date         not null,
+    next_occurrence_date date,
+    occurrence_count     integer      not null default 0 check (occurrence_count >= 0),
+    status               varchar(20)  not null default 'active' check (status IN ('active', 'paused', 'ended')),
+    created_by_id        uuid         not null,
+    created_at           timestamptz  not null default now(),
+    updated_at           timestamptz  not null default now(),
+    deleted_at           timestamptz,
+    foreign key (template_task_id) references tasks (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE UNIQUE INDEX idx_task_recurrences_template_task_id ON task_recurrences (template_task_id) WHERE deleted_at IS NULL;
+CREATE INDEX idx_task_recurrences_next_occurrence_date ON task_recurrences (next_occurrence_date);
+CREATE INDEX idx_task_recurrences_deleted_at ON task_recurrences (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_task_recurrences_table
+    BEFORE UPDATE
+    ON task_recurrences
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+alter table tasks
+    add column recurrence_id   uuid references task_recurrences (id),
+    add column occurrence_date date;
+
+CREATE UNIQUE INDEX idx_tasks_recurrence_occurrence ON tasks (recurrence_id, occurrence_date) WHERE deleted_at IS NULL;
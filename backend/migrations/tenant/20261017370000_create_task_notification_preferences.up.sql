@@ -0,0 +1,39 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Per-user email preferences of task notifications, users without a row get the defaults
+create table task_notification_preferences
+(
+    user_id              uuid primary key,
+    assignment_emails    boolean     not null default true,
+    due_date_emails      boolean     not null default true,
+    reminder_days_before integer     not null default 1 check (reminder_days_before BETWEEN 0 AND 30),
+    created_at           timestamptz not null default now(),
+    updated_at           timestamptz not null default now(),
+    foreign key (user_id) references users (id)
+);
+
+CREATE TRIGGER update_updated_at_on_task_notification_preferences_table
+    BEFORE UPDATE
+    ON task_notification_preferences
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- The due date the reminder was sent for, a changed due date is reminded again
+ALTER TABLE task_assignments ADD COLUMN due_reminder_sent_for timestamptz;
@@ -0,0 +1,57 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Kanban ordering within a status column, positions are spread with gaps so a moved task only
+-- needs a new value between its neighbours
+ALTER TABLE tasks ADD COLUMN position double precision;
+
+UPDATE tasks
+SET position = ranked.row_number * 1024
+FROM (SELECT id, row_number() OVER (PARTITION BY status ORDER BY created_at, id) AS row_number
+      FROM tasks) ranked
+WHERE tasks.id = ranked.id;
+
+ALTER TABLE tasks ALTER COLUMN position SET NOT NULL;
+
+CREATE INDEX idx_tasks_status_position ON tasks (status, position) WHERE deleted_at IS NULL;
+
+-- New tasks are placed at the end of their column whichever way they are created
+CREATE OR REPLACE FUNCTION set_task_position()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.position IS NULL THEN
+        NEW.position := COALESCE(
+                            (SELECT MAX(position)
+                             FROM tasks
+                             WHERE status = NEW.status
+                               AND deleted_at IS NULL),
+                            0
+                        ) + 1024;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_task_position_on_tasks_table
+    BEFORE INSERT
+    ON tasks
+    FOR EACH ROW
+EXECUTE FUNCTION set_task_position();
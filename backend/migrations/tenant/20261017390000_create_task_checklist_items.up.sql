@@ -0,0 +1,46 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Lightweight checklist items under a task, ordered by position like the kanban board
+create table task_checklist_items
+(
+    id            uuid primary key          default uuid_generate_v4(),
+    task_id       uuid             not null,
+    title         varchar(255)     not null,
+    is_done       boolean          not null default false,
+    position      double precision not null,
+    done_by_id    uuid,
+    done_at       timestamptz,
+    created_by_id uuid             not null,
+    created_at    timestamptz      not null default now(),
+    updated_at    timestamptz      not null default now(),
+    deleted_at    timestamptz,
+    foreign key (task_id) references tasks (id),
+    foreign key (done_by_id) references users (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_task_checklist_items_task_id ON task_checklist_items (task_id, position)
+    WHERE deleted_at IS NULL;
+
+CREATE TRIGGER update_updated_at_on_task_checklist_items_table
+    BEFORE UPDATE
+    ON task_checklist_items
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,33 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Customer signature of a completed worksheet, the signed worksheet is locked. The hash covers
+-- the worksheet and its tasks as they were at signing, so later changes can be detected.
+create table worksheet_signatures
+(
+    worksheet_id       uuid primary key,
+    signer_name        varchar(255) not null,
+    image_storage_key  varchar(255) not null,
+    image_content_type varchar(50)  not null,
+    content_hash       varchar(64)  not null,
+    signed_by_id       uuid         not null,
+    signed_at          timestamptz  not null default now(),
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (signed_by_id) references users (id)
+);
@@ -0,0 +1,24 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- The detailed worksheet document lists tasks, time entries and materials, the summary one only the totals
+ALTER TABLE document_settings
+    ADD COLUMN worksheet_layout    varchar(20) not null default 'detailed',
+    ADD COLUMN worksheet_hide_logo boolean     not null default false,
+    ADD CONSTRAINT check_worksheet_layout check (worksheet_layout IN ('detailed', 'summary'));
@@ -0,0 +1,36 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TRIGGER IF EXISTS refresh_worksheet_billing_status_on_inventory_movements_table ON inventory_movements;
+DROP TRIGGER IF EXISTS refresh_worksheet_billing_status_on_time_entries_table ON time_entries;
+DROP TRIGGER IF EXISTS refresh_worksheet_billing_status_on_tasks_table ON tasks;
+DROP FUNCTION IF EXISTS refresh_worksheet_billing_status_on_change();
+DROP FUNCTION IF EXISTS refresh_worksheet_billing_status(uuid);
+
+DROP TABLE IF EXISTS worksheet_receivables;
+
+ALTER TABLE inventory_movements
+    DROP COLUMN IF EXISTS receivable_id;
+ALTER TABLE time_entries
+    DROP COLUMN IF EXISTS receivable_id;
+ALTER TABLE tasks
+    DROP COLUMN IF EXISTS receivable_id;
+
+ALTER TABLE worksheets
+    DROP COLUMN IF EXISTS billing_status;
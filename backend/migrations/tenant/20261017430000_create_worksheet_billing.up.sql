@@ -0,0 +1,137 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Billed tasks, worksheet time entries and consumed materials point to their invoice, the
+-- worksheet billing status is derived from them
+ALTER TABLE worksheets
+    ADD COLUMN billing_status varchar(50) not null default 'not_billed'
+        check (billing_status IN ('not_billed', 'partially_billed', 'billed'));
+
+CREATE INDEX idx_worksheets_billing_status ON worksheets (billing_status);
+
+ALTER TABLE tasks
+    ADD COLUMN receivable_id uuid REFERENCES receivables (id);
+ALTER TABLE time_entries
+    ADD COLUMN receivable_id uuid REFERENCES receivables (id);
+ALTER TABLE inventory_movements
+    ADD COLUMN receivable_id uuid REFERENCES receivables (id);
+
+CREATE INDEX idx_tasks_receivable_id ON tasks (receivable_id);
+CREATE INDEX idx_time_entries_receivable_id ON time_entries (receivable_id);
+CREATE INDEX idx_inventory_movements_receivable_id ON inventory_movements (receivable_id);
+
+create table worksheet_receivables
+(
+    worksheet_id  uuid        not null,
+    receivable_id uuid        not null,
+    created_at    timestamptz not null default now(),
+    primary key (worksheet_id, receivable_id),
+    foreign key (worksheet_id) references worksheets (id),
+    foreign key (receivable_id) references receivables (id)
+);
+
+CREATE INDEX idx_worksheet_receivables_receivable_id ON worksheet_receivables (receivable_id);
+
+-- NOTE: only time booked directly on the worksheet is billed, time on a task is covered by the task
+CREATE OR REPLACE FUNCTION refresh_worksheet_billing_status(p_worksheet_id uuid)
+    RETURNS void
+    LANGUAGE plpgsql
+AS
+$$
+DECLARE
+    v_billed   boolean;
+    v_unbilled boolean;
+    v_status   varchar(50);
+BEGIN
+    SELECT EXISTS (SELECT 1 FROM tasks WHERE worksheet_id = p_worksheet_id AND receivable_id IS NOT NULL)
+               OR EXISTS (SELECT 1 FROM time_entries WHERE worksheet_id = p_worksheet_id AND receivable_id IS NOT NULL)
+               OR EXISTS (SELECT 1
+                          FROM inventory_movements
+                          WHERE reference_type = 'worksheets'
+                            AND reference_id = p_worksheet_id
+                            AND receivable_id IS NOT NULL)
+    INTO v_billed;
+
+    SELECT EXISTS (SELECT 1
+                   FROM tasks
+                   WHERE worksheet_id = p_worksheet_id
+                     AND deleted_at IS NULL
+                     AND receivable_id IS NULL)
+               OR EXISTS (SELECT 1
+                          FROM time_entries
+                          WHERE worksheet_id = p_worksheet_id
+                            AND deleted_at IS NULL
+                            AND billable
+                            AND ended_at IS NOT NULL
+                            AND receivable_id IS NULL)
+               OR EXISTS (SELECT 1
+                          FROM inventory_movements
+                          WHERE reference_type = 'worksheets'
+                            AND reference_id = p_worksheet_id
+                            AND movement_type = 'out'
+                            AND receivable_id IS NULL)
+    INTO v_unbilled;
+
+    v_status := CASE
+                    WHEN NOT v_billed THEN 'not_billed'
+                    WHEN v_unbilled THEN 'partially_billed'
+                    ELSE 'billed'
+        END;
+
+    UPDATE worksheets
+    SET billing_status = v_status
+    WHERE id = p_worksheet_id
+      AND billing_status <> v_status;
+END;
+$$;
+
+CREATE OR REPLACE FUNCTION refresh_worksheet_billing_status_on_change()
+    RETURNS trigger
+    LANGUAGE plpgsql
+AS
+$$
+BEGIN
+    IF TG_TABLE_NAME = 'inventory_movements' THEN
+        IF NEW.reference_type = 'worksheets' AND NEW.reference_id IS NOT NULL THEN
+            PERFORM refresh_worksheet_billing_status(NEW.reference_id);
+        END IF;
+    ELSIF NEW.worksheet_id IS NOT NULL THEN
+        PERFORM refresh_worksheet_billing_status(NEW.worksheet_id);
+    END IF;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER refresh_worksheet_billing_status_on_tasks_table
+    AFTER INSERT OR UPDATE OF receivable_id, deleted_at
+    ON tasks
+    FOR EACH ROW
+EXECUTE FUNCTION refresh_worksheet_billing_status_on_change();
+
+CREATE TRIGGER refresh_worksheet_billing_status_on_time_entries_table
+    AFTER INSERT OR UPDATE OF receivable_id, deleted_at, billable, ended_at
+    ON time_entries
+    FOR EACH ROW
+EXECUTE FUNCTION refresh_worksheet_billing_status_on_change();
+
+CREATE TRIGGER refresh_worksheet_billing_status_on_inventory_movements_table
+    AFTER INSERT OR UPDATE OF receivable_id
+    ON inventory_movements
+    FOR EACH ROW
+EXECUTE FUNCTION refresh_worksheet_billing_status_on_change();
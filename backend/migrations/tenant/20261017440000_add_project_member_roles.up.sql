@@ -0,0 +1,33 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- project_id pointed at tasks by mistake, assignments that do not belong to a project are dropped
+DELETE
+FROM project_assignments
+WHERE project_id NOT IN (SELECT id FROM projects);
+
+ALTER TABLE project_assignments
+    DROP CONSTRAINT project_assignments_project_id_fkey,
+    ADD CONSTRAINT project_assignments_project_id_fkey
+        FOREIGN KEY (project_id) REFERENCES projects (id),
+    ADD COLUMN role varchar(20) NOT NULL DEFAULT 'member',
+    ADD CONSTRAINT check_project_assignment_role
+        CHECK (role IN ('manager', 'member', 'viewer'));
+
+CREATE INDEX idx_project_assignments_role ON project_assignments (role);
@@ -0,0 +1,48 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+CREATE OR REPLACE FUNCTION notify_record_watchers()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+    SELECT user_id,
+           TG_TABLE_NAME,
+           NEW.id,
+           CASE WHEN NEW.deleted_at IS NOT NULL AND OLD.deleted_at IS NULL THEN 'deleted' ELSE 'updated' END
+    FROM watches
+    WHERE watchable_type = TG_TABLE_NAME
+      AND watchable_id = NEW.id;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS idx_watch_notifications_unread;
+
+UPDATE watch_notifications
+SET event = 'updated'
+WHERE event IN ('status_changed', 'due_date_changed');
+
+ALTER TABLE watch_notifications
+    DROP CONSTRAINT watch_notifications_event_check;
+ALTER TABLE watch_notifications
+    ADD CONSTRAINT watch_notifications_event_check
+        CHECK (event IN ('updated', 'deleted', 'commented', 'assigned'));
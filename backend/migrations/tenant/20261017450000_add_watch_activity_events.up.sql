@@ -0,0 +1,65 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+ALTER TABLE watch_notifications
+    DROP CONSTRAINT watch_notifications_event_check;
+ALTER TABLE watch_notifications
+    ADD CONSTRAINT watch_notifications_event_check
+        CHECK (event IN ('updated', 'deleted', 'commented', 'assigned', 'status_changed', 'due_date_changed'));
+
+CREATE INDEX idx_watch_notifications_unread
+    ON watch_notifications (user_id, watchable_type, watchable_id)
+    WHERE read_at IS NULL;
+
+-- Status and due date changes get their own event so watchers can tell them apart from any other edit.
+-- Projects keep their due date in end_date, the rest of the watchable tables in due_date (if at all).
+CREATE OR REPLACE FUNCTION notify_record_watchers()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    new_record      jsonb  := to_jsonb(NEW);
+    old_record      jsonb  := to_jsonb(OLD);
+    due_date_column text   := CASE WHEN TG_TABLE_NAME = 'projects' THEN 'end_date' ELSE 'due_date' END;
+    events          text[] := ARRAY []::text[];
+BEGIN
+    IF NEW.deleted_at IS NOT NULL AND OLD.deleted_at IS NULL THEN
+        events := ARRAY ['deleted'];
+    ELSE
+        IF new_record -> 'status' IS DISTINCT FROM old_record -> 'status' THEN
+            events := events || 'status_changed'::text;
+        END IF;
+        IF new_record -> due_date_column IS DISTINCT FROM old_record -> due_date_column THEN
+            events := events || 'due_date_changed'::text;
+        END IF;
+        IF cardinality(events) = 0 THEN
+            events := ARRAY ['updated'];
+        END IF;
+    END IF;
+
+    INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+    SELECT watches.user_id, TG_TABLE_NAME, NEW.id, changes.event
+    FROM watches
+             CROSS JOIN unnest(events) AS changes(event)
+    WHERE watches.watchable_type = TG_TABLE_NAME
+      AND watches.watchable_id = NEW.id;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,33 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_worksheets_table ON worksheets;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_tasks_table ON tasks;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_worksheet_checklist_items_table ON worksheet_checklist_items;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_worksheet_planned_materials_table ON worksheet_planned_materials;
+
+DROP FUNCTION IF EXISTS prevent_archived_project_changes();
+DROP FUNCTION IF EXISTS archived_project_id(jsonb);
+
+DROP INDEX IF EXISTS idx_projects_archived_at;
+
+ALTER TABLE projects
+    DROP COLUMN IF EXISTS archived_by_id,
+    DROP COLUMN IF EXISTS archived_at;
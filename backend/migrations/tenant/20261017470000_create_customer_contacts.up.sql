@@ -0,0 +1,60 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+-- Named contacts of a customer, the primary one is the default recipient of its invoices and
+-- documents
+create table customer_contacts
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    customer_id   uuid         not null,
+    name          varchar(255) not null,
+    role          varchar(100),
+    email         varchar(255),
+    phone_number  varchar(50),
+    is_primary    boolean      not null default false,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    updated_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (customer_id) references customers (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_customer_contacts_customer_id ON customer_contacts (customer_id)
+    WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX idx_customer_contacts_primary ON customer_contacts (customer_id)
+    WHERE is_primary AND deleted_at IS NULL;
+
+CREATE TRIGGER update_updated_at_on_customer_contacts_table
+    BEFORE UPDATE
+    ON customer_contacts
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- The single contact of the existing customers becomes their primary contact
+INSERT INTO customer_contacts (customer_id, name, email, phone_number, is_primary, created_by_id)
+SELECT id,
+       COALESCE(NULLIF(TRIM(contact_name), ''), name),
+       email,
+       phone_number,
+       true,
+       created_by_id
+FROM customers
+WHERE deleted_at IS NULL;
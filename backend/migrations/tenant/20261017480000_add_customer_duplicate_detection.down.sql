@@ -0,0 +1,60 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+CREATE OR REPLACE FUNCTION prevent_archived_project_changes()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    -- Billing keeps maintaining these on archived projects as well
+    bookkeeping_columns text[] := ARRAY ['updated_at', 'billing_status', 'receivable_id'];
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF to_jsonb(NEW) - bookkeeping_columns = to_jsonb(OLD) - bookkeeping_columns THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'INSERT' THEN
+        IF archived_project_id(to_jsonb(OLD)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        IF archived_project_id(to_jsonb(NEW)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+        RETURN NEW;
+    END IF;
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS idx_customers_lower_email;
+DROP INDEX IF EXISTS idx_customers_tax_number;
+DROP INDEX IF EXISTS idx_customers_normalized_name;
+DROP FUNCTION IF EXISTS normalize_customer_name(text);
+
+ALTER TABLE customers
+    DROP COLUMN IF EXISTS merged_into_id,
+    DROP COLUMN IF EXISTS tax_number;
@@ -0,0 +1,77 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+ALTER TABLE customers
+    ADD COLUMN tax_number varchar(50),
+    ADD COLUMN merged_into_id uuid REFERENCES customers (id);
+
+-- Legal form suffixes are dropped so "Teszt Kft." and "TESZT kft" are compared as equal
+CREATE OR REPLACE FUNCTION normalize_customer_name(p_name text) RETURNS text
+    LANGUAGE sql
+    IMMUTABLE
+    PARALLEL SAFE
+AS
+$$
+SELECT trim(regexp_replace(
+        regexp_replace(
+                translate(lower(p_name), 'áäéíóöőúüű', 'aaeiooouuu'),
+                '[^a-z0-9]+', ' ', 'g'),
+        '\m(kft|bt|zrt|nyrt|kkt|ev|rt|ltd|gmbh|inc)\M', ' ', 'g'))
+$$;
+
+CREATE INDEX idx_customers_normalized_name ON customers USING gin (normalize_customer_name(name) gin_trgm_ops)
+    WHERE deleted_at IS NULL;
+CREATE INDEX idx_customers_tax_number ON customers (tax_number)
+    WHERE deleted_at IS NULL;
+CREATE INDEX idx_customers_lower_email ON customers (lower(email))
+    WHERE deleted_at IS NULL;
+
+CREATE OR REPLACE FUNCTION prevent_archived_project_changes()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    -- Billing keeps maintaining these on archived projects as well, customer merges re-point
+    -- the worksheets of archived projects too
+    bookkeeping_columns text[] := ARRAY ['updated_at', 'billing_status', 'receivable_id', 'customer_id'];
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF to_jsonb(NEW) - bookkeeping_columns = to_jsonb(OLD) - bookkeeping_columns THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'INSERT' THEN
+        IF archived_project_id(to_jsonb(OLD)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        IF archived_project_id(to_jsonb(NEW)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+        RETURN NEW;
+    END IF;
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
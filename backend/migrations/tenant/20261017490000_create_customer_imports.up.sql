@@ -0,0 +1,32 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table customer_imports
+(
+    id                uuid primary key     default uuid_generate_v4(),
+    row_count         integer     not null,
+    customers_created integer     not null,
+    customers_updated integer     not null,
+    created_by_id     uuid        not null,
+    created_at        timestamptz not null default now(),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_customer_imports_created_at ON customer_imports (created_at);
+CREATE INDEX idx_customer_imports_created_by_id ON customer_imports (created_by_id);
@@ -0,0 +1,121 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table customer_segments
+(
+    id                    uuid primary key        default uuid_generate_v4(),
+    name                  varchar(255)   not null,
+    description           text,
+    statuses              varchar(50)[]  not null default '{}',
+    tag_ids               uuid[]         not null default '{}',
+    min_revenue           numeric(15, 2),
+    max_revenue           numeric(15, 2),
+    revenue_currency_code varchar(3),
+    last_activity_after   date,
+    last_activity_before  date,
+    created_by_id         uuid           not null,
+    created_at            timestamptz    not null default now(),
+    updated_at            timestamptz    not null default now(),
+    deleted_at            timestamptz,
+    foreign key (revenue_currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id),
+    unique nulls not distinct (name, deleted_at),
+    constraint check_customer_segment_revenue_currency
+        check ((min_revenue IS NULL AND max_revenue IS NULL) OR revenue_currency_code IS NOT NULL),
+    constraint check_customer_segment_revenue_range check (min_revenue <= max_revenue),
+    constraint check_customer_segment_activity_range check (last_activity_after <= last_activity_before)
+);
+
+CREATE INDEX idx_customer_segments_deleted_at ON customer_segments (deleted_at);
+
+CREATE TRIGGER update_updated_at_on_customer_segments_table
+    BEFORE UPDATE
+    ON customer_segments
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- Invoiced amount in the given currency, credit notes included, written off invoices left out
+CREATE OR REPLACE FUNCTION customer_revenue(p_customer_id uuid, p_currency_code varchar)
+    RETURNS numeric
+    LANGUAGE sql
+    STABLE AS
+$$
+SELECT COALESCE(SUM(amount), 0)
+FROM (SELECT amount
+      FROM receivables
+      WHERE customer_id = p_customer_id
+        AND currency_code = p_currency_code
+        AND status <> 'written_off'
+        AND deleted_at IS NULL
+      UNION ALL
+      SELECT amount
+      FROM credit_notes
+      WHERE customer_id = p_customer_id
+        AND currency_code = p_currency_code) AS documents
+$$;
+
+-- The latest event of the customer timeline, the creation of the customer if there is none
+CREATE OR REPLACE FUNCTION customer_last_activity(p_customer_id uuid)
+    RETURNS timestamptz
+    LANGUAGE sql
+    STABLE AS
+$$
+SELECT GREATEST(
+               (SELECT created_at FROM customers WHERE id = p_customer_id),
+               (SELECT MAX(created_at)
+                FROM comments
+                WHERE commentable_type = 'customers'
+                  AND commentable_id = p_customer_id
+                  AND deleted_at IS NULL),
+               (SELECT MAX(created_at) FROM worksheets WHERE customer_id = p_customer_id AND deleted_at IS NULL),
+               (SELECT MAX(created_at) FROM receivables WHERE customer_id = p_customer_id AND deleted_at IS NULL),
+               (SELECT MAX(created_at) FROM payments WHERE customer_id = p_customer_id AND deleted_at IS NULL),
+               (SELECT MAX(created_at) FROM quotes WHERE customer_id = p_customer_id AND deleted_at IS NULL),
+               (SELECT MAX(created_at) FROM collection_activities WHERE customer_id = p_customer_id)
+       )
+$$;
+
+-- Empty criteria match every customer, anonymized customers are never part of a segment
+CREATE OR REPLACE FUNCTION customer_in_segment(p_customer_id uuid, p_segment_id uuid)
+    RETURNS boolean
+    LANGUAGE sql
+    STABLE AS
+$$
+SELECT EXISTS (SELECT 1
+               FROM customer_segments AS segment
+                        JOIN customers ON customers.id = p_customer_id
+               WHERE segment.id = p_segment_id
+                 AND segment.deleted_at IS NULL
+                 AND NOT EXISTS (SELECT 1 FROM customer_anonymizations WHERE customer_id = customers.id)
+                 AND (cardinality(segment.statuses) = 0 OR customers.status = ANY (segment.statuses))
+                 AND (cardinality(segment.tag_ids) = 0 OR EXISTS (SELECT 1
+                                                                  FROM tag_connect
+                                                                  WHERE taggable_type = 'customers'
+                                                                    AND taggable_id = customers.id
+                                                                    AND tag_id = ANY (segment.tag_ids)
+                                                                    AND deleted_at IS NULL))
+                 AND (segment.min_revenue IS NULL OR
+                      customer_revenue(customers.id, segment.revenue_currency_code) >= segment.min_revenue)
+                 AND (segment.max_revenue IS NULL OR
+                      customer_revenue(customers.id, segment.revenue_currency_code) <= segment.max_revenue)
+                 AND (segment.last_activity_after IS NULL OR
+                      customer_last_activity(customers.id)::date >= segment.last_activity_after)
+                 AND (segment.last_activity_before IS NULL OR
+                      customer_last_activity(customers.id)::date <= segment.last_activity_before))
+$$;
@@ -0,0 +1,43 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table credit_limit_settings
+(
+    id            boolean primary key  default true check (id),
+    -- warn: the user has to acknowledge the overrun, block: the document is rejected
+    mode          varchar(10) not null default 'warn' check (mode IN ('warn', 'block')),
+    updated_by_id uuid,
+    updated_at    timestamptz not null default now(),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO credit_limit_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_credit_limit_settings_table
+    BEFORE UPDATE
+    ON credit_limit_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- null means no limit
+ALTER TABLE customers
+    ADD COLUMN credit_limit               numeric(15, 2) check (credit_limit >= 0),
+    ADD COLUMN credit_limit_currency_code varchar(3) references currencies (code),
+    ADD CONSTRAINT check_customer_credit_limit_currency
+        check ((credit_limit IS NULL) = (credit_limit_currency_code IS NULL));
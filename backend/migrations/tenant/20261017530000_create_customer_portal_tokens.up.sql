@@ -0,0 +1,40 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Only the SHA-256 hash of the token is stored, the token itself is shown once when issued
+create table customer_portal_tokens
+(
+    id            uuid primary key      default uuid_generate_v4(),
+    customer_id   uuid         not null,
+    token_hash    varchar(64)  not null,
+    description   varchar(255),
+    scopes        text[]       not null check (scopes <@ ARRAY ['invoices.read', 'worksheets.read'] AND cardinality(scopes) > 0),
+    -- documents downloaded through the portal are rendered in the timezone of the issuer
+    timezone      varchar(64)  not null,
+    expires_at    timestamptz  not null,
+    revoked_at    timestamptz,
+    last_used_at  timestamptz,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    foreign key (customer_id) references customers (id),
+    foreign key (created_by_id) references users (id),
+    unique (token_hash)
+);
+
+CREATE INDEX idx_customer_portal_tokens_customer_id ON customer_portal_tokens (customer_id);
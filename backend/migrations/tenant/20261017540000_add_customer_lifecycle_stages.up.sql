@@ -0,0 +1,42 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- customers recorded before the stages existed are already doing business with the tenant
+ALTER TABLE customers
+    ADD COLUMN lifecycle_stage varchar(16) not null default 'active'
+        check (lifecycle_stage IN ('lead', 'prospect', 'active', 'churned'));
+ALTER TABLE customers
+    ALTER COLUMN lifecycle_stage SET DEFAULT 'lead';
+
+CREATE INDEX idx_customers_lifecycle_stage ON customers (lifecycle_stage);
+
+create table customer_stage_transitions
+(
+    id            uuid primary key     default uuid_generate_v4(),
+    customer_id   uuid        not null,
+    from_stage    varchar(16) not null,
+    to_stage      varchar(16) not null check (to_stage IN ('lead', 'prospect', 'active', 'churned')),
+    note          text,
+    created_by_id uuid        not null,
+    created_at    timestamptz not null default now(),
+    foreign key (customer_id) references customers (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_customer_stage_transitions_customer_id ON customer_stage_transitions (customer_id);
@@ -0,0 +1,41 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table currency_rates
+(
+    id            uuid primary key        default uuid_generate_v4(),
+    currency_code varchar(3)     not null,
+    rate_date     date           not null,
+    -- value of one unit of the currency in HUF
+    rate          numeric(20, 8) not null check (rate > 0),
+    -- mnb, ecb: fetched by the scheduler, manual: recorded by a user and never overwritten by a fetch
+    source        varchar(16)    not null check (source IN ('mnb', 'ecb', 'manual')),
+    created_by_id uuid,
+    created_at    timestamptz    not null default now(),
+    updated_at    timestamptz    not null default now(),
+    unique (currency_code, rate_date),
+    foreign key (currency_code) references currencies (code),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE TRIGGER update_updated_at_on_currency_rates_table
+    BEFORE UPDATE
+    ON currency_rates
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
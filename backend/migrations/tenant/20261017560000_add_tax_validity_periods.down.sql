@@ -0,0 +1,30 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP FUNCTION IF EXISTS effective_tax_id(uuid, date);
+DROP INDEX IF EXISTS idx_taxes_previous_version_id;
+
+alter table taxes
+    drop constraint taxes_rate_country_code_tax_category_valid_from_deleted_at_key,
+    add constraint taxes_rate_country_code_tax_category_deleted_at_key
+        unique nulls not distinct (rate, country_code, tax_category, deleted_at),
+    drop constraint check_tax_validity,
+    drop column previous_version_id,
+    drop column valid_to,
+    drop column valid_from;
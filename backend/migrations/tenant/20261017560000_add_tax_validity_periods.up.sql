@@ -0,0 +1,63 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+alter table taxes
+    add column valid_from          date,
+    add column valid_to            date,
+    -- the version of the same tax this one superseded, e.g. the 27% rate before a VAT change
+    add column previous_version_id uuid references taxes (id),
+    add constraint check_tax_validity check (valid_from is null or valid_to is null or valid_to >= valid_from),
+    drop constraint taxes_rate_country_code_tax_category_deleted_at_key,
+    add constraint taxes_rate_country_code_tax_category_valid_from_deleted_at_key
+        unique nulls not distinct (rate, country_code, tax_category, valid_from, deleted_at);
+
+CREATE UNIQUE INDEX idx_taxes_previous_version_id ON taxes (previous_version_id) WHERE deleted_at IS NULL;
+
+-- The version of the tax effective on the given day, searched along the version chain in both
+-- directions; the tax itself when no version covers the day
+CREATE OR REPLACE FUNCTION effective_tax_id(p_tax_id uuid, p_on date)
+    RETURNS uuid
+    LANGUAGE sql
+    STABLE
+AS
+$$
+WITH RECURSIVE older AS (SELECT taxes.id, taxes.previous_version_id
+                         FROM taxes
+                         WHERE taxes.id = p_tax_id
+                         UNION ALL
+                         SELECT taxes.id, taxes.previous_version_id
+                         FROM taxes
+                         JOIN older ON taxes.id = older.previous_version_id),
+               newer AS (SELECT taxes.id
+                         FROM taxes
+                         WHERE taxes.id = p_tax_id
+                         UNION ALL
+                         SELECT taxes.id
+                         FROM taxes
+                         JOIN newer ON taxes.previous_version_id = newer.id
+                         WHERE taxes.deleted_at IS NULL)
+SELECT COALESCE((SELECT taxes.id
+                 FROM taxes
+                 WHERE taxes.id IN (SELECT older.id FROM older UNION SELECT newer.id FROM newer)
+                   AND taxes.deleted_at IS NULL
+                   AND (taxes.valid_from IS NULL OR taxes.valid_from <= p_on)
+                   AND (taxes.valid_to IS NULL OR taxes.valid_to >= p_on)
+                 ORDER BY taxes.valid_from DESC NULLS LAST
+                 LIMIT 1), p_tax_id)
+$$;
@@ -0,0 +1,32 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- special VAT treatment of a document line: 'domestic' applies the rate of the tax, the others
+-- (reverse charge, AAM, intra-community supply) are invoiced without VAT
+alter table quote_lines
+    add column vat_treatment varchar(50) not null default 'domestic'
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply'));
+
+alter table recurring_invoice_lines
+    add column vat_treatment varchar(50) not null default 'domestic'
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply'));
+
+alter table receivable_lines
+    add column vat_treatment varchar(50) not null default 'domestic'
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply'));
@@ -0,0 +1,38 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+ALTER TABLE currencies ADD COLUMN is_enabled boolean not null default true;
+
+create table currency_settings
+(
+    id                    boolean primary key     default true check (id),
+    default_currency_code varchar(3)  not null default 'HUF',
+    updated_by_id         uuid,
+    updated_at            timestamptz not null default now(),
+    foreign key (default_currency_code) references currencies (code),
+    foreign key (updated_by_id) references users (id)
+);
+
+INSERT INTO currency_settings (id) VALUES (true);
+
+CREATE TRIGGER update_updated_at_on_currency_settings_table
+    BEFORE UPDATE
+    ON currency_settings
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
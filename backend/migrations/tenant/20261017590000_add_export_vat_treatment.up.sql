@@ -0,0 +1,34 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- export of goods outside the EU is invoiced without VAT as well
+alter table quote_lines
+    drop constraint quote_lines_vat_treatment_check,
+    add constraint quote_lines_vat_treatment_check
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply', 'export'));
+
+alter table recurring_invoice_lines
+    drop constraint recurring_invoice_lines_vat_treatment_check,
+    add constraint recurring_invoice_lines_vat_treatment_check
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply', 'export'));
+
+alter table receivable_lines
+    drop constraint receivable_lines_vat_treatment_check,
+    add constraint receivable_lines_vat_treatment_check
+        check (vat_treatment in ('domestic', 'reverse_charge', 'aam', 'eu_supply', 'export'));
@@ -0,0 +1,72 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+create table tag_connect
+(
+    id            uuid         not null primary key,
+    taggable_id   uuid         not null,
+    taggable_type varchar(255) not null,
+    tag_id        uuid,
+    created_by_id uuid         not null,
+    created_at    timestamptz  not null default now(),
+    deleted_at    timestamptz,
+    foreign key (tag_id) references tags (id),
+    foreign key (created_by_id) references users (id)
+);
+
+CREATE INDEX idx_tag_connect_tag_id ON tag_connect (tag_id);
+CREATE INDEX idx_tag_connect_taggable_type_id ON tag_connect (taggable_type, taggable_id);
+CREATE INDEX idx_tag_connect_created_by_id ON tag_connect (created_by_id);
+CREATE INDEX idx_tag_connect_created_at ON tag_connect (created_at);
+CREATE INDEX idx_tag_connect_deleted_at ON tag_connect (deleted_at);
+
+INSERT INTO tag_connect (id, taggable_id, taggable_type, tag_id, created_by_id, created_at)
+SELECT id, taggable_id, taggable_type, tag_id, created_by_id, created_at
+FROM taggings;
+
+-- Empty criteria match every customer, anonymized customers are never part of a segment
+CREATE OR REPLACE FUNCTION customer_in_segment(p_customer_id uuid, p_segment_id uuid)
+    RETURNS boolean
+    LANGUAGE sql
+    STABLE AS
+$$
+SELECT EXISTS (SELECT 1
+               FROM customer_segments AS segment
+                        JOIN customers ON customers.id = p_customer_id
+               WHERE segment.id = p_segment_id
+                 AND segment.deleted_at IS NULL
+                 AND NOT EXISTS (SELECT 1 FROM customer_anonymizations WHERE customer_id = customers.id)
+                 AND (cardinality(segment.statuses) = 0 OR customers.status = ANY (segment.statuses))
+                 AND (cardinality(segment.tag_ids) = 0 OR EXISTS (SELECT 1
+                                                                  FROM tag_connect
+                                                                  WHERE taggable_type = 'customers'
+                                                                    AND taggable_id = customers.id
+                                                                    AND tag_id = ANY (segment.tag_ids)
+                                                                    AND deleted_at IS NULL))
+                 AND (segment.min_revenue IS NULL OR
+                      customer_revenue(customers.id, segment.revenue_currency_code) >= segment.min_revenue)
+                 AND (segment.max_revenue IS NULL OR
+                      customer_revenue(customers.id, segment.revenue_currency_code) <= segment.max_revenue)
+                 AND (segment.last_activity_after IS NULL OR
+                      customer_last_activity(customers.id)::date >= segment.last_activity_after)
+                 AND (segment.last_activity_before IS NULL OR
+                      customer_last_activity(customers.id)::date <= segment.last_activity_before))
+$$;
+
+DROP TABLE IF EXISTS taggings;
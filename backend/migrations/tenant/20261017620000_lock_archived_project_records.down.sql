@@ -0,0 +1,73 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_task_dependencies_table ON task_dependencies;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_task_checklist_items_table ON task_checklist_items;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_task_assignments_table ON task_assignments;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_task_recurrences_table ON task_recurrences;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_time_entries_table ON time_entries;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_worksheet_signatures_table ON worksheet_signatures;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_comments_table ON comments;
+DROP TRIGGER IF EXISTS prevent_archived_project_changes_on_attachments_table ON attachments;
+
+CREATE OR REPLACE FUNCTION archived_project_id(row_data jsonb)
+    RETURNS uuid AS
+$$
+SELECT projects.id
+FROM projects
+WHERE projects.archived_at IS NOT NULL
+  AND projects.id = COALESCE(
+        (row_data ->> 'project_id')::uuid,
+        (SELECT worksheets.project_id
+         FROM worksheets
+         WHERE worksheets.id = (row_data ->> 'worksheet_id')::uuid));
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION prevent_archived_project_changes()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    -- Billing keeps maintaining these on archived projects as well, customer merges re-point
+    -- the worksheets of archived projects too
+    bookkeeping_columns text[] := ARRAY ['updated_at', 'billing_status', 'receivable_id', 'customer_id'];
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF to_jsonb(NEW) - bookkeeping_columns = to_jsonb(OLD) - bookkeeping_columns THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'INSERT' THEN
+        IF archived_project_id(to_jsonb(OLD)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        IF archived_project_id(to_jsonb(NEW)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+        RETURN NEW;
+    END IF;
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,96 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+-- Rows hanging off a task or pointing to a project record through a type/id pair belong to the
+-- project as well
+CREATE OR REPLACE FUNCTION archived_project_id(row_data jsonb)
+    RETURNS uuid AS
+$$
+WITH owner AS (SELECT COALESCE(row_data ->> 'commentable_type', row_data ->> 'attachable_type') AS type,
+                      COALESCE(row_data ->> 'commentable_id', row_data ->> 'attachable_id')::uuid AS id)
+SELECT projects.id
+FROM projects,
+     owner
+WHERE projects.archived_at IS NOT NULL
+  AND projects.id = COALESCE(
+        (row_data ->> 'project_id')::uuid,
+        CASE WHEN owner.type = 'projects' THEN owner.id END,
+        (SELECT worksheets.project_id
+         FROM worksheets
+         WHERE worksheets.id = COALESCE(
+                 (row_data ->> 'worksheet_id')::uuid,
+                 CASE WHEN owner.type = 'worksheets' THEN owner.id END,
+                 (SELECT tasks.worksheet_id
+                  FROM tasks
+                  WHERE tasks.id = COALESCE(
+                          (row_data ->> 'task_id')::uuid,
+                          (row_data ->> 'template_task_id')::uuid,
+                          CASE WHEN owner.type = 'tasks' THEN owner.id END)))));
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION prevent_archived_project_changes()
+    RETURNS TRIGGER AS
+$$
+DECLARE
+    -- Billing keeps maintaining these on archived projects as well, customer merges re-point
+    -- the worksheets of archived projects too. Running timers can still be stopped and the
+    -- time entries approved for billing.
+    bookkeeping_columns text[] := ARRAY ['updated_at', 'billing_status', 'receivable_id', 'customer_id',
+        'ended_at', 'approved_at', 'approved_by_id'];
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF to_jsonb(NEW) - bookkeeping_columns = to_jsonb(OLD) - bookkeeping_columns THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'INSERT' THEN
+        IF archived_project_id(to_jsonb(OLD)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        IF archived_project_id(to_jsonb(NEW)) IS NOT NULL THEN
+            RAISE EXCEPTION 'Records of archived projects cannot be modified'
+                USING ERRCODE = 'check_violation', CONSTRAINT = 'project_archived';
+        END IF;
+        RETURN NEW;
+    END IF;
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+-- NOTE: project members stay editable so access to the archive can still be managed
+DO
+$$
+    DECLARE
+        locked_table text;
+    BEGIN
+        FOREACH locked_table IN ARRAY ARRAY ['task_dependencies', 'task_checklist_items', 'task_assignments',
+            'task_recurrences', 'time_entries', 'worksheet_signatures', 'comments', 'attachments']
+            LOOP
+                EXECUTE format(
+                        'CREATE TRIGGER prevent_archived_project_changes_on_%1$s_table BEFORE INSERT OR UPDATE OR DELETE ON %1$s FOR EACH ROW EXECUTE FUNCTION prevent_archived_project_changes()',
+                        locked_table);
+            END LOOP;
+    END;
+$$;
@@ -0,0 +1,28 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+INSERT INTO taxes (rate, description, country_code, tax_category, is_rate_applicable, legal_text,
+                   reporting_code, is_default, created_by_id)
+VALUES (27.00, 'Általános adókulcs (27%)', 'HU', 'standard', true, NULL, NULL,
+        NOT EXISTS (SELECT 1 FROM taxes WHERE is_default AND deleted_at IS NULL), $1),
+       (18.00, 'Kedvezményes adókulcs (18%)', 'HU', 'reduced', true, NULL, NULL, false, $1),
+       (5.00, 'Kedvezményes adókulcs (5%)', 'HU', 'reduced', true, NULL, NULL, false, $1),
+       (NULL, 'Alanyi adómentes', 'HU', 'small_business_exempt', false,
+        'Alanyi adómentes (Áfa tv. XIII. fejezet)', 'AAM', false, $1)
+ON CONFLICT DO NOTHING;
@@ -22,8 +22,13 @@ use chrono::{Duration, Utc};
 use chrono_tz::Tz;
 use clap::{Parser, Subcommand};
 use obvia::{
-    common::{config::AppConfig, init::init_default_app_state, service::Service},
-    manager::auth::dto::claims::Claims,
+    common::{config::AppConfig, crypto::keyring, init::init_default_app_state, service::Service},
+    manager::{
+        auth::dto::claims::Claims,
+        tenant_integrity::service::TenantIntegrityService,
+        tenant_limits::{dto::SetTenantLimits, service::TenantLimitsService},
+        tenants::{dto::RelinkTenant, service::TenantService},
+    },
     tenant::{
         Modules, customers::service::CustomerService, inventory::service::InventoryService,
         inventory_movements::service::InventoryMovementService,
@@ -33,7 +38,7 @@ use obvia::{
         warehouses::service::WarehouseService, worksheets::service::WorksheetService,
     },
 };
-use std::{path::Path, sync::Arc};
+use std::path::Path;
 use uuid::Uuid;
 
 #[derive(Parser, Debug)]
@@ -49,6 +54,11 @@ enum CliCommands {
         #[command(subcommand)]
         command: DevCommands,
     },
+    /// Tenant maintenance
+    Tenant {
+        #[command(subcommand)]
+        command: TenantCommands,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -62,6 +72,76 @@ enum DevCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum TenantCommands {
+    /// Re-link an existing (restored or moved) tenant database to the manager database
+    Relink {
+        #[arg(long)]
+        tenant_id: Uuid,
+
+        #[arg(long)]
+        owner_id: Uuid,
+
+        #[arg(long)]
+        name: String,
+
+        #[arg(long)]
+        db_host: String,
+
+        #[arg(long, default_value_t = 5432)]
+        db_port: u16,
+
+        #[arg(long)]
+        db_name: String,
+
+        #[arg(long)]
+        db_user: String,
+
+        #[arg(long)]
+        db_password: String,
+
+        #[arg(long)]
+        db_max_pool_size: Option<u32>,
+
+        #[arg(long, default_value = "disable")]
+        db_ssl_mode: String,
+    },
+    /// Re-encrypt tenant database passwords with the active encryption key
+    ReencryptPasswords,
+    /// Show the plan limits of a tenant
+    GetLimits {
+        #[arg(long)]
+        tenant_id: Uuid,
+    },
+    /// Set the plan limits of a tenant (omitted limits are unlimited)
+    SetLimits {
+        #[arg(long)]
+        tenant_id: Uuid,
+
+        #[arg(long)]
+        max_users: Option<i32>,
+
+        #[arg(long)]
+        max_products: Option<i32>,
+
+        #[arg(long)]
+        max_storage_mb: Option<i64>,
+    },
+    /// Report broken references in a tenant database
+    IntegrityReport {
+        #[arg(long)]
+        tenant_id: Uuid,
+    },
+    /// Run the cleanup of one integrity check in a single transaction
+    IntegrityCleanup {
+        #[arg(long)]
+        tenant_id: Uuid,
+
+        #[arg(long)]
+        check: String,
+    },
+}
+
 fn gen_exp(expiration_mins: u64) -> anyhow::Result<usize> {
     (Utc::now()
         + Duration::minutes(
@@ -96,7 +176,7 @@ async fn gen_pdf_test_snapshot(module: &Modules, folder: &str) -> anyhow::Result
     let config = AppConfig::from_env()?;
     let claims = init_dev_claims(&config)?;
     let app_state = init_default_app_state(config).await?;
-    let service = Service::new(Some(&claims), Arc::new(app_state));
+    let service = Service::new(Some(&claims), app_state);
     let path = format!("{folder}/{module}_test.pdf");
     let path = Path::new(&path);
     match module {
@@ -117,6 +197,66 @@ async fn gen_pdf_test_snapshot(module: &Modules, folder: &str) -> anyhow::Result
     }
 }
 
+async fn relink_tenant(payload: &RelinkTenant) -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let service = Service::new(None, app_state);
+    let tenant = TenantService::relink(&service, payload).await?;
+    println!("Tenant relinked: {} ({})", tenant.id, tenant.name);
+    Ok(())
+}
+
+async fn reencrypt_tenant_passwords() -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let keyring = keyring().ok_or(anyhow!("[encryption] is not configured"))?;
+    let service = Service::new(None, app_state);
+    let count = TenantService::reencrypt_db_passwords(&service, keyring).await?;
+    println!("Tenant database passwords re-encrypted: {count}");
+    Ok(())
+}
+
+async fn get_tenant_limits(tenant_id: Uuid) -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let service = Service::new(None, app_state);
+    match TenantLimitsService::get(&service, tenant_id).await? {
+        Some(limits) => println!("{}", serde_json::to_string_pretty(&limits)?),
+        None => println!("Tenant has no limits: {tenant_id}"),
+    }
+    Ok(())
+}
+
+async fn set_tenant_limits(payload: &SetTenantLimits) -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let service = Service::new(None, app_state);
+    let limits = TenantLimitsService::set(&service, payload).await?;
+    println!("{}", serde_json::to_string_pretty(&limits)?);
+    Ok(())
+}
+
+async fn tenant_integrity_report(tenant_id: Uuid) -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let service = Service::new(None, app_state);
+    let report = TenantIntegrityService::report(&service, tenant_id).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+async fn tenant_integrity_cleanup(tenant_id: Uuid, check: &str) -> anyhow::Result<()> {
+    let config = AppConfig::from_env()?;
+    let app_state = init_default_app_state(config).await?;
+    let service = Service::new(None, app_state);
+    let result = TenantIntegrityService::cleanup(&service, tenant_id, check).await?;
+    println!(
+        "Integrity cleanup {}: {} records",
+        result.check, result.affected
+    );
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -127,6 +267,60 @@ async fn main() -> anyhow::Result<()> {
                 gen_pdf_test_snapshot(module, folder).await?;
             }
         },
+        CliCommands::Tenant { command } => match &command {
+            TenantCommands::Relink {
+                tenant_id,
+                owner_id,
+                name,
+                db_host,
+                db_port,
+                db_name,
+                db_user,
+                db_password,
+                db_max_pool_size,
+                db_ssl_mode,
+            } => {
+                relink_tenant(&RelinkTenant {
+                    tenant_id: *tenant_id,
+                    owner_id: *owner_id,
+                    name: name.clone(),
+                    db_host: db_host.clone(),
+                    db_port: *db_port,
+                    db_name: db_name.clone(),
+                    db_user: db_user.clone(),
+                    db_password: db_password.clone(),
+                    db_max_pool_size: *db_max_pool_size,
+                    db_ssl_mode: Some(db_ssl_mode.clone()),
+                })
+                .await?;
+            }
+            TenantCommands::ReencryptPasswords => {
+                reencrypt_tenant_passwords().await?;
+            }
+            TenantCommands::GetLimits { tenant_id } => {
+                get_tenant_limits(*tenant_id).await?;
+            }
+            TenantCommands::SetLimits {
+                tenant_id,
+                max_users,
+                max_products,
+                max_storage_mb,
+            } => {
+                set_tenant_limits(&SetTenantLimits {
+                    tenant_id: *tenant_id,
+                    max_users: *max_users,
+                    max_products: *max_products,
+                    max_storage_mb: *max_storage_mb,
+                })
+                .await?;
+            }
+            TenantCommands::IntegrityReport { tenant_id } => {
+                tenant_integrity_report(*tenant_id).await?;
+            }
+            TenantCommands::IntegrityCleanup { tenant_id, check } => {
+                tenant_integrity_cleanup(*tenant_id, check).await?;
+            }
+        },
     }
     Ok(())
 }
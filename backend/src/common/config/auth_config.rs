@@ -18,6 +18,7 @@
  */
 
 use serde::Deserialize;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AuthConfig {
@@ -26,6 +27,8 @@ pub struct AuthConfig {
     jwt_audience: String,
     access_token_expiration_mins: u64,
     refresh_token_expiration_mins: u64,
+    #[serde(default)]
+    operator_user_ids: Vec<Uuid>,
 }
 
 impl AuthConfig {
@@ -44,6 +47,9 @@ impl AuthConfig {
     pub fn refresh_token_expiration_mins(&self) -> u64 {
         self.refresh_token_expiration_mins
     }
+    pub fn is_operator(&self, user_id: Uuid) -> bool {
+        self.operator_user_ids.contains(&user_id)
+    }
 }
 
 #[cfg(test)]
@@ -56,6 +62,7 @@ pub(crate) mod tests {
         jwt_audience: Option<String>,
         access_token_expiration_mins: Option<u64>,
         refresh_token_expiration_mins: Option<u64>,
+        operator_user_ids: Vec<Uuid>,
     }
 
     impl AuthConfigBuilder {
@@ -66,6 +73,7 @@ pub(crate) mod tests {
                 jwt_audience: None,
                 access_token_expiration_mins: None,
                 refresh_token_expiration_mins: None,
+                operator_user_ids: Vec::new(),
             }
         }
         pub fn jwt_secret(mut self, jwt_secret: &str) -> Self {
@@ -88,6 +96,10 @@ pub(crate) mod tests {
             self.refresh_token_expiration_mins = Some(refresh_token_expiration_mins);
             self
         }
+        pub fn operator_user_ids(mut self, operator_user_ids: Vec<Uuid>) -> Self {
+            self.operator_user_ids = operator_user_ids;
+            self
+        }
         pub fn build(self) -> Result<AuthConfig, String> {
             Ok(AuthConfig {
                 jwt_secret: self.jwt_secret.ok_or("jwt_secret is required")?,
@@ -99,6 +111,7 @@ pub(crate) mod tests {
                 refresh_token_expiration_mins: self
                     .refresh_token_expiration_mins
                     .ok_or("refresh_token_expiration_mins is required")?,
+                operator_user_ids: self.operator_user_ids,
             })
         }
     }
@@ -17,6 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::crypto::decrypt_secret;
 use crate::common::types::{DbHost, DbName, DbPassword, DbPort, DbUser};
 use crate::common::value_object::{ValueObjectError, ValueObjectRequired};
 use crate::manager::tenants::model::Tenant;
@@ -107,8 +108,8 @@ impl TryFrom<&Tenant> for TenantDatabaseConfig {
                 .db_user
                 .parse()
                 .map_err(|e: ValueObjectError| e.to_string())?,
-            password: value
-                .db_password
+            password: decrypt_secret(&value.db_password)
+                .map_err(|e| e.to_string())?
                 .parse()
                 .map_err(|e: ValueObjectError| e.to_string())?,
             database: value
@@ -132,7 +133,7 @@ impl TryFrom<&Tenant> for BasicDatabaseConfig {
             host: value.db_host.clone(),
             port: value.db_port as u16,
             username: value.db_user.clone(),
-            password: value.db_password.clone(),
+            password: decrypt_secret(&value.db_password).map_err(|e| e.to_string())?,
             database: value.db_name.clone(),
             max_pool_size: Some(
                 u32::try_from(value.db_max_pool_size)
@@ -0,0 +1,39 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct InventoryConfig {
+    low_stock_alert_interval_hours: Option<u64>,
+    stock_snapshot_interval_hours: Option<u64>,
+    capacity_alert_interval_hours: Option<u64>,
+}
+
+impl InventoryConfig {
+    pub fn low_stock_alert_interval_hours(&self) -> u64 {
+        self.low_stock_alert_interval_hours.unwrap_or(24)
+    }
+    pub fn stock_snapshot_interval_hours(&self) -> u64 {
+        self.stock_snapshot_interval_hours.unwrap_or(24)
+    }
+    pub fn capacity_alert_interval_hours(&self) -> u64 {
+        self.capacity_alert_interval_hours.unwrap_or(1)
+    }
+}
@@ -17,17 +17,45 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::manager::tenants::model::Tenant;
 use serde::Deserialize;
 
+pub(crate) mod accounting_exports_config;
 pub(crate) mod auth_config;
+pub(crate) mod carriers_config;
 pub(crate) mod database_config;
+pub(crate) mod encryption_config;
+pub(crate) mod exchange_rates_config;
+pub(crate) mod inventory_config;
 pub(crate) mod mail_config;
+pub(crate) mod nav_config;
+pub(crate) mod payment_links_config;
+pub(crate) mod provisioning_config;
+pub(crate) mod receivables_config;
+pub(crate) mod sandbox_config;
 pub(crate) mod server_config;
+pub(crate) mod storage_config;
+pub(crate) mod tasks_config;
 
+pub(crate) use accounting_exports_config::AccountingExportsConfig;
 pub(crate) use auth_config::AuthConfig;
+pub(crate) use carriers_config::CarriersConfig;
 pub(crate) use database_config::BasicDatabaseConfig;
+pub(crate) use encryption_config::EncryptionConfig;
+pub(crate) use exchange_rates_config::ExchangeRatesConfig;
+pub(crate) use inventory_config::InventoryConfig;
 pub(crate) use mail_config::MailConfig;
+pub(crate) use nav_config::NavConfig;
+pub(crate) use payment_links_config::PaymentLinksConfig;
+pub(crate) use provisioning_config::{PlacementStrategy, ProvisioningConfig};
+pub(crate) use receivables_config::ReceivablesConfig;
+pub(crate) use sandbox_config::SandboxConfig;
 pub(crate) use server_config::ServerConfig;
+pub(crate) use storage_config::StorageConfig;
+pub(crate) use tasks_config::TasksConfig;
+
+// NOTE: name of the implicit cluster when no [[provisioning.clusters]] are configured
+pub const DEFAULT_CLUSTER_NAME: &str = "default";
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
@@ -35,6 +63,30 @@ pub struct AppConfig {
     main_database: BasicDatabaseConfig,
     auth: AuthConfig,
     mail: MailConfig,
+    #[serde(default)]
+    sandbox: SandboxConfig,
+    #[serde(default)]
+    provisioning: ProvisioningConfig,
+    #[serde(default)]
+    carriers: CarriersConfig,
+    #[serde(default)]
+    encryption: EncryptionConfig,
+    #[serde(default)]
+    receivables: ReceivablesConfig,
+    #[serde(default)]
+    inventory: InventoryConfig,
+    #[serde(default)]
+    storage: StorageConfig,
+    #[serde(default)]
+    nav: NavConfig,
+    #[serde(default)]
+    payment_links: PaymentLinksConfig,
+    #[serde(default)]
+    accounting_exports: AccountingExportsConfig,
+    #[serde(default)]
+    tasks: TasksConfig,
+    #[serde(default)]
+    exchange_rates: ExchangeRatesConfig,
 }
 
 impl AppConfig {
@@ -58,13 +110,86 @@ impl AppConfig {
     pub fn mail(&self) -> &MailConfig {
         &self.mail
     }
+    pub fn sandbox(&self) -> &SandboxConfig {
+        &self.sandbox
+    }
+    pub fn provisioning(&self) -> &ProvisioningConfig {
+        &self.provisioning
+    }
+    pub fn provisioning_clusters(&self) -> Vec<(&str, &BasicDatabaseConfig)> {
+        if self.provisioning.clusters().is_empty() {
+            vec![(
+                DEFAULT_CLUSTER_NAME,
+                self.provisioning.cluster().unwrap_or(&self.main_database),
+            )]
+        } else {
+            self.provisioning
+                .clusters()
+                .iter()
+                .map(|cluster| (cluster.name.as_str(), &cluster.database))
+                .collect()
+        }
+    }
+    pub fn provisioning_cluster(&self, name: &str) -> Option<&BasicDatabaseConfig> {
+        self.provisioning_clusters()
+            .into_iter()
+            .find(|(cluster_name, _)| *cluster_name == name)
+            .map(|(_, cluster)| cluster)
+    }
+    pub fn tenant_database(&self, tenant: &Tenant) -> Result<BasicDatabaseConfig, String> {
+        let db_config = BasicDatabaseConfig::try_from(tenant)?;
+        match &tenant.db_cluster {
+            Some(name) if !tenant.is_self_hosted => {
+                let cluster = self
+                    .provisioning_cluster(name)
+                    .ok_or(format!("unknown database cluster: {name}"))?;
+                Ok(BasicDatabaseConfig {
+                    host: cluster.host.clone(),
+                    port: cluster.port,
+                    ..db_config
+                })
+            }
+            _ => Ok(db_config),
+        }
+    }
+    pub fn carriers(&self) -> &CarriersConfig {
+        &self.carriers
+    }
+    pub fn encryption(&self) -> &EncryptionConfig {
+        &self.encryption
+    }
+    pub fn receivables(&self) -> &ReceivablesConfig {
+        &self.receivables
+    }
+    pub fn inventory(&self) -> &InventoryConfig {
+        &self.inventory
+    }
+    pub fn storage(&self) -> &StorageConfig {
+        &self.storage
+    }
+    pub fn nav(&self) -> &NavConfig {
+        &self.nav
+    }
+    pub fn payment_links(&self) -> &PaymentLinksConfig {
+        &self.payment_links
+    }
+    pub fn accounting_exports(&self) -> &AccountingExportsConfig {
+        &self.accounting_exports
+    }
+    pub fn tasks(&self) -> &TasksConfig {
+        &self.tasks
+    }
+    pub fn exchange_rates(&self) -> &ExchangeRatesConfig {
+        &self.exchange_rates
+    }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use crate::common::config::{
         auth_config::tests::AuthConfigBuilder, database_config::tests::DatabaseConfigBuilder,
-        mail_config::tests::MailConfigBuilder, server_config::tests::ServerConfigBuilder,
+        mail_config::tests::MailConfigBuilder, sandbox_config::tests::SandboxConfigBuilder,
+        server_config::tests::ServerConfigBuilder,
     };
 
     use super::*;
@@ -74,6 +199,7 @@ pub(crate) mod tests {
         main_database: Option<BasicDatabaseConfig>,
         auth: Option<AuthConfig>,
         mail: Option<MailConfig>,
+        sandbox: Option<SandboxConfig>,
     }
 
     impl AppConfigBuilder {
@@ -83,6 +209,7 @@ pub(crate) mod tests {
                 main_database: None,
                 auth: None,
                 mail: None,
+                sandbox: None,
             }
         }
         pub fn server(mut self, server: ServerConfig) -> Self {
@@ -102,12 +229,28 @@ pub(crate) mod tests {
             self.mail = Some(mail);
             self
         }
+        pub fn sandbox(mut self, sandbox: SandboxConfig) -> Self {
+            self.sandbox = Some(sandbox);
+            self
+        }
         pub fn build(self) -> Result<AppConfig, String> {
             Ok(AppConfig {
                 server: self.server.ok_or("server is required")?,
                 main_database: self.main_database.ok_or("main_database is required")?,
                 auth: self.auth.ok_or("auth is required")?,
                 mail: self.mail.ok_or("mail is required")?,
+                sandbox: self.sandbox.unwrap_or_default(),
+                provisioning: ProvisioningConfig::default(),
+                carriers: CarriersConfig::default(),
+                encryption: EncryptionConfig::default(),
+                receivables: ReceivablesConfig::default(),
+                inventory: InventoryConfig::default(),
+                storage: StorageConfig::default(),
+                nav: NavConfig::default(),
+                payment_links: PaymentLinksConfig::default(),
+                accounting_exports: AccountingExportsConfig::default(),
+                tasks: TasksConfig::default(),
+                exchange_rates: ExchangeRatesConfig::default(),
             })
         }
     }
@@ -119,6 +262,7 @@ pub(crate) mod tests {
                 .main_database(DatabaseConfigBuilder::default().build().unwrap())
                 .auth(AuthConfigBuilder::default().build().unwrap())
                 .mail(MailConfigBuilder::default().build().unwrap())
+                .sandbox(SandboxConfigBuilder::default().build().unwrap())
         }
     }
 }
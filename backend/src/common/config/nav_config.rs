@@ -0,0 +1,53 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct NavConfig {
+    queue_interval_mins: Option<u64>,
+    request_timeout_secs: Option<u64>,
+    max_attempts: Option<i32>,
+    software_id: Option<String>,
+    software_dev_name: Option<String>,
+    software_dev_contact: Option<String>,
+}
+
+impl NavConfig {
+    pub fn queue_interval_mins(&self) -> u64 {
+        self.queue_interval_mins.unwrap_or(5)
+    }
+    pub fn request_timeout_secs(&self) -> u64 {
+        self.request_timeout_secs.unwrap_or(30)
+    }
+    pub fn max_attempts(&self) -> i32 {
+        self.max_attempts.unwrap_or(10)
+    }
+    pub fn software_id(&self) -> &str {
+        self.software_id.as_deref().unwrap_or("HU00000000OBVIA001")
+    }
+    pub fn software_dev_name(&self) -> &str {
+        self.software_dev_name.as_deref().unwrap_or("Kovács Dávid")
+    }
+    pub fn software_dev_contact(&self) -> &str {
+        self.software_dev_contact
+            .as_deref()
+            .unwrap_or("kapcsolat@kovacsdavid.dev")
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::config::BasicDatabaseConfig;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlacementStrategy {
+    RoundRobin,
+    #[default]
+    LeastTenants,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProvisioningClusterConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub database: BasicDatabaseConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ProvisioningConfig {
+    cluster: Option<BasicDatabaseConfig>,
+    #[serde(default)]
+    clusters: Vec<ProvisioningClusterConfig>,
+    placement: Option<PlacementStrategy>,
+    tenant_ssl_mode: Option<String>,
+    tenant_max_pool_size: Option<u32>,
+    password_length: Option<usize>,
+}
+
+impl ProvisioningConfig {
+    pub fn cluster(&self) -> Option<&BasicDatabaseConfig> {
+        self.cluster.as_ref()
+    }
+    pub fn clusters(&self) -> &[ProvisioningClusterConfig] {
+        &self.clusters
+    }
+    pub fn placement(&self) -> PlacementStrategy {
+        self.placement.unwrap_or_default()
+    }
+    pub fn tenant_ssl_mode(&self) -> &str {
+        self.tenant_ssl_mode.as_deref().unwrap_or("disable")
+    }
+    pub fn tenant_max_pool_size(&self) -> Option<u32> {
+        self.tenant_max_pool_size
+    }
+    pub fn password_length(&self) -> usize {
+        self.password_length.unwrap_or(40)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_clusters() {
+        let config: ProvisioningConfig = config::Config::builder()
+            .add_source(config::File::from_str(
+                r#"
+                placement = "round_robin"
+
+                [[clusters]]
+                name = "eu-1"
+                host = "10.0.0.1"
+                port = 5432
+                username = "admin"
+                password = "secret"
+                database = "postgres"
+
+                [[clusters]]
+                name = "eu-2"
+                host = "10.0.0.2"
+                port = 5433
+                username = "admin"
+                password = "secret"
+                database = "postgres"
+                ssl_mode = "require"
+                "#,
+                config::FileFormat::Toml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        assert_eq!(config.placement(), PlacementStrategy::RoundRobin);
+        assert_eq!(config.clusters().len(), 2);
+        assert_eq!(config.clusters()[1].name, "eu-2");
+        assert_eq!(config.clusters()[1].database.port, 5433);
+        assert_eq!(
+            config.clusters()[1].database.ssl_mode.as_deref(),
+            Some("require")
+        );
+    }
+}
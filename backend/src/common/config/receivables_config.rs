@@ -0,0 +1,41 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ReceivablesConfig {
+    summary_interval_hours: Option<u64>,
+    recurring_invoice_interval_hours: Option<u64>,
+    dunning_interval_hours: Option<u64>,
+}
+
+impl ReceivablesConfig {
+    pub fn summary_interval_hours(&self) -> u64 {
+        self.summary_interval_hours.unwrap_or(168)
+    }
+
+    pub fn recurring_invoice_interval_hours(&self) -> u64 {
+        self.recurring_invoice_interval_hours.unwrap_or(1)
+    }
+
+    pub fn dunning_interval_hours(&self) -> u64 {
+        self.dunning_interval_hours.unwrap_or(6)
+    }
+}
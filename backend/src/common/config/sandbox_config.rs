@@ -0,0 +1,77 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SandboxConfig {
+    sandbox_enabled: bool,
+    tenant_id: Option<Uuid>,
+    seed_file: Option<String>,
+    reset_interval_mins: Option<u64>,
+}
+
+impl SandboxConfig {
+    pub fn sandbox_enabled(&self) -> bool {
+        self.sandbox_enabled
+    }
+    pub fn tenant_id(&self) -> Option<Uuid> {
+        self.tenant_id
+    }
+    pub fn seed_file(&self) -> Option<&str> {
+        self.seed_file.as_deref()
+    }
+    pub fn reset_interval_mins(&self) -> u64 {
+        self.reset_interval_mins.unwrap_or(60)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub struct SandboxConfigBuilder {
+        sandbox_enabled: Option<bool>,
+    }
+
+    impl SandboxConfigBuilder {
+        pub fn new() -> Self {
+            SandboxConfigBuilder {
+                sandbox_enabled: None,
+            }
+        }
+        pub fn sandbox_enabled(mut self, sandbox_enabled: bool) -> Self {
+            self.sandbox_enabled = Some(sandbox_enabled);
+            self
+        }
+        pub fn build(self) -> Result<SandboxConfig, String> {
+            Ok(SandboxConfig {
+                sandbox_enabled: self.sandbox_enabled.unwrap_or(false),
+                ..Default::default()
+            })
+        }
+    }
+
+    impl Default for SandboxConfigBuilder {
+        fn default() -> Self {
+            SandboxConfigBuilder::new().sandbox_enabled(false)
+        }
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+
+const DEFAULT_ATTACHMENT_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "application/pdf",
+    "text/plain",
+    "text/csv",
+    "application/zip",
+    "application/msword",
+    "application/vnd.ms-excel",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+];
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct StorageConfig {
+    driver: Option<String>,
+    local_path: Option<String>,
+    s3_endpoint: Option<String>,
+    s3_bucket: Option<String>,
+    s3_region: Option<String>,
+    s3_access_key: Option<String>,
+    s3_secret_key: Option<String>,
+    max_attachment_size_mb: Option<usize>,
+    allowed_attachment_types: Option<Vec<String>>,
+}
+
+impl StorageConfig {
+    pub fn driver(&self) -> &str {
+        self.driver.as_deref().unwrap_or("local")
+    }
+    pub fn local_path(&self) -> &str {
+        self.local_path.as_deref().unwrap_or("storage")
+    }
+    pub fn s3_endpoint(&self) -> &str {
+        self.s3_endpoint
+            .as_deref()
+            .unwrap_or("https://s3.amazonaws.com")
+    }
+    pub fn s3_bucket(&self) -> &str {
+        self.s3_bucket.as_deref().unwrap_or_default()
+    }
+    pub fn s3_region(&self) -> &str {
+        self.s3_region.as_deref().unwrap_or("us-east-1")
+    }
+    pub fn s3_access_key(&self) -> &str {
+        self.s3_access_key.as_deref().unwrap_or_default()
+    }
+    pub fn s3_secret_key(&self) -> &str {
+        self.s3_secret_key.as_deref().unwrap_or_default()
+    }
+    pub fn max_attachment_size(&self) -> usize {
+        self.max_attachment_size_mb.unwrap_or(20) * 1024 * 1024
+    }
+    pub fn allowed_attachment_types(&self) -> Vec<&str> {
+        match &self.allowed_attachment_types {
+            Some(types) => types.iter().map(String::as_str).collect(),
+            None => DEFAULT_ATTACHMENT_TYPES.to_vec(),
+        }
+    }
+}
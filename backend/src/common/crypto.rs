@@ -0,0 +1,276 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::config::EncryptionConfig;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+// Format: enc:v1:<key id>:<nonce + data key wrapped by the key encryption key>:<nonce + ciphertext>
+const PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+static KEYRING: OnceLock<Option<Keyring>> = OnceLock::new();
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CryptoError {
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
+    #[error("Unknown encryption key: {0}")]
+    UnknownKey(String),
+
+    #[error("Encryption is not configured")]
+    NotConfigured,
+
+    #[error("Malformed encrypted value")]
+    Malformed,
+
+    #[error("Encryption failed")]
+    Encryption,
+
+    #[error("Decryption failed")]
+    Decryption,
+}
+
+pub struct Keyring {
+    active_key_id: String,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl Keyring {
+    pub fn from_config(config: &EncryptionConfig) -> Result<Option<Self>, CryptoError> {
+        let Some(active_key_id) = config.active_key_id() else {
+            return Ok(None);
+        };
+        let mut keys = HashMap::new();
+        for (key_id, encoded) in config.keys() {
+            if key_id.is_empty() || key_id.contains(':') {
+                return Err(CryptoError::InvalidKey(key_id.clone()));
+            }
+            let key = STANDARD
+                .decode(encoded)
+                .ok()
+                .filter(|key| key.len() == 32)
+                .ok_or(CryptoError::InvalidKey(key_id.clone()))?;
+            keys.insert(
+                key_id.clone(),
+                Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+            );
+        }
+        if !keys.contains_key(active_key_id) {
+            return Err(CryptoError::UnknownKey(active_key_id.to_string()));
+        }
+        Ok(Some(Self {
+            active_key_id: active_key_id.to_string(),
+            keys,
+        }))
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, CryptoError> {
+        let kek = &self.keys[&self.active_key_id];
+        let data_key = Aes256Gcm::generate_key(&mut OsRng);
+
+        let key_nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let wrapped_key = kek
+            .encrypt(&key_nonce, data_key.as_slice())
+            .map_err(|_| CryptoError::Encryption)?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(&data_key)
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| CryptoError::Encryption)?;
+
+        Ok(format!(
+            "{PREFIX}{}:{}:{}",
+            self.active_key_id,
+            STANDARD.encode([key_nonce.as_slice(), &wrapped_key].concat()),
+            STANDARD.encode([nonce.as_slice(), &ciphertext].concat()),
+        ))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, CryptoError> {
+        let Some(encrypted) = value.strip_prefix(PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let mut parts = encrypted.splitn(3, ':');
+        let (Some(key_id), Some(wrapped_key), Some(ciphertext)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(CryptoError::Malformed);
+        };
+        let kek = self
+            .keys
+            .get(key_id)
+            .ok_or(CryptoError::UnknownKey(key_id.to_string()))?;
+
+        let data_key = open(kek, wrapped_key)?;
+        if data_key.len() != 32 {
+            return Err(CryptoError::Malformed);
+        }
+        let plaintext = open(
+            &Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key)),
+            ciphertext,
+        )?;
+        String::from_utf8(plaintext).map_err(|_| CryptoError::Malformed)
+    }
+
+    pub fn needs_reencryption(&self, value: &str) -> bool {
+        !value.starts_with(&format!("{PREFIX}{}:", self.active_key_id))
+    }
+}
+
+fn open(cipher: &Aes256Gcm, encoded: &str) -> Result<Vec<u8>, CryptoError> {
+    let bytes = STANDARD
+        .decode(encoded)
+        .map_err(|_| CryptoError::Malformed)?;
+    if bytes.len() <= NONCE_LEN {
+        return Err(CryptoError::Malformed);
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::Decryption)
+}
+
+pub fn init_keyring(config: &EncryptionConfig) -> Result<(), CryptoError> {
+    let keyring = Keyring::from_config(config)?;
+    let _ = KEYRING.set(keyring);
+    Ok(())
+}
+
+pub fn keyring() -> Option<&'static Keyring> {
+    KEYRING.get().and_then(Option::as_ref)
+}
+
+// NOTE: without a configured keyring secrets are stored as is (development setups)
+pub fn encrypt_secret(plaintext: &str) -> Result<String, CryptoError> {
+    match keyring() {
+        Some(keyring) => keyring.encrypt(plaintext),
+        None => Ok(plaintext.to_string()),
+    }
+}
+
+pub fn decrypt_secret(value: &str) -> Result<String, CryptoError> {
+    match keyring() {
+        Some(keyring) => keyring.decrypt(value),
+        None if value.starts_with(PREFIX) => Err(CryptoError::NotConfigured),
+        None => Ok(value.to_string()),
+    }
+}
+
+// NOTE: access tokens are random and long, so an unsalted digest is enough to look them up
+// without storing the token itself
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(active_key_id: &str) -> Keyring {
+        let config: EncryptionConfig = serde_json::from_value(serde_json::json!({
+            "active_key_id": active_key_id,
+            "keys": {
+                "k1": STANDARD.encode([1u8; 32]),
+                "k2": STANDARD.encode([2u8; 32]),
+            }
+        }))
+        .unwrap();
+        Keyring::from_config(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_and_rotation() {
+        let old = keyring("k1");
+        let encrypted = old.encrypt("s3cr3t:password").unwrap();
+        assert!(encrypted.starts_with("enc:v1:k1:"));
+        assert_ne!(encrypted, old.encrypt("s3cr3t:password").unwrap());
+        assert_eq!(old.decrypt(&encrypted).unwrap(), "s3cr3t:password");
+
+        let rotated = keyring("k2");
+        assert!(rotated.needs_reencryption(&encrypted));
+        assert_eq!(rotated.decrypt(&encrypted).unwrap(), "s3cr3t:password");
+        let reencrypted = rotated
+            .encrypt(&rotated.decrypt(&encrypted).unwrap())
+            .unwrap();
+        assert!(!rotated.needs_reencryption(&reencrypted));
+    }
+
+    #[test]
+    fn test_plaintext_and_tampering() {
+        let keyring = keyring("k1");
+        assert_eq!(keyring.decrypt("legacy").unwrap(), "legacy");
+        assert!(keyring.needs_reencryption("legacy"));
+
+        let encrypted = keyring.encrypt("password").unwrap();
+        let mut tampered = encrypted.clone();
+        let position = tampered.len() - 3;
+        let replacement = if &tampered[position..position + 1] == "A" {
+            "B"
+        } else {
+            "A"
+        };
+        tampered.replace_range(position..position + 1, replacement);
+        assert!(matches!(
+            keyring.decrypt(&tampered),
+            Err(CryptoError::Decryption) | Err(CryptoError::Malformed)
+        ));
+        assert_eq!(
+            keyring.decrypt(&encrypted.replace("enc:v1:k1:", "enc:v1:k9:")),
+            Err(CryptoError::UnknownKey("k9".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config: EncryptionConfig = serde_json::from_value(serde_json::json!({
+            "active_key_id": "k1",
+            "keys": {"k1": STANDARD.encode([1u8; 16])}
+        }))
+        .unwrap();
+        assert!(matches!(
+            Keyring::from_config(&config),
+            Err(CryptoError::InvalidKey(_))
+        ));
+        assert!(
+            Keyring::from_config(&EncryptionConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_hash_token() {
+        assert_eq!(
+            hash_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_token("abc"), hash_token("abd"));
+    }
+}
@@ -18,15 +18,15 @@
  */
 
 use crate::common::config::{
-    BasicDatabaseConfig, database_config::DatabasePoolSizeProvider,
+    AppConfig, BasicDatabaseConfig, database_config::DatabasePoolSizeProvider,
     database_config::DatabaseUrlProvider,
 };
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::manager::tenants::model::Tenant;
 #[cfg(test)]
 use mockall::automock;
-use sqlx::PgPool;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+use sqlx::{PgConnection, PgPool};
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
@@ -68,22 +68,30 @@ impl PgPoolManager {
             tenant_pools: Arc::new(RwLock::new(HashMap::new())),
         })
     }
-    pub async fn init_tenant_pools(&self, tenants: &[Tenant]) -> anyhow::Result<()> {
+    pub async fn init_tenant_pools(
+        &self,
+        tenants: &[Tenant],
+        config: &AppConfig,
+    ) -> Vec<(Uuid, String)> {
+        let mut failures = Vec::new();
         for tenant in tenants {
-            match BasicDatabaseConfig::try_from(tenant) {
-                Ok(db_config) => {
-                    match self.add_tenant_pool(tenant.id, &db_config).await {
-                        Ok(tenant_id) => {
-                            info!("Tenant pool initialization is successful: {}", &tenant_id)
-                        }
-                        // TODO: Notify the administrator about the failed tenant pool initialization
-                        Err(e) => error!("Tenant pool initialization failed: {}", e),
+            match config.tenant_database(tenant) {
+                Ok(db_config) => match self.add_tenant_pool(tenant.id, &db_config).await {
+                    Ok(tenant_id) => {
+                        info!("Tenant pool initialization is successful: {}", &tenant_id)
                     }
+                    Err(e) => {
+                        error!("Tenant pool initialization failed: {}", e);
+                        failures.push((tenant.id, e.to_string()));
+                    }
+                },
+                Err(e) => {
+                    error!("Error parsing tenant: {}", e);
+                    failures.push((tenant.id, e.to_string()));
                 }
-                Err(e) => error!("Error parsing tenant: {}", e),
             }
         }
-        Ok(())
+        failures
     }
 }
 
@@ -176,6 +184,13 @@ impl ConnectionTester for PgConnectionTester {
     }
 }
 
+pub fn latest_tenant_schema_version() -> Option<i64> {
+    sqlx::migrate!("./migrations/tenant")
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+}
+
 #[cfg_attr(test, automock)]
 pub trait DatabaseMigrator: Send + Sync {
     fn migrate_main_db(&self) -> impl Future<Output = RepositoryResult<()>> + Send;
@@ -210,3 +225,29 @@ impl DatabaseMigrator for PgPoolManager {
         Ok(())
     }
 }
+
+pub(crate) async fn copy_tags(
+    conn: &mut PgConnection,
+    taggable_type: &str,
+    source_id: Uuid,
+    target_id: Uuid,
+    sub: Uuid,
+) -> RepositoryResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO taggings (taggable_id, taggable_type, tag_id, created_by_id)
+        SELECT $1, taggable_type, tag_id, $2
+        FROM taggings
+        WHERE taggable_type = $3
+          AND taggable_id = $4
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(target_id)
+    .bind(sub)
+    .bind(taggable_type)
+    .bind(source_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -145,5 +145,14 @@ pub struct UuidParam {
     pub uuid: Uuid,
 }
 
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DuplicateParams {
+    pub id: Uuid,
+    #[serde(default)]
+    pub include_lines: bool,
+    #[serde(default)]
+    pub include_tags: bool,
+}
+
 #[cfg(test)]
 mod tests {}
@@ -0,0 +1,697 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use handlebars::{Handlebars, RenderError, no_escape};
+use lettre::Message;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MessageBuilder, MultiPart};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailTemplate {
+    EmailVerification,
+    ForgottenPassword,
+    TenantIncident,
+    ReceivablesSummary,
+    LowStockDigest,
+    InvoiceIssued,
+    InvoiceDelivery,
+    InvoiceDeliveryEn,
+    DunningReminder,
+    DunningSecondReminder,
+    DunningFinalNotice,
+    TaskAssigned,
+    TaskDueReminder,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: ContentType,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+impl RenderedEmail {
+    pub fn into_message(self, from: Mailbox, to: Mailbox) -> Result<Message, lettre::error::Error> {
+        Message::builder()
+            .from(from)
+            .to(to)
+            .subject(self.subject)
+            .multipart(MultiPart::alternative_plain_html(self.text, self.html))
+    }
+
+    /// Builds the message on a prepared builder (addresses, reply-to) with the attachments
+    /// after the text and html alternatives
+    pub fn into_message_with_attachments(
+        self,
+        builder: MessageBuilder,
+        attachments: Vec<EmailAttachment>,
+    ) -> Result<Message, lettre::error::Error> {
+        let body = attachments.into_iter().fold(
+            MultiPart::mixed().multipart(MultiPart::alternative_plain_html(self.text, self.html)),
+            |body, attachment| {
+                body.singlepart(
+                    Attachment::new(attachment.filename)
+                        .body(attachment.data, attachment.content_type),
+                )
+            },
+        );
+        builder.subject(self.subject).multipart(body)
+    }
+}
+
+impl EmailTemplate {
+    pub const ALL: [EmailTemplate; 13] = [
+        EmailTemplate::EmailVerification,
+        EmailTemplate::ForgottenPassword,
+        EmailTemplate::TenantIncident,
+        EmailTemplate::ReceivablesSummary,
+        EmailTemplate::LowStockDigest,
+        EmailTemplate::InvoiceIssued,
+        EmailTemplate::InvoiceDelivery,
+        EmailTemplate::InvoiceDeliveryEn,
+        EmailTemplate::DunningReminder,
+        EmailTemplate::DunningSecondReminder,
+        EmailTemplate::DunningFinalNotice,
+        EmailTemplate::TaskAssigned,
+        EmailTemplate::TaskDueReminder,
+    ];
+
+    pub fn subject(&self) -> &'static str {
+        match self {
+            EmailTemplate::EmailVerification => "Kérlek, erősítsd meg az e-mail címedet!",
+            EmailTemplate::ForgottenPassword => "Elfelejtett jelszó",
+            EmailTemplate::TenantIncident => "Tenant incident: {{incident_type}}",
+            EmailTemplate::ReceivablesSummary => "Kintlévőség összesítő ({{as_of}})",
+            EmailTemplate::LowStockDigest => "Alacsony készlet ({{as_of}})",
+            EmailTemplate::InvoiceIssued => "Számla: {{document_number}}",
+            EmailTemplate::InvoiceDelivery => "{{company_name}} számla: {{document_number}}",
+            EmailTemplate::InvoiceDeliveryEn => "Invoice {{document_number}} from {{company_name}}",
+            EmailTemplate::DunningReminder => "Fizetési emlékeztető: {{document_number}}",
+            EmailTemplate::DunningSecondReminder => {
+                "Ismételt fizetési felszólítás: {{document_number}}"
+            }
+            EmailTemplate::DunningFinalNotice => "Utolsó fizetési felszólítás: {{document_number}}",
+            EmailTemplate::TaskAssigned => "Feladat hozzárendelés: {{task}}",
+            EmailTemplate::TaskDueReminder => "Közelgő határidő: {{task}} ({{due_date}})",
+        }
+    }
+
+    pub fn html(&self) -> &'static str {
+        match self {
+            EmailTemplate::EmailVerification => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{last_name}} {{first_name}}!
+                </p>
+                <p>
+                    Kérlek a következő hivatkozásra kattintva erősítsd meg az e-mail címedet!<br>
+                    <a href="{{verification_link}}">{{verification_link}}</a>
+                </p>
+                "##
+            }
+            EmailTemplate::ForgottenPassword => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{last_name}} {{first_name}}!
+                </p>
+                <p>
+                    A következő hivatkozásra kattintva megváltoztathatod a fiókodhoz tartozó jelszavadat.<br>
+                    Ha nem te kérted a jelszó emlékeztető e-mail-t, ne használd a hivatkozást
+                    és értesítd a rendszergazdát a következő e-mail címen: {{admin_email}}!<br>
+                    <a href="{{forgotten_password_link}}">{{forgotten_password_link}}</a>
+                </p>
+                "##
+            }
+            EmailTemplate::TenantIncident => {
+                r##"
+                <p>Dear Admin!</p>
+                <p>An incident has been recorded for tenant {{tenant_id}}.</p>
+                <p>
+                    Type: {{incident_type}}<br>
+                    Time: {{created_at}}<br>
+                    Message: {{message}}
+                </p>
+                "##
+            }
+            EmailTemplate::ReceivablesSummary => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{sales_rep_name}}!
+                </p>
+                <p>A hozzád rendelt ügyfelek nyitott kintlévőségei {{as_of}} napon:</p>
+                <table cellpadding="4" style="border-collapse: collapse;">
+                    <tr>
+                        <th>Pénznem</th><th>Nem lejárt</th><th>1-30 nap</th><th>31-60 nap</th>
+                        <th>61-90 nap</th><th>90 nap felett</th><th>Lejárt összesen</th>
+                    </tr>
+                    {{#each rows}}
+                    <tr>
+                        <td>{{currency_code}}</td><td>{{not_due}}</td><td>{{days_1_30}}</td>
+                        <td>{{days_31_60}}</td><td>{{days_61_90}}</td><td>{{days_over_90}}</td>
+                        <td>{{overdue_total}} ({{overdue_count}} db)</td>
+                    </tr>
+                    {{/each}}
+                </table>
+                "##
+            }
+            EmailTemplate::LowStockDigest => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{name}}!
+                </p>
+                <p>A következő termékek készlete elérte a minimális szintet ({{as_of}}):</p>
+                {{#each warehouses}}
+                <p style="font-weight: bold;">{{warehouse}}</p>
+                <table cellpadding="4" style="border-collapse: collapse;">
+                    <tr>
+                        <th>Termék</th><th>Elérhető</th><th>Minimum</th>
+                    </tr>
+                    {{#each items}}
+                    <tr>
+                        <td>{{product}}</td><td>{{quantity_available}}</td><td>{{minimum_stock}}</td>
+                    </tr>
+                    {{/each}}
+                </table>
+                {{/each}}
+                "##
+            }
+            EmailTemplate::InvoiceIssued => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{customer_name}}!
+                </p>
+                <p>
+                    Tájékoztatjuk, hogy {{issue_date}} kelettel kiállítottuk a(z)
+                    {{document_number}} sorszámú számlát.
+                </p>
+                <table cellpadding="4" style="border-collapse: collapse;">
+                    <tr>
+                        <th>Tétel</th><th>Mennyiség</th><th>Bruttó összeg</th>
+                    </tr>
+                    {{#each lines}}
+                    <tr>
+                        <td>{{description}}</td><td>{{quantity}}</td><td>{{gross_amount}}</td>
+                    </tr>
+                    {{/each}}
+                </table>
+                <p>
+                    Végösszeg: {{amount}} {{currency_code}}<br>
+                    Fizetési határidő: {{due_date}}
+                </p>
+                "##
+            }
+            EmailTemplate::InvoiceDelivery => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Tisztelt {{customer_name}}!
+                </p>
+                <p>
+                    Mellékelten küldjük a(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánkat.
+                </p>
+                <p>
+                    Fizetendő: {{open_balance}}<br>
+                    Fizetési határidő: {{due_date}}
+                    {{#if bank_account}}<br>Bankszámlaszám: {{bank_account}}{{/if}}
+                </p>
+                {{#if payment_url}}
+                <p>
+                    A számlát bankkártyával online is kifizetheti:<br>
+                    <a href="{{payment_url}}">{{payment_url}}</a>
+                </p>
+                {{/if}}
+                <p>
+                    Üdvözlettel:<br>
+                    {{company_name}}
+                </p>
+                "##
+            }
+            EmailTemplate::InvoiceDeliveryEn => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Dear {{customer_name}},
+                </p>
+                <p>
+                    Please find attached our invoice {{document_number}} issued on {{issue_date}}.
+                </p>
+                <p>
+                    Amount due: {{open_balance}}<br>
+                    Due date: {{due_date}}
+                    {{#if bank_account}}<br>Bank account: {{bank_account}}{{/if}}
+                </p>
+                {{#if payment_url}}
+                <p>
+                    You can also pay the invoice online by card:<br>
+                    <a href="{{payment_url}}">{{payment_url}}</a>
+                </p>
+                {{/if}}
+                <p>
+                    Kind regards,<br>
+                    {{company_name}}
+                </p>
+                "##
+            }
+            EmailTemplate::DunningReminder => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Tisztelt {{customer_name}}!
+                </p>
+                <p>
+                    Szeretnénk emlékeztetni, hogy a(z) {{document_number}} sorszámú, {{issue_date}} kelt
+                    számlánk fizetési határideje ({{due_date}}) {{days_overdue}} napja lejárt.
+                    Amennyiben a számlát időközben kiegyenlítette, kérjük, tekintse levelünket tárgytalannak.
+                </p>
+                <p>
+                    Fizetendő: {{open_balance}}
+                    {{#if bank_account}}<br>Bankszámlaszám: {{bank_account}}{{/if}}
+                </p>
+                {{#if payment_url}}
+                <p>
+                    A számlát bankkártyával online is kifizetheti:<br>
+                    <a href="{{payment_url}}">{{payment_url}}</a>
+                </p>
+                {{/if}}
+                <p>
+                    Üdvözlettel:<br>
+                    {{company_name}}
+                </p>
+                "##
+            }
+            EmailTemplate::DunningSecondReminder => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Tisztelt {{customer_name}}!
+                </p>
+                <p>
+                    Korábbi emlékeztetőnk ellenére a(z) {{document_number}} sorszámú, {{issue_date}} kelt
+                    számlánk {{due_date}} fizetési határideje óta {{days_overdue}} nap telt el, és a számla
+                    továbbra is kiegyenlítetlen. Kérjük, a tartozást haladéktalanul rendezze.
+                </p>
+                <p>
+                    Fizetendő: {{open_balance}}
+                    {{#if bank_account}}<br>Bankszámlaszám: {{bank_account}}{{/if}}
+                </p>
+                {{#if payment_url}}
+                <p>
+                    A számlát bankkártyával online is kifizetheti:<br>
+                    <a href="{{payment_url}}">{{payment_url}}</a>
+                </p>
+                {{/if}}
+                <p>
+                    Üdvözlettel:<br>
+                    {{company_name}}
+                </p>
+                "##
+            }
+            EmailTemplate::DunningFinalNotice => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Tisztelt {{customer_name}}!
+                </p>
+                <p>
+                    A(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánk fizetési határideje
+                    ({{due_date}}) {{days_overdue}} napja lejárt, ismételt felszólításaink ellenére sem
+                    került kiegyenlítésre. Ez az utolsó felszólításunk: amennyiben a tartozás 8 napon belül
+                    nem kerül rendezésre, a követelés érvényesítése érdekében további lépéseket teszünk.
+                </p>
+                <p>
+                    Fizetendő: {{open_balance}}
+                    {{#if bank_account}}<br>Bankszámlaszám: {{bank_account}}{{/if}}
+                </p>
+                {{#if payment_url}}
+                <p>
+                    A számlát bankkártyával online is kifizetheti:<br>
+                    <a href="{{payment_url}}">{{payment_url}}</a>
+                </p>
+                {{/if}}
+                <p>
+                    Üdvözlettel:<br>
+                    {{company_name}}
+                </p>
+                "##
+            }
+            EmailTemplate::TaskAssigned => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{name}}!
+                </p>
+                <p>
+                    {{#if reassigned}}Átvetted{{else}}Hozzád rendelték{{/if}} a(z) {{worksheet}}
+                    munkalap következő feladatát: {{task}}
+                </p>
+                <p>
+                    {{#if description}}Leírás: {{description}}<br>{{/if}}
+                    {{#if priority}}Prioritás: {{priority}}<br>{{/if}}
+                    Határidő: {{#if due_date}}{{due_date}}{{else}}nincs megadva{{/if}}
+                </p>
+                "##
+            }
+            EmailTemplate::TaskDueReminder => {
+                r##"
+                <p style="font-weight: bold; margin-bottom: 25px;">
+                    Kedves {{name}}!
+                </p>
+                <p>
+                    Emlékeztetünk, hogy a(z) {{worksheet}} munkalap hozzád rendelt feladatának
+                    határideje közeleg: {{task}}
+                </p>
+                <p>
+                    {{#if description}}Leírás: {{description}}<br>{{/if}}
+                    Határidő: {{due_date}}
+                </p>
+                "##
+            }
+        }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            EmailTemplate::EmailVerification => {
+                r##"Kedves {{last_name}} {{first_name}}!
+
+Kérlek a következő hivatkozásra kattintva erősítsd meg az e-mail címedet!
+{{verification_link}}
+"##
+            }
+            EmailTemplate::ForgottenPassword => {
+                r##"Kedves {{last_name}} {{first_name}}!
+
+A következő hivatkozásra kattintva megváltoztathatod a fiókodhoz tartozó jelszavadat.
+Ha nem te kérted a jelszó emlékeztető e-mail-t, ne használd a hivatkozást és értesítd a rendszergazdát a következő e-mail címen: {{admin_email}}!
+{{forgotten_password_link}}
+"##
+            }
+            EmailTemplate::TenantIncident => {
+                r##"Dear Admin!
+
+An incident has been recorded for tenant {{tenant_id}}.
+
+Type: {{incident_type}}
+Time: {{created_at}}
+Message: {{message}}
+"##
+            }
+            EmailTemplate::ReceivablesSummary => {
+                r##"Kedves {{sales_rep_name}}!
+
+A hozzád rendelt ügyfelek nyitott kintlévőségei {{as_of}} napon:
+{{#each rows}}
+{{currency_code}}: nem lejárt {{not_due}}, 1-30 nap {{days_1_30}}, 31-60 nap {{days_31_60}}, 61-90 nap {{days_61_90}}, 90 nap felett {{days_over_90}}, lejárt összesen {{overdue_total}} ({{overdue_count}} db)
+{{/each}}
+"##
+            }
+            EmailTemplate::LowStockDigest => {
+                r##"Kedves {{name}}!
+
+A következő termékek készlete elérte a minimális szintet ({{as_of}}):
+{{#each warehouses}}
+
+{{warehouse}}
+{{#each items}}
+- {{product}}: elérhető {{quantity_available}}, minimum {{minimum_stock}}
+{{/each}}
+{{/each}}
+"##
+            }
+            EmailTemplate::InvoiceIssued => {
+                r##"Kedves {{customer_name}}!
+
+Tájékoztatjuk, hogy {{issue_date}} kelettel kiállítottuk a(z) {{document_number}} sorszámú számlát.
+{{#each lines}}
+- {{description}}: {{quantity}}, {{gross_amount}}
+{{/each}}
+
+Végösszeg: {{amount}} {{currency_code}}
+Fizetési határidő: {{due_date}}
+"##
+            }
+            EmailTemplate::InvoiceDelivery => {
+                r##"Tisztelt {{customer_name}}!
+
+Mellékelten küldjük a(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánkat.
+
+Fizetendő: {{open_balance}}
+Fizetési határidő: {{due_date}}
+{{#if bank_account}}Bankszámlaszám: {{bank_account}}
+{{/if}}
+{{#if payment_url}}
+A számlát bankkártyával online is kifizetheti:
+{{payment_url}}
+{{/if}}
+Üdvözlettel:
+{{company_name}}
+"##
+            }
+            EmailTemplate::InvoiceDeliveryEn => {
+                r##"Dear {{customer_name}},
+
+Please find attached our invoice {{document_number}} issued on {{issue_date}}.
+
+Amount due: {{open_balance}}
+Due date: {{due_date}}
+{{#if bank_account}}Bank account: {{bank_account}}
+{{/if}}
+{{#if payment_url}}
+You can also pay the invoice online by card:
+{{payment_url}}
+{{/if}}
+Kind regards,
+{{company_name}}
+"##
+            }
+            EmailTemplate::DunningReminder => {
+                r##"Tisztelt {{customer_name}}!
+
+Szeretnénk emlékeztetni, hogy a(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánk fizetési határideje ({{due_date}}) {{days_overdue}} napja lejárt. Amennyiben a számlát időközben kiegyenlítette, kérjük, tekintse levelünket tárgytalannak.
+
+Fizetendő: {{open_balance}}
+{{#if bank_account}}Bankszámlaszám: {{bank_account}}
+{{/if}}
+{{#if payment_url}}
+A számlát bankkártyával online is kifizetheti:
+{{payment_url}}
+{{/if}}
+Üdvözlettel:
+{{company_name}}
+"##
+            }
+            EmailTemplate::DunningSecondReminder => {
+                r##"Tisztelt {{customer_name}}!
+
+Korábbi emlékeztetőnk ellenére a(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánk {{due_date}} fizetési határideje óta {{days_overdue}} nap telt el, és a számla továbbra is kiegyenlítetlen. Kérjük, a tartozást haladéktalanul rendezze.
+
+Fizetendő: {{open_balance}}
+{{#if bank_account}}Bankszámlaszám: {{bank_account}}
+{{/if}}
+{{#if payment_url}}
+A számlát bankkártyával online is kifizetheti:
+{{payment_url}}
+{{/if}}
+Üdvözlettel:
+{{company_name}}
+"##
+            }
+            EmailTemplate::DunningFinalNotice => {
+                r##"Tisztelt {{customer_name}}!
+
+A(z) {{document_number}} sorszámú, {{issue_date}} kelt számlánk fizetési határideje ({{due_date}}) {{days_overdue}} napja lejárt, ismételt felszólításaink ellenére sem került kiegyenlítésre. Ez az utolsó felszólításunk: amennyiben a tartozás 8 napon belül nem kerül rendezésre, a követelés érvényesítése érdekében további lépéseket teszünk.
+
+Fizetendő: {{open_balance}}
+{{#if bank_account}}Bankszámlaszám: {{bank_account}}
+{{/if}}
+{{#if payment_url}}
+A számlát bankkártyával online is kifizetheti:
+{{payment_url}}
+{{/if}}
+Üdvözlettel:
+{{company_name}}
+"##
+            }
+            EmailTemplate::TaskAssigned => {
+                r##"Kedves {{name}}!
+
+{{#if reassigned}}Átvetted{{else}}Hozzád rendelték{{/if}} a(z) {{worksheet}} munkalap következő feladatát: {{task}}
+
+{{#if description}}Leírás: {{description}}
+{{/if}}
+{{#if priority}}Prioritás: {{priority}}
+{{/if}}
+Határidő: {{#if due_date}}{{due_date}}{{else}}nincs megadva{{/if}}
+"##
+            }
+            EmailTemplate::TaskDueReminder => {
+                r##"Kedves {{name}}!
+
+Emlékeztetünk, hogy a(z) {{worksheet}} munkalap hozzád rendelt feladatának határideje közeleg: {{task}}
+
+{{#if description}}Leírás: {{description}}
+{{/if}}
+Határidő: {{due_date}}
+"##
+            }
+        }
+    }
+
+    pub fn sample_data(&self) -> Value {
+        match self {
+            EmailTemplate::EmailVerification => json!({
+                "last_name": "Minta",
+                "first_name": "János",
+                "verification_link": "https://example.com/email_megerosites/00000000-0000-0000-0000-000000000000",
+            }),
+            EmailTemplate::ForgottenPassword => json!({
+                "last_name": "Minta",
+                "first_name": "János",
+                "forgotten_password_link": "https://example.com/elfelejtett_jelszo/00000000-0000-0000-0000-000000000000",
+                "admin_email": "admin@example.com",
+            }),
+            EmailTemplate::TenantIncident => json!({
+                "tenant_id": "00000000-0000-0000-0000-000000000000",
+                "incident_type": "pool_init_failed",
+                "created_at": "2026-01-01T00:00:00Z",
+                "message": "connection refused",
+            }),
+            EmailTemplate::ReceivablesSummary => json!({
+                "sales_rep_name": "Minta János",
+                "as_of": "2026-01-01",
+                "rows": [{
+                    "currency_code": "HUF",
+                    "not_due": "150000.00",
+                    "days_1_30": "40000.00",
+                    "days_31_60": "0.00",
+                    "days_61_90": "12500.00",
+                    "days_over_90": "0.00",
+                    "overdue_total": "52500.00",
+                    "overdue_count": 3,
+                }],
+            }),
+            EmailTemplate::LowStockDigest => json!({
+                "name": "Minta János",
+                "as_of": "2026-01-01",
+                "warehouses": [{
+                    "warehouse": "Központi raktár",
+                    "items": [{
+                        "product": "Csavar M6",
+                        "quantity_available": "12.00",
+                        "minimum_stock": "50.00",
+                    }],
+                }],
+            }),
+            EmailTemplate::InvoiceIssued => json!({
+                "customer_name": "Minta Kft.",
+                "document_number": "SZ-2026-00001",
+                "issue_date": "2026-01-01",
+                "due_date": "2026-01-09",
+                "currency_code": "HUF",
+                "amount": "31750.00",
+                "lines": [{
+                    "description": "Rendszerüzemeltetés",
+                    "quantity": "1.00",
+                    "gross_amount": "31750.00",
+                }],
+            }),
+            EmailTemplate::InvoiceDelivery | EmailTemplate::InvoiceDeliveryEn => json!({
+                "company_name": "Obvia Kft.",
+                "customer_name": "Minta Kft.",
+                "document_number": "SZ-2026-00001",
+                "issue_date": "2026. 01. 01.",
+                "due_date": "2026. 01. 09.",
+                "open_balance": "31 750 Ft",
+                "bank_account": "11111111-22222222-33333333",
+                "payment_url": "https://example.com/api/payment_links/pay?tenant_id=00000000-0000-0000-0000-000000000000&receivable_id=00000000-0000-0000-0000-000000000000",
+            }),
+            EmailTemplate::DunningReminder
+            | EmailTemplate::DunningSecondReminder
+            | EmailTemplate::DunningFinalNotice => json!({
+                "company_name": "Obvia Kft.",
+                "customer_name": "Minta Kft.",
+                "document_number": "SZ-2026-00001",
+                "issue_date": "2026. 01. 01.",
+                "due_date": "2026. 01. 09.",
+                "days_overdue": 7,
+                "open_balance": "31 750 Ft",
+                "bank_account": "11111111-22222222-33333333",
+                "payment_url": "https://example.com/api/payment_links/pay?tenant_id=00000000-0000-0000-0000-000000000000&receivable_id=00000000-0000-0000-0000-000000000000",
+            }),
+            EmailTemplate::TaskAssigned | EmailTemplate::TaskDueReminder => json!({
+                "name": "Minta János",
+                "task": "Klíma karbantartás",
+                "worksheet": "Irodaház üzemeltetés",
+                "description": "Szűrők cseréje a 2. emeleten",
+                "priority": "high",
+                "due_date": "2026. 01. 09. 16:00",
+                "reassigned": false,
+            }),
+        }
+    }
+
+    pub fn render(&self, data: &Value) -> Result<RenderedEmail, RenderError> {
+        render_email(self.subject(), self.html(), self.text(), data)
+    }
+}
+
+pub fn render_email(
+    subject: &str,
+    html: &str,
+    text: &str,
+    data: &Value,
+) -> Result<RenderedEmail, RenderError> {
+    let html_renderer = Handlebars::new();
+    let mut plain_renderer = Handlebars::new();
+    plain_renderer.register_escape_fn(no_escape);
+
+    Ok(RenderedEmail {
+        subject: plain_renderer.render_template(subject, data)?,
+        html: html_renderer.render_template(html, data)?,
+        text: plain_renderer.render_template(text, data)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_templates_render_sample_data() {
+        for template in EmailTemplate::ALL {
+            let rendered = template.render(&template.sample_data()).unwrap();
+            assert!(!rendered.subject.contains("{{"));
+            assert!(!rendered.html.contains("{{"));
+            assert!(!rendered.text.contains("{{"));
+        }
+    }
+
+    #[test]
+    fn test_text_part_is_not_html_escaped() {
+        let rendered = EmailTemplate::TenantIncident
+            .render(&json!({"message": "a < b & c"}))
+            .unwrap();
+        assert!(rendered.text.contains("Message: a < b & c"));
+        assert!(rendered.html.contains("Message: a &lt; b &amp; c"));
+    }
+}
@@ -21,6 +21,7 @@ use std::num::TryFromIntError;
 use thiserror::Error;
 
 use crate::common::{
+    crypto::CryptoError,
     error::v2::{AppError, AppErrorVisibility},
     value_object::ValueObjectError,
 };
@@ -53,6 +54,9 @@ pub enum RepositoryError {
 
     #[error("Tenant pool not found")]
     TenantPoolNotFound,
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
 }
 
 impl From<ValueObjectError> for RepositoryError {
@@ -80,6 +84,36 @@ impl RepositoryError {
         }
         false
     }
+
+    pub fn is_foreign_key_violation(&self) -> bool {
+        if let RepositoryError::Database(sqlxe) = self
+            && let Error::Database(database_error) = sqlxe
+            && database_error.is_foreign_key_violation()
+        {
+            return true;
+        }
+        false
+    }
+
+    pub fn is_negative_stock_violation(&self) -> bool {
+        if let RepositoryError::Database(sqlxe) = self
+            && let Error::Database(database_error) = sqlxe
+            && database_error.constraint() == Some("inventory_negative_stock")
+        {
+            return true;
+        }
+        false
+    }
+
+    pub fn is_project_archived_violation(&self) -> bool {
+        if let RepositoryError::Database(sqlxe) = self
+            && let Error::Database(database_error) = sqlxe
+            && database_error.constraint() == Some("project_archived")
+        {
+            return true;
+        }
+        false
+    }
 }
 
 pub type RepositoryResult<T> = Result<T, RepositoryError>;
@@ -124,6 +158,7 @@ pub mod v2 {
 
     use crate::common::{
         BaseModule, ConfigProvider, MailTransporter, config::AppConfig, dto::ErrorResponse,
+        error_code::ErrorCode,
     };
 
     #[derive(Debug, PartialEq)]
@@ -244,14 +279,25 @@ pub mod v2 {
     impl IntoResponse for AppError {
         fn into_response(self) -> Response {
             match self.visibility {
-                AppErrorVisibility::UserFacing => ErrorResponse {
-                    status_code: self.http_status,
-                    error: self.json,
+                AppErrorVisibility::UserFacing => {
+                    let mut error = self.json;
+                    if let Some(body) = error.as_object_mut()
+                        && !body.contains_key("code")
+                    {
+                        let error_code =
+                            ErrorCode::from_status(self.http_status, body.contains_key("fields"));
+                        body.insert("code".to_string(), json!(error_code.code()));
+                    }
+                    ErrorResponse {
+                        status_code: self.http_status,
+                        error,
+                    }
+                    .into_response()
                 }
-                .into_response(),
                 AppErrorVisibility::Internal => ErrorResponse {
                     status_code: StatusCode::INTERNAL_SERVER_ERROR,
                     error: json!({
+                        "code": ErrorCode::Internal.code(),
                         "message":
                         "Váratlan hiba történt a feldolgozás során"
                     }),
@@ -260,4 +306,44 @@ pub mod v2 {
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::common::handler::tests::extract_json_response;
+
+        #[tokio::test]
+        async fn test_into_response_adds_missing_code() {
+            let response = AppError::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            )
+            .into_response();
+
+            assert_eq!(
+                extract_json_response(response).await,
+                json!({"error": {"code": "NOT_FOUND", "message": "Nem található"}})
+            );
+        }
+
+        #[tokio::test]
+        async fn test_into_response_keeps_explicit_code() {
+            let response = AppError::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"code": ErrorCode::InsufficientStock.code(), "message": "Nincs készlet"}),
+            )
+            .into_response();
+
+            assert_eq!(
+                extract_json_response(response).await["error"]["code"],
+                ErrorCode::InsufficientStock.code()
+            );
+        }
+    }
 }
@@ -0,0 +1,274 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use axum::http::StatusCode;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unauthorized,
+    Forbidden,
+    SandboxRestricted,
+    NotFound,
+    BadRequest,
+    ValidationFailed,
+    UnprocessableEntry,
+    Conflict,
+    QuotaExceeded,
+    InsufficientStock,
+    CreditLimitExceeded,
+    UpstreamUnavailable,
+    Internal,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LocalizedText {
+    pub hu: &'static str,
+    pub en: &'static str,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ErrorCodeEntry {
+    pub code: &'static str,
+    pub http_status: u16,
+    pub description: LocalizedText,
+    pub remediation: LocalizedText,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 13] = [
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::SandboxRestricted,
+        ErrorCode::NotFound,
+        ErrorCode::BadRequest,
+        ErrorCode::ValidationFailed,
+        ErrorCode::UnprocessableEntry,
+        ErrorCode::Conflict,
+        ErrorCode::QuotaExceeded,
+        ErrorCode::InsufficientStock,
+        ErrorCode::CreditLimitExceeded,
+        ErrorCode::UpstreamUnavailable,
+        ErrorCode::Internal,
+    ];
+
+    /// The code of an error response which did not set one explicitly
+    pub fn from_status(http_status: StatusCode, has_fields: bool) -> Self {
+        match http_status {
+            StatusCode::UNAUTHORIZED => ErrorCode::Unauthorized,
+            StatusCode::FORBIDDEN => ErrorCode::Forbidden,
+            StatusCode::NOT_FOUND => ErrorCode::NotFound,
+            StatusCode::BAD_REQUEST => ErrorCode::BadRequest,
+            StatusCode::UNPROCESSABLE_ENTITY if has_fields => ErrorCode::ValidationFailed,
+            StatusCode::CONFLICT => ErrorCode::Conflict,
+            StatusCode::PAYMENT_REQUIRED => ErrorCode::QuotaExceeded,
+            StatusCode::BAD_GATEWAY => ErrorCode::UpstreamUnavailable,
+            status if status.is_client_error() => ErrorCode::UnprocessableEntry,
+            _ => ErrorCode::Internal,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::SandboxRestricted => "SANDBOX_RESTRICTED",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::ValidationFailed => "VALIDATION_FAILED",
+            ErrorCode::UnprocessableEntry => "UNPROCESSABLE_ENTRY",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ErrorCode::InsufficientStock => "INSUFFICIENT_STOCK",
+            ErrorCode::CreditLimitExceeded => "CREDIT_LIMIT_EXCEEDED",
+            ErrorCode::UpstreamUnavailable => "UPSTREAM_UNAVAILABLE",
+            ErrorCode::Internal => "INTERNAL",
+        }
+    }
+
+    pub fn http_status(&self) -> StatusCode {
+        match self {
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden | ErrorCode::SandboxRestricted => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::BadRequest => StatusCode::BAD_REQUEST,
+            ErrorCode::ValidationFailed | ErrorCode::UnprocessableEntry => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ErrorCode::Conflict | ErrorCode::InsufficientStock | ErrorCode::CreditLimitExceeded => {
+                StatusCode::CONFLICT
+            }
+            ErrorCode::QuotaExceeded => StatusCode::PAYMENT_REQUIRED,
+            ErrorCode::UpstreamUnavailable => StatusCode::BAD_GATEWAY,
+            ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn description(&self) -> LocalizedText {
+        match self {
+            ErrorCode::Unauthorized => LocalizedText {
+                hu: "Hozzáférés megtagadva!",
+                en: "Access denied.",
+            },
+            ErrorCode::Forbidden => LocalizedText {
+                hu: "A művelet nem engedélyezett.",
+                en: "The operation is not permitted.",
+            },
+            ErrorCode::SandboxRestricted => LocalizedText {
+                hu: "Ez a művelet a demó környezetben nem engedélyezett!",
+                en: "This operation is disabled in the demo environment.",
+            },
+            ErrorCode::NotFound => LocalizedText {
+                hu: "Nem található",
+                en: "Not found.",
+            },
+            ErrorCode::BadRequest => LocalizedText {
+                hu: "A kérés formátuma hibás.",
+                en: "The request is malformed.",
+            },
+            ErrorCode::ValidationFailed => LocalizedText {
+                hu: "Kérjük ellenőrizze a hibás mezőket!",
+                en: "Some fields are invalid.",
+            },
+            ErrorCode::UnprocessableEntry => LocalizedText {
+                hu: "Hiba történt az adatok feldolgozása során.",
+                en: "The request could not be processed.",
+            },
+            ErrorCode::Conflict => LocalizedText {
+                hu: "A művelet ütközik a meglévő adatokkal.",
+                en: "The operation conflicts with existing data.",
+            },
+            ErrorCode::QuotaExceeded => LocalizedText {
+                hu: "Elérte az előfizetésében engedélyezett korlátot.",
+                en: "The limit of your plan has been reached.",
+            },
+            ErrorCode::InsufficientStock => LocalizedText {
+                hu: "Nincs elegendő szabad készlet.",
+                en: "There is not enough available stock.",
+            },
+            ErrorCode::CreditLimitExceeded => LocalizedText {
+                hu: "A bizonylat túllépi a vevő hitelkeretét.",
+                en: "The document exceeds the credit limit of the customer.",
+            },
+            ErrorCode::UpstreamUnavailable => LocalizedText {
+                hu: "A külső szolgáltatás nem válaszolt megfelelően.",
+                en: "The external service did not respond properly.",
+            },
+            ErrorCode::Internal => LocalizedText {
+                hu: "Váratlan hiba történt a feldolgozás során",
+                en: "An unexpected error occurred.",
+            },
+        }
+    }
+
+    pub fn remediation(&self) -> LocalizedText {
+        match self {
+            ErrorCode::Unauthorized => LocalizedText {
+                hu: "Jelentkezzen be újra, vagy válasszon olyan szervezeti egységet, amelyhez hozzáféréssel rendelkezik.",
+                en: "Sign in again or select a tenant you have access to.",
+            },
+            ErrorCode::Forbidden => LocalizedText {
+                hu: "Kérjen jogosultságot a szervezeti egység tulajdonosától.",
+                en: "Ask the owner of the tenant for permission.",
+            },
+            ErrorCode::SandboxRestricted => LocalizedText {
+                hu: "Próbálja ki a funkciót egy saját telepítésen vagy előfizetéssel.",
+                en: "Try this feature on your own installation or subscription.",
+            },
+            ErrorCode::NotFound => LocalizedText {
+                hu: "Ellenőrizze az azonosítót, az elem törölve lehetett.",
+                en: "Check the identifier, the item may have been deleted.",
+            },
+            ErrorCode::BadRequest => LocalizedText {
+                hu: "Ellenőrizze, hogy a kérés törzse érvényes JSON, majd küldje el újra.",
+                en: "Check that the request body is valid JSON and resend it.",
+            },
+            ErrorCode::ValidationFailed => LocalizedText {
+                hu: "Javítsa a \"fields\" objektumban jelzett mezőket, majd küldje el újra a kérést.",
+                en: "Fix the fields listed in the \"fields\" object and resend the request.",
+            },
+            ErrorCode::UnprocessableEntry => LocalizedText {
+                hu: "Ellenőrizze a kapcsolódó adatokat, majd próbálja újra.",
+                en: "Check the related data and try again.",
+            },
+            ErrorCode::Conflict => LocalizedText {
+                hu: "Töltse be újra az adatokat, majd próbálja újra.",
+                en: "Reload the data and try again.",
+            },
+            ErrorCode::QuotaExceeded => LocalizedText {
+                hu: "Töröljön nem használt elemeket, vagy váltson nagyobb csomagra.",
+                en: "Delete unused items or upgrade your plan.",
+            },
+            ErrorCode::InsufficientStock => LocalizedText {
+                hu: "Csökkentse a mennyiséget, vagy várja meg a készlet feltöltését.",
+                en: "Reduce the quantity or wait until the stock is replenished.",
+            },
+            ErrorCode::CreditLimitExceeded => LocalizedText {
+                hu: "Rögzítse a vevő befizetéseit vagy emelje a hitelkeretét. Figyelmeztetés esetén az \"acknowledge_credit_limit\" mezővel megerősítheti a műveletet.",
+                en: "Record the payments of the customer or raise the credit limit. In case of a warning, confirm the operation with the \"acknowledge_credit_limit\" field.",
+            },
+            ErrorCode::UpstreamUnavailable => LocalizedText {
+                hu: "Próbálja újra később, vagy ellenőrizze a külső szolgáltatás beállításait.",
+                en: "Try again later or check the settings of the external service.",
+            },
+            ErrorCode::Internal => LocalizedText {
+                hu: "Próbálja újra később. Az adminisztrátor értesítést kapott a hibáról.",
+                en: "Try again later. The administrator has been notified.",
+            },
+        }
+    }
+
+    pub fn registry() -> Vec<ErrorCodeEntry> {
+        Self::ALL
+            .iter()
+            .map(|error_code| ErrorCodeEntry {
+                code: error_code.code(),
+                http_status: error_code.http_status().as_u16(),
+                description: error_code.description(),
+                remediation: error_code.remediation(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registry_codes_are_unique() {
+        let registry = ErrorCode::registry();
+        let codes: HashSet<&str> = registry.iter().map(|entry| entry.code).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+    }
+
+    #[test]
+    fn test_from_status_matches_http_status() {
+        for error_code in ErrorCode::ALL {
+            let has_fields = error_code == ErrorCode::ValidationFailed;
+            let derived = ErrorCode::from_status(error_code.http_status(), has_fields);
+            assert_eq!(derived.http_status(), error_code.http_status());
+        }
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::UNPROCESSABLE_ENTITY, false),
+            ErrorCode::UnprocessableEntry
+        );
+    }
+}
@@ -91,6 +91,82 @@ pub mod tests {
         }
     }
 
+    pub struct MockNegativeStockViolation;
+
+    impl Error for MockNegativeStockViolation {}
+    impl Debug for MockNegativeStockViolation {
+        fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+            unimplemented!()
+        }
+    }
+    impl Display for MockNegativeStockViolation {
+        fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+            unimplemented!()
+        }
+    }
+    impl DatabaseError for MockNegativeStockViolation {
+        fn message(&self) -> &str {
+            unimplemented!()
+        }
+
+        fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+            unimplemented!()
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+            unimplemented!()
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+            unimplemented!()
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::CheckViolation
+        }
+        fn constraint(&self) -> Option<&str> {
+            Some("inventory_negative_stock")
+        }
+    }
+
+    pub struct MockProjectArchivedViolation;
+
+    impl Error for MockProjectArchivedViolation {}
+    impl Debug for MockProjectArchivedViolation {
+        fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+            unimplemented!()
+        }
+    }
+    impl Display for MockProjectArchivedViolation {
+        fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+            unimplemented!()
+        }
+    }
+    impl DatabaseError for MockProjectArchivedViolation {
+        fn message(&self) -> &str {
+            unimplemented!()
+        }
+
+        fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+            unimplemented!()
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+            unimplemented!()
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+            unimplemented!()
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::CheckViolation
+        }
+        fn constraint(&self) -> Option<&str> {
+            Some("project_archived")
+        }
+    }
+
     pub fn generate_valid_jwt(sub: Option<Uuid>, active_tenant_id: Option<Uuid>) -> String {
         let config = AppConfigBuilder::default().build().unwrap();
         let sub = match sub {
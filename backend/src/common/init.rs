@@ -20,14 +20,31 @@
 use std::sync::Arc;
 
 use crate::common::AppState;
+use crate::common::ConfigProvider;
 use crate::common::config::AppConfig;
+use crate::common::crypto::init_keyring;
 use crate::common::database::{DatabaseMigrator, PgPoolManager, PoolManager};
+use crate::common::sandbox::spawn_sandbox_reset;
+use crate::common::service::Service;
+use crate::manager::tenant_incidents::service::TenantIncidentsService;
 use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::dunning::scheduler::spawn_dunning;
+use crate::tenant::exchange_rates::fetcher::spawn_rate_fetcher;
+use crate::tenant::inventory::low_stock::spawn_low_stock_alerts;
+use crate::tenant::nav_reporting::queue::spawn_nav_queue;
+use crate::tenant::receivables::summary::spawn_summary_mailer;
+use crate::tenant::recurring_invoices::scheduler::spawn_recurring_invoices;
+use crate::tenant::shipments::tracking::spawn_tracking_poller;
+use crate::tenant::stock_snapshots::scheduled::spawn_stock_snapshots;
+use crate::tenant::task_assignments::reminders::spawn_task_due_reminders;
+use crate::tenant::task_recurrences::scheduler::spawn_task_recurrences;
+use crate::tenant::warehouses::capacity::spawn_capacity_alerts;
 use anyhow::Result;
 use axum::Router;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{AsyncSmtpTransport, Tokio1Executor};
 use tower_http::trace::TraceLayer;
+use tracing::error;
 use tracing_subscriber::FmtSubscriber;
 
 pub fn init_subscriber(config: &AppConfig) {
@@ -41,7 +58,8 @@ pub fn init_subscriber(config: &AppConfig) {
 
 pub async fn init_default_app_state(
     config: AppConfig,
-) -> Result<AppState<PgPoolManager, AsyncSmtpTransport<Tokio1Executor>>> {
+) -> Result<Arc<AppState<PgPoolManager, AsyncSmtpTransport<Tokio1Executor>>>> {
+    init_keyring(config.encryption())?;
     let pg_pool_manager = PgPoolManager::new(config.main_database()).await?;
     let smtp_transport =
         AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(config.mail().smtp_host())?
@@ -50,11 +68,20 @@ pub async fn init_default_app_state(
                 config.mail().smtp_passwd().to_owned(),
             ))
             .build();
-    let app_state = AppState::new(config, pg_pool_manager, smtp_transport).await?;
+    let app_state = Arc::new(AppState::new(config, pg_pool_manager, smtp_transport).await?);
 
     let tenants = TenantsRepository::get_all(&*app_state.pool_manager().get_main_pool()).await?;
     app_state.pool_manager().migrate_main_db().await?;
-    app_state.pool_manager().init_tenant_pools(&tenants).await?;
+    let failures = app_state
+        .pool_manager()
+        .init_tenant_pools(&tenants, app_state.config())
+        .await;
+    let service = Service::new(None, app_state.clone());
+    for (tenant_id, message) in failures {
+        if let Err(e) = service.report_pool_init_failure(tenant_id, &message).await {
+            error!("Could not report tenant pool failure: {}", e);
+        }
+    }
     app_state
         .pool_manager()
         .migrate_all_tenant_dbs(&tenants)
@@ -64,32 +91,208 @@ pub async fn init_default_app_state(
 }
 
 pub async fn init_default_app(
-    app_state: AppState<PgPoolManager, AsyncSmtpTransport<Tokio1Executor>>,
+    app_state: Arc<AppState<PgPoolManager, AsyncSmtpTransport<Tokio1Executor>>>,
 ) -> Result<Router> {
-    let app_state = Arc::new(app_state);
+    if app_state.config().sandbox().sandbox_enabled() {
+        spawn_sandbox_reset(app_state.clone());
+    }
+    if app_state.config().carriers().tracking_poll_interval_mins() > 0 {
+        spawn_tracking_poller(app_state.clone());
+    }
+    if app_state.config().receivables().summary_interval_hours() > 0 {
+        spawn_summary_mailer(app_state.clone());
+    }
+    if app_state
+        .config()
+        .receivables()
+        .recurring_invoice_interval_hours()
+        > 0
+    {
+        spawn_recurring_invoices(app_state.clone());
+    }
+    if app_state.config().receivables().dunning_interval_hours() > 0 {
+        spawn_dunning(app_state.clone());
+    }
+    if app_state
+        .config()
+        .inventory()
+        .low_stock_alert_interval_hours()
+        > 0
+    {
+        spawn_low_stock_alerts(app_state.clone());
+    }
+    if app_state
+        .config()
+        .inventory()
+        .stock_snapshot_interval_hours()
+        > 0
+    {
+        spawn_stock_snapshots(app_state.clone());
+    }
+    if app_state
+        .config()
+        .inventory()
+        .capacity_alert_interval_hours()
+        > 0
+    {
+        spawn_capacity_alerts(app_state.clone());
+    }
+    if app_state.config().tasks().recurrence_interval_hours() > 0 {
+        spawn_task_recurrences(app_state.clone());
+    }
+    if app_state.config().tasks().due_reminder_interval_hours() > 0 {
+        spawn_task_due_reminders(app_state.clone());
+    }
+    if app_state.config().nav().queue_interval_mins() > 0 {
+        spawn_nav_queue(app_state.clone());
+    }
+    if app_state.config().exchange_rates().fetch_interval_hours() > 0 {
+        spawn_rate_fetcher(app_state.clone());
+    }
     Ok(Router::new().nest(
         "/api",
         Router::new()
             .merge(crate::manager::auth::routes::routes(app_state.clone()))
+            .merge(crate::manager::emails::routes::routes(app_state.clone()))
+            .merge(crate::manager::meta::routes::routes(app_state.clone()))
             .merge(crate::manager::users::routes::routes(app_state.clone()))
             .merge(crate::manager::tenants::routes::routes(app_state.clone()))
+            .merge(crate::manager::tenant_incidents::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::manager::tenant_integrity::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::accounting_exports::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::activity_feed::routes::routes(
                 app_state.clone(),
             ))
+            .merge(crate::tenant::attachments::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::categories::routes::routes(app_state.clone()))
             .merge(crate::tenant::comments::routes::routes(app_state.clone()))
+            .merge(crate::tenant::credit_notes::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::currencies::routes::routes(app_state.clone()))
+            .merge(crate::tenant::customer_contacts::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::customer_notes::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::customer_portal::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::customer_segments::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::customers::routes::routes(app_state.clone()))
+            .merge(crate::tenant::document_settings::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::dunning::routes::routes(app_state.clone()))
+            .merge(crate::tenant::exchange_rates::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::goods_receipts::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::inventory::routes::routes(app_state.clone()))
+            .merge(crate::tenant::inventory_adjustments::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::inventory_costing::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::inventory_movements::routes::routes(
                 app_state.clone(),
             ))
             .merge(crate::tenant::inventory_reservations::routes::routes(
                 app_state.clone(),
             ))
+            .merge(crate::tenant::inventory_serials::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::nav_reporting::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::package_types::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::payment_links::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::payments::routes::routes(app_state.clone()))
+            .merge(crate::tenant::permissions::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::picking_lists::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::products::routes::routes(app_state.clone()))
+            .merge(crate::tenant::project_members::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::project_schedules::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::projects::routes::routes(app_state.clone()))
+            .merge(crate::tenant::purchase_invoices::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::purchase_orders::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::quotes::routes::routes(app_state.clone()))
+            .merge(crate::tenant::receivables::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::recurring_invoices::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::services::routes::routes(app_state.clone()))
+            .merge(crate::tenant::shipments::routes::routes(app_state.clone()))
+            .merge(crate::tenant::stock_snapshots::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::stock_transfers::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::stocktakes::routes::routes(app_state.clone()))
+            .merge(crate::tenant::supplier_portal::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::suppliers::routes::routes(app_state.clone()))
+            .merge(crate::tenant::tags::routes::routes(app_state.clone()))
+            .merge(crate::tenant::task_assignments::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::task_checklist_items::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::task_comments::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::task_recurrences::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::tasks::routes::routes(app_state.clone()))
             .merge(crate::tenant::taxes::routes::routes(app_state.clone()))
+            .merge(crate::tenant::time_entries::routes::routes(
+                app_state.clone(),
+            ))
+            .merge(crate::tenant::vat_reports::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::warehouses::routes::routes(app_state.clone()))
+            .merge(crate::tenant::watches::routes::routes(app_state.clone()))
+            .merge(crate::tenant::worksheet_templates::routes::routes(
+                app_state.clone(),
+            ))
             .merge(crate::tenant::worksheets::routes::routes(app_state.clone()))
             .layer(TraceLayer::new_for_http()),
     ))
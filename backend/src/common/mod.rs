@@ -39,9 +39,12 @@ use lettre::{
 use tracing::{error, info};
 
 pub mod config;
+pub mod crypto;
 pub mod database;
 pub(crate) mod dto;
+pub(crate) mod email_template;
 pub(crate) mod error;
+pub(crate) mod error_code;
 pub(crate) mod extractors;
 pub(crate) mod handler;
 pub mod init;
@@ -49,7 +52,9 @@ pub(crate) mod macros;
 pub(crate) mod model;
 pub(crate) mod pdf;
 pub(crate) mod query_parser;
+pub(crate) mod sandbox;
 pub mod service;
+pub(crate) mod storage;
 pub(crate) mod types;
 pub(crate) mod utils;
 pub(crate) mod value_object;
@@ -20,6 +20,10 @@
 #![allow(dead_code)]
 use std::{fmt::Display, fs, path::Path, process::Command};
 
+use crate::common::types::Money;
+
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::NaiveDate;
 #[cfg(test)]
 use mockall::automock;
 use serde::Serialize;
@@ -47,12 +51,17 @@ pub enum PdfTemplates {
     WarehouseView,
     TaxView,
     ProductView,
+    ProductBarcodeLabels,
     InventoryView,
     InventoryMovementView,
     InventoryReservationView,
     ServiceView,
     TaskView,
     WorksheetView,
+    QuoteDocument,
+    InvoiceDocument,
+    WorksheetDocument,
+    CustomerStatementDocument,
 }
 
 impl Display for PdfTemplates {
@@ -63,17 +72,65 @@ impl Display for PdfTemplates {
             Self::WarehouseView => "warehouse_view",
             Self::TaxView => "tax_view",
             Self::ProductView => "product_view",
+            Self::ProductBarcodeLabels => "product_barcode_labels",
             Self::InventoryView => "inventory_view",
             Self::InventoryMovementView => "inventory_movement_view",
             Self::InventoryReservationView => "inventory_reservation_view",
             Self::ServiceView => "service_view",
             Self::TaskView => "task_view",
             Self::WorksheetView => "worksheet_view",
+            Self::QuoteDocument => "quote_document",
+            Self::InvoiceDocument => "invoice_document",
+            Self::WorksheetDocument => "worksheet_document",
+            Self::CustomerStatementDocument => "customer_statement_document",
         };
         write!(f, "templates/{template}.typ")
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfLogo {
+    pub data: Vec<u8>,
+    pub extension: &'static str,
+}
+
+// NOTE: Hungarian conventions, grouping uses a non-breaking space so amounts never wrap in tables
+pub fn format_number(value: &BigDecimal, decimals: i64) -> String {
+    let rounded = value
+        .with_scale_round(decimals, RoundingMode::HalfUp)
+        .to_plain_string();
+    let (sign, digits) = match rounded.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", rounded.as_str()),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let mut grouped = String::new();
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push('\u{a0}');
+        }
+        grouped.push(c);
+    }
+    if fraction.is_empty() {
+        format!("{sign}{grouped}")
+    } else {
+        format!("{sign}{grouped},{fraction}")
+    }
+}
+
+pub fn format_quantity(value: &BigDecimal) -> String {
+    let (_, scale) = value.normalized().as_bigint_and_exponent();
+    format_number(value, scale.clamp(0, 3))
+}
+
+pub fn format_money(value: &BigDecimal, currency_code: &str) -> String {
+    Money::new(value.clone(), currency_code).format()
+}
+
+pub fn format_date(value: NaiveDate) -> String {
+    value.format("%Y. %m. %d.").to_string()
+}
+
 #[derive(Debug)]
 pub struct PdfGenerator {}
 
@@ -123,6 +180,69 @@ impl PdfGenerator {
         fs::read(tmp_file.path()).map_err(|e| PdfGenError::IOError(e.to_string()))
     }
 
+    pub fn gen_pdf_document<T>(
+        template: &PdfTemplates,
+        payload: T,
+        logo: Option<PdfLogo>,
+    ) -> PdfGenResult<Vec<u8>>
+    where
+        T: Serialize + 'static,
+    {
+        PdfGenerator::gen_pdf_document_with_images(
+            template,
+            payload,
+            logo.map(|logo| ("logo", logo)).into_iter().collect(),
+        )
+    }
+
+    /// Every image is passed to the template as a `sys.inputs` path under its own name
+    pub fn gen_pdf_document_with_images<T>(
+        template: &PdfTemplates,
+        payload: T,
+        images: Vec<(&'static str, PdfLogo)>,
+    ) -> PdfGenResult<Vec<u8>>
+    where
+        T: Serialize + 'static,
+    {
+        let tmp_file = NamedTempFile::new().map_err(|e| PdfGenError::IOError(e.to_string()))?;
+        let mut output = Command::new("typst");
+
+        for arg in PdfGenerator::typst_compile_args(template, tmp_file.path(), payload)? {
+            output.arg(arg);
+        }
+
+        // NOTE: typst only reads files below its root, the images live in the system temp directory
+        if !images.is_empty() {
+            output.arg("--root").arg("/");
+        }
+        let mut image_files = Vec::with_capacity(images.len());
+        for (name, image) in images {
+            let image_file = tempfile::Builder::new()
+                .suffix(&format!(".{}", image.extension))
+                .tempfile()
+                .map_err(|e| PdfGenError::IOError(e.to_string()))?;
+            fs::write(image_file.path(), &image.data)
+                .map_err(|e| PdfGenError::IOError(e.to_string()))?;
+            output
+                .arg("--input")
+                .arg(format!("{name}={}", image_file.path().to_string_lossy()));
+            image_files.push(image_file);
+        }
+
+        let output = output
+            .output()
+            .map_err(|e| PdfGenError::IOError(e.to_string()))?;
+        drop(image_files);
+
+        if !output.status.success() {
+            return Err(PdfGenError::SubProcess(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+
+        fs::read(tmp_file.path()).map_err(|e| PdfGenError::IOError(e.to_string()))
+    }
+
     pub fn gen_pdf_persistent<T>(
         path: &'static Path,
         template: &PdfTemplates,
@@ -152,6 +272,7 @@ impl PdfGenerator {
 
 #[cfg(test)]
 pub mod tests {
+    use super::*;
     use lopdf::Document;
     use std::sync::Mutex;
 
@@ -163,4 +284,32 @@ pub mod tests {
         let page_numbers: Vec<u32> = pages.keys().copied().collect();
         document.extract_text(&page_numbers)
     }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(
+            format_number(&"1234567.891".parse().unwrap(), 2),
+            "1\u{a0}234\u{a0}567,89"
+        );
+        assert_eq!(format_number(&"-999.5".parse().unwrap(), 0), "-1\u{a0}000");
+        assert_eq!(format_number(&"12".parse().unwrap(), 2), "12,00");
+        assert_eq!(format_quantity(&"2.50".parse().unwrap()), "2,5");
+        assert_eq!(format_quantity(&"1000.00".parse().unwrap()), "1\u{a0}000");
+    }
+
+    #[test]
+    fn test_format_money_and_date() {
+        assert_eq!(
+            format_money(&"31750.40".parse().unwrap(), "HUF"),
+            "31\u{a0}750\u{a0}Ft"
+        );
+        assert_eq!(
+            format_money(&"99.5".parse().unwrap(), "EUR"),
+            "99,50\u{a0}EUR"
+        );
+        assert_eq!(
+            format_date(NaiveDate::from_ymd_opt(2026, 3, 7).unwrap()),
+            "2026. 03. 07."
+        );
+    }
 }
@@ -220,7 +220,7 @@ where
     fn from_str(s: &str) -> Result<Filtering<F>, Self::Err> {
         let collection: Vec<String> = s
             .replace("filtering:", "")
-            .split("-")
+            .splitn(2, "-")
             .map(|v| v.to_string())
             .collect();
         if collection.len() == 2 {
@@ -317,6 +317,27 @@ impl CommonRawQuery {
     }
 }
 
+/// List query of resources that belong to archivable projects, archived ones are left out
+/// unless `include_archived=true` is given
+#[derive(Deserialize, Debug, Clone)]
+pub struct ArchivableRawQuery {
+    q: Option<String>,
+    #[serde(default)]
+    include_archived: bool,
+}
+
+impl ArchivableRawQuery {
+    pub fn q(&self) -> &str {
+        match &self.q {
+            Some(v) => v,
+            None => "",
+        }
+    }
+    pub fn include_archived(&self) -> bool {
+        self.include_archived
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -598,4 +619,16 @@ mod tests {
         );
         assert_eq!(query_from_str, query_constructed);
     }
+
+    #[test]
+    fn test_query_from_str_filter_value_with_dash() {
+        let test_str = "filtering:type-|3f2b1c9e-0d4a-4b6e-9c1f-2a7d8e5b6c40|";
+        let query_from_str =
+            ResourceQuery::<TestOrderBy, TestFilterBy>::from_str(test_str).unwrap();
+
+        assert_eq!(
+            query_from_str.filtering().value_unchecked(),
+            Some("3f2b1c9e-0d4a-4b6e-9c1f-2a7d8e5b6c40")
+        );
+    }
 }
@@ -0,0 +1,197 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::config::AppConfig;
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::error_code::ErrorCode;
+use crate::common::{AppState, ConfigProvider};
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use serde_json::json;
+use sqlx::{AssertSqlSafe, PgPool};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+// NOTE: reference data seeded by the migrations must survive the reset
+const PRESERVED_TABLES: [&str; 8] = [
+    "_sqlx_migrations",
+    "tenant_marker",
+    "users",
+    "countries",
+    "currencies",
+    "states",
+    "cities",
+    "postal_codes",
+];
+
+// NOTE: single row settings tables seeded by the migrations, the services expect their row to
+// exist, so the defaults are seeded again after the reset
+const SETTINGS_TABLES: [&str; 6] = [
+    "costing_settings",
+    "inventory_settings",
+    "document_settings",
+    "purchase_invoice_settings",
+    "credit_limit_settings",
+    "currency_settings",
+];
+
+pub async fn reject_in_sandbox<M: ConfigProvider<Cfg = AppConfig>>(
+    State(module): State<Arc<M>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    if module.config().sandbox().sandbox_enabled() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            json!({
+                "error": {
+                    "code": ErrorCode::SandboxRestricted.code(),
+                    "message": ErrorCode::SandboxRestricted.description().hu
+                }
+            })
+            .to_string(),
+        ));
+    }
+    Ok(next.run(req).await)
+}
+
+#[async_trait]
+pub trait SandboxRepository: Send + Sync {
+    async fn reset(&self, seed_sql: &str) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl SandboxRepository for PgPool {
+    async fn reset(&self, seed_sql: &str) -> RepositoryResult<()> {
+        let mut tx = self.begin().await?;
+
+        let tables = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT quote_ident(tablename)
+            FROM pg_tables
+            WHERE schemaname = 'public'
+                AND tablename <> ALL($1)
+            "#,
+        )
+        .bind(&PRESERVED_TABLES[..])
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !tables.is_empty() {
+            let _ = sqlx::query(AssertSqlSafe(format!(
+                "TRUNCATE TABLE {} RESTART IDENTITY CASCADE",
+                tables.join(", ") // Security: quote_ident
+            )))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for table in SETTINGS_TABLES {
+            let _ = sqlx::query(AssertSqlSafe(format!(
+                "INSERT INTO {table} (id) VALUES (true)" // Security: constant table names
+            )))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let _ = sqlx::raw_sql(AssertSqlSafe(seed_sql.to_owned())) // Security: operator provided file
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+async fn reset_sandbox_tenant<P, T>(app_state: &AppState<P, T>) -> anyhow::Result<()>
+where
+    P: PoolManager,
+    T: Send + Sync,
+{
+    let sandbox = app_state.config().sandbox();
+    let tenant_id = sandbox
+        .tenant_id()
+        .ok_or(anyhow::anyhow!("sandbox tenant_id is not configured"))?;
+    let seed_file = sandbox
+        .seed_file()
+        .ok_or(anyhow::anyhow!("sandbox seed_file is not configured"))?;
+    let seed_sql = tokio::fs::read_to_string(seed_file).await?;
+
+    app_state
+        .pool_manager()
+        .get_tenant_pool(tenant_id)?
+        .reset(&seed_sql)
+        .await?;
+
+    info!("Sandbox tenant reset is successful: {}", tenant_id);
+    Ok(())
+}
+
+pub fn spawn_sandbox_reset<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + 'static,
+    T: Send + Sync + 'static,
+{
+    let interval_mins = app_state.config().sandbox().reset_interval_mins().max(1);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_mins * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = reset_sandbox_tenant(&app_state).await {
+                error!("Sandbox tenant reset failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_settings_tables_match_migration_seeds() {
+        let seed = Regex::new(r"(?i)INSERT INTO (\w+) \(id\) VALUES \(true\)").unwrap();
+        let migrations = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations/tenant");
+        let mut seeded = BTreeSet::new();
+        for entry in std::fs::read_dir(migrations).unwrap() {
+            let path = entry.unwrap().path();
+            if path.to_string_lossy().ends_with(".up.sql") {
+                let sql = std::fs::read_to_string(&path).unwrap();
+                seeded.extend(seed.captures_iter(&sql).map(|c| c[1].to_string()));
+            }
+        }
+
+        assert_eq!(
+            seeded,
+            SETTINGS_TABLES
+                .iter()
+                .map(|table| table.to_string())
+                .collect::<BTreeSet<_>>()
+        );
+    }
+}
@@ -19,6 +19,7 @@
 
 use std::sync::Arc;
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::manager::auth::dto::claims::Claims;
 
@@ -52,3 +53,11 @@ where
         &self.module
     }
 }
+
+/// The tenant the user of the service is working in
+pub fn active_tenant<T: Send + Sync>(service: &Service<'_, T>) -> Result<Uuid, ServiceError> {
+    service
+        .claims()?
+        .active_tenant()
+        .ok_or(ServiceError::Unauthorized)
+}
@@ -29,6 +29,7 @@ pub(crate) mod first_name;
 pub(crate) mod float64;
 pub(crate) mod integer32;
 pub(crate) mod last_name;
+pub(crate) mod money;
 pub(crate) mod password;
 pub(crate) mod quantity;
 pub(crate) mod uuid;
@@ -45,5 +46,6 @@ pub(crate) use first_name::FirstName;
 pub(crate) use float64::Float64;
 pub(crate) use integer32::Integer32;
 pub(crate) use last_name::LastName;
+pub(crate) use money::{CurrencyRules, Money, MoneyError};
 pub(crate) use password::Password;
 pub(crate) use uuid::UuidVO;
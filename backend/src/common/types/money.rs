@@ -0,0 +1,375 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::pdf::format_number;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
+use std::cmp::Ordering;
+use std::ops::Neg;
+use thiserror::Error;
+use tracing::Level;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MoneyError {
+    #[error("Eltérő pénznemű összegek nem kezelhetők együtt: {0}, {1}")]
+    CurrencyMismatch(String, String),
+}
+
+impl From<MoneyError> for AppError {
+    fn from(value: MoneyError) -> Self {
+        Self::new(
+            Level::DEBUG,
+            StatusCode::UNPROCESSABLE_ENTITY,
+            file!(),
+            AppErrorVisibility::UserFacing,
+            json!({"message": value.to_string()}),
+        )
+    }
+}
+
+pub type MoneyResult<T> = Result<T, MoneyError>;
+
+/// Rounding and display rules of a currency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyRules {
+    /// Decimal places of document amounts: line totals, taxes and invoice totals
+    pub scale: i64,
+    /// Decimal places of amounts settled in cash
+    pub cash_scale: i64,
+    /// Decimal places printed on documents
+    pub display_scale: i64,
+    /// Printed after the amount instead of the currency code
+    pub symbol: Option<&'static str>,
+}
+
+const DEFAULT_RULES: CurrencyRules = CurrencyRules {
+    scale: 2,
+    cash_scale: 2,
+    display_scale: 2,
+    symbol: None,
+};
+
+// NOTE: ISO 4217 currencies without minor units
+const ZERO_DECIMAL_CURRENCIES: [&str; 16] = [
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "VND", "VUV",
+    "XAF", "XOF", "XPF",
+];
+
+// NOTE: ISO 4217 currencies with three decimal places
+const THREE_DECIMAL_CURRENCIES: [&str; 7] = ["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+impl CurrencyRules {
+    pub fn of(currency_code: &str) -> Self {
+        match currency_code.to_uppercase().as_str() {
+            // NOTE: invoices may carry fillér, but the smallest coin is 5 Ft and cash payments
+            // are settled in whole forints
+            "HUF" => Self {
+                scale: 2,
+                cash_scale: 0,
+                display_scale: 0,
+                symbol: Some("Ft"),
+            },
+            "CZK" => Self {
+                cash_scale: 0,
+                ..DEFAULT_RULES
+            },
+            code if ZERO_DECIMAL_CURRENCIES.contains(&code) => Self {
+                scale: 0,
+                cash_scale: 0,
+                display_scale: 0,
+                symbol: None,
+            },
+            code if THREE_DECIMAL_CURRENCIES.contains(&code) => Self {
+                scale: 3,
+                cash_scale: 3,
+                display_scale: 3,
+                symbol: None,
+            },
+            _ => DEFAULT_RULES,
+        }
+    }
+}
+
+/// An amount in a currency. Every rounding of document and payment amounts goes through this
+/// type, so the precision of a currency is decided in one place.
+///
+/// Amounts of different currencies are never added up or compared: the checked operations
+/// return [`MoneyError::CurrencyMismatch`] and `partial_cmp` returns `None` for them. Currency
+/// codes are kept in upper case, so equality and ordering agree on what the same currency is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Money {
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "deserialize_currency_code")]
+    pub currency_code: String,
+}
+
+fn deserialize_currency_code<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(String::deserialize(deserializer)?.to_uppercase())
+}
+
+impl Money {
+    pub fn new(amount: BigDecimal, currency_code: &str) -> Self {
+        Self {
+            amount,
+            currency_code: currency_code.to_uppercase(),
+        }
+    }
+
+    pub fn zero(currency_code: &str) -> Self {
+        Self::new(BigDecimal::zero(), currency_code)
+    }
+
+    /// Net amount of a document line, rounded to the currency
+    pub fn line_net(quantity: &BigDecimal, unit_price: &BigDecimal, currency_code: &str) -> Self {
+        Self::new(quantity * unit_price, currency_code).round()
+    }
+
+    /// Sum of `amounts` in `currency_code`, zero when there are none
+    pub fn sum<'a>(
+        currency_code: &str,
+        amounts: impl IntoIterator<Item = &'a Money>,
+    ) -> MoneyResult<Self> {
+        amounts
+            .into_iter()
+            .try_fold(Self::zero(currency_code), |total, amount| {
+                total.checked_add(amount)
+            })
+    }
+
+    pub fn is_same_currency(&self, other: &Money) -> bool {
+        self.currency_code == other.currency_code
+    }
+
+    pub fn checked_add(&self, other: &Money) -> MoneyResult<Self> {
+        self.ensure_same_currency(other)?;
+        Ok(Self::new(&self.amount + &other.amount, &self.currency_code))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> MoneyResult<Self> {
+        self.ensure_same_currency(other)?;
+        Ok(Self::new(&self.amount - &other.amount, &self.currency_code))
+    }
+
+    pub fn checked_cmp(&self, other: &Money) -> MoneyResult<Ordering> {
+        self.ensure_same_currency(other)?;
+        Ok(self.amount.cmp(&other.amount))
+    }
+
+    /// The amount multiplied by `factor`, e.g. a quantity, without rounding
+    pub fn times(&self, factor: &BigDecimal) -> Self {
+        Self::new(&self.amount * factor, &self.currency_code)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > BigDecimal::zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount < BigDecimal::zero()
+    }
+
+    pub fn rules(&self) -> CurrencyRules {
+        CurrencyRules::of(&self.currency_code)
+    }
+
+    pub fn round(&self) -> Self {
+        self.round_to(self.rules().scale)
+    }
+
+    pub fn round_cash(&self) -> Self {
+        self.round_to(self.rules().cash_scale)
+    }
+
+    /// Tax on this net amount at a rate given in percents, rounded to the currency
+    pub fn tax(&self, rate: &BigDecimal) -> Self {
+        Self::new(
+            &self.amount * rate / BigDecimal::from(100),
+            &self.currency_code,
+        )
+        .round()
+    }
+
+    /// The amount in another currency at `rate`, rounded to the target currency
+    pub fn convert(&self, rate: &BigDecimal, currency_code: &str) -> Self {
+        Self::new(&self.amount * rate, currency_code).round()
+    }
+
+    pub fn format(&self) -> String {
+        let rules = self.rules();
+        format!(
+            "{}\u{a0}{}",
+            format_number(&self.amount, rules.display_scale),
+            rules.symbol.unwrap_or(self.currency_code.as_str())
+        )
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> MoneyResult<()> {
+        if self.is_same_currency(other) {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch(
+                self.currency_code.clone(),
+                other.currency_code.clone(),
+            ))
+        }
+    }
+
+    fn round_to(&self, scale: i64) -> Self {
+        Self::new(
+            self.amount.with_scale_round(scale, RoundingMode::HalfUp),
+            &self.currency_code,
+        )
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.checked_cmp(other).ok()
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.amount, &self.currency_code)
+    }
+}
+
+impl Neg for &Money {
+    type Output = Money;
+
+    fn neg(self) -> Self::Output {
+        Money::new(-&self.amount, &self.currency_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(amount: &str, currency_code: &str) -> Money {
+        Money::new(amount.parse().unwrap(), currency_code)
+    }
+
+    #[test]
+    fn test_round_follows_currency_precision() {
+        assert_eq!(money("1234.565", "HUF").round(), money("1234.57", "HUF"));
+        assert_eq!(money("1234.5", "JPY").round(), money("1235", "JPY"));
+        assert_eq!(money("1.23456", "KWD").round(), money("1.235", "KWD"));
+        assert_eq!(money("-10.005", "EUR").round(), money("-10.01", "EUR"));
+    }
+
+    #[test]
+    fn test_round_cash_settles_forint_in_whole_units() {
+        assert_eq!(money("12700.49", "HUF").round_cash(), money("12700", "HUF"));
+        assert_eq!(money("12700.50", "HUF").round_cash(), money("12701", "HUF"));
+        assert_eq!(money("12.345", "EUR").round_cash(), money("12.35", "EUR"));
+    }
+
+    #[test]
+    fn test_line_net_and_tax() {
+        let net = Money::line_net(&"3".parse().unwrap(), &"33.335".parse().unwrap(), "EUR");
+        assert_eq!(net, money("100.01", "EUR"));
+        assert_eq!(net.tax(&"27".parse().unwrap()), money("27.00", "EUR"));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(money("31750.40", "HUF").format(), "31\u{a0}750\u{a0}Ft");
+        assert_eq!(money("1234.5", "EUR").format(), "1\u{a0}234,50\u{a0}EUR");
+        assert_eq!(money("1234.5", "jpy").format(), "1\u{a0}235\u{a0}JPY");
+    }
+
+    #[test]
+    fn test_checked_arithmetic_requires_same_currency() {
+        let total = money("100.50", "EUR")
+            .checked_add(&money("20", "EUR"))
+            .unwrap();
+        assert_eq!(total, money("120.50", "EUR"));
+        assert_eq!(
+            total.checked_sub(&money("200", "EUR")).unwrap(),
+            money("-79.50", "EUR")
+        );
+        assert_eq!(
+            money("100", "EUR").checked_add(&money("100", "HUF")),
+            Err(MoneyError::CurrencyMismatch(
+                "EUR".to_string(),
+                "HUF".to_string()
+            ))
+        );
+        assert_eq!(
+            Money::sum("HUF", &[money("1000", "HUF"), money("250.5", "HUF")]).unwrap(),
+            money("1250.5", "HUF")
+        );
+        assert!(Money::sum("HUF", &[money("1000", "HUF"), money("5", "EUR")]).is_err());
+        assert_eq!(Money::sum("HUF", &[]).unwrap(), Money::zero("HUF"));
+    }
+
+    #[test]
+    fn test_comparison_only_within_currency() {
+        assert!(money("10", "EUR") < money("10.01", "EUR"));
+        assert!(money("3000", "HUF") > money("2999.99", "huf"));
+        assert_eq!(money("10", "EUR").partial_cmp(&money("5", "USD")), None);
+        assert_ne!(
+            money("10", "EUR").partial_cmp(&money("5", "USD")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            money("10", "EUR").checked_cmp(&money("10.00", "EUR")),
+            Ok(Ordering::Equal)
+        );
+        assert!(money("10", "EUR").checked_cmp(&money("10", "USD")).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let value = serde_json::to_value(money("1234.50", "EUR")).unwrap();
+        assert_eq!(value["currency_code"], "EUR");
+        assert_eq!(
+            serde_json::from_value::<Money>(value).unwrap(),
+            money("1234.5", "EUR")
+        );
+        assert_eq!(
+            serde_json::from_value::<Money>(json!({"amount": "1", "currency_code": "huf"}))
+                .unwrap(),
+            money("1", "HUF")
+        );
+    }
+
+    #[test]
+    fn test_currency_code_is_normalized() {
+        assert_eq!(money("1", "huf"), money("1", "HUF"));
+        assert_eq!(money("1", "huf").currency_code, "HUF");
+        assert_eq!(
+            money("1", "huf").partial_cmp(&money("1", "HUF")),
+            Some(Ordering::Equal)
+        );
+    }
+}
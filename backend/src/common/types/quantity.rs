@@ -18,10 +18,12 @@
  */
 
 use crate::common::value_object::*;
+use bigdecimal::{BigDecimal, Zero};
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct Quantity(f64);
+pub struct Quantity(BigDecimal);
 
 impl Quantity {
     pub const PARSE_ERROR: &'static str = "Hibás mennyiség formátum!";
@@ -30,19 +32,20 @@ impl Quantity {
 }
 
 impl ValueObjectData for Quantity {
-    type DataType = f64;
+    type DataType = BigDecimal;
 
     fn new(data: &str) -> ValueObjectResult<Option<Self>> {
         if !data.trim().is_empty() {
-            Ok(Some(Self(data.replace(",", ".").parse::<f64>().map_err(
-                |_| ValueObjectError::InvalidInput(Self::PARSE_ERROR),
-            )?)))
+            Ok(Some(Self(
+                BigDecimal::from_str(&data.replace(",", "."))
+                    .map_err(|_| ValueObjectError::InvalidInput(Self::PARSE_ERROR))?,
+            )))
         } else {
             Ok(None)
         }
     }
     fn validate(&self) -> Result<(), ValueObjectError> {
-        if self.0 >= 0_f64 {
+        if self.0 >= BigDecimal::zero() {
             Ok(())
         } else {
             Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR))
@@ -73,19 +76,28 @@ mod tests {
     #[test]
     fn test_validate_valid_float() {
         let quantity = "123.456".parse::<ValueObjectRequired<Quantity>>().unwrap();
-        assert_eq!(quantity.as_f64().unwrap(), 123.456_f64);
+        assert_eq!(
+            quantity.as_decimal().unwrap(),
+            &BigDecimal::from_str("123.456").unwrap()
+        );
     }
 
     #[test]
     fn test_validate_valid_integer() {
         let quantity = "123".parse::<ValueObjectRequired<Quantity>>().unwrap();
-        assert_eq!(quantity.as_f64().unwrap(), 123_f64);
+        assert_eq!(
+            quantity.as_decimal().unwrap(),
+            &BigDecimal::from_str("123").unwrap()
+        );
     }
 
     #[test]
     fn test_validate_zero() {
         let quantity = "0".parse::<ValueObjectRequired<Quantity>>().unwrap();
-        assert_eq!(quantity.as_f64().unwrap(), 0_f64);
+        assert_eq!(
+            quantity.as_decimal().unwrap(),
+            &BigDecimal::from_str("0").unwrap()
+        );
     }
 
     #[test]
@@ -97,7 +109,10 @@ mod tests {
     #[test]
     fn test_validate_comma_decimal() {
         let quantity = "123,456".parse::<ValueObjectRequired<Quantity>>().unwrap();
-        assert_eq!(quantity.as_f64().unwrap(), 123.456_f64);
+        assert_eq!(
+            quantity.as_decimal().unwrap(),
+            &BigDecimal::from_str("123.456").unwrap()
+        );
     }
 
     #[test]
@@ -17,8 +17,73 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use lettre::Address;
 use rand::rngs::{StdRng, SysRng};
 use rand::{RngExt, SeedableRng};
+use serde::Serialize;
+use std::str::FromStr;
+
+pub fn start_of_local_day(date: NaiveDate, tz: Tz) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|start| start.with_timezone(&Utc))
+}
+
+pub fn to_csv<T: Serialize>(rows: &[T]) -> Result<Vec<u8>, csv::Error> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.into_inner().map_err(|e| e.into_error().into())
+}
+
+/// Opens an uploaded CSV file. Both `,` and `;` separated files are accepted, the latter
+/// with decimal commas, as exported by Excel with Hungarian locale. The returned flag tells
+/// whether the file uses decimal commas.
+pub fn csv_reader(data: &[u8]) -> Result<(csv::Reader<&[u8]>, bool), &'static str> {
+    let data = std::str::from_utf8(data)
+        .map_err(|_| "A fájl nem UTF-8 kódolású!")?
+        .trim_start_matches('\u{feff}');
+    let header = data.lines().next().unwrap_or_default();
+    let semicolon = header.contains(';') && !header.contains(',');
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(if semicolon { b';' } else { b',' })
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+    Ok((reader, semicolon))
+}
+
+pub fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_owned())
+}
+
+/// Splits a comma or semicolon separated address list, `None` means one of the addresses is invalid
+pub fn parse_email_list(value: &str) -> Option<Vec<Address>> {
+    value
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| address.parse::<Address>().ok())
+        .collect()
+}
+
+pub fn parse_amount(value: &str, decimal_comma: bool) -> Option<Result<BigDecimal, ()>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let value = if decimal_comma {
+        value.replace(',', ".")
+    } else {
+        value.to_owned()
+    };
+    Some(BigDecimal::from_str(&value).map_err(|_| ()))
+}
 
 pub fn generate_string_csprng(length: usize) -> Result<String, &'static str> {
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
@@ -32,3 +97,30 @@ pub fn generate_string_csprng(length: usize) -> Result<String, &'static str> {
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_of_local_day() {
+        let tz: Tz = "Europe/Budapest".parse().unwrap();
+        let start = start_of_local_day(NaiveDate::from_ymd_opt(2026, 8, 1).unwrap(), tz).unwrap();
+
+        assert_eq!(start.to_rfc3339(), "2026-07-31T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_email_list() {
+        let addresses = parse_email_list(" konyveles@example.com; iroda@example.com,, ").unwrap();
+
+        assert_eq!(
+            addresses
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["konyveles@example.com", "iroda@example.com"]
+        );
+        assert!(parse_email_list("konyveles@example.com, iroda").is_none());
+    }
+}
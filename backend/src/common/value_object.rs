@@ -18,6 +18,7 @@
  */
 
 use axum::http::StatusCode;
+use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
 use serde_json::json;
 use std::str::FromStr;
@@ -169,6 +170,20 @@ where
     }
 }
 
+#[allow(dead_code)]
+impl<T> ValueObjectRequired<T>
+where
+    T: ValueObjectData<DataType = BigDecimal>,
+{
+    pub fn as_decimal(&self) -> ValueObjectResult<&BigDecimal> {
+        Ok(self
+            .0
+            .as_ref()
+            .ok_or(ValueObjectError::InvalidState)?
+            .get_data())
+    }
+}
+
 #[allow(dead_code)]
 impl<T> ValueObjectRequired<T>
 where
@@ -244,6 +259,15 @@ where
     }
 }
 
+impl<T> ValueObjectOptional<T>
+where
+    T: ValueObjectData<DataType = BigDecimal>,
+{
+    pub fn as_decimal(&self) -> Option<&BigDecimal> {
+        self.0.as_ref().map(|v| v.get_data())
+    }
+}
+
 impl<T> ValueObjectOptional<T>
 where
     T: ValueObjectData<DataType = i32>,
@@ -530,6 +530,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hibás e-mail cím vagy jelszó"
             }
         });
@@ -690,6 +691,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "CONFLICT",
                 "message": "A megadott e-mail cím már foglalt!"
             }
         });
@@ -966,6 +968,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hibás e-mail megerősítő hivatkozás"
             }
         });
@@ -1099,6 +1102,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "A megerősítő e-mail újraküldése sikertelen"
             }
         });
@@ -1548,6 +1552,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hibás elfelejtett jelszó hivatkozás!"
             }
         });
@@ -1801,6 +1806,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Nincs jogosultságod az erőforrás használatához"
             }
         });
@@ -1835,6 +1841,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Nincs jogosultságod az erőforrás használatához"
             }
         });
@@ -2063,6 +2070,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Nincs jogosultságod az erőforrás használatához"
             }
         });
@@ -2097,6 +2105,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Nincs jogosultságod az erőforrás használatához"
             }
         });
@@ -29,6 +29,7 @@ use serde_json::json;
 use std::sync::Arc;
 
 use super::dto::claims::Claims;
+use crate::common::error_code::ErrorCode;
 use crate::common::{ConfigProvider, config::AppConfig};
 
 pub async fn require_auth<M: ConfigProvider<Cfg = AppConfig>>(
@@ -50,7 +51,8 @@ pub async fn require_auth<M: ConfigProvider<Cfg = AppConfig>>(
                 StatusCode::UNAUTHORIZED,
                 json!({
                     "error": {
-                        "message": "Hozzáférés megtagadva!"
+                        "code": ErrorCode::Unauthorized.code(),
+                        "message": ErrorCode::Unauthorized.description().hu
                     }
                 })
                 .to_string(),
@@ -60,6 +62,27 @@ pub async fn require_auth<M: ConfigProvider<Cfg = AppConfig>>(
     Ok(next.run(req).await)
 }
 
+pub async fn require_operator<M: ConfigProvider<Cfg = AppConfig>>(
+    State(module): State<Arc<M>>,
+    AuthenticatedUser(claims): AuthenticatedUser,
+    req: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    if !module.config().auth().is_operator(claims.sub()) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            json!({
+                "error": {
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }
+            })
+            .to_string(),
+        ));
+    }
+    Ok(next.run(req).await)
+}
+
 pub struct AuthenticatedUser(pub Claims);
 
 impl<S> FromRequestParts<S> for AuthenticatedUser
@@ -78,7 +101,8 @@ where
                 StatusCode::UNAUTHORIZED,
                 json!({
                     "error": {
-                        "message": "Hozzáférés megtagadva!"
+                        "code": ErrorCode::Unauthorized.code(),
+                        "message": ErrorCode::Unauthorized.description().hu
                     }
                 })
                 .to_string(),
@@ -19,6 +19,8 @@
 
 use super::AuthModuleInterface;
 use super::handler;
+use crate::common::sandbox::reject_in_sandbox;
+use axum::middleware::from_fn_with_state;
 use axum::{
     Router,
     routing::{get, post},
@@ -38,9 +40,14 @@ pub fn routes<M: AuthModuleInterface>(auth_module: Arc<M>) -> Router {
             )
             .route(
                 "/forgotten_password",
-                post(handler::forgotten_password::<M>),
+                post(handler::forgotten_password::<M>)
+                    .route_layer(from_fn_with_state(auth_module.clone(), reject_in_sandbox)),
+            )
+            .route(
+                "/new_password",
+                post(handler::new_password::<M>)
+                    .route_layer(from_fn_with_state(auth_module.clone(), reject_in_sandbox)),
             )
-            .route("/new_password", post(handler::new_password::<M>))
             .route("/t/refresh", post(handler::refresh::<M>)) // "[t]oken" nest is for cookie path restriction
             .route("/t/logout", post(handler::logout::<M>)) // "[t]oken" nest is for cookie path restriction
             .with_state(auth_module),
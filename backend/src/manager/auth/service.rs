@@ -21,6 +21,7 @@ use super::{
     AuthModuleInterface,
     dto::{claims::Claims, login::UserPublic},
 };
+use crate::common::email_template::EmailTemplate;
 use crate::manager::auth::dto::register::{ForgottenPasswordRequest, NewPasswordRequest};
 use crate::{common::error::RepositoryError, manager::auth::model::ForgottenPassword};
 use crate::{
@@ -54,13 +55,8 @@ use axum_extra::extract::CookieJar;
 use axum_extra::extract::cookie::{Cookie, SameSite};
 use chrono::{Duration, Utc};
 use chrono_tz::Tz;
-use handlebars::Handlebars;
 use jsonwebtoken::{EncodingKey, Header, encode};
-use lettre::{
-    Message,
-    address::AddressError,
-    message::{Mailbox, header::ContentType},
-};
+use lettre::{address::AddressError, message::Mailbox};
 use rand::RngExt;
 use serde_json::json;
 use thiserror::Error;
@@ -1606,49 +1602,32 @@ async fn send_email_verification<T>(
 where
     T: AuthModuleInterface,
 {
-    let handlebars = Handlebars::new();
     let hostname = auth_module.config().server().public_base_url().to_owned();
     let verification_uuid = email_verification.id;
     let verification_link = format!("https://{hostname}/email_megerosites/{verification_uuid}");
-    let email = Message::builder()
-        .from(Mailbox::new(
-            Some(auth_module.config().mail().default_from_name().to_owned()),
-            auth_module
-                .config()
-                .mail()
-                .default_from()
-                .parse()
-                .map_err(|e: AddressError| AuthServiceError::MailTransport(e.to_string()))?,
-        ))
-        .to(Mailbox::new(
-            None,
-            user.email
-                .parse()
-                .map_err(|e: AddressError| AuthServiceError::MailTransport(e.to_string()))?,
-        ))
-        .subject("Kérlek, erősítsd meg az e-mail címedet!")
-        .header(ContentType::TEXT_HTML)
-        .body(
-            handlebars
-                .render_template(
-                    r##"
-                <p style="font-weight: bold; margin-bottom: 25px;">
-                    Kedves {{last_name}} {{first_name}}!
-                </p>
-                <p>
-                    Kérlek a következő hivatkozásra kattintva erősítsd meg az e-mail címedet!<br>
-                    <a href="{{verification_link}}">{{verification_link}}</a>
-                </p>
-                "##,
-                    &json!({
-                        "last_name": user.last_name,
-                        "first_name": user.first_name,
-                        "verification_link": verification_link,
-                    }),
-                )
-                .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?,
-        )
-        .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?;
+    let email =
+        EmailTemplate::EmailVerification
+            .render(&json!({
+                "last_name": user.last_name,
+                "first_name": user.first_name,
+                "verification_link": verification_link,
+            }))
+            .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?
+            .into_message(
+                Mailbox::new(
+                    Some(auth_module.config().mail().default_from_name().to_owned()),
+                    auth_module.config().mail().default_from().parse().map_err(
+                        |e: AddressError| AuthServiceError::MailTransport(e.to_string()),
+                    )?,
+                ),
+                Mailbox::new(
+                    None,
+                    user.email.parse().map_err(|e: AddressError| {
+                        AuthServiceError::MailTransport(e.to_string())
+                    })?,
+                ),
+            )
+            .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?;
 
     match auth_module.send(email).await {
         Ok(_) => Ok(()),
@@ -1664,55 +1643,32 @@ async fn send_forgotten_password_email<T>(
 where
     T: AuthModuleInterface,
 {
-    let handlebars = Handlebars::new();
     let hostname = auth_module.config().server().public_base_url().to_owned();
     let forgotten_password_uuid = forgotten_password.id;
     let forgotten_password_link =
         format!("https://{hostname}/elfelejtett_jelszo/{forgotten_password_uuid}");
-    let email = Message::builder()
-            .from(Mailbox::new(
-                Some(auth_module.config().mail().default_from_name().to_owned()),
-                auth_module
-                    .config()
-                    .mail()
-                    .default_from()
-                    .parse()
-                    .map_err(|e: AddressError| AuthServiceError::MailTransport(e.to_string()))?,
-            ))
-            .to(Mailbox::new(
-                None,
-                user
-                    .email
-                    .parse()
-                    .map_err(|e: AddressError| AuthServiceError::MailTransport(e.to_string()))?,
-            ))
-            .subject("Elfelejtett jelszó")
-            .header(ContentType::TEXT_HTML)
-            .body(
-                handlebars
-                    .render_template(
-                        r##"
-                <p style="font-weight: bold; margin-bottom: 25px;">
-                    Kedves {{last_name}} {{first_name}}!
-                </p>
-                <p>
-                    A következő hivatkozásra kattintva megváltoztathatod a fiókodhoz tartozó jelszavadat.<br>
-                    Ha nem te kérted a jelszó emlékeztető e-mail-t, ne használd a hivatkozást 
-                    és értesítd a rendszergazdát a következő e-mail címen: {{admin_email}}!<br>
-                    <a href="{{forgotten_password_link}}">{{forgotten_password_link}}</a>
-                </p>
-                "##,
-                        &json!({
-                            "last_name": user.last_name,
-                            "first_name": user.first_name,
-                            "forgotten_password_link": forgotten_password_link,
-                            "admin_email": auth_module
-                            .config()
-                            .mail()
-                            .default_notification_email()
-                        }),
-                    )
-                    .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?,
+    let email =
+        EmailTemplate::ForgottenPassword
+            .render(&json!({
+                "last_name": user.last_name,
+                "first_name": user.first_name,
+                "forgotten_password_link": forgotten_password_link,
+                "admin_email": auth_module.config().mail().default_notification_email(),
+            }))
+            .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?
+            .into_message(
+                Mailbox::new(
+                    Some(auth_module.config().mail().default_from_name().to_owned()),
+                    auth_module.config().mail().default_from().parse().map_err(
+                        |e: AddressError| AuthServiceError::MailTransport(e.to_string()),
+                    )?,
+                ),
+                Mailbox::new(
+                    None,
+                    user.email.parse().map_err(|e: AddressError| {
+                        AuthServiceError::MailTransport(e.to_string())
+                    })?,
+                ),
             )
             .map_err(|e| AuthServiceError::MailTransport(e.to_string()))?;
 
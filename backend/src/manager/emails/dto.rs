@@ -0,0 +1,52 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::email_template::EmailTemplate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct EmailPreviewRequest {
+    pub template: EmailTemplate,
+    pub data: Option<Value>,
+    pub subject: Option<String>,
+    pub html: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct EmailTemplateInfo {
+    pub template: EmailTemplate,
+    pub subject: &'static str,
+    pub html: &'static str,
+    pub text: &'static str,
+    pub sample_data: Value,
+}
+
+impl From<EmailTemplate> for EmailTemplateInfo {
+    fn from(template: EmailTemplate) -> Self {
+        Self {
+            template,
+            subject: template.subject(),
+            html: template.html(),
+            text: template.text(),
+            sample_data: template.sample_data(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::BaseModule;
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::manager::emails::dto::EmailPreviewRequest;
+use crate::manager::emails::service::EmailsService;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn templates<M: BaseModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(emails_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), emails_module.clone());
+    let templates = map_handler_err(service.templates(), emails_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(templates)
+            .build(),
+        emails_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn preview<M: BaseModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(emails_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<EmailPreviewRequest>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), emails_module.clone());
+    let rendered = map_handler_err(service.preview(&payload), emails_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(rendered)
+            .build(),
+        emails_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::auth_config::tests::AuthConfigBuilder;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::email_template::EmailTemplate;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::emails;
+    use crate::manager::emails::tests::MockEmailsModule;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(operator_id: Uuid) -> Router {
+        let mut emails_module = MockEmailsModule::new();
+        emails_module.expect_config().return_const(
+            AppConfigBuilder::default()
+                .auth(
+                    AuthConfigBuilder::default()
+                        .operator_user_ids(vec![operator_id])
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        );
+        emails_module.expect_send().never();
+        Router::new().nest(
+            "/api",
+            Router::new().merge(emails::routes::routes(Arc::new(emails_module))),
+        )
+    }
+
+    fn preview_request(user_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(Some(user_id), None)),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/admin/emails/preview")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_preview_success() {
+        let operator_id = Uuid::new_v4();
+        let data = json!({
+            "last_name": "Teszt",
+            "first_name": "<Elek>",
+            "verification_link": "https://example.com/verify",
+        });
+
+        let response = app(operator_id)
+            .oneshot(preview_request(
+                operator_id,
+                json!({"template": "email_verification", "data": data}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected = EmailTemplate::EmailVerification.render(&data).unwrap();
+        assert_eq!(response_body, json!({"meta": null, "data": expected}));
+        assert!(expected.html.contains("Teszt &lt;Elek&gt;"));
+        assert!(expected.text.contains("Teszt <Elek>"));
+    }
+
+    #[tokio::test]
+    async fn test_preview_with_override() {
+        let operator_id = Uuid::new_v4();
+
+        let response = app(operator_id)
+            .oneshot(preview_request(
+                operator_id,
+                json!({
+                    "template": "forgotten_password",
+                    "html": "<b>{{first_name}}</b>",
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["html"], json!("<b>János</b>"));
+        assert_eq!(
+            response_body["data"]["subject"],
+            json!("Elfelejtett jelszó")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preview_invalid_template_source() {
+        let operator_id = Uuid::new_v4();
+
+        let response = app(operator_id)
+            .oneshot(preview_request(
+                operator_id,
+                json!({"template": "tenant_incident", "text": "{{#if}}"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_preview_forbidden_for_non_operator() {
+        let response = app(Uuid::new_v4())
+            .oneshot(preview_request(
+                Uuid::new_v4(),
+                json!({"template": "email_verification"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
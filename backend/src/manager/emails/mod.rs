@@ -0,0 +1,46 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod dto;
+mod handler;
+pub(crate) mod routes;
+pub mod service;
+
+#[cfg(test)]
+pub mod tests {
+    use crate::common::config::AppConfig;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub EmailsModule {}
+        impl ConfigProvider for EmailsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for EmailsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for EmailsModule {}
+    );
+}
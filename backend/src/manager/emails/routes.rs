@@ -0,0 +1,41 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::sync::Arc;
+
+use super::handler;
+use crate::common::BaseModule;
+use crate::manager::auth::middleware::{require_auth, require_operator};
+use axum::middleware::from_fn_with_state;
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+pub fn routes<M: BaseModule>(emails_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/admin/emails",
+        Router::new()
+            .route("/templates", get(handler::templates::<M>))
+            .route("/preview", post(handler::preview::<M>))
+            .layer(from_fn_with_state(emails_module.clone(), require_operator))
+            .layer(from_fn_with_state(emails_module.clone(), require_auth))
+            .with_state(emails_module),
+    )
+}
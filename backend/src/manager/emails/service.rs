@@ -0,0 +1,101 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::BaseModule;
+use crate::common::email_template::{EmailTemplate, RenderedEmail, render_email};
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::manager::emails::dto::{EmailPreviewRequest, EmailTemplateInfo};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+
+#[derive(Debug, Error)]
+pub enum EmailsServiceError {
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hibás sablon: {0}")]
+    Render(String),
+}
+
+impl From<ServiceError> for EmailsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => EmailsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<EmailsServiceError> for AppError {
+    fn from(value: EmailsServiceError) -> Self {
+        match value {
+            EmailsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            EmailsServiceError::Render(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+type EmailsServiceResult<T> = Result<T, EmailsServiceError>;
+
+pub trait EmailsService {
+    fn templates(&self) -> EmailsServiceResult<Vec<EmailTemplateInfo>>;
+    fn preview(&self, payload: &EmailPreviewRequest) -> EmailsServiceResult<RenderedEmail>;
+}
+
+impl<'a, T> EmailsService for Service<'a, T>
+where
+    T: BaseModule,
+{
+    fn templates(&self) -> EmailsServiceResult<Vec<EmailTemplateInfo>> {
+        self.claims()?;
+        Ok(EmailTemplate::ALL
+            .into_iter()
+            .map(EmailTemplateInfo::from)
+            .collect())
+    }
+
+    fn preview(&self, payload: &EmailPreviewRequest) -> EmailsServiceResult<RenderedEmail> {
+        self.claims()?;
+        let template = payload.template;
+        render_email(
+            payload.subject.as_deref().unwrap_or(template.subject()),
+            payload.html.as_deref().unwrap_or(template.html()),
+            payload.text.as_deref().unwrap_or(template.text()),
+            &payload
+                .data
+                .clone()
+                .unwrap_or_else(|| template.sample_data()),
+        )
+        .map_err(|e| EmailsServiceError::Render(e.to_string()))
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::BaseModule;
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::error_code::ErrorCode;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn error_codes<M: BaseModule>(State(meta_module): State<Arc<M>>) -> HandlerResult {
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(ErrorCode::registry())
+            .build(),
+        meta_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::handler::tests::extract_json_response;
+    use crate::manager::meta;
+    use crate::manager::meta::tests::MockMetaModule;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_error_codes_success() {
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(meta::routes::routes(Arc::new(MockMetaModule::new()))),
+        );
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/api/meta/error-codes")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": ErrorCode::registry()
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+}
@@ -18,5 +18,10 @@
  */
 
 pub mod auth;
+pub mod emails;
+pub mod meta;
+pub mod tenant_incidents;
+pub mod tenant_integrity;
+pub mod tenant_limits;
 pub mod tenants;
 pub mod users;
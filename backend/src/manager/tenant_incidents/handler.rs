@@ -0,0 +1,168 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::SuccessResponseBuilder;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::manager::tenant_incidents::TenantIncidentsModuleInterface;
+use crate::manager::tenant_incidents::service::TenantIncidentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn list<M: TenantIncidentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tenant_incidents_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tenant_incidents_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        tenant_incidents_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        tenant_incidents_module.clone(),
+    )
+    .await?;
+
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        tenant_incidents_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::auth_config::tests::AuthConfigBuilder;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::dto::PaginatorMeta;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenant_incidents::model::{POOL_INIT_FAILED, TenantIncident};
+    use crate::manager::tenant_incidents::{
+        self, repository::MockTenantIncidentsRepository, tests::MockTenantIncidentsModule,
+    };
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn list_request(user_id: Uuid) -> Request<String> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(Some(user_id), None)),
+            )
+            .method("GET")
+            .uri("/api/admin/tenant_incidents/list")
+            .body("".to_string())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_success() {
+        let operator_id = Uuid::new_v4();
+        let paginator_meta = PaginatorMeta {
+            page: 1,
+            limit: 25,
+            total: 1,
+        };
+        let incident = TenantIncident {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            incident_type: POOL_INIT_FAILED.to_string(),
+            message: "connection refused".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockTenantIncidentsRepository::new();
+        repo.expect_get_paged().times(1).returning({
+            let incident = incident.clone();
+            move |_| Ok((paginator_meta, vec![incident.clone()]))
+        });
+        let repo = Arc::new(repo);
+
+        let mut app_state = MockTenantIncidentsModule::new();
+        app_state
+            .expect_tenant_incidents_repo()
+            .times(1)
+            .returning(move || repo.clone());
+        app_state.expect_config().times(2).return_const(
+            AppConfigBuilder::default()
+                .auth(
+                    AuthConfigBuilder::default()
+                        .operator_user_ids(vec![operator_id])
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        );
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tenant_incidents::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(list_request(operator_id)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({
+                "meta": paginator_meta,
+                "data": vec![incident]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_forbidden_for_non_operator() {
+        let mut app_state = MockTenantIncidentsModule::new();
+        app_state.expect_tenant_incidents_repo().never();
+        app_state
+            .expect_config()
+            .times(2)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tenant_incidents::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(list_request(Uuid::new_v4())).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
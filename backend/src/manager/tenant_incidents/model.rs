@@ -0,0 +1,33 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const POOL_INIT_FAILED: &str = "pool_init_failed";
+
+#[derive(Serialize, FromRow, Debug, Clone, PartialEq)]
+pub struct TenantIncident {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub incident_type: String,
+    pub message: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
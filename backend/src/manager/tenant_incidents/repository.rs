@@ -0,0 +1,101 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::manager::tenant_incidents::model::TenantIncident;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantIncidentsRepository: Send + Sync {
+    async fn insert(
+        &self,
+        tenant_id: Uuid,
+        incident_type: &str,
+        message: &str,
+    ) -> RepositoryResult<TenantIncident>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<TenantIncident>)>;
+}
+
+#[async_trait]
+impl TenantIncidentsRepository for PgPool {
+    async fn insert(
+        &self,
+        tenant_id: Uuid,
+        incident_type: &str,
+        message: &str,
+    ) -> RepositoryResult<TenantIncident> {
+        Ok(sqlx::query_as::<_, TenantIncident>(
+            r#"
+            INSERT INTO tenant_incidents (tenant_id, incident_type, message)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(incident_type)
+        .bind(message)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<TenantIncident>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tenant_incidents")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let incidents = sqlx::query_as::<_, TenantIncident>(
+            r#"
+            SELECT *
+            FROM tenant_incidents
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            incidents,
+        ))
+    }
+}
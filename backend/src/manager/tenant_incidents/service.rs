@@ -0,0 +1,221 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::email_template::EmailTemplate;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::manager::tenant_incidents::TenantIncidentsModuleInterface;
+use crate::manager::tenant_incidents::model::{POOL_INIT_FAILED, TenantIncident};
+use axum::http::StatusCode;
+use lettre::{address::AddressError, message::Mailbox};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TenantIncidentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Mail transport error: {0}")]
+    MailTransport(String),
+}
+
+impl From<ServiceError> for TenantIncidentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TenantIncidentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TenantIncidentsServiceError> for AppError {
+    fn from(value: TenantIncidentsServiceError) -> Self {
+        match value {
+            TenantIncidentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+type TenantIncidentsServiceResult<T> = Result<T, TenantIncidentsServiceError>;
+
+pub trait TenantIncidentsService {
+    fn get_paged(
+        &self,
+        query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = TenantIncidentsServiceResult<(PaginatorMeta, Vec<TenantIncident>)>> + Send;
+    fn report_pool_init_failure(
+        &self,
+        tenant_id: Uuid,
+        message: &str,
+    ) -> impl Future<Output = TenantIncidentsServiceResult<TenantIncident>> + Send;
+}
+
+impl<'a, T> TenantIncidentsService for Service<'a, T>
+where
+    T: TenantIncidentsModuleInterface,
+{
+    async fn get_paged(
+        &self,
+        query: &ResourceQuery<Empty, Empty>,
+    ) -> TenantIncidentsServiceResult<(PaginatorMeta, Vec<TenantIncident>)> {
+        self.claims()?;
+        Ok(self
+            .module()
+            .tenant_incidents_repo()
+            .get_paged(query)
+            .await?)
+    }
+
+    async fn report_pool_init_failure(
+        &self,
+        tenant_id: Uuid,
+        message: &str,
+    ) -> TenantIncidentsServiceResult<TenantIncident> {
+        let incident = self
+            .module()
+            .tenant_incidents_repo()
+            .insert(tenant_id, POOL_INIT_FAILED, message)
+            .await?;
+        send_incident_email(self.module(), &incident).await?;
+        Ok(incident)
+    }
+}
+
+async fn send_incident_email<T>(
+    module: &T,
+    incident: &TenantIncident,
+) -> TenantIncidentsServiceResult<()>
+where
+    T: TenantIncidentsModuleInterface,
+{
+    let email = EmailTemplate::TenantIncident
+        .render(&json!({
+            "tenant_id": incident.tenant_id,
+            "incident_type": incident.incident_type,
+            "created_at": incident.created_at,
+            "message": incident.message,
+        }))
+        .map_err(|e| TenantIncidentsServiceError::MailTransport(e.to_string()))?
+        .into_message(
+            Mailbox::new(
+                Some(module.config().mail().default_from_name().to_owned()),
+                module
+                    .config()
+                    .mail()
+                    .default_from()
+                    .parse()
+                    .map_err(|e: AddressError| {
+                        TenantIncidentsServiceError::MailTransport(e.to_string())
+                    })?,
+            ),
+            Mailbox::new(
+                None,
+                module
+                    .config()
+                    .mail()
+                    .default_notification_email()
+                    .parse()
+                    .map_err(|e: AddressError| {
+                        TenantIncidentsServiceError::MailTransport(e.to_string())
+                    })?,
+            ),
+        )
+        .map_err(|e| TenantIncidentsServiceError::MailTransport(e.to_string()))?;
+
+    match module.send(email).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TenantIncidentsServiceError::MailTransport(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::manager::tenant_incidents::repository::MockTenantIncidentsRepository;
+    use crate::manager::tenant_incidents::tests::MockTenantIncidentsModule;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_report_pool_init_failure_records_and_notifies() {
+        let tenant_id = Uuid::new_v4();
+        let incident = TenantIncident {
+            id: Uuid::new_v4(),
+            tenant_id,
+            incident_type: POOL_INIT_FAILED.to_string(),
+            message: "connection refused".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockTenantIncidentsRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .with(
+                eq(tenant_id),
+                eq(POOL_INIT_FAILED),
+                eq("connection refused"),
+            )
+            .returning({
+                let incident = incident.clone();
+                move |_, _, _| Ok(incident.clone())
+            });
+        let repo = Arc::new(repo);
+
+        let mut module = MockTenantIncidentsModule::new();
+        module
+            .expect_tenant_incidents_repo()
+            .times(1)
+            .returning(move || repo.clone());
+        module
+            .expect_config()
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        module.expect_send().times(1).returning(|_| Ok(None));
+
+        let result = Service::new(None, Arc::new(module))
+            .report_pool_init_failure(tenant_id, "connection refused")
+            .await
+            .unwrap();
+
+        assert_eq!(result, incident);
+    }
+}
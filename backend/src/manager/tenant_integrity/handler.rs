@@ -0,0 +1,212 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::manager::tenant_integrity::TenantIntegrityModuleInterface;
+use crate::manager::tenant_integrity::dto::IntegrityCleanup;
+use crate::manager::tenant_integrity::service::TenantIntegrityService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn report<M: TenantIntegrityModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tenant_integrity_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tenant_integrity_module.clone());
+    let result = map_handler_err(
+        service.report(payload.uuid).await,
+        tenant_integrity_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tenant_integrity_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn cleanup<M: TenantIntegrityModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tenant_integrity_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<IntegrityCleanup>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tenant_integrity_module.clone());
+    let result = map_handler_err(
+        service.cleanup(payload.uuid, &payload.check).await,
+        tenant_integrity_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tenant_integrity_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::auth_config::tests::AuthConfigBuilder;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenant_integrity::model::INTEGRITY_CHECKS;
+    use crate::manager::tenant_integrity::{
+        self, repository::MockTenantIntegrityRepository, tests::MockTenantIntegrityModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(module: MockTenantIntegrityModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(tenant_integrity::routes::routes(Arc::new(module))),
+        )
+    }
+
+    fn operator_module(operator_id: Uuid) -> MockTenantIntegrityModule {
+        let mut module = MockTenantIntegrityModule::new();
+        module.expect_config().times(2).return_const(
+            AppConfigBuilder::default()
+                .auth(
+                    AuthConfigBuilder::default()
+                        .operator_user_ids(vec![operator_id])
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        );
+        module
+    }
+
+    fn request(method: &str, uri: &str, user_id: Uuid, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(Some(user_id), None)),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_report_success() {
+        let operator_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let orphan_id = Uuid::new_v4();
+
+        let mut repo = MockTenantIntegrityRepository::new();
+        repo.expect_find()
+            .times(INTEGRITY_CHECKS.len())
+            .returning(move |sql| {
+                if sql == INTEGRITY_CHECKS[1].detect_sql {
+                    Ok(vec![orphan_id])
+                } else {
+                    Ok(vec![])
+                }
+            });
+        let repo = Arc::new(repo);
+
+        let mut module = operator_module(operator_id);
+        module
+            .expect_tenant_integrity_repo()
+            .times(1)
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+
+        let response = app(module)
+            .oneshot(request(
+                "GET",
+                &format!("/api/admin/tenant_integrity/report?uuid={tenant_id}"),
+                operator_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["issue_count"], json!(1));
+        assert_eq!(body["data"]["checks"][1]["record_ids"], json!([orphan_id]));
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_unknown_check() {
+        let operator_id = Uuid::new_v4();
+        let mut module = operator_module(operator_id);
+        module.expect_tenant_integrity_repo().never();
+
+        let response = app(module)
+            .oneshot(request(
+                "POST",
+                "/api/admin/tenant_integrity/cleanup",
+                operator_id,
+                json!({"uuid": Uuid::new_v4(), "check": "drop_everything"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_report_forbidden_for_non_operator() {
+        let mut module = MockTenantIntegrityModule::new();
+        module.expect_tenant_integrity_repo().never();
+        module
+            .expect_config()
+            .times(2)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = app(module)
+            .oneshot(request(
+                "GET",
+                &format!("/api/admin/tenant_integrity/report?uuid={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Serialize;
+use uuid::Uuid;
+
+pub struct IntegrityCheck {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cleanup: &'static str,
+    pub detect_sql: &'static str,
+    pub cleanup_sql: &'static str,
+}
+
+// NOTE: polymorphic connections (comments, tags, watches) have no foreign keys,
+// so their targets are matched against this list of live records
+macro_rules! live_records {
+    () => {
+        r#"
+        SELECT id, 'customers' AS record_type FROM customers WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'products' FROM products WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'services' FROM services WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'tasks' FROM tasks WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'warehouses' FROM warehouses WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'worksheets' FROM worksheets WHERE deleted_at IS NULL
+        UNION ALL SELECT id, 'projects' FROM projects WHERE deleted_at IS NULL
+        "#
+    };
+}
+
+macro_rules! inventory_with_deleted_product {
+    () => {
+        r#"
+        SELECT inventory.id
+        FROM inventory
+        JOIN products ON inventory.product_id = products.id
+        WHERE inventory.deleted_at IS NULL
+          AND products.deleted_at IS NOT NULL
+        "#
+    };
+}
+
+macro_rules! tasks_with_deleted_worksheet {
+    () => {
+        r#"
+        SELECT tasks.id
+        FROM tasks
+        JOIN worksheets ON tasks.worksheet_id = worksheets.id
+        WHERE tasks.deleted_at IS NULL
+          AND worksheets.deleted_at IS NOT NULL
+        "#
+    };
+}
+
+macro_rules! reservations_with_deleted_inventory {
+    () => {
+        r#"
+        SELECT inventory_reservations.id
+        FROM inventory_reservations
+        JOIN inventory ON inventory_reservations.inventory_id = inventory.id
+        WHERE inventory_reservations.status = 'active'
+          AND inventory.deleted_at IS NOT NULL
+        "#
+    };
+}
+
+macro_rules! orphaned_comments {
+    () => {
+        concat!(
+            r#"
+            SELECT comments.id
+            FROM comments
+            WHERE comments.deleted_at IS NULL
+              AND comments.commentable_type IN ('customers', 'products', 'services', 'tasks', 'warehouses', 'worksheets', 'projects')
+              AND NOT EXISTS (SELECT 1 FROM ("#,
+            live_records!(),
+            r#") AS live
+                              WHERE live.id = comments.commentable_id
+                                AND live.record_type = comments.commentable_type)
+            "#
+        )
+    };
+}
+
+macro_rules! orphaned_tag_connections {
+    () => {
+        concat!(
+            r#"
+            SELECT taggings.id
+            FROM taggings
+            WHERE NOT EXISTS (SELECT 1 FROM ("#,
+            live_records!(),
+            r#") AS live
+                              WHERE live.id = taggings.taggable_id
+                                AND live.record_type = taggings.taggable_type)
+            "#
+        )
+    };
+}
+
+macro_rules! orphaned_watches {
+    () => {
+        concat!(
+            r#"
+            SELECT watches.id
+            FROM watches
+            WHERE NOT EXISTS (SELECT 1 FROM ("#,
+            live_records!(),
+            r#") AS live
+                              WHERE live.id = watches.watchable_id
+                                AND live.record_type = watches.watchable_type)
+            "#
+        )
+    };
+}
+
+pub const INTEGRITY_CHECKS: [IntegrityCheck; 6] = [
+    IntegrityCheck {
+        name: "inventory_deleted_product",
+        description: "Aktív készletsor törölt termékre hivatkozik",
+        cleanup: "A készletsorok törlése",
+        detect_sql: inventory_with_deleted_product!(),
+        cleanup_sql: concat!(
+            "UPDATE inventory SET deleted_at = now() WHERE id IN (",
+            inventory_with_deleted_product!(),
+            ")"
+        ),
+    },
+    IntegrityCheck {
+        name: "tasks_deleted_worksheet",
+        description: "Aktív feladat törölt munkalaphoz tartozik",
+        cleanup: "A feladatok törlése",
+        detect_sql: tasks_with_deleted_worksheet!(),
+        cleanup_sql: concat!(
+            "UPDATE tasks SET deleted_at = now() WHERE id IN (",
+            tasks_with_deleted_worksheet!(),
+            ")"
+        ),
+    },
+    IntegrityCheck {
+        name: "reservations_deleted_inventory",
+        description: "Aktív foglalás törölt készletsorra hivatkozik",
+        cleanup: "A foglalások visszavonása",
+        detect_sql: reservations_with_deleted_inventory!(),
+        cleanup_sql: concat!(
+            "UPDATE inventory_reservations SET status = 'cancelled' WHERE id IN (",
+            reservations_with_deleted_inventory!(),
+            ")"
+        ),
+    },
+    IntegrityCheck {
+        name: "orphaned_comments",
+        description: "Megjegyzés nem létező vagy törölt rekordhoz tartozik",
+        cleanup: "A megjegyzések törlése",
+        detect_sql: orphaned_comments!(),
+        cleanup_sql: concat!(
+            "UPDATE comments SET deleted_at = now() WHERE id IN (",
+            orphaned_comments!(),
+            ")"
+        ),
+    },
+    IntegrityCheck {
+        name: "orphaned_tag_connections",
+        description: "Címke nem létező vagy törölt rekordhoz van kapcsolva",
+        cleanup: "A címke kapcsolatok törlése",
+        detect_sql: orphaned_tag_connections!(),
+        cleanup_sql: concat!(
+            "DELETE FROM taggings WHERE id IN (",
+            orphaned_tag_connections!(),
+            ")"
+        ),
+    },
+    IntegrityCheck {
+        name: "orphaned_watches",
+        description: "Követés nem létező vagy törölt rekordra mutat",
+        cleanup: "A követések törlése",
+        detect_sql: orphaned_watches!(),
+        cleanup_sql: concat!(
+            "DELETE FROM watches WHERE id IN (",
+            orphaned_watches!(),
+            ")"
+        ),
+    },
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IntegrityCheckResult {
+    pub check: &'static str,
+    pub description: &'static str,
+    pub cleanup: &'static str,
+    pub count: usize,
+    pub record_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IntegrityReport {
+    pub tenant_id: Uuid,
+    pub issue_count: usize,
+    pub checks: Vec<IntegrityCheckResult>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IntegrityCleanupResult {
+    pub tenant_id: Uuid,
+    pub check: &'static str,
+    pub affected: u64,
+}
@@ -0,0 +1,69 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::{RepositoryError, RepositoryResult};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantIntegrityRepository: Send + Sync {
+    async fn find(&self, detect_sql: &'static str) -> RepositoryResult<Vec<Uuid>>;
+    async fn cleanup(
+        &self,
+        detect_sql: &'static str,
+        cleanup_sql: &'static str,
+    ) -> RepositoryResult<u64>;
+}
+
+#[async_trait]
+impl TenantIntegrityRepository for PgPool {
+    async fn find(&self, detect_sql: &'static str) -> RepositoryResult<Vec<Uuid>> {
+        Ok(sqlx::query_scalar::<_, Uuid>(detect_sql)
+            .fetch_all(self)
+            .await?)
+    }
+
+    async fn cleanup(
+        &self,
+        detect_sql: &'static str,
+        cleanup_sql: &'static str,
+    ) -> RepositoryResult<u64> {
+        let mut tx = self.begin().await?;
+        let affected = sqlx::query(cleanup_sql)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        let remaining = sqlx::query_scalar::<_, Uuid>(detect_sql)
+            .fetch_all(&mut *tx)
+            .await?;
+        if !remaining.is_empty() {
+            tx.rollback().await?;
+            return Err(RepositoryError::Custom(format!(
+                "cleanup left {} broken references behind, rolled back",
+                remaining.len()
+            )));
+        }
+        tx.commit().await?;
+        Ok(affected)
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::manager::tenant_integrity::TenantIntegrityModuleInterface;
+use crate::manager::tenant_integrity::model::{
+    INTEGRITY_CHECKS, IntegrityCheckResult, IntegrityCleanupResult, IntegrityReport,
+};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::{Level, info};
+use uuid::Uuid;
+
+const MAX_REPORTED_RECORDS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum TenantIntegrityServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Ismeretlen ellenőrzés: {0}")]
+    UnknownCheck(String),
+}
+
+impl From<ServiceError> for TenantIntegrityServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TenantIntegrityServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TenantIntegrityServiceError> for AppError {
+    fn from(value: TenantIntegrityServiceError) -> Self {
+        match value {
+            TenantIntegrityServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TenantIntegrityServiceError::UnknownCheck(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+type TenantIntegrityServiceResult<T> = Result<T, TenantIntegrityServiceError>;
+
+pub trait TenantIntegrityService {
+    fn report(
+        &self,
+        tenant_id: Uuid,
+    ) -> impl Future<Output = TenantIntegrityServiceResult<IntegrityReport>> + Send;
+    fn cleanup(
+        &self,
+        tenant_id: Uuid,
+        check: &str,
+    ) -> impl Future<Output = TenantIntegrityServiceResult<IntegrityCleanupResult>> + Send;
+}
+
+impl<'a, T> TenantIntegrityService for Service<'a, T>
+where
+    T: TenantIntegrityModuleInterface,
+{
+    async fn report(&self, tenant_id: Uuid) -> TenantIntegrityServiceResult<IntegrityReport> {
+        let repo = self.module().tenant_integrity_repo(tenant_id)?;
+        let mut checks = Vec::with_capacity(INTEGRITY_CHECKS.len());
+        for check in INTEGRITY_CHECKS.iter() {
+            let mut record_ids = repo.find(check.detect_sql).await?;
+            let count = record_ids.len();
+            record_ids.truncate(MAX_REPORTED_RECORDS);
+            checks.push(IntegrityCheckResult {
+                check: check.name,
+                description: check.description,
+                cleanup: check.cleanup,
+                count,
+                record_ids,
+            });
+        }
+        Ok(IntegrityReport {
+            tenant_id,
+            issue_count: checks.iter().map(|check| check.count).sum(),
+            checks,
+        })
+    }
+
+    async fn cleanup(
+        &self,
+        tenant_id: Uuid,
+        check: &str,
+    ) -> TenantIntegrityServiceResult<IntegrityCleanupResult> {
+        let check = INTEGRITY_CHECKS
+            .iter()
+            .find(|integrity_check| integrity_check.name == check)
+            .ok_or_else(|| TenantIntegrityServiceError::UnknownCheck(check.to_string()))?;
+        let affected = self
+            .module()
+            .tenant_integrity_repo(tenant_id)?
+            .cleanup(check.detect_sql, check.cleanup_sql)
+            .await?;
+        info!(
+            "Integrity cleanup {} on tenant {}: {} records",
+            check.name, tenant_id, affected
+        );
+        Ok(IntegrityCleanupResult {
+            tenant_id,
+            check: check.name,
+            affected,
+        })
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::{AppState, BaseModule};
+use crate::manager::tenant_limits::repository::TenantLimitsRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+pub mod dto;
+pub mod model;
+pub(crate) mod repository;
+pub mod service;
+
+pub trait TenantLimitsModuleInterface: BaseModule {
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+}
+
+impl<P, T> TenantLimitsModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync> {
+        self.get_main_pool()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub TenantLimitsModule {}
+        impl ConfigProvider for TenantLimitsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for TenantLimitsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for TenantLimitsModule {}
+        impl TenantLimitsModuleInterface for TenantLimitsModule {
+            fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+        }
+    );
+}
@@ -0,0 +1,77 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Serialize, FromRow, Debug, Clone, Default, PartialEq)]
+pub struct TenantLimits {
+    pub tenant_id: Uuid,
+    pub max_users: Option<i32>,
+    pub max_products: Option<i32>,
+    pub max_storage_mb: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TenantLimits {
+    pub fn allows_users(&self, current: i64, new_users: i64) -> bool {
+        self.max_users
+            .is_none_or(|max| current.saturating_add(new_users) <= i64::from(max))
+    }
+    pub fn allows_products(&self, current: i64) -> bool {
+        self.max_products.is_none_or(|max| current < i64::from(max))
+    }
+    pub fn allows_storage(&self, current_bytes: i64, new_bytes: i64) -> bool {
+        self.max_storage_mb
+            .is_none_or(|max| current_bytes.saturating_add(new_bytes) <= max * 1024 * 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_by_default() {
+        let limits = TenantLimits::default();
+        assert!(limits.allows_users(i64::MAX, 1));
+        assert!(limits.allows_products(i64::MAX));
+        assert!(limits.allows_storage(i64::MAX, 1));
+    }
+
+    #[test]
+    fn test_limits_reached() {
+        let limits = TenantLimits {
+            max_users: Some(2),
+            max_products: Some(10),
+            max_storage_mb: Some(1),
+            ..Default::default()
+        };
+        assert!(limits.allows_users(1, 1));
+        assert!(limits.allows_users(0, 2));
+        assert!(!limits.allows_users(2, 1));
+        assert!(!limits.allows_users(0, 3));
+        assert!(limits.allows_products(9));
+        assert!(!limits.allows_products(10));
+        assert!(limits.allows_storage(0, 1024 * 1024));
+        assert!(!limits.allows_storage(1, 1024 * 1024));
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::manager::tenant_limits::dto::SetTenantLimits;
+use crate::manager::tenant_limits::model::TenantLimits;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantLimitsRepository: Send + Sync {
+    async fn get_by_tenant_id(&self, tenant_id: Uuid) -> RepositoryResult<Option<TenantLimits>>;
+    async fn upsert(&self, limits: &SetTenantLimits) -> RepositoryResult<TenantLimits>;
+}
+
+#[async_trait]
+impl TenantLimitsRepository for PgPool {
+    async fn get_by_tenant_id(&self, tenant_id: Uuid) -> RepositoryResult<Option<TenantLimits>> {
+        Ok(
+            sqlx::query_as::<_, TenantLimits>("SELECT * FROM tenant_limits WHERE tenant_id = $1")
+                .bind(tenant_id)
+                .fetch_optional(self)
+                .await?,
+        )
+    }
+
+    async fn upsert(&self, limits: &SetTenantLimits) -> RepositoryResult<TenantLimits> {
+        Ok(sqlx::query_as::<_, TenantLimits>(
+            r#"
+            INSERT INTO tenant_limits (tenant_id, max_users, max_products, max_storage_mb)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id) DO UPDATE SET
+                max_users = EXCLUDED.max_users,
+                max_products = EXCLUDED.max_products,
+                max_storage_mb = EXCLUDED.max_storage_mb
+            RETURNING *
+            "#,
+        )
+        .bind(limits.tenant_id)
+        .bind(limits.max_users)
+        .bind(limits.max_products)
+        .bind(limits.max_storage_mb)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::service::Service;
+use crate::manager::tenant_limits::TenantLimitsModuleInterface;
+use crate::manager::tenant_limits::dto::SetTenantLimits;
+use crate::manager::tenant_limits::model::TenantLimits;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TenantLimitsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+type TenantLimitsServiceResult<T> = Result<T, TenantLimitsServiceError>;
+
+pub trait TenantLimitsService {
+    fn get(
+        &self,
+        tenant_id: Uuid,
+    ) -> impl Future<Output = TenantLimitsServiceResult<Option<TenantLimits>>> + Send;
+    fn set(
+        &self,
+        payload: &SetTenantLimits,
+    ) -> impl Future<Output = TenantLimitsServiceResult<TenantLimits>> + Send;
+}
+
+impl<'a, T> TenantLimitsService for Service<'a, T>
+where
+    T: TenantLimitsModuleInterface,
+{
+    async fn get(&self, tenant_id: Uuid) -> TenantLimitsServiceResult<Option<TenantLimits>> {
+        Ok(self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?)
+    }
+
+    async fn set(&self, payload: &SetTenantLimits) -> TenantLimitsServiceResult<TenantLimits> {
+        Ok(self.module().tenant_limits_repo().upsert(payload).await?)
+    }
+}
@@ -17,6 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::config::BasicDatabaseConfig;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
 use crate::common::value_object::{ValueObjectError, ValueObjectRequired};
 use crate::manager::auth::dto::claims::Claims;
@@ -32,6 +33,8 @@ use uuid::Uuid;
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CreateTenantHelper {
     pub name: String,
+    #[serde(default)]
+    pub seed_defaults: bool,
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -79,6 +82,7 @@ impl From<ValueObjectError> for CreateTenantError {
 #[allow(dead_code)]
 pub struct CreateTenant {
     pub name: ValueObjectRequired<Name>,
+    pub seed_defaults: bool,
 }
 
 impl TryFrom<CreateTenantHelper> for CreateTenant {
@@ -94,7 +98,10 @@ impl TryFrom<CreateTenantHelper> for CreateTenant {
             });
 
         if error.is_empty() {
-            Ok(CreateTenant { name: name? })
+            Ok(CreateTenant {
+                name: name?,
+                seed_defaults: value.seed_defaults,
+            })
         } else {
             Err(error)
         }
@@ -177,6 +184,44 @@ pub struct TenantIdRequest {
     pub uuid: Uuid,
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TenantHealth {
+    pub tenant_id: Uuid,
+    pub healthy: bool,
+    pub latency_ms: Option<u64>,
+    pub schema_version: Option<i64>,
+    pub latest_schema_version: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RelinkTenant {
+    pub tenant_id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub db_host: String,
+    pub db_port: u16,
+    pub db_name: String,
+    pub db_user: String,
+    pub db_password: String,
+    pub db_max_pool_size: Option<u32>,
+    pub db_ssl_mode: Option<String>,
+}
+
+impl From<&RelinkTenant> for BasicDatabaseConfig {
+    fn from(value: &RelinkTenant) -> Self {
+        Self {
+            host: value.db_host.clone(),
+            port: value.db_port,
+            username: value.db_user.clone(),
+            password: value.db_password.clone(),
+            database: value.db_name.clone(),
+            max_pool_size: value.db_max_pool_size,
+            ssl_mode: value.db_ssl_mode.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct NewTokenResponse {
     pub token: String,
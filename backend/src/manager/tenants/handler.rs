@@ -101,6 +101,42 @@ pub async fn list<M: TenantsModuleInterface>(
     .into_response())
 }
 
+pub async fn mine<M: TenantsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tenants_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tenants_module.clone());
+    let result = map_handler_err(service.mine().await, tenants_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tenants_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn health<M: TenantsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tenants_module): State<Arc<M>>,
+    Query(payload): Query<TenantIdRequest>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tenants_module.clone());
+    let result =
+        map_handler_err(service.health(payload.uuid).await, tenants_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tenants_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn activate<M: TenantsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(tenants_module): State<Arc<M>>,
@@ -141,6 +177,7 @@ pub async fn delete<M: TenantsModuleInterface>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::config::sandbox_config::tests::SandboxConfigBuilder;
     use crate::common::config::tests::AppConfigBuilder;
     use crate::common::dto::PaginatorMeta;
     use crate::common::handler::tests::{
@@ -150,8 +187,11 @@ mod tests {
     use crate::manager::auth::dto::claims::Claims;
     use crate::manager::tenants;
     use crate::manager::tenants::dto::PublicTenant;
-    use crate::manager::tenants::model::{Tenant, UserTenant};
-    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::manager::tenants::model::{Tenant, TenantMembership, UserTenant};
+    use crate::manager::tenants::repository::{
+        MockTenantMarkerRepository, MockTenantProvisioner, MockTenantSeedRepository,
+        MockTenantsRepository,
+    };
     use crate::manager::tenants::tests::MockTenantsModule;
     use crate::manager::users::model::User as ManagerUser;
     use crate::manager::users::repository::MockUsersRepository as MockManagerUserRepository;
@@ -198,7 +238,7 @@ mod tests {
         tenants_repo
             .expect_setup_managed()
             .times(1)
-            .withf(|_, name, _, _, _| name == "test")
+            .withf(|_, name, _, cluster, _| name == "test" && cluster == "default")
             .returning(move |_, _, _, _, _| {
                 Ok(Tenant {
                     id: new_tenant_id,
@@ -211,6 +251,7 @@ mod tests {
                     db_password: "password".to_string(),
                     db_max_pool_size: 5,
                     db_ssl_mode: "disable".to_string(),
+                    db_cluster: Some("default".to_string()),
                     created_at: now,
                     updated_at: now,
                     deleted_at: None,
@@ -235,8 +276,31 @@ mod tests {
             .times(1)
             .returning(Ok);
 
+        let mut tenant_marker_repo = MockTenantMarkerRepository::new();
+        tenant_marker_repo
+            .expect_write_marker()
+            .with(eq(new_tenant_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut tenant_seed_repo = MockTenantSeedRepository::new();
+        tenant_seed_repo
+            .expect_seed_defaults()
+            .with(eq(sub))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut tenant_provisioner = MockTenantProvisioner::new();
+        tenant_provisioner
+            .expect_provision()
+            .times(1)
+            .withf(|_, db_password| db_password.len() == 40)
+            .returning(|_, _| Ok(()));
+        tenant_provisioner.expect_deprovision().never();
+
         let payload = serde_json::to_string(&CreateTenantHelper {
             name: "test".to_string(),
+            seed_defaults: true,
         })
         .unwrap();
 
@@ -254,12 +318,15 @@ mod tests {
         let tenants_repo = Arc::new(tenants_repo);
         let tenant_user_repo = Arc::new(tenant_user_repo);
         let manager_user_repo = Arc::new(manager_user_repo);
+        let tenant_marker_repo = Arc::new(tenant_marker_repo);
+        let tenant_seed_repo = Arc::new(tenant_seed_repo);
+        let tenant_provisioner = Arc::new(tenant_provisioner);
 
         let test_config = AppConfigBuilder::default().build().unwrap();
         let mut tenants_module = MockTenantsModule::new();
         tenants_module
             .expect_config()
-            .times(2)
+            .times(3)
             .return_const(test_config);
         tenants_module
             .expect_tenants_repo()
@@ -282,6 +349,20 @@ mod tests {
             .expect_migrate_tenant_db()
             .times(1)
             .returning(|_| Box::pin(ready(Ok(()))));
+        tenants_module
+            .expect_tenant_marker_repo()
+            .times(1)
+            .with(eq(new_tenant_id))
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        tenants_module
+            .expect_tenant_seed_repo()
+            .times(1)
+            .with(eq(new_tenant_id))
+            .returning(move |_| Ok(tenant_seed_repo.clone()));
+        tenants_module
+            .expect_tenant_provisioner()
+            .times(1)
+            .returning(move |_| tenant_provisioner.clone());
 
         let app = Router::new().nest(
             "/api",
@@ -320,6 +401,7 @@ mod tests {
     async fn test_create_managed_unauthorized_expired() {
         let payload = serde_json::to_string(&CreateTenantHelper {
             name: "test".to_string(),
+            seed_defaults: false,
         })
         .unwrap();
 
@@ -353,6 +435,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -364,6 +447,7 @@ mod tests {
     async fn test_create_managed_unauthorized_invalid_signature() {
         let payload = serde_json::to_string(&CreateTenantHelper {
             name: "test".to_string(),
+            seed_defaults: false,
         })
         .unwrap();
 
@@ -397,6 +481,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -408,6 +493,7 @@ mod tests {
     async fn test_create_managed_unauthorized_missing() {
         let payload = serde_json::to_string(&CreateTenantHelper {
             name: "test".to_string(),
+            seed_defaults: false,
         })
         .unwrap();
 
@@ -469,6 +555,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -510,6 +597,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -579,6 +667,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -620,6 +709,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -675,6 +765,7 @@ mod tests {
             db_password: "test_password".to_string(),
             db_max_pool_size: 3,
             db_ssl_mode: "verify-full".to_string(),
+            db_cluster: None,
             created_at: utc_now,
             updated_at: utc_now,
             deleted_at: None,
@@ -728,6 +819,76 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_mine_success() {
+        let sub = Uuid::new_v4();
+        let active_tenant_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let memberships = vec![
+            TenantMembership {
+                tenant_id: active_tenant_id,
+                name: "Aktív".to_string(),
+                is_self_hosted: false,
+                role: "owner".to_string(),
+                last_activated: utc_now,
+                is_active: true,
+            },
+            TenantMembership {
+                tenant_id: Uuid::new_v4(),
+                name: "Régebbi".to_string(),
+                is_self_hosted: false,
+                role: "member".to_string(),
+                last_activated: utc_now - chrono::Duration::days(3),
+                is_active: false,
+            },
+        ];
+
+        let mut repo = MockTenantsRepository::new();
+        repo.expect_get_memberships_by_user_id()
+            .times(1)
+            .with(eq(sub), eq(Some(active_tenant_id)))
+            .returning({
+                let memberships = memberships.clone();
+                move |_, _| Ok(memberships.clone())
+            });
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri("/api/tenants/mine")
+            .body("".to_string())
+            .unwrap();
+
+        let mut tenants_module = MockTenantsModule::new();
+        let repo = Arc::new(repo);
+        tenants_module
+            .expect_tenants_repo()
+            .times(1)
+            .returning(move || repo.clone());
+        tenants_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tenants::routes::routes(Arc::new(tenants_module))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": memberships})
+        );
+    }
+
     #[tokio::test]
     async fn test_list_unauthorized_expired() {
         let request = Request::builder()
@@ -760,6 +921,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -799,6 +961,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -958,6 +1121,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1005,6 +1169,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1065,6 +1230,7 @@ mod tests {
                     db_password: "test_password".to_string(),
                     db_max_pool_size: 3,
                     db_ssl_mode: "verify-full".to_string(),
+                    db_cluster: None,
                     created_at: utc_now,
                     updated_at: utc_now,
                     deleted_at: None,
@@ -1085,7 +1251,7 @@ mod tests {
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
-            .times(2)
+            .times(3)
             .return_const(test_config.clone());
 
         let app = Router::new().nest(
@@ -1135,6 +1301,50 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_delete_forbidden_in_sandbox() {
+        let sub = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+
+        let mut app_state = MockTenantsModule::new();
+        app_state.expect_tenants_repo().never();
+        app_state.expect_delete_tenant_pool().never();
+        let test_config = AppConfigBuilder::default()
+            .sandbox(
+                SandboxConfigBuilder::default()
+                    .sandbox_enabled(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        app_state.expect_config().return_const(test_config);
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tenants::routes::routes(Arc::new(app_state))),
+        );
+
+        let payload = json!({
+            "uuid": tenant_id
+        });
+
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(Some(sub), None)),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/tenants/delete")
+            .body(Body::from(payload.to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
     #[tokio::test]
     async fn test_delete_unauthorized_expired() {
         let tenant_id = Uuid::new_v4();
@@ -1169,6 +1379,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1210,6 +1421,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -19,10 +19,15 @@
 
 use crate::common::AppState;
 use crate::common::BaseModule;
+use crate::common::config::BasicDatabaseConfig;
 use crate::common::database::DatabaseMigrator;
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
-use crate::manager::tenants::repository::TenantsRepository;
+use crate::manager::tenant_limits::repository::TenantLimitsRepository;
+use crate::manager::tenants::repository::{
+    PgTenantProvisioner, TenantMarkerRepository, TenantProvisioner, TenantSeedRepository,
+    TenantsRepository,
+};
 use crate::manager::users::repository::UsersRepository as ManagerUserRepository;
 use crate::tenant::users::repository::UsersRepository as TenantUserRepository;
 use lettre::{
@@ -32,12 +37,12 @@ use lettre::{
 use std::sync::Arc;
 use uuid::Uuid;
 
-pub(crate) mod dto;
+pub mod dto;
 mod handler;
-pub(crate) mod model;
+pub mod model;
 pub(crate) mod repository;
 pub(crate) mod routes;
-mod service;
+pub mod service;
 pub(crate) mod types;
 
 pub trait TenantsModuleInterface: DatabaseMigrator + PoolManager + BaseModule {
@@ -47,6 +52,19 @@ pub trait TenantsModuleInterface: DatabaseMigrator + PoolManager + BaseModule {
         tenant_id: Uuid,
     ) -> RepositoryResult<Arc<dyn TenantUserRepository + Send + Sync>>;
     fn manager_user_repo(&self) -> Arc<dyn ManagerUserRepository + Send + Sync>;
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+    fn tenant_marker_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn TenantMarkerRepository + Send + Sync>>;
+    fn tenant_seed_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn TenantSeedRepository + Send + Sync>>;
+    fn tenant_provisioner(
+        &self,
+        cluster: &BasicDatabaseConfig,
+    ) -> Arc<dyn TenantProvisioner + Send + Sync>;
 }
 
 impl<P, T> TenantsModuleInterface for AppState<P, T>
@@ -66,6 +84,27 @@ where
     fn manager_user_repo(&self) -> Arc<dyn ManagerUserRepository + Send + Sync> {
         self.get_main_pool()
     }
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync> {
+        self.get_main_pool()
+    }
+    fn tenant_marker_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn TenantMarkerRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn tenant_seed_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn TenantSeedRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn tenant_provisioner(
+        &self,
+        cluster: &BasicDatabaseConfig,
+    ) -> Arc<dyn TenantProvisioner + Send + Sync> {
+        Arc::new(PgTenantProvisioner::new(cluster.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +166,19 @@ pub mod tests {
                 tenant_id: Uuid,
             ) -> RepositoryResult<Arc<dyn TenantUserRepository + Send + Sync>>;
             fn manager_user_repo(&self) -> Arc<dyn ManagerUserRepository + Send + Sync>;
+            fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+            fn tenant_marker_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn TenantMarkerRepository + Send + Sync>>;
+            fn tenant_seed_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn TenantSeedRepository + Send + Sync>>;
+            fn tenant_provisioner(
+                &self,
+                cluster: &BasicDatabaseConfig,
+            ) -> Arc<dyn TenantProvisioner + Send + Sync>;
         }
     );
 }
@@ -33,11 +33,29 @@ pub struct Tenant {
     pub db_password: String,
     pub db_max_pool_size: i32,
     pub db_ssl_mode: String,
+    pub db_cluster: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+#[derive(Serialize, FromRow, Debug, Clone, PartialEq)]
+pub struct TenantMembership {
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub is_self_hosted: bool,
+    pub role: String,
+    pub last_activated: chrono::DateTime<chrono::Utc>,
+    pub is_active: bool,
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct ClusterUsage {
+    pub db_cluster: String,
+    pub tenant_count: i64,
+    pub last_created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Serialize, FromRow, Debug, Clone, Default)]
 pub struct UserTenant {
     pub id: Uuid,
@@ -17,22 +17,26 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::config::{
-    AppConfig, BasicDatabaseConfig, database_config::DatabasePoolSizeProvider,
+use crate::common::config::BasicDatabaseConfig;
+use crate::common::config::database_config::{
+    DatabasePgSslModeProvider, DatabasePoolSizeProvider, DatabaseUrlProvider,
 };
+use crate::common::crypto::encrypt_secret;
 use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::types::DdlParameter;
 use crate::common::value_object::ValueObjectRequired;
 use crate::manager::auth::dto::claims::Claims;
-use crate::manager::tenants::model::{Tenant, UserTenant};
+use crate::manager::tenants::model::{ClusterUsage, Tenant, TenantMembership, UserTenant};
 use crate::manager::tenants::types::{TenantFilterBy, TenantOrderBy};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
-use sqlx::{AssertSqlSafe, PgConnection};
+use sqlx::postgres::PgConnectOptions;
+use sqlx::{AssertSqlSafe, Connection, PgConnection};
 use sqlx::{Error, PgPool};
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
@@ -46,9 +50,17 @@ pub trait TenantsRepository: Send + Sync {
         uuid: Uuid,
         name: &str,
         db_config: &BasicDatabaseConfig,
+        db_cluster: &str,
         claims: &Claims,
-        app_config: &AppConfig,
     ) -> RepositoryResult<Tenant>;
+    async fn get_cluster_usage(&self) -> RepositoryResult<Vec<ClusterUsage>>;
+    async fn get_db_passwords(&self) -> RepositoryResult<Vec<(Uuid, String)>>;
+    async fn replace_db_password(
+        &self,
+        id: Uuid,
+        current: &str,
+        replacement: &str,
+    ) -> RepositoryResult<bool>;
     #[allow(dead_code)]
     async fn get_all_by_user_id(
         &self,
@@ -56,13 +68,79 @@ pub trait TenantsRepository: Send + Sync {
         query_params: &ResourceQuery<TenantOrderBy, TenantFilterBy>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<Tenant>)>;
     async fn get_all(&self) -> RepositoryResult<Vec<Tenant>>;
+    async fn get_memberships_by_user_id(
+        &self,
+        user_id: Uuid,
+        active_tenant_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<TenantMembership>>;
 
     async fn get_user_active_tenant_by_id(
         &self,
         user_id: Uuid,
         tenant_id: Uuid,
     ) -> RepositoryResult<Option<UserTenant>>;
+    async fn get_role(&self, user_id: Uuid, tenant_id: Uuid) -> RepositoryResult<Option<String>>;
     async fn delete(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Tenant>;
+
+    async fn relink(
+        &self,
+        tenant_id: Uuid,
+        name: &str,
+        db_config: &BasicDatabaseConfig,
+        owner_id: Uuid,
+    ) -> RepositoryResult<Tenant>;
+
+    async fn reconcile_memberships(
+        &self,
+        tenant_id: Uuid,
+        owner_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> RepositoryResult<u64>;
+}
+
+const TENANT_SEEDERS: &[(&str, &str)] = &[
+    (
+        "currencies",
+        include_str!("../../../seeds/tenant/0001_currencies.sql"),
+    ),
+    (
+        "hungarian_vat_rates",
+        include_str!("../../../seeds/tenant/0002_hungarian_vat_rates.sql"),
+    ),
+    (
+        "default_warehouse",
+        include_str!("../../../seeds/tenant/0003_default_warehouse.sql"),
+    ),
+    (
+        "example_project",
+        include_str!("../../../seeds/tenant/0004_example_project.sql"),
+    ),
+];
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantSeedRepository: Send + Sync {
+    async fn seed_defaults(&self, created_by_id: Uuid) -> RepositoryResult<()>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantMarkerRepository: Send + Sync {
+    async fn write_marker(&self, tenant_id: Uuid) -> RepositoryResult<()>;
+
+    async fn get_marker(&self) -> RepositoryResult<Option<Uuid>>;
+
+    async fn get_schema_version(&self) -> RepositoryResult<Option<i64>>;
+
+    async fn ping(&self) -> RepositoryResult<()>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TenantProvisioner: Send + Sync {
+    async fn provision(&self, tenant_id: Uuid, db_password: &str) -> RepositoryResult<()>;
+
+    async fn deprovision(&self, tenant_id: Uuid) -> RepositoryResult<()>;
 }
 
 #[async_trait]
@@ -80,33 +158,54 @@ impl TenantsRepository for PgPool {
         uuid: Uuid,
         name: &str,
         db_config: &BasicDatabaseConfig,
+        db_cluster: &str,
         claims: &Claims,
-        app_config: &AppConfig,
     ) -> RepositoryResult<Tenant> {
         let mut tx = self.begin().await?;
-        let tenant = insert_and_connect_with_user(&mut tx, uuid, name, db_config, claims).await?;
-
-        let mut main_pool = self.acquire().await?;
-        create_database_user_for_managed(&mut main_pool, &tenant, app_config).await?;
+        let tenant =
+            insert_and_connect_with_user(&mut tx, uuid, name, db_config, db_cluster, claims)
+                .await?;
         tx.commit().await?;
+        Ok(tenant)
+    }
 
-        let tenant_name = tenant
-            .id
-            .to_string()
-            .replace("-", "")
-            .parse::<ValueObjectRequired<DdlParameter>>()?;
+    async fn get_db_passwords(&self) -> RepositoryResult<Vec<(Uuid, String)>> {
+        Ok(
+            sqlx::query_as::<_, (Uuid, String)>("SELECT id, db_password FROM tenants ORDER BY id")
+                .fetch_all(self)
+                .await?,
+        )
+    }
 
-        // NOTE: Postgres is not allow CREATE DATABASE in TX
-        let create_db_sql = format!(
-            "CREATE DATABASE tenant_{} WITH OWNER = 'tenant_{}'",
-            tenant_name.as_str()?, // SECRUTIY: ValueObejct
-            tenant_name.as_str()?, // SECRUTIY: ValueObejct
-        );
-
-        let _create_db = sqlx::query(AssertSqlSafe(create_db_sql))
-            .execute(self)
-            .await?;
-        Ok(tenant)
+    async fn replace_db_password(
+        &self,
+        id: Uuid,
+        current: &str,
+        replacement: &str,
+    ) -> RepositoryResult<bool> {
+        let result =
+            sqlx::query("UPDATE tenants SET db_password = $1 WHERE id = $2 AND db_password = $3")
+                .bind(replacement)
+                .bind(id)
+                .bind(current)
+                .execute(self)
+                .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn get_cluster_usage(&self) -> RepositoryResult<Vec<ClusterUsage>> {
+        Ok(sqlx::query_as::<_, ClusterUsage>(
+            r#"
+            SELECT db_cluster,
+                   COUNT(*) FILTER (WHERE deleted_at IS NULL) AS tenant_count,
+                   MAX(created_at) AS last_created_at
+            FROM tenants
+            WHERE db_cluster IS NOT NULL
+            GROUP BY db_cluster
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
     }
 
     async fn get_all_by_user_id(
@@ -230,6 +329,43 @@ impl TenantsRepository for PgPool {
         )
     }
 
+    async fn get_memberships_by_user_id(
+        &self,
+        user_id: Uuid,
+        active_tenant_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<TenantMembership>> {
+        Ok(sqlx::query_as::<_, TenantMembership>(
+            r#"
+            SELECT tenants.id AS tenant_id,
+                   tenants.name,
+                   tenants.is_self_hosted,
+                   user_tenants.role,
+                   user_tenants.last_activated,
+                   COALESCE(tenants.id = $2, false) AS is_active
+            FROM user_tenants
+            INNER JOIN tenants ON user_tenants.tenant_id = tenants.id
+            WHERE user_tenants.user_id = $1
+                AND user_tenants.deleted_at IS NULL
+                AND tenants.deleted_at IS NULL
+            ORDER BY user_tenants.last_activated DESC, tenants.name
+            "#,
+        )
+        .bind(user_id)
+        .bind(active_tenant_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_role(&self, user_id: Uuid, tenant_id: Uuid) -> RepositoryResult<Option<String>> {
+        Ok(sqlx::query_scalar::<_, String>(
+            "SELECT role FROM user_tenants WHERE user_id = $1 AND tenant_id = $2 AND deleted_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(tenant_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
     async fn get_user_active_tenant_by_id(
         &self,
         user_id: Uuid,
@@ -293,6 +429,138 @@ impl TenantsRepository for PgPool {
 
         Ok(tenant)
     }
+
+    async fn relink(
+        &self,
+        tenant_id: Uuid,
+        name: &str,
+        db_config: &BasicDatabaseConfig,
+        owner_id: Uuid,
+    ) -> RepositoryResult<Tenant> {
+        Ok(sqlx::query_as::<_, Tenant>(
+            r#"
+            INSERT INTO tenants (
+                id, name, is_self_hosted, db_host, db_port, db_name, db_user, db_password, db_max_pool_size, db_ssl_mode, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (id) DO UPDATE SET
+                db_host = EXCLUDED.db_host,
+                db_port = EXCLUDED.db_port,
+                db_name = EXCLUDED.db_name,
+                db_user = EXCLUDED.db_user,
+                db_password = EXCLUDED.db_password,
+                db_max_pool_size = EXCLUDED.db_max_pool_size,
+                db_ssl_mode = EXCLUDED.db_ssl_mode,
+                deleted_at = NULL
+            RETURNING *
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(name)
+        .bind(false)
+        .bind(&db_config.host)
+        .bind(i32::from(db_config.port))
+        .bind(&db_config.database)
+        .bind(&db_config.username)
+        .bind(encrypt_secret(&db_config.password)?)
+        .bind(
+            i32::try_from(db_config.max_pool_size())
+                .map_err(|e| RepositoryError::InvalidInput(e.to_string()))?,
+        )
+        .bind(&db_config.ssl_mode)
+        .bind(owner_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn reconcile_memberships(
+        &self,
+        tenant_id: Uuid,
+        owner_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> RepositoryResult<u64> {
+        // NOTE: tenant users share their id with the manager user they were copied from
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_tenants (user_id, tenant_id, role)
+            SELECT users.id, $2, CASE WHEN users.id = $3 THEN 'owner' ELSE 'member' END
+            FROM users
+            WHERE users.id = ANY($1)
+                AND users.deleted_at IS NULL
+            ON CONFLICT (user_id, tenant_id) DO UPDATE SET deleted_at = NULL
+            "#,
+        )
+        .bind(user_ids)
+        .bind(tenant_id)
+        .bind(owner_id)
+        .execute(self)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl TenantSeedRepository for PgPool {
+    async fn seed_defaults(&self, created_by_id: Uuid) -> RepositoryResult<()> {
+        let mut tx = self.begin().await?;
+        for (name, seeder) in TENANT_SEEDERS {
+            // NOTE: every seeder has to be idempotent, they can run against an already seeded database
+            sqlx::query(*seeder)
+                .bind(created_by_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| RepositoryError::Custom(format!("Seeder {name} failed: {e}")))?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TenantMarkerRepository for PgPool {
+    async fn write_marker(&self, tenant_id: Uuid) -> RepositoryResult<()> {
+        let _ = sqlx::query(
+            "INSERT INTO tenant_marker (tenant_id) VALUES ($1) ON CONFLICT (tenant_id) DO NOTHING",
+        )
+        .bind(tenant_id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_marker(&self) -> RepositoryResult<Option<Uuid>> {
+        match sqlx::query_scalar::<_, Uuid>("SELECT tenant_id FROM tenant_marker LIMIT 1")
+            .fetch_optional(self)
+            .await
+        {
+            Ok(marker) => Ok(marker),
+            // NOTE: undefined_table, the database predates the marker or is not a tenant database
+            Err(Error::Database(e)) if e.code().as_deref() == Some("42P01") => Ok(None),
+            Err(e) => Err(RepositoryError::Database(e)),
+        }
+    }
+
+    async fn get_schema_version(&self) -> RepositoryResult<Option<i64>> {
+        let (version, dirty): (Option<i64>, bool) = sqlx::query_as(
+            r#"
+            SELECT MAX(version) FILTER (WHERE success), COALESCE(BOOL_OR(NOT success), false)
+            FROM _sqlx_migrations
+            "#,
+        )
+        .fetch_one(self)
+        .await?;
+        if dirty {
+            return Err(RepositoryError::Custom(
+                "Tenant database has a failed migration".to_string(),
+            ));
+        }
+        Ok(version)
+    }
+
+    async fn ping(&self) -> RepositoryResult<()> {
+        let _ = sqlx::query("SELECT 1").execute(self).await?;
+        Ok(())
+    }
 }
 
 async fn insert_and_connect_with_user(
@@ -300,12 +568,13 @@ async fn insert_and_connect_with_user(
     uuid: Uuid,
     name: &str,
     db_config: &BasicDatabaseConfig,
+    db_cluster: &str,
     claims: &Claims,
 ) -> RepositoryResult<Tenant> {
     let tenant = sqlx::query_as::<_, Tenant>(
         "INSERT INTO tenants (
-            id, name, is_self_hosted, db_host, db_port, db_name, db_user, db_password, db_max_pool_size, db_ssl_mode, created_by
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING *",
+            id, name, is_self_hosted, db_host, db_port, db_name, db_user, db_password, db_max_pool_size, db_ssl_mode, db_cluster, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *",
     )
     .bind(uuid)
     .bind(name)
@@ -314,12 +583,13 @@ async fn insert_and_connect_with_user(
     .bind(i32::from(db_config.port))
     .bind(&db_config.database)
     .bind(&db_config.username)
-    .bind(&db_config.password)
+    .bind(encrypt_secret(&db_config.password)?)
     .bind(
         i32::try_from(db_config.max_pool_size())
             .map_err(|e| RepositoryError::InvalidInput(e.to_string()))?,
     )
     .bind(&db_config.ssl_mode)
+    .bind(db_cluster)
     .bind(claims.sub())
     .fetch_one(&mut *conn)
     .await?;
@@ -338,38 +608,90 @@ async fn insert_and_connect_with_user(
     Ok(tenant)
 }
 
-async fn create_database_user_for_managed(
-    conn: &mut PgConnection,
-    tenant: &Tenant,
-    app_config: &AppConfig,
-) -> RepositoryResult<()> {
-    let tenant_name = tenant
-        .id
-        .to_string()
-        .replace("-", "")
-        .parse::<ValueObjectRequired<DdlParameter>>()?;
-    let tenant_password = tenant
-        .db_password
-        .parse::<ValueObjectRequired<DdlParameter>>()?;
-    let create_user_sql = format!(
-        "CREATE USER tenant_{} WITH PASSWORD '{}'",
-        tenant_name.as_str()?,     // safety: ValueObject
-        tenant_password.as_str()?, // safety: ValueObject
-    );
-
-    let _create_user = sqlx::query(AssertSqlSafe(create_user_sql))
-        .execute(&mut *conn)
-        .await?;
+pub struct PgTenantProvisioner {
+    cluster: BasicDatabaseConfig,
+}
 
-    let grant_sql = format!(
-        "GRANT tenant_{} to {};",
-        tenant_name.as_str()?,               // safety: ValueObject
-        app_config.main_database().username  // safety: not user input
-    );
+impl PgTenantProvisioner {
+    pub fn new(cluster: BasicDatabaseConfig) -> Self {
+        Self { cluster }
+    }
 
-    let _grant = sqlx::query(AssertSqlSafe(grant_sql))
-        .execute(&mut *conn)
-        .await?;
+    async fn connect(&self, database: &str) -> RepositoryResult<PgConnection> {
+        let mut options = PgConnectOptions::from_str(&self.cluster.url())?.database(database);
+        if self.cluster.ssl_mode.is_some() {
+            options = options.ssl_mode(
+                self.cluster
+                    .pg_ssl_mode()
+                    .map_err(RepositoryError::InvalidInput)?,
+            );
+        }
+        Ok(PgConnection::connect_with(&options).await?)
+    }
+}
 
-    Ok(())
+#[async_trait]
+impl TenantProvisioner for PgTenantProvisioner {
+    async fn provision(&self, tenant_id: Uuid, db_password: &str) -> RepositoryResult<()> {
+        let tenant_name = managed_tenant_name(tenant_id)?;
+        let tenant_name = tenant_name.as_str()?; // Security: ValueObject
+        let db_password = db_password.parse::<ValueObjectRequired<DdlParameter>>()?;
+
+        let mut conn = self.connect(&self.cluster.database).await?;
+        // NOTE: Postgres is not allow CREATE DATABASE in TX
+        for sql in [
+            format!(
+                "CREATE ROLE tenant_{tenant_name} WITH LOGIN PASSWORD '{}'",
+                db_password.as_str()? // Security: ValueObject
+            ),
+            format!(
+                "GRANT tenant_{tenant_name} TO {}",
+                self.cluster.username // Security: not user input
+            ),
+            format!("CREATE DATABASE tenant_{tenant_name} WITH OWNER = tenant_{tenant_name}"),
+            format!("REVOKE ALL ON DATABASE tenant_{tenant_name} FROM PUBLIC"),
+            format!(
+                "GRANT ALL PRIVILEGES ON DATABASE tenant_{tenant_name} TO tenant_{tenant_name}"
+            ),
+        ] {
+            sqlx::query(AssertSqlSafe(sql)).execute(&mut conn).await?;
+        }
+        conn.close().await?;
+
+        let mut tenant_conn = self.connect(&format!("tenant_{tenant_name}")).await?;
+        for sql in [
+            "REVOKE ALL ON SCHEMA public FROM PUBLIC".to_string(),
+            format!("ALTER SCHEMA public OWNER TO tenant_{tenant_name}"),
+        ] {
+            sqlx::query(AssertSqlSafe(sql))
+                .execute(&mut tenant_conn)
+                .await?;
+        }
+        tenant_conn.close().await?;
+
+        Ok(())
+    }
+
+    async fn deprovision(&self, tenant_id: Uuid) -> RepositoryResult<()> {
+        let tenant_name = managed_tenant_name(tenant_id)?;
+        let tenant_name = tenant_name.as_str()?; // Security: ValueObject
+
+        let mut conn = self.connect(&self.cluster.database).await?;
+        for sql in [
+            format!("DROP DATABASE IF EXISTS tenant_{tenant_name} WITH (FORCE)"),
+            format!("DROP ROLE IF EXISTS tenant_{tenant_name}"),
+        ] {
+            sqlx::query(AssertSqlSafe(sql)).execute(&mut conn).await?;
+        }
+        conn.close().await?;
+
+        Ok(())
+    }
+}
+
+fn managed_tenant_name(tenant_id: Uuid) -> RepositoryResult<ValueObjectRequired<DdlParameter>> {
+    Ok(tenant_id
+        .to_string()
+        .replace("-", "")
+        .parse::<ValueObjectRequired<DdlParameter>>()?)
 }
@@ -19,6 +19,7 @@
 
 use super::TenantsModuleInterface;
 use super::handler;
+use crate::common::sandbox::reject_in_sandbox;
 use crate::manager::auth::middleware::require_auth;
 use axum::Router;
 use axum::middleware::from_fn_with_state;
@@ -29,12 +30,26 @@ pub fn routes<M: TenantsModuleInterface>(tenants_module: Arc<M>) -> Router {
     Router::new().nest(
         "/tenants",
         Router::new()
-            .route("/create", post(handler::create::<M>))
+            .route(
+                "/create",
+                post(handler::create::<M>).route_layer(from_fn_with_state(
+                    tenants_module.clone(),
+                    reject_in_sandbox,
+                )),
+            )
             .route("/get", get(handler::get::<M>))
             .route("/get_resolved", get(handler::get_resolved::<M>))
             .route("/list", get(handler::list::<M>))
+            .route("/mine", get(handler::mine::<M>))
+            .route("/health", get(handler::health::<M>))
             .route("/activate", post(handler::activate::<M>))
-            .route("/delete", post(handler::delete::<M>))
+            .route(
+                "/delete",
+                post(handler::delete::<M>).route_layer(from_fn_with_state(
+                    tenants_module.clone(),
+                    reject_in_sandbox,
+                )),
+            )
             .layer(from_fn_with_state(tenants_module.clone(), require_auth))
             .with_state(tenants_module),
     )
@@ -17,23 +17,28 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::config::PlacementStrategy;
 use crate::common::config::database_config::BasicDatabaseConfig;
-use crate::common::database::{DatabaseMigrator, PoolManager};
+use crate::common::crypto::Keyring;
+use crate::common::database::{DatabaseMigrator, PoolManager, latest_tenant_schema_version};
 use crate::common::dto::PaginatorMeta;
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
 use crate::common::utils::generate_string_csprng;
-use crate::common::value_object::ValueObjectError;
+use crate::common::value_object::{ValueObjectError, ValueObjectRequired};
 use crate::manager::tenants::TenantsModuleInterface;
-use crate::manager::tenants::dto::{CreateTenant, NewTokenResponse, PublicTenant, TenantIdRequest};
-use crate::manager::tenants::model::Tenant;
-use crate::manager::tenants::types::{TenantFilterBy, TenantOrderBy};
+use crate::manager::tenants::dto::{
+    CreateTenant, NewTokenResponse, PublicTenant, RelinkTenant, TenantHealth, TenantIdRequest,
+};
+use crate::manager::tenants::model::{ClusterUsage, Tenant, TenantMembership};
+use crate::manager::tenants::types::{Name, TenantFilterBy, TenantOrderBy};
 use axum::http::StatusCode;
 use serde_json::json;
+use std::time::Instant;
 use thiserror::Error;
-use tracing::Level;
+use tracing::{Level, error, info};
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -55,6 +60,9 @@ pub enum TenantsServiceError {
 
     #[error("ValueObjectError {0}")]
     ValueObjectError(#[from] ValueObjectError),
+
+    #[error("Relink error: {0}")]
+    Relink(String),
 }
 
 impl From<ServiceError> for TenantsServiceError {
@@ -88,6 +96,25 @@ impl From<TenantsServiceError> for AppError {
 
 type TenantsServiceResult<T> = Result<T, TenantsServiceError>;
 
+fn choose_cluster(strategy: PlacementStrategy, names: &[&str], usage: &[ClusterUsage]) -> usize {
+    let usage_of = |name: &str| usage.iter().find(|usage| usage.db_cluster == name);
+    match strategy {
+        PlacementStrategy::RoundRobin => names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, name)| usage_of(name).map(|usage| (i, usage.last_created_at)))
+            .max_by_key(|(_, last_created_at)| *last_created_at)
+            .map(|(i, _)| (i + 1) % names.len())
+            .unwrap_or(0),
+        PlacementStrategy::LeastTenants => names
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, name)| usage_of(name).map_or(0, |usage| usage.tenant_count))
+            .map(|(i, _)| i)
+            .unwrap_or(0),
+    }
+}
+
 pub trait TenantService {
     fn create_managed(
         &self,
@@ -97,6 +124,7 @@ pub trait TenantService {
         &self,
         get_query: &ResourceQuery<TenantOrderBy, TenantFilterBy>,
     ) -> impl Future<Output = TenantsServiceResult<(PaginatorMeta, Vec<PublicTenant>)>> + Send;
+    fn mine(&self) -> impl Future<Output = TenantsServiceResult<Vec<TenantMembership>>> + Send;
     fn activate(
         &self,
         payload: &TenantIdRequest,
@@ -105,6 +133,16 @@ pub trait TenantService {
         &self,
         uuid: Uuid,
     ) -> impl Future<Output = TenantsServiceResult<NewTokenResponse>> + Send;
+    fn relink(
+        &self,
+        payload: &RelinkTenant,
+    ) -> impl Future<Output = TenantsServiceResult<Tenant>> + Send;
+    fn health(&self, uuid: Uuid)
+    -> impl Future<Output = TenantsServiceResult<TenantHealth>> + Send;
+    fn reencrypt_db_passwords(
+        &self,
+        keyring: &Keyring,
+    ) -> impl Future<Output = TenantsServiceResult<usize>> + Send;
 }
 
 impl<'a, T> TenantService for Service<'a, T>
@@ -113,38 +151,65 @@ where
 {
     async fn create_managed(&self, payload: &CreateTenant) -> TenantsServiceResult<Tenant> {
         let config = self.module().config();
+        let claims = self.claims()?;
+        let name = payload.name.as_str()?;
+        let clusters = config.provisioning_clusters();
+        let usage = if clusters.len() > 1 {
+            self.module().tenants_repo().get_cluster_usage().await?
+        } else {
+            vec![]
+        };
+        let names: Vec<&str> = clusters.iter().map(|(name, _)| *name).collect();
+        let (cluster_name, cluster) =
+            clusters[choose_cluster(config.provisioning().placement(), &names, &usage)];
         let uuid = Uuid::new_v4();
         let db_config = BasicDatabaseConfig {
-            host: config.main_database().host.clone(),
-            port: config.main_database().port,
+            host: cluster.host.clone(),
+            port: cluster.port,
             username: format!("tenant_{}", uuid.to_string().replace("-", "")),
-            password: generate_string_csprng(40).map_err(|_| TenantsServiceError::RngError)?,
+            password: generate_string_csprng(config.provisioning().password_length())
+                .map_err(|_| TenantsServiceError::RngError)?,
             database: format!("tenant_{}", uuid.to_string().replace("-", "")),
-            max_pool_size: None,
-            ssl_mode: Some(String::from("disable")),
+            max_pool_size: config.provisioning().tenant_max_pool_size(),
+            ssl_mode: Some(config.provisioning().tenant_ssl_mode().to_owned()),
         };
 
-        let tenant = self
-            .module()
-            .tenants_repo()
-            .setup_managed(
-                uuid,
-                payload.name.as_str()?,
-                &db_config,
-                self.claims()?,
-                config,
-            )
-            .await?;
+        let provisioner = self.module().tenant_provisioner(cluster);
+        let setup_result = match provisioner.provision(uuid, &db_config.password).await {
+            Ok(()) => {
+                self.module()
+                    .tenants_repo()
+                    .setup_managed(uuid, name, &db_config, cluster_name, claims)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let tenant = match setup_result {
+            Ok(tenant) => tenant,
+            Err(e) => {
+                if let Err(cleanup_error) = provisioner.deprovision(uuid).await {
+                    error!("Managed tenant deprovisioning failed: {}", cleanup_error);
+                }
+                return Err(e.into());
+            }
+        };
 
         PoolManager::add_tenant_pool(
             self.module(),
             tenant.id,
-            &BasicDatabaseConfig::try_from(&tenant).map_err(TenantsServiceError::Config)?,
+            &config
+                .tenant_database(&tenant)
+                .map_err(TenantsServiceError::Config)?,
         )
         .await?;
 
         DatabaseMigrator::migrate_tenant_db(self.module(), tenant.id).await?;
 
+        self.module()
+            .tenant_marker_repo(tenant.id)?
+            .write_marker(tenant.id)
+            .await?;
+
         let manager_user = self
             .module()
             .manager_user_repo()
@@ -156,6 +221,16 @@ where
             .insert_from_manager(manager_user.into())
             .await?;
 
+        if payload.seed_defaults
+            && let Err(e) = self
+                .module()
+                .tenant_seed_repo(tenant.id)?
+                .seed_defaults(claims.sub())
+                .await
+        {
+            error!("Tenant onboarding seed failed ({}): {}", tenant.id, e);
+        }
+
         Ok(tenant)
     }
 
@@ -175,6 +250,15 @@ where
         Ok((meta, public_tenants))
     }
 
+    async fn mine(&self) -> TenantsServiceResult<Vec<TenantMembership>> {
+        let claims = self.claims()?;
+        Ok(self
+            .module()
+            .tenants_repo()
+            .get_memberships_by_user_id(claims.sub(), claims.active_tenant())
+            .await?)
+    }
+
     async fn activate(&self, payload: &TenantIdRequest) -> TenantsServiceResult<NewTokenResponse> {
         let user_tenant = self
             .module()
@@ -218,4 +302,678 @@ where
             claims,
         })
     }
+
+    async fn relink(&self, payload: &RelinkTenant) -> TenantsServiceResult<Tenant> {
+        let name = payload.name.parse::<ValueObjectRequired<Name>>()?;
+        let db_config = BasicDatabaseConfig::from(payload);
+
+        PoolManager::add_tenant_pool(self.module(), payload.tenant_id, &db_config).await?;
+
+        if let Err(e) = verify_tenant_database(self.module(), payload.tenant_id).await {
+            self.module().delete_tenant_pool(payload.tenant_id).await?;
+            return Err(e);
+        }
+
+        let user_ids = self
+            .module()
+            .tenant_user_repo(payload.tenant_id)?
+            .get_all_ids()
+            .await?;
+
+        // NOTE: the restored users become members again, so they have to fit into the user
+        // limit of the tenant before anything is relinked
+        if let Some(limits) = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(payload.tenant_id)
+            .await?
+            && !limits.allows_users(0, user_ids.len() as i64)
+        {
+            self.module().delete_tenant_pool(payload.tenant_id).await?;
+            return Err(TenantsServiceError::Relink(format!(
+                "the tenant has {} users, its user limit is {}",
+                user_ids.len(),
+                limits.max_users.unwrap_or_default()
+            )));
+        }
+
+        let tenant = self
+            .module()
+            .tenants_repo()
+            .relink(
+                payload.tenant_id,
+                name.as_str()?,
+                &db_config,
+                payload.owner_id,
+            )
+            .await?;
+
+        DatabaseMigrator::migrate_tenant_db(self.module(), tenant.id).await?;
+
+        let reconciled = self
+            .module()
+            .tenants_repo()
+            .reconcile_memberships(tenant.id, payload.owner_id, &user_ids)
+            .await?;
+
+        info!(
+            "Tenant relinked: {} ({} membership records reconciled)",
+            tenant.id, reconciled
+        );
+
+        Ok(tenant)
+    }
+
+    async fn health(&self, uuid: Uuid) -> TenantsServiceResult<TenantHealth> {
+        self.module()
+            .tenants_repo()
+            .get_user_active_tenant_by_id(self.claims()?.sub(), uuid)
+            .await?
+            .ok_or(TenantsServiceError::Unauthorized)?;
+
+        let latest_schema_version = latest_tenant_schema_version();
+        let started = Instant::now();
+        // NOTE: connection problems are part of the report, not an error of the request
+        let check = async {
+            let marker_repo = self.module().tenant_marker_repo(uuid)?;
+            marker_repo.ping().await?;
+            let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+            Ok::<_, RepositoryError>((latency_ms, marker_repo.get_schema_version().await?))
+        };
+
+        Ok(match check.await {
+            Ok((latency_ms, schema_version)) => TenantHealth {
+                tenant_id: uuid,
+                healthy: schema_version.is_some() && schema_version == latest_schema_version,
+                latency_ms: Some(latency_ms),
+                schema_version,
+                latest_schema_version,
+                error: None,
+            },
+            Err(e) => TenantHealth {
+                tenant_id: uuid,
+                healthy: false,
+                latency_ms: None,
+                schema_version: None,
+                latest_schema_version,
+                error: Some(e.to_string()),
+            },
+        })
+    }
+
+    async fn reencrypt_db_passwords(&self, keyring: &Keyring) -> TenantsServiceResult<usize> {
+        let repo = self.module().tenants_repo();
+        let mut reencrypted = 0;
+        for (tenant_id, current) in repo.get_db_passwords().await? {
+            if !keyring.needs_reencryption(&current) {
+                continue;
+            }
+            let replacement = keyring
+                .decrypt(&current)
+                .and_then(|plaintext| keyring.encrypt(&plaintext))
+                .map_err(RepositoryError::from)?;
+            if repo
+                .replace_db_password(tenant_id, &current, &replacement)
+                .await?
+            {
+                reencrypted += 1;
+            } else {
+                error!("Tenant db_password changed during re-encryption: {tenant_id}");
+            }
+        }
+        Ok(reencrypted)
+    }
+}
+
+async fn verify_tenant_database<T: TenantsModuleInterface>(
+    module: &T,
+    tenant_id: Uuid,
+) -> TenantsServiceResult<()> {
+    let marker_repo = module.tenant_marker_repo(tenant_id)?;
+
+    match marker_repo.get_marker().await? {
+        Some(marker) if marker == tenant_id => {}
+        Some(marker) => {
+            return Err(TenantsServiceError::Relink(format!(
+                "tenant marker mismatch: the database belongs to {marker}"
+            )));
+        }
+        None => {
+            return Err(TenantsServiceError::Relink(
+                "tenant marker is missing".to_string(),
+            ));
+        }
+    }
+
+    let schema_version =
+        marker_repo
+            .get_schema_version()
+            .await?
+            .ok_or(TenantsServiceError::Relink(
+                "tenant database schema version is unknown".to_string(),
+            ))?;
+    let latest_version = latest_tenant_schema_version().unwrap_or_default();
+    if schema_version > latest_version {
+        return Err(TenantsServiceError::Relink(format!(
+            "tenant database schema version {schema_version} is newer than {latest_version}"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::manager::auth::dto::claims::Claims;
+    use crate::manager::tenant_limits::model::TenantLimits;
+    use crate::manager::tenant_limits::repository::MockTenantLimitsRepository;
+    use crate::manager::tenants::model::UserTenant;
+    use crate::manager::tenants::repository::{
+        MockTenantMarkerRepository, MockTenantProvisioner, MockTenantsRepository,
+    };
+    use crate::manager::tenants::tests::MockTenantsModule;
+    use crate::tenant::users::repository::MockUsersRepository as MockTenantUserRepository;
+    use mockall::predicate::eq;
+    use std::future::ready;
+    use std::sync::Arc;
+
+    fn relink_payload(tenant_id: Uuid, owner_id: Uuid) -> RelinkTenant {
+        RelinkTenant {
+            tenant_id,
+            owner_id,
+            name: "restored".to_string(),
+            db_host: "10.0.0.5".to_string(),
+            db_port: 5432,
+            db_name: "tenant_restored".to_string(),
+            db_user: "tenant_restored".to_string(),
+            db_password: "password".to_string(),
+            db_max_pool_size: None,
+            db_ssl_mode: Some("disable".to_string()),
+        }
+    }
+
+    fn tenant_limits_repo(max_users: Option<i32>) -> MockTenantLimitsRepository {
+        let mut repo = MockTenantLimitsRepository::new();
+        repo.expect_get_by_tenant_id().returning(move |tenant_id| {
+            Ok(Some(TenantLimits {
+                tenant_id,
+                max_users,
+                ..Default::default()
+            }))
+        });
+        repo
+    }
+
+    fn marker_repo(
+        marker: Option<Uuid>,
+        schema_version: Option<i64>,
+    ) -> MockTenantMarkerRepository {
+        let mut repo = MockTenantMarkerRepository::new();
+        repo.expect_get_marker().returning(move || Ok(marker));
+        repo.expect_get_schema_version()
+            .returning(move || Ok(schema_version));
+        repo
+    }
+
+    fn claims() -> Claims {
+        Claims::new(
+            Uuid::new_v4(),
+            0,
+            0,
+            0,
+            "obvia".to_string(),
+            "obvia-api".to_string(),
+            Uuid::new_v4(),
+            "hu-HU".to_string(),
+            "Europe/Budapest".parse().unwrap(),
+            None,
+            None,
+        )
+    }
+
+    fn member_tenants_repo() -> Arc<MockTenantsRepository> {
+        let mut tenants_repo = MockTenantsRepository::new();
+        tenants_repo
+            .expect_get_user_active_tenant_by_id()
+            .times(1)
+            .returning(|_, _| Ok(Some(UserTenant::default())));
+        Arc::new(tenants_repo)
+    }
+
+    fn cluster_usage(name: &str, tenant_count: i64, minutes_ago: i64) -> ClusterUsage {
+        ClusterUsage {
+            db_cluster: name.to_string(),
+            tenant_count,
+            last_created_at: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_choose_cluster_least_tenants() {
+        let names = ["a", "b", "c"];
+        let usage = [cluster_usage("a", 4, 10), cluster_usage("b", 2, 5)];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::LeastTenants, &names, &usage),
+            2
+        );
+        let usage = [
+            cluster_usage("a", 4, 10),
+            cluster_usage("b", 2, 5),
+            cluster_usage("c", 2, 1),
+        ];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::LeastTenants, &names, &usage),
+            1
+        );
+    }
+
+    #[test]
+    fn test_choose_cluster_round_robin() {
+        let names = ["a", "b", "c"];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::RoundRobin, &names, &[]),
+            0
+        );
+        let usage = [cluster_usage("a", 4, 10), cluster_usage("b", 2, 5)];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::RoundRobin, &names, &usage),
+            2
+        );
+        let usage = [cluster_usage("b", 2, 5), cluster_usage("c", 1, 1)];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::RoundRobin, &names, &usage),
+            0
+        );
+        let usage = [cluster_usage("removed", 9, 0), cluster_usage("a", 1, 5)];
+        assert_eq!(
+            choose_cluster(PlacementStrategy::RoundRobin, &names, &usage),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_db_passwords() {
+        use crate::common::config::EncryptionConfig;
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD;
+
+        let build_keyring = |active_key_id: &str| {
+            let config: EncryptionConfig = serde_json::from_value(json!({
+                "active_key_id": active_key_id,
+                "keys": {
+                    "k1": STANDARD.encode([1u8; 32]),
+                    "k2": STANDARD.encode([2u8; 32]),
+                }
+            }))
+            .unwrap();
+            Keyring::from_config(&config).unwrap().unwrap()
+        };
+        let old_keyring = build_keyring("k1");
+        let keyring = build_keyring("k2");
+        let check_keyring = build_keyring("k2");
+
+        let legacy_id = Uuid::new_v4();
+        let old_id = Uuid::new_v4();
+        let current_id = Uuid::new_v4();
+        let old_value = old_keyring.encrypt("old").unwrap();
+        let current_value = keyring.encrypt("current").unwrap();
+
+        let mut tenants_repo = MockTenantsRepository::new();
+        tenants_repo.expect_get_db_passwords().times(1).returning({
+            let old_value = old_value.clone();
+            move || {
+                Ok(vec![
+                    (legacy_id, "legacy".to_string()),
+                    (old_id, old_value.clone()),
+                    (current_id, current_value.clone()),
+                ])
+            }
+        });
+        tenants_repo
+            .expect_replace_db_password()
+            .times(1)
+            .withf(move |id, current, replacement| {
+                *id == legacy_id
+                    && current == "legacy"
+                    && check_keyring.decrypt(replacement).unwrap() == "legacy"
+            })
+            .returning(|_, _, _| Ok(true));
+        tenants_repo
+            .expect_replace_db_password()
+            .times(1)
+            .withf(move |id, current, replacement| {
+                *id == old_id && current == old_value && replacement.starts_with("enc:v1:k2:")
+            })
+            .returning(|_, _, _| Ok(false));
+        let tenants_repo = Arc::new(tenants_repo);
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_tenants_repo()
+            .returning(move || tenants_repo.clone());
+
+        let service = Service::new(None, Arc::new(module));
+        assert_eq!(service.reencrypt_db_passwords(&keyring).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_relink_success() {
+        let tenant_id = Uuid::new_v4();
+        let owner_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+
+        let tenant_marker_repo =
+            Arc::new(marker_repo(Some(tenant_id), latest_tenant_schema_version()));
+
+        let mut tenants_repo = MockTenantsRepository::new();
+        tenants_repo
+            .expect_relink()
+            .times(1)
+            .withf(move |id, name, db_config, owner| {
+                *id == tenant_id
+                    && name == "restored"
+                    && db_config.host == "10.0.0.5"
+                    && *owner == owner_id
+            })
+            .returning(move |id, name, _, _| {
+                Ok(Tenant {
+                    id,
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+            });
+        tenants_repo
+            .expect_reconcile_memberships()
+            .times(1)
+            .withf(move |id, owner, user_ids| {
+                *id == tenant_id && *owner == owner_id && user_ids == [owner_id, member_id]
+            })
+            .returning(|_, _, user_ids| Ok(user_ids.len() as u64));
+        let tenants_repo = Arc::new(tenants_repo);
+
+        let mut tenant_user_repo = MockTenantUserRepository::new();
+        tenant_user_repo
+            .expect_get_all_ids()
+            .times(1)
+            .returning(move || Ok(vec![owner_id, member_id]));
+        let tenant_user_repo = Arc::new(tenant_user_repo);
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_add_tenant_pool()
+            .with(eq(tenant_id), mockall::predicate::always())
+            .times(1)
+            .returning(|tenant_id, _| Box::pin(ready(Ok(tenant_id))));
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        module
+            .expect_tenants_repo()
+            .times(2)
+            .returning(move || tenants_repo.clone());
+        module
+            .expect_migrate_tenant_db()
+            .with(eq(tenant_id))
+            .times(1)
+            .returning(|_| Box::pin(ready(Ok(()))));
+        module
+            .expect_tenant_user_repo()
+            .times(1)
+            .returning(move |_| Ok(tenant_user_repo.clone()));
+        module
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(|| Arc::new(tenant_limits_repo(Some(2))));
+        module.expect_delete_tenant_pool().never();
+
+        let service = Service::new(None, Arc::new(module));
+        let tenant = service
+            .relink(&relink_payload(tenant_id, owner_id))
+            .await
+            .unwrap();
+
+        assert_eq!(tenant.id, tenant_id);
+    }
+
+    #[tokio::test]
+    async fn test_relink_rejects_users_over_limit() {
+        let tenant_id = Uuid::new_v4();
+        let tenant_marker_repo =
+            Arc::new(marker_repo(Some(tenant_id), latest_tenant_schema_version()));
+
+        let mut tenant_user_repo = MockTenantUserRepository::new();
+        tenant_user_repo
+            .expect_get_all_ids()
+            .times(1)
+            .returning(|| Ok(vec![Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()]));
+        let tenant_user_repo = Arc::new(tenant_user_repo);
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_add_tenant_pool()
+            .times(1)
+            .returning(|tenant_id, _| Box::pin(ready(Ok(tenant_id))));
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        module
+            .expect_tenant_user_repo()
+            .times(1)
+            .returning(move |_| Ok(tenant_user_repo.clone()));
+        module
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(|| Arc::new(tenant_limits_repo(Some(2))));
+        module.expect_tenants_repo().never();
+        module
+            .expect_delete_tenant_pool()
+            .with(eq(tenant_id))
+            .times(1)
+            .returning(|_| Box::pin(ready(Ok(()))));
+
+        let service = Service::new(None, Arc::new(module));
+        let result = service
+            .relink(&relink_payload(tenant_id, Uuid::new_v4()))
+            .await;
+
+        assert!(matches!(result, Err(TenantsServiceError::Relink(_))));
+    }
+
+    #[tokio::test]
+    async fn test_relink_marker_mismatch() {
+        let tenant_id = Uuid::new_v4();
+        let tenant_marker_repo = Arc::new(marker_repo(
+            Some(Uuid::new_v4()),
+            latest_tenant_schema_version(),
+        ));
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_add_tenant_pool()
+            .times(1)
+            .returning(|tenant_id, _| Box::pin(ready(Ok(tenant_id))));
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        module
+            .expect_delete_tenant_pool()
+            .with(eq(tenant_id))
+            .times(1)
+            .returning(|_| Box::pin(ready(Ok(()))));
+        module.expect_tenants_repo().never();
+
+        let service = Service::new(None, Arc::new(module));
+        let result = service
+            .relink(&relink_payload(tenant_id, Uuid::new_v4()))
+            .await;
+
+        assert!(matches!(result, Err(TenantsServiceError::Relink(_))));
+    }
+
+    #[tokio::test]
+    async fn test_relink_missing_marker() {
+        let tenant_id = Uuid::new_v4();
+        let tenant_marker_repo = Arc::new(marker_repo(None, None));
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_add_tenant_pool()
+            .times(1)
+            .returning(|tenant_id, _| Box::pin(ready(Ok(tenant_id))));
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        module
+            .expect_delete_tenant_pool()
+            .times(1)
+            .returning(|_| Box::pin(ready(Ok(()))));
+        module.expect_tenants_repo().never();
+
+        let service = Service::new(None, Arc::new(module));
+        let result = service
+            .relink(&relink_payload(tenant_id, Uuid::new_v4()))
+            .await;
+
+        assert!(matches!(result, Err(TenantsServiceError::Relink(_))));
+    }
+
+    #[tokio::test]
+    async fn test_relink_newer_schema_version() {
+        let tenant_id = Uuid::new_v4();
+        let tenant_marker_repo = Arc::new(marker_repo(
+            Some(tenant_id),
+            latest_tenant_schema_version().map(|version| version + 1),
+        ));
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_add_tenant_pool()
+            .times(1)
+            .returning(|tenant_id, _| Box::pin(ready(Ok(tenant_id))));
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+        module
+            .expect_delete_tenant_pool()
+            .times(1)
+            .returning(|_| Box::pin(ready(Ok(()))));
+        module.expect_tenants_repo().never();
+
+        let service = Service::new(None, Arc::new(module));
+        let result = service
+            .relink(&relink_payload(tenant_id, Uuid::new_v4()))
+            .await;
+
+        assert!(matches!(result, Err(TenantsServiceError::Relink(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_managed_deprovisions_on_failure() {
+        let claims = claims();
+
+        let mut tenant_provisioner = MockTenantProvisioner::new();
+        tenant_provisioner
+            .expect_provision()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        tenant_provisioner
+            .expect_deprovision()
+            .times(1)
+            .returning(|_| Ok(()));
+        let tenant_provisioner = Arc::new(tenant_provisioner);
+
+        let mut tenants_repo = MockTenantsRepository::new();
+        tenants_repo
+            .expect_setup_managed()
+            .times(1)
+            .returning(|_, _, _, _, _| Err(RepositoryError::Custom("insert failed".to_string())));
+        let tenants_repo = Arc::new(tenants_repo);
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_config()
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        module
+            .expect_tenant_provisioner()
+            .times(1)
+            .returning(move |_| tenant_provisioner.clone());
+        module
+            .expect_tenants_repo()
+            .times(1)
+            .returning(move || tenants_repo.clone());
+        module.expect_add_tenant_pool().never();
+
+        let service = Service::new(Some(&claims), Arc::new(module));
+        let result = service
+            .create_managed(&CreateTenant {
+                name: "test".parse().unwrap(),
+                seed_defaults: false,
+            })
+            .await;
+
+        assert!(matches!(result, Err(TenantsServiceError::Repository(_))));
+    }
+
+    #[tokio::test]
+    async fn test_health_success() {
+        let tenant_id = Uuid::new_v4();
+        let claims = claims();
+
+        let mut tenant_marker_repo = marker_repo(Some(tenant_id), latest_tenant_schema_version());
+        tenant_marker_repo
+            .expect_ping()
+            .times(1)
+            .returning(|| Ok(()));
+        let tenant_marker_repo = Arc::new(tenant_marker_repo);
+        let tenants_repo = member_tenants_repo();
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_tenants_repo()
+            .returning(move || tenants_repo.clone());
+        module
+            .expect_tenant_marker_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+
+        let service = Service::new(Some(&claims), Arc::new(module));
+        let health = service.health(tenant_id).await.unwrap();
+
+        assert!(health.healthy);
+        assert!(health.latency_ms.is_some());
+        assert_eq!(health.schema_version, latest_tenant_schema_version());
+        assert_eq!(health.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_connection_failure() {
+        let tenant_id = Uuid::new_v4();
+        let claims = claims();
+
+        let mut tenant_marker_repo = MockTenantMarkerRepository::new();
+        tenant_marker_repo
+            .expect_ping()
+            .times(1)
+            .returning(|| Err(RepositoryError::Custom("connection refused".to_string())));
+        tenant_marker_repo.expect_get_schema_version().never();
+        let tenant_marker_repo = Arc::new(tenant_marker_repo);
+        let tenants_repo = member_tenants_repo();
+
+        let mut module = MockTenantsModule::new();
+        module
+            .expect_tenants_repo()
+            .returning(move || tenants_repo.clone());
+        module
+            .expect_tenant_marker_repo()
+            .returning(move |_| Ok(tenant_marker_repo.clone()));
+
+        let service = Service::new(Some(&claims), Arc::new(module));
+        let health = service.health(tenant_id).await.unwrap();
+
+        assert!(!health.healthy);
+        assert_eq!(health.latency_ms, None);
+        assert!(health.error.unwrap().contains("connection refused"));
+    }
 }
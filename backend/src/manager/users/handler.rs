@@ -199,7 +199,7 @@ mod tests {
             .returning(move || users_repo.clone());
         app_state
             .expect_config()
-            .times(1)
+            .times(2)
             .return_const(test_config.clone());
 
         let request = Request::builder()
@@ -268,6 +268,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -307,6 +308,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -436,7 +438,7 @@ mod tests {
             .returning(move || users_repo.clone());
         app_state
             .expect_config()
-            .times(1)
+            .times(2)
             .return_const(test_config.clone());
 
         let payload = serde_json::to_string(&OtpUserInputHelper {
@@ -565,7 +567,7 @@ mod tests {
             .returning(move || users_repo.clone());
         app_state
             .expect_config()
-            .times(1)
+            .times(2)
             .return_const(test_config.clone());
 
         let otp = if user.get_mfa_token().unwrap() == "111111" {
@@ -603,6 +605,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "A kétlépcsős azonosításhoz hasznát kód hibás!"
             }
         });
@@ -674,6 +677,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -745,6 +749,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -908,7 +913,7 @@ mod tests {
             .returning(move || users_repo.clone());
         app_state
             .expect_config()
-            .times(1)
+            .times(2)
             .return_const(test_config.clone());
 
         let payload = serde_json::to_string(&OtpUserInputHelper {
@@ -1046,7 +1051,7 @@ mod tests {
             .returning(move || users_repo.clone());
         app_state
             .expect_config()
-            .times(1)
+            .times(2)
             .return_const(test_config.clone());
 
         let otp = if user.get_mfa_token().unwrap() == "111111" {
@@ -1084,6 +1089,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "A kétlépcsős azonosításhoz hasznát kód hibás!"
             }
         });
@@ -1155,6 +1161,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1226,6 +1233,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
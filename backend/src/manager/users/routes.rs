@@ -19,6 +19,7 @@
 
 use super::UsersModuleInterface;
 use super::handler;
+use crate::common::sandbox::reject_in_sandbox;
 use crate::manager::auth::middleware::require_auth;
 use axum::middleware::from_fn_with_state;
 use axum::{
@@ -32,9 +33,21 @@ pub fn routes<M: UsersModuleInterface>(users_module: Arc<M>) -> Router {
         "/users",
         Router::new()
             .route("/get_claims", get(handler::get_claims::<M>))
-            .route("/otp/enable", get(handler::otp_enable::<M>))
-            .route("/otp/verify", post(handler::otp_verify::<M>))
-            .route("/otp/disable", post(handler::otp_disable::<M>))
+            .route(
+                "/otp/enable",
+                get(handler::otp_enable::<M>)
+                    .route_layer(from_fn_with_state(users_module.clone(), reject_in_sandbox)),
+            )
+            .route(
+                "/otp/verify",
+                post(handler::otp_verify::<M>)
+                    .route_layer(from_fn_with_state(users_module.clone(), reject_in_sandbox)),
+            )
+            .route(
+                "/otp/disable",
+                post(handler::otp_disable::<M>)
+                    .route_layer(from_fn_with_state(users_module.clone(), reject_in_sandbox)),
+            )
             .layer(from_fn_with_state(users_module.clone(), require_auth))
             .with_state(users_module),
     )
@@ -0,0 +1,310 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::decrypt_secret;
+use crate::tenant::accounting_exports::adapter::{
+    AccountingExportClient, AccountingExportError, vat_override,
+};
+use crate::tenant::accounting_exports::model::{
+    AccountingConnection, ExportCustomer, ExportInvoice, ExportInvoiceLine, ExportReceipt,
+    MAPPING_BLOCK_ID, MAPPING_LANGUAGE, MAPPING_PAYMENT_METHOD, MAPPING_UNIT,
+};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+pub const API_URL: &str = "https://api.billingo.hu/v3";
+
+// NOTE: Billingo API v3, see https://app.swaggerhub.com/apis/Billingo/Billingo/3.0.14
+pub struct BillingoClient {
+    api_key: String,
+    block_id: i64,
+    mapping: BTreeMap<String, String>,
+    http: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct PartnerAddress<'a> {
+    country_code: &'a str,
+    post_code: &'a str,
+    city: &'a str,
+    address: &'a str,
+}
+
+#[derive(Serialize)]
+struct Partner<'a> {
+    name: &'a str,
+    address: PartnerAddress<'a>,
+    emails: [&'a str; 1],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    taxcode: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct DocumentItem<'a> {
+    name: &'a str,
+    unit_price: f64,
+    unit_price_type: &'static str,
+    quantity: f64,
+    unit: &'a str,
+    vat: String,
+    comment: &'a str,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Document<'a> {
+    partner_id: i64,
+    block_id: i64,
+    #[serde(rename = "type")]
+    document_type: &'static str,
+    fulfillment_date: NaiveDate,
+    due_date: NaiveDate,
+    payment_method: &'a str,
+    language: &'a str,
+    currency: &'a str,
+    electronic: bool,
+    paid: bool,
+    comment: &'a str,
+    items: Vec<DocumentItem<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Created {
+    id: i64,
+    invoice_number: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    message: Option<String>,
+    error: Option<ErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    message: Option<String>,
+}
+
+fn number(value: &BigDecimal) -> Result<f64, AccountingExportError> {
+    value
+        .with_scale_round(2, RoundingMode::HalfUp)
+        .to_f64()
+        .ok_or_else(|| AccountingExportError::Rejected("invalid amount".to_string()))
+}
+
+// NOTE: percentage rates are written as `27%`, exemptions (e.g. `AAM`) as they are
+fn vat(mapping: &BTreeMap<String, String>, line: &ExportInvoiceLine) -> String {
+    vat_override(mapping, line).unwrap_or_else(|| {
+        if line.is_rate_applicable {
+            format!("{}%", line.vat_code())
+        } else {
+            line.vat_code()
+        }
+    })
+}
+
+fn document<'a>(
+    mapping: &'a BTreeMap<String, String>,
+    block_id: i64,
+    partner_id: i64,
+    invoice: &'a ExportInvoice,
+) -> Result<Document<'a>, AccountingExportError> {
+    let mapped =
+        |key: &str, default: &'static str| mapping.get(key).map(String::as_str).unwrap_or(default);
+    Ok(Document {
+        partner_id,
+        block_id,
+        document_type: "invoice",
+        fulfillment_date: invoice.header.issue_date,
+        due_date: invoice.header.due_date,
+        payment_method: mapped(MAPPING_PAYMENT_METHOD, "wire_transfer"),
+        language: mapped(MAPPING_LANGUAGE, "hu"),
+        currency: &invoice.header.currency_code,
+        electronic: false,
+        paid: false,
+        comment: &invoice.header.document_number,
+        items: invoice
+            .lines
+            .iter()
+            .map(|line| {
+                Ok(DocumentItem {
+                    name: &line.item,
+                    unit_price: number(&line.unit_price)?,
+                    unit_price_type: "net",
+                    quantity: number(&line.quantity)?,
+                    unit: line.unit.as_deref().unwrap_or(mapped(MAPPING_UNIT, "db")),
+                    vat: vat(mapping, line),
+                    comment: &line.description,
+                })
+            })
+            .collect::<Result<Vec<_>, AccountingExportError>>()?,
+    })
+}
+
+impl BillingoClient {
+    pub fn new(
+        connection: &AccountingConnection,
+        timeout: Duration,
+    ) -> Result<Self, AccountingExportError> {
+        Ok(Self {
+            api_key: decrypt_secret(
+                connection
+                    .api_key
+                    .as_deref()
+                    .ok_or(AccountingExportError::Configuration("hiányzó API kulcs"))?,
+            )?,
+            block_id: connection
+                .mapping(MAPPING_BLOCK_ID)
+                .and_then(|block_id| block_id.parse().ok())
+                .ok_or(AccountingExportError::Configuration("hiányzó számlatömb"))?,
+            mapping: connection.field_mapping.0.clone(),
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+
+    async fn post<B: Serialize + Sync, R: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<R, AccountingExportError> {
+        let response = self
+            .http
+            .post(format!("{API_URL}{path}"))
+            .header("X-API-KEY", &self.api_key)
+            .json(body)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            return Ok(response.json::<R>().await?);
+        }
+        let status = response.status();
+        let message = response
+            .json::<ErrorBody>()
+            .await
+            .ok()
+            .and_then(|body| body.error.and_then(|error| error.message).or(body.message))
+            .unwrap_or_else(|| status.to_string());
+        Err(AccountingExportError::Rejected(message))
+    }
+}
+
+#[async_trait]
+impl AccountingExportClient for BillingoClient {
+    async fn push_invoice(
+        &self,
+        invoice: &ExportInvoice,
+        customer: &ExportCustomer,
+    ) -> Result<ExportReceipt, AccountingExportError> {
+        // NOTE: documents reference a partner, a new one is created for every pushed invoice as
+        // the partner list can not be searched by tax number
+        let partner: Created = self
+            .post(
+                "/partners",
+                &Partner {
+                    name: &invoice.header.customer_name,
+                    address: PartnerAddress {
+                        country_code: &customer.country_code,
+                        post_code: &customer.postal_code,
+                        city: &customer.city,
+                        address: &customer.street_address,
+                    },
+                    emails: [invoice.header.customer_email.as_str()],
+                    taxcode: customer.tax_number.as_deref(),
+                },
+            )
+            .await?;
+        let created: Created = self
+            .post(
+                "/documents",
+                &document(&self.mapping, self.block_id, partner.id, invoice)?,
+            )
+            .await?;
+        Ok(ExportReceipt {
+            external_id: Some(created.id.to_string()),
+            external_number: created.invoice_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::accounting_exports::model::ExportInvoiceHeader;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_document_maps_fields() {
+        let receivable_id = Uuid::new_v4();
+        let invoice = ExportInvoice {
+            header: ExportInvoiceHeader {
+                receivable_id,
+                document_number: "SZ-2026-00042".to_string(),
+                issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                due_date: NaiveDate::from_ymd_opt(2026, 10, 9).unwrap(),
+                currency_code: "EUR".to_string(),
+                amount: BigDecimal::from_str("127.00").unwrap(),
+                customer_name: "Példa Kft.".to_string(),
+                customer_email: "vevo@example.com".to_string(),
+            },
+            lines: vec![ExportInvoiceLine {
+                receivable_id,
+                item: "Csavar, M6".to_string(),
+                description: "Horganyzott".to_string(),
+                quantity: BigDecimal::from_str("2.50").unwrap(),
+                unit: Some("kg".to_string()),
+                unit_price: BigDecimal::from_str("40.00").unwrap(),
+                tax_rate: BigDecimal::from_str("27.00").unwrap(),
+                is_rate_applicable: true,
+                reporting_code: None,
+                net_amount: BigDecimal::from_str("100.00").unwrap(),
+                tax_amount: BigDecimal::from_str("27.00").unwrap(),
+                gross_amount: BigDecimal::from_str("127.00").unwrap(),
+            }],
+        };
+        let mapping = BTreeMap::from([
+            ("language".to_string(), "en".to_string()),
+            ("block_id".to_string(), "12".to_string()),
+        ]);
+
+        let document = document(&mapping, 12, 345, &invoice).unwrap();
+
+        assert_eq!(document.partner_id, 345);
+        assert_eq!(document.block_id, 12);
+        assert_eq!(document.payment_method, "wire_transfer");
+        assert_eq!(document.language, "en");
+        assert_eq!(document.currency, "EUR");
+        assert_eq!(
+            document.items,
+            vec![DocumentItem {
+                name: "Csavar, M6",
+                unit_price: 40.0,
+                unit_price_type: "net",
+                quantity: 2.5,
+                unit: "kg",
+                vat: "27%".to_string(),
+                comment: "Horganyzott",
+            }]
+        );
+    }
+}
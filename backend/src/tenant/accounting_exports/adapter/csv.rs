@@ -0,0 +1,170 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::accounting_exports::adapter::{AccountingExportError, vat_override};
+use crate::tenant::accounting_exports::model::{
+    ExportInvoice, ExportInvoiceHeader, ExportInvoiceLine, MAPPING_DELIMITER,
+};
+use bigdecimal::BigDecimal;
+use std::collections::BTreeMap;
+
+/// Columns of the accountant CSV with their default headers. The field mapping renames a
+/// column by its key, an empty header leaves the column out.
+pub const COLUMNS: [(&str, &str); 15] = [
+    ("document_number", "Számlaszám"),
+    ("issue_date", "Kelt"),
+    ("due_date", "Fizetési határidő"),
+    ("customer_name", "Vevő"),
+    ("customer_email", "Vevő e-mail"),
+    ("currency_code", "Pénznem"),
+    ("item", "Tétel"),
+    ("description", "Leírás"),
+    ("quantity", "Mennyiség"),
+    ("unit", "Mennyiségi egység"),
+    ("unit_price", "Nettó egységár"),
+    ("vat", "ÁFA kulcs"),
+    ("net_amount", "Nettó érték"),
+    ("tax_amount", "ÁFA érték"),
+    ("gross_amount", "Bruttó érték"),
+];
+
+fn value(
+    column: &str,
+    header: &ExportInvoiceHeader,
+    line: &ExportInvoiceLine,
+    mapping: &BTreeMap<String, String>,
+    decimal_comma: bool,
+) -> String {
+    let decimal = |value: &BigDecimal| {
+        let value = value.to_string();
+        if decimal_comma {
+            value.replace('.', ",")
+        } else {
+            value
+        }
+    };
+    match column {
+        "document_number" => header.document_number.clone(),
+        "issue_date" => header.issue_date.to_string(),
+        "due_date" => header.due_date.to_string(),
+        "customer_name" => header.customer_name.clone(),
+        "customer_email" => header.customer_email.clone(),
+        "currency_code" => header.currency_code.clone(),
+        "item" => line.item.clone(),
+        "description" => line.description.clone(),
+        "quantity" => decimal(&line.quantity),
+        "unit" => line.unit.clone().unwrap_or_default(),
+        "unit_price" => decimal(&line.unit_price),
+        "vat" => vat_override(mapping, line).unwrap_or_else(|| line.vat_code()),
+        "net_amount" => decimal(&line.net_amount),
+        "tax_amount" => decimal(&line.tax_amount),
+        "gross_amount" => decimal(&line.gross_amount),
+        _ => String::new(),
+    }
+}
+
+/// One row per invoice line. With the `;` delimiter decimal commas are used, as expected by
+/// Excel with Hungarian locale.
+pub fn invoices_csv(
+    mapping: &BTreeMap<String, String>,
+    invoices: &[ExportInvoice],
+) -> Result<Vec<u8>, AccountingExportError> {
+    let decimal_comma = mapping.get(MAPPING_DELIMITER).map(String::as_str) == Some(";");
+    let columns: Vec<(&str, &str)> = COLUMNS
+        .iter()
+        .map(|(column, default)| {
+            (
+                *column,
+                mapping.get(*column).map(String::as_str).unwrap_or(default),
+            )
+        })
+        .filter(|(_, header)| !header.is_empty())
+        .collect();
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(if decimal_comma { b';' } else { b',' })
+        .from_writer(vec![]);
+    writer.write_record(columns.iter().map(|(_, header)| *header))?;
+    for invoice in invoices {
+        for line in &invoice.lines {
+            writer.write_record(
+                columns.iter().map(|(column, _)| {
+                    value(column, &invoice.header, line, mapping, decimal_comma)
+                }),
+            )?;
+        }
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AccountingExportError::Csv(e.into_error().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_invoices_csv_renames_and_excludes_columns() {
+        let receivable_id = Uuid::new_v4();
+        let invoice = ExportInvoice {
+            header: ExportInvoiceHeader {
+                receivable_id,
+                document_number: "SZ-2026-00042".to_string(),
+                issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                due_date: NaiveDate::from_ymd_opt(2026, 10, 9).unwrap(),
+                currency_code: "HUF".to_string(),
+                amount: BigDecimal::from_str("12700.00").unwrap(),
+                customer_name: "Példa Kft.".to_string(),
+                customer_email: "vevo@example.com".to_string(),
+            },
+            lines: vec![ExportInvoiceLine {
+                receivable_id,
+                item: "Szerviz".to_string(),
+                description: "Éves karbantartás".to_string(),
+                quantity: BigDecimal::from_str("1.00").unwrap(),
+                unit: None,
+                unit_price: BigDecimal::from_str("10000.00").unwrap(),
+                tax_rate: BigDecimal::from_str("27.00").unwrap(),
+                is_rate_applicable: true,
+                reporting_code: None,
+                net_amount: BigDecimal::from_str("10000.00").unwrap(),
+                tax_amount: BigDecimal::from_str("2700.00").unwrap(),
+                gross_amount: BigDecimal::from_str("12700.00").unwrap(),
+            }],
+        };
+        let mut mapping: BTreeMap<String, String> = COLUMNS
+            .iter()
+            .map(|(column, _)| (column.to_string(), String::new()))
+            .collect();
+        mapping.insert("document_number".to_string(), "Bizonylatszám".to_string());
+        mapping.insert("gross_amount".to_string(), "Bruttó".to_string());
+        mapping.insert("vat".to_string(), "ÁFA".to_string());
+        mapping.insert("vat_27".to_string(), "27%".to_string());
+        mapping.insert("delimiter".to_string(), ";".to_string());
+
+        let csv = invoices_csv(&mapping, &[invoice]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Bizonylatszám;ÁFA;Bruttó\nSZ-2026-00042;27%;12700,00\n"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::CryptoError;
+use crate::tenant::accounting_exports::adapter::billingo::BillingoClient;
+use crate::tenant::accounting_exports::adapter::szamlazz::SzamlazzClient;
+use crate::tenant::accounting_exports::model::{
+    ADAPTER_BILLINGO, ADAPTER_SZAMLAZZ_HU, AccountingConnection, ExportCustomer, ExportInvoice,
+    ExportInvoiceLine, ExportReceipt, MAPPING_VAT_PREFIX,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+pub mod billingo;
+pub mod csv;
+pub mod szamlazz;
+
+#[derive(Debug, Error)]
+pub enum AccountingExportError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("A könyvelési szolgáltatás elutasította a számlát: {0}")]
+    Rejected(String),
+
+    #[error("A kapcsolat nem támogatja a számlák beküldését: {0}")]
+    Unsupported(String),
+
+    #[error("Hibás kapcsolat beállítások: {0}")]
+    Configuration(&'static str),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] ::csv::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AccountingExportClient: Send + Sync {
+    /// Creates the invoice at the external service, returning its identifiers there
+    async fn push_invoice(
+        &self,
+        invoice: &ExportInvoice,
+        customer: &ExportCustomer,
+    ) -> Result<ExportReceipt, AccountingExportError>;
+}
+
+pub fn accounting_export_client(
+    connection: &AccountingConnection,
+    timeout: Duration,
+) -> Result<Arc<dyn AccountingExportClient + Send + Sync>, AccountingExportError> {
+    match connection.adapter.as_str() {
+        ADAPTER_SZAMLAZZ_HU => Ok(Arc::new(SzamlazzClient::new(connection, timeout)?)),
+        ADAPTER_BILLINGO => Ok(Arc::new(BillingoClient::new(connection, timeout)?)),
+        other => Err(AccountingExportError::Unsupported(other.to_string())),
+    }
+}
+
+/// VAT code of the line as overridden by the `vat_<code>` keys of the field mapping, `None`
+/// when the adapter's own format applies
+fn vat_override(mapping: &BTreeMap<String, String>, line: &ExportInvoiceLine) -> Option<String> {
+    mapping
+        .get(&format!("{MAPPING_VAT_PREFIX}{}", line.vat_code()))
+        .cloned()
+}
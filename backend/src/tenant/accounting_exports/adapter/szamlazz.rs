@@ -0,0 +1,284 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::decrypt_secret;
+use crate::tenant::accounting_exports::adapter::{
+    AccountingExportClient, AccountingExportError, vat_override,
+};
+use crate::tenant::accounting_exports::model::{
+    AccountingConnection, ExportCustomer, ExportInvoice, ExportReceipt, MAPPING_LANGUAGE,
+    MAPPING_PAYMENT_METHOD, MAPPING_UNIT,
+};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, RoundingMode};
+use quick_xml::escape::{escape, unescape};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const AGENT_URL: &str = "https://www.szamlazz.hu/szamla/";
+
+// NOTE: Számla Agent, see https://docs.szamlazz.hu/hu/agent/generating_invoice
+pub struct SzamlazzClient {
+    agent_key: String,
+    mapping: BTreeMap<String, String>,
+    http: reqwest::Client,
+}
+
+fn amount(value: &BigDecimal) -> String {
+    value.with_scale_round(2, RoundingMode::HalfUp).to_string()
+}
+
+fn element(name: &str, value: &str) -> String {
+    format!("<{name}>{}</{name}>", escape(value))
+}
+
+pub(crate) fn invoice_xml(
+    agent_key: &str,
+    mapping: &BTreeMap<String, String>,
+    invoice: &ExportInvoice,
+    customer: &ExportCustomer,
+) -> String {
+    let header = &invoice.header;
+    let mapped =
+        |key: &str, default: &'static str| mapping.get(key).map(String::as_str).unwrap_or(default);
+    let issue_date = header.issue_date.to_string();
+    // NOTE: the service numbers the invoice itself, our document number is kept as the order number
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><xmlszamla xmlns="http://www.szamlazz.hu/xmlszamla" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.szamlazz.hu/xmlszamla https://www.szamlazz.hu/szamla/docs/xsds/agent/xmlszamla.xsd">"#,
+    );
+    xml.push_str("<beallitasok>");
+    xml.push_str(&element("szamlaagentkulcs", agent_key));
+    xml.push_str("<eszamla>false</eszamla><szamlaLetoltes>false</szamlaLetoltes><valaszVerzio>2</valaszVerzio>");
+    xml.push_str("</beallitasok><fejlec>");
+    xml.push_str(&element("keltDatum", &issue_date));
+    xml.push_str(&element("teljesitesDatum", &issue_date));
+    xml.push_str(&element(
+        "fizetesiHataridoDatum",
+        &header.due_date.to_string(),
+    ));
+    xml.push_str(&element(
+        "fizmod",
+        mapped(MAPPING_PAYMENT_METHOD, "Átutalás"),
+    ));
+    xml.push_str(&element("penznem", &header.currency_code));
+    xml.push_str(&element("szamlaNyelve", mapped(MAPPING_LANGUAGE, "hu")));
+    xml.push_str(&element("megjegyzes", &header.document_number));
+    if header.currency_code != "HUF" {
+        // NOTE: a zero rate makes the service use the MNB rate of the issue date
+        xml.push_str("<arfolyamBank>MNB</arfolyamBank><arfolyam>0</arfolyam>");
+    }
+    xml.push_str(&element("rendelesSzam", &header.document_number));
+    xml.push_str("</fejlec><elado></elado><vevo>");
+    xml.push_str(&element("nev", &header.customer_name));
+    xml.push_str(&element("orszag", &customer.country_code));
+    xml.push_str(&element("irsz", &customer.postal_code));
+    xml.push_str(&element("telepules", &customer.city));
+    xml.push_str(&element("cim", &customer.street_address));
+    xml.push_str(&element("email", &header.customer_email));
+    xml.push_str("<sendEmail>false</sendEmail>");
+    if let Some(tax_number) = &customer.tax_number {
+        xml.push_str(&element("adoszam", tax_number));
+    }
+    xml.push_str("</vevo><tetelek>");
+    for line in &invoice.lines {
+        xml.push_str("<tetel>");
+        xml.push_str(&element("megnevezes", &line.item));
+        xml.push_str(&element("mennyiseg", &amount(&line.quantity)));
+        xml.push_str(&element(
+            "mennyisegiEgyseg",
+            line.unit.as_deref().unwrap_or(mapped(MAPPING_UNIT, "db")),
+        ));
+        xml.push_str(&element("nettoEgysegar", &amount(&line.unit_price)));
+        xml.push_str(&element(
+            "afakulcs",
+            &vat_override(mapping, line).unwrap_or_else(|| line.vat_code()),
+        ));
+        xml.push_str(&element("nettoErtek", &amount(&line.net_amount)));
+        xml.push_str(&element("afaErtek", &amount(&line.tax_amount)));
+        xml.push_str(&element("bruttoErtek", &amount(&line.gross_amount)));
+        xml.push_str(&element("megjegyzes", &line.description));
+        xml.push_str("</tetel>");
+    }
+    xml.push_str("</tetelek></xmlszamla>");
+    xml
+}
+
+// NOTE: the answer is a flat document, the few fields needed are looked up by name
+fn response_field(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = start + xml[start..].find(&format!("</{name}>"))?;
+    unescape(&xml[start..end])
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn receipt_from_response(xml: &str) -> Result<ExportReceipt, AccountingExportError> {
+    if response_field(xml, "sikeres").as_deref() != Some("true") {
+        return Err(AccountingExportError::Rejected(format!(
+            "{}: {}",
+            response_field(xml, "hibakod").unwrap_or_default(),
+            response_field(xml, "hibauzenet").unwrap_or_default()
+        )));
+    }
+    let number = response_field(xml, "szamlaszam");
+    Ok(ExportReceipt {
+        external_id: number.clone(),
+        external_number: number,
+    })
+}
+
+impl SzamlazzClient {
+    pub fn new(
+        connection: &AccountingConnection,
+        timeout: Duration,
+    ) -> Result<Self, AccountingExportError> {
+        Ok(Self {
+            agent_key: decrypt_secret(connection.api_key.as_deref().ok_or(
+                AccountingExportError::Configuration("hiányzó Számla Agent kulcs"),
+            )?)?,
+            mapping: connection.field_mapping.0.clone(),
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+}
+
+#[async_trait]
+impl AccountingExportClient for SzamlazzClient {
+    async fn push_invoice(
+        &self,
+        invoice: &ExportInvoice,
+        customer: &ExportCustomer,
+    ) -> Result<ExportReceipt, AccountingExportError> {
+        let xml = invoice_xml(&self.agent_key, &self.mapping, invoice, customer);
+        // NOTE: the agent only accepts the document as a multipart file upload
+        let boundary = format!("obvia-{}", Uuid::new_v4().simple());
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"action-xmlagentxmlfile\"; filename=\"szamla.xml\"\r\nContent-Type: text/xml\r\n\r\n{xml}\r\n--{boundary}--\r\n"
+        );
+        let response = self
+            .http
+            .post(AGENT_URL)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(body)
+            .send()
+            .await?
+            .text()
+            .await?;
+        receipt_from_response(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::accounting_exports::model::{ExportInvoiceHeader, ExportInvoiceLine};
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    fn invoice() -> ExportInvoice {
+        let receivable_id = Uuid::new_v4();
+        let line =
+            |item: &str, rate: &str, applicable: bool, net: &str, tax: &str| ExportInvoiceLine {
+                receivable_id,
+                item: item.to_string(),
+                description: format!("{item} leírás"),
+                quantity: BigDecimal::from(1),
+                unit: None,
+                unit_price: BigDecimal::from_str(net).unwrap(),
+                tax_rate: BigDecimal::from_str(rate).unwrap(),
+                is_rate_applicable: applicable,
+                reporting_code: None,
+                net_amount: BigDecimal::from_str(net).unwrap(),
+                tax_amount: BigDecimal::from_str(tax).unwrap(),
+                gross_amount: BigDecimal::from_str(net).unwrap()
+                    + BigDecimal::from_str(tax).unwrap(),
+            };
+        ExportInvoice {
+            header: ExportInvoiceHeader {
+                receivable_id,
+                document_number: "SZ-2026-00042".to_string(),
+                issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                due_date: NaiveDate::from_ymd_opt(2026, 10, 9).unwrap(),
+                currency_code: "HUF".to_string(),
+                amount: BigDecimal::from(22700),
+                customer_name: "Példa & Társa Kft.".to_string(),
+                customer_email: "vevo@example.com".to_string(),
+            },
+            lines: vec![
+                line("Szerviz", "27.00", true, "10000", "2700"),
+                line("Tankönyv", "0.00", false, "10000", "0"),
+            ],
+        }
+    }
+
+    fn customer() -> ExportCustomer {
+        ExportCustomer {
+            tax_number: Some("12345678-2-42".to_string()),
+            country_code: "HU".to_string(),
+            postal_code: "1111".to_string(),
+            city: "Budapest".to_string(),
+            street_address: "Példa utca 1.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_invoice_xml_maps_fields() {
+        let mapping = BTreeMap::from([
+            ("payment_method".to_string(), "Bankkártya".to_string()),
+            ("vat_AAM".to_string(), "TAM".to_string()),
+        ]);
+        let xml = invoice_xml("agent-key", &mapping, &invoice(), &customer());
+
+        assert!(xml.contains("<szamlaagentkulcs>agent-key</szamlaagentkulcs>"));
+        assert!(xml.contains("<fizmod>Bankkártya</fizmod>"));
+        assert!(xml.contains("<rendelesSzam>SZ-2026-00042</rendelesSzam>"));
+        assert!(xml.contains("<nev>Példa &amp; Társa Kft.</nev>"));
+        assert!(xml.contains("<adoszam>12345678-2-42</adoszam>"));
+        assert!(xml.contains("<afakulcs>27</afakulcs>"));
+        assert!(xml.contains("<afakulcs>TAM</afakulcs>"));
+        assert!(xml.contains("<mennyisegiEgyseg>db</mennyisegiEgyseg>"));
+        assert!(xml.contains("<bruttoErtek>12700.00</bruttoErtek>"));
+        assert!(!xml.contains("<arfolyam>"));
+    }
+
+    #[test]
+    fn test_receipt_from_response() {
+        assert_eq!(
+            receipt_from_response(
+                r#"<?xml version="1.0" encoding="UTF-8"?><xmlszamlavalasz xmlns="http://www.szamlazz.hu/xmlszamlavalasz"><sikeres>true</sikeres><szamlaszam>E-OBV-2026-12</szamlaszam><szamlanetto>20000</szamlanetto></xmlszamlavalasz>"#
+            )
+            .unwrap(),
+            ExportReceipt {
+                external_id: Some("E-OBV-2026-12".to_string()),
+                external_number: Some("E-OBV-2026-12".to_string()),
+            }
+        );
+        assert!(matches!(
+            receipt_from_response(
+                "<xmlszamlavalasz><sikeres>false</sikeres><hibakod>3</hibakod><hibauzenet>Sikertelen bejelentkez&#233;s.</hibauzenet></xmlszamlavalasz>"
+            ),
+            Err(AccountingExportError::Rejected(message)) if message == "3: Sikertelen bejelentkezés."
+        ));
+    }
+}
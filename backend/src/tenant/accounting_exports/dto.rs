@@ -0,0 +1,72 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AccountingConnectionInput {
+    pub adapter: String,
+    pub name: String,
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub field_mapping: BTreeMap<String, String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UpdateAccountingConnection {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub connection: AccountingConnectionInput,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PushInvoice {
+    pub connection_id: Uuid,
+    pub receivable_id: Uuid,
+    pub customer_tax_number: Option<String>,
+    pub customer_country_code: Option<String>,
+    pub customer_postal_code: Option<String>,
+    pub customer_city: Option<String>,
+    pub customer_street_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CsvExportQuery {
+    pub connection_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAccountingExport {
+    pub connection_id: Uuid,
+    pub adapter: String,
+    pub receivable_id: Option<Uuid>,
+    pub period_from: Option<NaiveDate>,
+    pub period_to: Option<NaiveDate>,
+    pub invoice_count: i32,
+    pub status: &'static str,
+    pub external_id: Option<String>,
+    pub external_number: Option<String>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,630 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::accounting_exports::AccountingExportsModuleInterface;
+use crate::tenant::accounting_exports::dto::{
+    AccountingConnectionInput, CsvExportQuery, PushInvoice, UpdateAccountingConnection,
+};
+use crate::tenant::accounting_exports::service::AccountingExportsService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn list<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list_connections<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let result = map_handler_err(
+        service.get_connections().await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_connection<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<AccountingConnectionInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let result = map_handler_err(
+        service.create_connection(&payload).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_connection<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateAccountingConnection>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let result = map_handler_err(
+        service.update_connection(&payload).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete_connection<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    map_handler_err(
+        service.delete_connection(payload.uuid).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A kapcsolat törlése sikeresen megtörtént",
+            ))
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn push<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PushInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let result = map_handler_err(
+        service.push(&payload).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        accounting_exports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn export_csv<M: AccountingExportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(accounting_exports_module): State<Arc<M>>,
+    Query(payload): Query<CsvExportQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), accounting_exports_module.clone());
+    let csv = map_handler_err(
+        service.export_csv(&payload).await,
+        accounting_exports_module.clone(),
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(
+            r#"attachment; filename="konyveles_{}_{}.csv""#,
+            payload.from, payload.to
+        )
+        .parse()
+        .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, csv).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::accounting_exports::adapter::{
+        AccountingExportError, MockAccountingExportClient,
+    };
+    use crate::tenant::accounting_exports::model::{
+        AccountingConnection, AccountingExport, ExportInvoiceHeader, ExportInvoiceLine,
+        ExportReceipt, STATUS_EXPORTED, STATUS_FAILED,
+    };
+    use crate::tenant::accounting_exports::{
+        self, repository::MockAccountingExportsRepository, tests::MockAccountingExportsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use sqlx::types::Json;
+    use std::collections::BTreeMap;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockAccountingExportsRepository,
+        tenant_id: Uuid,
+    ) -> MockAccountingExportsModule {
+        let repo = Arc::new(repo);
+        let mut accounting_exports_module = MockAccountingExportsModule::new();
+        accounting_exports_module
+            .expect_accounting_exports_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        accounting_exports_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        accounting_exports_module
+    }
+
+    fn app(accounting_exports_module: MockAccountingExportsModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(accounting_exports::routes::routes(Arc::new(
+                accounting_exports_module,
+            ))),
+        )
+    }
+
+    fn connection(adapter: &str, mapping: &[(&str, &str)]) -> AccountingConnection {
+        AccountingConnection {
+            id: Uuid::new_v4(),
+            adapter: adapter.to_string(),
+            name: "Könyvelő".to_string(),
+            api_key: Some("encrypted".to_string()),
+            field_mapping: Json(
+                mapping
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            enabled: true,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn invoice_header(document_number: &str) -> ExportInvoiceHeader {
+        ExportInvoiceHeader {
+            receivable_id: Uuid::new_v4(),
+            document_number: document_number.to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 9).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(12700),
+            customer_name: "Példa Kft.".to_string(),
+            customer_email: "vevo@example.com".to_string(),
+        }
+    }
+
+    fn line(receivable_id: Uuid) -> ExportInvoiceLine {
+        ExportInvoiceLine {
+            receivable_id,
+            item: "Szerviz".to_string(),
+            description: "Éves karbantartás".to_string(),
+            quantity: BigDecimal::from(1),
+            unit: None,
+            unit_price: BigDecimal::from(10000),
+            tax_rate: BigDecimal::from(27),
+            is_rate_applicable: true,
+            reporting_code: None,
+            net_amount: BigDecimal::from(10000),
+            tax_amount: BigDecimal::from(2700),
+            gross_amount: BigDecimal::from(12700),
+        }
+    }
+
+    fn export(connection: &AccountingConnection, status: &str) -> AccountingExport {
+        AccountingExport {
+            id: Uuid::new_v4(),
+            connection_id: connection.id,
+            adapter: connection.adapter.clone(),
+            receivable_id: None,
+            period_from: None,
+            period_to: None,
+            invoice_count: 1,
+            status: status.to_string(),
+            external_id: None,
+            external_number: None,
+            error: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn push_request(tenant_id: Uuid, connection_id: Uuid, receivable_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/accounting_exports/push")
+            .body(Body::from(
+                json!({
+                    "connection_id": connection_id,
+                    "receivable_id": receivable_id,
+                    "customer_tax_number": "12345678-2-42",
+                    "customer_country_code": "HU",
+                    "customer_postal_code": "1111",
+                    "customer_city": "Budapest",
+                    "customer_street_address": "Példa utca 1."
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_push_logs_exported_invoice() {
+        let tenant_id = Uuid::new_v4();
+        let connection = connection("billingo", &[("block_id", "12")]);
+        let header = invoice_header("SZ-2026-00042");
+        let exported = AccountingExport {
+            receivable_id: Some(header.receivable_id),
+            external_id: Some("987654".to_string()),
+            external_number: Some("E-OBV-2026-12".to_string()),
+            ..export(&connection, STATUS_EXPORTED)
+        };
+
+        let mut repo = MockAccountingExportsRepository::new();
+        repo.expect_get_connection()
+            .with(eq(connection.id))
+            .times(1)
+            .returning({
+                let connection = connection.clone();
+                move |_| Ok(connection.clone())
+            });
+        repo.expect_is_exported()
+            .with(eq(connection.id), eq(header.receivable_id))
+            .times(1)
+            .returning(|_, _| Ok(false));
+        repo.expect_get_invoice_header().times(1).returning({
+            let header = header.clone();
+            move |_| Ok(header.clone())
+        });
+        repo.expect_get_invoice_lines()
+            .with(eq(vec![header.receivable_id]))
+            .times(1)
+            .returning({
+                let receivable_id = header.receivable_id;
+                move |_| Ok(vec![line(receivable_id)])
+            });
+        repo.expect_insert_export()
+            .times(1)
+            .withf(|input, _| {
+                input.status == STATUS_EXPORTED
+                    && input.external_id.as_deref() == Some("987654")
+                    && input.external_number.as_deref() == Some("E-OBV-2026-12")
+            })
+            .returning({
+                let exported = exported.clone();
+                move |_, _| Ok(exported.clone())
+            });
+
+        let mut client = MockAccountingExportClient::new();
+        client
+            .expect_push_invoice()
+            .times(1)
+            .withf(|invoice, customer| {
+                invoice.header.document_number == "SZ-2026-00042"
+                    && invoice.lines.len() == 1
+                    && customer.tax_number.as_deref() == Some("12345678-2-42")
+                    && customer.city == "Budapest"
+            })
+            .returning(|_, _| {
+                Ok(ExportReceipt {
+                    external_id: Some("987654".to_string()),
+                    external_number: Some("E-OBV-2026-12".to_string()),
+                })
+            });
+        let client = Arc::new(client);
+
+        let mut accounting_exports_module = module(repo, tenant_id);
+        accounting_exports_module
+            .expect_accounting_export_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(accounting_exports_module)
+            .oneshot(push_request(tenant_id, connection.id, header.receivable_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": exported})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_logs_failed_attempt() {
+        let tenant_id = Uuid::new_v4();
+        let connection = connection("szamlazz_hu", &[]);
+        let header = invoice_header("SZ-2026-00042");
+
+        let mut repo = MockAccountingExportsRepository::new();
+        repo.expect_get_connection().times(1).returning({
+            let connection = connection.clone();
+            move |_| Ok(connection.clone())
+        });
+        repo.expect_is_exported()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        repo.expect_get_invoice_header().times(1).returning({
+            let header = header.clone();
+            move |_| Ok(header.clone())
+        });
+        repo.expect_get_invoice_lines().times(1).returning({
+            let receivable_id = header.receivable_id;
+            move |_| Ok(vec![line(receivable_id)])
+        });
+        repo.expect_insert_export()
+            .times(1)
+            .withf(|input, _| {
+                input.status == STATUS_FAILED
+                    && input
+                        .error
+                        .as_deref()
+                        .is_some_and(|error| error.contains("Sikertelen bejelentkezés"))
+            })
+            .returning({
+                let failed = export(&connection, STATUS_FAILED);
+                move |_, _| Ok(failed.clone())
+            });
+
+        let mut client = MockAccountingExportClient::new();
+        client.expect_push_invoice().times(1).returning(|_, _| {
+            Err(AccountingExportError::Rejected(
+                "3: Sikertelen bejelentkezés.".to_string(),
+            ))
+        });
+        let client = Arc::new(client);
+
+        let mut accounting_exports_module = module(repo, tenant_id);
+        accounting_exports_module
+            .expect_accounting_export_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(accounting_exports_module)
+            .oneshot(push_request(tenant_id, connection.id, header.receivable_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_push_rejects_already_exported_invoice() {
+        let tenant_id = Uuid::new_v4();
+        let connection = connection("szamlazz_hu", &[]);
+        let receivable_id = Uuid::new_v4();
+
+        let mut repo = MockAccountingExportsRepository::new();
+        repo.expect_get_connection().times(1).returning({
+            let connection = connection.clone();
+            move |_| Ok(connection.clone())
+        });
+        repo.expect_is_exported()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        repo.expect_get_invoice_header().never();
+        repo.expect_insert_export().never();
+
+        let mut accounting_exports_module = module(repo, tenant_id);
+        accounting_exports_module
+            .expect_accounting_export_client()
+            .never();
+
+        let response = app(accounting_exports_module)
+            .oneshot(push_request(tenant_id, connection.id, receivable_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_downloads_and_logs_period() {
+        let tenant_id = Uuid::new_v4();
+        let connection = connection("csv", &[("delimiter", ";")]);
+        let first = invoice_header("SZ-2026-00041");
+        let second = invoice_header("SZ-2026-00042");
+
+        let mut repo = MockAccountingExportsRepository::new();
+        repo.expect_get_connection().times(1).returning({
+            let connection = connection.clone();
+            move |_| Ok(connection.clone())
+        });
+        repo.expect_get_invoice_headers()
+            .with(
+                eq(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()),
+                eq(NaiveDate::from_ymd_opt(2026, 10, 31).unwrap()),
+            )
+            .times(1)
+            .returning({
+                let headers = vec![first.clone(), second.clone()];
+                move |_, _| Ok(headers.clone())
+            });
+        repo.expect_get_invoice_lines().times(1).returning({
+            let lines = vec![line(second.receivable_id), line(first.receivable_id)];
+            move |_| Ok(lines.clone())
+        });
+        repo.expect_insert_export()
+            .times(1)
+            .withf(|input, _| {
+                input.receivable_id.is_none()
+                    && input.invoice_count == 2
+                    && input.period_from == NaiveDate::from_ymd_opt(2026, 10, 1)
+            })
+            .returning({
+                let exported = export(&connection, STATUS_EXPORTED);
+                move |_, _| Ok(exported.clone())
+            });
+
+        let accounting_exports_module = module(repo, tenant_id);
+
+        let response = app(accounting_exports_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .method("GET")
+                    .uri(format!(
+                        "/api/accounting_exports/csv?connection_id={}&from=2026-10-01&to=2026-10-31",
+                        connection.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            r#"attachment; filename="konyveles_2026-10-01_2026-10-31.csv""#
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let rows: Vec<&str> = std::str::from_utf8(&body).unwrap().lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("Számlaszám;Kelt;"));
+        assert!(rows[1].starts_with("SZ-2026-00041;2026-10-01;"));
+        assert!(rows[2].starts_with("SZ-2026-00042;2026-10-01;"));
+    }
+
+    #[tokio::test]
+    async fn test_create_connection_requires_block_id_for_billingo() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockAccountingExportsRepository::new();
+        repo.expect_insert_connection().never();
+
+        let accounting_exports_module = module(repo, tenant_id);
+
+        let response = app(accounting_exports_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("POST")
+                    .uri("/api/accounting_exports/connections/create")
+                    .body(Body::from(
+                        json!({
+                            "adapter": "billingo",
+                            "name": "Billingo",
+                            "api_key": "billingo-api-key",
+                            "field_mapping": {"payment_method": "bankcard"},
+                            "enabled": true
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::{AppState, BaseModule, ConfigProvider};
+use crate::tenant::accounting_exports::adapter::{AccountingExportClient, AccountingExportError};
+use crate::tenant::accounting_exports::model::AccountingConnection;
+use crate::tenant::accounting_exports::repository::AccountingExportsRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub mod adapter;
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+
+pub trait AccountingExportsModuleInterface: BaseModule {
+    fn accounting_exports_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn AccountingExportsRepository + Send + Sync>>;
+    fn accounting_export_client(
+        &self,
+        connection: &AccountingConnection,
+    ) -> Result<Arc<dyn AccountingExportClient + Send + Sync>, AccountingExportError>;
+}
+
+impl<P, T> AccountingExportsModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn accounting_exports_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn AccountingExportsRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+
+    fn accounting_export_client(
+        &self,
+        connection: &AccountingConnection,
+    ) -> Result<Arc<dyn AccountingExportClient + Send + Sync>, AccountingExportError> {
+        adapter::accounting_export_client(
+            connection,
+            Duration::from_secs(self.config().accounting_exports().request_timeout_secs()),
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub AccountingExportsModule {}
+        impl ConfigProvider for AccountingExportsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for AccountingExportsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for AccountingExportsModule {}
+        impl AccountingExportsModuleInterface for AccountingExportsModule {
+            fn accounting_exports_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn AccountingExportsRepository + Send + Sync>>;
+            fn accounting_export_client(
+                &self,
+                connection: &AccountingConnection,
+            ) -> Result<Arc<dyn AccountingExportClient + Send + Sync>, AccountingExportError>;
+        }
+    );
+}
@@ -0,0 +1,146 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub const ADAPTER_SZAMLAZZ_HU: &str = "szamlazz_hu";
+pub const ADAPTER_BILLINGO: &str = "billingo";
+pub const ADAPTER_CSV: &str = "csv";
+pub const ADAPTERS: [&str; 3] = [ADAPTER_SZAMLAZZ_HU, ADAPTER_BILLINGO, ADAPTER_CSV];
+
+pub const STATUS_EXPORTED: &str = "exported";
+pub const STATUS_FAILED: &str = "failed";
+
+// NOTE: field mapping keys understood by the adapters pushing invoices, VAT codes are
+// overridden with `vat_<code>` keys (e.g. `vat_27`, `vat_AAM`)
+pub const MAPPING_PAYMENT_METHOD: &str = "payment_method";
+pub const MAPPING_UNIT: &str = "unit";
+pub const MAPPING_LANGUAGE: &str = "language";
+pub const MAPPING_BLOCK_ID: &str = "block_id";
+pub const MAPPING_DELIMITER: &str = "delimiter";
+pub const MAPPING_VAT_PREFIX: &str = "vat_";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct AccountingConnection {
+    pub id: Uuid,
+    pub adapter: String,
+    pub name: String,
+    #[serde(skip)]
+    pub api_key: Option<String>,
+    pub field_mapping: Json<BTreeMap<String, String>>,
+    pub enabled: bool,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl AccountingConnection {
+    pub fn mapping(&self, key: &str) -> Option<&str> {
+        self.field_mapping.get(key).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct AccountingExport {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub adapter: String,
+    pub receivable_id: Option<Uuid>,
+    pub period_from: Option<NaiveDate>,
+    pub period_to: Option<NaiveDate>,
+    pub invoice_count: i32,
+    pub status: String,
+    pub external_id: Option<String>,
+    pub external_number: Option<String>,
+    pub error: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ExportInvoiceHeader {
+    pub receivable_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    pub customer_name: String,
+    pub customer_email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ExportInvoiceLine {
+    pub receivable_id: Uuid,
+    pub item: String,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit: Option<String>,
+    pub unit_price: BigDecimal,
+    pub tax_rate: BigDecimal,
+    pub is_rate_applicable: bool,
+    pub reporting_code: Option<String>,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+}
+
+impl ExportInvoiceLine {
+    /// VAT code of the line as used by the Hungarian invoicing services: the rate in whole
+    /// percents (e.g. `27`) or the exemption case (e.g. `AAM`)
+    pub fn vat_code(&self) -> String {
+        if self.is_rate_applicable {
+            self.tax_rate
+                .with_scale_round(0, RoundingMode::HalfUp)
+                .to_string()
+        } else {
+            self.reporting_code
+                .clone()
+                .unwrap_or_else(|| "AAM".to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportInvoice {
+    pub header: ExportInvoiceHeader,
+    pub lines: Vec<ExportInvoiceLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportCustomer {
+    pub tax_number: Option<String>,
+    pub country_code: String,
+    pub postal_code: String,
+    pub city: String,
+    pub street_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportReceipt {
+    pub external_id: Option<String>,
+    pub external_number: Option<String>,
+}
@@ -0,0 +1,335 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::accounting_exports::dto::{AccountingConnectionInput, NewAccountingExport};
+use crate::tenant::accounting_exports::model::{
+    AccountingConnection, AccountingExport, ExportInvoiceHeader, ExportInvoiceLine, STATUS_EXPORTED,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::types::Json;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+const INVOICE_HEADER_SELECT: &str = r#"
+    SELECT receivables.id AS receivable_id,
+           receivables.document_number,
+           receivables.issue_date,
+           receivables.due_date,
+           receivables.currency_code,
+           receivables.amount,
+           customers.name AS customer_name,
+           customers.email AS customer_email
+    FROM receivables
+    JOIN customers ON receivables.customer_id = customers.id
+"#;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AccountingExportsRepository: Send + Sync {
+    async fn get_connections(&self) -> RepositoryResult<Vec<AccountingConnection>>;
+    async fn get_connection(&self, id: Uuid) -> RepositoryResult<AccountingConnection>;
+    async fn insert_connection(
+        &self,
+        input: &AccountingConnectionInput,
+        api_key: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<AccountingConnection>;
+    async fn update_connection(
+        &self,
+        id: Uuid,
+        input: &AccountingConnectionInput,
+        api_key: Option<String>,
+    ) -> RepositoryResult<AccountingConnection>;
+    async fn delete_connection(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_invoice_header(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<ExportInvoiceHeader>;
+    async fn get_invoice_headers(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<ExportInvoiceHeader>>;
+    async fn get_invoice_lines(
+        &self,
+        receivable_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<ExportInvoiceLine>>;
+    async fn is_exported(&self, connection_id: Uuid, receivable_id: Uuid)
+    -> RepositoryResult<bool>;
+    async fn get_exports_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<AccountingExport>)>;
+    async fn insert_export(
+        &self,
+        input: &NewAccountingExport,
+        sub: Uuid,
+    ) -> RepositoryResult<AccountingExport>;
+}
+
+#[async_trait]
+impl AccountingExportsRepository for PgPool {
+    async fn get_connections(&self) -> RepositoryResult<Vec<AccountingConnection>> {
+        Ok(sqlx::query_as::<_, AccountingConnection>(
+            "SELECT * FROM accounting_connections WHERE deleted_at IS NULL ORDER BY name",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_connection(&self, id: Uuid) -> RepositoryResult<AccountingConnection> {
+        Ok(sqlx::query_as::<_, AccountingConnection>(
+            "SELECT * FROM accounting_connections WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_connection(
+        &self,
+        input: &AccountingConnectionInput,
+        api_key: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<AccountingConnection> {
+        Ok(sqlx::query_as::<_, AccountingConnection>(
+            r#"
+            INSERT INTO accounting_connections (adapter, name, api_key, field_mapping, enabled,
+                                                created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.adapter)
+        .bind(&input.name)
+        .bind(api_key)
+        .bind(Json(&input.field_mapping))
+        .bind(input.enabled)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update_connection(
+        &self,
+        id: Uuid,
+        input: &AccountingConnectionInput,
+        api_key: Option<String>,
+    ) -> RepositoryResult<AccountingConnection> {
+        Ok(sqlx::query_as::<_, AccountingConnection>(
+            r#"
+            UPDATE accounting_connections
+            SET adapter       = $2,
+                name          = $3,
+                api_key       = $4,
+                field_mapping = $5,
+                enabled       = $6
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&input.adapter)
+        .bind(&input.name)
+        .bind(api_key)
+        .bind(Json(&input.field_mapping))
+        .bind(input.enabled)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_connection(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE accounting_connections
+            SET deleted_at = now()
+            WHERE id = $1
+              AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_invoice_header(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<ExportInvoiceHeader> {
+        Ok(
+            sqlx::query_as::<_, ExportInvoiceHeader>(AssertSqlSafe(format!(
+                r#"
+            {INVOICE_HEADER_SELECT}
+            WHERE receivables.id = $1
+              AND receivables.deleted_at IS NULL
+            "#
+            )))
+            .bind(receivable_id)
+            .fetch_one(self)
+            .await?,
+        )
+    }
+
+    async fn get_invoice_headers(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<ExportInvoiceHeader>> {
+        Ok(
+            sqlx::query_as::<_, ExportInvoiceHeader>(AssertSqlSafe(format!(
+                r#"
+            {INVOICE_HEADER_SELECT}
+            WHERE receivables.issue_date BETWEEN $1 AND $2
+              AND receivables.deleted_at IS NULL
+            ORDER BY receivables.issue_date, receivables.document_number
+            "#
+            )))
+            .bind(from)
+            .bind(to)
+            .fetch_all(self)
+            .await?,
+        )
+    }
+
+    async fn get_invoice_lines(
+        &self,
+        receivable_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<ExportInvoiceLine>> {
+        Ok(sqlx::query_as::<_, ExportInvoiceLine>(
+            r#"
+            SELECT receivable_lines.receivable_id,
+                   COALESCE(services.name, products.name) AS item,
+                   receivable_lines.description,
+                   receivable_lines.quantity,
+                   units_of_measure.unit_of_measure AS unit,
+                   receivable_lines.unit_price,
+                   receivable_lines.tax_rate,
+                   taxes.is_rate_applicable,
+                   taxes.reporting_code,
+                   receivable_lines.net_amount,
+                   receivable_lines.tax_amount,
+                   receivable_lines.net_amount + receivable_lines.tax_amount AS gross_amount
+            FROM receivable_lines
+            JOIN taxes ON receivable_lines.tax_id = taxes.id
+            LEFT JOIN services ON receivable_lines.service_id = services.id
+            LEFT JOIN products ON receivable_lines.product_id = products.id
+            LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+            WHERE receivable_lines.receivable_id = ANY($1)
+            ORDER BY receivable_lines.receivable_id, receivable_lines.position
+            "#,
+        )
+        .bind(receivable_ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn is_exported(
+        &self,
+        connection_id: Uuid,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<bool> {
+        let exported: (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS(SELECT 1
+                          FROM accounting_exports
+                          WHERE connection_id = $1
+                            AND receivable_id = $2
+                            AND status = $3)
+            "#,
+        )
+        .bind(connection_id)
+        .bind(receivable_id)
+        .bind(STATUS_EXPORTED)
+        .fetch_one(self)
+        .await?;
+        Ok(exported.0)
+    }
+
+    async fn get_exports_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<AccountingExport>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounting_exports")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let exports = sqlx::query_as::<_, AccountingExport>(
+            r#"
+            SELECT *
+            FROM accounting_exports
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            exports,
+        ))
+    }
+
+    async fn insert_export(
+        &self,
+        input: &NewAccountingExport,
+        sub: Uuid,
+    ) -> RepositoryResult<AccountingExport> {
+        Ok(sqlx::query_as::<_, AccountingExport>(
+            r#"
+            INSERT INTO accounting_exports (connection_id, adapter, receivable_id, period_from,
+                                            period_to, invoice_count, status, external_id,
+                                            external_number, error, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(input.connection_id)
+        .bind(&input.adapter)
+        .bind(input.receivable_id)
+        .bind(input.period_from)
+        .bind(input.period_to)
+        .bind(input.invoice_count)
+        .bind(input.status)
+        .bind(&input.external_id)
+        .bind(&input.external_number)
+        .bind(&input.error)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,469 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::{CryptoError, encrypt_secret};
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::accounting_exports::AccountingExportsModuleInterface;
+use crate::tenant::accounting_exports::adapter::AccountingExportError;
+use crate::tenant::accounting_exports::adapter::csv::{COLUMNS, invoices_csv};
+use crate::tenant::accounting_exports::dto::{
+    AccountingConnectionInput, CsvExportQuery, NewAccountingExport, PushInvoice,
+    UpdateAccountingConnection,
+};
+use crate::tenant::accounting_exports::model::{
+    ADAPTER_BILLINGO, ADAPTER_CSV, ADAPTERS, AccountingConnection, AccountingExport,
+    ExportCustomer, ExportInvoice, ExportInvoiceLine, MAPPING_BLOCK_ID, MAPPING_DELIMITER,
+    MAPPING_LANGUAGE, MAPPING_PAYMENT_METHOD, MAPPING_UNIT, MAPPING_VAT_PREFIX, STATUS_EXPORTED,
+    STATUS_FAILED,
+};
+use crate::tenant::nav_reporting::service::is_tax_number;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum AccountingExportsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Export(#[from] AccountingExportError),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for AccountingExportsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => AccountingExportsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<AccountingExportsServiceError> for AppError {
+    fn from(value: AccountingExportsServiceError) -> Self {
+        match value {
+            AccountingExportsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            AccountingExportsServiceError::UnprocessableEntry(_)
+            | AccountingExportsServiceError::Export(AccountingExportError::Rejected(_))
+            | AccountingExportsServiceError::Export(AccountingExportError::Configuration(_))
+            | AccountingExportsServiceError::Export(AccountingExportError::Unsupported(_)) => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            AccountingExportsServiceError::Export(AccountingExportError::Http(_)) => Self::new(
+                Level::WARN,
+                StatusCode::BAD_GATEWAY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "A könyvelési szolgáltatás jelenleg nem érhető el!"}),
+            ),
+            AccountingExportsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type AccountingExportsServiceResult<T> = Result<T, AccountingExportsServiceError>;
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn validate_mapping(
+    adapter: &str,
+    field_mapping: &BTreeMap<String, String>,
+) -> AccountingExportsServiceResult<BTreeMap<String, String>> {
+    let mut mapping = BTreeMap::new();
+    for (key, value) in field_mapping {
+        let (key, value) = (key.trim(), value.trim());
+        let known = if adapter == ADAPTER_CSV {
+            // NOTE: an empty header leaves the column out of the file
+            COLUMNS.iter().any(|(column, _)| *column == key)
+                || key == MAPPING_DELIMITER && (value == "," || value == ";")
+        } else {
+            [MAPPING_PAYMENT_METHOD, MAPPING_UNIT, MAPPING_LANGUAGE].contains(&key)
+                || key == MAPPING_BLOCK_ID && adapter == ADAPTER_BILLINGO
+        };
+        if !known && !key.starts_with(MAPPING_VAT_PREFIX) {
+            return Err(AccountingExportsServiceError::UnprocessableEntry(
+                "Ismeretlen mező hozzárendelés!",
+            ));
+        }
+        if value.is_empty() && adapter != ADAPTER_CSV {
+            continue;
+        }
+        mapping.insert(key.to_string(), value.to_string());
+    }
+    if adapter == ADAPTER_BILLINGO
+        && mapping
+            .get(MAPPING_BLOCK_ID)
+            .and_then(|block_id| block_id.parse::<i64>().ok())
+            .is_none_or(|block_id| block_id <= 0)
+    {
+        return Err(AccountingExportsServiceError::UnprocessableEntry(
+            "Billingo esetén a számlatömb azonosítójának megadása kötelező!",
+        ));
+    }
+    Ok(mapping)
+}
+
+fn validate_connection(
+    payload: &AccountingConnectionInput,
+    current: Option<&AccountingConnection>,
+) -> AccountingExportsServiceResult<(AccountingConnectionInput, Option<String>)> {
+    if !ADAPTERS.contains(&payload.adapter.as_str()) {
+        return Err(AccountingExportsServiceError::UnprocessableEntry(
+            "Nem támogatott könyvelési kapcsolat!",
+        ));
+    }
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(AccountingExportsServiceError::UnprocessableEntry(
+            "A kapcsolat nevének megadása kötelező!",
+        ));
+    }
+    let field_mapping = validate_mapping(&payload.adapter, &payload.field_mapping)?;
+
+    // NOTE: the stored API key is only kept while the adapter does not change
+    let current = current.filter(|connection| connection.adapter == payload.adapter);
+    let api_key = match optional(&payload.api_key) {
+        _ if payload.adapter == ADAPTER_CSV => None,
+        Some(api_key) => Some(encrypt_secret(&api_key)?),
+        None => Some(
+            current
+                .and_then(|connection| connection.api_key.clone())
+                .ok_or(AccountingExportsServiceError::UnprocessableEntry(
+                    "Az API kulcs megadása kötelező!",
+                ))?,
+        ),
+    };
+    Ok((
+        AccountingConnectionInput {
+            adapter: payload.adapter.clone(),
+            name: name.to_string(),
+            api_key: None,
+            field_mapping,
+            enabled: payload.enabled,
+        },
+        api_key,
+    ))
+}
+
+fn validate_customer(payload: &PushInvoice) -> AccountingExportsServiceResult<ExportCustomer> {
+    let country_code = optional(&payload.customer_country_code)
+        .map(|country_code| country_code.to_uppercase())
+        .unwrap_or_else(|| "HU".to_string());
+    let tax_number = optional(&payload.customer_tax_number);
+    if let Some(tax_number) = &tax_number
+        && country_code == "HU"
+        && !is_tax_number(tax_number)
+    {
+        return Err(AccountingExportsServiceError::UnprocessableEntry(
+            "Belföldi vevő adószámát xxxxxxxx-y-zz formátumban kell megadni!",
+        ));
+    }
+    let missing_address =
+        || AccountingExportsServiceError::UnprocessableEntry("A vevő címének megadása kötelező!");
+    Ok(ExportCustomer {
+        tax_number,
+        country_code,
+        postal_code: optional(&payload.customer_postal_code).ok_or_else(missing_address)?,
+        city: optional(&payload.customer_city).ok_or_else(missing_address)?,
+        street_address: optional(&payload.customer_street_address).ok_or_else(missing_address)?,
+    })
+}
+
+fn enabled_connection(
+    connection: AccountingConnection,
+    csv: bool,
+) -> AccountingExportsServiceResult<AccountingConnection> {
+    if !connection.enabled {
+        return Err(AccountingExportsServiceError::UnprocessableEntry(
+            "A kapcsolat nincs engedélyezve!",
+        ));
+    }
+    match (connection.adapter == ADAPTER_CSV, csv) {
+        (true, false) => Err(AccountingExportsServiceError::UnprocessableEntry(
+            "CSV kapcsolattal csak fájl exportálható!",
+        )),
+        (false, true) => Err(AccountingExportsServiceError::UnprocessableEntry(
+            "A kapcsolat nem CSV export!",
+        )),
+        _ => Ok(connection),
+    }
+}
+
+pub trait AccountingExportsService {
+    fn get_connections(
+        &self,
+    ) -> impl Future<Output = AccountingExportsServiceResult<Vec<AccountingConnection>>> + Send;
+    fn create_connection(
+        &self,
+        payload: &AccountingConnectionInput,
+    ) -> impl Future<Output = AccountingExportsServiceResult<AccountingConnection>> + Send;
+    fn update_connection(
+        &self,
+        payload: &UpdateAccountingConnection,
+    ) -> impl Future<Output = AccountingExportsServiceResult<AccountingConnection>> + Send;
+    fn delete_connection(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = AccountingExportsServiceResult<()>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = AccountingExportsServiceResult<(PaginatorMeta, Vec<AccountingExport>)>>
+    + Send;
+    fn push(
+        &self,
+        payload: &PushInvoice,
+    ) -> impl Future<Output = AccountingExportsServiceResult<AccountingExport>> + Send;
+    fn export_csv(
+        &self,
+        query: &CsvExportQuery,
+    ) -> impl Future<Output = AccountingExportsServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> AccountingExportsService for Service<'a, T>
+where
+    T: AccountingExportsModuleInterface,
+{
+    async fn get_connections(&self) -> AccountingExportsServiceResult<Vec<AccountingConnection>> {
+        Ok(self
+            .module()
+            .accounting_exports_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(AccountingExportsServiceError::Unauthorized)?,
+            )?
+            .get_connections()
+            .await?)
+    }
+
+    async fn create_connection(
+        &self,
+        payload: &AccountingConnectionInput,
+    ) -> AccountingExportsServiceResult<AccountingConnection> {
+        let (input, api_key) = validate_connection(payload, None)?;
+        Ok(self
+            .module()
+            .accounting_exports_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(AccountingExportsServiceError::Unauthorized)?,
+            )?
+            .insert_connection(&input, api_key, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update_connection(
+        &self,
+        payload: &UpdateAccountingConnection,
+    ) -> AccountingExportsServiceResult<AccountingConnection> {
+        let repo = self.module().accounting_exports_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(AccountingExportsServiceError::Unauthorized)?,
+        )?;
+        let current = repo.get_connection(payload.id).await?;
+        let (input, api_key) = validate_connection(&payload.connection, Some(&current))?;
+        Ok(repo.update_connection(payload.id, &input, api_key).await?)
+    }
+
+    async fn delete_connection(&self, id: Uuid) -> AccountingExportsServiceResult<()> {
+        Ok(self
+            .module()
+            .accounting_exports_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(AccountingExportsServiceError::Unauthorized)?,
+            )?
+            .delete_connection(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> AccountingExportsServiceResult<(PaginatorMeta, Vec<AccountingExport>)> {
+        Ok(self
+            .module()
+            .accounting_exports_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(AccountingExportsServiceError::Unauthorized)?,
+            )?
+            .get_exports_paged(get_query)
+            .await?)
+    }
+
+    // NOTE: failed attempts are logged as well, the error is returned after that
+    async fn push(
+        &self,
+        payload: &PushInvoice,
+    ) -> AccountingExportsServiceResult<AccountingExport> {
+        let repo = self.module().accounting_exports_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(AccountingExportsServiceError::Unauthorized)?,
+        )?;
+        let connection =
+            enabled_connection(repo.get_connection(payload.connection_id).await?, false)?;
+        if repo
+            .is_exported(connection.id, payload.receivable_id)
+            .await?
+        {
+            return Err(AccountingExportsServiceError::UnprocessableEntry(
+                "A számla ezzel a kapcsolattal már exportálva lett!",
+            ));
+        }
+        let customer = validate_customer(payload)?;
+        let header = repo.get_invoice_header(payload.receivable_id).await?;
+        let lines = repo.get_invoice_lines(vec![header.receivable_id]).await?;
+        if lines.is_empty() {
+            return Err(AccountingExportsServiceError::UnprocessableEntry(
+                "Csak tételes számla exportálható!",
+            ));
+        }
+        let invoice = ExportInvoice { header, lines };
+
+        let result = self
+            .module()
+            .accounting_export_client(&connection)?
+            .push_invoice(&invoice, &customer)
+            .await;
+        let mut entry = NewAccountingExport {
+            connection_id: connection.id,
+            adapter: connection.adapter.clone(),
+            receivable_id: Some(invoice.header.receivable_id),
+            period_from: None,
+            period_to: None,
+            invoice_count: 1,
+            status: STATUS_EXPORTED,
+            external_id: None,
+            external_number: None,
+            error: None,
+        };
+        match result {
+            Ok(receipt) => {
+                entry.external_id = receipt.external_id;
+                entry.external_number = receipt.external_number;
+                Ok(repo.insert_export(&entry, self.claims()?.sub()).await?)
+            }
+            Err(e) => {
+                entry.status = STATUS_FAILED;
+                entry.error = Some(e.to_string());
+                repo.insert_export(&entry, self.claims()?.sub()).await?;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn export_csv(&self, query: &CsvExportQuery) -> AccountingExportsServiceResult<Vec<u8>> {
+        if query.from > query.to {
+            return Err(AccountingExportsServiceError::UnprocessableEntry(
+                "Hibás időszak!",
+            ));
+        }
+        let repo = self.module().accounting_exports_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(AccountingExportsServiceError::Unauthorized)?,
+        )?;
+        let connection = enabled_connection(repo.get_connection(query.connection_id).await?, true)?;
+        let headers = repo.get_invoice_headers(query.from, query.to).await?;
+        let mut lines: HashMap<Uuid, Vec<ExportInvoiceLine>> = HashMap::new();
+        for line in repo
+            .get_invoice_lines(headers.iter().map(|header| header.receivable_id).collect())
+            .await?
+        {
+            lines.entry(line.receivable_id).or_default().push(line);
+        }
+        let invoices: Vec<ExportInvoice> = headers
+            .into_iter()
+            .map(|header| ExportInvoice {
+                lines: lines.remove(&header.receivable_id).unwrap_or_default(),
+                header,
+            })
+            .collect();
+        let csv = invoices_csv(&connection.field_mapping.0, &invoices)?;
+        repo.insert_export(
+            &NewAccountingExport {
+                connection_id: connection.id,
+                adapter: connection.adapter.clone(),
+                receivable_id: None,
+                period_from: Some(query.from),
+                period_to: Some(query.to),
+                invoice_count: i32::try_from(invoices.len()).unwrap_or(i32::MAX),
+                status: STATUS_EXPORTED,
+                external_id: None,
+                external_number: None,
+                error: None,
+            },
+            self.claims()?.sub(),
+        )
+        .await?;
+        Ok(csv)
+    }
+}
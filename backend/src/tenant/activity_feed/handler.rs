@@ -219,6 +219,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -264,6 +265,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -360,6 +362,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
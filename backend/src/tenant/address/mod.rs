@@ -17,5 +17,6 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod model;
 pub(crate) mod repository;
 pub(crate) mod types;
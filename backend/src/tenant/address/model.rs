@@ -0,0 +1,30 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Where a customer is invoiced to: the first address recorded for it, with whether it buys as a
+/// business, i.e. it is a legal entity or has a tax number
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct BillingAddress {
+    pub country_code: String,
+    pub region: Option<String>,
+    pub is_business: bool,
+}
@@ -19,15 +19,21 @@
 
 use crate::common::error::RepositoryResult;
 use crate::common::model::SelectOption;
+use crate::tenant::address::model::BillingAddress;
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait AddressRepository: Send + Sync {
     async fn get_all_countries_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>>;
+    async fn get_customer_billing_address(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Option<BillingAddress>>;
 }
 
 #[async_trait]
@@ -39,4 +45,30 @@ impl AddressRepository for PgPool {
         .fetch_all(self)
         .await?)
     }
+
+    async fn get_customer_billing_address(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Option<BillingAddress>> {
+        Ok(sqlx::query_as::<_, BillingAddress>(
+            r#"
+            SELECT address.country_code,
+                   states.name AS region,
+                   customers.customer_type = 'legal' OR customers.tax_number IS NOT NULL AS is_business
+            FROM customers
+            JOIN address_connect ON address_connect.addressable_type = 'customers'
+                AND address_connect.addressable_id = customers.id
+                AND address_connect.deleted_at IS NULL
+            JOIN address ON address_connect.address_id = address.id
+                AND address.deleted_at IS NULL
+            LEFT JOIN states ON address.state_id = states.id
+            WHERE customers.id = $1
+            ORDER BY address.created_at
+            LIMIT 1
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_optional(self)
+        .await?)
+    }
 }
@@ -0,0 +1,80 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::products::dto::attachment::sanitize_file_name;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachableType {
+    Tasks,
+    Worksheets,
+}
+
+impl AttachableType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttachableType::Tasks => "tasks",
+            AttachableType::Worksheets => "worksheets",
+        }
+    }
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "tasks" => Some(AttachableType::Tasks),
+            "worksheets" => Some(AttachableType::Worksheets),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AttachmentsQuery {
+    pub attachable_type: AttachableType,
+    pub attachable_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachmentUpload {
+    pub attachable_type: AttachableType,
+    pub attachable_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl AttachmentUpload {
+    // NOTE: images are recognised by their content, otherwise the client supplied type is used without parameters
+    pub fn detected_content_type(&self) -> String {
+        match image::guess_format(&self.data) {
+            Ok(format) => format.to_mime_type().to_string(),
+            Err(_) => self
+                .content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase(),
+        }
+    }
+
+    pub fn sanitized_file_name(&self) -> String {
+        sanitize_file_name(&self.file_name)
+    }
+}
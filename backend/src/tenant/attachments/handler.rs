@@ -0,0 +1,621 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::attachments::AttachmentsModuleInterface;
+use crate::tenant::attachments::dto::{AttachableType, AttachmentUpload, AttachmentsQuery};
+use crate::tenant::attachments::service::{AttachmentsService, AttachmentsServiceError};
+use crate::tenant::products::dto::attachment::content_disposition;
+use axum::extract::{Multipart, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn read_upload(
+    mut multipart: Multipart,
+) -> Result<AttachmentUpload, AttachmentsServiceError> {
+    let invalid = |_| AttachmentsServiceError::UnprocessableEntry("Hibás feltöltési kérés!");
+    let mut attachable_type = None;
+    let mut attachable_id = None;
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        match field.name() {
+            Some("attachable_type") => {
+                attachable_type = Some(
+                    AttachableType::parse(&field.text().await.map_err(invalid)?).ok_or(
+                        AttachmentsServiceError::UnprocessableEntry(
+                            "Csatolmány csak feladathoz vagy munkalaphoz adható!",
+                        ),
+                    )?,
+                );
+            }
+            Some("attachable_id") => {
+                attachable_id = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(invalid)?
+                        .trim()
+                        .parse::<Uuid>()
+                        .map_err(|_| {
+                            AttachmentsServiceError::UnprocessableEntry("Hibás azonosító!")
+                        })?,
+                );
+            }
+            Some("file") => {
+                let file_name = field.file_name().unwrap_or_default().to_string();
+                let content_type = field.content_type().unwrap_or_default().to_string();
+                file = Some((
+                    file_name,
+                    content_type,
+                    field.bytes().await.map_err(invalid)?,
+                ));
+            }
+            _ => {}
+        }
+    }
+    match (attachable_type, attachable_id, file) {
+        (Some(attachable_type), Some(attachable_id), Some((file_name, content_type, data))) => {
+            Ok(AttachmentUpload {
+                attachable_type,
+                attachable_id,
+                file_name,
+                content_type,
+                data: data.to_vec(),
+            })
+        }
+        _ => Err(AttachmentsServiceError::UnprocessableEntry(
+            "A típus, az azonosító és a fájl megadása kötelező!",
+        )),
+    }
+}
+
+pub async fn list<M: AttachmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(attachments_module): State<Arc<M>>,
+    Query(payload): Query<AttachmentsQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), attachments_module.clone());
+    let result = map_handler_err(service.list(&payload).await, attachments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        attachments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn upload<M: AttachmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(attachments_module): State<Arc<M>>,
+    multipart: Multipart,
+) -> HandlerResult {
+    let payload = map_handler_err(read_upload(multipart).await, attachments_module.clone()).await?;
+    let service = Service::new(Some(&claims), attachments_module.clone());
+    let result = map_handler_err(service.upload(payload).await, attachments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        attachments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn download<M: AttachmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(attachments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), attachments_module.clone());
+    let (attachment, data) =
+        map_handler_err(service.download(payload.uuid).await, attachments_module).await?;
+    let disposition = if attachment.content_type.starts_with("image/") {
+        "inline"
+    } else {
+        "attachment"
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        attachment.content_type.parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        content_disposition(disposition, &attachment.file_name)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, data).into_response())
+}
+
+pub async fn delete<M: AttachmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(attachments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), attachments_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        attachments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A csatolmány törlése sikeresen megtörtént",
+            ))
+            .build(),
+        attachments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::common::storage::MockFileStorage;
+    use crate::manager::tenant_limits::model::TenantLimits;
+    use crate::manager::tenant_limits::repository::MockTenantLimitsRepository;
+    use crate::tenant::attachments::model::Attachment;
+    use crate::tenant::attachments::{
+        self, repository::MockAttachmentsRepository, tests::MockAttachmentsModule,
+    };
+    use crate::tenant::products::dto::attachment::tests::png;
+    use crate::tenant::project_members::tests::project_access_repos;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use tower::ServiceExt;
+
+    fn app(
+        repo: MockAttachmentsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        app_with_limits(repo, storage, active_tenant_id, None)
+    }
+
+    fn app_with_limits(
+        repo: MockAttachmentsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> Router {
+        router(module(
+            repo,
+            storage,
+            active_tenant_id,
+            limits,
+            Some("member"),
+        ))
+    }
+
+    /// Every task and worksheet belongs to a project, where the user holds `project_role`
+    fn module(
+        repo: MockAttachmentsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+        limits: Option<TenantLimits>,
+        project_role: Option<&'static str>,
+    ) -> MockAttachmentsModule {
+        let repo = Arc::new(repo);
+        let (project_members_repo, membership_repo, permissions_repo) =
+            project_access_repos(project_role);
+        let storage = Arc::new(storage);
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(limits.clone()));
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
+        let mut attachments_module = MockAttachmentsModule::new();
+        attachments_module
+            .expect_attachments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        attachments_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        attachments_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        attachments_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        attachments_module
+            .expect_tenant_limits_repo()
+            .returning(move || tenant_limits_repo.clone());
+        attachments_module
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        // NOTE: the routes read the upload size limit and the service the allowed types as well
+        attachments_module
+            .expect_config()
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        attachments_module
+    }
+
+    fn router(attachments_module: MockAttachmentsModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(attachments::routes::routes(Arc::new(attachments_module))),
+        )
+    }
+
+    fn upload_request(
+        active_tenant_id: Uuid,
+        task_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Request<Body> {
+        let boundary = "obvia-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"attachable_type\"\r\n\r\ntasks\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"attachable_id\"\r\n\r\n{task_id}\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+             Content-Type: {content_type}\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .method("POST")
+            .uri("/api/attachments/upload")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn attachment(file_name: &str, content_type: &str, storage_key: &str) -> Attachment {
+        Attachment {
+            id: Uuid::new_v4(),
+            attachable_type: "worksheets".to_string(),
+            attachable_id: Uuid::new_v4(),
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            size_bytes: 8,
+            storage_key: storage_key.to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_stores_file_under_tenant_prefix() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_attachable_exists()
+            .times(1)
+            .with(eq(AttachableType::Tasks), eq(task_id))
+            .returning(|_, _| Ok(true));
+        repo.expect_insert()
+            .times(1)
+            .withf(move |attachment, limits| {
+                attachment.attachable_type == "tasks"
+                    && attachment.attachable_id == task_id
+                    && attachment.content_type == "image/png"
+                    && attachment.file_name == "helyszin.png"
+                    && limits.is_none()
+            })
+            .returning(|attachment, _| Ok(Some(attachment.clone())));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_put()
+            .times(1)
+            .withf(move |key, content_type, _| {
+                key.starts_with(&format!("{active_tenant_id}/attachments/tasks/{task_id}/"))
+                    && content_type == "image/png"
+            })
+            .returning(|_, _, _| Ok(()));
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(upload_request(
+                active_tenant_id,
+                task_id,
+                "C:\\fakepath\\helyszin.png",
+                "application/octet-stream",
+                &png(8, 8),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["file_name"], "helyszin.png");
+        assert!(body["data"].get("storage_key").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_type_not_allowed_by_config() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_attachable_exists().never();
+        repo.expect_insert().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().never();
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(upload_request(
+                active_tenant_id,
+                Uuid::new_v4(),
+                "setup.exe",
+                "application/x-msdownload",
+                b"MZ\x90\x00",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_missing_task_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_attachable_exists()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        repo.expect_insert().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().never();
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(upload_request(
+                active_tenant_id,
+                Uuid::new_v4(),
+                "jegyzokonyv.pdf",
+                "application/pdf; charset=binary",
+                b"%PDF-1.7",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_storage_limit_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_attachable_exists()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        repo.expect_insert()
+            .times(1)
+            .withf(|_, limits| limits.as_ref().is_some_and(|l| l.max_storage_mb == Some(1)))
+            .returning(|_, _| Ok(None));
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().times(1).returning(|_, _, _| Ok(()));
+        storage
+            .expect_delete()
+            .times(1)
+            .withf(move |key| key.starts_with(&format!("{active_tenant_id}/attachments/tasks/")))
+            .returning(|_| Ok(()));
+
+        let response = app_with_limits(
+            repo,
+            storage,
+            active_tenant_id,
+            Some(TenantLimits {
+                tenant_id: active_tenant_id,
+                max_storage_mb: Some(1),
+                ..Default::default()
+            }),
+        )
+        .oneshot(upload_request(
+            active_tenant_id,
+            Uuid::new_v4(),
+            "jegyzokonyv.pdf",
+            "application/pdf",
+            b"%PDF-1.7",
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    }
+
+    #[tokio::test]
+    async fn test_upload_removes_stored_file_when_insert_fails() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_attachable_exists()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        repo.expect_insert()
+            .times(1)
+            .returning(|_, _| Err(RepositoryError::Database(sqlx::Error::PoolTimedOut)));
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().times(1).returning(|_, _, _| Ok(()));
+        storage
+            .expect_delete()
+            .times(1)
+            .withf(move |key| key.starts_with(&format!("{active_tenant_id}/attachments/tasks/")))
+            .returning(|_| Ok(()));
+        let mut attachments_module = module(repo, storage, active_tenant_id, None, Some("member"));
+        attachments_module
+            .expect_send()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let response = router(attachments_module)
+            .oneshot(upload_request(
+                active_tenant_id,
+                Uuid::new_v4(),
+                "jegyzokonyv.pdf",
+                "application/pdf",
+                b"%PDF-1.7",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_download_sends_file_as_attachment() {
+        let active_tenant_id = Uuid::new_v4();
+        let stored = attachment("mérési jegyzőkönyv.pdf", "application/pdf", "t/a/1");
+        let id = stored.id;
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(id))
+            .returning(move |_| Ok(stored.clone()));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_get()
+            .times(1)
+            .with(eq("t/a/1"))
+            .returning(|_| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri(format!("/api/attachments/download?uuid={id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+        assert!(
+            response.headers()[header::CONTENT_DISPOSITION]
+                .to_str()
+                .unwrap()
+                .starts_with("attachment; ")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_stored_file() {
+        let active_tenant_id = Uuid::new_v4();
+        let deleted = attachment("foto.jpg", "image/jpeg", "t/a/2");
+        let id = deleted.id;
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_get_by_id().times(1).with(eq(id)).returning({
+            let deleted = deleted.clone();
+            move |_| Ok(deleted.clone())
+        });
+        repo.expect_delete_by_id()
+            .times(1)
+            .with(eq(id))
+            .returning(move |_| Ok(deleted.clone()));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_delete()
+            .times(1)
+            .with(eq("t/a/2"))
+            .returning(|_| Ok(()));
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("DELETE")
+                    .uri(format!("/api/attachments/delete?uuid={id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_download_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let stored = attachment("mérési jegyzőkönyv.pdf", "application/pdf", "t/a/1");
+        let id = stored.id;
+        let mut repo = MockAttachmentsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(id))
+            .returning(move |_| Ok(stored.clone()));
+        let mut storage = MockFileStorage::new();
+        storage.expect_get().never();
+
+        let response = router(module(repo, storage, active_tenant_id, None, None))
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri(format!("/api/attachments/download?uuid={id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
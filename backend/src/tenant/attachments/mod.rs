@@ -0,0 +1,119 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::storage::{FileStorage, file_storage};
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenant_limits::repository::TenantLimitsRepository;
+use crate::tenant::attachments::repository::AttachmentsRepository;
+use crate::tenant::project_members::ProjectMembersModuleInterface;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+
+pub trait AttachmentsModuleInterface: ProjectMembersModuleInterface {
+    fn attachments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn AttachmentsRepository + Send + Sync>>;
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
+}
+
+impl<P, T> AttachmentsModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn attachments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn AttachmentsRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync> {
+        self.get_main_pool()
+    }
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync> {
+        file_storage(self.config().storage())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::TenantsRepository;
+    use crate::tenant::permissions::PermissionsModuleInterface;
+    use crate::tenant::permissions::repository::PermissionsRepository;
+    use crate::tenant::project_members::repository::ProjectMembersRepository;
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub AttachmentsModule {}
+        impl ConfigProvider for AttachmentsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for AttachmentsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for AttachmentsModule {}
+        impl PermissionsModuleInterface for AttachmentsModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
+        impl ProjectMembersModuleInterface for AttachmentsModule {
+            fn project_members_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>>;
+        }
+        impl AttachmentsModuleInterface for AttachmentsModule {
+            fn attachments_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn AttachmentsRepository + Send + Sync>>;
+            fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+            fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
+        }
+    );
+}
@@ -0,0 +1,164 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::manager::tenant_limits::model::TenantLimits;
+use crate::tenant::attachments::dto::AttachableType;
+use crate::tenant::attachments::model::Attachment;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Bytes stored in every attachment of the tenant, the storage limit is checked against it.
+/// Takes the storage quota lock for the rest of the transaction, so that concurrent uploads
+/// are checked and inserted one after the other.
+pub(crate) async fn lock_attachments_size_total(conn: &mut PgConnection) -> RepositoryResult<i64> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('storage_quota'))")
+        .execute(&mut *conn)
+        .await?;
+    Ok(sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT ((SELECT COALESCE(SUM(size_bytes), 0)
+                 FROM product_attachments
+                 WHERE deleted_at IS NULL)
+            + (SELECT COALESCE(SUM(size_bytes), 0)
+               FROM attachments
+               WHERE deleted_at IS NULL))::BIGINT
+        "#,
+    )
+    .fetch_one(&mut *conn)
+    .await?)
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AttachmentsRepository: Send + Sync {
+    async fn attachable_exists(
+        &self,
+        attachable_type: AttachableType,
+        attachable_id: Uuid,
+    ) -> RepositoryResult<bool>;
+    async fn get_by_attachable(
+        &self,
+        attachable_type: AttachableType,
+        attachable_id: Uuid,
+    ) -> RepositoryResult<Vec<Attachment>>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Attachment>;
+    async fn insert(
+        &self,
+        attachment: &Attachment,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Attachment>>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<Attachment>;
+}
+
+#[async_trait]
+impl AttachmentsRepository for PgPool {
+    async fn attachable_exists(
+        &self,
+        attachable_type: AttachableType,
+        attachable_id: Uuid,
+    ) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(AssertSqlSafe(format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1 AND deleted_at IS NULL)", // Security: constant
+            attachable_type.as_str()
+        )))
+        .bind(attachable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_attachable(
+        &self,
+        attachable_type: AttachableType,
+        attachable_id: Uuid,
+    ) -> RepositoryResult<Vec<Attachment>> {
+        Ok(sqlx::query_as::<_, Attachment>(
+            r#"
+            SELECT *
+            FROM attachments
+            WHERE attachable_type = $1
+                AND attachable_id = $2
+                AND deleted_at IS NULL
+            ORDER BY created_at
+            "#,
+        )
+        .bind(attachable_type.as_str())
+        .bind(attachable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Attachment> {
+        Ok(sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM attachments WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        attachment: &Attachment,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Attachment>> {
+        let mut tx = self.begin().await?;
+        let size_total = lock_attachments_size_total(&mut tx).await?;
+        if limits.is_some_and(|limits| !limits.allows_storage(size_total, attachment.size_bytes)) {
+            return Ok(None);
+        }
+        let attachment = sqlx::query_as::<_, Attachment>(
+            r#"
+            INSERT INTO attachments (id, attachable_type, attachable_id, file_name, content_type,
+                                     size_bytes, storage_key, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(attachment.id)
+        .bind(&attachment.attachable_type)
+        .bind(attachment.attachable_id)
+        .bind(&attachment.file_name)
+        .bind(&attachment.content_type)
+        .bind(attachment.size_bytes)
+        .bind(&attachment.storage_key)
+        .bind(attachment.created_by_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(attachment))
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<Attachment> {
+        Ok(sqlx::query_as::<_, Attachment>(
+            r#"
+            UPDATE attachments
+            SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+}
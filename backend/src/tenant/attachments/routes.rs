@@ -0,0 +1,45 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::AttachmentsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, post};
+use std::sync::Arc;
+
+pub fn routes<M: AttachmentsModuleInterface>(attachments_module: Arc<M>) -> Router {
+    let max_upload_size = attachments_module.config().storage().max_attachment_size();
+    Router::new().nest(
+        "/attachments",
+        Router::new()
+            .route("/list", get(handler::list::<M>))
+            .route(
+                "/upload",
+                post(handler::upload::<M>)
+                    .layer(DefaultBodyLimit::max(max_upload_size + 64 * 1024)),
+            )
+            .route("/download", get(handler::download::<M>))
+            .route("/delete", delete(handler::delete::<M>))
+            .layer(from_fn_with_state(attachments_module.clone(), require_auth))
+            .with_state(attachments_module),
+    )
+}
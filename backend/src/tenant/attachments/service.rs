@@ -0,0 +1,339 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::common::storage::StorageError;
+use crate::tenant::attachments::AttachmentsModuleInterface;
+use crate::tenant::attachments::dto::{AttachableType, AttachmentUpload, AttachmentsQuery};
+use crate::tenant::attachments::model::Attachment;
+use crate::tenant::attachments::repository::AttachmentsRepository;
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{ensure_task_access, ensure_worksheet_access};
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{Level, error};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum AttachmentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("Elérte az előfizetésében engedélyezett maximális tárhelyet!")]
+    StorageQuotaExceeded,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+impl From<ServiceError> for AttachmentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => AttachmentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<AttachmentsServiceError> for AppError {
+    fn from(value: AttachmentsServiceError) -> Self {
+        match value {
+            AttachmentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            AttachmentsServiceError::StorageQuotaExceeded => Self::new(
+                Level::DEBUG,
+                StatusCode::PAYMENT_REQUIRED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            AttachmentsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            AttachmentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            AttachmentsServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": AttachmentsServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            AttachmentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type AttachmentsServiceResult<T> = Result<T, AttachmentsServiceError>;
+
+pub trait AttachmentsService {
+    fn list(
+        &self,
+        query: &AttachmentsQuery,
+    ) -> impl Future<Output = AttachmentsServiceResult<Vec<Attachment>>> + Send;
+    fn upload(
+        &self,
+        payload: AttachmentUpload,
+    ) -> impl Future<Output = AttachmentsServiceResult<Attachment>> + Send;
+    fn download(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = AttachmentsServiceResult<(Attachment, Vec<u8>)>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = AttachmentsServiceResult<()>> + Send;
+    fn repo(&self) -> AttachmentsServiceResult<Arc<dyn AttachmentsRepository + Send + Sync>>;
+    fn remove_file(&self, attachment: &Attachment) -> impl Future<Output = ()> + Send;
+    fn ensure_attachable_access(
+        &self,
+        attachable_type: &str,
+        attachable_id: Uuid,
+        access: ProjectAccess,
+    ) -> impl Future<Output = AttachmentsServiceResult<()>> + Send;
+}
+
+impl<'a, T> AttachmentsService for Service<'a, T>
+where
+    T: AttachmentsModuleInterface,
+{
+    fn repo(&self) -> AttachmentsServiceResult<Arc<dyn AttachmentsRepository + Send + Sync>> {
+        Ok(self.module().attachments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(AttachmentsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn remove_file(&self, attachment: &Attachment) {
+        if let Err(e) = self
+            .module()
+            .file_storage()
+            .delete(&attachment.storage_key)
+            .await
+        {
+            error!(
+                "Could not remove attachment file {}: {}",
+                attachment.storage_key, e
+            );
+        }
+    }
+
+    /// The attachments follow the project access of the task or worksheet they belong to
+    async fn ensure_attachable_access(
+        &self,
+        attachable_type: &str,
+        attachable_id: Uuid,
+        access: ProjectAccess,
+    ) -> AttachmentsServiceResult<()> {
+        match AttachableType::parse(attachable_type) {
+            Some(AttachableType::Tasks) => {
+                ensure_task_access(
+                    self,
+                    attachable_id,
+                    access,
+                    AttachmentsServiceError::Forbidden,
+                )
+                .await
+            }
+            Some(AttachableType::Worksheets) => {
+                ensure_worksheet_access(
+                    self,
+                    attachable_id,
+                    access,
+                    AttachmentsServiceError::Forbidden,
+                )
+                .await
+            }
+            None => Err(AttachmentsServiceError::Forbidden),
+        }
+    }
+
+    async fn list(&self, query: &AttachmentsQuery) -> AttachmentsServiceResult<Vec<Attachment>> {
+        self.ensure_attachable_access(
+            query.attachable_type.as_str(),
+            query.attachable_id,
+            ProjectAccess::Read,
+        )
+        .await?;
+        Ok(self
+            .repo()?
+            .get_by_attachable(query.attachable_type, query.attachable_id)
+            .await?)
+    }
+
+    async fn upload(&self, payload: AttachmentUpload) -> AttachmentsServiceResult<Attachment> {
+        if payload.data.is_empty() {
+            return Err(AttachmentsServiceError::UnprocessableEntry(
+                "A feltöltött fájl üres!",
+            ));
+        }
+        let storage_config = self.module().config().storage();
+        if payload.data.len() > storage_config.max_attachment_size() {
+            return Err(AttachmentsServiceError::UnprocessableEntry(
+                "A fájl mérete meghaladja a megengedett legnagyobb méretet!",
+            ));
+        }
+        let content_type = payload.detected_content_type();
+        if !storage_config
+            .allowed_attachment_types()
+            .contains(&content_type.as_str())
+        {
+            return Err(AttachmentsServiceError::UnprocessableEntry(
+                "Ilyen típusú fájl nem tölthető fel!",
+            ));
+        }
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(AttachmentsServiceError::Unauthorized)?;
+        self.ensure_attachable_access(
+            payload.attachable_type.as_str(),
+            payload.attachable_id,
+            ProjectAccess::Write,
+        )
+        .await?;
+        let repo = self.repo()?;
+        if !repo
+            .attachable_exists(payload.attachable_type, payload.attachable_id)
+            .await?
+        {
+            return Err(AttachmentsServiceError::UnprocessableEntry(
+                "A feladat vagy munkalap nem található!",
+            ));
+        }
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        let id = Uuid::new_v4();
+        let attachment = Attachment {
+            id,
+            attachable_type: payload.attachable_type.as_str().to_string(),
+            attachable_id: payload.attachable_id,
+            file_name: payload.sanitized_file_name(),
+            size_bytes: payload.data.len() as i64,
+            storage_key: format!(
+                "{tenant_id}/attachments/{}/{}/{id}",
+                payload.attachable_type.as_str(),
+                payload.attachable_id
+            ),
+            content_type,
+            created_by_id: self.claims()?.sub(),
+            created_at: Utc::now(),
+            deleted_at: None,
+        };
+        self.module()
+            .file_storage()
+            .put(
+                &attachment.storage_key,
+                &attachment.content_type,
+                payload.data,
+            )
+            .await?;
+        match repo.insert(&attachment, limits).await {
+            Ok(Some(attachment)) => Ok(attachment),
+            Ok(None) => {
+                self.remove_file(&attachment).await;
+                Err(AttachmentsServiceError::StorageQuotaExceeded)
+            }
+            Err(e) => {
+                self.remove_file(&attachment).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn download(&self, id: Uuid) -> AttachmentsServiceResult<(Attachment, Vec<u8>)> {
+        let attachment = self.repo()?.get_by_id(id).await?;
+        self.ensure_attachable_access(
+            &attachment.attachable_type,
+            attachment.attachable_id,
+            ProjectAccess::Read,
+        )
+        .await?;
+        let data = self
+            .module()
+            .file_storage()
+            .get(&attachment.storage_key)
+            .await?;
+        Ok((attachment, data))
+    }
+
+    async fn delete(&self, id: Uuid) -> AttachmentsServiceResult<()> {
+        let repo = self.repo()?;
+        let attachment = repo.get_by_id(id).await?;
+        self.ensure_attachable_access(
+            &attachment.attachable_type,
+            attachment.attachable_id,
+            ProjectAccess::Write,
+        )
+        .await?;
+        let attachment = repo.delete_by_id(id).await?;
+        self.remove_file(&attachment).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CategoryInput {
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub slug: Option<String>,
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductCategoriesInput {
+    pub product_id: Uuid,
+    pub category_ids: Vec<Uuid>,
+}
+
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for c in value.trim().to_lowercase().chars() {
+        let c = match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'ő' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' | 'ű' => 'u',
+            c => c,
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= 255
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && !slug.contains("--")
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify(" Árvíztűrő  Tükörfúrógép "),
+            "arvizturo-tukorfurogep"
+        );
+        assert_eq!(
+            slugify("Kábelek & csatlakozók (2m+)"),
+            "kabelek-csatlakozok-2m"
+        );
+        assert_eq!(slugify("---"), "");
+    }
+
+    #[test]
+    fn test_is_valid_slug() {
+        assert!(is_valid_slug("kabelek-2m"));
+        assert!(!is_valid_slug("Kabelek"));
+        assert!(!is_valid_slug("kabelek--2m"));
+        assert!(!is_valid_slug("-kabelek"));
+        assert!(!is_valid_slug(""));
+    }
+}
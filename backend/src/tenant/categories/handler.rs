@@ -0,0 +1,397 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::categories::CategoriesModuleInterface;
+use crate::tenant::categories::dto::{CategoryInput, ProductCategoriesInput};
+use crate::tenant::categories::service::CategoriesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result =
+        map_handler_err(service.get(payload.uuid).await, categories_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        categories_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        categories_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn tree<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result = map_handler_err(service.get_tree().await, categories_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CategoryInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result = map_handler_err(service.create(&payload).await, categories_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CategoryInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result = map_handler_err(service.update(&payload).await, categories_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        categories_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A kategória törlése sikeresen megtörtént",
+            ))
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn product_categories<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result = map_handler_err(
+        service.get_product_categories(payload.uuid).await,
+        categories_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_product_categories<M: CategoriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(categories_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductCategoriesInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), categories_module.clone());
+    let result = map_handler_err(
+        service.set_product_categories(&payload).await,
+        categories_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        categories_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::categories::model::ProductCategory;
+    use crate::tenant::categories::{
+        self, repository::MockCategoriesRepository, tests::MockCategoriesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockCategoriesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut categories_module = MockCategoriesModule::new();
+        categories_module
+            .expect_categories_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        categories_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(categories::routes::routes(Arc::new(categories_module))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn category(name: &str, slug: &str, parent_id: Option<Uuid>) -> ProductCategory {
+        ProductCategory {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            slug: slug.to_string(),
+            parent_id,
+            sort_order: 0,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_generates_slug() {
+        let active_tenant_id = Uuid::new_v4();
+        let parent = category("Szerszámok", "szerszamok", None);
+        let created = category("Ütvefúrók", "utvefurok", Some(parent.id));
+
+        let mut repo = MockCategoriesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(parent.id))
+            .returning({
+                let parent = parent.clone();
+                move |_| Ok(parent.clone())
+            });
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| {
+                input.name == "Ütvefúrók" && input.slug.as_deref() == Some("utvefurok")
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _| Ok(created.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/categories/create",
+                active_tenant_id,
+                json!({
+                    "name": " Ütvefúrók ",
+                    "description": null,
+                    "slug": null,
+                    "parent_id": parent.id
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": created})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_descendant_parent() {
+        let active_tenant_id = Uuid::new_v4();
+        let tools = category("Szerszámok", "szerszamok", None);
+        let drills = category("Fúrók", "furok", Some(tools.id));
+
+        let mut repo = MockCategoriesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(drills.id))
+            .returning({
+                let drills = drills.clone();
+                move |_| Ok(drills.clone())
+            });
+        repo.expect_is_in_subtree()
+            .times(1)
+            .with(eq(tools.id), eq(drills.id))
+            .returning(|_, _| Ok(true));
+        repo.expect_update().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/categories/update",
+                active_tenant_id,
+                json!({
+                    "id": tools.id,
+                    "name": "Szerszámok",
+                    "description": null,
+                    "slug": "szerszamok",
+                    "parent_id": drills.id,
+                    "sort_order": 0
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_tree() {
+        let active_tenant_id = Uuid::new_v4();
+        let tools = category("Szerszámok", "szerszamok", None);
+        let drills = category("Fúrók", "furok", Some(tools.id));
+
+        let mut repo = MockCategoriesRepository::new();
+        repo.expect_get_all().times(1).returning({
+            let categories = vec![drills.clone(), tools.clone()];
+            move || Ok(categories.clone())
+        });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri("/api/categories/tree")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"][0]["id"], json!(tools.id));
+        assert_eq!(body["data"][0]["children"][0]["id"], json!(drills.id));
+        assert_eq!(body["data"][0]["children"][0]["children"], json!([]));
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductCategory {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub slug: String,
+    pub parent_id: Option<Uuid>,
+    pub sort_order: i32,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CategoryTreeNode {
+    #[serde(flatten)]
+    pub category: ProductCategory,
+    pub children: Vec<CategoryTreeNode>,
+}
+
+pub fn build_tree(categories: Vec<ProductCategory>) -> Vec<CategoryTreeNode> {
+    let ids: HashSet<Uuid> = categories.iter().map(|category| category.id).collect();
+    let mut by_parent: HashMap<Option<Uuid>, Vec<ProductCategory>> = HashMap::new();
+    for category in categories {
+        // NOTE: a category whose parent is not in the list is shown as a root, so nothing is lost
+        let parent_id = category
+            .parent_id
+            .filter(|parent_id| ids.contains(parent_id));
+        by_parent.entry(parent_id).or_default().push(category);
+    }
+    children_of(None, &mut by_parent)
+}
+
+fn children_of(
+    parent_id: Option<Uuid>,
+    by_parent: &mut HashMap<Option<Uuid>, Vec<ProductCategory>>,
+) -> Vec<CategoryTreeNode> {
+    let mut categories = by_parent.remove(&parent_id).unwrap_or_default();
+    categories.sort_by(|a, b| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    categories
+        .into_iter()
+        .map(|category| CategoryTreeNode {
+            children: children_of(Some(category.id), by_parent),
+            category,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str, parent_id: Option<Uuid>, sort_order: i32) -> ProductCategory {
+        ProductCategory {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            slug: name.to_lowercase(),
+            parent_id,
+            sort_order,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_build_tree_nests_and_orders() {
+        let tools = category("Szerszámok", None, 1);
+        let cables = category("Kábelek", None, 0);
+        let drills = category("Fúrók", Some(tools.id), 0);
+        let saws = category("Fűrészek", Some(tools.id), 0);
+        let bits = category("Fúrószárak", Some(drills.id), 0);
+
+        let tree = build_tree(vec![
+            bits.clone(),
+            tools.clone(),
+            saws.clone(),
+            cables.clone(),
+            drills.clone(),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].category, cables);
+        assert_eq!(tree[1].category, tools);
+        let children: Vec<_> = tree[1].children.iter().map(|c| &c.category).collect();
+        assert_eq!(children, vec![&drills, &saws]);
+        assert_eq!(tree[1].children[0].children[0].category, bits);
+    }
+
+    #[test]
+    fn test_build_tree_promotes_orphans() {
+        let orphan = category("Árva", Some(Uuid::new_v4()), 0);
+
+        let tree = build_tree(vec![orphan.clone()]);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].category, orphan);
+        assert!(tree[0].children.is_empty());
+    }
+}
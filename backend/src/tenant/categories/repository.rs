@@ -0,0 +1,289 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::categories::dto::CategoryInput;
+use crate::tenant::categories::model::ProductCategory;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CategoriesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<ProductCategory>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<ProductCategory>)>;
+    async fn get_all(&self) -> RepositoryResult<Vec<ProductCategory>>;
+    async fn insert(&self, input: &CategoryInput, sub: Uuid) -> RepositoryResult<ProductCategory>;
+    async fn update(&self, id: Uuid, input: &CategoryInput) -> RepositoryResult<ProductCategory>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn has_children(&self, id: Uuid) -> RepositoryResult<bool>;
+    async fn is_in_subtree(&self, root_id: Uuid, id: Uuid) -> RepositoryResult<bool>;
+    async fn get_by_product(&self, product_id: Uuid) -> RepositoryResult<Vec<ProductCategory>>;
+    async fn set_product_categories(
+        &self,
+        product_id: Uuid,
+        category_ids: &[Uuid],
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<ProductCategory>>;
+}
+
+#[async_trait]
+impl CategoriesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<ProductCategory> {
+        Ok(sqlx::query_as::<_, ProductCategory>(
+            "SELECT * FROM product_category WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<ProductCategory>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM product_category WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let categories = sqlx::query_as::<_, ProductCategory>(
+            r#"
+            SELECT *
+            FROM product_category
+            WHERE deleted_at IS NULL
+            ORDER BY sort_order, name
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            categories,
+        ))
+    }
+
+    async fn get_all(&self) -> RepositoryResult<Vec<ProductCategory>> {
+        Ok(sqlx::query_as::<_, ProductCategory>(
+            "SELECT * FROM product_category WHERE deleted_at IS NULL ORDER BY sort_order, name",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &CategoryInput, sub: Uuid) -> RepositoryResult<ProductCategory> {
+        Ok(sqlx::query_as::<_, ProductCategory>(
+            r#"
+            INSERT INTO product_category (name, description, slug, parent_id, sort_order,
+                                          created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.slug)
+        .bind(input.parent_id)
+        .bind(input.sort_order)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(&self, id: Uuid, input: &CategoryInput) -> RepositoryResult<ProductCategory> {
+        Ok(sqlx::query_as::<_, ProductCategory>(
+            r#"
+            UPDATE product_category
+            SET name = $1,
+                description = $2,
+                slug = $3,
+                parent_id = $4,
+                sort_order = $5
+            WHERE id = $6
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.slug)
+        .bind(input.parent_id)
+        .bind(input.sort_order)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        let mut tx = self.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE product_category_connect
+            SET deleted_at = NOW()
+            WHERE product_category_id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE product_category
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn has_children(&self, id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM product_category WHERE parent_id = $1 AND deleted_at IS NULL
+            )
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn is_in_subtree(&self, root_id: Uuid, id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id FROM product_category WHERE id = $1
+                UNION
+                SELECT product_category.id
+                FROM product_category
+                JOIN subtree ON product_category.parent_id = subtree.id
+                WHERE product_category.deleted_at IS NULL
+            )
+            SELECT EXISTS(SELECT 1 FROM subtree WHERE id = $2)
+            "#,
+        )
+        .bind(root_id)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_product(&self, product_id: Uuid) -> RepositoryResult<Vec<ProductCategory>> {
+        Ok(sqlx::query_as::<_, ProductCategory>(
+            r#"
+            SELECT product_category.*
+            FROM product_category
+            JOIN product_category_connect
+                ON product_category_connect.product_category_id = product_category.id
+            WHERE product_category_connect.product_id = $1
+                AND product_category_connect.deleted_at IS NULL
+                AND product_category.deleted_at IS NULL
+            ORDER BY product_category.sort_order, product_category.name
+            "#,
+        )
+        .bind(product_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_product_categories(
+        &self,
+        product_id: Uuid,
+        category_ids: &[Uuid],
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<ProductCategory>> {
+        let mut tx = self.begin().await?;
+        sqlx::query("SELECT id FROM products WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let found: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM product_category WHERE id = ANY($1) AND deleted_at IS NULL",
+        )
+        .bind(category_ids)
+        .fetch_one(&mut *tx)
+        .await?;
+        if usize::try_from(found)? != category_ids.len() {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        sqlx::query(
+            r#"
+            UPDATE product_category_connect
+            SET deleted_at = NOW()
+            WHERE product_id = $1
+                AND deleted_at IS NULL
+                AND NOT (product_category_id = ANY($2))
+            "#,
+        )
+        .bind(product_id)
+        .bind(category_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO product_category_connect (product_id, product_category_id, created_by_id)
+            SELECT $1, category_id, $3
+            FROM UNNEST($2::uuid[]) AS category_id
+            WHERE NOT EXISTS(
+                SELECT 1
+                FROM product_category_connect
+                WHERE product_id = $1
+                    AND product_category_id = category_id
+                    AND deleted_at IS NULL
+            )
+            "#,
+        )
+        .bind(product_id)
+        .bind(category_ids)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        self.get_by_product(product_id).await
+    }
+}
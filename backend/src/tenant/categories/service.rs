@@ -0,0 +1,317 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::categories::CategoriesModuleInterface;
+use crate::tenant::categories::dto::{
+    CategoryInput, ProductCategoriesInput, is_valid_slug, slugify,
+};
+use crate::tenant::categories::model::{CategoryTreeNode, ProductCategory, build_tree};
+use crate::tenant::categories::repository::CategoriesRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum CategoriesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A megadott azonosítóval (slug) már létezik kategória")]
+    SlugExists,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for CategoriesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CategoriesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CategoriesServiceError> for AppError {
+    fn from(value: CategoriesServiceError) -> Self {
+        match value {
+            CategoriesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CategoriesServiceError::SlugExists => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CategoriesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CategoriesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CategoriesServiceResult<T> = Result<T, CategoriesServiceError>;
+
+fn validate(payload: &CategoryInput) -> CategoriesServiceResult<CategoryInput> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(CategoriesServiceError::UnprocessableEntry(
+            "A megnevezés megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let slug = match payload.slug.as_deref().map(str::trim) {
+        Some(slug) if !slug.is_empty() => slug.to_string(),
+        _ => slugify(name),
+    };
+    if !is_valid_slug(&slug) {
+        return Err(CategoriesServiceError::UnprocessableEntry(
+            "Az azonosító (slug) csak kisbetűket, számokat és kötőjelet tartalmazhat!",
+        ));
+    }
+    Ok(CategoryInput {
+        name: name.to_string(),
+        description: payload
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map(str::to_string),
+        slug: Some(slug),
+        ..payload.clone()
+    })
+}
+
+async fn ensure_parent_exists(
+    repo: &Arc<dyn CategoriesRepository + Send + Sync>,
+    parent_id: Option<Uuid>,
+) -> CategoriesServiceResult<()> {
+    if let Some(parent_id) = parent_id {
+        repo.get_by_id(parent_id).await.map_err(|e| match e {
+            RepositoryError::Database(sqlx::Error::RowNotFound) => {
+                CategoriesServiceError::UnprocessableEntry("A szülő kategória nem létezik!")
+            }
+            e => e.into(),
+        })?;
+    }
+    Ok(())
+}
+
+fn map_slug_error(e: RepositoryError) -> CategoriesServiceError {
+    if e.is_unique_violation() {
+        CategoriesServiceError::SlugExists
+    } else {
+        e.into()
+    }
+}
+
+pub trait CategoriesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = CategoriesServiceResult<ProductCategory>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = CategoriesServiceResult<(PaginatorMeta, Vec<ProductCategory>)>> + Send;
+    fn get_tree(
+        &self,
+    ) -> impl Future<Output = CategoriesServiceResult<Vec<CategoryTreeNode>>> + Send;
+    fn create(
+        &self,
+        payload: &CategoryInput,
+    ) -> impl Future<Output = CategoriesServiceResult<ProductCategory>> + Send;
+    fn update(
+        &self,
+        payload: &CategoryInput,
+    ) -> impl Future<Output = CategoriesServiceResult<ProductCategory>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = CategoriesServiceResult<()>> + Send;
+    fn get_product_categories(
+        &self,
+        product_id: Uuid,
+    ) -> impl Future<Output = CategoriesServiceResult<Vec<ProductCategory>>> + Send;
+    fn set_product_categories(
+        &self,
+        payload: &ProductCategoriesInput,
+    ) -> impl Future<Output = CategoriesServiceResult<Vec<ProductCategory>>> + Send;
+}
+
+impl<'a, T> CategoriesService for Service<'a, T>
+where
+    T: CategoriesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> CategoriesServiceResult<ProductCategory> {
+        Ok(self
+            .module()
+            .categories_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CategoriesServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> CategoriesServiceResult<(PaginatorMeta, Vec<ProductCategory>)> {
+        Ok(self
+            .module()
+            .categories_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CategoriesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn get_tree(&self) -> CategoriesServiceResult<Vec<CategoryTreeNode>> {
+        Ok(build_tree(
+            self.module()
+                .categories_repo(
+                    self.claims()?
+                        .active_tenant()
+                        .ok_or(CategoriesServiceError::Unauthorized)?,
+                )?
+                .get_all()
+                .await?,
+        ))
+    }
+
+    async fn create(&self, payload: &CategoryInput) -> CategoriesServiceResult<ProductCategory> {
+        let input = validate(payload)?;
+        let repo = self.module().categories_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CategoriesServiceError::Unauthorized)?,
+        )?;
+        ensure_parent_exists(&repo, input.parent_id).await?;
+        repo.insert(&input, self.claims()?.sub())
+            .await
+            .map_err(map_slug_error)
+    }
+
+    async fn update(&self, payload: &CategoryInput) -> CategoriesServiceResult<ProductCategory> {
+        let id = payload
+            .id
+            .ok_or(CategoriesServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let input = validate(payload)?;
+        let repo = self.module().categories_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CategoriesServiceError::Unauthorized)?,
+        )?;
+        if let Some(parent_id) = input.parent_id {
+            ensure_parent_exists(&repo, Some(parent_id)).await?;
+            if repo.is_in_subtree(id, parent_id).await? {
+                return Err(CategoriesServiceError::UnprocessableEntry(
+                    "A kategória nem helyezhető saját maga vagy alkategóriája alá!",
+                ));
+            }
+        }
+        repo.update(id, &input).await.map_err(map_slug_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> CategoriesServiceResult<()> {
+        let repo = self.module().categories_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CategoriesServiceError::Unauthorized)?,
+        )?;
+        if repo.has_children(id).await? {
+            return Err(CategoriesServiceError::UnprocessableEntry(
+                "Alkategóriákkal rendelkező kategória nem törölhető!",
+            ));
+        }
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn get_product_categories(
+        &self,
+        product_id: Uuid,
+    ) -> CategoriesServiceResult<Vec<ProductCategory>> {
+        Ok(self
+            .module()
+            .categories_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CategoriesServiceError::Unauthorized)?,
+            )?
+            .get_by_product(product_id)
+            .await?)
+    }
+
+    async fn set_product_categories(
+        &self,
+        payload: &ProductCategoriesInput,
+    ) -> CategoriesServiceResult<Vec<ProductCategory>> {
+        let mut category_ids = payload.category_ids.clone();
+        category_ids.sort_unstable();
+        category_ids.dedup();
+        Ok(self
+            .module()
+            .categories_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CategoriesServiceError::Unauthorized)?,
+            )?
+            .set_product_categories(payload.product_id, &category_ids, self.claims()?.sub())
+            .await?)
+    }
+}
@@ -207,6 +207,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             }
@@ -256,6 +257,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -304,6 +306,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
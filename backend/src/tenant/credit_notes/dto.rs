@@ -0,0 +1,68 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreditNoteLineInput {
+    pub receivable_line_id: Uuid,
+    pub quantity: BigDecimal,
+    pub inventory_id: Option<Uuid>,
+}
+
+// NOTE: without lines every remaining quantity of the invoice is credited, returned goods are
+// received into the inventory given on the line
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateCreditNote {
+    pub receivable_id: Uuid,
+    pub reason: String,
+    #[serde(default)]
+    pub lines: Vec<CreditNoteLineInput>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewCreditNoteLine {
+    pub receivable_line_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub inventory_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewCreditNote {
+    pub receivable_id: Uuid,
+    pub customer_id: Uuid,
+    pub issue_date: NaiveDate,
+    pub currency_code: String,
+    pub reason: String,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub amount: BigDecimal,
+    pub lines: Vec<NewCreditNoteLine>,
+}
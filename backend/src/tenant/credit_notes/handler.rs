@@ -0,0 +1,403 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::credit_notes::CreditNotesModuleInterface;
+use crate::tenant::credit_notes::dto::CreateCreditNote;
+use crate::tenant::credit_notes::service::CreditNotesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: CreditNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(credit_notes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), credit_notes_module.clone());
+    let result =
+        map_handler_err(service.get(payload.uuid).await, credit_notes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        credit_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: CreditNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(credit_notes_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), credit_notes_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        credit_notes_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        credit_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        credit_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: CreditNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(credit_notes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateCreditNote>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), credit_notes_module.clone());
+    let tz = map_handler_err(claims.tz(), credit_notes_module.clone()).await?;
+    let result = map_handler_err(
+        service.create(&payload, tz).await,
+        credit_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        credit_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn creditable_lines<M: CreditNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(credit_notes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), credit_notes_module.clone());
+    let result = map_handler_err(
+        service.creditable_lines(payload.uuid).await,
+        credit_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        credit_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::credit_notes::model::{CreditNote, CreditableLine};
+    use crate::tenant::credit_notes::{
+        self, repository::MockCreditNotesRepository, tests::MockCreditNotesModule,
+    };
+    use crate::tenant::receivables::model::Receivable;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockCreditNotesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut credit_notes_module = MockCreditNotesModule::new();
+        credit_notes_module
+            .expect_credit_notes_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        credit_notes_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(credit_notes::routes::routes(Arc::new(credit_notes_module))),
+        )
+    }
+
+    fn request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/credit_notes/create")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn receivable() -> Receivable {
+        Receivable {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            document_number: "SZ-2026-00001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 9, 9).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: decimal("1397.00"),
+            paid_amount: decimal("0"),
+            credited_amount: decimal("0"),
+            status: "open".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn creditable_line(product: bool, quantity: &str, credited_quantity: &str) -> CreditableLine {
+        CreditableLine {
+            receivable_line_id: Uuid::new_v4(),
+            service_id: (!product).then(Uuid::new_v4),
+            product_id: product.then(Uuid::new_v4),
+            description: "Tétel".to_string(),
+            quantity: decimal(quantity),
+            credited_quantity: decimal(credited_quantity),
+            unit_price: decimal("100.00"),
+            tax_id: Uuid::new_v4(),
+            tax_rate: decimal("27.00"),
+            is_rate_applicable: true,
+        }
+    }
+
+    fn expect_receivable(repo: &mut MockCreditNotesRepository, receivable: &Receivable) {
+        repo.expect_get_receivable()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+    }
+
+    #[tokio::test]
+    async fn test_full_credit_reverses_remaining_quantities() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable();
+        let lines = vec![
+            creditable_line(true, "10.00", "0"),
+            creditable_line(false, "3.00", "1.00"),
+            creditable_line(false, "1.00", "1.00"),
+        ];
+        let mut repo = MockCreditNotesRepository::new();
+        expect_receivable(&mut repo, &receivable);
+        repo.expect_get_creditable_lines()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let lines = lines.clone();
+                move |_| Ok(lines.clone())
+            });
+        repo.expect_get_inventory_product_id().never();
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| {
+                input.lines.len() == 2
+                    && input.lines[0].quantity == decimal("-10")
+                    && input.lines[1].quantity == decimal("-2")
+                    && input.lines[1].tax_amount == decimal("-54.00")
+                    && input.net_amount == decimal("-1200")
+                    && input.tax_amount == decimal("-324")
+                    && input.amount == decimal("-1524")
+                    && input.reason == "Visszáru"
+            })
+            .returning(|input, sub| {
+                Ok(CreditNote {
+                    id: Uuid::new_v4(),
+                    receivable_id: input.receivable_id,
+                    customer_id: input.customer_id,
+                    document_number: "HSZ-2026-00001".to_string(),
+                    issue_date: input.issue_date,
+                    currency_code: input.currency_code.clone(),
+                    reason: input.reason.clone(),
+                    net_amount: input.net_amount.clone(),
+                    tax_amount: input.tax_amount.clone(),
+                    amount: input.amount.clone(),
+                    applied_amount: decimal("1397.00"),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({"receivable_id": receivable.id, "reason": " Visszáru "}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_keeps_currency_of_receivable_even_if_disabled_since() {
+        let active_tenant_id = Uuid::new_v4();
+        // NOTE: the tenant works in HUF only, EUR was disabled after the invoice was issued
+        let receivable = Receivable {
+            currency_code: "EUR".to_string(),
+            ..receivable()
+        };
+        let mut repo = MockCreditNotesRepository::new();
+        expect_receivable(&mut repo, &receivable);
+        repo.expect_get_creditable_lines()
+            .times(1)
+            .returning(|_| Ok(vec![creditable_line(false, "1.00", "0")]));
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.currency_code == "EUR")
+            .returning(|input, sub| {
+                Ok(CreditNote {
+                    id: Uuid::new_v4(),
+                    receivable_id: input.receivable_id,
+                    customer_id: input.customer_id,
+                    document_number: "HSZ-2026-00001".to_string(),
+                    issue_date: input.issue_date,
+                    currency_code: input.currency_code.clone(),
+                    reason: input.reason.clone(),
+                    net_amount: input.net_amount.clone(),
+                    tax_amount: input.tax_amount.clone(),
+                    amount: input.amount.clone(),
+                    applied_amount: decimal("0"),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({"receivable_id": receivable.id, "reason": "Visszáru"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_quantity_above_remaining() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable();
+        let line = creditable_line(false, "3.00", "2.00");
+        let mut repo = MockCreditNotesRepository::new();
+        expect_receivable(&mut repo, &receivable);
+        repo.expect_get_creditable_lines().times(1).returning({
+            let line = line.clone();
+            move |_| Ok(vec![line.clone()])
+        });
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({
+                    "receivable_id": receivable.id,
+                    "reason": "Árengedmény",
+                    "lines": [{
+                        "receivable_line_id": line.receivable_line_id,
+                        "quantity": "1.50",
+                        "inventory_id": null
+                    }]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_return_into_inventory_of_other_product() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable();
+        let line = creditable_line(true, "5.00", "0");
+        let inventory_id = Uuid::new_v4();
+        let mut repo = MockCreditNotesRepository::new();
+        expect_receivable(&mut repo, &receivable);
+        repo.expect_get_creditable_lines().times(1).returning({
+            let line = line.clone();
+            move |_| Ok(vec![line.clone()])
+        });
+        repo.expect_get_inventory_product_id()
+            .times(1)
+            .with(eq(inventory_id))
+            .returning(|_| Ok(Uuid::new_v4()));
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({
+                    "receivable_id": receivable.id,
+                    "reason": "Visszáru",
+                    "lines": [{
+                        "receivable_line_id": line.receivable_line_id,
+                        "quantity": "2",
+                        "inventory_id": inventory_id
+                    }]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
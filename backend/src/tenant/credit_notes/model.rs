@@ -0,0 +1,88 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CreditNote {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub customer_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub currency_code: String,
+    pub reason: String,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub amount: BigDecimal,
+    pub applied_amount: BigDecimal,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CreditNoteLine {
+    pub id: Uuid,
+    pub credit_note_id: Uuid,
+    pub receivable_line_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub position: i32,
+    pub inventory_id: Option<Uuid>,
+    pub inventory_movement_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CreditNoteDetails {
+    #[serde(flatten)]
+    pub credit_note: CreditNote,
+    pub lines: Vec<CreditNoteLine>,
+}
+
+/// A line of the original invoice together with the quantity already credited on it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CreditableLine {
+    pub receivable_line_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub credited_quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub is_rate_applicable: bool,
+}
+
+impl CreditableLine {
+    pub fn remaining_quantity(&self) -> BigDecimal {
+        &self.quantity - &self.credited_quantity
+    }
+}
@@ -0,0 +1,290 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::{RepositoryError, RepositoryResult};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::credit_notes::dto::NewCreditNote;
+use crate::tenant::credit_notes::model::{CreditNote, CreditNoteLine, CreditableLine};
+use crate::tenant::receivables::model::Receivable;
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CreditNotesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CreditNote>;
+    async fn get_lines(&self, credit_note_id: Uuid) -> RepositoryResult<Vec<CreditNoteLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<CreditNote>)>;
+    async fn get_receivable(&self, id: Uuid) -> RepositoryResult<Receivable>;
+    async fn get_creditable_lines(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<CreditableLine>>;
+    async fn get_inventory_product_id(&self, inventory_id: Uuid) -> RepositoryResult<Uuid>;
+    async fn insert(&self, input: &NewCreditNote, sub: Uuid) -> RepositoryResult<CreditNote>;
+}
+
+#[async_trait]
+impl CreditNotesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CreditNote> {
+        Ok(
+            sqlx::query_as::<_, CreditNote>("SELECT * FROM credit_notes WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_lines(&self, credit_note_id: Uuid) -> RepositoryResult<Vec<CreditNoteLine>> {
+        Ok(sqlx::query_as::<_, CreditNoteLine>(
+            "SELECT * FROM credit_note_lines WHERE credit_note_id = $1 ORDER BY position",
+        )
+        .bind(credit_note_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<CreditNote>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM credit_notes")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let credit_notes = sqlx::query_as::<_, CreditNote>(
+            r#"
+            SELECT *
+            FROM credit_notes
+            ORDER BY issue_date DESC, document_number DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            credit_notes,
+        ))
+    }
+
+    async fn get_receivable(&self, id: Uuid) -> RepositoryResult<Receivable> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            "SELECT * FROM receivables WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_creditable_lines(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<CreditableLine>> {
+        Ok(sqlx::query_as::<_, CreditableLine>(
+            r#"
+            SELECT receivable_lines.id AS receivable_line_id,
+                   receivable_lines.service_id,
+                   receivable_lines.product_id,
+                   receivable_lines.description,
+                   receivable_lines.quantity,
+                   COALESCE(-credited.quantity, 0) AS credited_quantity,
+                   receivable_lines.unit_price,
+                   receivable_lines.tax_id,
+                   receivable_lines.tax_rate,
+                   taxes.is_rate_applicable
+            FROM receivable_lines
+            JOIN taxes ON receivable_lines.tax_id = taxes.id
+            LEFT JOIN LATERAL (
+                SELECT SUM(credit_note_lines.quantity) AS quantity
+                FROM credit_note_lines
+                WHERE credit_note_lines.receivable_line_id = receivable_lines.id
+            ) AS credited ON TRUE
+            WHERE receivable_lines.receivable_id = $1
+            ORDER BY receivable_lines.position
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_inventory_product_id(&self, inventory_id: Uuid) -> RepositoryResult<Uuid> {
+        Ok(sqlx::query_scalar::<_, Uuid>(
+            "SELECT product_id FROM inventory WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(inventory_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &NewCreditNote, sub: Uuid) -> RepositoryResult<CreditNote> {
+        let mut tx = self.begin().await?;
+        // NOTE: the lock serializes credit notes of the same invoice, so the quantities checked
+        // below can not be credited twice
+        let (open_balance,): (BigDecimal,) = sqlx::query_as(
+            r#"
+            SELECT CASE WHEN status = 'open' THEN amount - paid_amount - credited_amount ELSE 0 END
+            FROM receivables
+            WHERE id = $1
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.receivable_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let applied_amount = open_balance.min(-&input.amount);
+
+        let credit_note = sqlx::query_as::<_, CreditNote>(
+            r#"
+            INSERT INTO credit_notes (receivable_id, customer_id, document_number, issue_date,
+                                      currency_code, reason, net_amount, tax_amount, amount,
+                                      applied_amount, created_by_id)
+            VALUES ($1, $2,
+                    'HSZ-' || to_char($3::date, 'YYYY') || '-'
+                        || lpad(nextval('credit_note_number_seq')::text, 5, '0'),
+                    $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(input.receivable_id)
+        .bind(input.customer_id)
+        .bind(input.issue_date)
+        .bind(&input.currency_code)
+        .bind(&input.reason)
+        .bind(&input.net_amount)
+        .bind(&input.tax_amount)
+        .bind(&input.amount)
+        .bind(&applied_amount)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (position, line) in input.lines.iter().enumerate() {
+            let inventory_movement_id = match line.inventory_id {
+                Some(inventory_id) => Some(
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        INSERT INTO inventory_movements (
+                            inventory_id, movement_type, quantity, reference_type, reference_id,
+                            tax_id, created_by_id
+                        ) VALUES ($1, 'in', $2, 'credit_notes', $3, $4, $5)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(inventory_id)
+                    .bind(-&line.quantity)
+                    .bind(credit_note.id)
+                    .bind(line.tax_id)
+                    .bind(sub)
+                    .fetch_one(&mut *tx)
+                    .await?,
+                ),
+                None => None,
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO credit_note_lines (credit_note_id, receivable_line_id, service_id,
+                                               product_id, description, quantity, unit_price,
+                                               tax_id, tax_rate, net_amount, tax_amount, position,
+                                               inventory_id, inventory_movement_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "#,
+            )
+            .bind(credit_note.id)
+            .bind(line.receivable_line_id)
+            .bind(line.service_id)
+            .bind(line.product_id)
+            .bind(&line.description)
+            .bind(&line.quantity)
+            .bind(&line.unit_price)
+            .bind(line.tax_id)
+            .bind(&line.tax_rate)
+            .bind(&line.net_amount)
+            .bind(&line.tax_amount)
+            .bind(i32::try_from(position)?)
+            .bind(line.inventory_id)
+            .bind(inventory_movement_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let over_credited: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM receivable_lines
+                JOIN credit_note_lines
+                    ON credit_note_lines.receivable_line_id = receivable_lines.id
+                WHERE receivable_lines.receivable_id = $1
+                GROUP BY receivable_lines.id, receivable_lines.quantity
+                HAVING receivable_lines.quantity + SUM(credit_note_lines.quantity) < 0
+            )
+            "#,
+        )
+        .bind(input.receivable_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if over_credited {
+            return Err(RepositoryError::InvalidInput(
+                "credited quantity exceeds the invoiced quantity".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE receivables
+            SET credited_amount = credited_amount + $1,
+                status = CASE
+                    WHEN status = 'open' AND paid_amount + credited_amount + $1 >= amount
+                        THEN 'paid'
+                    ELSE status
+                END
+            WHERE id = $2
+            "#,
+        )
+        .bind(&applied_amount)
+        .bind(input.receivable_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(credit_note)
+    }
+}
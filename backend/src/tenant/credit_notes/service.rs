@@ -0,0 +1,328 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{Empty, Money, MoneyError};
+use crate::tenant::credit_notes::CreditNotesModuleInterface;
+use crate::tenant::credit_notes::dto::{
+    CreateCreditNote, CreditNoteLineInput, NewCreditNote, NewCreditNoteLine,
+};
+use crate::tenant::credit_notes::model::{CreditNote, CreditNoteDetails, CreditableLine};
+use crate::tenant::receivables::model::STATUS_WRITTEN_OFF;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use chrono_tz::Tz;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum CreditNotesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Money(#[from] MoneyError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for CreditNotesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CreditNotesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CreditNotesServiceError> for AppError {
+    fn from(value: CreditNotesServiceError) -> Self {
+        match value {
+            CreditNotesServiceError::Money(error) => error.into(),
+            CreditNotesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CreditNotesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CreditNotesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CreditNotesServiceResult<T> = Result<T, CreditNotesServiceError>;
+
+// NOTE: the tax is recalculated from the credited net amount with the rate of the original
+// line, so a fully credited line reverses the original amounts exactly
+fn credit_line(
+    line: &CreditableLine,
+    quantity: &BigDecimal,
+    inventory_id: Option<Uuid>,
+    currency_code: &str,
+) -> NewCreditNoteLine {
+    let net_amount = Money::line_net(&-quantity, &line.unit_price, currency_code);
+    let tax_amount = if line.is_rate_applicable {
+        net_amount.tax(&line.tax_rate).amount
+    } else {
+        BigDecimal::zero()
+    };
+    let net_amount = net_amount.amount;
+    NewCreditNoteLine {
+        receivable_line_id: line.receivable_line_id,
+        service_id: line.service_id,
+        product_id: line.product_id,
+        description: line.description.clone(),
+        quantity: -quantity,
+        unit_price: line.unit_price.clone(),
+        tax_id: line.tax_id,
+        tax_rate: line.tax_rate.clone(),
+        net_amount,
+        tax_amount,
+        inventory_id,
+    }
+}
+
+fn credit_lines(
+    creditable_lines: &[CreditableLine],
+    inputs: &[CreditNoteLineInput],
+    currency_code: &str,
+) -> CreditNotesServiceResult<Vec<NewCreditNoteLine>> {
+    if inputs.is_empty() {
+        return Ok(creditable_lines
+            .iter()
+            .filter(|line| line.remaining_quantity() > BigDecimal::zero())
+            .map(|line| credit_line(line, &line.remaining_quantity(), None, currency_code))
+            .collect());
+    }
+    let mut lines: Vec<NewCreditNoteLine> = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let line = creditable_lines
+            .iter()
+            .find(|line| line.receivable_line_id == input.receivable_line_id)
+            .ok_or(CreditNotesServiceError::UnprocessableEntry(
+                "A tétel nem az eredeti számlához tartozik!",
+            ))?;
+        if lines
+            .iter()
+            .any(|credited| credited.receivable_line_id == input.receivable_line_id)
+        {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "Egy tétel csak egyszer szerepelhet a helyesbítésben!",
+            ));
+        }
+        if input.quantity <= BigDecimal::zero() || input.quantity > line.remaining_quantity() {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "A helyesbített mennyiség pozitív és legfeljebb a még nem helyesbített mennyiség lehet!",
+            ));
+        }
+        if input.inventory_id.is_some() && line.product_id.is_none() {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "Készletre csak termék vételezhető vissza!",
+            ));
+        }
+        lines.push(credit_line(
+            line,
+            &input.quantity,
+            input.inventory_id,
+            currency_code,
+        ));
+    }
+    Ok(lines)
+}
+
+pub trait CreditNotesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = CreditNotesServiceResult<CreditNoteDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = CreditNotesServiceResult<(PaginatorMeta, Vec<CreditNote>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreateCreditNote,
+        tz: Tz,
+    ) -> impl Future<Output = CreditNotesServiceResult<CreditNote>> + Send;
+    fn creditable_lines(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = CreditNotesServiceResult<Vec<CreditableLine>>> + Send;
+}
+
+impl<'a, T> CreditNotesService for Service<'a, T>
+where
+    T: CreditNotesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> CreditNotesServiceResult<CreditNoteDetails> {
+        let repo = self.module().credit_notes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CreditNotesServiceError::Unauthorized)?,
+        )?;
+        Ok(CreditNoteDetails {
+            credit_note: repo.get_by_id(id).await?,
+            lines: repo.get_lines(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> CreditNotesServiceResult<(PaginatorMeta, Vec<CreditNote>)> {
+        Ok(self
+            .module()
+            .credit_notes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CreditNotesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateCreditNote,
+        tz: Tz,
+    ) -> CreditNotesServiceResult<CreditNote> {
+        let reason = payload.reason.trim();
+        if reason.is_empty() || reason.chars().count() > 1000 {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "A helyesbítés okának megadása kötelező és legfeljebb 1000 karakter lehet!",
+            ));
+        }
+        let repo = self.module().credit_notes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CreditNotesServiceError::Unauthorized)?,
+        )?;
+        let receivable = repo.get_receivable(payload.receivable_id).await?;
+        if receivable.status == STATUS_WRITTEN_OFF {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "Leírt követelés nem helyesbíthető!",
+            ));
+        }
+        let creditable_lines = repo.get_creditable_lines(receivable.id).await?;
+        if creditable_lines.is_empty() {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "Csak tételes számla helyesbíthető!",
+            ));
+        }
+        let lines = credit_lines(&creditable_lines, &payload.lines, &receivable.currency_code)?;
+        if lines.is_empty() {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "A számla minden tétele helyesbítésre került már!",
+            ));
+        }
+        for line in &lines {
+            if let Some(inventory_id) = line.inventory_id
+                && Some(repo.get_inventory_product_id(inventory_id).await?) != line.product_id
+            {
+                return Err(CreditNotesServiceError::UnprocessableEntry(
+                    "A visszavételezés készlete más termékhez tartozik!",
+                ));
+            }
+        }
+        let mut net_amount = Money::zero(&receivable.currency_code);
+        let mut tax_amount = Money::zero(&receivable.currency_code);
+        for line in &lines {
+            net_amount = net_amount.checked_add(&Money::new(
+                line.net_amount.clone(),
+                &receivable.currency_code,
+            ))?;
+            tax_amount = tax_amount.checked_add(&Money::new(
+                line.tax_amount.clone(),
+                &receivable.currency_code,
+            ))?;
+        }
+        if net_amount.is_zero() && tax_amount.is_zero() {
+            return Err(CreditNotesServiceError::UnprocessableEntry(
+                "Nulla végösszegű helyesbítés nem állítható ki!",
+            ));
+        }
+        let input = NewCreditNote {
+            receivable_id: receivable.id,
+            customer_id: receivable.customer_id,
+            issue_date: Utc::now().with_timezone(&tz).date_naive(),
+            currency_code: receivable.currency_code,
+            reason: reason.to_string(),
+            amount: net_amount.checked_add(&tax_amount)?.amount,
+            net_amount: net_amount.amount,
+            tax_amount: tax_amount.amount,
+            lines,
+        };
+        repo.insert(&input, self.claims()?.sub())
+            .await
+            .map_err(|e| match e {
+                RepositoryError::InvalidInput(_) => CreditNotesServiceError::UnprocessableEntry(
+                    "A helyesbített mennyiség meghaladja a számlázott mennyiséget!",
+                ),
+                e => e.into(),
+            })
+    }
+
+    async fn creditable_lines(
+        &self,
+        receivable_id: Uuid,
+    ) -> CreditNotesServiceResult<Vec<CreditableLine>> {
+        Ok(self
+            .module()
+            .credit_notes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CreditNotesServiceError::Unauthorized)?,
+            )?
+            .get_creditable_lines(receivable_id)
+            .await?)
+    }
+}
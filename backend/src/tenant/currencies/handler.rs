@@ -0,0 +1,253 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::currencies::CurrenciesModuleInterface;
+use crate::tenant::currencies::dto::{CurrencyEnabledInput, DefaultCurrencyInput};
+use crate::tenant::currencies::service::CurrenciesService;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: CurrenciesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(currencies_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), currencies_module.clone());
+    let result = map_handler_err(service.get_all().await, currencies_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        currencies_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_enabled<M: CurrenciesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(currencies_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CurrencyEnabledInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), currencies_module.clone());
+    let result = map_handler_err(
+        service.set_enabled(&payload).await,
+        currencies_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        currencies_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn settings<M: CurrenciesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(currencies_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), currencies_module.clone());
+    let result = map_handler_err(service.get_settings().await, currencies_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        currencies_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_default<M: CurrenciesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(currencies_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DefaultCurrencyInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), currencies_module.clone());
+    let result = map_handler_err(
+        service.set_default(&payload).await,
+        currencies_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        currencies_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::currencies;
+    use crate::tenant::currencies::model::CurrencySettings;
+    use crate::tenant::currencies::repository::MockCurrenciesRepository;
+    use crate::tenant::currencies::tests::{MockCurrenciesModule, currencies_repo, currency};
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::{always, eq};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockCurrenciesRepository, tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut currencies_module = MockCurrenciesModule::new();
+        currencies_module
+            .expect_currencies_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        currencies_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(currencies::routes::routes(Arc::new(currencies_module))),
+        )
+    }
+
+    fn request(uri: &str, tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_set_default_accepts_enabled_currency() {
+        let tenant_id = Uuid::new_v4();
+        let mut repo = currencies_repo("HUF", &["HUF", "EUR"]);
+        repo.expect_set_default_currency()
+            .with(eq("EUR"), always())
+            .times(1)
+            .returning(|code, sub| {
+                Ok(CurrencySettings {
+                    default_currency_code: code.to_string(),
+                    updated_by_id: Some(sub),
+                    updated_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "/api/currencies/set_default",
+                tenant_id,
+                json!({"currency_code": " eur "}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["default_currency_code"], json!("EUR"));
+    }
+
+    #[tokio::test]
+    async fn test_set_default_rejects_disabled_currency() {
+        let tenant_id = Uuid::new_v4();
+        let mut repo = currencies_repo("HUF", &["HUF"]);
+        repo.expect_set_default_currency().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "/api/currencies/set_default",
+                tenant_id,
+                json!({"currency_code": "USD"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_enabled_keeps_default_currency_enabled() {
+        let tenant_id = Uuid::new_v4();
+        let mut repo = currencies_repo("HUF", &["HUF", "EUR"]);
+        repo.expect_set_enabled().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "/api/currencies/set_enabled",
+                tenant_id,
+                json!({"currency_code": "huf", "is_enabled": false}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = extract_json_response(response).await;
+        assert_eq!(
+            body["error"]["message"],
+            json!(
+                "Hiba történt az adatok feldolgozása során: Az alapértelmezett pénznem nem tiltható le!"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_enabled_disables_currency() {
+        let tenant_id = Uuid::new_v4();
+        let mut repo = currencies_repo("HUF", &["HUF", "EUR"]);
+        repo.expect_set_enabled()
+            .with(eq("EUR"), eq(false))
+            .times(1)
+            .returning(|code, is_enabled| Ok(currency(code, is_enabled)));
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "/api/currencies/set_enabled",
+                tenant_id,
+                json!({"currency_code": "EUR", "is_enabled": false}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["is_enabled"], json!(false));
+    }
+}
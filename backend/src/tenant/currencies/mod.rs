@@ -1,7 +1,7 @@
 /*
  * This file is part of the Obvia ERP.
  *
- * Copyright (C) 2025 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
  *
  * This program is free software: you can redistribute it and/or modify
  * it under the terms of the GNU Affero General Public License as published
@@ -17,5 +17,106 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::AppState;
+use crate::common::BaseModule;
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::tenant::currencies::repository::CurrenciesRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
 pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
 pub(crate) mod types;
+
+pub trait CurrenciesModuleInterface: BaseModule {
+    fn currencies_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn CurrenciesRepository + Send + Sync>>;
+}
+
+impl<P, T> CurrenciesModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn currencies_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn CurrenciesRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::tenant::currencies::model::{Currency, CurrencySettings};
+    use crate::tenant::currencies::repository::MockCurrenciesRepository;
+    use chrono::Utc;
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub CurrenciesModule {}
+        impl ConfigProvider for CurrenciesModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for CurrenciesModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for CurrenciesModule {}
+        impl CurrenciesModuleInterface for CurrenciesModule {
+            fn currencies_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn CurrenciesRepository + Send + Sync>>;
+        }
+    );
+
+    pub fn currency(code: &str, is_enabled: bool) -> Currency {
+        Currency {
+            code: code.to_string(),
+            number: "000".to_string(),
+            name: code.to_string(),
+            is_enabled,
+        }
+    }
+
+    /// Currencies of a tenant for the document tests: `enabled` can be used on documents,
+    /// every other currency is known but disabled
+    pub fn currencies_repo(
+        default_currency_code: &'static str,
+        enabled: &'static [&'static str],
+    ) -> MockCurrenciesRepository {
+        let mut repo = MockCurrenciesRepository::new();
+        repo.expect_get_settings().returning(move || {
+            Ok(CurrencySettings {
+                default_currency_code: default_currency_code.to_string(),
+                updated_by_id: None,
+                updated_at: Utc::now(),
+            })
+        });
+        repo.expect_get_by_code()
+            .returning(move |code| Ok(Some(currency(code, enabled.contains(&code)))));
+        repo
+    }
+}
@@ -1,7 +1,7 @@
 /*
  * This file is part of the Obvia ERP.
  *
- * Copyright (C) 2025 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
  *
  * This program is free software: you can redistribute it and/or modify
  * it under the terms of the GNU Affero General Public License as published
@@ -19,24 +19,89 @@
 
 use crate::common::error::RepositoryResult;
 use crate::common::model::SelectOption;
+use crate::tenant::currencies::model::{Currency, CurrencySettings};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait CurrenciesRepository: Send + Sync {
     async fn get_all_countries_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>>;
+    async fn get_all(&self) -> RepositoryResult<Vec<Currency>>;
+    async fn get_by_code(&self, code: &str) -> RepositoryResult<Option<Currency>>;
+    async fn set_enabled(&self, code: &str, is_enabled: bool) -> RepositoryResult<Currency>;
+    async fn get_settings(&self) -> RepositoryResult<CurrencySettings>;
+    async fn set_default_currency(
+        &self,
+        code: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CurrencySettings>;
 }
 
 #[async_trait]
 impl CurrenciesRepository for PgPool {
     async fn get_all_countries_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>> {
         Ok(sqlx::query_as::<_, SelectOption>(
-            r#"SELECT code as value, code || ' - ' || name as title FROM currencies"#,
+            r#"SELECT code as value, code || ' - ' || name as title FROM currencies WHERE is_enabled ORDER BY code"#,
         )
         .fetch_all(self)
         .await?)
     }
+
+    async fn get_all(&self) -> RepositoryResult<Vec<Currency>> {
+        Ok(
+            sqlx::query_as::<_, Currency>("SELECT * FROM currencies ORDER BY code")
+                .fetch_all(self)
+                .await?,
+        )
+    }
+
+    async fn get_by_code(&self, code: &str) -> RepositoryResult<Option<Currency>> {
+        Ok(
+            sqlx::query_as::<_, Currency>("SELECT * FROM currencies WHERE code = $1")
+                .bind(code)
+                .fetch_optional(self)
+                .await?,
+        )
+    }
+
+    async fn set_enabled(&self, code: &str, is_enabled: bool) -> RepositoryResult<Currency> {
+        Ok(sqlx::query_as::<_, Currency>(
+            "UPDATE currencies SET is_enabled = $1 WHERE code = $2 RETURNING *",
+        )
+        .bind(is_enabled)
+        .bind(code)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_settings(&self) -> RepositoryResult<CurrencySettings> {
+        Ok(sqlx::query_as::<_, CurrencySettings>(
+            "SELECT default_currency_code, updated_by_id, updated_at FROM currency_settings",
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_default_currency(
+        &self,
+        code: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CurrencySettings> {
+        Ok(sqlx::query_as::<_, CurrencySettings>(
+            r#"
+            UPDATE currency_settings
+            SET default_currency_code = $1,
+                updated_by_id = $2
+            RETURNING default_currency_code, updated_by_id, updated_at
+            "#,
+        )
+        .bind(code)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
 }
@@ -0,0 +1,197 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::currencies::CurrenciesModuleInterface;
+use crate::tenant::currencies::dto::{CurrencyEnabledInput, DefaultCurrencyInput};
+use crate::tenant::currencies::model::{Currency, CurrencySettings};
+use crate::tenant::currencies::repository::CurrenciesRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+
+#[derive(Debug, Error)]
+pub enum CurrenciesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for CurrenciesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CurrenciesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CurrenciesServiceError> for AppError {
+    fn from(value: CurrenciesServiceError) -> Self {
+        match value {
+            CurrenciesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CurrenciesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CurrenciesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CurrenciesServiceResult<T> = Result<T, CurrenciesServiceError>;
+
+const UNKNOWN_CURRENCY: &str = "Hibás pénznem!";
+const DISABLED_CURRENCY: &str =
+    "A választott pénznem nincs engedélyezve, kérjük válasszon az engedélyezett pénznemek közül!";
+
+/// The currency of a new document: the requested one when it is enabled, the default currency
+/// of the tenant when none is requested
+pub(crate) async fn document_currency(
+    repo: &(dyn CurrenciesRepository + Send + Sync),
+    currency_code: &str,
+) -> CurrenciesServiceResult<String> {
+    let currency_code = currency_code.trim().to_uppercase();
+    if currency_code.is_empty() {
+        return Ok(repo.get_settings().await?.default_currency_code);
+    }
+    match repo.get_by_code(&currency_code).await? {
+        Some(currency) if currency.is_enabled => Ok(currency.code),
+        Some(_) => Err(CurrenciesServiceError::UnprocessableEntry(
+            DISABLED_CURRENCY,
+        )),
+        None => Err(CurrenciesServiceError::UnprocessableEntry(UNKNOWN_CURRENCY)),
+    }
+}
+
+pub trait CurrenciesService {
+    fn get_all(&self) -> impl Future<Output = CurrenciesServiceResult<Vec<Currency>>> + Send;
+    fn set_enabled(
+        &self,
+        payload: &CurrencyEnabledInput,
+    ) -> impl Future<Output = CurrenciesServiceResult<Currency>> + Send;
+    fn get_settings(
+        &self,
+    ) -> impl Future<Output = CurrenciesServiceResult<CurrencySettings>> + Send;
+    fn set_default(
+        &self,
+        payload: &DefaultCurrencyInput,
+    ) -> impl Future<Output = CurrenciesServiceResult<CurrencySettings>> + Send;
+}
+
+impl<'a, T> CurrenciesService for Service<'a, T>
+where
+    T: CurrenciesModuleInterface,
+{
+    async fn get_all(&self) -> CurrenciesServiceResult<Vec<Currency>> {
+        Ok(self
+            .module()
+            .currencies_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CurrenciesServiceError::Unauthorized)?,
+            )?
+            .get_all()
+            .await?)
+    }
+
+    async fn set_enabled(
+        &self,
+        payload: &CurrencyEnabledInput,
+    ) -> CurrenciesServiceResult<Currency> {
+        let repo = self.module().currencies_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CurrenciesServiceError::Unauthorized)?,
+        )?;
+        let currency_code = payload.currency_code.trim().to_uppercase();
+        if !payload.is_enabled && repo.get_settings().await?.default_currency_code == currency_code
+        {
+            return Err(CurrenciesServiceError::UnprocessableEntry(
+                "Az alapértelmezett pénznem nem tiltható le!",
+            ));
+        }
+        Ok(repo.set_enabled(&currency_code, payload.is_enabled).await?)
+    }
+
+    async fn get_settings(&self) -> CurrenciesServiceResult<CurrencySettings> {
+        Ok(self
+            .module()
+            .currencies_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CurrenciesServiceError::Unauthorized)?,
+            )?
+            .get_settings()
+            .await?)
+    }
+
+    async fn set_default(
+        &self,
+        payload: &DefaultCurrencyInput,
+    ) -> CurrenciesServiceResult<CurrencySettings> {
+        let claims = self.claims()?;
+        let repo = self.module().currencies_repo(
+            claims
+                .active_tenant()
+                .ok_or(CurrenciesServiceError::Unauthorized)?,
+        )?;
+        let currency_code = payload.currency_code.trim().to_uppercase();
+        match repo.get_by_code(&currency_code).await? {
+            Some(currency) if currency.is_enabled => Ok(repo
+                .set_default_currency(&currency.code, claims.sub())
+                .await?),
+            Some(_) => Err(CurrenciesServiceError::UnprocessableEntry(
+                "Csak engedélyezett pénznem lehet az alapértelmezett!",
+            )),
+            None => Err(CurrenciesServiceError::UnprocessableEntry(UNKNOWN_CURRENCY)),
+        }
+    }
+}
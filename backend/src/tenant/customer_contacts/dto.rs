@@ -0,0 +1,58 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CustomerContactsQuery {
+    pub customer_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateCustomerContact {
+    pub customer_id: Uuid,
+    pub name: String,
+    pub role: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UpdateCustomerContact {
+    pub id: Uuid,
+    pub name: String,
+    pub role: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+/// Trimmed and validated contact data, blank optional fields are `None`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomerContactFields {
+    pub name: String,
+    pub role: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub is_primary: bool,
+}
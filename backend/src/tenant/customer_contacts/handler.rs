@@ -0,0 +1,380 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::customer_contacts::CustomerContactsModuleInterface;
+use crate::tenant::customer_contacts::dto::{
+    CreateCustomerContact, CustomerContactsQuery, UpdateCustomerContact,
+};
+use crate::tenant::customer_contacts::service::CustomerContactsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: CustomerContactsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_contacts_module): State<Arc<M>>,
+    Query(payload): Query<CustomerContactsQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_contacts_module.clone());
+    let result = map_handler_err(
+        service.list(payload.customer_id).await,
+        customer_contacts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_contacts_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: CustomerContactsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_contacts_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateCustomerContact>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_contacts_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        customer_contacts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        customer_contacts_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: CustomerContactsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_contacts_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateCustomerContact>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_contacts_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        customer_contacts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_contacts_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: CustomerContactsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_contacts_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_contacts_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        customer_contacts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A kapcsolattartó törlése sikeresen megtörtént",
+            ))
+            .build(),
+        customer_contacts_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::customer_contacts::model::CustomerContact;
+    use crate::tenant::customer_contacts::{
+        self, repository::MockCustomerContactsRepository, tests::MockCustomerContactsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockCustomerContactsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut customer_contacts_module = MockCustomerContactsModule::new();
+        customer_contacts_module
+            .expect_customer_contacts_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        customer_contacts_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(customer_contacts::routes::routes(Arc::new(
+                customer_contacts_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn contact(customer_id: Uuid, is_primary: bool) -> CustomerContact {
+        CustomerContact {
+            id: Uuid::new_v4(),
+            customer_id,
+            name: "Teszt Elek".to_string(),
+            role: Some("Pénzügy".to_string()),
+            email: Some("penzugy@example.com".to_string()),
+            phone_number: Some("+36301234567".to_string()),
+            is_primary,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_success() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let contacts = vec![contact(customer_id, true), contact(customer_id, false)];
+
+        let mut repo = MockCustomerContactsRepository::new();
+        repo.expect_get_by_customer()
+            .with(eq(customer_id))
+            .times(1)
+            .returning({
+                let contacts = contacts.clone();
+                move |_| Ok(contacts.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/customer_contacts/list?customer_id={customer_id}"),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<CustomerContact> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, contacts);
+    }
+
+    #[tokio::test]
+    async fn test_create_trims_fields_and_drops_blank_ones() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let created = contact(customer_id, true);
+
+        let mut repo = MockCustomerContactsRepository::new();
+        repo.expect_customer_exists()
+            .with(eq(customer_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        repo.expect_insert()
+            .withf(move |id, fields, sub| {
+                *id == customer_id
+                    && fields.name == "Teszt Elek"
+                    && fields.role.is_none()
+                    && fields.email.as_deref() == Some("penzugy@example.com")
+                    && fields.phone_number.is_none()
+                    && fields.is_primary
+                    && *sub == user_id
+            })
+            .times(1)
+            .returning({
+                let created = created.clone();
+                move |_, _, _| Ok(created.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/customer_contacts/create",
+                user_id,
+                tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "name": " Teszt Elek ",
+                    "role": "  ",
+                    "email": "penzugy@example.com ",
+                    "phone_number": "",
+                    "is_primary": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: CustomerContact =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_email() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomerContactsRepository::new();
+        repo.expect_customer_exists().never();
+        repo.expect_insert().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/customer_contacts/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "customer_id": Uuid::new_v4(),
+                    "name": "Teszt Elek",
+                    "email": "nem-email-cim"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_unknown_customer() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomerContactsRepository::new();
+        repo.expect_customer_exists()
+            .times(1)
+            .returning(|_| Ok(false));
+        repo.expect_insert().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/customer_contacts/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "customer_id": Uuid::new_v4(),
+                    "name": "Teszt Elek"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_sets_primary_contact() {
+        let tenant_id = Uuid::new_v4();
+        let updated = contact(Uuid::new_v4(), true);
+
+        let mut repo = MockCustomerContactsRepository::new();
+        repo.expect_update()
+            .withf({
+                let id = updated.id;
+                move |contact_id, fields| {
+                    *contact_id == id
+                        && fields.phone_number.as_deref() == Some("+36301234567")
+                        && fields.is_primary
+                }
+            })
+            .times(1)
+            .returning({
+                let updated = updated.clone();
+                move |_, _| Ok(updated.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/customer_contacts/update",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "id": updated.id,
+                    "name": "Teszt Elek",
+                    "role": "Pénzügy",
+                    "email": "penzugy@example.com",
+                    "phone_number": "+36301234567",
+                    "is_primary": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: CustomerContact =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, updated);
+    }
+}
@@ -0,0 +1,212 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::customer_contacts::dto::CustomerContactFields;
+use crate::tenant::customer_contacts::model::CustomerContact;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CustomerContactsRepository: Send + Sync {
+    async fn customer_exists(&self, customer_id: Uuid) -> RepositoryResult<bool>;
+    async fn get_by_customer(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerContact>>;
+    /// The first contact of a customer becomes its primary contact
+    async fn insert(
+        &self,
+        customer_id: Uuid,
+        fields: &CustomerContactFields,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerContact>;
+    async fn update(
+        &self,
+        id: Uuid,
+        fields: &CustomerContactFields,
+    ) -> RepositoryResult<CustomerContact>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+}
+
+/// Locks the customer so concurrent primary contact changes are serialized
+async fn lock_customer(conn: &mut PgConnection, customer_id: Uuid) -> RepositoryResult<()> {
+    sqlx::query("SELECT id FROM customers WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+        .bind(customer_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn clear_primary(
+    conn: &mut PgConnection,
+    customer_id: Uuid,
+    except_id: Option<Uuid>,
+) -> RepositoryResult<()> {
+    sqlx::query(
+        r#"
+        UPDATE customer_contacts
+        SET is_primary = false
+        WHERE customer_id = $1
+            AND is_primary
+            AND deleted_at IS NULL
+            AND ($2::UUID IS NULL OR id <> $2)
+        "#,
+    )
+    .bind(customer_id)
+    .bind(except_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl CustomerContactsRepository for PgPool {
+    async fn customer_exists(&self, customer_id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM customers WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(customer_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_customer(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerContact>> {
+        Ok(sqlx::query_as::<_, CustomerContact>(
+            r#"
+            SELECT id, customer_id, name, role, email, phone_number, is_primary, created_by_id,
+                   created_at, updated_at
+            FROM customer_contacts
+            WHERE customer_id = $1
+                AND deleted_at IS NULL
+            ORDER BY is_primary DESC, name, created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        customer_id: Uuid,
+        fields: &CustomerContactFields,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerContact> {
+        let mut tx = self.begin().await?;
+        lock_customer(&mut tx, customer_id).await?;
+        let has_primary = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (SELECT 1
+                           FROM customer_contacts
+                           WHERE customer_id = $1
+                               AND is_primary
+                               AND deleted_at IS NULL)
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let is_primary = fields.is_primary || !has_primary;
+        if is_primary {
+            clear_primary(&mut tx, customer_id, None).await?;
+        }
+        let contact = sqlx::query_as::<_, CustomerContact>(
+            r#"
+            INSERT INTO customer_contacts (customer_id, name, role, email, phone_number, is_primary,
+                                           created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, customer_id, name, role, email, phone_number, is_primary, created_by_id,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(customer_id)
+        .bind(&fields.name)
+        .bind(&fields.role)
+        .bind(&fields.email)
+        .bind(&fields.phone_number)
+        .bind(is_primary)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(contact)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        fields: &CustomerContactFields,
+    ) -> RepositoryResult<CustomerContact> {
+        let mut tx = self.begin().await?;
+        let customer_id = sqlx::query_scalar::<_, Uuid>(
+            "SELECT customer_id FROM customer_contacts WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        lock_customer(&mut tx, customer_id).await?;
+        if fields.is_primary {
+            clear_primary(&mut tx, customer_id, Some(id)).await?;
+        }
+        let contact = sqlx::query_as::<_, CustomerContact>(
+            r#"
+            UPDATE customer_contacts
+            SET name = $2,
+                role = $3,
+                email = $4,
+                phone_number = $5,
+                is_primary = $6
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING id, customer_id, name, role, email, phone_number, is_primary, created_by_id,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&fields.name)
+        .bind(&fields.role)
+        .bind(&fields.email)
+        .bind(&fields.phone_number)
+        .bind(fields.is_primary)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(contact)
+    }
+
+    // NOTE: deleting the primary contact leaves the customer without one, its own e-mail address
+    // is used as the recipient until a new primary contact is chosen
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE customer_contacts
+            SET deleted_at = NOW(),
+                is_primary = false
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::common::value_object::ValueObjectData;
+use crate::tenant::customer_contacts::CustomerContactsModuleInterface;
+use crate::tenant::customer_contacts::dto::{
+    CreateCustomerContact, CustomerContactFields, UpdateCustomerContact,
+};
+use crate::tenant::customer_contacts::model::CustomerContact;
+use crate::tenant::customer_contacts::repository::CustomerContactsRepository;
+use crate::tenant::customers::types::customer::CustomerPhoneNumber;
+use axum::http::StatusCode;
+use lettre::Address;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_ROLE_LENGTH: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum CustomerContactsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for CustomerContactsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CustomerContactsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CustomerContactsServiceError> for AppError {
+    fn from(value: CustomerContactsServiceError) -> Self {
+        match value {
+            CustomerContactsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerContactsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerContactsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CustomerContactsServiceResult<T> = Result<T, CustomerContactsServiceError>;
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn validate_fields(
+    name: &str,
+    role: Option<&str>,
+    email: Option<&str>,
+    phone_number: Option<&str>,
+    is_primary: bool,
+) -> CustomerContactsServiceResult<CustomerContactFields> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(CustomerContactsServiceError::UnprocessableEntry(
+            "A kapcsolattartó neve nem lehet üres és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let role = non_empty(role);
+    if role
+        .as_ref()
+        .is_some_and(|role| role.chars().count() > MAX_ROLE_LENGTH)
+    {
+        return Err(CustomerContactsServiceError::UnprocessableEntry(
+            "A kapcsolattartó szerepköre legfeljebb 100 karakter lehet!",
+        ));
+    }
+    let email = non_empty(email);
+    if email
+        .as_ref()
+        .is_some_and(|email| email.parse::<Address>().is_err())
+    {
+        return Err(CustomerContactsServiceError::UnprocessableEntry(
+            "Hibás e-mail cím formátum",
+        ));
+    }
+    let phone_number = non_empty(phone_number);
+    if let Some(phone_number) = &phone_number
+        && CustomerPhoneNumber::new(phone_number)
+            .ok()
+            .flatten()
+            .is_none_or(|phone_number| phone_number.validate().is_err())
+    {
+        return Err(CustomerContactsServiceError::UnprocessableEntry(
+            CustomerPhoneNumber::VALIDATION_ERROR,
+        ));
+    }
+    Ok(CustomerContactFields {
+        name: name.to_string(),
+        role,
+        email,
+        phone_number,
+        is_primary,
+    })
+}
+
+pub trait CustomerContactsService {
+    fn list(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = CustomerContactsServiceResult<Vec<CustomerContact>>> + Send;
+    fn create(
+        &self,
+        payload: &CreateCustomerContact,
+    ) -> impl Future<Output = CustomerContactsServiceResult<CustomerContact>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateCustomerContact,
+    ) -> impl Future<Output = CustomerContactsServiceResult<CustomerContact>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = CustomerContactsServiceResult<()>> + Send;
+    fn repo(
+        &self,
+    ) -> CustomerContactsServiceResult<Arc<dyn CustomerContactsRepository + Send + Sync>>;
+}
+
+impl<'a, T> CustomerContactsService for Service<'a, T>
+where
+    T: CustomerContactsModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> CustomerContactsServiceResult<Arc<dyn CustomerContactsRepository + Send + Sync>> {
+        Ok(self.module().customer_contacts_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomerContactsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn list(&self, customer_id: Uuid) -> CustomerContactsServiceResult<Vec<CustomerContact>> {
+        Ok(self.repo()?.get_by_customer(customer_id).await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateCustomerContact,
+    ) -> CustomerContactsServiceResult<CustomerContact> {
+        let fields = validate_fields(
+            &payload.name,
+            payload.role.as_deref(),
+            payload.email.as_deref(),
+            payload.phone_number.as_deref(),
+            payload.is_primary,
+        )?;
+        let repo = self.repo()?;
+        if !repo.customer_exists(payload.customer_id).await? {
+            return Err(CustomerContactsServiceError::UnprocessableEntry(
+                "Az ügyfél nem található!",
+            ));
+        }
+        Ok(repo
+            .insert(payload.customer_id, &fields, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update(
+        &self,
+        payload: &UpdateCustomerContact,
+    ) -> CustomerContactsServiceResult<CustomerContact> {
+        let fields = validate_fields(
+            &payload.name,
+            payload.role.as_deref(),
+            payload.email.as_deref(),
+            payload.phone_number.as_deref(),
+            payload.is_primary,
+        )?;
+        Ok(self.repo()?.update(payload.id, &fields).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> CustomerContactsServiceResult<()> {
+        Ok(self.repo()?.delete_by_id(id).await?)
+    }
+}
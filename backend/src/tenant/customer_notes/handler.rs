@@ -0,0 +1,332 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::customer_notes::CustomerNotesModuleInterface;
+use crate::tenant::customer_notes::dto::{
+    CreateCustomerNote, CustomerNotesQuery, UpdateCustomerNote,
+};
+use crate::tenant::customer_notes::service::CustomerNotesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: CustomerNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_notes_module): State<Arc<M>>,
+    Query(payload): Query<CustomerNotesQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_notes_module.clone());
+    let result = map_handler_err(
+        service.list(payload.customer_id).await,
+        customer_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: CustomerNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_notes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateCustomerNote>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_notes_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        customer_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        customer_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: CustomerNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_notes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateCustomerNote>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_notes_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        customer_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: CustomerNotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_notes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_notes_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        customer_notes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A jegyzet törlése sikeresen megtörtént",
+            ))
+            .build(),
+        customer_notes_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::customer_notes::model::CustomerNote;
+    use crate::tenant::customer_notes::{
+        self, repository::MockCustomerNotesRepository, tests::MockCustomerNotesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockCustomerNotesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut customer_notes_module = MockCustomerNotesModule::new();
+        customer_notes_module
+            .expect_customer_notes_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        customer_notes_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(customer_notes::routes::routes(Arc::new(
+                customer_notes_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn customer_note(customer_id: Uuid, created_by_id: Uuid) -> CustomerNote {
+        CustomerNote {
+            id: Uuid::new_v4(),
+            customer_id,
+            note: "Telefonon egyeztettünk a karbantartás időpontjáról.".to_string(),
+            created_by_id,
+            created_by: "Teszt Elek".to_string(),
+            edited: false,
+            edited_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_success() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let notes = vec![
+            customer_note(customer_id, Uuid::new_v4()),
+            customer_note(customer_id, Uuid::new_v4()),
+        ];
+
+        let mut repo = MockCustomerNotesRepository::new();
+        repo.expect_get_by_customer()
+            .with(eq(customer_id))
+            .times(1)
+            .returning({
+                let notes = notes.clone();
+                move |_| Ok(notes.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/customer_notes/list?customer_id={customer_id}"),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<CustomerNote> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, notes);
+    }
+
+    #[tokio::test]
+    async fn test_create_trims_note() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let created = customer_note(customer_id, user_id);
+
+        let mut repo = MockCustomerNotesRepository::new();
+        repo.expect_customer_exists()
+            .with(eq(customer_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        repo.expect_insert()
+            .withf(move |id, note, sub| {
+                *id == customer_id
+                    && note == "Telefonon egyeztettünk a karbantartás időpontjáról."
+                    && *sub == user_id
+            })
+            .times(1)
+            .returning({
+                let created = created.clone();
+                move |_, _, _| Ok(created.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/customer_notes/create",
+                user_id,
+                tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "note": "  Telefonon egyeztettünk a karbantartás időpontjáról.\n"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: CustomerNote =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_empty_note() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomerNotesRepository::new();
+        repo.expect_customer_exists().never();
+        repo.expect_insert().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/customer_notes/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "customer_id": Uuid::new_v4(),
+                    "note": " "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_by_other_user_is_forbidden() {
+        let tenant_id = Uuid::new_v4();
+        let current = customer_note(Uuid::new_v4(), Uuid::new_v4());
+
+        let mut repo = MockCustomerNotesRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(current.id))
+            .times(1)
+            .returning({
+                let current = current.clone();
+                move |_| Ok(current.clone())
+            });
+        repo.expect_update().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/customer_notes/update",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "id": current.id,
+                    "note": "Módosított jegyzet"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::customer_notes::model::{COMMENTABLE_TYPE, CustomerNote};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CustomerNotesRepository: Send + Sync {
+    async fn customer_exists(&self, customer_id: Uuid) -> RepositoryResult<bool>;
+    async fn get_by_customer(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerNote>>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CustomerNote>;
+    async fn insert(
+        &self,
+        customer_id: Uuid,
+        note: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerNote>;
+    async fn update(&self, id: Uuid, note: &str) -> RepositoryResult<CustomerNote>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+}
+
+const CUSTOMER_NOTE_SELECT: &str = r#"
+    SELECT comments.id,
+           comments.commentable_id AS customer_id,
+           comments.comment AS note,
+           comments.created_by_id,
+           users.last_name || ' ' || users.first_name AS created_by,
+           comments.edited_at IS NOT NULL AS edited,
+           comments.edited_at,
+           comments.created_at
+    FROM comments
+    JOIN users ON comments.created_by_id = users.id
+    WHERE comments.commentable_type = 'customers'
+        AND comments.deleted_at IS NULL
+"#;
+
+#[async_trait]
+impl CustomerNotesRepository for PgPool {
+    async fn customer_exists(&self, customer_id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM customers WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(customer_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_customer(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerNote>> {
+        Ok(sqlx::query_as::<_, CustomerNote>(AssertSqlSafe(format!(
+            "{CUSTOMER_NOTE_SELECT} AND comments.commentable_id = $1 ORDER BY comments.created_at DESC" // Security: constant
+        )))
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CustomerNote> {
+        Ok(sqlx::query_as::<_, CustomerNote>(AssertSqlSafe(format!(
+            "{CUSTOMER_NOTE_SELECT} AND comments.id = $1" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        customer_id: Uuid,
+        note: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerNote> {
+        let id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO comments (id, commentable_type, commentable_id, comment, created_by_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(COMMENTABLE_TYPE)
+        .bind(customer_id)
+        .bind(note)
+        .bind(sub)
+        .fetch_one(self)
+        .await?;
+        self.get_by_id(id).await
+    }
+
+    async fn update(&self, id: Uuid, note: &str) -> RepositoryResult<CustomerNote> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE comments
+            SET comment = $1,
+                edited_at = NOW()
+            WHERE id = $2
+                AND commentable_type = 'customers'
+                AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(note)
+        .bind(id)
+        .fetch_one(self)
+        .await?;
+        self.get_by_id(id).await
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE comments
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND commentable_type = 'customers'
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+}
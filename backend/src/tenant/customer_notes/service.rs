@@ -0,0 +1,190 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::customer_notes::CustomerNotesModuleInterface;
+use crate::tenant::customer_notes::dto::{CreateCustomerNote, UpdateCustomerNote};
+use crate::tenant::customer_notes::model::CustomerNote;
+use crate::tenant::customer_notes::repository::CustomerNotesRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const MAX_NOTE_LENGTH: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum CustomerNotesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for CustomerNotesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CustomerNotesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CustomerNotesServiceError> for AppError {
+    fn from(value: CustomerNotesServiceError) -> Self {
+        match value {
+            CustomerNotesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerNotesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            CustomerNotesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerNotesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CustomerNotesServiceResult<T> = Result<T, CustomerNotesServiceError>;
+
+fn validate_note(note: &str) -> CustomerNotesServiceResult<String> {
+    let note = note.trim();
+    if note.is_empty() || note.chars().count() > MAX_NOTE_LENGTH {
+        return Err(CustomerNotesServiceError::UnprocessableEntry(
+            "A jegyzet nem lehet üres és legfeljebb 10 000 karakter lehet!",
+        ));
+    }
+    Ok(note.to_string())
+}
+
+fn ensure_author(customer_note: &CustomerNote, sub: Uuid) -> CustomerNotesServiceResult<()> {
+    if customer_note.created_by_id != sub {
+        return Err(CustomerNotesServiceError::Forbidden);
+    }
+    Ok(())
+}
+
+pub trait CustomerNotesService {
+    fn list(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = CustomerNotesServiceResult<Vec<CustomerNote>>> + Send;
+    fn create(
+        &self,
+        payload: &CreateCustomerNote,
+    ) -> impl Future<Output = CustomerNotesServiceResult<CustomerNote>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateCustomerNote,
+    ) -> impl Future<Output = CustomerNotesServiceResult<CustomerNote>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = CustomerNotesServiceResult<()>> + Send;
+    fn repo(&self) -> CustomerNotesServiceResult<Arc<dyn CustomerNotesRepository + Send + Sync>>;
+}
+
+impl<'a, T> CustomerNotesService for Service<'a, T>
+where
+    T: CustomerNotesModuleInterface,
+{
+    fn repo(&self) -> CustomerNotesServiceResult<Arc<dyn CustomerNotesRepository + Send + Sync>> {
+        Ok(self.module().customer_notes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomerNotesServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn list(&self, customer_id: Uuid) -> CustomerNotesServiceResult<Vec<CustomerNote>> {
+        Ok(self.repo()?.get_by_customer(customer_id).await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateCustomerNote,
+    ) -> CustomerNotesServiceResult<CustomerNote> {
+        let note = validate_note(&payload.note)?;
+        let repo = self.repo()?;
+        if !repo.customer_exists(payload.customer_id).await? {
+            return Err(CustomerNotesServiceError::UnprocessableEntry(
+                "Az ügyfél nem található!",
+            ));
+        }
+        Ok(repo
+            .insert(payload.customer_id, &note, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update(
+        &self,
+        payload: &UpdateCustomerNote,
+    ) -> CustomerNotesServiceResult<CustomerNote> {
+        let note = validate_note(&payload.note)?;
+        let repo = self.repo()?;
+        ensure_author(&repo.get_by_id(payload.id).await?, self.claims()?.sub())?;
+        Ok(repo.update(payload.id, &note).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> CustomerNotesServiceResult<()> {
+        let repo = self.repo()?;
+        ensure_author(&repo.get_by_id(id).await?, self.claims()?.sub())?;
+        Ok(repo.delete_by_id(id).await?)
+    }
+}
@@ -0,0 +1,474 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::customer_portal::CustomerPortalModuleInterface;
+use crate::tenant::customer_portal::dto::{
+    CreateCustomerPortalToken, CustomerPortalTokenQuery, PortalDocumentQuery, PortalQuery,
+};
+use crate::tenant::customer_portal::service::CustomerPortalService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use axum_extra::TypedHeader;
+use headers::{Authorization, authorization::Bearer};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn pdf_response(id: Uuid, pdf: Vec<u8>) -> HandlerResult {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"attachment; filename="{id}.pdf""#)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+pub async fn list_tokens<M: CustomerPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_portal_module): State<Arc<M>>,
+    Query(payload): Query<CustomerPortalTokenQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_portal_module.clone());
+    let result = map_handler_err(
+        service.list_tokens(payload.customer_id).await,
+        customer_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_token<M: CustomerPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_portal_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateCustomerPortalToken>,
+) -> HandlerResult {
+    let tz = map_handler_err(claims.tz(), customer_portal_module.clone()).await?;
+    let service = Service::new(Some(&claims), customer_portal_module.clone());
+    let result = map_handler_err(
+        service.create_token(&payload, tz).await,
+        customer_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        customer_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn revoke_token<M: CustomerPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_portal_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_portal_module.clone());
+    let result = map_handler_err(
+        service.revoke_token(payload.uuid).await,
+        customer_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn invoices<M: CustomerPortalModuleInterface>(
+    State(customer_portal_module): State<Arc<M>>,
+    Query(payload): Query<PortalQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> HandlerResult {
+    let service = Service::new(None, customer_portal_module.clone());
+    let result = map_handler_err(
+        service
+            .portal_invoices(payload.tenant_id, bearer.token())
+            .await,
+        customer_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn invoice_pdf<M: CustomerPortalModuleInterface>(
+    State(customer_portal_module): State<Arc<M>>,
+    Query(payload): Query<PortalDocumentQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> HandlerResult {
+    let service = Service::new(None, customer_portal_module.clone());
+    let pdf = map_handler_err(
+        service
+            .portal_invoice_pdf(payload.tenant_id, bearer.token(), payload.uuid)
+            .await,
+        customer_portal_module,
+    )
+    .await?;
+    pdf_response(payload.uuid, pdf)
+}
+
+pub async fn worksheets<M: CustomerPortalModuleInterface>(
+    State(customer_portal_module): State<Arc<M>>,
+    Query(payload): Query<PortalQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> HandlerResult {
+    let service = Service::new(None, customer_portal_module.clone());
+    let result = map_handler_err(
+        service
+            .portal_worksheets(payload.tenant_id, bearer.token())
+            .await,
+        customer_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn worksheet_pdf<M: CustomerPortalModuleInterface>(
+    State(customer_portal_module): State<Arc<M>>,
+    Query(payload): Query<PortalDocumentQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> HandlerResult {
+    let service = Service::new(None, customer_portal_module.clone());
+    let pdf = map_handler_err(
+        service
+            .portal_worksheet_pdf(payload.tenant_id, bearer.token(), payload.uuid)
+            .await,
+        customer_portal_module,
+    )
+    .await?;
+    pdf_response(payload.uuid, pdf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::crypto::hash_token;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::customer_portal::model::{
+        CustomerPortalToken, IssuedCustomerPortalToken, PortalInvoice, SCOPE_INVOICES_READ,
+        SCOPE_WORKSHEETS_READ,
+    };
+    use crate::tenant::customer_portal::{
+        self, repository::MockCustomerPortalRepository, tests::MockCustomerPortalModule,
+    };
+    use crate::tenant::receivables::model::Receivable;
+    use crate::tenant::receivables::repository::MockReceivablesRepository;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, TimeDelta, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "portal-secret";
+
+    fn module(repo: MockCustomerPortalRepository, tenant_id: Uuid) -> MockCustomerPortalModule {
+        let repo = Arc::new(repo);
+        let mut customer_portal_module = MockCustomerPortalModule::new();
+        customer_portal_module
+            .expect_customer_portal_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        customer_portal_module
+    }
+
+    fn app(customer_portal_module: MockCustomerPortalModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(customer_portal::routes::routes(Arc::new(
+                customer_portal_module,
+            ))),
+        )
+    }
+
+    fn token(customer_id: Uuid, scopes: &[&str]) -> CustomerPortalToken {
+        CustomerPortalToken {
+            id: Uuid::new_v4(),
+            customer_id,
+            description: Some("Ügyfélkapu".to_string()),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            timezone: "Europe/Budapest".to_string(),
+            expires_at: Utc::now() + TimeDelta::days(30),
+            revoked_at: None,
+            last_used_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn invoice() -> PortalInvoice {
+        PortalInvoice {
+            id: Uuid::new_v4(),
+            document_number: "SZ-2026-00042".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(127000),
+            paid_amount: BigDecimal::from(0),
+            credited_amount: BigDecimal::from(0),
+            status: "open".to_string(),
+        }
+    }
+
+    fn receivable(customer_id: Uuid) -> Receivable {
+        Receivable {
+            id: Uuid::new_v4(),
+            customer_id,
+            document_number: "SZ-2026-00043".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(50000),
+            paid_amount: BigDecimal::from(0),
+            credited_amount: BigDecimal::from(0),
+            status: "open".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn portal_request(uri: String) -> Request<Body> {
+        Request::builder()
+            .header("Authorization", format!("Bearer {SECRET}"))
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_token_stores_hash_and_issuer_timezone() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let issued = token(customer_id, &[SCOPE_INVOICES_READ, SCOPE_WORKSHEETS_READ]);
+
+        let mut repo = MockCustomerPortalRepository::new();
+        repo.expect_insert_token()
+            .times(1)
+            .withf(move |input, token_hash, timezone, expires_at, _| {
+                input.customer_id == customer_id
+                    && input.scopes == vec!["invoices.read", "worksheets.read"]
+                    && token_hash.len() == 64
+                    && timezone == "Europe/Budapest"
+                    && *expires_at > Utc::now() + TimeDelta::days(89)
+            })
+            .returning({
+                let issued = issued.clone();
+                move |_, _, _, _, _| Ok(issued.clone())
+            });
+
+        let mut customer_portal_module = module(repo, tenant_id);
+        customer_portal_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = app(customer_portal_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("POST")
+                    .uri("/api/customer_portal/tokens/create")
+                    .body(Body::from(
+                        json!({
+                            "customer_id": customer_id,
+                            "description": null,
+                            "scopes": ["invoices.read", "worksheets.read", "invoices.read"],
+                            "valid_days": 90
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: IssuedCustomerPortalToken =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body.token, issued);
+        assert_eq!(body.secret.len(), 48);
+    }
+
+    #[tokio::test]
+    async fn test_portal_invoices_lists_invoices_of_token_customer() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let invoice = invoice();
+
+        let mut repo = MockCustomerPortalRepository::new();
+        repo.expect_authenticate()
+            .with(eq(hash_token(SECRET)))
+            .times(1)
+            .returning(move |_| Ok(Some(token(customer_id, &[SCOPE_INVOICES_READ]))));
+        repo.expect_get_invoices()
+            .with(eq(customer_id))
+            .times(1)
+            .returning({
+                let invoice = invoice.clone();
+                move |_| Ok(vec![invoice.clone()])
+            });
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(format!(
+                "/api/portal/invoices?tenant_id={tenant_id}"
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<PortalInvoice> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, vec![invoice]);
+    }
+
+    #[tokio::test]
+    async fn test_portal_rejects_invalid_token() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomerPortalRepository::new();
+        repo.expect_authenticate().times(1).returning(|_| Ok(None));
+        repo.expect_get_worksheets().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(format!(
+                "/api/portal/worksheets?tenant_id={tenant_id}"
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_portal_worksheets_require_worksheets_scope() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockCustomerPortalRepository::new();
+        repo.expect_authenticate()
+            .times(1)
+            .returning(move |_| Ok(Some(token(customer_id, &[SCOPE_INVOICES_READ]))));
+        repo.expect_get_worksheets().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(format!(
+                "/api/portal/worksheets?tenant_id={tenant_id}"
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            extract_json_response(response).await["error"]["code"],
+            "FORBIDDEN"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_portal_invoice_pdf_hides_other_customers_invoice() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let receivable = receivable(Uuid::new_v4());
+
+        let mut repo = MockCustomerPortalRepository::new();
+        repo.expect_authenticate()
+            .times(1)
+            .returning(move |_| Ok(Some(token(customer_id, &[SCOPE_INVOICES_READ]))));
+
+        let mut receivables_repo = MockReceivablesRepository::new();
+        receivables_repo
+            .expect_get_by_id()
+            .with(eq(receivable.id))
+            .times(1)
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+        let receivables_repo = Arc::new(receivables_repo);
+
+        let mut customer_portal_module = module(repo, tenant_id);
+        customer_portal_module
+            .expect_receivables_repo()
+            .with(eq(tenant_id))
+            .times(1)
+            .returning(move |_| Ok(receivables_repo.clone()));
+        customer_portal_module
+            .expect_document_settings_repo()
+            .never();
+
+        let response = app(customer_portal_module)
+            .oneshot(portal_request(format!(
+                "/api/portal/invoices/pdf?tenant_id={tenant_id}&uuid={}",
+                receivable.id
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
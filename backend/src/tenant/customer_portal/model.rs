@@ -0,0 +1,83 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const SCOPE_INVOICES_READ: &str = "invoices.read";
+pub const SCOPE_WORKSHEETS_READ: &str = "worksheets.read";
+
+pub const SCOPES: [&str; 2] = [SCOPE_INVOICES_READ, SCOPE_WORKSHEETS_READ];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerPortalToken {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub timezone: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CustomerPortalToken {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
+/// Returned only when the token is issued, the secret itself is not stored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IssuedCustomerPortalToken {
+    #[serde(flatten)]
+    pub token: CustomerPortalToken,
+    pub secret: String,
+}
+
+/// Invoice as shown to the customer, without the internal audit fields
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PortalInvoice {
+    pub id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    pub paid_amount: BigDecimal,
+    pub credited_amount: BigDecimal,
+    pub status: String,
+}
+
+/// Worksheet as shown to the customer, costs and project details stay internal
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PortalWorksheet {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub billing_status: String,
+    pub signed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
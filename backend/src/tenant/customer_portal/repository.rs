@@ -0,0 +1,200 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::customer_portal::dto::CreateCustomerPortalToken;
+use crate::tenant::customer_portal::model::{CustomerPortalToken, PortalInvoice, PortalWorksheet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CustomerPortalRepository: Send + Sync {
+    async fn get_tokens(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerPortalToken>>;
+    async fn insert_token(
+        &self,
+        input: &CreateCustomerPortalToken,
+        token_hash: &str,
+        timezone: &str,
+        expires_at: DateTime<Utc>,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerPortalToken>;
+    async fn revoke_token(&self, id: Uuid) -> RepositoryResult<CustomerPortalToken>;
+    async fn authenticate(&self, token_hash: &str)
+    -> RepositoryResult<Option<CustomerPortalToken>>;
+    async fn get_invoices(&self, customer_id: Uuid) -> RepositoryResult<Vec<PortalInvoice>>;
+    async fn get_worksheets(&self, customer_id: Uuid) -> RepositoryResult<Vec<PortalWorksheet>>;
+}
+
+const TOKEN_COLUMNS: &str = r#"
+    id,
+    customer_id,
+    description,
+    scopes,
+    timezone,
+    expires_at,
+    revoked_at,
+    last_used_at,
+    created_by_id,
+    created_at
+"#;
+
+#[async_trait]
+impl CustomerPortalRepository for PgPool {
+    async fn get_tokens(&self, customer_id: Uuid) -> RepositoryResult<Vec<CustomerPortalToken>> {
+        Ok(
+            sqlx::query_as::<_, CustomerPortalToken>(AssertSqlSafe(format!(
+                r#"
+            SELECT {TOKEN_COLUMNS}
+            FROM customer_portal_tokens
+            WHERE customer_id = $1
+            ORDER BY created_at DESC
+            "# // Security: constant
+            )))
+            .bind(customer_id)
+            .fetch_all(self)
+            .await?,
+        )
+    }
+
+    async fn insert_token(
+        &self,
+        input: &CreateCustomerPortalToken,
+        token_hash: &str,
+        timezone: &str,
+        expires_at: DateTime<Utc>,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerPortalToken> {
+        Ok(
+            sqlx::query_as::<_, CustomerPortalToken>(AssertSqlSafe(format!(
+                r#"
+            INSERT INTO customer_portal_tokens (customer_id, token_hash, description, scopes,
+                                                timezone, expires_at, created_by_id)
+            SELECT id, $2, $3, $4, $5, $6, $7
+            FROM customers
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(input.customer_id)
+            .bind(token_hash)
+            .bind(&input.description)
+            .bind(&input.scopes)
+            .bind(timezone)
+            .bind(expires_at)
+            .bind(sub)
+            .fetch_one(self)
+            .await?,
+        )
+    }
+
+    async fn revoke_token(&self, id: Uuid) -> RepositoryResult<CustomerPortalToken> {
+        Ok(
+            sqlx::query_as::<_, CustomerPortalToken>(AssertSqlSafe(format!(
+                r#"
+            UPDATE customer_portal_tokens
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(id)
+            .fetch_one(self)
+            .await?,
+        )
+    }
+
+    async fn authenticate(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<CustomerPortalToken>> {
+        Ok(
+            sqlx::query_as::<_, CustomerPortalToken>(AssertSqlSafe(format!(
+                r#"
+            UPDATE customer_portal_tokens
+            SET last_used_at = NOW()
+            WHERE token_hash = $1
+                AND revoked_at IS NULL
+                AND expires_at > NOW()
+                AND EXISTS (
+                    SELECT 1
+                    FROM customers
+                    WHERE customers.id = customer_portal_tokens.customer_id
+                        AND customers.status = 'active'
+                        AND customers.deleted_at IS NULL
+                )
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(token_hash)
+            .fetch_optional(self)
+            .await?,
+        )
+    }
+
+    async fn get_invoices(&self, customer_id: Uuid) -> RepositoryResult<Vec<PortalInvoice>> {
+        Ok(sqlx::query_as::<_, PortalInvoice>(
+            r#"
+            SELECT id,
+                   document_number,
+                   issue_date,
+                   due_date,
+                   currency_code,
+                   amount,
+                   paid_amount,
+                   credited_amount,
+                   status
+            FROM receivables
+            WHERE customer_id = $1
+                AND deleted_at IS NULL
+            ORDER BY issue_date DESC, document_number DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_worksheets(&self, customer_id: Uuid) -> RepositoryResult<Vec<PortalWorksheet>> {
+        Ok(sqlx::query_as::<_, PortalWorksheet>(
+            r#"
+            SELECT worksheets.id,
+                   worksheets.name,
+                   worksheets.description,
+                   worksheets.status,
+                   worksheets.billing_status,
+                   worksheet_signatures.signed_at,
+                   worksheets.created_at
+            FROM worksheets
+            LEFT JOIN worksheet_signatures ON worksheets.id = worksheet_signatures.worksheet_id
+            WHERE worksheets.customer_id = $1
+                AND worksheets.deleted_at IS NULL
+            ORDER BY worksheets.created_at DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+}
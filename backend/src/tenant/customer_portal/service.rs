@@ -0,0 +1,375 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::hash_token;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::common::utils::generate_string_csprng;
+use crate::tenant::customer_portal::CustomerPortalModuleInterface;
+use crate::tenant::customer_portal::dto::CreateCustomerPortalToken;
+use crate::tenant::customer_portal::model::{
+    CustomerPortalToken, IssuedCustomerPortalToken, PortalInvoice, PortalWorksheet,
+    SCOPE_INVOICES_READ, SCOPE_WORKSHEETS_READ, SCOPES,
+};
+use crate::tenant::customer_portal::repository::CustomerPortalRepository;
+use crate::tenant::receivables::service::{ReceivablesServiceError, render_invoice_pdf};
+use crate::tenant::worksheets::service::{WorksheetsServiceError, render_worksheet_document};
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const TOKEN_LENGTH: usize = 48;
+const MAX_VALID_DAYS: i64 = 365;
+
+#[derive(Debug, Error)]
+pub enum CustomerPortalServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Érvénytelen vagy lejárt hozzáférési kulcs!")]
+    InvalidToken,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("rng error")]
+    RngError,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("{0}")]
+    Receivables(#[from] ReceivablesServiceError),
+
+    #[error("{0}")]
+    Worksheets(#[from] WorksheetsServiceError),
+}
+
+impl From<ServiceError> for CustomerPortalServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CustomerPortalServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CustomerPortalServiceError> for AppError {
+    fn from(value: CustomerPortalServiceError) -> Self {
+        match value {
+            CustomerPortalServiceError::Unauthorized | CustomerPortalServiceError::InvalidToken => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNAUTHORIZED,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            CustomerPortalServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            CustomerPortalServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerPortalServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            // NOTE: document rendering is shared with the internal modules, so are their errors
+            CustomerPortalServiceError::Receivables(error) => error.into(),
+            CustomerPortalServiceError::Worksheets(error) => error.into(),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CustomerPortalServiceResult<T> = Result<T, CustomerPortalServiceError>;
+
+fn validate_token_request(
+    payload: &CreateCustomerPortalToken,
+) -> CustomerPortalServiceResult<CreateCustomerPortalToken> {
+    let description = payload
+        .description
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > 255)
+    {
+        return Err(CustomerPortalServiceError::UnprocessableEntry(
+            "A megjegyzés legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let mut seen = HashSet::new();
+    let scopes: Vec<String> = payload
+        .scopes
+        .iter()
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| seen.insert(scope.clone()))
+        .collect();
+    if scopes.is_empty() || scopes.iter().any(|scope| !SCOPES.contains(&scope.as_str())) {
+        return Err(CustomerPortalServiceError::UnprocessableEntry(
+            "Legalább egy érvényes jogosultságot meg kell adni!",
+        ));
+    }
+    if !(1..=MAX_VALID_DAYS).contains(&payload.valid_days) {
+        return Err(CustomerPortalServiceError::UnprocessableEntry(
+            "Az érvényesség 1 és 365 nap között lehet!",
+        ));
+    }
+    Ok(CreateCustomerPortalToken {
+        customer_id: payload.customer_id,
+        description,
+        scopes,
+        valid_days: payload.valid_days,
+    })
+}
+
+pub trait CustomerPortalService {
+    fn list_tokens(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = CustomerPortalServiceResult<Vec<CustomerPortalToken>>> + Send;
+    fn create_token(
+        &self,
+        payload: &CreateCustomerPortalToken,
+        tz: Tz,
+    ) -> impl Future<Output = CustomerPortalServiceResult<IssuedCustomerPortalToken>> + Send;
+    fn revoke_token(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = CustomerPortalServiceResult<CustomerPortalToken>> + Send;
+    fn portal_invoices(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> impl Future<Output = CustomerPortalServiceResult<Vec<PortalInvoice>>> + Send;
+    fn portal_invoice_pdf(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        id: Uuid,
+    ) -> impl Future<Output = CustomerPortalServiceResult<Vec<u8>>> + Send;
+    fn portal_worksheets(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> impl Future<Output = CustomerPortalServiceResult<Vec<PortalWorksheet>>> + Send;
+    fn portal_worksheet_pdf(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        id: Uuid,
+    ) -> impl Future<Output = CustomerPortalServiceResult<Vec<u8>>> + Send;
+    fn repo(&self) -> CustomerPortalServiceResult<Arc<dyn CustomerPortalRepository + Send + Sync>>;
+    fn authenticate(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        scope: &str,
+    ) -> impl Future<
+        Output = CustomerPortalServiceResult<(
+            Arc<dyn CustomerPortalRepository + Send + Sync>,
+            CustomerPortalToken,
+        )>,
+    > + Send;
+}
+
+impl<'a, T> CustomerPortalService for Service<'a, T>
+where
+    T: CustomerPortalModuleInterface,
+{
+    fn repo(&self) -> CustomerPortalServiceResult<Arc<dyn CustomerPortalRepository + Send + Sync>> {
+        Ok(self.module().customer_portal_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomerPortalServiceError::Unauthorized)?,
+        )?)
+    }
+
+    // NOTE: reached by the customer without a session, an unknown tenant is reported the same
+    // way as an unknown token so the portal does not reveal which tenants exist
+    async fn authenticate(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        scope: &str,
+    ) -> CustomerPortalServiceResult<(
+        Arc<dyn CustomerPortalRepository + Send + Sync>,
+        CustomerPortalToken,
+    )> {
+        let repo = self
+            .module()
+            .customer_portal_repo(tenant_id)
+            .map_err(|error| match error {
+                RepositoryError::TenantPoolNotFound => CustomerPortalServiceError::InvalidToken,
+                error => error.into(),
+            })?;
+        let token = repo
+            .authenticate(&hash_token(token))
+            .await?
+            .ok_or(CustomerPortalServiceError::InvalidToken)?;
+        if !token.has_scope(scope) {
+            return Err(CustomerPortalServiceError::Forbidden);
+        }
+        Ok((repo, token))
+    }
+
+    async fn list_tokens(
+        &self,
+        customer_id: Uuid,
+    ) -> CustomerPortalServiceResult<Vec<CustomerPortalToken>> {
+        Ok(self.repo()?.get_tokens(customer_id).await?)
+    }
+
+    async fn create_token(
+        &self,
+        payload: &CreateCustomerPortalToken,
+        tz: Tz,
+    ) -> CustomerPortalServiceResult<IssuedCustomerPortalToken> {
+        let input = validate_token_request(payload)?;
+        let secret = generate_string_csprng(TOKEN_LENGTH)
+            .map_err(|_| CustomerPortalServiceError::RngError)?;
+        let token = self
+            .repo()?
+            .insert_token(
+                &input,
+                &hash_token(&secret),
+                tz.name(),
+                Utc::now() + Duration::days(input.valid_days),
+                self.claims()?.sub(),
+            )
+            .await?;
+        Ok(IssuedCustomerPortalToken { token, secret })
+    }
+
+    async fn revoke_token(&self, id: Uuid) -> CustomerPortalServiceResult<CustomerPortalToken> {
+        Ok(self.repo()?.revoke_token(id).await?)
+    }
+
+    async fn portal_invoices(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> CustomerPortalServiceResult<Vec<PortalInvoice>> {
+        let (repo, token) = self
+            .authenticate(tenant_id, token, SCOPE_INVOICES_READ)
+            .await?;
+        Ok(repo.get_invoices(token.customer_id).await?)
+    }
+
+    // NOTE: documents of other customers are reported as missing, not as forbidden
+    async fn portal_invoice_pdf(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        id: Uuid,
+    ) -> CustomerPortalServiceResult<Vec<u8>> {
+        let (_, token) = self
+            .authenticate(tenant_id, token, SCOPE_INVOICES_READ)
+            .await?;
+        let receivables_repo = self.module().receivables_repo(tenant_id)?;
+        let receivable = receivables_repo.get_by_id(id).await?;
+        if receivable.customer_id != token.customer_id {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound).into());
+        }
+        let document_settings_repo = self.module().document_settings_repo(tenant_id)?;
+        let recipient = document_settings_repo
+            .get_recipient(receivable.customer_id)
+            .await?;
+        let (_, pdf) = render_invoice_pdf(
+            &*receivables_repo,
+            &*document_settings_repo,
+            &*self.module().file_storage(),
+            receivable,
+            recipient,
+        )
+        .await?;
+        Ok(pdf)
+    }
+
+    async fn portal_worksheets(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> CustomerPortalServiceResult<Vec<PortalWorksheet>> {
+        let (repo, token) = self
+            .authenticate(tenant_id, token, SCOPE_WORKSHEETS_READ)
+            .await?;
+        Ok(repo.get_worksheets(token.customer_id).await?)
+    }
+
+    async fn portal_worksheet_pdf(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        id: Uuid,
+    ) -> CustomerPortalServiceResult<Vec<u8>> {
+        let (_, token) = self
+            .authenticate(tenant_id, token, SCOPE_WORKSHEETS_READ)
+            .await?;
+        let worksheets_repo = self.module().worksheets_repo(tenant_id)?;
+        let worksheet = worksheets_repo.get_resolved_by_id(id).await?;
+        if worksheet.customer_id != token.customer_id {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound).into());
+        }
+        Ok(render_worksheet_document(
+            &*worksheets_repo,
+            &*self.module().document_settings_repo(tenant_id)?,
+            &*self.module().file_storage(),
+            worksheet,
+            token.timezone.parse().unwrap_or(Tz::UTC),
+        )
+        .await?)
+    }
+}
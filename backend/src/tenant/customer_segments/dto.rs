@@ -0,0 +1,56 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Criteria of a segment, every empty criterion matches all customers. Revenue is the invoiced
+/// amount in `revenue_currency_code`, last activity is the date of the latest timeline event.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CustomerSegmentInput {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>,
+    pub min_revenue: Option<BigDecimal>,
+    pub max_revenue: Option<BigDecimal>,
+    pub revenue_currency_code: Option<String>,
+    pub last_activity_after: Option<NaiveDate>,
+    pub last_activity_before: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UpdateCustomerSegment {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub segment: CustomerSegmentInput,
+}
+
+/// Plain text email sent to every member of a segment, `{{customer_name}}` is replaced with
+/// the name of the recipient
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CustomerSegmentEmail {
+    pub segment_id: Uuid,
+    pub subject: String,
+    pub body: String,
+}
@@ -0,0 +1,404 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::customer_segments::CustomerSegmentsModuleInterface;
+use crate::tenant::customer_segments::dto::{
+    CustomerSegmentEmail, CustomerSegmentInput, UpdateCustomerSegment,
+};
+use crate::tenant::customer_segments::service::CustomerSegmentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    let result = map_handler_err(service.list().await, customer_segments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerSegmentInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        customer_segments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateCustomerSegment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        customer_segments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        customer_segments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A szegmens törlése sikeresen megtörtént",
+            ))
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn members<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    let result = map_handler_err(
+        service.members(payload.uuid).await,
+        customer_segments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn send_email<M: CustomerSegmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customer_segments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerSegmentEmail>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customer_segments_module.clone());
+    let result = map_handler_err(
+        service.send_email(&payload).await,
+        customer_segments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customer_segments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::customer_segments::model::{
+        CustomerSegment, CustomerSegmentEmailReport, CustomerSegmentMember,
+    };
+    use crate::tenant::customer_segments::tests::MockCustomerSegmentsModule;
+    use crate::tenant::customer_segments::{self, repository::MockCustomerSegmentsRepository};
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockCustomerSegmentsRepository,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+    ) -> MockCustomerSegmentsModule {
+        let repo = Arc::new(repo);
+        let mut customer_segments_module = MockCustomerSegmentsModule::new();
+        customer_segments_module
+            .expect_customer_segments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        customer_segments_module
+            .expect_config()
+            .times(config_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        customer_segments_module
+    }
+
+    fn app(customer_segments_module: MockCustomerSegmentsModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(customer_segments::routes::routes(Arc::new(
+                customer_segments_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn segment(name: &str) -> CustomerSegment {
+        CustomerSegment {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            statuses: vec!["active".to_string()],
+            tag_ids: vec![],
+            min_revenue: Some(BigDecimal::from(1_000_000)),
+            max_revenue: None,
+            revenue_currency_code: Some("HUF".to_string()),
+            last_activity_after: None,
+            last_activity_before: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn member(email: &str) -> CustomerSegmentMember {
+        CustomerSegmentMember {
+            id: Uuid::new_v4(),
+            name: "Minta Kft.".to_string(),
+            status: "active".to_string(),
+            email: email.to_string(),
+            recipient_name: "Kiss Anna".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_normalizes_criteria() {
+        let active_tenant_id = Uuid::new_v4();
+        let created = segment("Kiemelt partnerek");
+
+        let mut repo = MockCustomerSegmentsRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| {
+                input.name == "Kiemelt partnerek"
+                    && input.statuses == vec!["active".to_string()]
+                    && input.revenue_currency_code.as_deref() == Some("HUF")
+                    && input.description.is_none()
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _| Ok(created.clone())
+            });
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/customer_segments/create",
+                active_tenant_id,
+                json!({
+                    "name": " Kiemelt partnerek ",
+                    "description": " ",
+                    "statuses": ["Active", "active"],
+                    "min_revenue": "1000000",
+                    "revenue_currency_code": "huf",
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let result: CustomerSegment =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_revenue_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockCustomerSegmentsRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/customer_segments/create",
+                active_tenant_id,
+                json!({"name": "Nagy vevők", "min_revenue": "500000"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_members_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let found = segment("Kiemelt partnerek");
+        let segment_id = found.id;
+        let members = vec![member("anna@minta.hu")];
+
+        let mut repo = MockCustomerSegmentsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(segment_id))
+            .times(1)
+            .returning(move |_| Ok(found.clone()));
+        repo.expect_get_members()
+            .with(eq(segment_id))
+            .times(1)
+            .returning({
+                let members = members.clone();
+                move |_| Ok(members.clone())
+            });
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "GET",
+                &format!("/api/customer_segments/members?uuid={segment_id}"),
+                active_tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: Vec<CustomerSegmentMember> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, members);
+    }
+
+    #[tokio::test]
+    async fn test_send_email_reports_failed_recipients() {
+        let active_tenant_id = Uuid::new_v4();
+        let found = segment("Kiemelt partnerek");
+        let segment_id = found.id;
+        let invalid = member("nem-email");
+
+        let mut repo = MockCustomerSegmentsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(segment_id))
+            .times(1)
+            .returning(move |_| Ok(found.clone()));
+        repo.expect_get_members().times(1).returning({
+            let invalid = invalid.clone();
+            move |_| Ok(vec![member("anna@minta.hu"), invalid.clone()])
+        });
+        let mut customer_segments_module = module(repo, active_tenant_id, 3);
+        customer_segments_module
+            .expect_send()
+            .times(1)
+            .withf(|message| {
+                let raw = String::from_utf8_lossy(&message.formatted()).to_string();
+                raw.contains("anna@minta.hu") && !raw.contains("nem-email")
+            })
+            .returning(|_| Ok(None));
+
+        let response = app(customer_segments_module)
+            .oneshot(request(
+                "POST",
+                "/api/customer_segments/send_email",
+                active_tenant_id,
+                json!({
+                    "segment_id": segment_id,
+                    "subject": "Ünnepi nyitvatartás",
+                    "body": "Kedves {{customer_name}}!\n\nDecember 24-én zárva tartunk.",
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: CustomerSegmentEmailReport =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result.recipient_count, 2);
+        assert_eq!(result.sent, 1);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].customer_id, invalid.id);
+    }
+}
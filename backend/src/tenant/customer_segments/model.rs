@@ -0,0 +1,66 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerSegment {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub statuses: Vec<String>,
+    pub tag_ids: Vec<Uuid>,
+    pub min_revenue: Option<BigDecimal>,
+    pub max_revenue: Option<BigDecimal>,
+    pub revenue_currency_code: Option<String>,
+    pub last_activity_after: Option<NaiveDate>,
+    pub last_activity_before: Option<NaiveDate>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A customer matching the segment criteria, `email` is the address of the primary contact
+/// when there is one
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerSegmentMember {
+    pub id: Uuid,
+    pub name: String,
+    pub status: String,
+    pub email: String,
+    pub recipient_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerSegmentEmailFailure {
+    pub customer_id: Uuid,
+    pub email: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerSegmentEmailReport {
+    pub recipient_count: usize,
+    pub sent: usize,
+    pub failed: Vec<CustomerSegmentEmailFailure>,
+}
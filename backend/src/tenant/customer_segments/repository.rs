@@ -0,0 +1,170 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::customer_segments::dto::CustomerSegmentInput;
+use crate::tenant::customer_segments::model::{CustomerSegment, CustomerSegmentMember};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CustomerSegmentsRepository: Send + Sync {
+    async fn get_all(&self) -> RepositoryResult<Vec<CustomerSegment>>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CustomerSegment>;
+    async fn insert(
+        &self,
+        input: &CustomerSegmentInput,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerSegment>;
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &CustomerSegmentInput,
+    ) -> RepositoryResult<CustomerSegment>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    /// Customers matching the criteria of the segment at the time of the query
+    async fn get_members(&self, id: Uuid) -> RepositoryResult<Vec<CustomerSegmentMember>>;
+}
+
+#[async_trait]
+impl CustomerSegmentsRepository for PgPool {
+    async fn get_all(&self) -> RepositoryResult<Vec<CustomerSegment>> {
+        Ok(sqlx::query_as::<_, CustomerSegment>(
+            "SELECT * FROM customer_segments WHERE deleted_at IS NULL ORDER BY name",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<CustomerSegment> {
+        Ok(sqlx::query_as::<_, CustomerSegment>(
+            "SELECT * FROM customer_segments WHERE deleted_at IS NULL AND id = $1",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        input: &CustomerSegmentInput,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerSegment> {
+        Ok(sqlx::query_as::<_, CustomerSegment>(
+            r#"
+            INSERT INTO customer_segments (
+                name, description, statuses, tag_ids, min_revenue, max_revenue,
+                revenue_currency_code, last_activity_after, last_activity_before, created_by_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.statuses)
+        .bind(&input.tag_ids)
+        .bind(&input.min_revenue)
+        .bind(&input.max_revenue)
+        .bind(&input.revenue_currency_code)
+        .bind(input.last_activity_after)
+        .bind(input.last_activity_before)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &CustomerSegmentInput,
+    ) -> RepositoryResult<CustomerSegment> {
+        Ok(sqlx::query_as::<_, CustomerSegment>(
+            r#"
+            UPDATE customer_segments
+            SET name = $2,
+                description = $3,
+                statuses = $4,
+                tag_ids = $5,
+                min_revenue = $6,
+                max_revenue = $7,
+                revenue_currency_code = $8,
+                last_activity_after = $9,
+                last_activity_before = $10
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.statuses)
+        .bind(&input.tag_ids)
+        .bind(&input.min_revenue)
+        .bind(&input.max_revenue)
+        .bind(&input.revenue_currency_code)
+        .bind(input.last_activity_after)
+        .bind(input.last_activity_before)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE customer_segments
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_members(&self, id: Uuid) -> RepositoryResult<Vec<CustomerSegmentMember>> {
+        Ok(sqlx::query_as::<_, CustomerSegmentMember>(
+            r#"
+            SELECT customers.id,
+                   customers.name,
+                   customers.status,
+                   COALESCE(customer_contacts.email, customers.email) AS email,
+                   COALESCE(customer_contacts.name, customers.contact_name, customers.name)
+                       AS recipient_name
+            FROM customers
+            LEFT JOIN customer_contacts ON customer_contacts.customer_id = customers.id
+                AND customer_contacts.is_primary
+                AND customer_contacts.deleted_at IS NULL
+            WHERE customers.deleted_at IS NULL
+                AND customer_in_segment(customers.id, $1)
+            ORDER BY customers.name
+            "#,
+        )
+        .bind(id)
+        .fetch_all(self)
+        .await?)
+    }
+}
@@ -0,0 +1,372 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::email_template::render_email;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::common::value_object::ValueObjectRequired;
+use crate::tenant::currencies::types::CurrencyCode;
+use crate::tenant::customer_segments::CustomerSegmentsModuleInterface;
+use crate::tenant::customer_segments::dto::{
+    CustomerSegmentEmail, CustomerSegmentInput, UpdateCustomerSegment,
+};
+use crate::tenant::customer_segments::model::{
+    CustomerSegment, CustomerSegmentEmailFailure, CustomerSegmentEmailReport, CustomerSegmentMember,
+};
+use crate::tenant::customer_segments::repository::CustomerSegmentsRepository;
+use crate::tenant::customers::types::customer::CustomerStatus;
+use crate::tenant::dunning::service::body_html;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use lettre::Message;
+use lettre::message::Mailbox;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_SUBJECT_LENGTH: usize = 255;
+const MAX_BODY_LENGTH: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum CustomerSegmentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("Ilyen nevű szegmens már létezik!")]
+    SegmentExists,
+}
+
+impl From<ServiceError> for CustomerSegmentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => CustomerSegmentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<CustomerSegmentsServiceError> for AppError {
+    fn from(value: CustomerSegmentsServiceError) -> Self {
+        match value {
+            CustomerSegmentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerSegmentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerSegmentsServiceError::SegmentExists => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomerSegmentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type CustomerSegmentsServiceResult<T> = Result<T, CustomerSegmentsServiceError>;
+
+fn map_write_err(e: RepositoryError) -> CustomerSegmentsServiceError {
+    if e.is_unique_violation() {
+        CustomerSegmentsServiceError::SegmentExists
+    } else if e.is_foreign_key_violation() {
+        CustomerSegmentsServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+    } else {
+        e.into()
+    }
+}
+
+/// Trims the segment and checks that every criterion can match, the returned input is stored as is
+fn validate_segment(
+    payload: &CustomerSegmentInput,
+) -> CustomerSegmentsServiceResult<CustomerSegmentInput> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+            "A szegmens neve nem lehet üres és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let mut statuses = Vec::with_capacity(payload.statuses.len());
+    for status in &payload.statuses {
+        let status = status.trim().to_lowercase();
+        status
+            .parse::<ValueObjectRequired<CustomerStatus>>()
+            .map_err(|_| {
+                CustomerSegmentsServiceError::UnprocessableEntry(CustomerStatus::VALIDATION_ERROR)
+            })?;
+        if !statuses.contains(&status) {
+            statuses.push(status);
+        }
+    }
+    let mut tag_ids = payload.tag_ids.clone();
+    tag_ids.sort();
+    tag_ids.dedup();
+
+    if [&payload.min_revenue, &payload.max_revenue]
+        .into_iter()
+        .flatten()
+        .any(|revenue| *revenue < BigDecimal::zero())
+    {
+        return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+            "A forgalom nem lehet negatív!",
+        ));
+    }
+    if let (Some(min), Some(max)) = (&payload.min_revenue, &payload.max_revenue)
+        && min > max
+    {
+        return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+            "A minimális forgalom nem lehet nagyobb a maximálisnál!",
+        ));
+    }
+    let revenue_filtered = payload.min_revenue.is_some() || payload.max_revenue.is_some();
+    let revenue_currency_code = match payload
+        .revenue_currency_code
+        .as_deref()
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+    {
+        Some(_) if !revenue_filtered => None,
+        Some(code) => {
+            let code = code.to_uppercase();
+            code.parse::<ValueObjectRequired<CurrencyCode>>()
+                .map_err(|_| {
+                    CustomerSegmentsServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+                })?;
+            Some(code)
+        }
+        None if revenue_filtered => {
+            return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+                "Forgalom szerinti szűrés esetén a pénznem megadása kötelező!",
+            ));
+        }
+        None => None,
+    };
+    if let (Some(after), Some(before)) = (payload.last_activity_after, payload.last_activity_before)
+        && after > before
+    {
+        return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+            "Az utolsó aktivitás kezdete nem lehet későbbi a végénél!",
+        ));
+    }
+
+    Ok(CustomerSegmentInput {
+        name: name.to_string(),
+        description: payload
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map(str::to_string),
+        statuses,
+        tag_ids,
+        min_revenue: payload.min_revenue.clone(),
+        max_revenue: payload.max_revenue.clone(),
+        revenue_currency_code,
+        last_activity_after: payload.last_activity_after,
+        last_activity_before: payload.last_activity_before,
+    })
+}
+
+pub trait CustomerSegmentsService {
+    fn list(
+        &self,
+    ) -> impl Future<Output = CustomerSegmentsServiceResult<Vec<CustomerSegment>>> + Send;
+    fn create(
+        &self,
+        payload: &CustomerSegmentInput,
+    ) -> impl Future<Output = CustomerSegmentsServiceResult<CustomerSegment>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateCustomerSegment,
+    ) -> impl Future<Output = CustomerSegmentsServiceResult<CustomerSegment>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = CustomerSegmentsServiceResult<()>> + Send;
+    fn members(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = CustomerSegmentsServiceResult<Vec<CustomerSegmentMember>>> + Send;
+    fn send_email(
+        &self,
+        payload: &CustomerSegmentEmail,
+    ) -> impl Future<Output = CustomerSegmentsServiceResult<CustomerSegmentEmailReport>> + Send;
+    fn repo(
+        &self,
+    ) -> CustomerSegmentsServiceResult<Arc<dyn CustomerSegmentsRepository + Send + Sync>>;
+}
+
+impl<'a, T> CustomerSegmentsService for Service<'a, T>
+where
+    T: CustomerSegmentsModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> CustomerSegmentsServiceResult<Arc<dyn CustomerSegmentsRepository + Send + Sync>> {
+        Ok(self.module().customer_segments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomerSegmentsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn list(&self) -> CustomerSegmentsServiceResult<Vec<CustomerSegment>> {
+        Ok(self.repo()?.get_all().await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CustomerSegmentInput,
+    ) -> CustomerSegmentsServiceResult<CustomerSegment> {
+        let segment = validate_segment(payload)?;
+        self.repo()?
+            .insert(&segment, self.claims()?.sub())
+            .await
+            .map_err(map_write_err)
+    }
+
+    async fn update(
+        &self,
+        payload: &UpdateCustomerSegment,
+    ) -> CustomerSegmentsServiceResult<CustomerSegment> {
+        let segment = validate_segment(&payload.segment)?;
+        self.repo()?
+            .update(payload.id, &segment)
+            .await
+            .map_err(map_write_err)
+    }
+
+    async fn delete(&self, id: Uuid) -> CustomerSegmentsServiceResult<()> {
+        Ok(self.repo()?.delete_by_id(id).await?)
+    }
+
+    async fn members(&self, id: Uuid) -> CustomerSegmentsServiceResult<Vec<CustomerSegmentMember>> {
+        let repo = self.repo()?;
+        repo.get_by_id(id).await?;
+        Ok(repo.get_members(id).await?)
+    }
+
+    // NOTE: a failed delivery does not stop the others, it is listed in the report instead
+    async fn send_email(
+        &self,
+        payload: &CustomerSegmentEmail,
+    ) -> CustomerSegmentsServiceResult<CustomerSegmentEmailReport> {
+        let subject = payload.subject.trim();
+        let body = payload.body.trim();
+        if subject.is_empty() || subject.chars().count() > MAX_SUBJECT_LENGTH {
+            return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+                "A tárgy nem lehet üres és legfeljebb 255 karakter lehet!",
+            ));
+        }
+        if body.is_empty() || body.chars().count() > MAX_BODY_LENGTH {
+            return Err(CustomerSegmentsServiceError::UnprocessableEntry(
+                "Az üzenet nem lehet üres és legfeljebb 10000 karakter lehet!",
+            ));
+        }
+        let html = body_html(body);
+        render_email(
+            subject,
+            &html,
+            body,
+            &json!({"customer_name": "Minta Kft."}),
+        )
+        .map_err(|_| CustomerSegmentsServiceError::UnprocessableEntry("Hibás sablon!"))?;
+
+        let repo = self.repo()?;
+        repo.get_by_id(payload.segment_id).await?;
+        let members = repo.get_members(payload.segment_id).await?;
+        let mut report = CustomerSegmentEmailReport {
+            recipient_count: members.len(),
+            sent: 0,
+            failed: vec![],
+        };
+        for member in members {
+            let result: anyhow::Result<()> = async {
+                let mail_config = self.module().config().mail();
+                let rendered = render_email(
+                    subject,
+                    &html,
+                    body,
+                    &json!({"customer_name": member.recipient_name}),
+                )?;
+                let message = rendered.into_message_with_attachments(
+                    Message::builder()
+                        .from(Mailbox::new(
+                            Some(mail_config.default_from_name().to_owned()),
+                            mail_config.default_from().parse()?,
+                        ))
+                        .to(Mailbox::new(
+                            Some(member.recipient_name.clone()),
+                            member.email.parse()?,
+                        )),
+                    vec![],
+                )?;
+                self.module().send(message).await?;
+                Ok(())
+            }
+            .await;
+            match result {
+                Ok(()) => report.sent += 1,
+                Err(e) => {
+                    warn!(
+                        "Could not send segment email to customer {}: {}",
+                        member.id, e
+                    );
+                    report.failed.push(CustomerSegmentEmailFailure {
+                        customer_id: member.id,
+                        email: member.email,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,238 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::common::utils::{csv_reader, non_empty};
+use crate::tenant::customers::dto::user_input::{
+    CustomerUserInput, CustomerUserInputError, CustomerUserInputHelper,
+};
+use csv::StringRecord;
+use serde::Deserialize;
+
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+fn default_dry_run() -> bool {
+    true
+}
+
+/// The `*_column` parameters map the CSV headers to customer fields, a field without mapping
+/// is read from the column with its own name, so exported files can be imported as they are.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct CustomerImportQuery {
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+    pub name_column: Option<String>,
+    pub contact_name_column: Option<String>,
+    pub email_column: Option<String>,
+    pub phone_number_column: Option<String>,
+    pub tax_number_column: Option<String>,
+    pub status_column: Option<String>,
+    pub customer_type_column: Option<String>,
+}
+
+/// Header positions of the customer fields, `None` for optional columns missing from the file
+struct ColumnMap {
+    name: usize,
+    contact_name: Option<usize>,
+    email: usize,
+    phone_number: Option<usize>,
+    tax_number: Option<usize>,
+    status: Option<usize>,
+    customer_type: usize,
+}
+
+impl ColumnMap {
+    fn new(headers: &StringRecord, query: &CustomerImportQuery) -> Result<Self, &'static str> {
+        let find = |mapped: &Option<String>, default: &str| {
+            let column = mapped.as_deref().map(str::trim).unwrap_or(default);
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(column))
+        };
+        let (Some(name), Some(email), Some(customer_type)) = (
+            find(&query.name_column, "name"),
+            find(&query.email_column, "email"),
+            find(&query.customer_type_column, "customer_type"),
+        ) else {
+            return Err("A fájlnak tartalmaznia kell a név, e-mail cím és vevő típus oszlopokat!");
+        };
+        Ok(Self {
+            name,
+            contact_name: find(&query.contact_name_column, "contact_name"),
+            email,
+            phone_number: find(&query.phone_number_column, "phone_number"),
+            tax_number: find(&query.tax_number_column, "tax_number"),
+            status: find(&query.status_column, "status"),
+            customer_type,
+        })
+    }
+}
+
+/// One CSV row after parsing. Customers are matched by e-mail address, so every row has to
+/// carry one. Rows with a non-empty `errors` list are reported back, but never touch the
+/// database, `input` is only set for valid rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomerImportRow {
+    pub line: u64,
+    pub name: String,
+    pub email: String,
+    pub input: Option<CustomerUserInput>,
+    pub errors: Vec<String>,
+}
+
+impl CustomerImportRow {
+    /// Parses an uploaded CSV file, see [`csv_reader`] for the accepted formats
+    pub fn parse_csv(data: &[u8], query: &CustomerImportQuery) -> Result<Vec<Self>, &'static str> {
+        let (mut reader, _) = csv_reader(data)?;
+
+        let headers = reader
+            .headers()
+            .map_err(|_| "A fájl fejléce nem olvasható!")?
+            .clone();
+        let columns = ColumnMap::new(&headers, query)?;
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            if rows.len() == MAX_IMPORT_ROWS {
+                return Err("Egyszerre legfeljebb 5000 sor importálható!");
+            }
+            match record {
+                Ok(record) => rows.push(Self::from_record(&record, &columns)),
+                Err(e) => rows.push(Self {
+                    line: e.position().map_or(0, |p| p.line()),
+                    name: String::new(),
+                    email: String::new(),
+                    input: None,
+                    errors: vec!["A sor nem olvasható!".to_owned()],
+                }),
+            }
+        }
+        if rows.is_empty() {
+            return Err("A fájl nem tartalmaz egy sort sem!");
+        }
+        Ok(rows)
+    }
+
+    fn from_record(record: &StringRecord, columns: &ColumnMap) -> Self {
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .unwrap_or_default()
+                .trim()
+                .to_owned()
+        };
+        let helper = CustomerUserInputHelper {
+            id: None,
+            name: field(Some(columns.name)),
+            contact_name: field(columns.contact_name),
+            email: field(Some(columns.email)),
+            phone_number: field(columns.phone_number),
+            tax_number: field(columns.tax_number),
+            status: non_empty(&field(columns.status)).unwrap_or_else(|| "active".to_owned()),
+            customer_type: field(Some(columns.customer_type)).to_lowercase(),
+        };
+        let (name, email) = (helper.name.clone(), helper.email.clone());
+        let (input, errors) = match CustomerUserInput::try_from(helper) {
+            Ok(input) => (Some(input), Vec::new()),
+            Err(error) => (None, Self::error_messages(error)),
+        };
+
+        Self {
+            line: record.position().map_or(0, |p| p.line()),
+            name,
+            email,
+            input,
+            errors,
+        }
+    }
+
+    fn error_messages(error: CustomerUserInputError) -> Vec<String> {
+        [
+            error.name,
+            error.contact_name,
+            error.email,
+            error.phone_number,
+            error.tax_number,
+            error.status,
+            error.customer_type,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_default_columns() {
+        let rows = CustomerImportRow::parse_csv(
+            b"name,contact_name,email,phone_number,tax_number,status,customer_type\n\
+              Minta Kft.,Kiss Anna,info@minta.hu,,12345678-2-41,,legal\n\
+              ,,nem-email,,123,unknown,natural\n",
+            &CustomerImportQuery::default(),
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert!(rows[0].errors.is_empty());
+        let input = rows[0].input.as_ref().unwrap();
+        assert_eq!(input.status.as_str().unwrap(), "active");
+        assert_eq!(input.tax_number.as_str(), Some("12345678-2-41"));
+        assert_eq!(rows[1].line, 3);
+        assert_eq!(rows[1].input, None);
+        assert_eq!(rows[1].errors.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_csv_mapped_columns() {
+        let query = CustomerImportQuery {
+            name_column: Some("Név".to_owned()),
+            email_column: Some("E-mail".to_owned()),
+            customer_type_column: Some("Típus".to_owned()),
+            ..Default::default()
+        };
+        let rows = CustomerImportRow::parse_csv(
+            "Név;E-mail;Típus\nKovács Béla;bela@example.com;NATURAL\n".as_bytes(),
+            &query,
+        )
+        .unwrap();
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(rows[0].email, "bela@example.com");
+        assert_eq!(
+            rows[0]
+                .input
+                .as_ref()
+                .unwrap()
+                .customer_type
+                .as_str()
+                .unwrap(),
+            "natural"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_missing_columns() {
+        let query = CustomerImportQuery::default();
+        assert!(
+            CustomerImportRow::parse_csv(b"name,email\nMinta,info@minta.hu\n", &query).is_err()
+        );
+        assert!(CustomerImportRow::parse_csv(b"name,email,customer_type\n", &query).is_err());
+    }
+}
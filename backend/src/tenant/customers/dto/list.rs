@@ -0,0 +1,39 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// List query of customers, `segment_id` keeps only the members of a saved customer segment,
+/// `stage` only the customers in the given lifecycle stage
+#[derive(Deserialize, Debug, Clone)]
+pub struct CustomerListQuery {
+    q: Option<String>,
+    pub segment_id: Option<Uuid>,
+    pub stage: Option<String>,
+}
+
+impl CustomerListQuery {
+    pub fn q(&self) -> &str {
+        match &self.q {
+            Some(v) => v,
+            None => "",
+        }
+    }
+}
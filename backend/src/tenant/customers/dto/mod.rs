@@ -17,5 +17,12 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod credit_limit;
+pub mod duplicate;
+pub mod import;
+pub mod list;
 pub mod print;
+pub mod sales_rep;
+pub mod stage;
+pub mod timeline;
 pub mod user_input;
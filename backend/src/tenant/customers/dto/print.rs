@@ -109,8 +109,10 @@ mod tests {
             contact_name: None,
             email: "teszt.elek@example.com".to_string(),
             phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_by: "Kovács Dávid".to_string(),
             created_at: input_date,
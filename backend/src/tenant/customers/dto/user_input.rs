@@ -34,6 +34,8 @@ pub struct CustomerUserInputHelper {
     pub contact_name: String,
     pub email: String,
     pub phone_number: String,
+    #[serde(default)]
+    pub tax_number: String,
     pub status: String,
     pub customer_type: String,
 }
@@ -45,6 +47,7 @@ pub struct CustomerUserInputError {
     pub contact_name: Option<String>,
     pub email: Option<String>,
     pub phone_number: Option<String>,
+    pub tax_number: Option<String>,
     pub status: Option<String>,
     pub customer_type: Option<String>,
 }
@@ -56,6 +59,7 @@ impl CustomerUserInputError {
             && self.contact_name.is_none()
             && self.email.is_none()
             && self.phone_number.is_none()
+            && self.tax_number.is_none()
             && self.status.is_none()
             && self.customer_type.is_none()
     }
@@ -98,6 +102,7 @@ pub struct CustomerUserInput {
     pub contact_name: Option<ValueObjectRequired<CustomerContactName>>,
     pub email: ValueObjectRequired<Email>,
     pub phone_number: ValueObjectOptional<CustomerPhoneNumber>,
+    pub tax_number: ValueObjectOptional<CustomerTaxNumber>,
     pub status: ValueObjectRequired<CustomerStatus>,
     pub customer_type: ValueObjectRequired<CustomerType>,
 }
@@ -135,6 +140,13 @@ impl TryFrom<CustomerUserInputHelper> for CustomerUserInput {
             .inspect_err(|e| {
                 error.phone_number = Some(e.to_string());
             });
+
+        let tax_number = value
+            .tax_number
+            .parse::<ValueObjectOptional<CustomerTaxNumber>>()
+            .inspect_err(|e| {
+                error.tax_number = Some(e.to_string());
+            });
         let status = value
             .status
             .parse::<ValueObjectRequired<CustomerStatus>>()
@@ -171,6 +183,7 @@ impl TryFrom<CustomerUserInputHelper> for CustomerUserInput {
                 contact_name: contact_name?,
                 email: email?,
                 phone_number: phone_number?,
+                tax_number: tax_number?,
                 status: status?,
                 customer_type: customer_type?,
             })
@@ -192,6 +205,7 @@ mod tests {
             contact_name: String::from(""),
             email: String::from("teszt.elek@example.com"),
             phone_number: String::from("+36301234567"),
+            tax_number: String::from(""),
             status: String::from("active"),
             customer_type: String::from("natural"),
         })
@@ -213,6 +227,7 @@ mod tests {
             contact_name: String::from("Teszt Elek"),
             email: String::from("teszt.elek@example.com"),
             phone_number: String::from("+36301234567"),
+            tax_number: String::from(""),
             status: String::from("active"),
             customer_type: String::from("legal"),
         })
@@ -233,6 +248,7 @@ mod tests {
             contact_name: String::from(""),
             email: String::from("teszt.elekexample.com"),
             phone_number: String::from("+36@301234567"),
+            tax_number: String::from(""),
             status: String::from("activee"),
             customer_type: String::from("natural"),
         })
@@ -257,6 +273,7 @@ mod tests {
             contact_name: String::from(""),
             email: String::from(""),
             phone_number: String::from("+3630a234567"),
+            tax_number: String::from(""),
             status: String::from(""),
             customer_type: String::from("legal"),
         })
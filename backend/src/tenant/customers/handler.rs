@@ -18,16 +18,28 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
-use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::query_parser::ResourceQuery;
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::customers::CustomersModuleInterface;
+use crate::tenant::customers::dto::credit_limit::{
+    CreditLimitSettingsInput, CustomerCreditLimitInput,
+};
+use crate::tenant::customers::dto::duplicate::{CustomerCreateQuery, CustomerMergeInput};
+use crate::tenant::customers::dto::import::CustomerImportQuery;
+use crate::tenant::customers::dto::list::CustomerListQuery;
 use crate::tenant::customers::dto::print::CustomerResolvedPrint;
+use crate::tenant::customers::dto::sales_rep::CustomerSalesRep;
+use crate::tenant::customers::dto::stage::CustomerStageInput;
+use crate::tenant::customers::dto::timeline::CustomerTimelineQuery;
 use crate::tenant::customers::dto::user_input::{CustomerUserInput, CustomerUserInputHelper};
 use crate::tenant::customers::service::CustomerService;
 use crate::tenant::customers::types::customer::{CustomerFilterBy, CustomerOrderBy};
+use crate::tenant::tags::dto::TagFilter;
+use axum::Json;
+use axum::body::Bytes;
 use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
@@ -77,9 +89,17 @@ pub async fn get<M: CustomersModuleInterface>(
 pub async fn create<M: CustomersModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(customers_module): State<Arc<M>>,
+    Query(create_query): Query<CustomerCreateQuery>,
     UserInput(user_input, _): UserInput<CustomerUserInput, CustomerUserInputHelper>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), customers_module.clone());
+    if create_query.check_duplicates {
+        map_handler_err(
+            service.check_duplicates(&user_input).await,
+            customers_module.clone(),
+        )
+        .await?;
+    }
     let result =
         map_handler_err(service.insert(&user_input).await, customers_module.clone()).await?;
     Ok(map_handler_err(
@@ -135,7 +155,8 @@ pub async fn delete<M: CustomersModuleInterface>(
 pub async fn list<M: CustomersModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(customers_module): State<Arc<M>>,
-    Query(payload): Query<CommonRawQuery>,
+    Query(payload): Query<CustomerListQuery>,
+    Query(tag_filter): Query<TagFilter>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), customers_module.clone());
     let resource_query = map_handler_err(
@@ -144,7 +165,14 @@ pub async fn list<M: CustomersModuleInterface>(
     )
     .await?;
     let (meta, data) = map_handler_err(
-        service.get_paged(&resource_query).await,
+        service
+            .get_paged(
+                &resource_query,
+                payload.segment_id,
+                payload.stage.as_deref(),
+                tag_filter.tags,
+            )
+            .await,
         customers_module.clone(),
     )
     .await?;
@@ -192,116 +220,446 @@ pub async fn print<M: CustomersModuleInterface>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::File;
-    use std::io::{Read, Write};
-    use std::path::Path;
-
-    use super::*;
-    use crate::common::dto::PaginatorMeta;
-    use crate::common::error::RepositoryError;
-    use crate::common::handler::tests::{
-        extract_json_response, generate_expired_jwt, generate_jwt_with_invalid_signature,
-        generate_valid_jwt,
-    };
-    use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
-    use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::customers::model::CustomerResolved;
-    use crate::{
-        common::config::tests::AppConfigBuilder,
-        tenant::customers::{
-            self, model::Customer, repository::MockCustomersRepository, tests::MockCustomersModule,
-        },
-    };
-    use axum::body::Body;
-    use axum::{Router, http::Request};
-    use chrono::{DateTime, Utc};
-    use mockall::predicate::eq;
-    use pretty_assertions::assert_eq;
-    use serde_json::json;
-    use tower::ServiceExt;
-    use uuid::Uuid;
-
-    #[tokio::test]
-    async fn test_get_success() {
-        let active_tenant_id = Uuid::new_v4();
-        let customer_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-
-        let customer = Customer {
-            id: customer_id,
-            name: "Test customer".to_string(),
-            contact_name: None,
-            email: "test_customer@example.com".to_string(),
-            phone_number: Some("+36301234567".to_string()),
-            status: "active".to_string(),
-            customer_type: "natural".to_string(),
-            created_by_id,
-            created_at: utc_now,
-            updated_at: utc_now,
-            deleted_at: None,
-        };
+pub async fn set_sales_rep<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerSalesRep>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    map_handler_err(
+        service.set_sales_rep(&payload).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az üzletkötő hozzárendelése sikeresen megtörtént",
+            ))
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let mut repo = MockCustomersRepository::new();
-        repo.expect_get_by_id()
-            .times(1)
-            .with(eq(customer_id))
-            .returning({
-                let customer = customer.clone();
-                move |_| Ok(customer.clone())
-            });
+pub async fn duplicates<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.get_duplicates(payload.uuid).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let mut app_state = MockCustomersModule::new();
-        let repo = Arc::new(repo);
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_customers_repo()
-            .with(eq(active_tenant_id))
-            .times(1)
-            .returning(move |_| Ok(repo.clone()));
-        app_state
-            .expect_config()
-            .times(1)
-            .return_const(test_config.clone());
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    generate_valid_jwt(None, Some(active_tenant_id))
-                ),
-            )
-            .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/customers/get?uuid={customer_id}"))
-            .body("".to_string())
-            .unwrap();
+pub async fn merge<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerMergeInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(service.merge(&payload).await, customers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
-        );
+pub async fn import<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<CustomerImportQuery>,
+    body: Bytes,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.import(&payload, &body).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let response = app.oneshot(request).await.unwrap();
+pub async fn import_history<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result =
+        map_handler_err(service.get_import_history().await, customers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        assert_eq!(response.status(), StatusCode::OK);
+/// Downloads every personal data stored about the customer as a JSON file
+pub async fn data_export<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let export = map_handler_err(
+        service.get_data_export(payload.uuid).await,
+        customers_module,
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(
+            r#"attachment; filename="vevo_adatai_{}.json""#,
+            payload.uuid
+        )
+        .parse()
+        .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, Json(export)).into_response())
+}
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "meta": null,
-            "data": customer
-        });
+pub async fn anonymize<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.anonymize(payload.uuid).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        assert_eq!(response_body, expected_body);
-    }
+pub async fn credit_limit<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.get_credit_exposure(payload.uuid).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-    #[tokio::test]
-    async fn test_get_unauthorized_expired() {
-        let customer_id = Uuid::new_v4();
+pub async fn set_credit_limit<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerCreditLimitInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.set_credit_limit(&payload).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let mut app_state = MockCustomersModule::new();
+pub async fn credit_limit_settings<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.get_credit_limit_settings().await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_credit_limit_settings<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreditLimitSettingsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.set_credit_limit_settings(&payload).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_stage<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CustomerStageInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result =
+        map_handler_err(service.set_stage(&payload).await, customers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn stage_history<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.get_stage_history(payload.uuid).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pipeline<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result =
+        map_handler_err(service.get_stage_counts().await, customers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn timeline<M: CustomersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(customers_module): State<Arc<M>>,
+    Query(payload): Query<CustomerTimelineQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), customers_module.clone());
+    let result = map_handler_err(
+        service.get_timeline(&payload).await,
+        customers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        customers_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::path::Path;
+
+    use super::*;
+    use crate::common::dto::PaginatorMeta;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::{
+        MockUniqueViolation, extract_json_response, generate_expired_jwt,
+        generate_jwt_with_invalid_signature, generate_valid_jwt,
+    };
+    use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
+    use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
+    use crate::tenant::customers::dto::import::CustomerImportRow;
+    use crate::tenant::customers::model::{
+        CustomerAnonymization, CustomerCreditExposure, CustomerDataExport,
+        CustomerDuplicateCandidate, CustomerExportDocument, CustomerExportEmail,
+        CustomerImportLine, CustomerImportReport, CustomerResolved, CustomerStageCount,
+        CustomerStageTransition, CustomerTimelineEvent,
+    };
+    use crate::{
+        common::config::tests::AppConfigBuilder,
+        tenant::customers::{
+            self, model::Customer, repository::MockCustomersRepository, tests::MockCustomersModule,
+        },
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use sqlx::error::DatabaseError;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_get_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+
+        let customer = Customer {
+            id: customer_id,
+            name: "Test customer".to_string(),
+            contact_name: None,
+            email: "test_customer@example.com".to_string(),
+            phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
+            status: "active".to_string(),
+            customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
+            created_by_id,
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(customer_id))
+            .returning({
+                let customer = customer.clone();
+                move |_| Ok(customer.clone())
+            });
+
+        let mut app_state = MockCustomersModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/customers/get?uuid={customer_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": customer
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_unauthorized_expired() {
+        let customer_id = Uuid::new_v4();
+
+        let mut app_state = MockCustomersModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -330,6 +688,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -370,6 +729,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -451,6 +811,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -471,8 +832,10 @@ mod tests {
             contact_name: None,
             email: "test_customer@example.com".to_string(),
             phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: utc_now,
@@ -566,6 +929,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -606,6 +970,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -687,6 +1052,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -712,8 +1078,10 @@ mod tests {
             contact_name: None,
             email: "test_customer@example.com".to_string(),
             phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: utc_now,
@@ -724,12 +1092,17 @@ mod tests {
         let mut repo = MockCustomersRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<CustomerOrderBy, CustomerFilterBy>>()
-                .unwrap()))
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<CustomerOrderBy, CustomerFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(None),
+                eq(None),
+            )
             .returning({
                 let customer_resolved = customer_resolved.clone();
-                move |_| Ok((paginator_meta, vec![customer_resolved.clone()]))
+                move |_, _, _, _| Ok((paginator_meta, vec![customer_resolved.clone()]))
             });
 
         let mut app_state = MockCustomersModule::new();
@@ -807,6 +1180,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -845,6 +1219,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -883,10 +1258,15 @@ mod tests {
         let mut repo = MockCustomersRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<CustomerOrderBy, CustomerFilterBy>>()
-                .unwrap()))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<CustomerOrderBy, CustomerFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(None),
+                eq(None),
+            )
+            .returning(|_, _, _, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockCustomersModule::new();
         let repo = Arc::new(repo);
@@ -926,6 +1306,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -947,6 +1328,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -958,8 +1340,10 @@ mod tests {
             contact_name: None,
             email: "test.customer@example.com".to_string(),
             phone_number: Some("36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_at: utc_now,
             updated_at: utc_now,
@@ -1042,6 +1426,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "activee".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1081,6 +1466,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": field_errors
             }
@@ -1097,6 +1483,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1131,6 +1518,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1146,6 +1534,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1180,6 +1569,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1195,6 +1585,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1236,6 +1627,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1247,8 +1639,10 @@ mod tests {
             contact_name: None,
             email: "test.customer@example.com".to_string(),
             phone_number: Some("36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_at: utc_now,
             updated_at: utc_now,
@@ -1320,6 +1714,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1357,6 +1752,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!",
             }
         });
@@ -1371,6 +1767,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1405,6 +1802,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1420,6 +1818,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1454,6 +1853,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1469,6 +1869,7 @@ mod tests {
             contact_name: "".to_string(),
             email: "test.customer@example.com".to_string(),
             phone_number: "+36301234567".to_string(),
+            tax_number: "".to_string(),
             status: "active".to_string(),
             customer_type: "natural".to_string(),
         };
@@ -1625,6 +2026,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1665,6 +2067,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1712,8 +2115,10 @@ mod tests {
             contact_name: None,
             email: "test.customer@example.com".to_string(),
             phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: test_time,
@@ -1834,6 +2239,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1875,6 +2281,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1909,4 +2316,945 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_set_sales_rep_not_found() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let sales_rep_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_sales_rep()
+            .times(1)
+            .with(eq(CustomerSalesRep {
+                id: customer_id,
+                sales_rep_id: Some(sales_rep_id),
+            }))
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let mut app_state = MockCustomersModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/customers/set_sales_rep")
+            .body(json!({"id": customer_id, "sales_rep_id": sales_rep_id}).to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_timeline_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let before: DateTime<Utc> = "2026-10-01T08:00:00Z".parse().unwrap();
+        let utc_now = Utc::now();
+
+        let customer = Customer {
+            id: customer_id,
+            name: "Test customer".to_string(),
+            contact_name: None,
+            email: "test_customer@example.com".to_string(),
+            phone_number: None,
+            tax_number: None,
+            status: "active".to_string(),
+            customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+        let events = vec![
+            CustomerTimelineEvent {
+                event_type: "payment_received".to_string(),
+                resource_type: "payments".to_string(),
+                resource_id: Uuid::new_v4(),
+                title: "bank_transfer".to_string(),
+                details: None,
+                amount: Some("12700".parse().unwrap()),
+                currency_code: Some("HUF".to_string()),
+                created_by: Some("Teszt Elek".to_string()),
+                occurred_at: "2026-09-30T10:00:00Z".parse().unwrap(),
+            },
+            CustomerTimelineEvent {
+                event_type: "note".to_string(),
+                resource_type: "comments".to_string(),
+                resource_id: Uuid::new_v4(),
+                title: "Jegyzet".to_string(),
+                details: Some("Fizetési emlékeztető telefonon".to_string()),
+                amount: None,
+                currency_code: None,
+                created_by: Some("Teszt Elek".to_string()),
+                occurred_at: "2026-09-29T10:00:00Z".parse().unwrap(),
+            },
+        ];
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(move |_| Ok(customer.clone()));
+        repo.expect_get_timeline()
+            .times(1)
+            .with(eq(customer_id), eq(Some(before)), eq(20))
+            .returning({
+                let events = events.clone();
+                move |_, _, _| Ok(events.clone())
+            });
+
+        let mut app_state = MockCustomersModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!(
+                "/api/customers/timeline?uuid={customer_id}&before=2026-10-01T08:00:00Z&limit=20"
+            ))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<CustomerTimelineEvent> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, events);
+    }
+
+    #[tokio::test]
+    async fn test_timeline_rejects_invalid_limit() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_by_id().never();
+        repo.expect_get_timeline().never();
+
+        let mut app_state = MockCustomersModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_customers_repo()
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!(
+                "/api/customers/timeline?uuid={}&limit=1000",
+                Uuid::new_v4()
+            ))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn customers_app(repo: MockCustomersRepository, active_tenant_id: Uuid) -> Router {
+        let mut app_state = MockCustomersModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(customers::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn customers_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<String> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.to_string())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_reports_possible_duplicates() {
+        let active_tenant_id = Uuid::new_v4();
+        let candidate = CustomerDuplicateCandidate {
+            id: Uuid::new_v4(),
+            name: "Teszt Kft.".to_string(),
+            email: "iroda@teszt.hu".to_string(),
+            tax_number: Some("12345678-2-42".to_string()),
+            status: "active".to_string(),
+            name_similarity: 1.0,
+            same_email: false,
+            same_tax_number: true,
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_find_duplicates()
+            .times(1)
+            .withf(|query| {
+                query.name == "TESZT kft"
+                    && query.email == "penzugy@teszt.hu"
+                    && query.tax_number.as_deref() == Some("12345678-2-42")
+                    && query.exclude_id.is_none()
+            })
+            .returning({
+                let candidate = candidate.clone();
+                move |_| Ok(vec![candidate.clone()])
+            });
+        repo.expect_insert().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "POST",
+                "/api/customers/create?check_duplicates=true",
+                active_tenant_id,
+                json!({
+                    "name": "TESZT kft",
+                    "contact_name": "Teszt Elek",
+                    "email": "penzugy@teszt.hu",
+                    "phone_number": "",
+                    "tax_number": "12345678-2-42",
+                    "status": "active",
+                    "customer_type": "legal"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({
+                "error": {
+                    "code": "CONFLICT",
+                    "message": "Hasonló vevő már szerepel a törzsben",
+                    "candidates": [candidate]
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let duplicate_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let surviving = Customer {
+            id: Uuid::new_v4(),
+            name: "Teszt Kft.".to_string(),
+            contact_name: Some("Teszt Elek".to_string()),
+            email: "iroda@teszt.hu".to_string(),
+            phone_number: None,
+            tax_number: Some("12345678-2-42".to_string()),
+            status: "active".to_string(),
+            customer_type: "legal".to_string(),
+            lifecycle_stage: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+        let surviving_id = surviving.id;
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_merge()
+            .times(1)
+            .with(eq(CustomerMergeInput {
+                duplicate_id,
+                surviving_id,
+            }))
+            .returning({
+                let surviving = surviving.clone();
+                move |_| Ok(surviving.clone())
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "POST",
+                "/api/customers/merge",
+                active_tenant_id,
+                json!({"duplicate_id": duplicate_id, "surviving_id": surviving_id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": surviving})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_rejects_same_customer() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_merge().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "POST",
+                "/api/customers/merge",
+                active_tenant_id,
+                json!({"duplicate_id": customer_id, "surviving_id": customer_id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn csv_request(uri: &str, active_tenant_id: Uuid, csv: &str) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "text/csv")
+            .method("POST")
+            .uri(uri)
+            .body(Body::from(csv.to_owned()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_import_dry_run() {
+        let active_tenant_id = Uuid::new_v4();
+        let report = CustomerImportReport {
+            import_id: None,
+            applied: false,
+            row_count: 2,
+            error_count: 1,
+            customers_created: 1,
+            customers_updated: 0,
+            lines: vec![
+                CustomerImportLine {
+                    line: 2,
+                    name: "Minta Kft.".to_string(),
+                    email: "info@minta.hu".to_string(),
+                    customer_id: Some(Uuid::new_v4()),
+                    customer_created: true,
+                    customer_updated: false,
+                    errors: vec![],
+                },
+                CustomerImportLine {
+                    line: 3,
+                    name: "Kovács Béla".to_string(),
+                    email: "bela@example.com".to_string(),
+                    customer_id: None,
+                    customer_created: false,
+                    customer_updated: false,
+                    errors: vec!["Hibás vevő státusz".to_string()],
+                },
+            ],
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_import()
+            .times(1)
+            .withf(|rows: &[CustomerImportRow], _, apply| {
+                rows.len() == 2
+                    && rows[0].input.is_some()
+                    && rows[1].errors == vec!["Hibás vevő státusz".to_string()]
+                    && !apply
+            })
+            .returning({
+                let report = report.clone();
+                move |_, _, _| Ok(report.clone())
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(csv_request(
+                "/api/customers/import?email_column=E-mail",
+                active_tenant_id,
+                "name,contact_name,E-mail,status,customer_type\n\
+                 Minta Kft.,Kiss Anna,info@minta.hu,,legal\n\
+                 Kovács Béla,,bela@example.com,archived,natural\n",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: CustomerImportReport =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, report);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unmapped_required_column() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_import().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(csv_request(
+                "/api/customers/import?dry_run=false",
+                active_tenant_id,
+                "name,E-mail,customer_type\nMinta Kft.,info@minta.hu,legal\n",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_data_export_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let export = CustomerDataExport {
+            exported_at: utc_now,
+            customer: Customer {
+                id: customer_id,
+                name: "Kovács Béla".to_string(),
+                contact_name: None,
+                email: "bela@example.com".to_string(),
+                phone_number: Some("+36301234567".to_string()),
+                tax_number: None,
+                status: "active".to_string(),
+                customer_type: "natural".to_string(),
+                lifecycle_stage: "active".to_string(),
+                created_by_id: Uuid::new_v4(),
+                created_at: utc_now,
+                updated_at: utc_now,
+                deleted_at: None,
+            },
+            contacts: vec![],
+            addresses: vec![],
+            notes: vec![],
+            documents: vec![CustomerExportDocument {
+                document_type: "invoice".to_string(),
+                id: Uuid::new_v4(),
+                reference: Some("INV-2026-001".to_string()),
+                issue_date: utc_now.date_naive(),
+                amount: Some(BigDecimal::from(12700)),
+                currency_code: Some("HUF".to_string()),
+                status: Some("open".to_string()),
+                details: None,
+                created_at: utc_now,
+            }],
+            emails: vec![CustomerExportEmail {
+                email_type: "invoice".to_string(),
+                id: Uuid::new_v4(),
+                recipient: "bela@example.com".to_string(),
+                cc: None,
+                bcc: None,
+                subject: "Számla INV-2026-001".to_string(),
+                status: "sent".to_string(),
+                sent_at: Some(utc_now),
+                created_at: utc_now,
+            }],
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_data_export()
+            .times(1)
+            .with(eq(customer_id))
+            .returning({
+                let export = export.clone();
+                move |_| Ok(export.clone())
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                &format!("/api/customers/data_export?uuid={customer_id}"),
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            format!(r#"attachment; filename="vevo_adatai_{customer_id}.json""#)
+        );
+        let body = extract_json_response(response).await;
+        assert_eq!(body["customer"]["email"], "bela@example.com");
+        assert_eq!(body["documents"][0]["reference"], "INV-2026-001");
+        assert_eq!(body["emails"][0]["subject"], "Számla INV-2026-001");
+    }
+
+    #[tokio::test]
+    async fn test_data_export_not_found() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_data_export()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                &format!("/api/customers/data_export?uuid={customer_id}"),
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_anonymize_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let anonymization = CustomerAnonymization {
+            id: Uuid::new_v4(),
+            customer_id,
+            invoice_data_retained: true,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_anonymize()
+            .times(1)
+            .withf(move |id, _| *id == customer_id)
+            .returning({
+                let anonymization = anonymization.clone();
+                move |_, _| Ok(anonymization.clone())
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "POST",
+                "/api/customers/anonymize",
+                active_tenant_id,
+                json!({"uuid": customer_id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: CustomerAnonymization =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, anonymization);
+    }
+
+    #[tokio::test]
+    async fn test_anonymize_twice_conflict() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_anonymize().times(1).returning(|_, _| {
+            Err(RepositoryError::Database(sqlx::Error::Database(
+                Box::new(MockUniqueViolation) as Box<dyn DatabaseError>,
+            )))
+        });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "POST",
+                "/api/customers/anonymize",
+                active_tenant_id,
+                json!({"uuid": customer_id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await["error"]["message"],
+            "A vevő adatai már anonimizálva lettek!"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_credit_limit_normalizes_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_credit_limit()
+            .times(1)
+            .withf(move |input| {
+                input.customer_id == customer_id && input.currency_code.as_deref() == Some("HUF")
+            })
+            .returning(|input| {
+                Ok(CustomerCreditExposure {
+                    customer_id: input.customer_id,
+                    credit_limit: input.credit_limit.clone(),
+                    currency_code: input.currency_code.clone(),
+                    open_balance: BigDecimal::from(120000),
+                    mode: "warn".to_string(),
+                })
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "PUT",
+                "/api/customers/set_credit_limit",
+                active_tenant_id,
+                json!({"customer_id": customer_id, "credit_limit": "500000", "currency_code": " huf "}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["currency_code"], json!("HUF"));
+        assert_eq!(body["data"]["open_balance"], json!("120000"));
+    }
+
+    #[tokio::test]
+    async fn test_set_credit_limit_requires_currency() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_credit_limit().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "PUT",
+                "/api/customers/set_credit_limit",
+                active_tenant_id,
+                json!({"customer_id": Uuid::new_v4(), "credit_limit": "500000", "currency_code": null}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_credit_limit_settings_rejects_unknown_mode() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_credit_limit_mode().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "PUT",
+                "/api/customers/set_credit_limit_settings",
+                active_tenant_id,
+                json!({"mode": "ignore"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_segment() {
+        let active_tenant_id = Uuid::new_v4();
+        let segment_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .withf(move |_, segment, stage, tags| {
+                *segment == Some(segment_id) && stage.is_none() && tags.is_none()
+            })
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                &format!("/api/customers/list?segment_id={segment_id}"),
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(extract_json_response(response).await["meta"]["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_stage() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .withf(|_, segment, stage, _| segment.is_none() && stage.as_deref() == Some("prospect"))
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                "/api/customers/list?stage=prospect",
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_tags() {
+        let active_tenant_id = Uuid::new_v4();
+        let first_tag_id = Uuid::new_v4();
+        let second_tag_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .withf(move |_, _, _, tags| *tags == Some(vec![first_tag_id, second_tag_id]))
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                &format!("/api/customers/list?tags={first_tag_id},{second_tag_id}"),
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_list_rejects_unknown_stage() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_paged().never();
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                "/api/customers/list?stage=won",
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_stage_records_transition() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let transition = CustomerStageTransition {
+            id: Uuid::new_v4(),
+            customer_id,
+            from_stage: "lead".to_string(),
+            to_stage: "prospect".to_string(),
+            note: Some("Árajánlatot kért".to_string()),
+            created_by_id: Uuid::new_v4(),
+            created_by: "Teszt Elek".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_stage()
+            .times(1)
+            .withf(move |input, _| {
+                input.customer_id == customer_id
+                    && input.stage == "prospect"
+                    && input.note.as_deref() == Some("Árajánlatot kért")
+            })
+            .returning({
+                let transition = transition.clone();
+                move |_, _| Ok(Some(transition.clone()))
+            });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "PUT",
+                "/api/customers/set_stage",
+                active_tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "stage": " prospect ",
+                    "note": " Árajánlatot kért "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: CustomerStageTransition =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, transition);
+    }
+
+    #[tokio::test]
+    async fn test_set_stage_rejects_current_stage() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_set_stage().times(1).returning(|_, _| Ok(None));
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "PUT",
+                "/api/customers/set_stage",
+                active_tenant_id,
+                json!({"customer_id": Uuid::new_v4(), "stage": "active", "note": null}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_returns_counts_per_stage() {
+        let active_tenant_id = Uuid::new_v4();
+        let counts = vec![
+            CustomerStageCount {
+                stage: "lead".to_string(),
+                customer_count: 12,
+            },
+            CustomerStageCount {
+                stage: "prospect".to_string(),
+                customer_count: 4,
+            },
+            CustomerStageCount {
+                stage: "active".to_string(),
+                customer_count: 31,
+            },
+            CustomerStageCount {
+                stage: "churned".to_string(),
+                customer_count: 0,
+            },
+        ];
+
+        let mut repo = MockCustomersRepository::new();
+        repo.expect_get_stage_counts().times(1).returning({
+            let counts = counts.clone();
+            move || Ok(counts.clone())
+        });
+
+        let response = customers_app(repo, active_tenant_id)
+            .oneshot(customers_request(
+                "GET",
+                "/api/customers/pipeline",
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<CustomerStageCount> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, counts);
+    }
 }
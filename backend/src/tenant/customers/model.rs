@@ -17,7 +17,10 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use chrono::{DateTime, Utc};
+use crate::tenant::customer_contacts::model::CustomerContact;
+use crate::tenant::customer_notes::model::CustomerNote;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -29,8 +32,10 @@ pub struct Customer {
     pub contact_name: Option<String>,
     pub email: String,
     pub phone_number: Option<String>,
+    pub tax_number: Option<String>,
     pub status: String,
     pub customer_type: String,
+    pub lifecycle_stage: String,
     pub created_by_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -44,11 +49,243 @@ pub struct CustomerResolved {
     pub contact_name: Option<String>,
     pub email: String,
     pub phone_number: Option<String>,
+    pub tax_number: Option<String>,
     pub status: String,
     pub customer_type: String,
+    pub lifecycle_stage: String,
     pub created_by_id: Uuid,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerDuplicateCandidate {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub tax_number: Option<String>,
+    pub status: String,
+    pub name_similarity: f32,
+    pub same_email: bool,
+    pub same_tax_number: bool,
+}
+
+/// One entry of the customer's relationship history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerTimelineEvent {
+    /// `note`, `worksheet_created`, `invoice_issued`, `payment_received` or `email_sent`
+    pub event_type: String,
+    /// The table of the record behind the event
+    pub resource_type: String,
+    pub resource_id: Uuid,
+    pub title: String,
+    pub details: Option<String>,
+    pub amount: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub created_by: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerImportLine {
+    pub line: u64,
+    pub name: String,
+    pub email: String,
+    pub customer_id: Option<Uuid>,
+    pub customer_created: bool,
+    pub customer_updated: bool,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerImportReport {
+    pub import_id: Option<Uuid>,
+    pub applied: bool,
+    pub row_count: usize,
+    pub error_count: usize,
+    pub customers_created: i32,
+    pub customers_updated: i32,
+    pub lines: Vec<CustomerImportLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerImport {
+    pub id: Uuid,
+    pub row_count: i32,
+    pub customers_created: i32,
+    pub customers_updated: i32,
+    pub created_by_id: Uuid,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Every piece of personal data stored about a customer, answering a data-subject access request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerDataExport {
+    pub exported_at: DateTime<Utc>,
+    pub customer: Customer,
+    pub contacts: Vec<CustomerContact>,
+    pub addresses: Vec<CustomerExportAddress>,
+    pub notes: Vec<CustomerNote>,
+    pub documents: Vec<CustomerExportDocument>,
+    pub emails: Vec<CustomerExportEmail>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerExportAddress {
+    pub id: Uuid,
+    pub street_address: String,
+    pub postal_code: String,
+    pub city: String,
+    pub state: String,
+    pub country_code: String,
+    pub additional_info: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Invoices, credit notes, quotes, payments and worksheets of the customer. `reference` is the
+/// document number, or the name of the worksheet.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerExportDocument {
+    pub document_type: String,
+    pub id: Uuid,
+    pub reference: Option<String>,
+    pub issue_date: NaiveDate,
+    pub amount: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub status: Option<String>,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerExportEmail {
+    pub email_type: String,
+    pub id: Uuid,
+    pub recipient: String,
+    pub cc: Option<String>,
+    pub bcc: Option<String>,
+    pub subject: String,
+    pub status: String,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Record of an irreversible anonymization. When the customer has invoices, the name, tax number
+/// and addresses are kept, as they are part of the legally retained invoice data.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerAnonymization {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub invoice_data_retained: bool,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub const CREDIT_LIMIT_MODE_WARN: &str = "warn";
+pub const CREDIT_LIMIT_MODE_BLOCK: &str = "block";
+pub const CREDIT_LIMIT_MODES: [&str; 2] = [CREDIT_LIMIT_MODE_WARN, CREDIT_LIMIT_MODE_BLOCK];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CreditLimitSettings {
+    pub mode: String,
+    pub updated_by_id: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Credit limit of a customer next to the open balance of its invoices in the limit currency
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerCreditExposure {
+    pub customer_id: Uuid,
+    pub credit_limit: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub open_balance: BigDecimal,
+    pub mode: String,
+}
+
+impl CustomerCreditExposure {
+    /// Documents in another currency than the limit cannot be compared with it, so they are
+    /// treated as an overrun. An acknowledged overrun only passes in warn mode.
+    pub fn check(
+        &self,
+        currency_code: &str,
+        amount: &BigDecimal,
+        acknowledged: bool,
+    ) -> Option<CreditLimitBreach> {
+        let (Some(credit_limit), Some(limit_currency_code)) =
+            (&self.credit_limit, &self.currency_code)
+        else {
+            return None;
+        };
+        if limit_currency_code == currency_code && &self.open_balance + amount <= *credit_limit {
+            return None;
+        }
+        let blocking = self.mode == CREDIT_LIMIT_MODE_BLOCK;
+        (blocking || !acknowledged).then(|| CreditLimitBreach {
+            customer_id: self.customer_id,
+            credit_limit: credit_limit.clone(),
+            currency_code: limit_currency_code.clone(),
+            open_balance: self.open_balance.clone(),
+            amount: amount.clone(),
+            document_currency_code: currency_code.to_string(),
+            blocking,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CreditLimitBreach {
+    pub customer_id: Uuid,
+    pub credit_limit: BigDecimal,
+    pub currency_code: String,
+    pub open_balance: BigDecimal,
+    pub amount: BigDecimal,
+    pub document_currency_code: String,
+    /// false: the document is accepted when resent with `acknowledge_credit_limit`
+    pub blocking: bool,
+}
+
+impl CreditLimitBreach {
+    pub fn message(&self) -> &'static str {
+        match (
+            self.currency_code == self.document_currency_code,
+            self.blocking,
+        ) {
+            (true, true) => "A bizonylat túllépné a vevő hitelkeretét!",
+            (true, false) => {
+                "A bizonylat túllépi a vevő hitelkeretét, a folytatáshoz megerősítés szükséges!"
+            }
+            (false, true) => "A bizonylat pénzneme eltér a vevő hitelkeretének pénznemétől!",
+            (false, false) => {
+                "A bizonylat pénzneme eltér a vevő hitelkeretének pénznemétől, a folytatáshoz megerősítés szükséges!"
+            }
+        }
+    }
+}
+
+pub const STAGE_LEAD: &str = "lead";
+pub const STAGE_PROSPECT: &str = "prospect";
+pub const STAGE_ACTIVE: &str = "active";
+pub const STAGE_CHURNED: &str = "churned";
+/// In pipeline order
+pub const STAGES: [&str; 4] = [STAGE_LEAD, STAGE_PROSPECT, STAGE_ACTIVE, STAGE_CHURNED];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerStageTransition {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub from_stage: String,
+    pub to_stage: String,
+    pub note: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerStageCount {
+    pub stage: String,
+    pub customer_count: i64,
+}
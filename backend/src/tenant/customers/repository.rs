@@ -21,13 +21,30 @@ use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::tenant::customer_contacts::model::CustomerContact;
+use crate::tenant::customer_notes::model::CustomerNote;
+use crate::tenant::customers::dto::credit_limit::CustomerCreditLimitInput;
+use crate::tenant::customers::dto::duplicate::{
+    CustomerDuplicateQuery, CustomerMergeInput, DUPLICATE_NAME_SIMILARITY,
+};
+use crate::tenant::customers::dto::import::CustomerImportRow;
+use crate::tenant::customers::dto::sales_rep::CustomerSalesRep;
+use crate::tenant::customers::dto::stage::CustomerStageInput;
 use crate::tenant::customers::dto::user_input::CustomerUserInput;
-use crate::tenant::customers::model::{Customer, CustomerResolved};
+use crate::tenant::customers::model::{
+    CreditLimitSettings, Customer, CustomerAnonymization, CustomerCreditExposure,
+    CustomerDataExport, CustomerDuplicateCandidate, CustomerExportAddress, CustomerExportDocument,
+    CustomerExportEmail, CustomerImport, CustomerImportLine, CustomerImportReport,
+    CustomerResolved, CustomerStageCount, CustomerStageTransition, CustomerTimelineEvent, STAGES,
+};
 use crate::tenant::customers::types::customer::{CustomerFilterBy, CustomerOrderBy};
+use crate::tenant::tags::repository::tagged_condition;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
-use sqlx::{AssertSqlSafe, PgPool};
+use sqlx::{Acquire, AssertSqlSafe, PgPool};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
@@ -38,11 +55,65 @@ pub trait CustomersRepository: Send + Sync {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<CustomerOrderBy, CustomerFilterBy>,
+        segment_id: Option<Uuid>,
+        stage: Option<String>,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<CustomerResolved>)>;
     async fn get_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>>;
     async fn insert(&self, customer: &CustomerUserInput, sub: Uuid) -> RepositoryResult<Customer>;
     async fn update(&self, customer: &CustomerUserInput) -> RepositoryResult<Customer>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn set_sales_rep(&self, assignment: &CustomerSalesRep) -> RepositoryResult<()>;
+    async fn find_duplicates(
+        &self,
+        query: &CustomerDuplicateQuery,
+    ) -> RepositoryResult<Vec<CustomerDuplicateCandidate>>;
+    async fn merge(&self, input: &CustomerMergeInput) -> RepositoryResult<Customer>;
+    /// Newest events first
+    async fn get_timeline(
+        &self,
+        customer_id: Uuid,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> RepositoryResult<Vec<CustomerTimelineEvent>>;
+    async fn import(
+        &self,
+        rows: &[CustomerImportRow],
+        sub: Uuid,
+        apply: bool,
+    ) -> RepositoryResult<CustomerImportReport>;
+    async fn get_imports(&self) -> RepositoryResult<Vec<CustomerImport>>;
+    async fn get_data_export(&self, customer_id: Uuid) -> RepositoryResult<CustomerDataExport>;
+    async fn anonymize(
+        &self,
+        customer_id: Uuid,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerAnonymization>;
+    async fn get_credit_exposure(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<CustomerCreditExposure>;
+    async fn set_credit_limit(
+        &self,
+        input: &CustomerCreditLimitInput,
+    ) -> RepositoryResult<CustomerCreditExposure>;
+    async fn get_credit_limit_settings(&self) -> RepositoryResult<CreditLimitSettings>;
+    async fn set_credit_limit_mode(
+        &self,
+        mode: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CreditLimitSettings>;
+    /// `None` when the customer is already in the requested stage
+    async fn set_stage(
+        &self,
+        input: &CustomerStageInput,
+        sub: Uuid,
+    ) -> RepositoryResult<Option<CustomerStageTransition>>;
+    async fn get_stage_history(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CustomerStageTransition>>;
+    async fn get_stage_counts(&self) -> RepositoryResult<Vec<CustomerStageCount>>;
 }
 
 #[async_trait]
@@ -70,8 +141,10 @@ impl CustomersRepository for PgPool {
                 customers.contact_name as contact_name,
                 customers.email as email,
                 customers.phone_number as phone_number,
+                customers.tax_number as tax_number,
                 customers.status as status,
                 customers.customer_type as customer_type,
+                customers.lifecycle_stage as lifecycle_stage,
                 customers.created_by_id as created_by_id,
                 users.last_name || ' ' || users.first_name as created_by,
                 customers.created_at as created_at,
@@ -91,6 +164,9 @@ impl CustomersRepository for PgPool {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<CustomerOrderBy, CustomerFilterBy>,
+        segment_id: Option<Uuid>,
+        stage: Option<String>,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<CustomerResolved>)> {
         let total: (i64,) = match (
             query_params.filtering().filter_by(), // Security: ValueObject
@@ -101,16 +177,33 @@ impl CustomersRepository for PgPool {
                 sqlx::query_as(AssertSqlSafe(format!(
                     r#"SELECT COUNT(*) FROM customers
                            WHERE deleted_at IS NULL
-                               AND ($1::TEXT IS NULL OR customers.{filter_by}::TEXT ILIKE '%' || $1 || '%')"#
+                               AND ($1::TEXT IS NULL OR customers.{filter_by}::TEXT ILIKE '%' || $1 || '%')
+                               AND ($2::UUID IS NULL OR customer_in_segment(customers.id, $2))
+                               AND ($3::TEXT IS NULL OR customers.lifecycle_stage = $3)
+                               AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("customers", "customers.id", 4)
                 )))
                 .bind(value_unchecked)
+                .bind(segment_id)
+                .bind(&stage)
+                .bind(&tags)
                 .fetch_one(self)
                 .await?
             }
             (_, _) => {
-                sqlx::query_as("SELECT COUNT(*) FROM customers WHERE deleted_at IS NULL")
-                    .fetch_one(self)
-                    .await?
+                sqlx::query_as(AssertSqlSafe(format!(
+                    r#"SELECT COUNT(*) FROM customers
+                           WHERE deleted_at IS NULL
+                               AND ($1::UUID IS NULL OR customer_in_segment(customers.id, $1))
+                               AND ($2::TEXT IS NULL OR customers.lifecycle_stage = $2)
+                               AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("customers", "customers.id", 3)
+                )))
+                .bind(segment_id)
+                .bind(&stage)
+                .bind(&tags)
+                .fetch_one(self)
+                .await?
             }
         };
 
@@ -137,8 +230,10 @@ impl CustomersRepository for PgPool {
                             customers.contact_name as contact_name,
                             customers.email as email,
                             customers.phone_number as phone_number,
+                            customers.tax_number as tax_number,
                             customers.status as status,
                             customers.customer_type as customer_type,
+                            customers.lifecycle_stage as lifecycle_stage,
                             customers.created_by_id as created_by_id,
                             users.last_name || ' ' || users.first_name as created_by,
                             customers.created_at as created_at,
@@ -148,16 +243,23 @@ impl CustomersRepository for PgPool {
                         LEFT JOIN users ON customers.created_by_id = users.id
                         WHERE customers.deleted_at IS NULL
                             AND ($1::TEXT IS NULL OR customers.{filter_by}::TEXT ILIKE '%' || $1 || '%')
+                            AND ($4::UUID IS NULL OR customer_in_segment(customers.id, $4))
+                            AND ($5::TEXT IS NULL OR customers.lifecycle_stage = $5)
+                            AND {tagged_condition}
                         {order_by_clause}
                         LIMIT $2
                         OFFSET $3
-                    "#
+                    "#,
+                    tagged_condition = tagged_condition("customers", "customers.id", 6)
                 );
 
                 sqlx::query_as::<_, CustomerResolved>(AssertSqlSafe(sql))
                     .bind(value_unchecked)
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(segment_id)
+                    .bind(&stage)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -170,8 +272,10 @@ impl CustomersRepository for PgPool {
                             customers.contact_name as contact_name,
                             customers.email as email,
                             customers.phone_number as phone_number,
+                            customers.tax_number as tax_number,
                             customers.status as status,
                             customers.customer_type as customer_type,
+                            customers.lifecycle_stage as lifecycle_stage,
                             customers.created_by_id as created_by_id,
                             users.last_name || ' ' || users.first_name as created_by,
                             customers.created_at as created_at,
@@ -180,15 +284,22 @@ impl CustomersRepository for PgPool {
                         FROM customers
                         LEFT JOIN users ON customers.created_by_id = users.id
                         WHERE customers.deleted_at IS NULL
+                            AND ($3::UUID IS NULL OR customer_in_segment(customers.id, $3))
+                            AND ($4::TEXT IS NULL OR customers.lifecycle_stage = $4)
+                            AND {tagged_condition}
                         {order_by_clause}
                         LIMIT $1
                         OFFSET $2
-                        "#
+                        "#,
+                    tagged_condition = tagged_condition("customers", "customers.id", 5)
                 );
 
                 sqlx::query_as::<_, CustomerResolved>(AssertSqlSafe(sql))
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(segment_id)
+                    .bind(&stage)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -218,8 +329,8 @@ impl CustomersRepository for PgPool {
             None => None,
         };
         Ok(sqlx::query_as::<_, Customer>(
-            "INSERT INTO customers (name, contact_name, email, phone_number, status, customer_type, created_by_id)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+            "INSERT INTO customers (name, contact_name, email, phone_number, tax_number, status, customer_type, created_by_id)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
         )
         .bind(customer.name.as_str()?)
         .bind(contact_name)
@@ -229,6 +340,7 @@ impl CustomersRepository for PgPool {
                 .phone_number
                     .as_str()
         )
+        .bind(customer.tax_number.as_str())
         .bind(customer.status.as_str()?)
         .bind(customer.customer_type.as_str()?)
         .bind(sub)
@@ -254,7 +366,8 @@ impl CustomersRepository for PgPool {
                 email = $3,
                 phone_number = $4,
                 status = $5,
-                customer_type = $6
+                customer_type = $6,
+                tax_number = $8
             WHERE id = $7
                 AND deleted_at IS NULL 
             RETURNING *
@@ -267,6 +380,7 @@ impl CustomersRepository for PgPool {
         .bind(customer.status.as_str()?)
         .bind(customer.customer_type.as_str()?)
         .bind(id)
+        .bind(customer.tax_number.as_str())
         .fetch_one(self)
         .await?)
     }
@@ -286,4 +400,943 @@ impl CustomersRepository for PgPool {
 
         Ok(())
     }
+
+    async fn set_sales_rep(&self, assignment: &CustomerSalesRep) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE customers
+            SET sales_rep_id = $1
+            WHERE id = $2
+                AND deleted_at IS NULL
+                AND ($1::UUID IS NULL OR EXISTS (
+                    SELECT 1 FROM users WHERE users.id = $1 AND users.deleted_at IS NULL
+                ))
+            "#,
+        )
+        .bind(assignment.sales_rep_id)
+        .bind(assignment.id)
+        .execute(self)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn find_duplicates(
+        &self,
+        query: &CustomerDuplicateQuery,
+    ) -> RepositoryResult<Vec<CustomerDuplicateCandidate>> {
+        Ok(sqlx::query_as::<_, CustomerDuplicateCandidate>(
+            r#"
+            SELECT id,
+                   name,
+                   email,
+                   tax_number,
+                   status,
+                   similarity(normalize_customer_name(name), normalize_customer_name($1))
+                       AS name_similarity,
+                   lower(email) = lower($2) AS same_email,
+                   COALESCE(tax_number = $3, false) AS same_tax_number
+            FROM customers
+            WHERE deleted_at IS NULL
+                AND ($4::UUID IS NULL OR id <> $4)
+                AND (lower(email) = lower($2)
+                    OR tax_number = $3
+                    OR (normalize_customer_name(name) % normalize_customer_name($1)
+                        AND similarity(normalize_customer_name(name), normalize_customer_name($1)) >= $5))
+            ORDER BY same_tax_number DESC, same_email DESC, name_similarity DESC, name
+            LIMIT 10
+            "#,
+        )
+        .bind(&query.name)
+        .bind(&query.email)
+        .bind(&query.tax_number)
+        .bind(query.exclude_id)
+        .bind(DUPLICATE_NAME_SIMILARITY)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn merge(&self, input: &CustomerMergeInput) -> RepositoryResult<Customer> {
+        let mut tx = self.begin().await?;
+        let locked: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id
+            FROM customers
+            WHERE id IN ($1, $2)
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.duplicate_id)
+        .bind(input.surviving_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+
+        // NOTE: where both customers already have the same tag, address or watcher, the surviving
+        // customer's entry is kept, and its primary contact stays the primary one
+        for statement in [
+            "UPDATE worksheets SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE receivables SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE credit_notes SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE payments SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE collection_activities SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE quotes SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE recurring_invoices SET customer_id = $2 WHERE customer_id = $1",
+            "UPDATE service_rates SET customer_id = $2 WHERE customer_id = $1",
+            r#"
+            UPDATE customer_contacts
+            SET is_primary = false
+            WHERE customer_id = $1
+                AND EXISTS(SELECT 1
+                           FROM customer_contacts
+                           WHERE customer_id = $2 AND is_primary AND deleted_at IS NULL)
+            "#,
+            "UPDATE customer_contacts SET customer_id = $2 WHERE customer_id = $1",
+            r#"
+            UPDATE comments
+            SET commentable_id = $2
+            WHERE commentable_type = 'customers'
+                AND commentable_id = $1
+            "#,
+            r#"
+            UPDATE address_connect
+            SET addressable_id = $2
+            WHERE addressable_type = 'customers'
+                AND addressable_id = $1
+                AND deleted_at IS NULL
+                AND address_id NOT IN (
+                    SELECT address_id
+                    FROM address_connect
+                    WHERE addressable_type = 'customers' AND addressable_id = $2 AND deleted_at IS NULL
+                )
+            "#,
+            r#"
+            UPDATE taggings
+            SET taggable_id = $2
+            WHERE taggable_type = 'customers'
+                AND taggable_id = $1
+                AND tag_id NOT IN (
+                    SELECT tag_id
+                    FROM taggings
+                    WHERE taggable_type = 'customers' AND taggable_id = $2
+                )
+            "#,
+            "DELETE FROM taggings WHERE taggable_type = 'customers' AND taggable_id = $1",
+            r#"
+            UPDATE watches
+            SET watchable_id = $2
+            WHERE watchable_type = 'customers'
+                AND watchable_id = $1
+                AND user_id NOT IN (
+                    SELECT user_id
+                    FROM watches
+                    WHERE watchable_type = 'customers' AND watchable_id = $2
+                )
+            "#,
+            "DELETE FROM watches WHERE watchable_type = 'customers' AND watchable_id = $1",
+            "UPDATE customers SET deleted_at = NOW(), merged_into_id = $2 WHERE id = $1",
+        ] {
+            sqlx::query(statement)
+                .bind(input.duplicate_id)
+                .bind(input.surviving_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let customer = sqlx::query_as::<_, Customer>(
+            r#"
+            UPDATE customers
+            SET contact_name = COALESCE(customers.contact_name, duplicate.contact_name),
+                phone_number = COALESCE(customers.phone_number, duplicate.phone_number),
+                tax_number = COALESCE(customers.tax_number, duplicate.tax_number),
+                sales_rep_id = COALESCE(customers.sales_rep_id, duplicate.sales_rep_id)
+            FROM customers AS duplicate
+            WHERE customers.id = $2
+                AND duplicate.id = $1
+            RETURNING customers.*
+            "#,
+        )
+        .bind(input.duplicate_id)
+        .bind(input.surviving_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(customer)
+    }
+
+    async fn get_timeline(
+        &self,
+        customer_id: Uuid,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> RepositoryResult<Vec<CustomerTimelineEvent>> {
+        Ok(sqlx::query_as::<_, CustomerTimelineEvent>(
+            r#"
+            WITH timeline AS (
+                SELECT 'note' AS event_type,
+                       'comments' AS resource_type,
+                       comments.id AS resource_id,
+                       'Jegyzet' AS title,
+                       comments.comment AS details,
+                       NULL::NUMERIC AS amount,
+                       NULL::VARCHAR AS currency_code,
+                       users.last_name || ' ' || users.first_name AS created_by,
+                       comments.created_at AS occurred_at
+                FROM comments
+                LEFT JOIN users ON comments.created_by_id = users.id
+                WHERE comments.commentable_type = 'customers'
+                    AND comments.commentable_id = $1
+                    AND comments.deleted_at IS NULL
+                UNION ALL
+                SELECT 'worksheet_created',
+                       'worksheets',
+                       worksheets.id,
+                       worksheets.name,
+                       worksheets.description,
+                       NULL,
+                       NULL,
+                       users.last_name || ' ' || users.first_name,
+                       worksheets.created_at
+                FROM worksheets
+                LEFT JOIN users ON worksheets.created_by_id = users.id
+                WHERE worksheets.customer_id = $1
+                    AND worksheets.deleted_at IS NULL
+                UNION ALL
+                SELECT 'invoice_issued',
+                       'receivables',
+                       receivables.id,
+                       receivables.document_number,
+                       NULL,
+                       receivables.amount,
+                       receivables.currency_code,
+                       users.last_name || ' ' || users.first_name,
+                       receivables.created_at
+                FROM receivables
+                LEFT JOIN users ON receivables.created_by_id = users.id
+                WHERE receivables.customer_id = $1
+                    AND receivables.deleted_at IS NULL
+                UNION ALL
+                SELECT 'payment_received',
+                       'payments',
+                       payments.id,
+                       COALESCE(payments.reference, payments.payment_method),
+                       payments.note,
+                       payments.amount,
+                       payments.currency_code,
+                       users.last_name || ' ' || users.first_name,
+                       payments.created_at
+                FROM payments
+                LEFT JOIN users ON payments.created_by_id = users.id
+                WHERE payments.customer_id = $1
+                    AND payments.deleted_at IS NULL
+                UNION ALL
+                SELECT 'email_sent',
+                       'invoice_email_deliveries',
+                       invoice_email_deliveries.id,
+                       invoice_email_deliveries.subject,
+                       invoice_email_deliveries.recipient,
+                       NULL,
+                       NULL,
+                       users.last_name || ' ' || users.first_name,
+                       COALESCE(invoice_email_deliveries.sent_at, invoice_email_deliveries.created_at)
+                FROM invoice_email_deliveries
+                JOIN receivables ON invoice_email_deliveries.receivable_id = receivables.id
+                LEFT JOIN users ON invoice_email_deliveries.created_by_id = users.id
+                WHERE receivables.customer_id = $1
+                    AND invoice_email_deliveries.status = 'sent'
+                UNION ALL
+                -- dunning reminders are sent by the scheduler, so they have no author
+                SELECT 'email_sent',
+                       'dunning_reminders',
+                       dunning_reminders.id,
+                       dunning_reminders.subject,
+                       dunning_reminders.recipient,
+                       NULL,
+                       NULL,
+                       NULL,
+                       dunning_reminders.created_at
+                FROM dunning_reminders
+                JOIN receivables ON dunning_reminders.receivable_id = receivables.id
+                WHERE receivables.customer_id = $1
+                    AND dunning_reminders.status = 'sent'
+            )
+            SELECT *
+            FROM timeline
+            WHERE $2::TIMESTAMPTZ IS NULL OR occurred_at < $2
+            ORDER BY occurred_at DESC, resource_id
+            LIMIT $3
+            "#,
+        )
+        .bind(customer_id)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn import(
+        &self,
+        rows: &[CustomerImportRow],
+        sub: Uuid,
+        apply: bool,
+    ) -> RepositoryResult<CustomerImportReport> {
+        // NOTE: a preview writes the customers as well and only rolls back at the end, so the
+        // e-mail matches and the rows rejected by constraints are reported exactly as on import
+        let import_id = Uuid::new_v4();
+        let mut tx = self.begin().await?;
+        let mut seen = HashSet::new();
+        let mut lines = Vec::with_capacity(rows.len());
+        let (mut customers_created, mut customers_updated) = (0, 0);
+
+        for row in rows {
+            let mut line = CustomerImportLine {
+                line: row.line,
+                name: row.name.clone(),
+                email: row.email.clone(),
+                customer_id: None,
+                customer_created: false,
+                customer_updated: false,
+                errors: row.errors.clone(),
+            };
+            let Some(input) = row.input.as_ref().filter(|_| line.errors.is_empty()) else {
+                lines.push(line);
+                continue;
+            };
+            if !seen.insert(row.email.to_lowercase()) {
+                line.errors
+                    .push("Az e-mail cím többször szerepel a fájlban!".to_owned());
+                lines.push(line);
+                continue;
+            }
+
+            let existing = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT id
+                FROM customers
+                WHERE deleted_at IS NULL AND lower(email) = lower($1)
+                LIMIT 1
+                FOR UPDATE
+                "#,
+            )
+            .bind(input.email.as_str()?)
+            .fetch_optional(&mut *tx)
+            .await?;
+            let contact_name = match &input.contact_name {
+                Some(v) => Some(v.as_str()?),
+                None => None,
+            };
+
+            let mut savepoint = tx.begin().await?;
+            let written = match existing {
+                // NOTE: rows that are identical to the stored customer are not counted as updates
+                Some(id) => sqlx::query_scalar::<_, Uuid>(
+                    r#"
+                    UPDATE customers
+                    SET name = $2,
+                        contact_name = $3,
+                        phone_number = $4,
+                        tax_number = $5,
+                        status = $6,
+                        customer_type = $7
+                    WHERE id = $1
+                        AND (name, contact_name, phone_number, tax_number, status, customer_type)
+                            IS DISTINCT FROM ($2, $3, $4, $5, $6, $7)
+                    RETURNING id
+                    "#,
+                )
+                .bind(id),
+                None => sqlx::query_scalar::<_, Uuid>(
+                    r#"
+                    INSERT INTO customers (
+                        email, name, contact_name, phone_number, tax_number, status,
+                        customer_type, created_by_id
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    RETURNING id
+                    "#,
+                )
+                .bind(input.email.as_str()?),
+            }
+            .bind(input.name.as_str()?)
+            .bind(contact_name)
+            .bind(input.phone_number.as_str())
+            .bind(input.tax_number.as_str())
+            .bind(input.status.as_str()?)
+            .bind(input.customer_type.as_str()?);
+            let written = match existing {
+                Some(_) => written.fetch_optional(&mut *savepoint).await,
+                None => written.bind(sub).fetch_optional(&mut *savepoint).await,
+            };
+            match written {
+                Ok(id) => {
+                    savepoint.commit().await?;
+                    line.customer_id = id.or(existing);
+                    line.customer_created = existing.is_none();
+                    line.customer_updated = existing.is_some() && id.is_some();
+                    if line.customer_created {
+                        customers_created += 1;
+                    } else if line.customer_updated {
+                        customers_updated += 1;
+                    }
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    let e = RepositoryError::from(e);
+                    if !e.is_unique_violation() {
+                        return Err(e);
+                    }
+                    line.errors
+                        .push("A megadot e-mail címmel már létezik vevő a rendszerben!".to_owned());
+                }
+            }
+            lines.push(line);
+        }
+
+        let error_count = lines.iter().filter(|line| !line.errors.is_empty()).count();
+        let applied = apply && error_count == 0;
+        if applied {
+            sqlx::query(
+                r#"
+                INSERT INTO customer_imports (
+                    id, row_count, customers_created, customers_updated, created_by_id
+                ) VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(import_id)
+            .bind(lines.len() as i32)
+            .bind(customers_created)
+            .bind(customers_updated)
+            .bind(sub)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+        } else {
+            tx.rollback().await?;
+        }
+
+        Ok(CustomerImportReport {
+            import_id: applied.then_some(import_id),
+            applied,
+            row_count: lines.len(),
+            error_count,
+            customers_created,
+            customers_updated,
+            lines,
+        })
+    }
+
+    async fn get_imports(&self) -> RepositoryResult<Vec<CustomerImport>> {
+        Ok(sqlx::query_as::<_, CustomerImport>(
+            r#"
+            SELECT customer_imports.id,
+                   customer_imports.row_count,
+                   customer_imports.customers_created,
+                   customer_imports.customers_updated,
+                   customer_imports.created_by_id,
+                   users.last_name || ' ' || users.first_name AS created_by,
+                   customer_imports.created_at
+            FROM customer_imports
+            JOIN users ON customer_imports.created_by_id = users.id
+            ORDER BY customer_imports.created_at DESC
+            LIMIT 100
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_data_export(&self, customer_id: Uuid) -> RepositoryResult<CustomerDataExport> {
+        // NOTE: every section is read from the same snapshot, so the package is consistent
+        let mut tx = self.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let customer = sqlx::query_as::<_, Customer>(
+            "SELECT * FROM customers WHERE deleted_at IS NULL AND id = $1",
+        )
+        .bind(customer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let contacts = sqlx::query_as::<_, CustomerContact>(
+            r#"
+            SELECT id, customer_id, name, role, email, phone_number, is_primary, created_by_id,
+                   created_at, updated_at
+            FROM customer_contacts
+            WHERE customer_id = $1
+                AND deleted_at IS NULL
+            ORDER BY is_primary DESC, name, created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let addresses = sqlx::query_as::<_, CustomerExportAddress>(
+            r#"
+            SELECT address.id,
+                   address.street_address,
+                   postal_codes.postal_code,
+                   cities.name AS city,
+                   states.name AS state,
+                   address.country_code,
+                   address.additional_info,
+                   address.created_at
+            FROM address_connect
+            JOIN address ON address_connect.address_id = address.id
+            JOIN cities ON address.city_id = cities.id
+            JOIN postal_codes ON cities.postal_code = postal_codes.id
+            JOIN states ON address.state_id = states.id
+            WHERE address_connect.addressable_type = 'customers'
+                AND address_connect.addressable_id = $1
+                AND address_connect.deleted_at IS NULL
+                AND address.deleted_at IS NULL
+            ORDER BY address.created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let notes = sqlx::query_as::<_, CustomerNote>(
+            r#"
+            SELECT comments.id,
+                   comments.commentable_id AS customer_id,
+                   comments.comment AS note,
+                   comments.created_by_id,
+                   users.last_name || ' ' || users.first_name AS created_by,
+                   comments.edited_at IS NOT NULL AS edited,
+                   comments.edited_at,
+                   comments.created_at
+            FROM comments
+            JOIN users ON comments.created_by_id = users.id
+            WHERE comments.commentable_type = 'customers'
+                AND comments.commentable_id = $1
+                AND comments.deleted_at IS NULL
+            ORDER BY comments.created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let documents = sqlx::query_as::<_, CustomerExportDocument>(
+            r#"
+            SELECT 'invoice' AS document_type,
+                   receivables.id,
+                   receivables.document_number AS reference,
+                   receivables.issue_date,
+                   receivables.amount,
+                   receivables.currency_code,
+                   receivables.status,
+                   NULL::TEXT AS details,
+                   receivables.created_at
+            FROM receivables
+            WHERE receivables.customer_id = $1
+                AND receivables.deleted_at IS NULL
+            UNION ALL
+            SELECT 'credit_note',
+                   credit_notes.id,
+                   credit_notes.document_number,
+                   credit_notes.issue_date,
+                   credit_notes.amount,
+                   credit_notes.currency_code,
+                   NULL,
+                   credit_notes.reason,
+                   credit_notes.created_at
+            FROM credit_notes
+            WHERE credit_notes.customer_id = $1
+            UNION ALL
+            SELECT 'quote',
+                   quotes.id,
+                   quotes.quote_number,
+                   quotes.created_at::DATE,
+                   NULL,
+                   quotes.currency_code,
+                   quotes.status,
+                   quotes.notes,
+                   quotes.created_at
+            FROM quotes
+            WHERE quotes.customer_id = $1
+                AND quotes.deleted_at IS NULL
+            UNION ALL
+            SELECT 'payment',
+                   payments.id,
+                   payments.reference,
+                   payments.paid_on,
+                   payments.amount,
+                   payments.currency_code,
+                   payments.payment_method,
+                   payments.note,
+                   payments.created_at
+            FROM payments
+            WHERE payments.customer_id = $1
+                AND payments.deleted_at IS NULL
+            UNION ALL
+            SELECT 'worksheet',
+                   worksheets.id,
+                   worksheets.name,
+                   worksheets.created_at::DATE,
+                   NULL,
+                   NULL,
+                   worksheets.status,
+                   worksheets.description,
+                   worksheets.created_at
+            FROM worksheets
+            WHERE worksheets.customer_id = $1
+                AND worksheets.deleted_at IS NULL
+            ORDER BY issue_date, created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let emails = sqlx::query_as::<_, CustomerExportEmail>(
+            r#"
+            SELECT 'invoice' AS email_type,
+                   invoice_email_deliveries.id,
+                   invoice_email_deliveries.recipient,
+                   invoice_email_deliveries.cc,
+                   invoice_email_deliveries.bcc,
+                   invoice_email_deliveries.subject::TEXT AS subject,
+                   invoice_email_deliveries.status,
+                   invoice_email_deliveries.sent_at,
+                   invoice_email_deliveries.created_at
+            FROM invoice_email_deliveries
+            JOIN receivables ON invoice_email_deliveries.receivable_id = receivables.id
+            WHERE receivables.customer_id = $1
+            UNION ALL
+            SELECT 'dunning_reminder',
+                   dunning_reminders.id,
+                   dunning_reminders.recipient,
+                   NULL,
+                   NULL,
+                   dunning_reminders.subject,
+                   dunning_reminders.status,
+                   CASE WHEN dunning_reminders.status = 'sent' THEN dunning_reminders.created_at END,
+                   dunning_reminders.created_at
+            FROM dunning_reminders
+            JOIN receivables ON dunning_reminders.receivable_id = receivables.id
+            WHERE receivables.customer_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(CustomerDataExport {
+            exported_at: Utc::now(),
+            customer,
+            contacts,
+            addresses,
+            notes,
+            documents,
+            emails,
+        })
+    }
+
+    async fn anonymize(
+        &self,
+        customer_id: Uuid,
+        sub: Uuid,
+    ) -> RepositoryResult<CustomerAnonymization> {
+        let mut tx = self.begin().await?;
+        sqlx::query("SELECT id FROM customers WHERE deleted_at IS NULL AND id = $1 FOR UPDATE")
+            .bind(customer_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let invoice_data_retained = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM receivables WHERE customer_id = $1)
+                OR EXISTS (SELECT 1 FROM credit_notes WHERE customer_id = $1)
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        // NOTE: the record is written first, so a second anonymization fails on the unique
+        // customer_id before touching any data
+        let anonymization = sqlx::query_as::<_, CustomerAnonymization>(
+            r#"
+            INSERT INTO customer_anonymizations (customer_id, invoice_data_retained, created_by_id)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(customer_id)
+        .bind(invoice_data_retained)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE customers
+            SET name = CASE WHEN $2 THEN name ELSE 'Anonimizált vevő' END,
+                tax_number = CASE WHEN $2 THEN tax_number END,
+                contact_name = NULL,
+                email = 'anonimizalt-' || id || '@anonimizalt.invalid',
+                phone_number = NULL,
+                status = 'inactive',
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(customer_id)
+        .bind(invoice_data_retained)
+        .execute(&mut *tx)
+        .await?;
+        if !invoice_data_retained {
+            sqlx::query(
+                r#"
+                UPDATE address
+                SET street_address = 'Anonimizált cím',
+                    additional_info = NULL,
+                    deleted_at = COALESCE(deleted_at, NOW())
+                WHERE id IN (SELECT address_id
+                             FROM address_connect
+                             WHERE addressable_type = 'customers' AND addressable_id = $1)
+                "#,
+            )
+            .bind(customer_id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                r#"
+                UPDATE address_connect
+                SET deleted_at = COALESCE(deleted_at, NOW())
+                WHERE addressable_type = 'customers' AND addressable_id = $1
+                "#,
+            )
+            .bind(customer_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for statement in [
+            r#"
+            UPDATE customer_contacts
+            SET name = 'Anonimizált kapcsolattartó',
+                role = NULL,
+                email = NULL,
+                phone_number = NULL,
+                is_primary = false,
+                deleted_at = COALESCE(deleted_at, NOW())
+            WHERE customer_id = $1
+            "#,
+            r#"
+            UPDATE comments
+            SET comment = 'Anonimizált jegyzet',
+                deleted_at = COALESCE(deleted_at, NOW())
+            WHERE commentable_type = 'customers' AND commentable_id = $1
+            "#,
+            "UPDATE collection_activities SET note = NULL WHERE customer_id = $1",
+            "UPDATE quotes SET notes = NULL WHERE customer_id = $1",
+            r#"
+            UPDATE invoice_email_deliveries
+            SET recipient = 'anonimizalt@anonimizalt.invalid',
+                cc = NULL,
+                bcc = NULL,
+                error = NULL
+            WHERE receivable_id IN (SELECT id FROM receivables WHERE customer_id = $1)
+            "#,
+            r#"
+            UPDATE dunning_reminders
+            SET recipient = 'anonimizalt@anonimizalt.invalid',
+                error = NULL
+            WHERE receivable_id IN (SELECT id FROM receivables WHERE customer_id = $1)
+            "#,
+        ] {
+            sqlx::query(statement)
+                .bind(customer_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(anonymization)
+    }
+
+    async fn get_credit_exposure(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<CustomerCreditExposure> {
+        // NOTE: only open invoices count, credit notes and payments already lower their balance
+        Ok(sqlx::query_as::<_, CustomerCreditExposure>(
+            r#"
+            SELECT customers.id                         AS customer_id,
+                   customers.credit_limit,
+                   customers.credit_limit_currency_code AS currency_code,
+                   COALESCE((SELECT SUM(receivables.amount - receivables.paid_amount
+                                        - receivables.credited_amount)
+                             FROM receivables
+                             WHERE receivables.customer_id = customers.id
+                               AND receivables.currency_code = customers.credit_limit_currency_code
+                               AND receivables.status = 'open'
+                               AND receivables.deleted_at IS NULL), 0) AS open_balance,
+                   credit_limit_settings.mode
+            FROM customers
+                     CROSS JOIN credit_limit_settings
+            WHERE customers.id = $1
+              AND customers.deleted_at IS NULL
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_credit_limit(
+        &self,
+        input: &CustomerCreditLimitInput,
+    ) -> RepositoryResult<CustomerCreditExposure> {
+        let result = sqlx::query(
+            r#"
+            UPDATE customers
+            SET credit_limit               = $1,
+                credit_limit_currency_code = $2,
+                updated_at                 = NOW()
+            WHERE id = $3
+              AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&input.credit_limit)
+        .bind(&input.currency_code)
+        .bind(input.customer_id)
+        .execute(self)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        self.get_credit_exposure(input.customer_id).await
+    }
+
+    async fn get_credit_limit_settings(&self) -> RepositoryResult<CreditLimitSettings> {
+        Ok(sqlx::query_as::<_, CreditLimitSettings>(
+            "SELECT mode, updated_by_id, updated_at FROM credit_limit_settings",
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_credit_limit_mode(
+        &self,
+        mode: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<CreditLimitSettings> {
+        Ok(sqlx::query_as::<_, CreditLimitSettings>(
+            r#"
+            UPDATE credit_limit_settings
+            SET mode = $1, updated_by_id = $2
+            RETURNING mode, updated_by_id, updated_at
+            "#,
+        )
+        .bind(mode)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_stage(
+        &self,
+        input: &CustomerStageInput,
+        sub: Uuid,
+    ) -> RepositoryResult<Option<CustomerStageTransition>> {
+        let mut tx = self.begin().await?;
+        let from_stage = sqlx::query_scalar::<_, String>(
+            "SELECT lifecycle_stage FROM customers WHERE deleted_at IS NULL AND id = $1 FOR UPDATE",
+        )
+        .bind(input.customer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if from_stage == input.stage {
+            return Ok(None);
+        }
+        sqlx::query("UPDATE customers SET lifecycle_stage = $1, updated_at = NOW() WHERE id = $2")
+            .bind(&input.stage)
+            .bind(input.customer_id)
+            .execute(&mut *tx)
+            .await?;
+        let transition = sqlx::query_as::<_, CustomerStageTransition>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO customer_stage_transitions (customer_id, from_stage, to_stage, note, created_by_id)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+            )
+            SELECT inserted.id,
+                   inserted.customer_id,
+                   inserted.from_stage,
+                   inserted.to_stage,
+                   inserted.note,
+                   inserted.created_by_id,
+                   users.last_name || ' ' || users.first_name AS created_by,
+                   inserted.created_at
+            FROM inserted
+            JOIN users ON inserted.created_by_id = users.id
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&from_stage)
+        .bind(&input.stage)
+        .bind(&input.note)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Some(transition))
+    }
+
+    async fn get_stage_history(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CustomerStageTransition>> {
+        Ok(sqlx::query_as::<_, CustomerStageTransition>(
+            r#"
+            SELECT customer_stage_transitions.id,
+                   customer_stage_transitions.customer_id,
+                   customer_stage_transitions.from_stage,
+                   customer_stage_transitions.to_stage,
+                   customer_stage_transitions.note,
+                   customer_stage_transitions.created_by_id,
+                   users.last_name || ' ' || users.first_name AS created_by,
+                   customer_stage_transitions.created_at
+            FROM customer_stage_transitions
+            JOIN users ON customer_stage_transitions.created_by_id = users.id
+            WHERE customer_stage_transitions.customer_id = $1
+            ORDER BY customer_stage_transitions.created_at DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_stage_counts(&self) -> RepositoryResult<Vec<CustomerStageCount>> {
+        // NOTE: every stage is listed in pipeline order, the empty ones with zero
+        Ok(sqlx::query_as::<_, CustomerStageCount>(
+            r#"
+            SELECT stages.stage,
+                   COUNT(customers.id) AS customer_count
+            FROM UNNEST($1::TEXT[]) WITH ORDINALITY AS stages (stage, position)
+            LEFT JOIN customers ON customers.lifecycle_stage = stages.stage
+                AND customers.deleted_at IS NULL
+            GROUP BY stages.stage, stages.position
+            ORDER BY stages.position
+            "#,
+        )
+        .bind(STAGES.to_vec())
+        .fetch_all(self)
+        .await?)
+    }
 }
@@ -36,6 +36,27 @@ pub fn routes<M: CustomersModuleInterface>(customers_module: Arc<M>) -> Router {
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
             .route("/print", get(handler::print::<M>))
+            .route("/set_sales_rep", put(handler::set_sales_rep::<M>))
+            .route("/duplicates", get(handler::duplicates::<M>))
+            .route("/merge", post(handler::merge::<M>))
+            .route("/timeline", get(handler::timeline::<M>))
+            .route("/import", post(handler::import::<M>))
+            .route("/import_history", get(handler::import_history::<M>))
+            .route("/data_export", get(handler::data_export::<M>))
+            .route("/anonymize", post(handler::anonymize::<M>))
+            .route("/credit_limit", get(handler::credit_limit::<M>))
+            .route("/set_credit_limit", put(handler::set_credit_limit::<M>))
+            .route(
+                "/credit_limit_settings",
+                get(handler::credit_limit_settings::<M>),
+            )
+            .route(
+                "/set_credit_limit_settings",
+                put(handler::set_credit_limit_settings::<M>),
+            )
+            .route("/set_stage", put(handler::set_stage::<M>))
+            .route("/stage_history", get(handler::stage_history::<M>))
+            .route("/pipeline", get(handler::pipeline::<M>))
             .layer(from_fn_with_state(customers_module.clone(), require_auth))
             .with_state(customers_module),
     )
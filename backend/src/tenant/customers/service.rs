@@ -25,12 +25,28 @@ use crate::common::pdf::PdfGenerator;
 use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::common::value_object::{ValueObjectError, ValueObjectRequired};
+use crate::tenant::currencies::types::CurrencyCode;
 use crate::tenant::customers::CustomersModuleInterface;
+use crate::tenant::customers::dto::credit_limit::{
+    CreditLimitSettingsInput, CustomerCreditLimitInput,
+};
+use crate::tenant::customers::dto::duplicate::{CustomerDuplicateQuery, CustomerMergeInput};
+use crate::tenant::customers::dto::import::{CustomerImportQuery, CustomerImportRow};
 use crate::tenant::customers::dto::print::CustomerResolvedPrint;
+use crate::tenant::customers::dto::sales_rep::CustomerSalesRep;
+use crate::tenant::customers::dto::stage::CustomerStageInput;
+use crate::tenant::customers::dto::timeline::CustomerTimelineQuery;
 use crate::tenant::customers::dto::user_input::CustomerUserInput;
-use crate::tenant::customers::model::{Customer, CustomerResolved};
+use crate::tenant::customers::model::{
+    CREDIT_LIMIT_MODES, CreditLimitSettings, Customer, CustomerAnonymization,
+    CustomerCreditExposure, CustomerDataExport, CustomerDuplicateCandidate, CustomerImport,
+    CustomerImportReport, CustomerResolved, CustomerStageCount, CustomerStageTransition,
+    CustomerTimelineEvent, STAGES,
+};
 use crate::tenant::customers::types::customer::{CustomerFilterBy, CustomerOrderBy};
 use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
@@ -42,6 +58,9 @@ use thiserror::Error;
 use tracing::Level;
 use uuid::Uuid;
 
+const DEFAULT_TIMELINE_LIMIT: i64 = 50;
+const MAX_TIMELINE_LIMIT: i64 = 200;
+
 #[derive(Debug, Error)]
 pub enum CustomersServiceError {
     #[error("Repository error: {0}")]
@@ -56,6 +75,12 @@ pub enum CustomersServiceError {
     #[error("A megadot e-mail címmel már létezik vevő a rendszerben!")]
     CustomerExists,
 
+    #[error("A vevő adatai már anonimizálva lettek!")]
+    AlreadyAnonymized,
+
+    #[error("Hasonló vevő már szerepel a törzsben")]
+    PossibleDuplicates(Vec<CustomerDuplicateCandidate>),
+
     #[error("PdfGen error: {0}")]
     PdfGenError(#[from] PdfGenError),
 
@@ -74,6 +99,17 @@ impl From<ServiceError> for CustomersServiceError {
     }
 }
 
+impl From<ValueObjectError> for CustomersServiceError {
+    fn from(value: ValueObjectError) -> Self {
+        match value {
+            ValueObjectError::InvalidInput(message) => {
+                CustomersServiceError::UnprocessableEntry(message)
+            }
+            value => CustomersServiceError::Repository(value.into()),
+        }
+    }
+}
+
 impl From<CustomersServiceError> for AppError {
     fn from(value: CustomersServiceError) -> Self {
         match value {
@@ -91,6 +127,20 @@ impl From<CustomersServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            CustomersServiceError::AlreadyAnonymized => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            CustomersServiceError::PossibleDuplicates(ref candidates) => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string(), "candidates": candidates}),
+            ),
             CustomersServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -120,6 +170,78 @@ impl From<CustomersServiceError> for AppError {
 
 type CustomersServiceResult<T> = Result<T, CustomersServiceError>;
 
+fn validate_credit_limit(
+    payload: &CustomerCreditLimitInput,
+) -> CustomersServiceResult<CustomerCreditLimitInput> {
+    let Some(credit_limit) = &payload.credit_limit else {
+        return Ok(CustomerCreditLimitInput {
+            currency_code: None,
+            ..payload.clone()
+        });
+    };
+    if *credit_limit < BigDecimal::zero() {
+        return Err(CustomersServiceError::UnprocessableEntry(
+            "A hitelkeret nem lehet negatív!",
+        ));
+    }
+    let currency_code = payload
+        .currency_code
+        .as_deref()
+        .map(|code| code.trim().to_uppercase())
+        .filter(|code| !code.is_empty())
+        .ok_or(CustomersServiceError::UnprocessableEntry(
+            "Hitelkeret megadása esetén a pénznem megadása kötelező!",
+        ))?;
+    currency_code
+        .parse::<ValueObjectRequired<CurrencyCode>>()
+        .map_err(|_| CustomersServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR))?;
+    Ok(CustomerCreditLimitInput {
+        customer_id: payload.customer_id,
+        credit_limit: Some(credit_limit.clone()),
+        currency_code: Some(currency_code),
+    })
+}
+
+const STAGE_VALIDATION_ERROR: &str = "Hibás életciklus-szakasz!";
+
+fn validate_stage_filter(stage: Option<&str>) -> CustomersServiceResult<Option<String>> {
+    match stage.map(str::trim).filter(|stage| !stage.is_empty()) {
+        Some(stage) if !STAGES.contains(&stage) => Err(CustomersServiceError::UnprocessableEntry(
+            STAGE_VALIDATION_ERROR,
+        )),
+        stage => Ok(stage.map(str::to_string)),
+    }
+}
+
+fn validate_stage_input(
+    payload: &CustomerStageInput,
+) -> CustomersServiceResult<CustomerStageInput> {
+    let stage = payload.stage.trim();
+    if !STAGES.contains(&stage) {
+        return Err(CustomersServiceError::UnprocessableEntry(
+            STAGE_VALIDATION_ERROR,
+        ));
+    }
+    let note = payload
+        .note
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > 1000)
+    {
+        return Err(CustomersServiceError::UnprocessableEntry(
+            "A megjegyzés legfeljebb 1000 karakter lehet!",
+        ));
+    }
+    Ok(CustomerStageInput {
+        customer_id: payload.customer_id,
+        stage: stage.to_string(),
+        note,
+    })
+}
+
 pub trait CustomerService {
     fn insert(
         &self,
@@ -135,10 +257,75 @@ pub trait CustomerService {
         payload: &CustomerUserInput,
     ) -> impl Future<Output = CustomersServiceResult<Customer>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = CustomersServiceResult<()>>;
+    fn set_sales_rep(
+        &self,
+        payload: &CustomerSalesRep,
+    ) -> impl Future<Output = CustomersServiceResult<()>> + Send;
+    fn check_duplicates(
+        &self,
+        payload: &CustomerUserInput,
+    ) -> impl Future<Output = CustomersServiceResult<()>> + Send;
+    fn get_duplicates(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = CustomersServiceResult<Vec<CustomerDuplicateCandidate>>> + Send;
+    fn merge(
+        &self,
+        payload: &CustomerMergeInput,
+    ) -> impl Future<Output = CustomersServiceResult<Customer>> + Send;
+    fn get_timeline(
+        &self,
+        payload: &CustomerTimelineQuery,
+    ) -> impl Future<Output = CustomersServiceResult<Vec<CustomerTimelineEvent>>> + Send;
+    fn import(
+        &self,
+        payload: &CustomerImportQuery,
+        data: &[u8],
+    ) -> impl Future<Output = CustomersServiceResult<CustomerImportReport>> + Send;
+    fn get_import_history(
+        &self,
+    ) -> impl Future<Output = CustomersServiceResult<Vec<CustomerImport>>> + Send;
+    fn get_data_export(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = CustomersServiceResult<CustomerDataExport>> + Send;
+    fn anonymize(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = CustomersServiceResult<CustomerAnonymization>> + Send;
+    fn get_credit_exposure(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = CustomersServiceResult<CustomerCreditExposure>> + Send;
+    fn set_credit_limit(
+        &self,
+        payload: &CustomerCreditLimitInput,
+    ) -> impl Future<Output = CustomersServiceResult<CustomerCreditExposure>> + Send;
+    fn get_credit_limit_settings(
+        &self,
+    ) -> impl Future<Output = CustomersServiceResult<CreditLimitSettings>> + Send;
+    fn set_credit_limit_settings(
+        &self,
+        payload: &CreditLimitSettingsInput,
+    ) -> impl Future<Output = CustomersServiceResult<CreditLimitSettings>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<CustomerOrderBy, CustomerFilterBy>,
+        segment_id: Option<Uuid>,
+        stage: Option<&str>,
+        tags: Option<Vec<Uuid>>,
     ) -> impl Future<Output = CustomersServiceResult<(PaginatorMeta, Vec<CustomerResolved>)>> + Send;
+    fn set_stage(
+        &self,
+        payload: &CustomerStageInput,
+    ) -> impl Future<Output = CustomersServiceResult<CustomerStageTransition>> + Send;
+    fn get_stage_history(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = CustomersServiceResult<Vec<CustomerStageTransition>>> + Send;
+    fn get_stage_counts(
+        &self,
+    ) -> impl Future<Output = CustomersServiceResult<Vec<CustomerStageCount>>> + Send;
     fn print(
         &self,
         payload: &[CustomerResolvedPrint],
@@ -219,10 +406,265 @@ where
             .delete_by_id(payload)
             .await?)
     }
+    async fn set_sales_rep(&self, payload: &CustomerSalesRep) -> CustomersServiceResult<()> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .set_sales_rep(payload)
+            .await?)
+    }
+    async fn check_duplicates(&self, payload: &CustomerUserInput) -> CustomersServiceResult<()> {
+        let candidates = self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .find_duplicates(&CustomerDuplicateQuery {
+                name: payload.name.as_str()?.to_string(),
+                email: payload.email.as_str()?.to_string(),
+                tax_number: payload.tax_number.as_str().map(str::to_string),
+                exclude_id: payload.id.as_uuid(),
+            })
+            .await?;
+        if candidates.is_empty() {
+            Ok(())
+        } else {
+            Err(CustomersServiceError::PossibleDuplicates(candidates))
+        }
+    }
+    async fn get_duplicates(
+        &self,
+        id: Uuid,
+    ) -> CustomersServiceResult<Vec<CustomerDuplicateCandidate>> {
+        let repo = self.module().customers_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomersServiceError::Unauthorized)?,
+        )?;
+        let customer = repo.get_by_id(id).await?;
+        Ok(repo
+            .find_duplicates(&CustomerDuplicateQuery {
+                name: customer.name,
+                email: customer.email,
+                tax_number: customer.tax_number,
+                exclude_id: Some(customer.id),
+            })
+            .await?)
+    }
+    async fn merge(&self, payload: &CustomerMergeInput) -> CustomersServiceResult<Customer> {
+        if payload.duplicate_id == payload.surviving_id {
+            return Err(CustomersServiceError::UnprocessableEntry(
+                "Egy vevő nem vonható össze önmagával!",
+            ));
+        }
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .merge(payload)
+            .await?)
+    }
+    // NOTE: the customer is loaded first so an unknown customer is a 404 instead of an empty history
+    async fn get_timeline(
+        &self,
+        payload: &CustomerTimelineQuery,
+    ) -> CustomersServiceResult<Vec<CustomerTimelineEvent>> {
+        let limit = payload.limit.unwrap_or(DEFAULT_TIMELINE_LIMIT);
+        if !(1..=MAX_TIMELINE_LIMIT).contains(&limit) {
+            return Err(CustomersServiceError::UnprocessableEntry(
+                "A lekérdezett események száma 1 és 200 között lehet!",
+            ));
+        }
+        let repo = self.module().customers_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(CustomersServiceError::Unauthorized)?,
+        )?;
+        repo.get_by_id(payload.uuid).await?;
+        Ok(repo
+            .get_timeline(payload.uuid, payload.before, limit)
+            .await?)
+    }
+    async fn import(
+        &self,
+        payload: &CustomerImportQuery,
+        data: &[u8],
+    ) -> CustomersServiceResult<CustomerImportReport> {
+        let rows = CustomerImportRow::parse_csv(data, payload)
+            .map_err(CustomersServiceError::UnprocessableEntry)?;
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .import(&rows, self.claims()?.sub(), !payload.dry_run)
+            .await?)
+    }
+    async fn get_import_history(&self) -> CustomersServiceResult<Vec<CustomerImport>> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_imports()
+            .await?)
+    }
+    async fn get_data_export(&self, payload: Uuid) -> CustomersServiceResult<CustomerDataExport> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_data_export(payload)
+            .await?)
+    }
+    async fn anonymize(&self, payload: Uuid) -> CustomersServiceResult<CustomerAnonymization> {
+        self.module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .anonymize(payload, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    CustomersServiceError::AlreadyAnonymized
+                } else {
+                    e.into()
+                }
+            })
+    }
+    async fn get_credit_exposure(
+        &self,
+        payload: Uuid,
+    ) -> CustomersServiceResult<CustomerCreditExposure> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_credit_exposure(payload)
+            .await?)
+    }
+    async fn set_credit_limit(
+        &self,
+        payload: &CustomerCreditLimitInput,
+    ) -> CustomersServiceResult<CustomerCreditExposure> {
+        let input = validate_credit_limit(payload)?;
+        self.module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .set_credit_limit(&input)
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    CustomersServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+                } else {
+                    e.into()
+                }
+            })
+    }
+    async fn get_credit_limit_settings(&self) -> CustomersServiceResult<CreditLimitSettings> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_credit_limit_settings()
+            .await?)
+    }
+    async fn set_credit_limit_settings(
+        &self,
+        payload: &CreditLimitSettingsInput,
+    ) -> CustomersServiceResult<CreditLimitSettings> {
+        if !CREDIT_LIMIT_MODES.contains(&payload.mode.as_str()) {
+            return Err(CustomersServiceError::UnprocessableEntry(
+                "Hibás hitelkeret-kezelési mód!",
+            ));
+        }
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .set_credit_limit_mode(&payload.mode, self.claims()?.sub())
+            .await?)
+    }
     async fn get_paged(
         &self,
         query: &ResourceQuery<CustomerOrderBy, CustomerFilterBy>,
+        segment_id: Option<Uuid>,
+        stage: Option<&str>,
+        tags: Option<Vec<Uuid>>,
     ) -> CustomersServiceResult<(PaginatorMeta, Vec<CustomerResolved>)> {
+        let stage = validate_stage_filter(stage)?;
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_paged(query, segment_id, stage, tags)
+            .await?)
+    }
+    async fn set_stage(
+        &self,
+        payload: &CustomerStageInput,
+    ) -> CustomersServiceResult<CustomerStageTransition> {
+        let input = validate_stage_input(payload)?;
+        self.module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .set_stage(&input, self.claims()?.sub())
+            .await?
+            .ok_or(CustomersServiceError::UnprocessableEntry(
+                "A vevő már ebben az életciklus-szakaszban van!",
+            ))
+    }
+    async fn get_stage_history(
+        &self,
+        payload: Uuid,
+    ) -> CustomersServiceResult<Vec<CustomerStageTransition>> {
+        Ok(self
+            .module()
+            .customers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(CustomersServiceError::Unauthorized)?,
+            )?
+            .get_stage_history(payload)
+            .await?)
+    }
+    async fn get_stage_counts(&self) -> CustomersServiceResult<Vec<CustomerStageCount>> {
         Ok(self
             .module()
             .customers_repo(
@@ -230,7 +672,7 @@ where
                     .active_tenant()
                     .ok_or(CustomersServiceError::Unauthorized)?,
             )?
-            .get_paged(query)
+            .get_stage_counts()
             .await?)
     }
     async fn print(&self, payload: &[CustomerResolvedPrint]) -> CustomersServiceResult<Vec<u8>> {
@@ -258,8 +700,10 @@ where
             contact_name: None,
             email: "test.customer@example.com".to_string(),
             phone_number: Some("+36301234567".to_string()),
+            tax_number: None,
             status: "active".to_string(),
             customer_type: "natural".to_string(),
+            lifecycle_stage: "active".to_string(),
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: test_time,
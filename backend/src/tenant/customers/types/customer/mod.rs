@@ -24,6 +24,7 @@ pub(crate) mod name;
 pub(crate) mod order_by;
 pub(crate) mod phone_number;
 pub(crate) mod status;
+pub(crate) mod tax_number;
 
 pub(crate) use contact_name::ContactName as CustomerContactName;
 pub(crate) use customer_type::CustomerType;
@@ -32,3 +33,4 @@ pub(crate) use name::Name as CustomerName;
 pub(crate) use order_by::OrderBy as CustomerOrderBy;
 pub(crate) use phone_number::PhoneNumber as CustomerPhoneNumber;
 pub(crate) use status::Status as CustomerStatus;
+pub(crate) use tax_number::TaxNumber as CustomerTaxNumber;
@@ -0,0 +1,86 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::common::value_object::*;
+use regex::Regex;
+use std::fmt::Display;
+
+/// Hungarian tax number (`12345678-1-42`) or EU community VAT number (`HU12345678`)
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaxNumber(String);
+
+impl TaxNumber {
+    pub const VALIDATION_ERROR: &'static str = "Hibás adószám formátum";
+}
+
+impl ValueObjectData for TaxNumber {
+    type DataType = String;
+
+    fn new(data: &str) -> ValueObjectResult<Option<Self>> {
+        let data_trim = data.trim();
+        if !data_trim.is_empty() {
+            Ok(Some(Self(data_trim.to_uppercase())))
+        } else {
+            Ok(None)
+        }
+    }
+    fn validate(&self) -> Result<(), ValueObjectError> {
+        match Regex::new(r##"^(\d{8}-\d-\d{2}|[A-Z]{2}[0-9A-Z]{8,12})$"##)?.is_match(&self.0) {
+            true => Ok(()),
+            false => Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR)),
+        }
+    }
+
+    fn get_data(&self) -> &Self::DataType {
+        &self.0
+    }
+}
+
+impl Display for TaxNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_hungarian_tax_number() {
+        let tax_number = "12345678-2-42"
+            .parse::<ValueObjectRequired<TaxNumber>>()
+            .unwrap();
+        assert_eq!(tax_number.as_str().unwrap(), "12345678-2-42");
+    }
+
+    #[test]
+    fn test_valid_community_tax_number_is_uppercased() {
+        let tax_number = " hu12345678 "
+            .parse::<ValueObjectRequired<TaxNumber>>()
+            .unwrap();
+        assert_eq!(tax_number.as_str().unwrap(), "HU12345678");
+    }
+
+    #[test]
+    fn test_invalid_tax_number() {
+        for case in ["1234567-2-42", "12345678242", "12345678-2-4a", "H12345678"] {
+            assert!(case.parse::<ValueObjectRequired<TaxNumber>>().is_err());
+        }
+    }
+}
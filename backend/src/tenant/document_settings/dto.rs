@@ -0,0 +1,92 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::pdf::PdfLogo;
+use crate::tenant::document_settings::model::DocumentSettings;
+use image::ImageFormat;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_LOGO_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DocumentSettingsInput {
+    pub company_name: Option<String>,
+    pub tax_number: Option<String>,
+    pub address: Option<String>,
+    pub bank_account: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub footer_note: Option<String>,
+    pub invoice_email_cc: Option<String>,
+    pub invoice_email_bcc: Option<String>,
+    pub worksheet_layout: Option<String>,
+    pub worksheet_hide_logo: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogoUpload {
+    pub data: Vec<u8>,
+}
+
+impl LogoUpload {
+    // NOTE: the logo is recognised by its content, typst can only embed PNG and JPEG
+    pub fn format(&self) -> Option<(&'static str, &'static str)> {
+        match image::guess_format(&self.data) {
+            Ok(ImageFormat::Png) => Some(("image/png", "png")),
+            Ok(ImageFormat::Jpeg) => Some(("image/jpeg", "jpg")),
+            _ => None,
+        }
+    }
+}
+
+pub fn logo_extension(content_type: &str) -> &'static str {
+    match content_type {
+        "image/jpeg" => "jpg",
+        _ => "png",
+    }
+}
+
+/// The issuer block printed on the top of every customer facing document
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct Letterhead {
+    pub company_name: Option<String>,
+    pub tax_number: Option<String>,
+    pub address: Option<String>,
+    pub bank_account: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub footer_note: Option<String>,
+    #[serde(skip)]
+    pub logo: Option<PdfLogo>,
+}
+
+impl From<DocumentSettings> for Letterhead {
+    fn from(value: DocumentSettings) -> Self {
+        Self {
+            company_name: value.company_name,
+            tax_number: value.tax_number,
+            address: value.address,
+            bank_account: value.bank_account,
+            email: value.email,
+            phone_number: value.phone_number,
+            footer_note: value.footer_note,
+            logo: None,
+        }
+    }
+}
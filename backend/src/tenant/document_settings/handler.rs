@@ -0,0 +1,366 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::document_settings::DocumentSettingsModuleInterface;
+use crate::tenant::document_settings::dto::{DocumentSettingsInput, LogoUpload};
+use crate::tenant::document_settings::service::{
+    DocumentSettingsService, DocumentSettingsServiceError,
+};
+use axum::extract::{Multipart, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+async fn read_logo(mut multipart: Multipart) -> Result<LogoUpload, DocumentSettingsServiceError> {
+    let invalid = |_| DocumentSettingsServiceError::UnprocessableEntry("Hibás feltöltési kérés!");
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        if field.name() == Some("file") {
+            return Ok(LogoUpload {
+                data: field.bytes().await.map_err(invalid)?.to_vec(),
+            });
+        }
+    }
+    Err(DocumentSettingsServiceError::UnprocessableEntry(
+        "A fájl megadása kötelező!",
+    ))
+}
+
+pub async fn get<M: DocumentSettingsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(document_settings_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), document_settings_module.clone());
+    let result = map_handler_err(service.get().await, document_settings_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        document_settings_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: DocumentSettingsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(document_settings_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DocumentSettingsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), document_settings_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        document_settings_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        document_settings_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn upload_logo<M: DocumentSettingsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(document_settings_module): State<Arc<M>>,
+    multipart: Multipart,
+) -> HandlerResult {
+    let payload =
+        map_handler_err(read_logo(multipart).await, document_settings_module.clone()).await?;
+    let service = Service::new(Some(&claims), document_settings_module.clone());
+    let result = map_handler_err(
+        service.upload_logo(payload).await,
+        document_settings_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        document_settings_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn logo<M: DocumentSettingsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(document_settings_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), document_settings_module.clone());
+    let (content_type, data) =
+        map_handler_err(service.get_logo().await, document_settings_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+    Ok((StatusCode::OK, headers, data).into_response())
+}
+
+pub async fn delete_logo<M: DocumentSettingsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(document_settings_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), document_settings_module.clone());
+    map_handler_err(
+        service.delete_logo().await,
+        document_settings_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A logó törlése sikeresen megtörtént",
+            ))
+            .build(),
+        document_settings_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::common::storage::MockFileStorage;
+    use crate::tenant::document_settings::model::DocumentSettings;
+    use crate::tenant::document_settings::{
+        self, repository::MockDocumentSettingsRepository, tests::MockDocumentSettingsModule,
+    };
+    use crate::tenant::products::dto::attachment::tests::png;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockDocumentSettingsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let storage = Arc::new(storage);
+        let mut document_settings_module = MockDocumentSettingsModule::new();
+        document_settings_module
+            .expect_document_settings_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        document_settings_module
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        document_settings_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(document_settings::routes::routes(Arc::new(
+                document_settings_module,
+            ))),
+        )
+    }
+
+    fn logo_request(active_tenant_id: Uuid, data: &[u8]) -> Request<Body> {
+        let boundary = "obvia-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"logo.png\"\r\n\
+             Content-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .method("POST")
+            .uri("/api/document_settings/upload_logo")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn settings(logo_storage_key: Option<&str>) -> DocumentSettings {
+        DocumentSettings {
+            company_name: Some("Obvia Kft.".to_string()),
+            logo_content_type: logo_storage_key.map(|_| "image/png".to_string()),
+            logo_storage_key: logo_storage_key.map(str::to_string),
+            updated_at: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_trims_and_clears_empty_values() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockDocumentSettingsRepository::new();
+        repo.expect_update()
+            .times(1)
+            .withf(|input, _| {
+                input.company_name.as_deref() == Some("Obvia Kft.")
+                    && input.tax_number.is_none()
+                    && input.footer_note.is_none()
+            })
+            .returning(|_, _| Ok(settings(None)));
+
+        let response = app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("PUT")
+                    .uri("/api/document_settings/update")
+                    .body(Body::from(
+                        json!({
+                            "company_name": "  Obvia Kft. ",
+                            "tax_number": "  ",
+                            "address": null,
+                            "bank_account": null,
+                            "email": null,
+                            "phone_number": null,
+                            "footer_note": ""
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_unknown_worksheet_layout() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockDocumentSettingsRepository::new();
+        repo.expect_update().never();
+
+        let response = app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("PUT")
+                    .uri("/api/document_settings/update")
+                    .body(Body::from(
+                        json!({
+                            "company_name": "Obvia Kft.",
+                            "worksheet_layout": "compact",
+                            "worksheet_hide_logo": true
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_upload_logo_rejects_unsupported_format() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockDocumentSettingsRepository::new();
+        repo.expect_set_logo().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().never();
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(logo_request(active_tenant_id, b"GIF89a"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_upload_logo_replaces_previous_file() {
+        let active_tenant_id = Uuid::new_v4();
+        let previous_key = format!("{active_tenant_id}/document_settings/logo_old.png");
+        let mut repo = MockDocumentSettingsRepository::new();
+        repo.expect_get().times(1).returning({
+            let previous_key = previous_key.clone();
+            move || Ok(settings(Some(&previous_key)))
+        });
+        repo.expect_set_logo()
+            .times(1)
+            .withf(|key, content_type, _| {
+                key.as_deref().is_some_and(|key| key.ends_with(".png"))
+                    && content_type.as_deref() == Some("image/png")
+            })
+            .returning(|key, _, _| Ok(settings(key.as_deref())));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_put()
+            .times(1)
+            .withf(move |key, content_type, _| {
+                key.starts_with(&format!("{active_tenant_id}/document_settings/"))
+                    && content_type == "image/png"
+            })
+            .returning(|_, _, _| Ok(()));
+        storage
+            .expect_delete()
+            .times(1)
+            .with(eq(previous_key))
+            .returning(|_| Ok(()));
+
+        let response = app(repo, storage, active_tenant_id)
+            .oneshot(logo_request(active_tenant_id, &png(64, 32)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct DocumentSettings {
+    pub company_name: Option<String>,
+    pub tax_number: Option<String>,
+    pub address: Option<String>,
+    pub bank_account: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub footer_note: Option<String>,
+    #[serde(skip)]
+    pub logo_storage_key: Option<String>,
+    pub logo_content_type: Option<String>,
+    pub invoice_email_cc: Option<String>,
+    pub invoice_email_bcc: Option<String>,
+    pub worksheet_layout: String,
+    pub worksheet_hide_logo: bool,
+    pub updated_by_id: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct DocumentRecipient {
+    pub name: String,
+    pub email: String,
+    pub phone_number: Option<String>,
+}
@@ -0,0 +1,136 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::document_settings::dto::DocumentSettingsInput;
+use crate::tenant::document_settings::model::{DocumentRecipient, DocumentSettings};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DocumentSettingsRepository: Send + Sync {
+    async fn get(&self) -> RepositoryResult<DocumentSettings>;
+    async fn update(
+        &self,
+        input: &DocumentSettingsInput,
+        sub: Uuid,
+    ) -> RepositoryResult<DocumentSettings>;
+    async fn set_logo(
+        &self,
+        storage_key: Option<String>,
+        content_type: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<DocumentSettings>;
+    async fn get_recipient(&self, customer_id: Uuid) -> RepositoryResult<DocumentRecipient>;
+}
+
+#[async_trait]
+impl DocumentSettingsRepository for PgPool {
+    async fn get(&self) -> RepositoryResult<DocumentSettings> {
+        Ok(
+            sqlx::query_as::<_, DocumentSettings>("SELECT * FROM document_settings")
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn update(
+        &self,
+        input: &DocumentSettingsInput,
+        sub: Uuid,
+    ) -> RepositoryResult<DocumentSettings> {
+        Ok(sqlx::query_as::<_, DocumentSettings>(
+            r#"
+            UPDATE document_settings
+            SET company_name = $1,
+                tax_number = $2,
+                address = $3,
+                bank_account = $4,
+                email = $5,
+                phone_number = $6,
+                footer_note = $7,
+                invoice_email_cc = $8,
+                invoice_email_bcc = $9,
+                worksheet_layout = COALESCE($10, 'detailed'),
+                worksheet_hide_logo = COALESCE($11, false),
+                updated_by_id = $12
+            RETURNING *
+            "#,
+        )
+        .bind(&input.company_name)
+        .bind(&input.tax_number)
+        .bind(&input.address)
+        .bind(&input.bank_account)
+        .bind(&input.email)
+        .bind(&input.phone_number)
+        .bind(&input.footer_note)
+        .bind(&input.invoice_email_cc)
+        .bind(&input.invoice_email_bcc)
+        .bind(&input.worksheet_layout)
+        .bind(input.worksheet_hide_logo)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_logo(
+        &self,
+        storage_key: Option<String>,
+        content_type: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<DocumentSettings> {
+        Ok(sqlx::query_as::<_, DocumentSettings>(
+            r#"
+            UPDATE document_settings
+            SET logo_storage_key = $1,
+                logo_content_type = $2,
+                updated_by_id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(storage_key)
+        .bind(content_type)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    // NOTE: the primary contact is the default recipient, the customer's own details are the fallback
+    async fn get_recipient(&self, customer_id: Uuid) -> RepositoryResult<DocumentRecipient> {
+        Ok(sqlx::query_as::<_, DocumentRecipient>(
+            r#"
+            SELECT customers.name,
+                   COALESCE(customer_contacts.email, customers.email) AS email,
+                   COALESCE(customer_contacts.phone_number, customers.phone_number) AS phone_number
+            FROM customers
+            LEFT JOIN customer_contacts ON customer_contacts.customer_id = customers.id
+                AND customer_contacts.is_primary
+                AND customer_contacts.deleted_at IS NULL
+            WHERE customers.id = $1
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(self)
+        .await?)
+    }
+}
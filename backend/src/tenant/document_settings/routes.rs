@@ -0,0 +1,49 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::DocumentSettingsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use crate::tenant::document_settings::dto::MAX_LOGO_SIZE;
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: DocumentSettingsModuleInterface>(document_settings_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/document_settings",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/update", put(handler::update::<M>))
+            .route(
+                "/upload_logo",
+                post(handler::upload_logo::<M>)
+                    .layer(DefaultBodyLimit::max(MAX_LOGO_SIZE + 64 * 1024)),
+            )
+            .route("/logo", get(handler::logo::<M>))
+            .route("/delete_logo", delete(handler::delete_logo::<M>))
+            .layer(from_fn_with_state(
+                document_settings_module.clone(),
+                require_auth,
+            ))
+            .with_state(document_settings_module),
+    )
+}
@@ -0,0 +1,357 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error::{RepositoryError, RepositoryResult};
+use crate::common::pdf::PdfLogo;
+use crate::common::service::{Service, ServiceError};
+use crate::common::storage::{FileStorage, StorageError};
+use crate::common::utils::parse_email_list;
+use crate::tenant::document_settings::DocumentSettingsModuleInterface;
+use crate::tenant::document_settings::dto::{
+    DocumentSettingsInput, Letterhead, LogoUpload, MAX_LOGO_SIZE, logo_extension,
+};
+use crate::tenant::document_settings::model::DocumentSettings;
+use crate::tenant::document_settings::repository::DocumentSettingsRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::{Level, error};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum DocumentSettingsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+impl From<ServiceError> for DocumentSettingsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => DocumentSettingsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<DocumentSettingsServiceError> for AppError {
+    fn from(value: DocumentSettingsServiceError) -> Self {
+        match value {
+            DocumentSettingsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            DocumentSettingsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            DocumentSettingsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type DocumentSettingsServiceResult<T> = Result<T, DocumentSettingsServiceError>;
+
+fn optional_text(
+    value: &Option<String>,
+    max_length: usize,
+    message: &'static str,
+) -> DocumentSettingsServiceResult<Option<String>> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) if value.chars().count() > max_length => {
+            Err(DocumentSettingsServiceError::UnprocessableEntry(message))
+        }
+        Some(value) => Ok(Some(value.to_string())),
+    }
+}
+
+fn email_list(
+    value: &Option<String>,
+    message: &'static str,
+) -> DocumentSettingsServiceResult<Option<String>> {
+    let Some(value) = optional_text(value, 1000, message)? else {
+        return Ok(None);
+    };
+    let addresses = parse_email_list(&value)
+        .ok_or(DocumentSettingsServiceError::UnprocessableEntry(message))?;
+    Ok(Some(
+        addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    ))
+}
+
+fn validate_settings(
+    payload: &DocumentSettingsInput,
+) -> DocumentSettingsServiceResult<DocumentSettingsInput> {
+    Ok(DocumentSettingsInput {
+        company_name: optional_text(
+            &payload.company_name,
+            255,
+            "A cégnév legfeljebb 255 karakter lehet!",
+        )?,
+        tax_number: optional_text(
+            &payload.tax_number,
+            50,
+            "Az adószám legfeljebb 50 karakter lehet!",
+        )?,
+        address: optional_text(
+            &payload.address,
+            500,
+            "A cím legfeljebb 500 karakter lehet!",
+        )?,
+        bank_account: optional_text(
+            &payload.bank_account,
+            100,
+            "A bankszámlaszám legfeljebb 100 karakter lehet!",
+        )?,
+        email: optional_text(
+            &payload.email,
+            255,
+            "Az e-mail cím legfeljebb 255 karakter lehet!",
+        )?,
+        phone_number: optional_text(
+            &payload.phone_number,
+            50,
+            "A telefonszám legfeljebb 50 karakter lehet!",
+        )?,
+        footer_note: optional_text(
+            &payload.footer_note,
+            2000,
+            "A lábléc legfeljebb 2000 karakter lehet!",
+        )?,
+        invoice_email_cc: email_list(
+            &payload.invoice_email_cc,
+            "A számla másolatot kapó (CC) címek formátuma nem megfelelő!",
+        )?,
+        invoice_email_bcc: email_list(
+            &payload.invoice_email_bcc,
+            "A számla titkos másolatot kapó (BCC) címek formátuma nem megfelelő!",
+        )?,
+        worksheet_layout: match payload.worksheet_layout.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(layout @ ("detailed" | "summary")) => Some(layout.to_string()),
+            Some(_) => {
+                return Err(DocumentSettingsServiceError::UnprocessableEntry(
+                    "A munkalap elrendezése csak részletes vagy összesítő lehet!",
+                ));
+            }
+        },
+        worksheet_hide_logo: payload.worksheet_hide_logo,
+    })
+}
+
+// NOTE: a missing logo file should not prevent issuing documents, it is logged and left out
+pub async fn load_letterhead(
+    repo: &(dyn DocumentSettingsRepository + Send + Sync),
+    storage: &(dyn FileStorage + Send + Sync),
+) -> RepositoryResult<Letterhead> {
+    Ok(letterhead_from_settings(repo.get().await?, storage).await)
+}
+
+pub async fn letterhead_from_settings(
+    settings: DocumentSettings,
+    storage: &(dyn FileStorage + Send + Sync),
+) -> Letterhead {
+    let logo = match (&settings.logo_storage_key, &settings.logo_content_type) {
+        (Some(key), Some(content_type)) => match storage.get(key).await {
+            Ok(data) => Some(PdfLogo {
+                data,
+                extension: logo_extension(content_type),
+            }),
+            Err(e) => {
+                error!("Could not load document logo {}: {}", key, e);
+                None
+            }
+        },
+        _ => None,
+    };
+    Letterhead {
+        logo,
+        ..settings.into()
+    }
+}
+
+pub trait DocumentSettingsService {
+    fn get(&self) -> impl Future<Output = DocumentSettingsServiceResult<DocumentSettings>> + Send;
+    fn update(
+        &self,
+        payload: &DocumentSettingsInput,
+    ) -> impl Future<Output = DocumentSettingsServiceResult<DocumentSettings>> + Send;
+    fn upload_logo(
+        &self,
+        payload: LogoUpload,
+    ) -> impl Future<Output = DocumentSettingsServiceResult<DocumentSettings>> + Send;
+    fn get_logo(
+        &self,
+    ) -> impl Future<Output = DocumentSettingsServiceResult<(String, Vec<u8>)>> + Send;
+    fn delete_logo(&self) -> impl Future<Output = DocumentSettingsServiceResult<()>> + Send;
+}
+
+impl<'a, T> DocumentSettingsService for Service<'a, T>
+where
+    T: DocumentSettingsModuleInterface,
+{
+    async fn get(&self) -> DocumentSettingsServiceResult<DocumentSettings> {
+        Ok(self
+            .module()
+            .document_settings_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DocumentSettingsServiceError::Unauthorized)?,
+            )?
+            .get()
+            .await?)
+    }
+
+    async fn update(
+        &self,
+        payload: &DocumentSettingsInput,
+    ) -> DocumentSettingsServiceResult<DocumentSettings> {
+        let input = validate_settings(payload)?;
+        Ok(self
+            .module()
+            .document_settings_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DocumentSettingsServiceError::Unauthorized)?,
+            )?
+            .update(&input, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn upload_logo(
+        &self,
+        payload: LogoUpload,
+    ) -> DocumentSettingsServiceResult<DocumentSettings> {
+        if payload.data.len() > MAX_LOGO_SIZE {
+            return Err(DocumentSettingsServiceError::UnprocessableEntry(
+                "A logó mérete legfeljebb 1 MB lehet!",
+            ));
+        }
+        let (content_type, extension) =
+            payload
+                .format()
+                .ok_or(DocumentSettingsServiceError::UnprocessableEntry(
+                    "Csak PNG vagy JPEG logó tölthető fel!",
+                ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(DocumentSettingsServiceError::Unauthorized)?;
+        let repo = self.module().document_settings_repo(tenant_id)?;
+        let previous_key = repo.get().await?.logo_storage_key;
+        let storage_key = format!(
+            "{tenant_id}/document_settings/logo_{}.{extension}",
+            Uuid::new_v4()
+        );
+        let storage = self.module().file_storage();
+        storage
+            .put(&storage_key, content_type, payload.data)
+            .await?;
+        let settings = match repo
+            .set_logo(
+                Some(storage_key.clone()),
+                Some(content_type.to_string()),
+                self.claims()?.sub(),
+            )
+            .await
+        {
+            Ok(settings) => settings,
+            Err(e) => {
+                if let Err(e) = storage.delete(&storage_key).await {
+                    error!("Could not remove logo file {}: {}", storage_key, e);
+                }
+                return Err(e.into());
+            }
+        };
+        if let Some(key) = previous_key
+            && let Err(e) = storage.delete(&key).await
+        {
+            error!("Could not remove logo file {}: {}", key, e);
+        }
+        Ok(settings)
+    }
+
+    async fn get_logo(&self) -> DocumentSettingsServiceResult<(String, Vec<u8>)> {
+        let settings = self
+            .module()
+            .document_settings_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DocumentSettingsServiceError::Unauthorized)?,
+            )?
+            .get()
+            .await?;
+        match (settings.logo_storage_key, settings.logo_content_type) {
+            (Some(key), Some(content_type)) => {
+                Ok((content_type, self.module().file_storage().get(&key).await?))
+            }
+            _ => Err(RepositoryError::Database(sqlx::Error::RowNotFound).into()),
+        }
+    }
+
+    async fn delete_logo(&self) -> DocumentSettingsServiceResult<()> {
+        let repo = self.module().document_settings_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(DocumentSettingsServiceError::Unauthorized)?,
+        )?;
+        let previous_key = repo.get().await?.logo_storage_key;
+        repo.set_logo(None, None, self.claims()?.sub()).await?;
+        if let Some(key) = previous_key
+            && let Err(e) = self.module().file_storage().delete(&key).await
+        {
+            error!("Could not remove logo file {}: {}", key, e);
+        }
+        Ok(())
+    }
+}
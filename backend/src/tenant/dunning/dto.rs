@@ -0,0 +1,54 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DunningLevelInput {
+    pub days_overdue: i32,
+    pub template: String,
+    pub custom_subject: Option<String>,
+    pub custom_body: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UpdateDunningLevel {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub level: DunningLevelInput,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DunningOptOut {
+    pub customer_id: Uuid,
+    pub opt_out: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewDunningReminder {
+    pub receivable_id: Uuid,
+    pub level_id: Uuid,
+    pub days_overdue: i32,
+    pub recipient: String,
+    pub subject: String,
+    pub status: &'static str,
+    pub error: Option<String>,
+}
@@ -0,0 +1,506 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::dunning::DunningModuleInterface;
+use crate::tenant::dunning::dto::{DunningLevelInput, DunningOptOut, UpdateDunningLevel};
+use crate::tenant::dunning::service::DunningService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list_levels<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    let result = map_handler_err(service.get_levels().await, dunning_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_level<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DunningLevelInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    let result =
+        map_handler_err(service.create_level(&payload).await, dunning_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_level<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateDunningLevel>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    let result =
+        map_handler_err(service.update_level(&payload).await, dunning_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete_level<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    map_handler_err(
+        service.delete_level(payload.uuid).await,
+        dunning_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A felszólítási szint törlése sikeresen megtörtént",
+            ))
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn opt_out<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DunningOptOut>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    map_handler_err(service.set_opt_out(&payload).await, dunning_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(if payload.opt_out {
+                "A vevő nem kap több fizetési emlékeztetőt"
+            } else {
+                "A vevő ismét kap fizetési emlékeztetőket"
+            }))
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reminders<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    let result = map_handler_err(
+        service.get_reminders(payload.uuid).await,
+        dunning_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn run<M: DunningModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(dunning_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), dunning_module.clone());
+    let result = map_handler_err(service.run().await, dunning_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        dunning_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::document_settings::model::DocumentSettings;
+    use crate::tenant::document_settings::repository::MockDocumentSettingsRepository;
+    use crate::tenant::dunning::model::{
+        DueReminder, DunningLevel, DunningReminder, REMINDER_STATUS_FAILED, REMINDER_STATUS_SENT,
+        TEMPLATE_FINAL_NOTICE, TEMPLATE_REMINDER,
+    };
+    use crate::tenant::dunning::{
+        self, repository::MockDunningRepository, tests::MockDunningModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{Duration, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockDunningRepository,
+        tenant_id: Uuid,
+        config_calls: usize,
+    ) -> MockDunningModule {
+        let repo = Arc::new(repo);
+        let mut dunning_module = MockDunningModule::new();
+        dunning_module
+            .expect_dunning_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        dunning_module
+            .expect_config()
+            .times(config_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        dunning_module
+    }
+
+    fn app(dunning_module: MockDunningModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(dunning::routes::routes(Arc::new(dunning_module))),
+        )
+    }
+
+    fn request(
+        tenant_id: Uuid,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap()
+    }
+
+    fn level(days_overdue: i32, template: &str) -> DunningLevel {
+        DunningLevel {
+            id: Uuid::new_v4(),
+            days_overdue,
+            template: template.to_string(),
+            custom_subject: None,
+            custom_body: None,
+            enabled: true,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn due_reminder(customer_email: &str) -> DueReminder {
+        let today = Utc::now().date_naive();
+        DueReminder {
+            receivable_id: Uuid::new_v4(),
+            document_number: "SZ-2026-00042".to_string(),
+            issue_date: today - Duration::days(15),
+            due_date: today - Duration::days(7),
+            currency_code: "HUF".to_string(),
+            open_balance: BigDecimal::from(12700),
+            customer_name: "Példa Kft.".to_string(),
+            customer_email: customer_email.to_string(),
+            level_id: Uuid::new_v4(),
+            template: TEMPLATE_REMINDER.to_string(),
+            custom_subject: None,
+            custom_body: None,
+        }
+    }
+
+    fn document_settings_repo() -> Arc<MockDocumentSettingsRepository> {
+        let mut repo = MockDocumentSettingsRepository::new();
+        repo.expect_get().times(1).returning(|| {
+            Ok(DocumentSettings {
+                company_name: Some("Obvia Kft.".to_string()),
+                tax_number: None,
+                address: None,
+                bank_account: Some("11111111-22222222-33333333".to_string()),
+                email: Some("penzugy@obvia.hu".to_string()),
+                phone_number: None,
+                footer_note: None,
+                logo_storage_key: None,
+                logo_content_type: None,
+                invoice_email_cc: None,
+                invoice_email_bcc: None,
+                worksheet_layout: "detailed".to_string(),
+                worksheet_hide_logo: false,
+                updated_by_id: None,
+                updated_at: Utc::now(),
+            })
+        });
+        Arc::new(repo)
+    }
+
+    fn reminder(due: &DueReminder, status: &str) -> DunningReminder {
+        DunningReminder {
+            id: Uuid::new_v4(),
+            receivable_id: due.receivable_id,
+            level_id: due.level_id,
+            days_overdue: 7,
+            recipient: due.customer_email.clone(),
+            subject: "Fizetési emlékeztető".to_string(),
+            status: status.to_string(),
+            error: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_sends_and_records_due_reminder() {
+        let tenant_id = Uuid::new_v4();
+        let due = due_reminder("vevo@example.com");
+        let sent = reminder(&due, REMINDER_STATUS_SENT);
+
+        let mut repo = MockDunningRepository::new();
+        repo.expect_get_due_reminders()
+            .with(eq(Utc::now().date_naive()))
+            .times(1)
+            .returning({
+                let due = due.clone();
+                move |_| Ok(vec![due.clone()])
+            });
+        repo.expect_online_payment_enabled()
+            .times(1)
+            .returning(|| Ok(false));
+        repo.expect_insert_reminder()
+            .times(1)
+            .withf(|input| {
+                input.status == REMINDER_STATUS_SENT
+                    && input.days_overdue == 7
+                    && input.recipient == "vevo@example.com"
+                    && input.subject.contains("SZ-2026-00042")
+                    && input.error.is_none()
+            })
+            .returning({
+                let sent = sent.clone();
+                move |_| Ok(sent.clone())
+            });
+
+        let mut dunning_module = module(repo, tenant_id, 2);
+        let document_settings_repo = document_settings_repo();
+        dunning_module
+            .expect_document_settings_repo()
+            .times(1)
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        dunning_module
+            .expect_send()
+            .times(1)
+            .withf(|message| {
+                let raw = String::from_utf8_lossy(&message.formatted()).to_string();
+                raw.contains("vevo@example.com") && raw.contains("penzugy@obvia.hu")
+            })
+            .returning(|_| Ok(None));
+
+        let response = app(dunning_module)
+            .oneshot(request(tenant_id, "POST", "/api/dunning/run", None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": [sent]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_records_failed_delivery() {
+        let tenant_id = Uuid::new_v4();
+        let due = due_reminder("nem-email-cim");
+        let failed = reminder(&due, REMINDER_STATUS_FAILED);
+
+        let mut repo = MockDunningRepository::new();
+        repo.expect_get_due_reminders().times(1).returning({
+            let due = due.clone();
+            move |_| Ok(vec![due.clone()])
+        });
+        repo.expect_online_payment_enabled()
+            .times(1)
+            .returning(|| Ok(false));
+        repo.expect_insert_reminder()
+            .times(1)
+            .withf(|input| input.status == REMINDER_STATUS_FAILED && input.error.is_some())
+            .returning({
+                let failed = failed.clone();
+                move |_| Ok(failed.clone())
+            });
+
+        let mut dunning_module = module(repo, tenant_id, 2);
+        let document_settings_repo = document_settings_repo();
+        dunning_module
+            .expect_document_settings_repo()
+            .times(1)
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        dunning_module.expect_send().never();
+
+        let response = app(dunning_module)
+            .oneshot(request(tenant_id, "POST", "/api/dunning/run", None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_level_rejects_duplicate_days() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockDunningRepository::new();
+        repo.expect_get_levels()
+            .times(1)
+            .returning(|| Ok(vec![level(7, TEMPLATE_REMINDER)]));
+        repo.expect_insert_level().never();
+
+        let response = app(module(repo, tenant_id, 1))
+            .oneshot(request(
+                tenant_id,
+                "POST",
+                "/api/dunning/levels/create",
+                Some(json!({
+                    "days_overdue": 7,
+                    "template": TEMPLATE_FINAL_NOTICE,
+                    "custom_subject": null,
+                    "custom_body": null,
+                    "enabled": true
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_level_trims_custom_texts() {
+        let tenant_id = Uuid::new_v4();
+        let created = DunningLevel {
+            custom_body: Some("Kérjük, rendezze a {{document_number}} számlát.".to_string()),
+            ..level(14, TEMPLATE_FINAL_NOTICE)
+        };
+
+        let mut repo = MockDunningRepository::new();
+        repo.expect_get_levels()
+            .times(1)
+            .returning(|| Ok(vec![level(7, TEMPLATE_REMINDER)]));
+        repo.expect_insert_level()
+            .times(1)
+            .withf(|input, _| {
+                input.custom_subject.is_none()
+                    && input.custom_body.as_deref()
+                        == Some("Kérjük, rendezze a {{document_number}} számlát.")
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _| Ok(created.clone())
+            });
+
+        let response = app(module(repo, tenant_id, 1))
+            .oneshot(request(
+                tenant_id,
+                "POST",
+                "/api/dunning/levels/create",
+                Some(json!({
+                    "days_overdue": 14,
+                    "template": TEMPLATE_FINAL_NOTICE,
+                    "custom_subject": "  ",
+                    "custom_body": " Kérjük, rendezze a {{document_number}} számlát. ",
+                    "enabled": true
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": created})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_opt_out_sets_customer_flag() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut repo = MockDunningRepository::new();
+        repo.expect_set_opt_out()
+            .with(eq(customer_id), eq(true))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let response = app(module(repo, tenant_id, 1))
+            .oneshot(request(
+                tenant_id,
+                "PUT",
+                "/api/dunning/opt_out",
+                Some(json!({"customer_id": customer_id, "opt_out": true})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
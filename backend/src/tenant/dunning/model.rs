@@ -0,0 +1,90 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::email_template::EmailTemplate;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const TEMPLATE_REMINDER: &str = "reminder";
+pub const TEMPLATE_SECOND_REMINDER: &str = "second_reminder";
+pub const TEMPLATE_FINAL_NOTICE: &str = "final_notice";
+pub const TEMPLATES: [&str; 3] = [
+    TEMPLATE_REMINDER,
+    TEMPLATE_SECOND_REMINDER,
+    TEMPLATE_FINAL_NOTICE,
+];
+
+pub const REMINDER_STATUS_SENT: &str = "sent";
+pub const REMINDER_STATUS_FAILED: &str = "failed";
+
+/// Built-in email of an escalation step, used for the parts not customized on the level
+pub fn email_template(template: &str) -> EmailTemplate {
+    match template {
+        TEMPLATE_SECOND_REMINDER => EmailTemplate::DunningSecondReminder,
+        TEMPLATE_FINAL_NOTICE => EmailTemplate::DunningFinalNotice,
+        _ => EmailTemplate::DunningReminder,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct DunningLevel {
+    pub id: Uuid,
+    pub days_overdue: i32,
+    pub template: String,
+    pub custom_subject: Option<String>,
+    pub custom_body: Option<String>,
+    pub enabled: bool,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct DunningReminder {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub level_id: Uuid,
+    pub days_overdue: i32,
+    pub recipient: String,
+    pub subject: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Overdue invoice with the highest escalation step it has reached and not been reminded of
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct DueReminder {
+    pub receivable_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub open_balance: BigDecimal,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub level_id: Uuid,
+    pub template: String,
+    pub custom_subject: Option<String>,
+    pub custom_body: Option<String>,
+}
@@ -0,0 +1,249 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::dunning::dto::{DunningLevelInput, NewDunningReminder};
+use crate::tenant::dunning::model::{
+    DueReminder, DunningLevel, DunningReminder, REMINDER_STATUS_FAILED, REMINDER_STATUS_SENT,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// NOTE: a reminder failing this many times is not retried for the same level
+const MAX_FAILED_ATTEMPTS: i64 = 3;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DunningRepository: Send + Sync {
+    async fn get_levels(&self) -> RepositoryResult<Vec<DunningLevel>>;
+    async fn insert_level(
+        &self,
+        input: &DunningLevelInput,
+        sub: Uuid,
+    ) -> RepositoryResult<DunningLevel>;
+    async fn update_level(
+        &self,
+        id: Uuid,
+        input: &DunningLevelInput,
+    ) -> RepositoryResult<DunningLevel>;
+    async fn delete_level(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn set_opt_out(&self, customer_id: Uuid, opt_out: bool) -> RepositoryResult<()>;
+    async fn get_reminders(&self, receivable_id: Uuid) -> RepositoryResult<Vec<DunningReminder>>;
+    async fn get_due_reminders(&self, today: NaiveDate) -> RepositoryResult<Vec<DueReminder>>;
+    async fn insert_reminder(
+        &self,
+        input: &NewDunningReminder,
+    ) -> RepositoryResult<DunningReminder>;
+    async fn online_payment_enabled(&self) -> RepositoryResult<bool>;
+}
+
+#[async_trait]
+impl DunningRepository for PgPool {
+    async fn get_levels(&self) -> RepositoryResult<Vec<DunningLevel>> {
+        Ok(sqlx::query_as::<_, DunningLevel>(
+            "SELECT * FROM dunning_levels WHERE deleted_at IS NULL ORDER BY days_overdue",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_level(
+        &self,
+        input: &DunningLevelInput,
+        sub: Uuid,
+    ) -> RepositoryResult<DunningLevel> {
+        Ok(sqlx::query_as::<_, DunningLevel>(
+            r#"
+            INSERT INTO dunning_levels (days_overdue, template, custom_subject, custom_body, enabled,
+                                        created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.days_overdue)
+        .bind(&input.template)
+        .bind(&input.custom_subject)
+        .bind(&input.custom_body)
+        .bind(input.enabled)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update_level(
+        &self,
+        id: Uuid,
+        input: &DunningLevelInput,
+    ) -> RepositoryResult<DunningLevel> {
+        Ok(sqlx::query_as::<_, DunningLevel>(
+            r#"
+            UPDATE dunning_levels
+            SET days_overdue   = $2,
+                template       = $3,
+                custom_subject = $4,
+                custom_body    = $5,
+                enabled        = $6
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(input.days_overdue)
+        .bind(&input.template)
+        .bind(&input.custom_subject)
+        .bind(&input.custom_body)
+        .bind(input.enabled)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_level(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            "UPDATE dunning_levels SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn set_opt_out(&self, customer_id: Uuid, opt_out: bool) -> RepositoryResult<()> {
+        sqlx::query_as::<_, (Uuid,)>(
+            r#"
+            UPDATE customers
+            SET dunning_opt_out = $2
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(customer_id)
+        .bind(opt_out)
+        .fetch_one(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_reminders(&self, receivable_id: Uuid) -> RepositoryResult<Vec<DunningReminder>> {
+        Ok(sqlx::query_as::<_, DunningReminder>(
+            "SELECT * FROM dunning_reminders WHERE receivable_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_due_reminders(&self, today: NaiveDate) -> RepositoryResult<Vec<DueReminder>> {
+        // NOTE: only the highest level reached is sent, lower levels missed (e.g. a new level,
+        // or the scheduler not running) are not caught up
+        Ok(sqlx::query_as::<_, DueReminder>(
+            r#"
+            SELECT receivables.id AS receivable_id,
+                   receivables.document_number,
+                   receivables.issue_date,
+                   receivables.due_date,
+                   receivables.currency_code,
+                   receivables.amount - receivables.paid_amount - receivables.credited_amount
+                       AS open_balance,
+                   customers.name AS customer_name,
+                   COALESCE(customer_contacts.email, customers.email) AS customer_email,
+                   levels.id AS level_id,
+                   levels.template,
+                   levels.custom_subject,
+                   levels.custom_body
+            FROM receivables
+            JOIN customers ON receivables.customer_id = customers.id
+            LEFT JOIN customer_contacts ON customer_contacts.customer_id = customers.id
+                AND customer_contacts.is_primary
+                AND customer_contacts.deleted_at IS NULL
+            JOIN LATERAL (SELECT *
+                          FROM dunning_levels
+                          WHERE dunning_levels.enabled
+                            AND dunning_levels.deleted_at IS NULL
+                            AND dunning_levels.days_overdue <= $1 - receivables.due_date
+                          ORDER BY dunning_levels.days_overdue DESC
+                          LIMIT 1) levels ON true
+            WHERE receivables.status = 'open'
+              AND receivables.deleted_at IS NULL
+              AND receivables.amount - receivables.paid_amount - receivables.credited_amount > 0
+              AND NOT customers.dunning_opt_out
+              -- a promise to pay holds the reminders until the promised date
+              AND NOT EXISTS (SELECT 1
+                              FROM collection_activities
+                              WHERE collection_activities.receivable_id = receivables.id
+                                AND collection_activities.activity_type = 'promise_to_pay'
+                                AND collection_activities.promised_date >= $1)
+              AND NOT EXISTS (SELECT 1
+                              FROM dunning_reminders
+                              JOIN dunning_levels reminded ON dunning_reminders.level_id = reminded.id
+                              WHERE dunning_reminders.receivable_id = receivables.id
+                                AND dunning_reminders.status = $2
+                                AND reminded.days_overdue >= levels.days_overdue)
+              AND (SELECT COUNT(*)
+                   FROM dunning_reminders
+                   WHERE dunning_reminders.receivable_id = receivables.id
+                     AND dunning_reminders.level_id = levels.id
+                     AND dunning_reminders.status = $3) < $4
+            ORDER BY receivables.due_date, receivables.document_number
+            "#,
+        )
+        .bind(today)
+        .bind(REMINDER_STATUS_SENT)
+        .bind(REMINDER_STATUS_FAILED)
+        .bind(MAX_FAILED_ATTEMPTS)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_reminder(
+        &self,
+        input: &NewDunningReminder,
+    ) -> RepositoryResult<DunningReminder> {
+        Ok(sqlx::query_as::<_, DunningReminder>(
+            r#"
+            INSERT INTO dunning_reminders (receivable_id, level_id, days_overdue, recipient, subject,
+                                           status, error)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(input.receivable_id)
+        .bind(input.level_id)
+        .bind(input.days_overdue)
+        .bind(&input.recipient)
+        .bind(&input.subject)
+        .bind(input.status)
+        .bind(&input.error)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn online_payment_enabled(&self) -> RepositoryResult<bool> {
+        let (enabled,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM payment_provider_settings WHERE enabled)")
+                .fetch_one(self)
+                .await?;
+        Ok(enabled)
+    }
+}
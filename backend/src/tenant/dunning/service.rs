@@ -0,0 +1,397 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::email_template::{RenderedEmail, render_email};
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::pdf::{format_date, format_money};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::document_settings::dto::Letterhead;
+use crate::tenant::dunning::DunningModuleInterface;
+use crate::tenant::dunning::dto::{
+    DunningLevelInput, DunningOptOut, NewDunningReminder, UpdateDunningLevel,
+};
+use crate::tenant::dunning::model::{
+    DueReminder, DunningLevel, DunningReminder, REMINDER_STATUS_FAILED, REMINDER_STATUS_SENT,
+    TEMPLATES, email_template,
+};
+use crate::tenant::payment_links::service::pay_url;
+use axum::http::StatusCode;
+use chrono::{NaiveDate, Utc};
+use handlebars::RenderError;
+use lettre::message::Mailbox;
+use lettre::{Address, Message};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum DunningServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for DunningServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => DunningServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<DunningServiceError> for AppError {
+    fn from(value: DunningServiceError) -> Self {
+        match value {
+            DunningServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            DunningServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            DunningServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type DunningServiceResult<T> = Result<T, DunningServiceError>;
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// NOTE: a custom body is written as plain text, paragraphs are kept in the html part
+pub(crate) fn body_html(body: &str) -> String {
+    format!(
+        "<p>{}</p>",
+        body.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace("\r\n", "\n")
+            .replace("\n\n", "</p><p>")
+            .replace('\n', "<br>")
+    )
+}
+
+/// Renders the reminder of a level: its custom subject and body when set, the built-in email
+/// of its escalation step otherwise
+pub fn render_reminder(
+    template: &str,
+    custom_subject: Option<&str>,
+    custom_body: Option<&str>,
+    data: &Value,
+) -> Result<RenderedEmail, RenderError> {
+    let template = email_template(template);
+    let subject = custom_subject.unwrap_or(template.subject());
+    match custom_body {
+        Some(body) => render_email(subject, &body_html(body), body, data),
+        None => render_email(subject, template.html(), template.text(), data),
+    }
+}
+
+fn validate_level(
+    payload: &DunningLevelInput,
+    levels: &[DunningLevel],
+    id: Option<Uuid>,
+) -> DunningServiceResult<DunningLevelInput> {
+    if !(1..=365).contains(&payload.days_overdue) {
+        return Err(DunningServiceError::UnprocessableEntry(
+            "A késedelmi napok száma 1 és 365 között lehet!",
+        ));
+    }
+    if levels
+        .iter()
+        .any(|level| level.days_overdue == payload.days_overdue && Some(level.id) != id)
+    {
+        return Err(DunningServiceError::UnprocessableEntry(
+            "Ehhez a késedelemhez már tartozik emlékeztető!",
+        ));
+    }
+    if !TEMPLATES.contains(&payload.template.as_str()) {
+        return Err(DunningServiceError::UnprocessableEntry(
+            "Ismeretlen emlékeztető sablon!",
+        ));
+    }
+    let custom_subject = optional(&payload.custom_subject);
+    let custom_body = optional(&payload.custom_body);
+    render_reminder(
+        &payload.template,
+        custom_subject.as_deref(),
+        custom_body.as_deref(),
+        &email_template(&payload.template).sample_data(),
+    )
+    .map_err(|_| DunningServiceError::UnprocessableEntry("Hibás sablon!"))?;
+    Ok(DunningLevelInput {
+        days_overdue: payload.days_overdue,
+        template: payload.template.clone(),
+        custom_subject,
+        custom_body,
+        enabled: payload.enabled,
+    })
+}
+
+async fn send_reminder<M: DunningModuleInterface>(
+    module: &M,
+    letterhead: &Letterhead,
+    reminder: &DueReminder,
+    data: &Value,
+) -> (String, anyhow::Result<()>) {
+    let rendered = render_reminder(
+        &reminder.template,
+        reminder.custom_subject.as_deref(),
+        reminder.custom_body.as_deref(),
+        data,
+    );
+    let subject = match &rendered {
+        Ok(rendered) => rendered.subject.clone(),
+        Err(_) => email_template(&reminder.template).subject().to_string(),
+    };
+    let result = async {
+        let mail_config = module.config().mail();
+        let mut builder = Message::builder()
+            .from(Mailbox::new(
+                Some(mail_config.default_from_name().to_owned()),
+                mail_config.default_from().parse()?,
+            ))
+            .to(Mailbox::new(
+                Some(reminder.customer_name.clone()),
+                reminder.customer_email.parse()?,
+            ));
+        if let Some(reply_to) = letterhead
+            .email
+            .as_deref()
+            .and_then(|email| email.parse::<Address>().ok())
+        {
+            builder = builder.reply_to(Mailbox::new(letterhead.company_name.clone(), reply_to));
+        }
+        let message = rendered?.into_message_with_attachments(builder, vec![])?;
+        module.send(message).await?;
+        Ok(())
+    }
+    .await;
+    (subject, result)
+}
+
+/// Emails the reminders due on the given day and records them on the invoices. A failed
+/// delivery is recorded as well and retried on the next run.
+pub(crate) async fn send_due_reminders<M: DunningModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    today: NaiveDate,
+) -> DunningServiceResult<Vec<DunningReminder>> {
+    let repo = module.dunning_repo(tenant_id)?;
+    let due = repo.get_due_reminders(today).await?;
+    if due.is_empty() {
+        return Ok(vec![]);
+    }
+    let letterhead = Letterhead::from(module.document_settings_repo(tenant_id)?.get().await?);
+    let online_payment = repo.online_payment_enabled().await?;
+    let mut reminders = Vec::with_capacity(due.len());
+    for reminder in due {
+        let days_overdue =
+            i32::try_from((today - reminder.due_date).num_days()).unwrap_or(i32::MAX);
+        let data = json!({
+            "company_name": letterhead.company_name,
+            "customer_name": reminder.customer_name,
+            "document_number": reminder.document_number,
+            "issue_date": format_date(reminder.issue_date),
+            "due_date": format_date(reminder.due_date),
+            "days_overdue": days_overdue,
+            "open_balance": format_money(&reminder.open_balance, &reminder.currency_code),
+            "bank_account": letterhead.bank_account,
+            "payment_url": online_payment.then(|| pay_url(
+                module.config().server().public_base_url(),
+                tenant_id,
+                reminder.receivable_id,
+            )),
+        });
+        let (subject, result) = send_reminder(module, &letterhead, &reminder, &data).await;
+        let error = result.err().map(|e| {
+            warn!(
+                "Could not send payment reminder for invoice {}: {}",
+                reminder.document_number, e
+            );
+            e.to_string()
+        });
+        reminders.push(
+            repo.insert_reminder(&NewDunningReminder {
+                receivable_id: reminder.receivable_id,
+                level_id: reminder.level_id,
+                days_overdue,
+                recipient: reminder.customer_email,
+                subject,
+                status: match error {
+                    None => REMINDER_STATUS_SENT,
+                    Some(_) => REMINDER_STATUS_FAILED,
+                },
+                error,
+            })
+            .await?,
+        );
+    }
+    Ok(reminders)
+}
+
+pub trait DunningService {
+    fn get_levels(&self) -> impl Future<Output = DunningServiceResult<Vec<DunningLevel>>> + Send;
+    fn create_level(
+        &self,
+        payload: &DunningLevelInput,
+    ) -> impl Future<Output = DunningServiceResult<DunningLevel>> + Send;
+    fn update_level(
+        &self,
+        payload: &UpdateDunningLevel,
+    ) -> impl Future<Output = DunningServiceResult<DunningLevel>> + Send;
+    fn delete_level(&self, id: Uuid) -> impl Future<Output = DunningServiceResult<()>> + Send;
+    fn set_opt_out(
+        &self,
+        payload: &DunningOptOut,
+    ) -> impl Future<Output = DunningServiceResult<()>> + Send;
+    fn get_reminders(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = DunningServiceResult<Vec<DunningReminder>>> + Send;
+    fn run(&self) -> impl Future<Output = DunningServiceResult<Vec<DunningReminder>>> + Send;
+}
+
+impl<'a, T> DunningService for Service<'a, T>
+where
+    T: DunningModuleInterface,
+{
+    async fn get_levels(&self) -> DunningServiceResult<Vec<DunningLevel>> {
+        Ok(self
+            .module()
+            .dunning_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DunningServiceError::Unauthorized)?,
+            )?
+            .get_levels()
+            .await?)
+    }
+
+    async fn create_level(
+        &self,
+        payload: &DunningLevelInput,
+    ) -> DunningServiceResult<DunningLevel> {
+        let repo = self.module().dunning_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(DunningServiceError::Unauthorized)?,
+        )?;
+        let input = validate_level(payload, &repo.get_levels().await?, None)?;
+        Ok(repo.insert_level(&input, self.claims()?.sub()).await?)
+    }
+
+    async fn update_level(
+        &self,
+        payload: &UpdateDunningLevel,
+    ) -> DunningServiceResult<DunningLevel> {
+        let repo = self.module().dunning_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(DunningServiceError::Unauthorized)?,
+        )?;
+        let input = validate_level(&payload.level, &repo.get_levels().await?, Some(payload.id))?;
+        Ok(repo.update_level(payload.id, &input).await?)
+    }
+
+    async fn delete_level(&self, id: Uuid) -> DunningServiceResult<()> {
+        Ok(self
+            .module()
+            .dunning_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DunningServiceError::Unauthorized)?,
+            )?
+            .delete_level(id)
+            .await?)
+    }
+
+    async fn set_opt_out(&self, payload: &DunningOptOut) -> DunningServiceResult<()> {
+        Ok(self
+            .module()
+            .dunning_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DunningServiceError::Unauthorized)?,
+            )?
+            .set_opt_out(payload.customer_id, payload.opt_out)
+            .await?)
+    }
+
+    async fn get_reminders(
+        &self,
+        receivable_id: Uuid,
+    ) -> DunningServiceResult<Vec<DunningReminder>> {
+        Ok(self
+            .module()
+            .dunning_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(DunningServiceError::Unauthorized)?,
+            )?
+            .get_reminders(receivable_id)
+            .await?)
+    }
+
+    async fn run(&self) -> DunningServiceResult<Vec<DunningReminder>> {
+        send_due_reminders(
+            self.module(),
+            self.claims()?
+                .active_tenant()
+                .ok_or(DunningServiceError::Unauthorized)?,
+            Utc::now().date_naive(),
+        )
+        .await
+    }
+}
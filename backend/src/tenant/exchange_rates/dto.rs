@@ -0,0 +1,45 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ExchangeRateListQuery {
+    pub currency_code: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// `date` defaults to today
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ExchangeRateLookupQuery {
+    pub from_currency_code: String,
+    pub to_currency_code: String,
+    pub date: Option<NaiveDate>,
+}
+
+/// Overrides the fetched rate of the day, if any
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateExchangeRate {
+    pub currency_code: String,
+    pub rate_date: NaiveDate,
+    pub rate: BigDecimal,
+}
@@ -0,0 +1,86 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::exchange_rates::ExchangeRatesModuleInterface;
+use crate::tenant::exchange_rates::service::store_rates;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+pub fn spawn_rate_fetcher<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let interval_hours = app_state.config().exchange_rates().fetch_interval_hours();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_hours * 3600));
+        loop {
+            interval.tick().await;
+            // NOTE: the rates are fetched once per run and stored into every tenant
+            let rates = match app_state.rate_source() {
+                Ok(source) => source.fetch_latest().await,
+                Err(e) => Err(e),
+            };
+            let rates = match rates {
+                Ok(rates) => rates,
+                Err(e) => {
+                    error!("Could not fetch exchange rates: {}", e);
+                    continue;
+                }
+            };
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for exchange rates: {}", e);
+                    continue;
+                }
+            };
+            for tenant in tenants {
+                let result = match app_state.exchange_rates_repo(tenant.id) {
+                    Ok(repo) => store_rates(&*repo, &rates).await,
+                    Err(e) => Err(e.into()),
+                };
+                match result {
+                    Ok(fetch) => info!(
+                        "Stored {} exchange rates of {} for tenant {}",
+                        fetch.stored_count, fetch.rate_date, tenant.id
+                    ),
+                    Err(e) => error!(
+                        "Could not store exchange rates for tenant {}: {}",
+                        tenant.id, e
+                    ),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,331 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::exchange_rates::ExchangeRatesModuleInterface;
+use crate::tenant::exchange_rates::dto::{
+    CreateExchangeRate, ExchangeRateListQuery, ExchangeRateLookupQuery,
+};
+use crate::tenant::exchange_rates::service::ExchangeRatesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: ExchangeRatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(exchange_rates_module): State<Arc<M>>,
+    Query(payload): Query<ExchangeRateListQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), exchange_rates_module.clone());
+    let result =
+        map_handler_err(service.list(&payload).await, exchange_rates_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        exchange_rates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn lookup<M: ExchangeRatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(exchange_rates_module): State<Arc<M>>,
+    Query(payload): Query<ExchangeRateLookupQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), exchange_rates_module.clone());
+    let result = map_handler_err(
+        service.lookup(&payload).await,
+        exchange_rates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        exchange_rates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: ExchangeRatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(exchange_rates_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateExchangeRate>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), exchange_rates_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        exchange_rates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        exchange_rates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn fetch<M: ExchangeRatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(exchange_rates_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), exchange_rates_module.clone());
+    let result = map_handler_err(service.fetch().await, exchange_rates_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        exchange_rates_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::exchange_rates::model::{
+        ExchangeRate, ExchangeRateFetch, ExchangeRateLookup, FetchedRate, FetchedRates,
+    };
+    use crate::tenant::exchange_rates::source::MockRateSource;
+    use crate::tenant::exchange_rates::{
+        self, repository::MockExchangeRatesRepository, tests::MockExchangeRatesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(repo: MockExchangeRatesRepository, tenant_id: Uuid) -> MockExchangeRatesModule {
+        let repo = Arc::new(repo);
+        let mut exchange_rates_module = MockExchangeRatesModule::new();
+        exchange_rates_module
+            .expect_exchange_rates_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        exchange_rates_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        exchange_rates_module
+    }
+
+    fn app(exchange_rates_module: MockExchangeRatesModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(exchange_rates::routes::routes(Arc::new(
+                exchange_rates_module,
+            ))),
+        )
+    }
+
+    fn request(method: &str, uri: &str, tenant_id: Uuid, body: Body) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(body)
+            .unwrap()
+    }
+
+    fn rate(currency_code: &str, rate_date: NaiveDate, rate: &str) -> ExchangeRate {
+        ExchangeRate {
+            id: Uuid::new_v4(),
+            currency_code: currency_code.to_string(),
+            rate_date,
+            rate: BigDecimal::from_str(rate).unwrap(),
+            source: "mnb".to_string(),
+            created_by_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_crosses_rates_through_huf() {
+        let tenant_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let friday = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let mut repo = MockExchangeRatesRepository::new();
+        repo.expect_get_rate_as_of()
+            .withf(move |currency_code, as_of| currency_code == "EUR" && *as_of == date)
+            .times(1)
+            .returning(move |_, _| Ok(Some(rate("EUR", friday, "390"))));
+        repo.expect_get_rate_as_of()
+            .withf(move |currency_code, as_of| currency_code == "USD" && *as_of == date)
+            .times(1)
+            .returning(move |_, _| Ok(Some(rate("USD", friday, "312"))));
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(request(
+                "GET",
+                "/api/exchange_rates/lookup?from_currency_code=eur&to_currency_code=USD&date=2026-10-18",
+                tenant_id,
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: ExchangeRateLookup =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            body,
+            ExchangeRateLookup {
+                from_currency_code: "EUR".to_string(),
+                to_currency_code: "USD".to_string(),
+                as_of: date,
+                rate: BigDecimal::from_str("1.25").unwrap(),
+                from_rate_date: Some(friday),
+                to_rate_date: Some(friday),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_rejects_outdated_rate() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockExchangeRatesRepository::new();
+        repo.expect_get_rate_as_of().times(1).returning(|_, _| {
+            Ok(Some(rate(
+                "EUR",
+                NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+                "390",
+            )))
+        });
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(request(
+                "GET",
+                "/api/exchange_rates/lookup?from_currency_code=EUR&to_currency_code=HUF&date=2026-10-16",
+                tenant_id,
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_base_currency() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockExchangeRatesRepository::new();
+        repo.expect_upsert_manual().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(request(
+                "POST",
+                "/api/exchange_rates/create",
+                tenant_id,
+                Body::from(
+                    json!({"currency_code": "huf", "rate_date": "2026-10-16", "rate": "1"})
+                        .to_string(),
+                ),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stores_latest_rates() {
+        let tenant_id = Uuid::new_v4();
+        let rates = FetchedRates {
+            source: "mnb".to_string(),
+            rate_date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            rates: vec![FetchedRate {
+                currency_code: "EUR".to_string(),
+                rate: BigDecimal::from_str("389.12").unwrap(),
+            }],
+        };
+
+        let mut repo = MockExchangeRatesRepository::new();
+        repo.expect_store_fetched()
+            .with(eq(rates.clone()))
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let mut source = MockRateSource::new();
+        source.expect_fetch_latest().times(1).returning({
+            let rates = rates.clone();
+            move || Ok(rates.clone())
+        });
+        let source = Arc::new(source);
+
+        let mut exchange_rates_module = module(repo, tenant_id);
+        exchange_rates_module
+            .expect_rate_source()
+            .times(1)
+            .returning(move || Ok(source.clone()));
+
+        let response = app(exchange_rates_module)
+            .oneshot(request(
+                "POST",
+                "/api/exchange_rates/fetch",
+                tenant_id,
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: ExchangeRateFetch =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            body,
+            ExchangeRateFetch {
+                source: "mnb".to_string(),
+                rate_date: rates.rate_date,
+                stored_count: 1,
+            }
+        );
+    }
+}
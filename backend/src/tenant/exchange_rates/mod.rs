@@ -0,0 +1,101 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::{AppState, BaseModule, ConfigProvider};
+use crate::tenant::exchange_rates::repository::ExchangeRatesRepository;
+use crate::tenant::exchange_rates::source::{RateSource, RateSourceError, rate_source};
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub mod dto;
+pub(crate) mod fetcher;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+pub(crate) mod source;
+
+pub trait ExchangeRatesModuleInterface: BaseModule {
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+    fn rate_source(&self) -> Result<Arc<dyn RateSource + Send + Sync>, RateSourceError>;
+}
+
+impl<P, T> ExchangeRatesModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn rate_source(&self) -> Result<Arc<dyn RateSource + Send + Sync>, RateSourceError> {
+        rate_source(
+            self.config().exchange_rates().source(),
+            Duration::from_secs(self.config().exchange_rates().request_timeout_secs()),
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub ExchangeRatesModule {}
+        impl ConfigProvider for ExchangeRatesModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for ExchangeRatesModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for ExchangeRatesModule {}
+        impl ExchangeRatesModuleInterface for ExchangeRatesModule {
+            fn exchange_rates_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+            fn rate_source(&self) -> Result<Arc<dyn RateSource + Send + Sync>, RateSourceError>;
+        }
+    );
+}
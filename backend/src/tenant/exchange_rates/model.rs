@@ -0,0 +1,84 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Every rate is stored as the value of one unit of the currency in this currency
+pub const BASE_CURRENCY_CODE: &str = "HUF";
+
+pub const SOURCE_MNB: &str = "mnb";
+pub const SOURCE_ECB: &str = "ecb";
+pub const SOURCE_MANUAL: &str = "manual";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ExchangeRate {
+    pub id: Uuid,
+    pub currency_code: String,
+    pub rate_date: NaiveDate,
+    pub rate: BigDecimal,
+    pub source: String,
+    pub created_by_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Rate between two currencies as of a date, built from the latest stored rates of both
+/// currencies not later than the date. The rate dates are missing for the base currency.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExchangeRateLookup {
+    pub from_currency_code: String,
+    pub to_currency_code: String,
+    pub as_of: NaiveDate,
+    pub rate: BigDecimal,
+    pub from_rate_date: Option<NaiveDate>,
+    pub to_rate_date: Option<NaiveDate>,
+}
+
+impl ExchangeRateLookup {
+    pub fn convert(&self, amount: &BigDecimal) -> BigDecimal {
+        amount * &self.rate
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FetchedRate {
+    pub currency_code: String,
+    /// Value of one unit in the base currency
+    pub rate: BigDecimal,
+}
+
+/// Rates published by a source for one day
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FetchedRates {
+    pub source: String,
+    pub rate_date: NaiveDate,
+    pub rates: Vec<FetchedRate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExchangeRateFetch {
+    pub source: String,
+    pub rate_date: NaiveDate,
+    /// Rates of currencies unknown to the tenant and days with a manual rate are skipped
+    pub stored_count: u64,
+}
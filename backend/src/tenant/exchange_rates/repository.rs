@@ -0,0 +1,149 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::exchange_rates::dto::{CreateExchangeRate, ExchangeRateListQuery};
+use crate::tenant::exchange_rates::model::{ExchangeRate, FetchedRates, SOURCE_MANUAL};
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ExchangeRatesRepository: Send + Sync {
+    async fn get_rates(&self, query: &ExchangeRateListQuery)
+    -> RepositoryResult<Vec<ExchangeRate>>;
+    /// The latest rate of the currency not later than the date
+    async fn get_rate_as_of(
+        &self,
+        currency_code: &str,
+        date: NaiveDate,
+    ) -> RepositoryResult<Option<ExchangeRate>>;
+    async fn upsert_manual(
+        &self,
+        input: &CreateExchangeRate,
+        sub: Uuid,
+    ) -> RepositoryResult<ExchangeRate>;
+    /// Returns the number of stored rates
+    async fn store_fetched(&self, rates: &FetchedRates) -> RepositoryResult<u64>;
+}
+
+#[async_trait]
+impl ExchangeRatesRepository for PgPool {
+    async fn get_rates(
+        &self,
+        query: &ExchangeRateListQuery,
+    ) -> RepositoryResult<Vec<ExchangeRate>> {
+        Ok(sqlx::query_as::<_, ExchangeRate>(
+            r#"
+            SELECT *
+            FROM currency_rates
+            WHERE ($1::VARCHAR IS NULL OR currency_code = $1)
+                AND ($2::DATE IS NULL OR rate_date >= $2)
+                AND ($3::DATE IS NULL OR rate_date <= $3)
+            ORDER BY rate_date DESC, currency_code
+            LIMIT 1000
+            "#,
+        )
+        .bind(&query.currency_code)
+        .bind(query.from)
+        .bind(query.to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_rate_as_of(
+        &self,
+        currency_code: &str,
+        date: NaiveDate,
+    ) -> RepositoryResult<Option<ExchangeRate>> {
+        Ok(sqlx::query_as::<_, ExchangeRate>(
+            r#"
+            SELECT *
+            FROM currency_rates
+            WHERE currency_code = $1
+                AND rate_date <= $2
+            ORDER BY rate_date DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(currency_code)
+        .bind(date)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn upsert_manual(
+        &self,
+        input: &CreateExchangeRate,
+        sub: Uuid,
+    ) -> RepositoryResult<ExchangeRate> {
+        Ok(sqlx::query_as::<_, ExchangeRate>(
+            r#"
+            INSERT INTO currency_rates (currency_code, rate_date, rate, source, created_by_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (currency_code, rate_date) DO UPDATE
+                SET rate          = EXCLUDED.rate,
+                    source        = EXCLUDED.source,
+                    created_by_id = EXCLUDED.created_by_id
+            RETURNING *
+            "#,
+        )
+        .bind(&input.currency_code)
+        .bind(input.rate_date)
+        .bind(&input.rate)
+        .bind(SOURCE_MANUAL)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn store_fetched(&self, rates: &FetchedRates) -> RepositoryResult<u64> {
+        let (currency_codes, values): (Vec<String>, Vec<BigDecimal>) = rates
+            .rates
+            .iter()
+            .map(|rate| (rate.currency_code.clone(), rate.rate.clone()))
+            .unzip();
+        // NOTE: currencies unknown to the tenant are skipped and a manual rate is never overwritten
+        Ok(sqlx::query(
+            r#"
+            INSERT INTO currency_rates (currency_code, rate_date, rate, source)
+            SELECT fetched.currency_code, $1, fetched.rate, $2
+            FROM UNNEST($3::VARCHAR[], $4::NUMERIC[]) AS fetched (currency_code, rate)
+            JOIN currencies ON currencies.code = fetched.currency_code
+            ON CONFLICT (currency_code, rate_date) DO UPDATE
+                SET rate   = EXCLUDED.rate,
+                    source = EXCLUDED.source
+                WHERE currency_rates.source <> $5
+            "#,
+        )
+        .bind(rates.rate_date)
+        .bind(&rates.source)
+        .bind(&currency_codes)
+        .bind(&values)
+        .bind(SOURCE_MANUAL)
+        .execute(self)
+        .await?
+        .rows_affected())
+    }
+}
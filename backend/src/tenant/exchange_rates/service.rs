@@ -0,0 +1,294 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::common::value_object::ValueObjectRequired;
+use crate::tenant::currencies::types::CurrencyCode;
+use crate::tenant::exchange_rates::ExchangeRatesModuleInterface;
+use crate::tenant::exchange_rates::dto::{
+    CreateExchangeRate, ExchangeRateListQuery, ExchangeRateLookupQuery,
+};
+use crate::tenant::exchange_rates::model::{
+    BASE_CURRENCY_CODE, ExchangeRate, ExchangeRateFetch, ExchangeRateLookup, FetchedRates,
+};
+use crate::tenant::exchange_rates::repository::ExchangeRatesRepository;
+use crate::tenant::exchange_rates::source::RateSourceError;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{Days, NaiveDate, Utc};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+
+/// Older rates are not used, e.g. when the fetcher has been failing for a while
+const MAX_RATE_AGE_DAYS: u64 = 7;
+
+#[derive(Debug, Error)]
+pub enum ExchangeRatesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("Nincs érvényes {0} árfolyam a megadott napra!")]
+    RateNotFound(String),
+
+    #[error("{0}")]
+    RateSource(#[from] RateSourceError),
+}
+
+impl From<ServiceError> for ExchangeRatesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ExchangeRatesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ExchangeRatesServiceError> for AppError {
+    fn from(value: ExchangeRatesServiceError) -> Self {
+        match value {
+            ExchangeRatesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ExchangeRatesServiceError::UnprocessableEntry(_)
+            | ExchangeRatesServiceError::RateNotFound(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ExchangeRatesServiceError::RateSource(RateSourceError::Http(_))
+            | ExchangeRatesServiceError::RateSource(RateSourceError::InvalidResponse(_)) => {
+                Self::new(
+                    Level::WARN,
+                    StatusCode::BAD_GATEWAY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": "Az árfolyam-szolgáltató jelenleg nem érhető el!"}),
+                )
+            }
+            ExchangeRatesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ExchangeRatesServiceResult<T> = Result<T, ExchangeRatesServiceError>;
+
+fn currency_code(value: &str) -> ExchangeRatesServiceResult<String> {
+    let code = value.trim().to_uppercase();
+    code.parse::<ValueObjectRequired<CurrencyCode>>()
+        .map_err(|_| {
+            ExchangeRatesServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+        })?;
+    Ok(code)
+}
+
+async fn base_rate(
+    repo: &(dyn ExchangeRatesRepository + Send + Sync),
+    currency_code: &str,
+    date: NaiveDate,
+) -> ExchangeRatesServiceResult<(BigDecimal, Option<NaiveDate>)> {
+    if currency_code == BASE_CURRENCY_CODE {
+        return Ok((BigDecimal::from(1), None));
+    }
+    let oldest = date
+        .checked_sub_days(Days::new(MAX_RATE_AGE_DAYS))
+        .unwrap_or(NaiveDate::MIN);
+    repo.get_rate_as_of(currency_code, date)
+        .await?
+        .filter(|rate| rate.rate_date >= oldest)
+        .map(|rate| (rate.rate, Some(rate.rate_date)))
+        .ok_or_else(|| ExchangeRatesServiceError::RateNotFound(currency_code.to_string()))
+}
+
+/// The rate converting `from_currency_code` amounts to `to_currency_code` as of the date, shared
+/// by every document converting between currencies so they agree on the rate of the day
+pub(crate) async fn exchange_rate(
+    repo: &(dyn ExchangeRatesRepository + Send + Sync),
+    from_currency_code: &str,
+    to_currency_code: &str,
+    date: NaiveDate,
+) -> ExchangeRatesServiceResult<ExchangeRateLookup> {
+    let (rate, from_rate_date, to_rate_date) = if from_currency_code == to_currency_code {
+        (BigDecimal::from(1), None, None)
+    } else {
+        let (from_rate, from_rate_date) = base_rate(repo, from_currency_code, date).await?;
+        let (to_rate, to_rate_date) = base_rate(repo, to_currency_code, date).await?;
+        (
+            (from_rate / to_rate).with_scale_round(8, RoundingMode::HalfUp),
+            from_rate_date,
+            to_rate_date,
+        )
+    };
+    Ok(ExchangeRateLookup {
+        from_currency_code: from_currency_code.to_string(),
+        to_currency_code: to_currency_code.to_string(),
+        as_of: date,
+        rate,
+        from_rate_date,
+        to_rate_date,
+    })
+}
+
+pub(crate) async fn store_rates(
+    repo: &(dyn ExchangeRatesRepository + Send + Sync),
+    rates: &FetchedRates,
+) -> ExchangeRatesServiceResult<ExchangeRateFetch> {
+    Ok(ExchangeRateFetch {
+        source: rates.source.clone(),
+        rate_date: rates.rate_date,
+        stored_count: repo.store_fetched(rates).await?,
+    })
+}
+
+pub trait ExchangeRatesService {
+    fn list(
+        &self,
+        query: &ExchangeRateListQuery,
+    ) -> impl Future<Output = ExchangeRatesServiceResult<Vec<ExchangeRate>>> + Send;
+    fn lookup(
+        &self,
+        query: &ExchangeRateLookupQuery,
+    ) -> impl Future<Output = ExchangeRatesServiceResult<ExchangeRateLookup>> + Send;
+    fn create(
+        &self,
+        payload: &CreateExchangeRate,
+    ) -> impl Future<Output = ExchangeRatesServiceResult<ExchangeRate>> + Send;
+    fn fetch(&self) -> impl Future<Output = ExchangeRatesServiceResult<ExchangeRateFetch>> + Send;
+}
+
+impl<'a, T> ExchangeRatesService for Service<'a, T>
+where
+    T: ExchangeRatesModuleInterface,
+{
+    async fn list(
+        &self,
+        query: &ExchangeRateListQuery,
+    ) -> ExchangeRatesServiceResult<Vec<ExchangeRate>> {
+        let query = ExchangeRateListQuery {
+            currency_code: query
+                .currency_code
+                .as_deref()
+                .map(currency_code)
+                .transpose()?,
+            ..query.clone()
+        };
+        Ok(self
+            .module()
+            .exchange_rates_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ExchangeRatesServiceError::Unauthorized)?,
+            )?
+            .get_rates(&query)
+            .await?)
+    }
+
+    async fn lookup(
+        &self,
+        query: &ExchangeRateLookupQuery,
+    ) -> ExchangeRatesServiceResult<ExchangeRateLookup> {
+        let repo = self.module().exchange_rates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ExchangeRatesServiceError::Unauthorized)?,
+        )?;
+        exchange_rate(
+            &*repo,
+            &currency_code(&query.from_currency_code)?,
+            &currency_code(&query.to_currency_code)?,
+            query.date.unwrap_or_else(|| Utc::now().date_naive()),
+        )
+        .await
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateExchangeRate,
+    ) -> ExchangeRatesServiceResult<ExchangeRate> {
+        let currency_code = currency_code(&payload.currency_code)?;
+        if currency_code == BASE_CURRENCY_CODE {
+            return Err(ExchangeRatesServiceError::UnprocessableEntry(
+                "A forint árfolyama nem adható meg!",
+            ));
+        }
+        if payload.rate <= BigDecimal::zero() {
+            return Err(ExchangeRatesServiceError::UnprocessableEntry(
+                "Az árfolyamnak pozitívnak kell lennie!",
+            ));
+        }
+        let input = CreateExchangeRate {
+            currency_code,
+            rate_date: payload.rate_date,
+            rate: payload.rate.with_scale_round(8, RoundingMode::HalfUp),
+        };
+        self.module()
+            .exchange_rates_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ExchangeRatesServiceError::Unauthorized)?,
+            )?
+            .upsert_manual(&input, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    ExchangeRatesServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn fetch(&self) -> ExchangeRatesServiceResult<ExchangeRateFetch> {
+        let repo = self.module().exchange_rates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ExchangeRatesServiceError::Unauthorized)?,
+        )?;
+        let rates = self.module().rate_source()?.fetch_latest().await?;
+        store_rates(&*repo, &rates).await
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::exchange_rates::model::{
+    BASE_CURRENCY_CODE, FetchedRate, FetchedRates, SOURCE_ECB,
+};
+use crate::tenant::exchange_rates::source::{RateSource, RateSourceError, stored_rate};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::str::FromStr;
+use std::time::Duration;
+
+// NOTE: euro foreign exchange reference rates of the European Central Bank
+const API_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+
+pub struct EcbClient {
+    http: reqwest::Client,
+}
+
+impl EcbClient {
+    pub fn new(timeout: Duration) -> Result<Self, RateSourceError> {
+        Ok(Self {
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+}
+
+fn invalid(e: &dyn std::fmt::Display) -> RateSourceError {
+    RateSourceError::InvalidResponse(e.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, RateSourceError> {
+    Ok(element
+        .try_get_attribute(name)
+        .map_err(|e| invalid(&e))?
+        .map(|attribute| String::from_utf8_lossy(&attribute.value).into_owned()))
+}
+
+/// `<Cube time="2026-10-16"><Cube currency="HUF" rate="389.35"/>...</Cube>`, the rates are the
+/// price of one euro, so they are converted through the HUF rate
+fn parse_rates(document: &str) -> Result<FetchedRates, RateSourceError> {
+    let mut reader = Reader::from_str(document);
+    let mut rate_date = None;
+    let mut euro_rates = Vec::new();
+    loop {
+        match reader.read_event().map_err(|e| invalid(&e))? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Cube" => {
+                if let Some(time) = attribute(&e, "time")? {
+                    rate_date = Some(NaiveDate::from_str(&time).map_err(|e| invalid(&e))?);
+                }
+                if let (Some(currency_code), Some(rate)) =
+                    (attribute(&e, "currency")?, attribute(&e, "rate")?)
+                {
+                    euro_rates.push((
+                        currency_code,
+                        BigDecimal::from_str(&rate).map_err(|e| invalid(&e))?,
+                    ));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let rate_date =
+        rate_date.ok_or(RateSourceError::InvalidResponse("missing time".to_string()))?;
+    let huf_per_euro = euro_rates
+        .iter()
+        .find(|(currency_code, _)| currency_code == BASE_CURRENCY_CODE)
+        .map(|(_, rate)| rate.clone())
+        .filter(|rate| *rate > BigDecimal::zero())
+        .ok_or(RateSourceError::InvalidResponse("missing HUF".to_string()))?;
+    let mut rates = vec![FetchedRate {
+        currency_code: "EUR".to_string(),
+        rate: stored_rate(huf_per_euro.clone()),
+    }];
+    rates.extend(
+        euro_rates
+            .into_iter()
+            .filter(|(currency_code, rate)| {
+                currency_code != BASE_CURRENCY_CODE && *rate > BigDecimal::zero()
+            })
+            .map(|(currency_code, rate)| FetchedRate {
+                currency_code,
+                rate: stored_rate(&huf_per_euro / rate),
+            }),
+    );
+    Ok(FetchedRates {
+        source: SOURCE_ECB.to_string(),
+        rate_date,
+        rates,
+    })
+}
+
+#[async_trait]
+impl RateSource for EcbClient {
+    async fn fetch_latest(&self) -> Result<FetchedRates, RateSourceError> {
+        let response = self
+            .http
+            .get(API_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        parse_rates(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_converts_to_huf() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+  <gesmes:subject>Reference rates</gesmes:subject>
+  <Cube>
+    <Cube time="2026-10-16">
+      <Cube currency="USD" rate="1.25"/>
+      <Cube currency="HUF" rate="390.00"/>
+    </Cube>
+  </Cube>
+</gesmes:Envelope>"#;
+
+        assert_eq!(
+            parse_rates(document).unwrap(),
+            FetchedRates {
+                source: "ecb".to_string(),
+                rate_date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+                rates: vec![
+                    FetchedRate {
+                        currency_code: "EUR".to_string(),
+                        rate: BigDecimal::from(390),
+                    },
+                    FetchedRate {
+                        currency_code: "USD".to_string(),
+                        rate: BigDecimal::from(312),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_requires_huf_rate() {
+        let document =
+            r#"<Cube><Cube time="2026-10-16"><Cube currency="USD" rate="1.25"/></Cube></Cube>"#;
+
+        assert!(matches!(
+            parse_rates(document),
+            Err(RateSourceError::InvalidResponse(_))
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::exchange_rates::model::{FetchedRate, FetchedRates, SOURCE_MNB};
+use crate::tenant::exchange_rates::source::{RateSource, RateSourceError, stored_rate};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use reqwest::header::CONTENT_TYPE;
+use std::str::FromStr;
+use std::time::Duration;
+
+// NOTE: SOAP web service of the Magyar Nemzeti Bank, see https://www.mnb.hu/arfolyamok.asmx
+const API_URL: &str = "https://www.mnb.hu/arfolyamok.asmx";
+const SOAP_ACTION: &str =
+    "http://www.mnb.hu/webservices/MNBArfolyamServiceSoap/GetCurrentExchangeRates";
+const REQUEST_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?><soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns:web="http://www.mnb.hu/webservices/"><soapenv:Header/><soapenv:Body><web:GetCurrentExchangeRates/></soapenv:Body></soapenv:Envelope>"#;
+
+pub struct MnbClient {
+    http: reqwest::Client,
+}
+
+impl MnbClient {
+    pub fn new(timeout: Duration) -> Result<Self, RateSourceError> {
+        Ok(Self {
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+}
+
+fn invalid(e: &dyn std::fmt::Display) -> RateSourceError {
+    RateSourceError::InvalidResponse(e.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, RateSourceError> {
+    Ok(element
+        .try_get_attribute(name)
+        .map_err(|e| invalid(&e))?
+        .map(|attribute| String::from_utf8_lossy(&attribute.value).into_owned()))
+}
+
+// NOTE: the rates arrive as an escaped XML document inside the SOAP response
+fn result_document(response: &str) -> Result<String, RateSourceError> {
+    let mut reader = Reader::from_str(response);
+    let mut raw: Option<String> = None;
+    loop {
+        match reader.read_event().map_err(|e| invalid(&e))? {
+            Event::Start(e) if e.local_name().as_ref() == b"GetCurrentExchangeRatesResult" => {
+                raw = Some(String::new())
+            }
+            Event::Text(e) => {
+                if let Some(raw) = raw.as_mut() {
+                    raw.push_str(&e.decode().map_err(|e| invalid(&e))?);
+                }
+            }
+            Event::GeneralRef(e) => {
+                if let Some(raw) = raw.as_mut() {
+                    raw.push_str(&format!("&{};", e.decode().map_err(|e| invalid(&e))?));
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"GetCurrentExchangeRatesResult" => {
+                let raw = raw.take().unwrap_or_default();
+                return Ok(unescape(&raw).map_err(|e| invalid(&e))?.into_owned());
+            }
+            Event::Eof => {
+                return Err(RateSourceError::InvalidResponse(
+                    "missing GetCurrentExchangeRatesResult".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `<Day date="2026-10-16"><Rate unit="100" curr="JPY">241,35</Rate>...</Day>`, the value is the
+/// price of `unit` units in HUF with a decimal comma
+fn parse_rates(document: &str) -> Result<FetchedRates, RateSourceError> {
+    let mut reader = Reader::from_str(document);
+    let mut rate_date = None;
+    let mut current: Option<(String, BigDecimal, String)> = None;
+    let mut rates = Vec::new();
+    loop {
+        match reader.read_event().map_err(|e| invalid(&e))? {
+            Event::Start(e) if e.local_name().as_ref() == b"Day" => {
+                let date = attribute(&e, "date")?
+                    .ok_or(RateSourceError::InvalidResponse("missing date".to_string()))?;
+                rate_date = Some(NaiveDate::from_str(&date).map_err(|e| invalid(&e))?);
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"Rate" => {
+                let currency_code = attribute(&e, "curr")?
+                    .ok_or(RateSourceError::InvalidResponse("missing curr".to_string()))?;
+                let unit = match attribute(&e, "unit")? {
+                    Some(unit) => BigDecimal::from_str(&unit).map_err(|e| invalid(&e))?,
+                    None => BigDecimal::from(1),
+                };
+                current = Some((currency_code, unit, String::new()));
+            }
+            Event::Text(e) => {
+                if let Some((_, _, value)) = current.as_mut() {
+                    value.push_str(&e.decode().map_err(|e| invalid(&e))?);
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"Rate" => {
+                if let Some((currency_code, unit, value)) = current.take() {
+                    let value = BigDecimal::from_str(&value.trim().replace(',', "."))
+                        .map_err(|e| invalid(&e))?;
+                    if value > BigDecimal::zero() && unit > BigDecimal::zero() {
+                        rates.push(FetchedRate {
+                            currency_code,
+                            rate: stored_rate(value / unit),
+                        });
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let rate_date = rate_date.ok_or(RateSourceError::InvalidResponse("missing Day".to_string()))?;
+    if rates.is_empty() {
+        return Err(RateSourceError::InvalidResponse("no rates".to_string()));
+    }
+    Ok(FetchedRates {
+        source: SOURCE_MNB.to_string(),
+        rate_date,
+        rates,
+    })
+}
+
+#[async_trait]
+impl RateSource for MnbClient {
+    async fn fetch_latest(&self) -> Result<FetchedRates, RateSourceError> {
+        let response = self
+            .http
+            .post(API_URL)
+            .header(CONTENT_TYPE, "text/xml; charset=utf-8")
+            .header("SOAPAction", SOAP_ACTION)
+            .body(REQUEST_BODY)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        parse_rates(&result_document(&response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_soap_response() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">
+  <s:Body>
+    <GetCurrentExchangeRatesResponse xmlns="http://www.mnb.hu/webservices/">
+      <GetCurrentExchangeRatesResult>&lt;MNBCurrentExchangeRates&gt;&lt;Day date="2026-10-16"&gt;&lt;Rate unit="1" curr="EUR"&gt;389,12&lt;/Rate&gt;&lt;Rate unit="100" curr="JPY"&gt;241,35&lt;/Rate&gt;&lt;/Day&gt;&lt;/MNBCurrentExchangeRates&gt;</GetCurrentExchangeRatesResult>
+    </GetCurrentExchangeRatesResponse>
+  </s:Body>
+</s:Envelope>"#;
+
+        let rates = parse_rates(&result_document(response).unwrap()).unwrap();
+
+        assert_eq!(
+            rates,
+            FetchedRates {
+                source: "mnb".to_string(),
+                rate_date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+                rates: vec![
+                    FetchedRate {
+                        currency_code: "EUR".to_string(),
+                        rate: BigDecimal::from_str("389.12000000").unwrap(),
+                    },
+                    FetchedRate {
+                        currency_code: "JPY".to_string(),
+                        rate: BigDecimal::from_str("2.41350000").unwrap(),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_day() {
+        assert!(matches!(
+            parse_rates(
+                r#"<MNBCurrentExchangeRates><Day date="2026-10-16"></Day></MNBCurrentExchangeRates>"#
+            ),
+            Err(RateSourceError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            result_document("<Envelope><Body/></Envelope>"),
+            Err(RateSourceError::InvalidResponse(_))
+        ));
+    }
+}
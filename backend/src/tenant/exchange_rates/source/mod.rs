@@ -0,0 +1,67 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::exchange_rates::model::{FetchedRates, SOURCE_ECB, SOURCE_MNB};
+use crate::tenant::exchange_rates::source::ecb::EcbClient;
+use crate::tenant::exchange_rates::source::mnb::MnbClient;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, RoundingMode};
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+pub mod ecb;
+pub mod mnb;
+
+#[derive(Debug, Error)]
+pub enum RateSourceError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Értelmezhetetlen árfolyam-adat: {0}")]
+    InvalidResponse(String),
+
+    #[error("Nem támogatott árfolyamforrás: {0}")]
+    Unsupported(String),
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RateSource: Send + Sync {
+    /// The rates of the latest published day
+    async fn fetch_latest(&self) -> Result<FetchedRates, RateSourceError>;
+}
+
+pub fn rate_source(
+    source: &str,
+    timeout: Duration,
+) -> Result<Arc<dyn RateSource + Send + Sync>, RateSourceError> {
+    match source {
+        SOURCE_MNB => Ok(Arc::new(MnbClient::new(timeout)?)),
+        SOURCE_ECB => Ok(Arc::new(EcbClient::new(timeout)?)),
+        other => Err(RateSourceError::Unsupported(other.to_string())),
+    }
+}
+
+// NOTE: the scale of currency_rates.rate
+fn stored_rate(rate: BigDecimal) -> BigDecimal {
+    rate.with_scale_round(8, RoundingMode::HalfUp)
+}
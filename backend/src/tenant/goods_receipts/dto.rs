@@ -0,0 +1,68 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct GoodsReceiptLineInput {
+    pub purchase_order_line_id: Uuid,
+    pub quantity: BigDecimal,
+    pub unit_cost: Option<BigDecimal>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+/// Without lines everything still outstanding on the order is received
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateGoodsReceipt {
+    pub purchase_order_id: Uuid,
+    pub receipt_date: Option<NaiveDate>,
+    pub delivery_note_number: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub close_order: bool,
+    #[serde(default)]
+    pub lines: Vec<GoodsReceiptLineInput>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewGoodsReceiptLine {
+    pub purchase_order_line_id: Uuid,
+    pub product_id: Uuid,
+    pub quantity: BigDecimal,
+    pub unit_cost: BigDecimal,
+    pub currency_code: String,
+    pub tax_id: Uuid,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewGoodsReceipt {
+    pub purchase_order_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub receipt_date: NaiveDate,
+    pub delivery_note_number: Option<String>,
+    pub notes: Option<String>,
+    pub close_order: bool,
+    pub lines: Vec<NewGoodsReceiptLine>,
+}
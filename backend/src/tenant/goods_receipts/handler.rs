@@ -0,0 +1,447 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::goods_receipts::GoodsReceiptsModuleInterface;
+use crate::tenant::goods_receipts::dto::CreateGoodsReceipt;
+use crate::tenant::goods_receipts::service::GoodsReceiptsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: GoodsReceiptsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(goods_receipts_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), goods_receipts_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        goods_receipts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        goods_receipts_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: GoodsReceiptsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(goods_receipts_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), goods_receipts_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        goods_receipts_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        goods_receipts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        goods_receipts_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: GoodsReceiptsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(goods_receipts_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateGoodsReceipt>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), goods_receipts_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        goods_receipts_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        goods_receipts_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::goods_receipts::dto::NewGoodsReceipt;
+    use crate::tenant::goods_receipts::model::{GoodsReceipt, ReceivingLine};
+    use crate::tenant::goods_receipts::{
+        self, repository::MockGoodsReceiptsRepository, tests::MockGoodsReceiptsModule,
+    };
+    use crate::tenant::purchase_orders::model::PurchaseOrder;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::Sequence;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockGoodsReceiptsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut goods_receipts_module = MockGoodsReceiptsModule::new();
+        goods_receipts_module
+            .expect_goods_receipts_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        goods_receipts_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(goods_receipts::routes::routes(Arc::new(
+                goods_receipts_module,
+            ))),
+        )
+    }
+
+    fn request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/goods_receipts/create")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn purchase_order(status: &str) -> PurchaseOrder {
+        PurchaseOrder {
+            id: Uuid::new_v4(),
+            order_number: "BR-2026-00001".to_string(),
+            supplier_id: Uuid::new_v4(),
+            warehouse_id: Uuid::new_v4(),
+            currency_code: "HUF".to_string(),
+            order_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            expected_delivery_date: None,
+            supplier_reference: None,
+            notes: None,
+            status: status.to_string(),
+            sent_at: Some(Utc::now()),
+            confirmed_at: None,
+            closed_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn receiving_line(
+        ordered: i32,
+        received: i32,
+        inventory_currency_code: Option<&str>,
+    ) -> ReceivingLine {
+        ReceivingLine {
+            purchase_order_line_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            item: "Csőbilincs".to_string(),
+            ordered_quantity: BigDecimal::from(ordered),
+            received_quantity: BigDecimal::from(received),
+            unit_price: "450.00".parse().unwrap(),
+            tax_id: Uuid::new_v4(),
+            inventory_currency_code: inventory_currency_code.map(str::to_string),
+        }
+    }
+
+    fn received(line: &ReceivingLine, quantity: i32) -> ReceivingLine {
+        ReceivingLine {
+            received_quantity: BigDecimal::from(quantity),
+            ..line.clone()
+        }
+    }
+
+    /// Expects the receipt to be recorded, then the order to be reloaded with the given status
+    /// and lines
+    fn expect_receipt(
+        purchase_order: &PurchaseOrder,
+        before: Vec<ReceivingLine>,
+        check: impl Fn(&NewGoodsReceipt) -> bool + Send + 'static,
+        status_after: &str,
+        after: Vec<ReceivingLine>,
+    ) -> MockGoodsReceiptsRepository {
+        let mut repo = MockGoodsReceiptsRepository::new();
+        let mut seq = Sequence::new();
+        repo.expect_get_purchase_order()
+            .with(eq(purchase_order.id))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning({
+                let purchase_order = purchase_order.clone();
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_get_receiving_lines()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |_| Ok(before.clone()));
+        repo.expect_insert()
+            .times(1)
+            .in_sequence(&mut seq)
+            .withf(move |input, _| check(input))
+            .returning({
+                let goods_receipt = GoodsReceipt {
+                    id: Uuid::new_v4(),
+                    receipt_number: "BE-2026-00001".to_string(),
+                    purchase_order_id: purchase_order.id,
+                    warehouse_id: purchase_order.warehouse_id,
+                    receipt_date: NaiveDate::from_ymd_opt(2026, 10, 8).unwrap(),
+                    delivery_note_number: None,
+                    notes: None,
+                    created_by_id: Uuid::new_v4(),
+                    created_at: Utc::now(),
+                };
+                move |_, _| Ok(goods_receipt.clone())
+            });
+        repo.expect_get_purchase_order()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning({
+                let purchase_order = PurchaseOrder {
+                    status: status_after.to_string(),
+                    ..purchase_order.clone()
+                };
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_get_receiving_lines()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |_| Ok(after.clone()));
+        repo.expect_get_lines().times(1).returning(|_| Ok(vec![]));
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_create_without_lines_receives_everything_outstanding() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("partially_received");
+        let open_line = receiving_line(10, 4, None);
+        let done_line = receiving_line(5, 5, Some("HUF"));
+
+        let repo = expect_receipt(
+            &purchase_order,
+            vec![open_line.clone(), done_line.clone()],
+            {
+                let open_line = open_line.clone();
+                move |input| {
+                    input.lines.len() == 1
+                        && input.lines[0].purchase_order_line_id == open_line.purchase_order_line_id
+                        && input.lines[0].quantity == 6
+                        && input.lines[0].unit_cost == open_line.unit_price
+                        && input.lines[0].currency_code == "HUF"
+                        && !input.close_order
+                }
+            },
+            "closed",
+            vec![received(&open_line, 10), done_line],
+        );
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({"purchase_order_id": purchase_order.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["purchase_order_status"], json!("closed"));
+        assert_eq!(body["data"]["discrepancies"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_create_flags_over_receipt() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("sent");
+        let line = receiving_line(10, 0, Some("HUF"));
+        let other_line = receiving_line(3, 0, Some("HUF"));
+
+        let repo = expect_receipt(
+            &purchase_order,
+            vec![line.clone(), other_line.clone()],
+            |input| input.lines.len() == 1 && input.lines[0].quantity == 12,
+            "partially_received",
+            vec![received(&line, 12), other_line],
+        );
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({
+                    "purchase_order_id": purchase_order.id,
+                    "lines": [{
+                        "purchase_order_line_id": line.purchase_order_line_id,
+                        "quantity": "12",
+                        "unit_cost": null,
+                        "lot_number": null,
+                        "expiry_date": null
+                    }]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(
+            body["data"]["purchase_order_status"],
+            json!("partially_received")
+        );
+        assert_eq!(
+            body["data"]["discrepancies"],
+            json!([{
+                "purchase_order_line_id": line.purchase_order_line_id,
+                "item": "Csőbilincs",
+                "ordered_quantity": "10",
+                "received_quantity": "12",
+                "kind": "over"
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_closing_order_flags_under_receipt() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("sent");
+        let line = receiving_line(10, 0, Some("HUF"));
+
+        let repo = expect_receipt(
+            &purchase_order,
+            vec![line.clone()],
+            |input| input.close_order && input.lines[0].quantity == 8,
+            "closed",
+            vec![received(&line, 8)],
+        );
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({
+                    "purchase_order_id": purchase_order.id,
+                    "close_order": true,
+                    "lines": [{
+                        "purchase_order_line_id": line.purchase_order_line_id,
+                        "quantity": "8",
+                        "unit_cost": null,
+                        "lot_number": null,
+                        "expiry_date": null
+                    }]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["discrepancies"][0]["kind"], json!("under"));
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_cost_for_stock_in_other_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("sent");
+        let line = receiving_line(10, 0, Some("EUR"));
+
+        let mut repo = MockGoodsReceiptsRepository::new();
+        repo.expect_get_purchase_order().times(1).returning({
+            let purchase_order = purchase_order.clone();
+            move |_| Ok(purchase_order.clone())
+        });
+        repo.expect_get_receiving_lines().times(1).returning({
+            let line = line.clone();
+            move |_| Ok(vec![line.clone()])
+        });
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({"purchase_order_id": purchase_order.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_draft_order() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("draft");
+
+        let mut repo = MockGoodsReceiptsRepository::new();
+        repo.expect_get_purchase_order().times(1).returning({
+            let purchase_order = purchase_order.clone();
+            move |_| Ok(purchase_order.clone())
+        });
+        repo.expect_get_receiving_lines().never();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                active_tenant_id,
+                json!({"purchase_order_id": purchase_order.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
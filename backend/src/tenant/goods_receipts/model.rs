@@ -0,0 +1,122 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const DISCREPANCY_OVER: &str = "over";
+pub const DISCREPANCY_UNDER: &str = "under";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct GoodsReceipt {
+    pub id: Uuid,
+    pub receipt_number: String,
+    pub purchase_order_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub receipt_date: NaiveDate,
+    pub delivery_note_number: Option<String>,
+    pub notes: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct GoodsReceiptLine {
+    pub id: Uuid,
+    pub goods_receipt_id: Uuid,
+    pub purchase_order_line_id: Uuid,
+    pub product_id: Uuid,
+    pub item: String,
+    pub inventory_movement_id: Uuid,
+    pub quantity: BigDecimal,
+    pub unit_cost: BigDecimal,
+    pub currency_code: String,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+}
+
+/// Purchase order line with what has been received against it so far and the currency the
+/// stock of its product is kept in at the receiving warehouse, if there is stock yet
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ReceivingLine {
+    pub purchase_order_line_id: Uuid,
+    pub product_id: Uuid,
+    pub item: String,
+    pub ordered_quantity: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub inventory_currency_code: Option<String>,
+}
+
+impl ReceivingLine {
+    pub fn outstanding_quantity(&self) -> BigDecimal {
+        let outstanding = &self.ordered_quantity - &self.received_quantity;
+        if outstanding > BigDecimal::zero() {
+            outstanding
+        } else {
+            BigDecimal::zero()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReceiptDiscrepancy {
+    pub purchase_order_line_id: Uuid,
+    pub item: String,
+    pub ordered_quantity: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub kind: String,
+}
+
+/// Lines received above the ordered quantity, and once the order is closed, lines that were
+/// received short
+pub fn discrepancies(lines: &[ReceivingLine], order_closed: bool) -> Vec<ReceiptDiscrepancy> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let kind = if line.received_quantity > line.ordered_quantity {
+                DISCREPANCY_OVER
+            } else if order_closed && line.received_quantity < line.ordered_quantity {
+                DISCREPANCY_UNDER
+            } else {
+                return None;
+            };
+            Some(ReceiptDiscrepancy {
+                purchase_order_line_id: line.purchase_order_line_id,
+                item: line.item.clone(),
+                ordered_quantity: line.ordered_quantity.clone(),
+                received_quantity: line.received_quantity.clone(),
+                kind: kind.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GoodsReceiptDetails {
+    #[serde(flatten)]
+    pub goods_receipt: GoodsReceipt,
+    pub lines: Vec<GoodsReceiptLine>,
+    pub purchase_order_status: String,
+    pub discrepancies: Vec<ReceiptDiscrepancy>,
+}
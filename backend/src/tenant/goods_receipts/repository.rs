@@ -0,0 +1,305 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::goods_receipts::dto::NewGoodsReceipt;
+use crate::tenant::goods_receipts::model::{GoodsReceipt, GoodsReceiptLine, ReceivingLine};
+use crate::tenant::purchase_orders::model::PurchaseOrder;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait GoodsReceiptsRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<GoodsReceipt>;
+    async fn get_lines(&self, goods_receipt_id: Uuid) -> RepositoryResult<Vec<GoodsReceiptLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<GoodsReceipt>)>;
+    async fn get_purchase_order(&self, id: Uuid) -> RepositoryResult<PurchaseOrder>;
+    async fn get_receiving_lines(
+        &self,
+        purchase_order: &PurchaseOrder,
+    ) -> RepositoryResult<Vec<ReceivingLine>>;
+    async fn insert(&self, input: &NewGoodsReceipt, sub: Uuid) -> RepositoryResult<GoodsReceipt>;
+}
+
+#[async_trait]
+impl GoodsReceiptsRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<GoodsReceipt> {
+        Ok(
+            sqlx::query_as::<_, GoodsReceipt>("SELECT * FROM goods_receipts WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_lines(&self, goods_receipt_id: Uuid) -> RepositoryResult<Vec<GoodsReceiptLine>> {
+        Ok(sqlx::query_as::<_, GoodsReceiptLine>(
+            r#"
+            SELECT goods_receipt_lines.id,
+                   goods_receipt_lines.goods_receipt_id,
+                   goods_receipt_lines.purchase_order_line_id,
+                   purchase_order_lines.product_id,
+                   products.name AS item,
+                   goods_receipt_lines.inventory_movement_id,
+                   goods_receipt_lines.quantity,
+                   goods_receipt_lines.unit_cost,
+                   goods_receipt_lines.currency_code,
+                   goods_receipt_lines.lot_number,
+                   goods_receipt_lines.expiry_date
+            FROM goods_receipt_lines
+            JOIN purchase_order_lines
+                ON goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            JOIN products ON purchase_order_lines.product_id = products.id
+            WHERE goods_receipt_lines.goods_receipt_id = $1
+            ORDER BY purchase_order_lines.position, goods_receipt_lines.id
+            "#,
+        )
+        .bind(goods_receipt_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<GoodsReceipt>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM goods_receipts")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let goods_receipts = sqlx::query_as::<_, GoodsReceipt>(
+            r#"
+            SELECT *
+            FROM goods_receipts
+            ORDER BY receipt_date DESC, created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            goods_receipts,
+        ))
+    }
+
+    async fn get_purchase_order(&self, id: Uuid) -> RepositoryResult<PurchaseOrder> {
+        Ok(sqlx::query_as::<_, PurchaseOrder>(
+            "SELECT * FROM purchase_orders WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_receiving_lines(
+        &self,
+        purchase_order: &PurchaseOrder,
+    ) -> RepositoryResult<Vec<ReceivingLine>> {
+        Ok(sqlx::query_as::<_, ReceivingLine>(
+            r#"
+            SELECT purchase_order_lines.id AS purchase_order_line_id,
+                   purchase_order_lines.product_id,
+                   products.name AS item,
+                   purchase_order_lines.quantity AS ordered_quantity,
+                   COALESCE(received.quantity, 0) AS received_quantity,
+                   purchase_order_lines.unit_price,
+                   purchase_order_lines.tax_id,
+                   inventory.currency_code AS inventory_currency_code
+            FROM purchase_order_lines
+            JOIN products ON purchase_order_lines.product_id = products.id
+            LEFT JOIN inventory ON inventory.product_id = purchase_order_lines.product_id
+                AND inventory.warehouse_id = $2
+                AND inventory.deleted_at IS NULL
+            LEFT JOIN LATERAL (
+                SELECT SUM(goods_receipt_lines.quantity) AS quantity
+                FROM goods_receipt_lines
+                WHERE goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            ) AS received ON true
+            WHERE purchase_order_lines.purchase_order_id = $1
+            ORDER BY purchase_order_lines.position
+            "#,
+        )
+        .bind(purchase_order.id)
+        .bind(purchase_order.warehouse_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &NewGoodsReceipt, sub: Uuid) -> RepositoryResult<GoodsReceipt> {
+        let mut tx = self.begin().await?;
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM purchase_orders
+            WHERE id = $1
+                AND status IN ('sent', 'partially_received')
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.purchase_order_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let goods_receipt = sqlx::query_as::<_, GoodsReceipt>(
+            r#"
+            INSERT INTO goods_receipts (receipt_number, purchase_order_id, warehouse_id,
+                                        receipt_date, delivery_note_number, notes, created_by_id)
+            VALUES ('BE-' || to_char(CURRENT_DATE, 'YYYY') || '-'
+                        || lpad(nextval('goods_receipt_number_seq')::text, 5, '0'),
+                    $1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.purchase_order_id)
+        .bind(input.warehouse_id)
+        .bind(input.receipt_date)
+        .bind(&input.delivery_note_number)
+        .bind(&input.notes)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for line in &input.lines {
+            let inventory_id = match sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT id
+                FROM inventory
+                WHERE product_id = $1
+                    AND warehouse_id = $2
+                    AND deleted_at IS NULL
+                "#,
+            )
+            .bind(line.product_id)
+            .bind(input.warehouse_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            {
+                Some(inventory_id) => inventory_id,
+                None => {
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        INSERT INTO inventory (product_id, warehouse_id, currency_code, created_by_id)
+                        VALUES ($1, $2, $3, $4)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(line.product_id)
+                    .bind(input.warehouse_id)
+                    .bind(&line.currency_code)
+                    .bind(sub)
+                    .fetch_one(&mut *tx)
+                    .await?
+                }
+            };
+
+            let inventory_movement_id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO inventory_movements (
+                    inventory_id, movement_type, quantity, reference_type, reference_id,
+                    unit_price, unit_cost, tax_id, lot_number, expiry_date, created_by_id
+                ) VALUES ($1, 'in', $2, 'goods_receipts', $3, round($4, 2), $4, $5, $6, $7, $8)
+                RETURNING id
+                "#,
+            )
+            .bind(inventory_id)
+            .bind(&line.quantity)
+            .bind(goods_receipt.id)
+            .bind(&line.unit_cost)
+            .bind(line.tax_id)
+            .bind(&line.lot_number)
+            .bind(line.expiry_date)
+            .bind(sub)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO goods_receipt_lines (goods_receipt_id, purchase_order_line_id,
+                                                 inventory_movement_id, quantity, unit_cost,
+                                                 currency_code, lot_number, expiry_date)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(goods_receipt.id)
+            .bind(line.purchase_order_line_id)
+            .bind(inventory_movement_id)
+            .bind(&line.quantity)
+            .bind(&line.unit_cost)
+            .bind(&line.currency_code)
+            .bind(&line.lot_number)
+            .bind(line.expiry_date)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // NOTE: the order is closed once every line is received in full, over-receipts included
+        sqlx::query(
+            r#"
+            UPDATE purchase_orders
+            SET status = next.status,
+                closed_at = CASE WHEN next.status = 'closed' THEN NOW() ELSE closed_at END
+            FROM (
+                SELECT CASE
+                           WHEN $2 OR NOT EXISTS (
+                               SELECT 1
+                               FROM purchase_order_lines
+                               WHERE purchase_order_lines.purchase_order_id = $1
+                                 AND purchase_order_lines.quantity > (
+                                   SELECT COALESCE(SUM(goods_receipt_lines.quantity), 0)
+                                   FROM goods_receipt_lines
+                                   WHERE goods_receipt_lines.purchase_order_line_id
+                                             = purchase_order_lines.id
+                                 )
+                           ) THEN 'closed'
+                           ELSE 'partially_received'
+                       END AS status
+            ) AS next
+            WHERE purchase_orders.id = $1
+            "#,
+        )
+        .bind(input.purchase_order_id)
+        .bind(input.close_order)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(goods_receipt)
+    }
+}
@@ -0,0 +1,322 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{Empty, Money};
+use crate::tenant::goods_receipts::GoodsReceiptsModuleInterface;
+use crate::tenant::goods_receipts::dto::{
+    CreateGoodsReceipt, GoodsReceiptLineInput, NewGoodsReceipt, NewGoodsReceiptLine,
+};
+use crate::tenant::goods_receipts::model::{
+    GoodsReceipt, GoodsReceiptDetails, ReceivingLine, discrepancies,
+};
+use crate::tenant::goods_receipts::repository::GoodsReceiptsRepository;
+use crate::tenant::purchase_orders::model::{
+    PurchaseOrder, STATUS_CLOSED, STATUS_PARTIALLY_RECEIVED, STATUS_SENT,
+};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum GoodsReceiptsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for GoodsReceiptsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => GoodsReceiptsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<GoodsReceiptsServiceError> for AppError {
+    fn from(value: GoodsReceiptsServiceError) -> Self {
+        match value {
+            GoodsReceiptsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            GoodsReceiptsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            GoodsReceiptsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type GoodsReceiptsServiceResult<T> = Result<T, GoodsReceiptsServiceError>;
+
+fn optional_text(
+    value: &Option<String>,
+    max_length: usize,
+    message: &'static str,
+) -> GoodsReceiptsServiceResult<Option<String>> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) if value.chars().count() > max_length => {
+            Err(GoodsReceiptsServiceError::UnprocessableEntry(message))
+        }
+        Some(value) => Ok(Some(value.to_string())),
+    }
+}
+
+/// The stock is costed in the currency it is kept in, so the order price can only be used
+/// when that matches the currency of the order
+fn receipt_line(
+    purchase_order: &PurchaseOrder,
+    line: &ReceivingLine,
+    input: &GoodsReceiptLineInput,
+) -> GoodsReceiptsServiceResult<NewGoodsReceiptLine> {
+    if input.quantity <= BigDecimal::zero() {
+        return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+            "Az átvett mennyiségnek pozitív számnak kell lennie!",
+        ));
+    }
+    let currency_code = line
+        .inventory_currency_code
+        .clone()
+        .unwrap_or_else(|| purchase_order.currency_code.clone());
+    let order_price = Money::new(line.unit_price.clone(), &purchase_order.currency_code);
+    let unit_cost = match &input.unit_cost {
+        Some(unit_cost) => Money::new(unit_cost.clone(), &currency_code),
+        None if order_price.currency_code == currency_code => order_price,
+        None => {
+            return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+                "A termék készlete más pénznemben van nyilvántartva, meg kell adni a bekerülési egységárat!",
+            ));
+        }
+    };
+    if unit_cost.is_negative() {
+        return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+            "A bekerülési egységár nem lehet negatív!",
+        ));
+    }
+    let lot_number = optional_text(
+        &input.lot_number,
+        100,
+        "A tételszám legfeljebb 100 karakter lehet!",
+    )?;
+    if input.expiry_date.is_some() && lot_number.is_none() {
+        return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+            "Lejárati dátum csak tételszámmal együtt adható meg!",
+        ));
+    }
+    Ok(NewGoodsReceiptLine {
+        purchase_order_line_id: line.purchase_order_line_id,
+        product_id: line.product_id,
+        quantity: input.quantity.clone(),
+        unit_cost: unit_cost.amount,
+        currency_code,
+        tax_id: line.tax_id,
+        lot_number,
+        expiry_date: input.expiry_date,
+    })
+}
+
+fn build_receipt(
+    purchase_order: &PurchaseOrder,
+    receiving_lines: &[ReceivingLine],
+    payload: &CreateGoodsReceipt,
+) -> GoodsReceiptsServiceResult<NewGoodsReceipt> {
+    let lines = if payload.lines.is_empty() {
+        receiving_lines
+            .iter()
+            .filter(|line| line.outstanding_quantity() > BigDecimal::zero())
+            .map(|line| {
+                receipt_line(
+                    purchase_order,
+                    line,
+                    &GoodsReceiptLineInput {
+                        purchase_order_line_id: line.purchase_order_line_id,
+                        quantity: line.outstanding_quantity(),
+                        unit_cost: None,
+                        lot_number: None,
+                        expiry_date: None,
+                    },
+                )
+            })
+            .collect::<GoodsReceiptsServiceResult<Vec<_>>>()?
+    } else {
+        payload
+            .lines
+            .iter()
+            .map(|input| {
+                let line = receiving_lines
+                    .iter()
+                    .find(|line| line.purchase_order_line_id == input.purchase_order_line_id)
+                    .ok_or(GoodsReceiptsServiceError::UnprocessableEntry(
+                        "A tétel nem ehhez a beszerzési rendeléshez tartozik!",
+                    ))?;
+                receipt_line(purchase_order, line, input)
+            })
+            .collect::<GoodsReceiptsServiceResult<Vec<_>>>()?
+    };
+    if lines.is_empty() {
+        return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+            "A beszerzési rendelésen nincs átvehető tétel!",
+        ));
+    }
+    let receipt_date = payload
+        .receipt_date
+        .unwrap_or_else(|| Utc::now().date_naive());
+    if receipt_date < purchase_order.order_date {
+        return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+            "Az átvétel dátuma nem lehet korábbi a rendelés dátumánál!",
+        ));
+    }
+    Ok(NewGoodsReceipt {
+        purchase_order_id: purchase_order.id,
+        warehouse_id: purchase_order.warehouse_id,
+        receipt_date,
+        delivery_note_number: optional_text(
+            &payload.delivery_note_number,
+            100,
+            "A szállítólevél száma legfeljebb 100 karakter lehet!",
+        )?,
+        notes: payload
+            .notes
+            .as_ref()
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        close_order: payload.close_order,
+        lines,
+    })
+}
+
+async fn details(
+    repo: &dyn GoodsReceiptsRepository,
+    goods_receipt: GoodsReceipt,
+) -> GoodsReceiptsServiceResult<GoodsReceiptDetails> {
+    let purchase_order = repo
+        .get_purchase_order(goods_receipt.purchase_order_id)
+        .await?;
+    let receiving_lines = repo.get_receiving_lines(&purchase_order).await?;
+    Ok(GoodsReceiptDetails {
+        lines: repo.get_lines(goods_receipt.id).await?,
+        discrepancies: discrepancies(&receiving_lines, purchase_order.status == STATUS_CLOSED),
+        purchase_order_status: purchase_order.status,
+        goods_receipt,
+    })
+}
+
+pub trait GoodsReceiptsService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = GoodsReceiptsServiceResult<GoodsReceiptDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = GoodsReceiptsServiceResult<(PaginatorMeta, Vec<GoodsReceipt>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreateGoodsReceipt,
+    ) -> impl Future<Output = GoodsReceiptsServiceResult<GoodsReceiptDetails>> + Send;
+}
+
+impl<'a, T> GoodsReceiptsService for Service<'a, T>
+where
+    T: GoodsReceiptsModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> GoodsReceiptsServiceResult<GoodsReceiptDetails> {
+        let repo = self.module().goods_receipts_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(GoodsReceiptsServiceError::Unauthorized)?,
+        )?;
+        details(&*repo, repo.get_by_id(id).await?).await
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> GoodsReceiptsServiceResult<(PaginatorMeta, Vec<GoodsReceipt>)> {
+        Ok(self
+            .module()
+            .goods_receipts_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(GoodsReceiptsServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateGoodsReceipt,
+    ) -> GoodsReceiptsServiceResult<GoodsReceiptDetails> {
+        let repo = self.module().goods_receipts_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(GoodsReceiptsServiceError::Unauthorized)?,
+        )?;
+        let purchase_order = repo.get_purchase_order(payload.purchase_order_id).await?;
+        if purchase_order.status != STATUS_SENT
+            && purchase_order.status != STATUS_PARTIALLY_RECEIVED
+        {
+            return Err(GoodsReceiptsServiceError::UnprocessableEntry(
+                "Csak elküldött, nyitott beszerzési rendelésre vehető át áru!",
+            ));
+        }
+        let receiving_lines = repo.get_receiving_lines(&purchase_order).await?;
+        let input = build_receipt(&purchase_order, &receiving_lines, payload)?;
+        let goods_receipt = repo.insert(&input, self.claims()?.sub()).await?;
+        details(&*repo, goods_receipt).await
+    }
+}
@@ -0,0 +1,239 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::utils::{csv_reader, non_empty, parse_amount};
+use crate::common::value_object::ValueObjectRequired;
+use crate::tenant::currencies::types::CurrencyCode;
+use crate::tenant::products::types::product::{ProductName, ProductSku};
+use crate::tenant::products::types::unit_of_measure::UnitsOfMeasure;
+use bigdecimal::{BigDecimal, Zero};
+use serde::Deserialize;
+
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct InventoryImportQuery {
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct InventoryImportRecord {
+    warehouse: String,
+    product: String,
+    sku: String,
+    unit_of_measure: String,
+    quantity: String,
+    unit_cost: String,
+    currency_code: String,
+}
+
+/// One CSV row after parsing. Rows with a non-empty `errors` list are reported
+/// back, but never touch the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryImportRow {
+    pub line: u64,
+    pub warehouse: String,
+    pub product: Option<String>,
+    pub sku: Option<String>,
+    pub unit_of_measure: Option<String>,
+    pub quantity: Option<BigDecimal>,
+    pub unit_cost: Option<BigDecimal>,
+    pub currency_code: String,
+    pub errors: Vec<String>,
+}
+
+impl InventoryImportRow {
+    /// Parses an uploaded CSV file, see [`csv_reader`] for the accepted formats.
+    pub fn parse_csv(data: &[u8]) -> Result<Vec<Self>, &'static str> {
+        let (mut reader, semicolon) = csv_reader(data)?;
+
+        let headers = reader
+            .headers()
+            .map_err(|_| "A fájl fejléce nem olvasható!")?
+            .clone();
+        if !headers.iter().any(|h| h == "warehouse") || !headers.iter().any(|h| h == "quantity") {
+            return Err("A fájl fejlécének tartalmaznia kell a warehouse és quantity oszlopokat!");
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            if rows.len() == MAX_IMPORT_ROWS {
+                return Err("Egyszerre legfeljebb 5000 sor importálható!");
+            }
+            let parsed = record.and_then(|record| {
+                let line = record.position().map_or(0, |p| p.line());
+                Ok((
+                    line,
+                    record.deserialize::<InventoryImportRecord>(Some(&headers))?,
+                ))
+            });
+            match parsed {
+                Ok((line, record)) => rows.push(Self::from_record(line, record, semicolon)),
+                Err(e) => rows.push(Self {
+                    line: e.position().map_or(0, |p| p.line()),
+                    warehouse: String::new(),
+                    product: None,
+                    sku: None,
+                    unit_of_measure: None,
+                    quantity: None,
+                    unit_cost: None,
+                    currency_code: String::new(),
+                    errors: vec!["A sor nem olvasható!".to_owned()],
+                }),
+            }
+        }
+        if rows.is_empty() {
+            return Err("A fájl nem tartalmaz egy sort sem!");
+        }
+        Ok(rows)
+    }
+
+    fn from_record(line: u64, record: InventoryImportRecord, decimal_comma: bool) -> Self {
+        let mut errors = Vec::new();
+
+        let warehouse = record.warehouse.trim().to_owned();
+        if warehouse.is_empty() {
+            errors.push("A raktár megadása kötelező!".to_owned());
+        }
+
+        let product = non_empty(&record.product);
+        if let Some(product) = &product
+            && let Err(e) = product.parse::<ValueObjectRequired<ProductName>>()
+        {
+            errors.push(e.to_string());
+        }
+        let sku = non_empty(&record.sku);
+        if let Some(sku) = &sku
+            && let Err(e) = sku.parse::<ValueObjectRequired<ProductSku>>()
+        {
+            errors.push(e.to_string());
+        }
+        if product.is_none() && sku.is_none() {
+            errors.push("A termék nevének vagy cikkszámának megadása kötelező!".to_owned());
+        }
+
+        let unit_of_measure = non_empty(&record.unit_of_measure);
+        if let Some(unit_of_measure) = &unit_of_measure
+            && let Err(e) = unit_of_measure.parse::<ValueObjectRequired<UnitsOfMeasure>>()
+        {
+            errors.push(e.to_string());
+        }
+
+        let quantity = match parse_amount(&record.quantity, decimal_comma) {
+            None => {
+                errors.push("A mennyiség megadása kötelező!".to_owned());
+                None
+            }
+            Some(Err(())) => {
+                errors.push("Érvénytelen mennyiség!".to_owned());
+                None
+            }
+            Some(Ok(quantity)) if quantity < BigDecimal::zero() => {
+                errors.push("A mennyiség nem lehet negatív!".to_owned());
+                None
+            }
+            Some(Ok(quantity)) => Some(quantity),
+        };
+        let unit_cost = match parse_amount(&record.unit_cost, decimal_comma) {
+            None => None,
+            Some(Err(())) => {
+                errors.push("Érvénytelen egységköltség!".to_owned());
+                None
+            }
+            Some(Ok(unit_cost)) if unit_cost < BigDecimal::zero() => {
+                errors.push("Az egységköltség nem lehet negatív!".to_owned());
+                None
+            }
+            Some(Ok(unit_cost)) => Some(unit_cost),
+        };
+
+        let currency_code = non_empty(&record.currency_code)
+            .unwrap_or_else(|| "HUF".to_owned())
+            .to_uppercase();
+        if let Err(e) = currency_code.parse::<ValueObjectRequired<CurrencyCode>>() {
+            errors.push(e.to_string());
+        }
+
+        Self {
+            line,
+            warehouse,
+            product,
+            sku,
+            unit_of_measure,
+            quantity,
+            unit_cost,
+            currency_code,
+            errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_csv_comma_separated() {
+        let rows = InventoryImportRow::parse_csv(
+            b"warehouse,product,sku,unit_of_measure,quantity,unit_cost\n\
+              Main,Cable,CSV-10,m,12.5,100\n\
+              Main,,,,-1,abc\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(
+            rows[0].quantity,
+            Some(BigDecimal::from_str("12.5").unwrap())
+        );
+        assert_eq!(rows[0].currency_code, "HUF");
+        assert_eq!(rows[1].line, 3);
+        assert_eq!(rows[1].errors.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_csv_semicolon_with_decimal_comma() {
+        let rows = InventoryImportRow::parse_csv(
+            "\u{feff}warehouse;sku;quantity;unit_cost;currency_code\nMain;CSV-10;3,5;1200,25;eur\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(rows[0].quantity, Some(BigDecimal::from_str("3.5").unwrap()));
+        assert_eq!(
+            rows[0].unit_cost,
+            Some(BigDecimal::from_str("1200.25").unwrap())
+        );
+        assert_eq!(rows[0].currency_code, "EUR");
+    }
+
+    #[test]
+    fn test_parse_csv_missing_columns() {
+        assert!(InventoryImportRow::parse_csv(b"product,sku\nCable,CSV-10\n").is_err());
+        assert!(InventoryImportRow::parse_csv(b"warehouse,quantity\n").is_err());
+    }
+}
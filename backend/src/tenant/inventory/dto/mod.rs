@@ -17,5 +17,10 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod import;
+pub mod location;
+pub mod lookup;
+pub mod lot;
 pub mod print;
+pub mod reorder;
 pub mod user_input;
@@ -18,16 +18,22 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::inventory::InventoryModuleInterface;
+use crate::tenant::inventory::dto::import::InventoryImportQuery;
+use crate::tenant::inventory::dto::location::InventoryLocation;
+use crate::tenant::inventory::dto::lookup::InventoryLookupQuery;
+use crate::tenant::inventory::dto::lot::{FefoQuery, LotNumberQuery};
 use crate::tenant::inventory::dto::print::InventoryResolvedPrint;
+use crate::tenant::inventory::dto::reorder::ReorderSuggestionQuery;
 use crate::tenant::inventory::dto::user_input::{InventoryUserInput, InventoryUserInputHelper};
 use crate::tenant::inventory::service::InventoryService;
 use crate::tenant::inventory::types::inventory::{InventoryFilterBy, InventoryOrderBy};
+use axum::body::Bytes;
 use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
@@ -94,6 +100,223 @@ pub async fn update<M: InventoryModuleInterface>(
     .into_response())
 }
 
+pub async fn set_location<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<InventoryLocation>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    map_handler_err(
+        service.set_location(&payload).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A tárolóhely mentése sikeresen megtörtént",
+            ))
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn low_stock<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(service.get_low_stock().await, inventory_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn lookup<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<InventoryLookupQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(service.lookup(&payload).await, inventory_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn import<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<InventoryImportQuery>,
+    body: Bytes,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(
+        service.import(&payload, &body).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reorder_suggestions<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<ReorderSuggestionQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(
+        service.get_reorder_suggestions(&payload).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn subscribe_low_stock_alerts<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    map_handler_err(
+        service.subscribe_low_stock_alerts().await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az alacsony készlet értesítések bekapcsolása sikeresen megtörtént",
+            ))
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn unsubscribe_low_stock_alerts<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    map_handler_err(
+        service.unsubscribe_low_stock_alerts().await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az alacsony készlet értesítések kikapcsolása sikeresen megtörtént",
+            ))
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn lots<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(
+        service.get_lots(payload.uuid).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn fefo<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<FefoQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let tz = map_handler_err(claims.tz(), inventory_module.clone()).await?;
+    let result = map_handler_err(
+        service.suggest_fefo(&payload, tz).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn lot_locations<M: InventoryModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_module): State<Arc<M>>,
+    Query(payload): Query<LotNumberQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_module.clone());
+    let result = map_handler_err(
+        service.get_lot_locations(&payload.lot_number).await,
+        inventory_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn delete<M: InventoryModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(inventory_module): State<Arc<M>>,
@@ -232,7 +455,14 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::inventory::model::InventoryResolved;
+    use crate::manager::tenant_limits::model::TenantLimits;
+    use crate::manager::tenant_limits::repository::MockTenantLimitsRepository;
+    use crate::tenant::inventory::dto::import::InventoryImportRow;
+    use crate::tenant::inventory::model::{
+        InventoryConsumption, InventoryImportLine, InventoryImportReport, InventoryLookup,
+        InventoryLookupProduct, InventoryLookupReservation, InventoryLookupStock, InventoryLot,
+        InventoryResolved, ReorderSuggestion,
+    };
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::inventory::{
@@ -241,6 +471,7 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
     use chrono::{DateTime, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
@@ -360,6 +591,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -400,6 +632,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -482,6 +715,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -605,6 +839,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -645,6 +880,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -727,6 +963,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -855,6 +1092,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -893,6 +1131,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -975,6 +1214,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -1136,6 +1376,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             }
@@ -1189,6 +1430,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1241,6 +1483,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1428,6 +1671,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!",
             }
         });
@@ -1481,6 +1725,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1534,6 +1779,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1639,6 +1885,59 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_set_location_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let inventory_id = Uuid::new_v4();
+        let mut repo = MockInventoryRepository::new();
+
+        repo.expect_set_location()
+            .times(1)
+            .withf(move |location| {
+                *location
+                    == InventoryLocation {
+                        id: inventory_id,
+                        zone: Some("A".to_string()),
+                        bin_location: None,
+                    }
+            })
+            .returning(|_| Ok(()));
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/inventory/set_location")
+            .body(json!({"id": inventory_id, "zone": " A ", "bin_location": ""}).to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_delete_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
@@ -1712,6 +2011,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1752,6 +2052,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1932,6 +2233,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1973,6 +2275,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2007,4 +2310,407 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_fefo_skips_expired_lots() {
+        let active_tenant_id = Uuid::new_v4();
+        let inventory_id = Uuid::new_v4();
+        let today = Utc::now().date_naive();
+        let lot = |lot_number: &str, days: i64, quantity: i32| InventoryLot {
+            lot_number: lot_number.to_string(),
+            expiry_date: Some(today + chrono::Duration::days(days)),
+            quantity: quantity.into(),
+        };
+
+        let mut repo = MockInventoryRepository::new();
+        repo.expect_get_lots()
+            .times(1)
+            .with(eq(inventory_id))
+            .returning({
+                let lots = vec![lot("L1", -2, 10), lot("L2", 5, 4), lot("L3", 30, 10)];
+                move |_| Ok(lots.clone())
+            });
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!(
+                "/api/inventory/fefo?uuid={inventory_id}&quantity=6"
+            ))
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": [lot("L2", 5, 4), lot("L3", 30, 2)]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_low_stock_alerts_uses_current_user() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let mut repo = MockInventoryRepository::new();
+        repo.expect_subscribe_low_stock_alerts()
+            .times(1)
+            .with(eq(user_id))
+            .returning(|_| Ok(()));
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .method("PUT")
+            .uri("/api/inventory/low_stock_alerts/subscribe")
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_suggestions_from_consumption() {
+        let active_tenant_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+        let row = move |product: &str, available: i32, lead_time_days: Option<i32>| {
+            InventoryConsumption {
+                inventory_id: Uuid::new_v4(),
+                product_id: Uuid::new_v4(),
+                product: product.to_string(),
+                warehouse_id,
+                warehouse: "Központi".to_string(),
+                quantity_available: available.into(),
+                minimum_stock: Some(10.into()),
+                maximum_stock: Some(50.into()),
+                consumed: 60.into(),
+                supplier_id: lead_time_days.map(|_| supplier_id),
+                supplier: lead_time_days.map(|_| "Csavar Nagyker Kft.".to_string()),
+                supplier_sku: None,
+                purchase_price: None,
+                currency_code: None,
+                lead_time_days,
+            }
+        };
+
+        let mut repo = MockInventoryRepository::new();
+        repo.expect_get_consumption()
+            .times(1)
+            .withf(move |_, w| *w == Some(warehouse_id))
+            .returning(move |_, _| {
+                Ok(vec![
+                    row("Csavar", 20, None),
+                    row("Anya", 100, None),
+                    row("Alátét", 40, Some(5)),
+                ])
+            });
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!(
+                "/api/inventory/reorder_suggestions?warehouse_id={warehouse_id}&window_days=30&cover_days=14"
+            ))
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let suggestions: Vec<ReorderSuggestion> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].product, "Csavar");
+        assert_eq!(suggestions[0].daily_consumption, BigDecimal::from(2));
+        assert_eq!(suggestions[0].reorder_point, BigDecimal::from(38));
+        assert_eq!(suggestions[0].suggested_quantity, BigDecimal::from(30));
+        assert_eq!(suggestions[0].supplier_id, None);
+        assert_eq!(suggestions[1].product, "Alátét");
+        assert_eq!(suggestions[1].reorder_point, BigDecimal::from(48));
+        assert_eq!(suggestions[1].suggested_quantity, BigDecimal::from(10));
+        assert_eq!(suggestions[1].supplier_id, Some(supplier_id));
+        assert_eq!(suggestions[1].lead_time_days, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_by_code() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = InventoryLookupProduct {
+            id: Uuid::new_v4(),
+            name: "Csavar".to_string(),
+            sku: Some("CSV-10".to_string()),
+            barcode: Some("5901234123457".to_string()),
+            unit_of_measure: "db".to_string(),
+            status: "active".to_string(),
+        };
+        let stock = InventoryLookupStock {
+            inventory_id: Uuid::new_v4(),
+            warehouse_id: Uuid::new_v4(),
+            warehouse: "Központi".to_string(),
+            quantity_on_hand: 10.into(),
+            quantity_reserved: 4.into(),
+            quantity_available: 6.into(),
+            status: "active".to_string(),
+        };
+        let reservation = InventoryLookupReservation {
+            id: Uuid::new_v4(),
+            inventory_id: stock.inventory_id,
+            warehouse_id: stock.warehouse_id,
+            quantity: 4.into(),
+            reference_type: Some("worksheets".to_string()),
+            reference_id: Some(Uuid::new_v4()),
+            reserved_until: None,
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockInventoryRepository::new();
+        repo.expect_lookup_product()
+            .times(1)
+            .withf(|code| code == "5901234123457")
+            .returning({
+                let product = product.clone();
+                move |_| Ok(product.clone())
+            });
+        repo.expect_get_product_stock()
+            .times(1)
+            .with(eq(product.id))
+            .returning({
+                let stock = stock.clone();
+                move |_| Ok(vec![stock.clone()])
+            });
+        repo.expect_get_product_open_reservations()
+            .times(1)
+            .with(eq(product.id))
+            .returning({
+                let reservation = reservation.clone();
+                move |_| Ok(vec![reservation.clone()])
+            });
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri("/api/inventory/lookup?code=%205901234123457%20")
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let lookup: InventoryLookup =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            lookup,
+            InventoryLookup {
+                product,
+                stock: vec![stock],
+                reservations: vec![reservation],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_dry_run() {
+        let active_tenant_id = Uuid::new_v4();
+        let report = InventoryImportReport {
+            import_id: None,
+            applied: false,
+            row_count: 2,
+            error_count: 1,
+            products_created: 0,
+            inventory_created: 1,
+            movements_created: 1,
+            lines: vec![
+                InventoryImportLine {
+                    line: 2,
+                    warehouse: "Központi".to_string(),
+                    product: None,
+                    sku: Some("CSV-10".to_string()),
+                    quantity: Some(12.into()),
+                    previous_quantity: Some(0.into()),
+                    difference: Some(12.into()),
+                    product_created: false,
+                    inventory_created: true,
+                    errors: vec![],
+                },
+                InventoryImportLine {
+                    line: 3,
+                    warehouse: "Központi".to_string(),
+                    product: Some("Csavar".to_string()),
+                    sku: None,
+                    quantity: None,
+                    previous_quantity: None,
+                    difference: None,
+                    product_created: false,
+                    inventory_created: false,
+                    errors: vec!["Érvénytelen mennyiség!".to_string()],
+                },
+            ],
+        };
+
+        let mut repo = MockInventoryRepository::new();
+        repo.expect_import()
+            .times(1)
+            .withf(|rows: &[InventoryImportRow], _, limits, apply| {
+                rows.len() == 2
+                    && rows[0].errors.is_empty()
+                    && rows[1].errors == vec!["Érvénytelen mennyiség!".to_string()]
+                    && limits.as_ref().and_then(|limits| limits.max_products) == Some(10)
+                    && !apply
+            })
+            .returning({
+                let report = report.clone();
+                move |_, _, _, _| Ok(report.clone())
+            });
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_users: None,
+                    max_products: Some(10),
+                    max_storage_mb: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }))
+            });
+
+        let mut app_state = MockInventoryModule::new();
+        let repo = Arc::new(repo);
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(move || tenant_limits_repo.clone());
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "text/csv")
+            .method("POST")
+            .uri("/api/inventory/import")
+            .body(Body::from(
+                "warehouse,product,sku,quantity\nKözponti,,CSV-10,12\nKözponti,Csavar,,sok\n",
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: InventoryImportReport =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, report);
+    }
 }
@@ -0,0 +1,182 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::email_template::EmailTemplate;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::inventory::InventoryModuleInterface;
+use crate::tenant::inventory::model::LowStockItem;
+use chrono::{NaiveDate, Utc};
+use lettre::{
+    AsyncTransport,
+    message::Mailbox,
+    transport::smtp::{Error, response::Response},
+};
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{Instant, interval_at};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+#[derive(Serialize)]
+struct WarehouseLowStock {
+    warehouse: String,
+    items: Vec<LowStockItem>,
+}
+
+fn group_by_warehouse(items: Vec<LowStockItem>) -> Vec<WarehouseLowStock> {
+    let mut groups: Vec<(Uuid, WarehouseLowStock)> = vec![];
+    for item in items {
+        match groups.iter_mut().find(|(id, _)| *id == item.warehouse_id) {
+            Some((_, group)) => group.items.push(item),
+            None => groups.push((
+                item.warehouse_id,
+                WarehouseLowStock {
+                    warehouse: item.warehouse.clone(),
+                    items: vec![item],
+                },
+            )),
+        }
+    }
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+async fn send_tenant_alerts<M: InventoryModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    as_of: NaiveDate,
+) -> anyhow::Result<()> {
+    let repo = module.inventory_repo(tenant_id)?;
+    let items = repo.get_low_stock().await?;
+    if items.is_empty() {
+        return Ok(());
+    }
+    let inventory_ids: Vec<Uuid> = items.iter().map(|item| item.inventory_id).collect();
+    repo.insert_low_stock_notifications(&inventory_ids).await?;
+
+    let recipients = repo.get_low_stock_recipients().await?;
+    if recipients.is_empty() {
+        return Ok(());
+    }
+    let warehouses = group_by_warehouse(items);
+    let from = Mailbox::new(
+        Some(module.config().mail().default_from_name().to_owned()),
+        module.config().mail().default_from().parse()?,
+    );
+    for recipient in recipients {
+        let to = match recipient.email.parse() {
+            Ok(address) => Mailbox::new(Some(recipient.name.clone()), address),
+            Err(e) => {
+                warn!("Invalid low stock alert email {}: {}", recipient.email, e);
+                continue;
+            }
+        };
+        let message = EmailTemplate::LowStockDigest
+            .render(&json!({
+                "name": recipient.name,
+                "as_of": as_of,
+                "warehouses": warehouses,
+            }))?
+            .into_message(from.clone(), to)?;
+        if let Err(e) = module.send(message).await {
+            warn!(
+                "Could not send low stock digest to {}: {}",
+                recipient.email, e
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_low_stock_alerts<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let period = Duration::from_secs(
+        app_state
+            .config()
+            .inventory()
+            .low_stock_alert_interval_hours()
+            * 3600,
+    );
+    tokio::spawn(async move {
+        let mut interval = interval_at(Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for low stock alerts: {}", e);
+                    continue;
+                }
+            };
+            let as_of = Utc::now().date_naive();
+            for tenant in tenants {
+                if let Err(e) = send_tenant_alerts(&*app_state, tenant.id, as_of).await {
+                    error!("Low stock alerts failed for tenant {}: {}", tenant.id, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+
+    fn item(warehouse_id: Uuid, warehouse: &str, product: &str) -> LowStockItem {
+        LowStockItem {
+            inventory_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            product: product.to_string(),
+            warehouse_id,
+            warehouse: warehouse.to_string(),
+            quantity_available: BigDecimal::from(2),
+            minimum_stock: BigDecimal::from(10),
+        }
+    }
+
+    #[test]
+    fn test_group_by_warehouse_keeps_order() {
+        let main = Uuid::new_v4();
+        let outlet = Uuid::new_v4();
+        let groups = group_by_warehouse(vec![
+            item(main, "Központi", "Anya"),
+            item(outlet, "Üzlet", "Csavar"),
+            item(main, "Központi", "Csavar"),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].warehouse, "Központi");
+        assert_eq!(groups[0].items.len(), 2);
+        assert_eq!(groups[1].warehouse, "Üzlet");
+        assert_eq!(groups[1].items.len(), 1);
+    }
+}
@@ -20,6 +20,7 @@
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
 use crate::common::{AppState, BaseModule};
+use crate::manager::tenant_limits::repository::TenantLimitsRepository;
 use crate::tenant::currencies::repository::CurrenciesRepository;
 use crate::tenant::inventory::repository::InventoryRepository;
 use crate::tenant::products::repository::ProductsRepository;
@@ -35,6 +36,7 @@ use uuid::Uuid;
 
 pub mod dto;
 mod handler;
+pub mod low_stock;
 pub mod model;
 pub(crate) mod repository;
 pub(crate) mod routes;
@@ -62,6 +64,7 @@ pub trait InventoryModuleInterface: BaseModule {
         &self,
         tenant_id: Uuid,
     ) -> RepositoryResult<Arc<dyn TaxesRepository + Send + Sync>>;
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
 }
 
 impl<P, T> InventoryModuleInterface for AppState<P, T>
@@ -100,6 +103,9 @@ where
     ) -> RepositoryResult<Arc<dyn TaxesRepository + Send + Sync>> {
         Ok(self.get_tenant_pool(tenant_id)?)
     }
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync> {
+        self.get_main_pool()
+    }
 }
 
 #[cfg(test)]
@@ -145,6 +151,7 @@ pub mod tests {
                 &self,
                 tenant_id: Uuid,
             ) -> RepositoryResult<Arc<dyn TaxesRepository + Send + Sync>>;
+            fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
         }
     );
 }
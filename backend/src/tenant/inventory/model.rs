@@ -18,7 +18,7 @@
  */
 
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -62,3 +62,154 @@ pub struct InventoryResolved {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLot {
+    pub lot_number: String,
+    pub expiry_date: Option<NaiveDate>,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct LowStockItem {
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub quantity_available: BigDecimal,
+    pub minimum_stock: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct LowStockRecipient {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryConsumption {
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub quantity_available: BigDecimal,
+    pub minimum_stock: Option<BigDecimal>,
+    pub maximum_stock: Option<BigDecimal>,
+    pub consumed: BigDecimal,
+    pub supplier_id: Option<Uuid>,
+    pub supplier: Option<String>,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReorderSuggestion {
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub quantity_available: BigDecimal,
+    pub minimum_stock: Option<BigDecimal>,
+    pub maximum_stock: Option<BigDecimal>,
+    pub consumed: BigDecimal,
+    pub daily_consumption: BigDecimal,
+    pub reorder_point: BigDecimal,
+    pub suggested_quantity: BigDecimal,
+    pub supplier_id: Option<Uuid>,
+    pub supplier: Option<String>,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLotLocation {
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub lot_number: String,
+    pub expiry_date: Option<NaiveDate>,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLookupProduct {
+    pub id: Uuid,
+    pub name: String,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub unit_of_measure: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLookupStock {
+    pub inventory_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub quantity_on_hand: BigDecimal,
+    pub quantity_reserved: BigDecimal,
+    pub quantity_available: BigDecimal,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLookupReservation {
+    pub id: Uuid,
+    pub inventory_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub quantity: BigDecimal,
+    pub reference_type: Option<String>,
+    pub reference_id: Option<Uuid>,
+    pub reserved_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct BundleComponentStock {
+    pub component_id: Uuid,
+    pub quantity: BigDecimal,
+    pub inventory_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InventoryLookup {
+    pub product: InventoryLookupProduct,
+    pub stock: Vec<InventoryLookupStock>,
+    pub reservations: Vec<InventoryLookupReservation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InventoryImportLine {
+    pub line: u64,
+    pub warehouse: String,
+    pub product: Option<String>,
+    pub sku: Option<String>,
+    pub quantity: Option<BigDecimal>,
+    pub previous_quantity: Option<BigDecimal>,
+    pub difference: Option<BigDecimal>,
+    pub product_created: bool,
+    pub inventory_created: bool,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InventoryImportReport {
+    pub import_id: Option<Uuid>,
+    pub applied: bool,
+    pub row_count: usize,
+    pub error_count: usize,
+    pub products_created: i32,
+    pub inventory_created: i32,
+    pub movements_created: i32,
+    pub lines: Vec<InventoryImportLine>,
+}
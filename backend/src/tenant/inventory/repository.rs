@@ -21,13 +21,25 @@ use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::manager::tenant_limits::model::TenantLimits;
+use crate::tenant::inventory::dto::import::InventoryImportRow;
+use crate::tenant::inventory::dto::location::InventoryLocation;
 use crate::tenant::inventory::dto::user_input::InventoryUserInput;
-use crate::tenant::inventory::model::{Inventory, InventoryResolved};
+use crate::tenant::inventory::model::{
+    BundleComponentStock, Inventory, InventoryConsumption, InventoryImportLine,
+    InventoryImportReport, InventoryLookupProduct, InventoryLookupReservation,
+    InventoryLookupStock, InventoryLot, InventoryLotLocation, InventoryResolved, LowStockItem,
+    LowStockRecipient,
+};
 use crate::tenant::inventory::types::inventory::{InventoryFilterBy, InventoryOrderBy};
+use crate::tenant::products::repository::lock_active_products;
 use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
-use sqlx::{AssertSqlSafe, PgPool};
+use sqlx::{Acquire, AssertSqlSafe, PgPool};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
@@ -47,6 +59,44 @@ pub trait InventoryRepository: Send + Sync {
     ) -> RepositoryResult<Inventory>;
     async fn update(&self, inventory: &InventoryUserInput) -> RepositoryResult<Inventory>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn set_location(&self, location: &InventoryLocation) -> RepositoryResult<()>;
+    async fn get_lots(&self, inventory_id: Uuid) -> RepositoryResult<Vec<InventoryLot>>;
+    async fn get_lot_locations(
+        &self,
+        lot_number: &str,
+    ) -> RepositoryResult<Vec<InventoryLotLocation>>;
+    async fn get_low_stock(&self) -> RepositoryResult<Vec<LowStockItem>>;
+    async fn get_consumption(
+        &self,
+        since: DateTime<Utc>,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<InventoryConsumption>>;
+    async fn lookup_product(&self, code: &str) -> RepositoryResult<InventoryLookupProduct>;
+    async fn get_product_stock(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<InventoryLookupStock>>;
+    async fn get_product_open_reservations(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<InventoryLookupReservation>>;
+    async fn get_bundle_stock(
+        &self,
+        bundle_id: Uuid,
+        warehouse_id: Uuid,
+    ) -> RepositoryResult<Vec<BundleComponentStock>>;
+    async fn get_low_stock_recipients(&self) -> RepositoryResult<Vec<LowStockRecipient>>;
+    async fn subscribe_low_stock_alerts(&self, user_id: Uuid) -> RepositoryResult<()>;
+    async fn unsubscribe_low_stock_alerts(&self, user_id: Uuid) -> RepositoryResult<()>;
+    async fn insert_low_stock_notifications(&self, inventory_ids: &[Uuid])
+    -> RepositoryResult<u64>;
+    async fn import(
+        &self,
+        rows: &[InventoryImportRow],
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+        apply: bool,
+    ) -> RepositoryResult<InventoryImportReport>;
 }
 
 #[async_trait]
@@ -320,4 +370,607 @@ impl InventoryRepository for PgPool {
 
         Ok(())
     }
+
+    async fn set_location(&self, location: &InventoryLocation) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE inventory
+            SET zone = $1,
+                bin_location = $2
+            WHERE id = $3
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(location.zone.as_deref())
+        .bind(location.bin_location.as_deref())
+        .bind(location.id)
+        .execute(self)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound));
+        }
+
+        Ok(())
+    }
+
+    async fn get_lots(&self, inventory_id: Uuid) -> RepositoryResult<Vec<InventoryLot>> {
+        Ok(sqlx::query_as::<_, InventoryLot>(
+            r#"
+            SELECT lot_number,
+                   MIN(expiry_date) AS expiry_date,
+                   SUM(quantity) AS quantity
+            FROM inventory_movements
+            WHERE inventory_id = $1 AND lot_number IS NOT NULL
+            GROUP BY lot_number
+            HAVING SUM(quantity) > 0
+            ORDER BY MIN(expiry_date) NULLS LAST, lot_number
+            "#,
+        )
+        .bind(inventory_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_lot_locations(
+        &self,
+        lot_number: &str,
+    ) -> RepositoryResult<Vec<InventoryLotLocation>> {
+        Ok(sqlx::query_as::<_, InventoryLotLocation>(
+            r#"
+            SELECT inventory.id AS inventory_id,
+                   inventory.product_id,
+                   products.name AS product,
+                   inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory_movements.lot_number,
+                   MIN(inventory_movements.expiry_date) AS expiry_date,
+                   SUM(inventory_movements.quantity) AS quantity
+            FROM inventory_movements
+            JOIN inventory ON inventory_movements.inventory_id = inventory.id
+            JOIN products ON inventory.product_id = products.id
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            WHERE inventory_movements.lot_number = $1
+            GROUP BY inventory.id, inventory.product_id, products.name, inventory.warehouse_id,
+                     warehouses.name, inventory_movements.lot_number
+            ORDER BY products.name, warehouses.name
+            "#,
+        )
+        .bind(lot_number)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_low_stock(&self) -> RepositoryResult<Vec<LowStockItem>> {
+        // NOTE: the warehouse level minimum overrides the product default
+        Ok(sqlx::query_as::<_, LowStockItem>(
+            r#"
+            SELECT inventory.id AS inventory_id,
+                   inventory.product_id,
+                   products.name AS product,
+                   inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory.quantity_available,
+                   COALESCE(inventory.minimum_stock, products.minimum_stock) AS minimum_stock
+            FROM inventory
+            JOIN products ON inventory.product_id = products.id
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            WHERE inventory.deleted_at IS NULL
+                AND products.deleted_at IS NULL
+                AND warehouses.deleted_at IS NULL
+                AND inventory.quantity_available
+                    <= COALESCE(inventory.minimum_stock, products.minimum_stock)
+            ORDER BY warehouses.name, products.name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_consumption(
+        &self,
+        since: DateTime<Utc>,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<InventoryConsumption>> {
+        Ok(sqlx::query_as::<_, InventoryConsumption>(
+            r#"
+            SELECT inventory.id AS inventory_id,
+                   inventory.product_id,
+                   products.name AS product,
+                   inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory.quantity_available,
+                   COALESCE(inventory.minimum_stock, products.minimum_stock) AS minimum_stock,
+                   inventory.maximum_stock,
+                   COALESCE(-SUM(inventory_movements.quantity), 0) AS consumed,
+                   product_preferred_suppliers.supplier_id,
+                   product_preferred_suppliers.supplier,
+                   product_preferred_suppliers.supplier_sku,
+                   product_preferred_suppliers.purchase_price,
+                   product_preferred_suppliers.currency_code,
+                   product_preferred_suppliers.lead_time_days
+            FROM inventory
+            JOIN products ON inventory.product_id = products.id
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            LEFT JOIN product_preferred_suppliers
+                ON product_preferred_suppliers.product_id = inventory.product_id
+            LEFT JOIN inventory_movements ON inventory_movements.inventory_id = inventory.id
+                AND inventory_movements.movement_type = 'out'
+                AND inventory_movements.movement_date >= $1
+            WHERE inventory.deleted_at IS NULL
+                AND products.deleted_at IS NULL
+                AND warehouses.deleted_at IS NULL
+                AND ($2::uuid IS NULL OR inventory.warehouse_id = $2)
+            GROUP BY inventory.id, products.name, products.minimum_stock, warehouses.name,
+                     product_preferred_suppliers.supplier_id,
+                     product_preferred_suppliers.supplier,
+                     product_preferred_suppliers.supplier_sku,
+                     product_preferred_suppliers.purchase_price,
+                     product_preferred_suppliers.currency_code,
+                     product_preferred_suppliers.lead_time_days
+            ORDER BY warehouses.name, products.name
+            "#,
+        )
+        .bind(since)
+        .bind(warehouse_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn lookup_product(&self, code: &str) -> RepositoryResult<InventoryLookupProduct> {
+        Ok(sqlx::query_as::<_, InventoryLookupProduct>(
+            r#"
+            SELECT products.id,
+                   products.name,
+                   products.sku,
+                   products.barcode,
+                   units_of_measure.unit_of_measure,
+                   products.status
+            FROM products
+            JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+            WHERE products.deleted_at IS NULL
+                AND (products.barcode = $1 OR products.sku = $1)
+            ORDER BY products.barcode = $1 DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(code)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_product_stock(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<InventoryLookupStock>> {
+        Ok(sqlx::query_as::<_, InventoryLookupStock>(
+            r#"
+            SELECT inventory.id AS inventory_id,
+                   inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory.quantity_on_hand,
+                   inventory.quantity_reserved,
+                   inventory.quantity_available,
+                   inventory.status
+            FROM inventory
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            WHERE inventory.product_id = $1
+                AND inventory.deleted_at IS NULL
+                AND warehouses.deleted_at IS NULL
+            ORDER BY warehouses.name
+            "#,
+        )
+        .bind(product_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_product_open_reservations(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<InventoryLookupReservation>> {
+        Ok(sqlx::query_as::<_, InventoryLookupReservation>(
+            r#"
+            SELECT inventory_reservations.id,
+                   inventory_reservations.inventory_id,
+                   inventory.warehouse_id,
+                   inventory_reservations.quantity,
+                   inventory_reservations.reference_type,
+                   inventory_reservations.reference_id,
+                   inventory_reservations.reserved_until,
+                   inventory_reservations.created_at
+            FROM inventory_reservations
+            JOIN inventory ON inventory_reservations.inventory_id = inventory.id
+            WHERE inventory.product_id = $1
+                AND inventory.deleted_at IS NULL
+                AND inventory_reservations.status = 'active'
+            ORDER BY inventory_reservations.reserved_until NULLS LAST,
+                     inventory_reservations.created_at
+            "#,
+        )
+        .bind(product_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_bundle_stock(
+        &self,
+        bundle_id: Uuid,
+        warehouse_id: Uuid,
+    ) -> RepositoryResult<Vec<BundleComponentStock>> {
+        Ok(sqlx::query_as::<_, BundleComponentStock>(
+            r#"
+            SELECT product_bundle_components.component_id,
+                   product_bundle_components.quantity,
+                   inventory.id AS inventory_id
+            FROM product_bundle_components
+            LEFT JOIN inventory ON inventory.product_id = product_bundle_components.component_id
+                AND inventory.warehouse_id = $2
+                AND inventory.deleted_at IS NULL
+                AND inventory.status = 'active'
+            WHERE product_bundle_components.bundle_id = $1
+            ORDER BY product_bundle_components.component_id
+            "#,
+        )
+        .bind(bundle_id)
+        .bind(warehouse_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_low_stock_recipients(&self) -> RepositoryResult<Vec<LowStockRecipient>> {
+        Ok(sqlx::query_as::<_, LowStockRecipient>(
+            r#"
+            SELECT users.id AS user_id,
+                   COALESCE(
+                       NULLIF(TRIM(CONCAT(users.last_name, ' ', users.first_name)), ''),
+                       users.email
+                   ) AS name,
+                   users.email
+            FROM low_stock_alert_subscriptions
+            JOIN users ON low_stock_alert_subscriptions.user_id = users.id
+            WHERE users.deleted_at IS NULL
+                AND users.status = 'active'
+            ORDER BY users.email
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn subscribe_low_stock_alerts(&self, user_id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            "INSERT INTO low_stock_alert_subscriptions (user_id) VALUES ($1) ON CONFLICT DO NOTHING",
+        )
+        .bind(user_id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn unsubscribe_low_stock_alerts(&self, user_id: Uuid) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM low_stock_alert_subscriptions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(self)
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_low_stock_notifications(
+        &self,
+        inventory_ids: &[Uuid],
+    ) -> RepositoryResult<u64> {
+        // NOTE: an unread alert for the same inventory is not repeated on every run
+        Ok(sqlx::query(
+            r#"
+            INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+            SELECT low_stock_alert_subscriptions.user_id, 'inventory', ids.id, 'low_stock'
+            FROM low_stock_alert_subscriptions
+            CROSS JOIN unnest($1::uuid[]) AS ids(id)
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM watch_notifications
+                WHERE watch_notifications.user_id = low_stock_alert_subscriptions.user_id
+                    AND watch_notifications.watchable_type = 'inventory'
+                    AND watch_notifications.watchable_id = ids.id
+                    AND watch_notifications.event = 'low_stock'
+                    AND watch_notifications.read_at IS NULL
+            )
+            "#,
+        )
+        .bind(inventory_ids)
+        .execute(self)
+        .await?
+        .rows_affected())
+    }
+    async fn import(
+        &self,
+        rows: &[InventoryImportRow],
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+        apply: bool,
+    ) -> RepositoryResult<InventoryImportReport> {
+        // NOTE: dry runs go through the same statements and are rolled back at the end,
+        // so the report always matches what an apply would do.
+        let import_id = Uuid::new_v4();
+        let mut tx = self.begin().await?;
+        let active_products = lock_active_products(&mut tx).await?;
+        let product_allowance = limits
+            .and_then(|limits| limits.max_products)
+            .map(|max| (i64::from(max) - active_products).max(0));
+        let mut seen = HashSet::new();
+        let mut lines = Vec::with_capacity(rows.len());
+        let (mut products_created, mut inventory_created, mut movements_created) = (0, 0, 0);
+
+        for row in rows {
+            let mut line = InventoryImportLine {
+                line: row.line,
+                warehouse: row.warehouse.clone(),
+                product: row.product.clone(),
+                sku: row.sku.clone(),
+                quantity: row.quantity.clone(),
+                previous_quantity: None,
+                difference: None,
+                product_created: false,
+                inventory_created: false,
+                errors: row.errors.clone(),
+            };
+            let Some(quantity) = row.quantity.as_ref().filter(|_| row.errors.is_empty()) else {
+                lines.push(line);
+                continue;
+            };
+
+            let warehouse_ids = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT id
+                FROM warehouses
+                WHERE deleted_at IS NULL AND lower(name) = lower($1)
+                LIMIT 2
+                "#,
+            )
+            .bind(&row.warehouse)
+            .fetch_all(&mut *tx)
+            .await?;
+            let warehouse_id = match warehouse_ids.as_slice() {
+                [id] => *id,
+                [] => {
+                    line.errors
+                        .push(format!("Ismeretlen raktár: {}", row.warehouse));
+                    lines.push(line);
+                    continue;
+                }
+                _ => {
+                    line.errors
+                        .push(format!("A raktár neve nem egyértelmű: {}", row.warehouse));
+                    lines.push(line);
+                    continue;
+                }
+            };
+
+            let product_ids = match &row.sku {
+                Some(sku) => {
+                    sqlx::query_scalar::<_, Uuid>(
+                        "SELECT id FROM products WHERE deleted_at IS NULL AND sku = $1",
+                    )
+                    .bind(sku)
+                    .fetch_all(&mut *tx)
+                    .await?
+                }
+                None => {
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT id
+                        FROM products
+                        WHERE deleted_at IS NULL AND lower(name) = lower($1)
+                        LIMIT 2
+                        "#,
+                    )
+                    .bind(&row.product)
+                    .fetch_all(&mut *tx)
+                    .await?
+                }
+            };
+            let product_id = match (product_ids.as_slice(), &row.product, &row.unit_of_measure) {
+                ([id], _, _) => *id,
+                ([], Some(name), Some(unit_of_measure)) => {
+                    if product_allowance
+                        .is_some_and(|allowance| i64::from(products_created) >= allowance)
+                    {
+                        line.errors.push(
+                            "Elérte az előfizetésében engedélyezett maximális termékszámot!"
+                                .to_owned(),
+                        );
+                        lines.push(line);
+                        continue;
+                    }
+                    let unit_of_measure_id = match sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT id
+                        FROM units_of_measure
+                        WHERE deleted_at IS NULL AND lower(unit_of_measure) = lower($1)
+                        LIMIT 1
+                        "#,
+                    )
+                    .bind(unit_of_measure)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    {
+                        Some(id) => id,
+                        None => {
+                            sqlx::query_scalar::<_, Uuid>(
+                                "INSERT INTO units_of_measure (unit_of_measure, created_by_id)
+                                 VALUES ($1, $2) RETURNING id",
+                            )
+                            .bind(unit_of_measure)
+                            .bind(sub)
+                            .fetch_one(&mut *tx)
+                            .await?
+                        }
+                    };
+                    let id = sqlx::query_scalar::<_, Uuid>(
+                        "INSERT INTO products (name, unit_of_measure_id, status, created_by_id, sku)
+                         VALUES ($1, $2, 'active', $3, $4) RETURNING id",
+                    )
+                    .bind(name)
+                    .bind(unit_of_measure_id)
+                    .bind(sub)
+                    .bind(&row.sku)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                    products_created += 1;
+                    line.product_created = true;
+                    id
+                }
+                ([], _, _) => {
+                    line.errors.push(
+                        "A termék nem található; új termékhez a név és a mértékegység megadása kötelező!"
+                            .to_owned(),
+                    );
+                    lines.push(line);
+                    continue;
+                }
+                _ => {
+                    line.errors
+                        .push("A termék neve nem egyértelmű, adja meg a cikkszámát!".to_owned());
+                    lines.push(line);
+                    continue;
+                }
+            };
+
+            if !seen.insert((product_id, warehouse_id)) {
+                line.errors
+                    .push("A termék ebben a raktárban többször szerepel a fájlban!".to_owned());
+                lines.push(line);
+                continue;
+            }
+
+            let (inventory_id, previous_quantity) = match sqlx::query_as::<_, (Uuid, BigDecimal)>(
+                r#"
+                SELECT id, quantity_on_hand
+                FROM inventory
+                WHERE deleted_at IS NULL AND product_id = $1 AND warehouse_id = $2
+                FOR UPDATE
+                "#,
+            )
+            .bind(product_id)
+            .bind(warehouse_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            {
+                Some(inventory) => inventory,
+                None => {
+                    let currency_exists = sqlx::query_scalar::<_, bool>(
+                        "SELECT EXISTS (SELECT 1 FROM currencies WHERE code = $1)",
+                    )
+                    .bind(&row.currency_code)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                    if !currency_exists {
+                        line.errors
+                            .push(format!("Ismeretlen pénznem: {}", row.currency_code));
+                        lines.push(line);
+                        continue;
+                    }
+                    let id = sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        INSERT INTO inventory (product_id, warehouse_id, currency_code, created_by_id)
+                        VALUES ($1, $2, $3, $4)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(product_id)
+                    .bind(warehouse_id)
+                    .bind(&row.currency_code)
+                    .bind(sub)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                    inventory_created += 1;
+                    line.inventory_created = true;
+                    (id, BigDecimal::zero())
+                }
+            };
+
+            let difference = quantity - &previous_quantity;
+            if !difference.is_zero() {
+                let mut savepoint = tx.begin().await?;
+                let inserted = sqlx::query(
+                    r#"
+                    INSERT INTO inventory_movements (
+                        inventory_id, movement_type, quantity, reference_type, reference_id,
+                        unit_cost, created_by_id
+                    ) VALUES ($1, $2, $3, 'inventory_imports', $4, $5, $6)
+                    "#,
+                )
+                .bind(inventory_id)
+                .bind(if line.inventory_created {
+                    "in"
+                } else {
+                    "adjustment"
+                })
+                .bind(&difference)
+                .bind(import_id)
+                .bind(
+                    row.unit_cost
+                        .as_ref()
+                        .filter(|_| difference > BigDecimal::zero()),
+                )
+                .bind(sub)
+                .execute(&mut *savepoint)
+                .await;
+                match inserted {
+                    Ok(_) => {
+                        savepoint.commit().await?;
+                        movements_created += 1;
+                    }
+                    Err(e) => {
+                        savepoint.rollback().await?;
+                        let e = RepositoryError::from(e);
+                        if !e.is_negative_stock_violation() {
+                            return Err(e);
+                        }
+                        line.errors.push(
+                            "A készlet nem csökkenthető a lefoglalt mennyiség alá!".to_owned(),
+                        );
+                    }
+                }
+            }
+            line.previous_quantity = Some(previous_quantity);
+            line.difference = Some(difference);
+            lines.push(line);
+        }
+
+        let error_count = lines.iter().filter(|line| !line.errors.is_empty()).count();
+        let applied = apply && error_count == 0;
+        if applied {
+            sqlx::query(
+                r#"
+                INSERT INTO inventory_imports (
+                    id, row_count, products_created, inventory_created, movements_created, created_by_id
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(import_id)
+            .bind(lines.len() as i32)
+            .bind(products_created)
+            .bind(inventory_created)
+            .bind(movements_created)
+            .bind(sub)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+        } else {
+            tx.rollback().await?;
+        }
+
+        Ok(InventoryImportReport {
+            import_id: applied.then_some(import_id),
+            applied,
+            row_count: lines.len(),
+            error_count,
+            products_created,
+            inventory_created,
+            movements_created,
+            lines,
+        })
+    }
 }
@@ -35,6 +35,25 @@ pub fn routes<M: InventoryModuleInterface>(inventory_module: Arc<M>) -> Router {
             .route("/select_list", get(handler::select_list::<M>))
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
+            .route("/set_location", put(handler::set_location::<M>))
+            .route("/lots", get(handler::lots::<M>))
+            .route("/fefo", get(handler::fefo::<M>))
+            .route("/lot_locations", get(handler::lot_locations::<M>))
+            .route("/lookup", get(handler::lookup::<M>))
+            .route("/import", post(handler::import::<M>))
+            .route("/low_stock", get(handler::low_stock::<M>))
+            .route(
+                "/reorder_suggestions",
+                get(handler::reorder_suggestions::<M>),
+            )
+            .route(
+                "/low_stock_alerts/subscribe",
+                put(handler::subscribe_low_stock_alerts::<M>),
+            )
+            .route(
+                "/low_stock_alerts/unsubscribe",
+                put(handler::unsubscribe_low_stock_alerts::<M>),
+            )
             .route("/delete", delete(handler::delete::<M>))
             .route("/print", get(handler::print::<M>))
             .layer(from_fn_with_state(inventory_module.clone(), require_auth))
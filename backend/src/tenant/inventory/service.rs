@@ -27,12 +27,21 @@ use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
 use crate::tenant::inventory::InventoryModuleInterface;
+use crate::tenant::inventory::dto::import::{InventoryImportQuery, InventoryImportRow};
+use crate::tenant::inventory::dto::location::InventoryLocation;
+use crate::tenant::inventory::dto::lookup::InventoryLookupQuery;
+use crate::tenant::inventory::dto::lot::FefoQuery;
 use crate::tenant::inventory::dto::print::InventoryResolvedPrint;
+use crate::tenant::inventory::dto::reorder::ReorderSuggestionQuery;
 use crate::tenant::inventory::dto::user_input::InventoryUserInput;
-use crate::tenant::inventory::model::{Inventory, InventoryResolved};
+use crate::tenant::inventory::model::{
+    Inventory, InventoryConsumption, InventoryImportReport, InventoryLookup, InventoryLot,
+    InventoryLotLocation, InventoryResolved, LowStockItem, ReorderSuggestion,
+};
 use crate::tenant::inventory::types::inventory::{InventoryFilterBy, InventoryOrderBy};
 use axum::http::StatusCode;
-use chrono::{DateTime, Utc};
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
 use serde_json::json;
@@ -146,6 +155,76 @@ impl FromStr for InventorySelectLists {
     }
 }
 
+pub(crate) fn suggest_fefo(
+    lots: &[InventoryLot],
+    quantity: &BigDecimal,
+    today: NaiveDate,
+) -> Option<Vec<InventoryLot>> {
+    let mut remaining = quantity.clone();
+    let mut picks = Vec::new();
+    for lot in lots.iter().filter(|lot| {
+        lot.expiry_date
+            .is_none_or(|expiry_date| expiry_date >= today)
+    }) {
+        if remaining <= BigDecimal::zero() {
+            break;
+        }
+        let picked = if lot.quantity < remaining {
+            lot.quantity.clone()
+        } else {
+            remaining.clone()
+        };
+        remaining -= &picked;
+        picks.push(InventoryLot {
+            quantity: picked,
+            ..lot.clone()
+        });
+    }
+    (remaining <= BigDecimal::zero()).then_some(picks)
+}
+
+pub(crate) fn suggest_reorder(
+    row: InventoryConsumption,
+    window_days: i64,
+    cover_days: i64,
+) -> Option<ReorderSuggestion> {
+    let daily_consumption =
+        (&row.consumed / BigDecimal::from(window_days)).with_scale_round(2, RoundingMode::Up);
+    // NOTE: the stock has to last until the preferred supplier's delivery arrives as well
+    let lead_time_days = i64::from(row.lead_time_days.unwrap_or(0));
+    let reorder_point = row.minimum_stock.clone().unwrap_or_default()
+        + &daily_consumption * BigDecimal::from(cover_days + lead_time_days);
+    if reorder_point <= BigDecimal::zero() || row.quantity_available > reorder_point {
+        return None;
+    }
+    // NOTE: refill up to the maximum stock when it is configured above the reorder point
+    let target = match &row.maximum_stock {
+        Some(maximum_stock) if *maximum_stock > reorder_point => maximum_stock.clone(),
+        _ => reorder_point.clone(),
+    };
+    let suggested_quantity = &target - &row.quantity_available;
+    (suggested_quantity > BigDecimal::zero()).then_some(ReorderSuggestion {
+        inventory_id: row.inventory_id,
+        product_id: row.product_id,
+        product: row.product,
+        warehouse_id: row.warehouse_id,
+        warehouse: row.warehouse,
+        quantity_available: row.quantity_available,
+        minimum_stock: row.minimum_stock,
+        maximum_stock: row.maximum_stock,
+        consumed: row.consumed,
+        daily_consumption,
+        reorder_point,
+        suggested_quantity,
+        supplier_id: row.supplier_id,
+        supplier: row.supplier,
+        supplier_sku: row.supplier_sku,
+        purchase_price: row.purchase_price,
+        currency_code: row.currency_code,
+        lead_time_days: row.lead_time_days,
+    })
+}
+
 pub trait InventoryService {
     fn insert(
         &self,
@@ -165,6 +244,44 @@ pub trait InventoryService {
         payload: &InventoryUserInput,
     ) -> impl Future<Output = InventoryServiceResult<Inventory>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = InventoryServiceResult<()>> + Send;
+    fn set_location(
+        &self,
+        payload: &InventoryLocation,
+    ) -> impl Future<Output = InventoryServiceResult<()>> + Send;
+    fn get_lots(
+        &self,
+        inventory_id: Uuid,
+    ) -> impl Future<Output = InventoryServiceResult<Vec<InventoryLot>>> + Send;
+    fn suggest_fefo(
+        &self,
+        payload: &FefoQuery,
+        tz: Tz,
+    ) -> impl Future<Output = InventoryServiceResult<Vec<InventoryLot>>> + Send;
+    fn get_lot_locations(
+        &self,
+        lot_number: &str,
+    ) -> impl Future<Output = InventoryServiceResult<Vec<InventoryLotLocation>>> + Send;
+    fn lookup(
+        &self,
+        payload: &InventoryLookupQuery,
+    ) -> impl Future<Output = InventoryServiceResult<InventoryLookup>> + Send;
+    fn import(
+        &self,
+        payload: &InventoryImportQuery,
+        data: &[u8],
+    ) -> impl Future<Output = InventoryServiceResult<InventoryImportReport>> + Send;
+    fn get_low_stock(
+        &self,
+    ) -> impl Future<Output = InventoryServiceResult<Vec<LowStockItem>>> + Send;
+    fn get_reorder_suggestions(
+        &self,
+        payload: &ReorderSuggestionQuery,
+    ) -> impl Future<Output = InventoryServiceResult<Vec<ReorderSuggestion>>> + Send;
+    fn subscribe_low_stock_alerts(&self)
+    -> impl Future<Output = InventoryServiceResult<()>> + Send;
+    fn unsubscribe_low_stock_alerts(
+        &self,
+    ) -> impl Future<Output = InventoryServiceResult<()>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<InventoryOrderBy, InventoryFilterBy>,
@@ -286,6 +403,205 @@ where
             .delete_by_id(payload)
             .await?)
     }
+    async fn set_location(&self, payload: &InventoryLocation) -> InventoryServiceResult<()> {
+        let normalize = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let location = InventoryLocation {
+            id: payload.id,
+            zone: normalize(&payload.zone),
+            bin_location: normalize(&payload.bin_location),
+        };
+        if location.zone.as_ref().is_some_and(|v| v.len() > 50)
+            || location.bin_location.as_ref().is_some_and(|v| v.len() > 50)
+        {
+            return Err(InventoryServiceError::UnprocessableEntry(
+                "A zóna és a tárolóhely legfeljebb 50 karakter lehet!",
+            ));
+        }
+        Ok(self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .set_location(&location)
+            .await?)
+    }
+    async fn get_lots(&self, inventory_id: Uuid) -> InventoryServiceResult<Vec<InventoryLot>> {
+        Ok(self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .get_lots(inventory_id)
+            .await?)
+    }
+    async fn suggest_fefo(
+        &self,
+        payload: &FefoQuery,
+        tz: Tz,
+    ) -> InventoryServiceResult<Vec<InventoryLot>> {
+        if payload.quantity <= BigDecimal::zero() {
+            return Err(InventoryServiceError::UnprocessableEntry(
+                "A mennyiségnek pozitív számnak kell lennie!",
+            ));
+        }
+        let lots = self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .get_lots(payload.uuid)
+            .await?;
+        suggest_fefo(
+            &lots,
+            &payload.quantity,
+            Utc::now().with_timezone(&tz).date_naive(),
+        )
+        .ok_or(InventoryServiceError::UnprocessableEntry(
+            "Nincs elegendő le nem járt tételes készlet!",
+        ))
+    }
+    async fn get_lot_locations(
+        &self,
+        lot_number: &str,
+    ) -> InventoryServiceResult<Vec<InventoryLotLocation>> {
+        let lot_number = lot_number.trim();
+        if lot_number.is_empty() {
+            return Err(InventoryServiceError::UnprocessableEntry(
+                "A tételszám megadása kötelező!",
+            ));
+        }
+        Ok(self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .get_lot_locations(lot_number)
+            .await?)
+    }
+    async fn lookup(
+        &self,
+        payload: &InventoryLookupQuery,
+    ) -> InventoryServiceResult<InventoryLookup> {
+        let code = payload.code.trim();
+        if code.is_empty() {
+            return Err(InventoryServiceError::UnprocessableEntry(
+                "A kód megadása kötelező!",
+            ));
+        }
+        let repo = self.module().inventory_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventoryServiceError::Unauthorized)?,
+        )?;
+        let product = repo.lookup_product(code).await?;
+        let (stock, reservations) = tokio::try_join!(
+            repo.get_product_stock(product.id),
+            repo.get_product_open_reservations(product.id)
+        )?;
+        Ok(InventoryLookup {
+            product,
+            stock,
+            reservations,
+        })
+    }
+    async fn import(
+        &self,
+        payload: &InventoryImportQuery,
+        data: &[u8],
+    ) -> InventoryServiceResult<InventoryImportReport> {
+        let rows = InventoryImportRow::parse_csv(data)
+            .map_err(InventoryServiceError::UnprocessableEntry)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(InventoryServiceError::Unauthorized)?;
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        Ok(self
+            .module()
+            .inventory_repo(tenant_id)?
+            .import(&rows, self.claims()?.sub(), limits, !payload.dry_run)
+            .await?)
+    }
+    async fn get_low_stock(&self) -> InventoryServiceResult<Vec<LowStockItem>> {
+        Ok(self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .get_low_stock()
+            .await?)
+    }
+    async fn get_reorder_suggestions(
+        &self,
+        payload: &ReorderSuggestionQuery,
+    ) -> InventoryServiceResult<Vec<ReorderSuggestion>> {
+        let window_days = payload.window_days.unwrap_or(30);
+        let cover_days = payload.cover_days.unwrap_or(14);
+        if !(1..=365).contains(&window_days) || !(1..=365).contains(&cover_days) {
+            return Err(InventoryServiceError::UnprocessableEntry(
+                "Az időszaknak 1 és 365 nap között kell lennie!",
+            ));
+        }
+        Ok(self
+            .module()
+            .inventory_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .get_consumption(
+                Utc::now() - Duration::days(window_days),
+                payload.warehouse_id,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|row| suggest_reorder(row, window_days, cover_days))
+            .collect())
+    }
+    async fn subscribe_low_stock_alerts(&self) -> InventoryServiceResult<()> {
+        let claims = self.claims()?;
+        Ok(self
+            .module()
+            .inventory_repo(
+                claims
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .subscribe_low_stock_alerts(claims.sub())
+            .await?)
+    }
+    async fn unsubscribe_low_stock_alerts(&self) -> InventoryServiceResult<()> {
+        let claims = self.claims()?;
+        Ok(self
+            .module()
+            .inventory_repo(
+                claims
+                    .active_tenant()
+                    .ok_or(InventoryServiceError::Unauthorized)?,
+            )?
+            .unsubscribe_low_stock_alerts(claims.sub())
+            .await?)
+    }
     async fn get_paged(
         &self,
         get_query: &ResourceQuery<InventoryOrderBy, InventoryFilterBy>,
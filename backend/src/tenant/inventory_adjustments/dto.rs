@@ -0,0 +1,54 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    Damage,
+    Loss,
+    Correction,
+    InitialLoad,
+}
+
+impl ReasonCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasonCode::Damage => "damage",
+            ReasonCode::Loss => "loss",
+            ReasonCode::Correction => "correction",
+            ReasonCode::InitialLoad => "initial_load",
+        }
+    }
+
+    pub fn decreases_stock(&self) -> bool {
+        matches!(self, ReasonCode::Damage | ReasonCode::Loss)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateInventoryAdjustment {
+    pub inventory_id: Uuid,
+    pub quantity: BigDecimal,
+    pub reason_code: ReasonCode,
+    pub note: Option<String>,
+}
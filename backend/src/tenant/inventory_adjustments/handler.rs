@@ -0,0 +1,276 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::inventory_adjustments::InventoryAdjustmentsModuleInterface;
+use crate::tenant::inventory_adjustments::dto::CreateInventoryAdjustment;
+use crate::tenant::inventory_adjustments::service::InventoryAdjustmentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: InventoryAdjustmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_adjustments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_adjustments_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        inventory_adjustments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_adjustments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: InventoryAdjustmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_adjustments_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_adjustments_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        inventory_adjustments_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        inventory_adjustments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        inventory_adjustments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: InventoryAdjustmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_adjustments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateInventoryAdjustment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_adjustments_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        inventory_adjustments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        inventory_adjustments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::inventory_adjustments::model::InventoryAdjustment;
+    use crate::tenant::inventory_adjustments::{
+        self, repository::MockInventoryAdjustmentsRepository, tests::MockInventoryAdjustmentsModule,
+    };
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockInventoryAdjustmentsRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let role = role.to_string();
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(Some(role.clone())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "inventory.adjust")
+            .returning(move |_, _| Ok(granted));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut inventory_adjustments_module = MockInventoryAdjustmentsModule::new();
+        inventory_adjustments_module
+            .expect_inventory_adjustments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        inventory_adjustments_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        inventory_adjustments_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        inventory_adjustments_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_adjustments::routes::routes(Arc::new(
+                inventory_adjustments_module,
+            ))),
+        )
+    }
+
+    fn create_request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_adjustments/create")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_success_with_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let inventory_id = Uuid::new_v4();
+        let adjustment = InventoryAdjustment {
+            id: Uuid::new_v4(),
+            inventory_id,
+            inventory_movement_id: Uuid::new_v4(),
+            quantity: BigDecimal::from(-2),
+            reason_code: "damage".to_string(),
+            note: Some("Törött".to_string()),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        };
+
+        let mut repo = MockInventoryAdjustmentsRepository::new();
+        repo.expect_get_quantity_available()
+            .times(1)
+            .with(eq(inventory_id))
+            .returning(|_| Ok(BigDecimal::from(5)));
+        repo.expect_insert().times(1).returning({
+            let adjustment = adjustment.clone();
+            move |_, _| Ok(adjustment.clone())
+        });
+
+        let response = app(repo, "member", true, active_tenant_id)
+            .oneshot(create_request(
+                active_tenant_id,
+                json!({
+                    "inventory_id": inventory_id,
+                    "quantity": "-2",
+                    "reason_code": "damage",
+                    "note": "Törött"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": adjustment})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_forbidden_without_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockInventoryAdjustmentsRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(create_request(
+                active_tenant_id,
+                json!({
+                    "inventory_id": Uuid::new_v4(),
+                    "quantity": "10",
+                    "reason_code": "initial_load",
+                    "note": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_loss_cannot_increase_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockInventoryAdjustmentsRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, "owner", false, active_tenant_id)
+            .oneshot(create_request(
+                active_tenant_id,
+                json!({
+                    "inventory_id": Uuid::new_v4(),
+                    "quantity": "3",
+                    "reason_code": "loss",
+                    "note": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::AppState;
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::tenant::inventory_adjustments::repository::InventoryAdjustmentsRepository;
+use crate::tenant::permissions::PermissionsModuleInterface;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+
+pub trait InventoryAdjustmentsModuleInterface: PermissionsModuleInterface {
+    fn inventory_adjustments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn InventoryAdjustmentsRepository + Send + Sync>>;
+}
+
+impl<P, T> InventoryAdjustmentsModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn inventory_adjustments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn InventoryAdjustmentsRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::TenantsRepository;
+    use crate::tenant::permissions::repository::PermissionsRepository;
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub InventoryAdjustmentsModule {}
+        impl ConfigProvider for InventoryAdjustmentsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for InventoryAdjustmentsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for InventoryAdjustmentsModule {}
+        impl PermissionsModuleInterface for InventoryAdjustmentsModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
+        impl InventoryAdjustmentsModuleInterface for InventoryAdjustmentsModule {
+            fn inventory_adjustments_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn InventoryAdjustmentsRepository + Send + Sync>>;
+        }
+    );
+}
@@ -0,0 +1,147 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::inventory_adjustments::dto::CreateInventoryAdjustment;
+use crate::tenant::inventory_adjustments::model::InventoryAdjustment;
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait InventoryAdjustmentsRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<InventoryAdjustment>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<InventoryAdjustment>)>;
+    async fn get_quantity_available(&self, inventory_id: Uuid) -> RepositoryResult<BigDecimal>;
+    async fn insert(
+        &self,
+        input: &CreateInventoryAdjustment,
+        sub: Uuid,
+    ) -> RepositoryResult<InventoryAdjustment>;
+}
+
+#[async_trait]
+impl InventoryAdjustmentsRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<InventoryAdjustment> {
+        Ok(sqlx::query_as::<_, InventoryAdjustment>(
+            "SELECT * FROM inventory_adjustments WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<InventoryAdjustment>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM inventory_adjustments")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let inventory_adjustments = sqlx::query_as::<_, InventoryAdjustment>(
+            r#"
+            SELECT *
+            FROM inventory_adjustments
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            inventory_adjustments,
+        ))
+    }
+
+    async fn get_quantity_available(&self, inventory_id: Uuid) -> RepositoryResult<BigDecimal> {
+        Ok(sqlx::query_scalar::<_, BigDecimal>(
+            "SELECT quantity_available FROM inventory WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(inventory_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        input: &CreateInventoryAdjustment,
+        sub: Uuid,
+    ) -> RepositoryResult<InventoryAdjustment> {
+        let mut tx = self.begin().await?;
+        let id = Uuid::new_v4();
+
+        let inventory_movement_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO inventory_movements (
+                inventory_id, movement_type, quantity, reference_type, reference_id, created_by_id
+            ) VALUES ($1, 'adjustment', $2, 'inventory_adjustments', $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(input.inventory_id)
+        .bind(&input.quantity)
+        .bind(id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let inventory_adjustment = sqlx::query_as::<_, InventoryAdjustment>(
+            r#"
+            INSERT INTO inventory_adjustments (
+                id, inventory_id, inventory_movement_id, quantity, reason_code, note, created_by_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(input.inventory_id)
+        .bind(inventory_movement_id)
+        .bind(&input.quantity)
+        .bind(input.reason_code.as_str())
+        .bind(&input.note)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(inventory_adjustment)
+    }
+}
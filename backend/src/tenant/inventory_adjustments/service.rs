@@ -0,0 +1,197 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::inventory_adjustments::InventoryAdjustmentsModuleInterface;
+use crate::tenant::inventory_adjustments::dto::CreateInventoryAdjustment;
+use crate::tenant::inventory_adjustments::model::InventoryAdjustment;
+use crate::tenant::permissions::model::INVENTORY_ADJUST;
+use crate::tenant::permissions::service::has_permission;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum InventoryAdjustmentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for InventoryAdjustmentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => InventoryAdjustmentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<InventoryAdjustmentsServiceError> for AppError {
+    fn from(value: InventoryAdjustmentsServiceError) -> Self {
+        match value {
+            InventoryAdjustmentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventoryAdjustmentsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            InventoryAdjustmentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventoryAdjustmentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type InventoryAdjustmentsServiceResult<T> = Result<T, InventoryAdjustmentsServiceError>;
+
+fn validate(payload: &CreateInventoryAdjustment) -> InventoryAdjustmentsServiceResult<()> {
+    if payload.quantity.is_zero() {
+        return Err(InventoryAdjustmentsServiceError::UnprocessableEntry(
+            "A mennyiség nem lehet nulla!",
+        ));
+    }
+    if payload.reason_code.decreases_stock() && payload.quantity > BigDecimal::zero() {
+        return Err(InventoryAdjustmentsServiceError::UnprocessableEntry(
+            "Sérülés és hiány esetén csak csökkenteni lehet a készletet!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait InventoryAdjustmentsService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = InventoryAdjustmentsServiceResult<InventoryAdjustment>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<
+        Output = InventoryAdjustmentsServiceResult<(PaginatorMeta, Vec<InventoryAdjustment>)>,
+    > + Send;
+    fn create(
+        &self,
+        payload: &CreateInventoryAdjustment,
+    ) -> impl Future<Output = InventoryAdjustmentsServiceResult<InventoryAdjustment>> + Send;
+}
+
+impl<'a, T> InventoryAdjustmentsService for Service<'a, T>
+where
+    T: InventoryAdjustmentsModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> InventoryAdjustmentsServiceResult<InventoryAdjustment> {
+        Ok(self
+            .module()
+            .inventory_adjustments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryAdjustmentsServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> InventoryAdjustmentsServiceResult<(PaginatorMeta, Vec<InventoryAdjustment>)> {
+        Ok(self
+            .module()
+            .inventory_adjustments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryAdjustmentsServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateInventoryAdjustment,
+    ) -> InventoryAdjustmentsServiceResult<InventoryAdjustment> {
+        validate(payload)?;
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(InventoryAdjustmentsServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), INVENTORY_ADJUST).await? {
+            return Err(InventoryAdjustmentsServiceError::Forbidden);
+        }
+
+        let repo = self.module().inventory_adjustments_repo(tenant_id)?;
+        if payload.quantity < BigDecimal::zero()
+            && repo.get_quantity_available(payload.inventory_id).await? + &payload.quantity
+                < BigDecimal::zero()
+        {
+            return Err(InventoryAdjustmentsServiceError::UnprocessableEntry(
+                "A szabad készlet nem elegendő a csökkentéshez!",
+            ));
+        }
+        Ok(repo.insert(payload, claims.sub()).await?)
+    }
+}
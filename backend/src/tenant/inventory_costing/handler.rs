@@ -0,0 +1,348 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::inventory_costing::InventoryCostingModuleInterface;
+use crate::tenant::inventory_costing::dto::{CostingMethodInput, ValuationQuery};
+use crate::tenant::inventory_costing::service::InventoryCostingService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn settings<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let result = map_handler_err(
+        service.get_settings().await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_costing_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_method<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CostingMethodInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let result = map_handler_err(
+        service.set_method(&payload).await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_costing_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn layers<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let result = map_handler_err(
+        service.get_layers(payload.uuid).await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_costing_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn product_valuation<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let result = map_handler_err(
+        service.get_product_valuation().await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_costing_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn valuation<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+    Query(payload): Query<ValuationQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let tz = map_handler_err(claims.tz(), inventory_costing_module.clone()).await?;
+    let result = map_handler_err(
+        service.get_valuation(&payload, tz).await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_costing_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn export_valuation<M: InventoryCostingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_costing_module): State<Arc<M>>,
+    Query(payload): Query<ValuationQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_costing_module.clone());
+    let tz = map_handler_err(claims.tz(), inventory_costing_module.clone()).await?;
+    let csv = map_handler_err(
+        service.export_valuation(&payload, tz).await,
+        inventory_costing_module.clone(),
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        r#"attachment; filename="keszletertekeles.csv""#.parse().unwrap(),
+    );
+    Ok((StatusCode::OK, headers, csv).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::inventory_costing::model::{CostingSettings, ValuationRow};
+    use crate::tenant::inventory_costing::{
+        self, repository::MockInventoryCostingRepository, tests::MockInventoryCostingModule,
+    };
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockInventoryCostingRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let role = role.to_string();
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(Some(role.clone())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "inventory.costing")
+            .returning(move |_, _| Ok(granted));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut inventory_costing_module = MockInventoryCostingModule::new();
+        inventory_costing_module
+            .expect_inventory_costing_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        inventory_costing_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        inventory_costing_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        inventory_costing_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_costing::routes::routes(Arc::new(
+                inventory_costing_module,
+            ))),
+        )
+    }
+
+    fn set_method_request(active_tenant_id: Uuid, user_id: Uuid, method: &str) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/inventory_costing/set_method")
+            .body(Body::from(json!({ "method": method }).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_set_method_forbidden_without_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockInventoryCostingRepository::new();
+        repo.expect_set_method().never();
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(set_method_request(active_tenant_id, Uuid::new_v4(), "fifo"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_set_method_rejects_unknown_method() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockInventoryCostingRepository::new();
+        repo.expect_set_method().never();
+
+        let response = app(repo, "owner", false, active_tenant_id)
+            .oneshot(set_method_request(active_tenant_id, Uuid::new_v4(), "lifo"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_export_valuation_csv() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockInventoryCostingRepository::new();
+        repo.expect_get_valuation().times(1).returning(|_, _| {
+            Ok(vec![ValuationRow {
+                warehouse_id: Uuid::nil(),
+                warehouse: "Központi".to_string(),
+                product_id: Uuid::nil(),
+                product: "Csavar, M6".to_string(),
+                quantity: BigDecimal::from(5),
+                unit_cost: BigDecimal::from(200),
+                value: BigDecimal::from(1000),
+            }])
+        });
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri("/api/inventory_costing/valuation/export?as_of=2026-07-31")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            format!(
+                "warehouse_id,warehouse,product_id,product,quantity,unit_cost,value\n{nil},Központi,{nil},\"Csavar, M6\",5,200,1000\n",
+                nil = Uuid::nil()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_method_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut repo = MockInventoryCostingRepository::new();
+        repo.expect_set_method()
+            .times(1)
+            .with(eq("fifo"), eq(user_id))
+            .returning(|method, sub| {
+                Ok(CostingSettings {
+                    method: method.to_string(),
+                    updated_by_id: Some(sub),
+                    updated_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, "member", true, active_tenant_id)
+            .oneshot(set_method_request(active_tenant_id, user_id, "fifo"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const COSTING_METHODS: [&str; 2] = ["weighted_average", "fifo"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CostingSettings {
+    pub method: String,
+    pub updated_by_id: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CostLayer {
+    pub id: Uuid,
+    pub inventory_id: Uuid,
+    pub movement_id: Option<Uuid>,
+    pub unit_cost: BigDecimal,
+    pub quantity_received: BigDecimal,
+    pub quantity_remaining: BigDecimal,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ValuationRow {
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub product_id: Uuid,
+    pub product: String,
+    pub quantity: BigDecimal,
+    pub unit_cost: BigDecimal,
+    pub value: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductValuation {
+    pub product_id: Uuid,
+    pub product: String,
+    pub quantity_on_hand: BigDecimal,
+    pub average_cost: BigDecimal,
+    pub value: BigDecimal,
+}
@@ -0,0 +1,165 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::inventory_costing::model::{
+    CostLayer, CostingSettings, ProductValuation, ValuationRow,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait InventoryCostingRepository: Send + Sync {
+    async fn get_settings(&self) -> RepositoryResult<CostingSettings>;
+    async fn set_method(&self, method: &str, sub: Uuid) -> RepositoryResult<CostingSettings>;
+    async fn get_layers(&self, inventory_id: Uuid) -> RepositoryResult<Vec<CostLayer>>;
+    async fn get_product_valuation(&self) -> RepositoryResult<Vec<ProductValuation>>;
+    async fn get_valuation(
+        &self,
+        before: DateTime<Utc>,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<ValuationRow>>;
+}
+
+#[async_trait]
+impl InventoryCostingRepository for PgPool {
+    async fn get_settings(&self) -> RepositoryResult<CostingSettings> {
+        Ok(sqlx::query_as::<_, CostingSettings>(
+            "SELECT method, updated_by_id, updated_at FROM costing_settings",
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_method(&self, method: &str, sub: Uuid) -> RepositoryResult<CostingSettings> {
+        // NOTE: the method applies to issues from now on, costs already booked are kept
+        Ok(sqlx::query_as::<_, CostingSettings>(
+            r#"
+            UPDATE costing_settings
+            SET method = $1,
+                updated_by_id = $2
+            RETURNING method, updated_by_id, updated_at
+            "#,
+        )
+        .bind(method)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_layers(&self, inventory_id: Uuid) -> RepositoryResult<Vec<CostLayer>> {
+        Ok(sqlx::query_as::<_, CostLayer>(
+            r#"
+            SELECT *
+            FROM inventory_cost_layers
+            WHERE inventory_id = $1
+                AND quantity_remaining > 0
+            ORDER BY received_at, id
+            "#,
+        )
+        .bind(inventory_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_product_valuation(&self) -> RepositoryResult<Vec<ProductValuation>> {
+        Ok(sqlx::query_as::<_, ProductValuation>(
+            r#"
+            SELECT products.id AS product_id,
+                   products.name AS product,
+                   SUM(inventory.quantity_on_hand) AS quantity_on_hand,
+                   COALESCE(
+                       round(
+                           SUM(inventory.quantity_on_hand * inventory.average_cost)
+                               / NULLIF(SUM(inventory.quantity_on_hand), 0),
+                           4
+                       ),
+                       0
+                   ) AS average_cost,
+                   round(SUM(inventory.quantity_on_hand * inventory.average_cost), 2) AS value
+            FROM inventory
+            JOIN products ON inventory.product_id = products.id
+            WHERE inventory.deleted_at IS NULL
+                AND products.deleted_at IS NULL
+            GROUP BY products.id, products.name
+            ORDER BY products.name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_valuation(
+        &self,
+        before: DateTime<Utc>,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<ValuationRow>> {
+        // NOTE: starts from the latest snapshot before the cutoff and replays only the
+        // movements booked after it, the value is stated at booked cost
+        Ok(sqlx::query_as::<_, ValuationRow>(
+            r#"
+            WITH base AS (
+                SELECT id, taken_at
+                FROM stock_snapshots
+                WHERE taken_at <= $1
+                ORDER BY taken_at DESC
+                LIMIT 1
+            ), totals AS (
+                SELECT inventory_id, quantity, total_cost
+                FROM stock_snapshot_lines
+                WHERE snapshot_id = (SELECT id FROM base)
+                UNION ALL
+                SELECT inventory_id, quantity, total_cost
+                FROM inventory_movements
+                WHERE movement_date >= COALESCE((SELECT taken_at FROM base), '-infinity')
+                    AND movement_date < $1
+            )
+            SELECT inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory.product_id,
+                   products.name AS product,
+                   SUM(totals.quantity) AS quantity,
+                   COALESCE(
+                       round(SUM(totals.total_cost) / NULLIF(SUM(totals.quantity), 0), 4),
+                       0
+                   ) AS unit_cost,
+                   COALESCE(SUM(totals.total_cost), 0) AS value
+            FROM totals
+            JOIN inventory ON totals.inventory_id = inventory.id
+            JOIN products ON inventory.product_id = products.id
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            WHERE inventory.deleted_at IS NULL
+                AND ($2::uuid IS NULL OR inventory.warehouse_id = $2)
+            GROUP BY inventory.warehouse_id, warehouses.name, inventory.product_id, products.name
+            HAVING SUM(totals.quantity) <> 0
+                OR COALESCE(SUM(totals.total_cost), 0) <> 0
+            ORDER BY warehouses.name, products.name
+            "#,
+        )
+        .bind(before)
+        .bind(warehouse_id)
+        .fetch_all(self)
+        .await?)
+    }
+}
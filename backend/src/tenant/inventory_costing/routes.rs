@@ -0,0 +1,44 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::InventoryCostingModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, put};
+use std::sync::Arc;
+
+pub fn routes<M: InventoryCostingModuleInterface>(inventory_costing_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/inventory_costing",
+        Router::new()
+            .route("/settings", get(handler::settings::<M>))
+            .route("/set_method", put(handler::set_method::<M>))
+            .route("/layers", get(handler::layers::<M>))
+            .route("/product_valuation", get(handler::product_valuation::<M>))
+            .route("/valuation", get(handler::valuation::<M>))
+            .route("/valuation/export", get(handler::export_valuation::<M>))
+            .layer(from_fn_with_state(
+                inventory_costing_module.clone(),
+                require_auth,
+            ))
+            .with_state(inventory_costing_module),
+    )
+}
@@ -0,0 +1,240 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::common::utils::{start_of_local_day, to_csv};
+use crate::tenant::inventory_costing::InventoryCostingModuleInterface;
+use crate::tenant::inventory_costing::dto::{CostingMethodInput, ValuationQuery};
+use crate::tenant::inventory_costing::model::{
+    COSTING_METHODS, CostLayer, CostingSettings, ProductValuation, ValuationRow,
+};
+use crate::tenant::permissions::model::INVENTORY_COSTING;
+use crate::tenant::permissions::service::has_permission;
+use axum::http::StatusCode;
+use chrono::Utc;
+use chrono_tz::Tz;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum InventoryCostingServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+impl From<ServiceError> for InventoryCostingServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => InventoryCostingServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<InventoryCostingServiceError> for AppError {
+    fn from(value: InventoryCostingServiceError) -> Self {
+        match value {
+            InventoryCostingServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventoryCostingServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            InventoryCostingServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventoryCostingServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type InventoryCostingServiceResult<T> = Result<T, InventoryCostingServiceError>;
+
+pub trait InventoryCostingService {
+    fn get_settings(
+        &self,
+    ) -> impl Future<Output = InventoryCostingServiceResult<CostingSettings>> + Send;
+    fn set_method(
+        &self,
+        payload: &CostingMethodInput,
+    ) -> impl Future<Output = InventoryCostingServiceResult<CostingSettings>> + Send;
+    fn get_layers(
+        &self,
+        inventory_id: Uuid,
+    ) -> impl Future<Output = InventoryCostingServiceResult<Vec<CostLayer>>> + Send;
+    fn get_product_valuation(
+        &self,
+    ) -> impl Future<Output = InventoryCostingServiceResult<Vec<ProductValuation>>> + Send;
+    fn get_valuation(
+        &self,
+        payload: &ValuationQuery,
+        tz: Tz,
+    ) -> impl Future<Output = InventoryCostingServiceResult<Vec<ValuationRow>>> + Send;
+    fn export_valuation(
+        &self,
+        payload: &ValuationQuery,
+        tz: Tz,
+    ) -> impl Future<Output = InventoryCostingServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> InventoryCostingService for Service<'a, T>
+where
+    T: InventoryCostingModuleInterface,
+{
+    async fn get_settings(&self) -> InventoryCostingServiceResult<CostingSettings> {
+        Ok(self
+            .module()
+            .inventory_costing_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryCostingServiceError::Unauthorized)?,
+            )?
+            .get_settings()
+            .await?)
+    }
+
+    async fn set_method(
+        &self,
+        payload: &CostingMethodInput,
+    ) -> InventoryCostingServiceResult<CostingSettings> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(InventoryCostingServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), INVENTORY_COSTING).await? {
+            return Err(InventoryCostingServiceError::Forbidden);
+        }
+        if !COSTING_METHODS.contains(&payload.method.as_str()) {
+            return Err(InventoryCostingServiceError::UnprocessableEntry(
+                "Ismeretlen készletértékelési módszer!",
+            ));
+        }
+        Ok(self
+            .module()
+            .inventory_costing_repo(tenant_id)?
+            .set_method(&payload.method, claims.sub())
+            .await?)
+    }
+
+    async fn get_layers(
+        &self,
+        inventory_id: Uuid,
+    ) -> InventoryCostingServiceResult<Vec<CostLayer>> {
+        Ok(self
+            .module()
+            .inventory_costing_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryCostingServiceError::Unauthorized)?,
+            )?
+            .get_layers(inventory_id)
+            .await?)
+    }
+
+    async fn get_product_valuation(&self) -> InventoryCostingServiceResult<Vec<ProductValuation>> {
+        Ok(self
+            .module()
+            .inventory_costing_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryCostingServiceError::Unauthorized)?,
+            )?
+            .get_product_valuation()
+            .await?)
+    }
+
+    async fn get_valuation(
+        &self,
+        payload: &ValuationQuery,
+        tz: Tz,
+    ) -> InventoryCostingServiceResult<Vec<ValuationRow>> {
+        let as_of = payload
+            .as_of
+            .unwrap_or_else(|| Utc::now().with_timezone(&tz).date_naive());
+        let cutoff = as_of
+            .succ_opt()
+            .and_then(|next_day| start_of_local_day(next_day, tz))
+            .ok_or(InventoryCostingServiceError::UnprocessableEntry(
+                "Érvénytelen dátum!",
+            ))?;
+        Ok(self
+            .module()
+            .inventory_costing_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryCostingServiceError::Unauthorized)?,
+            )?
+            .get_valuation(cutoff, payload.warehouse_id)
+            .await?)
+    }
+
+    async fn export_valuation(
+        &self,
+        payload: &ValuationQuery,
+        tz: Tz,
+    ) -> InventoryCostingServiceResult<Vec<u8>> {
+        Ok(to_csv(&self.get_valuation(payload, tz).await?)?)
+    }
+}
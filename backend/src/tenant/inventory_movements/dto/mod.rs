@@ -17,5 +17,8 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod bundle;
+pub mod ledger;
+pub mod negative_stock;
 pub mod print;
 pub mod user_input;
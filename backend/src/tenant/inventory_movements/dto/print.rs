@@ -33,7 +33,7 @@ pub struct InventoryMovementsResolvedPrint {
     reference_id: Option<Uuid>,
     unit_price: Option<BigDecimal>,
     total_price: Option<BigDecimal>,
-    tax_id: Uuid,
+    tax_id: Option<Uuid>,
     tax: Option<String>,
     movement_date: String,
     created_by_id: Uuid,
@@ -78,6 +78,8 @@ impl InventoryMovementsResolvedPrint {
         match movement_type {
             "in" => "Bevétel",
             "out" => "Kiadás",
+            "adjustment" => "Készletkorrekció",
+            "transfer" => "Átmozgatás",
             _ => "Ismeretlen művelet",
         }
         .to_string()
@@ -85,6 +87,9 @@ impl InventoryMovementsResolvedPrint {
     fn map_reference_type(reference_type: &str) -> String {
         match reference_type {
             "worksheets" => "Munkalap",
+            "inventory_adjustments" => "Készletkorrekció",
+            "stock_transfers" => "Raktárközi átmozgatás",
+            "credit_notes" => "Helyesbítő számla",
             _ => "Ismeretlen referencia típus",
         }
         .to_string()
@@ -117,8 +122,14 @@ mod tests {
             reference_id,
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
             tax: Some("Áfa".to_string()),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: input_date,
             created_by_id,
             created_by: "Test User".to_string(),
@@ -138,7 +149,7 @@ mod tests {
             reference_id,
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            tax_id: Some(tax_id),
             tax: Some("Áfa".to_string()),
             movement_date: output_date.clone(),
             created_by_id,
@@ -27,6 +27,8 @@ use crate::common::value_object::ValueObjectOptional;
 use crate::common::value_object::ValueObjectRequired;
 use crate::tenant::inventory_movements::types::{InventoryMovementType, InventoryReferenceType};
 use axum::http::StatusCode;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fmt::{Display, Formatter};
@@ -43,6 +45,10 @@ pub struct InventoryMovementUserInputHelper {
     pub reference_id: String,
     pub unit_price: String,
     pub tax_id: String,
+    #[serde(default)]
+    pub lot_number: String,
+    #[serde(default)]
+    pub expiry_date: String,
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -56,6 +62,8 @@ pub struct InventoryMovementUserInputError {
     pub unit_price: Option<String>,
     pub total_price: Option<String>,
     pub tax_id: Option<String>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<String>,
 }
 
 impl InventoryMovementUserInputError {
@@ -68,6 +76,8 @@ impl InventoryMovementUserInputError {
             && self.reference_id.is_none()
             && self.unit_price.is_none()
             && self.tax_id.is_none()
+            && self.lot_number.is_none()
+            && self.expiry_date.is_none()
     }
 }
 
@@ -112,14 +122,16 @@ pub struct InventoryMovementUserInput {
     pub reference_id: ValueObjectOptional<UuidVO>,
     pub unit_price: ValueObjectOptional<Float64>,
     pub tax_id: ValueObjectRequired<UuidVO>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
 }
 
 impl InventoryMovementUserInput {
-    pub fn quantity(&self, negate: bool) -> Result<f64, ValueObjectError> {
+    pub fn quantity(&self, negate: bool) -> Result<BigDecimal, ValueObjectError> {
         if negate {
-            Ok(-self.quantity.as_f64()?)
+            Ok(-self.quantity.as_decimal()?.clone())
         } else {
-            Ok(self.quantity.as_f64()?)
+            Ok(self.quantity.as_decimal()?.clone())
         }
     }
 }
@@ -187,6 +199,27 @@ impl TryFrom<InventoryMovementUserInputHelper> for InventoryMovementUserInput {
                 error.unit_price = Some(e.to_string());
             });
 
+        let lot_number = Some(value.lot_number.trim())
+            .filter(|lot_number| !lot_number.is_empty())
+            .map(str::to_string);
+        if lot_number
+            .as_ref()
+            .is_some_and(|lot_number| lot_number.chars().count() > 100)
+        {
+            error.lot_number = Some("A tételszám legfeljebb 100 karakter lehet!".to_string());
+        }
+
+        let expiry_date = match value.expiry_date.trim() {
+            "" => None,
+            expiry_date => NaiveDate::parse_from_str(expiry_date, "%Y-%m-%d")
+                .inspect_err(|_| error.expiry_date = Some("Hibás dátum formátum!".to_string()))
+                .ok(),
+        };
+        if expiry_date.is_some() && lot_number.is_none() {
+            error.expiry_date =
+                Some("Lejárati dátum csak tételszámmal együtt adható meg!".to_string());
+        }
+
         if error.is_empty() {
             Ok(InventoryMovementUserInput {
                 id: id?,
@@ -197,6 +230,8 @@ impl TryFrom<InventoryMovementUserInputHelper> for InventoryMovementUserInput {
                 reference_id: reference_id?,
                 unit_price: unit_price?,
                 tax_id: tax_id?,
+                lot_number,
+                expiry_date,
             })
         } else {
             Err(error)
@@ -240,12 +275,14 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: String::from("1000"),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         })
         .unwrap();
 
         assert_eq!(imui.inventory_id.as_uuid().unwrap(), inventory_id);
         assert_eq!(imui.movement_type.as_str().unwrap(), "out");
-        assert_eq!(imui.quantity.as_f64().unwrap(), 10_f64);
+        assert_eq!(imui.quantity.as_decimal().unwrap(), &BigDecimal::from(10));
         assert_eq!(imui.reference_type.unwrap().as_str().unwrap(), "worksheets");
         assert_eq!(imui.reference_id.as_uuid().unwrap(), reference_id);
         assert_eq!(imui.unit_price.as_f64().unwrap(), 1000_f64);
@@ -264,6 +301,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: String::from("asd"),
             tax_id: String::from("asd"),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         })
         .unwrap_err();
 
@@ -278,4 +317,27 @@ mod tests {
         assert_eq!(imui.unit_price.unwrap(), Float64::PARSE_ERROR);
         assert_eq!(imui.tax_id.unwrap(), UuidVO::PARSE_ERROR);
     }
+
+    #[test]
+    fn expiry_date_requires_lot_number() {
+        let imui = InventoryMovementUserInput::try_from(InventoryMovementUserInputHelper {
+            id: None,
+            inventory_id: Uuid::new_v4().to_string(),
+            movement_type: String::from("in"),
+            quantity: String::from("10"),
+            reference_type: String::new(),
+            reference_id: String::new(),
+            unit_price: String::new(),
+            tax_id: Uuid::new_v4().to_string(),
+            lot_number: String::from("  "),
+            expiry_date: String::from("2026-12-31"),
+        })
+        .unwrap_err();
+
+        assert_eq!(imui.lot_number, None);
+        assert_eq!(
+            imui.expiry_date.unwrap(),
+            "Lejárati dátum csak tételszámmal együtt adható meg!"
+        );
+    }
 }
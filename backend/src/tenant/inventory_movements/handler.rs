@@ -18,12 +18,15 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::inventory_movements::InventoryMovementsModuleInterface;
+use crate::tenant::inventory_movements::dto::bundle::BundleIssueInput;
+use crate::tenant::inventory_movements::dto::ledger::InventoryLedgerQuery;
+use crate::tenant::inventory_movements::dto::negative_stock::NegativeStockInput;
 use crate::tenant::inventory_movements::dto::print::InventoryMovementsResolvedPrint;
 use crate::tenant::inventory_movements::dto::user_input::{
     InventoryMovementUserInput, InventoryMovementUserInputHelper, InventoryMovementsRawQuery,
@@ -108,6 +111,28 @@ pub async fn create<M: InventoryMovementsModuleInterface>(
     .into_response())
 }
 
+pub async fn issue_bundle<M: InventoryMovementsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_movements_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<BundleIssueInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_movements_module.clone());
+    let result = map_handler_err(
+        service.issue_bundle(&payload).await,
+        inventory_movements_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        inventory_movements_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn update<M: InventoryMovementsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(inventory_movements_module): State<Arc<M>>,
@@ -216,6 +241,94 @@ pub async fn select_list<M: InventoryMovementsModuleInterface>(
     .into_response())
 }
 
+pub async fn ledger<M: InventoryMovementsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_movements_module): State<Arc<M>>,
+    Query(payload): Query<InventoryLedgerQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_movements_module.clone());
+    let tz = map_handler_err(claims.tz(), inventory_movements_module.clone()).await?;
+    let result = map_handler_err(
+        service.get_ledger(&payload, tz).await,
+        inventory_movements_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_movements_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn negative_stock<M: InventoryMovementsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_movements_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_movements_module.clone());
+    let result = map_handler_err(
+        service.get_negative_stock_settings().await,
+        inventory_movements_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_movements_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_negative_stock<M: InventoryMovementsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_movements_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<NegativeStockInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_movements_module.clone());
+    let result = map_handler_err(
+        service.set_negative_stock(&payload).await,
+        inventory_movements_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_movements_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reverse<M: InventoryMovementsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_movements_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_movements_module.clone());
+    let result = map_handler_err(
+        service.reverse(payload.uuid).await,
+        inventory_movements_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        inventory_movements_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn print<M: InventoryMovementsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(inventory_movements_module): State<Arc<M>>,
@@ -254,12 +367,18 @@ mod tests {
     use crate::common::dto::PaginatorMeta;
     use crate::common::error::RepositoryError;
     use crate::common::handler::tests::{
-        extract_json_response, generate_expired_jwt, generate_jwt_with_invalid_signature,
-        generate_valid_jwt,
+        MockNegativeStockViolation, extract_json_response, generate_expired_jwt,
+        generate_jwt_with_invalid_signature, generate_valid_jwt,
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::inventory_movements::model::InventoryMovementResolved;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::inventory::model::BundleComponentStock;
+    use crate::tenant::inventory::repository::MockInventoryRepository;
+    use crate::tenant::inventory_movements::model::{
+        InventoryLedgerEntry, InventoryMovementResolved,
+    };
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::inventory_movements::{
@@ -269,6 +388,7 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
     use chrono::{DateTime, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
@@ -298,7 +418,13 @@ mod tests {
             reference_id,
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: utc_now,
             created_by_id,
             created_at: utc_now,
@@ -394,6 +520,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -436,6 +563,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -522,6 +650,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -547,8 +676,14 @@ mod tests {
             reference_id,
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
             tax: Some("Test Tax".to_string()),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: utc_now,
             created_by_id,
             created_by: "Test User".to_string(),
@@ -645,6 +780,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -687,6 +823,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -772,6 +909,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -802,8 +940,14 @@ mod tests {
             reference_id,
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
             tax: Some("Test Tax".to_string()),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: utc_now,
             created_by_id,
             created_by: "Test User".to_string(),
@@ -905,6 +1049,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -947,6 +1092,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1036,6 +1182,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -1062,7 +1209,13 @@ mod tests {
             reference_id: Some(reference_id),
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: utc_now,
             created_by_id,
             created_at: utc_now,
@@ -1077,6 +1230,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
         let user_input = InventoryMovementUserInput::try_from(user_input_helper.clone()).unwrap();
 
@@ -1146,6 +1301,70 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_create_negative_stock_not_allowed() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_input_helper = InventoryMovementUserInputHelper {
+            id: None,
+            inventory_id: Uuid::new_v4().to_string(),
+            movement_type: "out".to_string(),
+            quantity: "10".to_string(),
+            reference_type: String::new(),
+            reference_id: String::new(),
+            unit_price: String::new(),
+            tax_id: Uuid::new_v4().to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
+        };
+
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_insert().times(1).returning(|_, _| {
+            Err(RepositoryError::Database(sqlx::Error::Database(Box::new(
+                MockNegativeStockViolation,
+            ))))
+        });
+
+        let mut app_state = MockInventoryMovementsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_movements_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_movements/create")
+            .body(Body::from(
+                serde_json::to_string(&user_input_helper).unwrap(),
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_movements::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await["error"]["code"],
+            json!("INSUFFICIENT_STOCK")
+        );
+    }
+
     #[tokio::test]
     async fn test_create_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
@@ -1163,6 +1382,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1200,6 +1421,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             },
@@ -1223,6 +1445,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1255,6 +1479,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1277,6 +1502,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1309,6 +1536,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1330,6 +1558,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let app_state = MockInventoryMovementsModule::new();
@@ -1374,7 +1604,13 @@ mod tests {
             reference_id: Some(reference_id),
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: utc_now,
             created_by_id,
             created_at: utc_now,
@@ -1389,6 +1625,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
         let user_input = InventoryMovementUserInput::try_from(user_input_helper.clone()).unwrap();
 
@@ -1463,6 +1701,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1498,6 +1738,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             },
         });
@@ -1521,6 +1762,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1553,6 +1796,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1576,6 +1820,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let mut app_state = MockInventoryMovementsModule::new();
@@ -1608,6 +1854,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1631,6 +1878,8 @@ mod tests {
             reference_id: reference_id.to_string(),
             unit_price: "20".to_string(),
             tax_id: tax_id.to_string(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
         };
 
         let app_state = MockInventoryMovementsModule::new();
@@ -1791,6 +2040,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1833,6 +2083,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1888,8 +2139,14 @@ mod tests {
             reference_id: Some(reference_id),
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
             tax: Some("Test Tax".to_string()),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: test_time,
             created_by_id,
             created_by: "Test User".to_string(),
@@ -2018,6 +2275,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2061,6 +2319,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2097,4 +2356,372 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_ledger_running_balance() {
+        let active_tenant_id = Uuid::new_v4();
+        let inventory_id = Uuid::new_v4();
+        let start: DateTime<Utc> = "2025-12-31T23:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2026-01-31T23:00:00Z".parse().unwrap();
+        let entry = |quantity: &str, balance: &str| InventoryLedgerEntry {
+            id: Uuid::new_v4(),
+            movement_date: start,
+            movement_type: "in".to_string(),
+            reference_type: None,
+            reference_id: None,
+            lot_number: None,
+            quantity: quantity.parse().unwrap(),
+            unit_cost: None,
+            total_cost: None,
+            balance: balance.parse().unwrap(),
+        };
+        let entries = vec![entry("10", "15"), entry("-4", "11"), entry("-2", "9")];
+
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_get_ledger()
+            .times(1)
+            .with(eq(inventory_id), eq(start), eq(end))
+            .returning({
+                let entries = entries.clone();
+                move |_, _, _| Ok(("5".parse().unwrap(), entries.clone()))
+            });
+
+        let mut app_state = MockInventoryMovementsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_movements_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!(
+                "/api/inventory_movements/ledger?inventory_id={inventory_id}&from=2026-01-01&to=2026-01-31"
+            ))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_movements::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["opening_quantity"], json!("5"));
+        assert_eq!(response_body["data"]["total_in"], json!("10"));
+        assert_eq!(response_body["data"]["total_out"], json!("6"));
+        assert_eq!(response_body["data"]["closing_quantity"], json!("9"));
+        assert_eq!(response_body["data"]["entries"], json!(entries));
+    }
+
+    fn movement(movement_type: &str, quantity: &str) -> InventoryMovement {
+        InventoryMovement {
+            id: Uuid::new_v4(),
+            inventory_id: Uuid::new_v4(),
+            movement_type: movement_type.to_string(),
+            quantity: quantity.parse().unwrap(),
+            reference_type: None,
+            reference_id: None,
+            unit_price: None,
+            total_price: None,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: None,
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
+            movement_date: Utc::now(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn reverse_app(repo: MockInventoryMovementsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "inventory.adjust")
+            .returning(|_, _| Ok(true));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut app_state = MockInventoryMovementsModule::new();
+        app_state
+            .expect_inventory_movements_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        app_state
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_movements::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn reverse_request(id: Uuid, active_tenant_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_movements/reverse")
+            .body(Body::from(json!({"uuid": id}).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reverse() {
+        let active_tenant_id = Uuid::new_v4();
+        let original = movement("in", "10");
+        let reversal = InventoryMovement {
+            id: Uuid::new_v4(),
+            movement_type: "out".to_string(),
+            quantity: "-10".parse().unwrap(),
+            reverses_id: Some(original.id),
+            ..original.clone()
+        };
+
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(original.id))
+            .returning({
+                let original = original.clone();
+                move |_| Ok(original.clone())
+            });
+        repo.expect_is_serialized()
+            .times(1)
+            .with(eq(original.id))
+            .returning(|_| Ok(false));
+        repo.expect_reverse()
+            .times(1)
+            .withf({
+                let original_id = original.id;
+                move |id, _| *id == original_id
+            })
+            .returning({
+                let reversal = reversal.clone();
+                move |_, _| Ok(Some(reversal.clone()))
+            });
+
+        let response = reverse_app(repo, active_tenant_id)
+            .oneshot(reverse_request(original.id, active_tenant_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["id"], json!(reversal.id));
+        assert_eq!(body["data"]["reverses_id"], json!(original.id));
+        assert_eq!(body["data"]["quantity"], json!("-10"));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_already_reversed() {
+        let active_tenant_id = Uuid::new_v4();
+        let original = InventoryMovement {
+            reversed_by_id: Some(Uuid::new_v4()),
+            ..movement("out", "-4")
+        };
+
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let original = original.clone();
+            move |_| Ok(original.clone())
+        });
+        repo.expect_reverse().never();
+
+        let response = reverse_app(repo, active_tenant_id)
+            .oneshot(reverse_request(original.id, active_tenant_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    fn bundle_app(
+        inventory_repo: MockInventoryRepository,
+        repo: MockInventoryMovementsRepository,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let inventory_repo = Arc::new(inventory_repo);
+        let repo = Arc::new(repo);
+        let mut app_state = MockInventoryMovementsModule::new();
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(inventory_repo.clone()));
+        app_state
+            .expect_inventory_movements_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_movements::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn bundle_request(active_tenant_id: Uuid, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_movements/issue_bundle")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_issue_bundle_explodes_into_component_movements() {
+        let active_tenant_id = Uuid::new_v4();
+        let bundle_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+        let components = vec![
+            BundleComponentStock {
+                component_id: Uuid::new_v4(),
+                quantity: "2".parse().unwrap(),
+                inventory_id: Some(Uuid::new_v4()),
+            },
+            BundleComponentStock {
+                component_id: Uuid::new_v4(),
+                quantity: "1.5".parse().unwrap(),
+                inventory_id: Some(Uuid::new_v4()),
+            },
+        ];
+
+        let mut inventory_repo = MockInventoryRepository::new();
+        inventory_repo
+            .expect_get_bundle_stock()
+            .times(1)
+            .with(eq(bundle_id), eq(warehouse_id))
+            .returning({
+                let components = components.clone();
+                move |_, _| Ok(components.clone())
+            });
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_insert_bundle_issue()
+            .times(1)
+            .withf({
+                let expected = components.clone();
+                move |input, components, _| {
+                    components == expected.as_slice()
+                        && input.component_quantity(&components[1].quantity)
+                            == "4.5".parse::<BigDecimal>().unwrap()
+                }
+            })
+            .returning(|input, components, _| {
+                Ok(components
+                    .iter()
+                    .map(|component| InventoryMovement {
+                        inventory_id: component.inventory_id.unwrap(),
+                        ..movement(
+                            "out",
+                            &(-input.component_quantity(&component.quantity)).to_string(),
+                        )
+                    })
+                    .collect())
+            });
+
+        let response = bundle_app(inventory_repo, repo, active_tenant_id)
+            .oneshot(bundle_request(
+                active_tenant_id,
+                json!({
+                    "bundle_id": bundle_id,
+                    "warehouse_id": warehouse_id,
+                    "quantity": "3",
+                    "reference_type": null,
+                    "reference_id": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(
+            body["data"][0]["inventory_id"],
+            json!(components[0].inventory_id)
+        );
+        assert_eq!(body["data"][0]["quantity"], json!("-6"));
+        assert_eq!(body["data"][1]["quantity"], json!("-4.5"));
+    }
+
+    #[tokio::test]
+    async fn test_issue_bundle_component_not_stocked() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut inventory_repo = MockInventoryRepository::new();
+        inventory_repo
+            .expect_get_bundle_stock()
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![BundleComponentStock {
+                    component_id: Uuid::new_v4(),
+                    quantity: "1".parse().unwrap(),
+                    inventory_id: None,
+                }])
+            });
+        let mut repo = MockInventoryMovementsRepository::new();
+        repo.expect_insert_bundle_issue().never();
+
+        let response = bundle_app(inventory_repo, repo, active_tenant_id)
+            .oneshot(bundle_request(
+                active_tenant_id,
+                json!({
+                    "bundle_id": Uuid::new_v4(),
+                    "warehouse_id": Uuid::new_v4(),
+                    "quantity": "1",
+                    "reference_type": "worksheets",
+                    "reference_id": Uuid::new_v4()
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
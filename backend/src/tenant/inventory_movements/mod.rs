@@ -17,11 +17,12 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::AppState;
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
-use crate::common::{AppState, BaseModule};
 use crate::tenant::inventory::repository::InventoryRepository;
 use crate::tenant::inventory_movements::repository::InventoryMovementsRepository;
+use crate::tenant::permissions::PermissionsModuleInterface;
 use crate::tenant::taxes::repository::TaxesRepository;
 use crate::tenant::worksheets::repository::WorksheetsRepository;
 use lettre::{
@@ -40,7 +41,7 @@ pub(crate) mod routes;
 pub mod service;
 pub(crate) mod types;
 
-pub trait InventoryMovementsModuleInterface: BaseModule {
+pub trait InventoryMovementsModuleInterface: PermissionsModuleInterface {
     fn inventory_movements_repo(
         &self,
         tenant_id: Uuid,
@@ -61,7 +62,7 @@ pub trait InventoryMovementsModuleInterface: BaseModule {
 
 impl<P, T> InventoryMovementsModuleInterface for AppState<P, T>
 where
-    P: PoolManager,
+    P: PoolManager + Send + Sync + 'static,
     T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
     T::Error: Debug,
 {
@@ -97,6 +98,8 @@ pub mod tests {
     use crate::common::config::AppConfig;
     use crate::common::error::RepositoryResult;
     use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::TenantsRepository;
+    use crate::tenant::permissions::repository::PermissionsRepository;
     use lettre::{
         Message,
         transport::smtp::{Error, response::Response},
@@ -113,6 +116,13 @@ pub mod tests {
             async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
         }
         impl BaseModule for InventoryMovementsModule {}
+        impl PermissionsModuleInterface for InventoryMovementsModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
         impl InventoryMovementsModuleInterface for InventoryMovementsModule {
             fn inventory_movements_repo(
                 &self,
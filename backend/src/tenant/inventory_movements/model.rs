@@ -18,7 +18,7 @@
  */
 
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -33,7 +33,13 @@ pub struct InventoryMovement {
     pub reference_id: Option<Uuid>,
     pub unit_price: Option<BigDecimal>,
     pub total_price: Option<BigDecimal>,
-    pub tax_id: Uuid,
+    pub unit_cost: Option<BigDecimal>,
+    pub total_cost: Option<BigDecimal>,
+    pub tax_id: Option<Uuid>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub reverses_id: Option<Uuid>,
+    pub reversed_by_id: Option<Uuid>,
     pub movement_date: DateTime<Utc>,
     pub created_by_id: Uuid,
     pub created_at: DateTime<Utc>,
@@ -49,10 +55,55 @@ pub struct InventoryMovementResolved {
     pub reference_id: Option<Uuid>,
     pub unit_price: Option<BigDecimal>,
     pub total_price: Option<BigDecimal>,
-    pub tax_id: Uuid,
+    pub unit_cost: Option<BigDecimal>,
+    pub total_cost: Option<BigDecimal>,
+    pub tax_id: Option<Uuid>,
     pub tax: Option<String>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub reverses_id: Option<Uuid>,
+    pub reversed_by_id: Option<Uuid>,
     pub movement_date: DateTime<Utc>,
     pub created_by_id: Uuid,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventoryLedgerEntry {
+    pub id: Uuid,
+    pub movement_date: DateTime<Utc>,
+    pub movement_type: String,
+    pub reference_type: Option<String>,
+    pub reference_id: Option<Uuid>,
+    pub lot_number: Option<String>,
+    pub quantity: BigDecimal,
+    pub unit_cost: Option<BigDecimal>,
+    pub total_cost: Option<BigDecimal>,
+    pub balance: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InventoryLedger {
+    pub inventory_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub opening_quantity: BigDecimal,
+    pub total_in: BigDecimal,
+    pub total_out: BigDecimal,
+    pub closing_quantity: BigDecimal,
+    pub entries: Vec<InventoryLedgerEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WarehouseNegativeStock {
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub allow_negative_stock: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NegativeStockSettings {
+    pub allow_negative_stock: bool,
+    pub warehouses: Vec<WarehouseNegativeStock>,
+}
@@ -20,12 +20,18 @@
 use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::query_parser::ResourceQuery;
+use crate::tenant::inventory::model::BundleComponentStock;
+use crate::tenant::inventory_movements::dto::bundle::BundleIssueInput;
 use crate::tenant::inventory_movements::dto::user_input::InventoryMovementUserInput;
-use crate::tenant::inventory_movements::model::{InventoryMovement, InventoryMovementResolved};
+use crate::tenant::inventory_movements::model::{
+    InventoryLedgerEntry, InventoryMovement, InventoryMovementResolved, WarehouseNegativeStock,
+};
 use crate::tenant::inventory_movements::types::{
     InventoryMovementFilterBy, InventoryMovementOrderBy,
 };
 use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
 use sqlx::{AssertSqlSafe, PgPool};
@@ -51,6 +57,33 @@ pub trait InventoryMovementsRepository: Send + Sync {
         input: &InventoryMovementUserInput,
     ) -> RepositoryResult<InventoryMovement>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn reverse(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<InventoryMovement>>;
+    async fn insert_bundle_issue(
+        &self,
+        input: &BundleIssueInput,
+        components: &[BundleComponentStock],
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<InventoryMovement>>;
+    async fn is_serialized(&self, id: Uuid) -> RepositoryResult<bool>;
+    async fn get_lot_quantity(
+        &self,
+        inventory_id: Uuid,
+        lot_number: &str,
+    ) -> RepositoryResult<BigDecimal>;
+    async fn get_ledger(
+        &self,
+        inventory_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> RepositoryResult<(BigDecimal, Vec<InventoryLedgerEntry>)>;
+    async fn get_allow_negative_stock(&self) -> RepositoryResult<bool>;
+    async fn get_warehouse_negative_stock(&self) -> RepositoryResult<Vec<WarehouseNegativeStock>>;
+    async fn set_allow_negative_stock(&self, allow: bool, sub: Uuid) -> RepositoryResult<()>;
+    async fn set_warehouse_allow_negative_stock(
+        &self,
+        warehouse_id: Uuid,
+        allow: Option<bool>,
+    ) -> RepositoryResult<()>;
 }
 
 #[async_trait]
@@ -80,8 +113,14 @@ impl InventoryMovementsRepository for PgPool {
                 inventory_movements.reference_id,
                 inventory_movements.unit_price,
                 inventory_movements.total_price,
+                inventory_movements.unit_cost,
+                inventory_movements.total_cost,
                 inventory_movements.tax_id,
                 taxes.description as tax,
+                inventory_movements.lot_number,
+                inventory_movements.expiry_date,
+                inventory_movements.reverses_id,
+                inventory_movements.reversed_by_id,
                 inventory_movements.movement_date,
                 inventory_movements.created_by_id,
                 (users.last_name || ' ' || users.first_name) AS created_by,
@@ -156,8 +195,14 @@ impl InventoryMovementsRepository for PgPool {
                         inventory_movements.reference_id,
                         inventory_movements.unit_price,
                         inventory_movements.total_price,
+                        inventory_movements.unit_cost,
+                        inventory_movements.total_cost,
                         inventory_movements.tax_id,
                         taxes.description as tax,
+                        inventory_movements.lot_number,
+                        inventory_movements.expiry_date,
+                        inventory_movements.reverses_id,
+                        inventory_movements.reversed_by_id,
                         inventory_movements.movement_date,
                         inventory_movements.created_by_id,
                         (users.last_name || ' ' || users.first_name) AS created_by,
@@ -193,8 +238,14 @@ impl InventoryMovementsRepository for PgPool {
                         inventory_movements.reference_id,
                         inventory_movements.unit_price,
                         inventory_movements.total_price,
+                        inventory_movements.unit_cost,
+                        inventory_movements.total_cost,
                         inventory_movements.tax_id,
                         taxes.description as tax,
+                        inventory_movements.lot_number,
+                        inventory_movements.expiry_date,
+                        inventory_movements.reverses_id,
+                        inventory_movements.reversed_by_id,
                         inventory_movements.movement_date,
                         inventory_movements.created_by_id,
                         (users.last_name || ' ' || users.first_name) AS created_by,
@@ -241,8 +292,8 @@ impl InventoryMovementsRepository for PgPool {
             r#"
             INSERT INTO inventory_movements (
                 inventory_id, movement_type, quantity, reference_type, reference_id, unit_price,
-                tax_id, created_by_id
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                tax_id, created_by_id, lot_number, expiry_date
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING *
             "#,
         )
@@ -258,6 +309,8 @@ impl InventoryMovementsRepository for PgPool {
         .bind(input.unit_price.as_f64())
         .bind(input.tax_id.as_uuid()?)
         .bind(sub)
+        .bind(&input.lot_number)
+        .bind(input.expiry_date)
         .fetch_one(self)
         .await?)
     }
@@ -281,4 +334,222 @@ impl InventoryMovementsRepository for PgPool {
         .await?;
         Ok(())
     }
+
+    async fn reverse(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<InventoryMovement>> {
+        let mut tx = self.begin().await?;
+        // NOTE: outgoing movements are returned at their issue cost, incoming ones are
+        // consumed by the costing trigger like any other issue
+        let Some(reversal) = sqlx::query_as::<_, InventoryMovement>(
+            r#"
+            INSERT INTO inventory_movements (
+                inventory_id, movement_type, quantity, reference_type, reference_id, unit_price,
+                unit_cost, tax_id, lot_number, expiry_date, reverses_id, created_by_id
+            )
+            SELECT inventory_id,
+                   CASE movement_type WHEN 'in' THEN 'out' WHEN 'out' THEN 'in' ELSE movement_type END,
+                   -quantity,
+                   reference_type,
+                   reference_id,
+                   unit_price,
+                   CASE WHEN quantity < 0 THEN unit_cost END,
+                   tax_id,
+                   lot_number,
+                   expiry_date,
+                   id,
+                   $2
+            FROM inventory_movements
+            WHERE id = $1
+                AND reverses_id IS NULL
+                AND reversed_by_id IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+
+        sqlx::query("UPDATE inventory_movements SET reversed_by_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(reversal.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(reversal))
+    }
+
+    async fn insert_bundle_issue(
+        &self,
+        input: &BundleIssueInput,
+        components: &[BundleComponentStock],
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<InventoryMovement>> {
+        let mut tx = self.begin().await?;
+        let mut movements = Vec::with_capacity(components.len());
+        for component in components {
+            let inventory_id = component
+                .inventory_id
+                .ok_or_else(|| RepositoryError::InvalidInput("inventory_id".to_string()))?;
+            movements.push(
+                sqlx::query_as::<_, InventoryMovement>(
+                    r#"
+                    INSERT INTO inventory_movements (
+                        inventory_id, movement_type, quantity, reference_type, reference_id,
+                        created_by_id
+                    ) VALUES ($1, 'out', $2, $3, $4, $5)
+                    RETURNING *
+                    "#,
+                )
+                .bind(inventory_id)
+                .bind(-input.component_quantity(&component.quantity))
+                .bind(&input.reference_type)
+                .bind(input.reference_id)
+                .bind(sub)
+                .fetch_one(&mut *tx)
+                .await?,
+            );
+        }
+        tx.commit().await?;
+        Ok(movements)
+    }
+
+    async fn is_serialized(&self, id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM inventory_serials
+                WHERE receipt_movement_id = $1 OR issue_movement_id = $1
+            )
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_lot_quantity(
+        &self,
+        inventory_id: Uuid,
+        lot_number: &str,
+    ) -> RepositoryResult<BigDecimal> {
+        Ok(sqlx::query_scalar::<_, BigDecimal>(
+            r#"
+            SELECT COALESCE(SUM(quantity), 0)
+            FROM inventory_movements
+            WHERE inventory_id = $1 AND lot_number = $2
+            "#,
+        )
+        .bind(inventory_id)
+        .bind(lot_number)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_ledger(
+        &self,
+        inventory_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> RepositoryResult<(BigDecimal, Vec<InventoryLedgerEntry>)> {
+        // NOTE: both reads see the same snapshot, so the balances add up to the opening quantity
+        let mut tx = self.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let opening_quantity = sqlx::query_scalar::<_, BigDecimal>(
+            r#"
+            SELECT COALESCE(SUM(quantity), 0)
+            FROM inventory_movements
+            WHERE inventory_id = $1 AND movement_date < $2
+            "#,
+        )
+        .bind(inventory_id)
+        .bind(start)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entries = sqlx::query_as::<_, InventoryLedgerEntry>(
+            r#"
+            SELECT id,
+                   movement_date,
+                   movement_type,
+                   reference_type,
+                   reference_id,
+                   lot_number,
+                   quantity,
+                   unit_cost,
+                   total_cost,
+                   $4 + SUM(quantity) OVER (ORDER BY movement_date, created_at, id) AS balance
+            FROM inventory_movements
+            WHERE inventory_id = $1
+                AND movement_date >= $2
+                AND movement_date < $3
+            ORDER BY movement_date, created_at, id
+            "#,
+        )
+        .bind(inventory_id)
+        .bind(start)
+        .bind(end)
+        .bind(&opening_quantity)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok((opening_quantity, entries))
+    }
+
+    async fn get_allow_negative_stock(&self) -> RepositoryResult<bool> {
+        Ok(
+            sqlx::query_scalar::<_, bool>("SELECT allow_negative_stock FROM inventory_settings")
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_warehouse_negative_stock(&self) -> RepositoryResult<Vec<WarehouseNegativeStock>> {
+        Ok(sqlx::query_as::<_, WarehouseNegativeStock>(
+            r#"
+            SELECT id AS warehouse_id, name AS warehouse, allow_negative_stock
+            FROM warehouses
+            WHERE deleted_at IS NULL
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_allow_negative_stock(&self, allow: bool, sub: Uuid) -> RepositoryResult<()> {
+        sqlx::query("UPDATE inventory_settings SET allow_negative_stock = $1, updated_by_id = $2")
+            .bind(allow)
+            .bind(sub)
+            .execute(self)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_warehouse_allow_negative_stock(
+        &self,
+        warehouse_id: Uuid,
+        allow: Option<bool>,
+    ) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            "UPDATE warehouses SET allow_negative_stock = $1 WHERE id = $2 AND deleted_at IS NULL",
+        )
+        .bind(allow)
+        .bind(warehouse_id)
+        .execute(self)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound));
+        }
+        Ok(())
+    }
 }
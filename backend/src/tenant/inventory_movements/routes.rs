@@ -36,6 +36,11 @@ pub fn routes<M: InventoryMovementsModuleInterface>(inventory_movements_module:
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
+            .route("/reverse", post(handler::reverse::<M>))
+            .route("/issue_bundle", post(handler::issue_bundle::<M>))
+            .route("/ledger", get(handler::ledger::<M>))
+            .route("/negative_stock", get(handler::negative_stock::<M>))
+            .route("/set_negative_stock", put(handler::set_negative_stock::<M>))
             .route("/print", get(handler::print::<M>))
             .layer(from_fn_with_state(
                 inventory_movements_module.clone(),
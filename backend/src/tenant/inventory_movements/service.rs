@@ -21,20 +21,31 @@ use crate::common::dto::PaginatorMeta;
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::AppError;
 use crate::common::error::v2::AppErrorVisibility;
+use crate::common::error_code::ErrorCode;
 use crate::common::model::SelectOption;
 #[double]
 use crate::common::pdf::PdfGenerator;
 use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::common::utils::start_of_local_day;
+use crate::common::value_object::ValueObjectRequired;
 use crate::tenant::inventory_movements::InventoryMovementsModuleInterface;
+use crate::tenant::inventory_movements::dto::bundle::BundleIssueInput;
+use crate::tenant::inventory_movements::dto::ledger::InventoryLedgerQuery;
+use crate::tenant::inventory_movements::dto::negative_stock::NegativeStockInput;
 use crate::tenant::inventory_movements::dto::print::InventoryMovementsResolvedPrint;
 use crate::tenant::inventory_movements::dto::user_input::InventoryMovementUserInput;
-use crate::tenant::inventory_movements::model::{InventoryMovement, InventoryMovementResolved};
+use crate::tenant::inventory_movements::model::{
+    InventoryLedger, InventoryMovement, InventoryMovementResolved, NegativeStockSettings,
+};
 use crate::tenant::inventory_movements::types::{
-    InventoryMovementFilterBy, InventoryMovementOrderBy,
+    InventoryMovementFilterBy, InventoryMovementOrderBy, InventoryReferenceType,
 };
+use crate::tenant::permissions::model::INVENTORY_ADJUST;
+use crate::tenant::permissions::service::has_permission;
 use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::DateTime;
 use chrono::Utc;
 use chrono_tz::Tz;
@@ -56,6 +67,15 @@ pub enum InventoryMovementsServiceError {
     #[error("Hozzáférés megtagadva!")]
     Unauthorized,
 
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("A készlet nem mehet nulla alá.")]
+    InsufficientStock,
+
+    #[error("A mozgást már sztornózták!")]
+    AlreadyReversed,
+
     #[error("Hiba történt az adatok feldolgozása során: {0}")]
     UnprocessableEntry(&'static str),
 
@@ -90,6 +110,33 @@ impl From<InventoryMovementsServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            InventoryMovementsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            InventoryMovementsServiceError::InsufficientStock => Self::new(
+                Level::DEBUG,
+                ErrorCode::InsufficientStock.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::InsufficientStock.code(),
+                    "message": ErrorCode::InsufficientStock.description().hu
+                }),
+            ),
+            InventoryMovementsServiceError::AlreadyReversed => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
             InventoryMovementsServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -119,6 +166,38 @@ impl From<InventoryMovementsServiceError> for AppError {
 
 pub type InventoryMovementsServiceResult<T> = Result<T, InventoryMovementsServiceError>;
 
+fn map_stock_error(error: RepositoryError) -> InventoryMovementsServiceError {
+    if error.is_negative_stock_violation() {
+        InventoryMovementsServiceError::InsufficientStock
+    } else {
+        error.into()
+    }
+}
+
+fn validate_bundle_issue(payload: &BundleIssueInput) -> InventoryMovementsServiceResult<()> {
+    if payload.quantity <= BigDecimal::zero() || !payload.quantity.is_integer() {
+        return Err(InventoryMovementsServiceError::UnprocessableEntry(
+            "A csomagok száma pozitív egész szám kell legyen!",
+        ));
+    }
+    match (&payload.reference_type, payload.reference_id) {
+        (None, None) => Ok(()),
+        (Some(reference_type), Some(_)) => {
+            reference_type
+                .parse::<ValueObjectRequired<InventoryReferenceType>>()
+                .map_err(|_| {
+                    InventoryMovementsServiceError::UnprocessableEntry(
+                        InventoryReferenceType::VALIDATION_ERROR,
+                    )
+                })?;
+            Ok(())
+        }
+        _ => Err(InventoryMovementsServiceError::UnprocessableEntry(
+            "A hivatkozás típusát és azonosítóját együtt kell megadni!",
+        )),
+    }
+}
+
 pub enum InventoryMovementsSelectLists {
     Worksheets,
     Taxes,
@@ -163,6 +242,14 @@ pub trait InventoryMovementService {
         &self,
         payload: Uuid,
     ) -> impl Future<Output = InventoryMovementsServiceResult<()>> + Send;
+    fn reverse(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = InventoryMovementsServiceResult<InventoryMovement>> + Send;
+    fn issue_bundle(
+        &self,
+        payload: &BundleIssueInput,
+    ) -> impl Future<Output = InventoryMovementsServiceResult<Vec<InventoryMovement>>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<InventoryMovementOrderBy, InventoryMovementFilterBy>,
@@ -170,6 +257,18 @@ pub trait InventoryMovementService {
     ) -> impl Future<
         Output = InventoryMovementsServiceResult<(PaginatorMeta, Vec<InventoryMovementResolved>)>,
     > + Send;
+    fn get_ledger(
+        &self,
+        payload: &InventoryLedgerQuery,
+        tz: Tz,
+    ) -> impl Future<Output = InventoryMovementsServiceResult<InventoryLedger>> + Send;
+    fn get_negative_stock_settings(
+        &self,
+    ) -> impl Future<Output = InventoryMovementsServiceResult<NegativeStockSettings>> + Send;
+    fn set_negative_stock(
+        &self,
+        payload: &NegativeStockInput,
+    ) -> impl Future<Output = InventoryMovementsServiceResult<NegativeStockSettings>> + Send;
     fn print(
         &self,
         payload: &[InventoryMovementsResolvedPrint],
@@ -188,15 +287,26 @@ where
         &self,
         payload: &InventoryMovementUserInput,
     ) -> InventoryMovementsServiceResult<InventoryMovement> {
-        Ok(self
-            .module()
-            .inventory_movements_repo(
-                self.claims()?
-                    .active_tenant()
-                    .ok_or(InventoryMovementsServiceError::Unauthorized)?,
-            )?
-            .insert(payload, self.claims()?.sub())
-            .await?)
+        let repo = self.module().inventory_movements_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventoryMovementsServiceError::Unauthorized)?,
+        )?;
+        if let Some(lot_number) = &payload.lot_number
+            && let (Ok("out"), Ok(inventory_id), Ok(quantity)) = (
+                payload.movement_type.as_str(),
+                payload.inventory_id.as_uuid(),
+                payload.quantity.as_decimal(),
+            )
+            && repo.get_lot_quantity(inventory_id, lot_number).await? < *quantity
+        {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "A megadott tételből nincs elegendő készlet!",
+            ));
+        }
+        repo.insert(payload, self.claims()?.sub())
+            .await
+            .map_err(map_stock_error)
     }
     async fn get(&self, payload: Uuid) -> InventoryMovementsServiceResult<InventoryMovement> {
         Ok(self
@@ -218,15 +328,15 @@ where
                 "Az azonosító megadása kötelező!",
             ));
         }
-        Ok(self
-            .module()
+        self.module()
             .inventory_movements_repo(
                 self.claims()?
                     .active_tenant()
                     .ok_or(InventoryMovementsServiceError::Unauthorized)?,
             )?
             .update(payload)
-            .await?)
+            .await
+            .map_err(map_stock_error)
     }
     async fn get_resolved(
         &self,
@@ -244,15 +354,89 @@ where
     }
 
     async fn delete(&self, payload: Uuid) -> InventoryMovementsServiceResult<()> {
-        Ok(self
-            .module()
+        self.module()
             .inventory_movements_repo(
                 self.claims()?
                     .active_tenant()
                     .ok_or(InventoryMovementsServiceError::Unauthorized)?,
             )?
             .delete_by_id(payload)
-            .await?)
+            .await
+            .map_err(map_stock_error)
+    }
+
+    async fn reverse(&self, payload: Uuid) -> InventoryMovementsServiceResult<InventoryMovement> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(InventoryMovementsServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), INVENTORY_ADJUST).await? {
+            return Err(InventoryMovementsServiceError::Forbidden);
+        }
+        let repo = self.module().inventory_movements_repo(tenant_id)?;
+        let movement = repo.get_by_id(payload).await?;
+        if movement.reverses_id.is_some() {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "Sztornó mozgás nem sztornózható!",
+            ));
+        }
+        if movement.reversed_by_id.is_some() {
+            return Err(InventoryMovementsServiceError::AlreadyReversed);
+        }
+        if movement.movement_type == "transfer" {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "Az áthelyezés mozgásai csak az áthelyezésen keresztül módosíthatók!",
+            ));
+        }
+        if repo.is_serialized(payload).await? {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "Sorozatszámos mozgás nem sztornózható!",
+            ));
+        }
+        repo.reverse(payload, claims.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    InventoryMovementsServiceError::AlreadyReversed
+                } else {
+                    map_stock_error(e)
+                }
+            })?
+            .ok_or(InventoryMovementsServiceError::AlreadyReversed)
+    }
+
+    async fn issue_bundle(
+        &self,
+        payload: &BundleIssueInput,
+    ) -> InventoryMovementsServiceResult<Vec<InventoryMovement>> {
+        validate_bundle_issue(payload)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(InventoryMovementsServiceError::Unauthorized)?;
+        let components = self
+            .module()
+            .inventory_repo(tenant_id)?
+            .get_bundle_stock(payload.bundle_id, payload.warehouse_id)
+            .await?;
+        if components.is_empty() {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "A termék nem csomag!",
+            ));
+        }
+        if components
+            .iter()
+            .any(|component| component.inventory_id.is_none())
+        {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "A csomag valamelyik eleméhez nem tartozik készlet a raktárban!",
+            ));
+        }
+        self.module()
+            .inventory_movements_repo(tenant_id)?
+            .insert_bundle_issue(payload, &components, self.claims()?.sub())
+            .await
+            .map_err(map_stock_error)
     }
 
     async fn get_paged(
@@ -311,6 +495,98 @@ where
             payload.to_vec(),
         )?)
     }
+    async fn get_ledger(
+        &self,
+        payload: &InventoryLedgerQuery,
+        tz: Tz,
+    ) -> InventoryMovementsServiceResult<InventoryLedger> {
+        if payload.from > payload.to {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "A kezdő dátum nem lehet későbbi a záró dátumnál!",
+            ));
+        }
+        let (Some(start), Some(end)) = (
+            start_of_local_day(payload.from, tz),
+            payload
+                .to
+                .succ_opt()
+                .and_then(|next_day| start_of_local_day(next_day, tz)),
+        ) else {
+            return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                "Érvénytelen dátum!",
+            ));
+        };
+        let (opening_quantity, entries) = self
+            .module()
+            .inventory_movements_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventoryMovementsServiceError::Unauthorized)?,
+            )?
+            .get_ledger(payload.inventory_id, start, end)
+            .await?;
+        let (total_in, total_out) = entries.iter().fold(
+            (BigDecimal::zero(), BigDecimal::zero()),
+            |(total_in, total_out), entry| {
+                if entry.quantity > BigDecimal::zero() {
+                    (total_in + &entry.quantity, total_out)
+                } else {
+                    (total_in, total_out - &entry.quantity)
+                }
+            },
+        );
+        Ok(InventoryLedger {
+            inventory_id: payload.inventory_id,
+            from: payload.from,
+            to: payload.to,
+            closing_quantity: entries
+                .last()
+                .map_or_else(|| opening_quantity.clone(), |entry| entry.balance.clone()),
+            opening_quantity,
+            total_in,
+            total_out,
+            entries,
+        })
+    }
+    async fn get_negative_stock_settings(
+        &self,
+    ) -> InventoryMovementsServiceResult<NegativeStockSettings> {
+        let repo = self.module().inventory_movements_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventoryMovementsServiceError::Unauthorized)?,
+        )?;
+        Ok(NegativeStockSettings {
+            allow_negative_stock: repo.get_allow_negative_stock().await?,
+            warehouses: repo.get_warehouse_negative_stock().await?,
+        })
+    }
+    async fn set_negative_stock(
+        &self,
+        payload: &NegativeStockInput,
+    ) -> InventoryMovementsServiceResult<NegativeStockSettings> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(InventoryMovementsServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), INVENTORY_ADJUST).await? {
+            return Err(InventoryMovementsServiceError::Forbidden);
+        }
+        let repo = self.module().inventory_movements_repo(tenant_id)?;
+        match (payload.warehouse_id, payload.allow_negative_stock) {
+            (Some(warehouse_id), allow) => {
+                repo.set_warehouse_allow_negative_stock(warehouse_id, allow)
+                    .await?
+            }
+            (None, Some(allow)) => repo.set_allow_negative_stock(allow, claims.sub()).await?,
+            (None, None) => {
+                return Err(InventoryMovementsServiceError::UnprocessableEntry(
+                    "A beállítás megadása kötelező!",
+                ));
+            }
+        }
+        self.get_negative_stock_settings().await
+    }
     async fn print_snapshot(&self, path: &Path) -> InventoryMovementsServiceResult<()> {
         let test_time: DateTime<Utc> =
             "2026-01-02T11:11:11Z"
@@ -347,8 +623,14 @@ where
             reference_id: Some(reference_id),
             unit_price: Some("20".parse().unwrap()),
             total_price: Some("30".parse().unwrap()),
-            tax_id,
+            unit_cost: None,
+            total_cost: None,
+            tax_id: Some(tax_id),
             tax: Some("Test Tax".to_string()),
+            lot_number: None,
+            expiry_date: None,
+            reverses_id: None,
+            reversed_by_id: None,
             movement_date: test_time,
             created_by_id,
             created_by: "Test User".to_string(),
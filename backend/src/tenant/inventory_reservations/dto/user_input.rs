@@ -202,6 +202,7 @@ impl InventoryReservationsRawQuery {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bigdecimal::BigDecimal;
     use chrono::{Days, Utc};
 
     #[test]
@@ -225,7 +226,7 @@ mod tests {
         .unwrap();
 
         assert_eq!(irui.inventory_id.as_uuid().unwrap(), inventory_id);
-        assert_eq!(irui.quantity.as_f64().unwrap(), 10_f64);
+        assert_eq!(irui.quantity.as_decimal().unwrap(), &BigDecimal::from(10));
         assert_eq!(irui.reference_type.unwrap().as_str().unwrap(), "worksheets");
         assert_eq!(irui.reference_id.as_uuid().unwrap(), reference_id);
         assert_eq!(irui.reserved_until.as_date_naive().unwrap(), &valid_date);
@@ -18,12 +18,13 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::inventory_reservations::InventoryReservationsModuleInterface;
+use crate::tenant::inventory_reservations::dto::bundle::BundleReservationInput;
 use crate::tenant::inventory_reservations::dto::print::InventoryReservationResolvedPrint;
 use crate::tenant::inventory_reservations::dto::user_input::{
     InventoryReservationUserInput, InventoryReservationUserInputHelper,
@@ -109,6 +110,28 @@ pub async fn create<M: InventoryReservationsModuleInterface>(
     .into_response())
 }
 
+pub async fn reserve_bundle<M: InventoryReservationsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_reservations_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<BundleReservationInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_reservations_module.clone());
+    let result = map_handler_err(
+        service.reserve_bundle(&payload).await,
+        inventory_reservations_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        inventory_reservations_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn update<M: InventoryReservationsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(inventory_reservations_module): State<Arc<M>>,
@@ -262,6 +285,8 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
+    use crate::tenant::inventory::model::BundleComponentStock;
+    use crate::tenant::inventory::repository::MockInventoryRepository;
     use crate::tenant::inventory_reservations::model::InventoryReservationResolved;
     use crate::{
         common::config::tests::AppConfigBuilder,
@@ -272,6 +297,7 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
     use chrono::{DateTime, Duration, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
@@ -282,6 +308,21 @@ mod tests {
     use tower::ServiceExt;
     use uuid::Uuid;
 
+    fn reservation(id: Uuid, inventory_id: Uuid) -> InventoryReservation {
+        InventoryReservation {
+            id,
+            inventory_id,
+            quantity: "10".parse().unwrap(),
+            reference_type: None,
+            reference_id: None,
+            reserved_until: None,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
     #[tokio::test]
     async fn test_get_success() {
         let active_tenant_id = Uuid::new_v4();
@@ -393,6 +434,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -435,6 +477,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -520,6 +563,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             },
         });
@@ -639,6 +683,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -681,6 +726,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -766,6 +812,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             },
         });
@@ -897,6 +944,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -939,6 +987,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1029,6 +1078,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             },
         });
@@ -1088,7 +1138,7 @@ mod tests {
             })
             .returning({
                 let inventory_reservation = inventory_reservation.clone();
-                move |_, _| Ok(inventory_reservation.clone())
+                move |_, _| Ok(Some(inventory_reservation.clone()))
             });
 
         let mut app_state = MockInventoryReservationsModule::new();
@@ -1136,6 +1186,65 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_create_insufficient_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let user_input_helper = InventoryReservationUserInputHelper {
+            id: None,
+            inventory_id: Uuid::new_v4().to_string(),
+            quantity: "10".to_string(),
+            reference_type: "worksheets".to_string(),
+            reference_id: Uuid::new_v4().to_string(),
+            reserved_until: (Utc::now() + Duration::days(2)).date_naive().to_string(),
+            status: "active".to_string(),
+        };
+
+        let mut repo = MockInventoryReservationsRepository::new();
+        repo.expect_insert().times(1).returning(|_, _| Ok(None));
+
+        let mut app_state = MockInventoryReservationsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_reservations_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_reservations/create")
+            .body(Body::from(
+                serde_json::to_string(&user_input_helper).unwrap(),
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_reservations::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await["error"]["code"],
+            json!("INSUFFICIENT_STOCK")
+        );
+    }
+
     #[tokio::test]
     async fn test_create_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
@@ -1189,6 +1298,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields":user_input_error
             }
@@ -1242,6 +1352,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1294,6 +1405,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1374,12 +1486,19 @@ mod tests {
         };
 
         let mut repo = MockInventoryReservationsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(inventory_reservation_id))
+            .returning({
+                let inventory_reservation = inventory_reservation.clone();
+                move |_| Ok(inventory_reservation.clone())
+            });
         repo.expect_update()
             .times(1)
             .with(eq(user_input))
             .returning({
                 let inventory_reservation = inventory_reservation.clone();
-                move |_| Ok(inventory_reservation.clone())
+                move |_| Ok(Some(inventory_reservation.clone()))
             });
 
         let mut app_state = MockInventoryReservationsModule::new();
@@ -1427,6 +1546,136 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
+    #[tokio::test]
+    async fn test_update_insufficient_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let inventory_reservation_id = Uuid::new_v4();
+        let inventory_id = Uuid::new_v4();
+
+        let user_input_helper = InventoryReservationUserInputHelper {
+            id: Some(inventory_reservation_id.to_string()),
+            inventory_id: inventory_id.to_string(),
+            quantity: "10.5".to_string(),
+            reference_type: "worksheets".to_string(),
+            reference_id: Uuid::new_v4().to_string(),
+            reserved_until: (Utc::now() + Duration::days(2)).date_naive().to_string(),
+            status: "active".to_string(),
+        };
+
+        let mut repo = MockInventoryReservationsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(inventory_reservation_id))
+            .returning(move |id| Ok(reservation(id, inventory_id)));
+        repo.expect_update()
+            .times(1)
+            .withf(|input| {
+                input.quantity.as_decimal().unwrap() == &"10.5".parse::<BigDecimal>().unwrap()
+            })
+            .returning(|_| Ok(None));
+
+        let mut app_state = MockInventoryReservationsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_reservations_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/inventory_reservations/update")
+            .body(Body::from(
+                serde_json::to_string(&user_input_helper).unwrap(),
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_reservations::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await["error"]["code"],
+            json!("INSUFFICIENT_STOCK")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_changed_inventory() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let inventory_reservation_id = Uuid::new_v4();
+
+        let user_input_helper = InventoryReservationUserInputHelper {
+            id: Some(inventory_reservation_id.to_string()),
+            inventory_id: Uuid::new_v4().to_string(),
+            quantity: "10".to_string(),
+            reference_type: "worksheets".to_string(),
+            reference_id: Uuid::new_v4().to_string(),
+            reserved_until: (Utc::now() + Duration::days(2)).date_naive().to_string(),
+            status: "active".to_string(),
+        };
+
+        let mut repo = MockInventoryReservationsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(inventory_reservation_id))
+            .returning(|id| Ok(reservation(id, Uuid::new_v4())));
+        repo.expect_update().never();
+
+        let mut app_state = MockInventoryReservationsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_reservations_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/inventory_reservations/update")
+            .body(Body::from(
+                serde_json::to_string(&user_input_helper).unwrap(),
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_reservations::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[tokio::test]
     async fn test_update_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
@@ -1478,6 +1727,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             },
         });
@@ -1531,6 +1781,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1584,6 +1835,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1765,6 +2017,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1807,6 +2060,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1989,6 +2243,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2032,6 +2287,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2068,4 +2324,84 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_reserve_bundle_insufficient_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let bundle_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+
+        let mut inventory_repo = MockInventoryRepository::new();
+        inventory_repo
+            .expect_get_bundle_stock()
+            .times(1)
+            .with(eq(bundle_id), eq(warehouse_id))
+            .returning(|_, _| {
+                Ok(vec![BundleComponentStock {
+                    component_id: Uuid::new_v4(),
+                    quantity: "2".parse().unwrap(),
+                    inventory_id: Some(Uuid::new_v4()),
+                }])
+            });
+        let mut repo = MockInventoryReservationsRepository::new();
+        repo.expect_insert_bundle()
+            .times(1)
+            .withf(|input, components, _| {
+                input.component_quantity(&components[0].quantity)
+                    == "10".parse::<BigDecimal>().unwrap()
+            })
+            .returning(|_, _, _| Ok(None));
+
+        let mut app_state = MockInventoryReservationsModule::new();
+        let inventory_repo = Arc::new(inventory_repo);
+        let repo = Arc::new(repo);
+        app_state
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(inventory_repo.clone()));
+        app_state
+            .expect_inventory_reservations_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/inventory_reservations/reserve_bundle")
+            .body(Body::from(
+                json!({
+                    "bundle_id": bundle_id,
+                    "warehouse_id": warehouse_id,
+                    "quantity": "5",
+                    "reference_type": "worksheets",
+                    "reference_id": Uuid::new_v4(),
+                    "reserved_until": Utc::now() + Duration::days(2)
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_reservations::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await["error"]["code"],
+            json!("INSUFFICIENT_STOCK")
+        );
+    }
 }
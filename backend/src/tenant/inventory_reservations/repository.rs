@@ -18,8 +18,10 @@
  */
 
 use crate::common::dto::PaginatorMeta;
-use crate::common::error::RepositoryResult;
+use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::query_parser::ResourceQuery;
+use crate::tenant::inventory::model::BundleComponentStock;
+use crate::tenant::inventory_reservations::dto::bundle::BundleReservationInput;
 use crate::tenant::inventory_reservations::dto::user_input::InventoryReservationUserInput;
 use crate::tenant::inventory_reservations::model::{
     InventoryReservation, InventoryReservationResolved,
@@ -47,12 +49,18 @@ pub trait InventoryReservationsRepository: Send + Sync {
         &self,
         input: InventoryReservationUserInput,
         sub: Uuid,
-    ) -> RepositoryResult<InventoryReservation>;
+    ) -> RepositoryResult<Option<InventoryReservation>>;
     async fn update(
         &self,
         input: &InventoryReservationUserInput,
-    ) -> RepositoryResult<InventoryReservation>;
+    ) -> RepositoryResult<Option<InventoryReservation>>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn insert_bundle(
+        &self,
+        input: &BundleReservationInput,
+        components: &[BundleComponentStock],
+        sub: Uuid,
+    ) -> RepositoryResult<Option<Vec<InventoryReservation>>>;
 }
 
 #[async_trait]
@@ -228,12 +236,34 @@ impl InventoryReservationsRepository for PgPool {
         &self,
         input: InventoryReservationUserInput,
         sub: Uuid,
-    ) -> RepositoryResult<InventoryReservation> {
+    ) -> RepositoryResult<Option<InventoryReservation>> {
         let reference_type = match &input.reference_type {
             Some(v) => Some(v.as_str()?),
             None => None,
         };
-        Ok(sqlx::query_as::<_, InventoryReservation>(
+        let mut tx = self.begin().await?;
+
+        // NOTE: the row lock serializes concurrent reservations of the same stock, the
+        // reserved quantity trigger of the insert below then sees every committed reservation
+        let sufficient = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT $3 <> 'active' OR quantity_available >= $2
+            FROM inventory
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.inventory_id.as_uuid()?)
+        .bind(input.quantity.as_decimal()?)
+        .bind(input.status.as_str()?)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !sufficient {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let inventory_reservation = sqlx::query_as::<_, InventoryReservation>(
             r#"
             INSERT INTO inventory_reservations (
                 inventory_id, quantity, reference_type, reference_id, reserved_until,
@@ -243,22 +273,139 @@ impl InventoryReservationsRepository for PgPool {
             "#,
         )
         .bind(input.inventory_id.as_uuid()?)
-        .bind(input.quantity.as_f64()?)
+        .bind(input.quantity.as_decimal()?)
         .bind(reference_type)
         .bind(input.reference_id.as_uuid())
         .bind(input.reserved_until.as_date_naive()?)
         .bind(input.status.as_str()?)
         .bind(sub)
-        .fetch_one(self)
-        .await?)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(inventory_reservation))
     }
     async fn update(
         &self,
-        _input: &InventoryReservationUserInput,
-    ) -> RepositoryResult<InventoryReservation> {
-        // TODO: implement this function!
-        todo!()
+        input: &InventoryReservationUserInput,
+    ) -> RepositoryResult<Option<InventoryReservation>> {
+        let id = input
+            .id
+            .as_uuid()
+            .ok_or_else(|| RepositoryError::InvalidInput("id".to_string()))?;
+        let reference_type = match &input.reference_type {
+            Some(v) => Some(v.as_str()?),
+            None => None,
+        };
+        let mut tx = self.begin().await?;
+
+        // NOTE: the reservation keeps its stock row, the reserved quantity trigger only
+        // recalculates the inventory of the updated row. The reservation's own active
+        // quantity is still counted in quantity_available, so it is added back here.
+        let sufficient = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT $3 <> 'active'
+                OR inventory.quantity_available
+                    + CASE
+                        WHEN inventory_reservations.status = 'active'
+                            THEN inventory_reservations.quantity
+                        ELSE 0
+                    END >= $2
+            FROM inventory_reservations
+            JOIN inventory ON inventory.id = inventory_reservations.inventory_id
+            WHERE inventory_reservations.id = $1 AND inventory.deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .bind(input.quantity.as_decimal()?)
+        .bind(input.status.as_str()?)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !sufficient {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        let inventory_reservation = sqlx::query_as::<_, InventoryReservation>(
+            r#"
+            UPDATE inventory_reservations
+            SET quantity = $2,
+                reference_type = $3,
+                reference_id = $4,
+                reserved_until = $5,
+                status = $6
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(input.quantity.as_decimal()?)
+        .bind(reference_type)
+        .bind(input.reference_id.as_uuid())
+        .bind(input.reserved_until.as_date_naive()?)
+        .bind(input.status.as_str()?)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(inventory_reservation))
+    }
+    async fn insert_bundle(
+        &self,
+        input: &BundleReservationInput,
+        components: &[BundleComponentStock],
+        sub: Uuid,
+    ) -> RepositoryResult<Option<Vec<InventoryReservation>>> {
+        let mut tx = self.begin().await?;
+        let mut reservations = Vec::with_capacity(components.len());
+        // NOTE: components arrive ordered by id, so concurrent bundle reservations lock the
+        // shared stock rows in the same order
+        for component in components {
+            let inventory_id = component
+                .inventory_id
+                .ok_or_else(|| RepositoryError::InvalidInput("inventory_id".to_string()))?;
+            let quantity = input.component_quantity(&component.quantity);
+            let sufficient = sqlx::query_scalar::<_, bool>(
+                r#"
+                SELECT quantity_available >= $2
+                FROM inventory
+                WHERE id = $1 AND deleted_at IS NULL
+                FOR UPDATE
+                "#,
+            )
+            .bind(inventory_id)
+            .bind(&quantity)
+            .fetch_one(&mut *tx)
+            .await?;
+            if !sufficient {
+                tx.rollback().await?;
+                return Ok(None);
+            }
+            reservations.push(
+                sqlx::query_as::<_, InventoryReservation>(
+                    r#"
+                    INSERT INTO inventory_reservations (
+                        inventory_id, quantity, reference_type, reference_id, reserved_until,
+                        status, created_by_id
+                    ) VALUES ($1, $2, $3, $4, $5, 'active', $6)
+                    RETURNING *
+                    "#,
+                )
+                .bind(inventory_id)
+                .bind(&quantity)
+                .bind(&input.reference_type)
+                .bind(input.reference_id)
+                .bind(input.reserved_until)
+                .bind(sub)
+                .fetch_one(&mut *tx)
+                .await?,
+            );
+        }
+        tx.commit().await?;
+        Ok(Some(reservations))
     }
+
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
         let _ = sqlx::query(
             r#"
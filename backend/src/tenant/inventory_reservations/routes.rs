@@ -36,6 +36,7 @@ pub fn routes<M: InventoryReservationsModuleInterface>(
             .route("/list", get(handler::list::<M>))
             .route("/select_list", get(handler::select_list::<M>))
             .route("/create", post(handler::create::<M>))
+            .route("/reserve_bundle", post(handler::reserve_bundle::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
             .route("/print", get(handler::print::<M>))
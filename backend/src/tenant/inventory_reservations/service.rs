@@ -20,22 +20,26 @@
 use crate::common::dto::PaginatorMeta;
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
 use crate::common::model::SelectOption;
 #[double]
 use crate::common::pdf::PdfGenerator;
 use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::common::value_object::ValueObjectRequired;
 use crate::tenant::inventory_reservations::InventoryReservationsModuleInterface;
+use crate::tenant::inventory_reservations::dto::bundle::BundleReservationInput;
 use crate::tenant::inventory_reservations::dto::print::InventoryReservationResolvedPrint;
 use crate::tenant::inventory_reservations::dto::user_input::InventoryReservationUserInput;
 use crate::tenant::inventory_reservations::model::{
     InventoryReservation, InventoryReservationResolved,
 };
 use crate::tenant::inventory_reservations::types::{
-    InventoryReservationFilterBy, InventoryReservationOrderBy,
+    InventoryReferenceType, InventoryReservationFilterBy, InventoryReservationOrderBy,
 };
 use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
@@ -62,6 +66,9 @@ pub enum InventoryReservationsServiceError {
     #[error("A lista nem létezik")]
     InvalidSelectList,
 
+    #[error("Nincs elegendő szabad készlet!")]
+    InsufficientStock,
+
     #[error("PdfGen error: {0}")]
     PdfGenError(#[from] PdfGenError),
 
@@ -90,6 +97,16 @@ impl From<InventoryReservationsServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            InventoryReservationsServiceError::InsufficientStock => Self::new(
+                Level::DEBUG,
+                ErrorCode::InsufficientStock.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::InsufficientStock.code(),
+                    "message": ErrorCode::InsufficientStock.description().hu
+                }),
+            ),
             InventoryReservationsServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -119,6 +136,33 @@ impl From<InventoryReservationsServiceError> for AppError {
 
 pub type InventoryReservationsServiceResult<T> = Result<T, InventoryReservationsServiceError>;
 
+fn validate_bundle_reservation(
+    payload: &BundleReservationInput,
+) -> InventoryReservationsServiceResult<()> {
+    if payload.quantity <= BigDecimal::zero() || !payload.quantity.is_integer() {
+        return Err(InventoryReservationsServiceError::UnprocessableEntry(
+            "A csomagok száma pozitív egész szám kell legyen!",
+        ));
+    }
+    payload
+        .reference_type
+        .parse::<ValueObjectRequired<InventoryReferenceType>>()
+        .map_err(|_| {
+            InventoryReservationsServiceError::UnprocessableEntry(
+                InventoryReferenceType::VALIDATION_ERROR,
+            )
+        })?;
+    if payload
+        .reserved_until
+        .is_some_and(|reserved_until| reserved_until <= Utc::now())
+    {
+        return Err(InventoryReservationsServiceError::UnprocessableEntry(
+            "A foglalás lejárata csak jövőbeli időpont lehet!",
+        ));
+    }
+    Ok(())
+}
+
 pub enum InventoryReservationsSelectLists {
     Worksheets,
     Inventory,
@@ -171,6 +215,10 @@ pub trait InventoryReservationService {
             Vec<InventoryReservationResolved>,
         )>,
     > + Send;
+    fn reserve_bundle(
+        &self,
+        payload: &BundleReservationInput,
+    ) -> impl Future<Output = InventoryReservationsServiceResult<Vec<InventoryReservation>>> + Send;
     fn print(
         &self,
         payload: &[InventoryReservationResolvedPrint],
@@ -189,34 +237,79 @@ where
         &self,
         payload: &InventoryReservationUserInput,
     ) -> InventoryReservationsServiceResult<InventoryReservation> {
-        Ok(self
-            .module()
+        self.module()
             .inventory_reservations_repo(
                 self.claims()?
                     .active_tenant()
                     .ok_or(InventoryReservationsServiceError::Unauthorized)?,
             )?
             .insert(payload.clone(), self.claims()?.sub())
-            .await?)
+            .await?
+            .ok_or(InventoryReservationsServiceError::InsufficientStock)
+    }
+    async fn reserve_bundle(
+        &self,
+        payload: &BundleReservationInput,
+    ) -> InventoryReservationsServiceResult<Vec<InventoryReservation>> {
+        validate_bundle_reservation(payload)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(InventoryReservationsServiceError::Unauthorized)?;
+        let components = self
+            .module()
+            .inventory_repo(tenant_id)?
+            .get_bundle_stock(payload.bundle_id, payload.warehouse_id)
+            .await?;
+        if components.is_empty() {
+            return Err(InventoryReservationsServiceError::UnprocessableEntry(
+                "A termék nem csomag!",
+            ));
+        }
+        if components
+            .iter()
+            .any(|component| component.inventory_id.is_none())
+        {
+            return Err(InventoryReservationsServiceError::InsufficientStock);
+        }
+        self.module()
+            .inventory_reservations_repo(tenant_id)?
+            .insert_bundle(payload, &components, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    InventoryReservationsServiceError::UnprocessableEntry(
+                        "A csomag valamelyik elemére már van aktív foglalás ehhez a hivatkozáshoz!",
+                    )
+                } else {
+                    e.into()
+                }
+            })?
+            .ok_or(InventoryReservationsServiceError::InsufficientStock)
     }
     async fn update(
         &self,
         payload: &InventoryReservationUserInput,
     ) -> InventoryReservationsServiceResult<InventoryReservation> {
-        if !payload.id.is_present() {
+        let Some(id) = payload.id.as_uuid() else {
             return Err(InventoryReservationsServiceError::UnprocessableEntry(
                 "Az azonosító megadása kötelező!",
             ));
+        };
+        let repo = self.module().inventory_reservations_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventoryReservationsServiceError::Unauthorized)?,
+        )?;
+        // NOTE: the reservation stays on its stock row, moving it means a new reservation
+        if payload.inventory_id.as_uuid().ok() != Some(repo.get_by_id(id).await?.inventory_id) {
+            return Err(InventoryReservationsServiceError::UnprocessableEntry(
+                "A foglalás készlete nem módosítható!",
+            ));
         }
-        Ok(self
-            .module()
-            .inventory_reservations_repo(
-                self.claims()?
-                    .active_tenant()
-                    .ok_or(InventoryReservationsServiceError::Unauthorized)?,
-            )?
-            .update(payload)
-            .await?)
+        repo.update(payload)
+            .await?
+            .ok_or(InventoryReservationsServiceError::InsufficientStock)
     }
     async fn get(&self, payload: Uuid) -> InventoryReservationsServiceResult<InventoryReservation> {
         Ok(self
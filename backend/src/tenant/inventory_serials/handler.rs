@@ -0,0 +1,357 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::inventory_serials::InventorySerialsModuleInterface;
+use crate::tenant::inventory_serials::dto::{IssueSerials, RegisterSerials, SerialNumberQuery};
+use crate::tenant::inventory_serials::service::InventorySerialsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn available<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.get_available(payload.uuid).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn lookup<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    Query(payload): Query<SerialNumberQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.lookup(&payload.serial_number).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn register<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RegisterSerials>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.register(&payload).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn issue<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<IssueSerials>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.issue(&payload).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reserve<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.reserve(payload.uuid).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn release<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.release(payload.uuid).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn mark_returned<M: InventorySerialsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(inventory_serials_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), inventory_serials_module.clone());
+    let result = map_handler_err(
+        service.mark_returned(payload.uuid).await,
+        inventory_serials_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        inventory_serials_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::inventory_serials::model::{InventorySerial, SerialMovement};
+    use crate::tenant::inventory_serials::{
+        self, repository::MockInventorySerialsRepository, tests::MockInventorySerialsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockInventorySerialsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut inventory_serials_module = MockInventorySerialsModule::new();
+        inventory_serials_module
+            .expect_inventory_serials_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        inventory_serials_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(inventory_serials::routes::routes(Arc::new(
+                inventory_serials_module,
+            ))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn movement(movement_type: &str, quantity: i64, serial_count: i64) -> SerialMovement {
+        SerialMovement {
+            id: Uuid::new_v4(),
+            inventory_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            movement_type: movement_type.to_string(),
+            quantity: BigDecimal::from(quantity),
+            serial_count,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_more_serials_than_received() {
+        let active_tenant_id = Uuid::new_v4();
+        let movement = movement("in", 2, 1);
+        let mut repo = MockInventorySerialsRepository::new();
+        repo.expect_get_movement()
+            .times(1)
+            .with(eq(movement.id))
+            .returning({
+                let movement = movement.clone();
+                move |_| Ok(movement.clone())
+            });
+        repo.expect_register().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/inventory_serials/register",
+                active_tenant_id,
+                json!({
+                    "inventory_movement_id": movement.id,
+                    "serial_numbers": ["SN-001", "SN-002"]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_issue_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let movement = movement("out", -1, 0);
+        let serial = InventorySerial {
+            id: Uuid::new_v4(),
+            product_id: movement.product_id,
+            inventory_id: movement.inventory_id,
+            serial_number: "SN-001".to_string(),
+            status: "sold".to_string(),
+            receipt_movement_id: Uuid::new_v4(),
+            issue_movement_id: Some(movement.id),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let mut repo = MockInventorySerialsRepository::new();
+        repo.expect_get_movement()
+            .times(1)
+            .with(eq(movement.id))
+            .returning({
+                let movement = movement.clone();
+                move |_| Ok(movement.clone())
+            });
+        repo.expect_issue()
+            .times(1)
+            .with(eq(movement.clone()), eq(vec![serial.id]))
+            .returning({
+                let serial = serial.clone();
+                move |_, _| Ok(Some(vec![serial.clone()]))
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/inventory_serials/issue",
+                active_tenant_id,
+                json!({
+                    "inventory_movement_id": movement.id,
+                    "serial_ids": [serial.id]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_return_serial_not_sold() {
+        let active_tenant_id = Uuid::new_v4();
+        let serial_id = Uuid::new_v4();
+        let mut repo = MockInventorySerialsRepository::new();
+        repo.expect_update_status()
+            .times(1)
+            .with(eq(serial_id), eq(vec!["sold"]), eq("returned"))
+            .returning(|_, _, _| Ok(None));
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/inventory_serials/return",
+                active_tenant_id,
+                json!({ "uuid": serial_id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
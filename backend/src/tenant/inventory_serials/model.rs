@@ -0,0 +1,66 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_IN_STOCK: &str = "in_stock";
+pub const STATUS_RESERVED: &str = "reserved";
+pub const STATUS_SOLD: &str = "sold";
+pub const STATUS_RETURNED: &str = "returned";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventorySerial {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub inventory_id: Uuid,
+    pub serial_number: String,
+    pub status: String,
+    pub receipt_movement_id: Uuid,
+    pub issue_movement_id: Option<Uuid>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InventorySerialLocation {
+    pub id: Uuid,
+    pub serial_number: String,
+    pub status: String,
+    pub product_id: Uuid,
+    pub product: String,
+    pub inventory_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SerialMovement {
+    pub id: Uuid,
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub movement_type: String,
+    pub quantity: BigDecimal,
+    pub serial_count: i64,
+}
@@ -0,0 +1,222 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::inventory_serials::model::{
+    InventorySerial, InventorySerialLocation, STATUS_IN_STOCK, STATUS_RESERVED, STATUS_RETURNED,
+    STATUS_SOLD, SerialMovement,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait InventorySerialsRepository: Send + Sync {
+    async fn get_movement(&self, inventory_movement_id: Uuid) -> RepositoryResult<SerialMovement>;
+    async fn get_available(&self, inventory_id: Uuid) -> RepositoryResult<Vec<InventorySerial>>;
+    async fn lookup(&self, serial_number: &str) -> RepositoryResult<Vec<InventorySerialLocation>>;
+    async fn get_registered(
+        &self,
+        product_id: Uuid,
+        serial_numbers: Vec<String>,
+    ) -> RepositoryResult<Vec<String>>;
+    async fn register(
+        &self,
+        movement: &SerialMovement,
+        serial_numbers: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<InventorySerial>>;
+    async fn issue(
+        &self,
+        movement: &SerialMovement,
+        serial_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Option<Vec<InventorySerial>>>;
+    async fn update_status(
+        &self,
+        id: Uuid,
+        from: Vec<&'static str>,
+        to: &'static str,
+    ) -> RepositoryResult<Option<InventorySerial>>;
+}
+
+#[async_trait]
+impl InventorySerialsRepository for PgPool {
+    async fn get_movement(&self, inventory_movement_id: Uuid) -> RepositoryResult<SerialMovement> {
+        Ok(sqlx::query_as::<_, SerialMovement>(
+            r#"
+            SELECT inventory_movements.id,
+                   inventory_movements.inventory_id,
+                   inventory.product_id,
+                   inventory_movements.movement_type,
+                   inventory_movements.quantity,
+                   (SELECT COUNT(*)
+                    FROM inventory_serials
+                    WHERE inventory_serials.receipt_movement_id = inventory_movements.id
+                       OR inventory_serials.issue_movement_id = inventory_movements.id) AS serial_count
+            FROM inventory_movements
+            JOIN inventory ON inventory_movements.inventory_id = inventory.id
+            WHERE inventory_movements.id = $1
+            "#,
+        )
+        .bind(inventory_movement_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_available(&self, inventory_id: Uuid) -> RepositoryResult<Vec<InventorySerial>> {
+        Ok(sqlx::query_as::<_, InventorySerial>(
+            r#"
+            SELECT *
+            FROM inventory_serials
+            WHERE inventory_id = $1
+              AND status = ANY($2)
+            ORDER BY created_at
+            "#,
+        )
+        .bind(inventory_id)
+        .bind([STATUS_IN_STOCK, STATUS_RETURNED])
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn lookup(&self, serial_number: &str) -> RepositoryResult<Vec<InventorySerialLocation>> {
+        Ok(sqlx::query_as::<_, InventorySerialLocation>(
+            r#"
+            SELECT inventory_serials.id,
+                   inventory_serials.serial_number,
+                   inventory_serials.status,
+                   inventory_serials.product_id,
+                   products.name AS product,
+                   inventory_serials.inventory_id,
+                   inventory.warehouse_id,
+                   warehouses.name AS warehouse,
+                   inventory_serials.updated_at
+            FROM inventory_serials
+            JOIN products ON inventory_serials.product_id = products.id
+            JOIN inventory ON inventory_serials.inventory_id = inventory.id
+            JOIN warehouses ON inventory.warehouse_id = warehouses.id
+            WHERE inventory_serials.serial_number = $1
+            ORDER BY products.name, warehouses.name
+            "#,
+        )
+        .bind(serial_number)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_registered(
+        &self,
+        product_id: Uuid,
+        serial_numbers: Vec<String>,
+    ) -> RepositoryResult<Vec<String>> {
+        Ok(sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT serial_number
+            FROM inventory_serials
+            WHERE product_id = $1
+              AND serial_number = ANY($2)
+            ORDER BY serial_number
+            "#,
+        )
+        .bind(product_id)
+        .bind(serial_numbers)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn register(
+        &self,
+        movement: &SerialMovement,
+        serial_numbers: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<InventorySerial>> {
+        Ok(sqlx::query_as::<_, InventorySerial>(
+            r#"
+            INSERT INTO inventory_serials (product_id, inventory_id, serial_number, receipt_movement_id, created_by_id)
+            SELECT $1, $2, serial_number, $3, $4
+            FROM unnest($5::varchar[]) AS serial_number
+            RETURNING *
+            "#,
+        )
+        .bind(movement.product_id)
+        .bind(movement.inventory_id)
+        .bind(movement.id)
+        .bind(sub)
+        .bind(serial_numbers)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn issue(
+        &self,
+        movement: &SerialMovement,
+        serial_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Option<Vec<InventorySerial>>> {
+        let mut tx = self.begin().await?;
+        let serials = sqlx::query_as::<_, InventorySerial>(
+            r#"
+            UPDATE inventory_serials
+            SET status = $1,
+                issue_movement_id = $2
+            WHERE id = ANY($3)
+              AND inventory_id = $4
+              AND status = ANY($5)
+            RETURNING *
+            "#,
+        )
+        .bind(STATUS_SOLD)
+        .bind(movement.id)
+        .bind(&serial_ids)
+        .bind(movement.inventory_id)
+        .bind([STATUS_IN_STOCK, STATUS_RESERVED, STATUS_RETURNED])
+        .fetch_all(&mut *tx)
+        .await?;
+        if serials.len() != serial_ids.len() {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+        tx.commit().await?;
+        Ok(Some(serials))
+    }
+
+    async fn update_status(
+        &self,
+        id: Uuid,
+        from: Vec<&'static str>,
+        to: &'static str,
+    ) -> RepositoryResult<Option<InventorySerial>> {
+        Ok(sqlx::query_as::<_, InventorySerial>(
+            r#"
+            UPDATE inventory_serials
+            SET status = $3
+            WHERE id = $1
+              AND status = ANY($2)
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(self)
+        .await?)
+    }
+}
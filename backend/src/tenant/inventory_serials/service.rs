@@ -0,0 +1,296 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::inventory_serials::InventorySerialsModuleInterface;
+use crate::tenant::inventory_serials::dto::{IssueSerials, RegisterSerials};
+use crate::tenant::inventory_serials::model::{
+    InventorySerial, InventorySerialLocation, STATUS_IN_STOCK, STATUS_RESERVED, STATUS_RETURNED,
+    STATUS_SOLD, SerialMovement,
+};
+use axum::http::StatusCode;
+use serde_json::json;
+use std::collections::HashSet;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum InventorySerialsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for InventorySerialsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => InventorySerialsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<InventorySerialsServiceError> for AppError {
+    fn from(value: InventorySerialsServiceError) -> Self {
+        match value {
+            InventorySerialsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventorySerialsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            InventorySerialsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type InventorySerialsServiceResult<T> = Result<T, InventorySerialsServiceError>;
+
+fn check_capacity(
+    movement: &SerialMovement,
+    movement_type: &str,
+    count: usize,
+) -> InventorySerialsServiceResult<()> {
+    if movement.movement_type != movement_type {
+        return Err(InventorySerialsServiceError::UnprocessableEntry(
+            "A sorozatszámok nem ehhez a mozgás típushoz tartoznak!",
+        ));
+    }
+    if movement.quantity.abs() < movement.serial_count + count as i64 {
+        return Err(InventorySerialsServiceError::UnprocessableEntry(
+            "Több sorozatszám lett megadva, mint a mozgás mennyisége!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait InventorySerialsService {
+    fn get_available(
+        &self,
+        inventory_id: Uuid,
+    ) -> impl Future<Output = InventorySerialsServiceResult<Vec<InventorySerial>>> + Send;
+    fn lookup(
+        &self,
+        serial_number: &str,
+    ) -> impl Future<Output = InventorySerialsServiceResult<Vec<InventorySerialLocation>>> + Send;
+    fn register(
+        &self,
+        payload: &RegisterSerials,
+    ) -> impl Future<Output = InventorySerialsServiceResult<Vec<InventorySerial>>> + Send;
+    fn issue(
+        &self,
+        payload: &IssueSerials,
+    ) -> impl Future<Output = InventorySerialsServiceResult<Vec<InventorySerial>>> + Send;
+    fn reserve(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = InventorySerialsServiceResult<InventorySerial>> + Send;
+    fn release(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = InventorySerialsServiceResult<InventorySerial>> + Send;
+    fn mark_returned(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = InventorySerialsServiceResult<InventorySerial>> + Send;
+    fn update_serial_status(
+        &self,
+        id: Uuid,
+        from: Vec<&'static str>,
+        to: &'static str,
+    ) -> impl Future<Output = InventorySerialsServiceResult<InventorySerial>> + Send;
+}
+
+impl<'a, T> InventorySerialsService for Service<'a, T>
+where
+    T: InventorySerialsModuleInterface,
+{
+    async fn update_serial_status(
+        &self,
+        id: Uuid,
+        from: Vec<&'static str>,
+        to: &'static str,
+    ) -> InventorySerialsServiceResult<InventorySerial> {
+        self.module()
+            .inventory_serials_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventorySerialsServiceError::Unauthorized)?,
+            )?
+            .update_status(id, from, to)
+            .await?
+            .ok_or(InventorySerialsServiceError::UnprocessableEntry(
+                "A sorozatszám jelenlegi állapotában ez a művelet nem végezhető el!",
+            ))
+    }
+
+    async fn get_available(
+        &self,
+        inventory_id: Uuid,
+    ) -> InventorySerialsServiceResult<Vec<InventorySerial>> {
+        Ok(self
+            .module()
+            .inventory_serials_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventorySerialsServiceError::Unauthorized)?,
+            )?
+            .get_available(inventory_id)
+            .await?)
+    }
+
+    async fn lookup(
+        &self,
+        serial_number: &str,
+    ) -> InventorySerialsServiceResult<Vec<InventorySerialLocation>> {
+        let serial_number = serial_number.trim();
+        if serial_number.is_empty() {
+            return Err(InventorySerialsServiceError::UnprocessableEntry(
+                "A sorozatszám megadása kötelező!",
+            ));
+        }
+        Ok(self
+            .module()
+            .inventory_serials_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(InventorySerialsServiceError::Unauthorized)?,
+            )?
+            .lookup(serial_number)
+            .await?)
+    }
+
+    async fn register(
+        &self,
+        payload: &RegisterSerials,
+    ) -> InventorySerialsServiceResult<Vec<InventorySerial>> {
+        let serial_numbers: Vec<String> = payload
+            .serial_numbers
+            .iter()
+            .map(|serial_number| serial_number.trim().to_string())
+            .collect();
+        if serial_numbers.is_empty()
+            || serial_numbers
+                .iter()
+                .any(|serial_number| serial_number.is_empty() || serial_number.len() > 100)
+        {
+            return Err(InventorySerialsServiceError::UnprocessableEntry(
+                "A sorozatszámok nem lehetnek üresek és legfeljebb 100 karakteresek lehetnek!",
+            ));
+        }
+        let mut unique = HashSet::new();
+        if !serial_numbers
+            .iter()
+            .all(|serial_number| unique.insert(serial_number))
+        {
+            return Err(InventorySerialsServiceError::UnprocessableEntry(
+                "Egy sorozatszám csak egyszer szerepelhet!",
+            ));
+        }
+        let repo = self.module().inventory_serials_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventorySerialsServiceError::Unauthorized)?,
+        )?;
+        let movement = repo.get_movement(payload.inventory_movement_id).await?;
+        check_capacity(&movement, "in", serial_numbers.len())?;
+        if !repo
+            .get_registered(movement.product_id, serial_numbers.clone())
+            .await?
+            .is_empty()
+        {
+            return Err(InventorySerialsServiceError::UnprocessableEntry(
+                "A megadott sorozatszám már rögzítve van ennél a terméknél!",
+            ));
+        }
+        Ok(repo
+            .register(&movement, serial_numbers, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn issue(
+        &self,
+        payload: &IssueSerials,
+    ) -> InventorySerialsServiceResult<Vec<InventorySerial>> {
+        let mut unique = HashSet::new();
+        if payload.serial_ids.is_empty() || !payload.serial_ids.iter().all(|id| unique.insert(*id))
+        {
+            return Err(InventorySerialsServiceError::UnprocessableEntry(
+                "Legalább egy, egyedi sorozatszám kiválasztása kötelező!",
+            ));
+        }
+        let repo = self.module().inventory_serials_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(InventorySerialsServiceError::Unauthorized)?,
+        )?;
+        let movement = repo.get_movement(payload.inventory_movement_id).await?;
+        check_capacity(&movement, "out", payload.serial_ids.len())?;
+        repo.issue(&movement, payload.serial_ids.clone())
+            .await?
+            .ok_or(InventorySerialsServiceError::UnprocessableEntry(
+                "A kiválasztott sorozatszámok közül nem mindegyik adható ki ebből a készletből!",
+            ))
+    }
+
+    async fn reserve(&self, id: Uuid) -> InventorySerialsServiceResult<InventorySerial> {
+        self.update_serial_status(id, vec![STATUS_IN_STOCK, STATUS_RETURNED], STATUS_RESERVED)
+            .await
+    }
+
+    async fn release(&self, id: Uuid) -> InventorySerialsServiceResult<InventorySerial> {
+        self.update_serial_status(id, vec![STATUS_RESERVED], STATUS_IN_STOCK)
+            .await
+    }
+
+    async fn mark_returned(&self, id: Uuid) -> InventorySerialsServiceResult<InventorySerial> {
+        self.update_serial_status(id, vec![STATUS_SOLD], STATUS_RETURNED)
+            .await
+    }
+}
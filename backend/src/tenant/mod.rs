@@ -20,20 +20,64 @@
 use clap::ValueEnum;
 use std::fmt::Display;
 
+pub mod accounting_exports;
 pub mod activity_feed;
 pub mod address;
+pub mod attachments;
+pub mod categories;
 pub mod comments;
+pub mod credit_notes;
 pub mod currencies;
+pub mod customer_contacts;
+pub mod customer_notes;
+pub mod customer_portal;
+pub mod customer_segments;
 pub mod customers;
+pub mod document_settings;
+pub mod dunning;
+pub mod exchange_rates;
+pub mod goods_receipts;
 pub mod inventory;
+pub mod inventory_adjustments;
+pub mod inventory_costing;
 pub mod inventory_movements;
 pub mod inventory_reservations;
+pub mod inventory_serials;
+pub mod nav_reporting;
+pub mod package_types;
+pub mod payment_links;
+pub mod payments;
+pub mod permissions;
+pub mod picking_lists;
 pub mod products;
+pub mod project_members;
+pub mod project_schedules;
+pub mod projects;
+pub mod purchase_invoices;
+pub mod purchase_orders;
+pub mod quotes;
+pub mod receivables;
+pub mod recurring_invoices;
 pub mod services;
+pub mod shipments;
+pub mod stock_snapshots;
+pub mod stock_transfers;
+pub mod stocktakes;
+pub mod supplier_portal;
+pub mod suppliers;
+pub mod tags;
+pub mod task_assignments;
+pub mod task_checklist_items;
+pub mod task_comments;
+pub mod task_recurrences;
 pub mod tasks;
 pub mod taxes;
+pub mod time_entries;
 pub mod users;
+pub mod vat_reports;
 pub mod warehouses;
+pub mod watches;
+pub mod worksheet_templates;
 pub mod worksheets;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -0,0 +1,460 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::config::NavConfig;
+use crate::common::crypto::{CryptoError, decrypt_secret};
+use crate::tenant::nav_reporting::model::{NavMessage, NavProcessingResult, NavSettings};
+use aes::Aes128;
+use aes::cipher::{BlockDecrypt, KeyInit, generic_array::GenericArray};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use quick_xml::Reader;
+use quick_xml::escape::{escape, unescape};
+use quick_xml::events::Event;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use sha2::Sha512;
+use sha3::{Digest, Sha3_512};
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+pub const TEST_API_URL: &str = "https://api-test.onlineszamla.nav.gov.hu/invoiceService/v3";
+pub const PRODUCTION_API_URL: &str = "https://api.onlineszamla.nav.gov.hu/invoiceService/v3";
+
+const API_NAMESPACE: &str = "http://schemas.nav.gov.hu/OSA/3.0/api";
+const COMMON_NAMESPACE: &str = "http://schemas.nav.gov.hu/NTCA/1.0/common";
+
+#[derive(Debug, Error)]
+pub enum NavError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("A NAV elutasította a kérést: {0}")]
+    Rejected(String),
+
+    #[error("Értelmezhetetlen NAV válasz: {0}")]
+    InvalidResponse(String),
+
+    #[error("Hibás NAV beállítások: {0}")]
+    Configuration(&'static str),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NavClient: Send + Sync {
+    async fn manage_invoice(&self, operation: &str, invoice_xml: &str) -> Result<String, NavError>;
+    async fn query_transaction_status(
+        &self,
+        transaction_id: &str,
+    ) -> Result<NavProcessingResult, NavError>;
+}
+
+fn hash_upper<D: Digest>(value: &str) -> String {
+    D::digest(value.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect()
+}
+
+pub fn password_hash(password: &str) -> String {
+    hash_upper::<Sha512>(password)
+}
+
+fn request_signature(
+    request_id: &str,
+    timestamp: DateTime<Utc>,
+    signature_key: &str,
+    operations: &[(&str, &str)],
+) -> String {
+    let mut value = format!(
+        "{request_id}{}{signature_key}",
+        timestamp.format("%Y%m%d%H%M%S")
+    );
+    for (operation, invoice_data) in operations {
+        value.push_str(&hash_upper::<Sha3_512>(&format!(
+            "{operation}{invoice_data}"
+        )));
+    }
+    hash_upper::<Sha3_512>(&value)
+}
+
+// NOTE: the exchange token is AES-128-ECB encrypted with the exchange key of the technical user
+fn decrypt_exchange_token(encoded: &str, exchange_key: &str) -> Result<String, NavError> {
+    let cipher = Aes128::new_from_slice(exchange_key.as_bytes())
+        .map_err(|_| NavError::Configuration("a cserekulcs hossza nem megfelelő"))?;
+    let mut data = STANDARD
+        .decode(encoded)
+        .map_err(|e| NavError::InvalidResponse(e.to_string()))?;
+    if data.is_empty() || data.len() % 16 != 0 {
+        return Err(NavError::InvalidResponse(
+            "invalid exchange token".to_string(),
+        ));
+    }
+    for block in data.chunks_exact_mut(16) {
+        cipher.decrypt_block(GenericArray::from_mut_slice(block));
+    }
+    let padding = usize::from(data[data.len() - 1]);
+    if padding == 0
+        || padding > 16
+        || data[data.len() - padding..]
+            .iter()
+            .any(|b| usize::from(*b) != padding)
+    {
+        return Err(NavError::InvalidResponse(
+            "invalid exchange token padding".to_string(),
+        ));
+    }
+    data.truncate(data.len() - padding);
+    String::from_utf8(data).map_err(|e| NavError::InvalidResponse(e.to_string()))
+}
+
+// NOTE: leaf elements of the response in document order, namespaces are dropped
+fn xml_fields(xml: &str) -> Result<Vec<(String, String)>, NavError> {
+    let invalid = |e: &dyn std::fmt::Display| NavError::InvalidResponse(e.to_string());
+    let mut reader = Reader::from_str(xml);
+    let mut fields = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    loop {
+        match reader.read_event().map_err(|e| invalid(&e))? {
+            Event::Start(e) => {
+                current = Some((
+                    String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+                    String::new(),
+                ))
+            }
+            Event::Empty(e) => fields.push((
+                String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+                String::new(),
+            )),
+            Event::Text(e) => {
+                if let Some((_, raw)) = current.as_mut() {
+                    raw.push_str(&e.decode().map_err(|e| invalid(&e))?);
+                }
+            }
+            Event::GeneralRef(e) => {
+                if let Some((_, raw)) = current.as_mut() {
+                    raw.push_str(&format!("&{};", e.decode().map_err(|e| invalid(&e))?));
+                }
+            }
+            Event::End(_) => {
+                if let Some((name, raw)) = current.take() {
+                    let value = unescape(&raw).map_err(|e| invalid(&e))?;
+                    fields.push((name, value.trim().to_string()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if fields.is_empty() {
+        return Err(NavError::InvalidResponse("empty response".to_string()));
+    }
+    Ok(fields)
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> Result<&'a str, NavError> {
+    fields
+        .iter()
+        .find(|(field_name, _)| field_name == name)
+        .map(|(_, value)| value.as_str())
+        .ok_or_else(|| NavError::InvalidResponse(format!("missing {name}")))
+}
+
+fn check_result(fields: &[(String, String)]) -> Result<(), NavError> {
+    let func_code = field(fields, "funcCode")?;
+    if func_code == "OK" {
+        return Ok(());
+    }
+    Err(NavError::Rejected(format!(
+        "{}: {}",
+        field(fields, "errorCode").unwrap_or(func_code),
+        field(fields, "message").unwrap_or_default()
+    )))
+}
+
+fn processing_result_from_fields(
+    fields: &[(String, String)],
+) -> Result<NavProcessingResult, NavError> {
+    let invoice_status = field(fields, "invoiceStatus")?.to_string();
+    let mut messages: Vec<NavMessage> = Vec::new();
+    for (name, value) in fields
+        .iter()
+        .skip_while(|(name, _)| name != "invoiceStatus")
+    {
+        match name.as_str() {
+            "validationResultCode" => messages.push(NavMessage {
+                result_code: value.clone(),
+                error_code: None,
+                message: String::new(),
+            }),
+            "validationErrorCode" => {
+                if let Some(message) = messages.last_mut() {
+                    message.error_code = Some(value.clone());
+                }
+            }
+            "message" => {
+                if let Some(message) = messages.last_mut() {
+                    message.message = value.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(NavProcessingResult {
+        invoice_status,
+        messages,
+    })
+}
+
+// NOTE: Online Számla 3.0 XML API, see the "Online Számla interfész specifikáció" of NAV
+pub struct OnlineSzamlaClient {
+    api_url: &'static str,
+    login: String,
+    password_hash: String,
+    taxpayer_id: String,
+    signature_key: String,
+    exchange_key: String,
+    software_id: String,
+    software_dev_name: String,
+    software_dev_contact: String,
+    http: reqwest::Client,
+}
+
+impl OnlineSzamlaClient {
+    pub fn new(settings: &NavSettings, config: &NavConfig) -> Result<Self, NavError> {
+        Ok(Self {
+            api_url: match settings.environment.as_str() {
+                "production" => PRODUCTION_API_URL,
+                _ => TEST_API_URL,
+            },
+            login: settings.login.clone(),
+            password_hash: settings.password_hash.clone(),
+            taxpayer_id: settings.tax_number.chars().take(8).collect(),
+            signature_key: decrypt_secret(&settings.signature_key)?,
+            exchange_key: decrypt_secret(&settings.exchange_key)?,
+            software_id: config.software_id().to_string(),
+            software_dev_name: config.software_dev_name().to_string(),
+            software_dev_contact: config.software_dev_contact().to_string(),
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(config.request_timeout_secs()))
+                .build()?,
+        })
+    }
+
+    fn request(&self, root: &str, operations: &[(&str, &str)], body: &str) -> String {
+        let request_id = format!("OBV{}", &Uuid::new_v4().simple().to_string()[..27]);
+        let timestamp = Utc::now();
+        let signature = request_signature(&request_id, timestamp, &self.signature_key, operations);
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<{root} xmlns="{API_NAMESPACE}" xmlns:common="{COMMON_NAMESPACE}">
+<common:header><common:requestId>{request_id}</common:requestId><common:timestamp>{}</common:timestamp><common:requestVersion>3.0</common:requestVersion><common:headerVersion>1.0</common:headerVersion></common:header>
+<common:user><common:login>{}</common:login><common:passwordHash cryptoType="SHA-512">{}</common:passwordHash><common:taxNumber>{}</common:taxNumber><common:requestSignature cryptoType="SHA3-512">{signature}</common:requestSignature></common:user>
+<software><softwareId>{}</softwareId><softwareName>Obvia ERP</softwareName><softwareOperation>ONLINE_SERVICE</softwareOperation><softwareMainVersion>{}</softwareMainVersion><softwareDevName>{}</softwareDevName><softwareDevContact>{}</softwareDevContact><softwareDevCountryCode>HU</softwareDevCountryCode></software>
+{body}
+</{root}>"#,
+            timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            escape(self.login.as_str()),
+            self.password_hash,
+            self.taxpayer_id,
+            escape(self.software_id.as_str()),
+            env!("CARGO_PKG_VERSION"),
+            escape(self.software_dev_name.as_str()),
+            escape(self.software_dev_contact.as_str()),
+        )
+    }
+
+    async fn call(
+        &self,
+        operation: &str,
+        request: String,
+    ) -> Result<Vec<(String, String)>, NavError> {
+        let response = self
+            .http
+            .post(format!("{}/{operation}", self.api_url))
+            .header(CONTENT_TYPE, "application/xml")
+            .header(ACCEPT, "application/xml")
+            .body(request)
+            .send()
+            .await?;
+        // NOTE: rejected requests come with a 4xx status and a GeneralErrorResponse body
+        let response = if response.status().is_server_error() {
+            response.error_for_status()?
+        } else {
+            response
+        };
+        let fields = xml_fields(&response.text().await?)?;
+        check_result(&fields)?;
+        Ok(fields)
+    }
+
+    async fn token_exchange(&self) -> Result<String, NavError> {
+        let fields = self
+            .call(
+                "tokenExchange",
+                self.request("TokenExchangeRequest", &[], ""),
+            )
+            .await?;
+        decrypt_exchange_token(field(&fields, "encodedExchangeToken")?, &self.exchange_key)
+    }
+}
+
+#[async_trait]
+impl NavClient for OnlineSzamlaClient {
+    async fn manage_invoice(&self, operation: &str, invoice_xml: &str) -> Result<String, NavError> {
+        let exchange_token = self.token_exchange().await?;
+        let invoice_data = STANDARD.encode(invoice_xml.as_bytes());
+        let body = format!(
+            "<exchangeToken>{}</exchangeToken><invoiceOperations><compressedContent>false</compressedContent><invoiceOperation><index>1</index><invoiceOperation>{}</invoiceOperation><invoiceData>{invoice_data}</invoiceData></invoiceOperation></invoiceOperations>",
+            escape(exchange_token.as_str()),
+            escape(operation),
+        );
+        let fields = self
+            .call(
+                "manageInvoice",
+                self.request("ManageInvoiceRequest", &[(operation, &invoice_data)], &body),
+            )
+            .await?;
+        Ok(field(&fields, "transactionId")?.to_string())
+    }
+
+    async fn query_transaction_status(
+        &self,
+        transaction_id: &str,
+    ) -> Result<NavProcessingResult, NavError> {
+        let body = format!(
+            "<transactionId>{}</transactionId><returnOriginalRequest>false</returnOriginalRequest>",
+            escape(transaction_id)
+        );
+        let fields = self
+            .call(
+                "queryTransactionStatus",
+                self.request("QueryTransactionStatusRequest", &[], &body),
+            )
+            .await?;
+        processing_result_from_fields(&fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::BlockEncrypt;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_request_signature() {
+        assert_eq!(
+            hash_upper::<Sha3_512>("abc"),
+            "B751850B1A57168A5693CD924B6B096E08F621827444F70D884F5D0240D2712E10E116E9192AF3C91A7EC57647E3934057340B4CF408D5A56592F8274EEC53F0"
+        );
+        let timestamp = Utc.with_ymd_and_hms(2026, 10, 16, 8, 5, 9).unwrap();
+        assert_eq!(
+            request_signature("RID1", timestamp, "key", &[]),
+            hash_upper::<Sha3_512>("RID120261016080509key")
+        );
+        assert_eq!(
+            request_signature("RID1", timestamp, "key", &[("CREATE", "ZGF0YQ==")]),
+            hash_upper::<Sha3_512>(&format!(
+                "RID120261016080509key{}",
+                hash_upper::<Sha3_512>("CREATEZGF0YQ==")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decrypt_exchange_token() {
+        let key = "0123456789abcdef";
+        let cipher = Aes128::new_from_slice(key.as_bytes()).unwrap();
+        let mut data = b"token-0123".to_vec();
+        data.extend([6u8; 6]);
+        for block in data.chunks_exact_mut(16) {
+            cipher.encrypt_block(GenericArray::from_mut_slice(block));
+        }
+        let encoded = STANDARD.encode(&data);
+
+        assert_eq!(decrypt_exchange_token(&encoded, key).unwrap(), "token-0123");
+        assert!(matches!(
+            decrypt_exchange_token(&encoded, "short"),
+            Err(NavError::Configuration(_))
+        ));
+        assert!(decrypt_exchange_token(&encoded, "fedcba9876543210").is_err());
+    }
+
+    #[test]
+    fn test_error_response_is_rejected() {
+        let fields = xml_fields(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<GeneralErrorResponse xmlns="http://schemas.nav.gov.hu/OSA/3.0/api" xmlns:common="http://schemas.nav.gov.hu/NTCA/1.0/common">
+  <common:header><common:requestId>RID1</common:requestId></common:header>
+  <common:result>
+    <common:funcCode>ERROR</common:funcCode>
+    <common:errorCode>INVALID_SECURITY_USER</common:errorCode>
+    <common:message>Helytelen authentikációs adatok &amp; jogosultság</common:message>
+  </common:result>
+</GeneralErrorResponse>"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            check_result(&fields),
+            Err(NavError::Rejected(message))
+                if message == "INVALID_SECURITY_USER: Helytelen authentikációs adatok & jogosultság"
+        ));
+    }
+
+    #[test]
+    fn test_processing_result_from_fields() {
+        let fields = xml_fields(
+            r#"<QueryTransactionStatusResponse xmlns="http://schemas.nav.gov.hu/OSA/3.0/api" xmlns:common="http://schemas.nav.gov.hu/NTCA/1.0/common">
+  <common:result><common:funcCode>OK</common:funcCode></common:result>
+  <processingResults>
+    <processingResult>
+      <index>1</index>
+      <invoiceStatus>DONE</invoiceStatus>
+      <businessValidationMessages>
+        <validationResultCode>WARN</validationResultCode>
+        <validationErrorCode>INCORRECT_COUNTY_CODE</validationErrorCode>
+        <message>A megyekód nem megfelelő</message>
+      </businessValidationMessages>
+      <compressedContentIndicator>false</compressedContentIndicator>
+    </processingResult>
+  </processingResults>
+</QueryTransactionStatusResponse>"#,
+        )
+        .unwrap();
+        assert!(check_result(&fields).is_ok());
+        let result = processing_result_from_fields(&fields).unwrap();
+        assert_eq!(result.invoice_status, "DONE");
+        assert_eq!(
+            result.messages,
+            vec![NavMessage {
+                result_code: "WARN".to_string(),
+                error_code: Some("INCORRECT_COUNTY_CODE".to_string()),
+                message: "A megyekód nem megfelelő".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct NavSettingsInput {
+    pub environment: String,
+    pub login: String,
+    pub password: Option<String>,
+    pub signature_key: Option<String>,
+    pub exchange_key: Option<String>,
+    pub tax_number: String,
+    pub company_name: String,
+    pub country_code: String,
+    pub postal_code: String,
+    pub city: String,
+    pub street_address: String,
+}
+
+// NOTE: as stored, the password is only kept as the SHA-512 hash required by the API
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavCredentials {
+    pub password_hash: String,
+    pub signature_key: String,
+    pub exchange_key: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct NavCustomerInput {
+    pub customer_vat_status: String,
+    pub customer_tax_number: Option<String>,
+    pub customer_country_code: Option<String>,
+    pub customer_postal_code: Option<String>,
+    pub customer_city: Option<String>,
+    pub customer_street_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SubmitInvoice {
+    pub receivable_id: Uuid,
+    #[serde(flatten)]
+    pub customer: NavCustomerInput,
+    pub exchange_rate: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SubmitCreditNote {
+    pub credit_note_id: Uuid,
+    #[serde(flatten)]
+    pub customer: NavCustomerInput,
+    pub exchange_rate: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavAddress {
+    pub country_code: String,
+    pub postal_code: String,
+    pub city: String,
+    pub street_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavCustomer {
+    pub vat_status: String,
+    pub tax_number: Option<String>,
+    pub name: Option<String>,
+    pub address: Option<NavAddress>,
+}
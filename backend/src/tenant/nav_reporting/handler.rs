@@ -0,0 +1,809 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::nav_reporting::NavReportingModuleInterface;
+use crate::tenant::nav_reporting::dto::{NavSettingsInput, SubmitCreditNote, SubmitInvoice};
+use crate::tenant::nav_reporting::service::NavReportingService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn submit<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SubmitInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result =
+        map_handler_err(service.submit(&payload).await, nav_reporting_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn submit_credit_note<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SubmitCreditNote>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result = map_handler_err(
+        service.submit_credit_note(&payload).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn refresh_status<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result = map_handler_err(
+        service.refresh_status(payload.uuid).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn retry<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result = map_handler_err(
+        service.retry(payload.uuid).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn get_settings<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result =
+        map_handler_err(service.get_settings().await, nav_reporting_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_settings<M: NavReportingModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(nav_reporting_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<NavSettingsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), nav_reporting_module.clone());
+    let result = map_handler_err(
+        service.save_settings(&payload).await,
+        nav_reporting_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        nav_reporting_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::exchange_rates::model::ExchangeRate;
+    use crate::tenant::exchange_rates::repository::MockExchangeRatesRepository;
+    use crate::tenant::nav_reporting::client::{MockNavClient, NavError};
+    use crate::tenant::nav_reporting::model::{
+        NavInvoiceHeader, NavInvoiceLine, NavMessage, NavModificationReference,
+        NavProcessingResult, NavSettings, NavSubmission,
+    };
+    use crate::tenant::nav_reporting::{
+        self, repository::MockNavReportingRepository, tests::MockNavReportingModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use sqlx::types::Json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockNavReportingRepository,
+        client: Option<MockNavClient>,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let mut nav_reporting_module = MockNavReportingModule::new();
+        nav_reporting_module
+            .expect_nav_reporting_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        match client {
+            Some(client) => {
+                let client = Arc::new(client);
+                nav_reporting_module
+                    .expect_nav_client()
+                    .times(1)
+                    .returning(move |_| Ok(client.clone()));
+            }
+            None => {
+                nav_reporting_module.expect_nav_client().never();
+            }
+        }
+        nav_reporting_module
+            .expect_config()
+            .times(config_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(nav_reporting::routes::routes(Arc::new(
+                nav_reporting_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn settings() -> NavSettings {
+        NavSettings {
+            environment: "test".to_string(),
+            login: "techuser".to_string(),
+            password_hash: "HASH".to_string(),
+            signature_key: "signature-key".to_string(),
+            exchange_key: "0123456789abcdef".to_string(),
+            tax_number: "12345678-2-41".to_string(),
+            company_name: "Obvia Kft.".to_string(),
+            country_code: "HU".to_string(),
+            postal_code: "1111".to_string(),
+            city: "Budapest".to_string(),
+            street_address: "Fő utca 1.".to_string(),
+            updated_by_id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn header() -> NavInvoiceHeader {
+        NavInvoiceHeader {
+            receivable_id: Uuid::new_v4(),
+            document_number: "SZ-2026-0001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 24).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(12700),
+            customer_name: "Teszt Ügyfél Kft.".to_string(),
+        }
+    }
+
+    fn line() -> NavInvoiceLine {
+        NavInvoiceLine {
+            item: "Karbantartás".to_string(),
+            description: "Karbantartás".to_string(),
+            tax_rate: BigDecimal::from(27),
+            is_rate_applicable: true,
+            reporting_code: None,
+            tax_description: "27%".to_string(),
+            legal_text: None,
+            vat_treatment: "domestic".to_string(),
+            net_amount: BigDecimal::from(10000),
+            tax_amount: BigDecimal::from(2700),
+            gross_amount: BigDecimal::from(12700),
+        }
+    }
+
+    fn submission(receivable_id: Uuid, status: &str) -> NavSubmission {
+        NavSubmission {
+            id: Uuid::new_v4(),
+            receivable_id,
+            credit_note_id: None,
+            operation: "CREATE".to_string(),
+            invoice_xml: "<InvoiceData/>".to_string(),
+            status: status.to_string(),
+            transaction_id: None,
+            nav_status: None,
+            messages: Json(Vec::new()),
+            attempts: 0,
+            next_attempt_at: Utc::now(),
+            last_error: None,
+            submitted_at: None,
+            completed_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn submit_payload(receivable_id: Uuid) -> serde_json::Value {
+        json!({
+            "receivable_id": receivable_id,
+            "customer_vat_status": "DOMESTIC",
+            "customer_tax_number": "87654321-2-13",
+            "customer_country_code": null,
+            "customer_postal_code": "2000",
+            "customer_city": "Szentendre",
+            "customer_street_address": "Kossuth u. 2.",
+            "exchange_rate": null
+        })
+    }
+
+    fn submission_repo(header: &NavInvoiceHeader) -> MockNavReportingRepository {
+        let mut repo = MockNavReportingRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings())));
+        repo.expect_get_invoice_header()
+            .times(1)
+            .with(eq(header.receivable_id))
+            .returning({
+                let header = header.clone();
+                move |_| Ok(header.clone())
+            });
+        repo.expect_get_invoice_lines()
+            .times(1)
+            .returning(|_| Ok(vec![line()]));
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_requires_secrets_on_first_setup() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockNavReportingRepository::new();
+        repo.expect_get_settings().times(1).returning(|| Ok(None));
+        repo.expect_save_settings().never();
+
+        let response = app(repo, None, active_tenant_id, 1)
+            .oneshot(request(
+                "PUT",
+                "/api/nav_reporting/settings/update",
+                active_tenant_id,
+                Some(json!({
+                    "environment": "test",
+                    "login": "techuser",
+                    "password": null,
+                    "signature_key": "signature-key",
+                    "exchange_key": "0123456789abcdef",
+                    "tax_number": "12345678-2-41",
+                    "company_name": "Obvia Kft.",
+                    "country_code": "hu",
+                    "postal_code": "1111",
+                    "city": "Budapest",
+                    "street_address": "Fő utca 1."
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_submit_sends_invoice_and_stores_transaction_id() {
+        let active_tenant_id = Uuid::new_v4();
+        let header = header();
+        let queued = submission(header.receivable_id, "queued");
+        let submitted = NavSubmission {
+            status: "submitted".to_string(),
+            transaction_id: Some("4M1A9XHB1KJ6YBQ2".to_string()),
+            attempts: 1,
+            submitted_at: Some(Utc::now()),
+            ..queued.clone()
+        };
+        let mut repo = submission_repo(&header);
+        repo.expect_insert_submission()
+            .times(1)
+            .withf(|_, credit_note_id, operation, xml, _| {
+                credit_note_id.is_none()
+                    && operation == "CREATE"
+                    && xml.contains("<customerVatStatus>DOMESTIC</customerVatStatus>")
+                    && xml.contains("<invoiceGrossAmount>12700.00</invoiceGrossAmount>")
+            })
+            .returning({
+                let queued = queued.clone();
+                move |_, _, _, _, _| Ok(queued.clone())
+            });
+        repo.expect_mark_submitted()
+            .times(1)
+            .with(eq(queued.id), eq("4M1A9XHB1KJ6YBQ2"))
+            .returning({
+                let submitted = submitted.clone();
+                move |_, _| Ok(submitted.clone())
+            });
+        let mut client = MockNavClient::new();
+        client
+            .expect_manage_invoice()
+            .times(1)
+            .with(eq("CREATE"), eq("<InvoiceData/>"))
+            .returning(|_, _| Ok("4M1A9XHB1KJ6YBQ2".to_string()));
+
+        let response = app(repo, Some(client), active_tenant_id, 1)
+            .oneshot(request(
+                "POST",
+                "/api/nav_reporting/submit",
+                active_tenant_id,
+                Some(submit_payload(header.receivable_id)),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": submitted})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_uses_stored_rate_for_foreign_currency_invoice() {
+        let active_tenant_id = Uuid::new_v4();
+        let header = NavInvoiceHeader {
+            currency_code: "EUR".to_string(),
+            ..header()
+        };
+        let queued = submission(header.receivable_id, "queued");
+        let mut repo = submission_repo(&header);
+        repo.expect_insert_submission()
+            .times(1)
+            .withf(|_, _, _, xml, _| {
+                xml.contains("<currencyCode>EUR</currencyCode>")
+                    && xml.contains("<exchangeRate>389.5</exchangeRate>")
+            })
+            .returning({
+                let queued = queued.clone();
+                move |_, _, _, _, _| Ok(queued.clone())
+            });
+        repo.expect_mark_submitted()
+            .times(1)
+            .returning(move |_, _| Ok(queued.clone()));
+        let mut exchange_rates_repo = MockExchangeRatesRepository::new();
+        exchange_rates_repo
+            .expect_get_rate_as_of()
+            .withf(|currency_code, date| {
+                currency_code == "EUR" && *date == NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(Some(ExchangeRate {
+                    id: Uuid::new_v4(),
+                    currency_code: "EUR".to_string(),
+                    rate_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+                    rate: BigDecimal::from_str("389.5").unwrap(),
+                    source: "mnb".to_string(),
+                    created_by_id: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }))
+            });
+        let exchange_rates_repo = Arc::new(exchange_rates_repo);
+        let repo = Arc::new(repo);
+        let mut client = MockNavClient::new();
+        client
+            .expect_manage_invoice()
+            .times(1)
+            .returning(|_, _| Ok("4M1A9XHB1KJ6YBQ2".to_string()));
+        let client = Arc::new(client);
+        let mut nav_reporting_module = MockNavReportingModule::new();
+        nav_reporting_module
+            .expect_nav_reporting_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        nav_reporting_module
+            .expect_exchange_rates_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(exchange_rates_repo.clone()));
+        nav_reporting_module
+            .expect_nav_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+        nav_reporting_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(nav_reporting::routes::routes(Arc::new(
+                    nav_reporting_module,
+                ))),
+            )
+            .oneshot(request(
+                "POST",
+                "/api/nav_reporting/submit",
+                active_tenant_id,
+                Some(submit_payload(header.receivable_id)),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_submit_keeps_submission_queued_when_nav_is_unreachable() {
+        let active_tenant_id = Uuid::new_v4();
+        let header = header();
+        let queued = submission(header.receivable_id, "queued");
+        let mut repo = submission_repo(&header);
+        repo.expect_insert_submission().times(1).returning({
+            let queued = queued.clone();
+            move |_, _, _, _, _| Ok(queued.clone())
+        });
+        repo.expect_mark_submitted().never();
+        repo.expect_record_failure()
+            .times(1)
+            .withf(|_, status, next_attempt_at, error| {
+                status == "queued"
+                    && *next_attempt_at > Utc::now()
+                    && error.contains("INVALID_REQUEST_SIGNATURE")
+            })
+            .returning({
+                let queued = queued.clone();
+                move |_, _, _, error| {
+                    Ok(NavSubmission {
+                        attempts: 1,
+                        last_error: Some(error.to_string()),
+                        ..queued.clone()
+                    })
+                }
+            });
+        let mut client = MockNavClient::new();
+        client.expect_manage_invoice().times(1).returning(|_, _| {
+            Err(NavError::Rejected(
+                "INVALID_REQUEST_SIGNATURE: Helytelen kérés aláírás".to_string(),
+            ))
+        });
+
+        let response = app(repo, Some(client), active_tenant_id, 2)
+            .oneshot(request(
+                "POST",
+                "/api/nav_reporting/submit",
+                active_tenant_id,
+                Some(submit_payload(header.receivable_id)),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["status"], json!("queued"));
+        assert_eq!(body["data"]["attempts"], json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_domestic_customer_without_tax_number() {
+        let active_tenant_id = Uuid::new_v4();
+        let header = header();
+        let mut repo = MockNavReportingRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings())));
+        repo.expect_get_invoice_header().times(1).returning({
+            let header = header.clone();
+            move |_| Ok(header.clone())
+        });
+        repo.expect_insert_submission().never();
+        let mut payload = submit_payload(header.receivable_id);
+        payload["customer_tax_number"] = json!("8765432");
+
+        let response = app(repo, None, active_tenant_id, 1)
+            .oneshot(request(
+                "POST",
+                "/api/nav_reporting/submit",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_submit_credit_note_sends_modification_of_original_invoice() {
+        let active_tenant_id = Uuid::new_v4();
+        let credit_note_id = Uuid::new_v4();
+        let header = NavInvoiceHeader {
+            document_number: "HSZ-2026-00001".to_string(),
+            amount: BigDecimal::from(-12700),
+            ..header()
+        };
+        let original_receivable_id = header.receivable_id;
+        let queued = NavSubmission {
+            credit_note_id: Some(credit_note_id),
+            operation: "MODIFY".to_string(),
+            ..submission(header.receivable_id, "queued")
+        };
+        let mut repo = MockNavReportingRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings())));
+        repo.expect_get_credit_note_header()
+            .times(1)
+            .with(eq(credit_note_id))
+            .returning({
+                let header = header.clone();
+                move |_| Ok(header.clone())
+            });
+        repo.expect_get_modification_reference()
+            .times(1)
+            .returning(|_| {
+                Ok(NavModificationReference {
+                    original_invoice_number: "SZ-2026-0001".to_string(),
+                    modification_index: 1,
+                    original_line_count: 1,
+                })
+            });
+        repo.expect_get_credit_note_lines().times(1).returning(|_| {
+            Ok(vec![NavInvoiceLine {
+                net_amount: BigDecimal::from(-10000),
+                tax_amount: BigDecimal::from(-2700),
+                gross_amount: BigDecimal::from(-12700),
+                ..line()
+            }])
+        });
+        repo.expect_insert_submission()
+            .times(1)
+            .withf(move |receivable_id, id, operation, xml, _| {
+                *receivable_id == original_receivable_id
+                    && *id == Some(credit_note_id)
+                    && operation == "MODIFY"
+                    && xml.contains("<originalInvoiceNumber>SZ-2026-0001</originalInvoiceNumber>")
+                    && xml.contains("<lineNumberReference>2</lineNumberReference>")
+            })
+            .returning({
+                let queued = queued.clone();
+                move |_, _, _, _, _| Ok(queued.clone())
+            });
+        repo.expect_mark_submitted().times(1).returning({
+            let queued = queued.clone();
+            move |_, transaction_id| {
+                Ok(NavSubmission {
+                    status: "submitted".to_string(),
+                    transaction_id: Some(transaction_id.to_string()),
+                    ..queued.clone()
+                })
+            }
+        });
+        let mut client = MockNavClient::new();
+        client
+            .expect_manage_invoice()
+            .times(1)
+            .with(eq("MODIFY"), eq("<InvoiceData/>"))
+            .returning(|_, _| Ok("4M1A9XHB1KJ6YBQ3".to_string()));
+        let mut payload = submit_payload(header.receivable_id);
+        payload["credit_note_id"] = json!(credit_note_id);
+        payload.as_object_mut().unwrap().remove("receivable_id");
+
+        let response = app(repo, Some(client), active_tenant_id, 1)
+            .oneshot(request(
+                "POST",
+                "/api/nav_reporting/submit_credit_note",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["operation"], json!("MODIFY"));
+        assert_eq!(body["data"]["credit_note_id"], json!(credit_note_id));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_status_records_aborted_result() {
+        let active_tenant_id = Uuid::new_v4();
+        let submitted = NavSubmission {
+            transaction_id: Some("4M1A9XHB1KJ6YBQ2".to_string()),
+            ..submission(Uuid::new_v4(), "submitted")
+        };
+        let result = NavProcessingResult {
+            invoice_status: "ABORTED".to_string(),
+            messages: vec![NavMessage {
+                result_code: "ERROR".to_string(),
+                error_code: Some("INVOICE_NUMBER_NOT_UNIQUE".to_string()),
+                message: "A számlaszám már szerepel a rendszerben".to_string(),
+            }],
+        };
+        let mut repo = MockNavReportingRepository::new();
+        repo.expect_get_submission()
+            .times(1)
+            .with(eq(submitted.id))
+            .returning({
+                let submitted = submitted.clone();
+                move |_| Ok(submitted.clone())
+            });
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings())));
+        repo.expect_record_processing_result()
+            .times(1)
+            .with(eq(submitted.id), eq("aborted"), eq(result.clone()))
+            .returning({
+                let submitted = submitted.clone();
+                move |_, status, result| {
+                    Ok(NavSubmission {
+                        status: status.to_string(),
+                        nav_status: Some(result.invoice_status.clone()),
+                        messages: Json(result.messages.clone()),
+                        ..submitted.clone()
+                    })
+                }
+            });
+        let mut client = MockNavClient::new();
+        client
+            .expect_query_transaction_status()
+            .times(1)
+            .with(eq("4M1A9XHB1KJ6YBQ2"))
+            .returning({
+                let result = result.clone();
+                move |_| Ok(result.clone())
+            });
+
+        let response = app(repo, Some(client), active_tenant_id, 1)
+            .oneshot(request(
+                "PUT",
+                "/api/nav_reporting/refresh_status",
+                active_tenant_id,
+                Some(json!({"uuid": submitted.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["status"], json!("aborted"));
+        assert_eq!(
+            body["data"]["messages"][0]["error_code"],
+            json!("INVOICE_NUMBER_NOT_UNIQUE")
+        );
+    }
+}
@@ -0,0 +1,466 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::nav_reporting::dto::{NavAddress, NavCustomer};
+use crate::tenant::nav_reporting::model::{
+    NavInvoiceHeader, NavInvoiceLine, NavModificationReference, NavSettings,
+};
+use crate::tenant::taxes::vat_treatment::{TaxLegalText, VatTreatment};
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use quick_xml::escape::escape;
+
+const DATA_NAMESPACE: &str = "http://schemas.nav.gov.hu/OSA/3.0/data";
+const BASE_NAMESPACE: &str = "http://schemas.nav.gov.hu/OSA/3.0/base";
+pub const UNKNOWN_VAT_TREATMENT: &str = "A számla egy tételének ismeretlen az áfa kezelése!";
+
+#[derive(Debug, Clone, PartialEq)]
+enum VatRate {
+    Percentage(BigDecimal),
+    Exemption { case: String, reason: String },
+    DomesticReverseCharge,
+}
+
+impl VatRate {
+    fn from_line(line: &NavInvoiceLine) -> Result<Self, &'static str> {
+        let treatment = line
+            .vat_treatment
+            .parse::<VatTreatment>()
+            .map_err(|_| UNKNOWN_VAT_TREATMENT)?;
+        Ok(if treatment == VatTreatment::ReverseCharge {
+            VatRate::DomesticReverseCharge
+        } else if let Some(case) = treatment.nav_exemption_case() {
+            VatRate::Exemption {
+                case: case.to_string(),
+                reason: TaxLegalText::of(treatment, line.legal_text.as_deref())
+                    .map(|text| text.as_str().chars().take(200).collect())
+                    .unwrap_or_default(),
+            }
+        } else if line.is_rate_applicable {
+            VatRate::Percentage((&line.tax_rate / BigDecimal::from(100)).normalized())
+        } else {
+            VatRate::Exemption {
+                case: line
+                    .reporting_code
+                    .clone()
+                    .unwrap_or_else(|| "AAM".to_string()),
+                reason: line
+                    .legal_text
+                    .as_deref()
+                    .unwrap_or(&line.tax_description)
+                    .chars()
+                    .take(200)
+                    .collect(),
+            }
+        })
+    }
+
+    fn xml(&self) -> String {
+        match self {
+            VatRate::Percentage(rate) => format!("<vatPercentage>{rate}</vatPercentage>"),
+            VatRate::Exemption { case, reason } => format!(
+                "<vatExemption><case>{}</case><reason>{}</reason></vatExemption>",
+                escape(case.as_str()),
+                escape(reason.as_str())
+            ),
+            VatRate::DomesticReverseCharge => {
+                "<vatDomesticReverseCharge>true</vatDomesticReverseCharge>".to_string()
+            }
+        }
+    }
+}
+
+struct VatRateSummary {
+    rate: VatRate,
+    net: BigDecimal,
+    vat: BigDecimal,
+    gross: BigDecimal,
+}
+
+fn amount(value: &BigDecimal) -> String {
+    value.with_scale_round(2, RoundingMode::HalfUp).to_string()
+}
+
+// NOTE: tax numbers are validated to the xxxxxxxx-y-zz format before building the document
+fn tax_number_xml(tax_number: &str) -> String {
+    let mut parts = tax_number.split('-');
+    format!(
+        "<base:taxpayerId>{}</base:taxpayerId><base:vatCode>{}</base:vatCode><base:countyCode>{}</base:countyCode>",
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default()
+    )
+}
+
+fn address_xml(address: &NavAddress) -> String {
+    format!(
+        "<base:simpleAddress><base:countryCode>{}</base:countryCode><base:postalCode>{}</base:postalCode><base:city>{}</base:city><base:additionalAddressDetail>{}</base:additionalAddressDetail></base:simpleAddress>",
+        escape(address.country_code.as_str()),
+        escape(address.postal_code.as_str()),
+        escape(address.city.as_str()),
+        escape(address.street_address.as_str())
+    )
+}
+
+fn customer_xml(customer: &NavCustomer) -> String {
+    let mut xml = format!(
+        "<customerVatStatus>{}</customerVatStatus>",
+        customer.vat_status
+    );
+    if let Some(tax_number) = &customer.tax_number {
+        xml.push_str(&format!(
+            "<customerVatData><customerTaxNumber>{}</customerTaxNumber></customerVatData>",
+            tax_number_xml(tax_number)
+        ));
+    }
+    if let Some(name) = &customer.name {
+        xml.push_str(&format!(
+            "<customerName>{}</customerName>",
+            escape(name.as_str())
+        ));
+    }
+    if let Some(address) = &customer.address {
+        xml.push_str(&format!(
+            "<customerAddress>{}</customerAddress>",
+            address_xml(address)
+        ));
+    }
+    xml
+}
+
+// NOTE: the invoice lines carry no natural unit of measure, so they are reported without
+// quantity and unit price (lineExpressionIndicator = false)
+// NOTE: a modifying document (credit note) only adds new negative lines after the lines of the
+// original invoice, it never rewrites them
+pub fn invoice_data_xml(
+    settings: &NavSettings,
+    header: &NavInvoiceHeader,
+    customer: &NavCustomer,
+    lines: &[NavInvoiceLine],
+    exchange_rate: &BigDecimal,
+    reference: Option<&NavModificationReference>,
+) -> Result<String, &'static str> {
+    let huf = |value: &BigDecimal| amount(&(value * exchange_rate));
+    let mut summaries: Vec<VatRateSummary> = Vec::new();
+    let mut lines_xml = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let rate = VatRate::from_line(line)?;
+        let description =
+            if line.description.trim().is_empty() || line.description.trim() == line.item {
+                line.item.clone()
+            } else {
+                format!("{} – {}", line.item, line.description.trim())
+            };
+        let line_reference = reference
+            .map(|reference| {
+                format!(
+                    "<lineModificationReference><lineNumberReference>{}</lineNumberReference><lineOperation>CREATE</lineOperation></lineModificationReference>",
+                    reference.original_line_count + index as i64 + 1
+                )
+            })
+            .unwrap_or_default();
+        lines_xml.push_str(&format!(
+            "<line><lineNumber>{}</lineNumber>{line_reference}<lineExpressionIndicator>false</lineExpressionIndicator><lineDescription>{}</lineDescription><lineAmountsNormal><lineNetAmountData><lineNetAmount>{}</lineNetAmount><lineNetAmountHUF>{}</lineNetAmountHUF></lineNetAmountData><lineVatRate>{}</lineVatRate><lineVatData><lineVatAmount>{}</lineVatAmount><lineVatAmountHUF>{}</lineVatAmountHUF></lineVatData><lineGrossAmountData><lineGrossAmountNormal>{}</lineGrossAmountNormal><lineGrossAmountNormalHUF>{}</lineGrossAmountNormalHUF></lineGrossAmountData></lineAmountsNormal></line>",
+            index + 1,
+            escape(description.chars().take(512).collect::<String>().as_str()),
+            amount(&line.net_amount),
+            huf(&line.net_amount),
+            rate.xml(),
+            amount(&line.tax_amount),
+            huf(&line.tax_amount),
+            amount(&line.gross_amount),
+            huf(&line.gross_amount),
+        ));
+        match summaries.iter_mut().find(|summary| summary.rate == rate) {
+            Some(summary) => {
+                summary.net += &line.net_amount;
+                summary.vat += &line.tax_amount;
+                summary.gross += &line.gross_amount;
+            }
+            None => summaries.push(VatRateSummary {
+                rate,
+                net: line.net_amount.clone(),
+                vat: line.tax_amount.clone(),
+                gross: line.gross_amount.clone(),
+            }),
+        }
+    }
+
+    let mut summary_xml = String::new();
+    let (mut net_total, mut vat_total, mut gross_total) =
+        (BigDecimal::zero(), BigDecimal::zero(), BigDecimal::zero());
+    for summary in &summaries {
+        summary_xml.push_str(&format!(
+            "<summaryByVatRate><vatRate>{}</vatRate><vatRateNetData><vatRateNetAmount>{}</vatRateNetAmount><vatRateNetAmountHUF>{}</vatRateNetAmountHUF></vatRateNetData><vatRateVatData><vatRateVatAmount>{}</vatRateVatAmount><vatRateVatAmountHUF>{}</vatRateVatAmountHUF></vatRateVatData><vatRateGrossData><vatRateGrossAmount>{}</vatRateGrossAmount><vatRateGrossAmountHUF>{}</vatRateGrossAmountHUF></vatRateGrossData></summaryByVatRate>",
+            summary.rate.xml(),
+            amount(&summary.net),
+            huf(&summary.net),
+            amount(&summary.vat),
+            huf(&summary.vat),
+            amount(&summary.gross),
+            huf(&summary.gross),
+        ));
+        net_total += &summary.net;
+        vat_total += &summary.vat;
+        gross_total += &summary.gross;
+    }
+
+    let invoice_reference_xml = reference
+        .map(|reference| {
+            format!(
+                "<invoiceReference><originalInvoiceNumber>{}</originalInvoiceNumber><modifyWithoutMaster>false</modifyWithoutMaster><modificationIndex>{}</modificationIndex></invoiceReference>",
+                escape(reference.original_invoice_number.as_str()),
+                reference.modification_index
+            )
+        })
+        .unwrap_or_default();
+    let supplier = NavAddress {
+        country_code: settings.country_code.clone(),
+        postal_code: settings.postal_code.clone(),
+        city: settings.city.clone(),
+        street_address: settings.street_address.clone(),
+    };
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<InvoiceData xmlns="{DATA_NAMESPACE}" xmlns:base="{BASE_NAMESPACE}"><invoiceNumber>{}</invoiceNumber><invoiceIssueDate>{}</invoiceIssueDate><completenessIndicator>false</completenessIndicator><invoiceMain><invoice>{invoice_reference_xml}<invoiceHead><supplierInfo><supplierTaxNumber>{}</supplierTaxNumber><supplierName>{}</supplierName><supplierAddress>{}</supplierAddress></supplierInfo><customerInfo>{}</customerInfo><invoiceDetail><invoiceCategory>NORMAL</invoiceCategory><invoiceDeliveryDate>{}</invoiceDeliveryDate><currencyCode>{}</currencyCode><exchangeRate>{}</exchangeRate><paymentDate>{}</paymentDate><invoiceAppearance>ELECTRONIC</invoiceAppearance></invoiceDetail></invoiceHead><invoiceLines><mergedItemIndicator>false</mergedItemIndicator>{lines_xml}</invoiceLines><invoiceSummary><summaryNormal>{summary_xml}<invoiceNetAmount>{}</invoiceNetAmount><invoiceNetAmountHUF>{}</invoiceNetAmountHUF><invoiceVatAmount>{}</invoiceVatAmount><invoiceVatAmountHUF>{}</invoiceVatAmountHUF></summaryNormal><summaryGrossData><invoiceGrossAmount>{}</invoiceGrossAmount><invoiceGrossAmountHUF>{}</invoiceGrossAmountHUF></summaryGrossData></invoiceSummary></invoice></invoiceMain></InvoiceData>"#,
+        escape(header.document_number.as_str()),
+        header.issue_date.format("%Y-%m-%d"),
+        tax_number_xml(&settings.tax_number),
+        escape(settings.company_name.as_str()),
+        address_xml(&supplier),
+        customer_xml(customer),
+        header.issue_date.format("%Y-%m-%d"),
+        header.currency_code,
+        exchange_rate
+            .with_scale_round(6, RoundingMode::HalfUp)
+            .normalized(),
+        header.due_date.format("%Y-%m-%d"),
+        amount(&net_total),
+        huf(&net_total),
+        amount(&vat_total),
+        huf(&vat_total),
+        amount(&gross_total),
+        huf(&gross_total),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::nav_reporting::model::{VAT_STATUS_DOMESTIC, VAT_STATUS_PRIVATE_PERSON};
+    use chrono::{NaiveDate, Utc};
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn settings() -> NavSettings {
+        NavSettings {
+            environment: "test".to_string(),
+            login: "techuser".to_string(),
+            password_hash: String::new(),
+            signature_key: String::new(),
+            exchange_key: String::new(),
+            tax_number: "12345678-2-41".to_string(),
+            company_name: "Obvia Kft.".to_string(),
+            country_code: "HU".to_string(),
+            postal_code: "1111".to_string(),
+            city: "Budapest".to_string(),
+            street_address: "Fő utca 1.".to_string(),
+            updated_by_id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn line(tax_rate: &str, net: &str, tax: &str, gross: &str) -> NavInvoiceLine {
+        NavInvoiceLine {
+            item: "Karbantartás".to_string(),
+            description: String::new(),
+            tax_rate: BigDecimal::from_str(tax_rate).unwrap(),
+            is_rate_applicable: tax_rate != "0",
+            reporting_code: None,
+            tax_description: "Tárgyi adómentes".to_string(),
+            legal_text: None,
+            vat_treatment: "domestic".to_string(),
+            net_amount: BigDecimal::from_str(net).unwrap(),
+            tax_amount: BigDecimal::from_str(tax).unwrap(),
+            gross_amount: BigDecimal::from_str(gross).unwrap(),
+        }
+    }
+
+    fn header(currency_code: &str) -> NavInvoiceHeader {
+        NavInvoiceHeader {
+            receivable_id: Uuid::new_v4(),
+            document_number: "SZ-2026-0001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 24).unwrap(),
+            currency_code: currency_code.to_string(),
+            amount: BigDecimal::from(1000),
+            customer_name: "Teszt & Társa Bt.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_invoice_data_groups_vat_rates_and_converts_to_huf() {
+        let customer = NavCustomer {
+            vat_status: VAT_STATUS_DOMESTIC.to_string(),
+            tax_number: Some("87654321-2-13".to_string()),
+            name: Some("Teszt & Társa Bt.".to_string()),
+            address: Some(NavAddress {
+                country_code: "HU".to_string(),
+                postal_code: "2000".to_string(),
+                city: "Szentendre".to_string(),
+                street_address: "Kossuth u. 2.".to_string(),
+            }),
+        };
+        let xml = invoice_data_xml(
+            &settings(),
+            &header("EUR"),
+            &customer,
+            &[
+                line("27", "100", "27", "127"),
+                line("0", "50", "0", "50"),
+                line("27", "10", "2.70", "12.70"),
+            ],
+            &BigDecimal::from_str("400.5").unwrap(),
+            None,
+        )
+        .unwrap();
+
+        assert!(xml.contains("<supplierTaxNumber><base:taxpayerId>12345678</base:taxpayerId><base:vatCode>2</base:vatCode><base:countyCode>41</base:countyCode></supplierTaxNumber>"));
+        assert!(xml.contains("<customerName>Teszt &amp; Társa Bt.</customerName>"));
+        assert!(xml.contains("<currencyCode>EUR</currencyCode><exchangeRate>400.5</exchangeRate>"));
+        assert_eq!(xml.matches("<summaryByVatRate>").count(), 2);
+        assert!(xml.contains("<vatRate><vatPercentage>0.27</vatPercentage></vatRate><vatRateNetData><vatRateNetAmount>110.00</vatRateNetAmount><vatRateNetAmountHUF>44055.00</vatRateNetAmountHUF>"));
+        assert!(xml.contains(
+            "<vatExemption><case>AAM</case><reason>Tárgyi adómentes</reason></vatExemption>"
+        ));
+        assert!(xml.contains("<invoiceGrossAmount>189.70</invoiceGrossAmount><invoiceGrossAmountHUF>75974.85</invoiceGrossAmountHUF>"));
+    }
+
+    #[test]
+    fn test_special_vat_treatments_are_reported_by_treatment() {
+        let customer = NavCustomer {
+            vat_status: VAT_STATUS_DOMESTIC.to_string(),
+            tax_number: Some("87654321-2-13".to_string()),
+            name: Some("Teszt Kft.".to_string()),
+            address: None,
+        };
+        let xml = invoice_data_xml(
+            &settings(),
+            &header("HUF"),
+            &customer,
+            &[
+                NavInvoiceLine {
+                    vat_treatment: "reverse_charge".to_string(),
+                    ..line("0", "1000", "0", "1000")
+                },
+                NavInvoiceLine {
+                    vat_treatment: "eu_supply".to_string(),
+                    ..line("27", "500", "0", "500")
+                },
+            ],
+            &BigDecimal::from(1),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(xml.matches("<summaryByVatRate>").count(), 2);
+        assert!(xml.contains(
+            "<lineVatRate><vatDomesticReverseCharge>true</vatDomesticReverseCharge></lineVatRate>"
+        ));
+        assert!(xml.contains("<vatExemption><case>KBAET</case><reason>Adómentes Közösségen belüli termékértékesítés (Áfa tv. 89. §).</reason></vatExemption>"));
+        assert!(!xml.contains("<vatPercentage>"));
+    }
+
+    #[test]
+    fn test_private_person_customer_has_no_identifying_data() {
+        let customer = NavCustomer {
+            vat_status: VAT_STATUS_PRIVATE_PERSON.to_string(),
+            tax_number: None,
+            name: None,
+            address: None,
+        };
+        let xml = invoice_data_xml(
+            &settings(),
+            &header("HUF"),
+            &customer,
+            &[line("27", "1000", "270", "1270")],
+            &BigDecimal::from(1),
+            None,
+        )
+        .unwrap();
+
+        assert!(xml.contains(
+            "<customerInfo><customerVatStatus>PRIVATE_PERSON</customerVatStatus></customerInfo>"
+        ));
+        assert!(xml.contains("<exchangeRate>1</exchangeRate>"));
+        assert!(xml.contains("<lineGrossAmountNormal>1270.00</lineGrossAmountNormal><lineGrossAmountNormalHUF>1270.00</lineGrossAmountNormalHUF>"));
+    }
+
+    #[test]
+    fn test_credit_note_references_original_invoice_and_continues_line_numbers() {
+        let customer = NavCustomer {
+            vat_status: VAT_STATUS_PRIVATE_PERSON.to_string(),
+            tax_number: None,
+            name: None,
+            address: None,
+        };
+        let mut header = header("HUF");
+        header.document_number = "HSZ-2026-00001".to_string();
+        header.amount = BigDecimal::from(-1270);
+        let xml = invoice_data_xml(
+            &settings(),
+            &header,
+            &customer,
+            &[line("27", "-1000", "-270", "-1270")],
+            &BigDecimal::from(1),
+            Some(&NavModificationReference {
+                original_invoice_number: "SZ-2026-0001".to_string(),
+                modification_index: 2,
+                original_line_count: 3,
+            }),
+        )
+        .unwrap();
+
+        assert!(xml.contains("<invoice><invoiceReference><originalInvoiceNumber>SZ-2026-0001</originalInvoiceNumber><modifyWithoutMaster>false</modifyWithoutMaster><modificationIndex>2</modificationIndex></invoiceReference><invoiceHead>"));
+        assert!(xml.contains("<lineNumber>1</lineNumber><lineModificationReference><lineNumberReference>4</lineNumberReference><lineOperation>CREATE</lineOperation></lineModificationReference>"));
+        assert!(xml.contains("<invoiceGrossAmount>-1270.00</invoiceGrossAmount>"));
+    }
+
+    #[test]
+    fn test_unknown_vat_treatment_is_rejected() {
+        let customer = NavCustomer {
+            vat_status: VAT_STATUS_PRIVATE_PERSON.to_string(),
+            tax_number: None,
+            name: None,
+            address: None,
+        };
+        let result = invoice_data_xml(
+            &settings(),
+            &header("HUF"),
+            &customer,
+            &[NavInvoiceLine {
+                vat_treatment: "unknown".to_string(),
+                ..line("27", "1000", "270", "1270")
+            }],
+            &BigDecimal::from(1),
+            None,
+        );
+
+        assert_eq!(result, Err(UNKNOWN_VAT_TREATMENT));
+    }
+}
@@ -0,0 +1,128 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::{AppState, BaseModule, ConfigProvider};
+use crate::tenant::exchange_rates::repository::ExchangeRatesRepository;
+use crate::tenant::nav_reporting::client::{NavClient, NavError, OnlineSzamlaClient};
+use crate::tenant::nav_reporting::model::NavSettings;
+use crate::tenant::nav_reporting::repository::NavReportingRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod client;
+pub mod dto;
+pub(crate) mod handler;
+pub mod invoice_data;
+pub mod model;
+pub mod queue;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+
+pub trait NavReportingModuleInterface: BaseModule {
+    fn nav_reporting_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn NavReportingRepository + Send + Sync>>;
+    fn nav_client(
+        &self,
+        settings: &NavSettings,
+    ) -> Result<Arc<dyn NavClient + Send + Sync>, NavError>;
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+}
+
+impl<P, T> NavReportingModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn nav_reporting_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn NavReportingRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+
+    fn nav_client(
+        &self,
+        settings: &NavSettings,
+    ) -> Result<Arc<dyn NavClient + Send + Sync>, NavError> {
+        Ok(Arc::new(OnlineSzamlaClient::new(
+            settings,
+            self.config().nav(),
+        )?))
+    }
+
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub NavReportingModule {}
+        impl ConfigProvider for NavReportingModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for NavReportingModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for NavReportingModule {}
+        impl NavReportingModuleInterface for NavReportingModule {
+            fn nav_reporting_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn NavReportingRepository + Send + Sync>>;
+            fn nav_client(
+                &self,
+                settings: &NavSettings,
+            ) -> Result<Arc<dyn NavClient + Send + Sync>, NavError>;
+            fn exchange_rates_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+        }
+    );
+}
@@ -0,0 +1,139 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+pub const ENVIRONMENTS: [&str; 2] = ["test", "production"];
+
+pub const OPERATION_CREATE: &str = "CREATE";
+pub const OPERATION_MODIFY: &str = "MODIFY";
+
+pub const STATUS_QUEUED: &str = "queued";
+pub const STATUS_SUBMITTED: &str = "submitted";
+pub const STATUS_DONE: &str = "done";
+pub const STATUS_ABORTED: &str = "aborted";
+pub const STATUS_FAILED: &str = "failed";
+
+pub const VAT_STATUS_DOMESTIC: &str = "DOMESTIC";
+pub const VAT_STATUS_PRIVATE_PERSON: &str = "PRIVATE_PERSON";
+pub const VAT_STATUS_OTHER: &str = "OTHER";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct NavSettings {
+    pub environment: String,
+    pub login: String,
+    #[serde(skip)]
+    pub password_hash: String,
+    #[serde(skip)]
+    pub signature_key: String,
+    #[serde(skip)]
+    pub exchange_key: String,
+    pub tax_number: String,
+    pub company_name: String,
+    pub country_code: String,
+    pub postal_code: String,
+    pub city: String,
+    pub street_address: String,
+    pub updated_by_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NavMessage {
+    pub result_code: String,
+    pub error_code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct NavSubmission {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub credit_note_id: Option<Uuid>,
+    pub operation: String,
+    #[serde(skip_serializing)]
+    pub invoice_xml: String,
+    pub status: String,
+    pub transaction_id: Option<String>,
+    pub nav_status: Option<String>,
+    pub messages: Json<Vec<NavMessage>>,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct NavInvoiceHeader {
+    pub receivable_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    pub customer_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct NavInvoiceLine {
+    pub item: String,
+    pub description: String,
+    pub tax_rate: BigDecimal,
+    pub is_rate_applicable: bool,
+    pub reporting_code: Option<String>,
+    pub tax_description: String,
+    pub legal_text: Option<String>,
+    pub vat_treatment: String,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+}
+
+/// Reference of a modifying document (credit note) to the invoice it corrects.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct NavModificationReference {
+    pub original_invoice_number: String,
+    pub modification_index: i64,
+    pub original_line_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavProcessingResult {
+    pub invoice_status: String,
+    pub messages: Vec<NavMessage>,
+}
+
+impl NavProcessingResult {
+    pub fn submission_status(&self) -> &'static str {
+        match self.invoice_status.as_str() {
+            "DONE" => STATUS_DONE,
+            "ABORTED" => STATUS_ABORTED,
+            _ => STATUS_SUBMITTED,
+        }
+    }
+}
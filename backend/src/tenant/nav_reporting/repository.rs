@@ -0,0 +1,477 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::nav_reporting::dto::{NavCredentials, NavSettingsInput};
+use crate::tenant::nav_reporting::model::{
+    NavInvoiceHeader, NavInvoiceLine, NavModificationReference, NavProcessingResult, NavSettings,
+    NavSubmission,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NavReportingRepository: Send + Sync {
+    async fn get_settings(&self) -> RepositoryResult<Option<NavSettings>>;
+    async fn save_settings(
+        &self,
+        input: &NavSettingsInput,
+        credentials: &NavCredentials,
+        sub: Uuid,
+    ) -> RepositoryResult<NavSettings>;
+    async fn get_invoice_header(&self, receivable_id: Uuid) -> RepositoryResult<NavInvoiceHeader>;
+    async fn get_invoice_lines(&self, receivable_id: Uuid)
+    -> RepositoryResult<Vec<NavInvoiceLine>>;
+    async fn get_credit_note_header(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<NavInvoiceHeader>;
+    async fn get_credit_note_lines(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<Vec<NavInvoiceLine>>;
+    async fn get_modification_reference(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<NavModificationReference>;
+    async fn get_submission(&self, id: Uuid) -> RepositoryResult<NavSubmission>;
+    async fn get_submissions_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<NavSubmission>)>;
+    async fn insert_submission(
+        &self,
+        receivable_id: Uuid,
+        credit_note_id: Option<Uuid>,
+        operation: &str,
+        invoice_xml: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<NavSubmission>;
+    async fn get_due_submissions(&self) -> RepositoryResult<Vec<NavSubmission>>;
+    async fn get_pending_transactions(&self) -> RepositoryResult<Vec<NavSubmission>>;
+    async fn mark_submitted(
+        &self,
+        id: Uuid,
+        transaction_id: &str,
+    ) -> RepositoryResult<NavSubmission>;
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> RepositoryResult<NavSubmission>;
+    async fn record_processing_result(
+        &self,
+        id: Uuid,
+        status: &str,
+        result: &NavProcessingResult,
+    ) -> RepositoryResult<NavSubmission>;
+    async fn requeue(&self, id: Uuid) -> RepositoryResult<NavSubmission>;
+}
+
+#[async_trait]
+impl NavReportingRepository for PgPool {
+    async fn get_settings(&self) -> RepositoryResult<Option<NavSettings>> {
+        Ok(
+            sqlx::query_as::<_, NavSettings>("SELECT * FROM nav_settings")
+                .fetch_optional(self)
+                .await?,
+        )
+    }
+
+    async fn save_settings(
+        &self,
+        input: &NavSettingsInput,
+        credentials: &NavCredentials,
+        sub: Uuid,
+    ) -> RepositoryResult<NavSettings> {
+        Ok(sqlx::query_as::<_, NavSettings>(
+            r#"
+            INSERT INTO nav_settings (environment, login, password_hash, signature_key,
+                                      exchange_key, tax_number, company_name, country_code,
+                                      postal_code, city, street_address, updated_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (id) DO UPDATE
+                SET environment    = EXCLUDED.environment,
+                    login          = EXCLUDED.login,
+                    password_hash  = EXCLUDED.password_hash,
+                    signature_key  = EXCLUDED.signature_key,
+                    exchange_key   = EXCLUDED.exchange_key,
+                    tax_number     = EXCLUDED.tax_number,
+                    company_name   = EXCLUDED.company_name,
+                    country_code   = EXCLUDED.country_code,
+                    postal_code    = EXCLUDED.postal_code,
+                    city           = EXCLUDED.city,
+                    street_address = EXCLUDED.street_address,
+                    updated_by_id  = EXCLUDED.updated_by_id
+            RETURNING *
+            "#,
+        )
+        .bind(&input.environment)
+        .bind(&input.login)
+        .bind(&credentials.password_hash)
+        .bind(&credentials.signature_key)
+        .bind(&credentials.exchange_key)
+        .bind(&input.tax_number)
+        .bind(&input.company_name)
+        .bind(&input.country_code)
+        .bind(&input.postal_code)
+        .bind(&input.city)
+        .bind(&input.street_address)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_invoice_header(&self, receivable_id: Uuid) -> RepositoryResult<NavInvoiceHeader> {
+        Ok(sqlx::query_as::<_, NavInvoiceHeader>(
+            r#"
+            SELECT receivables.id AS receivable_id,
+                   receivables.document_number,
+                   receivables.issue_date,
+                   receivables.due_date,
+                   receivables.currency_code,
+                   receivables.amount,
+                   customers.name AS customer_name
+            FROM receivables
+            JOIN customers ON receivables.customer_id = customers.id
+            WHERE receivables.id = $1
+              AND receivables.deleted_at IS NULL
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_invoice_lines(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<NavInvoiceLine>> {
+        Ok(sqlx::query_as::<_, NavInvoiceLine>(
+            r#"
+            SELECT COALESCE(services.name, products.name) AS item,
+                   receivable_lines.description,
+                   receivable_lines.tax_rate,
+                   taxes.is_rate_applicable,
+                   taxes.reporting_code,
+                   taxes.description AS tax_description,
+                   taxes.legal_text,
+                   receivable_lines.vat_treatment,
+                   receivable_lines.net_amount,
+                   receivable_lines.tax_amount,
+                   receivable_lines.net_amount + receivable_lines.tax_amount AS gross_amount
+            FROM receivable_lines
+            JOIN taxes ON receivable_lines.tax_id = taxes.id
+            LEFT JOIN services ON receivable_lines.service_id = services.id
+            LEFT JOIN products ON receivable_lines.product_id = products.id
+            WHERE receivable_lines.receivable_id = $1
+            ORDER BY receivable_lines.position
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_credit_note_header(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<NavInvoiceHeader> {
+        Ok(sqlx::query_as::<_, NavInvoiceHeader>(
+            r#"
+            SELECT credit_notes.receivable_id,
+                   credit_notes.document_number,
+                   credit_notes.issue_date,
+                   credit_notes.issue_date AS due_date,
+                   credit_notes.currency_code,
+                   credit_notes.amount,
+                   customers.name AS customer_name
+            FROM credit_notes
+            JOIN customers ON credit_notes.customer_id = customers.id
+            WHERE credit_notes.id = $1
+            "#,
+        )
+        .bind(credit_note_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_credit_note_lines(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<Vec<NavInvoiceLine>> {
+        Ok(sqlx::query_as::<_, NavInvoiceLine>(
+            r#"
+            SELECT COALESCE(services.name, products.name) AS item,
+                   credit_note_lines.description,
+                   credit_note_lines.tax_rate,
+                   taxes.is_rate_applicable,
+                   taxes.reporting_code,
+                   taxes.description AS tax_description,
+                   taxes.legal_text,
+                   receivable_lines.vat_treatment,
+                   credit_note_lines.net_amount,
+                   credit_note_lines.tax_amount,
+                   credit_note_lines.net_amount + credit_note_lines.tax_amount AS gross_amount
+            FROM credit_note_lines
+            JOIN taxes ON credit_note_lines.tax_id = taxes.id
+            JOIN receivable_lines ON credit_note_lines.receivable_line_id = receivable_lines.id
+            LEFT JOIN services ON credit_note_lines.service_id = services.id
+            LEFT JOIN products ON credit_note_lines.product_id = products.id
+            WHERE credit_note_lines.credit_note_id = $1
+            ORDER BY credit_note_lines.position
+            "#,
+        )
+        .bind(credit_note_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    // NOTE: line numbers of a modification continue after the lines of the original invoice and
+    // of every earlier credit note issued for it
+    async fn get_modification_reference(
+        &self,
+        credit_note_id: Uuid,
+    ) -> RepositoryResult<NavModificationReference> {
+        Ok(sqlx::query_as::<_, NavModificationReference>(
+            r#"
+            SELECT receivables.document_number AS original_invoice_number,
+                   (SELECT COUNT(*)
+                    FROM credit_notes earlier
+                    WHERE earlier.receivable_id = credit_notes.receivable_id
+                      AND (earlier.created_at, earlier.id) <= (credit_notes.created_at, credit_notes.id))
+                       AS modification_index,
+                   (SELECT COUNT(*) FROM receivable_lines WHERE receivable_id = receivables.id)
+                       + (SELECT COUNT(*)
+                          FROM credit_note_lines
+                          JOIN credit_notes earlier ON credit_note_lines.credit_note_id = earlier.id
+                          WHERE earlier.receivable_id = credit_notes.receivable_id
+                            AND (earlier.created_at, earlier.id) < (credit_notes.created_at, credit_notes.id))
+                       AS original_line_count
+            FROM credit_notes
+            JOIN receivables ON credit_notes.receivable_id = receivables.id
+            WHERE credit_notes.id = $1
+              AND EXISTS (SELECT 1
+                          FROM nav_invoice_submissions
+                          WHERE nav_invoice_submissions.receivable_id = receivables.id
+                            AND nav_invoice_submissions.credit_note_id IS NULL
+                            AND nav_invoice_submissions.status IN ('submitted', 'done'))
+            "#,
+        )
+        .bind(credit_note_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_submission(&self, id: Uuid) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            "SELECT * FROM nav_invoice_submissions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_submissions_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<NavSubmission>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM nav_invoice_submissions")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let submissions = sqlx::query_as::<_, NavSubmission>(
+            r#"
+            SELECT *
+            FROM nav_invoice_submissions
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            submissions,
+        ))
+    }
+
+    async fn insert_submission(
+        &self,
+        receivable_id: Uuid,
+        credit_note_id: Option<Uuid>,
+        operation: &str,
+        invoice_xml: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            INSERT INTO nav_invoice_submissions (receivable_id, credit_note_id, operation,
+                                                 invoice_xml, created_by_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(receivable_id)
+        .bind(credit_note_id)
+        .bind(operation)
+        .bind(invoice_xml)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_due_submissions(&self) -> RepositoryResult<Vec<NavSubmission>> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            SELECT *
+            FROM nav_invoice_submissions
+            WHERE status = 'queued'
+              AND next_attempt_at <= now()
+            ORDER BY next_attempt_at
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_pending_transactions(&self) -> RepositoryResult<Vec<NavSubmission>> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            SELECT *
+            FROM nav_invoice_submissions
+            WHERE status = 'submitted'
+            ORDER BY submitted_at
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn mark_submitted(
+        &self,
+        id: Uuid,
+        transaction_id: &str,
+    ) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            UPDATE nav_invoice_submissions
+            SET status         = 'submitted',
+                transaction_id = $2,
+                attempts       = attempts + 1,
+                last_error     = NULL,
+                submitted_at   = now()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(transaction_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            UPDATE nav_invoice_submissions
+            SET status          = $2,
+                attempts        = attempts + 1,
+                next_attempt_at = $3,
+                last_error      = $4
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(next_attempt_at)
+        .bind(error)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn record_processing_result(
+        &self,
+        id: Uuid,
+        status: &str,
+        result: &NavProcessingResult,
+    ) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            UPDATE nav_invoice_submissions
+            SET status       = $2,
+                nav_status   = $3,
+                messages     = $4,
+                completed_at = CASE WHEN $2 = 'submitted' THEN NULL ELSE now() END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(&result.invoice_status)
+        .bind(Json(&result.messages))
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn requeue(&self, id: Uuid) -> RepositoryResult<NavSubmission> {
+        Ok(sqlx::query_as::<_, NavSubmission>(
+            r#"
+            UPDATE nav_invoice_submissions
+            SET status          = 'queued',
+                attempts        = 0,
+                next_attempt_at = now()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::NavReportingModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: NavReportingModuleInterface>(nav_reporting_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/nav_reporting",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/list", get(handler::list::<M>))
+            .route("/submit", post(handler::submit::<M>))
+            .route(
+                "/submit_credit_note",
+                post(handler::submit_credit_note::<M>),
+            )
+            .route("/refresh_status", put(handler::refresh_status::<M>))
+            .route("/retry", put(handler::retry::<M>))
+            .route("/settings/get", get(handler::get_settings::<M>))
+            .route("/settings/update", put(handler::update_settings::<M>))
+            .layer(from_fn_with_state(
+                nav_reporting_module.clone(),
+                require_auth,
+            ))
+            .with_state(nav_reporting_module),
+    )
+}
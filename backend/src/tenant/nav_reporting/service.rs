@@ -0,0 +1,631 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::{CryptoError, encrypt_secret};
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::exchange_rates::model::BASE_CURRENCY_CODE;
+use crate::tenant::exchange_rates::service::{
+    ExchangeRatesServiceError, exchange_rate as stored_rate,
+};
+use crate::tenant::nav_reporting::NavReportingModuleInterface;
+use crate::tenant::nav_reporting::client::{NavError, password_hash};
+use crate::tenant::nav_reporting::dto::{
+    NavAddress, NavCredentials, NavCustomer, NavCustomerInput, NavSettingsInput, SubmitCreditNote,
+    SubmitInvoice,
+};
+use crate::tenant::nav_reporting::invoice_data::invoice_data_xml;
+use crate::tenant::nav_reporting::model::{
+    ENVIRONMENTS, NavInvoiceHeader, NavInvoiceLine, NavSettings, NavSubmission, OPERATION_CREATE,
+    OPERATION_MODIFY, STATUS_FAILED, STATUS_QUEUED, STATUS_SUBMITTED, VAT_STATUS_DOMESTIC,
+    VAT_STATUS_OTHER, VAT_STATUS_PRIVATE_PERSON,
+};
+use crate::tenant::nav_reporting::repository::NavReportingRepository;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{TimeDelta, Utc};
+use serde_json::json;
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum NavReportingServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Nav(#[from] NavError),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("{0}")]
+    ExchangeRates(#[from] ExchangeRatesServiceError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for NavReportingServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => NavReportingServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<NavReportingServiceError> for AppError {
+    fn from(value: NavReportingServiceError) -> Self {
+        match value {
+            NavReportingServiceError::ExchangeRates(error) => error.into(),
+            NavReportingServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            NavReportingServiceError::UnprocessableEntry(_)
+            | NavReportingServiceError::Nav(NavError::Rejected(_))
+            | NavReportingServiceError::Nav(NavError::Configuration(_)) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            NavReportingServiceError::Nav(NavError::Http(_)) => Self::new(
+                Level::WARN,
+                StatusCode::BAD_GATEWAY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "A NAV Online Számla rendszere jelenleg nem érhető el!"}),
+            ),
+            NavReportingServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type NavReportingServiceResult<T> = Result<T, NavReportingServiceError>;
+
+fn required(value: &str, max_len: usize) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty() && value.chars().count() <= max_len).then(|| value.to_string())
+}
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// NOTE: Hungarian tax number in the xxxxxxxx-y-zz format
+pub(crate) fn is_tax_number(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    matches!(parts.as_slice(), [taxpayer_id, vat_code, county_code]
+        if taxpayer_id.len() == 8
+            && vat_code.len() == 1
+            && county_code.len() == 2
+            && parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())))
+}
+
+fn validate_settings(
+    payload: &NavSettingsInput,
+    current: Option<&NavSettings>,
+) -> NavReportingServiceResult<(NavSettingsInput, NavCredentials)> {
+    if !ENVIRONMENTS.contains(&payload.environment.as_str()) {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "Hibás NAV környezet!",
+        ));
+    }
+    let tax_number = payload.tax_number.trim();
+    if !is_tax_number(tax_number) {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "Az adószámot xxxxxxxx-y-zz formátumban kell megadni!",
+        ));
+    }
+    let country_code = payload.country_code.trim().to_uppercase();
+    if country_code.len() != 2 || !country_code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "Hibás országkód!",
+        ));
+    }
+    let invalid = || {
+        NavReportingServiceError::UnprocessableEntry(
+            "A technikai felhasználó és a cég adatainak megadása kötelező!",
+        )
+    };
+    let input = NavSettingsInput {
+        environment: payload.environment.clone(),
+        login: required(&payload.login, 15).ok_or_else(invalid)?,
+        password: None,
+        signature_key: None,
+        exchange_key: None,
+        tax_number: tax_number.to_string(),
+        company_name: required(&payload.company_name, 255).ok_or_else(invalid)?,
+        country_code,
+        postal_code: required(&payload.postal_code, 10).ok_or_else(invalid)?,
+        city: required(&payload.city, 255).ok_or_else(invalid)?,
+        street_address: required(&payload.street_address, 255).ok_or_else(invalid)?,
+    };
+
+    let missing_secrets = || {
+        NavReportingServiceError::UnprocessableEntry(
+            "A jelszó, az aláírókulcs és a cserekulcs megadása kötelező!",
+        )
+    };
+    if let Some(exchange_key) = optional(&payload.exchange_key)
+        && exchange_key.len() != 16
+    {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "A cserekulcsnak 16 karakter hosszúnak kell lennie!",
+        ));
+    }
+    let credentials = NavCredentials {
+        password_hash: match optional(&payload.password) {
+            Some(password) => password_hash(&password),
+            None => current
+                .map(|settings| settings.password_hash.clone())
+                .ok_or_else(missing_secrets)?,
+        },
+        signature_key: match optional(&payload.signature_key) {
+            Some(signature_key) => encrypt_secret(&signature_key)?,
+            None => current
+                .map(|settings| settings.signature_key.clone())
+                .ok_or_else(missing_secrets)?,
+        },
+        exchange_key: match optional(&payload.exchange_key) {
+            Some(exchange_key) => encrypt_secret(&exchange_key)?,
+            None => current
+                .map(|settings| settings.exchange_key.clone())
+                .ok_or_else(missing_secrets)?,
+        },
+    };
+    Ok((input, credentials))
+}
+
+fn validate_customer(
+    payload: &NavCustomerInput,
+    customer_name: &str,
+) -> NavReportingServiceResult<NavCustomer> {
+    let vat_status = payload.customer_vat_status.as_str();
+    if vat_status == VAT_STATUS_PRIVATE_PERSON {
+        return Ok(NavCustomer {
+            vat_status: vat_status.to_string(),
+            tax_number: None,
+            name: None,
+            address: None,
+        });
+    }
+    if vat_status != VAT_STATUS_DOMESTIC && vat_status != VAT_STATUS_OTHER {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "Hibás vevői ÁFA státusz!",
+        ));
+    }
+    let tax_number = match optional(&payload.customer_tax_number) {
+        Some(tax_number) if is_tax_number(&tax_number) => Some(tax_number),
+        _ if vat_status == VAT_STATUS_DOMESTIC => {
+            return Err(NavReportingServiceError::UnprocessableEntry(
+                "Belföldi vevő adószámát xxxxxxxx-y-zz formátumban kell megadni!",
+            ));
+        }
+        _ => None,
+    };
+    let missing_address =
+        || NavReportingServiceError::UnprocessableEntry("A vevő címének megadása kötelező!");
+    let address = NavAddress {
+        country_code: optional(&payload.customer_country_code)
+            .map(|country_code| country_code.to_uppercase())
+            .unwrap_or_else(|| "HU".to_string()),
+        postal_code: optional(&payload.customer_postal_code).ok_or_else(missing_address)?,
+        city: optional(&payload.customer_city).ok_or_else(missing_address)?,
+        street_address: optional(&payload.customer_street_address).ok_or_else(missing_address)?,
+    };
+    Ok(NavCustomer {
+        vat_status: vat_status.to_string(),
+        tax_number,
+        name: Some(customer_name.to_string()),
+        address: Some(address),
+    })
+}
+
+/// The given rate, or the stored rate of the issue date when none is given
+async fn exchange_rate<M: NavReportingModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    header: &NavInvoiceHeader,
+    exchange_rate: &Option<BigDecimal>,
+) -> NavReportingServiceResult<BigDecimal> {
+    if header.currency_code == BASE_CURRENCY_CODE {
+        return Ok(BigDecimal::from(1));
+    }
+    match exchange_rate {
+        Some(rate) if *rate > BigDecimal::zero() => Ok(rate.clone()),
+        Some(_) => Err(NavReportingServiceError::UnprocessableEntry(
+            "Az árfolyamnak pozitívnak kell lennie!",
+        )),
+        None => Ok(stored_rate(
+            &*module.exchange_rates_repo(tenant_id)?,
+            &header.currency_code,
+            BASE_CURRENCY_CODE,
+            header.issue_date,
+        )
+        .await?
+        .rate),
+    }
+}
+
+fn validate_lines(
+    header: &NavInvoiceHeader,
+    lines: &[NavInvoiceLine],
+) -> NavReportingServiceResult<()> {
+    if lines.is_empty() {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "Csak tételes számla küldhető be!",
+        ));
+    }
+    let gross_total = lines
+        .iter()
+        .fold(BigDecimal::zero(), |total, line| total + &line.gross_amount);
+    if gross_total != header.amount {
+        return Err(NavReportingServiceError::UnprocessableEntry(
+            "A számla összege eltér a tételek összegétől!",
+        ));
+    }
+    Ok(())
+}
+
+fn map_insert_err(e: RepositoryError) -> NavReportingServiceError {
+    if e.is_unique_violation() {
+        NavReportingServiceError::UnprocessableEntry(
+            "A számla adatszolgáltatása már folyamatban van vagy megtörtént!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+fn retry_delay(interval_mins: u64, attempts: i32) -> TimeDelta {
+    let factor = 2u64.saturating_pow(u32::try_from(attempts.saturating_sub(1)).unwrap_or(0));
+    let minutes = interval_mins.max(1).saturating_mul(factor).min(24 * 60);
+    TimeDelta::minutes(i64::try_from(minutes).unwrap_or(24 * 60))
+}
+
+// NOTE: transport and authentication errors keep the submission in the queue until the
+// configured number of attempts is reached
+pub(crate) async fn send_submission<M: NavReportingModuleInterface>(
+    module: &M,
+    repo: &(dyn NavReportingRepository + Send + Sync),
+    settings: &NavSettings,
+    submission: &NavSubmission,
+) -> NavReportingServiceResult<NavSubmission> {
+    let result = match module.nav_client(settings) {
+        Ok(client) => {
+            client
+                .manage_invoice(&submission.operation, &submission.invoice_xml)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(transaction_id) => Ok(repo.mark_submitted(submission.id, &transaction_id).await?),
+        Err(e) => {
+            warn!("NAV submission failed: {} {}", submission.id, e);
+            let attempts = submission.attempts + 1;
+            let config = module.config().nav();
+            let status = if attempts >= config.max_attempts() {
+                STATUS_FAILED
+            } else {
+                STATUS_QUEUED
+            };
+            Ok(repo
+                .record_failure(
+                    submission.id,
+                    status,
+                    Utc::now() + retry_delay(config.queue_interval_mins(), attempts),
+                    &e.to_string(),
+                )
+                .await?)
+        }
+    }
+}
+
+pub(crate) async fn sync_status<M: NavReportingModuleInterface>(
+    module: &M,
+    repo: &(dyn NavReportingRepository + Send + Sync),
+    settings: &NavSettings,
+    submission: &NavSubmission,
+) -> NavReportingServiceResult<NavSubmission> {
+    let transaction_id = submission.transaction_id.as_deref().ok_or(
+        NavReportingServiceError::UnprocessableEntry(
+            "A beküldés még nem kapott tranzakcióazonosítót!",
+        ),
+    )?;
+    let result = module
+        .nav_client(settings)?
+        .query_transaction_status(transaction_id)
+        .await?;
+    Ok(repo
+        .record_processing_result(submission.id, result.submission_status(), &result)
+        .await?)
+}
+
+pub trait NavReportingService {
+    fn get_settings(
+        &self,
+    ) -> impl Future<Output = NavReportingServiceResult<Option<NavSettings>>> + Send;
+    fn save_settings(
+        &self,
+        payload: &NavSettingsInput,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSettings>> + Send;
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSubmission>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = NavReportingServiceResult<(PaginatorMeta, Vec<NavSubmission>)>> + Send;
+    fn submit(
+        &self,
+        payload: &SubmitInvoice,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSubmission>> + Send;
+    fn submit_credit_note(
+        &self,
+        payload: &SubmitCreditNote,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSubmission>> + Send;
+    fn refresh_status(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSubmission>> + Send;
+    fn retry(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = NavReportingServiceResult<NavSubmission>> + Send;
+}
+
+impl<'a, T> NavReportingService for Service<'a, T>
+where
+    T: NavReportingModuleInterface,
+{
+    async fn get_settings(&self) -> NavReportingServiceResult<Option<NavSettings>> {
+        Ok(self
+            .module()
+            .nav_reporting_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(NavReportingServiceError::Unauthorized)?,
+            )?
+            .get_settings()
+            .await?)
+    }
+
+    async fn save_settings(
+        &self,
+        payload: &NavSettingsInput,
+    ) -> NavReportingServiceResult<NavSettings> {
+        let repo = self.module().nav_reporting_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+        )?;
+        let current = repo.get_settings().await?;
+        let (input, credentials) = validate_settings(payload, current.as_ref())?;
+        Ok(repo
+            .save_settings(&input, &credentials, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get(&self, id: Uuid) -> NavReportingServiceResult<NavSubmission> {
+        Ok(self
+            .module()
+            .nav_reporting_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(NavReportingServiceError::Unauthorized)?,
+            )?
+            .get_submission(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> NavReportingServiceResult<(PaginatorMeta, Vec<NavSubmission>)> {
+        Ok(self
+            .module()
+            .nav_reporting_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(NavReportingServiceError::Unauthorized)?,
+            )?
+            .get_submissions_paged(get_query)
+            .await?)
+    }
+
+    async fn submit(&self, payload: &SubmitInvoice) -> NavReportingServiceResult<NavSubmission> {
+        let repo = self.module().nav_reporting_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+        )?;
+        let settings =
+            repo.get_settings()
+                .await?
+                .ok_or(NavReportingServiceError::UnprocessableEntry(
+                    "A NAV adatszolgáltatás nincs beállítva!",
+                ))?;
+        let header = repo.get_invoice_header(payload.receivable_id).await?;
+        let customer = validate_customer(&payload.customer, &header.customer_name)?;
+        let exchange_rate = exchange_rate(
+            self.module(),
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+            &header,
+            &payload.exchange_rate,
+        )
+        .await?;
+        let lines = repo.get_invoice_lines(header.receivable_id).await?;
+        validate_lines(&header, &lines)?;
+        let invoice_xml =
+            invoice_data_xml(&settings, &header, &customer, &lines, &exchange_rate, None)
+                .map_err(NavReportingServiceError::UnprocessableEntry)?;
+        let submission = repo
+            .insert_submission(
+                header.receivable_id,
+                None,
+                OPERATION_CREATE,
+                &invoice_xml,
+                self.claims()?.sub(),
+            )
+            .await
+            .map_err(map_insert_err)?;
+        send_submission(self.module(), &*repo, &settings, &submission).await
+    }
+
+    async fn submit_credit_note(
+        &self,
+        payload: &SubmitCreditNote,
+    ) -> NavReportingServiceResult<NavSubmission> {
+        let repo = self.module().nav_reporting_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+        )?;
+        let settings =
+            repo.get_settings()
+                .await?
+                .ok_or(NavReportingServiceError::UnprocessableEntry(
+                    "A NAV adatszolgáltatás nincs beállítva!",
+                ))?;
+        let header = repo.get_credit_note_header(payload.credit_note_id).await?;
+        let customer = validate_customer(&payload.customer, &header.customer_name)?;
+        let exchange_rate = exchange_rate(
+            self.module(),
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+            &header,
+            &payload.exchange_rate,
+        )
+        .await?;
+        let reference = repo
+            .get_modification_reference(payload.credit_note_id)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Database(sqlx::Error::RowNotFound) => {
+                    NavReportingServiceError::UnprocessableEntry(
+                        "Az eredeti számla adatszolgáltatása még nem történt meg!",
+                    )
+                }
+                e => e.into(),
+            })?;
+        let lines = repo.get_credit_note_lines(payload.credit_note_id).await?;
+        validate_lines(&header, &lines)?;
+        let invoice_xml = invoice_data_xml(
+            &settings,
+            &header,
+            &customer,
+            &lines,
+            &exchange_rate,
+            Some(&reference),
+        )
+        .map_err(NavReportingServiceError::UnprocessableEntry)?;
+        let submission = repo
+            .insert_submission(
+                header.receivable_id,
+                Some(payload.credit_note_id),
+                OPERATION_MODIFY,
+                &invoice_xml,
+                self.claims()?.sub(),
+            )
+            .await
+            .map_err(map_insert_err)?;
+        send_submission(self.module(), &*repo, &settings, &submission).await
+    }
+
+    async fn refresh_status(&self, id: Uuid) -> NavReportingServiceResult<NavSubmission> {
+        let repo = self.module().nav_reporting_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+        )?;
+        let submission = repo.get_submission(id).await?;
+        if submission.status != STATUS_SUBMITTED {
+            return Err(NavReportingServiceError::UnprocessableEntry(
+                "Csak feldolgozás alatt álló beküldés állapota kérdezhető le!",
+            ));
+        }
+        let settings =
+            repo.get_settings()
+                .await?
+                .ok_or(NavReportingServiceError::UnprocessableEntry(
+                    "A NAV adatszolgáltatás nincs beállítva!",
+                ))?;
+        sync_status(self.module(), &*repo, &settings, &submission).await
+    }
+
+    async fn retry(&self, id: Uuid) -> NavReportingServiceResult<NavSubmission> {
+        let repo = self.module().nav_reporting_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(NavReportingServiceError::Unauthorized)?,
+        )?;
+        let submission = repo.get_submission(id).await?;
+        if submission.status != STATUS_FAILED && submission.status != STATUS_QUEUED {
+            return Err(NavReportingServiceError::UnprocessableEntry(
+                "Csak sikertelen vagy várakozó beküldés küldhető újra!",
+            ));
+        }
+        let settings =
+            repo.get_settings()
+                .await?
+                .ok_or(NavReportingServiceError::UnprocessableEntry(
+                    "A NAV adatszolgáltatás nincs beállítva!",
+                ))?;
+        let submission = repo.requeue(submission.id).await?;
+        send_submission(self.module(), &*repo, &settings, &submission).await
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PackageTypeInput {
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub length_mm: i32,
+    pub width_mm: i32,
+    pub height_mm: i32,
+    pub tare_weight_g: i32,
+    pub max_weight_g: Option<i32>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PackingItem {
+    pub product_id: Uuid,
+    pub quantity: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct EstimatePacking {
+    pub items: Vec<PackingItem>,
+}
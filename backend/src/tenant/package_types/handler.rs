@@ -0,0 +1,353 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::package_types::PackageTypesModuleInterface;
+use crate::tenant::package_types::dto::{EstimatePacking, PackageTypeInput};
+use crate::tenant::package_types::service::PackageTypesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        package_types_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        package_types_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        package_types_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PackageTypeInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    let result =
+        map_handler_err(service.create(&payload).await, package_types_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PackageTypeInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    let result =
+        map_handler_err(service.update(&payload).await, package_types_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        package_types_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A csomagolás törlése sikeresen megtörtént",
+            ))
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn estimate<M: PackageTypesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(package_types_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<EstimatePacking>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), package_types_module.clone());
+    let result = map_handler_err(
+        service.estimate(&payload).await,
+        package_types_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        package_types_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::package_types::model::PackageType;
+    use crate::tenant::package_types::{
+        self, repository::MockPackageTypesRepository, tests::MockPackageTypesModule,
+    };
+    use crate::tenant::products::model::ProductDimensions;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockPackageTypesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut package_types_module = MockPackageTypesModule::new();
+        package_types_module
+            .expect_package_types_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        package_types_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(package_types::routes::routes(Arc::new(
+                package_types_module,
+            ))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let package_type = PackageType {
+            id: Uuid::new_v4(),
+            name: "Közepes doboz".to_string(),
+            length_mm: 400,
+            width_mm: 300,
+            height_mm: 200,
+            tare_weight_g: 300,
+            max_weight_g: Some(15000),
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let mut repo = MockPackageTypesRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.name == "Közepes doboz")
+            .returning({
+                let package_type = package_type.clone();
+                move |_, _| Ok(package_type.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/package_types/create",
+                active_tenant_id,
+                json!({
+                    "name": " Közepes doboz ",
+                    "length_mm": 400,
+                    "width_mm": 300,
+                    "height_mm": 200,
+                    "tare_weight_g": 300,
+                    "max_weight_g": 15000,
+                    "status": "active"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": package_type})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_invalid_dimensions() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockPackageTypesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/package_types/create",
+                active_tenant_id,
+                json!({
+                    "name": "Doboz",
+                    "length_mm": 400,
+                    "width_mm": -1,
+                    "height_mm": 200,
+                    "tare_weight_g": 300,
+                    "max_weight_g": null,
+                    "status": "active"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+
+        let mut repo = MockPackageTypesRepository::new();
+        repo.expect_get_product_dimensions()
+            .times(1)
+            .withf(move |product_ids| product_ids == [product_id])
+            .returning(move |_| {
+                Ok(vec![ProductDimensions {
+                    product_id,
+                    weight_g: Some(250),
+                    length_mm: Some(100),
+                    width_mm: Some(50),
+                    height_mm: Some(20),
+                }])
+            });
+        repo.expect_get_active().times(1).returning(|| Ok(vec![]));
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/package_types/estimate",
+                active_tenant_id,
+                json!({"items": [
+                    {"product_id": product_id, "quantity": 2},
+                    {"product_id": product_id, "quantity": 1}
+                ]}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": {
+                "items_weight_g": 750,
+                "items_volume_mm3": 300000,
+                "package_type": null,
+                "gross_weight_g": 750,
+                "missing_dimensions": []
+            }})
+        );
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::products::model::ProductDimensions;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PackageType {
+    pub id: Uuid,
+    pub name: String,
+    pub length_mm: i32,
+    pub width_mm: i32,
+    pub height_mm: i32,
+    pub tare_weight_g: i32,
+    pub max_weight_g: Option<i32>,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl PackageType {
+    fn volume_mm3(&self) -> i64 {
+        i64::from(self.length_mm) * i64::from(self.width_mm) * i64::from(self.height_mm)
+    }
+
+    fn sorted_dimensions(&self) -> [i32; 3] {
+        sorted([self.length_mm, self.width_mm, self.height_mm])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PackingEstimate {
+    pub items_weight_g: i64,
+    pub items_volume_mm3: i64,
+    pub package_type: Option<PackageType>,
+    pub gross_weight_g: i64,
+    pub missing_dimensions: Vec<Uuid>,
+}
+
+fn sorted(mut dimensions: [i32; 3]) -> [i32; 3] {
+    dimensions.sort_unstable();
+    dimensions
+}
+
+// NOTE: this is a volume based estimate, it does not try to solve the actual 3D packing problem,
+// it only guarantees that every single item fits into the suggested box on its own
+pub fn estimate_packing(
+    items: &[(ProductDimensions, i32)],
+    package_types: &[PackageType],
+) -> PackingEstimate {
+    let mut items_weight_g = 0i64;
+    let mut items_volume_mm3 = 0i64;
+    let mut largest_item = [0i32; 3];
+    let mut missing_dimensions = vec![];
+
+    for (product, quantity) in items {
+        let quantity = i64::from(*quantity);
+        match product.weight_g {
+            Some(weight_g) => items_weight_g += i64::from(weight_g) * quantity,
+            None => missing_dimensions.push(product.product_id),
+        }
+        match (product.length_mm, product.width_mm, product.height_mm) {
+            (Some(length_mm), Some(width_mm), Some(height_mm)) => {
+                items_volume_mm3 +=
+                    i64::from(length_mm) * i64::from(width_mm) * i64::from(height_mm) * quantity;
+                let item = sorted([length_mm, width_mm, height_mm]);
+                for (largest, current) in largest_item.iter_mut().zip(item) {
+                    *largest = (*largest).max(current);
+                }
+            }
+            _ => {
+                if !missing_dimensions.contains(&product.product_id) {
+                    missing_dimensions.push(product.product_id);
+                }
+            }
+        }
+    }
+
+    let package_type = package_types
+        .iter()
+        .filter(|package_type| package_type.status == "active")
+        .filter(|package_type| package_type.volume_mm3() >= items_volume_mm3)
+        .filter(|package_type| {
+            package_type
+                .sorted_dimensions()
+                .iter()
+                .zip(largest_item)
+                .all(|(package, item)| *package >= item)
+        })
+        .filter(|package_type| {
+            package_type
+                .max_weight_g
+                .is_none_or(|max_weight_g| i64::from(max_weight_g) >= items_weight_g)
+        })
+        .min_by_key(|package_type| (package_type.volume_mm3(), package_type.tare_weight_g))
+        .cloned();
+
+    let gross_weight_g = items_weight_g
+        + package_type
+            .as_ref()
+            .map(|package_type| i64::from(package_type.tare_weight_g))
+            .unwrap_or(0);
+
+    PackingEstimate {
+        items_weight_g,
+        items_volume_mm3,
+        package_type,
+        gross_weight_g,
+        missing_dimensions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package_type(name: &str, dimensions: [i32; 3], tare: i32, max: Option<i32>) -> PackageType {
+        PackageType {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            length_mm: dimensions[0],
+            width_mm: dimensions[1],
+            height_mm: dimensions[2],
+            tare_weight_g: tare,
+            max_weight_g: max,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn product(weight_g: Option<i32>, dimensions: Option<[i32; 3]>) -> ProductDimensions {
+        ProductDimensions {
+            product_id: Uuid::new_v4(),
+            weight_g,
+            length_mm: dimensions.map(|d| d[0]),
+            width_mm: dimensions.map(|d| d[1]),
+            height_mm: dimensions.map(|d| d[2]),
+        }
+    }
+
+    #[test]
+    fn test_estimate_picks_smallest_fitting_box() {
+        let small = package_type("S", [200, 150, 100], 150, Some(3000));
+        let medium = package_type("M", [400, 300, 200], 300, Some(15000));
+        let large = package_type("L", [600, 400, 400], 500, None);
+        let items = vec![
+            (product(Some(1000), Some([100, 100, 100])), 2),
+            (product(Some(500), Some([50, 50, 50])), 4),
+        ];
+
+        let estimate = estimate_packing(&items, &[large, medium.clone(), small]);
+
+        assert_eq!(estimate.items_weight_g, 4000);
+        assert_eq!(estimate.items_volume_mm3, 2_500_000);
+        assert_eq!(estimate.package_type, Some(medium));
+        assert_eq!(estimate.gross_weight_g, 4300);
+        assert!(estimate.missing_dimensions.is_empty());
+    }
+
+    #[test]
+    fn test_estimate_respects_longest_item_and_weight_limit() {
+        let flat = package_type("Flat", [700, 500, 50], 200, Some(2000));
+        let tube = package_type("Tube", [1000, 100, 100], 250, Some(3000));
+        let items = vec![(product(Some(2500), Some([80, 900, 60])), 1)];
+
+        let estimate = estimate_packing(&items, &[flat, tube.clone()]);
+
+        assert_eq!(estimate.package_type, Some(tube));
+        assert_eq!(estimate.gross_weight_g, 2750);
+    }
+
+    #[test]
+    fn test_estimate_without_fitting_box_reports_missing_data() {
+        let inactive = PackageType {
+            status: "inactive".to_string(),
+            ..package_type("Huge", [2000, 2000, 2000], 1000, None)
+        };
+        let unknown = product(None, None);
+        let items = vec![(unknown.clone(), 3)];
+
+        let estimate = estimate_packing(&items, &[inactive]);
+
+        assert_eq!(estimate.package_type, None);
+        assert_eq!(estimate.gross_weight_g, 0);
+        assert_eq!(estimate.missing_dimensions, vec![unknown.product_id]);
+    }
+}
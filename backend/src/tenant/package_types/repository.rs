@@ -0,0 +1,187 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::package_types::dto::PackageTypeInput;
+use crate::tenant::package_types::model::PackageType;
+use crate::tenant::products::model::ProductDimensions;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PackageTypesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PackageType>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PackageType>)>;
+    async fn get_active(&self) -> RepositoryResult<Vec<PackageType>>;
+    async fn insert(&self, input: &PackageTypeInput, sub: Uuid) -> RepositoryResult<PackageType>;
+    async fn update(&self, id: Uuid, input: &PackageTypeInput) -> RepositoryResult<PackageType>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_product_dimensions(
+        &self,
+        product_ids: &[Uuid],
+    ) -> RepositoryResult<Vec<ProductDimensions>>;
+}
+
+#[async_trait]
+impl PackageTypesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PackageType> {
+        Ok(sqlx::query_as::<_, PackageType>(
+            "SELECT * FROM package_types WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PackageType>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM package_types WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let package_types = sqlx::query_as::<_, PackageType>(
+            r#"
+            SELECT *
+            FROM package_types
+            WHERE deleted_at IS NULL
+            ORDER BY name
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            package_types,
+        ))
+    }
+
+    async fn get_active(&self) -> RepositoryResult<Vec<PackageType>> {
+        Ok(sqlx::query_as::<_, PackageType>(
+            "SELECT * FROM package_types WHERE status = 'active' AND deleted_at IS NULL",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &PackageTypeInput, sub: Uuid) -> RepositoryResult<PackageType> {
+        Ok(sqlx::query_as::<_, PackageType>(
+            r#"
+            INSERT INTO package_types (name, length_mm, width_mm, height_mm, tare_weight_g,
+                                       max_weight_g, status, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(input.length_mm)
+        .bind(input.width_mm)
+        .bind(input.height_mm)
+        .bind(input.tare_weight_g)
+        .bind(input.max_weight_g)
+        .bind(&input.status)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(&self, id: Uuid, input: &PackageTypeInput) -> RepositoryResult<PackageType> {
+        Ok(sqlx::query_as::<_, PackageType>(
+            r#"
+            UPDATE package_types
+            SET name = $1,
+                length_mm = $2,
+                width_mm = $3,
+                height_mm = $4,
+                tare_weight_g = $5,
+                max_weight_g = $6,
+                status = $7
+            WHERE id = $8
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(input.length_mm)
+        .bind(input.width_mm)
+        .bind(input.height_mm)
+        .bind(input.tare_weight_g)
+        .bind(input.max_weight_g)
+        .bind(&input.status)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE package_types
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_product_dimensions(
+        &self,
+        product_ids: &[Uuid],
+    ) -> RepositoryResult<Vec<ProductDimensions>> {
+        Ok(sqlx::query_as::<_, ProductDimensions>(
+            r#"
+            SELECT id AS product_id, weight_g, length_mm, width_mm, height_mm
+            FROM products
+            WHERE id = ANY($1)
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(product_ids)
+        .fetch_all(self)
+        .await?)
+    }
+}
@@ -0,0 +1,258 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::package_types::PackageTypesModuleInterface;
+use crate::tenant::package_types::dto::{EstimatePacking, PackageTypeInput};
+use crate::tenant::package_types::model::{PackageType, PackingEstimate, estimate_packing};
+use axum::http::StatusCode;
+use serde_json::json;
+use std::collections::HashMap;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PackageTypesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PackageTypesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PackageTypesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PackageTypesServiceError> for AppError {
+    fn from(value: PackageTypesServiceError) -> Self {
+        match value {
+            PackageTypesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PackageTypesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PackageTypesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PackageTypesServiceResult<T> = Result<T, PackageTypesServiceError>;
+
+fn validate(payload: &PackageTypeInput) -> PackageTypesServiceResult<PackageTypeInput> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(PackageTypesServiceError::UnprocessableEntry(
+            "A megnevezés megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    if payload.length_mm <= 0 || payload.width_mm <= 0 || payload.height_mm <= 0 {
+        return Err(PackageTypesServiceError::UnprocessableEntry(
+            "A méreteknek pozitív számnak kell lenniük!",
+        ));
+    }
+    if payload.tare_weight_g < 0 || payload.max_weight_g.is_some_and(|v| v <= 0) {
+        return Err(PackageTypesServiceError::UnprocessableEntry(
+            "Hibás tára vagy teherbírás!",
+        ));
+    }
+    if !matches!(payload.status.as_str(), "active" | "inactive") {
+        return Err(PackageTypesServiceError::UnprocessableEntry(
+            "Hibás státusz!",
+        ));
+    }
+    Ok(PackageTypeInput {
+        name: name.to_string(),
+        ..payload.clone()
+    })
+}
+
+pub trait PackageTypesService {
+    fn get(&self, id: Uuid) -> impl Future<Output = PackageTypesServiceResult<PackageType>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = PackageTypesServiceResult<(PaginatorMeta, Vec<PackageType>)>> + Send;
+    fn create(
+        &self,
+        payload: &PackageTypeInput,
+    ) -> impl Future<Output = PackageTypesServiceResult<PackageType>> + Send;
+    fn update(
+        &self,
+        payload: &PackageTypeInput,
+    ) -> impl Future<Output = PackageTypesServiceResult<PackageType>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = PackageTypesServiceResult<()>> + Send;
+    fn estimate(
+        &self,
+        payload: &EstimatePacking,
+    ) -> impl Future<Output = PackageTypesServiceResult<PackingEstimate>> + Send;
+}
+
+impl<'a, T> PackageTypesService for Service<'a, T>
+where
+    T: PackageTypesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> PackageTypesServiceResult<PackageType> {
+        Ok(self
+            .module()
+            .package_types_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PackageTypesServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> PackageTypesServiceResult<(PaginatorMeta, Vec<PackageType>)> {
+        Ok(self
+            .module()
+            .package_types_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PackageTypesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &PackageTypeInput) -> PackageTypesServiceResult<PackageType> {
+        let input = validate(payload)?;
+        Ok(self
+            .module()
+            .package_types_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PackageTypesServiceError::Unauthorized)?,
+            )?
+            .insert(&input, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update(&self, payload: &PackageTypeInput) -> PackageTypesServiceResult<PackageType> {
+        let id = payload
+            .id
+            .ok_or(PackageTypesServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let input = validate(payload)?;
+        Ok(self
+            .module()
+            .package_types_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PackageTypesServiceError::Unauthorized)?,
+            )?
+            .update(id, &input)
+            .await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> PackageTypesServiceResult<()> {
+        Ok(self
+            .module()
+            .package_types_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PackageTypesServiceError::Unauthorized)?,
+            )?
+            .delete_by_id(id)
+            .await?)
+    }
+
+    async fn estimate(
+        &self,
+        payload: &EstimatePacking,
+    ) -> PackageTypesServiceResult<PackingEstimate> {
+        if payload.items.is_empty() || payload.items.iter().any(|item| item.quantity <= 0) {
+            return Err(PackageTypesServiceError::UnprocessableEntry(
+                "Legalább egy tétel megadása kötelező, pozitív mennyiséggel!",
+            ));
+        }
+        let repo = self.module().package_types_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PackageTypesServiceError::Unauthorized)?,
+        )?;
+
+        let mut quantities: HashMap<Uuid, i32> = HashMap::new();
+        for item in &payload.items {
+            let quantity = quantities.entry(item.product_id).or_default();
+            *quantity = quantity.checked_add(item.quantity).ok_or(
+                PackageTypesServiceError::UnprocessableEntry("Túl nagy mennyiség!"),
+            )?;
+        }
+        let product_ids: Vec<Uuid> = quantities.keys().copied().collect();
+        let products = repo.get_product_dimensions(&product_ids).await?;
+        if products.len() != product_ids.len() {
+            return Err(PackageTypesServiceError::UnprocessableEntry(
+                "Nem létező termék szerepel a tételek között!",
+            ));
+        }
+
+        let items: Vec<_> = products
+            .into_iter()
+            .map(|product| {
+                let quantity = quantities[&product.product_id];
+                (product, quantity)
+            })
+            .collect();
+
+        Ok(estimate_packing(&items, &repo.get_active().await?))
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PaymentProviderSettingsInput {
+    pub provider: String,
+    pub environment: String,
+    pub secret_key: Option<String>,
+    pub webhook_secret: Option<String>,
+    pub payee_email: Option<String>,
+    pub enabled: bool,
+}
+
+// NOTE: as stored, the secrets are encrypted
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentProviderCredentials {
+    pub secret_key: String,
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PayQuery {
+    pub tenant_id: Uuid,
+    pub receivable_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct WebhookQuery {
+    pub tenant_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPaymentLink {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub provider: String,
+    pub external_id: String,
+    pub checkout_url: String,
+    pub amount: BigDecimal,
+    pub currency_code: String,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,551 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::payment_links::PaymentLinksModuleInterface;
+use crate::tenant::payment_links::dto::{PayQuery, PaymentProviderSettingsInput, WebhookQuery};
+use crate::tenant::payment_links::model::WebhookRequest;
+use crate::tenant::payment_links::service::PaymentLinksService;
+use axum::extract::{Query, RawQuery, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect};
+use std::sync::Arc;
+
+pub async fn list<M: PaymentLinksModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payment_links_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payment_links_module.clone());
+    let result = map_handler_err(
+        service.get_by_receivable(payload.uuid).await,
+        payment_links_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payment_links_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn get_settings<M: PaymentLinksModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payment_links_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payment_links_module.clone());
+    let result =
+        map_handler_err(service.get_settings().await, payment_links_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payment_links_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_settings<M: PaymentLinksModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payment_links_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PaymentProviderSettingsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payment_links_module.clone());
+    let result = map_handler_err(
+        service.save_settings(&payload).await,
+        payment_links_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payment_links_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pay<M: PaymentLinksModuleInterface>(
+    State(payment_links_module): State<Arc<M>>,
+    Query(payload): Query<PayQuery>,
+) -> HandlerResult {
+    let service = Service::new(None, payment_links_module.clone());
+    let link = map_handler_err(service.pay(&payload).await, payment_links_module).await?;
+    Ok(Redirect::to(&link.checkout_url).into_response())
+}
+
+pub async fn webhook<M: PaymentLinksModuleInterface>(
+    State(payment_links_module): State<Arc<M>>,
+    Query(payload): Query<WebhookQuery>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    body: String,
+) -> HandlerResult {
+    let service = Service::new(None, payment_links_module.clone());
+    let request = WebhookRequest {
+        signature: headers
+            .get("Stripe-Signature")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        query,
+        body,
+    };
+    let result = map_handler_err(
+        service.handle_webhook(payload.tenant_id, &request).await,
+        payment_links_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payment_links_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::payment_links::model::{
+        Checkout, PayableInvoice, PaymentEvent, PaymentLink, PaymentProviderSettings, STATUS_PAID,
+        STATUS_PENDING,
+    };
+    use crate::tenant::payment_links::provider::{MockPaymentProviderClient, PaymentProviderError};
+    use crate::tenant::payment_links::{
+        self, repository::MockPaymentLinksRepository, tests::MockPaymentLinksModule,
+    };
+    use axum::body::Body;
+    use axum::http::header;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{TimeDelta, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(repo: MockPaymentLinksRepository, tenant_id: Uuid) -> MockPaymentLinksModule {
+        let repo = Arc::new(repo);
+        let mut payment_links_module = MockPaymentLinksModule::new();
+        payment_links_module
+            .expect_payment_links_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        payment_links_module
+    }
+
+    fn app(payment_links_module: MockPaymentLinksModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(payment_links::routes::routes(Arc::new(
+                payment_links_module,
+            ))),
+        )
+    }
+
+    fn settings(provider: &str) -> PaymentProviderSettings {
+        PaymentProviderSettings {
+            provider: provider.to_string(),
+            environment: "test".to_string(),
+            secret_key: "sk_test_123".to_string(),
+            webhook_secret: Some("whsec_123".to_string()),
+            payee_email: None,
+            enabled: true,
+            updated_by_id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn invoice(status: &str) -> PayableInvoice {
+        PayableInvoice {
+            id: Uuid::new_v4(),
+            document_number: "SZ-2026-00042".to_string(),
+            currency_code: "HUF".to_string(),
+            open_balance: BigDecimal::from_str("12700.00").unwrap(),
+            status: status.to_string(),
+            customer_email: Some("vevo@example.com".to_string()),
+            created_by_id: Uuid::new_v4(),
+        }
+    }
+
+    fn link(receivable_id: Uuid, status: &str) -> PaymentLink {
+        PaymentLink {
+            id: Uuid::new_v4(),
+            receivable_id,
+            provider: "stripe".to_string(),
+            external_id: "cs_test_1".to_string(),
+            checkout_url: "https://checkout.stripe.com/c/pay/cs_test_1".to_string(),
+            amount: BigDecimal::from_str("12700.00").unwrap(),
+            currency_code: "HUF".to_string(),
+            status: status.to_string(),
+            expires_at: Utc::now() + TimeDelta::hours(24),
+            paid_at: None,
+            payment_id: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pay_creates_checkout_and_redirects() {
+        let tenant_id = Uuid::new_v4();
+        let invoice = invoice("open");
+        let created = link(invoice.id, STATUS_PENDING);
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("stripe"))));
+        repo.expect_get_payable_invoice()
+            .with(eq(invoice.id))
+            .times(1)
+            .returning({
+                let invoice = invoice.clone();
+                move |_| Ok(invoice.clone())
+            });
+        repo.expect_get_reusable()
+            .times(1)
+            .withf(|_, provider, amount, _| {
+                provider == "stripe" && *amount == BigDecimal::from_str("12700").unwrap()
+            })
+            .returning(|_, _, _, _| Ok(None));
+        repo.expect_insert()
+            .times(1)
+            .withf({
+                let created_by_id = invoice.created_by_id;
+                move |input, sub| input.external_id == "cs_test_1" && *sub == created_by_id
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _| Ok(created.clone())
+            });
+
+        let mut client = MockPaymentProviderClient::new();
+        client
+            .expect_create_checkout()
+            .times(1)
+            .withf(move |request| {
+                request.document_number == "SZ-2026-00042"
+                    && request.customer_email.as_deref() == Some("vevo@example.com")
+                    && request.callback_url
+                        == format!(
+                            "https://example.com/api/payment_links/webhook?tenant_id={tenant_id}"
+                        )
+            })
+            .returning(|_| {
+                Ok(Checkout {
+                    external_id: "cs_test_1".to_string(),
+                    checkout_url: "https://checkout.stripe.com/c/pay/cs_test_1".to_string(),
+                })
+            });
+        let client = Arc::new(client);
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        payment_links_module
+            .expect_payment_provider_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/api/payment_links/pay?tenant_id={tenant_id}&receivable_id={}",
+                        invoice.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            "https://checkout.stripe.com/c/pay/cs_test_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pay_reuses_pending_checkout() {
+        let tenant_id = Uuid::new_v4();
+        let invoice = invoice("open");
+        let pending = link(invoice.id, STATUS_PENDING);
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("stripe"))));
+        repo.expect_get_payable_invoice().times(1).returning({
+            let invoice = invoice.clone();
+            move |_| Ok(invoice.clone())
+        });
+        repo.expect_get_reusable().times(1).returning({
+            let pending = pending.clone();
+            move |_, _, _, _| Ok(Some(pending.clone()))
+        });
+        repo.expect_insert().never();
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_payment_provider_client()
+            .never();
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/api/payment_links/pay?tenant_id={tenant_id}&receivable_id={}",
+                        invoice.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            pending.checkout_url.as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pay_rejects_settled_invoice() {
+        let tenant_id = Uuid::new_v4();
+        let invoice = invoice("paid");
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("stripe"))));
+        repo.expect_get_payable_invoice().times(1).returning({
+            let invoice = invoice.clone();
+            move |_| Ok(invoice.clone())
+        });
+        repo.expect_get_reusable().never();
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_payment_provider_client()
+            .never();
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/api/payment_links/pay?tenant_id={tenant_id}&receivable_id={}",
+                        invoice.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_marks_invoice_paid() {
+        let tenant_id = Uuid::new_v4();
+        let pending = link(Uuid::new_v4(), STATUS_PENDING);
+        let paid = PaymentLink {
+            status: STATUS_PAID.to_string(),
+            paid_at: Some(Utc::now()),
+            payment_id: Some(Uuid::new_v4()),
+            ..pending.clone()
+        };
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("stripe"))));
+        repo.expect_get_by_external_id()
+            .withf(|provider, external_id| provider == "stripe" && external_id == "cs_test_1")
+            .times(1)
+            .returning({
+                let pending = pending.clone();
+                move |_, _| Ok(Some(pending.clone()))
+            });
+        repo.expect_mark_paid()
+            .with(eq(pending.id))
+            .times(1)
+            .returning({
+                let paid = paid.clone();
+                move |_| Ok(Some(paid.clone()))
+            });
+        repo.expect_mark_closed().never();
+
+        let mut client = MockPaymentProviderClient::new();
+        client
+            .expect_payment_event()
+            .times(1)
+            .withf(|request| {
+                request.signature.as_deref() == Some("t=1,v1=abc")
+                    && request.body.contains("checkout.session.completed")
+            })
+            .returning(|_| {
+                Ok(Some(PaymentEvent {
+                    external_id: "cs_test_1".to_string(),
+                    status: STATUS_PAID,
+                }))
+            });
+        let client = Arc::new(client);
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_payment_provider_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/payment_links/webhook?tenant_id={tenant_id}"))
+                    .header("Stripe-Signature", "t=1,v1=abc")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(
+                        json!({"type": "checkout.session.completed"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": paid})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_rejects_invalid_signature() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("stripe"))));
+        repo.expect_get_by_external_id().never();
+        repo.expect_mark_paid().never();
+
+        let mut client = MockPaymentProviderClient::new();
+        client
+            .expect_payment_event()
+            .times(1)
+            .returning(|_| Err(PaymentProviderError::InvalidNotification("hibás aláírás")));
+        let client = Arc::new(client);
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_payment_provider_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/payment_links/webhook?tenant_id={tenant_id}"))
+                    .header("Stripe-Signature", "t=1,v1=forged")
+                    .body(Body::from("{}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_requires_webhook_secret_for_stripe() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockPaymentLinksRepository::new();
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(Some(settings("barion"))));
+        repo.expect_save_settings().never();
+
+        let mut payment_links_module = module(repo, tenant_id);
+        payment_links_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = app(payment_links_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("PUT")
+                    .uri("/api/payment_links/settings/update")
+                    .body(Body::from(
+                        json!({
+                            "provider": "stripe",
+                            "environment": "production",
+                            "secret_key": "sk_live_123",
+                            "webhook_secret": null,
+                            "payee_email": null,
+                            "enabled": true
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
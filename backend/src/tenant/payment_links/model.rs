@@ -0,0 +1,109 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const PROVIDER_STRIPE: &str = "stripe";
+pub const PROVIDER_BARION: &str = "barion";
+pub const PROVIDERS: [&str; 2] = [PROVIDER_STRIPE, PROVIDER_BARION];
+
+pub const ENVIRONMENTS: [&str; 2] = ["test", "production"];
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_PAID: &str = "paid";
+pub const STATUS_EXPIRED: &str = "expired";
+pub const STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PaymentProviderSettings {
+    pub provider: String,
+    pub environment: String,
+    #[serde(skip)]
+    pub secret_key: String,
+    #[serde(skip)]
+    pub webhook_secret: Option<String>,
+    pub payee_email: Option<String>,
+    pub enabled: bool,
+    pub updated_by_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PaymentLink {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub provider: String,
+    pub external_id: String,
+    pub checkout_url: String,
+    pub amount: BigDecimal,
+    pub currency_code: String,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub payment_id: Option<Uuid>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PayableInvoice {
+    pub id: Uuid,
+    pub document_number: String,
+    pub currency_code: String,
+    pub open_balance: BigDecimal,
+    pub status: String,
+    pub customer_email: Option<String>,
+    pub created_by_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckoutRequest {
+    pub reference: Uuid,
+    pub document_number: String,
+    pub amount: BigDecimal,
+    pub currency_code: String,
+    pub customer_email: Option<String>,
+    pub return_url: String,
+    pub callback_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkout {
+    pub external_id: String,
+    pub checkout_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookRequest {
+    pub signature: Option<String>,
+    pub query: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentEvent {
+    pub external_id: String,
+    pub status: &'static str,
+}
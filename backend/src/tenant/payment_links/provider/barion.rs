@@ -0,0 +1,361 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::decrypt_secret;
+use crate::tenant::payment_links::model::{
+    Checkout, CheckoutRequest, PaymentEvent, PaymentProviderSettings, STATUS_EXPIRED,
+    STATUS_FAILED, STATUS_PAID, WebhookRequest,
+};
+use crate::tenant::payment_links::provider::{PaymentProviderClient, PaymentProviderError};
+use async_trait::async_trait;
+use bigdecimal::ToPrimitive;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const TEST_API_URL: &str = "https://api.test.barion.com";
+pub const PRODUCTION_API_URL: &str = "https://api.barion.com";
+
+// NOTE: Barion Payment API v2, see https://docs.barion.com/Payment-Start-v2
+pub struct BarionClient {
+    api_url: &'static str,
+    pos_key: String,
+    payee_email: String,
+    http: reqwest::Client,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct BarionItem<'a> {
+    name: &'a str,
+    description: &'a str,
+    quantity: i32,
+    unit: &'static str,
+    unit_price: f64,
+    item_total: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct BarionTransaction<'a> {
+    #[serde(rename = "POSTransactionId")]
+    pos_transaction_id: String,
+    payee: &'a str,
+    total: f64,
+    comment: &'a str,
+    items: Vec<BarionItem<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct StartPaymentRequest<'a> {
+    #[serde(rename = "POSKey")]
+    pos_key: &'a str,
+    payment_type: &'static str,
+    payment_window: String,
+    guest_check_out: bool,
+    funding_sources: [&'static str; 1],
+    payment_request_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payer_hint: Option<&'a str>,
+    redirect_url: &'a str,
+    callback_url: &'a str,
+    locale: &'static str,
+    currency: &'a str,
+    transactions: Vec<BarionTransaction<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BarionError {
+    error_code: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StartPaymentResponse {
+    payment_id: Option<String>,
+    gateway_url: Option<String>,
+    #[serde(default)]
+    errors: Vec<BarionError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PaymentStateResponse {
+    payment_id: Option<String>,
+    status: Option<String>,
+    #[serde(default)]
+    errors: Vec<BarionError>,
+}
+
+// NOTE: TimeSpan format (d.hh:mm:ss), Barion requires at least one minute
+fn payment_window(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (expires_at - now).num_seconds().max(60);
+    format!(
+        "{}.{:02}:{:02}:{:02}",
+        seconds / 86400,
+        seconds % 86400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn errors_to_string(errors: &[BarionError]) -> String {
+    errors
+        .iter()
+        .map(|e| {
+            format!(
+                "{}: {}",
+                e.error_code.as_deref().unwrap_or_default(),
+                e.description
+                    .as_deref()
+                    .or(e.title.as_deref())
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// NOTE: the callback only carries the payment id, as a query or form parameter
+fn callback_payment_id(request: &WebhookRequest) -> Option<String> {
+    [request.query.as_deref(), Some(request.body.as_str())]
+        .into_iter()
+        .flatten()
+        .flat_map(|params| params.split('&'))
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.eq_ignore_ascii_case("paymentid"))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn event_from_state(
+    response: PaymentStateResponse,
+) -> Result<Option<PaymentEvent>, PaymentProviderError> {
+    if !response.errors.is_empty() {
+        return Err(PaymentProviderError::Rejected(errors_to_string(
+            &response.errors,
+        )));
+    }
+    let external_id = response
+        .payment_id
+        .ok_or_else(|| PaymentProviderError::Rejected("missing payment id".to_string()))?;
+    let status = match response.status.as_deref() {
+        Some("Succeeded") => STATUS_PAID,
+        Some("Canceled") | Some("Expired") => STATUS_EXPIRED,
+        Some("Failed") => STATUS_FAILED,
+        _ => return Ok(None),
+    };
+    Ok(Some(PaymentEvent {
+        external_id,
+        status,
+    }))
+}
+
+impl BarionClient {
+    pub fn new(
+        settings: &PaymentProviderSettings,
+        timeout: Duration,
+    ) -> Result<Self, PaymentProviderError> {
+        Ok(Self {
+            api_url: match settings.environment.as_str() {
+                "production" => PRODUCTION_API_URL,
+                _ => TEST_API_URL,
+            },
+            pos_key: decrypt_secret(&settings.secret_key)?,
+            payee_email: settings.payee_email.clone().ok_or(
+                PaymentProviderError::Configuration("hiányzó kedvezményezett"),
+            )?,
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentProviderClient for BarionClient {
+    async fn create_checkout(
+        &self,
+        request: &CheckoutRequest,
+    ) -> Result<Checkout, PaymentProviderError> {
+        // NOTE: forint amounts are accepted without decimals only
+        let scale = if request.currency_code == "HUF" { 0 } else { 2 };
+        let amount = request
+            .amount
+            .round(scale)
+            .to_f64()
+            .ok_or_else(|| PaymentProviderError::Rejected("invalid amount".to_string()))?;
+        let reference = request.reference.simple().to_string();
+        let payment = StartPaymentRequest {
+            pos_key: &self.pos_key,
+            payment_type: "Immediate",
+            payment_window: payment_window(request.expires_at, Utc::now()),
+            guest_check_out: true,
+            funding_sources: ["All"],
+            payment_request_id: reference.clone(),
+            payer_hint: request.customer_email.as_deref(),
+            redirect_url: &request.return_url,
+            callback_url: &request.callback_url,
+            locale: "hu-HU",
+            currency: &request.currency_code,
+            transactions: vec![BarionTransaction {
+                pos_transaction_id: reference,
+                payee: &self.payee_email,
+                total: amount,
+                comment: &request.document_number,
+                items: vec![BarionItem {
+                    name: &request.document_number,
+                    description: &request.document_number,
+                    quantity: 1,
+                    unit: "db",
+                    unit_price: amount,
+                    item_total: amount,
+                }],
+            }],
+        };
+        // NOTE: validation errors come with a 400 status and the details in the body
+        let response = self
+            .http
+            .post(format!("{}/v2/Payment/Start", self.api_url))
+            .json(&payment)
+            .send()
+            .await?
+            .json::<StartPaymentResponse>()
+            .await?;
+        if !response.errors.is_empty() {
+            return Err(PaymentProviderError::Rejected(errors_to_string(
+                &response.errors,
+            )));
+        }
+        match (response.payment_id, response.gateway_url) {
+            (Some(external_id), Some(checkout_url)) => Ok(Checkout {
+                external_id,
+                checkout_url,
+            }),
+            _ => Err(PaymentProviderError::Rejected(
+                "missing payment id".to_string(),
+            )),
+        }
+    }
+
+    // NOTE: the callback is not signed, the state is always queried back with the POS key
+    async fn payment_event(
+        &self,
+        request: &WebhookRequest,
+    ) -> Result<Option<PaymentEvent>, PaymentProviderError> {
+        let payment_id = callback_payment_id(request).ok_or(
+            PaymentProviderError::InvalidNotification("hiányzó fizetés azonosító"),
+        )?;
+        let response = self
+            .http
+            .get(format!("{}/v2/Payment/GetPaymentState", self.api_url))
+            .query(&[
+                ("POSKey", self.pos_key.as_str()),
+                ("PaymentId", payment_id.as_str()),
+            ])
+            .send()
+            .await?
+            .json::<PaymentStateResponse>()
+            .await?;
+        event_from_state(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use serde_json::json;
+
+    #[test]
+    fn test_payment_window() {
+        let now = Utc::now();
+        assert_eq!(
+            payment_window(now + TimeDelta::hours(24), now),
+            "1.00:00:00"
+        );
+        assert_eq!(
+            payment_window(now + TimeDelta::seconds(3723), now),
+            "0.01:02:03"
+        );
+        assert_eq!(payment_window(now, now), "0.00:01:00");
+    }
+
+    #[test]
+    fn test_callback_payment_id() {
+        let request = |query: Option<&str>, body: &str| WebhookRequest {
+            signature: None,
+            query: query.map(str::to_string),
+            body: body.to_string(),
+        };
+        assert_eq!(
+            callback_payment_id(&request(
+                Some("tenant_id=8c1f&paymentId=64157032d3dc4a7ba30e5b9f3e9e2b4c"),
+                ""
+            )),
+            Some("64157032d3dc4a7ba30e5b9f3e9e2b4c".to_string())
+        );
+        assert_eq!(
+            callback_payment_id(&request(None, "PaymentId=64157032d3dc4a7ba30e5b9f3e9e2b4c")),
+            Some("64157032d3dc4a7ba30e5b9f3e9e2b4c".to_string())
+        );
+        assert_eq!(
+            callback_payment_id(&request(None, "paymentId=1%27%20OR")),
+            None
+        );
+        assert_eq!(callback_payment_id(&request(None, "")), None);
+    }
+
+    #[test]
+    fn test_event_from_state() {
+        let state = |status: &str| -> PaymentStateResponse {
+            serde_json::from_value(json!({
+                "PaymentId": "64157032d3dc4a7ba30e5b9f3e9e2b4c",
+                "Status": status,
+                "Errors": []
+            }))
+            .unwrap()
+        };
+        assert_eq!(
+            event_from_state(state("Succeeded")).unwrap(),
+            Some(PaymentEvent {
+                external_id: "64157032d3dc4a7ba30e5b9f3e9e2b4c".to_string(),
+                status: STATUS_PAID,
+            })
+        );
+        assert_eq!(
+            event_from_state(state("Expired"))
+                .unwrap()
+                .map(|event| event.status),
+            Some(STATUS_EXPIRED)
+        );
+        assert_eq!(event_from_state(state("Prepared")).unwrap(), None);
+
+        let rejected: PaymentStateResponse = serde_json::from_value(json!({
+            "Errors": [{"ErrorCode": "InvalidPosKey", "Title": "Invalid POSKey", "Description": "Invalid POSKey"}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            event_from_state(rejected),
+            Err(PaymentProviderError::Rejected(message)) if message == "InvalidPosKey: Invalid POSKey"
+        ));
+    }
+}
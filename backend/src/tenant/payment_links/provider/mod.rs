@@ -0,0 +1,82 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::CryptoError;
+use crate::tenant::payment_links::model::{
+    Checkout, CheckoutRequest, PROVIDER_BARION, PROVIDER_STRIPE, PaymentEvent,
+    PaymentProviderSettings, WebhookRequest,
+};
+use crate::tenant::payment_links::provider::barion::BarionClient;
+use crate::tenant::payment_links::provider::stripe::StripeClient;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+pub mod barion;
+pub mod stripe;
+
+#[derive(Debug, Error)]
+pub enum PaymentProviderError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("A fizetési szolgáltató elutasította a kérést: {0}")]
+    Rejected(String),
+
+    #[error("Nem támogatott fizetési szolgáltató: {0}")]
+    Unsupported(String),
+
+    #[error("Hibás fizetési beállítások: {0}")]
+    Configuration(&'static str),
+
+    #[error("Érvénytelen értesítés: {0}")]
+    InvalidNotification(&'static str),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PaymentProviderClient: Send + Sync {
+    async fn create_checkout(
+        &self,
+        request: &CheckoutRequest,
+    ) -> Result<Checkout, PaymentProviderError>;
+    /// Authenticates the provider notification and returns the payment state change it
+    /// reports, `None` for events unrelated to hosted checkouts
+    async fn payment_event(
+        &self,
+        request: &WebhookRequest,
+    ) -> Result<Option<PaymentEvent>, PaymentProviderError>;
+}
+
+pub fn payment_provider_client(
+    settings: &PaymentProviderSettings,
+    timeout: Duration,
+) -> Result<Arc<dyn PaymentProviderClient + Send + Sync>, PaymentProviderError> {
+    match settings.provider.as_str() {
+        PROVIDER_STRIPE => Ok(Arc::new(StripeClient::new(settings, timeout)?)),
+        PROVIDER_BARION => Ok(Arc::new(BarionClient::new(settings, timeout)?)),
+        other => Err(PaymentProviderError::Unsupported(other.to_string())),
+    }
+}
@@ -0,0 +1,371 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::decrypt_secret;
+use crate::tenant::payment_links::model::{
+    Checkout, CheckoutRequest, PaymentEvent, PaymentProviderSettings, STATUS_EXPIRED,
+    STATUS_FAILED, STATUS_PAID, WebhookRequest,
+};
+use crate::tenant::payment_links::provider::{PaymentProviderClient, PaymentProviderError};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+pub const API_URL: &str = "https://api.stripe.com/v1";
+
+const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+// NOTE: https://docs.stripe.com/currencies#zero-decimal
+const ZERO_DECIMAL_CURRENCIES: [&str; 16] = [
+    "BIF", "CLP", "DJF", "GNF", "JPY", "KMF", "KRW", "MGA", "PYG", "RWF", "UGX", "VND", "VUV",
+    "XAF", "XOF", "XPF",
+];
+
+// NOTE: Stripe Checkout Sessions API, see https://docs.stripe.com/api/checkout/sessions
+pub struct StripeClient {
+    secret_key: String,
+    webhook_secret: Option<String>,
+    http: reqwest::Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeSession {
+    id: String,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeErrorResponse {
+    error: StripeError,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeError {
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: StripeEventObject,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventObject {
+    id: String,
+    payment_status: Option<String>,
+}
+
+// NOTE: HUF is a two-decimal currency at Stripe but only whole forint amounts are accepted
+fn unit_amount(amount: &BigDecimal, currency_code: &str) -> Option<i64> {
+    let currency_code = currency_code.to_uppercase();
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency_code.as_str()) {
+        amount.round(0).to_i64()
+    } else if currency_code == "HUF" {
+        amount.round(0).to_i64()?.checked_mul(100)
+    } else {
+        (amount * BigDecimal::from(100)).round(0).to_i64()
+    }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// NOTE: the Stripe-Signature header is `t=<timestamp>,v1=<signature>[,v1=<signature>]`, the
+// signature is the hex encoded HMAC-SHA256 of `<timestamp>.<payload>` with the endpoint secret
+fn verify_signature(
+    webhook_secret: &str,
+    header: &str,
+    payload: &str,
+    now: i64,
+) -> Result<(), PaymentProviderError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or(PaymentProviderError::InvalidNotification(
+        "hiányzó időbélyeg",
+    ))?;
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return Err(PaymentProviderError::InvalidNotification("lejárt aláírás"));
+    }
+    let signed_payload = format!("{timestamp}.{payload}");
+    let valid = signatures
+        .into_iter()
+        .filter_map(decode_hex)
+        .any(|signature| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(webhook_secret.as_bytes())
+                .expect("HMAC accepts keys of any length");
+            mac.update(signed_payload.as_bytes());
+            mac.verify_slice(&signature).is_ok()
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(PaymentProviderError::InvalidNotification("hibás aláírás"))
+    }
+}
+
+fn event_from_payload(payload: &str) -> Result<Option<PaymentEvent>, PaymentProviderError> {
+    let event: StripeEvent = serde_json::from_str(payload)
+        .map_err(|_| PaymentProviderError::InvalidNotification("értelmezhetetlen esemény"))?;
+    let session = event.data.object;
+    let status = match event.event_type.as_str() {
+        "checkout.session.completed" if session.payment_status.as_deref() == Some("paid") => {
+            STATUS_PAID
+        }
+        "checkout.session.async_payment_succeeded" => STATUS_PAID,
+        "checkout.session.async_payment_failed" => STATUS_FAILED,
+        "checkout.session.expired" => STATUS_EXPIRED,
+        _ => return Ok(None),
+    };
+    Ok(Some(PaymentEvent {
+        external_id: session.id,
+        status,
+    }))
+}
+
+impl StripeClient {
+    pub fn new(
+        settings: &PaymentProviderSettings,
+        timeout: Duration,
+    ) -> Result<Self, PaymentProviderError> {
+        Ok(Self {
+            secret_key: decrypt_secret(&settings.secret_key)?,
+            webhook_secret: settings
+                .webhook_secret
+                .as_deref()
+                .map(decrypt_secret)
+                .transpose()?,
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentProviderClient for StripeClient {
+    async fn create_checkout(
+        &self,
+        request: &CheckoutRequest,
+    ) -> Result<Checkout, PaymentProviderError> {
+        let unit_amount = unit_amount(&request.amount, &request.currency_code)
+            .filter(|amount| *amount > 0)
+            .ok_or_else(|| PaymentProviderError::Rejected("invalid amount".to_string()))?;
+        let reference = request.reference.to_string();
+        let mut form = vec![
+            ("mode", "payment".to_string()),
+            ("client_reference_id", reference.clone()),
+            ("metadata[payment_link_id]", reference),
+            ("success_url", request.return_url.clone()),
+            ("cancel_url", request.return_url.clone()),
+            ("expires_at", request.expires_at.timestamp().to_string()),
+            ("line_items[0][quantity]", "1".to_string()),
+            (
+                "line_items[0][price_data][currency]",
+                request.currency_code.to_lowercase(),
+            ),
+            (
+                "line_items[0][price_data][unit_amount]",
+                unit_amount.to_string(),
+            ),
+            (
+                "line_items[0][price_data][product_data][name]",
+                request.document_number.clone(),
+            ),
+        ];
+        if let Some(customer_email) = &request.customer_email {
+            form.push(("customer_email", customer_email.clone()));
+        }
+        let response = self
+            .http
+            .post(format!("{API_URL}/checkout/sessions"))
+            .bearer_auth(&self.secret_key)
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let message = response
+                .json::<StripeErrorResponse>()
+                .await
+                .ok()
+                .and_then(|response| response.error.message)
+                .unwrap_or_else(|| "unknown error".to_string());
+            return Err(PaymentProviderError::Rejected(message));
+        }
+        let session = response.json::<StripeSession>().await?;
+        Ok(Checkout {
+            checkout_url: session
+                .url
+                .ok_or_else(|| PaymentProviderError::Rejected("missing url".to_string()))?,
+            external_id: session.id,
+        })
+    }
+
+    async fn payment_event(
+        &self,
+        request: &WebhookRequest,
+    ) -> Result<Option<PaymentEvent>, PaymentProviderError> {
+        let webhook_secret = self
+            .webhook_secret
+            .as_deref()
+            .ok_or(PaymentProviderError::Configuration("hiányzó webhook titok"))?;
+        let signature = request
+            .signature
+            .as_deref()
+            .ok_or(PaymentProviderError::InvalidNotification("hiányzó aláírás"))?;
+        verify_signature(
+            webhook_secret,
+            signature,
+            &request.body,
+            Utc::now().timestamp(),
+        )?;
+        event_from_payload(&request.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{timestamp}.{payload}").as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_unit_amount() {
+        let amount = BigDecimal::from_str("12345.67").unwrap();
+        assert_eq!(unit_amount(&amount, "EUR"), Some(1234567));
+        assert_eq!(unit_amount(&amount, "HUF"), Some(1234600));
+        assert_eq!(unit_amount(&amount, "jpy"), Some(12346));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let payload = r#"{"id":"evt_1"}"#;
+        let now = 1760608800;
+        let signature = sign("whsec_test", now - 10, payload);
+
+        assert!(
+            verify_signature(
+                "whsec_test",
+                &format!("t={},v1=00ff,v1={signature}", now - 10),
+                payload,
+                now
+            )
+            .is_ok()
+        );
+        assert!(matches!(
+            verify_signature(
+                "whsec_other",
+                &format!("t={},v1={signature}", now - 10),
+                payload,
+                now
+            ),
+            Err(PaymentProviderError::InvalidNotification("hibás aláírás"))
+        ));
+        assert!(matches!(
+            verify_signature(
+                "whsec_test",
+                &format!("t={},v1={signature}", now - 10),
+                r#"{"id":"evt_2"}"#,
+                now
+            ),
+            Err(PaymentProviderError::InvalidNotification("hibás aláírás"))
+        ));
+        assert!(matches!(
+            verify_signature(
+                "whsec_test",
+                &format!("t={},v1={signature}", now - 10),
+                payload,
+                now + SIGNATURE_TOLERANCE_SECS
+            ),
+            Err(PaymentProviderError::InvalidNotification("lejárt aláírás"))
+        ));
+        assert!(matches!(
+            verify_signature("whsec_test", &format!("v1={signature}"), payload, now),
+            Err(PaymentProviderError::InvalidNotification(
+                "hiányzó időbélyeg"
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_event_from_payload() {
+        let event = |event_type: &str, payment_status: &str| {
+            json!({
+                "id": "evt_1",
+                "type": event_type,
+                "data": {"object": {"id": "cs_test_1", "object": "checkout.session", "payment_status": payment_status}}
+            })
+            .to_string()
+        };
+        assert_eq!(
+            event_from_payload(&event("checkout.session.completed", "paid")).unwrap(),
+            Some(PaymentEvent {
+                external_id: "cs_test_1".to_string(),
+                status: STATUS_PAID,
+            })
+        );
+        assert_eq!(
+            event_from_payload(&event("checkout.session.completed", "unpaid")).unwrap(),
+            None
+        );
+        assert_eq!(
+            event_from_payload(&event("checkout.session.expired", "unpaid"))
+                .unwrap()
+                .map(|event| event.status),
+            Some(STATUS_EXPIRED)
+        );
+        assert_eq!(
+            event_from_payload(&event("payment_intent.created", "unpaid")).unwrap(),
+            None
+        );
+        assert!(event_from_payload("not json").is_err());
+    }
+}
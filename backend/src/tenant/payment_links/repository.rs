@@ -0,0 +1,304 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::payment_links::dto::{
+    NewPaymentLink, PaymentProviderCredentials, PaymentProviderSettingsInput,
+};
+use crate::tenant::payment_links::model::{PayableInvoice, PaymentLink, PaymentProviderSettings};
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PaymentLinksRepository: Send + Sync {
+    async fn get_settings(&self) -> RepositoryResult<Option<PaymentProviderSettings>>;
+    async fn save_settings(
+        &self,
+        input: &PaymentProviderSettingsInput,
+        credentials: &PaymentProviderCredentials,
+        sub: Uuid,
+    ) -> RepositoryResult<PaymentProviderSettings>;
+    async fn get_payable_invoice(&self, receivable_id: Uuid) -> RepositoryResult<PayableInvoice>;
+    async fn get_by_receivable(&self, receivable_id: Uuid) -> RepositoryResult<Vec<PaymentLink>>;
+    async fn get_reusable(
+        &self,
+        receivable_id: Uuid,
+        provider: &str,
+        amount: &BigDecimal,
+        valid_until: DateTime<Utc>,
+    ) -> RepositoryResult<Option<PaymentLink>>;
+    async fn get_by_external_id(
+        &self,
+        provider: &str,
+        external_id: &str,
+    ) -> RepositoryResult<Option<PaymentLink>>;
+    async fn insert(&self, input: &NewPaymentLink, sub: Uuid) -> RepositoryResult<PaymentLink>;
+    async fn mark_paid(&self, id: Uuid) -> RepositoryResult<Option<PaymentLink>>;
+    async fn mark_closed(&self, id: Uuid, status: &str) -> RepositoryResult<Option<PaymentLink>>;
+}
+
+#[async_trait]
+impl PaymentLinksRepository for PgPool {
+    async fn get_settings(&self) -> RepositoryResult<Option<PaymentProviderSettings>> {
+        Ok(
+            sqlx::query_as::<_, PaymentProviderSettings>("SELECT * FROM payment_provider_settings")
+                .fetch_optional(self)
+                .await?,
+        )
+    }
+
+    async fn save_settings(
+        &self,
+        input: &PaymentProviderSettingsInput,
+        credentials: &PaymentProviderCredentials,
+        sub: Uuid,
+    ) -> RepositoryResult<PaymentProviderSettings> {
+        Ok(sqlx::query_as::<_, PaymentProviderSettings>(
+            r#"
+            INSERT INTO payment_provider_settings (provider, environment, secret_key,
+                                                   webhook_secret, payee_email, enabled,
+                                                   updated_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE
+                SET provider       = EXCLUDED.provider,
+                    environment    = EXCLUDED.environment,
+                    secret_key     = EXCLUDED.secret_key,
+                    webhook_secret = EXCLUDED.webhook_secret,
+                    payee_email    = EXCLUDED.payee_email,
+                    enabled        = EXCLUDED.enabled,
+                    updated_by_id  = EXCLUDED.updated_by_id
+            RETURNING *
+            "#,
+        )
+        .bind(&input.provider)
+        .bind(&input.environment)
+        .bind(&credentials.secret_key)
+        .bind(&credentials.webhook_secret)
+        .bind(&input.payee_email)
+        .bind(input.enabled)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_payable_invoice(&self, receivable_id: Uuid) -> RepositoryResult<PayableInvoice> {
+        Ok(sqlx::query_as::<_, PayableInvoice>(
+            r#"
+            SELECT receivables.id,
+                   receivables.document_number,
+                   receivables.currency_code,
+                   receivables.amount - receivables.paid_amount
+                       - receivables.credited_amount AS open_balance,
+                   receivables.status,
+                   NULLIF(customers.email, '') AS customer_email,
+                   receivables.created_by_id
+            FROM receivables
+            JOIN customers ON receivables.customer_id = customers.id
+            WHERE receivables.id = $1
+              AND receivables.deleted_at IS NULL
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_receivable(&self, receivable_id: Uuid) -> RepositoryResult<Vec<PaymentLink>> {
+        Ok(sqlx::query_as::<_, PaymentLink>(
+            "SELECT * FROM payment_links WHERE receivable_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_reusable(
+        &self,
+        receivable_id: Uuid,
+        provider: &str,
+        amount: &BigDecimal,
+        valid_until: DateTime<Utc>,
+    ) -> RepositoryResult<Option<PaymentLink>> {
+        Ok(sqlx::query_as::<_, PaymentLink>(
+            r#"
+            SELECT *
+            FROM payment_links
+            WHERE receivable_id = $1
+              AND provider = $2
+              AND amount = $3
+              AND status = 'pending'
+              AND expires_at > $4
+            ORDER BY expires_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(receivable_id)
+        .bind(provider)
+        .bind(amount)
+        .bind(valid_until)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn get_by_external_id(
+        &self,
+        provider: &str,
+        external_id: &str,
+    ) -> RepositoryResult<Option<PaymentLink>> {
+        Ok(sqlx::query_as::<_, PaymentLink>(
+            "SELECT * FROM payment_links WHERE provider = $1 AND external_id = $2",
+        )
+        .bind(provider)
+        .bind(external_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &NewPaymentLink, sub: Uuid) -> RepositoryResult<PaymentLink> {
+        Ok(sqlx::query_as::<_, PaymentLink>(
+            r#"
+            INSERT INTO payment_links (id, receivable_id, provider, external_id, checkout_url,
+                                       amount, currency_code, expires_at, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+        )
+        .bind(input.id)
+        .bind(input.receivable_id)
+        .bind(&input.provider)
+        .bind(&input.external_id)
+        .bind(&input.checkout_url)
+        .bind(&input.amount)
+        .bind(&input.currency_code)
+        .bind(input.expires_at)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    // NOTE: the payment is recorded like a card payment on the invoice, an amount exceeding the
+    // open balance (or paid on an invoice settled in the meantime) remains customer credit
+    async fn mark_paid(&self, id: Uuid) -> RepositoryResult<Option<PaymentLink>> {
+        let mut tx = self.begin().await?;
+        let Some(link) = sqlx::query_as::<_, PaymentLink>(
+            "SELECT * FROM payment_links WHERE id = $1 AND status = 'pending' FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.commit().await?;
+            return Ok(None);
+        };
+
+        let (customer_id, open_balance, status): (Uuid, BigDecimal, String) = sqlx::query_as(
+            r#"
+            SELECT customer_id, amount - paid_amount - credited_amount, status
+            FROM receivables
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(link.receivable_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let (receivable_id, allocated_amount) = if status == "open" {
+            let allocated_amount = open_balance
+                .max(BigDecimal::zero())
+                .min(link.amount.clone());
+            sqlx::query(
+                r#"
+                UPDATE receivables
+                SET paid_amount = paid_amount + $1,
+                    status = CASE
+                        WHEN paid_amount + $1 + credited_amount >= amount THEN 'paid'
+                        ELSE status
+                    END
+                WHERE id = $2
+                "#,
+            )
+            .bind(&allocated_amount)
+            .bind(link.receivable_id)
+            .execute(&mut *tx)
+            .await?;
+            (Some(link.receivable_id), allocated_amount)
+        } else {
+            (None, BigDecimal::zero())
+        };
+
+        let (payment_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO payments (customer_id, receivable_id, payment_method, amount,
+                                  allocated_amount, currency_code, paid_on, reference, note,
+                                  created_by_id)
+            VALUES ($1, $2, 'card', $3, $4, $5, CURRENT_DATE, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(customer_id)
+        .bind(receivable_id)
+        .bind(&link.amount)
+        .bind(&allocated_amount)
+        .bind(&link.currency_code)
+        .bind(&link.external_id)
+        .bind(format!("Online fizetés ({})", link.provider))
+        .bind(link.created_by_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let link = sqlx::query_as::<_, PaymentLink>(
+            r#"
+            UPDATE payment_links
+            SET status = 'paid',
+                paid_at = NOW(),
+                payment_id = $2
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(payment_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(link))
+    }
+
+    async fn mark_closed(&self, id: Uuid, status: &str) -> RepositoryResult<Option<PaymentLink>> {
+        Ok(sqlx::query_as::<_, PaymentLink>(
+            r#"
+            UPDATE payment_links
+            SET status = $2
+            WHERE id = $1
+              AND status = 'pending'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_optional(self)
+        .await?)
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::PaymentLinksModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: PaymentLinksModuleInterface>(payment_links_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/payment_links",
+        Router::new()
+            .route("/list", get(handler::list::<M>))
+            .route("/settings/get", get(handler::get_settings::<M>))
+            .route("/settings/update", put(handler::update_settings::<M>))
+            .layer(from_fn_with_state(
+                payment_links_module.clone(),
+                require_auth,
+            ))
+            // NOTE: reached by customers and payment providers without a session
+            .route("/pay", get(handler::pay::<M>))
+            .route("/webhook", post(handler::webhook::<M>))
+            .with_state(payment_links_module),
+    )
+}
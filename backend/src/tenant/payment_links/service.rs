@@ -0,0 +1,386 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::{CryptoError, encrypt_secret};
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::payment_links::PaymentLinksModuleInterface;
+use crate::tenant::payment_links::dto::{
+    NewPaymentLink, PayQuery, PaymentProviderCredentials, PaymentProviderSettingsInput,
+};
+use crate::tenant::payment_links::model::{
+    CheckoutRequest, ENVIRONMENTS, PROVIDER_BARION, PROVIDER_STRIPE, PROVIDERS, PaymentLink,
+    PaymentProviderSettings, STATUS_PAID, WebhookRequest,
+};
+use crate::tenant::payment_links::provider::PaymentProviderError;
+use crate::tenant::receivables::model::STATUS_OPEN;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{TimeDelta, Utc};
+use lettre::Address;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PaymentLinksServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Provider(#[from] PaymentProviderError),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PaymentLinksServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PaymentLinksServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaymentLinksServiceError> for AppError {
+    fn from(value: PaymentLinksServiceError) -> Self {
+        match value {
+            PaymentLinksServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PaymentLinksServiceError::Provider(PaymentProviderError::InvalidNotification(_)) => {
+                Self::new(
+                    Level::WARN,
+                    StatusCode::BAD_REQUEST,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            PaymentLinksServiceError::UnprocessableEntry(_)
+            | PaymentLinksServiceError::Provider(PaymentProviderError::Rejected(_))
+            | PaymentLinksServiceError::Provider(PaymentProviderError::Configuration(_))
+            | PaymentLinksServiceError::Provider(PaymentProviderError::Unsupported(_)) => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            PaymentLinksServiceError::Provider(PaymentProviderError::Http(_)) => Self::new(
+                Level::WARN,
+                StatusCode::BAD_GATEWAY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "A fizetési szolgáltató jelenleg nem érhető el!"}),
+            ),
+            PaymentLinksServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PaymentLinksServiceResult<T> = Result<T, PaymentLinksServiceError>;
+
+// NOTE: a checkout about to expire is not handed out again
+const REUSE_MARGIN_MINS: i64 = 10;
+
+/// Stable link of the invoice for the customer, every visit redirects to a valid hosted checkout
+pub fn pay_url(public_base_url: &str, tenant_id: Uuid, receivable_id: Uuid) -> String {
+    format!(
+        "https://{public_base_url}/api/payment_links/pay?tenant_id={tenant_id}&receivable_id={receivable_id}"
+    )
+}
+
+fn webhook_url(public_base_url: &str, tenant_id: Uuid) -> String {
+    format!("https://{public_base_url}/api/payment_links/webhook?tenant_id={tenant_id}")
+}
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn validate_settings(
+    payload: &PaymentProviderSettingsInput,
+    current: Option<&PaymentProviderSettings>,
+) -> PaymentLinksServiceResult<(PaymentProviderSettingsInput, PaymentProviderCredentials)> {
+    if !PROVIDERS.contains(&payload.provider.as_str()) {
+        return Err(PaymentLinksServiceError::UnprocessableEntry(
+            "Nem támogatott fizetési szolgáltató!",
+        ));
+    }
+    if !ENVIRONMENTS.contains(&payload.environment.as_str()) {
+        return Err(PaymentLinksServiceError::UnprocessableEntry(
+            "Hibás környezet!",
+        ));
+    }
+    let payee_email = optional(&payload.payee_email);
+    if let Some(payee_email) = &payee_email
+        && payee_email.parse::<Address>().is_err()
+    {
+        return Err(PaymentLinksServiceError::UnprocessableEntry(
+            "A kedvezményezett e-mail címe nem megfelelő!",
+        ));
+    }
+    if payload.provider == PROVIDER_BARION && payee_email.is_none() {
+        return Err(PaymentLinksServiceError::UnprocessableEntry(
+            "Barion esetén a kedvezményezett e-mail címének megadása kötelező!",
+        ));
+    }
+
+    // NOTE: the stored secrets are only kept while the provider does not change
+    let current = current.filter(|settings| settings.provider == payload.provider);
+    let secret_key = match optional(&payload.secret_key) {
+        Some(secret_key) => encrypt_secret(&secret_key)?,
+        None => current.map(|settings| settings.secret_key.clone()).ok_or(
+            PaymentLinksServiceError::UnprocessableEntry("A titkos kulcs megadása kötelező!"),
+        )?,
+    };
+    let webhook_secret = match optional(&payload.webhook_secret) {
+        _ if payload.provider != PROVIDER_STRIPE => None,
+        Some(webhook_secret) => Some(encrypt_secret(&webhook_secret)?),
+        None => Some(
+            current
+                .and_then(|settings| settings.webhook_secret.clone())
+                .ok_or(PaymentLinksServiceError::UnprocessableEntry(
+                    "Stripe esetén a webhook aláíró titok megadása kötelező!",
+                ))?,
+        ),
+    };
+    Ok((
+        PaymentProviderSettingsInput {
+            provider: payload.provider.clone(),
+            environment: payload.environment.clone(),
+            secret_key: None,
+            webhook_secret: None,
+            payee_email,
+            enabled: payload.enabled,
+        },
+        PaymentProviderCredentials {
+            secret_key,
+            webhook_secret,
+        },
+    ))
+}
+
+pub trait PaymentLinksService {
+    fn get_settings(
+        &self,
+    ) -> impl Future<Output = PaymentLinksServiceResult<Option<PaymentProviderSettings>>> + Send;
+    fn save_settings(
+        &self,
+        payload: &PaymentProviderSettingsInput,
+    ) -> impl Future<Output = PaymentLinksServiceResult<PaymentProviderSettings>> + Send;
+    fn get_by_receivable(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = PaymentLinksServiceResult<Vec<PaymentLink>>> + Send;
+    fn pay(
+        &self,
+        query: &PayQuery,
+    ) -> impl Future<Output = PaymentLinksServiceResult<PaymentLink>> + Send;
+    fn handle_webhook(
+        &self,
+        tenant_id: Uuid,
+        request: &WebhookRequest,
+    ) -> impl Future<Output = PaymentLinksServiceResult<Option<PaymentLink>>> + Send;
+}
+
+impl<'a, T> PaymentLinksService for Service<'a, T>
+where
+    T: PaymentLinksModuleInterface,
+{
+    async fn get_settings(&self) -> PaymentLinksServiceResult<Option<PaymentProviderSettings>> {
+        Ok(self
+            .module()
+            .payment_links_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentLinksServiceError::Unauthorized)?,
+            )?
+            .get_settings()
+            .await?)
+    }
+
+    async fn save_settings(
+        &self,
+        payload: &PaymentProviderSettingsInput,
+    ) -> PaymentLinksServiceResult<PaymentProviderSettings> {
+        let repo = self.module().payment_links_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PaymentLinksServiceError::Unauthorized)?,
+        )?;
+        let current = repo.get_settings().await?;
+        let (input, credentials) = validate_settings(payload, current.as_ref())?;
+        Ok(repo
+            .save_settings(&input, &credentials, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get_by_receivable(
+        &self,
+        receivable_id: Uuid,
+    ) -> PaymentLinksServiceResult<Vec<PaymentLink>> {
+        Ok(self
+            .module()
+            .payment_links_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentLinksServiceError::Unauthorized)?,
+            )?
+            .get_by_receivable(receivable_id)
+            .await?)
+    }
+
+    // NOTE: reached by the customer without a session, the checkout is created for the current
+    // open balance and reused until it is about to expire
+    async fn pay(&self, query: &PayQuery) -> PaymentLinksServiceResult<PaymentLink> {
+        let repo = self.module().payment_links_repo(query.tenant_id)?;
+        let settings = repo
+            .get_settings()
+            .await?
+            .filter(|settings| settings.enabled)
+            .ok_or(PaymentLinksServiceError::UnprocessableEntry(
+                "Az online fizetés nem elérhető!",
+            ))?;
+        let invoice = repo.get_payable_invoice(query.receivable_id).await?;
+        if invoice.status != STATUS_OPEN || invoice.open_balance <= BigDecimal::zero() {
+            return Err(PaymentLinksServiceError::UnprocessableEntry(
+                "A számla már nem fizethető!",
+            ));
+        }
+        let now = Utc::now();
+        if let Some(link) = repo
+            .get_reusable(
+                invoice.id,
+                &settings.provider,
+                &invoice.open_balance,
+                now + TimeDelta::minutes(REUSE_MARGIN_MINS),
+            )
+            .await?
+        {
+            return Ok(link);
+        }
+
+        let config = self.module().config();
+        let public_base_url = config.server().public_base_url();
+        let id = Uuid::new_v4();
+        let expires_at = now
+            + TimeDelta::hours(
+                i64::try_from(config.payment_links().checkout_validity_hours()).unwrap_or(24),
+            );
+        let checkout = self
+            .module()
+            .payment_provider_client(&settings)?
+            .create_checkout(&CheckoutRequest {
+                reference: id,
+                document_number: invoice.document_number.clone(),
+                amount: invoice.open_balance.clone(),
+                currency_code: invoice.currency_code.clone(),
+                customer_email: invoice.customer_email.clone(),
+                return_url: format!("https://{public_base_url}/"),
+                callback_url: webhook_url(public_base_url, query.tenant_id),
+                expires_at,
+            })
+            .await?;
+        Ok(repo
+            .insert(
+                &NewPaymentLink {
+                    id,
+                    receivable_id: invoice.id,
+                    provider: settings.provider,
+                    external_id: checkout.external_id,
+                    checkout_url: checkout.checkout_url,
+                    amount: invoice.open_balance,
+                    currency_code: invoice.currency_code,
+                    expires_at,
+                },
+                invoice.created_by_id,
+            )
+            .await?)
+    }
+
+    // NOTE: notifications are processed even when online payment is disabled, checkouts handed
+    // out before can still be completed
+    async fn handle_webhook(
+        &self,
+        tenant_id: Uuid,
+        request: &WebhookRequest,
+    ) -> PaymentLinksServiceResult<Option<PaymentLink>> {
+        let repo = self.module().payment_links_repo(tenant_id)?;
+        let settings =
+            repo.get_settings()
+                .await?
+                .ok_or(PaymentLinksServiceError::UnprocessableEntry(
+                    "Az online fizetés nincs beállítva!",
+                ))?;
+        let Some(event) = self
+            .module()
+            .payment_provider_client(&settings)?
+            .payment_event(request)
+            .await?
+        else {
+            return Ok(None);
+        };
+        // NOTE: checkouts not created by us (e.g. a shared provider account) are ignored
+        let Some(link) = repo
+            .get_by_external_id(&settings.provider, &event.external_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(match event.status {
+            STATUS_PAID => repo.mark_paid(link.id).await?,
+            status => repo.mark_closed(link.id, status).await?,
+        })
+    }
+}
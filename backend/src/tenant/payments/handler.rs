@@ -0,0 +1,473 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::payments::PaymentsModuleInterface;
+use crate::tenant::payments::dto::{CreatePayment, UnpaidInvoicesQuery};
+use crate::tenant::payments::service::PaymentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let result = map_handler_err(service.get(payload.uuid).await, payments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        payments_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        payments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreatePayment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let tz = map_handler_err(claims.tz(), payments_module.clone()).await?;
+    let result =
+        map_handler_err(service.create(&payload, tz).await, payments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    map_handler_err(service.delete(payload.uuid).await, payments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A befizetés törlése sikeresen megtörtént",
+            ))
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn unpaid_invoices<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<UnpaidInvoicesQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let tz = map_handler_err(claims.tz(), payments_module.clone()).await?;
+    let result = map_handler_err(
+        service.unpaid_invoices(&payload, tz).await,
+        payments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn invoice_balance<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let result = map_handler_err(
+        service.invoice_balance(payload.uuid).await,
+        payments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn customer_balance<M: PaymentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(payments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), payments_module.clone());
+    let result = map_handler_err(
+        service.customer_balance(payload.uuid).await,
+        payments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        payments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::payments::model::Payment;
+    use crate::tenant::payments::{
+        self, repository::MockPaymentsRepository, tests::MockPaymentsModule,
+    };
+    use crate::tenant::receivables::model::Receivable;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockPaymentsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut payments_module = MockPaymentsModule::new();
+        payments_module
+            .expect_payments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        payments_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(payments::routes::routes(Arc::new(payments_module))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn receivable(status: &str, paid_amount: i32) -> Receivable {
+        Receivable {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            document_number: "SZ-2026-0001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 9, 9).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(10000),
+            paid_amount: BigDecimal::from(paid_amount),
+            credited_amount: BigDecimal::from(0),
+            status: status.to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn payment(receivable: &Receivable, amount: i32, allocated_amount: i32) -> Payment {
+        Payment {
+            id: Uuid::new_v4(),
+            customer_id: receivable.customer_id,
+            receivable_id: Some(receivable.id),
+            payment_method: "bank_transfer".to_string(),
+            amount: BigDecimal::from(amount),
+            allocated_amount: BigDecimal::from(allocated_amount),
+            currency_code: receivable.currency_code.clone(),
+            paid_on: NaiveDate::from_ymd_opt(2026, 9, 5).unwrap(),
+            reference: Some(receivable.document_number.clone()),
+            note: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_currency_mismatch() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("open", 0);
+        let mut repo = MockPaymentsRepository::new();
+        repo.expect_get_receivable()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/payments/create",
+                active_tenant_id,
+                Some(json!({
+                    "customer_id": receivable.customer_id,
+                    "receivable_id": receivable.id,
+                    "payment_method": "bank_transfer",
+                    "amount": "40",
+                    "currency_code": "eur",
+                    "paid_on": "2026-09-05",
+                    "reference": null,
+                    "note": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_records_overpayment() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("open", 4000);
+        let payment = payment(&receivable, 8000, 6000);
+        let mut repo = MockPaymentsRepository::new();
+        repo.expect_get_receivable().times(1).returning({
+            let receivable = receivable.clone();
+            move |_| Ok(receivable.clone())
+        });
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.amount == 8000 && input.note.is_none())
+            .returning({
+                let payment = payment.clone();
+                move |_, _| Ok(payment.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/payments/create",
+                active_tenant_id,
+                Some(json!({
+                    "customer_id": receivable.customer_id,
+                    "receivable_id": receivable.id,
+                    "payment_method": "bank_transfer",
+                    "amount": "8000",
+                    "currency_code": "HUF",
+                    "paid_on": "2026-09-05",
+                    "reference": "SZ-2026-0001",
+                    "note": "  "
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": payment})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rounds_cash_payment_to_whole_forints() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("open", 0);
+        let payment = payment(&receivable, 12700, 12700);
+        let mut repo = MockPaymentsRepository::new();
+        repo.expect_get_receivable().times(1).returning({
+            let receivable = receivable.clone();
+            move |_| Ok(receivable.clone())
+        });
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.amount == 12700 && input.payment_method == "cash")
+            .returning({
+                let payment = payment.clone();
+                move |_, _| Ok(payment.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/payments/create",
+                active_tenant_id,
+                Some(json!({
+                    "customer_id": receivable.customer_id,
+                    "receivable_id": receivable.id,
+                    "payment_method": "cash",
+                    "amount": "12700.40",
+                    "currency_code": "HUF",
+                    "paid_on": "2026-09-05",
+                    "reference": null,
+                    "note": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_invoice_balance_returns_open_balance_and_payments() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("open", 4000);
+        let payment = payment(&receivable, 4000, 4000);
+        let mut repo = MockPaymentsRepository::new();
+        repo.expect_get_receivable()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+        repo.expect_get_by_receivable()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let payment = payment.clone();
+                move |_| Ok(vec![payment.clone()])
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/payments/invoice_balance?uuid={}", receivable.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["open_balance"], json!("6000"));
+        assert_eq!(body["data"]["payments"], json!([payment]));
+    }
+
+    #[tokio::test]
+    async fn test_delete_rejects_payment_of_written_off_receivable() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("written_off", 4000);
+        let payment = payment(&receivable, 4000, 4000);
+        let mut repo = MockPaymentsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(payment.id))
+            .returning({
+                let payment = payment.clone();
+                move |_| Ok(payment.clone())
+            });
+        repo.expect_get_receivable().times(1).returning({
+            let receivable = receivable.clone();
+            move |_| Ok(receivable.clone())
+        });
+        repo.expect_delete_by_id().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/payments/delete?uuid={}", payment.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
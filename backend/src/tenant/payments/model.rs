@@ -0,0 +1,79 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::receivables::model::Receivable;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const PAYMENT_METHOD_CASH: &str = "cash";
+pub const PAYMENT_METHODS: [&str; 3] = ["bank_transfer", PAYMENT_METHOD_CASH, "card"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Payment {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub receivable_id: Option<Uuid>,
+    pub payment_method: String,
+    pub amount: BigDecimal,
+    pub allocated_amount: BigDecimal,
+    pub currency_code: String,
+    pub paid_on: NaiveDate,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct UnpaidInvoice {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    pub paid_amount: BigDecimal,
+    pub open_balance: BigDecimal,
+    pub days_overdue: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InvoiceBalance {
+    #[serde(flatten)]
+    pub receivable: Receivable,
+    pub open_balance: BigDecimal,
+    pub payments: Vec<Payment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerBalance {
+    pub currency_code: String,
+    pub invoiced_amount: BigDecimal,
+    pub paid_amount: BigDecimal,
+    pub open_balance: BigDecimal,
+    pub unallocated_credit: BigDecimal,
+    pub net_balance: BigDecimal,
+}
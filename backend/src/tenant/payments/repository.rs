@@ -0,0 +1,307 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::payments::dto::CreatePayment;
+use crate::tenant::payments::model::{CustomerBalance, Payment, UnpaidInvoice};
+use crate::tenant::receivables::model::Receivable;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PaymentsRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Payment>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Payment>)>;
+    async fn get_by_receivable(&self, receivable_id: Uuid) -> RepositoryResult<Vec<Payment>>;
+    async fn get_receivable(&self, id: Uuid) -> RepositoryResult<Receivable>;
+    async fn insert(&self, input: &CreatePayment, sub: Uuid) -> RepositoryResult<Payment>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_unpaid_invoices(
+        &self,
+        customer_id: Option<Uuid>,
+        today: NaiveDate,
+    ) -> RepositoryResult<Vec<UnpaidInvoice>>;
+    async fn get_customer_balance(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CustomerBalance>>;
+}
+
+#[async_trait]
+impl PaymentsRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Payment> {
+        Ok(sqlx::query_as::<_, Payment>(
+            "SELECT * FROM payments WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Payment>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM payments WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let payments = sqlx::query_as::<_, Payment>(
+            r#"
+            SELECT *
+            FROM payments
+            WHERE deleted_at IS NULL
+            ORDER BY paid_on DESC, created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            payments,
+        ))
+    }
+
+    async fn get_by_receivable(&self, receivable_id: Uuid) -> RepositoryResult<Vec<Payment>> {
+        Ok(sqlx::query_as::<_, Payment>(
+            r#"
+            SELECT *
+            FROM payments
+            WHERE receivable_id = $1
+                AND deleted_at IS NULL
+            ORDER BY paid_on, created_at
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_receivable(&self, id: Uuid) -> RepositoryResult<Receivable> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            "SELECT * FROM receivables WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &CreatePayment, sub: Uuid) -> RepositoryResult<Payment> {
+        let mut tx = self.begin().await?;
+        let allocated_amount = match input.receivable_id {
+            Some(receivable_id) => {
+                let (open_balance,): (BigDecimal,) = sqlx::query_as(
+                    r#"
+                    SELECT amount - paid_amount - credited_amount
+                    FROM receivables
+                    WHERE id = $1
+                        AND status = 'open'
+                        AND deleted_at IS NULL
+                    FOR UPDATE
+                    "#,
+                )
+                .bind(receivable_id)
+                .fetch_one(&mut *tx)
+                .await?;
+                let allocated_amount = open_balance.min(input.amount.clone());
+                sqlx::query(
+                    r#"
+                    UPDATE receivables
+                    SET paid_amount = paid_amount + $1,
+                        status = CASE
+                            WHEN paid_amount + $1 + credited_amount >= amount THEN 'paid'
+                            ELSE status
+                        END
+                    WHERE id = $2
+                    "#,
+                )
+                .bind(&allocated_amount)
+                .bind(receivable_id)
+                .execute(&mut *tx)
+                .await?;
+                allocated_amount
+            }
+            None => BigDecimal::zero(),
+        };
+
+        let payment = sqlx::query_as::<_, Payment>(
+            r#"
+            INSERT INTO payments (customer_id, receivable_id, payment_method, amount,
+                                  allocated_amount, currency_code, paid_on, reference, note,
+                                  created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(input.receivable_id)
+        .bind(&input.payment_method)
+        .bind(&input.amount)
+        .bind(&allocated_amount)
+        .bind(&input.currency_code)
+        .bind(input.paid_on)
+        .bind(&input.reference)
+        .bind(&input.note)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(payment)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        let mut tx = self.begin().await?;
+        let payment = sqlx::query_as::<_, Payment>(
+            r#"
+            UPDATE payments
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(receivable_id) = payment.receivable_id {
+            sqlx::query(
+                r#"
+                UPDATE receivables
+                SET paid_amount = GREATEST(paid_amount - $1, 0),
+                    status = CASE WHEN status = 'paid' THEN 'open' ELSE status END
+                WHERE id = $2
+                "#,
+            )
+            .bind(&payment.allocated_amount)
+            .bind(receivable_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_unpaid_invoices(
+        &self,
+        customer_id: Option<Uuid>,
+        today: NaiveDate,
+    ) -> RepositoryResult<Vec<UnpaidInvoice>> {
+        Ok(sqlx::query_as::<_, UnpaidInvoice>(
+            r#"
+            SELECT receivables.id,
+                   receivables.customer_id,
+                   customers.name AS customer_name,
+                   receivables.document_number,
+                   receivables.issue_date,
+                   receivables.due_date,
+                   receivables.currency_code,
+                   receivables.amount,
+                   receivables.paid_amount,
+                   receivables.amount - receivables.paid_amount - receivables.credited_amount
+                       AS open_balance,
+                   GREATEST($2 - receivables.due_date, 0) AS days_overdue
+            FROM receivables
+            JOIN customers ON receivables.customer_id = customers.id
+            WHERE receivables.status = 'open'
+                AND receivables.deleted_at IS NULL
+                AND ($1::uuid IS NULL OR receivables.customer_id = $1)
+            ORDER BY receivables.due_date, receivables.document_number
+            "#,
+        )
+        .bind(customer_id)
+        .bind(today)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_customer_balance(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CustomerBalance>> {
+        Ok(sqlx::query_as::<_, CustomerBalance>(
+            r#"
+            WITH invoiced AS (
+                SELECT currency_code,
+                       SUM(amount) AS invoiced_amount,
+                       SUM(paid_amount) AS paid_amount,
+                       COALESCE(SUM(amount - paid_amount - credited_amount)
+                                    FILTER (WHERE status = 'open'), 0) AS open_balance
+                FROM receivables
+                WHERE customer_id = $1
+                    AND status <> 'written_off'
+                    AND deleted_at IS NULL
+                GROUP BY currency_code
+            ),
+            credit AS (
+                SELECT currency_code,
+                       SUM(unallocated_credit) AS unallocated_credit
+                FROM (
+                    SELECT currency_code, amount - allocated_amount AS unallocated_credit
+                    FROM payments
+                    WHERE customer_id = $1
+                        AND deleted_at IS NULL
+                    UNION ALL
+                    SELECT currency_code, -amount - applied_amount AS unallocated_credit
+                    FROM credit_notes
+                    WHERE customer_id = $1
+                ) AS unallocated
+                GROUP BY currency_code
+            )
+            SELECT currency_code,
+                   COALESCE(invoiced.invoiced_amount, 0) AS invoiced_amount,
+                   COALESCE(invoiced.paid_amount, 0) AS paid_amount,
+                   COALESCE(invoiced.open_balance, 0) AS open_balance,
+                   COALESCE(credit.unallocated_credit, 0) AS unallocated_credit,
+                   COALESCE(invoiced.open_balance, 0) - COALESCE(credit.unallocated_credit, 0)
+                       AS net_balance
+            FROM invoiced
+            FULL JOIN credit USING (currency_code)
+            ORDER BY currency_code
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+}
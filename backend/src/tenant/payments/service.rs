@@ -0,0 +1,313 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{Empty, Money};
+use crate::tenant::payments::PaymentsModuleInterface;
+use crate::tenant::payments::dto::{CreatePayment, UnpaidInvoicesQuery};
+use crate::tenant::payments::model::{
+    CustomerBalance, InvoiceBalance, PAYMENT_METHOD_CASH, PAYMENT_METHODS, Payment, UnpaidInvoice,
+};
+use crate::tenant::receivables::model::{STATUS_OPEN, STATUS_PAID};
+use axum::http::StatusCode;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PaymentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PaymentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PaymentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaymentsServiceError> for AppError {
+    fn from(value: PaymentsServiceError) -> Self {
+        match value {
+            PaymentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PaymentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PaymentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PaymentsServiceResult<T> = Result<T, PaymentsServiceError>;
+
+fn validate_payment(
+    payload: &CreatePayment,
+    today: NaiveDate,
+) -> PaymentsServiceResult<CreatePayment> {
+    if !PAYMENT_METHODS.contains(&payload.payment_method.as_str()) {
+        return Err(PaymentsServiceError::UnprocessableEntry(
+            "Hibás fizetési mód!",
+        ));
+    }
+    let currency_code = payload.currency_code.trim().to_uppercase();
+    if currency_code.len() != 3 {
+        return Err(PaymentsServiceError::UnprocessableEntry("Hibás pénznem!"));
+    }
+    let amount = Money::new(payload.amount.clone(), &currency_code);
+    let amount = if payload.payment_method == PAYMENT_METHOD_CASH {
+        amount.round_cash()
+    } else {
+        amount.round()
+    };
+    if !amount.is_positive() {
+        return Err(PaymentsServiceError::UnprocessableEntry(
+            "Az összegnek pozitív számnak kell lennie!",
+        ));
+    }
+    if payload.paid_on > today {
+        return Err(PaymentsServiceError::UnprocessableEntry(
+            "A befizetés dátuma nem lehet jövőbeli!",
+        ));
+    }
+    let reference = payload
+        .reference
+        .as_deref()
+        .map(str::trim)
+        .filter(|reference| !reference.is_empty());
+    if reference.is_some_and(|reference| reference.chars().count() > 255) {
+        return Err(PaymentsServiceError::UnprocessableEntry(
+            "A közlemény legfeljebb 255 karakter lehet!",
+        ));
+    }
+    Ok(CreatePayment {
+        amount: amount.amount,
+        currency_code,
+        reference: reference.map(str::to_string),
+        note: payload
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty())
+            .map(str::to_string),
+        ..payload.clone()
+    })
+}
+
+pub trait PaymentsService {
+    fn get(&self, id: Uuid) -> impl Future<Output = PaymentsServiceResult<Payment>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = PaymentsServiceResult<(PaginatorMeta, Vec<Payment>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreatePayment,
+        tz: Tz,
+    ) -> impl Future<Output = PaymentsServiceResult<Payment>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = PaymentsServiceResult<()>> + Send;
+    fn unpaid_invoices(
+        &self,
+        query: &UnpaidInvoicesQuery,
+        tz: Tz,
+    ) -> impl Future<Output = PaymentsServiceResult<Vec<UnpaidInvoice>>> + Send;
+    fn invoice_balance(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = PaymentsServiceResult<InvoiceBalance>> + Send;
+    fn customer_balance(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = PaymentsServiceResult<Vec<CustomerBalance>>> + Send;
+}
+
+impl<'a, T> PaymentsService for Service<'a, T>
+where
+    T: PaymentsModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> PaymentsServiceResult<Payment> {
+        Ok(self
+            .module()
+            .payments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentsServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> PaymentsServiceResult<(PaginatorMeta, Vec<Payment>)> {
+        Ok(self
+            .module()
+            .payments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentsServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &CreatePayment, tz: Tz) -> PaymentsServiceResult<Payment> {
+        let input = validate_payment(payload, Utc::now().with_timezone(&tz).date_naive())?;
+        let repo = self.module().payments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PaymentsServiceError::Unauthorized)?,
+        )?;
+        if let Some(receivable_id) = input.receivable_id {
+            let receivable = repo.get_receivable(receivable_id).await?;
+            if receivable.status != STATUS_OPEN {
+                return Err(PaymentsServiceError::UnprocessableEntry(
+                    "Csak nyitott számlára rögzíthető befizetés!",
+                ));
+            }
+            if receivable.customer_id != input.customer_id {
+                return Err(PaymentsServiceError::UnprocessableEntry(
+                    "A számla nem a megadott ügyfélhez tartozik!",
+                ));
+            }
+            if receivable.currency_code != input.currency_code {
+                return Err(PaymentsServiceError::UnprocessableEntry(
+                    "A befizetés pénzneme eltér a számla pénznemétől!",
+                ));
+            }
+        }
+        repo.insert(&input, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    PaymentsServiceError::UnprocessableEntry(
+                        "A megadott ügyfél vagy pénznem nem létezik!",
+                    )
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn delete(&self, id: Uuid) -> PaymentsServiceResult<()> {
+        let repo = self.module().payments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PaymentsServiceError::Unauthorized)?,
+        )?;
+        let payment = repo.get_by_id(id).await?;
+        if let Some(receivable_id) = payment.receivable_id {
+            let status = repo.get_receivable(receivable_id).await?.status;
+            if status != STATUS_OPEN && status != STATUS_PAID {
+                return Err(PaymentsServiceError::UnprocessableEntry(
+                    "Leírt követeléshez tartozó befizetés nem törölhető!",
+                ));
+            }
+        }
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn unpaid_invoices(
+        &self,
+        query: &UnpaidInvoicesQuery,
+        tz: Tz,
+    ) -> PaymentsServiceResult<Vec<UnpaidInvoice>> {
+        Ok(self
+            .module()
+            .payments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentsServiceError::Unauthorized)?,
+            )?
+            .get_unpaid_invoices(
+                query.customer_id,
+                Utc::now().with_timezone(&tz).date_naive(),
+            )
+            .await?)
+    }
+
+    async fn invoice_balance(&self, receivable_id: Uuid) -> PaymentsServiceResult<InvoiceBalance> {
+        let repo = self.module().payments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PaymentsServiceError::Unauthorized)?,
+        )?;
+        let receivable = repo.get_receivable(receivable_id).await?;
+        Ok(InvoiceBalance {
+            open_balance: receivable.open_balance(),
+            payments: repo.get_by_receivable(receivable_id).await?,
+            receivable,
+        })
+    }
+
+    async fn customer_balance(
+        &self,
+        customer_id: Uuid,
+    ) -> PaymentsServiceResult<Vec<CustomerBalance>> {
+        Ok(self
+            .module()
+            .payments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PaymentsServiceError::Unauthorized)?,
+            )?
+            .get_customer_balance(customer_id)
+            .await?)
+    }
+}
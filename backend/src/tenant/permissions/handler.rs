@@ -0,0 +1,166 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::permissions::PermissionsModuleInterface;
+use crate::tenant::permissions::dto::PermissionInput;
+use crate::tenant::permissions::service::PermissionsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: PermissionsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(permissions_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), permissions_module.clone());
+    let result =
+        map_handler_err(service.list(payload.uuid).await, permissions_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        permissions_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn grant<M: PermissionsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(permissions_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PermissionInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), permissions_module.clone());
+    let result = map_handler_err(service.grant(&payload).await, permissions_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        permissions_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn revoke<M: PermissionsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(permissions_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PermissionInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), permissions_module.clone());
+    map_handler_err(service.revoke(&payload).await, permissions_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A jogosultság visszavonása sikeresen megtörtént",
+            ))
+            .build(),
+        permissions_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::{
+        self, repository::MockPermissionsRepository, tests::MockPermissionsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_grant_requires_owner() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .times(1)
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo.expect_grant().never();
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut permissions_module = MockPermissionsModule::new();
+        permissions_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        permissions_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        permissions_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(permissions::routes::routes(Arc::new(permissions_module))),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("POST")
+                    .uri("/api/permissions/grant")
+                    .body(Body::from(
+                        json!({"user_id": Uuid::new_v4(), "permission": "inventory.adjust"})
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"error": {"code": "FORBIDDEN", "message": "A művelet nem engedélyezett."}})
+        );
+    }
+}
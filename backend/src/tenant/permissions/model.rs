@@ -0,0 +1,48 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const INVENTORY_ADJUST: &str = "inventory.adjust";
+pub const INVENTORY_COSTING: &str = "inventory.costing";
+/// Sees and edits the worksheets and tasks of every project, not only of those the user is a
+/// member of
+pub const PROJECTS_ACCESS_ALL: &str = "projects.access_all";
+pub const PURCHASE_INVOICES_APPROVE: &str = "purchase_invoices.approve";
+pub const TIME_ENTRIES_APPROVE: &str = "time_entries.approve";
+
+pub const ALL: [&str; 5] = [
+    INVENTORY_ADJUST,
+    INVENTORY_COSTING,
+    PROJECTS_ACCESS_ALL,
+    PURCHASE_INVOICES_APPROVE,
+    TIME_ENTRIES_APPROVE,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct UserPermission {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub permission: String,
+    pub granted_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,92 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::permissions::model::UserPermission;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PermissionsRepository: Send + Sync {
+    async fn has_permission(&self, user_id: Uuid, permission: &str) -> RepositoryResult<bool>;
+    async fn get_by_user_id(&self, user_id: Uuid) -> RepositoryResult<Vec<UserPermission>>;
+    async fn grant(
+        &self,
+        user_id: Uuid,
+        permission: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<UserPermission>;
+    async fn revoke(&self, user_id: Uuid, permission: &str) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl PermissionsRepository for PgPool {
+    async fn has_permission(&self, user_id: Uuid, permission: &str) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM user_permissions WHERE user_id = $1 AND permission = $2)",
+        )
+        .bind(user_id)
+        .bind(permission)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_user_id(&self, user_id: Uuid) -> RepositoryResult<Vec<UserPermission>> {
+        Ok(sqlx::query_as::<_, UserPermission>(
+            "SELECT * FROM user_permissions WHERE user_id = $1 ORDER BY permission",
+        )
+        .bind(user_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn grant(
+        &self,
+        user_id: Uuid,
+        permission: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<UserPermission> {
+        Ok(sqlx::query_as::<_, UserPermission>(
+            r#"
+            INSERT INTO user_permissions (user_id, permission, granted_by_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, permission) DO UPDATE SET permission = EXCLUDED.permission
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(permission)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn revoke(&self, user_id: Uuid, permission: &str) -> RepositoryResult<()> {
+        let _ = sqlx::query("DELETE FROM user_permissions WHERE user_id = $1 AND permission = $2")
+            .bind(user_id)
+            .bind(permission)
+            .execute(self)
+            .await?;
+        Ok(())
+    }
+}
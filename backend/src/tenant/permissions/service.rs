@@ -0,0 +1,187 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error::{RepositoryError, RepositoryResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::permissions::PermissionsModuleInterface;
+use crate::tenant::permissions::dto::PermissionInput;
+use crate::tenant::permissions::model::{self, UserPermission};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PermissionsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PermissionsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PermissionsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PermissionsServiceError> for AppError {
+    fn from(value: PermissionsServiceError) -> Self {
+        match value {
+            PermissionsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PermissionsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            PermissionsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PermissionsServiceResult<T> = Result<T, PermissionsServiceError>;
+
+async fn is_owner<M: PermissionsModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> RepositoryResult<bool> {
+    Ok(module
+        .membership_repo()
+        .get_role(user_id, tenant_id)
+        .await?
+        .is_some_and(|role| role == "owner"))
+}
+
+// NOTE: the owner of the tenant implicitly holds every permission
+pub(crate) async fn has_permission<M: PermissionsModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    permission: &str,
+) -> RepositoryResult<bool> {
+    Ok(is_owner(module, tenant_id, user_id).await?
+        || module
+            .permissions_repo(tenant_id)?
+            .has_permission(user_id, permission)
+            .await?)
+}
+
+pub trait PermissionsService {
+    fn list(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = PermissionsServiceResult<Vec<UserPermission>>> + Send;
+    fn grant(
+        &self,
+        payload: &PermissionInput,
+    ) -> impl Future<Output = PermissionsServiceResult<UserPermission>> + Send;
+    fn revoke(
+        &self,
+        payload: &PermissionInput,
+    ) -> impl Future<Output = PermissionsServiceResult<()>> + Send;
+    fn owner_tenant(&self) -> impl Future<Output = PermissionsServiceResult<Uuid>> + Send;
+}
+
+impl<'a, T> PermissionsService for Service<'a, T>
+where
+    T: PermissionsModuleInterface,
+{
+    async fn owner_tenant(&self) -> PermissionsServiceResult<Uuid> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(PermissionsServiceError::Unauthorized)?;
+        if !is_owner(self.module(), tenant_id, claims.sub()).await? {
+            return Err(PermissionsServiceError::Forbidden);
+        }
+        Ok(tenant_id)
+    }
+
+    async fn list(&self, user_id: Uuid) -> PermissionsServiceResult<Vec<UserPermission>> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(PermissionsServiceError::Unauthorized)?;
+        Ok(self
+            .module()
+            .permissions_repo(tenant_id)?
+            .get_by_user_id(user_id)
+            .await?)
+    }
+
+    async fn grant(&self, payload: &PermissionInput) -> PermissionsServiceResult<UserPermission> {
+        if !model::ALL.contains(&payload.permission.as_str()) {
+            return Err(PermissionsServiceError::UnprocessableEntry(
+                "Ismeretlen jogosultság!",
+            ));
+        }
+        let tenant_id = self.owner_tenant().await?;
+        Ok(self
+            .module()
+            .permissions_repo(tenant_id)?
+            .grant(payload.user_id, &payload.permission, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn revoke(&self, payload: &PermissionInput) -> PermissionsServiceResult<()> {
+        let tenant_id = self.owner_tenant().await?;
+        Ok(self
+            .module()
+            .permissions_repo(tenant_id)?
+            .revoke(payload.user_id, &payload.permission)
+            .await?)
+    }
+}
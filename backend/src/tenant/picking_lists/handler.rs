@@ -0,0 +1,428 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::picking_lists::PickingListsModuleInterface;
+use crate::tenant::picking_lists::dto::{AssignPicker, GeneratePickingLists, RecordPick};
+use crate::tenant::picking_lists::service::PickingListsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        picking_lists_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn generate<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<GeneratePickingLists>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result = map_handler_err(
+        service.generate(&payload).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn assign<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<AssignPicker>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result =
+        map_handler_err(service.assign(&payload).await, picking_lists_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn record_pick<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RecordPick>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result = map_handler_err(
+        service.record_pick(&payload).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn complete<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result = map_handler_err(
+        service.complete(payload.uuid).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn cancel<M: PickingListsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(picking_lists_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), picking_lists_module.clone());
+    let result = map_handler_err(
+        service.cancel(payload.uuid).await,
+        picking_lists_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        picking_lists_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::picking_lists::model::{PickingList, PickingListItem};
+    use crate::tenant::picking_lists::{
+        self, repository::MockPickingListsRepository, tests::MockPickingListsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn picking_list(id: Uuid, warehouse_id: Uuid) -> PickingList {
+        PickingList {
+            id,
+            warehouse_id,
+            zone: Some("A".to_string()),
+            status: "open".to_string(),
+            assigned_to_id: None,
+            completed_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn picking_list_item(id: Uuid, quantity_requested: i32) -> PickingListItem {
+        PickingListItem {
+            id,
+            picking_list_id: Uuid::new_v4(),
+            inventory_reservation_id: Uuid::new_v4(),
+            inventory_id: Uuid::new_v4(),
+            zone: Some("A".to_string()),
+            bin_location: Some("A-1".to_string()),
+            walk_order: 1,
+            quantity_requested: BigDecimal::from(quantity_requested),
+            quantity_picked: None,
+            short_reason: None,
+            picked_by_id: None,
+            picked_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn app(repo: MockPickingListsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut picking_lists_module = MockPickingListsModule::new();
+        picking_lists_module
+            .expect_picking_lists_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        picking_lists_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(picking_lists::routes::routes(Arc::new(
+                picking_lists_module,
+            ))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+        let generated = vec![picking_list(Uuid::new_v4(), warehouse_id)];
+
+        let mut repo = MockPickingListsRepository::new();
+        repo.expect_generate()
+            .times(1)
+            .withf(move |input, _| input.warehouse_id == warehouse_id)
+            .returning({
+                let generated = generated.clone();
+                move |_, _| Ok(generated.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/picking_lists/generate",
+                active_tenant_id,
+                json!({"warehouse_id": warehouse_id, "reference_type": "worksheets"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": generated})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_pick_short_requires_reason() {
+        let active_tenant_id = Uuid::new_v4();
+        let item_id = Uuid::new_v4();
+
+        let mut repo = MockPickingListsRepository::new();
+        repo.expect_get_item_by_id()
+            .times(1)
+            .with(eq(item_id))
+            .returning(move |id| Ok(picking_list_item(id, 5)));
+        repo.expect_record_pick().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/picking_lists/record_pick",
+                active_tenant_id,
+                json!({"item_id": item_id, "quantity_picked": "3"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_record_pick_short_with_reason() {
+        let active_tenant_id = Uuid::new_v4();
+        let item_id = Uuid::new_v4();
+        let mut picked = picking_list_item(item_id, 5);
+        picked.quantity_picked = Some(BigDecimal::from(3));
+        picked.short_reason = Some("Sérült csomagolás".to_string());
+
+        let mut repo = MockPickingListsRepository::new();
+        repo.expect_get_item_by_id()
+            .times(1)
+            .returning(move |id| Ok(picking_list_item(id, 5)));
+        repo.expect_record_pick()
+            .times(1)
+            .withf(|input, _| input.short_reason.as_deref() == Some("Sérült csomagolás"))
+            .returning({
+                let picked = picked.clone();
+                move |_, _| Ok(picked.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/picking_lists/record_pick",
+                active_tenant_id,
+                json!({
+                    "item_id": item_id,
+                    "quantity_picked": "3",
+                    "short_reason": " Sérült csomagolás "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": picked})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_with_unpicked_items() {
+        let active_tenant_id = Uuid::new_v4();
+        let picking_list_id = Uuid::new_v4();
+
+        let mut repo = MockPickingListsRepository::new();
+        repo.expect_get_items()
+            .times(1)
+            .with(eq(picking_list_id))
+            .returning(|picking_list_id| {
+                Ok(vec![
+                    crate::tenant::picking_lists::model::PickingListItemResolved {
+                        id: Uuid::new_v4(),
+                        picking_list_id,
+                        inventory_reservation_id: Uuid::new_v4(),
+                        inventory_id: Uuid::new_v4(),
+                        product_id: Uuid::new_v4(),
+                        product: "Csavar".to_string(),
+                        reference_type: "worksheets".to_string(),
+                        reference_id: Uuid::new_v4(),
+                        zone: None,
+                        bin_location: None,
+                        walk_order: 1,
+                        quantity_requested: BigDecimal::from(1),
+                        quantity_picked: None,
+                        short_reason: None,
+                        picked_by_id: None,
+                        picked_at: None,
+                    },
+                ])
+            });
+        repo.expect_complete().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/picking_lists/complete",
+                active_tenant_id,
+                json!({"uuid": picking_list_id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,225 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PickingList {
+    pub id: Uuid,
+    pub warehouse_id: Uuid,
+    pub zone: Option<String>,
+    pub status: String,
+    pub assigned_to_id: Option<Uuid>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PickingListItem {
+    pub id: Uuid,
+    pub picking_list_id: Uuid,
+    pub inventory_reservation_id: Uuid,
+    pub inventory_id: Uuid,
+    pub zone: Option<String>,
+    pub bin_location: Option<String>,
+    pub walk_order: i32,
+    pub quantity_requested: BigDecimal,
+    pub quantity_picked: Option<BigDecimal>,
+    pub short_reason: Option<String>,
+    pub picked_by_id: Option<Uuid>,
+    pub picked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PickingListItemResolved {
+    pub id: Uuid,
+    pub picking_list_id: Uuid,
+    pub inventory_reservation_id: Uuid,
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub reference_type: String,
+    pub reference_id: Uuid,
+    pub zone: Option<String>,
+    pub bin_location: Option<String>,
+    pub walk_order: i32,
+    pub quantity_requested: BigDecimal,
+    pub quantity_picked: Option<BigDecimal>,
+    pub short_reason: Option<String>,
+    pub picked_by_id: Option<Uuid>,
+    pub picked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PickingListWithItems {
+    #[serde(flatten)]
+    pub picking_list: PickingList,
+    pub items: Vec<PickingListItemResolved>,
+}
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct PickCandidate {
+    pub inventory_reservation_id: Uuid,
+    pub inventory_id: Uuid,
+    pub product: String,
+    pub zone: Option<String>,
+    pub bin_location: Option<String>,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickWave {
+    pub zone: Option<String>,
+    pub items: Vec<PickCandidate>,
+}
+
+/// Groups the candidates into one wave per zone and orders every wave by bin
+/// location, so a picker walks each aisle once. Unlocated stock comes last.
+pub fn plan_pick_waves(mut candidates: Vec<PickCandidate>) -> Vec<PickWave> {
+    candidates.sort_by(|a, b| {
+        cmp_location(a.zone.as_deref(), b.zone.as_deref())
+            .then_with(|| cmp_location(a.bin_location.as_deref(), b.bin_location.as_deref()))
+            .then_with(|| a.product.cmp(&b.product))
+    });
+
+    let mut waves: Vec<PickWave> = Vec::new();
+    for candidate in candidates {
+        match waves.last_mut() {
+            Some(wave) if wave.zone == candidate.zone => wave.items.push(candidate),
+            _ => waves.push(PickWave {
+                zone: candidate.zone.clone(),
+                items: vec![candidate],
+            }),
+        }
+    }
+    waves
+}
+
+fn cmp_location(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => natural_cmp(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+// NOTE: "A-2" must come before "A-10", so digit runs are compared by value
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let mut x_num = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    x_num.push(c);
+                }
+                let mut y_num = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    y_num.push(c);
+                }
+                let x_trimmed = x_num.trim_start_matches('0');
+                let y_trimmed = y_num.trim_start_matches('0');
+                let ordering = x_trimmed
+                    .len()
+                    .cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(product: &str, zone: Option<&str>, bin_location: Option<&str>) -> PickCandidate {
+        PickCandidate {
+            inventory_reservation_id: Uuid::new_v4(),
+            inventory_id: Uuid::new_v4(),
+            product: product.to_string(),
+            zone: zone.map(str::to_string),
+            bin_location: bin_location.map(str::to_string),
+            quantity: BigDecimal::from(1),
+        }
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("A-2", "A-10"), Ordering::Less);
+        assert_eq!(natural_cmp("A-10", "B-1"), Ordering::Less);
+        assert_eq!(natural_cmp("a-01", "A-1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_plan_pick_waves_groups_by_zone_in_walk_order() {
+        let waves = plan_pick_waves(vec![
+            candidate("Csavar", Some("B"), Some("B-10")),
+            candidate("Anya", None, None),
+            candidate("Alátét", Some("A"), Some("A-10")),
+            candidate("Szeg", Some("B"), Some("B-2")),
+            candidate("Kalapács", Some("A"), Some("A-2")),
+        ]);
+
+        let summary: Vec<(Option<&str>, Vec<&str>)> = waves
+            .iter()
+            .map(|wave| {
+                (
+                    wave.zone.as_deref(),
+                    wave.items
+                        .iter()
+                        .map(|item| item.product.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("A"), vec!["Kalapács", "Alátét"]),
+                (Some("B"), vec!["Szeg", "Csavar"]),
+                (None, vec!["Anya"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::picking_lists::dto::{AssignPicker, GeneratePickingLists, RecordPick};
+use crate::tenant::picking_lists::model::{
+    PickCandidate, PickingList, PickingListItem, PickingListItemResolved, plan_pick_waves,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PickingListsRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PickingList>;
+    async fn get_items(
+        &self,
+        picking_list_id: Uuid,
+    ) -> RepositoryResult<Vec<PickingListItemResolved>>;
+    async fn get_item_by_id(&self, id: Uuid) -> RepositoryResult<PickingListItem>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PickingList>)>;
+    async fn generate(
+        &self,
+        input: &GeneratePickingLists,
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<PickingList>>;
+    async fn assign(&self, input: &AssignPicker) -> RepositoryResult<PickingList>;
+    async fn record_pick(&self, input: &RecordPick, sub: Uuid)
+    -> RepositoryResult<PickingListItem>;
+    async fn complete(&self, id: Uuid) -> RepositoryResult<PickingList>;
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<PickingList>;
+}
+
+#[async_trait]
+impl PickingListsRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PickingList> {
+        Ok(
+            sqlx::query_as::<_, PickingList>("SELECT * FROM picking_lists WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_items(
+        &self,
+        picking_list_id: Uuid,
+    ) -> RepositoryResult<Vec<PickingListItemResolved>> {
+        Ok(sqlx::query_as::<_, PickingListItemResolved>(
+            r#"
+            SELECT picking_list_items.id,
+                   picking_list_items.picking_list_id,
+                   picking_list_items.inventory_reservation_id,
+                   picking_list_items.inventory_id,
+                   products.id AS product_id,
+                   products.name AS product,
+                   inventory_reservations.reference_type,
+                   inventory_reservations.reference_id,
+                   picking_list_items.zone,
+                   picking_list_items.bin_location,
+                   picking_list_items.walk_order,
+                   picking_list_items.quantity_requested,
+                   picking_list_items.quantity_picked,
+                   picking_list_items.short_reason,
+                   picking_list_items.picked_by_id,
+                   picking_list_items.picked_at
+            FROM picking_list_items
+            LEFT JOIN inventory ON picking_list_items.inventory_id = inventory.id
+            LEFT JOIN products ON inventory.product_id = products.id
+            LEFT JOIN inventory_reservations
+                ON picking_list_items.inventory_reservation_id = inventory_reservations.id
+            WHERE picking_list_items.picking_list_id = $1
+            ORDER BY picking_list_items.walk_order
+            "#,
+        )
+        .bind(picking_list_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_item_by_id(&self, id: Uuid) -> RepositoryResult<PickingListItem> {
+        Ok(
+            sqlx::query_as::<_, PickingListItem>("SELECT * FROM picking_list_items WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PickingList>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM picking_lists")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let picking_lists = sqlx::query_as::<_, PickingList>(
+            r#"
+            SELECT *
+            FROM picking_lists
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            picking_lists,
+        ))
+    }
+
+    async fn generate(
+        &self,
+        input: &GeneratePickingLists,
+        sub: Uuid,
+    ) -> RepositoryResult<Vec<PickingList>> {
+        let mut tx = self.begin().await?;
+
+        // NOTE: a reservation can only be on one not cancelled picking list
+        let candidates = sqlx::query_as::<_, PickCandidate>(
+            r#"
+            SELECT inventory_reservations.id AS inventory_reservation_id,
+                   inventory_reservations.inventory_id,
+                   products.name AS product,
+                   inventory.zone,
+                   inventory.bin_location,
+                   inventory_reservations.quantity
+            FROM inventory_reservations
+            JOIN inventory ON inventory_reservations.inventory_id = inventory.id
+            JOIN products ON inventory.product_id = products.id
+            WHERE inventory_reservations.status = 'active'
+                AND inventory.warehouse_id = $1
+                AND inventory.deleted_at IS NULL
+                AND ($2::TEXT IS NULL OR inventory_reservations.reference_type = $2)
+                AND ($3::UUID[] IS NULL OR inventory_reservations.reference_id = ANY($3))
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM picking_list_items
+                    JOIN picking_lists ON picking_list_items.picking_list_id = picking_lists.id
+                    WHERE picking_list_items.inventory_reservation_id = inventory_reservations.id
+                        AND picking_lists.status <> 'cancelled'
+                )
+            FOR UPDATE OF inventory_reservations
+            "#,
+        )
+        .bind(input.warehouse_id)
+        .bind(&input.reference_type)
+        .bind(&input.reference_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut picking_lists = Vec::new();
+        for wave in plan_pick_waves(candidates) {
+            let picking_list = sqlx::query_as::<_, PickingList>(
+                r#"
+                INSERT INTO picking_lists (warehouse_id, zone, created_by_id)
+                VALUES ($1, $2, $3)
+                RETURNING *
+                "#,
+            )
+            .bind(input.warehouse_id)
+            .bind(&wave.zone)
+            .bind(sub)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for (walk_order, item) in wave.items.iter().enumerate() {
+                sqlx::query(
+                    r#"
+                    INSERT INTO picking_list_items (
+                        picking_list_id, inventory_reservation_id, inventory_id, zone,
+                        bin_location, walk_order, quantity_requested
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#,
+                )
+                .bind(picking_list.id)
+                .bind(item.inventory_reservation_id)
+                .bind(item.inventory_id)
+                .bind(&item.zone)
+                .bind(&item.bin_location)
+                .bind(i32::try_from(walk_order + 1)?)
+                .bind(&item.quantity)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            picking_lists.push(picking_list);
+        }
+
+        tx.commit().await?;
+        Ok(picking_lists)
+    }
+
+    async fn assign(&self, input: &AssignPicker) -> RepositoryResult<PickingList> {
+        Ok(sqlx::query_as::<_, PickingList>(
+            r#"
+            UPDATE picking_lists
+            SET assigned_to_id = $2,
+                status = CASE WHEN status = 'open' THEN 'in_progress' ELSE status END
+            WHERE id = $1
+                AND status IN ('open', 'in_progress')
+            RETURNING *
+            "#,
+        )
+        .bind(input.picking_list_id)
+        .bind(input.user_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn record_pick(
+        &self,
+        input: &RecordPick,
+        sub: Uuid,
+    ) -> RepositoryResult<PickingListItem> {
+        let mut tx = self.begin().await?;
+
+        let item = sqlx::query_as::<_, PickingListItem>(
+            r#"
+            UPDATE picking_list_items
+            SET quantity_picked = $2,
+                short_reason = $3,
+                picked_by_id = $4,
+                picked_at = NOW()
+            WHERE id = $1
+                AND picking_list_id IN (
+                    SELECT id FROM picking_lists WHERE status IN ('open', 'in_progress')
+                )
+            RETURNING *
+            "#,
+        )
+        .bind(input.item_id)
+        .bind(&input.quantity_picked)
+        .bind(&input.short_reason)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE picking_lists SET status = 'in_progress' WHERE id = $1 AND status = 'open'",
+        )
+        .bind(item.picking_list_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(item)
+    }
+
+    async fn complete(&self, id: Uuid) -> RepositoryResult<PickingList> {
+        let mut tx = self.begin().await?;
+
+        let picking_list = sqlx::query_as::<_, PickingList>(
+            r#"
+            UPDATE picking_lists
+            SET status = 'completed',
+                completed_at = NOW()
+            WHERE id = $1
+                AND status IN ('open', 'in_progress')
+                AND NOT EXISTS (
+                    SELECT 1 FROM picking_list_items
+                    WHERE picking_list_id = $1 AND quantity_picked IS NULL
+                )
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // NOTE: short picks release the missing quantity, the rest stays reserved
+        // until the shipment is confirmed
+        sqlx::query(
+            r#"
+            UPDATE inventory_reservations
+            SET status = 'cancelled'
+            FROM picking_list_items
+            WHERE picking_list_items.inventory_reservation_id = inventory_reservations.id
+                AND picking_list_items.picking_list_id = $1
+                AND picking_list_items.quantity_picked = 0
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE inventory_reservations
+            SET quantity = picking_list_items.quantity_picked
+            FROM picking_list_items
+            WHERE picking_list_items.inventory_reservation_id = inventory_reservations.id
+                AND picking_list_items.picking_list_id = $1
+                AND picking_list_items.quantity_picked > 0
+                AND picking_list_items.quantity_picked < picking_list_items.quantity_requested
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(picking_list)
+    }
+
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<PickingList> {
+        Ok(sqlx::query_as::<_, PickingList>(
+            r#"
+            UPDATE picking_lists
+            SET status = 'cancelled'
+            WHERE id = $1
+                AND status IN ('open', 'in_progress')
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+}
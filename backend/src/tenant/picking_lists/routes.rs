@@ -0,0 +1,45 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::PickingListsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: PickingListsModuleInterface>(picking_lists_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/picking_lists",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/list", get(handler::list::<M>))
+            .route("/generate", post(handler::generate::<M>))
+            .route("/assign", put(handler::assign::<M>))
+            .route("/record_pick", put(handler::record_pick::<M>))
+            .route("/complete", put(handler::complete::<M>))
+            .route("/cancel", put(handler::cancel::<M>))
+            .layer(from_fn_with_state(
+                picking_lists_module.clone(),
+                require_auth,
+            ))
+            .with_state(picking_lists_module),
+    )
+}
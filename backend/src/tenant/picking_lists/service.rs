@@ -0,0 +1,257 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::picking_lists::PickingListsModuleInterface;
+use crate::tenant::picking_lists::dto::{AssignPicker, GeneratePickingLists, RecordPick};
+use crate::tenant::picking_lists::model::{PickingList, PickingListItem, PickingListWithItems};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PickingListsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PickingListsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PickingListsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PickingListsServiceError> for AppError {
+    fn from(value: PickingListsServiceError) -> Self {
+        match value {
+            PickingListsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PickingListsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PickingListsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PickingListsServiceResult<T> = Result<T, PickingListsServiceError>;
+
+pub trait PickingListsService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = PickingListsServiceResult<PickingListWithItems>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = PickingListsServiceResult<(PaginatorMeta, Vec<PickingList>)>> + Send;
+    fn generate(
+        &self,
+        payload: &GeneratePickingLists,
+    ) -> impl Future<Output = PickingListsServiceResult<Vec<PickingList>>> + Send;
+    fn assign(
+        &self,
+        payload: &AssignPicker,
+    ) -> impl Future<Output = PickingListsServiceResult<PickingList>> + Send;
+    fn record_pick(
+        &self,
+        payload: &RecordPick,
+    ) -> impl Future<Output = PickingListsServiceResult<PickingListItem>> + Send;
+    fn complete(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = PickingListsServiceResult<PickingList>> + Send;
+    fn cancel(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = PickingListsServiceResult<PickingList>> + Send;
+}
+
+impl<'a, T> PickingListsService for Service<'a, T>
+where
+    T: PickingListsModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> PickingListsServiceResult<PickingListWithItems> {
+        let repo = self.module().picking_lists_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PickingListsServiceError::Unauthorized)?,
+        )?;
+        Ok(PickingListWithItems {
+            picking_list: repo.get_by_id(id).await?,
+            items: repo.get_items(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> PickingListsServiceResult<(PaginatorMeta, Vec<PickingList>)> {
+        Ok(self
+            .module()
+            .picking_lists_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PickingListsServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn generate(
+        &self,
+        payload: &GeneratePickingLists,
+    ) -> PickingListsServiceResult<Vec<PickingList>> {
+        Ok(self
+            .module()
+            .picking_lists_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PickingListsServiceError::Unauthorized)?,
+            )?
+            .generate(payload, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn assign(&self, payload: &AssignPicker) -> PickingListsServiceResult<PickingList> {
+        Ok(self
+            .module()
+            .picking_lists_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PickingListsServiceError::Unauthorized)?,
+            )?
+            .assign(payload)
+            .await?)
+    }
+
+    async fn record_pick(
+        &self,
+        payload: &RecordPick,
+    ) -> PickingListsServiceResult<PickingListItem> {
+        let repo = self.module().picking_lists_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PickingListsServiceError::Unauthorized)?,
+        )?;
+        let item = repo.get_item_by_id(payload.item_id).await?;
+
+        if payload.quantity_picked < BigDecimal::zero()
+            || payload.quantity_picked > item.quantity_requested
+        {
+            return Err(PickingListsServiceError::UnprocessableEntry(
+                "A kiszedett mennyiség nem lehet negatív vagy több a kértnél!",
+            ));
+        }
+
+        let short_reason = payload
+            .short_reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty());
+        let is_short = payload.quantity_picked < item.quantity_requested;
+        if is_short && short_reason.is_none() {
+            return Err(PickingListsServiceError::UnprocessableEntry(
+                "Hiányos kiszedés esetén az ok megadása kötelező!",
+            ));
+        }
+
+        Ok(repo
+            .record_pick(
+                &RecordPick {
+                    item_id: payload.item_id,
+                    quantity_picked: payload.quantity_picked.clone(),
+                    short_reason: short_reason.filter(|_| is_short).map(str::to_owned),
+                },
+                self.claims()?.sub(),
+            )
+            .await?)
+    }
+
+    async fn complete(&self, id: Uuid) -> PickingListsServiceResult<PickingList> {
+        let repo = self.module().picking_lists_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PickingListsServiceError::Unauthorized)?,
+        )?;
+        if repo
+            .get_items(id)
+            .await?
+            .iter()
+            .any(|item| item.quantity_picked.is_none())
+        {
+            return Err(PickingListsServiceError::UnprocessableEntry(
+                "A lista lezárása előtt minden tétel kiszedését rögzíteni kell!",
+            ));
+        }
+        Ok(repo.complete(id).await?)
+    }
+
+    async fn cancel(&self, id: Uuid) -> PickingListsServiceResult<PickingList> {
+        Ok(self
+            .module()
+            .picking_lists_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PickingListsServiceError::Unauthorized)?,
+            )?
+            .cancel(id)
+            .await?)
+    }
+}
@@ -0,0 +1,171 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use image::ImageFormat;
+use image::codecs::jpeg::JpegEncoder;
+use uuid::Uuid;
+
+pub const MAX_ATTACHMENT_SIZE: usize = 20 * 1024 * 1024;
+pub const THUMBNAIL_SIZE: u32 = 320;
+
+const DOCUMENT_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "text/plain",
+    "text/csv",
+    "application/msword",
+    "application/vnd.ms-excel",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachmentUpload {
+    pub product_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl AttachmentUpload {
+    // NOTE: images are recognised by their content, the client supplied type is only trusted for documents
+    pub fn kind(&self) -> Option<(&'static str, String)> {
+        match image::guess_format(&self.data) {
+            Ok(format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)) => {
+                Some(("image", format.to_mime_type().to_string()))
+            }
+            _ => {
+                let content_type = self.content_type.trim().to_lowercase();
+                DOCUMENT_CONTENT_TYPES
+                    .contains(&content_type.as_str())
+                    .then_some(("document", content_type))
+            }
+        }
+    }
+
+    pub fn sanitized_file_name(&self) -> String {
+        sanitize_file_name(&self.file_name)
+    }
+}
+
+// NOTE: browsers may send the full client side path, only the last component is kept
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(255)
+        .collect::<String>();
+    match name.trim() {
+        "" => "attachment".to_string(),
+        name => name.to_string(),
+    }
+}
+
+// NOTE: header values must be ASCII, the original name is passed along as RFC 5987 filename*
+pub fn content_disposition(disposition: &str, file_name: &str) -> String {
+    let fallback: String = file_name
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect();
+    format!(r#"{disposition}; filename="{fallback}"; filename*=UTF-8''{encoded}"#)
+}
+
+pub fn thumbnail(data: &[u8]) -> Result<Vec<u8>, image::ImageError> {
+    let image = image::load_from_memory(data)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    let mut buffer = vec![];
+    image
+        .to_rgb8()
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut buffer, 80))?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+    use std::io::Cursor;
+
+    pub(crate) fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buffer = Cursor::new(vec![]);
+        ImageBuffer::from_pixel(width, height, Rgb([200u8, 30, 30]))
+            .write_to(&mut buffer, ImageFormat::Png)
+            .unwrap();
+        buffer.into_inner()
+    }
+
+    fn upload(file_name: &str, content_type: &str, data: Vec<u8>) -> AttachmentUpload {
+        AttachmentUpload {
+            product_id: Uuid::new_v4(),
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_kind_detection() {
+        assert_eq!(
+            upload("a.png", "application/octet-stream", png(2, 2)).kind(),
+            Some(("image", "image/png".to_string()))
+        );
+        assert_eq!(
+            upload("a.pdf", "Application/PDF", b"%PDF-1.7".to_vec()).kind(),
+            Some(("document", "application/pdf".to_string()))
+        );
+        assert_eq!(upload("a.png", "image/png", b"nope".to_vec()).kind(), None);
+        assert_eq!(
+            upload("C:\\fakepath\\adat\"lap.pdf", "application/pdf", vec![]).sanitized_file_name(),
+            "adatlap.pdf"
+        );
+    }
+
+    #[test]
+    fn test_content_disposition_encodes_non_ascii_name() {
+        assert_eq!(
+            content_disposition("attachment", "műszaki adatlap.pdf"),
+            r#"attachment; filename="m_szaki adatlap.pdf"; filename*=UTF-8''m%C5%B1szaki%20adatlap.pdf"#
+        );
+    }
+
+    #[test]
+    fn test_thumbnail_fits_bounding_box() {
+        let thumbnail = thumbnail(&png(1200, 600)).unwrap();
+        let image = image::load_from_memory(&thumbnail).unwrap();
+
+        assert_eq!(image::guess_format(&thumbnail).unwrap(), ImageFormat::Jpeg);
+        assert_eq!((image.width(), image.height()), (320, 160));
+    }
+}
@@ -0,0 +1,230 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::tenant::products::types::product::ProductBarcode;
+use image::codecs::png::PngEncoder;
+use image::{GrayImage, Luma};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const MAX_BARCODE_PRODUCTS: usize = 500;
+pub const MAX_LABEL_COPIES: u32 = 100;
+
+// NOTE: EAN-13 codes are generated in the GS1 restricted circulation range (prefix 20), which is
+// reserved for in-store use and never collides with manufacturer codes
+const EAN13_PREFIX: &str = "20";
+const CODE128_PREFIX: &str = "INT-";
+const QUIET_ZONE: u32 = 10;
+const MODULE_PX: u32 = 3;
+const BAR_HEIGHT_PX: u32 = 180;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BarcodeSymbology {
+    Ean13,
+    Code128,
+}
+
+impl BarcodeSymbology {
+    /// Builds the code for the given sequence number.
+    pub fn code(&self, sequence: i64) -> String {
+        match self {
+            Self::Ean13 => {
+                let payload = format!("{EAN13_PREFIX}{sequence:010}");
+                let digits: Vec<u8> = payload.bytes().map(|b| b - b'0').collect();
+                format!("{payload}{}", ProductBarcode::gtin_check_digit(&digits))
+            }
+            Self::Code128 => format!("{CODE128_PREFIX}{sequence:08}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct BarcodeGenerateInput {
+    pub product_ids: Vec<Uuid>,
+    pub symbology: BarcodeSymbology,
+}
+
+fn default_copies() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct BarcodeLabelsInput {
+    pub product_ids: Vec<Uuid>,
+    #[serde(default = "default_copies")]
+    pub copies: u32,
+}
+
+/// One printed label, `modules` holds the bars as a string of `1` (bar) and `0` (space).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BarcodeLabelPrint {
+    pub name: String,
+    pub sku: Option<String>,
+    pub barcode: String,
+    pub modules: String,
+}
+
+const EAN13_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+const EAN13_G: [&str; 10] = [
+    "0100111", "0110011", "0011011", "0100001", "0011101", "0111001", "0000101", "0010001",
+    "0001001", "0010111",
+];
+const EAN13_R: [&str; 10] = [
+    "1110010", "1100110", "1101100", "1000010", "1011100", "1001110", "1010000", "1000100",
+    "1001000", "1110100",
+];
+const EAN13_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+// NOTE: bar/space widths of the Code128 symbols 0-105, followed by the stop pattern
+const CODE128: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE128_START_B: usize = 104;
+const CODE128_STOP: usize = 106;
+
+fn ean13_modules(code: &str) -> String {
+    let digits: Vec<usize> = code.bytes().map(|b| usize::from(b - b'0')).collect();
+    let mut modules = String::from("101");
+    for (digit, parity) in digits[1..7].iter().zip(EAN13_PARITY[digits[0]].chars()) {
+        modules.push_str(if parity == 'L' {
+            EAN13_L[*digit]
+        } else {
+            EAN13_G[*digit]
+        });
+    }
+    modules.push_str("01010");
+    for digit in &digits[7..] {
+        modules.push_str(EAN13_R[*digit]);
+    }
+    modules.push_str("101");
+    modules
+}
+
+fn code128_modules(code: &str) -> String {
+    let values: Vec<usize> = code.bytes().map(|b| usize::from(b - b' ')).collect();
+    let checksum = values
+        .iter()
+        .enumerate()
+        .fold(CODE128_START_B, |sum, (i, value)| sum + (i + 1) * value)
+        % 103;
+    let mut modules = String::new();
+    for symbol in std::iter::once(CODE128_START_B)
+        .chain(values)
+        .chain([checksum, CODE128_STOP])
+    {
+        for (i, width) in CODE128[symbol].bytes().enumerate() {
+            let module = if i % 2 == 0 { '1' } else { '0' };
+            modules.extend(std::iter::repeat_n(module, usize::from(width - b'0')));
+        }
+    }
+    modules
+}
+
+/// Encodes a stored barcode into its bars. Valid EAN-13 codes are printed as EAN-13, anything
+/// else as Code128 (subset B), which covers every value the barcode value object accepts.
+pub fn barcode_modules(code: &str) -> Option<String> {
+    if code.is_empty() || !code.bytes().all(|b| b.is_ascii_graphic()) {
+        return None;
+    }
+    if code.len() == 13 && code.bytes().all(|b| b.is_ascii_digit()) {
+        let digits: Vec<u8> = code.bytes().map(|b| b - b'0').collect();
+        if ProductBarcode::gtin_check_digit(&digits[..12]) == digits[12] {
+            return Some(ean13_modules(code));
+        }
+    }
+    Some(code128_modules(code))
+}
+
+pub fn barcode_png(modules: &str) -> Result<Vec<u8>, image::ImageError> {
+    let width = (modules.len() as u32 + 2 * QUIET_ZONE) * MODULE_PX;
+    let height = BAR_HEIGHT_PX + 2 * QUIET_ZONE * MODULE_PX;
+    let bars: Vec<bool> = modules.chars().map(|module| module == '1').collect();
+    let bar_area = QUIET_ZONE * MODULE_PX..QUIET_ZONE * MODULE_PX + BAR_HEIGHT_PX;
+    let image = GrayImage::from_fn(width, height, |x, y| {
+        let module = (x / MODULE_PX).checked_sub(QUIET_ZONE);
+        match module.and_then(|module| bars.get(module as usize)) {
+            Some(true) if bar_area.contains(&y) => Luma([0u8]),
+            _ => Luma([255u8]),
+        }
+    });
+    let mut buffer = vec![];
+    image.write_with_encoder(PngEncoder::new(&mut buffer))?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::value_object::ValueObjectRequired;
+
+    #[test]
+    fn test_generated_codes_are_valid() {
+        let ean13 = BarcodeSymbology::Ean13.code(42);
+        assert_eq!(ean13, "2000000000428");
+        assert!(ean13.parse::<ValueObjectRequired<ProductBarcode>>().is_ok());
+        assert_eq!(BarcodeSymbology::Code128.code(42), "INT-00000042");
+    }
+
+    #[test]
+    fn test_ean13_modules() {
+        let modules = barcode_modules("5901234123457").unwrap();
+        assert_eq!(modules.len(), 95);
+        assert!(modules.starts_with("1010001011"));
+        assert!(modules.ends_with("1000100101"));
+    }
+
+    #[test]
+    fn test_code128_modules() {
+        assert!(
+            CODE128[..106]
+                .iter()
+                .all(|pattern| { pattern.bytes().map(|b| u32::from(b - b'0')).sum::<u32>() == 11 })
+        );
+        // NOTE: start, 8 data symbols, checksum and the 13 module wide stop pattern
+        let modules = barcode_modules("INT-0042").unwrap();
+        assert_eq!(modules.len(), 11 * 10 + 13);
+        assert!(modules.starts_with("11010010000"));
+        assert!(modules.ends_with("1100011101011"));
+        assert_eq!(barcode_modules("INT 0042"), None);
+    }
+
+    #[test]
+    fn test_barcode_png() {
+        let png = barcode_png(&barcode_modules("5901234123457").unwrap()).unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!(image.width(), (95 + 2 * QUIET_ZONE) * MODULE_PX);
+    }
+}
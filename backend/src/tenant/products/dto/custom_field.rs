@@ -0,0 +1,264 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::products::model::ProductCustomField;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use uuid::Uuid;
+
+pub const CUSTOM_FIELD_TYPES: [&str; 4] = ["text", "number", "date", "select"];
+const MAX_TEXT_LENGTH: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductCustomFieldInput {
+    pub id: Option<Uuid>,
+    pub key: String,
+    pub label: String,
+    pub field_type: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub is_required: bool,
+    #[serde(default)]
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductCustomFieldValuesInput {
+    pub product_id: Uuid,
+    pub values: Map<String, Value>,
+}
+
+/// The key is interpolated into the `custom_fields.<key>` list filter, so only lowercase
+/// identifiers are accepted.
+pub fn is_custom_field_key(key: &str) -> bool {
+    key.len() <= 64
+        && key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+impl ProductCustomFieldInput {
+    pub fn validate(&self) -> Result<Self, &'static str> {
+        let key = self.key.trim();
+        if !is_custom_field_key(key) {
+            return Err(
+                "A mező kulcsa kisbetűvel kezdődik, és csak kisbetűt, számot vagy aláhúzást tartalmazhat!",
+            );
+        }
+        let label = self.label.trim();
+        if label.is_empty() || label.chars().count() > 255 {
+            return Err("A mező megnevezése kötelező, legfeljebb 255 karakter!");
+        }
+        if !CUSTOM_FIELD_TYPES.contains(&self.field_type.as_str()) {
+            return Err("Hibás mezőtípus!");
+        }
+        let mut options: Vec<String> = Vec::new();
+        if self.field_type == "select" {
+            for option in self.options.iter().map(|option| option.trim()) {
+                if option.is_empty() || option.chars().count() > 255 {
+                    return Err("A választható értékek legfeljebb 255 karakteresek lehetnek!");
+                }
+                if !options.iter().any(|existing| existing == option) {
+                    options.push(option.to_string());
+                }
+            }
+            if options.is_empty() {
+                return Err("A választó mezőhöz legalább egy értéket meg kell adni!");
+            }
+        }
+        Ok(Self {
+            id: self.id,
+            key: key.to_string(),
+            label: label.to_string(),
+            field_type: self.field_type.clone(),
+            options,
+            is_required: self.is_required,
+            position: self.position,
+        })
+    }
+}
+
+fn normalize_value(field: &ProductCustomField, value: &Value) -> Result<Value, &'static str> {
+    match (field.field_type.as_str(), value) {
+        ("number", Value::Number(number)) => Ok(Value::Number(number.clone())),
+        ("number", Value::String(number)) => number
+            .trim()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .ok_or("Az egyedi mező értéke nem szám!"),
+        ("date", Value::String(date)) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map(|date| Value::String(date.to_string()))
+            .map_err(|_| "Az egyedi mező értéke nem dátum (ÉÉÉÉ-HH-NN)!"),
+        ("select", Value::String(option)) => field
+            .options
+            .iter()
+            .find(|allowed| *allowed == option.trim())
+            .map(|allowed| Value::String(allowed.clone()))
+            .ok_or("Az egyedi mező értéke nem szerepel a választható értékek között!"),
+        ("text", Value::String(text))
+            if !text.trim().is_empty() && text.trim().chars().count() <= MAX_TEXT_LENGTH =>
+        {
+            Ok(Value::String(text.trim().to_string()))
+        }
+        _ => Err("Az egyedi mező értéke nem felel meg a mező típusának!"),
+    }
+}
+
+/// Applies `values` on top of the product's current custom fields. A `null` value clears the
+/// field, values of fields that have been removed since are dropped.
+pub fn merge_custom_field_values(
+    definitions: &[ProductCustomField],
+    current: &Value,
+    values: &Map<String, Value>,
+) -> Result<Map<String, Value>, &'static str> {
+    let mut merged: Map<String, Value> = current
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| definitions.iter().any(|field| &field.key == *key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for (key, value) in values {
+        let field = definitions
+            .iter()
+            .find(|field| &field.key == key)
+            .ok_or("Ismeretlen egyedi mező!")?;
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            merged.insert(key.clone(), normalize_value(field, value)?);
+        }
+    }
+    if definitions
+        .iter()
+        .any(|field| field.is_required && !merged.contains_key(&field.key))
+    {
+        return Err("Egy kötelező egyedi mező nincs kitöltve!");
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn field(
+        key: &str,
+        field_type: &str,
+        options: &[&str],
+        is_required: bool,
+    ) -> ProductCustomField {
+        ProductCustomField {
+            id: Uuid::new_v4(),
+            key: key.to_string(),
+            label: key.to_string(),
+            field_type: field_type.to_string(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+            is_required,
+            position: 0,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_custom_field_input() {
+        let input = ProductCustomFieldInput {
+            id: None,
+            key: " material ".to_string(),
+            label: " Anyag ".to_string(),
+            field_type: "select".to_string(),
+            options: vec!["fa".to_string(), " fém ".to_string(), "fa".to_string()],
+            is_required: false,
+            position: 1,
+        };
+        let valid = input.validate().unwrap();
+        assert_eq!(valid.key, "material");
+        assert_eq!(valid.label, "Anyag");
+        assert_eq!(valid.options, vec!["fa", "fém"]);
+
+        for key in [
+            "Material",
+            "1st",
+            "anyag-tipus",
+            "",
+            "a'; DROP TABLE products; --",
+        ] {
+            let input = ProductCustomFieldInput {
+                key: key.to_string(),
+                ..input.clone()
+            };
+            assert!(input.validate().is_err(), "{key}");
+        }
+        let input = ProductCustomFieldInput {
+            options: vec![],
+            ..input
+        };
+        assert!(input.validate().is_err());
+    }
+
+    #[test]
+    fn test_merge_custom_field_values() {
+        let definitions = vec![
+            field("material", "select", &["fa", "fém"], true),
+            field("weight_limit", "number", &[], false),
+            field("certified_at", "date", &[], false),
+            field("note", "text", &[], false),
+        ];
+        let current = json!({"material": "fa", "note": "régi", "removed": "x"});
+
+        let merged = merge_custom_field_values(
+            &definitions,
+            &current,
+            json!({"weight_limit": "12,5", "certified_at": "2026-03-01", "note": null})
+                .as_object()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            Value::Object(merged),
+            json!({"material": "fa", "weight_limit": 12.5, "certified_at": "2026-03-01"})
+        );
+
+        for values in [
+            json!({"unknown": "x"}),
+            json!({"material": "műanyag"}),
+            json!({"material": null}),
+            json!({"weight_limit": "sok"}),
+            json!({"certified_at": "2026.03.01"}),
+            json!({"note": 5}),
+        ] {
+            assert!(
+                merge_custom_field_values(&definitions, &current, values.as_object().unwrap())
+                    .is_err(),
+                "{values}"
+            );
+        }
+    }
+}
@@ -0,0 +1,45 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Minimum trigram similarity of the normalized names for a product to be reported as a
+/// likely duplicate.
+pub const DUPLICATE_NAME_SIMILARITY: f32 = 0.5;
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ProductCreateQuery {
+    #[serde(default)]
+    pub check_duplicates: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductDuplicateQuery {
+    pub name: String,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub exclude_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductMergeInput {
+    pub duplicate_id: Uuid,
+    pub surviving_id: Uuid,
+}
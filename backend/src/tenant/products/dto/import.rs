@@ -0,0 +1,241 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::common::utils::{csv_reader, non_empty, parse_amount};
+use crate::common::value_object::{ValueObjectOptional, ValueObjectRequired};
+use crate::tenant::currencies::types::CurrencyCode;
+use crate::tenant::products::types::product::{
+    ProductBarcode, ProductDescription, ProductName, ProductSku, ProductStatus,
+};
+use crate::tenant::products::types::unit_of_measure::UnitsOfMeasure;
+use bigdecimal::{BigDecimal, Zero};
+use serde::Deserialize;
+
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductImportQuery {
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ProductImportRecord {
+    sku: String,
+    name: String,
+    description: String,
+    barcode: String,
+    unit_of_measure: String,
+    status: String,
+    price: String,
+    currency_code: String,
+}
+
+/// One CSV row after parsing. Products are matched by SKU, so every row has to carry one.
+/// Rows with a non-empty `errors` list are reported back, but never touch the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductImportRow {
+    pub line: u64,
+    pub sku: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub barcode: Option<String>,
+    pub unit_of_measure: String,
+    pub status: String,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl ProductImportRow {
+    /// Parses an uploaded CSV file in the same layout the export produces, see [`csv_reader`]
+    /// for the accepted formats.
+    pub fn parse_csv(data: &[u8]) -> Result<Vec<Self>, &'static str> {
+        let (mut reader, semicolon) = csv_reader(data)?;
+
+        let headers = reader
+            .headers()
+            .map_err(|_| "A fájl fejléce nem olvasható!")?
+            .clone();
+        if !["sku", "name", "unit_of_measure"]
+            .iter()
+            .all(|column| headers.iter().any(|h| h == *column))
+        {
+            return Err(
+                "A fájl fejlécének tartalmaznia kell a sku, name és unit_of_measure oszlopokat!",
+            );
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            if rows.len() == MAX_IMPORT_ROWS {
+                return Err("Egyszerre legfeljebb 5000 sor importálható!");
+            }
+            let parsed = record.and_then(|record| {
+                let line = record.position().map_or(0, |p| p.line());
+                Ok((
+                    line,
+                    record.deserialize::<ProductImportRecord>(Some(&headers))?,
+                ))
+            });
+            match parsed {
+                Ok((line, record)) => rows.push(Self::from_record(line, record, semicolon)),
+                Err(e) => rows.push(Self {
+                    line: e.position().map_or(0, |p| p.line()),
+                    sku: String::new(),
+                    name: String::new(),
+                    description: None,
+                    barcode: None,
+                    unit_of_measure: String::new(),
+                    status: String::new(),
+                    price: None,
+                    currency_code: None,
+                    errors: vec!["A sor nem olvasható!".to_owned()],
+                }),
+            }
+        }
+        if rows.is_empty() {
+            return Err("A fájl nem tartalmaz egy sort sem!");
+        }
+        Ok(rows)
+    }
+
+    fn from_record(line: u64, record: ProductImportRecord, decimal_comma: bool) -> Self {
+        let mut errors = Vec::new();
+
+        let sku = record.sku.trim().to_owned();
+        if sku.is_empty() {
+            errors.push("A cikkszám megadása kötelező!".to_owned());
+        } else if let Err(e) = sku.parse::<ValueObjectRequired<ProductSku>>() {
+            errors.push(e.to_string());
+        }
+        let name = record.name.trim().to_owned();
+        if let Err(e) = name.parse::<ValueObjectRequired<ProductName>>() {
+            errors.push(e.to_string());
+        }
+        let unit_of_measure = record.unit_of_measure.trim().to_owned();
+        if let Err(e) = unit_of_measure.parse::<ValueObjectRequired<UnitsOfMeasure>>() {
+            errors.push(e.to_string());
+        }
+
+        let description = non_empty(&record.description);
+        if let Some(description) = &description
+            && let Err(e) = description.parse::<ValueObjectOptional<ProductDescription>>()
+        {
+            errors.push(e.to_string());
+        }
+        let barcode = non_empty(&record.barcode);
+        if let Some(barcode) = &barcode
+            && let Err(e) = barcode.parse::<ValueObjectOptional<ProductBarcode>>()
+        {
+            errors.push(e.to_string());
+        }
+
+        let status = non_empty(&record.status)
+            .unwrap_or_else(|| "active".to_owned())
+            .to_lowercase();
+        if let Err(e) = status.parse::<ValueObjectRequired<ProductStatus>>() {
+            errors.push(e.to_string());
+        }
+
+        let price = match parse_amount(&record.price, decimal_comma) {
+            None => None,
+            Some(Err(())) => {
+                errors.push("Érvénytelen ár!".to_owned());
+                None
+            }
+            Some(Ok(price)) if price < BigDecimal::zero() => {
+                errors.push("Az ár nem lehet negatív!".to_owned());
+                None
+            }
+            Some(Ok(price)) => Some(price),
+        };
+        let currency_code = non_empty(&record.currency_code).map(|code| code.to_uppercase());
+        if let Some(currency_code) = &currency_code
+            && let Err(e) = currency_code.parse::<ValueObjectRequired<CurrencyCode>>()
+        {
+            errors.push(e.to_string());
+        }
+        if price.is_some() && currency_code.is_none() {
+            errors.push("Ár megadása esetén a pénznem megadása kötelező!".to_owned());
+        }
+
+        Self {
+            line,
+            sku,
+            name,
+            description,
+            barcode,
+            unit_of_measure,
+            status,
+            price,
+            currency_code,
+            errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_csv_comma_separated() {
+        let rows = ProductImportRow::parse_csv(
+            b"sku,name,description,barcode,unit_of_measure,status,price,currency_code\n\
+              CSV-10,Cable,Copper,,m,,120.5,huf\n\
+              ,Cable,,5901234123458,,unknown,-1,\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(rows[0].status, "active");
+        assert_eq!(rows[0].price, Some(BigDecimal::from_str("120.5").unwrap()));
+        assert_eq!(rows[0].currency_code.as_deref(), Some("HUF"));
+        assert_eq!(rows[1].line, 3);
+        assert_eq!(rows[1].errors.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_csv_semicolon_with_decimal_comma() {
+        let rows = ProductImportRow::parse_csv(
+            "\u{feff}sku;name;unit_of_measure;price;currency_code\nCSV-10;Cable;m;1200,25;EUR\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(
+            rows[0].price,
+            Some(BigDecimal::from_str("1200.25").unwrap())
+        );
+        assert_eq!(rows[0].description, None);
+    }
+
+    #[test]
+    fn test_parse_csv_missing_columns() {
+        assert!(ProductImportRow::parse_csv(b"sku,name\nCSV-10,Cable\n").is_err());
+        assert!(ProductImportRow::parse_csv(b"sku,name,unit_of_measure\n").is_err());
+    }
+}
@@ -17,5 +17,15 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod attachment;
+pub mod barcode;
+pub mod bundle;
+pub mod custom_field;
+pub mod dimensions;
+pub mod duplicate;
+pub mod import;
 pub mod print;
+pub mod status;
+pub mod stock_threshold;
 pub mod user_input;
+pub mod variant;
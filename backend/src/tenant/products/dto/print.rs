@@ -67,8 +67,10 @@ impl ProductsResolvedPrint {
     }
     fn map_status(status: &str) -> String {
         match status {
+            "draft" => "Tervezet",
             "active" => "Aktív",
-            "inactive" => "Inaktív",
+            "discontinued" => "Kifutó",
+            "archived" => "Archivált",
             _ => "Ismeretlen státusz",
         }
         .to_string()
@@ -81,6 +83,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_from_product_resolved() {
@@ -94,6 +97,13 @@ mod tests {
             id: product_id,
             name: "Test product".to_string(),
             description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
             unit_of_measure_id,
             unit_of_measure: "cm".to_string(),
             status: "active".to_string(),
@@ -102,6 +112,7 @@ mod tests {
             created_at: input_date,
             updated_at: input_date,
             deleted_at: None,
+            attachments: vec![],
         };
         let product_resolved_print =
             ProductsResolvedPrint::from_product_resolved(product_resolved, tz);
@@ -20,7 +20,9 @@
 use crate::common::error::v2::{AppError, AppErrorVisibility};
 use crate::common::types::UuidVO;
 use crate::common::value_object::{ValueObjectError, ValueObjectOptional, ValueObjectRequired};
-use crate::tenant::products::types::product::{ProductDescription, ProductName, ProductStatus};
+use crate::tenant::products::types::product::{
+    ProductBarcode, ProductDescription, ProductName, ProductSku, ProductStatus,
+};
 use crate::tenant::products::types::unit_of_measure::UnitsOfMeasure;
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
@@ -36,6 +38,10 @@ pub struct ProductUserInputHelper {
     pub unit_of_measure_id: String,
     pub new_unit_of_measure: String,
     pub status: String,
+    #[serde(default)]
+    pub sku: String,
+    #[serde(default)]
+    pub barcode: String,
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -46,6 +52,8 @@ pub struct ProductUserInputError {
     pub unit_of_measure_id: Option<String>,
     pub new_unit_of_measure: Option<String>,
     pub status: Option<String>,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
 }
 
 impl ProductUserInputError {
@@ -55,6 +63,8 @@ impl ProductUserInputError {
             && self.description.is_none()
             && self.unit_of_measure_id.is_none()
             && self.status.is_none()
+            && self.sku.is_none()
+            && self.barcode.is_none()
     }
 }
 
@@ -96,6 +106,8 @@ pub struct ProductUserInput {
     pub unit_of_measure_id: Option<ValueObjectRequired<UuidVO>>,
     pub new_unit_of_measure: Option<ValueObjectRequired<UnitsOfMeasure>>,
     pub status: ValueObjectRequired<ProductStatus>,
+    pub sku: ValueObjectOptional<ProductSku>,
+    pub barcode: ValueObjectOptional<ProductBarcode>,
 }
 
 impl TryFrom<ProductUserInputHelper> for ProductUserInput {
@@ -132,6 +144,20 @@ impl TryFrom<ProductUserInputHelper> for ProductUserInput {
                 error.description = Some(e.to_string());
             });
 
+        let sku = value
+            .sku
+            .parse::<ValueObjectOptional<ProductSku>>()
+            .inspect_err(|e| {
+                error.sku = Some(e.to_string());
+            });
+
+        let barcode = value
+            .barcode
+            .parse::<ValueObjectOptional<ProductBarcode>>()
+            .inspect_err(|e| {
+                error.barcode = Some(e.to_string());
+            });
+
         let unit_of_measure_id = if value.unit_of_measure_id.as_str() != "other" {
             value
                 .unit_of_measure_id
@@ -164,6 +190,8 @@ impl TryFrom<ProductUserInputHelper> for ProductUserInput {
                 unit_of_measure_id: unit_of_measure_id?,
                 new_unit_of_measure: new_unit_of_measure?,
                 status: status?,
+                sku: sku?,
+                barcode: barcode?,
             })
         } else {
             Err(error)
@@ -184,6 +212,8 @@ mod tests {
             unit_of_measure_id: String::from("other"),
             new_unit_of_measure: String::from("cm"),
             status: String::from("active"),
+            sku: String::from("CSV-10"),
+            barcode: String::from("5901234123457"),
         })
         .unwrap();
 
@@ -196,6 +226,8 @@ mod tests {
             "cm"
         );
         assert_eq!(user_input.status.as_str().unwrap(), "active");
+        assert_eq!(user_input.sku.as_str().unwrap(), "CSV-10");
+        assert_eq!(user_input.barcode.as_str().unwrap(), "5901234123457");
     }
 
     #[test]
@@ -208,6 +240,8 @@ mod tests {
             unit_of_measure_id: String::from(""),
             new_unit_of_measure: String::from(""),
             status: String::from("activeee"),
+            sku: String::from("CSV 10"),
+            barcode: String::from("5901234123458"),
         })
         .unwrap_err();
 
@@ -226,5 +260,10 @@ mod tests {
             ValueObjectError::REQUIRED
         );
         assert_eq!(user_input.status.unwrap(), ProductStatus::VALIDATION_ERROR);
+        assert_eq!(user_input.sku.unwrap(), ProductSku::VALIDATION_ERROR);
+        assert_eq!(
+            user_input.barcode.unwrap(),
+            ProductBarcode::VALIDATION_ERROR
+        );
     }
 }
@@ -0,0 +1,59 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductVariantInput {
+    pub id: Option<Uuid>,
+    pub parent_id: Uuid,
+    pub attributes: BTreeMap<String, String>,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub status: String,
+}
+
+impl ProductVariantInput {
+    pub fn variant_name(&self, template_name: &str) -> String {
+        let values: Vec<&str> = self.attributes.values().map(String::as_str).collect();
+        format!("{template_name} ({})", values.join(" / "))
+            .chars()
+            .take(255)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VariantListMode {
+    #[default]
+    Flatten,
+    Group,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProductListQuery {
+    #[serde(default)]
+    pub variants: VariantListMode,
+}
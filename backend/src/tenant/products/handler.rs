@@ -17,23 +17,43 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::dto::{
+    DuplicateParams, EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam,
+};
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::products::ProductsModuleInterface;
+use crate::tenant::products::dto::attachment::{AttachmentUpload, content_disposition};
+use crate::tenant::products::dto::barcode::{BarcodeGenerateInput, BarcodeLabelsInput};
+use crate::tenant::products::dto::bundle::ProductBundleInput;
+use crate::tenant::products::dto::custom_field::{
+    ProductCustomFieldInput, ProductCustomFieldValuesInput,
+};
+use crate::tenant::products::dto::dimensions::ProductDimensionsInput;
+use crate::tenant::products::dto::duplicate::{ProductCreateQuery, ProductMergeInput};
+use crate::tenant::products::dto::import::ProductImportQuery;
 use crate::tenant::products::dto::print::ProductsResolvedPrint;
+use crate::tenant::products::dto::status::ProductStatusInput;
+use crate::tenant::products::dto::stock_threshold::ProductStockThresholdInput;
 use crate::tenant::products::dto::user_input::{ProductUserInput, ProductUserInputHelper};
-use crate::tenant::products::service::ProductService;
+use crate::tenant::products::dto::variant::{
+    ProductListQuery, ProductVariantInput, VariantListMode,
+};
+use crate::tenant::products::service::{ProductService, ProductsServiceError};
 use crate::tenant::products::types::product::{ProductFilterBy, ProductOrderBy};
-use axum::extract::{Query, State};
+use crate::tenant::suppliers::dto::ProductSupplierInput;
+use crate::tenant::tags::dto::TagFilter;
+use axum::body::Bytes;
+use axum::extract::{Multipart, Query, State};
 use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub async fn get_resolved<M: ProductsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
@@ -114,12 +134,127 @@ pub async fn delete<M: ProductsModuleInterface>(
     .into_response())
 }
 
+pub async fn get_dimensions<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_dimensions(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_dimensions<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductDimensionsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.set_dimensions(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_status<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductStatusInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result =
+        map_handler_err(service.set_status(&payload).await, products_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn get_stock_threshold<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_stock_threshold(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_stock_threshold<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductStockThresholdInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.set_stock_threshold(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn create<M: ProductsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(products_module): State<Arc<M>>,
+    Query(create_query): Query<ProductCreateQuery>,
     UserInput(mut user_input, _): UserInput<ProductUserInput, ProductUserInputHelper>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), products_module.clone());
+    if create_query.check_duplicates {
+        map_handler_err(
+            service.check_duplicates(&user_input).await,
+            products_module.clone(),
+        )
+        .await?;
+    }
     let result = map_handler_err(
         service.insert(&mut user_input).await,
         products_module.clone(),
@@ -140,6 +275,8 @@ pub async fn list<M: ProductsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(products_module): State<Arc<M>>,
     Query(payload): Query<CommonRawQuery>,
+    Query(list_query): Query<ProductListQuery>,
+    Query(tag_filter): Query<TagFilter>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), products_module.clone());
     let resource_query = map_handler_err(
@@ -147,16 +284,105 @@ pub async fn list<M: ProductsModuleInterface>(
         products_module.clone(),
     )
     .await?;
-    let (meta, data) = map_handler_err(
-        service.get_paged(&resource_query).await,
+    match list_query.variants {
+        VariantListMode::Flatten => {
+            let (meta, data) = map_handler_err(
+                service.get_paged(&resource_query, tag_filter.tags).await,
+                products_module.clone(),
+            )
+            .await?;
+            Ok(map_handler_err(
+                SuccessResponseBuilder::new()
+                    .status_code(StatusCode::OK)
+                    .meta(meta)
+                    .data(data)
+                    .build(),
+                products_module,
+            )
+            .await?
+            .into_response())
+        }
+        VariantListMode::Group => {
+            let (meta, data) = map_handler_err(
+                service
+                    .get_paged_grouped(&resource_query, tag_filter.tags)
+                    .await,
+                products_module.clone(),
+            )
+            .await?;
+            Ok(map_handler_err(
+                SuccessResponseBuilder::new()
+                    .status_code(StatusCode::OK)
+                    .meta(meta)
+                    .data(data)
+                    .build(),
+                products_module,
+            )
+            .await?
+            .into_response())
+        }
+    }
+}
+
+pub async fn variants<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_variants(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_variant<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductVariantInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.create_variant(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_variant<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductVariantInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.update_variant(&payload).await,
         products_module.clone(),
     )
     .await?;
     Ok(map_handler_err(
-        SuccessResponseBuilder::new()
+        SuccessResponseBuilder::<EmptyType, _>::new()
             .status_code(StatusCode::OK)
-            .meta(meta)
-            .data(data)
+            .data(result)
             .build(),
         products_module,
     )
@@ -222,64 +448,1179 @@ pub async fn print<M: ProductsModuleInterface>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::File;
-    use std::io::{Read, Write};
-    use std::path::Path;
+pub async fn bundle_components<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_bundle_components(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
 
-    use super::*;
-    use crate::common::dto::PaginatorMeta;
-    use crate::common::error::RepositoryError;
-    use crate::common::handler::tests::{
-        extract_json_response, generate_expired_jwt, generate_jwt_with_invalid_signature,
-        generate_valid_jwt,
-    };
-    use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
-    use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::products::model::ProductResolved;
-    use crate::{
-        common::config::tests::AppConfigBuilder,
-        tenant::products::{
-            self, model::Product, repository::MockProductsRepository, tests::MockProductsModule,
-        },
-    };
-    use axum::body::Body;
-    use axum::{Router, http::Request};
-    use chrono::{DateTime, Utc};
-    use mockall::predicate::eq;
-    use pretty_assertions::assert_eq;
-    use serde_json::json;
-    use tower::ServiceExt;
-    use uuid::Uuid;
+pub async fn set_bundle_components<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductBundleInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.set_bundle_components(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
 
-    #[tokio::test]
-    async fn test_get_success() {
-        let active_tenant_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-        let product = Product {
-            id: product_id,
-            name: "Test product".to_string(),
-            description: None,
-            unit_of_measure_id,
-            status: "active".to_string(),
-            created_by_id,
-            created_at: utc_now,
-            updated_at: utc_now,
-            deleted_at: None,
-        };
+pub async fn bundle_availability<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_bundle_availability(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
 
-        let mut repo = MockProductsRepository::new();
-        repo.expect_get_by_id()
-            .times(1)
+async fn read_upload(mut multipart: Multipart) -> Result<AttachmentUpload, ProductsServiceError> {
+    let invalid = |_| ProductsServiceError::UnprocessableEntry("Hibás feltöltési kérés!");
+    let mut product_id = None;
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        match field.name() {
+            Some("product_id") => {
+                product_id = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(invalid)?
+                        .trim()
+                        .parse::<Uuid>()
+                        .map_err(|_| {
+                            ProductsServiceError::UnprocessableEntry("Hibás termék azonosító!")
+                        })?,
+                );
+            }
+            Some("file") => {
+                let file_name = field.file_name().unwrap_or_default().to_string();
+                let content_type = field.content_type().unwrap_or_default().to_string();
+                file = Some((
+                    file_name,
+                    content_type,
+                    field.bytes().await.map_err(invalid)?,
+                ));
+            }
+            _ => {}
+        }
+    }
+    match (product_id, file) {
+        (Some(product_id), Some((file_name, content_type, data))) => Ok(AttachmentUpload {
+            product_id,
+            file_name,
+            content_type,
+            data: data.to_vec(),
+        }),
+        _ => Err(ProductsServiceError::UnprocessableEntry(
+            "A termék azonosító és a fájl megadása kötelező!",
+        )),
+    }
+}
+
+pub async fn upload_attachment<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    multipart: Multipart,
+) -> HandlerResult {
+    let payload = map_handler_err(read_upload(multipart).await, products_module.clone()).await?;
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.upload_attachment(payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn attachments<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_attachments(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn attachment<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let (attachment, data) = map_handler_err(
+        service.get_attachment_file(payload.uuid, false).await,
+        products_module,
+    )
+    .await?;
+    let disposition = if attachment.kind == "image" {
+        "inline"
+    } else {
+        "attachment"
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        attachment.content_type.parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        content_disposition(disposition, &attachment.file_name)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, data).into_response())
+}
+
+pub async fn attachment_thumbnail<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let (attachment, data) = map_handler_err(
+        service.get_attachment_file(payload.uuid, true).await,
+        products_module,
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "image/jpeg".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"inline; filename="{}_thumb.jpg""#, attachment.id)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, data).into_response())
+}
+
+pub async fn delete_attachment<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    map_handler_err(
+        service.delete_attachment(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A csatolmány törlése sikeresen megtörtént",
+            ))
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn generate_barcodes<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<BarcodeGenerateInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.generate_barcodes(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn barcode<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let (barcode, png) =
+        map_handler_err(service.get_barcode_png(payload.uuid).await, products_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "image/png".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        content_disposition("inline", &format!("{barcode}.png"))
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, png).into_response())
+}
+
+pub async fn barcode_labels<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<BarcodeLabelsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let pdf = map_handler_err(
+        service.print_barcode_labels(&payload).await,
+        products_module,
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        r#"inline; filename="vonalkodok.pdf""#.parse().unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+pub async fn export<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let csv = map_handler_err(service.export().await, products_module.clone()).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        r#"attachment; filename="termekek.csv""#.parse().unwrap(),
+    );
+    Ok((StatusCode::OK, headers, csv).into_response())
+}
+
+pub async fn import<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<ProductImportQuery>,
+    body: Bytes,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.import(&payload, &body).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn import_history<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result =
+        map_handler_err(service.get_import_history().await, products_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn suppliers<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_suppliers(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_supplier<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductSupplierInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.set_supplier(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn remove_supplier<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    map_handler_err(
+        service.remove_supplier(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A beszállító eltávolítása sikeresen megtörtént",
+            ))
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn custom_fields<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result =
+        map_handler_err(service.get_custom_fields().await, products_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_custom_field<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductCustomFieldInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.create_custom_field(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_custom_field<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductCustomFieldInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.update_custom_field(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete_custom_field<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    map_handler_err(
+        service.delete_custom_field(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az egyedi mező törlése sikeresen megtörtént",
+            ))
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_custom_fields<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductCustomFieldValuesInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.set_custom_fields(&payload).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn duplicates<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(
+        service.get_duplicates(payload.uuid).await,
+        products_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn merge<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProductMergeInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result = map_handler_err(service.merge(&payload).await, products_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn duplicate<M: ProductsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(products_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DuplicateParams>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), products_module.clone());
+    let result =
+        map_handler_err(service.duplicate(&payload).await, products_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        products_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::path::Path;
+
+    use super::*;
+    use crate::common::dto::PaginatorMeta;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::{
+        extract_json_response, generate_expired_jwt, generate_jwt_with_invalid_signature,
+        generate_valid_jwt,
+    };
+    use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
+    use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
+    use crate::common::storage::MockFileStorage;
+    use crate::manager::tenant_limits::model::TenantLimits;
+    use crate::manager::tenant_limits::repository::MockTenantLimitsRepository;
+    use crate::tenant::products::dto::attachment::tests::png;
+    use crate::tenant::products::dto::barcode::{
+        BarcodeLabelPrint, BarcodeSymbology, barcode_modules,
+    };
+    use crate::tenant::products::dto::import::ProductImportRow;
+    use crate::tenant::products::model::{
+        ProductBundleComponent, ProductCustomField, ProductDuplicateCandidate, ProductExportRow,
+        ProductImportLine, ProductImportReport, ProductResolved, ProductVariant,
+    };
+    use crate::tenant::suppliers::model::ProductSupplier;
+    use crate::tenant::suppliers::repository::MockSuppliersRepository;
+    use crate::{
+        common::config::tests::AppConfigBuilder,
+        tenant::products::{
+            self, model::Product, repository::MockProductsRepository, tests::MockProductsModule,
+        },
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_get_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let product = Product {
+            id: product_id,
+            name: "Test product".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id,
+            status: "active".to_string(),
+            created_by_id,
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning({
+                let product = product.clone();
+                move |_| Ok(product.clone())
+            });
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": product
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_unauthorized_expired() {
+        let product_id = Uuid::new_v4();
+
+        let mut app_state = MockProductsModule::new();
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_expired_jwt()),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNAUTHORIZED",
+                "message": "Hozzáférés megtagadva!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_unauthorized_invalid_signature() {
+        let product_id = Uuid::new_v4();
+
+        let mut app_state = MockProductsModule::new();
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_jwt_with_invalid_signature()),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNAUTHORIZED",
+                "message": "Hozzáférés megtagadva!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_unauthorized_missing() {
+        let product_id = Uuid::new_v4();
+        let app_state = MockProductsModule::new();
+        let request = Request::builder()
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({});
+
+        assert_eq!(response_body, expected_body);
+    }
+    #[tokio::test]
+    async fn test_get_not_found() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "NOT_FOUND",
+                "message": "Nem található"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_resolved_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let product_resolved = ProductResolved {
+            id: product_id,
+            name: "Test product".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id,
+            unit_of_measure: "cm".to_string(),
+            status: "active".to_string(),
+            created_by_id,
+            created_by: "Test User".to_string(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+            attachments: vec![],
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning({
+                let product_resolved = product_resolved.clone();
+                move |_| Ok(product_resolved.clone())
+            });
+        repo.expect_get_attachments()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": product_resolved
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_resolved_unauthorized_expired() {
+        let product_id = Uuid::new_v4();
+
+        let mut app_state = MockProductsModule::new();
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_expired_jwt()),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNAUTHORIZED",
+                "message": "Hozzáférés megtagadva!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_resolved_unauthorized_invalid_signature() {
+        let product_id = Uuid::new_v4();
+
+        let mut app_state = MockProductsModule::new();
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_jwt_with_invalid_signature()),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNAUTHORIZED",
+                "message": "Hozzáférés megtagadva!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_get_resolved_unauthorized_missing() {
+        let product_id = Uuid::new_v4();
+        let app_state = MockProductsModule::new();
+        let request = Request::builder()
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({});
+
+        assert_eq!(response_body, expected_body);
+    }
+    #[tokio::test]
+    async fn test_get_resolved_not_found() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .times(1)
             .with(eq(product_id))
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "NOT_FOUND",
+                "message": "Nem található"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_list_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let paginator_meta = PaginatorMeta {
+            page: 1,
+            limit: 25,
+            total: 100,
+        };
+        let product_resolved = ProductResolved {
+            id: product_id,
+            name: "Test product".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id,
+            unit_of_measure: "cm".to_string(),
+            status: "active".to_string(),
+            created_by_id,
+            created_by: "Test User".to_string(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+            attachments: vec![],
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<ProductOrderBy, ProductFilterBy>>()
+                    .unwrap()),
+                eq(None),
+            )
             .returning({
-                let product = product.clone();
-                move |_| Ok(product.clone())
+                let product_resolved = product_resolved.clone();
+                move |_, _| Ok((paginator_meta, vec![product_resolved.clone()]))
             });
+        repo.expect_get_attachments()
+            .times(1)
+            .returning(|_| Ok(vec![]));
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
@@ -303,7 +1644,7 @@ mod tests {
             )
             .header("Content-Type", "application/json")
             .method("GET")
-            .uri(format!("/api/products/get?uuid={product_id}"))
+            .uri("/api/products/list")
             .body("".to_string())
             .unwrap();
 
@@ -318,17 +1659,53 @@ mod tests {
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
-            "meta": null,
-            "data": product
+            "meta": paginator_meta,
+            "data": [product_resolved]
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_get_unauthorized_expired() {
-        let product_id = Uuid::new_v4();
+    async fn test_list_invalid_category_filter() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_paged().never();
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_products_repo()
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri("/api/products/list?q=filtering%3Acategory_id-%7Cnot-a-uuid%7C")
+            .body("".to_string())
+            .unwrap();
 
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_unauthorized_expired() {
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -342,7 +1719,46 @@ mod tests {
             )
             .header("Content-Type", "application/json")
             .method("GET")
-            .uri(format!("/api/products/get?uuid={product_id}"))
+            .uri("/api/products/list")
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNAUTHORIZED",
+                "message": "Hozzáférés megtagadva!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_list_unauthorized_invalid_signature() {
+        let mut app_state = MockProductsModule::new();
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_jwt_with_invalid_signature()),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri("/api/products/list")
             .body("".to_string())
             .unwrap();
 
@@ -358,6 +1774,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -365,25 +1782,187 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
 
-    #[tokio::test]
-    async fn test_get_unauthorized_invalid_signature() {
-        let product_id = Uuid::new_v4();
+    #[tokio::test]
+    async fn test_list_unauthorized_missing() {
+        let app_state = MockProductsModule::new();
+        let request = Request::builder()
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri("/api/products/list")
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({});
+
+        assert_eq!(response_body, expected_body);
+    }
+    #[tokio::test]
+    async fn test_list_not_found() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<ProductOrderBy, ProductFilterBy>>()
+                    .unwrap()),
+                eq(None),
+            )
+            .returning(|_, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri("/api/products/list")
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "NOT_FOUND",
+                "message": "Nem található"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_create_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let product = Product {
+            id: product_id,
+            name: "Test product".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id,
+            status: "active".to_string(),
+            created_by_id,
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+        let user_input = ProductUserInput::try_from(user_input_helper.clone()).unwrap();
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf({
+                let user_input_expected = user_input.clone();
+                move |user_input, user_id_inner, limits| {
+                    user_input.name == user_input_expected.name
+                        && user_input.description == user_input_expected.description
+                        && user_input.unit_of_measure_id == user_input_expected.unit_of_measure_id
+                        && user_input.new_unit_of_measure == user_input_expected.new_unit_of_measure
+                        && user_input.status == user_input_expected.status
+                        && user_id == *user_id_inner
+                        && limits.is_none()
+                }
+            })
+            .returning({
+                let product = product.clone();
+                move |_, _, _| Ok(Some(product.clone()))
+            });
+
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(|_| Ok(None));
 
         let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
+        app_state
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(move || tenant_limits_repo.clone());
         let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
-                format!("Bearer {}", generate_jwt_with_invalid_signature()),
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -393,27 +1972,81 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.status(), StatusCode::CREATED);
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
-            "error": {
-                "message": "Hozzáférés megtagadva!"
-            }
+            "meta": null,
+            "data": product
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_get_unauthorized_missing() {
-        let product_id = Uuid::new_v4();
-        let app_state = MockProductsModule::new();
+    async fn test_create_quota_exceeded() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: Uuid::new_v4().to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(|_, _, limits| {
+                limits.as_ref().and_then(|limits| limits.max_products) == Some(10)
+            })
+            .returning(|_, _, _| Ok(None));
+
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_products: Some(10),
+                    ..Default::default()
+                }))
+            });
+
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(move || tenant_limits_repo.clone());
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state.expect_config().return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -423,36 +2056,48 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({});
-
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
     }
+
     #[tokio::test]
-    async fn test_get_not_found() {
+    async fn test_duplicate_quota_exceeded() {
         let active_tenant_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
 
         let mut repo = MockProductsRepository::new();
-        repo.expect_get_by_id()
+        repo.expect_duplicate()
             .times(1)
-            .with(eq(product_id))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .withf(|_, _, limits| {
+                limits.as_ref().and_then(|limits| limits.max_products) == Some(10)
+            })
+            .returning(|_, _, _| Ok(None));
+
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_products: Some(10),
+                    ..Default::default()
+                }))
+            });
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_tenant_limits_repo()
+            .times(1)
+            .returning(move || tenant_limits_repo.clone());
         app_state
             .expect_products_repo()
             .with(eq(active_tenant_id))
             .times(1)
             .returning(move |_| Ok(repo.clone()));
-        app_state
-            .expect_config()
-            .times(1)
-            .return_const(test_config.clone());
+        app_state.expect_config().return_const(test_config.clone());
         let request = Request::builder()
             .header(
                 "Authorization",
@@ -462,9 +2107,9 @@ mod tests {
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/duplicate")
+            .body(Body::from(json!({"id": Uuid::new_v4()}).to_string()))
             .unwrap();
 
         let app = Router::new().nest(
@@ -474,72 +2119,45 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Nem található"
-            }
-        });
-
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
     }
 
     #[tokio::test]
-    async fn test_get_resolved_success() {
+    async fn test_create_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
         let unit_of_measure_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-        let product_resolved = ProductResolved {
-            id: product_id,
+        let user_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
             name: "Test product".to_string(),
-            description: None,
-            unit_of_measure_id,
-            unit_of_measure: "cm".to_string(),
-            status: "active".to_string(),
-            created_by_id,
-            created_by: "Test User".to_string(),
-            created_at: utc_now,
-            updated_at: utc_now,
-            deleted_at: None,
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "activee".to_string(),
         };
 
-        let mut repo = MockProductsRepository::new();
-        repo.expect_get_resolved_by_id()
-            .times(1)
-            .with(eq(product_id))
-            .returning({
-                let product_resolved = product_resolved.clone();
-                move |_| Ok(product_resolved.clone())
-            });
-
         let mut app_state = MockProductsModule::new();
-        let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_products_repo()
-            .with(eq(active_tenant_id))
-            .times(1)
-            .returning(move |_| Ok(repo.clone()));
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!(
                     "Bearer {}",
-                    generate_valid_jwt(None, Some(active_tenant_id))
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -549,20 +2167,36 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let user_input_error = ProductUserInput::try_from(user_input_helper).unwrap_err();
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
-            "meta": null,
-            "data": product_resolved
+            "error": {
+                "code": "VALIDATION_FAILED",
+                "message": "Kérjük ellenőrizze a hibás mezőket!",
+                "fields": user_input_error
+            }
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_get_resolved_unauthorized_expired() {
-        let product_id = Uuid::new_v4();
+    async fn test_create_unauthorized_expired() {
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -570,15 +2204,16 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_expired_jwt()),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -593,6 +2228,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -601,8 +2237,19 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_resolved_unauthorized_invalid_signature() {
-        let product_id = Uuid::new_v4();
+    async fn test_create_unauthorized_invalid_signature() {
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -610,15 +2257,16 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_jwt_with_invalid_signature()),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -633,6 +2281,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -641,14 +2290,27 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_resolved_unauthorized_missing() {
-        let product_id = Uuid::new_v4();
+    async fn test_create_unauthorized_missing() {
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+
         let app_state = MockProductsModule::new();
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
-            .body("".to_string())
+            .method("POST")
+            .uri("/api/products/create")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -666,15 +2328,59 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
     #[tokio::test]
-    async fn test_get_resolved_not_found() {
+    async fn test_update_success() {
         let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
         let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+        let created_by_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let product = Product {
+            id: product_id,
+            name: "Test product".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id,
+            status: "active".to_string(),
+            created_by_id,
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        };
+
+        let user_input_helper = ProductUserInputHelper {
+            id: Some(product_id.to_string()),
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+        let user_input = ProductUserInput::try_from(user_input_helper.clone()).unwrap();
 
         let mut repo = MockProductsRepository::new();
-        repo.expect_get_resolved_by_id()
+        repo.expect_get_by_id()
             .times(1)
             .with(eq(product_id))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .returning({
+                let product = product.clone();
+                move |_| Ok(product.clone())
+            });
+        repo.expect_update()
+            .times(1)
+            .with(eq(user_input))
+            .returning({
+                let product = product.clone();
+                move |_| Ok(product.clone())
+            });
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
@@ -688,18 +2394,19 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!(
                     "Bearer {}",
-                    generate_valid_jwt(None, Some(active_tenant_id))
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/get_resolved?uuid={product_id}"))
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/update")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -709,79 +2416,53 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::OK);
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
-            "error": {
-                "message": "Nem található"
-            }
+            "meta": null,
+            "data": product,
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_list_success() {
+    async fn test_update_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
         let unit_of_measure_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-        let paginator_meta = PaginatorMeta {
-            page: 1,
-            limit: 25,
-            total: 100,
-        };
-        let product_resolved = ProductResolved {
-            id: product_id,
+        let user_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: None,
             name: "Test product".to_string(),
-            description: None,
-            unit_of_measure_id,
-            unit_of_measure: "cm".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
             status: "active".to_string(),
-            created_by_id,
-            created_by: "Test User".to_string(),
-            created_at: utc_now,
-            updated_at: utc_now,
-            deleted_at: None,
         };
 
-        let mut repo = MockProductsRepository::new();
-        repo.expect_get_paged()
-            .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<ProductOrderBy, ProductFilterBy>>()
-                .unwrap()))
-            .returning({
-                let product_resolved = product_resolved.clone();
-                move |_| Ok((paginator_meta, vec![product_resolved.clone()]))
-            });
-
         let mut app_state = MockProductsModule::new();
-        let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_products_repo()
-            .with(eq(active_tenant_id))
-            .times(1)
-            .returning(move |_| Ok(repo.clone()));
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!(
                     "Bearer {}",
-                    generate_valid_jwt(None, Some(active_tenant_id))
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri("/api/products/list")
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/update")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -791,34 +2472,51 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
-            "meta": paginator_meta,
-            "data": [product_resolved]
+            "error": {
+                "code": "UNPROCESSABLE_ENTRY",
+                "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
+            }
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_list_unauthorized_expired() {
+    async fn test_update_unauthorized_expired() {
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: Some(product_id.to_string()),
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_expired_jwt()),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri("/api/products/list")
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/update")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -833,6 +2531,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -841,22 +2540,37 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_list_unauthorized_invalid_signature() {
+    async fn test_update_unauthorized_invalid_signature() {
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: Some(product_id.to_string()),
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_jwt_with_invalid_signature()),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri("/api/products/list")
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/update")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -871,6 +2585,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -879,13 +2594,28 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_list_unauthorized_missing() {
+    async fn test_update_unauthorized_missing() {
+        let product_id = Uuid::new_v4();
+        let unit_of_measure_id = Uuid::new_v4();
+
+        let user_input_helper = ProductUserInputHelper {
+            id: Some(product_id.to_string()),
+            name: "Test product".to_string(),
+            description: "".to_string(),
+            sku: "".to_string(),
+            barcode: "".to_string(),
+            unit_of_measure_id: unit_of_measure_id.to_string(),
+            new_unit_of_measure: "".to_string(),
+            status: "active".to_string(),
+        };
+
         let app_state = MockProductsModule::new();
+        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri("/api/products/list")
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/update")
+            .body(Body::from(payload))
             .unwrap();
 
         let app = Router::new().nest(
@@ -903,29 +2633,21 @@ mod tests {
         assert_eq!(response_body, expected_body);
     }
     #[tokio::test]
-    async fn test_list_not_found() {
+    async fn test_set_dimensions_rejects_non_positive_size() {
         let active_tenant_id = Uuid::new_v4();
-
+        let product_id = Uuid::new_v4();
         let mut repo = MockProductsRepository::new();
-        repo.expect_get_paged()
-            .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<ProductOrderBy, ProductFilterBy>>()
-                .unwrap()))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+        repo.expect_set_dimensions().never();
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
-        let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_products_repo()
-            .with(eq(active_tenant_id))
-            .times(1)
             .returning(move |_| Ok(repo.clone()));
         app_state
             .expect_config()
             .times(1)
-            .return_const(test_config.clone());
+            .return_const(AppConfigBuilder::default().build().unwrap());
         let request = Request::builder()
             .header(
                 "Authorization",
@@ -935,9 +2657,18 @@ mod tests {
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("GET")
-            .uri("/api/products/list")
-            .body("".to_string())
+            .method("PUT")
+            .uri("/api/products/set_dimensions")
+            .body(
+                json!({
+                    "product_id": product_id,
+                    "weight_g": 1200,
+                    "length_mm": 300,
+                    "width_mm": 0,
+                    "height_mm": 100
+                })
+                .to_string(),
+            )
             .unwrap();
 
         let app = Router::new().nest(
@@ -947,66 +2678,66 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Nem található"
-            }
-        });
+    #[tokio::test]
+    async fn test_set_stock_threshold_rejects_negative() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockProductsRepository::new();
+        repo.expect_set_stock_threshold().never();
 
-        assert_eq!(response_body, expected_body);
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_products_repo()
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/products/set_stock_threshold")
+            .body(
+                json!({
+                    "product_id": product_id,
+                    "minimum_stock": "-5"
+                })
+                .to_string(),
+            )
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     #[tokio::test]
-    async fn test_create_success() {
+    async fn test_delete_success() {
         let active_tenant_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
         let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-        let product = Product {
-            id: product_id,
-            name: "Test product".to_string(),
-            description: None,
-            unit_of_measure_id,
-            status: "active".to_string(),
-            created_by_id,
-            created_at: utc_now,
-            updated_at: utc_now,
-            deleted_at: None,
-        };
-
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
-        let user_input = ProductUserInput::try_from(user_input_helper.clone()).unwrap();
-
         let mut repo = MockProductsRepository::new();
-        repo.expect_insert()
-            .times(1)
-            .withf({
-                let user_input_expected = user_input.clone();
-                move |user_input, user_id_inner| {
-                    user_input.name == user_input_expected.name
-                        && user_input.description == user_input_expected.description
-                        && user_input.unit_of_measure_id == user_input_expected.unit_of_measure_id
-                        && user_input.new_unit_of_measure == user_input_expected.new_unit_of_measure
-                        && user_input.status == user_input_expected.status
-                        && user_id == *user_id_inner
-                }
-            })
-            .returning({
-                let product = product.clone();
-                move |_, _| Ok(product.clone())
-            });
+
+        repo.expect_delete_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(move |_| Ok(()));
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
@@ -1020,7 +2751,6 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
@@ -1030,9 +2760,9 @@ mod tests {
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("POST")
-            .uri("/api/products/create")
-            .body(Body::from(payload))
+            .method("DELETE")
+            .uri(format!("/api/products/delete?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1042,39 +2772,30 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.status(), StatusCode::OK);
 
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "meta": null,
-            "data": product
+            "data": {
+                "message": "A termék törlése sikeresen megtörtént"
+            },
         });
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_create_invalid_user_input() {
+    async fn test_delete_invalid_user_input() {
         let active_tenant_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
 
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "activee".to_string(),
-        };
-
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
@@ -1084,9 +2805,9 @@ mod tests {
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("POST")
-            .uri("/api/products/create")
-            .body(Body::from(payload))
+            .method("DELETE")
+            .uri("/api/products/delete?uuid=invalid_user_input")
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1096,33 +2817,17 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
-
-        let user_input_error = ProductUserInput::try_from(user_input_helper).unwrap_err();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
         let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Kérjük ellenőrizze a hibás mezőket!",
-                "fields": user_input_error
-            }
-        });
+        let expected_body = json!({});
 
         assert_eq!(response_body, expected_body);
     }
 
     #[tokio::test]
-    async fn test_create_unauthorized_expired() {
-        let unit_of_measure_id = Uuid::new_v4();
-
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
+    async fn test_delete_unauthorized_expired() {
+        let product_id = Uuid::new_v4();
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -1130,16 +2835,15 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_expired_jwt()),
             )
             .header("Content-Type", "application/json")
-            .method("POST")
-            .uri("/api/products/create")
-            .body(Body::from(payload))
+            .method("DELETE")
+            .uri(format!("/api/products/delete?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1154,6 +2858,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1162,17 +2867,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_create_unauthorized_invalid_signature() {
-        let unit_of_measure_id = Uuid::new_v4();
-
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
+    async fn test_delete_unauthorized_invalid_signature() {
+        let product_id = Uuid::new_v4();
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -1180,16 +2876,15 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_jwt_with_invalid_signature()),
             )
             .header("Content-Type", "application/json")
-            .method("POST")
-            .uri("/api/products/create")
-            .body(Body::from(payload))
+            .method("DELETE")
+            .uri(format!("/api/products/delete?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1204,6 +2899,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1212,25 +2908,15 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_create_unauthorized_missing() {
-        let unit_of_measure_id = Uuid::new_v4();
-
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
+    async fn test_delete_unauthorized_missing() {
+        let product_id = Uuid::new_v4();
 
         let app_state = MockProductsModule::new();
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header("Content-Type", "application/json")
-            .method("POST")
-            .uri("/api/products/create")
-            .body(Body::from(payload))
+            .method("DELETE")
+            .uri(format!("/api/products/delete?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1247,44 +2933,48 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
     #[tokio::test]
-    async fn test_update_success() {
+    async fn test_print_success() {
         let active_tenant_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-        let created_by_id = Uuid::new_v4();
-        let utc_now = Utc::now();
-        let product = Product {
+        let product_id = "4f321721-37c6-4e91-8e42-6281c36937bc".parse().unwrap();
+        let created_by_id = "97054cdb-781c-4f40-a489-b43373d75bf0".parse().unwrap();
+        let unit_of_measure_id = "0237354a-21ab-46f4-a4ca-b21cb08561d7".parse().unwrap();
+        let test_time: DateTime<Utc> = "2026-01-02T11:11:11Z".parse().unwrap();
+
+        let product_resolved = ProductResolved {
             id: product_id,
             name: "Test product".to_string(),
             description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
             unit_of_measure_id,
+            unit_of_measure: "cm".to_string(),
             status: "active".to_string(),
             created_by_id,
-            created_at: utc_now,
-            updated_at: utc_now,
+            created_by: "Test User".to_string(),
+            created_at: test_time,
+            updated_at: test_time,
             deleted_at: None,
+            attachments: vec![],
         };
 
-        let user_input_helper = ProductUserInputHelper {
-            id: Some(product_id.to_string()),
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
-        let user_input = ProductUserInput::try_from(user_input_helper.clone()).unwrap();
-
         let mut repo = MockProductsRepository::new();
-        repo.expect_update()
+        repo.expect_get_resolved_by_id()
             .times(1)
-            .with(eq(user_input))
+            .with(eq(product_id))
             .returning({
-                let product = product.clone();
-                move |_| Ok(product.clone())
+                let product_resolved = product_resolved.clone();
+                move |_| Ok(product_resolved.clone())
             });
+        repo.expect_get_attachments()
+            .times(1)
+            .returning(|_| Ok(vec![]));
 
         let mut app_state = MockProductsModule::new();
         let repo = Arc::new(repo);
@@ -1298,73 +2988,34 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
-                ),
-            )
-            .header("Content-Type", "application/json")
-            .method("PUT")
-            .uri("/api/products/update")
-            .body(Body::from(payload))
-            .unwrap();
-
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::OK);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "meta": null,
-            "data": product,
-        });
-
-        assert_eq!(response_body, expected_body);
-    }
-
-    #[tokio::test]
-    async fn test_update_invalid_user_input() {
-        let active_tenant_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
 
-        let user_input_helper = ProductUserInputHelper {
-            id: None,
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
+        let pdf_gen_payload_expected = vec![ProductsResolvedPrint::from_product_resolved(
+            product_resolved,
+            "Europe/Budapest".parse().unwrap(),
+        )];
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_temporary_context();
+        pdf_gen
+            .expect::<Vec<ProductsResolvedPrint>>()
             .times(1)
-            .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
+            .with(eq(PdfTemplates::ProductView), eq(pdf_gen_payload_expected))
+            .returning(|template, payload| {
+                Ok(PdfGenerator::gen_pdf_temporary(template, payload).unwrap())
+            });
+
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!(
                     "Bearer {}",
-                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                    generate_valid_jwt(None, Some(active_tenant_id))
                 ),
             )
             .header("Content-Type", "application/json")
-            .method("PUT")
-            .uri("/api/products/update")
-            .body(Body::from(payload))
+            .method("GET")
+            .uri(format!("/api/products/print?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1374,31 +3025,30 @@ mod tests {
 
         let response = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
-            }
-        });
+        assert_eq!(response.status(), StatusCode::OK);
 
-        assert_eq!(response_body, expected_body);
-    }
+        let snapshot_path = Path::new("testing/pdf/snapshots/products_test.pdf");
+        let mut file = File::open(snapshot_path).unwrap();
+        let mut snapshot = vec![];
+        file.read_to_end(&mut snapshot).unwrap();
 
-    #[tokio::test]
-    async fn test_update_unauthorized_expired() {
-        let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec();
+        let test_result_path = Path::new("testing/pdf/test_results/products_test.pdf");
+        let mut file = File::create(test_result_path).unwrap();
+        file.write_all(&body_bytes).unwrap();
 
-        let user_input_helper = ProductUserInputHelper {
-            id: Some(product_id.to_string()),
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
+        assert_eq!(
+            extract_pdf_text(&body_bytes).unwrap(),
+            extract_pdf_text(&snapshot).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_print_unauthorized_expired() {
+        let product_id = Uuid::new_v4();
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -1406,16 +3056,16 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
+
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_expired_jwt()),
             )
             .header("Content-Type", "application/json")
-            .method("PUT")
-            .uri("/api/products/update")
-            .body(Body::from(payload))
+            .method("GET")
+            .uri(format!("/api/products/print?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1430,6 +3080,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1438,18 +3089,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_update_unauthorized_invalid_signature() {
+    async fn test_print_unauthorized_invalid_signature() {
         let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-
-        let user_input_helper = ProductUserInputHelper {
-            id: Some(product_id.to_string()),
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
 
         let mut app_state = MockProductsModule::new();
         let test_config = AppConfigBuilder::default().build().unwrap();
@@ -1457,16 +3098,16 @@ mod tests {
             .expect_config()
             .times(1)
             .return_const(test_config.clone());
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
+
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!("Bearer {}", generate_jwt_with_invalid_signature()),
             )
             .header("Content-Type", "application/json")
-            .method("PUT")
-            .uri("/api/products/update")
-            .body(Body::from(payload))
+            .method("GET")
+            .uri(format!("/api/products/print?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
@@ -1481,6 +3122,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1489,455 +3131,1303 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_update_unauthorized_missing() {
+    async fn test_print_unauthorized_missing() {
         let product_id = Uuid::new_v4();
-        let unit_of_measure_id = Uuid::new_v4();
-
-        let user_input_helper = ProductUserInputHelper {
-            id: Some(product_id.to_string()),
-            name: "Test product".to_string(),
-            description: "".to_string(),
-            unit_of_measure_id: unit_of_measure_id.to_string(),
-            new_unit_of_measure: "".to_string(),
-            status: "active".to_string(),
-        };
 
-        let app_state = MockProductsModule::new();
-        let payload = serde_json::to_string(&user_input_helper).unwrap();
         let request = Request::builder()
             .header("Content-Type", "application/json")
-            .method("PUT")
-            .uri("/api/products/update")
-            .body(Body::from(payload))
+            .method("GET")
+            .uri(format!("/api/products/print?uuid={product_id}"))
+            .body("".to_string())
             .unwrap();
 
         let app = Router::new().nest(
             "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+            Router::new().merge(products::routes::routes(
+                Arc::new(MockProductsModule::new()),
+            )),
         );
 
         let response = app.oneshot(request).await.unwrap();
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({});
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({});
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    fn variant_app(
+        repo: MockProductsRepository,
+        tenant_limits_repo: Option<MockTenantLimitsRepository>,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        if let Some(tenant_limits_repo) = tenant_limits_repo {
+            let tenant_limits_repo = Arc::new(tenant_limits_repo);
+            app_state
+                .expect_tenant_limits_repo()
+                .returning(move || tenant_limits_repo.clone());
+        }
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn variant_product(
+        name: &str,
+        parent_id: Option<Uuid>,
+        variant_attributes: Option<serde_json::Value>,
+    ) -> Product {
+        Product {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id,
+            variant_attributes,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id: Uuid::new_v4(),
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn variant_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        body: String,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_variant_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let template = variant_product("Póló", None, None);
+        let variant = variant_product(
+            "Póló (piros / M)",
+            Some(template.id),
+            Some(json!({"colour": "piros", "size": "M"})),
+        );
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(template.id))
+            .returning({
+                let template = template.clone();
+                move |_| Ok(template.clone())
+            });
+        repo.expect_insert_variant()
+            .times(1)
+            .withf(|template, input, _, _| {
+                input.variant_name(&template.name) == "Póló (piros / M)"
+                    && input.sku.as_deref() == Some("POLO-PIROS-M")
+                    && input.currency_code.as_deref() == Some("HUF")
+            })
+            .returning({
+                let variant = variant.clone();
+                move |_, _, _, _| Ok(Some(variant.clone()))
+            });
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(|tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_products: Some(10),
+                    ..Default::default()
+                }))
+            });
+
+        let response = variant_app(repo, Some(tenant_limits_repo), active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/create_variant",
+                active_tenant_id,
+                json!({
+                    "parent_id": template.id,
+                    "attributes": {"size": " M ", "colour": "piros"},
+                    "sku": "POLO-PIROS-M",
+                    "barcode": null,
+                    "price": "4990",
+                    "currency_code": "huf",
+                    "status": "active"
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": variant})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_variant_of_variant() {
+        let active_tenant_id = Uuid::new_v4();
+        let variant = variant_product("Póló (M)", Some(Uuid::new_v4()), Some(json!({"size": "M"})));
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let variant = variant.clone();
+            move |_| Ok(variant.clone())
+        });
+        repo.expect_insert_variant().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/create_variant",
+                active_tenant_id,
+                json!({
+                    "parent_id": variant.id,
+                    "attributes": {"colour": "kék"},
+                    "sku": null,
+                    "barcode": null,
+                    "price": null,
+                    "currency_code": null,
+                    "status": "active"
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_grouped_variants() {
+        let active_tenant_id = Uuid::new_v4();
+        let template_id = Uuid::new_v4();
+        let template = ProductResolved {
+            id: template_id,
+            name: "Póló".to_string(),
+            description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
+            unit_of_measure_id: Uuid::new_v4(),
+            unit_of_measure: "db".to_string(),
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_by: "Test User".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            attachments: vec![],
+        };
+        let variant = ProductVariant {
+            id: Uuid::new_v4(),
+            parent_id: template_id,
+            name: "Póló (M)".to_string(),
+            sku: Some("POLO-M".to_string()),
+            barcode: None,
+            variant_attributes: json!({"size": "M"}),
+            price: Some(BigDecimal::from(4990)),
+            currency_code: Some("HUF".to_string()),
+            status: "active".to_string(),
+            quantity_on_hand: BigDecimal::from(12),
+            quantity_available: BigDecimal::from(10),
+        };
+        let paginator_meta = PaginatorMeta {
+            page: 1,
+            limit: 25,
+            total: 1,
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_paged().never();
+        repo.expect_get_paged_templates().times(1).returning({
+            let template = template.clone();
+            move |_, _| Ok((paginator_meta, vec![template.clone()]))
+        });
+        repo.expect_get_variants()
+            .times(1)
+            .withf(move |parent_ids| parent_ids == [template_id])
+            .returning({
+                let variant = variant.clone();
+                move |_| Ok(vec![variant.clone()])
+            });
+        repo.expect_get_attachments()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "GET",
+                "/api/products/list?variants=group",
+                active_tenant_id,
+                "".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["meta"], json!(paginator_meta));
+        assert_eq!(body["data"][0]["id"], json!(template_id));
+        assert_eq!(body["data"][0]["variants"], json!([variant]));
+    }
+
+    fn attachment_app(
+        repo: MockProductsRepository,
+        tenant_limits_repo: MockTenantLimitsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let mut app_state = MockProductsModule::new();
+        let repo = Arc::new(repo);
+        let tenant_limits_repo = Arc::new(tenant_limits_repo);
+        let storage = Arc::new(storage);
+        app_state
+            .expect_products_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_tenant_limits_repo()
+            .returning(move || tenant_limits_repo.clone());
+        app_state
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn attachment_request(
+        active_tenant_id: Uuid,
+        product_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Request<Body> {
+        let boundary = "obvia-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"product_id\"\r\n\r\n{product_id}\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+             Content-Type: {content_type}\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .method("POST")
+            .uri("/api/products/upload_attachment")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn storage_limits(max_storage_mb: i64) -> MockTenantLimitsRepository {
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .times(1)
+            .returning(move |tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_storage_mb: Some(max_storage_mb),
+                    ..Default::default()
+                }))
+            });
+        tenant_limits_repo
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_image_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Póló", None, None);
+        let product_id = product.id;
+        let image = png(640, 480);
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_insert_attachment()
+            .times(1)
+            .withf(move |attachment, limits| {
+                attachment.product_id == product_id
+                    && attachment.kind == "image"
+                    && attachment.content_type == "image/png"
+                    && attachment.file_name == "polo.png"
+                    && attachment.thumbnail_key.as_deref()
+                        == Some(format!("{}_thumb.jpg", attachment.storage_key).as_str())
+                    && limits.as_ref().and_then(|limits| limits.max_storage_mb) == Some(10)
+            })
+            .returning(|attachment, _| Ok(Some(attachment.clone())));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_put()
+            .times(1)
+            .withf(move |key, content_type, _| {
+                key.starts_with(&format!("{active_tenant_id}/products/{product_id}/"))
+                    && !key.ends_with("_thumb.jpg")
+                    && content_type == "image/png"
+            })
+            .returning(|_, _, _| Ok(()));
+        storage
+            .expect_put()
+            .times(1)
+            .withf(|key, content_type, data| {
+                key.ends_with("_thumb.jpg")
+                    && content_type == "image/jpeg"
+                    && image::load_from_memory(data).is_ok_and(|thumbnail| thumbnail.width() == 320)
+            })
+            .returning(|_, _, _| Ok(()));
+
+        let response = attachment_app(repo, storage_limits(10), storage, active_tenant_id)
+            .oneshot(attachment_request(
+                active_tenant_id,
+                product_id,
+                "polo.png",
+                "image/png",
+                &image,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        let attachment_id = body["data"]["id"].as_str().unwrap();
+        assert_eq!(
+            body["data"]["url"],
+            json!(format!("/api/products/attachment?uuid={attachment_id}"))
+        );
+        assert_eq!(
+            body["data"]["thumbnail_url"],
+            json!(format!(
+                "/api/products/attachment_thumbnail?uuid={attachment_id}"
+            ))
+        );
+        assert!(body["data"].get("storage_key").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_unsupported_type() {
+        let active_tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id().never();
+        repo.expect_insert_attachment().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().never();
+
+        let response = attachment_app(
+            repo,
+            MockTenantLimitsRepository::new(),
+            storage,
+            active_tenant_id,
+        )
+        .oneshot(attachment_request(
+            active_tenant_id,
+            Uuid::new_v4(),
+            "setup.exe",
+            "application/x-msdownload",
+            b"MZ\x90\x00",
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_storage_quota_exceeded() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Póló", None, None);
+        let product_id = product.id;
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_insert_attachment()
+            .times(1)
+            .withf(|_, limits| limits.as_ref().and_then(|limits| limits.max_storage_mb) == Some(1))
+            .returning(|_, _| Ok(None));
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().times(1).returning(|_, _, _| Ok(()));
+        storage
+            .expect_delete()
+            .times(1)
+            .withf(move |key| {
+                key.starts_with(&format!("{active_tenant_id}/products/{product_id}/"))
+            })
+            .returning(|_| Ok(()));
+
+        let response = attachment_app(repo, storage_limits(1), storage, active_tenant_id)
+            .oneshot(attachment_request(
+                active_tenant_id,
+                product_id,
+                "adatlap.pdf",
+                "application/pdf",
+                b"%PDF-1.7",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    }
+
+    #[tokio::test]
+    async fn test_set_bundle_components_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let bundle = variant_product("Szerelőkészlet", None, None);
+        let bundle_id = bundle.id;
+        let screw_id = Uuid::new_v4();
+        let components = vec![ProductBundleComponent {
+            component_id: screw_id,
+            name: "Csavar".to_string(),
+            sku: None,
+            unit_of_measure: "db".to_string(),
+            quantity: "4".parse().unwrap(),
+        }];
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(bundle_id))
+            .returning(move |_| Ok(bundle.clone()));
+        repo.expect_is_bundle_component()
+            .times(1)
+            .with(eq(bundle_id))
+            .returning(|_| Ok(false));
+        repo.expect_get_bundle_ids()
+            .times(1)
+            .withf(move |ids| ids == [screw_id])
+            .returning(|_| Ok(vec![]));
+        repo.expect_set_bundle_components()
+            .times(1)
+            .withf(move |input, _| input.bundle_id == bundle_id && input.components.len() == 1)
+            .returning(|_, _| Ok(()));
+        repo.expect_get_bundle_components()
+            .times(1)
+            .with(eq(bundle_id))
+            .returning({
+                let components = components.clone();
+                move |_| Ok(components.clone())
+            });
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_bundle_components",
+                active_tenant_id,
+                json!({
+                    "bundle_id": bundle_id,
+                    "components": [{"component_id": screw_id, "quantity": "4"}]
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": components})
+        );
     }
+
     #[tokio::test]
-    async fn test_delete_success() {
+    async fn test_set_bundle_components_rejects_nested_bundle() {
         let active_tenant_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let product_id = Uuid::new_v4();
-        let mut repo = MockProductsRepository::new();
+        let bundle = variant_product("Szerelőkészlet", None, None);
+        let bundle_id = bundle.id;
+        let nested_bundle_id = Uuid::new_v4();
 
-        repo.expect_delete_by_id()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
             .times(1)
-            .with(eq(product_id))
-            .returning(move |_| Ok(()));
-
-        let mut app_state = MockProductsModule::new();
-        let repo = Arc::new(repo);
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_products_repo()
-            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(bundle.clone()));
+        repo.expect_is_bundle_component()
             .times(1)
-            .returning(move |_| Ok(repo.clone()));
-        app_state
-            .expect_config()
+            .returning(|_| Ok(false));
+        repo.expect_get_bundle_ids()
             .times(1)
-            .return_const(test_config.clone());
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
-                ),
-            )
-            .header("Content-Type", "application/json")
-            .method("DELETE")
-            .uri(format!("/api/products/delete?uuid={product_id}"))
-            .body("".to_string())
+            .returning(move |_| Ok(vec![nested_bundle_id]));
+        repo.expect_set_bundle_components().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_bundle_components",
+                active_tenant_id,
+                json!({
+                    "bundle_id": bundle_id,
+                    "components": [{"component_id": nested_bundle_id, "quantity": "1"}]
+                })
+                .to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 
-        assert_eq!(response.status(), StatusCode::OK);
+    #[tokio::test]
+    async fn test_export_csv() {
+        let active_tenant_id = Uuid::new_v4();
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "meta": null,
-            "data": {
-                "message": "A termék törlése sikeresen megtörtént"
-            },
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_export_rows().times(1).returning(|| {
+            Ok(vec![ProductExportRow {
+                sku: Some("CSV-10".to_string()),
+                name: "Kábel, réz".to_string(),
+                description: None,
+                barcode: None,
+                unit_of_measure: "m".to_string(),
+                status: "active".to_string(),
+                price: Some("120.50".parse().unwrap()),
+                currency_code: Some("HUF".to_string()),
+            }])
         });
 
-        assert_eq!(response_body, expected_body);
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "GET",
+                "/api/products/export",
+                active_tenant_id,
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            "sku,name,description,barcode,unit_of_measure,status,price,currency_code\n\
+             CSV-10,\"Kábel, réz\",,,m,active,120.50,HUF\n"
+        );
     }
 
     #[tokio::test]
-    async fn test_delete_invalid_user_input() {
+    async fn test_import_dry_run() {
         let active_tenant_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
+        let report = ProductImportReport {
+            import_id: None,
+            applied: false,
+            row_count: 2,
+            error_count: 1,
+            products_created: 1,
+            products_updated: 0,
+            lines: vec![
+                ProductImportLine {
+                    line: 2,
+                    sku: "CSV-10".to_string(),
+                    name: "Kábel".to_string(),
+                    product_id: Some(Uuid::new_v4()),
+                    product_created: true,
+                    product_updated: false,
+                    errors: vec![],
+                },
+                ProductImportLine {
+                    line: 3,
+                    sku: "CSV-11".to_string(),
+                    name: "Csavar".to_string(),
+                    product_id: None,
+                    product_created: false,
+                    product_updated: false,
+                    errors: vec!["Az ár nem lehet negatív!".to_string()],
+                },
+            ],
+        };
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_import()
             .times(1)
-            .return_const(test_config.clone());
+            .withf(|rows: &[ProductImportRow], _, limits, apply| {
+                rows.len() == 2
+                    && rows[0].errors.is_empty()
+                    && rows[1].errors == vec!["Az ár nem lehet negatív!".to_string()]
+                    && limits.as_ref().and_then(|limits| limits.max_products) == Some(10)
+                    && !apply
+            })
+            .returning({
+                let report = report.clone();
+                move |_, _, _, _| Ok(report.clone())
+            });
+        let mut tenant_limits_repo = MockTenantLimitsRepository::new();
+        tenant_limits_repo
+            .expect_get_by_tenant_id()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |tenant_id| {
+                Ok(Some(TenantLimits {
+                    tenant_id,
+                    max_products: Some(10),
+                    ..Default::default()
+                }))
+            });
+
         let request = Request::builder()
             .header(
                 "Authorization",
                 format!(
                     "Bearer {}",
-                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                    generate_valid_jwt(None, Some(active_tenant_id))
                 ),
             )
-            .header("Content-Type", "application/json")
-            .method("DELETE")
-            .uri("/api/products/delete?uuid=invalid_user_input")
-            .body("".to_string())
+            .header("Content-Type", "text/csv")
+            .method("POST")
+            .uri("/api/products/import")
+            .body(Body::from(
+                "sku,name,unit_of_measure,price,currency_code\nCSV-10,Kábel,m,120,HUF\nCSV-11,Csavar,db,-5,HUF\n",
+            ))
+            .unwrap();
+        let response = variant_app(repo, Some(tenant_limits_repo), active_tenant_id)
+            .oneshot(request)
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({});
-
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: ProductImportReport =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, report);
     }
 
     #[tokio::test]
-    async fn test_delete_unauthorized_expired() {
-        let product_id = Uuid::new_v4();
+    async fn test_generate_barcodes_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut product = variant_product("Csavar", None, None);
+        product.barcode = Some("2000000000015".to_string());
+        let product_id = product.id;
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_assign_barcodes()
             .times(1)
-            .return_const(test_config.clone());
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!("Bearer {}", generate_expired_jwt()),
-            )
-            .header("Content-Type", "application/json")
-            .method("DELETE")
-            .uri(format!("/api/products/delete?uuid={product_id}"))
-            .body("".to_string())
+            .withf(move |ids, symbology| {
+                ids == [product_id] && *symbology == BarcodeSymbology::Ean13
+            })
+            .returning({
+                let product = product.clone();
+                move |_, _| Ok(vec![product.clone()])
+            });
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/generate_barcodes",
+                active_tenant_id,
+                json!({"product_ids": [product_id], "symbology": "ean13"}).to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await["data"][0]["barcode"],
+            "2000000000015"
         );
+    }
 
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    #[tokio::test]
+    async fn test_barcode_png_without_barcode() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Csavar", None, None);
+        let product_id = product.id;
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Hozzáférés megtagadva!"
-            }
-        });
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(move |_| Ok(product.clone()));
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "GET",
+                &format!("/api/products/barcode?uuid={product_id}"),
+                active_tenant_id,
+                String::new(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     #[tokio::test]
-    async fn test_delete_unauthorized_invalid_signature() {
-        let product_id = Uuid::new_v4();
+    async fn test_barcode_labels_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut product = variant_product("Csavar", None, None);
+        product.sku = Some("CSV-10".to_string());
+        product.barcode = Some("5901234123457".to_string());
+        let product_id = product.id;
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_ids()
             .times(1)
-            .return_const(test_config.clone());
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!("Bearer {}", generate_jwt_with_invalid_signature()),
+            .withf(move |ids| ids == [product_id])
+            .returning(move |_| Ok(vec![product.clone()]));
+
+        let label = BarcodeLabelPrint {
+            name: "Csavar".to_string(),
+            sku: Some("CSV-10".to_string()),
+            barcode: "5901234123457".to_string(),
+            modules: barcode_modules("5901234123457").unwrap(),
+        };
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_temporary_context();
+        pdf_gen
+            .expect::<Vec<BarcodeLabelPrint>>()
+            .times(1)
+            .with(
+                eq(PdfTemplates::ProductBarcodeLabels),
+                eq(vec![label.clone(), label]),
             )
-            .header("Content-Type", "application/json")
-            .method("DELETE")
-            .uri(format!("/api/products/delete?uuid={product_id}"))
-            .body("".to_string())
+            .returning(|_, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/barcode_labels",
+                active_tenant_id,
+                json!({"product_ids": [product_id], "copies": 2}).to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Hozzáférés megtagadva!"
-            }
-        });
-
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
     }
 
     #[tokio::test]
-    async fn test_delete_unauthorized_missing() {
-        let product_id = Uuid::new_v4();
+    async fn test_set_status_rejects_invalid_transition() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Csavar", None, None);
+        let product_id = product.id;
 
-        let app_state = MockProductsModule::new();
-        let request = Request::builder()
-            .header("Content-Type", "application/json")
-            .method("DELETE")
-            .uri(format!("/api/products/delete?uuid={product_id}"))
-            .body("".to_string())
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_set_status().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_status",
+                active_tenant_id,
+                json!({"product_id": product_id, "status": "draft"}).to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({});
-
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     #[tokio::test]
-    async fn test_print_success() {
-        let active_tenant_id = Uuid::new_v4();
-        let product_id = "4f321721-37c6-4e91-8e42-6281c36937bc".parse().unwrap();
-        let created_by_id = "97054cdb-781c-4f40-a489-b43373d75bf0".parse().unwrap();
-        let unit_of_measure_id = "0237354a-21ab-46f4-a4ca-b21cb08561d7".parse().unwrap();
-        let test_time: DateTime<Utc> = "2026-01-02T11:11:11Z".parse().unwrap();
-
-        let product_resolved = ProductResolved {
-            id: product_id,
-            name: "Test product".to_string(),
-            description: None,
-            unit_of_measure_id,
-            unit_of_measure: "cm".to_string(),
-            status: "active".to_string(),
-            created_by_id,
-            created_by: "Test User".to_string(),
-            created_at: test_time,
-            updated_at: test_time,
-            deleted_at: None,
+    async fn test_set_status_discontinue() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Csavar", None, None);
+        let product_id = product.id;
+        let discontinued = Product {
+            status: "discontinued".to_string(),
+            ..product.clone()
         };
 
         let mut repo = MockProductsRepository::new();
-        repo.expect_get_resolved_by_id()
+        repo.expect_get_by_id()
             .times(1)
-            .with(eq(product_id))
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_set_status()
+            .times(1)
+            .with(eq(product_id), eq("discontinued"))
             .returning({
-                let product_resolved = product_resolved.clone();
-                move |_| Ok(product_resolved.clone())
+                let discontinued = discontinued.clone();
+                move |_, _| Ok(discontinued.clone())
             });
 
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_status",
+                active_tenant_id,
+                json!({"product_id": product_id, "status": "discontinued"}).to_string(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": discontinued})
+        );
+    }
+
+    fn supplier_app(suppliers_repo: MockSuppliersRepository, active_tenant_id: Uuid) -> Router {
         let mut app_state = MockProductsModule::new();
-        let repo = Arc::new(repo);
-        let test_config = AppConfigBuilder::default().build().unwrap();
+        let suppliers_repo = Arc::new(suppliers_repo);
         app_state
-            .expect_products_repo()
+            .expect_suppliers_repo()
             .with(eq(active_tenant_id))
-            .times(1)
-            .returning(move |_| Ok(repo.clone()));
+            .returning(move |_| Ok(suppliers_repo.clone()));
         app_state
             .expect_config()
             .times(1)
-            .return_const(test_config.clone());
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        )
+    }
 
-        let pdf_gen_payload_expected = vec![ProductsResolvedPrint::from_product_resolved(
-            product_resolved,
-            "Europe/Budapest".parse().unwrap(),
-        )];
+    #[tokio::test]
+    async fn test_set_supplier_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+        let product_supplier = ProductSupplier {
+            id: Uuid::new_v4(),
+            product_id,
+            supplier_id,
+            supplier: "Csavar Nagyker Kft.".to_string(),
+            supplier_sku: Some("CS-M8".to_string()),
+            purchase_price: Some(BigDecimal::from(120)),
+            currency_code: Some("HUF".to_string()),
+            lead_time_days: Some(3),
+            is_preferred: true,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
 
-        let _m = PDF_GENERATOR_TEST_SYNC.lock();
-        let pdf_gen = MockPdfGenerator::gen_pdf_temporary_context();
-        pdf_gen
-            .expect::<Vec<ProductsResolvedPrint>>()
+        let mut suppliers_repo = MockSuppliersRepository::new();
+        suppliers_repo
+            .expect_set_product_supplier()
             .times(1)
-            .with(eq(PdfTemplates::ProductView), eq(pdf_gen_payload_expected))
-            .returning(|template, payload| {
-                Ok(PdfGenerator::gen_pdf_temporary(template, payload).unwrap())
+            .withf(move |input, _| {
+                input.product_id == product_id
+                    && input.supplier_sku.as_deref() == Some("CS-M8")
+                    && input.currency_code.as_deref() == Some("HUF")
+                    && input.is_preferred
+            })
+            .returning({
+                let product_supplier = product_supplier.clone();
+                move |_, _| Ok(product_supplier.clone())
             });
 
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!(
-                    "Bearer {}",
-                    generate_valid_jwt(None, Some(active_tenant_id))
-                ),
-            )
-            .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/print?uuid={product_id}"))
-            .body("".to_string())
+        let response = supplier_app(suppliers_repo, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_supplier",
+                active_tenant_id,
+                json!({
+                    "product_id": product_id,
+                    "supplier_id": supplier_id,
+                    "supplier_sku": " CS-M8 ",
+                    "purchase_price": "120",
+                    "currency_code": "HUF",
+                    "lead_time_days": 3,
+                    "is_preferred": true
+                })
+                .to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": product_supplier})
         );
+    }
 
-        let response = app.oneshot(request).await.unwrap();
+    #[tokio::test]
+    async fn test_set_supplier_requires_currency_for_price() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut suppliers_repo = MockSuppliersRepository::new();
+        suppliers_repo.expect_set_product_supplier().never();
+
+        let response = supplier_app(suppliers_repo, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_supplier",
+                active_tenant_id,
+                json!({
+                    "product_id": Uuid::new_v4(),
+                    "supplier_id": Uuid::new_v4(),
+                    "purchase_price": "120",
+                    "lead_time_days": 3
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 
-        let snapshot_path = Path::new("testing/pdf/snapshots/products_test.pdf");
-        let mut file = File::open(snapshot_path).unwrap();
-        let mut snapshot = vec![];
-        file.read_to_end(&mut snapshot).unwrap();
+    fn custom_field(key: &str, field_type: &str, options: &[&str]) -> ProductCustomField {
+        ProductCustomField {
+            id: Uuid::new_v4(),
+            key: key.to_string(),
+            label: key.to_string(),
+            field_type: field_type.to_string(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+            is_required: false,
+            position: 0,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
 
-        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+    #[tokio::test]
+    async fn test_set_custom_fields_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut product = variant_product("Polc", None, None);
+        product.custom_fields = json!({"material": "fa", "note": "raktári"});
+        let product_id = product.id;
+        let updated = Product {
+            custom_fields: json!({"material": "fém", "note": "raktári", "max_load_kg": 12.5}),
+            ..product.clone()
+        };
+
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(product_id))
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_get_custom_fields().times(1).returning(|| {
+            Ok(vec![
+                custom_field("material", "select", &["fa", "fém"]),
+                custom_field("max_load_kg", "number", &[]),
+                custom_field("note", "text", &[]),
+            ])
+        });
+        repo.expect_set_custom_fields()
+            .times(1)
+            .withf(move |id, values| {
+                *id == product_id
+                    && serde_json::Value::Object(values.clone())
+                        == json!({"material": "fém", "note": "raktári", "max_load_kg": 12.5})
+            })
+            .returning({
+                let updated = updated.clone();
+                move |_, _| Ok(updated.clone())
+            });
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_custom_fields",
+                active_tenant_id,
+                json!({
+                    "product_id": product_id,
+                    "values": {"material": " fém ", "max_load_kg": "12,5"}
+                })
+                .to_string(),
+            ))
             .await
-            .unwrap()
-            .to_vec();
-        let test_result_path = Path::new("testing/pdf/test_results/products_test.pdf");
-        let mut file = File::create(test_result_path).unwrap();
-        file.write_all(&body_bytes).unwrap();
+            .unwrap();
 
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            extract_pdf_text(&body_bytes).unwrap(),
-            extract_pdf_text(&snapshot).unwrap()
+            extract_json_response(response).await,
+            json!({"meta": null, "data": updated})
         );
     }
 
     #[tokio::test]
-    async fn test_print_unauthorized_expired() {
-        let product_id = Uuid::new_v4();
+    async fn test_set_custom_fields_rejects_invalid_option() {
+        let active_tenant_id = Uuid::new_v4();
+        let product = variant_product("Polc", None, None);
+        let product_id = product.id;
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
             .times(1)
-            .return_const(test_config.clone());
-
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!("Bearer {}", generate_expired_jwt()),
-            )
-            .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/print?uuid={product_id}"))
-            .body("".to_string())
+            .returning(move |_| Ok(product.clone()));
+        repo.expect_get_custom_fields()
+            .times(1)
+            .returning(|| Ok(vec![custom_field("material", "select", &["fa", "fém"])]));
+        repo.expect_set_custom_fields().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "PUT",
+                "/api/products/set_custom_fields",
+                active_tenant_id,
+                json!({"product_id": product_id, "values": {"material": "műanyag"}}).to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    #[tokio::test]
+    async fn test_list_filter_by_custom_field() {
+        let active_tenant_id = Uuid::new_v4();
+        let paginator_meta = PaginatorMeta {
+            page: 1,
+            limit: 25,
+            total: 0,
+        };
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Hozzáférés megtagadva!"
-            }
-        });
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_custom_fields()
+            .times(1)
+            .returning(|| Ok(vec![custom_field("material", "select", &["fa", "fém"])]));
+        repo.expect_get_paged()
+            .times(1)
+            .withf(|query, _| {
+                query.filtering().filter_by() == Some("custom_fields.material")
+                    && query.filtering().value_unchecked() == Some("fém")
+            })
+            .returning(move |_, _| Ok((paginator_meta, vec![])));
+        repo.expect_get_attachments()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "GET",
+                "/api/products/list?q=filtering%3Acustom_fields.material-%7Cf%C3%A9m%7C",
+                active_tenant_id,
+                "".to_string(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[tokio::test]
-    async fn test_print_unauthorized_invalid_signature() {
-        let product_id = Uuid::new_v4();
+    async fn test_list_rejects_unknown_custom_field() {
+        let active_tenant_id = Uuid::new_v4();
 
-        let mut app_state = MockProductsModule::new();
-        let test_config = AppConfigBuilder::default().build().unwrap();
-        app_state
-            .expect_config()
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_custom_fields()
             .times(1)
-            .return_const(test_config.clone());
-
-        let request = Request::builder()
-            .header(
-                "Authorization",
-                format!("Bearer {}", generate_jwt_with_invalid_signature()),
-            )
-            .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/print?uuid={product_id}"))
-            .body("".to_string())
+            .returning(|| Ok(vec![custom_field("material", "select", &["fa", "fém"])]));
+        repo.expect_get_paged().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "GET",
+                "/api/products/list?q=filtering%3Acustom_fields.color-%7Cpiros%7C",
+                active_tenant_id,
+                "".to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(Arc::new(app_state))),
-        );
-
-        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    #[tokio::test]
+    async fn test_create_reports_possible_duplicates() {
+        let active_tenant_id = Uuid::new_v4();
+        let candidate = ProductDuplicateCandidate {
+            id: Uuid::new_v4(),
+            name: "Fúrógép 500W".to_string(),
+            sku: Some("FG-500".to_string()),
+            barcode: None,
+            status: "active".to_string(),
+            name_similarity: 0.6875,
+            same_sku: false,
+            same_barcode: false,
+        };
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({
-            "error": {
-                "message": "Hozzáférés megtagadva!"
-            }
-        });
+        let mut repo = MockProductsRepository::new();
+        repo.expect_find_duplicates()
+            .times(1)
+            .withf(|query| {
+                query.name == "furogep 500 w"
+                    && query.sku.is_none()
+                    && query.barcode.is_none()
+                    && query.exclude_id.is_none()
+            })
+            .returning({
+                let candidate = candidate.clone();
+                move |_| Ok(vec![candidate.clone()])
+            });
+        repo.expect_insert().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/create?check_duplicates=true",
+                active_tenant_id,
+                json!({
+                    "name": "furogep 500 w",
+                    "description": "",
+                    "unit_of_measure_id": Uuid::new_v4(),
+                    "new_unit_of_measure": "",
+                    "status": "active"
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({
+                "error": {
+                    "code": "CONFLICT",
+                    "message": "Hasonló termék már szerepel a törzsben",
+                    "candidates": [candidate]
+                }
+            })
+        );
     }
 
     #[tokio::test]
-    async fn test_print_unauthorized_missing() {
-        let product_id = Uuid::new_v4();
+    async fn test_merge_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let duplicate_id = Uuid::new_v4();
+        let surviving = variant_product("Fúrógép 500W", None, None);
+        let surviving_id = surviving.id;
 
-        let request = Request::builder()
-            .header("Content-Type", "application/json")
-            .method("GET")
-            .uri(format!("/api/products/print?uuid={product_id}"))
-            .body("".to_string())
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(surviving_id))
+            .returning({
+                let surviving = surviving.clone();
+                move |_| Ok(surviving.clone())
+            });
+        repo.expect_get_variants().never();
+        repo.expect_merge()
+            .times(1)
+            .withf(move |input| {
+                input.duplicate_id == duplicate_id && input.surviving_id == surviving_id
+            })
+            .returning({
+                let surviving = surviving.clone();
+                move |_| Ok(surviving.clone())
+            });
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/merge",
+                active_tenant_id,
+                json!({"duplicate_id": duplicate_id, "surviving_id": surviving_id}).to_string(),
+            ))
+            .await
             .unwrap();
 
-        let app = Router::new().nest(
-            "/api",
-            Router::new().merge(products::routes::routes(
-                Arc::new(MockProductsModule::new()),
-            )),
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": surviving})
         );
+    }
 
-        let response = app.oneshot(request).await.unwrap();
-
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    #[tokio::test]
+    async fn test_merge_rejects_template_into_variant() {
+        let active_tenant_id = Uuid::new_v4();
+        let duplicate_id = Uuid::new_v4();
+        let surviving = variant_product("Póló (M)", Some(Uuid::new_v4()), None);
+        let surviving_id = surviving.id;
 
-        let response_body = extract_json_response(response).await;
-        let expected_body = json!({});
+        let mut repo = MockProductsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(surviving.clone()));
+        repo.expect_get_variants()
+            .times(1)
+            .withf(move |parent_ids| parent_ids == [duplicate_id])
+            .returning(move |_| {
+                Ok(vec![ProductVariant {
+                    id: Uuid::new_v4(),
+                    parent_id: duplicate_id,
+                    name: "Póló (L)".to_string(),
+                    sku: None,
+                    barcode: None,
+                    variant_attributes: json!({"size": "L"}),
+                    price: None,
+                    currency_code: None,
+                    status: "active".to_string(),
+                    quantity_on_hand: BigDecimal::from(0),
+                    quantity_available: BigDecimal::from(0),
+                }])
+            });
+        repo.expect_merge().never();
+
+        let response = variant_app(repo, None, active_tenant_id)
+            .oneshot(variant_request(
+                "POST",
+                "/api/products/merge",
+                active_tenant_id,
+                json!({"duplicate_id": duplicate_id, "surviving_id": surviving_id}).to_string(),
+            ))
+            .await
+            .unwrap();
 
-        assert_eq!(response_body, expected_body);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 }
@@ -19,8 +19,11 @@
 
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
-use crate::common::{AppState, BaseModule};
+use crate::common::storage::{FileStorage, file_storage};
+use crate::common::{AppState, BaseModule, ConfigProvider};
+use crate::manager::tenant_limits::repository::TenantLimitsRepository;
 use crate::tenant::products::repository::ProductsRepository;
+use crate::tenant::suppliers::repository::SuppliersRepository;
 use lettre::{
     AsyncTransport,
     transport::smtp::{Error, response::Response},
@@ -42,6 +45,12 @@ pub trait ProductsModuleInterface: BaseModule {
         &self,
         tenant_id: Uuid,
     ) -> RepositoryResult<Arc<dyn ProductsRepository + Send + Sync>>;
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
+    fn suppliers_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn SuppliersRepository + Send + Sync>>;
 }
 
 impl<P, T> ProductsModuleInterface for AppState<P, T>
@@ -56,6 +65,18 @@ where
     ) -> RepositoryResult<Arc<dyn ProductsRepository + Send + Sync>> {
         Ok(self.get_tenant_pool(tenant_id)?)
     }
+    fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync> {
+        self.get_main_pool()
+    }
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync> {
+        file_storage(self.config().storage())
+    }
+    fn suppliers_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn SuppliersRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +106,12 @@ pub mod tests {
                 &self,
                 tenant_id: Uuid,
             ) -> RepositoryResult<Arc<dyn ProductsRepository + Send + Sync>>;
+            fn tenant_limits_repo(&self) -> Arc<dyn TenantLimitsRepository + Send + Sync>;
+            fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
+            fn suppliers_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn SuppliersRepository + Send + Sync>>;
         }
     );
 }
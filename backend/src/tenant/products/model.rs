@@ -17,9 +17,11 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use sqlx::types::JsonValue;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -27,6 +29,13 @@ pub struct Product {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub variant_attributes: Option<JsonValue>,
+    pub custom_fields: JsonValue,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
     pub unit_of_measure_id: Uuid,
     pub status: String,
     pub created_by_id: Uuid,
@@ -40,6 +49,13 @@ pub struct ProductResolved {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub variant_attributes: Option<JsonValue>,
+    pub custom_fields: JsonValue,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
     pub unit_of_measure_id: Uuid,
     pub unit_of_measure: String,
     pub status: String,
@@ -48,6 +64,36 @@ pub struct ProductResolved {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub attachments: Vec<ProductAttachmentLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductCustomField {
+    pub id: Uuid,
+    pub key: String,
+    pub label: String,
+    pub field_type: String,
+    pub options: Vec<String>,
+    pub is_required: bool,
+    pub position: i32,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductDuplicateCandidate {
+    pub id: Uuid,
+    pub name: String,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub status: String,
+    pub name_similarity: f32,
+    pub same_sku: bool,
+    pub same_barcode: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -57,3 +103,141 @@ pub struct UnitOfMeasure {
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductDimensions {
+    pub product_id: Uuid,
+    pub weight_g: Option<i32>,
+    pub length_mm: Option<i32>,
+    pub width_mm: Option<i32>,
+    pub height_mm: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductStockThreshold {
+    pub product_id: Uuid,
+    pub minimum_stock: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductVariant {
+    pub id: Uuid,
+    pub parent_id: Uuid,
+    pub name: String,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub variant_attributes: JsonValue,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub status: String,
+    pub quantity_on_hand: BigDecimal,
+    pub quantity_available: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductGroup {
+    #[serde(flatten)]
+    pub product: ProductResolved,
+    pub variants: Vec<ProductVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductAttachment {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub kind: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    #[serde(skip)]
+    pub storage_key: String,
+    #[serde(skip)]
+    pub thumbnail_key: Option<String>,
+    pub sort_order: i32,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductAttachmentLink {
+    #[serde(flatten)]
+    pub attachment: ProductAttachment,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+}
+
+impl From<ProductAttachment> for ProductAttachmentLink {
+    fn from(attachment: ProductAttachment) -> Self {
+        Self {
+            url: format!("/api/products/attachment?uuid={}", attachment.id),
+            thumbnail_url: attachment
+                .thumbnail_key
+                .as_ref()
+                .map(|_| format!("/api/products/attachment_thumbnail?uuid={}", attachment.id)),
+            attachment,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductBundleComponent {
+    pub component_id: Uuid,
+    pub name: String,
+    pub sku: Option<String>,
+    pub unit_of_measure: String,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductBundleAvailability {
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub quantity_available: BigDecimal,
+}
+
+/// Column layout of the catalogue export, which is also what the import expects.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductExportRow {
+    pub sku: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub barcode: Option<String>,
+    pub unit_of_measure: String,
+    pub status: String,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductImportLine {
+    pub line: u64,
+    pub sku: String,
+    pub name: String,
+    pub product_id: Option<Uuid>,
+    pub product_created: bool,
+    pub product_updated: bool,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductImportReport {
+    pub import_id: Option<Uuid>,
+    pub applied: bool,
+    pub row_count: usize,
+    pub error_count: usize,
+    pub products_created: i32,
+    pub products_updated: i32,
+    pub lines: Vec<ProductImportLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductImport {
+    pub id: Uuid,
+    pub row_count: i32,
+    pub products_created: i32,
+    pub products_updated: i32,
+    pub created_by_id: Uuid,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -17,19 +17,196 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::database::copy_tags;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::manager::tenant_limits::model::TenantLimits;
+use crate::tenant::attachments::repository::lock_attachments_size_total;
+use crate::tenant::products::dto::barcode::BarcodeSymbology;
+use crate::tenant::products::dto::bundle::ProductBundleInput;
+use crate::tenant::products::dto::custom_field::ProductCustomFieldInput;
+use crate::tenant::products::dto::dimensions::ProductDimensionsInput;
+use crate::tenant::products::dto::duplicate::{
+    DUPLICATE_NAME_SIMILARITY, ProductDuplicateQuery, ProductMergeInput,
+};
+use crate::tenant::products::dto::import::ProductImportRow;
+use crate::tenant::products::dto::stock_threshold::ProductStockThresholdInput;
 use crate::tenant::products::dto::user_input::ProductUserInput;
-use crate::tenant::products::model::{Product, ProductResolved, UnitOfMeasure};
-use crate::tenant::products::types::product::{ProductFilterBy, ProductOrderBy};
+use crate::tenant::products::dto::variant::ProductVariantInput;
+use crate::tenant::products::model::{
+    Product, ProductAttachment, ProductBundleAvailability, ProductBundleComponent,
+    ProductCustomField, ProductDimensions, ProductDuplicateCandidate, ProductExportRow,
+    ProductImport, ProductImportLine, ProductImportReport, ProductResolved, ProductStockThreshold,
+    ProductVariant, UnitOfMeasure,
+};
+use crate::tenant::products::types::product::{ProductFilterBy, ProductOrderBy, ProductStatus};
+use crate::tenant::tags::repository::tagged_condition;
 use async_trait::async_trait;
+use bigdecimal::BigDecimal;
 #[cfg(test)]
 use mockall::automock;
-use sqlx::{AssertSqlSafe, PgPool};
+use serde_json::Map;
+use sqlx::types::JsonValue;
+use sqlx::{Acquire, AssertSqlSafe, PgConnection, PgPool};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+const RESOLVED_COLUMNS: &str = r#"
+    products.id as id,
+    products.name as name,
+    products.description as description,
+    products.sku as sku,
+    products.barcode as barcode,
+    products.parent_id as parent_id,
+    products.variant_attributes as variant_attributes,
+    products.custom_fields as custom_fields,
+    products.price as price,
+    products.currency_code as currency_code,
+    products.unit_of_measure_id as unit_of_measure_id,
+    units_of_measure.unit_of_measure as unit_of_measure,
+    products.status as status,
+    products.created_by_id as created_by_id,
+    users.last_name || ' ' || users.first_name as created_by,
+    products.created_at as created_at,
+    products.updated_at as updated_at,
+    products.deleted_at as deleted_at
+"#;
+
+// NOTE: the category filter also matches the products of every descendant category, custom
+// field keys are validated by `ProductFilterBy`
+fn filter_clause(filter_by: &str, table: &str) -> String {
+    match filter_by {
+        "category_id" => format!(
+            r#"{table}.id IN (
+            WITH RECURSIVE subtree AS (
+                SELECT id FROM product_category WHERE id = $1::UUID AND deleted_at IS NULL
+                UNION
+                SELECT product_category.id
+                FROM product_category
+                JOIN subtree ON product_category.parent_id = subtree.id
+                WHERE product_category.deleted_at IS NULL
+            )
+            SELECT product_category_connect.product_id
+            FROM product_category_connect
+            JOIN subtree ON product_category_connect.product_category_id = subtree.id
+            WHERE product_category_connect.deleted_at IS NULL
+        )"#
+        ),
+        filter_by if filter_by.starts_with("custom_fields.") => format!(
+            "({table}.custom_fields ->> '{}' ILIKE '%' || $1 || '%')",
+            &filter_by["custom_fields.".len()..]
+        ),
+        filter_by => format!("({table}.{filter_by}::TEXT ILIKE '%' || $1 || '%')"),
+    }
+}
+
+// NOTE: when only templates are listed, a template also matches if any of its variants does
+fn where_clause(filter_by: Option<&str>, templates_only: bool) -> String {
+    match (filter_by, templates_only) {
+        (None, false) => "products.deleted_at IS NULL".to_string(),
+        (None, true) => "products.deleted_at IS NULL AND products.parent_id IS NULL".to_string(),
+        (Some(filter_by), false) => format!(
+            "products.deleted_at IS NULL AND {}",
+            filter_clause(filter_by, "products")
+        ),
+        (Some(filter_by), true) => format!(
+            r#"products.deleted_at IS NULL
+                AND products.parent_id IS NULL
+                AND ({} OR EXISTS(
+                    SELECT 1
+                    FROM products AS variants
+                    WHERE variants.parent_id = products.id
+                        AND variants.deleted_at IS NULL
+                        AND {}
+                ))"#,
+            filter_clause(filter_by, "products"),
+            filter_clause(filter_by, "variants")
+        ),
+    }
+}
+
+async fn get_paged(
+    pool: &PgPool,
+    query_params: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+    templates_only: bool,
+    tags: Option<Vec<Uuid>>,
+) -> RepositoryResult<(PaginatorMeta, Vec<ProductResolved>)> {
+    let value_unchecked = query_params.filtering().value_unchecked(); // Security: bind
+    let where_clause = where_clause(
+        query_params
+            .filtering()
+            .filter_by() // Security: ValueObject
+            .filter(|_| value_unchecked.is_some()),
+        templates_only,
+    );
+
+    let order_by_clause = match (
+        query_params.ordering().order_by(), // Security: ValueObject
+        query_params.ordering().order(),    // Security: enum
+    ) {
+        (Some(order_by), Some(order)) => format!("ORDER BY products.{order_by} {order}"),
+        (_, _) => "".to_string(),
+    };
+
+    let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+    let offset = i32::try_from(query_params.paging().offset().unwrap_or(0))?;
+
+    let total: (i64,) = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT COUNT(*) FROM products WHERE {where_clause} AND {}",
+        tagged_condition("products", "products.id", 2)
+    )))
+    .bind(value_unchecked)
+    .bind(&tags)
+    .fetch_one(pool)
+    .await?;
+
+    let products = sqlx::query_as::<_, ProductResolved>(AssertSqlSafe(format!(
+        r#"
+        SELECT {RESOLVED_COLUMNS}
+        FROM products
+        LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+        LEFT JOIN users ON products.created_by_id = users.id
+        WHERE {where_clause}
+            AND {tagged_condition}
+        {order_by_clause}
+        LIMIT $2
+        OFFSET $3
+        "#,
+        tagged_condition = tagged_condition("products", "products.id", 4)
+    )))
+    .bind(value_unchecked)
+    .bind(limit)
+    .bind(offset)
+    .bind(&tags)
+    .fetch_all(pool)
+    .await?;
+
+    Ok((
+        PaginatorMeta {
+            page: query_params.paging().page().unwrap_or(1).try_into()?,
+            limit,
+            total: total.0,
+        },
+        products,
+    ))
+}
+
+/// Active products of the tenant, the product limit is checked against it. Takes the product
+/// quota lock for the rest of the transaction, so that concurrent inserts are checked and
+/// inserted one after the other.
+pub(crate) async fn lock_active_products(conn: &mut PgConnection) -> RepositoryResult<i64> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('products_quota'))")
+        .execute(&mut *conn)
+        .await?;
+    Ok(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM products WHERE deleted_at IS NULL")
+            .fetch_one(&mut *conn)
+            .await?,
+    )
+}
+
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait ProductsRepository: Send + Sync {
@@ -39,8 +216,32 @@ pub trait ProductsRepository: Send + Sync {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<ProductResolved>)>;
-    async fn insert(&self, product: &ProductUserInput, sub: Uuid) -> RepositoryResult<Product>;
+    async fn get_paged_templates(
+        &self,
+        query_params: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<ProductResolved>)>;
+    async fn get_variants(&self, parent_ids: &[Uuid]) -> RepositoryResult<Vec<ProductVariant>>;
+    async fn insert_variant(
+        &self,
+        template: &Product,
+        input: &ProductVariantInput,
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>>;
+    async fn update_variant(
+        &self,
+        template: &Product,
+        input: &ProductVariantInput,
+    ) -> RepositoryResult<Product>;
+    async fn insert(
+        &self,
+        product: &ProductUserInput,
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>>;
     async fn update(&self, product: ProductUserInput) -> RepositoryResult<Product>;
     async fn insert_unit_of_measure(
         &self,
@@ -49,6 +250,85 @@ pub trait ProductsRepository: Send + Sync {
     ) -> RepositoryResult<UnitOfMeasure>;
     async fn get_units_of_measure_select_list(&self) -> RepositoryResult<Vec<SelectOption>>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn duplicate(
+        &self,
+        params: &DuplicateParams,
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>>;
+    async fn get_dimensions(&self, id: Uuid) -> RepositoryResult<ProductDimensions>;
+    async fn get_stock_threshold(&self, id: Uuid) -> RepositoryResult<ProductStockThreshold>;
+    async fn set_stock_threshold(
+        &self,
+        threshold: &ProductStockThresholdInput,
+    ) -> RepositoryResult<ProductStockThreshold>;
+    async fn set_dimensions(
+        &self,
+        dimensions: &ProductDimensionsInput,
+    ) -> RepositoryResult<ProductDimensions>;
+    async fn get_attachments(
+        &self,
+        product_ids: &[Uuid],
+    ) -> RepositoryResult<Vec<ProductAttachment>>;
+    async fn get_attachment(&self, id: Uuid) -> RepositoryResult<ProductAttachment>;
+    async fn insert_attachment(
+        &self,
+        attachment: &ProductAttachment,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<ProductAttachment>>;
+    async fn delete_attachment(&self, id: Uuid) -> RepositoryResult<ProductAttachment>;
+    async fn get_bundle_components(
+        &self,
+        bundle_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductBundleComponent>>;
+    async fn set_bundle_components(
+        &self,
+        input: &ProductBundleInput,
+        sub: Uuid,
+    ) -> RepositoryResult<()>;
+    async fn get_bundle_ids(&self, product_ids: &[Uuid]) -> RepositoryResult<Vec<Uuid>>;
+    async fn is_bundle_component(&self, product_id: Uuid) -> RepositoryResult<bool>;
+    async fn get_bundle_availability(
+        &self,
+        bundle_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductBundleAvailability>>;
+    async fn get_export_rows(&self) -> RepositoryResult<Vec<ProductExportRow>>;
+    async fn import(
+        &self,
+        rows: &[ProductImportRow],
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+        apply: bool,
+    ) -> RepositoryResult<ProductImportReport>;
+    async fn get_imports(&self) -> RepositoryResult<Vec<ProductImport>>;
+    async fn get_by_ids(&self, ids: &[Uuid]) -> RepositoryResult<Vec<Product>>;
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<Product>;
+    async fn assign_barcodes(
+        &self,
+        product_ids: &[Uuid],
+        symbology: BarcodeSymbology,
+    ) -> RepositoryResult<Vec<Product>>;
+    async fn get_custom_fields(&self) -> RepositoryResult<Vec<ProductCustomField>>;
+    async fn insert_custom_field(
+        &self,
+        input: &ProductCustomFieldInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ProductCustomField>;
+    async fn update_custom_field(
+        &self,
+        input: &ProductCustomFieldInput,
+    ) -> RepositoryResult<ProductCustomField>;
+    async fn delete_custom_field(&self, id: Uuid) -> RepositoryResult<ProductCustomField>;
+    async fn set_custom_fields(
+        &self,
+        product_id: Uuid,
+        values: &Map<String, JsonValue>,
+    ) -> RepositoryResult<Product>;
+    async fn find_duplicates(
+        &self,
+        query: &ProductDuplicateQuery,
+    ) -> RepositoryResult<Vec<ProductDuplicateCandidate>>;
+    async fn merge(&self, input: &ProductMergeInput) -> RepositoryResult<Product>;
 }
 
 #[async_trait]
@@ -68,27 +348,16 @@ impl ProductsRepository for PgPool {
     }
 
     async fn get_resolved_by_id(&self, id: Uuid) -> RepositoryResult<ProductResolved> {
-        Ok(sqlx::query_as::<_, ProductResolved>(
-            r#"
-            SELECT
-                products.id as id,
-                products.name as name,
-                products.description as description,
-                products.unit_of_measure_id as unit_of_measure_id,
-                units_of_measure.unit_of_measure as unit_of_measure,
-                products.status as status,
-                products.created_by_id as created_by_id,
-                users.last_name || ' ' || users.first_name as created_by,
-                products.created_at as created_at,
-                products.updated_at as updated_at,
-                products.deleted_at as deleted_at
-            FROM products
-            LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
-            LEFT JOIN users ON products.created_by_id = users.id
-            WHERE products.deleted_at IS NULL
-                AND products.id = $1
-            "#,
-        )
+        Ok(sqlx::query_as::<_, ProductResolved>(AssertSqlSafe(format!(
+            r#"
+                SELECT {RESOLVED_COLUMNS}
+                FROM products
+                LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+                LEFT JOIN users ON products.created_by_id = users.id
+                WHERE products.deleted_at IS NULL
+                    AND products.id = $1
+                "#
+        )))
         .bind(id)
         .fetch_one(self)
         .await?)
@@ -103,137 +372,48 @@ impl ProductsRepository for PgPool {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<ProductResolved>)> {
-        let total: (i64,) = match (
-            query_params.filtering().filter_by(), // Security: ValueObject
-            query_params.filtering().value_unchecked(), // Security: bind
-        ) {
-            (Some(filter_by), Some(value_unchecked)) => {
-                sqlx::query_as(AssertSqlSafe(format!(
-                    r#"SELECT COUNT(*) FROM products
-                        WHERE deleted_at IS NULL
-                            AND ($1::TEXT IS NULL OR products.{filter_by}::TEXT ILIKE '%' || $1 || '%')"#
-                )))
-                .bind(value_unchecked)
-                .fetch_one(self)
-                .await?
-            }
-            (_, _) => {
-                sqlx::query_as("SELECT COUNT(*) FROM products WHERE deleted_at IS NULL")
-                    .fetch_one(self)
-                    .await?
-            }
-        };
-
-        let order_by_clause = match (
-            query_params.ordering().order_by(), // Security: ValueObject
-            query_params.ordering().order(),    // Security: enum
-        ) {
-            (Some(order_by), Some(order)) => format!("ORDER BY products.{order_by} {order}"),
-            (_, _) => "".to_string(),
-        };
-
-        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
-
-        let products = match (
-            query_params.filtering().filter_by(), // Security: ValueObject
-            query_params.filtering().value_unchecked(), // Security: bind
-        ) {
-            (Some(filter_by), Some(value_unchecked)) => {
-                let sql = format!(
-                    r#"
-                    SELECT
-                        products.id as id,
-                        products.name as name,
-                        products.description as description,
-                        products.unit_of_measure_id as unit_of_measure_id,
-                        units_of_measure.unit_of_measure as unit_of_measure,
-                        products.status as status,
-                        products.created_by_id as created_by_id,
-                        users.last_name || ' ' || users.first_name as created_by,
-                        products.created_at as created_at,
-                        products.updated_at as updated_at,
-                        products.deleted_at as deleted_at
-                    FROM products
-                    LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
-                    LEFT JOIN users ON products.created_by_id = users.id
-                    WHERE products.deleted_at IS NULL
-                        AND ($1::TEXT IS NULL OR products.{filter_by}::TEXT ILIKE '%' || $1 || '%')
-                    {order_by_clause}
-                    LIMIT $2
-                    OFFSET $3
-                    "#
-                );
-
-                sqlx::query_as::<_, ProductResolved>(AssertSqlSafe(sql))
-                    .bind(value_unchecked)
-                    .bind(limit)
-                    .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
-                    .fetch_all(self)
-                    .await?
-            }
-            (_, _) => {
-                let sql = format!(
-                    r#"
-                    SELECT
-                        products.id as id,
-                        products.name as name,
-                        products.description as description,
-                        products.unit_of_measure_id as unit_of_measure_id,
-                        units_of_measure.unit_of_measure as unit_of_measure,
-                        products.status as status,
-                        products.created_by_id as created_by_id,
-                        users.last_name || ' ' || users.first_name as created_by,
-                        products.created_at as created_at,
-                        products.updated_at as updated_at,
-                        products.deleted_at as deleted_at
-                    FROM products
-                    LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
-                    LEFT JOIN users ON products.created_by_id = users.id
-                    WHERE products.deleted_at IS NULL
-                    {order_by_clause}
-                    LIMIT $1
-                    OFFSET $2
-                    "#
-                );
-
-                sqlx::query_as::<_, ProductResolved>(AssertSqlSafe(sql))
-                    .bind(limit)
-                    .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
-                    .fetch_all(self)
-                    .await?
-            }
-        };
+        get_paged(self, query_params, false, tags).await
+    }
 
-        Ok((
-            PaginatorMeta {
-                page: query_params.paging().page().unwrap_or(1).try_into()?,
-                limit,
-                total: total.0,
-            },
-            products,
-        ))
+    async fn get_paged_templates(
+        &self,
+        query_params: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<ProductResolved>)> {
+        get_paged(self, query_params, true, tags).await
     }
     async fn insert(
         &self,
         input: &ProductUserInput,
         sub: Uuid,
-    ) -> Result<Product, RepositoryError> {
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>> {
         let unit_of_measure_id = match &input.unit_of_measure_id {
             Some(v) => Some(v.as_uuid()?),
             None => None,
         };
-        Ok(sqlx::query_as::<_, Product>(
-            "INSERT INTO products (name, description, unit_of_measure_id, status, created_by_id)
-                 VALUES ($1, $2, $3, $4, $5) RETURNING *",
+        let mut tx = self.begin().await?;
+        let active_products = lock_active_products(&mut tx).await?;
+        if limits.is_some_and(|limits| !limits.allows_products(active_products)) {
+            return Ok(None);
+        }
+        let product = sqlx::query_as::<_, Product>(
+            "INSERT INTO products (name, description, unit_of_measure_id, status, created_by_id, sku, barcode)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
         )
         .bind(input.name.as_str()?)
         .bind(input.description.as_str())
         .bind(unit_of_measure_id)
         .bind(input.status.as_str()?)
         .bind(sub)
-        .fetch_one(self)
-        .await?)
+        .bind(input.sku.as_str())
+        .bind(input.barcode.as_str())
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(product))
     }
 
     async fn update(&self, input: ProductUserInput) -> RepositoryResult<Product> {
@@ -251,7 +431,9 @@ impl ProductsRepository for PgPool {
             SET name = $1,
                 description = $2,
                 unit_of_measure_id = $3,
-                status = $4
+                status = $4,
+                sku = $6,
+                barcode = $7
             WHERE id = $5
                 AND deleted_at IS NULL
             RETURNING *
@@ -262,6 +444,8 @@ impl ProductsRepository for PgPool {
         .bind(unit_of_measure_id)
         .bind(input.status.as_str()?)
         .bind(id)
+        .bind(input.sku.as_str())
+        .bind(input.barcode.as_str())
         .fetch_one(self)
         .await?)
     }
@@ -292,9 +476,9 @@ impl ProductsRepository for PgPool {
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
         sqlx::query(
             r#"
-            UPDATE products 
+            UPDATE products
             SET deleted_at = NOW()
-            WHERE id = $1
+            WHERE (id = $1 OR parent_id = $1)
                 AND deleted_at IS NULL
             "#,
         )
@@ -304,4 +488,1011 @@ impl ProductsRepository for PgPool {
 
         Ok(())
     }
+
+    async fn get_dimensions(&self, id: Uuid) -> RepositoryResult<ProductDimensions> {
+        Ok(sqlx::query_as::<_, ProductDimensions>(
+            r#"
+            SELECT id AS product_id, weight_g, length_mm, width_mm, height_mm
+            FROM products
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_dimensions(
+        &self,
+        dimensions: &ProductDimensionsInput,
+    ) -> RepositoryResult<ProductDimensions> {
+        Ok(sqlx::query_as::<_, ProductDimensions>(
+            r#"
+            UPDATE products
+            SET weight_g = $1,
+                length_mm = $2,
+                width_mm = $3,
+                height_mm = $4
+            WHERE id = $5
+                AND deleted_at IS NULL
+            RETURNING id AS product_id, weight_g, length_mm, width_mm, height_mm
+            "#,
+        )
+        .bind(dimensions.weight_g)
+        .bind(dimensions.length_mm)
+        .bind(dimensions.width_mm)
+        .bind(dimensions.height_mm)
+        .bind(dimensions.product_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_stock_threshold(&self, id: Uuid) -> RepositoryResult<ProductStockThreshold> {
+        Ok(sqlx::query_as::<_, ProductStockThreshold>(
+            r#"
+            SELECT id AS product_id, minimum_stock
+            FROM products
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_stock_threshold(
+        &self,
+        threshold: &ProductStockThresholdInput,
+    ) -> RepositoryResult<ProductStockThreshold> {
+        Ok(sqlx::query_as::<_, ProductStockThreshold>(
+            r#"
+            UPDATE products
+            SET minimum_stock = $1
+            WHERE id = $2
+                AND deleted_at IS NULL
+            RETURNING id AS product_id, minimum_stock
+            "#,
+        )
+        .bind(&threshold.minimum_stock)
+        .bind(threshold.product_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn duplicate(
+        &self,
+        params: &DuplicateParams,
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>> {
+        let mut tx = self.begin().await?;
+        let active_products = lock_active_products(&mut tx).await?;
+        if limits.is_some_and(|limits| !limits.allows_products(active_products)) {
+            return Ok(None);
+        }
+        // NOTE: the copy starts as a draft, it is released like any new product
+        let product = sqlx::query_as::<_, Product>(
+            r#"
+            INSERT INTO products (name, description, unit_of_measure_id, status, created_by_id,
+                                  weight_g, length_mm, width_mm, height_mm, price, currency_code,
+                                  custom_fields)
+            SELECT left(name || ' (másolat)', 255), description, unit_of_measure_id, 'draft', $2,
+                   weight_g, length_mm, width_mm, height_mm, price, currency_code, custom_fields
+            FROM products
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(params.id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO product_category_connect (product_id, product_category_id, created_by_id)
+            SELECT $1, product_category_id, $2
+            FROM product_category_connect
+            WHERE product_id = $3 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(product.id)
+        .bind(sub)
+        .bind(params.id)
+        .execute(&mut *tx)
+        .await?;
+        if params.include_tags {
+            copy_tags(&mut tx, "products", params.id, product.id, sub).await?;
+        }
+        tx.commit().await?;
+        Ok(Some(product))
+    }
+
+    async fn get_variants(&self, parent_ids: &[Uuid]) -> RepositoryResult<Vec<ProductVariant>> {
+        Ok(sqlx::query_as::<_, ProductVariant>(
+            r#"
+            SELECT products.id,
+                   products.parent_id,
+                   products.name,
+                   products.sku,
+                   products.barcode,
+                   products.variant_attributes,
+                   products.price,
+                   products.currency_code,
+                   products.status,
+                   COALESCE(SUM(inventory.quantity_on_hand), 0) AS quantity_on_hand,
+                   COALESCE(SUM(inventory.quantity_available), 0) AS quantity_available
+            FROM products
+            LEFT JOIN inventory ON inventory.product_id = products.id
+                AND inventory.deleted_at IS NULL
+                AND inventory.status = 'active'
+            WHERE products.parent_id = ANY($1)
+                AND products.deleted_at IS NULL
+            GROUP BY products.id
+            ORDER BY products.parent_id, products.name
+            "#,
+        )
+        .bind(parent_ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_variant(
+        &self,
+        template: &Product,
+        input: &ProductVariantInput,
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<Product>> {
+        let mut tx = self.begin().await?;
+        let active_products = lock_active_products(&mut tx).await?;
+        if limits.is_some_and(|limits| !limits.allows_products(active_products)) {
+            return Ok(None);
+        }
+        let variant = sqlx::query_as::<_, Product>(
+            r#"
+            INSERT INTO products (name, description, unit_of_measure_id, status, created_by_id, sku,
+                                  barcode, parent_id, variant_attributes, price, currency_code,
+                                  weight_g, length_mm, width_mm, height_mm, custom_fields)
+            SELECT $1, description, unit_of_measure_id, $2, $3, $4, $5, id, $6, $7, $8,
+                   weight_g, length_mm, width_mm, height_mm, custom_fields
+            FROM products
+            WHERE id = $9
+                AND parent_id IS NULL
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.variant_name(&template.name))
+        .bind(&input.status)
+        .bind(sub)
+        .bind(&input.sku)
+        .bind(&input.barcode)
+        .bind(sqlx::types::Json(&input.attributes))
+        .bind(&input.price)
+        .bind(&input.currency_code)
+        .bind(template.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(variant))
+    }
+
+    async fn update_variant(
+        &self,
+        template: &Product,
+        input: &ProductVariantInput,
+    ) -> RepositoryResult<Product> {
+        let id = input
+            .id
+            .ok_or_else(|| RepositoryError::InvalidInput("id".to_string()))?;
+        Ok(sqlx::query_as::<_, Product>(
+            r#"
+            UPDATE products
+            SET name = $1,
+                status = $2,
+                sku = $3,
+                barcode = $4,
+                variant_attributes = $5,
+                price = $6,
+                currency_code = $7
+            WHERE id = $8
+                AND parent_id = $9
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.variant_name(&template.name))
+        .bind(&input.status)
+        .bind(&input.sku)
+        .bind(&input.barcode)
+        .bind(sqlx::types::Json(&input.attributes))
+        .bind(&input.price)
+        .bind(&input.currency_code)
+        .bind(id)
+        .bind(template.id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_attachments(
+        &self,
+        product_ids: &[Uuid],
+    ) -> RepositoryResult<Vec<ProductAttachment>> {
+        Ok(sqlx::query_as::<_, ProductAttachment>(
+            r#"
+            SELECT *
+            FROM product_attachments
+            WHERE product_id = ANY($1)
+                AND deleted_at IS NULL
+            ORDER BY product_id, sort_order, created_at
+            "#,
+        )
+        .bind(product_ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_attachment(&self, id: Uuid) -> RepositoryResult<ProductAttachment> {
+        Ok(sqlx::query_as::<_, ProductAttachment>(
+            "SELECT * FROM product_attachments WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_attachment(
+        &self,
+        attachment: &ProductAttachment,
+        limits: Option<TenantLimits>,
+    ) -> RepositoryResult<Option<ProductAttachment>> {
+        let mut tx = self.begin().await?;
+        let size_total = lock_attachments_size_total(&mut tx).await?;
+        if limits.is_some_and(|limits| !limits.allows_storage(size_total, attachment.size_bytes)) {
+            return Ok(None);
+        }
+        let attachment = sqlx::query_as::<_, ProductAttachment>(
+            r#"
+            INSERT INTO product_attachments (id, product_id, kind, file_name, content_type,
+                                             size_bytes, storage_key, thumbnail_key, sort_order,
+                                             created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8,
+                    (SELECT COALESCE(MAX(sort_order) + 1, 0)
+                     FROM product_attachments
+                     WHERE product_id = $2 AND deleted_at IS NULL),
+                    $9)
+            RETURNING *
+            "#,
+        )
+        .bind(attachment.id)
+        .bind(attachment.product_id)
+        .bind(&attachment.kind)
+        .bind(&attachment.file_name)
+        .bind(&attachment.content_type)
+        .bind(attachment.size_bytes)
+        .bind(&attachment.storage_key)
+        .bind(&attachment.thumbnail_key)
+        .bind(attachment.created_by_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(attachment))
+    }
+
+    async fn delete_attachment(&self, id: Uuid) -> RepositoryResult<ProductAttachment> {
+        Ok(sqlx::query_as::<_, ProductAttachment>(
+            r#"
+            UPDATE product_attachments
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_bundle_components(
+        &self,
+        bundle_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductBundleComponent>> {
+        Ok(sqlx::query_as::<_, ProductBundleComponent>(
+            r#"
+            SELECT product_bundle_components.component_id,
+                   products.name,
+                   products.sku,
+                   units_of_measure.unit_of_measure,
+                   product_bundle_components.quantity
+            FROM product_bundle_components
+            JOIN products ON product_bundle_components.component_id = products.id
+            LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+            WHERE product_bundle_components.bundle_id = $1
+            ORDER BY products.name
+            "#,
+        )
+        .bind(bundle_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_bundle_components(
+        &self,
+        input: &ProductBundleInput,
+        sub: Uuid,
+    ) -> RepositoryResult<()> {
+        let component_ids: Vec<Uuid> = input
+            .components
+            .iter()
+            .map(|component| component.component_id)
+            .collect();
+        let quantities: Vec<BigDecimal> = input
+            .components
+            .iter()
+            .map(|component| component.quantity.clone())
+            .collect();
+        let mut tx = self.begin().await?;
+        sqlx::query("DELETE FROM product_bundle_components WHERE bundle_id = $1")
+            .bind(input.bundle_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO product_bundle_components (bundle_id, component_id, quantity, created_by_id)
+            SELECT $1, components.component_id, components.quantity, $4
+            FROM UNNEST($2::UUID[], $3::NUMERIC[]) AS components(component_id, quantity)
+            "#,
+        )
+        .bind(input.bundle_id)
+        .bind(&component_ids)
+        .bind(&quantities)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_bundle_ids(&self, product_ids: &[Uuid]) -> RepositoryResult<Vec<Uuid>> {
+        Ok(sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT DISTINCT bundle_id
+            FROM product_bundle_components
+            WHERE bundle_id = ANY($1)
+            "#,
+        )
+        .bind(product_ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn is_bundle_component(&self, product_id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM product_bundle_components WHERE component_id = $1)",
+        )
+        .bind(product_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    // NOTE: a bundle is as available as its scarcest component, warehouses missing a component
+    // entirely offer zero bundles
+    async fn get_bundle_availability(
+        &self,
+        bundle_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductBundleAvailability>> {
+        Ok(sqlx::query_as::<_, ProductBundleAvailability>(
+            r#"
+            SELECT warehouses.id AS warehouse_id,
+                   warehouses.name AS warehouse,
+                   MIN(GREATEST(FLOOR(COALESCE(inventory.quantity_available, 0)
+                       / product_bundle_components.quantity), 0)) AS quantity_available
+            FROM product_bundle_components
+            CROSS JOIN warehouses
+            LEFT JOIN inventory ON inventory.product_id = product_bundle_components.component_id
+                AND inventory.warehouse_id = warehouses.id
+                AND inventory.deleted_at IS NULL
+                AND inventory.status = 'active'
+            WHERE product_bundle_components.bundle_id = $1
+                AND warehouses.deleted_at IS NULL
+            GROUP BY warehouses.id, warehouses.name
+            HAVING COUNT(inventory.id) > 0
+            ORDER BY warehouses.name
+            "#,
+        )
+        .bind(bundle_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_export_rows(&self) -> RepositoryResult<Vec<ProductExportRow>> {
+        Ok(sqlx::query_as::<_, ProductExportRow>(
+            r#"
+            SELECT products.sku,
+                   products.name,
+                   products.description,
+                   products.barcode,
+                   units_of_measure.unit_of_measure,
+                   products.status,
+                   products.price,
+                   products.currency_code
+            FROM products
+            JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+            WHERE products.deleted_at IS NULL
+            ORDER BY products.name, products.sku
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn import(
+        &self,
+        rows: &[ProductImportRow],
+        sub: Uuid,
+        limits: Option<TenantLimits>,
+        apply: bool,
+    ) -> RepositoryResult<ProductImportReport> {
+        // NOTE: dry runs go through the same statements and are rolled back at the end,
+        // so the report always matches what an apply would do.
+        let import_id = Uuid::new_v4();
+        let mut tx = self.begin().await?;
+        let active_products = lock_active_products(&mut tx).await?;
+        let product_allowance = limits
+            .and_then(|limits| limits.max_products)
+            .map(|max| (i64::from(max) - active_products).max(0));
+        let mut seen = HashSet::new();
+        let mut lines = Vec::with_capacity(rows.len());
+        let (mut products_created, mut products_updated) = (0, 0);
+
+        for row in rows {
+            let mut line = ProductImportLine {
+                line: row.line,
+                sku: row.sku.clone(),
+                name: row.name.clone(),
+                product_id: None,
+                product_created: false,
+                product_updated: false,
+                errors: row.errors.clone(),
+            };
+            if !line.errors.is_empty() {
+                lines.push(line);
+                continue;
+            }
+            if !seen.insert(row.sku.as_str()) {
+                line.errors
+                    .push("A cikkszám többször szerepel a fájlban!".to_owned());
+                lines.push(line);
+                continue;
+            }
+
+            if let Some(currency_code) = &row.currency_code {
+                let currency_exists = sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS (SELECT 1 FROM currencies WHERE code = $1)",
+                )
+                .bind(currency_code)
+                .fetch_one(&mut *tx)
+                .await?;
+                if !currency_exists {
+                    line.errors
+                        .push(format!("Ismeretlen pénznem: {currency_code}"));
+                    lines.push(line);
+                    continue;
+                }
+            }
+
+            let existing = sqlx::query_as::<_, (Uuid, String)>(
+                "SELECT id, status FROM products WHERE deleted_at IS NULL AND sku = $1 FOR UPDATE",
+            )
+            .bind(&row.sku)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some((_, status)) = &existing
+                && !ProductStatus::can_transition(status, &row.status)
+            {
+                line.errors.push(ProductStatus::TRANSITION_ERROR.to_owned());
+                lines.push(line);
+                continue;
+            }
+            let existing = existing.map(|(id, _)| id);
+            if existing.is_none()
+                && product_allowance
+                    .is_some_and(|allowance| i64::from(products_created) >= allowance)
+            {
+                line.errors.push(
+                    "Elérte az előfizetésében engedélyezett maximális termékszámot!".to_owned(),
+                );
+                lines.push(line);
+                continue;
+            }
+
+            let unit_of_measure_id = match sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT id
+                FROM units_of_measure
+                WHERE deleted_at IS NULL AND lower(unit_of_measure) = lower($1)
+                LIMIT 1
+                "#,
+            )
+            .bind(&row.unit_of_measure)
+            .fetch_optional(&mut *tx)
+            .await?
+            {
+                Some(id) => id,
+                None => {
+                    sqlx::query_scalar::<_, Uuid>(
+                        "INSERT INTO units_of_measure (unit_of_measure, created_by_id)
+                         VALUES ($1, $2) RETURNING id",
+                    )
+                    .bind(&row.unit_of_measure)
+                    .bind(sub)
+                    .fetch_one(&mut *tx)
+                    .await?
+                }
+            };
+
+            let mut savepoint = tx.begin().await?;
+            let written = match existing {
+                // NOTE: rows that are identical to the stored product are not counted as updates
+                Some(id) => sqlx::query_scalar::<_, Uuid>(
+                    r#"
+                    UPDATE products
+                    SET name = $2,
+                        description = $3,
+                        barcode = $4,
+                        unit_of_measure_id = $5,
+                        status = $6,
+                        price = $7,
+                        currency_code = $8,
+                        updated_at = NOW()
+                    WHERE id = $1
+                        AND (name, description, barcode, unit_of_measure_id, status, price, currency_code)
+                            IS DISTINCT FROM ($2, $3, $4, $5, $6, $7::NUMERIC, $8)
+                    RETURNING id
+                    "#,
+                )
+                .bind(id),
+                None => sqlx::query_scalar::<_, Uuid>(
+                    r#"
+                    INSERT INTO products (
+                        sku, name, description, barcode, unit_of_measure_id, status, price,
+                        currency_code, created_by_id
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    RETURNING id
+                    "#,
+                )
+                .bind(&row.sku),
+            }
+            .bind(&row.name)
+            .bind(&row.description)
+            .bind(&row.barcode)
+            .bind(unit_of_measure_id)
+            .bind(&row.status)
+            .bind(&row.price)
+            .bind(&row.currency_code);
+            let written = match existing {
+                Some(_) => written.fetch_optional(&mut *savepoint).await,
+                None => written.bind(sub).fetch_optional(&mut *savepoint).await,
+            };
+            match written {
+                Ok(id) => {
+                    savepoint.commit().await?;
+                    line.product_id = id.or(existing);
+                    line.product_created = existing.is_none();
+                    line.product_updated = existing.is_some() && id.is_some();
+                    if line.product_created {
+                        products_created += 1;
+                    } else if line.product_updated {
+                        products_updated += 1;
+                    }
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    let e = RepositoryError::from(e);
+                    if !e.is_unique_violation() {
+                        return Err(e);
+                    }
+                    line.errors
+                        .push("A vonalkód már egy másik terméknél szerepel!".to_owned());
+                }
+            }
+            lines.push(line);
+        }
+
+        let error_count = lines.iter().filter(|line| !line.errors.is_empty()).count();
+        let applied = apply && error_count == 0;
+        if applied {
+            sqlx::query(
+                r#"
+                INSERT INTO product_imports (
+                    id, row_count, products_created, products_updated, created_by_id
+                ) VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(import_id)
+            .bind(lines.len() as i32)
+            .bind(products_created)
+            .bind(products_updated)
+            .bind(sub)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+        } else {
+            tx.rollback().await?;
+        }
+
+        Ok(ProductImportReport {
+            import_id: applied.then_some(import_id),
+            applied,
+            row_count: lines.len(),
+            error_count,
+            products_created,
+            products_updated,
+            lines,
+        })
+    }
+
+    async fn get_imports(&self) -> RepositoryResult<Vec<ProductImport>> {
+        Ok(sqlx::query_as::<_, ProductImport>(
+            r#"
+            SELECT product_imports.id,
+                   product_imports.row_count,
+                   product_imports.products_created,
+                   product_imports.products_updated,
+                   product_imports.created_by_id,
+                   users.last_name || ' ' || users.first_name AS created_by,
+                   product_imports.created_at
+            FROM product_imports
+            JOIN users ON product_imports.created_by_id = users.id
+            ORDER BY product_imports.created_at DESC
+            LIMIT 100
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_ids(&self, ids: &[Uuid]) -> RepositoryResult<Vec<Product>> {
+        Ok(sqlx::query_as::<_, Product>(
+            "SELECT * FROM products WHERE deleted_at IS NULL AND id = ANY($1)",
+        )
+        .bind(ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<Product> {
+        Ok(sqlx::query_as::<_, Product>(
+            r#"
+            UPDATE products
+            SET status = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(self)
+        .await?)
+    }
+
+    // NOTE: products that already have a barcode are left alone, sequence values whose code is
+    // already taken (e.g. entered by hand) are skipped
+    async fn assign_barcodes(
+        &self,
+        product_ids: &[Uuid],
+        symbology: BarcodeSymbology,
+    ) -> RepositoryResult<Vec<Product>> {
+        let mut tx = self.begin().await?;
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM products
+            WHERE deleted_at IS NULL AND barcode IS NULL AND id = ANY($1)
+            ORDER BY name, id
+            FOR UPDATE
+            "#,
+        )
+        .bind(product_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut products = Vec::with_capacity(ids.len());
+        for id in ids {
+            loop {
+                let sequence =
+                    sqlx::query_scalar::<_, i64>("SELECT nextval('product_barcode_seq')")
+                        .fetch_one(&mut *tx)
+                        .await?;
+                let product = sqlx::query_as::<_, Product>(
+                    r#"
+                    UPDATE products
+                    SET barcode = $2, updated_at = NOW()
+                    WHERE id = $1
+                        AND NOT EXISTS (
+                            SELECT 1 FROM products WHERE deleted_at IS NULL AND barcode = $2
+                        )
+                    RETURNING *
+                    "#,
+                )
+                .bind(id)
+                .bind(symbology.code(sequence))
+                .fetch_optional(&mut *tx)
+                .await?;
+                if let Some(product) = product {
+                    products.push(product);
+                    break;
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(products)
+    }
+
+    async fn get_custom_fields(&self) -> RepositoryResult<Vec<ProductCustomField>> {
+        Ok(sqlx::query_as::<_, ProductCustomField>(
+            r#"
+            SELECT *
+            FROM product_custom_fields
+            WHERE deleted_at IS NULL
+            ORDER BY position, label
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_custom_field(
+        &self,
+        input: &ProductCustomFieldInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ProductCustomField> {
+        Ok(sqlx::query_as::<_, ProductCustomField>(
+            r#"
+            INSERT INTO product_custom_fields (key, label, field_type, options, is_required,
+                                               position, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.key)
+        .bind(&input.label)
+        .bind(&input.field_type)
+        .bind(&input.options)
+        .bind(input.is_required)
+        .bind(input.position)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update_custom_field(
+        &self,
+        input: &ProductCustomFieldInput,
+    ) -> RepositoryResult<ProductCustomField> {
+        let id = input
+            .id
+            .ok_or_else(|| RepositoryError::InvalidInput("id".to_string()))?;
+        // NOTE: the key and the type are fixed once created, the stored values depend on them
+        Ok(sqlx::query_as::<_, ProductCustomField>(
+            r#"
+            UPDATE product_custom_fields
+            SET label = $1,
+                options = $2,
+                is_required = $3,
+                position = $4
+            WHERE id = $5
+                AND key = $6
+                AND field_type = $7
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.label)
+        .bind(&input.options)
+        .bind(input.is_required)
+        .bind(input.position)
+        .bind(id)
+        .bind(&input.key)
+        .bind(&input.field_type)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_custom_field(&self, id: Uuid) -> RepositoryResult<ProductCustomField> {
+        let mut tx = self.begin().await?;
+        let field = sqlx::query_as::<_, ProductCustomField>(
+            r#"
+            UPDATE product_custom_fields
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE products
+            SET custom_fields = custom_fields - $1
+            WHERE custom_fields ? $1
+            "#,
+        )
+        .bind(&field.key)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(field)
+    }
+
+    async fn set_custom_fields(
+        &self,
+        product_id: Uuid,
+        values: &Map<String, JsonValue>,
+    ) -> RepositoryResult<Product> {
+        Ok(sqlx::query_as::<_, Product>(
+            r#"
+            UPDATE products
+            SET custom_fields = $1
+            WHERE id = $2
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(sqlx::types::Json(values))
+        .bind(product_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn find_duplicates(
+        &self,
+        query: &ProductDuplicateQuery,
+    ) -> RepositoryResult<Vec<ProductDuplicateCandidate>> {
+        Ok(sqlx::query_as::<_, ProductDuplicateCandidate>(
+            r#"
+            SELECT id,
+                   name,
+                   sku,
+                   barcode,
+                   status,
+                   similarity(normalize_product_name(name), normalize_product_name($1))
+                       AS name_similarity,
+                   COALESCE(sku = $2, false) AS same_sku,
+                   COALESCE(barcode = $3, false) AS same_barcode
+            FROM products
+            WHERE deleted_at IS NULL
+                AND ($4::UUID IS NULL OR id <> $4)
+                AND (sku = $2
+                    OR barcode = $3
+                    OR (normalize_product_name(name) % normalize_product_name($1)
+                        AND similarity(normalize_product_name(name), normalize_product_name($1)) >= $5))
+            ORDER BY same_sku DESC, same_barcode DESC, name_similarity DESC, name
+            LIMIT 10
+            "#,
+        )
+        .bind(&query.name)
+        .bind(&query.sku)
+        .bind(&query.barcode)
+        .bind(query.exclude_id)
+        .bind(DUPLICATE_NAME_SIMILARITY)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn merge(&self, input: &ProductMergeInput) -> RepositoryResult<Product> {
+        let mut tx = self.begin().await?;
+        let locked: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id
+            FROM products
+            WHERE id IN ($1, $2)
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.duplicate_id)
+        .bind(input.surviving_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+
+        // NOTE: where both products already have the same category, tag, supplier or bundle
+        // component, the surviving product's entry is kept
+        for statement in [
+            r#"
+            UPDATE products
+            SET parent_id = $2
+            WHERE parent_id = $1
+                AND deleted_at IS NULL
+            "#,
+            r#"
+            UPDATE product_category_connect
+            SET product_id = $2
+            WHERE product_id = $1
+                AND deleted_at IS NULL
+                AND product_category_id NOT IN (
+                    SELECT product_category_id
+                    FROM product_category_connect
+                    WHERE product_id = $2 AND deleted_at IS NULL
+                )
+            "#,
+            r#"
+            UPDATE taggings
+            SET taggable_id = $2
+            WHERE taggable_type = 'products'
+                AND taggable_id = $1
+                AND tag_id NOT IN (
+                    SELECT tag_id
+                    FROM taggings
+                    WHERE taggable_type = 'products' AND taggable_id = $2
+                )
+            "#,
+            "DELETE FROM taggings WHERE taggable_type = 'products' AND taggable_id = $1",
+            r#"
+            DELETE FROM product_suppliers
+            WHERE product_id = $1
+                AND supplier_id IN (SELECT supplier_id FROM product_suppliers WHERE product_id = $2)
+            "#,
+            r#"
+            UPDATE product_suppliers
+            SET is_preferred = false
+            WHERE product_id = $1
+                AND EXISTS(SELECT 1 FROM product_suppliers WHERE product_id = $2 AND is_preferred)
+            "#,
+            "UPDATE product_suppliers SET product_id = $2 WHERE product_id = $1",
+            r#"
+            DELETE FROM product_bundle_components
+            WHERE (bundle_id = $1 AND component_id IN (
+                    SELECT component_id FROM product_bundle_components WHERE bundle_id = $2
+                ))
+                OR (component_id = $1 AND bundle_id IN (
+                    SELECT bundle_id FROM product_bundle_components WHERE component_id = $2
+                ))
+            "#,
+            "UPDATE product_bundle_components SET bundle_id = $2 WHERE bundle_id = $1",
+            "UPDATE product_bundle_components SET component_id = $2 WHERE component_id = $1",
+            "DELETE FROM product_bundle_components WHERE bundle_id = component_id",
+            "UPDATE product_attachments SET product_id = $2 WHERE product_id = $1",
+            "UPDATE inventory SET product_id = $2 WHERE product_id = $1",
+            "UPDATE inventory_serials SET product_id = $2 WHERE product_id = $1",
+            "UPDATE stock_transfer_items SET product_id = $2 WHERE product_id = $1",
+            "UPDATE stock_snapshot_lines SET product_id = $2 WHERE product_id = $1",
+            "UPDATE worksheet_planned_materials SET product_id = $2 WHERE product_id = $1",
+            "UPDATE worksheet_template_materials SET product_id = $2 WHERE product_id = $1",
+            "UPDATE products SET deleted_at = NOW() WHERE id = $1",
+        ] {
+            sqlx::query(statement)
+                .bind(input.duplicate_id)
+                .bind(input.surviving_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let product = sqlx::query_as::<_, Product>(
+            r#"
+            UPDATE products
+            SET sku = COALESCE(products.sku, duplicate.sku),
+                barcode = COALESCE(products.barcode, duplicate.barcode),
+                custom_fields = duplicate.custom_fields || products.custom_fields
+            FROM products AS duplicate
+            WHERE products.id = $2
+                AND duplicate.id = $1
+            RETURNING products.*
+            "#,
+        )
+        .bind(input.duplicate_id)
+        .bind(input.surviving_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(product)
+    }
 }
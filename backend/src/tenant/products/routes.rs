@@ -20,7 +20,9 @@
 use super::ProductsModuleInterface;
 use super::handler;
 use crate::manager::auth::middleware::require_auth;
+use crate::tenant::products::dto::attachment::MAX_ATTACHMENT_SIZE;
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
 use axum::middleware::from_fn_with_state;
 use axum::routing::{delete, get, post, put};
 use std::sync::Arc;
@@ -36,6 +38,67 @@ pub fn routes<M: ProductsModuleInterface>(products_module: Arc<M>) -> Router {
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
+            .route("/duplicate", post(handler::duplicate::<M>))
+            .route("/duplicates", get(handler::duplicates::<M>))
+            .route("/merge", post(handler::merge::<M>))
+            .route("/set_status", put(handler::set_status::<M>))
+            .route("/dimensions", get(handler::get_dimensions::<M>))
+            .route("/set_dimensions", put(handler::set_dimensions::<M>))
+            .route("/stock_threshold", get(handler::get_stock_threshold::<M>))
+            .route(
+                "/set_stock_threshold",
+                put(handler::set_stock_threshold::<M>),
+            )
+            .route("/bundle_components", get(handler::bundle_components::<M>))
+            .route(
+                "/set_bundle_components",
+                put(handler::set_bundle_components::<M>),
+            )
+            .route(
+                "/bundle_availability",
+                get(handler::bundle_availability::<M>),
+            )
+            .route("/variants", get(handler::variants::<M>))
+            .route("/create_variant", post(handler::create_variant::<M>))
+            .route("/update_variant", put(handler::update_variant::<M>))
+            .route(
+                "/upload_attachment",
+                post(handler::upload_attachment::<M>)
+                    .layer(DefaultBodyLimit::max(MAX_ATTACHMENT_SIZE + 64 * 1024)),
+            )
+            .route("/attachments", get(handler::attachments::<M>))
+            .route("/attachment", get(handler::attachment::<M>))
+            .route(
+                "/attachment_thumbnail",
+                get(handler::attachment_thumbnail::<M>),
+            )
+            .route(
+                "/delete_attachment",
+                delete(handler::delete_attachment::<M>),
+            )
+            .route("/generate_barcodes", post(handler::generate_barcodes::<M>))
+            .route("/barcode", get(handler::barcode::<M>))
+            .route("/barcode_labels", post(handler::barcode_labels::<M>))
+            .route("/export", get(handler::export::<M>))
+            .route("/import", post(handler::import::<M>))
+            .route("/import_history", get(handler::import_history::<M>))
+            .route("/suppliers", get(handler::suppliers::<M>))
+            .route("/set_supplier", put(handler::set_supplier::<M>))
+            .route("/remove_supplier", delete(handler::remove_supplier::<M>))
+            .route("/custom_fields", get(handler::custom_fields::<M>))
+            .route(
+                "/create_custom_field",
+                post(handler::create_custom_field::<M>),
+            )
+            .route(
+                "/update_custom_field",
+                put(handler::update_custom_field::<M>),
+            )
+            .route(
+                "/delete_custom_field",
+                delete(handler::delete_custom_field::<M>),
+            )
+            .route("/set_custom_fields", put(handler::set_custom_fields::<M>))
             .route("/print", get(handler::print::<M>))
             .layer(from_fn_with_state(products_module.clone(), require_auth))
             .with_state(products_module),
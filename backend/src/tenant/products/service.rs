@@ -17,7 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
 use crate::common::model::SelectOption;
@@ -26,24 +26,54 @@ use crate::common::pdf::PdfGenerator;
 use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::common::storage::{FileStorage, StorageError};
 use crate::common::types::UuidVO;
-use crate::common::value_object::{ValueObjectError, ValueObjectRequired};
+use crate::common::utils::to_csv;
+use crate::common::value_object::{ValueObjectError, ValueObjectOptional, ValueObjectRequired};
+use crate::tenant::currencies::types::CurrencyCode;
 use crate::tenant::products::ProductsModuleInterface;
+use crate::tenant::products::dto::attachment::{AttachmentUpload, MAX_ATTACHMENT_SIZE, thumbnail};
+use crate::tenant::products::dto::barcode::{
+    BarcodeGenerateInput, BarcodeLabelPrint, BarcodeLabelsInput, MAX_BARCODE_PRODUCTS,
+    MAX_LABEL_COPIES, barcode_modules, barcode_png,
+};
+use crate::tenant::products::dto::bundle::ProductBundleInput;
+use crate::tenant::products::dto::custom_field::{
+    ProductCustomFieldInput, ProductCustomFieldValuesInput, merge_custom_field_values,
+};
+use crate::tenant::products::dto::dimensions::ProductDimensionsInput;
+use crate::tenant::products::dto::duplicate::{ProductDuplicateQuery, ProductMergeInput};
+use crate::tenant::products::dto::import::{ProductImportQuery, ProductImportRow};
 use crate::tenant::products::dto::print::ProductsResolvedPrint;
+use crate::tenant::products::dto::status::ProductStatusInput;
+use crate::tenant::products::dto::stock_threshold::ProductStockThresholdInput;
 use crate::tenant::products::dto::user_input::ProductUserInput;
-use crate::tenant::products::model::{Product, ProductResolved};
-use crate::tenant::products::types::product::{ProductFilterBy, ProductOrderBy};
+use crate::tenant::products::dto::variant::ProductVariantInput;
+use crate::tenant::products::model::{
+    Product, ProductAttachment, ProductAttachmentLink, ProductBundleAvailability,
+    ProductBundleComponent, ProductCustomField, ProductDimensions, ProductDuplicateCandidate,
+    ProductGroup, ProductImport, ProductImportReport, ProductResolved, ProductStockThreshold,
+    ProductVariant,
+};
+use crate::tenant::products::repository::ProductsRepository;
+use crate::tenant::products::types::product::{
+    ProductBarcode, ProductFilterBy, ProductOrderBy, ProductSku, ProductStatus,
+};
+use crate::tenant::suppliers::dto::ProductSupplierInput;
+use crate::tenant::suppliers::model::ProductSupplier;
 use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
 use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
-use tracing::Level;
+use tracing::{Level, error};
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -60,6 +90,9 @@ pub enum ProductsServiceError {
     #[error("A lista nem létezik")]
     InvalidSelectList,
 
+    #[error("A megadott cikkszám vagy vonalkód már egy másik terméknél szerepel")]
+    CodeExists,
+
     #[error("Hiba történt az adatok feldolgozása során: {0}")]
     UnprocessableEntry(&'static str),
 
@@ -74,6 +107,27 @@ pub enum ProductsServiceError {
 
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
+
+    #[error("Elérte az előfizetésében engedélyezett maximális termékszámot!")]
+    QuotaExceeded,
+
+    #[error("Ilyen tulajdonságú változat, cikkszám vagy vonalkód már létezik")]
+    VariantExists,
+
+    #[error("Ilyen kulcsú egyedi mező már létezik")]
+    CustomFieldExists,
+
+    #[error("Hasonló termék már szerepel a törzsben")]
+    PossibleDuplicates(Vec<ProductDuplicateCandidate>),
+
+    #[error("Elérte az előfizetésében engedélyezett maximális tárhelyet!")]
+    StorageQuotaExceeded,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
 }
 
 impl From<ServiceError> for ProductsServiceError {
@@ -94,6 +148,31 @@ impl From<ProductsServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            ProductsServiceError::QuotaExceeded | ProductsServiceError::StorageQuotaExceeded => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::PAYMENT_REQUIRED,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            ProductsServiceError::PossibleDuplicates(ref candidates) => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string(), "candidates": candidates}),
+            ),
+            ProductsServiceError::CodeExists
+            | ProductsServiceError::VariantExists
+            | ProductsServiceError::CustomFieldExists => Self::new(
+                Level::DEBUG,
+                StatusCode::CONFLICT,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
             ProductsServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -138,6 +217,252 @@ impl FromStr for ProductsSelectLists {
     }
 }
 
+fn validate_filter(
+    get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+) -> ProductsServiceResult<()> {
+    if get_query.filtering().filter_by() == Some("category_id")
+        && get_query
+            .filtering()
+            .value_unchecked()
+            .is_some_and(|value| Uuid::parse_str(value).is_err())
+    {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Hibás kategória azonosító!",
+        ));
+    }
+    Ok(())
+}
+
+async fn validate_custom_field_filter(
+    repo: &(dyn ProductsRepository + Send + Sync),
+    get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+) -> ProductsServiceResult<()> {
+    if let Some(key) = get_query
+        .filtering()
+        .filter_by()
+        .and_then(|filter_by| filter_by.strip_prefix("custom_fields."))
+        && !repo
+            .get_custom_fields()
+            .await?
+            .iter()
+            .any(|field| field.key == key)
+    {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Ismeretlen egyedi mező!",
+        ));
+    }
+    Ok(())
+}
+
+fn map_custom_field_error(e: RepositoryError) -> ProductsServiceError {
+    if e.is_unique_violation() {
+        ProductsServiceError::CustomFieldExists
+    } else {
+        e.into()
+    }
+}
+
+fn map_variant_error(e: RepositoryError) -> ProductsServiceError {
+    if e.is_unique_violation() {
+        ProductsServiceError::VariantExists
+    } else {
+        e.into()
+    }
+}
+
+fn validate_variant(payload: &ProductVariantInput) -> ProductsServiceResult<ProductVariantInput> {
+    let mut attributes = BTreeMap::new();
+    for (key, value) in &payload.attributes {
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty()
+            || value.is_empty()
+            || key.chars().count() > 50
+            || value.chars().count() > 100
+        {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A változat tulajdonságainak neve és értéke nem lehet üres és túl hosszú!",
+            ));
+        }
+        attributes.insert(key.to_string(), value.to_string());
+    }
+    if attributes.is_empty() {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Legalább egy tulajdonság (pl. méret, szín) megadása kötelező!",
+        ));
+    }
+    let sku = payload
+        .sku
+        .as_deref()
+        .unwrap_or_default()
+        .parse::<ValueObjectOptional<ProductSku>>()
+        .map_err(|_| ProductsServiceError::UnprocessableEntry(ProductSku::VALIDATION_ERROR))?;
+    let barcode = payload
+        .barcode
+        .as_deref()
+        .unwrap_or_default()
+        .parse::<ValueObjectOptional<ProductBarcode>>()
+        .map_err(|_| ProductsServiceError::UnprocessableEntry(ProductBarcode::VALIDATION_ERROR))?;
+    let status = payload
+        .status
+        .parse::<ValueObjectRequired<ProductStatus>>()
+        .map_err(|_| ProductsServiceError::UnprocessableEntry(ProductStatus::VALIDATION_ERROR))?;
+    if payload
+        .price
+        .as_ref()
+        .is_some_and(|price| *price < BigDecimal::zero())
+    {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Az ár nem lehet negatív!",
+        ));
+    }
+    let currency_code = match payload.currency_code.as_deref().map(str::trim) {
+        Some(currency_code) if !currency_code.is_empty() => Some(
+            currency_code
+                .to_uppercase()
+                .parse::<ValueObjectRequired<CurrencyCode>>()
+                .map_err(|_| {
+                    ProductsServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+                })?
+                .as_str()?
+                .to_string(),
+        ),
+        _ => None,
+    };
+    if payload.price.is_some() && currency_code.is_none() {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Ár megadása esetén a pénznem megadása kötelező!",
+        ));
+    }
+    Ok(ProductVariantInput {
+        attributes,
+        sku: sku.as_str().map(str::to_string),
+        barcode: barcode.as_str().map(str::to_string),
+        price: payload.price.clone(),
+        currency_code,
+        status: status.as_str()?.to_string(),
+        ..payload.clone()
+    })
+}
+
+fn validate_bundle(payload: &ProductBundleInput) -> ProductsServiceResult<()> {
+    let mut component_ids = HashSet::new();
+    for component in &payload.components {
+        if component.component_id == payload.bundle_id {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A csomag nem tartalmazhatja saját magát!",
+            ));
+        }
+        if !component_ids.insert(component.component_id) {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "Egy termék csak egyszer szerepelhet a csomagban!",
+            ));
+        }
+        if component.quantity <= BigDecimal::zero()
+            || component.quantity.fractional_digit_count() > 2
+        {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A mennyiségnek pozitív, legfeljebb két tizedesjegyű számnak kell lennie!",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_transition(from: &str, to: &str) -> ProductsServiceResult<()> {
+    if !ProductStatus::can_transition(from, to) {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            ProductStatus::TRANSITION_ERROR,
+        ));
+    }
+    Ok(())
+}
+
+fn validate_barcode_products(product_ids: &[Uuid]) -> ProductsServiceResult<()> {
+    if product_ids.is_empty() || product_ids.len() > MAX_BARCODE_PRODUCTS {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "Legalább egy, legfeljebb 500 terméket kell kiválasztani!",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_product_supplier(
+    payload: &ProductSupplierInput,
+) -> ProductsServiceResult<ProductSupplierInput> {
+    if payload
+        .purchase_price
+        .as_ref()
+        .is_some_and(|price| *price < BigDecimal::zero())
+    {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "A beszerzési ár nem lehet negatív!",
+        ));
+    }
+    if payload
+        .lead_time_days
+        .is_some_and(|days| !(0..=365).contains(&days))
+    {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "A szállítási időnek 0 és 365 nap között kell lennie!",
+        ));
+    }
+    let currency_code = match payload.currency_code.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(code) => Some(
+            code.parse::<ValueObjectRequired<CurrencyCode>>()
+                .map_err(|_| ProductsServiceError::UnprocessableEntry("Hibás pénznem!"))?
+                .as_str()
+                .map_err(|_| ProductsServiceError::UnprocessableEntry("Hibás pénznem!"))?
+                .to_string(),
+        ),
+    };
+    if payload.purchase_price.is_some() && currency_code.is_none() {
+        return Err(ProductsServiceError::UnprocessableEntry(
+            "A beszerzési árhoz pénznem megadása kötelező!",
+        ));
+    }
+    let supplier_sku = match payload.supplier_sku.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(sku) if sku.chars().count() > 100 => {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A beszállítói cikkszám legfeljebb 100 karakter lehet!",
+            ));
+        }
+        Some(sku) => Some(sku.to_string()),
+    };
+    Ok(ProductSupplierInput {
+        supplier_sku,
+        currency_code,
+        ..payload.clone()
+    })
+}
+
+async fn attach_links(
+    repo: &(dyn ProductsRepository + Send + Sync),
+    products: &mut [ProductResolved],
+) -> ProductsServiceResult<()> {
+    let product_ids: Vec<Uuid> = products.iter().map(|product| product.id).collect();
+    let mut attachments: HashMap<Uuid, Vec<ProductAttachmentLink>> = HashMap::new();
+    for attachment in repo.get_attachments(&product_ids).await? {
+        attachments
+            .entry(attachment.product_id)
+            .or_default()
+            .push(attachment.into());
+    }
+    for product in products {
+        product.attachments = attachments.remove(&product.id).unwrap_or_default();
+    }
+    Ok(())
+}
+
+async fn remove_files(storage: &(dyn FileStorage + Send + Sync), attachment: &ProductAttachment) {
+    for key in std::iter::once(&attachment.storage_key).chain(&attachment.thumbnail_key) {
+        if let Err(e) = storage.delete(key).await {
+            error!("Could not remove attachment file {}: {}", key, e);
+        }
+    }
+}
+
 pub trait ProductService {
     fn insert(
         &self,
@@ -157,10 +482,140 @@ pub trait ProductService {
         payload: &ProductUserInput,
     ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = ProductsServiceResult<()>> + Send;
+    fn duplicate(
+        &self,
+        payload: &DuplicateParams,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
+    fn get_dimensions(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<ProductDimensions>> + Send;
+    fn set_dimensions(
+        &self,
+        payload: &ProductDimensionsInput,
+    ) -> impl Future<Output = ProductsServiceResult<ProductDimensions>> + Send;
+    fn set_status(
+        &self,
+        payload: &ProductStatusInput,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
+    fn get_stock_threshold(
+        &self,
+        payload: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<ProductStockThreshold>> + Send;
+    fn set_stock_threshold(
+        &self,
+        payload: &ProductStockThresholdInput,
+    ) -> impl Future<Output = ProductsServiceResult<ProductStockThreshold>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
     ) -> impl Future<Output = ProductsServiceResult<(PaginatorMeta, Vec<ProductResolved>)>> + Send;
+    fn get_paged_grouped(
+        &self,
+        get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
+    ) -> impl Future<Output = ProductsServiceResult<(PaginatorMeta, Vec<ProductGroup>)>> + Send;
+    fn get_variants(
+        &self,
+        parent_id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductVariant>>> + Send;
+    fn create_variant(
+        &self,
+        payload: &ProductVariantInput,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
+    fn update_variant(
+        &self,
+        payload: &ProductVariantInput,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
+    fn get_bundle_components(
+        &self,
+        bundle_id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductBundleComponent>>> + Send;
+    fn set_bundle_components(
+        &self,
+        payload: &ProductBundleInput,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductBundleComponent>>> + Send;
+    fn get_bundle_availability(
+        &self,
+        bundle_id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductBundleAvailability>>> + Send;
+    fn upload_attachment(
+        &self,
+        payload: AttachmentUpload,
+    ) -> impl Future<Output = ProductsServiceResult<ProductAttachmentLink>> + Send;
+    fn get_attachments(
+        &self,
+        product_id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductAttachmentLink>>> + Send;
+    fn get_attachment_file(
+        &self,
+        id: Uuid,
+        thumbnail: bool,
+    ) -> impl Future<Output = ProductsServiceResult<(ProductAttachment, Vec<u8>)>> + Send;
+    fn delete_attachment(&self, id: Uuid)
+    -> impl Future<Output = ProductsServiceResult<()>> + Send;
+    fn generate_barcodes(
+        &self,
+        payload: &BarcodeGenerateInput,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<Product>>> + Send;
+    fn get_barcode_png(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<(String, Vec<u8>)>> + Send;
+    fn print_barcode_labels(
+        &self,
+        payload: &BarcodeLabelsInput,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<u8>>> + Send;
+    fn export(&self) -> impl Future<Output = ProductsServiceResult<Vec<u8>>> + Send;
+    fn import(
+        &self,
+        payload: &ProductImportQuery,
+        data: &[u8],
+    ) -> impl Future<Output = ProductsServiceResult<ProductImportReport>> + Send;
+    fn get_import_history(
+        &self,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductImport>>> + Send;
+    fn get_suppliers(
+        &self,
+        product_id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductSupplier>>> + Send;
+    fn set_supplier(
+        &self,
+        payload: &ProductSupplierInput,
+    ) -> impl Future<Output = ProductsServiceResult<ProductSupplier>> + Send;
+    fn remove_supplier(&self, id: Uuid) -> impl Future<Output = ProductsServiceResult<()>> + Send;
+    fn check_duplicates(
+        &self,
+        payload: &ProductUserInput,
+    ) -> impl Future<Output = ProductsServiceResult<()>> + Send;
+    fn get_duplicates(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductDuplicateCandidate>>> + Send;
+    fn merge(
+        &self,
+        payload: &ProductMergeInput,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
+    fn get_custom_fields(
+        &self,
+    ) -> impl Future<Output = ProductsServiceResult<Vec<ProductCustomField>>> + Send;
+    fn create_custom_field(
+        &self,
+        payload: &ProductCustomFieldInput,
+    ) -> impl Future<Output = ProductsServiceResult<ProductCustomField>> + Send;
+    fn update_custom_field(
+        &self,
+        payload: &ProductCustomFieldInput,
+    ) -> impl Future<Output = ProductsServiceResult<ProductCustomField>> + Send;
+    fn delete_custom_field(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ProductsServiceResult<()>> + Send;
+    fn set_custom_fields(
+        &self,
+        payload: &ProductCustomFieldValuesInput,
+    ) -> impl Future<Output = ProductsServiceResult<Product>> + Send;
     fn print(
         &self,
         payload: &[ProductsResolvedPrint],
@@ -174,6 +629,15 @@ where
     T: ProductsModuleInterface,
 {
     async fn insert(&self, payload: &mut ProductUserInput) -> ProductsServiceResult<Product> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ProductsServiceError::Unauthorized)?;
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
         if let Some(new_unit_of_measure) = &payload.new_unit_of_measure {
             payload.unit_of_measure_id = self
                 .module()
@@ -190,15 +654,22 @@ where
                 .map(Some)
                 .map_err(|_| ProductsServiceError::InvalidState)?;
         }
-        Ok(self
-            .module()
+        self.module()
             .products_repo(
                 self.claims()?
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .insert(payload, self.claims()?.sub())
-            .await?)
+            .insert(payload, self.claims()?.sub(), limits)
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    ProductsServiceError::CodeExists
+                } else {
+                    e.into()
+                }
+            })?
+            .ok_or(ProductsServiceError::QuotaExceeded)
     }
 
     async fn get_select_list_items(
@@ -219,6 +690,17 @@ where
     }
 
     async fn get_resolved(&self, payload: Uuid) -> ProductsServiceResult<ProductResolved> {
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        let mut product = repo.get_resolved_by_id(payload).await?;
+        attach_links(&*repo, std::slice::from_mut(&mut product)).await?;
+        Ok(product)
+    }
+
+    async fn get(&self, payload: Uuid) -> ProductsServiceResult<Product> {
         Ok(self
             .module()
             .products_repo(
@@ -226,11 +708,32 @@ where
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .get_resolved_by_id(payload)
+            .get_by_id(payload)
             .await?)
     }
 
-    async fn get(&self, payload: Uuid) -> ProductsServiceResult<Product> {
+    async fn update(&self, payload: &ProductUserInput) -> ProductsServiceResult<Product> {
+        let id = payload
+            .id
+            .as_uuid()
+            .ok_or(ProductsServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        validate_transition(&repo.get_by_id(id).await?.status, payload.status.as_str()?)?;
+        repo.update(payload.clone()).await.map_err(|e| {
+            if e.is_unique_violation() {
+                ProductsServiceError::CodeExists
+            } else {
+                e.into()
+            }
+        })
+    }
+    async fn delete(&self, payload: Uuid) -> ProductsServiceResult<()> {
         Ok(self
             .module()
             .products_repo(
@@ -238,14 +741,52 @@ where
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .get_by_id(payload)
+            .delete_by_id(payload)
             .await?)
     }
 
-    async fn update(&self, payload: &ProductUserInput) -> ProductsServiceResult<Product> {
-        if !payload.id.is_present() {
+    async fn duplicate(&self, payload: &DuplicateParams) -> ProductsServiceResult<Product> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ProductsServiceError::Unauthorized)?;
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        self.module()
+            .products_repo(tenant_id)?
+            .duplicate(payload, self.claims()?.sub(), limits)
+            .await?
+            .ok_or(ProductsServiceError::QuotaExceeded)
+    }
+    async fn get_dimensions(&self, payload: Uuid) -> ProductsServiceResult<ProductDimensions> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_dimensions(payload)
+            .await?)
+    }
+    async fn set_dimensions(
+        &self,
+        payload: &ProductDimensionsInput,
+    ) -> ProductsServiceResult<ProductDimensions> {
+        if payload.weight_g.is_some_and(|v| v < 0) {
             return Err(ProductsServiceError::UnprocessableEntry(
-                "Az azonosító megadása kötelező!",
+                "A tömeg nem lehet negatív!",
+            ));
+        }
+        if [payload.length_mm, payload.width_mm, payload.height_mm]
+            .iter()
+            .any(|v| v.is_some_and(|v| v <= 0))
+        {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A méreteknek pozitív számnak kell lenniük!",
             ));
         }
         Ok(self
@@ -255,10 +796,34 @@ where
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .update(payload.clone())
+            .set_dimensions(payload)
             .await?)
     }
-    async fn delete(&self, payload: Uuid) -> ProductsServiceResult<()> {
+    async fn set_status(&self, payload: &ProductStatusInput) -> ProductsServiceResult<Product> {
+        let status = payload
+            .status
+            .parse::<ValueObjectRequired<ProductStatus>>()
+            .map_err(|_| {
+                ProductsServiceError::UnprocessableEntry(ProductStatus::VALIDATION_ERROR)
+            })?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        validate_transition(
+            &repo.get_by_id(payload.product_id).await?.status,
+            status.as_str()?,
+        )?;
+        Ok(repo
+            .set_status(payload.product_id, status.as_str()?)
+            .await?)
+    }
+
+    async fn get_stock_threshold(
+        &self,
+        payload: Uuid,
+    ) -> ProductsServiceResult<ProductStockThreshold> {
         Ok(self
             .module()
             .products_repo(
@@ -266,13 +831,143 @@ where
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .delete_by_id(payload)
+            .get_stock_threshold(payload)
+            .await?)
+    }
+    async fn set_stock_threshold(
+        &self,
+        payload: &ProductStockThresholdInput,
+    ) -> ProductsServiceResult<ProductStockThreshold> {
+        if payload.minimum_stock.as_ref().is_some_and(|v| *v < 0) {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A minimális készlet nem lehet negatív!",
+            ));
+        }
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .set_stock_threshold(payload)
             .await?)
     }
     async fn get_paged(
         &self,
         get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
     ) -> ProductsServiceResult<(PaginatorMeta, Vec<ProductResolved>)> {
+        validate_filter(get_query)?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        validate_custom_field_filter(&*repo, get_query).await?;
+        let (meta, mut products) = repo.get_paged(get_query, tags).await?;
+        attach_links(&*repo, &mut products).await?;
+        Ok((meta, products))
+    }
+
+    async fn get_paged_grouped(
+        &self,
+        get_query: &ResourceQuery<ProductOrderBy, ProductFilterBy>,
+        tags: Option<Vec<Uuid>>,
+    ) -> ProductsServiceResult<(PaginatorMeta, Vec<ProductGroup>)> {
+        validate_filter(get_query)?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        validate_custom_field_filter(&*repo, get_query).await?;
+        let (meta, mut products) = repo.get_paged_templates(get_query, tags).await?;
+        attach_links(&*repo, &mut products).await?;
+        let parent_ids: Vec<Uuid> = products.iter().map(|product| product.id).collect();
+        let mut variants: HashMap<Uuid, Vec<ProductVariant>> = HashMap::new();
+        for variant in repo.get_variants(&parent_ids).await? {
+            variants.entry(variant.parent_id).or_default().push(variant);
+        }
+        Ok((
+            meta,
+            products
+                .into_iter()
+                .map(|product| ProductGroup {
+                    variants: variants.remove(&product.id).unwrap_or_default(),
+                    product,
+                })
+                .collect(),
+        ))
+    }
+
+    async fn get_variants(&self, parent_id: Uuid) -> ProductsServiceResult<Vec<ProductVariant>> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_variants(&[parent_id])
+            .await?)
+    }
+
+    async fn create_variant(
+        &self,
+        payload: &ProductVariantInput,
+    ) -> ProductsServiceResult<Product> {
+        let input = validate_variant(payload)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ProductsServiceError::Unauthorized)?;
+        let repo = self.module().products_repo(tenant_id)?;
+        let template = repo.get_by_id(input.parent_id).await?;
+        if template.parent_id.is_some() {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "Változatnak nem lehet további változata!",
+            ));
+        }
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        repo.insert_variant(&template, &input, self.claims()?.sub(), limits)
+            .await
+            .map_err(map_variant_error)?
+            .ok_or(ProductsServiceError::QuotaExceeded)
+    }
+
+    async fn update_variant(
+        &self,
+        payload: &ProductVariantInput,
+    ) -> ProductsServiceResult<Product> {
+        if payload.id.is_none() {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ));
+        }
+        let input = validate_variant(payload)?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        if let Some(id) = input.id {
+            validate_transition(&repo.get_by_id(id).await?.status, &input.status)?;
+        }
+        let template = repo.get_by_id(input.parent_id).await?;
+        repo.update_variant(&template, &input)
+            .await
+            .map_err(map_variant_error)
+    }
+
+    async fn get_bundle_components(
+        &self,
+        bundle_id: Uuid,
+    ) -> ProductsServiceResult<Vec<ProductBundleComponent>> {
         Ok(self
             .module()
             .products_repo(
@@ -280,10 +975,542 @@ where
                     .active_tenant()
                     .ok_or(ProductsServiceError::Unauthorized)?,
             )?
-            .get_paged(get_query)
+            .get_bundle_components(bundle_id)
             .await?)
     }
 
+    async fn set_bundle_components(
+        &self,
+        payload: &ProductBundleInput,
+    ) -> ProductsServiceResult<Vec<ProductBundleComponent>> {
+        validate_bundle(payload)?;
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        repo.get_by_id(payload.bundle_id).await?;
+        // NOTE: bundles are kept one level deep so that exploding them never recurses
+        if !payload.components.is_empty() {
+            if repo.is_bundle_component(payload.bundle_id).await? {
+                return Err(ProductsServiceError::UnprocessableEntry(
+                    "A termék egy másik csomag része, ezért nem lehet csomag!",
+                ));
+            }
+            let component_ids: Vec<Uuid> = payload
+                .components
+                .iter()
+                .map(|component| component.component_id)
+                .collect();
+            if !repo.get_bundle_ids(&component_ids).await?.is_empty() {
+                return Err(ProductsServiceError::UnprocessableEntry(
+                    "A csomag nem tartalmazhat másik csomagot!",
+                ));
+            }
+        }
+        repo.set_bundle_components(payload, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    ProductsServiceError::UnprocessableEntry("A megadott termék nem létezik!")
+                } else {
+                    e.into()
+                }
+            })?;
+        Ok(repo.get_bundle_components(payload.bundle_id).await?)
+    }
+
+    async fn get_bundle_availability(
+        &self,
+        bundle_id: Uuid,
+    ) -> ProductsServiceResult<Vec<ProductBundleAvailability>> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_bundle_availability(bundle_id)
+            .await?)
+    }
+
+    async fn upload_attachment(
+        &self,
+        payload: AttachmentUpload,
+    ) -> ProductsServiceResult<ProductAttachmentLink> {
+        if payload.data.is_empty() {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A feltöltött fájl üres!",
+            ));
+        }
+        if payload.data.len() > MAX_ATTACHMENT_SIZE {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A fájl mérete legfeljebb 20 MB lehet!",
+            ));
+        }
+        let (kind, content_type) = payload.kind().ok_or(ProductsServiceError::UnprocessableEntry(
+            "Csak JPEG, PNG, WebP képek, illetve PDF, szöveges és Office dokumentumok tölthetők fel!",
+        ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ProductsServiceError::Unauthorized)?;
+        let repo = self.module().products_repo(tenant_id)?;
+        repo.get_by_id(payload.product_id).await?;
+        let size_bytes =
+            i64::try_from(payload.data.len()).map_err(|_| ProductsServiceError::InvalidState)?;
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        let thumbnail = if kind == "image" {
+            let data = payload.data.clone();
+            Some(
+                tokio::task::spawn_blocking(move || thumbnail(&data))
+                    .await
+                    .map_err(|_| ProductsServiceError::InvalidState)?
+                    .map_err(|_| {
+                        ProductsServiceError::UnprocessableEntry("A kép nem dolgozható fel!")
+                    })?,
+            )
+        } else {
+            None
+        };
+        let id = Uuid::new_v4();
+        let storage_key = format!("{tenant_id}/products/{}/{id}", payload.product_id);
+        let attachment = ProductAttachment {
+            id,
+            product_id: payload.product_id,
+            kind: kind.to_string(),
+            file_name: payload.sanitized_file_name(),
+            content_type,
+            size_bytes,
+            thumbnail_key: thumbnail
+                .as_ref()
+                .map(|_| format!("{storage_key}_thumb.jpg")),
+            storage_key,
+            sort_order: 0,
+            created_by_id: self.claims()?.sub(),
+            created_at: Utc::now(),
+            deleted_at: None,
+        };
+        let storage = self.module().file_storage();
+        storage
+            .put(
+                &attachment.storage_key,
+                &attachment.content_type,
+                payload.data,
+            )
+            .await?;
+        if let (Some(key), Some(data)) = (&attachment.thumbnail_key, thumbnail)
+            && let Err(e) = storage.put(key, "image/jpeg", data).await
+        {
+            remove_files(&*storage, &attachment).await;
+            return Err(e.into());
+        }
+        match repo.insert_attachment(&attachment, limits).await {
+            Ok(Some(attachment)) => Ok(attachment.into()),
+            Ok(None) => {
+                remove_files(&*storage, &attachment).await;
+                Err(ProductsServiceError::StorageQuotaExceeded)
+            }
+            Err(e) => {
+                remove_files(&*storage, &attachment).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn get_attachments(
+        &self,
+        product_id: Uuid,
+    ) -> ProductsServiceResult<Vec<ProductAttachmentLink>> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_attachments(&[product_id])
+            .await?
+            .into_iter()
+            .map(ProductAttachmentLink::from)
+            .collect())
+    }
+
+    async fn get_attachment_file(
+        &self,
+        id: Uuid,
+        thumbnail: bool,
+    ) -> ProductsServiceResult<(ProductAttachment, Vec<u8>)> {
+        let attachment = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_attachment(id)
+            .await?;
+        let key = match (thumbnail, &attachment.thumbnail_key) {
+            (false, _) => &attachment.storage_key,
+            (true, Some(thumbnail_key)) => thumbnail_key,
+            (true, None) => {
+                return Err(RepositoryError::Database(sqlx::Error::RowNotFound).into());
+            }
+        };
+        let data = self.module().file_storage().get(key).await?;
+        Ok((attachment, data))
+    }
+
+    async fn delete_attachment(&self, id: Uuid) -> ProductsServiceResult<()> {
+        let attachment = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .delete_attachment(id)
+            .await?;
+        remove_files(&*self.module().file_storage(), &attachment).await;
+        Ok(())
+    }
+
+    async fn generate_barcodes(
+        &self,
+        payload: &BarcodeGenerateInput,
+    ) -> ProductsServiceResult<Vec<Product>> {
+        validate_barcode_products(&payload.product_ids)?;
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .assign_barcodes(&payload.product_ids, payload.symbology)
+            .await?)
+    }
+
+    async fn get_barcode_png(&self, id: Uuid) -> ProductsServiceResult<(String, Vec<u8>)> {
+        let product = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?;
+        let barcode = product
+            .barcode
+            .ok_or(ProductsServiceError::UnprocessableEntry(
+                "A terméknek nincs vonalkódja!",
+            ))?;
+        let modules = barcode_modules(&barcode).ok_or(ProductsServiceError::InvalidState)?;
+        let png = barcode_png(&modules).map_err(|_| ProductsServiceError::InvalidState)?;
+        Ok((barcode, png))
+    }
+
+    async fn print_barcode_labels(
+        &self,
+        payload: &BarcodeLabelsInput,
+    ) -> ProductsServiceResult<Vec<u8>> {
+        validate_barcode_products(&payload.product_ids)?;
+        if !(1..=MAX_LABEL_COPIES).contains(&payload.copies) {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "A példányszámnak 1 és 100 között kell lennie!",
+            ));
+        }
+        let products: HashMap<Uuid, Product> = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_by_ids(&payload.product_ids)
+            .await?
+            .into_iter()
+            .map(|product| (product.id, product))
+            .collect();
+        let mut labels = Vec::with_capacity(payload.product_ids.len());
+        for id in &payload.product_ids {
+            let product = products
+                .get(id)
+                .ok_or_else(|| RepositoryError::Database(sqlx::Error::RowNotFound))?;
+            let barcode =
+                product
+                    .barcode
+                    .clone()
+                    .ok_or(ProductsServiceError::UnprocessableEntry(
+                        "Vonalkód nélküli termékhez nem nyomtatható címke!",
+                    ))?;
+            let label = BarcodeLabelPrint {
+                name: product.name.clone(),
+                sku: product.sku.clone(),
+                modules: barcode_modules(&barcode).ok_or(ProductsServiceError::InvalidState)?,
+                barcode,
+            };
+            labels.extend(std::iter::repeat_n(label, payload.copies as usize));
+        }
+        Ok(PdfGenerator::gen_pdf_temporary(
+            &PdfTemplates::ProductBarcodeLabels,
+            labels,
+        )?)
+    }
+
+    async fn export(&self) -> ProductsServiceResult<Vec<u8>> {
+        let rows = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_export_rows()
+            .await?;
+        Ok(to_csv(&rows)?)
+    }
+
+    async fn import(
+        &self,
+        payload: &ProductImportQuery,
+        data: &[u8],
+    ) -> ProductsServiceResult<ProductImportReport> {
+        let rows =
+            ProductImportRow::parse_csv(data).map_err(ProductsServiceError::UnprocessableEntry)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ProductsServiceError::Unauthorized)?;
+        let limits = self
+            .module()
+            .tenant_limits_repo()
+            .get_by_tenant_id(tenant_id)
+            .await?;
+        Ok(self
+            .module()
+            .products_repo(tenant_id)?
+            .import(&rows, self.claims()?.sub(), limits, !payload.dry_run)
+            .await?)
+    }
+
+    async fn get_import_history(&self) -> ProductsServiceResult<Vec<ProductImport>> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_imports()
+            .await?)
+    }
+
+    async fn get_suppliers(&self, product_id: Uuid) -> ProductsServiceResult<Vec<ProductSupplier>> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_product_suppliers(product_id)
+            .await?)
+    }
+
+    async fn set_supplier(
+        &self,
+        payload: &ProductSupplierInput,
+    ) -> ProductsServiceResult<ProductSupplier> {
+        let input = validate_product_supplier(payload)?;
+        self.module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .set_product_supplier(&input, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    ProductsServiceError::UnprocessableEntry(
+                        "A megadott termék, beszállító vagy pénznem nem létezik!",
+                    )
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn remove_supplier(&self, id: Uuid) -> ProductsServiceResult<()> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .delete_product_supplier(id)
+            .await?)
+    }
+
+    async fn check_duplicates(&self, payload: &ProductUserInput) -> ProductsServiceResult<()> {
+        let candidates = self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .find_duplicates(&ProductDuplicateQuery {
+                name: payload.name.as_str()?.to_string(),
+                sku: payload.sku.as_str().map(str::to_string),
+                barcode: payload.barcode.as_str().map(str::to_string),
+                exclude_id: payload.id.as_uuid(),
+            })
+            .await?;
+        if candidates.is_empty() {
+            Ok(())
+        } else {
+            Err(ProductsServiceError::PossibleDuplicates(candidates))
+        }
+    }
+
+    async fn get_duplicates(
+        &self,
+        id: Uuid,
+    ) -> ProductsServiceResult<Vec<ProductDuplicateCandidate>> {
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        let product = repo.get_by_id(id).await?;
+        Ok(repo
+            .find_duplicates(&ProductDuplicateQuery {
+                name: product.name,
+                sku: product.sku,
+                barcode: product.barcode,
+                exclude_id: Some(product.id),
+            })
+            .await?)
+    }
+
+    async fn merge(&self, payload: &ProductMergeInput) -> ProductsServiceResult<Product> {
+        if payload.duplicate_id == payload.surviving_id {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "Egy termék nem vonható össze önmagával!",
+            ));
+        }
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        let surviving = repo.get_by_id(payload.surviving_id).await?;
+        if surviving.parent_id.is_some()
+            && !repo.get_variants(&[payload.duplicate_id]).await?.is_empty()
+        {
+            return Err(ProductsServiceError::UnprocessableEntry(
+                "Változatokkal rendelkező termék csak alaptermékbe vonható össze!",
+            ));
+        }
+        repo.merge(payload).await.map_err(|e| {
+            if e.is_unique_violation() {
+                ProductsServiceError::UnprocessableEntry(
+                    "A termékek ütköző készlet-, sorozatszám- vagy változatadatok miatt nem vonhatók össze!",
+                )
+            } else {
+                e.into()
+            }
+        })
+    }
+
+    async fn get_custom_fields(&self) -> ProductsServiceResult<Vec<ProductCustomField>> {
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .get_custom_fields()
+            .await?)
+    }
+
+    async fn create_custom_field(
+        &self,
+        payload: &ProductCustomFieldInput,
+    ) -> ProductsServiceResult<ProductCustomField> {
+        let input = payload
+            .validate()
+            .map_err(ProductsServiceError::UnprocessableEntry)?;
+        self.module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .insert_custom_field(&input, self.claims()?.sub())
+            .await
+            .map_err(map_custom_field_error)
+    }
+
+    async fn update_custom_field(
+        &self,
+        payload: &ProductCustomFieldInput,
+    ) -> ProductsServiceResult<ProductCustomField> {
+        let input = payload
+            .validate()
+            .map_err(ProductsServiceError::UnprocessableEntry)?;
+        Ok(self
+            .module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .update_custom_field(&input)
+            .await?)
+    }
+
+    async fn delete_custom_field(&self, id: Uuid) -> ProductsServiceResult<()> {
+        self.module()
+            .products_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ProductsServiceError::Unauthorized)?,
+            )?
+            .delete_custom_field(id)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_custom_fields(
+        &self,
+        payload: &ProductCustomFieldValuesInput,
+    ) -> ProductsServiceResult<Product> {
+        let repo = self.module().products_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProductsServiceError::Unauthorized)?,
+        )?;
+        let product = repo.get_by_id(payload.product_id).await?;
+        let values = merge_custom_field_values(
+            &repo.get_custom_fields().await?,
+            &product.custom_fields,
+            &payload.values,
+        )
+        .map_err(ProductsServiceError::UnprocessableEntry)?;
+        Ok(repo.set_custom_fields(product.id, &values).await?)
+    }
+
     async fn print(&self, payload: &[ProductsResolvedPrint]) -> ProductsServiceResult<Vec<u8>> {
         Ok(PdfGenerator::gen_pdf_temporary(
             &PdfTemplates::ProductView,
@@ -311,6 +1538,13 @@ where
             id: product_id,
             name: "Test product".to_string(),
             description: None,
+            sku: None,
+            barcode: None,
+            parent_id: None,
+            variant_attributes: None,
+            custom_fields: json!({}),
+            price: None,
+            currency_code: None,
             unit_of_measure_id,
             unit_of_measure: "cm".to_string(),
             status: "active".to_string(),
@@ -319,6 +1553,7 @@ where
             created_at: test_time,
             updated_at: test_time,
             deleted_at: None,
+            attachments: vec![],
         };
         let products_resolved_print =
             ProductsResolvedPrint::from_product_resolved(product_resolved, tz);
@@ -0,0 +1,110 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::value_object::*;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Barcode(String);
+
+impl Barcode {
+    pub const VALIDATION_ERROR: &'static str = "Hibás vonalkód!";
+
+    /// GTIN (EAN-8, UPC-A, EAN-13, GTIN-14) check digit of the given digits without the check digit
+    pub fn gtin_check_digit(digits: &[u8]) -> u8 {
+        let sum: u32 = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, d)| u32::from(*d) * if i % 2 == 0 { 3 } else { 1 })
+            .sum();
+        ((10 - sum % 10) % 10) as u8
+    }
+}
+
+impl ValueObjectData for Barcode {
+    type DataType = String;
+
+    fn new(data: &str) -> ValueObjectResult<Option<Self>> {
+        let data = data.trim();
+        if !data.is_empty() {
+            Ok(Some(Self(data.to_owned())))
+        } else {
+            Ok(None)
+        }
+    }
+    fn validate(&self) -> Result<(), ValueObjectError> {
+        if self.0.len() > 48 || !self.0.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR));
+        }
+        // NOTE: numeric codes of GTIN length must carry a valid check digit, anything else is
+        // treated as an internal code
+        if matches!(self.0.len(), 8 | 12 | 13 | 14) && self.0.chars().all(|c| c.is_ascii_digit()) {
+            let digits: Vec<u8> = self.0.bytes().map(|b| b - b'0').collect();
+            let (check_digit, payload) = digits
+                .split_last()
+                .ok_or(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR))?;
+            if Self::gtin_check_digit(payload) != *check_digit {
+                return Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR));
+            }
+        }
+        Ok(())
+    }
+    fn get_data(&self) -> &Self::DataType {
+        &self.0
+    }
+}
+
+impl Display for Barcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_ean13() {
+        let barcode = "5901234123457"
+            .parse::<ValueObjectRequired<Barcode>>()
+            .unwrap();
+        assert_eq!(barcode.as_str().unwrap(), "5901234123457");
+    }
+
+    #[test]
+    fn test_invalid_ean13_check_digit() {
+        assert!(
+            "5901234123458"
+                .parse::<ValueObjectRequired<Barcode>>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_internal_code() {
+        assert!("INT-000123".parse::<ValueObjectRequired<Barcode>>().is_ok());
+        assert!(
+            "INT 000123"
+                .parse::<ValueObjectRequired<Barcode>>()
+                .is_err()
+        );
+    }
+}
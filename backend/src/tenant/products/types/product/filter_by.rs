@@ -18,6 +18,7 @@
  */
 
 use crate::common::value_object::*;
+use crate::tenant::products::dto::custom_field::is_custom_field_key;
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -36,7 +37,14 @@ impl ValueObjectData for FilterBy {
     }
     fn validate(&self) -> Result<(), ValueObjectError> {
         match self.0.as_str() {
-            "name" => Ok(()),
+            "name" | "sku" | "barcode" | "category_id" => Ok(()),
+            filter_by
+                if filter_by
+                    .strip_prefix("custom_fields.")
+                    .is_some_and(is_custom_field_key) =>
+            {
+                Ok(())
+            }
             _ => Err(ValueObjectError::InvalidInput("Hibás sorrend formátum")),
         }
     }
@@ -67,4 +75,17 @@ mod tests {
         let filter_by = "invalid".parse::<ValueObjectRequired<FilterBy>>();
         assert!(filter_by.is_err());
     }
+
+    #[test]
+    fn test_custom_field_filter_by() {
+        let filter_by = "custom_fields.material"
+            .parse::<ValueObjectRequired<FilterBy>>()
+            .unwrap();
+        assert_eq!(filter_by.as_str().unwrap(), "custom_fields.material");
+        assert!(
+            "custom_fields.x' OR 1=1 --"
+                .parse::<ValueObjectRequired<FilterBy>>()
+                .is_err()
+        );
+    }
 }
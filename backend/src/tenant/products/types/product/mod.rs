@@ -17,14 +17,18 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub(crate) mod barcode;
 pub(crate) mod description;
 pub(crate) mod filter_by;
 pub(crate) mod name;
 pub(crate) mod order_by;
+pub(crate) mod sku;
 pub(crate) mod status;
 
+pub(crate) use barcode::Barcode as ProductBarcode;
 pub(crate) use description::Description as ProductDescription;
 pub(crate) use filter_by::FilterBy as ProductFilterBy;
 pub(crate) use name::Name as ProductName;
 pub(crate) use order_by::OrderBy as ProductOrderBy;
+pub(crate) use sku::Sku as ProductSku;
 pub(crate) use status::Status as ProductStatus;
@@ -0,0 +1,81 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::value_object::*;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sku(String);
+
+impl Sku {
+    pub const VALIDATION_ERROR: &'static str = "A cikkszám legfeljebb 64 karakter lehet, és csak betűt, számot, valamint - _ . / karaktert tartalmazhat!";
+}
+
+impl ValueObjectData for Sku {
+    type DataType = String;
+
+    fn new(data: &str) -> ValueObjectResult<Option<Self>> {
+        let data = data.trim();
+        if !data.is_empty() {
+            Ok(Some(Self(data.to_owned())))
+        } else {
+            Ok(None)
+        }
+    }
+    fn validate(&self) -> Result<(), ValueObjectError> {
+        if self.0.len() <= 64
+            && self
+                .0
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+        {
+            Ok(())
+        } else {
+            Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR))
+        }
+    }
+    fn get_data(&self) -> &Self::DataType {
+        &self.0
+    }
+}
+
+impl Display for Sku {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_sku() {
+        let sku = " CSV-10/A_2.5 "
+            .parse::<ValueObjectRequired<Sku>>()
+            .unwrap();
+        assert_eq!(sku.as_str().unwrap(), "CSV-10/A_2.5");
+    }
+
+    #[test]
+    fn test_invalid_sku() {
+        assert!("CSV 10".parse::<ValueObjectRequired<Sku>>().is_err());
+        assert!("a".repeat(65).parse::<ValueObjectRequired<Sku>>().is_err());
+    }
+}
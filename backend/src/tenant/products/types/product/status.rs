@@ -25,6 +25,22 @@ pub struct Status(String);
 
 impl Status {
     pub const VALIDATION_ERROR: &'static str = "Hibás termék státusz";
+    pub const TRANSITION_ERROR: &'static str =
+        "A termék státusza nem módosítható a megadott értékre!";
+
+    /// Lifecycle: draft → active → discontinued → archived. A discontinued product can be put back
+    /// on sale and a draft can be archived without ever being released.
+    pub fn can_transition(from: &str, to: &str) -> bool {
+        from == to
+            || matches!(
+                (from, to),
+                ("draft", "active")
+                    | ("draft", "archived")
+                    | ("active", "discontinued")
+                    | ("discontinued", "active")
+                    | ("discontinued", "archived")
+            )
+    }
 }
 
 impl ValueObjectData for Status {
@@ -40,8 +56,7 @@ impl ValueObjectData for Status {
     }
     fn validate(&self) -> Result<(), ValueObjectError> {
         match self.0.as_str() {
-            "active" => Ok(()),
-            "inactive" => Ok(()),
+            "draft" | "active" | "discontinued" | "archived" => Ok(()),
             _ => Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR)),
         }
     }
@@ -71,5 +86,17 @@ mod tests {
     fn test_invalid_status() {
         let status = "invalid".parse::<ValueObjectRequired<Status>>();
         assert!(status.is_err());
+        assert!("inactive".parse::<ValueObjectRequired<Status>>().is_err());
+    }
+
+    #[test]
+    fn test_transitions() {
+        assert!(Status::can_transition("draft", "active"));
+        assert!(Status::can_transition("active", "discontinued"));
+        assert!(Status::can_transition("discontinued", "archived"));
+        assert!(Status::can_transition("archived", "archived"));
+        assert!(!Status::can_transition("active", "draft"));
+        assert!(!Status::can_transition("active", "archived"));
+        assert!(!Status::can_transition("archived", "active"));
     }
 }
@@ -0,0 +1,315 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::project_members::ProjectMembersModuleInterface;
+use crate::tenant::project_members::dto::{ProjectMemberInput, ProjectMemberRemoval};
+use crate::tenant::project_members::service::ProjectMembersService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: ProjectMembersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(project_members_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), project_members_module.clone());
+    let result = map_handler_err(
+        service.list(payload.uuid).await,
+        project_members_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        project_members_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set<M: ProjectMembersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(project_members_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProjectMemberInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), project_members_module.clone());
+    let result =
+        map_handler_err(service.set(&payload).await, project_members_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        project_members_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn remove<M: ProjectMembersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(project_members_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ProjectMemberRemoval>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), project_members_module.clone());
+    map_handler_err(
+        service.remove(&payload).await,
+        project_members_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A projekttag eltávolítása sikeresen megtörtént",
+            ))
+            .build(),
+        project_members_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::project_members::model::ProjectMember;
+    use crate::tenant::project_members::{
+        self, repository::MockProjectMembersRepository, tests::MockProjectMembersModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockProjectMembersRepository, active_tenant_id: Uuid, access_all: bool) -> Router {
+        let repo = Arc::new(repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "projects.access_all")
+            .returning(move |_, _| Ok(access_all));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut project_members_module = MockProjectMembersModule::new();
+        project_members_module
+            .expect_project_members_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        project_members_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        project_members_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        project_members_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(project_members::routes::routes(Arc::new(
+                project_members_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_manager_sets_member_role() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut repo = MockProjectMembersRepository::new();
+        repo.expect_get_role()
+            .times(1)
+            .with(eq(project_id), eq(sub))
+            .returning(|_, _| Ok(Some("manager".to_string())));
+        repo.expect_set()
+            .times(1)
+            .withf(move |project, user, role, created_by| {
+                *project == project_id && *user == user_id && role == "viewer" && *created_by == sub
+            })
+            .returning(move |_, _, _, _| {
+                Ok(ProjectMember {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    project_id,
+                    role: "viewer".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, active_tenant_id, false)
+            .oneshot(request(
+                "PUT",
+                "/api/project_members/set",
+                sub,
+                active_tenant_id,
+                Some(json!({"project_id": project_id, "user_id": user_id, "role": "viewer"})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_set_requires_manager_role() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockProjectMembersRepository::new();
+        repo.expect_get_role()
+            .times(1)
+            .returning(|_, _| Ok(Some("member".to_string())));
+        repo.expect_set().never();
+
+        let response = app(repo, active_tenant_id, false)
+            .oneshot(request(
+                "PUT",
+                "/api/project_members/set",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({
+                    "project_id": Uuid::new_v4(),
+                    "user_id": Uuid::new_v4(),
+                    "role": "manager"
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_unknown_role() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockProjectMembersRepository::new();
+        repo.expect_set().never();
+
+        let response = app(repo, active_tenant_id, true)
+            .oneshot(request(
+                "PUT",
+                "/api/project_members/set",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({
+                    "project_id": Uuid::new_v4(),
+                    "user_id": Uuid::new_v4(),
+                    "role": "owner"
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_is_hidden_from_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockProjectMembersRepository::new();
+        repo.expect_get_role().times(1).returning(|_, _| Ok(None));
+        repo.expect_get_by_project().never();
+
+        let response = app(repo, active_tenant_id, false)
+            .oneshot(request(
+                "GET",
+                &format!("/api/project_members/list?uuid={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_remove_with_tenant_wide_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut repo = MockProjectMembersRepository::new();
+        repo.expect_get_role().times(1).returning(|_, _| Ok(None));
+        repo.expect_remove()
+            .times(1)
+            .with(eq(project_id), eq(user_id))
+            .returning(|_, _| Ok(()));
+
+        let response = app(repo, active_tenant_id, true)
+            .oneshot(request(
+                "PUT",
+                "/api/project_members/remove",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({"project_id": project_id, "user_id": user_id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,136 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::AppState;
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::tenant::permissions::PermissionsModuleInterface;
+use crate::tenant::project_members::repository::ProjectMembersRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+
+pub trait ProjectMembersModuleInterface: PermissionsModuleInterface {
+    fn project_members_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>>;
+}
+
+impl<P, T> ProjectMembersModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn project_members_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::{MockTenantsRepository, TenantsRepository};
+    use crate::tenant::permissions::repository::{
+        MockPermissionsRepository, PermissionsRepository,
+    };
+    use crate::tenant::project_members::repository::MockProjectMembersRepository;
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    /// Repositories of a tenant member who holds `role` in the project of every task and
+    /// worksheet, as project members, tenant membership and permissions repositories
+    pub fn project_access_repos(
+        role: Option<&'static str>,
+    ) -> (
+        Arc<MockProjectMembersRepository>,
+        Arc<MockTenantsRepository>,
+        Arc<MockPermissionsRepository>,
+    ) {
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_task_project_id()
+            .returning(|_| Ok(Some(Uuid::new_v4())));
+        project_members_repo
+            .expect_get_worksheet_project_id()
+            .returning(|_| Ok(Some(Uuid::new_v4())));
+        project_members_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(role.map(str::to_string)));
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .returning(|_, _| Ok(false));
+        (
+            Arc::new(project_members_repo),
+            Arc::new(membership_repo),
+            Arc::new(permissions_repo),
+        )
+    }
+
+    mock!(
+        pub ProjectMembersModule {}
+        impl ConfigProvider for ProjectMembersModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for ProjectMembersModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for ProjectMembersModule {}
+        impl PermissionsModuleInterface for ProjectMembersModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
+        impl ProjectMembersModuleInterface for ProjectMembersModule {
+            fn project_members_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>>;
+        }
+    );
+}
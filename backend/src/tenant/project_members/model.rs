@@ -0,0 +1,84 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Edits the project's worksheets and tasks and manages its members
+pub const ROLE_MANAGER: &str = "manager";
+/// Edits the project's worksheets and tasks
+pub const ROLE_MEMBER: &str = "member";
+/// Only sees the project's worksheets and tasks
+pub const ROLE_VIEWER: &str = "viewer";
+
+pub const ROLES: [&str; 3] = [ROLE_MANAGER, ROLE_MEMBER, ROLE_VIEWER];
+
+/// What a user wants to do with the worksheets and tasks of a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectAccess {
+    Read,
+    Write,
+}
+
+impl ProjectAccess {
+    pub fn allowed_for(self, role: &str) -> bool {
+        match self {
+            ProjectAccess::Read => ROLES.contains(&role),
+            ProjectAccess::Write => role == ROLE_MANAGER || role == ROLE_MEMBER,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProjectMember {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub project_id: Uuid,
+    pub role: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProjectMemberResolved {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user: String,
+    pub email: String,
+    pub project_id: Uuid,
+    pub role: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewers_can_only_read() {
+        assert!(ProjectAccess::Read.allowed_for(ROLE_VIEWER));
+        assert!(!ProjectAccess::Write.allowed_for(ROLE_VIEWER));
+        assert!(ProjectAccess::Write.allowed_for(ROLE_MEMBER));
+        assert!(ProjectAccess::Write.allowed_for(ROLE_MANAGER));
+        assert!(!ProjectAccess::Read.allowed_for("owner"));
+    }
+}
@@ -0,0 +1,215 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::project_members::model::{ProjectMember, ProjectMemberResolved};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Condition keeping the rows whose `project_column` is visible to the user bound as
+/// `$user_param`, binding NULL as the user makes every project visible
+pub(crate) fn visible_project_condition(project_column: &str, user_param: usize) -> String {
+    format!(
+        r#"({project_column} IS NULL
+            OR ${user_param}::UUID IS NULL
+            OR EXISTS (SELECT 1
+                       FROM project_assignments
+                       WHERE project_assignments.project_id = {project_column}
+                         AND project_assignments.user_id = ${user_param}
+                         AND project_assignments.deleted_at IS NULL))"#
+    )
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ProjectMembersRepository: Send + Sync {
+    async fn get_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> RepositoryResult<Vec<ProjectMemberResolved>>;
+    async fn get_role(&self, project_id: Uuid, user_id: Uuid) -> RepositoryResult<Option<String>>;
+    /// Adds the user to the project or changes the role of an existing member
+    async fn set(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        role: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<ProjectMember>;
+    async fn remove(&self, project_id: Uuid, user_id: Uuid) -> RepositoryResult<()>;
+    async fn get_worksheet_project_id(&self, worksheet_id: Uuid) -> RepositoryResult<Option<Uuid>>;
+    async fn get_task_project_id(&self, task_id: Uuid) -> RepositoryResult<Option<Uuid>>;
+}
+
+#[async_trait]
+impl ProjectMembersRepository for PgPool {
+    async fn get_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> RepositoryResult<Vec<ProjectMemberResolved>> {
+        Ok(sqlx::query_as::<_, ProjectMemberResolved>(
+            r#"
+            SELECT project_assignments.id,
+                   project_assignments.user_id,
+                   COALESCE(
+                       NULLIF(TRIM(CONCAT(users.last_name, ' ', users.first_name)), ''),
+                       users.email
+                   ) AS user,
+                   users.email,
+                   project_assignments.project_id,
+                   project_assignments.role,
+                   project_assignments.created_by_id,
+                   project_assignments.created_at
+            FROM project_assignments
+            JOIN users ON project_assignments.user_id = users.id
+            WHERE project_assignments.project_id = $1
+                AND project_assignments.deleted_at IS NULL
+            ORDER BY project_assignments.created_at
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_role(&self, project_id: Uuid, user_id: Uuid) -> RepositoryResult<Option<String>> {
+        Ok(sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT role
+            FROM project_assignments
+            WHERE project_id = $1
+                AND user_id = $2
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn set(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        role: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<ProjectMember> {
+        let mut tx = self.begin().await?;
+        let project = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM projects
+            WHERE id = $1
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(project_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if project.is_none() {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        let updated = sqlx::query_as::<_, ProjectMember>(
+            r#"
+            UPDATE project_assignments
+            SET role = $3
+            WHERE project_id = $1
+                AND user_id = $2
+                AND deleted_at IS NULL
+            RETURNING id, user_id, project_id, role, created_by_id, created_at
+            "#,
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(role)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let member = match updated {
+            Some(member) => member,
+            None => {
+                sqlx::query_as::<_, ProjectMember>(
+                    r#"
+                    INSERT INTO project_assignments (user_id, project_id, role, created_by_id)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING id, user_id, project_id, role, created_by_id, created_at
+                    "#,
+                )
+                .bind(user_id)
+                .bind(project_id)
+                .bind(role)
+                .bind(sub)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+        tx.commit().await?;
+        Ok(member)
+    }
+
+    async fn remove(&self, project_id: Uuid, user_id: Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE project_assignments
+            SET deleted_at = now()
+            WHERE project_id = $1
+                AND user_id = $2
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .execute(self)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn get_worksheet_project_id(&self, worksheet_id: Uuid) -> RepositoryResult<Option<Uuid>> {
+        Ok(sqlx::query_scalar::<_, Option<Uuid>>(
+            "SELECT project_id FROM worksheets WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(worksheet_id)
+        .fetch_optional(self)
+        .await?
+        .flatten())
+    }
+
+    async fn get_task_project_id(&self, task_id: Uuid) -> RepositoryResult<Option<Uuid>> {
+        Ok(sqlx::query_scalar::<_, Option<Uuid>>(
+            r#"
+            SELECT worksheets.project_id
+            FROM tasks
+            JOIN worksheets ON tasks.worksheet_id = worksheets.id
+            WHERE tasks.id = $1
+                AND tasks.deleted_at IS NULL
+            "#,
+        )
+        .bind(task_id)
+        .fetch_optional(self)
+        .await?
+        .flatten())
+    }
+}
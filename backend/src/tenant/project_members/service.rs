@@ -0,0 +1,336 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error::{RepositoryError, RepositoryResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError, active_tenant};
+use crate::tenant::permissions::model::PROJECTS_ACCESS_ALL;
+use crate::tenant::permissions::service::has_permission;
+use crate::tenant::project_members::ProjectMembersModuleInterface;
+use crate::tenant::project_members::dto::{ProjectMemberInput, ProjectMemberRemoval};
+use crate::tenant::project_members::model::{
+    self, ProjectAccess, ProjectMember, ProjectMemberResolved, ROLE_MANAGER,
+};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ProjectMembersServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for ProjectMembersServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ProjectMembersServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ProjectMembersServiceError> for AppError {
+    fn from(value: ProjectMembersServiceError) -> Self {
+        match value {
+            ProjectMembersServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectMembersServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            ProjectMembersServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectMembersServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ProjectMembersServiceResult<T> = Result<T, ProjectMembersServiceError>;
+
+/// Whether the user may `access` the worksheets and tasks of the project, records outside of
+/// any project stay open to everyone
+pub(crate) async fn can_access_project<M: ProjectMembersModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    project_id: Option<Uuid>,
+    access: ProjectAccess,
+) -> RepositoryResult<bool> {
+    let Some(project_id) = project_id else {
+        return Ok(true);
+    };
+    let role = module
+        .project_members_repo(tenant_id)?
+        .get_role(project_id, user_id)
+        .await?;
+    Ok(role.is_some_and(|role| access.allowed_for(&role))
+        || has_permission(module, tenant_id, user_id, PROJECTS_ACCESS_ALL).await?)
+}
+
+pub(crate) async fn can_access_worksheet<M: ProjectMembersModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    worksheet_id: Uuid,
+    access: ProjectAccess,
+) -> RepositoryResult<bool> {
+    let project_id = module
+        .project_members_repo(tenant_id)?
+        .get_worksheet_project_id(worksheet_id)
+        .await?;
+    can_access_project(module, tenant_id, user_id, project_id, access).await
+}
+
+pub(crate) async fn can_access_task<M: ProjectMembersModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    task_id: Uuid,
+    access: ProjectAccess,
+) -> RepositoryResult<bool> {
+    let project_id = module
+        .project_members_repo(tenant_id)?
+        .get_task_project_id(task_id)
+        .await?;
+    can_access_project(module, tenant_id, user_id, project_id, access).await
+}
+
+/// Fails with `forbidden` unless the user of the service may `access` the worksheet
+pub(crate) async fn ensure_worksheet_access<M, E>(
+    service: &Service<'_, M>,
+    worksheet_id: Uuid,
+    access: ProjectAccess,
+    forbidden: E,
+) -> Result<(), E>
+where
+    M: ProjectMembersModuleInterface,
+    E: From<ServiceError> + From<RepositoryError>,
+{
+    let tenant_id = active_tenant(service)?;
+    if !can_access_worksheet(
+        service.module(),
+        tenant_id,
+        service.claims()?.sub(),
+        worksheet_id,
+        access,
+    )
+    .await?
+    {
+        return Err(forbidden);
+    }
+    Ok(())
+}
+
+/// Fails with `forbidden` unless the user of the service may `access` the task
+pub(crate) async fn ensure_task_access<M, E>(
+    service: &Service<'_, M>,
+    task_id: Uuid,
+    access: ProjectAccess,
+    forbidden: E,
+) -> Result<(), E>
+where
+    M: ProjectMembersModuleInterface,
+    E: From<ServiceError> + From<RepositoryError>,
+{
+    let tenant_id = active_tenant(service)?;
+    if !can_access_task(
+        service.module(),
+        tenant_id,
+        service.claims()?.sub(),
+        task_id,
+        access,
+    )
+    .await?
+    {
+        return Err(forbidden);
+    }
+    Ok(())
+}
+
+/// Whether the user manages the project, either as its manager or by accessing every project
+pub(crate) async fn is_project_manager<M: ProjectMembersModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    project_id: Uuid,
+) -> RepositoryResult<bool> {
+    let role = module
+        .project_members_repo(tenant_id)?
+        .get_role(project_id, user_id)
+        .await?;
+    Ok(role.as_deref() == Some(ROLE_MANAGER)
+        || has_permission(module, tenant_id, user_id, PROJECTS_ACCESS_ALL).await?)
+}
+
+/// The user whose projects the listings are restricted to, `None` when every project is visible
+pub(crate) async fn visible_projects_of<M: ProjectMembersModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> RepositoryResult<Option<Uuid>> {
+    Ok(
+        (!has_permission(module, tenant_id, user_id, PROJECTS_ACCESS_ALL).await?)
+            .then_some(user_id),
+    )
+}
+
+fn map_set_error(e: RepositoryError) -> ProjectMembersServiceError {
+    if e.is_foreign_key_violation() {
+        ProjectMembersServiceError::UnprocessableEntry("A megadott felhasználó nem létezik!")
+    } else {
+        e.into()
+    }
+}
+
+pub trait ProjectMembersService {
+    fn list(
+        &self,
+        project_id: Uuid,
+    ) -> impl Future<Output = ProjectMembersServiceResult<Vec<ProjectMemberResolved>>> + Send;
+    fn set(
+        &self,
+        payload: &ProjectMemberInput,
+    ) -> impl Future<Output = ProjectMembersServiceResult<ProjectMember>> + Send;
+    fn remove(
+        &self,
+        payload: &ProjectMemberRemoval,
+    ) -> impl Future<Output = ProjectMembersServiceResult<()>> + Send;
+    fn ensure_manager(
+        &self,
+        project_id: Uuid,
+    ) -> impl Future<Output = ProjectMembersServiceResult<()>> + Send;
+}
+
+impl<'a, T> ProjectMembersService for Service<'a, T>
+where
+    T: ProjectMembersModuleInterface,
+{
+    /// Members are managed by the managers of the project and by those who can access every
+    /// project
+    async fn ensure_manager(&self, project_id: Uuid) -> ProjectMembersServiceResult<()> {
+        if !is_project_manager(
+            self.module(),
+            active_tenant(self)?,
+            self.claims()?.sub(),
+            project_id,
+        )
+        .await?
+        {
+            return Err(ProjectMembersServiceError::Forbidden);
+        }
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        project_id: Uuid,
+    ) -> ProjectMembersServiceResult<Vec<ProjectMemberResolved>> {
+        let tenant_id = active_tenant(self)?;
+        if !can_access_project(
+            self.module(),
+            tenant_id,
+            self.claims()?.sub(),
+            Some(project_id),
+            ProjectAccess::Read,
+        )
+        .await?
+        {
+            return Err(ProjectMembersServiceError::Forbidden);
+        }
+        Ok(self
+            .module()
+            .project_members_repo(tenant_id)?
+            .get_by_project(project_id)
+            .await?)
+    }
+
+    async fn set(
+        &self,
+        payload: &ProjectMemberInput,
+    ) -> ProjectMembersServiceResult<ProjectMember> {
+        if !model::ROLES.contains(&payload.role.as_str()) {
+            return Err(ProjectMembersServiceError::UnprocessableEntry(
+                "A szerepkör csak vezető, tag vagy megtekintő lehet!",
+            ));
+        }
+        self.ensure_manager(payload.project_id).await?;
+        self.module()
+            .project_members_repo(active_tenant(self)?)?
+            .set(
+                payload.project_id,
+                payload.user_id,
+                &payload.role,
+                self.claims()?.sub(),
+            )
+            .await
+            .map_err(map_set_error)
+    }
+
+    async fn remove(&self, payload: &ProjectMemberRemoval) -> ProjectMembersServiceResult<()> {
+        self.ensure_manager(payload.project_id).await?;
+        Ok(self
+            .module()
+            .project_members_repo(active_tenant(self)?)?
+            .remove(payload.project_id, payload.user_id)
+            .await?)
+    }
+}
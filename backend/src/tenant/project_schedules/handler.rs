@@ -0,0 +1,439 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::project_schedules::ProjectSchedulesModuleInterface;
+use crate::tenant::project_schedules::dto::RescheduleTask;
+use crate::tenant::project_schedules::service::ProjectSchedulesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn get<M: ProjectSchedulesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(project_schedules_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), project_schedules_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        project_schedules_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        project_schedules_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reschedule<M: ProjectSchedulesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(project_schedules_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RescheduleTask>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), project_schedules_module.clone());
+    map_handler_err(
+        service.reschedule(&payload).await,
+        project_schedules_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A feladat ütemezése sikeresen módosult",
+            ))
+            .build(),
+        project_schedules_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::{
+        MockProjectArchivedViolation, extract_json_response, generate_valid_jwt,
+    };
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::project_members::repository::MockProjectMembersRepository;
+    use crate::tenant::project_schedules::model::{
+        Project, ProjectSchedule, ScheduleDependency, ScheduleTask,
+    };
+    use crate::tenant::project_schedules::{
+        self, repository::MockProjectSchedulesRepository, tests::MockProjectSchedulesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    /// Every task belongs to a project, where the user holds `role`
+    fn expect_project_access(
+        project_schedules_module: &mut MockProjectSchedulesModule,
+        role: Option<&'static str>,
+    ) {
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_task_project_id()
+            .returning(|_| Ok(Some(Uuid::new_v4())));
+        project_members_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(role.map(str::to_string)));
+        let project_members_repo = Arc::new(project_members_repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "projects.access_all")
+            .returning(|_, _| Ok(false));
+        let permissions_repo = Arc::new(permissions_repo);
+        project_schedules_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        project_schedules_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        project_schedules_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+    }
+
+    fn app(repo: MockProjectSchedulesRepository, active_tenant_id: Uuid) -> Router {
+        app_with_role(repo, active_tenant_id, Some("member"))
+    }
+
+    fn app_with_role(
+        repo: MockProjectSchedulesRepository,
+        active_tenant_id: Uuid,
+        role: Option<&'static str>,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let mut project_schedules_module = MockProjectSchedulesModule::new();
+        expect_project_access(&mut project_schedules_module, role);
+        project_schedules_module
+            .expect_project_schedules_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        project_schedules_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(project_schedules::routes::routes(Arc::new(
+                project_schedules_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn schedule_task(start_day: u32, due_day: u32) -> ScheduleTask {
+        ScheduleTask {
+            id: Uuid::new_v4(),
+            worksheet_id: Uuid::new_v4(),
+            worksheet: "Irodaház felújítás".to_string(),
+            service: "Villanyszerelés".to_string(),
+            status: "pending".to_string(),
+            start_date: Some(Utc.with_ymd_and_hms(2026, 5, start_day, 0, 0, 0).unwrap()),
+            due_date: Some(Utc.with_ymd_and_hms(2026, 5, due_day, 0, 0, 0).unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_critical_path() {
+        let tenant_id = Uuid::new_v4();
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: "Irodaház".to_string(),
+            status: "active".to_string(),
+            start_date: None,
+            end_date: None,
+        };
+        let wiring = schedule_task(4, 8);
+        let painting = schedule_task(6, 12);
+        let cleaning = schedule_task(5, 6);
+        let dependency = ScheduleDependency {
+            task_id: painting.id,
+            blocked_by_id: wiring.id,
+        };
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_get_project()
+            .with(eq(project.id))
+            .times(1)
+            .returning({
+                let project = project.clone();
+                move |_| Ok(project.clone())
+            });
+        repo.expect_get_tasks().times(1).returning({
+            let tasks = vec![wiring.clone(), painting.clone(), cleaning.clone()];
+            move |_| Ok(tasks.clone())
+        });
+        repo.expect_get_dependencies().times(1).returning({
+            let dependency = dependency.clone();
+            move |_| Ok(vec![dependency.clone()])
+        });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/project_schedules/get?uuid={}", project.id),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: ProjectSchedule =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body.finish_date, NaiveDate::from_ymd_opt(2026, 5, 14));
+        assert_eq!(body.dependencies, vec![dependency]);
+        assert_eq!(
+            body.tasks
+                .iter()
+                .map(|task| (task.id, task.is_critical, task.slack_days))
+                .collect::<Vec<_>>(),
+            vec![
+                (wiring.id, true, Some(0)),
+                (painting.id, true, Some(0)),
+                (cleaning.id, false, Some(8)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_project_not_found() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_get_project()
+            .times(1)
+            .returning(|_| Err(sqlx::Error::RowNotFound.into()));
+        repo.expect_get_tasks().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/project_schedules/get?uuid={}", Uuid::new_v4()),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_success() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_reschedule()
+            .with(eq(RescheduleTask {
+                task_id,
+                start_date: NaiveDate::from_ymd_opt(2026, 5, 4),
+                due_date: NaiveDate::from_ymd_opt(2026, 5, 8),
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/project_schedules/reschedule",
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "start_date": "2026-05-04",
+                    "due_date": "2026-05-08"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_in_archived_project_is_rejected() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_reschedule().times(1).returning(|_| {
+            Err(RepositoryError::Database(sqlx::Error::Database(Box::new(
+                MockProjectArchivedViolation,
+            ))))
+        });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/project_schedules/reschedule",
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "start_date": "2026-05-04",
+                    "due_date": "2026-05-08"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_start_after_due_date() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_reschedule().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/project_schedules/reschedule",
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "start_date": "2026-05-09",
+                    "due_date": "2026-05-08"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_get_is_forbidden_for_non_members() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_get_project().never();
+        repo.expect_get_tasks().never();
+
+        let response = app_with_role(repo, tenant_id, None)
+            .oneshot(request(
+                "GET",
+                &format!("/api/project_schedules/get?uuid={}", Uuid::new_v4()),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_is_forbidden_for_project_viewers() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_reschedule().never();
+
+        let response = app_with_role(repo, tenant_id, Some("viewer"))
+            .oneshot(request(
+                "PUT",
+                "/api/project_schedules/reschedule",
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "start_date": "2026-05-04",
+                    "due_date": "2026-05-08"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_is_forbidden_for_non_members() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockProjectSchedulesRepository::new();
+        repo.expect_reschedule().never();
+
+        let response = app_with_role(repo, tenant_id, None)
+            .oneshot(request(
+                "PUT",
+                "/api/project_schedules/reschedule",
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "start_date": "2026-05-04",
+                    "due_date": "2026-05-08"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
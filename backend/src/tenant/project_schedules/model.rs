@@ -0,0 +1,320 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Project {
+    pub id: Uuid,
+    pub name: String,
+    pub status: String,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ScheduleTask {
+    pub id: Uuid,
+    pub worksheet_id: Uuid,
+    pub worksheet: String,
+    pub service: String,
+    pub status: String,
+    pub start_date: Option<DateTime<Utc>>,
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+impl ScheduleTask {
+    /// Planned start and finish, a task without a start date is a milestone on its due date
+    fn planned(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let due_date = self.due_date?.date_naive();
+        let start_date = self
+            .start_date
+            .map(|start_date| start_date.date_naive())
+            .unwrap_or(due_date);
+        Some((start_date.min(due_date), due_date))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ScheduleDependency {
+    pub task_id: Uuid,
+    pub blocked_by_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledTask {
+    pub id: Uuid,
+    pub worksheet_id: Uuid,
+    pub worksheet: String,
+    pub service: String,
+    pub status: String,
+    pub start_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub earliest_start: Option<NaiveDate>,
+    pub earliest_finish: Option<NaiveDate>,
+    pub latest_start: Option<NaiveDate>,
+    pub latest_finish: Option<NaiveDate>,
+    /// Days the task can slip without delaying the project, `None` for unscheduled tasks
+    pub slack_days: Option<i64>,
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectSchedule {
+    pub project_id: Uuid,
+    pub project: String,
+    pub project_status: String,
+    pub project_start_date: Option<NaiveDate>,
+    pub project_end_date: Option<NaiveDate>,
+    /// The earliest date every scheduled task can be finished by
+    pub finish_date: Option<NaiveDate>,
+    pub tasks: Vec<ScheduledTask>,
+    pub dependencies: Vec<ScheduleDependency>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    earliest_start: NaiveDate,
+    earliest_finish: NaiveDate,
+    latest_start: NaiveDate,
+    latest_finish: NaiveDate,
+}
+
+impl ProjectSchedule {
+    /// Runs the critical path method over the tasks with a due date. A task can not start before
+    /// its planned start nor before every task blocking it is finished, keeping its planned
+    /// duration. Dependencies leaving the project and tasks without a due date are not scheduled.
+    pub fn build(
+        project: Project,
+        tasks: Vec<ScheduleTask>,
+        dependencies: Vec<ScheduleDependency>,
+    ) -> ProjectSchedule {
+        let planned: HashMap<Uuid, (NaiveDate, NaiveDate)> = tasks
+            .iter()
+            .filter_map(|task| task.planned().map(|planned| (task.id, planned)))
+            .collect();
+        let dependencies: Vec<ScheduleDependency> = dependencies
+            .into_iter()
+            .filter(|dependency| {
+                planned.contains_key(&dependency.task_id)
+                    && planned.contains_key(&dependency.blocked_by_id)
+            })
+            .collect();
+
+        let mut predecessors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        let mut successors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        let mut in_degree: HashMap<Uuid, usize> = planned.keys().map(|id| (*id, 0)).collect();
+        for dependency in &dependencies {
+            predecessors
+                .entry(dependency.task_id)
+                .or_default()
+                .push(dependency.blocked_by_id);
+            successors
+                .entry(dependency.blocked_by_id)
+                .or_default()
+                .push(dependency.task_id);
+            *in_degree.entry(dependency.task_id).or_default() += 1;
+        }
+
+        // Topological order in task order, tasks left in a cycle are not scheduled
+        let mut queue: VecDeque<Uuid> = tasks
+            .iter()
+            .filter(|task| in_degree.get(&task.id) == Some(&0))
+            .map(|task| task.id)
+            .collect();
+        let mut order = Vec::with_capacity(planned.len());
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for successor in successors.get(&id).into_iter().flatten() {
+                if let Some(degree) = in_degree.get_mut(successor) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(*successor);
+                    }
+                }
+            }
+        }
+
+        let mut earliest: HashMap<Uuid, (NaiveDate, NaiveDate)> = HashMap::new();
+        for id in &order {
+            let (start_date, due_date) = planned[id];
+            let earliest_start = predecessors
+                .get(id)
+                .into_iter()
+                .flatten()
+                .filter_map(|predecessor| earliest.get(predecessor).map(|(_, finish)| *finish))
+                .fold(start_date, NaiveDate::max);
+            earliest.insert(
+                *id,
+                (earliest_start, earliest_start + (due_date - start_date)),
+            );
+        }
+        let finish_date = earliest.values().map(|(_, finish)| *finish).max();
+
+        let mut windows: HashMap<Uuid, Window> = HashMap::new();
+        if let Some(finish_date) = finish_date {
+            for id in order.iter().rev() {
+                let (earliest_start, earliest_finish) = earliest[id];
+                let latest_finish = successors
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|successor| windows.get(successor).map(|w| w.latest_start))
+                    .fold(finish_date, NaiveDate::min);
+                windows.insert(
+                    *id,
+                    Window {
+                        earliest_start,
+                        earliest_finish,
+                        latest_start: latest_finish - (earliest_finish - earliest_start),
+                        latest_finish,
+                    },
+                );
+            }
+        }
+
+        ProjectSchedule {
+            project_id: project.id,
+            project: project.name,
+            project_status: project.status,
+            project_start_date: project.start_date.map(|date| date.date_naive()),
+            project_end_date: project.end_date.map(|date| date.date_naive()),
+            finish_date,
+            tasks: tasks
+                .into_iter()
+                .map(|task| {
+                    let window = windows.get(&task.id).copied();
+                    let slack_days = window
+                        .map(|window| (window.latest_start - window.earliest_start).num_days());
+                    ScheduledTask {
+                        id: task.id,
+                        worksheet_id: task.worksheet_id,
+                        worksheet: task.worksheet,
+                        service: task.service,
+                        status: task.status,
+                        start_date: task.start_date.map(|date| date.date_naive()),
+                        due_date: task.due_date.map(|date| date.date_naive()),
+                        earliest_start: window.map(|window| window.earliest_start),
+                        earliest_finish: window.map(|window| window.earliest_finish),
+                        latest_start: window.map(|window| window.latest_start),
+                        latest_finish: window.map(|window| window.latest_finish),
+                        slack_days,
+                        is_critical: slack_days == Some(0),
+                    }
+                })
+                .collect(),
+            dependencies,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn date(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, 0, 0, 0).unwrap()
+    }
+
+    fn task(start_date: Option<u32>, due_date: Option<u32>) -> ScheduleTask {
+        ScheduleTask {
+            id: Uuid::new_v4(),
+            worksheet_id: Uuid::nil(),
+            worksheet: "Karbantartás".to_string(),
+            service: "Szerelés".to_string(),
+            status: "pending".to_string(),
+            start_date: start_date.map(date),
+            due_date: due_date.map(date),
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            id: Uuid::new_v4(),
+            name: "Irodaház".to_string(),
+            status: "planning".to_string(),
+            start_date: Some(date(1)),
+            end_date: None,
+        }
+    }
+
+    fn blocked_by(task: &ScheduleTask, blocker: &ScheduleTask) -> ScheduleDependency {
+        ScheduleDependency {
+            task_id: task.id,
+            blocked_by_id: blocker.id,
+        }
+    }
+
+    #[test]
+    fn test_build_critical_path_and_slack() {
+        let design = task(Some(1), Some(5));
+        let build = task(Some(3), Some(10));
+        let paint = task(Some(2), Some(4));
+        let schedule = ProjectSchedule::build(
+            project(),
+            vec![design.clone(), build.clone(), paint.clone()],
+            vec![blocked_by(&build, &design)],
+        );
+
+        assert_eq!(schedule.finish_date, Some(date(12).date_naive()));
+        let build = &schedule.tasks[1];
+        assert_eq!(build.earliest_start, Some(date(5).date_naive()));
+        assert_eq!(build.earliest_finish, Some(date(12).date_naive()));
+        assert!(schedule.tasks[0].is_critical);
+        assert!(build.is_critical);
+        assert_eq!(schedule.tasks[2].slack_days, Some(8));
+        assert!(!schedule.tasks[2].is_critical);
+    }
+
+    #[test]
+    fn test_build_skips_unscheduled_tasks_and_cycles() {
+        let unscheduled = task(Some(1), None);
+        let first = task(None, Some(3));
+        let second = task(Some(1), Some(2));
+        let milestone = task(None, Some(4));
+        let schedule = ProjectSchedule::build(
+            project(),
+            vec![
+                unscheduled.clone(),
+                first.clone(),
+                second.clone(),
+                milestone.clone(),
+            ],
+            vec![
+                blocked_by(&first, &unscheduled),
+                blocked_by(&first, &second),
+                blocked_by(&second, &first),
+            ],
+        );
+
+        assert_eq!(schedule.dependencies.len(), 2);
+        assert_eq!(schedule.tasks[0].slack_days, None);
+        assert_eq!(schedule.tasks[1].earliest_start, None);
+        assert_eq!(schedule.tasks[2].earliest_start, None);
+        assert_eq!(schedule.tasks[3].earliest_start, Some(date(4).date_naive()));
+        assert!(schedule.tasks[3].is_critical);
+        assert_eq!(schedule.finish_date, Some(date(4).date_naive()));
+    }
+}
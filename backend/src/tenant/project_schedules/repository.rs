@@ -0,0 +1,118 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::project_schedules::dto::RescheduleTask;
+use crate::tenant::project_schedules::model::{Project, ScheduleDependency, ScheduleTask};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ProjectSchedulesRepository: Send + Sync {
+    async fn get_project(&self, project_id: Uuid) -> RepositoryResult<Project>;
+    async fn get_tasks(&self, project_id: Uuid) -> RepositoryResult<Vec<ScheduleTask>>;
+    async fn get_dependencies(&self, project_id: Uuid)
+    -> RepositoryResult<Vec<ScheduleDependency>>;
+    async fn reschedule(&self, input: &RescheduleTask) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl ProjectSchedulesRepository for PgPool {
+    async fn get_project(&self, project_id: Uuid) -> RepositoryResult<Project> {
+        Ok(sqlx::query_as::<_, Project>(
+            r#"
+            SELECT id, name, status, start_date, end_date
+            FROM projects
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(project_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_tasks(&self, project_id: Uuid) -> RepositoryResult<Vec<ScheduleTask>> {
+        Ok(sqlx::query_as::<_, ScheduleTask>(
+            r#"
+            SELECT tasks.id,
+                   tasks.worksheet_id,
+                   worksheets.name as worksheet,
+                   services.name as service,
+                   tasks.status,
+                   tasks.start_date,
+                   tasks.due_date
+            FROM tasks
+            JOIN worksheets ON tasks.worksheet_id = worksheets.id
+            LEFT JOIN services ON tasks.service_id = services.id
+            WHERE worksheets.project_id = $1
+                AND worksheets.deleted_at IS NULL
+                AND tasks.deleted_at IS NULL
+            ORDER BY COALESCE(tasks.start_date, tasks.due_date) NULLS LAST, tasks.created_at
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_dependencies(
+        &self,
+        project_id: Uuid,
+    ) -> RepositoryResult<Vec<ScheduleDependency>> {
+        Ok(sqlx::query_as::<_, ScheduleDependency>(
+            r#"
+            SELECT task_dependencies.task_id, task_dependencies.blocked_by_id
+            FROM task_dependencies
+            JOIN tasks ON task_dependencies.task_id = tasks.id
+            JOIN worksheets ON tasks.worksheet_id = worksheets.id
+            WHERE worksheets.project_id = $1
+                AND tasks.deleted_at IS NULL
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn reschedule(&self, input: &RescheduleTask) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET start_date = $2,
+                due_date = $3
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(input.task_id)
+        .bind(input.start_date)
+        .bind(input.due_date)
+        .execute(self)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+}
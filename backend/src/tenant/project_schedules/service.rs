@@ -0,0 +1,199 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{can_access_project, can_access_task};
+use crate::tenant::project_schedules::ProjectSchedulesModuleInterface;
+use crate::tenant::project_schedules::dto::RescheduleTask;
+use crate::tenant::project_schedules::model::ProjectSchedule;
+use crate::tenant::project_schedules::repository::ProjectSchedulesRepository;
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ProjectSchedulesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for ProjectSchedulesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ProjectSchedulesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ProjectSchedulesServiceError> for AppError {
+    fn from(value: ProjectSchedulesServiceError) -> Self {
+        match value {
+            ProjectSchedulesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectSchedulesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            ProjectSchedulesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectSchedulesServiceError::Repository(ref e)
+                if e.is_project_archived_violation() =>
+            {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": ProjectSchedulesServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            ProjectSchedulesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ProjectSchedulesServiceResult<T> = Result<T, ProjectSchedulesServiceError>;
+
+pub trait ProjectSchedulesService {
+    fn get(
+        &self,
+        project_id: Uuid,
+    ) -> impl Future<Output = ProjectSchedulesServiceResult<ProjectSchedule>> + Send;
+    fn reschedule(
+        &self,
+        payload: &RescheduleTask,
+    ) -> impl Future<Output = ProjectSchedulesServiceResult<()>> + Send;
+    fn repo(
+        &self,
+    ) -> ProjectSchedulesServiceResult<Arc<dyn ProjectSchedulesRepository + Send + Sync>>;
+}
+
+impl<'a, T> ProjectSchedulesService for Service<'a, T>
+where
+    T: ProjectSchedulesModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> ProjectSchedulesServiceResult<Arc<dyn ProjectSchedulesRepository + Send + Sync>> {
+        Ok(self.module().project_schedules_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ProjectSchedulesServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn get(&self, project_id: Uuid) -> ProjectSchedulesServiceResult<ProjectSchedule> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(ProjectSchedulesServiceError::Unauthorized)?;
+        if !can_access_project(
+            self.module(),
+            tenant_id,
+            claims.sub(),
+            Some(project_id),
+            ProjectAccess::Read,
+        )
+        .await?
+        {
+            return Err(ProjectSchedulesServiceError::Forbidden);
+        }
+        let repo = self.repo()?;
+        let project = repo.get_project(project_id).await?;
+        Ok(ProjectSchedule::build(
+            project,
+            repo.get_tasks(project_id).await?,
+            repo.get_dependencies(project_id).await?,
+        ))
+    }
+
+    async fn reschedule(&self, payload: &RescheduleTask) -> ProjectSchedulesServiceResult<()> {
+        if let (Some(start_date), Some(due_date)) = (payload.start_date, payload.due_date)
+            && start_date > due_date
+        {
+            return Err(ProjectSchedulesServiceError::UnprocessableEntry(
+                "A kezdés dátuma nem lehet későbbi a határidőnél!",
+            ));
+        }
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(ProjectSchedulesServiceError::Unauthorized)?;
+        if !can_access_task(
+            self.module(),
+            tenant_id,
+            claims.sub(),
+            payload.task_id,
+            ProjectAccess::Write,
+        )
+        .await?
+        {
+            return Err(ProjectSchedulesServiceError::Forbidden);
+        }
+        Ok(self.repo()?.reschedule(payload).await?)
+    }
+}
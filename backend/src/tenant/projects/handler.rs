@@ -0,0 +1,352 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{ArchivableRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::projects::ProjectsModuleInterface;
+use crate::tenant::projects::service::ProjectsService;
+use crate::tenant::tags::dto::TagFilter;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn list<M: ProjectsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(projects_module): State<Arc<M>>,
+    Query(payload): Query<ArchivableRawQuery>,
+    Query(tag_filter): Query<TagFilter>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), projects_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        projects_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service
+            .get_paged(&resource_query, payload.include_archived(), tag_filter.tags)
+            .await,
+        projects_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        projects_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn archive<M: ProjectsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(projects_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), projects_module.clone());
+    let result =
+        map_handler_err(service.archive(payload.uuid).await, projects_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        projects_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn restore<M: ProjectsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(projects_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), projects_module.clone());
+    let result =
+        map_handler_err(service.restore(payload.uuid).await, projects_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        projects_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::dto::PaginatorMeta;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::project_members::repository::MockProjectMembersRepository;
+    use crate::tenant::projects::model::Project;
+    use crate::tenant::projects::{
+        self, repository::MockProjectsRepository, tests::MockProjectsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::{always, eq};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockProjectsRepository,
+        active_tenant_id: Uuid,
+        role: Option<&'static str>,
+        access_all: bool,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(role.map(str::to_string)));
+        let project_members_repo = Arc::new(project_members_repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "projects.access_all")
+            .returning(move |_, _| Ok(access_all));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut projects_module = MockProjectsModule::new();
+        projects_module
+            .expect_projects_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        projects_module
+            .expect_project_members_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(project_members_repo.clone()));
+        projects_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        projects_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        projects_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(projects::routes::routes(Arc::new(projects_module))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn project(id: Uuid, archived: bool) -> Project {
+        Project {
+            id,
+            name: "Irodaház felújítás".to_string(),
+            description: None,
+            created_by_id: Uuid::new_v4(),
+            status: "in_progress".to_string(),
+            start_date: None,
+            end_date: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            archived_at: archived.then(Utc::now),
+            archived_by_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_manager_archives_project() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let mut repo = MockProjectsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(project_id))
+            .returning(|id| Ok(project(id, false)));
+        repo.expect_archive()
+            .times(1)
+            .with(eq(project_id), eq(sub))
+            .returning(|id, sub| {
+                Ok(Project {
+                    archived_by_id: Some(sub),
+                    ..project(id, true)
+                })
+            });
+
+        let response = app(repo, active_tenant_id, Some("manager"), false)
+            .oneshot(request(
+                "PUT",
+                "/api/projects/archive",
+                sub,
+                active_tenant_id,
+                Some(json!({ "uuid": project_id })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_archive_already_archived_project() {
+        let active_tenant_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let mut repo = MockProjectsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(|id| Ok(project(id, true)));
+        repo.expect_archive().never();
+
+        let response = app(repo, active_tenant_id, None, true)
+            .oneshot(request(
+                "PUT",
+                "/api/projects/archive",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({ "uuid": project_id })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_member_cannot_restore_project() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockProjectsRepository::new();
+        repo.expect_get_by_id().never();
+        repo.expect_restore().never();
+
+        let response = app(repo, active_tenant_id, Some("member"), false)
+            .oneshot(request(
+                "PUT",
+                "/api/projects/restore",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({ "uuid": Uuid::new_v4() })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_list_includes_archived_only_on_request() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let mut repo = MockProjectsRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .with(always(), eq(Some(sub)), eq(false), eq(None))
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = app(repo, active_tenant_id, None, false)
+            .oneshot(request(
+                "GET",
+                "/api/projects/list",
+                sub,
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut repo = MockProjectsRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .with(always(), eq(None), eq(true), eq(None))
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = app(repo, active_tenant_id, None, true)
+            .oneshot(request(
+                "GET",
+                "/api/projects/list?include_archived=true",
+                sub,
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,165 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::project_members::repository::visible_project_condition;
+use crate::tenant::projects::model::Project;
+use crate::tenant::tags::repository::tagged_condition;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+/// Condition leaving out the rows whose `project_column` points to an archived project, or
+/// nothing at all when archived ones are included
+pub(crate) fn archived_project_condition(project_column: &str, include_archived: bool) -> String {
+    if include_archived {
+        return "TRUE".to_string();
+    }
+    format!(
+        r#"({project_column} IS NULL
+            OR NOT EXISTS (SELECT 1
+                           FROM projects archived_projects
+                           WHERE archived_projects.id = {project_column}
+                             AND archived_projects.archived_at IS NOT NULL))"#
+    )
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ProjectsRepository: Send + Sync {
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Project>)>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Project>;
+    async fn archive(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Project>;
+    async fn restore(&self, id: Uuid) -> RepositoryResult<Project>;
+}
+
+#[async_trait]
+impl ProjectsRepository for PgPool {
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Project>)> {
+        let archived_condition = archived_project_condition("projects.id", include_archived);
+
+        let total: (i64,) = sqlx::query_as(AssertSqlSafe(format!(
+            r#"SELECT COUNT(*) FROM projects
+                WHERE projects.deleted_at IS NULL
+                    AND {visible_condition}
+                    AND {archived_condition}
+                    AND {tagged_condition}"#,
+            visible_condition = visible_project_condition("projects.id", 1),
+            tagged_condition = tagged_condition("projects", "projects.id", 2)
+        )))
+        .bind(visible_to)
+        .bind(&tags)
+        .fetch_one(self)
+        .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let projects = sqlx::query_as::<_, Project>(AssertSqlSafe(format!(
+            r#"
+            SELECT *
+            FROM projects
+            WHERE projects.deleted_at IS NULL
+                AND {visible_condition}
+                AND {archived_condition}
+                AND {tagged_condition}
+            ORDER BY projects.archived_at IS NOT NULL, projects.created_at DESC
+            LIMIT $2
+            OFFSET $3
+            "#,
+            visible_condition = visible_project_condition("projects.id", 1),
+            tagged_condition = tagged_condition("projects", "projects.id", 4)
+        )))
+        .bind(visible_to)
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .bind(&tags)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            projects,
+        ))
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Project> {
+        Ok(sqlx::query_as::<_, Project>(
+            "SELECT * FROM projects WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn archive(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Project> {
+        Ok(sqlx::query_as::<_, Project>(
+            r#"
+            UPDATE projects
+            SET archived_at = now(),
+                archived_by_id = $2
+            WHERE id = $1
+              AND deleted_at IS NULL
+              AND archived_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn restore(&self, id: Uuid) -> RepositoryResult<Project> {
+        Ok(sqlx::query_as::<_, Project>(
+            r#"
+            UPDATE projects
+            SET archived_at = NULL,
+                archived_by_id = NULL
+            WHERE id = $1
+              AND deleted_at IS NULL
+              AND archived_at IS NOT NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+}
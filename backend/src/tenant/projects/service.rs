@@ -0,0 +1,182 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError, active_tenant};
+use crate::common::types::Empty;
+use crate::tenant::project_members::service::{is_project_manager, visible_projects_of};
+use crate::tenant::projects::ProjectsModuleInterface;
+use crate::tenant::projects::model::Project;
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ProjectsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for ProjectsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ProjectsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ProjectsServiceError> for AppError {
+    fn from(value: ProjectsServiceError) -> Self {
+        match value {
+            ProjectsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            ProjectsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ProjectsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ProjectsServiceResult<T> = Result<T, ProjectsServiceError>;
+
+pub trait ProjectsService {
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
+    ) -> impl Future<Output = ProjectsServiceResult<(PaginatorMeta, Vec<Project>)>> + Send;
+    fn archive(&self, id: Uuid) -> impl Future<Output = ProjectsServiceResult<Project>> + Send;
+    fn restore(&self, id: Uuid) -> impl Future<Output = ProjectsServiceResult<Project>> + Send;
+    fn managed_project(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ProjectsServiceResult<Project>> + Send;
+}
+
+impl<'a, T> ProjectsService for Service<'a, T>
+where
+    T: ProjectsModuleInterface,
+{
+    /// Projects are archived and restored by their managers and by those who can access every
+    /// project
+    async fn managed_project(&self, id: Uuid) -> ProjectsServiceResult<Project> {
+        let tenant_id = active_tenant(self)?;
+        if !is_project_manager(self.module(), tenant_id, self.claims()?.sub(), id).await? {
+            return Err(ProjectsServiceError::Forbidden);
+        }
+        Ok(self
+            .module()
+            .projects_repo(tenant_id)?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
+    ) -> ProjectsServiceResult<(PaginatorMeta, Vec<Project>)> {
+        let tenant_id = active_tenant(self)?;
+        let visible_to =
+            visible_projects_of(self.module(), tenant_id, self.claims()?.sub()).await?;
+        Ok(self
+            .module()
+            .projects_repo(tenant_id)?
+            .get_paged(get_query, visible_to, include_archived, tags)
+            .await?)
+    }
+
+    async fn archive(&self, id: Uuid) -> ProjectsServiceResult<Project> {
+        if self.managed_project(id).await?.is_archived() {
+            return Err(ProjectsServiceError::UnprocessableEntry(
+                "A projekt már archiválva van!",
+            ));
+        }
+        Ok(self
+            .module()
+            .projects_repo(active_tenant(self)?)?
+            .archive(id, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn restore(&self, id: Uuid) -> ProjectsServiceResult<Project> {
+        if !self.managed_project(id).await?.is_archived() {
+            return Err(ProjectsServiceError::UnprocessableEntry(
+                "A projekt nincs archiválva!",
+            ));
+        }
+        Ok(self
+            .module()
+            .projects_repo(active_tenant(self)?)?
+            .restore(id)
+            .await?)
+    }
+}
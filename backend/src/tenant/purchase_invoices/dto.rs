@@ -0,0 +1,83 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Without a unit price the line is billed at the ordered price
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PurchaseInvoiceLineInput {
+    pub purchase_order_line_id: Uuid,
+    pub quantity: BigDecimal,
+    pub unit_price: Option<BigDecimal>,
+}
+
+/// Without lines everything received and not yet invoiced is billed at the ordered prices
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreatePurchaseInvoice {
+    pub purchase_order_id: Uuid,
+    pub invoice_number: String,
+    pub issue_date: Option<NaiveDate>,
+    pub due_date: NaiveDate,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub lines: Vec<PurchaseInvoiceLineInput>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPurchaseInvoiceLine {
+    pub purchase_order_line_id: Uuid,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPurchaseInvoice {
+    pub invoice_number: String,
+    pub supplier_id: Uuid,
+    pub purchase_order_id: Uuid,
+    pub currency_code: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub notes: Option<String>,
+    pub lines: Vec<NewPurchaseInvoiceLine>,
+}
+
+/// Discrepancies found by the matching have to be accepted explicitly, with a reason
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ApprovePurchaseInvoice {
+    pub purchase_invoice_id: Uuid,
+    #[serde(default)]
+    pub accept_discrepancies: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RejectPurchaseInvoice {
+    pub purchase_invoice_id: Uuid,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct MatchingSettingsInput {
+    pub quantity_tolerance_percent: BigDecimal,
+    pub price_tolerance_percent: BigDecimal,
+}
@@ -0,0 +1,727 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::purchase_invoices::PurchaseInvoicesModuleInterface;
+use crate::tenant::purchase_invoices::dto::{
+    ApprovePurchaseInvoice, CreatePurchaseInvoice, MatchingSettingsInput, RejectPurchaseInvoice,
+};
+use crate::tenant::purchase_invoices::service::PurchaseInvoicesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreatePurchaseInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A beszerzési számla törlése sikeresen megtörtént",
+            ))
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn approve<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ApprovePurchaseInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.approve(&payload).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reject<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RejectPurchaseInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.reject(&payload).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn settings<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.get_settings().await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_settings<M: PurchaseInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<MatchingSettingsInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_invoices_module.clone());
+    let result = map_handler_err(
+        service.update_settings(&payload).await,
+        purchase_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::purchase_invoices::model::{
+        InvoiceDiscrepancy, MatchingLine, MatchingSettings, PurchaseInvoice, PurchaseInvoiceLine,
+    };
+    use crate::tenant::purchase_invoices::{
+        self, repository::MockPurchaseInvoicesRepository, tests::MockPurchaseInvoicesModule,
+    };
+    use crate::tenant::purchase_orders::model::PurchaseOrder;
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockPurchaseInvoicesRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let role = role.to_string();
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(Some(role.clone())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "purchase_invoices.approve")
+            .returning(move |_, _| Ok(granted));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut purchase_invoices_module = MockPurchaseInvoicesModule::new();
+        purchase_invoices_module
+            .expect_purchase_invoices_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        purchase_invoices_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        purchase_invoices_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        purchase_invoices_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(purchase_invoices::routes::routes(Arc::new(
+                purchase_invoices_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn purchase_order() -> PurchaseOrder {
+        PurchaseOrder {
+            id: Uuid::new_v4(),
+            order_number: "BR-2026-00001".to_string(),
+            supplier_id: Uuid::new_v4(),
+            warehouse_id: Uuid::new_v4(),
+            currency_code: "HUF".to_string(),
+            order_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            expected_delivery_date: None,
+            supplier_reference: None,
+            notes: None,
+            status: "partially_received".to_string(),
+            sent_at: Some(Utc::now()),
+            confirmed_at: None,
+            closed_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn purchase_invoice(purchase_order: &PurchaseOrder, status: &str) -> PurchaseInvoice {
+        PurchaseInvoice {
+            id: Uuid::new_v4(),
+            invoice_number: "CSN-2026/0042".to_string(),
+            supplier_id: purchase_order.supplier_id,
+            purchase_order_id: purchase_order.id,
+            currency_code: purchase_order.currency_code.clone(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 10).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(),
+            notes: None,
+            status: status.to_string(),
+            decided_by_id: None,
+            decided_at: None,
+            decision_note: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn settings() -> MatchingSettings {
+        MatchingSettings {
+            quantity_tolerance_percent: BigDecimal::from(5),
+            price_tolerance_percent: BigDecimal::from(2),
+            updated_by_id: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn invoice_line(
+        purchase_invoice: &PurchaseInvoice,
+        quantity: &str,
+        unit_price: &str,
+    ) -> PurchaseInvoiceLine {
+        PurchaseInvoiceLine {
+            id: Uuid::new_v4(),
+            purchase_invoice_id: purchase_invoice.id,
+            purchase_order_line_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            item: "Csőbilincs".to_string(),
+            quantity: quantity.parse().unwrap(),
+            unit_price: unit_price.parse().unwrap(),
+            net_amount: quantity.parse::<BigDecimal>().unwrap()
+                * unit_price.parse::<BigDecimal>().unwrap(),
+            ordered_quantity: BigDecimal::from(20),
+            order_unit_price: BigDecimal::from(100),
+            received_quantity: BigDecimal::from(15),
+            invoiced_quantity: BigDecimal::from(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_bills_uninvoiced_received_quantities() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order();
+        let purchase_invoice = purchase_invoice(&purchase_order, "recorded");
+        let matching_line = |received: i32, invoiced: i32| MatchingLine {
+            purchase_order_line_id: Uuid::new_v4(),
+            item: "Csőbilincs".to_string(),
+            ordered_quantity: BigDecimal::from(20),
+            unit_price: BigDecimal::from(100),
+            received_quantity: BigDecimal::from(received),
+            invoiced_quantity: BigDecimal::from(invoiced),
+        };
+        let partly_invoiced = matching_line(15, 5);
+        let not_received = matching_line(0, 0);
+        let expected_line_id = partly_invoiced.purchase_order_line_id;
+
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_get_purchase_order()
+            .with(eq(purchase_order.id))
+            .times(1)
+            .returning({
+                let purchase_order = purchase_order.clone();
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_get_matching_lines()
+            .with(eq(purchase_order.id))
+            .times(1)
+            .returning(move |_| Ok(vec![partly_invoiced.clone(), not_received.clone()]));
+        repo.expect_insert()
+            .times(1)
+            .withf({
+                let supplier_id = purchase_order.supplier_id;
+                move |input, _| {
+                    input.invoice_number == "CSN-2026/0042"
+                        && input.supplier_id == supplier_id
+                        && input.currency_code == "HUF"
+                        && input.lines.len() == 1
+                        && input.lines[0].purchase_order_line_id == expected_line_id
+                        && input.lines[0].quantity == 10
+                        && input.lines[0].unit_price == 100
+                }
+            })
+            .returning({
+                let purchase_invoice = purchase_invoice.clone();
+                move |_, _| Ok(purchase_invoice.clone())
+            });
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(settings()));
+        repo.expect_get_lines()
+            .with(eq(purchase_invoice.id), eq(2))
+            .times(1)
+            .returning({
+                let line = invoice_line(&purchase_invoice, "10", "100");
+                move |_, _| Ok(vec![line.clone()])
+            });
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_invoices/create",
+                active_tenant_id,
+                json!({
+                    "purchase_order_id": purchase_order.id,
+                    "invoice_number": " CSN-2026/0042 ",
+                    "issue_date": "2026-10-10",
+                    "due_date": "2026-10-25"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["discrepancies"], json!([]));
+        assert_eq!(body["data"]["net_total"], json!(BigDecimal::from(1000)));
+    }
+
+    #[tokio::test]
+    async fn test_create_keeps_currency_of_order_even_if_disabled_since() {
+        let active_tenant_id = Uuid::new_v4();
+        // NOTE: the tenant works in HUF only, EUR was disabled after the order was sent
+        let purchase_order = PurchaseOrder {
+            currency_code: "EUR".to_string(),
+            ..purchase_order()
+        };
+        let purchase_invoice = purchase_invoice(&purchase_order, "recorded");
+
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_get_purchase_order().times(1).returning({
+            let purchase_order = purchase_order.clone();
+            move |_| Ok(purchase_order.clone())
+        });
+        repo.expect_get_matching_lines().times(1).returning(|_| {
+            Ok(vec![MatchingLine {
+                purchase_order_line_id: Uuid::new_v4(),
+                item: "Csőbilincs".to_string(),
+                ordered_quantity: BigDecimal::from(20),
+                unit_price: BigDecimal::from(100),
+                received_quantity: BigDecimal::from(10),
+                invoiced_quantity: BigDecimal::from(0),
+            }])
+        });
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.currency_code == "EUR")
+            .returning({
+                let purchase_invoice = purchase_invoice.clone();
+                move |_, _| Ok(purchase_invoice.clone())
+            });
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(settings()));
+        repo.expect_get_lines().times(1).returning({
+            let line = invoice_line(&purchase_invoice, "10", "100");
+            move |_, _| Ok(vec![line.clone()])
+        });
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_invoices/create",
+                active_tenant_id,
+                json!({
+                    "purchase_order_id": purchase_order.id,
+                    "invoice_number": "CSN-2026/0042",
+                    "issue_date": "2026-10-10",
+                    "due_date": "2026-10-25"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_duplicate_order_line() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order();
+        let line = MatchingLine {
+            purchase_order_line_id: Uuid::new_v4(),
+            item: "Csőbilincs".to_string(),
+            ordered_quantity: BigDecimal::from(20),
+            unit_price: BigDecimal::from(100),
+            received_quantity: BigDecimal::from(20),
+            invoiced_quantity: BigDecimal::from(0),
+        };
+        let line_id = line.purchase_order_line_id;
+
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_get_purchase_order().times(1).returning({
+            let purchase_order = purchase_order.clone();
+            move |_| Ok(purchase_order.clone())
+        });
+        repo.expect_get_matching_lines()
+            .times(1)
+            .returning(move |_| Ok(vec![line.clone()]));
+        repo.expect_insert().never();
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_invoices/create",
+                active_tenant_id,
+                json!({
+                    "purchase_order_id": purchase_order.id,
+                    "invoice_number": "CSN-2026/0042",
+                    "due_date": "2099-01-01",
+                    "lines": [
+                        {"purchase_order_line_id": line_id, "quantity": "10"},
+                        {"purchase_order_line_id": line_id, "quantity": "10"}
+                    ]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_get_flags_discrepancies_above_tolerance() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_invoice = purchase_invoice(&purchase_order(), "recorded");
+        // 10 received and not yet invoiced on each line, ordered at 100 per unit
+        let within_tolerance = invoice_line(&purchase_invoice, "10.40", "102");
+        let above_tolerance = invoice_line(&purchase_invoice, "12", "103");
+        let above_tolerance_id = above_tolerance.purchase_order_line_id;
+
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(purchase_invoice.id))
+            .times(1)
+            .returning({
+                let purchase_invoice = purchase_invoice.clone();
+                move |_| Ok(purchase_invoice.clone())
+            });
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(settings()));
+        repo.expect_get_lines()
+            .times(1)
+            .returning(move |_, _| Ok(vec![within_tolerance.clone(), above_tolerance.clone()]));
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/purchase_invoices/get?uuid={}", purchase_invoice.id),
+                active_tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let discrepancies: Vec<InvoiceDiscrepancy> = serde_json::from_value(
+            extract_json_response(response).await["data"]["discrepancies"].clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            discrepancies,
+            vec![
+                InvoiceDiscrepancy {
+                    purchase_order_line_id: above_tolerance_id,
+                    item: "Csőbilincs".to_string(),
+                    kind: "quantity".to_string(),
+                    expected: BigDecimal::from(10),
+                    invoiced: BigDecimal::from(12),
+                    deviation_percent: Some(BigDecimal::from(20)),
+                },
+                InvoiceDiscrepancy {
+                    purchase_order_line_id: above_tolerance_id,
+                    item: "Csőbilincs".to_string(),
+                    kind: "price".to_string(),
+                    expected: BigDecimal::from(100),
+                    invoiced: BigDecimal::from(103),
+                    deviation_percent: Some(BigDecimal::from(3)),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approve_forbidden_without_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_decide().never();
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/purchase_invoices/approve",
+                active_tenant_id,
+                json!({"purchase_invoice_id": Uuid::new_v4()}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    fn over_billed_repo(purchase_invoice: &PurchaseInvoice) -> MockPurchaseInvoicesRepository {
+        let over_billed = invoice_line(purchase_invoice, "12", "100");
+        let mut repo = MockPurchaseInvoicesRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(purchase_invoice.id))
+            .times(1)
+            .returning({
+                let purchase_invoice = purchase_invoice.clone();
+                move |_| Ok(purchase_invoice.clone())
+            });
+        repo.expect_get_settings()
+            .times(1)
+            .returning(|| Ok(settings()));
+        repo.expect_get_lines()
+            .times(1)
+            .returning(move |_, _| Ok(vec![over_billed.clone()]));
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_approve_requires_accepting_discrepancies() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_invoice = purchase_invoice(&purchase_order(), "recorded");
+        let mut repo = over_billed_repo(&purchase_invoice);
+        repo.expect_decide().never();
+
+        let response = app(repo, "member", true, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/purchase_invoices/approve",
+                active_tenant_id,
+                json!({"purchase_invoice_id": purchase_invoice.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_approve_with_accepted_discrepancies() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_invoice = purchase_invoice(&purchase_order(), "recorded");
+        let mut repo = over_billed_repo(&purchase_invoice);
+        repo.expect_decide()
+            .times(1)
+            .withf(|_, status, note, _| {
+                status == "approved" && note.as_deref() == Some("Előre leszállított mennyiség")
+            })
+            .returning({
+                let purchase_invoice = purchase_invoice.clone();
+                move |_, status, note, sub| {
+                    Ok(PurchaseInvoice {
+                        status: status.to_string(),
+                        decided_by_id: Some(sub),
+                        decided_at: Some(Utc::now()),
+                        decision_note: note,
+                        ..purchase_invoice.clone()
+                    })
+                }
+            });
+
+        let response = app(repo, "owner", false, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/purchase_invoices/approve",
+                active_tenant_id,
+                json!({
+                    "purchase_invoice_id": purchase_invoice.id,
+                    "accept_discrepancies": true,
+                    "note": " Előre leszállított mennyiség "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["status"], json!("approved"));
+        assert_eq!(body["data"]["discrepancies"][0]["kind"], json!("quantity"));
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_RECORDED: &str = "recorded";
+pub const STATUS_APPROVED: &str = "approved";
+pub const STATUS_REJECTED: &str = "rejected";
+
+pub const DISCREPANCY_QUANTITY: &str = "quantity";
+pub const DISCREPANCY_PRICE: &str = "price";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PurchaseInvoice {
+    pub id: Uuid,
+    pub invoice_number: String,
+    pub supplier_id: Uuid,
+    pub purchase_order_id: Uuid,
+    pub currency_code: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub notes: Option<String>,
+    pub status: String,
+    pub decided_by_id: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decision_note: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Invoice line together with its purchase order line, what has been received against it
+/// and what other (not rejected) invoices have already billed for it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PurchaseInvoiceLine {
+    pub id: Uuid,
+    pub purchase_invoice_id: Uuid,
+    pub purchase_order_line_id: Uuid,
+    pub product_id: Uuid,
+    pub item: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub net_amount: BigDecimal,
+    pub ordered_quantity: BigDecimal,
+    pub order_unit_price: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub invoiced_quantity: BigDecimal,
+}
+
+/// Purchase order line with the quantities received and invoiced against it so far
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct MatchingLine {
+    pub purchase_order_line_id: Uuid,
+    pub item: String,
+    pub ordered_quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub invoiced_quantity: BigDecimal,
+}
+
+fn uninvoiced(received_quantity: &BigDecimal, invoiced_quantity: &BigDecimal) -> BigDecimal {
+    let uninvoiced = received_quantity - invoiced_quantity;
+    if uninvoiced > BigDecimal::zero() {
+        uninvoiced
+    } else {
+        BigDecimal::zero()
+    }
+}
+
+impl MatchingLine {
+    pub fn uninvoiced_quantity(&self) -> BigDecimal {
+        uninvoiced(&self.received_quantity, &self.invoiced_quantity)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct MatchingSettings {
+    pub quantity_tolerance_percent: BigDecimal,
+    pub price_tolerance_percent: BigDecimal,
+    pub updated_by_id: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InvoiceDiscrepancy {
+    pub purchase_order_line_id: Uuid,
+    pub item: String,
+    pub kind: String,
+    pub expected: BigDecimal,
+    pub invoiced: BigDecimal,
+    /// Deviation from the expected value, not set when nothing was expected
+    pub deviation_percent: Option<BigDecimal>,
+}
+
+fn deviation_percent(expected: &BigDecimal, invoiced: &BigDecimal) -> Option<BigDecimal> {
+    (!expected.is_zero()).then(|| {
+        ((invoiced - expected) * BigDecimal::from(100) / expected)
+            .with_scale_round(2, RoundingMode::HalfUp)
+    })
+}
+
+fn exceeds(expected: &BigDecimal, deviation: &BigDecimal, tolerance_percent: &BigDecimal) -> bool {
+    deviation * BigDecimal::from(100) > expected * tolerance_percent
+}
+
+/// Three-way match of the invoice lines: the quantity billed may not exceed what was
+/// received and not yet invoiced elsewhere, and the unit price may not differ from the
+/// ordered one, both beyond the configured tolerance. Billing less than received is a
+/// partial invoice, not a discrepancy.
+pub fn discrepancies(
+    lines: &[PurchaseInvoiceLine],
+    settings: &MatchingSettings,
+) -> Vec<InvoiceDiscrepancy> {
+    let mut discrepancies = Vec::new();
+    for line in lines {
+        let uninvoiced = uninvoiced(&line.received_quantity, &line.invoiced_quantity);
+        let over_billed = &line.quantity - &uninvoiced;
+        if over_billed > BigDecimal::zero()
+            && exceeds(
+                &uninvoiced,
+                &over_billed,
+                &settings.quantity_tolerance_percent,
+            )
+        {
+            discrepancies.push(InvoiceDiscrepancy {
+                purchase_order_line_id: line.purchase_order_line_id,
+                item: line.item.clone(),
+                kind: DISCREPANCY_QUANTITY.to_string(),
+                deviation_percent: deviation_percent(&uninvoiced, &line.quantity),
+                expected: uninvoiced,
+                invoiced: line.quantity.clone(),
+            });
+        }
+        let price_difference = (&line.unit_price - &line.order_unit_price).abs();
+        if price_difference > BigDecimal::zero()
+            && exceeds(
+                &line.order_unit_price,
+                &price_difference,
+                &settings.price_tolerance_percent,
+            )
+        {
+            discrepancies.push(InvoiceDiscrepancy {
+                purchase_order_line_id: line.purchase_order_line_id,
+                item: line.item.clone(),
+                kind: DISCREPANCY_PRICE.to_string(),
+                expected: line.order_unit_price.clone(),
+                invoiced: line.unit_price.clone(),
+                deviation_percent: deviation_percent(&line.order_unit_price, &line.unit_price),
+            });
+        }
+    }
+    discrepancies
+}
+
+/// Purchase invoice with its lines and the discrepancies found by matching it against the
+/// order and the goods received, the total is in the currency of the order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PurchaseInvoiceDetails {
+    #[serde(flatten)]
+    pub purchase_invoice: PurchaseInvoice,
+    pub lines: Vec<PurchaseInvoiceLine>,
+    pub net_total: BigDecimal,
+    pub discrepancies: Vec<InvoiceDiscrepancy>,
+}
+
+impl PurchaseInvoiceDetails {
+    pub fn new(
+        purchase_invoice: PurchaseInvoice,
+        lines: Vec<PurchaseInvoiceLine>,
+        settings: &MatchingSettings,
+    ) -> Self {
+        let net_total = lines
+            .iter()
+            .fold(BigDecimal::zero(), |total, line| total + &line.net_amount);
+        Self {
+            discrepancies: discrepancies(&lines, settings),
+            purchase_invoice,
+            lines,
+            net_total,
+        }
+    }
+}
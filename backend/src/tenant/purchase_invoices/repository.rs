@@ -0,0 +1,360 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::purchase_invoices::dto::{MatchingSettingsInput, NewPurchaseInvoice};
+use crate::tenant::purchase_invoices::model::{
+    MatchingLine, MatchingSettings, PurchaseInvoice, PurchaseInvoiceLine,
+};
+use crate::tenant::purchase_orders::model::PurchaseOrder;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PurchaseInvoicesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PurchaseInvoice>;
+    /// Lines with their amounts rounded to `scale` decimal places, the scale of the invoice's
+    /// currency
+    async fn get_lines(
+        &self,
+        purchase_invoice_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<PurchaseInvoiceLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PurchaseInvoice>)>;
+    async fn get_purchase_order(&self, id: Uuid) -> RepositoryResult<PurchaseOrder>;
+    async fn get_matching_lines(
+        &self,
+        purchase_order_id: Uuid,
+    ) -> RepositoryResult<Vec<MatchingLine>>;
+    async fn insert(
+        &self,
+        input: &NewPurchaseInvoice,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseInvoice>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn decide(
+        &self,
+        id: Uuid,
+        status: &str,
+        note: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseInvoice>;
+    async fn get_settings(&self) -> RepositoryResult<MatchingSettings>;
+    async fn update_settings(
+        &self,
+        input: &MatchingSettingsInput,
+        sub: Uuid,
+    ) -> RepositoryResult<MatchingSettings>;
+}
+
+#[async_trait]
+impl PurchaseInvoicesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PurchaseInvoice> {
+        Ok(sqlx::query_as::<_, PurchaseInvoice>(
+            "SELECT * FROM purchase_invoices WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_lines(
+        &self,
+        purchase_invoice_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<PurchaseInvoiceLine>> {
+        // NOTE: only invoices recorded earlier count as already invoiced, so the matching of
+        // an invoice does not change once later invoices arrive for the same order
+        Ok(sqlx::query_as::<_, PurchaseInvoiceLine>(
+            r#"
+            SELECT purchase_invoice_lines.id,
+                   purchase_invoice_lines.purchase_invoice_id,
+                   purchase_invoice_lines.purchase_order_line_id,
+                   purchase_order_lines.product_id,
+                   products.name AS item,
+                   purchase_invoice_lines.quantity,
+                   purchase_invoice_lines.unit_price,
+                   round(purchase_invoice_lines.quantity * purchase_invoice_lines.unit_price,
+                         $2::integer) AS net_amount,
+                   purchase_order_lines.quantity AS ordered_quantity,
+                   purchase_order_lines.unit_price AS order_unit_price,
+                   COALESCE(received.quantity, 0) AS received_quantity,
+                   COALESCE(invoiced.quantity, 0) AS invoiced_quantity
+            FROM purchase_invoice_lines
+            JOIN purchase_invoices
+                ON purchase_invoice_lines.purchase_invoice_id = purchase_invoices.id
+            JOIN purchase_order_lines
+                ON purchase_invoice_lines.purchase_order_line_id = purchase_order_lines.id
+            JOIN products ON purchase_order_lines.product_id = products.id
+            LEFT JOIN LATERAL (
+                SELECT SUM(goods_receipt_lines.quantity) AS quantity
+                FROM goods_receipt_lines
+                WHERE goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            ) AS received ON true
+            LEFT JOIN LATERAL (
+                SELECT SUM(earlier_lines.quantity) AS quantity
+                FROM purchase_invoice_lines earlier_lines
+                JOIN purchase_invoices earlier
+                    ON earlier_lines.purchase_invoice_id = earlier.id
+                WHERE earlier_lines.purchase_order_line_id = purchase_order_lines.id
+                    AND earlier.id <> purchase_invoices.id
+                    AND earlier.created_at < purchase_invoices.created_at
+                    AND earlier.status <> 'rejected'
+                    AND earlier.deleted_at IS NULL
+            ) AS invoiced ON true
+            WHERE purchase_invoice_lines.purchase_invoice_id = $1
+            ORDER BY purchase_order_lines.position
+            "#,
+        )
+        .bind(purchase_invoice_id)
+        .bind(scale)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PurchaseInvoice>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM purchase_invoices WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let purchase_invoices = sqlx::query_as::<_, PurchaseInvoice>(
+            r#"
+            SELECT *
+            FROM purchase_invoices
+            WHERE deleted_at IS NULL
+            ORDER BY issue_date DESC, created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            purchase_invoices,
+        ))
+    }
+
+    async fn get_purchase_order(&self, id: Uuid) -> RepositoryResult<PurchaseOrder> {
+        Ok(sqlx::query_as::<_, PurchaseOrder>(
+            "SELECT * FROM purchase_orders WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_matching_lines(
+        &self,
+        purchase_order_id: Uuid,
+    ) -> RepositoryResult<Vec<MatchingLine>> {
+        Ok(sqlx::query_as::<_, MatchingLine>(
+            r#"
+            SELECT purchase_order_lines.id AS purchase_order_line_id,
+                   products.name AS item,
+                   purchase_order_lines.quantity AS ordered_quantity,
+                   purchase_order_lines.unit_price,
+                   COALESCE(received.quantity, 0) AS received_quantity,
+                   COALESCE(invoiced.quantity, 0) AS invoiced_quantity
+            FROM purchase_order_lines
+            JOIN products ON purchase_order_lines.product_id = products.id
+            LEFT JOIN LATERAL (
+                SELECT SUM(goods_receipt_lines.quantity) AS quantity
+                FROM goods_receipt_lines
+                WHERE goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            ) AS received ON true
+            LEFT JOIN LATERAL (
+                SELECT SUM(purchase_invoice_lines.quantity) AS quantity
+                FROM purchase_invoice_lines
+                JOIN purchase_invoices
+                    ON purchase_invoice_lines.purchase_invoice_id = purchase_invoices.id
+                WHERE purchase_invoice_lines.purchase_order_line_id = purchase_order_lines.id
+                    AND purchase_invoices.status <> 'rejected'
+                    AND purchase_invoices.deleted_at IS NULL
+            ) AS invoiced ON true
+            WHERE purchase_order_lines.purchase_order_id = $1
+            ORDER BY purchase_order_lines.position
+            "#,
+        )
+        .bind(purchase_order_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        input: &NewPurchaseInvoice,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseInvoice> {
+        let mut tx = self.begin().await?;
+        // NOTE: invoices of the same order are recorded one at a time, and stamped after the
+        // lock is taken, so that each one is matched against the ones invoiced before it
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM purchase_orders
+            WHERE id = $1
+                AND status <> 'draft'
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.purchase_order_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let purchase_invoice = sqlx::query_as::<_, PurchaseInvoice>(
+            r#"
+            INSERT INTO purchase_invoices (invoice_number, supplier_id, purchase_order_id,
+                                           currency_code, issue_date, due_date, notes,
+                                           created_by_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, clock_timestamp())
+            RETURNING *
+            "#,
+        )
+        .bind(&input.invoice_number)
+        .bind(input.supplier_id)
+        .bind(input.purchase_order_id)
+        .bind(&input.currency_code)
+        .bind(input.issue_date)
+        .bind(input.due_date)
+        .bind(&input.notes)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for line in &input.lines {
+            sqlx::query(
+                r#"
+                INSERT INTO purchase_invoice_lines (purchase_invoice_id, purchase_order_line_id,
+                                                    quantity, unit_price)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(purchase_invoice.id)
+            .bind(line.purchase_order_line_id)
+            .bind(&line.quantity)
+            .bind(&line.unit_price)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(purchase_invoice)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE purchase_invoices
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND status = 'recorded'
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn decide(
+        &self,
+        id: Uuid,
+        status: &str,
+        note: Option<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseInvoice> {
+        Ok(sqlx::query_as::<_, PurchaseInvoice>(
+            r#"
+            UPDATE purchase_invoices
+            SET status = $1,
+                decided_by_id = $2,
+                decided_at = NOW(),
+                decision_note = $3
+            WHERE id = $4
+                AND status = 'recorded'
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(sub)
+        .bind(note)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_settings(&self) -> RepositoryResult<MatchingSettings> {
+        Ok(sqlx::query_as::<_, MatchingSettings>(
+            r#"
+            SELECT quantity_tolerance_percent, price_tolerance_percent, updated_by_id, updated_at
+            FROM purchase_invoice_settings
+            "#,
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update_settings(
+        &self,
+        input: &MatchingSettingsInput,
+        sub: Uuid,
+    ) -> RepositoryResult<MatchingSettings> {
+        Ok(sqlx::query_as::<_, MatchingSettings>(
+            r#"
+            UPDATE purchase_invoice_settings
+            SET quantity_tolerance_percent = $1,
+                price_tolerance_percent = $2,
+                updated_by_id = $3
+            RETURNING quantity_tolerance_percent, price_tolerance_percent, updated_by_id,
+                      updated_at
+            "#,
+        )
+        .bind(&input.quantity_tolerance_percent)
+        .bind(&input.price_tolerance_percent)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,446 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::permissions::model::PURCHASE_INVOICES_APPROVE;
+use crate::tenant::permissions::service::has_permission;
+use crate::tenant::purchase_invoices::PurchaseInvoicesModuleInterface;
+use crate::tenant::purchase_invoices::dto::{
+    ApprovePurchaseInvoice, CreatePurchaseInvoice, MatchingSettingsInput, NewPurchaseInvoice,
+    NewPurchaseInvoiceLine, RejectPurchaseInvoice,
+};
+use crate::tenant::purchase_invoices::model::{
+    MatchingLine, MatchingSettings, PurchaseInvoice, PurchaseInvoiceDetails, STATUS_APPROVED,
+    STATUS_RECORDED, STATUS_REJECTED,
+};
+use crate::tenant::purchase_invoices::repository::PurchaseInvoicesRepository;
+use crate::tenant::purchase_orders::model::{PurchaseOrder, STATUS_DRAFT};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PurchaseInvoicesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PurchaseInvoicesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PurchaseInvoicesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PurchaseInvoicesServiceError> for AppError {
+    fn from(value: PurchaseInvoicesServiceError) -> Self {
+        match value {
+            PurchaseInvoicesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PurchaseInvoicesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            PurchaseInvoicesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PurchaseInvoicesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PurchaseInvoicesServiceResult<T> = Result<T, PurchaseInvoicesServiceError>;
+
+fn optional_text(value: &Option<String>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn build_invoice(
+    purchase_order: &PurchaseOrder,
+    matching_lines: &[MatchingLine],
+    payload: &CreatePurchaseInvoice,
+) -> PurchaseInvoicesServiceResult<NewPurchaseInvoice> {
+    let invoice_number = payload.invoice_number.trim();
+    if invoice_number.is_empty() || invoice_number.chars().count() > 100 {
+        return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+            "A számlaszám megadása kötelező és legfeljebb 100 karakter lehet!",
+        ));
+    }
+    let issue_date = payload
+        .issue_date
+        .unwrap_or_else(|| Utc::now().date_naive());
+    if payload.due_date < issue_date {
+        return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+            "A fizetési határidő nem lehet korábbi a számla keltezésénél!",
+        ));
+    }
+    let lines = if payload.lines.is_empty() {
+        matching_lines
+            .iter()
+            .filter(|line| line.uninvoiced_quantity() > BigDecimal::zero())
+            .map(|line| NewPurchaseInvoiceLine {
+                purchase_order_line_id: line.purchase_order_line_id,
+                quantity: line.uninvoiced_quantity(),
+                unit_price: line.unit_price.clone(),
+            })
+            .collect()
+    } else {
+        let mut lines: Vec<NewPurchaseInvoiceLine> = Vec::with_capacity(payload.lines.len());
+        for input in &payload.lines {
+            let line = matching_lines
+                .iter()
+                .find(|line| line.purchase_order_line_id == input.purchase_order_line_id)
+                .ok_or(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "A tétel nem ehhez a beszerzési rendeléshez tartozik!",
+                ))?;
+            if lines
+                .iter()
+                .any(|other| other.purchase_order_line_id == line.purchase_order_line_id)
+            {
+                return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "Egy rendelési tétel csak egyszer szerepelhet a számlán!",
+                ));
+            }
+            if input.quantity <= BigDecimal::zero() {
+                return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "A számlázott mennyiségnek pozitív számnak kell lennie!",
+                ));
+            }
+            let unit_price = input
+                .unit_price
+                .clone()
+                .unwrap_or_else(|| line.unit_price.clone());
+            if unit_price < BigDecimal::zero() {
+                return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "Az egységár nem lehet negatív!",
+                ));
+            }
+            lines.push(NewPurchaseInvoiceLine {
+                purchase_order_line_id: line.purchase_order_line_id,
+                quantity: input.quantity.clone(),
+                unit_price,
+            });
+        }
+        lines
+    };
+    if lines.is_empty() {
+        return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+            "A beszerzési rendelésen nincs számlázható tétel!",
+        ));
+    }
+    Ok(NewPurchaseInvoice {
+        invoice_number: invoice_number.to_string(),
+        supplier_id: purchase_order.supplier_id,
+        purchase_order_id: purchase_order.id,
+        currency_code: purchase_order.currency_code.clone(),
+        issue_date,
+        due_date: payload.due_date,
+        notes: optional_text(&payload.notes),
+        lines,
+    })
+}
+
+async fn details(
+    repo: &dyn PurchaseInvoicesRepository,
+    purchase_invoice: PurchaseInvoice,
+) -> PurchaseInvoicesServiceResult<PurchaseInvoiceDetails> {
+    let settings = repo.get_settings().await?;
+    let lines = repo
+        .get_lines(
+            purchase_invoice.id,
+            CurrencyRules::of(&purchase_invoice.currency_code).scale,
+        )
+        .await?;
+    Ok(PurchaseInvoiceDetails::new(
+        purchase_invoice,
+        lines,
+        &settings,
+    ))
+}
+
+pub trait PurchaseInvoicesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<PurchaseInvoiceDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<(PaginatorMeta, Vec<PurchaseInvoice>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreatePurchaseInvoice,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<PurchaseInvoiceDetails>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = PurchaseInvoicesServiceResult<()>> + Send;
+    fn approve(
+        &self,
+        payload: &ApprovePurchaseInvoice,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<PurchaseInvoiceDetails>> + Send;
+    fn reject(
+        &self,
+        payload: &RejectPurchaseInvoice,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<PurchaseInvoice>> + Send;
+    fn get_settings(
+        &self,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<MatchingSettings>> + Send;
+    fn update_settings(
+        &self,
+        payload: &MatchingSettingsInput,
+    ) -> impl Future<Output = PurchaseInvoicesServiceResult<MatchingSettings>> + Send;
+    fn approver_repo(
+        &self,
+    ) -> impl Future<
+        Output = PurchaseInvoicesServiceResult<Arc<dyn PurchaseInvoicesRepository + Send + Sync>>,
+    > + Send;
+}
+
+impl<'a, T> PurchaseInvoicesService for Service<'a, T>
+where
+    T: PurchaseInvoicesModuleInterface,
+{
+    /// Approving invoices for payment and setting the matching tolerances are reserved for
+    /// users with the corresponding permission
+    async fn approver_repo(
+        &self,
+    ) -> PurchaseInvoicesServiceResult<Arc<dyn PurchaseInvoicesRepository + Send + Sync>> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(PurchaseInvoicesServiceError::Unauthorized)?;
+        if !has_permission(
+            self.module(),
+            tenant_id,
+            claims.sub(),
+            PURCHASE_INVOICES_APPROVE,
+        )
+        .await?
+        {
+            return Err(PurchaseInvoicesServiceError::Forbidden);
+        }
+        Ok(self.module().purchase_invoices_repo(tenant_id)?)
+    }
+
+    async fn get(&self, id: Uuid) -> PurchaseInvoicesServiceResult<PurchaseInvoiceDetails> {
+        let repo = self.module().purchase_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseInvoicesServiceError::Unauthorized)?,
+        )?;
+        details(&*repo, repo.get_by_id(id).await?).await
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> PurchaseInvoicesServiceResult<(PaginatorMeta, Vec<PurchaseInvoice>)> {
+        Ok(self
+            .module()
+            .purchase_invoices_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PurchaseInvoicesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreatePurchaseInvoice,
+    ) -> PurchaseInvoicesServiceResult<PurchaseInvoiceDetails> {
+        let repo = self.module().purchase_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseInvoicesServiceError::Unauthorized)?,
+        )?;
+        let purchase_order = repo.get_purchase_order(payload.purchase_order_id).await?;
+        if purchase_order.status == STATUS_DRAFT {
+            return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                "Piszkozat állapotú beszerzési rendeléshez nem rögzíthető számla!",
+            ));
+        }
+        let matching_lines = repo.get_matching_lines(purchase_order.id).await?;
+        let input = build_invoice(&purchase_order, &matching_lines, payload)?;
+        let purchase_invoice = repo
+            .insert(&input, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    PurchaseInvoicesServiceError::UnprocessableEntry(
+                        "Ez a számla már rögzítve van a beszállítótól!",
+                    )
+                } else {
+                    e.into()
+                }
+            })?;
+        details(&*repo, purchase_invoice).await
+    }
+
+    async fn delete(&self, id: Uuid) -> PurchaseInvoicesServiceResult<()> {
+        let repo = self.module().purchase_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseInvoicesServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(id).await?.status != STATUS_RECORDED {
+            return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                "Csak jóváhagyásra váró számla törölhető!",
+            ));
+        }
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn approve(
+        &self,
+        payload: &ApprovePurchaseInvoice,
+    ) -> PurchaseInvoicesServiceResult<PurchaseInvoiceDetails> {
+        let repo = self.approver_repo().await?;
+        let current = details(&*repo, repo.get_by_id(payload.purchase_invoice_id).await?).await?;
+        if current.purchase_invoice.status != STATUS_RECORDED {
+            return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                "Csak jóváhagyásra váró számla hagyható jóvá!",
+            ));
+        }
+        let note = optional_text(&payload.note);
+        if !current.discrepancies.is_empty() {
+            if !payload.accept_discrepancies {
+                return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "A számla eltér a rendeléstől vagy az átvételtől, az eltéréseket el kell fogadni!",
+                ));
+            }
+            if note.is_none() {
+                return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                    "Az eltérések elfogadásához indoklás megadása kötelező!",
+                ));
+            }
+        }
+        let purchase_invoice = repo
+            .decide(
+                current.purchase_invoice.id,
+                STATUS_APPROVED,
+                note,
+                self.claims()?.sub(),
+            )
+            .await?;
+        Ok(PurchaseInvoiceDetails {
+            purchase_invoice,
+            ..current
+        })
+    }
+
+    async fn reject(
+        &self,
+        payload: &RejectPurchaseInvoice,
+    ) -> PurchaseInvoicesServiceResult<PurchaseInvoice> {
+        let repo = self.approver_repo().await?;
+        if repo.get_by_id(payload.purchase_invoice_id).await?.status != STATUS_RECORDED {
+            return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                "Csak jóváhagyásra váró számla utasítható el!",
+            ));
+        }
+        Ok(repo
+            .decide(
+                payload.purchase_invoice_id,
+                STATUS_REJECTED,
+                optional_text(&payload.note),
+                self.claims()?.sub(),
+            )
+            .await?)
+    }
+
+    async fn get_settings(&self) -> PurchaseInvoicesServiceResult<MatchingSettings> {
+        Ok(self
+            .module()
+            .purchase_invoices_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PurchaseInvoicesServiceError::Unauthorized)?,
+            )?
+            .get_settings()
+            .await?)
+    }
+
+    async fn update_settings(
+        &self,
+        payload: &MatchingSettingsInput,
+    ) -> PurchaseInvoicesServiceResult<MatchingSettings> {
+        let repo = self.approver_repo().await?;
+        let valid = |value: &BigDecimal| *value >= 0 && *value <= 100;
+        if !valid(&payload.quantity_tolerance_percent) || !valid(&payload.price_tolerance_percent) {
+            return Err(PurchaseInvoicesServiceError::UnprocessableEntry(
+                "A tűréshatárnak 0 és 100 százalék között kell lennie!",
+            ));
+        }
+        Ok(repo.update_settings(payload, self.claims()?.sub()).await?)
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PurchaseOrderLineInput {
+    pub product_id: Uuid,
+    pub supplier_sku: Option<String>,
+    pub description: Option<String>,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub expected_delivery_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PurchaseOrderInput {
+    pub id: Option<Uuid>,
+    pub supplier_id: Uuid,
+    pub warehouse_id: Uuid,
+    /// The default currency of the tenant when empty
+    #[serde(default)]
+    pub currency_code: String,
+    pub order_date: Option<NaiveDate>,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub supplier_reference: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub lines: Vec<PurchaseOrderLineInput>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PurchaseOrderStatusInput {
+    pub purchase_order_id: Uuid,
+    pub status: String,
+}
+
+/// Order confirmation received from the supplier after the order was sent
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ConfirmPurchaseOrder {
+    pub purchase_order_id: Uuid,
+    pub supplier_reference: Option<String>,
+    pub expected_delivery_date: Option<NaiveDate>,
+}
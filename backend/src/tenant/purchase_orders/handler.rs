@@ -0,0 +1,543 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::purchase_orders::PurchaseOrdersModuleInterface;
+use crate::tenant::purchase_orders::dto::{
+    ConfirmPurchaseOrder, PurchaseOrderInput, PurchaseOrderStatusInput,
+};
+use crate::tenant::purchase_orders::service::PurchaseOrdersService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PurchaseOrderInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PurchaseOrderInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A beszerzési rendelés törlése sikeresen megtörtént",
+            ))
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_status<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<PurchaseOrderStatusInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let result = map_handler_err(
+        service.set_status(&payload).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn confirm<M: PurchaseOrdersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(purchase_orders_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ConfirmPurchaseOrder>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), purchase_orders_module.clone());
+    let result = map_handler_err(
+        service.confirm(&payload).await,
+        purchase_orders_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        purchase_orders_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::currencies::tests::currencies_repo;
+    use crate::tenant::purchase_orders::model::{PurchaseOrder, PurchaseOrderLine};
+    use crate::tenant::purchase_orders::{
+        self, repository::MockPurchaseOrdersRepository, tests::MockPurchaseOrdersModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockPurchaseOrdersRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let currencies_repo = Arc::new(currencies_repo("HUF", &["HUF", "EUR"]));
+        let mut purchase_orders_module = MockPurchaseOrdersModule::new();
+        purchase_orders_module
+            .expect_purchase_orders_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        purchase_orders_module
+            .expect_currencies_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(currencies_repo.clone()));
+        purchase_orders_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(purchase_orders::routes::routes(Arc::new(
+                purchase_orders_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn purchase_order(status: &str) -> PurchaseOrder {
+        PurchaseOrder {
+            id: Uuid::new_v4(),
+            order_number: "BR-2026-00001".to_string(),
+            supplier_id: Uuid::new_v4(),
+            warehouse_id: Uuid::new_v4(),
+            currency_code: "EUR".to_string(),
+            order_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            expected_delivery_date: None,
+            supplier_reference: None,
+            notes: None,
+            status: status.to_string(),
+            sent_at: None,
+            confirmed_at: None,
+            closed_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn line(purchase_order_id: Uuid, net: &str, tax: &str, gross: &str) -> PurchaseOrderLine {
+        PurchaseOrderLine {
+            id: Uuid::new_v4(),
+            purchase_order_id,
+            product_id: Uuid::new_v4(),
+            item: "Csőbilincs".to_string(),
+            supplier_sku: Some("CB-22".to_string()),
+            description: "Csőbilincs".to_string(),
+            quantity: BigDecimal::from(1),
+            unit_price: net.parse().unwrap(),
+            tax_id: Uuid::new_v4(),
+            tax_rate: BigDecimal::from(27),
+            net_amount: net.parse().unwrap(),
+            tax_amount: tax.parse().unwrap(),
+            gross_amount: gross.parse().unwrap(),
+            received_quantity: BigDecimal::from(0),
+            expected_delivery_date: None,
+            position: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_totals_in_order_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("sent");
+        let lines = vec![
+            line(purchase_order.id, "120.00", "32.40", "152.40"),
+            line(purchase_order.id, "15.50", "4.19", "19.69"),
+        ];
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(purchase_order.id))
+            .returning({
+                let purchase_order = purchase_order.clone();
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_get_lines()
+            .times(1)
+            .with(eq(purchase_order.id), eq(2))
+            .returning({
+                let lines = lines.clone();
+                move |_, _| Ok(lines.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/purchase_orders/get?uuid={}", purchase_order.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["net_total"], json!("135.50"));
+        assert_eq!(body["data"]["tax_total"], json!("36.59"));
+        assert_eq!(body["data"]["gross_total"], json!("172.09"));
+        assert_eq!(body["data"]["currency_code"], json!("EUR"));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_delivery_date_before_order_date() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_orders/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "supplier_id": Uuid::new_v4(),
+                    "warehouse_id": Uuid::new_v4(),
+                    "currency_code": "eur",
+                    "order_date": "2026-10-10",
+                    "expected_delivery_date": null,
+                    "supplier_reference": null,
+                    "notes": null,
+                    "lines": [{
+                        "product_id": Uuid::new_v4(),
+                        "supplier_sku": null,
+                        "description": null,
+                        "quantity": "10",
+                        "unit_price": "12.00",
+                        "tax_id": Uuid::new_v4(),
+                        "expected_delivery_date": "2026-10-05"
+                    }]
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_normalizes_input() {
+        let active_tenant_id = Uuid::new_v4();
+        let created = purchase_order("draft");
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_count_unorderable_products()
+            .times(1)
+            .returning(|_| Ok(0));
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| {
+                input.currency_code == "EUR"
+                    && input.order_date.is_some()
+                    && input.supplier_reference.is_none()
+                    && input.lines[0].supplier_sku.as_deref() == Some("CB-22")
+                    && input.lines[0].description.is_none()
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _| Ok(created.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_orders/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "supplier_id": created.supplier_id,
+                    "warehouse_id": created.warehouse_id,
+                    "currency_code": " eur ",
+                    "order_date": null,
+                    "expected_delivery_date": null,
+                    "supplier_reference": "  ",
+                    "notes": null,
+                    "lines": [{
+                        "product_id": Uuid::new_v4(),
+                        "supplier_sku": " CB-22 ",
+                        "description": " ",
+                        "quantity": "10",
+                        "unit_price": "12.00",
+                        "tax_id": Uuid::new_v4(),
+                        "expected_delivery_date": null
+                    }]
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": created})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_discontinued_product() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_count_unorderable_products()
+            .withf(move |product_ids| product_ids == [product_id])
+            .times(1)
+            .returning(|_| Ok(1));
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/purchase_orders/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "supplier_id": Uuid::new_v4(),
+                    "warehouse_id": Uuid::new_v4(),
+                    "currency_code": "HUF",
+                    "order_date": null,
+                    "expected_delivery_date": null,
+                    "supplier_reference": null,
+                    "notes": null,
+                    "lines": [{
+                        "product_id": product_id,
+                        "supplier_sku": null,
+                        "description": null,
+                        "quantity": "10",
+                        "unit_price": "12.00",
+                        "tax_id": Uuid::new_v4(),
+                        "expected_delivery_date": null
+                    }]
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_status_rejects_closing_draft() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("draft");
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(purchase_order.id))
+            .returning({
+                let purchase_order = purchase_order.clone();
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_set_status().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/purchase_orders/set_status",
+                active_tenant_id,
+                Some(json!({"purchase_order_id": purchase_order.id, "status": "closed"})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_requires_sent_order() {
+        let active_tenant_id = Uuid::new_v4();
+        let purchase_order = purchase_order("draft");
+        let mut repo = MockPurchaseOrdersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(purchase_order.id))
+            .returning({
+                let purchase_order = purchase_order.clone();
+                move |_| Ok(purchase_order.clone())
+            });
+        repo.expect_confirm().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/purchase_orders/confirm",
+                active_tenant_id,
+                Some(json!({
+                    "purchase_order_id": purchase_order.id,
+                    "supplier_reference": "VR-55812",
+                    "expected_delivery_date": "2026-10-20"
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
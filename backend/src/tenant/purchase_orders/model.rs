@@ -0,0 +1,112 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_SENT: &str = "sent";
+pub const STATUS_PARTIALLY_RECEIVED: &str = "partially_received";
+pub const STATUS_CLOSED: &str = "closed";
+
+/// Only sending and closing are manual steps, partial receipt is recorded by the goods
+/// receiving flow. An order can be closed early when the rest will not be delivered.
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (STATUS_DRAFT, STATUS_SENT) | (STATUS_SENT | STATUS_PARTIALLY_RECEIVED, STATUS_CLOSED)
+    )
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PurchaseOrder {
+    pub id: Uuid,
+    pub order_number: String,
+    pub supplier_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub currency_code: String,
+    pub order_date: NaiveDate,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub supplier_reference: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PurchaseOrderLine {
+    pub id: Uuid,
+    pub purchase_order_id: Uuid,
+    pub product_id: Uuid,
+    pub item: String,
+    pub supplier_sku: Option<String>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub position: i32,
+}
+
+/// Purchase order with its lines, the totals are in the currency of the order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PurchaseOrderDetails {
+    #[serde(flatten)]
+    pub purchase_order: PurchaseOrder,
+    pub lines: Vec<PurchaseOrderLine>,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub gross_total: BigDecimal,
+}
+
+impl PurchaseOrderDetails {
+    pub fn new(purchase_order: PurchaseOrder, lines: Vec<PurchaseOrderLine>) -> Self {
+        let (net_total, tax_total, gross_total) = lines.iter().fold(
+            (BigDecimal::zero(), BigDecimal::zero(), BigDecimal::zero()),
+            |(net, tax, gross), line| {
+                (
+                    net + &line.net_amount,
+                    tax + &line.tax_amount,
+                    gross + &line.gross_amount,
+                )
+            },
+        );
+        Self {
+            purchase_order,
+            lines,
+            net_total,
+            tax_total,
+            gross_total,
+        }
+    }
+}
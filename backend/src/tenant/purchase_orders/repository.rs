@@ -0,0 +1,330 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::purchase_orders::dto::{ConfirmPurchaseOrder, PurchaseOrderInput};
+use crate::tenant::purchase_orders::model::{PurchaseOrder, PurchaseOrderLine};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PurchaseOrdersRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PurchaseOrder>;
+    /// Lines with their amounts rounded to `scale` decimal places, the scale of the order's currency
+    async fn get_lines(
+        &self,
+        purchase_order_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<PurchaseOrderLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PurchaseOrder>)>;
+    async fn insert(
+        &self,
+        input: &PurchaseOrderInput,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseOrder>;
+    async fn update(&self, id: Uuid, input: &PurchaseOrderInput)
+    -> RepositoryResult<PurchaseOrder>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<PurchaseOrder>;
+    async fn confirm(&self, input: &ConfirmPurchaseOrder) -> RepositoryResult<PurchaseOrder>;
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64>;
+}
+
+// NOTE: the supplier's item number defaults to the one recorded on the product's supplier
+async fn insert_lines(
+    tx: &mut Transaction<'_, Postgres>,
+    purchase_order_id: Uuid,
+    input: &PurchaseOrderInput,
+) -> RepositoryResult<()> {
+    for (position, line) in input.lines.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO purchase_order_lines (purchase_order_id, product_id, supplier_sku,
+                                              description, quantity, unit_price, tax_id,
+                                              expected_delivery_date, position)
+            SELECT $1, $2, COALESCE($3, product_suppliers.supplier_sku),
+                   COALESCE($4, products.name, ''), $5, $6, $7, $8, $9
+            FROM (VALUES (1)) AS line
+            LEFT JOIN products ON products.id = $2
+            LEFT JOIN product_suppliers ON product_suppliers.product_id = $2
+                AND product_suppliers.supplier_id = $10
+            "#,
+        )
+        .bind(purchase_order_id)
+        .bind(line.product_id)
+        .bind(&line.supplier_sku)
+        .bind(&line.description)
+        .bind(&line.quantity)
+        .bind(&line.unit_price)
+        .bind(line.tax_id)
+        .bind(line.expected_delivery_date)
+        .bind(i32::try_from(position)?)
+        .bind(input.supplier_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl PurchaseOrdersRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<PurchaseOrder> {
+        Ok(sqlx::query_as::<_, PurchaseOrder>(
+            "SELECT * FROM purchase_orders WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_lines(
+        &self,
+        purchase_order_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<PurchaseOrderLine>> {
+        Ok(sqlx::query_as::<_, PurchaseOrderLine>(
+            r#"
+            SELECT purchase_order_lines.id,
+                   purchase_order_lines.purchase_order_id,
+                   purchase_order_lines.product_id,
+                   products.name AS item,
+                   purchase_order_lines.supplier_sku,
+                   purchase_order_lines.description,
+                   purchase_order_lines.quantity,
+                   purchase_order_lines.unit_price,
+                   purchase_order_lines.tax_id,
+                   amounts.tax_rate,
+                   amounts.net_amount,
+                   amounts.tax_amount,
+                   amounts.net_amount + amounts.tax_amount AS gross_amount,
+                   COALESCE(received.quantity, 0) AS received_quantity,
+                   purchase_order_lines.expected_delivery_date,
+                   purchase_order_lines.position
+            FROM purchase_order_lines
+            JOIN products ON purchase_order_lines.product_id = products.id
+            JOIN taxes ON purchase_order_lines.tax_id = taxes.id
+            CROSS JOIN LATERAL (
+                SELECT COALESCE(taxes.rate, 0) AS tax_rate,
+                       round(purchase_order_lines.quantity * purchase_order_lines.unit_price,
+                             $2::integer) AS net_amount,
+                       CASE
+                           WHEN taxes.is_rate_applicable
+                               THEN round(purchase_order_lines.quantity
+                                              * purchase_order_lines.unit_price
+                                              * COALESCE(taxes.rate, 0) / 100, $2::integer)
+                           ELSE 0
+                       END AS tax_amount
+            ) AS amounts
+            LEFT JOIN LATERAL (
+                SELECT SUM(goods_receipt_lines.quantity) AS quantity
+                FROM goods_receipt_lines
+                WHERE goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            ) AS received ON true
+            WHERE purchase_order_lines.purchase_order_id = $1
+            ORDER BY purchase_order_lines.position
+            "#,
+        )
+        .bind(purchase_order_id)
+        .bind(scale)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<PurchaseOrder>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM purchase_orders WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let purchase_orders = sqlx::query_as::<_, PurchaseOrder>(
+            r#"
+            SELECT *
+            FROM purchase_orders
+            WHERE deleted_at IS NULL
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            purchase_orders,
+        ))
+    }
+
+    async fn insert(
+        &self,
+        input: &PurchaseOrderInput,
+        sub: Uuid,
+    ) -> RepositoryResult<PurchaseOrder> {
+        let mut tx = self.begin().await?;
+        let purchase_order = sqlx::query_as::<_, PurchaseOrder>(
+            r#"
+            INSERT INTO purchase_orders (order_number, supplier_id, warehouse_id, currency_code,
+                                         order_date, expected_delivery_date, supplier_reference,
+                                         notes, created_by_id)
+            VALUES ('BR-' || to_char(CURRENT_DATE, 'YYYY') || '-'
+                        || lpad(nextval('purchase_order_number_seq')::text, 5, '0'),
+                    $1, $2, $3, COALESCE($4, CURRENT_DATE), $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(input.supplier_id)
+        .bind(input.warehouse_id)
+        .bind(&input.currency_code)
+        .bind(input.order_date)
+        .bind(input.expected_delivery_date)
+        .bind(&input.supplier_reference)
+        .bind(&input.notes)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_lines(&mut tx, purchase_order.id, input).await?;
+        tx.commit().await?;
+        Ok(purchase_order)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &PurchaseOrderInput,
+    ) -> RepositoryResult<PurchaseOrder> {
+        let mut tx = self.begin().await?;
+        let purchase_order = sqlx::query_as::<_, PurchaseOrder>(
+            r#"
+            UPDATE purchase_orders
+            SET supplier_id = $1,
+                warehouse_id = $2,
+                currency_code = $3,
+                order_date = COALESCE($4, order_date),
+                expected_delivery_date = $5,
+                supplier_reference = $6,
+                notes = $7
+            WHERE id = $8
+                AND status = 'draft'
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.supplier_id)
+        .bind(input.warehouse_id)
+        .bind(&input.currency_code)
+        .bind(input.order_date)
+        .bind(input.expected_delivery_date)
+        .bind(&input.supplier_reference)
+        .bind(&input.notes)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM purchase_order_lines WHERE purchase_order_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        insert_lines(&mut tx, id, input).await?;
+        tx.commit().await?;
+        Ok(purchase_order)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE purchase_orders
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND status = 'draft'
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<PurchaseOrder> {
+        Ok(sqlx::query_as::<_, PurchaseOrder>(
+            r#"
+            UPDATE purchase_orders
+            SET status = $1,
+                sent_at = CASE WHEN $1 = 'sent' THEN NOW() ELSE sent_at END,
+                closed_at = CASE WHEN $1 = 'closed' THEN NOW() ELSE closed_at END
+            WHERE id = $2
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn confirm(&self, input: &ConfirmPurchaseOrder) -> RepositoryResult<PurchaseOrder> {
+        Ok(sqlx::query_as::<_, PurchaseOrder>(
+            r#"
+            UPDATE purchase_orders
+            SET supplier_reference = COALESCE($1, supplier_reference),
+                expected_delivery_date = COALESCE($2, expected_delivery_date),
+                confirmed_at = NOW()
+            WHERE id = $3
+                AND status IN ('sent', 'partially_received')
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.supplier_reference)
+        .bind(input.expected_delivery_date)
+        .bind(input.purchase_order_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM products WHERE id = ANY($1) AND status <> 'active'",
+        )
+        .bind(product_ids)
+        .fetch_one(self)
+        .await?)
+    }
+}
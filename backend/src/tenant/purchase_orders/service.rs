@@ -0,0 +1,391 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::currencies::service::{CurrenciesServiceError, document_currency};
+use crate::tenant::purchase_orders::PurchaseOrdersModuleInterface;
+use crate::tenant::purchase_orders::dto::{
+    ConfirmPurchaseOrder, PurchaseOrderInput, PurchaseOrderStatusInput,
+};
+use crate::tenant::purchase_orders::model::{
+    PurchaseOrder, PurchaseOrderDetails, STATUS_DRAFT, STATUS_PARTIALLY_RECEIVED, STATUS_SENT,
+    can_transition,
+};
+use crate::tenant::purchase_orders::repository::PurchaseOrdersRepository;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum PurchaseOrdersServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Currencies(#[from] CurrenciesServiceError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for PurchaseOrdersServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => PurchaseOrdersServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<PurchaseOrdersServiceError> for AppError {
+    fn from(value: PurchaseOrdersServiceError) -> Self {
+        match value {
+            PurchaseOrdersServiceError::Currencies(error) => error.into(),
+            PurchaseOrdersServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PurchaseOrdersServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            PurchaseOrdersServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type PurchaseOrdersServiceResult<T> = Result<T, PurchaseOrdersServiceError>;
+
+fn optional_text(
+    value: &Option<String>,
+    max_length: usize,
+    message: &'static str,
+) -> PurchaseOrdersServiceResult<Option<String>> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) if value.chars().count() > max_length => {
+            Err(PurchaseOrdersServiceError::UnprocessableEntry(message))
+        }
+        Some(value) => Ok(Some(value.to_string())),
+    }
+}
+
+fn validate_purchase_order(
+    payload: &PurchaseOrderInput,
+    currency_code: String,
+) -> PurchaseOrdersServiceResult<PurchaseOrderInput> {
+    let order_date = payload
+        .order_date
+        .unwrap_or_else(|| Utc::now().date_naive());
+    if payload
+        .expected_delivery_date
+        .is_some_and(|date| date < order_date)
+    {
+        return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+            "A várható szállítási dátum nem lehet korábbi a rendelés dátumánál!",
+        ));
+    }
+    if payload.lines.is_empty() {
+        return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+            "A beszerzési rendelésnek legalább egy tételt tartalmaznia kell!",
+        ));
+    }
+    let mut lines = Vec::with_capacity(payload.lines.len());
+    for line in &payload.lines {
+        if line.quantity <= BigDecimal::zero() {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "A mennyiségnek pozitív számnak kell lennie!",
+            ));
+        }
+        if line.unit_price < BigDecimal::zero() {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "Az egységár nem lehet negatív!",
+            ));
+        }
+        if line
+            .expected_delivery_date
+            .is_some_and(|date| date < order_date)
+        {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "A várható szállítási dátum nem lehet korábbi a rendelés dátumánál!",
+            ));
+        }
+        let mut line = line.clone();
+        line.supplier_sku = optional_text(
+            &line.supplier_sku,
+            100,
+            "A beszállítói cikkszám legfeljebb 100 karakter lehet!",
+        )?;
+        line.description = line
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+        lines.push(line);
+    }
+    Ok(PurchaseOrderInput {
+        currency_code,
+        order_date: Some(order_date),
+        supplier_reference: optional_text(
+            &payload.supplier_reference,
+            100,
+            "A beszállítói hivatkozás legfeljebb 100 karakter lehet!",
+        )?,
+        notes: payload
+            .notes
+            .as_ref()
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        lines,
+        ..payload.clone()
+    })
+}
+
+async fn check_orderable_products(
+    repo: &(dyn PurchaseOrdersRepository + Send + Sync),
+    input: &PurchaseOrderInput,
+) -> PurchaseOrdersServiceResult<()> {
+    let product_ids: Vec<Uuid> = input.lines.iter().map(|line| line.product_id).collect();
+    if !product_ids.is_empty() && repo.count_unorderable_products(&product_ids).await? > 0 {
+        return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+            "Csak aktív termék rendelhető!",
+        ));
+    }
+    Ok(())
+}
+
+fn map_reference_error(e: RepositoryError) -> PurchaseOrdersServiceError {
+    if e.is_foreign_key_violation() {
+        PurchaseOrdersServiceError::UnprocessableEntry(
+            "A megadott beszállító, raktár, termék, adó vagy pénznem nem létezik!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+pub trait PurchaseOrdersService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<PurchaseOrderDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<(PaginatorMeta, Vec<PurchaseOrder>)>> + Send;
+    fn create(
+        &self,
+        payload: &PurchaseOrderInput,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<PurchaseOrder>> + Send;
+    fn update(
+        &self,
+        payload: &PurchaseOrderInput,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<PurchaseOrder>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = PurchaseOrdersServiceResult<()>> + Send;
+    fn set_status(
+        &self,
+        payload: &PurchaseOrderStatusInput,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<PurchaseOrder>> + Send;
+    fn confirm(
+        &self,
+        payload: &ConfirmPurchaseOrder,
+    ) -> impl Future<Output = PurchaseOrdersServiceResult<PurchaseOrder>> + Send;
+}
+
+impl<'a, T> PurchaseOrdersService for Service<'a, T>
+where
+    T: PurchaseOrdersModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> PurchaseOrdersServiceResult<PurchaseOrderDetails> {
+        let repo = self.module().purchase_orders_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseOrdersServiceError::Unauthorized)?,
+        )?;
+        let purchase_order = repo.get_by_id(id).await?;
+        let lines = repo
+            .get_lines(id, CurrencyRules::of(&purchase_order.currency_code).scale)
+            .await?;
+        Ok(PurchaseOrderDetails::new(purchase_order, lines))
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> PurchaseOrdersServiceResult<(PaginatorMeta, Vec<PurchaseOrder>)> {
+        Ok(self
+            .module()
+            .purchase_orders_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(PurchaseOrdersServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &PurchaseOrderInput,
+    ) -> PurchaseOrdersServiceResult<PurchaseOrder> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(PurchaseOrdersServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_purchase_order(payload, currency_code)?;
+        let repo = self.module().purchase_orders_repo(tenant_id)?;
+        check_orderable_products(&*repo, &input).await?;
+        repo.insert(&input, self.claims()?.sub())
+            .await
+            .map_err(map_reference_error)
+    }
+
+    async fn update(
+        &self,
+        payload: &PurchaseOrderInput,
+    ) -> PurchaseOrdersServiceResult<PurchaseOrder> {
+        let id = payload
+            .id
+            .ok_or(PurchaseOrdersServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(PurchaseOrdersServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_purchase_order(payload, currency_code)?;
+        let repo = self.module().purchase_orders_repo(tenant_id)?;
+        if repo.get_by_id(id).await?.status != STATUS_DRAFT {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú beszerzési rendelés módosítható!",
+            ));
+        }
+        check_orderable_products(&*repo, &input).await?;
+        repo.update(id, &input).await.map_err(map_reference_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> PurchaseOrdersServiceResult<()> {
+        let repo = self.module().purchase_orders_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseOrdersServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(id).await?.status != STATUS_DRAFT {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú beszerzési rendelés törölhető!",
+            ));
+        }
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn set_status(
+        &self,
+        payload: &PurchaseOrderStatusInput,
+    ) -> PurchaseOrdersServiceResult<PurchaseOrder> {
+        let repo = self.module().purchase_orders_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseOrdersServiceError::Unauthorized)?,
+        )?;
+        let purchase_order = repo.get_by_id(payload.purchase_order_id).await?;
+        if !can_transition(&purchase_order.status, &payload.status) {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "A beszerzési rendelés ebből az állapotból nem vihető a kért állapotba!",
+            ));
+        }
+        Ok(repo.set_status(purchase_order.id, &payload.status).await?)
+    }
+
+    async fn confirm(
+        &self,
+        payload: &ConfirmPurchaseOrder,
+    ) -> PurchaseOrdersServiceResult<PurchaseOrder> {
+        let repo = self.module().purchase_orders_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(PurchaseOrdersServiceError::Unauthorized)?,
+        )?;
+        let purchase_order = repo.get_by_id(payload.purchase_order_id).await?;
+        if purchase_order.status != STATUS_SENT
+            && purchase_order.status != STATUS_PARTIALLY_RECEIVED
+        {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "Csak elküldött, nyitott beszerzési rendelés igazolható vissza!",
+            ));
+        }
+        if payload
+            .expected_delivery_date
+            .is_some_and(|date| date < purchase_order.order_date)
+        {
+            return Err(PurchaseOrdersServiceError::UnprocessableEntry(
+                "A várható szállítási dátum nem lehet korábbi a rendelés dátumánál!",
+            ));
+        }
+        let input = ConfirmPurchaseOrder {
+            supplier_reference: optional_text(
+                &payload.supplier_reference,
+                100,
+                "A beszállítói hivatkozás legfeljebb 100 karakter lehet!",
+            )?,
+            ..payload.clone()
+        };
+        Ok(repo.confirm(&input).await?)
+    }
+}
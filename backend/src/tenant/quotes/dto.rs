@@ -0,0 +1,164 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::pdf::{format_date, format_money, format_number, format_quantity};
+use crate::tenant::document_settings::dto::Letterhead;
+use crate::tenant::document_settings::model::DocumentRecipient;
+use crate::tenant::quotes::model::{QuoteDetails, QuoteLine};
+use crate::tenant::taxes::vat_treatment::{TaxLegalText, VatTreatment};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payment term used when an invoice is created from a quote without a due date.
+pub const DEFAULT_PAYMENT_TERM_DAYS: u64 = 8;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct QuoteLineInput {
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    #[serde(default)]
+    pub vat_treatment: VatTreatment,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct QuoteInput {
+    pub id: Option<Uuid>,
+    pub customer_id: Uuid,
+    pub title: String,
+    pub notes: Option<String>,
+    /// The default currency of the tenant when empty
+    #[serde(default)]
+    pub currency_code: String,
+    pub valid_until: NaiveDate,
+    #[serde(default)]
+    pub lines: Vec<QuoteLineInput>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct QuoteStatusInput {
+    pub quote_id: Uuid,
+    pub status: String,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ConvertQuoteToWorksheet {
+    pub quote_id: Uuid,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ConvertQuoteToInvoice {
+    pub quote_id: Uuid,
+    pub document_number: Option<String>,
+    pub issue_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct QuoteLinePrint {
+    pub description: String,
+    pub quantity: String,
+    pub unit_price: String,
+    pub tax_rate: String,
+    pub net_amount: String,
+    pub gross_amount: String,
+}
+
+impl QuoteLinePrint {
+    fn from_line(line: QuoteLine, currency_code: &str) -> Self {
+        let tax_rate = match line.vat_treatment().label() {
+            Some(label) => label.to_string(),
+            None => format!("{}%", format_number(&line.tax_rate, 0)),
+        };
+        Self {
+            description: line.description,
+            quantity: format_quantity(&line.quantity),
+            unit_price: format_money(&line.unit_price, currency_code),
+            tax_rate,
+            net_amount: format_money(&line.net_amount, currency_code),
+            gross_amount: format_money(&line.gross_amount, currency_code),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct QuotePrint {
+    pub letterhead: Letterhead,
+    pub recipient: DocumentRecipient,
+    pub quote_number: String,
+    pub title: String,
+    pub notes: Option<String>,
+    pub issue_date: String,
+    pub valid_until: String,
+    pub currency_code: String,
+    pub lines: Vec<QuoteLinePrint>,
+    pub net_total: String,
+    pub tax_total: String,
+    pub gross_total: String,
+    pub legal_texts: Vec<TaxLegalText>,
+}
+
+impl QuotePrint {
+    pub fn new(
+        details: QuoteDetails,
+        letterhead: Letterhead,
+        recipient: DocumentRecipient,
+        tz: Tz,
+    ) -> Self {
+        let currency_code = details.quote.currency_code;
+        let legal_texts = TaxLegalText::collect(
+            details
+                .lines
+                .iter()
+                .map(|line| (line.vat_treatment(), line.legal_text.as_deref())),
+        );
+        Self {
+            letterhead,
+            recipient,
+            quote_number: details.quote.quote_number,
+            title: details.quote.title,
+            notes: details.quote.notes,
+            issue_date: format_date(details.quote.created_at.with_timezone(&tz).date_naive()),
+            valid_until: format_date(details.quote.valid_until),
+            lines: details
+                .lines
+                .into_iter()
+                .map(|line| QuoteLinePrint::from_line(line, &currency_code))
+                .collect(),
+            net_total: format_money(&details.net_total, &currency_code),
+            tax_total: format_money(&details.tax_total, &currency_code),
+            gross_total: format_money(&details.gross_total, &currency_code),
+            legal_texts,
+            currency_code,
+        }
+    }
+}
@@ -0,0 +1,952 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{
+    DuplicateParams, EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam,
+};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::quotes::QuotesModuleInterface;
+use crate::tenant::quotes::dto::{
+    ConvertQuoteToInvoice, ConvertQuoteToWorksheet, QuoteInput, QuoteStatusInput,
+};
+use crate::tenant::quotes::service::QuotesService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(service.get(payload.uuid).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        quotes_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        quotes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<QuoteInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(service.create(&payload).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn duplicate<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DuplicateParams>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(service.duplicate(&payload).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<QuoteInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(service.update(&payload).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    map_handler_err(service.delete(payload.uuid).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az árajánlat törlése sikeresen megtörtént",
+            ))
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn convert_to_worksheet<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ConvertQuoteToWorksheet>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(
+        service.convert_to_worksheet(&payload).await,
+        quotes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_status<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<QuoteStatusInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(service.set_status(&payload).await, quotes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn convert_to_invoice<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ConvertQuoteToInvoice>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let result = map_handler_err(
+        service.convert_to_invoice(&payload).await,
+        quotes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        quotes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pdf<M: QuotesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(quotes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), quotes_module.clone());
+    let tz = map_handler_err(claims.tz(), quotes_module.clone()).await?;
+    let pdf = map_handler_err(service.print(payload.uuid, tz).await, quotes_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"inline; filename="{}""#, payload.uuid)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::common::pdf::tests::PDF_GENERATOR_TEST_SYNC;
+    use crate::common::pdf::{MockPdfGenerator, PdfLogo, PdfTemplates};
+    use crate::common::storage::MockFileStorage;
+    use crate::tenant::address::repository::MockAddressRepository;
+    use crate::tenant::currencies::tests::currencies_repo;
+    use crate::tenant::customers::model::CustomerCreditExposure;
+    use crate::tenant::customers::repository::MockCustomersRepository;
+    use crate::tenant::document_settings::model::{DocumentRecipient, DocumentSettings};
+    use crate::tenant::document_settings::repository::MockDocumentSettingsRepository;
+    use crate::tenant::quotes::dto::QuotePrint;
+    use crate::tenant::quotes::model::{Quote, QuoteLine};
+    use crate::tenant::quotes::{self, repository::MockQuotesRepository, tests::MockQuotesModule};
+    use crate::tenant::receivables::model::Receivable;
+    use crate::tenant::taxes::vat_treatment::{TaxLegalText, VatTreatment};
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{Days, NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockQuotesRepository, active_tenant_id: Uuid) -> Router {
+        credit_app(repo, MockCustomersRepository::new(), active_tenant_id)
+    }
+
+    fn credit_app(
+        repo: MockQuotesRepository,
+        customers_repo: MockCustomersRepository,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let customers_repo = Arc::new(customers_repo);
+        let currencies_repo = Arc::new(currencies_repo("HUF", &["HUF", "EUR"]));
+        let mut address_repo = MockAddressRepository::new();
+        address_repo
+            .expect_get_customer_billing_address()
+            .returning(|_| Ok(None));
+        let address_repo = Arc::new(address_repo);
+        let mut quotes_module = MockQuotesModule::new();
+        quotes_module
+            .expect_quotes_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        quotes_module
+            .expect_currencies_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(currencies_repo.clone()));
+        quotes_module
+            .expect_address_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(address_repo.clone()));
+        quotes_module
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(customers_repo.clone()));
+        quotes_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(quotes::routes::routes(Arc::new(quotes_module))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn quote(status: &str) -> Quote {
+        Quote {
+            id: Uuid::new_v4(),
+            quote_number: "AJ-2026-00001".to_string(),
+            customer_id: Uuid::new_v4(),
+            title: "Fürdőszoba felújítás".to_string(),
+            notes: None,
+            currency_code: "HUF".to_string(),
+            valid_until: Utc::now().date_naive() + Days::new(30),
+            status: status.to_string(),
+            responded_at: None,
+            worksheet_id: None,
+            receivable_id: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn line(quote_id: Uuid, net: &str, tax: &str, gross: &str) -> QuoteLine {
+        QuoteLine {
+            id: Uuid::new_v4(),
+            quote_id,
+            service_id: Some(Uuid::new_v4()),
+            product_id: None,
+            item: "Szerelés".to_string(),
+            description: "Szerelés".to_string(),
+            quantity: BigDecimal::from(1),
+            unit_price: net.parse().unwrap(),
+            tax_id: Uuid::new_v4(),
+            tax_rate: BigDecimal::from(27),
+            vat_treatment: "domestic".to_string(),
+            legal_text: None,
+            net_amount: net.parse().unwrap(),
+            tax_amount: tax.parse().unwrap(),
+            gross_amount: gross.parse().unwrap(),
+            position: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_totals() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("draft");
+        let lines = vec![
+            line(quote.id, "25000.00", "6750.00", "31750.00"),
+            line(quote.id, "333.30", "89.99", "423.29"),
+        ];
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(quote.id))
+            .returning({
+                let quote = quote.clone();
+                move |_| Ok(quote.clone())
+            });
+        repo.expect_get_lines()
+            .times(1)
+            .with(eq(quote.id), eq(2))
+            .returning({
+                let lines = lines.clone();
+                move |_, _| Ok(lines.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/quotes/get?uuid={}", quote.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["net_total"], json!("25333.30"));
+        assert_eq!(body["data"]["tax_total"], json!("6839.99"));
+        assert_eq!(body["data"]["gross_total"], json!("32173.29"));
+        assert_eq!(body["data"]["quote_number"], json!("AJ-2026-00001"));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_line_with_service_and_product() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "customer_id": Uuid::new_v4(),
+                    "title": "Fürdőszoba felújítás",
+                    "notes": null,
+                    "currency_code": "HUF",
+                    "valid_until": Utc::now().date_naive() + Days::new(30),
+                    "lines": [{
+                        "service_id": Uuid::new_v4(),
+                        "product_id": Uuid::new_v4(),
+                        "description": null,
+                        "quantity": "1",
+                        "unit_price": "10000",
+                        "tax_id": Uuid::new_v4()
+                    }]
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn quote_payload(currency_code: &str) -> serde_json::Value {
+        json!({
+            "id": null,
+            "customer_id": Uuid::new_v4(),
+            "title": "Fürdőszoba felújítás",
+            "notes": null,
+            "currency_code": currency_code,
+            "valid_until": Utc::now().date_naive() + Days::new(30),
+            "lines": [{
+                "service_id": Uuid::new_v4(),
+                "product_id": null,
+                "description": null,
+                "quantity": "1",
+                "unit_price": "10000",
+                "tax_id": Uuid::new_v4()
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_defaults_to_tenant_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_insert()
+            .withf(|input, _| input.currency_code == "HUF")
+            .times(1)
+            .returning(|_, _| Ok(quote("draft")));
+
+        let mut payload = quote_payload("");
+        payload.as_object_mut().unwrap().remove("currency_code");
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/create",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_discontinued_product() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_count_unorderable_products()
+            .withf(move |product_ids| product_ids == [product_id])
+            .times(1)
+            .returning(|_| Ok(1));
+        repo.expect_insert().never();
+
+        let mut payload = quote_payload("HUF");
+        payload["lines"][0]["service_id"] = json!(null);
+        payload["lines"][0]["product_id"] = json!(product_id);
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/create",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_disabled_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/create",
+                active_tenant_id,
+                Some(quote_payload("usd")),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            extract_json_response(response).await["error"]["message"],
+            json!(
+                "Hiba történt az adatok feldolgozása során: A választott pénznem nincs engedélyezve, kérjük válasszon az engedélyezett pénznemek közül!"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_eu_supply_to_domestic_customer() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_insert().never();
+
+        let mut payload = quote_payload("HUF");
+        payload["lines"][0]["vat_treatment"] = json!("eu_supply");
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/create",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            extract_json_response(response).await["error"]["message"],
+            json!(
+                "Hiba történt az adatok feldolgozása során: A tétel áfakezelése nem felel meg az ügyfél számlázási címének!"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_status_rejects_invalid_transition() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("rejected");
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(quote.id))
+            .returning({
+                let quote = quote.clone();
+                move |_| Ok(quote.clone())
+            });
+        repo.expect_set_status().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/quotes/set_status",
+                active_tenant_id,
+                Some(json!({"quote_id": quote.id, "status": "accepted"})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_status_accepted_over_credit_limit_needs_acknowledgement() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("sent");
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(quote.id))
+            .returning({
+                let quote = quote.clone();
+                move |_| Ok(quote.clone())
+            });
+        repo.expect_get_lines().times(1).returning({
+            let lines = vec![line(quote.id, "25000.00", "6750.00", "31750.00")];
+            move |_, _| Ok(lines.clone())
+        });
+        repo.expect_set_status().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(quote.customer_id))
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: Some(BigDecimal::from(50000)),
+                    currency_code: Some("HUF".to_string()),
+                    open_balance: BigDecimal::from(20000),
+                    mode: "warn".to_string(),
+                })
+            });
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/quotes/set_status",
+                active_tenant_id,
+                Some(json!({"quote_id": quote.id, "status": "accepted"})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["error"]["code"], json!("CREDIT_LIMIT_EXCEEDED"));
+        assert_eq!(body["error"]["credit_limit"]["blocking"], json!(false));
+        assert_eq!(body["error"]["credit_limit"]["amount"], json!("31750.00"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_to_worksheet_requires_accepted_quote() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("sent");
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(quote.id))
+            .returning({
+                let quote = quote.clone();
+                move |_| Ok(quote.clone())
+            });
+        repo.expect_convert_to_worksheet().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/convert_to_worksheet",
+                active_tenant_id,
+                Some(json!({"quote_id": quote.id, "name": null})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_convert_to_invoice_uses_gross_total_and_default_due_date() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("accepted");
+        let issue_date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(quote.id))
+            .returning({
+                let quote = quote.clone();
+                move |_| Ok(quote.clone())
+            });
+        repo.expect_get_lines().times(1).returning({
+            let lines = vec![line(quote.id, "25000.00", "6750.00", "31750.00")];
+            move |_, _| Ok(lines.clone())
+        });
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(quote.customer_id))
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: None,
+                    currency_code: None,
+                    open_balance: BigDecimal::from(0),
+                    mode: "block".to_string(),
+                })
+            });
+        repo.expect_convert_to_invoice()
+            .times(1)
+            .withf(move |_, invoice, _| {
+                invoice.document_number == "AJ-2026-00001"
+                    && invoice.due_date == NaiveDate::from_ymd_opt(2026, 10, 9).unwrap()
+                    && invoice.amount == "31750.00".parse::<BigDecimal>().unwrap()
+            })
+            .returning(|quote, invoice, sub| {
+                Ok(Receivable {
+                    id: Uuid::new_v4(),
+                    customer_id: quote.customer_id,
+                    document_number: invoice.document_number.clone(),
+                    issue_date: invoice.issue_date,
+                    due_date: invoice.due_date,
+                    currency_code: invoice.currency_code.clone(),
+                    amount: invoice.amount.clone(),
+                    paid_amount: BigDecimal::from(0),
+                    credited_amount: BigDecimal::from(0),
+                    status: "open".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                })
+            });
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/convert_to_invoice",
+                active_tenant_id,
+                Some(json!({
+                    "quote_id": quote.id,
+                    "document_number": null,
+                    "issue_date": issue_date,
+                    "due_date": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_pdf_renders_quote_document_with_logo() {
+        let active_tenant_id = Uuid::new_v4();
+        let quote = quote("sent");
+        let logo_key = format!("{active_tenant_id}/document_settings/logo.png");
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let quote = quote.clone();
+            move |_| Ok(quote.clone())
+        });
+        repo.expect_get_lines().times(1).returning({
+            let lines = vec![
+                line(quote.id, "25000.00", "6750.00", "31750.00"),
+                QuoteLine {
+                    tax_rate: BigDecimal::from(0),
+                    vat_treatment: "reverse_charge".to_string(),
+                    ..line(quote.id, "10000.00", "0.00", "10000.00")
+                },
+            ];
+            move |_, _| Ok(lines.clone())
+        });
+        let mut document_settings_repo = MockDocumentSettingsRepository::new();
+        document_settings_repo.expect_get().times(1).returning({
+            let logo_key = logo_key.clone();
+            move || {
+                Ok(DocumentSettings {
+                    company_name: Some("Obvia Kft.".to_string()),
+                    logo_storage_key: Some(logo_key.clone()),
+                    logo_content_type: Some("image/png".to_string()),
+                    updated_at: Utc::now(),
+                    ..Default::default()
+                })
+            }
+        });
+        document_settings_repo
+            .expect_get_recipient()
+            .times(1)
+            .with(eq(quote.customer_id))
+            .returning(|_| {
+                Ok(DocumentRecipient {
+                    name: "Teszt Ügyfél".to_string(),
+                    email: "ugyfel@example.com".to_string(),
+                    phone_number: None,
+                })
+            });
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_get()
+            .times(1)
+            .with(eq(logo_key))
+            .returning(|_| Ok(b"logo".to_vec()));
+
+        let repo = Arc::new(repo);
+        let document_settings_repo = Arc::new(document_settings_repo);
+        let storage = Arc::new(storage);
+        let mut quotes_module = MockQuotesModule::new();
+        quotes_module
+            .expect_quotes_repo()
+            .returning(move |_| Ok(repo.clone()));
+        quotes_module
+            .expect_document_settings_repo()
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        quotes_module
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        quotes_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_document_context();
+        pdf_gen
+            .expect::<Vec<QuotePrint>>()
+            .times(1)
+            .withf(|template, payload, logo| {
+                *template == PdfTemplates::QuoteDocument
+                    && payload[0].gross_total == "41\u{a0}750\u{a0}Ft"
+                    && payload[0].lines[0].tax_rate == "27%"
+                    && payload[0].lines[1].tax_rate == "FAD"
+                    && payload[0].legal_texts
+                        == vec![TaxLegalText::of(VatTreatment::ReverseCharge, None).unwrap()]
+                    && payload[0].recipient.name == "Teszt Ügyfél"
+                    && payload[0].letterhead.company_name.as_deref() == Some("Obvia Kft.")
+                    && *logo
+                        == Some(PdfLogo {
+                            data: b"logo".to_vec(),
+                            extension: "png",
+                        })
+            })
+            .returning(|_, _, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(quotes::routes::routes(Arc::new(quotes_module))),
+            )
+            .oneshot(request(
+                "GET",
+                &format!("/api/quotes/pdf?uuid={}", quote.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let source = quote("accepted");
+        let copy = Quote {
+            quote_number: "AJ-2026-00002".to_string(),
+            title: "Fürdőszoba felújítás (másolat)".to_string(),
+            ..quote("draft")
+        };
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(source.id))
+            .returning({
+                let source = source.clone();
+                move |_| Ok(source.clone())
+            });
+        repo.expect_get_lines().times(1).returning(|quote_id, _| {
+            Ok(vec![QuoteLine {
+                service_id: None,
+                product_id: Some(Uuid::new_v4()),
+                ..line(quote_id, "10000", "2700", "12700")
+            }])
+        });
+        repo.expect_count_unorderable_products()
+            .times(1)
+            .returning(|_| Ok(0));
+        repo.expect_duplicate()
+            .times(1)
+            .withf({
+                let source_id = source.id;
+                move |params, _| {
+                    *params
+                        == DuplicateParams {
+                            id: source_id,
+                            include_lines: true,
+                            include_tags: true,
+                        }
+                }
+            })
+            .returning({
+                let copy = copy.clone();
+                move |_, _| Ok(copy.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/duplicate",
+                active_tenant_id,
+                Some(json!({"id": source.id, "include_lines": true, "include_tags": true})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["status"], json!("draft"));
+        assert_eq!(body["data"]["quote_number"], json!("AJ-2026-00002"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_rejects_disabled_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let source = Quote {
+            currency_code: "USD".to_string(),
+            ..quote("sent")
+        };
+        let mut repo = MockQuotesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let source = source.clone();
+            move |_| Ok(source.clone())
+        });
+        repo.expect_duplicate().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/quotes/duplicate",
+                active_tenant_id,
+                Some(json!({"id": source.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,121 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::taxes::vat_treatment::VatTreatment;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_SENT: &str = "sent";
+pub const STATUS_ACCEPTED: &str = "accepted";
+pub const STATUS_REJECTED: &str = "rejected";
+pub const STATUS_CONVERTED: &str = "converted";
+
+/// A quote can be accepted or rejected without being sent first (e.g. verbal agreement), but
+/// once the customer has answered, only a conversion can move it further.
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (STATUS_DRAFT, STATUS_SENT)
+            | (
+                STATUS_DRAFT | STATUS_SENT,
+                STATUS_ACCEPTED | STATUS_REJECTED
+            )
+    )
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Quote {
+    pub id: Uuid,
+    pub quote_number: String,
+    pub customer_id: Uuid,
+    pub title: String,
+    pub notes: Option<String>,
+    pub currency_code: String,
+    pub valid_until: NaiveDate,
+    pub status: String,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub worksheet_id: Option<Uuid>,
+    pub receivable_id: Option<Uuid>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct QuoteLine {
+    pub id: Uuid,
+    pub quote_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub item: String,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub vat_treatment: String,
+    pub legal_text: Option<String>,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+    pub position: i32,
+}
+
+impl QuoteLine {
+    pub fn vat_treatment(&self) -> VatTreatment {
+        self.vat_treatment.parse().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuoteDetails {
+    #[serde(flatten)]
+    pub quote: Quote,
+    pub lines: Vec<QuoteLine>,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub gross_total: BigDecimal,
+}
+
+impl QuoteDetails {
+    pub fn new(quote: Quote, lines: Vec<QuoteLine>) -> Self {
+        let (net_total, tax_total, gross_total) = lines.iter().fold(
+            (BigDecimal::zero(), BigDecimal::zero(), BigDecimal::zero()),
+            |(net, tax, gross), line| {
+                (
+                    net + &line.net_amount,
+                    tax + &line.tax_amount,
+                    gross + &line.gross_amount,
+                )
+            },
+        );
+        Self {
+            quote,
+            lines,
+            net_total,
+            tax_total,
+            gross_total,
+        }
+    }
+}
@@ -0,0 +1,495 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::copy_tags;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::quotes::dto::QuoteInput;
+use crate::tenant::quotes::model::{Quote, QuoteLine};
+use crate::tenant::receivables::dto::CreateReceivable;
+use crate::tenant::receivables::model::Receivable;
+use crate::tenant::worksheets::model::Worksheet;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait QuotesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Quote>;
+    /// Lines with their amounts rounded to `scale` decimal places, the scale of the quote's currency
+    async fn get_lines(&self, quote_id: Uuid, scale: i64) -> RepositoryResult<Vec<QuoteLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Quote>)>;
+    async fn insert(&self, input: &QuoteInput, sub: Uuid) -> RepositoryResult<Quote>;
+    async fn update(&self, id: Uuid, input: &QuoteInput) -> RepositoryResult<Quote>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Quote>;
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<Quote>;
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64>;
+    async fn convert_to_worksheet(
+        &self,
+        quote: &Quote,
+        name: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<Worksheet>;
+    async fn convert_to_invoice(
+        &self,
+        quote: &Quote,
+        invoice: &CreateReceivable,
+        sub: Uuid,
+    ) -> RepositoryResult<Receivable>;
+}
+
+async fn insert_lines(
+    tx: &mut Transaction<'_, Postgres>,
+    quote_id: Uuid,
+    input: &QuoteInput,
+) -> RepositoryResult<()> {
+    for (position, line) in input.lines.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO quote_lines (quote_id, service_id, product_id, description, quantity,
+                                     unit_price, tax_id, position, vat_treatment)
+            SELECT $1, $2, $3, COALESCE($4, services.name, products.name), $5, $6, $7, $8, $9
+            FROM (VALUES (1)) AS line
+            LEFT JOIN services ON services.id = $2
+            LEFT JOIN products ON products.id = $3
+            "#,
+        )
+        .bind(quote_id)
+        .bind(line.service_id)
+        .bind(line.product_id)
+        .bind(&line.description)
+        .bind(&line.quantity)
+        .bind(&line.unit_price)
+        .bind(line.tax_id)
+        .bind(i32::try_from(position)?)
+        .bind(line.vat_treatment.as_str())
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn lock_for_conversion(
+    tx: &mut Transaction<'_, Postgres>,
+    quote_id: Uuid,
+    link_column: &str,
+) -> RepositoryResult<()> {
+    sqlx::query_scalar::<_, Uuid>(sqlx::AssertSqlSafe(format!(
+        r#"
+        SELECT id
+        FROM quotes
+        WHERE id = $1
+            AND deleted_at IS NULL
+            AND status IN ('accepted', 'converted')
+            AND {link_column} IS NULL
+        FOR UPDATE
+        "#
+    )))
+    .bind(quote_id)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl QuotesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Quote> {
+        Ok(
+            sqlx::query_as::<_, Quote>("SELECT * FROM quotes WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_lines(&self, quote_id: Uuid, scale: i64) -> RepositoryResult<Vec<QuoteLine>> {
+        Ok(sqlx::query_as::<_, QuoteLine>(
+            r#"
+            SELECT quote_lines.id,
+                   quote_lines.quote_id,
+                   quote_lines.service_id,
+                   quote_lines.product_id,
+                   COALESCE(services.name, products.name) AS item,
+                   quote_lines.description,
+                   quote_lines.quantity,
+                   quote_lines.unit_price,
+                   quote_lines.tax_id,
+                   amounts.tax_rate,
+                   quote_lines.vat_treatment,
+                   taxes.legal_text,
+                   amounts.net_amount,
+                   amounts.tax_amount,
+                   amounts.net_amount + amounts.tax_amount AS gross_amount,
+                   quote_lines.position
+            FROM quote_lines
+            JOIN quotes ON quotes.id = quote_lines.quote_id
+            JOIN taxes ON taxes.id = effective_tax_id(quote_lines.tax_id, quotes.created_at::date)
+            LEFT JOIN services ON quote_lines.service_id = services.id
+            LEFT JOIN products ON quote_lines.product_id = products.id
+            CROSS JOIN LATERAL (
+                SELECT CASE
+                           WHEN quote_lines.vat_treatment = 'domestic'
+                               THEN COALESCE(taxes.rate, 0)
+                           ELSE 0
+                       END AS tax_rate,
+                       round(quote_lines.quantity * quote_lines.unit_price, $2::integer)
+                           AS net_amount,
+                       CASE
+                           WHEN taxes.is_rate_applicable AND quote_lines.vat_treatment = 'domestic'
+                               THEN round(quote_lines.quantity * quote_lines.unit_price
+                                              * COALESCE(taxes.rate, 0) / 100, $2::integer)
+                           ELSE 0
+                       END AS tax_amount
+            ) AS amounts
+            WHERE quote_lines.quote_id = $1
+            ORDER BY quote_lines.position
+            "#,
+        )
+        .bind(quote_id)
+        .bind(scale)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Quote>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM quotes WHERE deleted_at IS NULL")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let quotes = sqlx::query_as::<_, Quote>(
+            r#"
+            SELECT *
+            FROM quotes
+            WHERE deleted_at IS NULL
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            quotes,
+        ))
+    }
+
+    async fn insert(&self, input: &QuoteInput, sub: Uuid) -> RepositoryResult<Quote> {
+        let mut tx = self.begin().await?;
+        let quote = sqlx::query_as::<_, Quote>(
+            r#"
+            INSERT INTO quotes (quote_number, customer_id, title, notes, currency_code, valid_until,
+                                created_by_id)
+            VALUES ('AJ-' || to_char(CURRENT_DATE, 'YYYY') || '-'
+                        || lpad(nextval('quote_number_seq')::text, 5, '0'),
+                    $1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&input.title)
+        .bind(&input.notes)
+        .bind(&input.currency_code)
+        .bind(input.valid_until)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_lines(&mut tx, quote.id, input).await?;
+        tx.commit().await?;
+        Ok(quote)
+    }
+
+    async fn update(&self, id: Uuid, input: &QuoteInput) -> RepositoryResult<Quote> {
+        let mut tx = self.begin().await?;
+        let quote = sqlx::query_as::<_, Quote>(
+            r#"
+            UPDATE quotes
+            SET customer_id = $1,
+                title = $2,
+                notes = $3,
+                currency_code = $4,
+                valid_until = $5
+            WHERE id = $6
+                AND status = 'draft'
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&input.title)
+        .bind(&input.notes)
+        .bind(&input.currency_code)
+        .bind(input.valid_until)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM quote_lines WHERE quote_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        insert_lines(&mut tx, id, input).await?;
+        tx.commit().await?;
+        Ok(quote)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE quotes
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND worksheet_id IS NULL
+                AND receivable_id IS NULL
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Quote> {
+        let mut tx = self.begin().await?;
+        // NOTE: the copy is a new draft, valid for as long from today as the source was
+        let quote = sqlx::query_as::<_, Quote>(
+            r#"
+            INSERT INTO quotes (quote_number, customer_id, title, notes, currency_code, valid_until,
+                                created_by_id)
+            SELECT 'AJ-' || to_char(CURRENT_DATE, 'YYYY') || '-'
+                       || lpad(nextval('quote_number_seq')::text, 5, '0'),
+                   customer_id, left(title || ' (másolat)', 255), notes, currency_code,
+                   CURRENT_DATE + GREATEST(valid_until - created_at::date, 0), $2
+            FROM quotes
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(params.id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        if params.include_lines {
+            sqlx::query(
+                r#"
+                INSERT INTO quote_lines (quote_id, service_id, product_id, description, quantity,
+                                         unit_price, tax_id, position, vat_treatment)
+                SELECT $1, service_id, product_id, description, quantity, unit_price, tax_id,
+                       position, vat_treatment
+                FROM quote_lines
+                WHERE quote_id = $2
+                "#,
+            )
+            .bind(quote.id)
+            .bind(params.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        if params.include_tags {
+            copy_tags(&mut tx, "quotes", params.id, quote.id, sub).await?;
+        }
+        tx.commit().await?;
+        Ok(quote)
+    }
+
+    async fn set_status(&self, id: Uuid, status: &str) -> RepositoryResult<Quote> {
+        Ok(sqlx::query_as::<_, Quote>(
+            r#"
+            UPDATE quotes
+            SET status = $1,
+                responded_at = CASE
+                    WHEN $1 IN ('accepted', 'rejected') THEN NOW()
+                    ELSE responded_at
+                END
+            WHERE id = $2
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn convert_to_worksheet(
+        &self,
+        quote: &Quote,
+        name: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<Worksheet> {
+        let mut tx = self.begin().await?;
+        lock_for_conversion(&mut tx, quote.id, "worksheet_id").await?;
+        let worksheet = sqlx::query_as::<_, Worksheet>(
+            r#"
+            INSERT INTO worksheets (name, description, customer_id, created_by_id, status)
+            VALUES ($1, $2, $3, $4, 'active')
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(&quote.notes)
+        .bind(quote.customer_id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tasks (worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                               created_by_id, description)
+            SELECT $1, service_id, $2, quantity, unit_price, tax_id, $3, description
+            FROM quote_lines
+            WHERE quote_id = $4
+                AND service_id IS NOT NULL
+            ORDER BY position
+            "#,
+        )
+        .bind(worksheet.id)
+        .bind(&quote.currency_code)
+        .bind(sub)
+        .bind(quote.id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO worksheet_planned_materials (worksheet_id, product_id, quantity)
+            SELECT $1, product_id, quantity
+            FROM quote_lines
+            WHERE quote_id = $2
+                AND product_id IS NOT NULL
+            "#,
+        )
+        .bind(worksheet.id)
+        .bind(quote.id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE quotes SET worksheet_id = $1, status = 'converted' WHERE id = $2")
+            .bind(worksheet.id)
+            .bind(quote.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(worksheet)
+    }
+
+    async fn convert_to_invoice(
+        &self,
+        quote: &Quote,
+        invoice: &CreateReceivable,
+        sub: Uuid,
+    ) -> RepositoryResult<Receivable> {
+        let mut tx = self.begin().await?;
+        lock_for_conversion(&mut tx, quote.id, "receivable_id").await?;
+        let receivable = sqlx::query_as::<_, Receivable>(
+            r#"
+            INSERT INTO receivables (customer_id, document_number, issue_date, due_date,
+                                     currency_code, amount, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(invoice.customer_id)
+        .bind(&invoice.document_number)
+        .bind(invoice.issue_date)
+        .bind(invoice.due_date)
+        .bind(&invoice.currency_code)
+        .bind(&invoice.amount)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO receivable_lines (receivable_id, service_id, product_id, description,
+                                          quantity, unit_price, tax_id, tax_rate, net_amount,
+                                          tax_amount, position, vat_treatment)
+            SELECT $1,
+                   quote_lines.service_id,
+                   quote_lines.product_id,
+                   quote_lines.description,
+                   quote_lines.quantity,
+                   quote_lines.unit_price,
+                   taxes.id,
+                   CASE
+                       WHEN quote_lines.vat_treatment = 'domestic' THEN COALESCE(taxes.rate, 0)
+                       ELSE 0
+                   END,
+                   round(quote_lines.quantity * quote_lines.unit_price, $4::integer),
+                   CASE
+                       WHEN taxes.is_rate_applicable AND quote_lines.vat_treatment = 'domestic'
+                           THEN round(quote_lines.quantity * quote_lines.unit_price
+                                          * COALESCE(taxes.rate, 0) / 100, $4::integer)
+                       ELSE 0
+                   END,
+                   quote_lines.position,
+                   quote_lines.vat_treatment
+            FROM quote_lines
+            JOIN taxes ON taxes.id = effective_tax_id(quote_lines.tax_id, $3)
+            WHERE quote_lines.quote_id = $2
+            "#,
+        )
+        .bind(receivable.id)
+        .bind(quote.id)
+        .bind(invoice.issue_date)
+        .bind(CurrencyRules::of(&invoice.currency_code).scale)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE quotes SET receivable_id = $1, status = 'converted' WHERE id = $2")
+            .bind(receivable.id)
+            .bind(quote.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(receivable)
+    }
+
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM products WHERE id = ANY($1) AND status <> 'active'",
+        )
+        .bind(product_ids)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,580 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+#[double]
+use crate::common::pdf::PdfGenerator;
+use crate::common::pdf::{PdfGenError, PdfTemplates};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::address::repository::AddressRepository;
+use crate::tenant::currencies::service::{CurrenciesServiceError, document_currency};
+use crate::tenant::customers::model::CreditLimitBreach;
+use crate::tenant::customers::repository::CustomersRepository;
+use crate::tenant::document_settings::service::load_letterhead;
+use crate::tenant::quotes::QuotesModuleInterface;
+use crate::tenant::quotes::dto::{
+    ConvertQuoteToInvoice, ConvertQuoteToWorksheet, DEFAULT_PAYMENT_TERM_DAYS, QuoteInput,
+    QuotePrint, QuoteStatusInput,
+};
+use crate::tenant::quotes::model::{
+    Quote, QuoteDetails, STATUS_ACCEPTED, STATUS_CONVERTED, STATUS_DRAFT, can_transition,
+};
+use crate::tenant::quotes::repository::QuotesRepository;
+use crate::tenant::receivables::dto::CreateReceivable;
+use crate::tenant::receivables::model::Receivable;
+use crate::tenant::taxes::jurisdiction::{TaxJurisdiction, customer_jurisdiction};
+use crate::tenant::worksheets::model::Worksheet;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Days, Utc};
+use chrono_tz::Tz;
+use mockall_double::double;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum QuotesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Currencies(#[from] CurrenciesServiceError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("PdfGen error: {0}")]
+    PdfGenError(#[from] PdfGenError),
+
+    #[error("{}", .0.message())]
+    CreditLimitExceeded(Box<CreditLimitBreach>),
+}
+
+impl From<ServiceError> for QuotesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => QuotesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<QuotesServiceError> for AppError {
+    fn from(value: QuotesServiceError) -> Self {
+        match value {
+            QuotesServiceError::Currencies(error) => error.into(),
+            QuotesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            QuotesServiceError::CreditLimitExceeded(ref breach) => Self::new(
+                Level::DEBUG,
+                ErrorCode::CreditLimitExceeded.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::CreditLimitExceeded.code(),
+                    "message": value.to_string(),
+                    "credit_limit": breach
+                }),
+            ),
+            QuotesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            QuotesServiceError::Repository(RepositoryError::Database(sqlx::Error::RowNotFound)) => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::NOT_FOUND,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": "Nem található"}),
+                )
+            }
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type QuotesServiceResult<T> = Result<T, QuotesServiceError>;
+
+fn validate_quote(payload: &QuoteInput, currency_code: String) -> QuotesServiceResult<QuoteInput> {
+    let title = payload.title.trim();
+    if title.is_empty() || title.chars().count() > 255 {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "A megnevezés megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    if payload.valid_until < Utc::now().date_naive() {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "Az érvényességi idő nem lehet múltbeli dátum!",
+        ));
+    }
+    if payload.lines.is_empty() {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "Az árajánlatnak legalább egy tételt tartalmaznia kell!",
+        ));
+    }
+    let mut lines = Vec::with_capacity(payload.lines.len());
+    for line in &payload.lines {
+        if line.service_id.is_some() == line.product_id.is_some() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Minden tételnél pontosan egy szolgáltatást vagy terméket kell megadni!",
+            ));
+        }
+        if line.quantity <= BigDecimal::zero() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "A mennyiségnek pozitív számnak kell lennie!",
+            ));
+        }
+        if line.unit_price < BigDecimal::zero() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Az egységár nem lehet negatív!",
+            ));
+        }
+        let mut line = line.clone();
+        line.description = line
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+        lines.push(line);
+    }
+    Ok(QuoteInput {
+        title: title.to_string(),
+        notes: payload
+            .notes
+            .as_ref()
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        currency_code,
+        lines,
+        ..payload.clone()
+    })
+}
+
+fn map_reference_error(e: RepositoryError) -> QuotesServiceError {
+    if e.is_foreign_key_violation() {
+        QuotesServiceError::UnprocessableEntry(
+            "A megadott ügyfél, szolgáltatás, termék, adó vagy pénznem nem létezik!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+async fn check_credit_limit(
+    customers_repo: &(dyn CustomersRepository + Send + Sync),
+    quote: &Quote,
+    amount: &BigDecimal,
+    acknowledged: bool,
+) -> QuotesServiceResult<()> {
+    match customers_repo
+        .get_credit_exposure(quote.customer_id)
+        .await?
+        .check(&quote.currency_code, amount, acknowledged)
+    {
+        Some(breach) => Err(QuotesServiceError::CreditLimitExceeded(Box::new(breach))),
+        None => Ok(()),
+    }
+}
+
+async fn check_orderable_products(
+    repo: &(dyn QuotesRepository + Send + Sync),
+    product_ids: Vec<Uuid>,
+) -> QuotesServiceResult<()> {
+    if !product_ids.is_empty() && repo.count_unorderable_products(&product_ids).await? > 0 {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "Csak aktív termék adható az árajánlathoz!",
+        ));
+    }
+    Ok(())
+}
+
+async fn check_vat_treatments(
+    address_repo: &(dyn AddressRepository + Send + Sync),
+    quote: &QuoteInput,
+) -> QuotesServiceResult<()> {
+    let jurisdiction = customer_jurisdiction(address_repo, quote.customer_id).await?;
+    if quote
+        .lines
+        .iter()
+        .any(|line| !jurisdiction.allows(line.vat_treatment))
+    {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            TaxJurisdiction::MISMATCH,
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_convertible(quote: &Quote, link: Option<Uuid>) -> QuotesServiceResult<()> {
+    if quote.status != STATUS_ACCEPTED && quote.status != STATUS_CONVERTED {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "Csak elfogadott árajánlat alakítható át!",
+        ));
+    }
+    if link.is_some() {
+        return Err(QuotesServiceError::UnprocessableEntry(
+            "Az árajánlatot már átalakították!",
+        ));
+    }
+    Ok(())
+}
+
+async fn details(repo: &dyn QuotesRepository, quote: Quote) -> QuotesServiceResult<QuoteDetails> {
+    let lines = repo
+        .get_lines(quote.id, CurrencyRules::of(&quote.currency_code).scale)
+        .await?;
+    Ok(QuoteDetails::new(quote, lines))
+}
+
+pub trait QuotesService {
+    fn get(&self, id: Uuid) -> impl Future<Output = QuotesServiceResult<QuoteDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = QuotesServiceResult<(PaginatorMeta, Vec<Quote>)>> + Send;
+    fn create(
+        &self,
+        payload: &QuoteInput,
+    ) -> impl Future<Output = QuotesServiceResult<Quote>> + Send;
+    fn update(
+        &self,
+        payload: &QuoteInput,
+    ) -> impl Future<Output = QuotesServiceResult<Quote>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = QuotesServiceResult<()>> + Send;
+    fn duplicate(
+        &self,
+        payload: &DuplicateParams,
+    ) -> impl Future<Output = QuotesServiceResult<Quote>> + Send;
+    fn set_status(
+        &self,
+        payload: &QuoteStatusInput,
+    ) -> impl Future<Output = QuotesServiceResult<Quote>> + Send;
+    fn convert_to_worksheet(
+        &self,
+        payload: &ConvertQuoteToWorksheet,
+    ) -> impl Future<Output = QuotesServiceResult<Worksheet>> + Send;
+    fn convert_to_invoice(
+        &self,
+        payload: &ConvertQuoteToInvoice,
+    ) -> impl Future<Output = QuotesServiceResult<Receivable>> + Send;
+    fn print(&self, id: Uuid, tz: Tz) -> impl Future<Output = QuotesServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> QuotesService for Service<'a, T>
+where
+    T: QuotesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> QuotesServiceResult<QuoteDetails> {
+        let repo = self.module().quotes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(QuotesServiceError::Unauthorized)?,
+        )?;
+        details(&*repo, repo.get_by_id(id).await?).await
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> QuotesServiceResult<(PaginatorMeta, Vec<Quote>)> {
+        Ok(self
+            .module()
+            .quotes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(QuotesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &QuoteInput) -> QuotesServiceResult<Quote> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_quote(payload, currency_code)?;
+        check_vat_treatments(&*self.module().address_repo(tenant_id)?, &input).await?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        check_orderable_products(
+            &*repo,
+            input
+                .lines
+                .iter()
+                .filter_map(|line| line.product_id)
+                .collect(),
+        )
+        .await?;
+        repo.insert(&input, self.claims()?.sub())
+            .await
+            .map_err(map_reference_error)
+    }
+
+    async fn update(&self, payload: &QuoteInput) -> QuotesServiceResult<Quote> {
+        let id = payload.id.ok_or(QuotesServiceError::UnprocessableEntry(
+            "Az azonosító megadása kötelező!",
+        ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_quote(payload, currency_code)?;
+        check_vat_treatments(&*self.module().address_repo(tenant_id)?, &input).await?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        if repo.get_by_id(id).await?.status != STATUS_DRAFT {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú árajánlat módosítható!",
+            ));
+        }
+        check_orderable_products(
+            &*repo,
+            input
+                .lines
+                .iter()
+                .filter_map(|line| line.product_id)
+                .collect(),
+        )
+        .await?;
+        repo.update(id, &input).await.map_err(map_reference_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> QuotesServiceResult<()> {
+        let repo = self.module().quotes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(QuotesServiceError::Unauthorized)?,
+        )?;
+        let quote = repo.get_by_id(id).await?;
+        if quote.worksheet_id.is_some() || quote.receivable_id.is_some() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Átalakított árajánlat nem törölhető!",
+            ));
+        }
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn duplicate(&self, payload: &DuplicateParams) -> QuotesServiceResult<Quote> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        let quote = repo.get_by_id(payload.id).await?;
+        document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &quote.currency_code,
+        )
+        .await?;
+        if payload.include_lines {
+            check_orderable_products(
+                &*repo,
+                repo.get_lines(quote.id, CurrencyRules::of(&quote.currency_code).scale)
+                    .await?
+                    .iter()
+                    .filter_map(|line| line.product_id)
+                    .collect(),
+            )
+            .await?;
+        }
+        Ok(repo.duplicate(payload, self.claims()?.sub()).await?)
+    }
+
+    async fn set_status(&self, payload: &QuoteStatusInput) -> QuotesServiceResult<Quote> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        let quote = repo.get_by_id(payload.quote_id).await?;
+        if !can_transition(&quote.status, &payload.status) {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Az árajánlat ebből az állapotból nem vihető a kért állapotba!",
+            ));
+        }
+        if payload.status == STATUS_ACCEPTED && quote.valid_until < Utc::now().date_naive() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Lejárt érvényességű árajánlat nem fogadható el!",
+            ));
+        }
+        if payload.status == STATUS_ACCEPTED {
+            let amount = details(&*repo, quote.clone()).await?.gross_total;
+            check_credit_limit(
+                self.module().customers_repo(tenant_id)?.as_ref(),
+                &quote,
+                &amount,
+                payload.acknowledge_credit_limit,
+            )
+            .await?;
+        }
+        Ok(repo.set_status(quote.id, &payload.status).await?)
+    }
+
+    async fn convert_to_worksheet(
+        &self,
+        payload: &ConvertQuoteToWorksheet,
+    ) -> QuotesServiceResult<Worksheet> {
+        let repo = self.module().quotes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(QuotesServiceError::Unauthorized)?,
+        )?;
+        let quote = repo.get_by_id(payload.quote_id).await?;
+        ensure_convertible(&quote, quote.worksheet_id)?;
+        let name = match payload.name.as_deref().map(str::trim) {
+            None | Some("") => format!("{} – {}", quote.quote_number, quote.title)
+                .chars()
+                .take(255)
+                .collect(),
+            Some(name) if name.chars().count() > 255 => {
+                return Err(QuotesServiceError::UnprocessableEntry(
+                    "A név legfeljebb 255 karakter lehet!",
+                ));
+            }
+            Some(name) => name.to_string(),
+        };
+        Ok(repo
+            .convert_to_worksheet(&quote, &name, self.claims()?.sub())
+            .await?)
+    }
+
+    // NOTE: the receivable carries the gross total, the quote lines are copied to it as the
+    // invoice lines
+    async fn convert_to_invoice(
+        &self,
+        payload: &ConvertQuoteToInvoice,
+    ) -> QuotesServiceResult<Receivable> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        let quote = repo.get_by_id(payload.quote_id).await?;
+        ensure_convertible(&quote, quote.receivable_id)?;
+        let document_number = match payload.document_number.as_deref().map(str::trim) {
+            None | Some("") => quote.quote_number.clone(),
+            Some(number) if number.chars().count() > 100 => {
+                return Err(QuotesServiceError::UnprocessableEntry(
+                    "A bizonylatszám legfeljebb 100 karakter lehet!",
+                ));
+            }
+            Some(number) => number.to_string(),
+        };
+        let issue_date = payload
+            .issue_date
+            .unwrap_or_else(|| Utc::now().date_naive());
+        let due_date = match payload.due_date {
+            Some(due_date) => due_date,
+            None => issue_date
+                .checked_add_days(Days::new(DEFAULT_PAYMENT_TERM_DAYS))
+                .ok_or(QuotesServiceError::UnprocessableEntry(
+                    "Hibás kiállítási dátum!",
+                ))?,
+        };
+        if due_date < issue_date {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "A fizetési határidő nem lehet korábbi a kiállítás dátumánál!",
+            ));
+        }
+        let amount = details(&*repo, quote.clone()).await?.gross_total;
+        if amount <= BigDecimal::zero() {
+            return Err(QuotesServiceError::UnprocessableEntry(
+                "Nulla végösszegű árajánlatból nem készíthető számla!",
+            ));
+        }
+        check_credit_limit(
+            self.module().customers_repo(tenant_id)?.as_ref(),
+            &quote,
+            &amount,
+            payload.acknowledge_credit_limit,
+        )
+        .await?;
+        let invoice = CreateReceivable {
+            customer_id: quote.customer_id,
+            document_number,
+            issue_date,
+            due_date,
+            currency_code: quote.currency_code.clone(),
+            amount,
+            acknowledge_credit_limit: payload.acknowledge_credit_limit,
+        };
+        repo.convert_to_invoice(&quote, &invoice, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    QuotesServiceError::UnprocessableEntry("A megadott bizonylatszám már foglalt!")
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn print(&self, id: Uuid, tz: Tz) -> QuotesServiceResult<Vec<u8>> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(QuotesServiceError::Unauthorized)?;
+        let repo = self.module().quotes_repo(tenant_id)?;
+        let details = details(&*repo, repo.get_by_id(id).await?).await?;
+        let document_settings_repo = self.module().document_settings_repo(tenant_id)?;
+        let mut letterhead =
+            load_letterhead(&*document_settings_repo, &*self.module().file_storage()).await?;
+        let recipient = document_settings_repo
+            .get_recipient(details.quote.customer_id)
+            .await?;
+        let logo = letterhead.logo.take();
+        Ok(PdfGenerator::gen_pdf_document(
+            &PdfTemplates::QuoteDocument,
+            vec![QuotePrint::new(details, letterhead, recipient, tz)],
+            logo,
+        )?)
+    }
+}
@@ -0,0 +1,255 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::pdf::{format_date, format_money};
+use crate::tenant::document_settings::dto::Letterhead;
+use crate::tenant::document_settings::model::DocumentRecipient;
+use crate::tenant::receivables::model::{OpenBalance, Receivable};
+use crate::tenant::taxes::vat_treatment::TaxLegalText;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateReceivable {
+    pub customer_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+/// Issues a copy of an invoice with a new number, dated today
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DuplicateReceivable {
+    pub id: Uuid,
+    #[serde(default)]
+    pub include_lines: bool,
+    #[serde(default)]
+    pub include_tags: bool,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SetPaidAmount {
+    pub id: Uuid,
+    pub paid_amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateCollectionActivity {
+    pub customer_id: Uuid,
+    pub receivable_id: Option<Uuid>,
+    pub activity_type: String,
+    pub note: Option<String>,
+    pub promised_amount: Option<BigDecimal>,
+    pub promised_date: Option<NaiveDate>,
+}
+
+/// Empty `to`, `cc` and `bcc` fall back to the customer e-mail and the document settings
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SendInvoiceEmail {
+    pub id: Uuid,
+    pub to: Option<String>,
+    pub cc: Option<String>,
+    pub bcc: Option<String>,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewInvoiceEmailDelivery {
+    pub receivable_id: Uuid,
+    pub recipient: String,
+    pub cc: Option<String>,
+    pub bcc: Option<String>,
+    pub language: String,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct InvoicePrint {
+    pub letterhead: Letterhead,
+    pub recipient: DocumentRecipient,
+    pub document_number: String,
+    pub issue_date: String,
+    pub due_date: String,
+    pub currency_code: String,
+    pub status: String,
+    pub amount: String,
+    pub paid_amount: String,
+    pub open_balance: String,
+    pub legal_texts: Vec<TaxLegalText>,
+}
+
+impl InvoicePrint {
+    pub fn new(
+        receivable: Receivable,
+        letterhead: Letterhead,
+        recipient: DocumentRecipient,
+        legal_texts: Vec<TaxLegalText>,
+    ) -> Self {
+        let open_balance = receivable.open_balance();
+        Self {
+            letterhead,
+            recipient,
+            document_number: receivable.document_number,
+            issue_date: format_date(receivable.issue_date),
+            due_date: format_date(receivable.due_date),
+            status: Self::map_status(&receivable.status),
+            amount: format_money(&receivable.amount, &receivable.currency_code),
+            paid_amount: format_money(&receivable.paid_amount, &receivable.currency_code),
+            open_balance: format_money(&open_balance, &receivable.currency_code),
+            currency_code: receivable.currency_code,
+            legal_texts,
+        }
+    }
+    fn map_status(status: &str) -> String {
+        match status {
+            "open" => "Nyitott",
+            "paid" => "Kiegyenlítve",
+            "written_off" => "Leírva",
+            _ => "Ismeretlen státusz!",
+        }
+        .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CustomerStatementQuery {
+    pub customer_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatementItemPrint {
+    pub document_number: String,
+    pub issue_date: String,
+    pub due_date: String,
+    pub amount: String,
+    pub settled_amount: String,
+    pub open_balance: String,
+    pub days_overdue: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatementCurrencyPrint {
+    pub currency_code: String,
+    pub earlier_balance: String,
+    pub invoiced_total: String,
+    pub period_balance: String,
+    pub closing_balance: String,
+    pub items: Vec<StatementItemPrint>,
+}
+
+// NOTE: the statement lists the invoices issued in the period with their current open balance,
+// invoices issued earlier only appear as a single carried balance per currency
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CustomerStatementPrint {
+    pub letterhead: Letterhead,
+    pub recipient: DocumentRecipient,
+    pub period: String,
+    pub as_of: String,
+    pub currencies: Vec<StatementCurrencyPrint>,
+}
+
+impl CustomerStatementPrint {
+    pub fn new(
+        letterhead: Letterhead,
+        recipient: DocumentRecipient,
+        query: &CustomerStatementQuery,
+        as_of: NaiveDate,
+        receivables: Vec<Receivable>,
+        earlier_balances: Vec<OpenBalance>,
+    ) -> Self {
+        let mut currency_codes: Vec<String> = earlier_balances
+            .iter()
+            .map(|balance| balance.currency_code.clone())
+            .chain(receivables.iter().map(|r| r.currency_code.clone()))
+            .collect();
+        currency_codes.sort();
+        currency_codes.dedup();
+        let currencies = currency_codes
+            .into_iter()
+            .map(|currency_code| {
+                let earlier_balance = earlier_balances
+                    .iter()
+                    .find(|balance| balance.currency_code == currency_code)
+                    .map(|balance| balance.open_balance.clone())
+                    .unwrap_or_else(BigDecimal::zero);
+                let items: Vec<&Receivable> = receivables
+                    .iter()
+                    .filter(|r| r.currency_code == currency_code)
+                    .collect();
+                let invoiced_total = items
+                    .iter()
+                    .fold(BigDecimal::zero(), |total, r| total + &r.amount);
+                let period_balance = items
+                    .iter()
+                    .fold(BigDecimal::zero(), |total, r| total + r.open_balance());
+                StatementCurrencyPrint {
+                    earlier_balance: format_money(&earlier_balance, &currency_code),
+                    invoiced_total: format_money(&invoiced_total, &currency_code),
+                    closing_balance: format_money(
+                        &(&earlier_balance + &period_balance),
+                        &currency_code,
+                    ),
+                    period_balance: format_money(&period_balance, &currency_code),
+                    items: items.into_iter().map(|r| Self::item(r, as_of)).collect(),
+                    currency_code,
+                }
+            })
+            .collect();
+        Self {
+            letterhead,
+            recipient,
+            period: format!("{} – {}", format_date(query.from), format_date(query.to)),
+            as_of: format_date(as_of),
+            currencies,
+        }
+    }
+
+    fn item(receivable: &Receivable, as_of: NaiveDate) -> StatementItemPrint {
+        let open_balance = receivable.open_balance();
+        let days_overdue = (as_of - receivable.due_date).num_days();
+        StatementItemPrint {
+            document_number: receivable.document_number.clone(),
+            issue_date: format_date(receivable.issue_date),
+            due_date: format_date(receivable.due_date),
+            amount: format_money(&receivable.amount, &receivable.currency_code),
+            settled_amount: format_money(
+                &(&receivable.paid_amount + &receivable.credited_amount),
+                &receivable.currency_code,
+            ),
+            days_overdue: if open_balance > BigDecimal::zero() && days_overdue > 0 {
+                days_overdue.to_string()
+            } else {
+                String::new()
+            },
+            open_balance: format_money(&open_balance, &receivable.currency_code),
+        }
+    }
+}
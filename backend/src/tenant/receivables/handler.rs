@@ -0,0 +1,1384 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::receivables::ReceivablesModuleInterface;
+use crate::tenant::receivables::dto::{
+    CreateCollectionActivity, CreateReceivable, CustomerStatementQuery, DuplicateReceivable,
+    SendInvoiceEmail, SetPaidAmount,
+};
+use crate::tenant::receivables::service::ReceivablesService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result =
+        map_handler_err(service.get(payload.uuid).await, receivables_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        receivables_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateReceivable>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result =
+        map_handler_err(service.create(&payload).await, receivables_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn duplicate<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DuplicateReceivable>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.duplicate(&payload).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_paid_amount<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SetPaidAmount>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.set_paid_amount(&payload).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_collection_activity<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateCollectionActivity>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.create_collection_activity(&payload).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list_collection_activities<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.get_collection_activities(payload.uuid).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn aging_by_sales_rep<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let tz = map_handler_err(claims.tz(), receivables_module.clone()).await?;
+    let result = map_handler_err(
+        service.aging_by_sales_rep(tz).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn my_aging<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let tz = map_handler_err(claims.tz(), receivables_module.clone()).await?;
+    let result = map_handler_err(service.my_aging(tz).await, receivables_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pdf<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let pdf = map_handler_err(service.print(payload.uuid).await, receivables_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"inline; filename="{}""#, payload.uuid)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+pub async fn send_email<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SendInvoiceEmail>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.send_email(&payload).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn email_deliveries<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.email_deliveries(payload.uuid).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn worksheets<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let result = map_handler_err(
+        service.worksheets(payload.uuid).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn aging_by_customer<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let tz = map_handler_err(claims.tz(), receivables_module.clone()).await?;
+    let result = map_handler_err(
+        service.aging_by_customer(tz).await,
+        receivables_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        receivables_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn statement_pdf<M: ReceivablesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(receivables_module): State<Arc<M>>,
+    Query(payload): Query<CustomerStatementQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), receivables_module.clone());
+    let tz = map_handler_err(claims.tz(), receivables_module.clone()).await?;
+    let pdf = map_handler_err(
+        service.print_statement(&payload, tz).await,
+        receivables_module,
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(
+            r#"inline; filename="{}_{}_{}""#,
+            payload.customer_id, payload.from, payload.to
+        )
+        .parse()
+        .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::common::model::SelectOption;
+    use crate::common::pdf::tests::PDF_GENERATOR_TEST_SYNC;
+    use crate::common::pdf::{MockPdfGenerator, PdfTemplates};
+    use crate::common::storage::MockFileStorage;
+    use crate::tenant::address::repository::MockAddressRepository;
+    use crate::tenant::currencies::tests::currencies_repo;
+    use crate::tenant::customers::model::CustomerCreditExposure;
+    use crate::tenant::customers::repository::MockCustomersRepository;
+    use crate::tenant::document_settings::model::{DocumentRecipient, DocumentSettings};
+    use crate::tenant::document_settings::repository::MockDocumentSettingsRepository;
+    use crate::tenant::receivables::dto::{CustomerStatementPrint, InvoicePrint};
+    use crate::tenant::receivables::model::{
+        CustomerAging, InvoiceEmailDelivery, OpenBalance, Receivable, ReceivableLineTax,
+        SalesRepAging,
+    };
+    use crate::tenant::receivables::{
+        self, repository::MockReceivablesRepository, tests::MockReceivablesModule,
+    };
+    use crate::tenant::taxes::vat_treatment::{TaxLegalText, VatTreatment};
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockReceivablesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut receivables_module = MockReceivablesModule::new();
+        receivables_module
+            .expect_receivables_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        receivables_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(receivables::routes::routes(Arc::new(receivables_module))),
+        )
+    }
+
+    fn credit_app(
+        repo: MockReceivablesRepository,
+        customers_repo: MockCustomersRepository,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let customers_repo = Arc::new(customers_repo);
+        let currencies_repo = Arc::new(currencies_repo("HUF", &["HUF", "EUR"]));
+        let mut address_repo = MockAddressRepository::new();
+        address_repo
+            .expect_get_customer_billing_address()
+            .returning(|_| Ok(None));
+        let address_repo = Arc::new(address_repo);
+        let mut receivables_module = MockReceivablesModule::new();
+        receivables_module
+            .expect_receivables_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        receivables_module
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(customers_repo.clone()));
+        receivables_module
+            .expect_currencies_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(currencies_repo.clone()));
+        receivables_module
+            .expect_address_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(address_repo.clone()));
+        receivables_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(receivables::routes::routes(Arc::new(receivables_module))),
+        )
+    }
+
+    fn line_taxes(vat_treatments: &[&str]) -> Vec<ReceivableLineTax> {
+        vat_treatments
+            .iter()
+            .map(|vat_treatment| ReceivableLineTax {
+                vat_treatment: vat_treatment.to_string(),
+                legal_text: None,
+            })
+            .collect()
+    }
+
+    fn credit_exposure(customer_id: Uuid, mode: &str) -> CustomerCreditExposure {
+        CustomerCreditExposure {
+            customer_id,
+            credit_limit: Some(BigDecimal::from(100000)),
+            currency_code: Some("HUF".to_string()),
+            open_balance: BigDecimal::from(90000),
+            mode: mode.to_string(),
+        }
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn receivable(amount: &str) -> Receivable {
+        Receivable {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            document_number: "SZ-2026-001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from_str(amount).unwrap(),
+            paid_amount: BigDecimal::from(0),
+            credited_amount: BigDecimal::from(0),
+            status: "open".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_blocked_by_credit_limit() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "block")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "document_number": "SZ-2026-002",
+                    "issue_date": "2026-10-01",
+                    "due_date": "2026-10-09",
+                    "currency_code": "huf",
+                    "amount": "10000.01",
+                    "acknowledge_credit_limit": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["error"]["code"], json!("CREDIT_LIMIT_EXCEEDED"));
+        assert_eq!(body["error"]["credit_limit"]["blocking"], json!(true));
+        assert_eq!(
+            body["error"]["credit_limit"]["open_balance"],
+            json!("90000")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_acknowledged_credit_limit_warning() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| input.acknowledge_credit_limit && input.currency_code == "HUF")
+            .returning(|input, _| {
+                Ok(Receivable {
+                    customer_id: input.customer_id,
+                    ..receivable("20000.00")
+                })
+            });
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "warn")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "document_number": "SZ-2026-002",
+                    "issue_date": "2026-10-01",
+                    "due_date": "2026-10-09",
+                    "currency_code": "HUF",
+                    "amount": "20000.00",
+                    "acknowledge_credit_limit": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_in_other_currency_than_credit_limit_is_blocked() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "block")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "document_number": "SZ-2026-002",
+                    "issue_date": "2026-10-01",
+                    "due_date": "2026-10-09",
+                    "currency_code": "EUR",
+                    "amount": "1.00"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["error"]["code"], json!("CREDIT_LIMIT_EXCEEDED"));
+        assert_eq!(
+            body["error"]["credit_limit"]["document_currency_code"],
+            json!("EUR")
+        );
+        assert_eq!(
+            body["error"]["message"],
+            json!("A bizonylat pénzneme eltér a vevő hitelkeretének pénznemétől!")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_in_other_currency_than_credit_limit_needs_acknowledgement() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "warn")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": customer_id,
+                    "document_number": "SZ-2026-002",
+                    "issue_date": "2026-10-01",
+                    "due_date": "2026-10-09",
+                    "currency_code": "EUR",
+                    "amount": "1.00"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["error"]["credit_limit"]["blocking"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let source = receivable("5000.00");
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(source.id))
+            .returning({
+                let source = source.clone();
+                move |_| Ok(source.clone())
+            });
+        repo.expect_get_line_taxes()
+            .times(1)
+            .with(eq(source.id))
+            .returning(|_| Ok(line_taxes(&["domestic", "aam"])));
+        repo.expect_duplicate()
+            .times(1)
+            .withf({
+                let source_id = source.id;
+                move |params, _| {
+                    *params
+                        == DuplicateReceivable {
+                            id: source_id,
+                            include_lines: true,
+                            include_tags: true,
+                            acknowledge_credit_limit: false,
+                        }
+                }
+            })
+            .returning({
+                let source = source.clone();
+                move |_, _| {
+                    Ok(Receivable {
+                        id: Uuid::new_v4(),
+                        document_number: "SZ-2026-00002".to_string(),
+                        ..source.clone()
+                    })
+                }
+            });
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(source.customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "block")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/duplicate",
+                active_tenant_id,
+                json!({"id": source.id, "include_lines": true, "include_tags": true}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["document_number"], json!("SZ-2026-00002"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_blocked_by_credit_limit() {
+        let active_tenant_id = Uuid::new_v4();
+        let source = receivable("20000.00");
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let source = source.clone();
+            move |_| Ok(source.clone())
+        });
+        repo.expect_get_line_taxes()
+            .times(1)
+            .returning(|_| Ok(line_taxes(&["domestic"])));
+        repo.expect_duplicate().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(source.customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, "block")));
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/duplicate",
+                active_tenant_id,
+                json!({
+                    "id": source.id,
+                    "include_lines": true,
+                    "acknowledge_credit_limit": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_requires_lines() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id().never();
+        repo.expect_duplicate().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo.expect_get_credit_exposure().never();
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/duplicate",
+                active_tenant_id,
+                json!({"id": Uuid::new_v4(), "include_lines": false}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_rejects_vat_treatment_outside_customer_jurisdiction() {
+        let active_tenant_id = Uuid::new_v4();
+        let source = receivable("5000.00");
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let source = source.clone();
+            move |_| Ok(source.clone())
+        });
+        repo.expect_get_line_taxes()
+            .times(1)
+            .returning(|_| Ok(line_taxes(&["domestic", "eu_supply"])));
+        repo.expect_duplicate().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo.expect_get_credit_exposure().never();
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/duplicate",
+                active_tenant_id,
+                json!({"id": source.id, "include_lines": true}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_disabled_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo.expect_get_credit_exposure().never();
+
+        let response = credit_app(repo, customers_repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": Uuid::new_v4(),
+                    "document_number": "SZ-2026-002",
+                    "issue_date": "2026-10-01",
+                    "due_date": "2026-10-09",
+                    "currency_code": "USD",
+                    "amount": "100.00",
+                    "acknowledge_credit_limit": false
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_paid_amount_exceeding_amount() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("10000.00");
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+        repo.expect_set_paid_amount().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/receivables/set_paid_amount",
+                active_tenant_id,
+                json!({"id": receivable.id, "paid_amount": "10000.01"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_activity_promise_requires_date() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_insert_collection_activity().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/collection_activities/create",
+                active_tenant_id,
+                json!({
+                    "customer_id": Uuid::new_v4(),
+                    "receivable_id": null,
+                    "activity_type": "promise_to_pay",
+                    "note": "Jövő héten utal",
+                    "promised_amount": "5000",
+                    "promised_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_worksheets_lists_billed_worksheets() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("12700.00");
+        let worksheet = SelectOption {
+            value: Uuid::new_v4().to_string(),
+            title: "Kazán karbantartás".to_string(),
+        };
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let receivable = receivable.clone();
+                move |_| Ok(receivable.clone())
+            });
+        repo.expect_get_worksheets()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning({
+                let worksheet = worksheet.clone();
+                move |_| Ok(vec![worksheet.clone()])
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri(format!(
+                        "/api/receivables/worksheets?uuid={}",
+                        receivable.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": [worksheet]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_my_aging_filters_by_current_user() {
+        let active_tenant_id = Uuid::new_v4();
+        let row = SalesRepAging {
+            sales_rep_id: Some(Uuid::new_v4()),
+            sales_rep_name: Some("Minta János".to_string()),
+            sales_rep_email: Some("janos@example.com".to_string()),
+            currency_code: "HUF".to_string(),
+            not_due: BigDecimal::from(1000),
+            days_1_30: BigDecimal::from(500),
+            days_31_60: BigDecimal::from(0),
+            days_61_90: BigDecimal::from(0),
+            days_over_90: BigDecimal::from(0),
+            overdue_total: BigDecimal::from(500),
+            open_total: BigDecimal::from(1500),
+            open_count: 2,
+            overdue_count: 1,
+            activities_last_30_days: 3,
+            last_activity_at: None,
+        };
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_aging_by_sales_rep()
+            .times(1)
+            .withf(|_, sales_rep_id| sales_rep_id.is_some())
+            .returning({
+                let row = row.clone();
+                move |_, _| Ok(vec![row.clone()])
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri("/api/receivables/my_aging")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": [row]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aging_by_customer_returns_buckets() {
+        let active_tenant_id = Uuid::new_v4();
+        let row = CustomerAging {
+            customer_id: Uuid::new_v4(),
+            customer_name: "Teszt Ügyfél Kft.".to_string(),
+            currency_code: "HUF".to_string(),
+            days_0_30: BigDecimal::from(1000),
+            days_31_60: BigDecimal::from(0),
+            days_61_90: BigDecimal::from(250),
+            days_over_90: BigDecimal::from(4000),
+            open_total: BigDecimal::from(5250),
+            open_count: 3,
+            oldest_due_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        };
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_aging_by_customer().times(1).returning({
+            let row = row.clone();
+            move |_| Ok(vec![row.clone()])
+        });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri("/api/receivables/aging_by_customer")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": [row]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_statement_carries_earlier_balance_per_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let partially_paid = Receivable {
+            customer_id,
+            paid_amount: BigDecimal::from(4000),
+            ..receivable("10000.00")
+        };
+        let paid = Receivable {
+            customer_id,
+            document_number: "SZ-2026-002".to_string(),
+            currency_code: "EUR".to_string(),
+            paid_amount: BigDecimal::from(100),
+            status: "paid".to_string(),
+            ..receivable("100.00")
+        };
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_statement_receivables()
+            .times(1)
+            .with(
+                eq(customer_id),
+                eq(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+                eq(NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()),
+            )
+            .returning(move |_, _, _| Ok(vec![partially_paid.clone(), paid.clone()]));
+        repo.expect_get_open_balances_before()
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![OpenBalance {
+                    currency_code: "HUF".to_string(),
+                    open_balance: BigDecimal::from(5000),
+                }])
+            });
+        let mut document_settings_repo = MockDocumentSettingsRepository::new();
+        document_settings_repo.expect_get().times(1).returning(|| {
+            Ok(DocumentSettings {
+                company_name: Some("Obvia Kft.".to_string()),
+                updated_at: Utc::now(),
+                ..Default::default()
+            })
+        });
+        document_settings_repo
+            .expect_get_recipient()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|_| {
+                Ok(DocumentRecipient {
+                    name: "Teszt Ügyfél".to_string(),
+                    email: "ugyfel@example.com".to_string(),
+                    phone_number: None,
+                })
+            });
+        let mut storage = MockFileStorage::new();
+        storage.expect_get().never();
+
+        let repo = Arc::new(repo);
+        let document_settings_repo = Arc::new(document_settings_repo);
+        let storage = Arc::new(storage);
+        let mut receivables_module = MockReceivablesModule::new();
+        receivables_module
+            .expect_receivables_repo()
+            .returning(move |_| Ok(repo.clone()));
+        receivables_module
+            .expect_document_settings_repo()
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        receivables_module
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        receivables_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_document_context();
+        pdf_gen
+            .expect::<Vec<CustomerStatementPrint>>()
+            .times(1)
+            .withf(|template, payload, logo| {
+                let currencies = &payload[0].currencies;
+                *template == PdfTemplates::CustomerStatementDocument
+                    && logo.is_none()
+                    && currencies.len() == 2
+                    && currencies[0].currency_code == "EUR"
+                    && currencies[0].closing_balance == "0,00\u{a0}EUR"
+                    && currencies[1].earlier_balance == "5\u{a0}000\u{a0}Ft"
+                    && currencies[1].closing_balance == "11\u{a0}000\u{a0}Ft"
+                    && currencies[1].items[0].settled_amount == "4\u{a0}000\u{a0}Ft"
+                    && !currencies[1].items[0].days_overdue.is_empty()
+            })
+            .returning(|_, _, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(receivables::routes::routes(Arc::new(receivables_module))),
+            )
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(None, Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri(format!(
+                        "/api/receivables/statement?customer_id={customer_id}&from=2026-01-01&to=2026-01-31"
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/pdf"
+        );
+    }
+
+    fn email_module(
+        repo: MockReceivablesRepository,
+        document_settings_repo: MockDocumentSettingsRepository,
+        send_calls: usize,
+    ) -> MockReceivablesModule {
+        let repo = Arc::new(repo);
+        let document_settings_repo = Arc::new(document_settings_repo);
+        let storage = Arc::new(MockFileStorage::new());
+        let mut receivables_module = MockReceivablesModule::new();
+        receivables_module
+            .expect_receivables_repo()
+            .returning(move |_| Ok(repo.clone()));
+        receivables_module
+            .expect_document_settings_repo()
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        receivables_module
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        receivables_module
+            .expect_config()
+            .times(1 + send_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        receivables_module
+    }
+
+    fn email_settings_repo(customer_id: Uuid) -> MockDocumentSettingsRepository {
+        let mut document_settings_repo = MockDocumentSettingsRepository::new();
+        document_settings_repo.expect_get().returning(|| {
+            Ok(DocumentSettings {
+                company_name: Some("Obvia Kft.".to_string()),
+                email: Some("iroda@obvia.hu".to_string()),
+                invoice_email_cc: Some("konyveles@obvia.hu".to_string()),
+                updated_at: Utc::now(),
+                ..Default::default()
+            })
+        });
+        document_settings_repo
+            .expect_get_recipient()
+            .with(eq(customer_id))
+            .returning(|_| {
+                Ok(DocumentRecipient {
+                    name: "Teszt Ügyfél".to_string(),
+                    email: "ugyfel@example.com".to_string(),
+                    phone_number: None,
+                })
+            });
+        document_settings_repo
+    }
+
+    fn delivery(receivable_id: Uuid, status: &str, error: Option<String>) -> InvoiceEmailDelivery {
+        InvoiceEmailDelivery {
+            id: Uuid::new_v4(),
+            receivable_id,
+            recipient: "ugyfel@example.com".to_string(),
+            cc: Some("konyveles@obvia.hu".to_string()),
+            bcc: None,
+            language: "hu".to_string(),
+            subject: "Obvia Kft. számla: SZ-2026-001".to_string(),
+            status: status.to_string(),
+            error,
+            sent_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_email_attaches_pdf_and_logs_delivery() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("10000.00");
+        let queued = delivery(receivable.id, "queued", None);
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let receivable = receivable.clone();
+            move |_| Ok(receivable.clone())
+        });
+        repo.expect_insert_email_delivery()
+            .times(1)
+            .withf(|input, _| {
+                input.recipient == "ugyfel@example.com"
+                    && input.cc.as_deref() == Some("konyveles@obvia.hu")
+                    && input.bcc.is_none()
+                    && input.subject == "Obvia Kft. számla: SZ-2026-001"
+            })
+            .returning({
+                let queued = queued.clone();
+                move |_, _| Ok(queued.clone())
+            });
+        repo.expect_get_line_taxes()
+            .times(1)
+            .with(eq(receivable.id))
+            .returning(|_| {
+                Ok(vec![
+                    ReceivableLineTax {
+                        vat_treatment: "aam".to_string(),
+                        legal_text: None,
+                    },
+                    ReceivableLineTax {
+                        vat_treatment: "domestic".to_string(),
+                        legal_text: None,
+                    },
+                ])
+            });
+        repo.expect_online_payment_enabled()
+            .times(1)
+            .returning(|| Ok(true));
+        repo.expect_set_email_delivery_status()
+            .times(1)
+            .withf(|_, status, error| status == "sent" && error.is_none())
+            .returning({
+                let queued = queued.clone();
+                move |_, status, _| {
+                    Ok(InvoiceEmailDelivery {
+                        status: status.to_string(),
+                        sent_at: Some(Utc::now()),
+                        ..queued.clone()
+                    })
+                }
+            });
+        let mut receivables_module =
+            email_module(repo, email_settings_repo(receivable.customer_id), 1);
+        receivables_module
+            .expect_send()
+            .times(1)
+            .withf(|message| {
+                let raw = String::from_utf8_lossy(&message.formatted()).to_string();
+                raw.contains("Cc: konyveles@obvia.hu")
+                    && raw.contains("Reply-To:")
+                    && raw.contains("filename=\"SZ-2026-001.pdf\"")
+            })
+            .returning(|_| Ok(None));
+
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_document_context();
+        pdf_gen
+            .expect::<Vec<InvoicePrint>>()
+            .times(1)
+            .withf(|_, payload, _| {
+                payload[0].legal_texts == vec![TaxLegalText::of(VatTreatment::Aam, None).unwrap()]
+            })
+            .returning(|_, _, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(receivables::routes::routes(Arc::new(receivables_module))),
+            )
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/send_email",
+                active_tenant_id,
+                json!({"id": receivable.id, "to": null, "cc": null, "bcc": "", "language": "hu"}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["status"], json!("sent"));
+    }
+
+    #[tokio::test]
+    async fn test_send_email_rejects_invalid_cc_address() {
+        let active_tenant_id = Uuid::new_v4();
+        let receivable = receivable("10000.00");
+        let mut repo = MockReceivablesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let receivable = receivable.clone();
+            move |_| Ok(receivable.clone())
+        });
+        repo.expect_insert_email_delivery().never();
+        let mut receivables_module =
+            email_module(repo, email_settings_repo(receivable.customer_id), 0);
+        receivables_module.expect_send().never();
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(receivables::routes::routes(Arc::new(receivables_module))),
+            )
+            .oneshot(json_request(
+                "POST",
+                "/api/receivables/send_email",
+                active_tenant_id,
+                json!({
+                    "id": receivable.id,
+                    "to": null,
+                    "cc": "konyveles@obvia.hu; penzugy",
+                    "bcc": null,
+                    "language": "en"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
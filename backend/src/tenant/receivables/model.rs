@@ -0,0 +1,135 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_OPEN: &str = "open";
+pub const STATUS_PAID: &str = "paid";
+pub const STATUS_WRITTEN_OFF: &str = "written_off";
+
+pub const DELIVERY_STATUS_QUEUED: &str = "queued";
+pub const DELIVERY_STATUS_SENT: &str = "sent";
+pub const DELIVERY_STATUS_FAILED: &str = "failed";
+
+pub const EMAIL_LANGUAGES: [&str; 2] = ["hu", "en"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Receivable {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub document_number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub amount: BigDecimal,
+    pub paid_amount: BigDecimal,
+    pub credited_amount: BigDecimal,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Receivable {
+    pub fn open_balance(&self) -> BigDecimal {
+        &self.amount - &self.paid_amount - &self.credited_amount
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CollectionActivity {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub receivable_id: Option<Uuid>,
+    pub activity_type: String,
+    pub note: Option<String>,
+    pub promised_amount: Option<BigDecimal>,
+    pub promised_date: Option<NaiveDate>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SalesRepAging {
+    pub sales_rep_id: Option<Uuid>,
+    pub sales_rep_name: Option<String>,
+    pub sales_rep_email: Option<String>,
+    pub currency_code: String,
+    pub not_due: BigDecimal,
+    pub days_1_30: BigDecimal,
+    pub days_31_60: BigDecimal,
+    pub days_61_90: BigDecimal,
+    pub days_over_90: BigDecimal,
+    pub overdue_total: BigDecimal,
+    pub open_total: BigDecimal,
+    pub open_count: i64,
+    pub overdue_count: i64,
+    pub activities_last_30_days: i64,
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+// NOTE: invoices not yet due are counted in the first bucket
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CustomerAging {
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    pub currency_code: String,
+    pub days_0_30: BigDecimal,
+    pub days_31_60: BigDecimal,
+    pub days_61_90: BigDecimal,
+    pub days_over_90: BigDecimal,
+    pub open_total: BigDecimal,
+    pub open_count: i64,
+    pub oldest_due_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct OpenBalance {
+    pub currency_code: String,
+    pub open_balance: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InvoiceEmailDelivery {
+    pub id: Uuid,
+    pub receivable_id: Uuid,
+    pub recipient: String,
+    pub cc: Option<String>,
+    pub bcc: Option<String>,
+    pub language: String,
+    pub subject: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// VAT treatment and tax legal text of an invoice line, the source of the printed legal texts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ReceivableLineTax {
+    pub vat_treatment: String,
+    pub legal_text: Option<String>,
+}
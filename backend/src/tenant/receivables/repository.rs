@@ -0,0 +1,586 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::copy_tags;
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::model::SelectOption;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::receivables::dto::{
+    CreateCollectionActivity, CreateReceivable, DuplicateReceivable, NewInvoiceEmailDelivery,
+};
+use crate::tenant::receivables::model::{
+    CollectionActivity, CustomerAging, InvoiceEmailDelivery, OpenBalance, Receivable,
+    ReceivableLineTax, SalesRepAging,
+};
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ReceivablesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Receivable>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Receivable>)>;
+    async fn insert(&self, input: &CreateReceivable, sub: Uuid) -> RepositoryResult<Receivable>;
+    async fn duplicate(
+        &self,
+        params: &DuplicateReceivable,
+        sub: Uuid,
+    ) -> RepositoryResult<Receivable>;
+    async fn set_paid_amount(
+        &self,
+        id: Uuid,
+        paid_amount: &BigDecimal,
+    ) -> RepositoryResult<Receivable>;
+    async fn insert_collection_activity(
+        &self,
+        input: &CreateCollectionActivity,
+        sub: Uuid,
+    ) -> RepositoryResult<CollectionActivity>;
+    async fn get_collection_activities(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CollectionActivity>>;
+    async fn get_aging_by_sales_rep(
+        &self,
+        as_of: NaiveDate,
+        sales_rep_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<SalesRepAging>>;
+    async fn get_aging_by_customer(&self, as_of: NaiveDate)
+    -> RepositoryResult<Vec<CustomerAging>>;
+    async fn get_statement_receivables(
+        &self,
+        customer_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<Receivable>>;
+    async fn get_open_balances_before(
+        &self,
+        customer_id: Uuid,
+        before: NaiveDate,
+    ) -> RepositoryResult<Vec<OpenBalance>>;
+    async fn insert_email_delivery(
+        &self,
+        input: &NewInvoiceEmailDelivery,
+        sub: Uuid,
+    ) -> RepositoryResult<InvoiceEmailDelivery>;
+    async fn set_email_delivery_status(
+        &self,
+        id: Uuid,
+        status: &str,
+        error: Option<String>,
+    ) -> RepositoryResult<InvoiceEmailDelivery>;
+    async fn get_email_deliveries(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<InvoiceEmailDelivery>>;
+    async fn online_payment_enabled(&self) -> RepositoryResult<bool>;
+    async fn get_worksheets(&self, receivable_id: Uuid) -> RepositoryResult<Vec<SelectOption>>;
+    async fn get_line_taxes(&self, receivable_id: Uuid)
+    -> RepositoryResult<Vec<ReceivableLineTax>>;
+}
+
+#[async_trait]
+impl ReceivablesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Receivable> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            "SELECT * FROM receivables WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Receivable>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM receivables WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let receivables = sqlx::query_as::<_, Receivable>(
+            r#"
+            SELECT *
+            FROM receivables
+            WHERE deleted_at IS NULL
+            ORDER BY due_date, document_number
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            receivables,
+        ))
+    }
+
+    async fn insert(&self, input: &CreateReceivable, sub: Uuid) -> RepositoryResult<Receivable> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            r#"
+            INSERT INTO receivables (customer_id, document_number, issue_date, due_date,
+                                     currency_code, amount, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&input.document_number)
+        .bind(input.issue_date)
+        .bind(input.due_date)
+        .bind(&input.currency_code)
+        .bind(&input.amount)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn duplicate(
+        &self,
+        params: &DuplicateReceivable,
+        sub: Uuid,
+    ) -> RepositoryResult<Receivable> {
+        let mut tx = self.begin().await?;
+        let scale = CurrencyRules::of(
+            &sqlx::query_scalar::<_, String>(
+                "SELECT currency_code FROM receivables WHERE id = $1 AND deleted_at IS NULL",
+            )
+            .bind(params.id)
+            .fetch_one(&mut *tx)
+            .await?,
+        )
+        .scale;
+        // NOTE: the copy keeps the payment term of the source, payments and credits stay behind,
+        // the lines are taxed at the rates in effect on the new issue date
+        let receivable = sqlx::query_as::<_, Receivable>(
+            r#"
+            INSERT INTO receivables (customer_id, document_number, issue_date, due_date,
+                                     currency_code, amount, created_by_id)
+            SELECT receivables.customer_id,
+                   'SZ-' || to_char(CURRENT_DATE, 'YYYY') || '-'
+                       || lpad(nextval('invoice_number_seq')::text, 5, '0'),
+                   CURRENT_DATE,
+                   CURRENT_DATE + (receivables.due_date - receivables.issue_date),
+                   receivables.currency_code,
+                   sum(amounts.net_amount + amounts.tax_amount),
+                   $2
+            FROM receivables
+            JOIN receivable_lines ON receivable_lines.receivable_id = receivables.id
+            JOIN taxes ON taxes.id = effective_tax_id(receivable_lines.tax_id, CURRENT_DATE)
+            CROSS JOIN LATERAL (
+                SELECT round(receivable_lines.quantity * receivable_lines.unit_price,
+                             $3::integer) AS net_amount,
+                       CASE
+                           WHEN taxes.is_rate_applicable
+                               AND receivable_lines.vat_treatment = 'domestic'
+                               THEN round(receivable_lines.quantity
+                                              * receivable_lines.unit_price
+                                              * COALESCE(taxes.rate, 0) / 100, $3::integer)
+                           ELSE 0
+                       END AS tax_amount
+            ) AS amounts
+            WHERE receivables.id = $1 AND receivables.deleted_at IS NULL
+            GROUP BY receivables.id
+            RETURNING *
+            "#,
+        )
+        .bind(params.id)
+        .bind(sub)
+        .bind(scale)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO receivable_lines (receivable_id, service_id, product_id, description,
+                                          quantity, unit_price, tax_id, tax_rate, net_amount,
+                                          tax_amount, position, vat_treatment)
+            SELECT $1,
+                   receivable_lines.service_id,
+                   receivable_lines.product_id,
+                   receivable_lines.description,
+                   receivable_lines.quantity,
+                   receivable_lines.unit_price,
+                   taxes.id,
+                   CASE
+                       WHEN receivable_lines.vat_treatment = 'domestic'
+                           THEN COALESCE(taxes.rate, 0)
+                       ELSE 0
+                   END,
+                   round(receivable_lines.quantity * receivable_lines.unit_price, $3::integer),
+                   CASE
+                       WHEN taxes.is_rate_applicable
+                           AND receivable_lines.vat_treatment = 'domestic'
+                           THEN round(receivable_lines.quantity
+                                          * receivable_lines.unit_price
+                                          * COALESCE(taxes.rate, 0) / 100, $3::integer)
+                       ELSE 0
+                   END,
+                   receivable_lines.position,
+                   receivable_lines.vat_treatment
+            FROM receivable_lines
+            JOIN taxes ON taxes.id = effective_tax_id(receivable_lines.tax_id, CURRENT_DATE)
+            WHERE receivable_lines.receivable_id = $2
+            "#,
+        )
+        .bind(receivable.id)
+        .bind(params.id)
+        .bind(scale)
+        .execute(&mut *tx)
+        .await?;
+        if params.include_tags {
+            copy_tags(&mut tx, "receivables", params.id, receivable.id, sub).await?;
+        }
+        tx.commit().await?;
+        Ok(receivable)
+    }
+
+    async fn set_paid_amount(
+        &self,
+        id: Uuid,
+        paid_amount: &BigDecimal,
+    ) -> RepositoryResult<Receivable> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            r#"
+            UPDATE receivables
+            SET paid_amount = $1,
+                status = CASE WHEN $1 + credited_amount >= amount THEN 'paid' ELSE 'open' END
+            WHERE id = $2
+                AND status <> 'written_off'
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(paid_amount)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_collection_activity(
+        &self,
+        input: &CreateCollectionActivity,
+        sub: Uuid,
+    ) -> RepositoryResult<CollectionActivity> {
+        Ok(sqlx::query_as::<_, CollectionActivity>(
+            r#"
+            INSERT INTO collection_activities (customer_id, receivable_id, activity_type, note,
+                                               promised_amount, promised_date, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(input.receivable_id)
+        .bind(&input.activity_type)
+        .bind(&input.note)
+        .bind(&input.promised_amount)
+        .bind(input.promised_date)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_collection_activities(
+        &self,
+        customer_id: Uuid,
+    ) -> RepositoryResult<Vec<CollectionActivity>> {
+        Ok(sqlx::query_as::<_, CollectionActivity>(
+            r#"
+            SELECT *
+            FROM collection_activities
+            WHERE customer_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_aging_by_sales_rep(
+        &self,
+        as_of: NaiveDate,
+        sales_rep_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<SalesRepAging>> {
+        Ok(sqlx::query_as::<_, SalesRepAging>(
+            r#"
+            WITH open_items AS (
+                SELECT customers.sales_rep_id,
+                       receivables.currency_code,
+                       receivables.amount - receivables.paid_amount - receivables.credited_amount
+                           AS balance,
+                       $1::DATE - receivables.due_date AS days_overdue
+                FROM receivables
+                JOIN customers ON customers.id = receivables.customer_id
+                WHERE receivables.status = 'open'
+                    AND receivables.deleted_at IS NULL
+                    AND ($2::UUID IS NULL OR customers.sales_rep_id = $2)
+            ),
+            activity AS (
+                SELECT customers.sales_rep_id,
+                       COUNT(*) FILTER (
+                           WHERE collection_activities.created_at >= $1::DATE - 30
+                       ) AS activities_last_30_days,
+                       MAX(collection_activities.created_at) AS last_activity_at
+                FROM collection_activities
+                JOIN customers ON customers.id = collection_activities.customer_id
+                GROUP BY customers.sales_rep_id
+            )
+            SELECT open_items.sales_rep_id,
+                   NULLIF(CONCAT_WS(' ', users.last_name, users.first_name), '') AS sales_rep_name,
+                   users.email AS sales_rep_email,
+                   open_items.currency_code,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue <= 0), 0) AS not_due,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue BETWEEN 1 AND 30), 0) AS days_1_30,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue BETWEEN 31 AND 60), 0) AS days_31_60,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue BETWEEN 61 AND 90), 0) AS days_61_90,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue > 90), 0) AS days_over_90,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue > 0), 0) AS overdue_total,
+                   SUM(balance) AS open_total,
+                   COUNT(*) AS open_count,
+                   COUNT(*) FILTER (WHERE days_overdue > 0) AS overdue_count,
+                   COALESCE(MAX(activity.activities_last_30_days), 0) AS activities_last_30_days,
+                   MAX(activity.last_activity_at) AS last_activity_at
+            FROM open_items
+            LEFT JOIN users ON users.id = open_items.sales_rep_id
+            LEFT JOIN activity ON activity.sales_rep_id IS NOT DISTINCT FROM open_items.sales_rep_id
+            GROUP BY open_items.sales_rep_id, users.last_name, users.first_name, users.email,
+                     open_items.currency_code
+            ORDER BY overdue_total DESC, sales_rep_name
+            "#,
+        )
+        .bind(as_of)
+        .bind(sales_rep_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_aging_by_customer(
+        &self,
+        as_of: NaiveDate,
+    ) -> RepositoryResult<Vec<CustomerAging>> {
+        Ok(sqlx::query_as::<_, CustomerAging>(
+            r#"
+            WITH open_items AS (
+                SELECT receivables.customer_id,
+                       receivables.currency_code,
+                       receivables.due_date,
+                       receivables.amount - receivables.paid_amount - receivables.credited_amount
+                           AS balance,
+                       $1::DATE - receivables.due_date AS days_overdue
+                FROM receivables
+                WHERE receivables.status = 'open'
+                    AND receivables.deleted_at IS NULL
+            )
+            SELECT open_items.customer_id,
+                   customers.name AS customer_name,
+                   open_items.currency_code,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue <= 30), 0) AS days_0_30,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue BETWEEN 31 AND 60), 0) AS days_31_60,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue BETWEEN 61 AND 90), 0) AS days_61_90,
+                   COALESCE(SUM(balance) FILTER (WHERE days_overdue > 90), 0) AS days_over_90,
+                   SUM(balance) AS open_total,
+                   COUNT(*) AS open_count,
+                   MIN(open_items.due_date) AS oldest_due_date
+            FROM open_items
+            JOIN customers ON customers.id = open_items.customer_id
+            GROUP BY open_items.customer_id, customers.name, open_items.currency_code
+            ORDER BY days_over_90 DESC, days_61_90 DESC, days_31_60 DESC, open_total DESC,
+                     customer_name
+            "#,
+        )
+        .bind(as_of)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_statement_receivables(
+        &self,
+        customer_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<Receivable>> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            r#"
+            SELECT *
+            FROM receivables
+            WHERE customer_id = $1
+                AND issue_date BETWEEN $2 AND $3
+                AND status <> 'written_off'
+                AND deleted_at IS NULL
+            ORDER BY issue_date, document_number
+            "#,
+        )
+        .bind(customer_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_open_balances_before(
+        &self,
+        customer_id: Uuid,
+        before: NaiveDate,
+    ) -> RepositoryResult<Vec<OpenBalance>> {
+        Ok(sqlx::query_as::<_, OpenBalance>(
+            r#"
+            SELECT currency_code,
+                   SUM(amount - paid_amount - credited_amount) AS open_balance
+            FROM receivables
+            WHERE customer_id = $1
+                AND issue_date < $2
+                AND status = 'open'
+                AND deleted_at IS NULL
+            GROUP BY currency_code
+            ORDER BY currency_code
+            "#,
+        )
+        .bind(customer_id)
+        .bind(before)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_email_delivery(
+        &self,
+        input: &NewInvoiceEmailDelivery,
+        sub: Uuid,
+    ) -> RepositoryResult<InvoiceEmailDelivery> {
+        Ok(sqlx::query_as::<_, InvoiceEmailDelivery>(
+            r#"
+            INSERT INTO invoice_email_deliveries (receivable_id, recipient, cc, bcc, language,
+                                                  subject, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(input.receivable_id)
+        .bind(&input.recipient)
+        .bind(&input.cc)
+        .bind(&input.bcc)
+        .bind(&input.language)
+        .bind(&input.subject)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_email_delivery_status(
+        &self,
+        id: Uuid,
+        status: &str,
+        error: Option<String>,
+    ) -> RepositoryResult<InvoiceEmailDelivery> {
+        Ok(sqlx::query_as::<_, InvoiceEmailDelivery>(
+            r#"
+            UPDATE invoice_email_deliveries
+            SET status = $2,
+                error = $3,
+                sent_at = CASE WHEN $2 = 'sent' THEN now() ELSE sent_at END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(error)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_email_deliveries(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<InvoiceEmailDelivery>> {
+        Ok(sqlx::query_as::<_, InvoiceEmailDelivery>(
+            r#"
+            SELECT *
+            FROM invoice_email_deliveries
+            WHERE receivable_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn online_payment_enabled(&self) -> RepositoryResult<bool> {
+        let (enabled,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM payment_provider_settings WHERE enabled)")
+                .fetch_one(self)
+                .await?;
+        Ok(enabled)
+    }
+
+    async fn get_worksheets(&self, receivable_id: Uuid) -> RepositoryResult<Vec<SelectOption>> {
+        Ok(sqlx::query_as::<_, SelectOption>(
+            r#"
+            SELECT worksheets.id::VARCHAR as value, worksheets.name as title
+            FROM worksheet_receivables
+            JOIN worksheets ON worksheet_receivables.worksheet_id = worksheets.id
+            WHERE worksheet_receivables.receivable_id = $1
+            ORDER BY worksheets.name
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_line_taxes(
+        &self,
+        receivable_id: Uuid,
+    ) -> RepositoryResult<Vec<ReceivableLineTax>> {
+        Ok(sqlx::query_as::<_, ReceivableLineTax>(
+            r#"
+            SELECT receivable_lines.vat_treatment, taxes.legal_text
+            FROM receivable_lines
+            JOIN taxes ON receivable_lines.tax_id = taxes.id
+            WHERE receivable_lines.receivable_id = $1
+            ORDER BY receivable_lines.position
+            "#,
+        )
+        .bind(receivable_id)
+        .fetch_all(self)
+        .await?)
+    }
+}
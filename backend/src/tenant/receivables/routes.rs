@@ -0,0 +1,56 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::ReceivablesModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: ReceivablesModuleInterface>(receivables_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/receivables",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/list", get(handler::list::<M>))
+            .route("/pdf", get(handler::pdf::<M>))
+            .route("/create", post(handler::create::<M>))
+            .route("/duplicate", post(handler::duplicate::<M>))
+            .route("/set_paid_amount", put(handler::set_paid_amount::<M>))
+            .route("/send_email", post(handler::send_email::<M>))
+            .route("/email_deliveries", get(handler::email_deliveries::<M>))
+            .route("/worksheets", get(handler::worksheets::<M>))
+            .route(
+                "/collection_activities/create",
+                post(handler::create_collection_activity::<M>),
+            )
+            .route(
+                "/collection_activities/list",
+                get(handler::list_collection_activities::<M>),
+            )
+            .route("/aging_by_sales_rep", get(handler::aging_by_sales_rep::<M>))
+            .route("/my_aging", get(handler::my_aging::<M>))
+            .route("/aging_by_customer", get(handler::aging_by_customer::<M>))
+            .route("/statement", get(handler::statement_pdf::<M>))
+            .layer(from_fn_with_state(receivables_module.clone(), require_auth))
+            .with_state(receivables_module),
+    )
+}
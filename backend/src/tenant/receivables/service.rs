@@ -0,0 +1,786 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::email_template::{EmailAttachment, EmailTemplate};
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::model::SelectOption;
+#[double]
+use crate::common::pdf::PdfGenerator;
+use crate::common::pdf::{PdfGenError, PdfTemplates, format_date, format_money};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::storage::FileStorage;
+use crate::common::types::Empty;
+use crate::common::utils::parse_email_list;
+use crate::tenant::currencies::service::{CurrenciesServiceError, document_currency};
+use crate::tenant::customers::model::CreditLimitBreach;
+use crate::tenant::document_settings::dto::Letterhead;
+use crate::tenant::document_settings::model::DocumentRecipient;
+use crate::tenant::document_settings::repository::DocumentSettingsRepository;
+use crate::tenant::document_settings::service::load_letterhead;
+use crate::tenant::payment_links::service::pay_url;
+use crate::tenant::receivables::ReceivablesModuleInterface;
+use crate::tenant::receivables::dto::{
+    CreateCollectionActivity, CreateReceivable, CustomerStatementPrint, CustomerStatementQuery,
+    DuplicateReceivable, InvoicePrint, NewInvoiceEmailDelivery, SendInvoiceEmail, SetPaidAmount,
+};
+use crate::tenant::receivables::model::{
+    CollectionActivity, CustomerAging, DELIVERY_STATUS_FAILED, DELIVERY_STATUS_SENT,
+    EMAIL_LANGUAGES, InvoiceEmailDelivery, Receivable, STATUS_OPEN, STATUS_PAID,
+    STATUS_WRITTEN_OFF, SalesRepAging,
+};
+use crate::tenant::receivables::repository::ReceivablesRepository;
+use crate::tenant::taxes::jurisdiction::{TaxJurisdiction, customer_jurisdiction};
+use crate::tenant::taxes::vat_treatment::TaxLegalText;
+
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use chrono_tz::Tz;
+use lettre::message::Mailbox;
+use lettre::message::header::ContentType;
+use lettre::{Address, Message};
+use mockall_double::double;
+use serde_json::json;
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ReceivablesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Currencies(#[from] CurrenciesServiceError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("PdfGen error: {0}")]
+    PdfGenError(#[from] PdfGenError),
+
+    #[error("Email template error: {0}")]
+    EmailTemplate(String),
+
+    #[error("{}", .0.message())]
+    CreditLimitExceeded(Box<CreditLimitBreach>),
+}
+
+impl From<ServiceError> for ReceivablesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ReceivablesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ReceivablesServiceError> for AppError {
+    fn from(value: ReceivablesServiceError) -> Self {
+        match value {
+            ReceivablesServiceError::Currencies(error) => error.into(),
+            ReceivablesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ReceivablesServiceError::CreditLimitExceeded(ref breach) => Self::new(
+                Level::DEBUG,
+                ErrorCode::CreditLimitExceeded.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::CreditLimitExceeded.code(),
+                    "message": value.to_string(),
+                    "credit_limit": breach
+                }),
+            ),
+            ReceivablesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ReceivablesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ReceivablesServiceResult<T> = Result<T, ReceivablesServiceError>;
+
+const ACTIVITY_TYPES: [&str; 6] = ["call", "email", "letter", "visit", "promise_to_pay", "note"];
+
+fn validate_receivable(payload: &CreateReceivable) -> ReceivablesServiceResult<CreateReceivable> {
+    let document_number = payload.document_number.trim();
+    if document_number.is_empty() || document_number.chars().count() > 100 {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "A bizonylatszám megadása kötelező és legfeljebb 100 karakter lehet!",
+        ));
+    }
+    if payload.due_date < payload.issue_date {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "A fizetési határidő nem lehet korábbi a kiállítás dátumánál!",
+        ));
+    }
+    if payload.amount <= BigDecimal::zero() {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "Az összegnek pozitív számnak kell lennie!",
+        ));
+    }
+    Ok(CreateReceivable {
+        document_number: document_number.to_string(),
+        ..payload.clone()
+    })
+}
+
+fn validate_activity(payload: &CreateCollectionActivity) -> ReceivablesServiceResult<()> {
+    if !ACTIVITY_TYPES.contains(&payload.activity_type.as_str()) {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "Hibás tevékenység típus!",
+        ));
+    }
+    if payload.activity_type == "promise_to_pay" && payload.promised_date.is_none() {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "Fizetési ígéret esetén a dátum megadása kötelező!",
+        ));
+    }
+    if payload
+        .promised_amount
+        .as_ref()
+        .is_some_and(|amount| *amount <= BigDecimal::zero())
+    {
+        return Err(ReceivablesServiceError::UnprocessableEntry(
+            "Az ígért összegnek pozitív számnak kell lennie!",
+        ));
+    }
+    Ok(())
+}
+
+fn email_addresses(
+    value: &Option<String>,
+    fallback: Option<&str>,
+    message: &'static str,
+) -> ReceivablesServiceResult<Vec<Address>> {
+    let value = value
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .or(fallback)
+        .unwrap_or_default();
+    parse_email_list(value).ok_or(ReceivablesServiceError::UnprocessableEntry(message))
+}
+
+fn join_addresses(addresses: &[Address]) -> Option<String> {
+    (!addresses.is_empty()).then(|| {
+        addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
+
+pub(crate) async fn render_invoice_pdf(
+    receivables_repo: &(dyn ReceivablesRepository + Send + Sync),
+    document_settings_repo: &(dyn DocumentSettingsRepository + Send + Sync),
+    storage: &(dyn FileStorage + Send + Sync),
+    receivable: Receivable,
+    recipient: DocumentRecipient,
+) -> ReceivablesServiceResult<(Letterhead, Vec<u8>)> {
+    let line_taxes = receivables_repo.get_line_taxes(receivable.id).await?;
+    let legal_texts = TaxLegalText::collect(line_taxes.iter().map(|line| {
+        (
+            line.vat_treatment.parse().unwrap_or_default(),
+            line.legal_text.as_deref(),
+        )
+    }));
+    let mut letterhead = load_letterhead(document_settings_repo, storage).await?;
+    let logo = letterhead.logo.take();
+    let pdf = PdfGenerator::gen_pdf_document(
+        &PdfTemplates::InvoiceDocument,
+        vec![InvoicePrint::new(
+            receivable,
+            letterhead.clone(),
+            recipient,
+            legal_texts,
+        )],
+        logo,
+    )?;
+    Ok((letterhead, pdf))
+}
+
+pub trait ReceivablesService {
+    fn get(&self, id: Uuid) -> impl Future<Output = ReceivablesServiceResult<Receivable>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = ReceivablesServiceResult<(PaginatorMeta, Vec<Receivable>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreateReceivable,
+    ) -> impl Future<Output = ReceivablesServiceResult<Receivable>> + Send;
+    fn duplicate(
+        &self,
+        payload: &DuplicateReceivable,
+    ) -> impl Future<Output = ReceivablesServiceResult<Receivable>> + Send;
+    fn set_paid_amount(
+        &self,
+        payload: &SetPaidAmount,
+    ) -> impl Future<Output = ReceivablesServiceResult<Receivable>> + Send;
+    fn create_collection_activity(
+        &self,
+        payload: &CreateCollectionActivity,
+    ) -> impl Future<Output = ReceivablesServiceResult<CollectionActivity>> + Send;
+    fn get_collection_activities(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<CollectionActivity>>> + Send;
+    fn aging_by_sales_rep(
+        &self,
+        tz: Tz,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<SalesRepAging>>> + Send;
+    fn my_aging(
+        &self,
+        tz: Tz,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<SalesRepAging>>> + Send;
+    fn print(&self, id: Uuid) -> impl Future<Output = ReceivablesServiceResult<Vec<u8>>> + Send;
+    fn send_email(
+        &self,
+        payload: &SendInvoiceEmail,
+    ) -> impl Future<Output = ReceivablesServiceResult<InvoiceEmailDelivery>> + Send;
+    fn email_deliveries(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<InvoiceEmailDelivery>>> + Send;
+    fn worksheets(
+        &self,
+        receivable_id: Uuid,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<SelectOption>>> + Send;
+    fn aging_by_customer(
+        &self,
+        tz: Tz,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<CustomerAging>>> + Send;
+    fn print_statement(
+        &self,
+        query: &CustomerStatementQuery,
+        tz: Tz,
+    ) -> impl Future<Output = ReceivablesServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> ReceivablesService for Service<'a, T>
+where
+    T: ReceivablesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> ReceivablesServiceResult<Receivable> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> ReceivablesServiceResult<(PaginatorMeta, Vec<Receivable>)> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &CreateReceivable) -> ReceivablesServiceResult<Receivable> {
+        let input = validate_receivable(payload)?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ReceivablesServiceError::Unauthorized)?;
+        let input = CreateReceivable {
+            currency_code: document_currency(
+                &*self.module().currencies_repo(tenant_id)?,
+                &input.currency_code,
+            )
+            .await?,
+            ..input
+        };
+        if let Some(breach) = self
+            .module()
+            .customers_repo(tenant_id)?
+            .get_credit_exposure(input.customer_id)
+            .await?
+            .check(
+                &input.currency_code,
+                &input.amount,
+                input.acknowledge_credit_limit,
+            )
+        {
+            return Err(ReceivablesServiceError::CreditLimitExceeded(Box::new(
+                breach,
+            )));
+        }
+        Ok(self
+            .module()
+            .receivables_repo(tenant_id)?
+            .insert(&input, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn duplicate(
+        &self,
+        payload: &DuplicateReceivable,
+    ) -> ReceivablesServiceResult<Receivable> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ReceivablesServiceError::Unauthorized)?;
+        // NOTE: the amount of an invoice comes from its lines, a copy without them would repeat
+        // the total of the source with outdated taxes
+        if !payload.include_lines {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "A számla csak a tételeivel együtt másolható!",
+            ));
+        }
+        let repo = self.module().receivables_repo(tenant_id)?;
+        let receivable = repo.get_by_id(payload.id).await?;
+        let lines = repo.get_line_taxes(receivable.id).await?;
+        if lines.is_empty() {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "Tételek nélküli számla nem másolható!",
+            ));
+        }
+        let jurisdiction = customer_jurisdiction(
+            &*self.module().address_repo(tenant_id)?,
+            receivable.customer_id,
+        )
+        .await?;
+        if lines
+            .iter()
+            .any(|line| !jurisdiction.allows(line.vat_treatment.parse().unwrap_or_default()))
+        {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                TaxJurisdiction::MISMATCH,
+            ));
+        }
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &receivable.currency_code,
+        )
+        .await?;
+        if let Some(breach) = self
+            .module()
+            .customers_repo(tenant_id)?
+            .get_credit_exposure(receivable.customer_id)
+            .await?
+            .check(
+                &currency_code,
+                &receivable.amount,
+                payload.acknowledge_credit_limit,
+            )
+        {
+            return Err(ReceivablesServiceError::CreditLimitExceeded(Box::new(
+                breach,
+            )));
+        }
+        Ok(repo.duplicate(payload, self.claims()?.sub()).await?)
+    }
+
+    async fn set_paid_amount(
+        &self,
+        payload: &SetPaidAmount,
+    ) -> ReceivablesServiceResult<Receivable> {
+        let repo = self.module().receivables_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ReceivablesServiceError::Unauthorized)?,
+        )?;
+        let receivable = repo.get_by_id(payload.id).await?;
+        if receivable.status != STATUS_OPEN && receivable.status != STATUS_PAID {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "Leírt követelés nem módosítható!",
+            ));
+        }
+        if payload.paid_amount < BigDecimal::zero()
+            || payload.paid_amount > &receivable.amount - &receivable.credited_amount
+        {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "A kiegyenlített összeg nem lehet negatív és nem haladhatja meg a követelés összegét!",
+            ));
+        }
+        Ok(repo
+            .set_paid_amount(payload.id, &payload.paid_amount)
+            .await?)
+    }
+
+    async fn create_collection_activity(
+        &self,
+        payload: &CreateCollectionActivity,
+    ) -> ReceivablesServiceResult<CollectionActivity> {
+        validate_activity(payload)?;
+        let repo = self.module().receivables_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ReceivablesServiceError::Unauthorized)?,
+        )?;
+        if let Some(receivable_id) = payload.receivable_id
+            && repo.get_by_id(receivable_id).await?.customer_id != payload.customer_id
+        {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "A követelés nem az adott ügyfélhez tartozik!",
+            ));
+        }
+        Ok(repo
+            .insert_collection_activity(payload, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get_collection_activities(
+        &self,
+        customer_id: Uuid,
+    ) -> ReceivablesServiceResult<Vec<CollectionActivity>> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_collection_activities(customer_id)
+            .await?)
+    }
+
+    async fn aging_by_sales_rep(&self, tz: Tz) -> ReceivablesServiceResult<Vec<SalesRepAging>> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_aging_by_sales_rep(Utc::now().with_timezone(&tz).date_naive(), None)
+            .await?)
+    }
+
+    async fn my_aging(&self, tz: Tz) -> ReceivablesServiceResult<Vec<SalesRepAging>> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_aging_by_sales_rep(
+                Utc::now().with_timezone(&tz).date_naive(),
+                Some(self.claims()?.sub()),
+            )
+            .await?)
+    }
+
+    async fn print(&self, id: Uuid) -> ReceivablesServiceResult<Vec<u8>> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ReceivablesServiceError::Unauthorized)?;
+        let repo = self.module().receivables_repo(tenant_id)?;
+        let receivable = repo.get_by_id(id).await?;
+        let document_settings_repo = self.module().document_settings_repo(tenant_id)?;
+        let recipient = document_settings_repo
+            .get_recipient(receivable.customer_id)
+            .await?;
+        let (_, pdf) = render_invoice_pdf(
+            &*repo,
+            &*document_settings_repo,
+            &*self.module().file_storage(),
+            receivable,
+            recipient,
+        )
+        .await?;
+        Ok(pdf)
+    }
+
+    // NOTE: the delivery is logged before sending, a transport error is recorded on the log
+    // entry instead of failing the request so the attempt stays visible on the invoice
+    async fn send_email(
+        &self,
+        payload: &SendInvoiceEmail,
+    ) -> ReceivablesServiceResult<InvoiceEmailDelivery> {
+        let language = payload
+            .language
+            .as_deref()
+            .map(str::trim)
+            .filter(|language| !language.is_empty())
+            .unwrap_or("hu");
+        if !EMAIL_LANGUAGES.contains(&language) {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "Nem támogatott nyelv!",
+            ));
+        }
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ReceivablesServiceError::Unauthorized)?;
+        let repo = self.module().receivables_repo(tenant_id)?;
+        let receivable = repo.get_by_id(payload.id).await?;
+        if receivable.status == STATUS_WRITTEN_OFF {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "Leírt számla nem küldhető ki!",
+            ));
+        }
+        let document_settings_repo = self.module().document_settings_repo(tenant_id)?;
+        let settings = document_settings_repo.get().await?;
+        let recipient = document_settings_repo
+            .get_recipient(receivable.customer_id)
+            .await?;
+        let to = email_addresses(
+            &payload.to,
+            Some(&recipient.email),
+            "A címzett e-mail címe nem megfelelő!",
+        )?;
+        if to.is_empty() {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "A címzett e-mail címének megadása kötelező!",
+            ));
+        }
+        let cc = email_addresses(
+            &payload.cc,
+            settings.invoice_email_cc.as_deref(),
+            "A másolatot kapó (CC) címek formátuma nem megfelelő!",
+        )?;
+        let bcc = email_addresses(
+            &payload.bcc,
+            settings.invoice_email_bcc.as_deref(),
+            "A titkos másolatot kapó (BCC) címek formátuma nem megfelelő!",
+        )?;
+
+        let (letterhead, pdf) = render_invoice_pdf(
+            &*repo,
+            &*document_settings_repo,
+            &*self.module().file_storage(),
+            receivable.clone(),
+            recipient.clone(),
+        )
+        .await?;
+        let config = self.module().config();
+        let open_balance = receivable.open_balance();
+        let payment_url = if receivable.status == STATUS_OPEN
+            && open_balance > BigDecimal::zero()
+            && repo.online_payment_enabled().await?
+        {
+            Some(pay_url(
+                config.server().public_base_url(),
+                tenant_id,
+                receivable.id,
+            ))
+        } else {
+            None
+        };
+        let template = match language {
+            "en" => EmailTemplate::InvoiceDeliveryEn,
+            _ => EmailTemplate::InvoiceDelivery,
+        };
+        let rendered = template
+            .render(&json!({
+                "company_name": letterhead.company_name,
+                "customer_name": recipient.name,
+                "document_number": receivable.document_number,
+                "issue_date": format_date(receivable.issue_date),
+                "due_date": format_date(receivable.due_date),
+                "open_balance": format_money(&open_balance, &receivable.currency_code),
+                "bank_account": letterhead.bank_account,
+                "payment_url": payment_url,
+            }))
+            .map_err(|e| ReceivablesServiceError::EmailTemplate(e.to_string()))?;
+
+        let delivery = repo
+            .insert_email_delivery(
+                &NewInvoiceEmailDelivery {
+                    receivable_id: receivable.id,
+                    recipient: join_addresses(&to).unwrap_or_default(),
+                    cc: join_addresses(&cc),
+                    bcc: join_addresses(&bcc),
+                    language: language.to_string(),
+                    subject: rendered.subject.clone(),
+                },
+                self.claims()?.sub(),
+            )
+            .await?;
+        let result: anyhow::Result<()> = async {
+            let mail_config = config.mail();
+            let mut builder = Message::builder().from(Mailbox::new(
+                Some(mail_config.default_from_name().to_owned()),
+                mail_config.default_from().parse()?,
+            ));
+            for address in to {
+                builder = builder.to(Mailbox::new(Some(recipient.name.clone()), address));
+            }
+            for address in cc {
+                builder = builder.cc(Mailbox::new(None, address));
+            }
+            for address in bcc {
+                builder = builder.bcc(Mailbox::new(None, address));
+            }
+            if let Some(reply_to) = letterhead
+                .email
+                .as_deref()
+                .and_then(|email| email.parse::<Address>().ok())
+            {
+                builder = builder.reply_to(Mailbox::new(letterhead.company_name.clone(), reply_to));
+            }
+            let message = rendered.into_message_with_attachments(
+                builder,
+                vec![EmailAttachment {
+                    filename: format!("{}.pdf", receivable.document_number.replace('/', "-")),
+                    content_type: ContentType::parse("application/pdf")?,
+                    data: pdf,
+                }],
+            )?;
+            self.module().send(message).await?;
+            Ok(())
+        }
+        .await;
+        Ok(match result {
+            Ok(()) => {
+                repo.set_email_delivery_status(delivery.id, DELIVERY_STATUS_SENT, None)
+                    .await?
+            }
+            Err(e) => {
+                warn!(
+                    "Could not email invoice {}: {}",
+                    receivable.document_number, e
+                );
+                repo.set_email_delivery_status(
+                    delivery.id,
+                    DELIVERY_STATUS_FAILED,
+                    Some(e.to_string()),
+                )
+                .await?
+            }
+        })
+    }
+
+    async fn email_deliveries(
+        &self,
+        receivable_id: Uuid,
+    ) -> ReceivablesServiceResult<Vec<InvoiceEmailDelivery>> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_email_deliveries(receivable_id)
+            .await?)
+    }
+
+    async fn worksheets(&self, receivable_id: Uuid) -> ReceivablesServiceResult<Vec<SelectOption>> {
+        let repo = self.module().receivables_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ReceivablesServiceError::Unauthorized)?,
+        )?;
+        repo.get_by_id(receivable_id).await?;
+        Ok(repo.get_worksheets(receivable_id).await?)
+    }
+
+    async fn aging_by_customer(&self, tz: Tz) -> ReceivablesServiceResult<Vec<CustomerAging>> {
+        Ok(self
+            .module()
+            .receivables_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ReceivablesServiceError::Unauthorized)?,
+            )?
+            .get_aging_by_customer(Utc::now().with_timezone(&tz).date_naive())
+            .await?)
+    }
+
+    async fn print_statement(
+        &self,
+        query: &CustomerStatementQuery,
+        tz: Tz,
+    ) -> ReceivablesServiceResult<Vec<u8>> {
+        if query.from > query.to {
+            return Err(ReceivablesServiceError::UnprocessableEntry(
+                "Az időszak kezdete nem lehet későbbi a végénél!",
+            ));
+        }
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(ReceivablesServiceError::Unauthorized)?;
+        let repo = self.module().receivables_repo(tenant_id)?;
+        let document_settings_repo = self.module().document_settings_repo(tenant_id)?;
+        let recipient = document_settings_repo
+            .get_recipient(query.customer_id)
+            .await?;
+        let receivables = repo
+            .get_statement_receivables(query.customer_id, query.from, query.to)
+            .await?;
+        let earlier_balances = repo
+            .get_open_balances_before(query.customer_id, query.from)
+            .await?;
+        let mut letterhead =
+            load_letterhead(&*document_settings_repo, &*self.module().file_storage()).await?;
+        let logo = letterhead.logo.take();
+        Ok(PdfGenerator::gen_pdf_document(
+            &PdfTemplates::CustomerStatementDocument,
+            vec![CustomerStatementPrint::new(
+                letterhead,
+                recipient,
+                query,
+                Utc::now().with_timezone(&tz).date_naive(),
+                receivables,
+                earlier_balances,
+            )],
+            logo,
+        )?)
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::email_template::EmailTemplate;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::receivables::ReceivablesModuleInterface;
+use crate::tenant::receivables::model::SalesRepAging;
+use chrono::{NaiveDate, Utc};
+use lettre::{
+    AsyncTransport,
+    message::Mailbox,
+    transport::smtp::{Error, response::Response},
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{Instant, interval_at};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+struct SalesRepSummary {
+    name: String,
+    email: String,
+    rows: Vec<SalesRepAging>,
+}
+
+fn group_by_sales_rep(rows: Vec<SalesRepAging>) -> Vec<SalesRepSummary> {
+    let mut summaries: BTreeMap<Uuid, SalesRepSummary> = BTreeMap::new();
+    for row in rows {
+        let (Some(sales_rep_id), Some(email)) = (row.sales_rep_id, row.sales_rep_email.clone())
+        else {
+            continue;
+        };
+        summaries
+            .entry(sales_rep_id)
+            .or_insert_with(|| SalesRepSummary {
+                name: row.sales_rep_name.clone().unwrap_or_else(|| email.clone()),
+                email,
+                rows: vec![],
+            })
+            .rows
+            .push(row);
+    }
+    summaries.into_values().collect()
+}
+
+async fn send_tenant_summaries<M: ReceivablesModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    as_of: NaiveDate,
+) -> anyhow::Result<()> {
+    let rows = module
+        .receivables_repo(tenant_id)?
+        .get_aging_by_sales_rep(as_of, None)
+        .await?;
+    let from = Mailbox::new(
+        Some(module.config().mail().default_from_name().to_owned()),
+        module.config().mail().default_from().parse()?,
+    );
+    for summary in group_by_sales_rep(rows) {
+        let to = match summary.email.parse() {
+            Ok(address) => Mailbox::new(Some(summary.name.clone()), address),
+            Err(e) => {
+                warn!("Invalid sales rep email {}: {}", summary.email, e);
+                continue;
+            }
+        };
+        let message = EmailTemplate::ReceivablesSummary
+            .render(&json!({
+                "sales_rep_name": summary.name,
+                "as_of": as_of,
+                "rows": summary.rows,
+            }))?
+            .into_message(from.clone(), to)?;
+        if let Err(e) = module.send(message).await {
+            warn!(
+                "Could not send receivables summary to {}: {}",
+                summary.email, e
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_summary_mailer<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let period =
+        Duration::from_secs(app_state.config().receivables().summary_interval_hours() * 3600);
+    tokio::spawn(async move {
+        // NOTE: the first tick is delayed by a full period so restarts do not resend the summary
+        let mut interval = interval_at(Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for receivables summary: {}", e);
+                    continue;
+                }
+            };
+            let as_of = Utc::now().date_naive();
+            for tenant in tenants {
+                if let Err(e) = send_tenant_summaries(&*app_state, tenant.id, as_of).await {
+                    error!("Receivables summary failed for tenant {}: {}", tenant.id, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+
+    fn row(sales_rep_id: Option<Uuid>, email: Option<&str>, currency_code: &str) -> SalesRepAging {
+        SalesRepAging {
+            sales_rep_id,
+            sales_rep_name: None,
+            sales_rep_email: email.map(str::to_string),
+            currency_code: currency_code.to_string(),
+            not_due: BigDecimal::from(0),
+            days_1_30: BigDecimal::from(0),
+            days_31_60: BigDecimal::from(0),
+            days_61_90: BigDecimal::from(0),
+            days_over_90: BigDecimal::from(0),
+            overdue_total: BigDecimal::from(0),
+            open_total: BigDecimal::from(0),
+            open_count: 0,
+            overdue_count: 0,
+            activities_last_30_days: 0,
+            last_activity_at: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_sales_rep_skips_unassigned() {
+        let sales_rep_id = Uuid::new_v4();
+        let summaries = group_by_sales_rep(vec![
+            row(Some(sales_rep_id), Some("rep@example.com"), "HUF"),
+            row(None, None, "HUF"),
+            row(Some(sales_rep_id), Some("rep@example.com"), "EUR"),
+        ]);
+
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].email, "rep@example.com");
+        assert_eq!(summaries[0].name, "rep@example.com");
+        assert_eq!(summaries[0].rows.len(), 2);
+    }
+}
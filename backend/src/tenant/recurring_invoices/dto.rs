@@ -0,0 +1,63 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::taxes::vat_treatment::VatTreatment;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IssueRunInput {
+    pub uuid: Uuid,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RecurringInvoiceLineInput {
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    #[serde(default)]
+    pub vat_treatment: VatTreatment,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RecurringInvoiceInput {
+    pub id: Option<Uuid>,
+    pub customer_id: Uuid,
+    pub title: String,
+    /// The default currency of the tenant when empty
+    #[serde(default)]
+    pub currency_code: String,
+    pub recurrence: String,
+    pub cron_expression: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub payment_term_days: Option<i32>,
+    pub auto_issue: bool,
+    pub send_email: bool,
+    #[serde(default)]
+    pub lines: Vec<RecurringInvoiceLineInput>,
+}
@@ -0,0 +1,748 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::recurring_invoices::RecurringInvoicesModuleInterface;
+use crate::tenant::recurring_invoices::dto::{IssueRunInput, RecurringInvoiceInput};
+use crate::tenant::recurring_invoices::service::RecurringInvoicesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RecurringInvoiceInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<RecurringInvoiceInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az ismétlődő számla törlése sikeresen megtörtént",
+            ))
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pause<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.pause(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn resume<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.resume(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn skip_next<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.skip_next(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn runs<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.get_runs(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn issue_run<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<IssueRunInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.issue_run(&payload).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn skip_run<M: RecurringInvoicesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(recurring_invoices_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), recurring_invoices_module.clone());
+    let result = map_handler_err(
+        service.skip_run(payload.uuid).await,
+        recurring_invoices_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        recurring_invoices_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::address::repository::MockAddressRepository;
+    use crate::tenant::currencies::tests::currencies_repo;
+    use crate::tenant::customers::model::CustomerCreditExposure;
+    use crate::tenant::customers::repository::MockCustomersRepository;
+    use crate::tenant::receivables::model::Receivable;
+    use crate::tenant::recurring_invoices::model::{
+        InvoiceRecipient, RecurringInvoice, RecurringInvoiceRun,
+    };
+    use crate::tenant::recurring_invoices::{
+        self, repository::MockRecurringInvoicesRepository, tests::MockRecurringInvoicesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{Days, Utc};
+    use mockall::predicate::{always, eq};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockRecurringInvoicesRepository,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+    ) -> MockRecurringInvoicesModule {
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .returning(|customer_id| Ok(credit_exposure(customer_id, None)));
+        module_with_customers(repo, customers_repo, active_tenant_id, config_calls)
+    }
+
+    fn module_with_customers(
+        repo: MockRecurringInvoicesRepository,
+        customers_repo: MockCustomersRepository,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+    ) -> MockRecurringInvoicesModule {
+        let repo = Arc::new(repo);
+        let customers_repo = Arc::new(customers_repo);
+        let currencies_repo = Arc::new(currencies_repo("HUF", &["HUF", "EUR"]));
+        let mut address_repo = MockAddressRepository::new();
+        address_repo
+            .expect_get_customer_billing_address()
+            .returning(|_| Ok(None));
+        let address_repo = Arc::new(address_repo);
+        let mut recurring_invoices_module = MockRecurringInvoicesModule::new();
+        recurring_invoices_module
+            .expect_recurring_invoices_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        recurring_invoices_module
+            .expect_currencies_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(currencies_repo.clone()));
+        recurring_invoices_module
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(customers_repo.clone()));
+        recurring_invoices_module
+            .expect_address_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(address_repo.clone()));
+        recurring_invoices_module
+            .expect_config()
+            .times(config_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        recurring_invoices_module
+    }
+
+    fn credit_exposure(customer_id: Uuid, credit_limit: Option<i32>) -> CustomerCreditExposure {
+        CustomerCreditExposure {
+            customer_id,
+            credit_limit: credit_limit.map(BigDecimal::from),
+            currency_code: credit_limit.map(|_| "HUF".to_string()),
+            open_balance: BigDecimal::from(20000),
+            mode: "block".to_string(),
+        }
+    }
+
+    fn app(recurring_invoices_module: MockRecurringInvoicesModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(recurring_invoices::routes::routes(Arc::new(
+                recurring_invoices_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn recurring_invoice(status: &str, send_email: bool) -> RecurringInvoice {
+        let today = Utc::now().date_naive();
+        RecurringInvoice {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            title: "Rendszerüzemeltetés".to_string(),
+            currency_code: "HUF".to_string(),
+            recurrence: "monthly".to_string(),
+            cron_expression: None,
+            start_date: today - Days::new(100),
+            end_date: None,
+            next_issue_date: Some(today - Days::new(40)),
+            payment_term_days: 8,
+            auto_issue: false,
+            send_email,
+            status: status.to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn run(recurring_invoice_id: Uuid, status: &str) -> RecurringInvoiceRun {
+        RecurringInvoiceRun {
+            id: Uuid::new_v4(),
+            recurring_invoice_id,
+            issue_date: Utc::now().date_naive(),
+            status: status.to_string(),
+            receivable_id: None,
+            emailed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn payload(recurrence: &str, cron_expression: Option<&str>) -> serde_json::Value {
+        json!({
+            "id": null,
+            "customer_id": Uuid::new_v4(),
+            "title": "Rendszerüzemeltetés",
+            "currency_code": "huf",
+            "recurrence": recurrence,
+            "cron_expression": cron_expression,
+            "start_date": Utc::now().date_naive() + Days::new(10),
+            "end_date": null,
+            "payment_term_days": null,
+            "auto_issue": true,
+            "send_email": true,
+            "lines": [{
+                "service_id": Uuid::new_v4(),
+                "product_id": null,
+                "description": null,
+                "quantity": "1",
+                "unit_price": "25000",
+                "tax_id": Uuid::new_v4()
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_schedules_first_issue_on_start_date() {
+        let active_tenant_id = Uuid::new_v4();
+        let start_date = Utc::now().date_naive() + Days::new(10);
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(move |input, next_issue_date, _| {
+                input.currency_code == "HUF"
+                    && input.payment_term_days == Some(8)
+                    && *next_issue_date == Some(start_date)
+            })
+            .returning(|_, _, _| {
+                let mut recurring_invoice = recurring_invoice("active", true);
+                recurring_invoice.next_issue_date = Some(Utc::now().date_naive() + Days::new(10));
+                Ok(recurring_invoice)
+            });
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/recurring_invoices/create",
+                active_tenant_id,
+                Some(payload("monthly", None)),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_cron_expression() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/recurring_invoices/create",
+                active_tenant_id,
+                Some(payload("cron", Some("every monday"))),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_disabled_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_insert().never();
+        let mut payload = payload("monthly", None);
+        payload["currency_code"] = json!("CHF");
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/recurring_invoices/create",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_export_to_domestic_customer() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_insert().never();
+        let mut payload = payload("monthly", None);
+        payload["lines"][0]["vat_treatment"] = json!("export");
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "POST",
+                "/api/recurring_invoices/create",
+                active_tenant_id,
+                Some(payload),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_resume_does_not_invoice_missed_periods() {
+        let active_tenant_id = Uuid::new_v4();
+        let recurring_invoice = recurring_invoice("paused", true);
+        let expected = recurring_invoice.next_issue_date_from(Utc::now().date_naive());
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurring_invoice.id))
+            .returning({
+                let recurring_invoice = recurring_invoice.clone();
+                move |_| Ok(recurring_invoice.clone())
+            });
+        repo.expect_set_schedule()
+            .times(1)
+            .with(eq(recurring_invoice.id), eq("active"), eq(expected))
+            .returning({
+                let recurring_invoice = recurring_invoice.clone();
+                move |_, _, _| Ok(recurring_invoice.clone())
+            });
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "PUT",
+                "/api/recurring_invoices/resume",
+                active_tenant_id,
+                Some(json!({"uuid": recurring_invoice.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(expected.unwrap() >= Utc::now().date_naive());
+    }
+
+    #[tokio::test]
+    async fn test_issue_run_rejects_skipped_run() {
+        let active_tenant_id = Uuid::new_v4();
+        let run = run(Uuid::new_v4(), "skipped");
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_get_run().times(1).with(eq(run.id)).returning({
+            let run = run.clone();
+            move |_| Ok(run.clone())
+        });
+        repo.expect_issue_draft().never();
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "PUT",
+                "/api/recurring_invoices/runs/issue",
+                active_tenant_id,
+                Some(json!({"uuid": run.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_issue_run_emails_customer() {
+        let active_tenant_id = Uuid::new_v4();
+        let recurring_invoice = recurring_invoice("active", true);
+        let draft = run(recurring_invoice.id, "draft");
+        let today = Utc::now().date_naive();
+        let receivable = Receivable {
+            id: Uuid::new_v4(),
+            customer_id: recurring_invoice.customer_id,
+            document_number: "SZ-2026-00001".to_string(),
+            issue_date: today,
+            due_date: today + Days::new(8),
+            currency_code: "HUF".to_string(),
+            amount: BigDecimal::from(31750),
+            paid_amount: BigDecimal::from(0),
+            credited_amount: BigDecimal::from(0),
+            status: "open".to_string(),
+            created_by_id: recurring_invoice.created_by_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_get_run()
+            .times(1)
+            .with(eq(draft.id))
+            .returning({
+                let draft = draft.clone();
+                move |_| Ok(draft.clone())
+            });
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurring_invoice.id))
+            .returning({
+                let recurring_invoice = recurring_invoice.clone();
+                move |_| Ok(recurring_invoice.clone())
+            });
+        repo.expect_issue_draft()
+            .times(1)
+            .with(always(), eq(draft.id), eq(today), eq(today + Days::new(8)))
+            .returning({
+                let draft = draft.clone();
+                let receivable = receivable.clone();
+                move |_, _, _, _| {
+                    let mut run = draft.clone();
+                    run.status = "issued".to_string();
+                    run.receivable_id = Some(receivable.id);
+                    Ok((run, receivable.clone()))
+                }
+            });
+        repo.expect_get_recipient()
+            .times(1)
+            .with(eq(recurring_invoice.customer_id))
+            .returning(|_| {
+                Ok(InvoiceRecipient {
+                    name: "Minta Kft.".to_string(),
+                    email: "szamla@example.com".to_string(),
+                })
+            });
+        repo.expect_get_lines()
+            .times(2)
+            .with(eq(recurring_invoice.id), eq(2))
+            .returning(|_, _| Ok(vec![]));
+        repo.expect_mark_emailed()
+            .times(1)
+            .with(eq(draft.id))
+            .returning(|_| Ok(()));
+        let mut recurring_invoices_module = module(repo, active_tenant_id, 3);
+        recurring_invoices_module
+            .expect_send()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let response = app(recurring_invoices_module)
+            .oneshot(request(
+                "PUT",
+                "/api/recurring_invoices/runs/issue",
+                active_tenant_id,
+                Some(json!({"uuid": draft.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_issue_run_blocked_by_credit_limit() {
+        let active_tenant_id = Uuid::new_v4();
+        let recurring_invoice = recurring_invoice("active", true);
+        let draft = run(recurring_invoice.id, "draft");
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_get_run()
+            .times(1)
+            .with(eq(draft.id))
+            .returning({
+                let draft = draft.clone();
+                move |_| Ok(draft.clone())
+            });
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurring_invoice.id))
+            .returning({
+                let recurring_invoice = recurring_invoice.clone();
+                move |_| Ok(recurring_invoice.clone())
+            });
+        repo.expect_get_lines()
+            .times(1)
+            .with(eq(recurring_invoice.id), eq(2))
+            .returning(|_, _| Ok(vec![]));
+        repo.expect_issue_draft().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(recurring_invoice.customer_id))
+            .returning(|customer_id| Ok(credit_exposure(customer_id, Some(10000))));
+
+        let response = app(module_with_customers(
+            repo,
+            customers_repo,
+            active_tenant_id,
+            1,
+        ))
+        .oneshot(request(
+            "PUT",
+            "/api/recurring_invoices/runs/issue",
+            active_tenant_id,
+            Some(json!({"uuid": draft.id, "acknowledge_credit_limit": true})),
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::recurring_invoices::schedule::next_issue_date;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const RECURRENCE_MONTHLY: &str = "monthly";
+pub const RECURRENCE_QUARTERLY: &str = "quarterly";
+pub const RECURRENCE_CRON: &str = "cron";
+
+pub const STATUS_ACTIVE: &str = "active";
+pub const STATUS_PAUSED: &str = "paused";
+pub const STATUS_ENDED: &str = "ended";
+
+pub const RUN_STATUS_DRAFT: &str = "draft";
+pub const RUN_STATUS_ISSUED: &str = "issued";
+pub const RUN_STATUS_SKIPPED: &str = "skipped";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct RecurringInvoice {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub title: String,
+    pub currency_code: String,
+    pub recurrence: String,
+    pub cron_expression: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub next_issue_date: Option<NaiveDate>,
+    pub payment_term_days: i32,
+    pub auto_issue: bool,
+    pub send_email: bool,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl RecurringInvoice {
+    /// First scheduled issue date on or after `from`, `None` once the schedule is exhausted.
+    pub fn next_issue_date_from(&self, from: NaiveDate) -> Option<NaiveDate> {
+        next_issue_date(
+            &self.recurrence,
+            self.cron_expression.as_deref(),
+            self.start_date,
+            self.end_date,
+            from,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct RecurringInvoiceLine {
+    pub id: Uuid,
+    pub recurring_invoice_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub item: String,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub tax_id: Uuid,
+    pub tax_rate: BigDecimal,
+    pub vat_treatment: String,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecurringInvoiceDetails {
+    #[serde(flatten)]
+    pub recurring_invoice: RecurringInvoice,
+    pub lines: Vec<RecurringInvoiceLine>,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub gross_total: BigDecimal,
+}
+
+impl RecurringInvoiceDetails {
+    pub fn new(recurring_invoice: RecurringInvoice, lines: Vec<RecurringInvoiceLine>) -> Self {
+        let (net_total, tax_total, gross_total) = lines.iter().fold(
+            (BigDecimal::zero(), BigDecimal::zero(), BigDecimal::zero()),
+            |(net, tax, gross), line| {
+                (
+                    net + &line.net_amount,
+                    tax + &line.tax_amount,
+                    gross + &line.gross_amount,
+                )
+            },
+        );
+        Self {
+            recurring_invoice,
+            lines,
+            net_total,
+            tax_total,
+            gross_total,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct RecurringInvoiceRun {
+    pub id: Uuid,
+    pub recurring_invoice_id: Uuid,
+    pub issue_date: NaiveDate,
+    pub status: String,
+    pub receivable_id: Option<Uuid>,
+    pub emailed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InvoiceRecipient {
+    pub name: String,
+    pub email: String,
+}
@@ -0,0 +1,644 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::{CurrencyRules, Empty};
+use crate::tenant::receivables::model::Receivable;
+use crate::tenant::recurring_invoices::dto::RecurringInvoiceInput;
+use crate::tenant::recurring_invoices::model::{
+    InvoiceRecipient, RecurringInvoice, RecurringInvoiceLine, RecurringInvoiceRun,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RecurringInvoicesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<RecurringInvoice>;
+    /// Lines with their amounts rounded to `scale` decimal places, the scale of the invoice's
+    /// currency
+    async fn get_lines(
+        &self,
+        recurring_invoice_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<RecurringInvoiceLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<RecurringInvoice>)>;
+    async fn insert(
+        &self,
+        input: &RecurringInvoiceInput,
+        next_issue_date: Option<NaiveDate>,
+        sub: Uuid,
+    ) -> RepositoryResult<RecurringInvoice>;
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &RecurringInvoiceInput,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoice>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn set_schedule(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoice>;
+    async fn get_due(&self, today: NaiveDate) -> RepositoryResult<Vec<RecurringInvoice>>;
+    async fn get_run(&self, id: Uuid) -> RepositoryResult<RecurringInvoiceRun>;
+    async fn get_runs(
+        &self,
+        recurring_invoice_id: Uuid,
+    ) -> RepositoryResult<Vec<RecurringInvoiceRun>>;
+    async fn create_run(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        status: &str,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoiceRun>;
+    async fn issue_scheduled(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        issue_date: NaiveDate,
+        due_date: NaiveDate,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<(RecurringInvoiceRun, Receivable)>;
+    async fn issue_draft(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        run_id: Uuid,
+        issue_date: NaiveDate,
+        due_date: NaiveDate,
+    ) -> RepositoryResult<(RecurringInvoiceRun, Receivable)>;
+    async fn skip_draft(&self, run_id: Uuid) -> RepositoryResult<RecurringInvoiceRun>;
+    async fn mark_emailed(&self, run_id: Uuid) -> RepositoryResult<()>;
+    async fn get_recipient(&self, customer_id: Uuid) -> RepositoryResult<InvoiceRecipient>;
+}
+
+async fn insert_lines(
+    tx: &mut Transaction<'_, Postgres>,
+    recurring_invoice_id: Uuid,
+    input: &RecurringInvoiceInput,
+) -> RepositoryResult<()> {
+    for (position, line) in input.lines.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO recurring_invoice_lines (recurring_invoice_id, service_id, product_id,
+                                                 description, quantity, unit_price, tax_id,
+                                                 position, vat_treatment)
+            SELECT $1, $2, $3, COALESCE($4, services.name, products.name), $5, $6, $7, $8, $9
+            FROM (VALUES (1)) AS line
+            LEFT JOIN services ON services.id = $2
+            LEFT JOIN products ON products.id = $3
+            "#,
+        )
+        .bind(recurring_invoice_id)
+        .bind(line.service_id)
+        .bind(line.product_id)
+        .bind(&line.description)
+        .bind(&line.quantity)
+        .bind(&line.unit_price)
+        .bind(line.tax_id)
+        .bind(i32::try_from(position)?)
+        .bind(line.vat_treatment.as_str())
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Moves the schedule forward, fails with `RowNotFound` when another run already did it.
+async fn advance_schedule(
+    tx: &mut Transaction<'_, Postgres>,
+    recurring_invoice: &RecurringInvoice,
+    next_issue_date: Option<NaiveDate>,
+) -> RepositoryResult<()> {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE recurring_invoices
+        SET next_issue_date = $1,
+            status = CASE WHEN $1 IS NULL THEN 'ended' ELSE status END
+        WHERE id = $2
+            AND next_issue_date = $3
+            AND deleted_at IS NULL
+        RETURNING id
+        "#,
+    )
+    .bind(next_issue_date)
+    .bind(recurring_invoice.id)
+    .bind(recurring_invoice.next_issue_date)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+async fn insert_invoice(
+    tx: &mut Transaction<'_, Postgres>,
+    recurring_invoice: &RecurringInvoice,
+    issue_date: NaiveDate,
+    due_date: NaiveDate,
+) -> RepositoryResult<Receivable> {
+    let scale = CurrencyRules::of(&recurring_invoice.currency_code).scale;
+    let receivable = sqlx::query_as::<_, Receivable>(
+        r#"
+        INSERT INTO receivables (customer_id, document_number, issue_date, due_date,
+                                 currency_code, amount, created_by_id)
+        SELECT $1,
+               'SZ-' || to_char($2::date, 'YYYY') || '-'
+                   || lpad(nextval('invoice_number_seq')::text, 5, '0'),
+               $2, $3, $4, sum(amounts.net_amount + amounts.tax_amount), $5
+        FROM recurring_invoice_lines
+        JOIN taxes ON taxes.id = effective_tax_id(recurring_invoice_lines.tax_id, $2)
+        CROSS JOIN LATERAL (
+            SELECT round(recurring_invoice_lines.quantity
+                             * recurring_invoice_lines.unit_price, $7::integer) AS net_amount,
+                   CASE
+                       WHEN taxes.is_rate_applicable
+                           AND recurring_invoice_lines.vat_treatment = 'domestic'
+                           THEN round(recurring_invoice_lines.quantity
+                                          * recurring_invoice_lines.unit_price
+                                          * COALESCE(taxes.rate, 0) / 100, $7::integer)
+                       ELSE 0
+                   END AS tax_amount
+        ) AS amounts
+        WHERE recurring_invoice_lines.recurring_invoice_id = $6
+        RETURNING *
+        "#,
+    )
+    .bind(recurring_invoice.customer_id)
+    .bind(issue_date)
+    .bind(due_date)
+    .bind(&recurring_invoice.currency_code)
+    .bind(recurring_invoice.created_by_id)
+    .bind(recurring_invoice.id)
+    .bind(scale)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO receivable_lines (receivable_id, service_id, product_id, description,
+                                      quantity, unit_price, tax_id, tax_rate, net_amount,
+                                      tax_amount, position, vat_treatment)
+        SELECT $1,
+               recurring_invoice_lines.service_id,
+               recurring_invoice_lines.product_id,
+               recurring_invoice_lines.description,
+               recurring_invoice_lines.quantity,
+               recurring_invoice_lines.unit_price,
+               taxes.id,
+               CASE
+                   WHEN recurring_invoice_lines.vat_treatment = 'domestic'
+                       THEN COALESCE(taxes.rate, 0)
+                   ELSE 0
+               END,
+               round(recurring_invoice_lines.quantity * recurring_invoice_lines.unit_price,
+                     $4::integer),
+               CASE
+                   WHEN taxes.is_rate_applicable
+                       AND recurring_invoice_lines.vat_treatment = 'domestic'
+                       THEN round(recurring_invoice_lines.quantity
+                                      * recurring_invoice_lines.unit_price
+                                      * COALESCE(taxes.rate, 0) / 100, $4::integer)
+                   ELSE 0
+               END,
+               recurring_invoice_lines.position,
+               recurring_invoice_lines.vat_treatment
+        FROM recurring_invoice_lines
+        JOIN taxes ON taxes.id = effective_tax_id(recurring_invoice_lines.tax_id, $3)
+        WHERE recurring_invoice_lines.recurring_invoice_id = $2
+        "#,
+    )
+    .bind(receivable.id)
+    .bind(recurring_invoice.id)
+    .bind(issue_date)
+    .bind(scale)
+    .execute(&mut **tx)
+    .await?;
+    Ok(receivable)
+}
+
+#[async_trait]
+impl RecurringInvoicesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<RecurringInvoice> {
+        Ok(sqlx::query_as::<_, RecurringInvoice>(
+            "SELECT * FROM recurring_invoices WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_lines(
+        &self,
+        recurring_invoice_id: Uuid,
+        scale: i64,
+    ) -> RepositoryResult<Vec<RecurringInvoiceLine>> {
+        Ok(sqlx::query_as::<_, RecurringInvoiceLine>(
+            r#"
+            SELECT recurring_invoice_lines.id,
+                   recurring_invoice_lines.recurring_invoice_id,
+                   recurring_invoice_lines.service_id,
+                   recurring_invoice_lines.product_id,
+                   COALESCE(services.name, products.name) AS item,
+                   recurring_invoice_lines.description,
+                   recurring_invoice_lines.quantity,
+                   recurring_invoice_lines.unit_price,
+                   recurring_invoice_lines.tax_id,
+                   amounts.tax_rate,
+                   recurring_invoice_lines.vat_treatment,
+                   amounts.net_amount,
+                   amounts.tax_amount,
+                   amounts.net_amount + amounts.tax_amount AS gross_amount,
+                   recurring_invoice_lines.position
+            FROM recurring_invoice_lines
+            JOIN recurring_invoices
+                ON recurring_invoices.id = recurring_invoice_lines.recurring_invoice_id
+            JOIN taxes ON taxes.id = effective_tax_id(
+                    recurring_invoice_lines.tax_id,
+                    COALESCE(recurring_invoices.next_issue_date, CURRENT_DATE))
+            LEFT JOIN services ON recurring_invoice_lines.service_id = services.id
+            LEFT JOIN products ON recurring_invoice_lines.product_id = products.id
+            CROSS JOIN LATERAL (
+                SELECT CASE
+                           WHEN recurring_invoice_lines.vat_treatment = 'domestic'
+                               THEN COALESCE(taxes.rate, 0)
+                           ELSE 0
+                       END AS tax_rate,
+                       round(recurring_invoice_lines.quantity
+                                 * recurring_invoice_lines.unit_price, $2::integer) AS net_amount,
+                       CASE
+                           WHEN taxes.is_rate_applicable
+                               AND recurring_invoice_lines.vat_treatment = 'domestic'
+                               THEN round(recurring_invoice_lines.quantity
+                                              * recurring_invoice_lines.unit_price
+                                              * COALESCE(taxes.rate, 0) / 100, $2::integer)
+                           ELSE 0
+                       END AS tax_amount
+            ) AS amounts
+            WHERE recurring_invoice_lines.recurring_invoice_id = $1
+            ORDER BY recurring_invoice_lines.position
+            "#,
+        )
+        .bind(recurring_invoice_id)
+        .bind(scale)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<RecurringInvoice>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM recurring_invoices WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let recurring_invoices = sqlx::query_as::<_, RecurringInvoice>(
+            r#"
+            SELECT *
+            FROM recurring_invoices
+            WHERE deleted_at IS NULL
+            ORDER BY next_issue_date NULLS LAST, created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            recurring_invoices,
+        ))
+    }
+
+    async fn insert(
+        &self,
+        input: &RecurringInvoiceInput,
+        next_issue_date: Option<NaiveDate>,
+        sub: Uuid,
+    ) -> RepositoryResult<RecurringInvoice> {
+        let mut tx = self.begin().await?;
+        let recurring_invoice = sqlx::query_as::<_, RecurringInvoice>(
+            r#"
+            INSERT INTO recurring_invoices (customer_id, title, currency_code, recurrence,
+                                            cron_expression, start_date, end_date,
+                                            next_issue_date, payment_term_days, auto_issue,
+                                            send_email, status, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+                    CASE WHEN $8::date IS NULL THEN 'ended' ELSE 'active' END, $12)
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&input.title)
+        .bind(&input.currency_code)
+        .bind(&input.recurrence)
+        .bind(&input.cron_expression)
+        .bind(input.start_date)
+        .bind(input.end_date)
+        .bind(next_issue_date)
+        .bind(input.payment_term_days)
+        .bind(input.auto_issue)
+        .bind(input.send_email)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_lines(&mut tx, recurring_invoice.id, input).await?;
+        tx.commit().await?;
+        Ok(recurring_invoice)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &RecurringInvoiceInput,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoice> {
+        let mut tx = self.begin().await?;
+        let recurring_invoice = sqlx::query_as::<_, RecurringInvoice>(
+            r#"
+            UPDATE recurring_invoices
+            SET customer_id = $1,
+                title = $2,
+                currency_code = $3,
+                recurrence = $4,
+                cron_expression = $5,
+                start_date = $6,
+                end_date = $7,
+                next_issue_date = $8,
+                payment_term_days = $9,
+                auto_issue = $10,
+                send_email = $11,
+                status = CASE
+                    WHEN $8::date IS NULL THEN 'ended'
+                    WHEN status = 'ended' THEN 'active'
+                    ELSE status
+                END
+            WHERE id = $12
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.customer_id)
+        .bind(&input.title)
+        .bind(&input.currency_code)
+        .bind(&input.recurrence)
+        .bind(&input.cron_expression)
+        .bind(input.start_date)
+        .bind(input.end_date)
+        .bind(next_issue_date)
+        .bind(input.payment_term_days)
+        .bind(input.auto_issue)
+        .bind(input.send_email)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM recurring_invoice_lines WHERE recurring_invoice_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        insert_lines(&mut tx, id, input).await?;
+        tx.commit().await?;
+        Ok(recurring_invoice)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            "UPDATE recurring_invoices SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn set_schedule(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoice> {
+        Ok(sqlx::query_as::<_, RecurringInvoice>(
+            r#"
+            UPDATE recurring_invoices
+            SET status = $1,
+                next_issue_date = $2
+            WHERE id = $3
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(next_issue_date)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_due(&self, today: NaiveDate) -> RepositoryResult<Vec<RecurringInvoice>> {
+        Ok(sqlx::query_as::<_, RecurringInvoice>(
+            r#"
+            SELECT *
+            FROM recurring_invoices
+            WHERE status = 'active'
+                AND next_issue_date <= $1
+                AND deleted_at IS NULL
+            ORDER BY next_issue_date
+            "#,
+        )
+        .bind(today)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_run(&self, id: Uuid) -> RepositoryResult<RecurringInvoiceRun> {
+        Ok(sqlx::query_as::<_, RecurringInvoiceRun>(
+            "SELECT * FROM recurring_invoice_runs WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_runs(
+        &self,
+        recurring_invoice_id: Uuid,
+    ) -> RepositoryResult<Vec<RecurringInvoiceRun>> {
+        Ok(sqlx::query_as::<_, RecurringInvoiceRun>(
+            r#"
+            SELECT *
+            FROM recurring_invoice_runs
+            WHERE recurring_invoice_id = $1
+            ORDER BY issue_date DESC
+            "#,
+        )
+        .bind(recurring_invoice_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn create_run(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        status: &str,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<RecurringInvoiceRun> {
+        let mut tx = self.begin().await?;
+        advance_schedule(&mut tx, recurring_invoice, next_issue_date).await?;
+        let run = sqlx::query_as::<_, RecurringInvoiceRun>(
+            r#"
+            INSERT INTO recurring_invoice_runs (recurring_invoice_id, issue_date, status)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(recurring_invoice.id)
+        .bind(recurring_invoice.next_issue_date)
+        .bind(status)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(run)
+    }
+
+    async fn issue_scheduled(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        issue_date: NaiveDate,
+        due_date: NaiveDate,
+        next_issue_date: Option<NaiveDate>,
+    ) -> RepositoryResult<(RecurringInvoiceRun, Receivable)> {
+        let mut tx = self.begin().await?;
+        advance_schedule(&mut tx, recurring_invoice, next_issue_date).await?;
+        let receivable = insert_invoice(&mut tx, recurring_invoice, issue_date, due_date).await?;
+        let run = sqlx::query_as::<_, RecurringInvoiceRun>(
+            r#"
+            INSERT INTO recurring_invoice_runs (recurring_invoice_id, issue_date, status,
+                                                receivable_id)
+            VALUES ($1, $2, 'issued', $3)
+            RETURNING *
+            "#,
+        )
+        .bind(recurring_invoice.id)
+        .bind(recurring_invoice.next_issue_date)
+        .bind(receivable.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok((run, receivable))
+    }
+
+    async fn issue_draft(
+        &self,
+        recurring_invoice: &RecurringInvoice,
+        run_id: Uuid,
+        issue_date: NaiveDate,
+        due_date: NaiveDate,
+    ) -> RepositoryResult<(RecurringInvoiceRun, Receivable)> {
+        let mut tx = self.begin().await?;
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM recurring_invoice_runs
+            WHERE id = $1
+                AND recurring_invoice_id = $2
+                AND status = 'draft'
+            FOR UPDATE
+            "#,
+        )
+        .bind(run_id)
+        .bind(recurring_invoice.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let receivable = insert_invoice(&mut tx, recurring_invoice, issue_date, due_date).await?;
+        let run = sqlx::query_as::<_, RecurringInvoiceRun>(
+            r#"
+            UPDATE recurring_invoice_runs
+            SET status = 'issued',
+                receivable_id = $1
+            WHERE id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(receivable.id)
+        .bind(run_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok((run, receivable))
+    }
+
+    async fn skip_draft(&self, run_id: Uuid) -> RepositoryResult<RecurringInvoiceRun> {
+        Ok(sqlx::query_as::<_, RecurringInvoiceRun>(
+            r#"
+            UPDATE recurring_invoice_runs
+            SET status = 'skipped'
+            WHERE id = $1
+                AND status = 'draft'
+            RETURNING *
+            "#,
+        )
+        .bind(run_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn mark_emailed(&self, run_id: Uuid) -> RepositoryResult<()> {
+        sqlx::query("UPDATE recurring_invoice_runs SET emailed_at = NOW() WHERE id = $1")
+            .bind(run_id)
+            .execute(self)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_recipient(&self, customer_id: Uuid) -> RepositoryResult<InvoiceRecipient> {
+        Ok(sqlx::query_as::<_, InvoiceRecipient>(
+            r#"
+            SELECT customers.name, COALESCE(customer_contacts.email, customers.email) AS email
+            FROM customers
+            LEFT JOIN customer_contacts ON customer_contacts.customer_id = customers.id
+                AND customer_contacts.is_primary
+                AND customer_contacts.deleted_at IS NULL
+            WHERE customers.id = $1
+                AND customers.deleted_at IS NULL
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::recurring_invoices::model::{
+    RECURRENCE_CRON, RECURRENCE_MONTHLY, RECURRENCE_QUARTERLY,
+};
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveTime, Utc};
+use cron::Schedule;
+use std::str::FromStr;
+
+/// Parses a cron expression, the usual five field form is accepted besides the six or seven
+/// field one (with seconds) of the cron crate.
+pub fn parse_cron(expression: &str) -> Result<Schedule, cron::error::Error> {
+    let expression = expression.trim();
+    if expression.split_whitespace().count() == 5 {
+        Schedule::from_str(&format!("0 {expression}"))
+    } else {
+        Schedule::from_str(expression)
+    }
+}
+
+fn next_monthly(start_date: NaiveDate, step: u32, from: NaiveDate) -> Option<NaiveDate> {
+    // NOTE: every occurrence is counted from the start date, so a schedule starting on the 31st
+    // returns to the 31st after a shorter month
+    let elapsed =
+        (from.year() - start_date.year()) * 12 + from.month() as i32 - start_date.month() as i32;
+    let mut period = u32::try_from(elapsed.max(0)).ok()? / step;
+    loop {
+        let date = start_date.checked_add_months(Months::new(period * step))?;
+        if date >= from {
+            return Some(date);
+        }
+        period += 1;
+    }
+}
+
+fn next_cron(expression: &str, from: NaiveDate) -> Option<NaiveDate> {
+    let after = from
+        .checked_sub_days(Days::new(1))?
+        .and_time(NaiveTime::from_hms_opt(23, 59, 59)?)
+        .and_utc();
+    parse_cron(expression)
+        .ok()?
+        .after(&after)
+        .next()
+        .map(|datetime| datetime.with_timezone(&Utc).date_naive())
+}
+
+/// First issue date of the schedule on or after `from`, `None` when the schedule has no more
+/// occurrences before its end date.
+pub fn next_issue_date(
+    recurrence: &str,
+    cron_expression: Option<&str>,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    from: NaiveDate,
+) -> Option<NaiveDate> {
+    let from = from.max(start_date);
+    let date = match recurrence {
+        RECURRENCE_MONTHLY => next_monthly(start_date, 1, from),
+        RECURRENCE_QUARTERLY => next_monthly(start_date, 3, from),
+        RECURRENCE_CRON => next_cron(cron_expression?, from),
+        _ => None,
+    }?;
+    match end_date {
+        Some(end_date) if date > end_date => None,
+        _ => Some(date),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_monthly_keeps_day_of_start_date() {
+        let start_date = date("2026-01-31");
+        let next = |from| next_issue_date(RECURRENCE_MONTHLY, None, start_date, None, from);
+
+        assert_eq!(next(date("2026-01-01")), Some(date("2026-01-31")));
+        assert_eq!(next(date("2026-02-01")), Some(date("2026-02-28")));
+        assert_eq!(next(date("2026-03-01")), Some(date("2026-03-31")));
+        assert_eq!(next(date("2026-03-31")), Some(date("2026-03-31")));
+    }
+
+    #[test]
+    fn test_quarterly_respects_end_date() {
+        let start_date = date("2026-01-15");
+        let end_date = Some(date("2026-08-01"));
+        let next = |from| next_issue_date(RECURRENCE_QUARTERLY, None, start_date, end_date, from);
+
+        assert_eq!(next(date("2026-01-16")), Some(date("2026-04-15")));
+        assert_eq!(next(date("2026-05-01")), Some(date("2026-07-15")));
+        assert_eq!(next(date("2026-07-16")), None);
+    }
+
+    #[test]
+    fn test_cron_accepts_five_field_expression() {
+        let next = |from| {
+            next_issue_date(
+                RECURRENCE_CRON,
+                Some("0 6 * * Mon"),
+                date("2026-01-01"),
+                None,
+                from,
+            )
+        };
+
+        assert_eq!(next(date("2026-01-01")), Some(date("2026-01-05")));
+        assert_eq!(next(date("2026-01-05")), Some(date("2026-01-05")));
+        assert_eq!(next(date("2026-01-06")), Some(date("2026-01-12")));
+        assert!(parse_cron("not a cron").is_err());
+    }
+}
@@ -0,0 +1,286 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::customers::repository::CustomersRepository;
+use crate::tenant::recurring_invoices::RecurringInvoicesModuleInterface;
+use crate::tenant::recurring_invoices::model::{RUN_STATUS_DRAFT, RecurringInvoice};
+use crate::tenant::recurring_invoices::repository::RecurringInvoicesRepository;
+use crate::tenant::recurring_invoices::service::{
+    RecurringInvoicesServiceResult, credit_limit_breach, due_date, send_invoice_email,
+};
+use chrono::{NaiveDate, Utc};
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+async fn generate_runs<M: RecurringInvoicesModuleInterface>(
+    module: &M,
+    repo: &(dyn RecurringInvoicesRepository + Send + Sync),
+    customers_repo: &(dyn CustomersRepository + Send + Sync),
+    mut recurring_invoice: RecurringInvoice,
+    today: NaiveDate,
+) -> RecurringInvoicesServiceResult<()> {
+    // NOTE: periods missed while the scheduler was not running are caught up one by one, all
+    // of them issued with the current date
+    while let Some(scheduled_date) = recurring_invoice
+        .next_issue_date
+        .filter(|scheduled_date| *scheduled_date <= today)
+    {
+        let next_issue_date = scheduled_date
+            .succ_opt()
+            .and_then(|from| recurring_invoice.next_issue_date_from(from));
+        // NOTE: nobody can acknowledge a credit limit overrun here, the run is left as a draft
+        // to be issued by hand
+        let issue = recurring_invoice.auto_issue
+            && match credit_limit_breach(repo, customers_repo, &recurring_invoice, false).await? {
+                Some(breach) => {
+                    warn!(
+                        "Recurring invoice {} is left as a draft: {}",
+                        recurring_invoice.id,
+                        breach.message()
+                    );
+                    false
+                }
+                None => true,
+            };
+        if issue {
+            let (run, receivable) = repo
+                .issue_scheduled(
+                    &recurring_invoice,
+                    today,
+                    due_date(&recurring_invoice, today)?,
+                    next_issue_date,
+                )
+                .await?;
+            if recurring_invoice.send_email {
+                send_invoice_email(module, repo, &run, &receivable).await;
+            }
+        } else {
+            repo.create_run(&recurring_invoice, RUN_STATUS_DRAFT, next_issue_date)
+                .await?;
+        }
+        recurring_invoice.next_issue_date = next_issue_date;
+    }
+    Ok(())
+}
+
+async fn process_tenant<M: RecurringInvoicesModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    today: NaiveDate,
+) -> anyhow::Result<()> {
+    let repo = module.recurring_invoices_repo(tenant_id)?;
+    let customers_repo = module.customers_repo(tenant_id)?;
+    for recurring_invoice in repo.get_due(today).await? {
+        let id = recurring_invoice.id;
+        if let Err(e) =
+            generate_runs(module, &*repo, &*customers_repo, recurring_invoice, today).await
+        {
+            warn!("Recurring invoice {} could not be generated: {}", id, e);
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_recurring_invoices<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let interval_hours = app_state
+        .config()
+        .receivables()
+        .recurring_invoice_interval_hours();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_hours * 3600));
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for recurring invoices: {}", e);
+                    continue;
+                }
+            };
+            let today = Utc::now().date_naive();
+            for tenant in tenants {
+                if let Err(e) = process_tenant(&*app_state, tenant.id, today).await {
+                    error!("Recurring invoices failed for tenant {}: {}", tenant.id, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::customers::model::CustomerCreditExposure;
+    use crate::tenant::customers::repository::MockCustomersRepository;
+    use crate::tenant::recurring_invoices::model::RecurringInvoiceRun;
+    use crate::tenant::recurring_invoices::repository::MockRecurringInvoicesRepository;
+    use crate::tenant::recurring_invoices::tests::MockRecurringInvoicesModule;
+    use bigdecimal::BigDecimal;
+    use mockall::Sequence;
+    use mockall::predicate::{always, eq};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generate_runs_catches_up_missed_periods() {
+        let recurring_invoice = RecurringInvoice {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            title: "Rendszerüzemeltetés".to_string(),
+            currency_code: "HUF".to_string(),
+            recurrence: "monthly".to_string(),
+            cron_expression: None,
+            start_date: date("2026-01-10"),
+            end_date: Some(date("2026-03-31")),
+            next_issue_date: Some(date("2026-02-10")),
+            payment_term_days: 8,
+            auto_issue: false,
+            send_email: true,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let mut sequence = Sequence::new();
+        let mut repo = MockRecurringInvoicesRepository::new();
+        for next_issue_date in [Some(date("2026-03-10")), None] {
+            repo.expect_create_run()
+                .times(1)
+                .in_sequence(&mut sequence)
+                .with(always(), eq("draft"), eq(next_issue_date))
+                .returning(|recurring_invoice, status, _| {
+                    Ok(RecurringInvoiceRun {
+                        id: Uuid::new_v4(),
+                        recurring_invoice_id: recurring_invoice.id,
+                        issue_date: recurring_invoice.next_issue_date.unwrap(),
+                        status: status.to_string(),
+                        receivable_id: None,
+                        emailed_at: None,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    })
+                });
+        }
+        repo.expect_issue_scheduled().never();
+        let mut module = MockRecurringInvoicesModule::new();
+        module.expect_send().never();
+
+        generate_runs(
+            &module,
+            &repo,
+            &MockCustomersRepository::new(),
+            recurring_invoice,
+            date("2026-04-01"),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_generate_runs_leaves_draft_over_credit_limit() {
+        let recurring_invoice = RecurringInvoice {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            title: "Rendszerüzemeltetés".to_string(),
+            currency_code: "HUF".to_string(),
+            recurrence: "monthly".to_string(),
+            cron_expression: None,
+            start_date: date("2026-01-10"),
+            end_date: None,
+            next_issue_date: Some(date("2026-03-10")),
+            payment_term_days: 8,
+            auto_issue: true,
+            send_email: true,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let mut repo = MockRecurringInvoicesRepository::new();
+        repo.expect_get_lines()
+            .times(1)
+            .with(eq(recurring_invoice.id), eq(2))
+            .returning(|_, _| Ok(vec![]));
+        repo.expect_create_run()
+            .times(1)
+            .with(always(), eq("draft"), eq(Some(date("2026-04-10"))))
+            .returning(|recurring_invoice, status, _| {
+                Ok(RecurringInvoiceRun {
+                    id: Uuid::new_v4(),
+                    recurring_invoice_id: recurring_invoice.id,
+                    issue_date: recurring_invoice.next_issue_date.unwrap(),
+                    status: status.to_string(),
+                    receivable_id: None,
+                    emailed_at: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+            });
+        repo.expect_issue_scheduled().never();
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .with(eq(recurring_invoice.customer_id))
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: Some(BigDecimal::from(100000)),
+                    currency_code: Some("HUF".to_string()),
+                    open_balance: BigDecimal::from(120000),
+                    mode: "warn".to_string(),
+                })
+            });
+        let mut module = MockRecurringInvoicesModule::new();
+        module.expect_send().never();
+
+        generate_runs(
+            &module,
+            &repo,
+            &customers_repo,
+            recurring_invoice,
+            date("2026-03-20"),
+        )
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,644 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::email_template::EmailTemplate;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{CurrencyRules, Empty, Money, MoneyError};
+use crate::tenant::address::repository::AddressRepository;
+use crate::tenant::currencies::service::{CurrenciesServiceError, document_currency};
+use crate::tenant::customers::model::CreditLimitBreach;
+use crate::tenant::customers::repository::CustomersRepository;
+use crate::tenant::quotes::dto::DEFAULT_PAYMENT_TERM_DAYS;
+use crate::tenant::receivables::model::Receivable;
+use crate::tenant::recurring_invoices::RecurringInvoicesModuleInterface;
+use crate::tenant::recurring_invoices::dto::{IssueRunInput, RecurringInvoiceInput};
+use crate::tenant::recurring_invoices::model::{
+    RECURRENCE_CRON, RECURRENCE_MONTHLY, RECURRENCE_QUARTERLY, RUN_STATUS_DRAFT,
+    RUN_STATUS_SKIPPED, RecurringInvoice, RecurringInvoiceDetails, RecurringInvoiceRun,
+    STATUS_ACTIVE, STATUS_ENDED, STATUS_PAUSED,
+};
+use crate::tenant::recurring_invoices::repository::RecurringInvoicesRepository;
+use crate::tenant::recurring_invoices::schedule::{next_issue_date, parse_cron};
+use crate::tenant::taxes::jurisdiction::{TaxJurisdiction, customer_jurisdiction};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Days, NaiveDate, Utc};
+use lettre::message::Mailbox;
+use serde_json::json;
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum RecurringInvoicesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Currencies(#[from] CurrenciesServiceError),
+
+    #[error("{0}")]
+    Money(#[from] MoneyError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("{}", .0.message())]
+    CreditLimitExceeded(Box<CreditLimitBreach>),
+}
+
+impl From<ServiceError> for RecurringInvoicesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => RecurringInvoicesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<RecurringInvoicesServiceError> for AppError {
+    fn from(value: RecurringInvoicesServiceError) -> Self {
+        match value {
+            RecurringInvoicesServiceError::Currencies(error) => error.into(),
+            RecurringInvoicesServiceError::Money(error) => error.into(),
+            RecurringInvoicesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            RecurringInvoicesServiceError::CreditLimitExceeded(ref breach) => Self::new(
+                Level::DEBUG,
+                ErrorCode::CreditLimitExceeded.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::CreditLimitExceeded.code(),
+                    "message": value.to_string(),
+                    "credit_limit": breach
+                }),
+            ),
+            RecurringInvoicesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            RecurringInvoicesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type RecurringInvoicesServiceResult<T> = Result<T, RecurringInvoicesServiceError>;
+
+fn validate_recurring_invoice(
+    payload: &RecurringInvoiceInput,
+    currency_code: String,
+) -> RecurringInvoicesServiceResult<RecurringInvoiceInput> {
+    let title = payload.title.trim();
+    if title.is_empty() || title.chars().count() > 255 {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            "A megnevezés megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let cron_expression = match payload.recurrence.as_str() {
+        RECURRENCE_MONTHLY | RECURRENCE_QUARTERLY => None,
+        RECURRENCE_CRON => {
+            let expression = payload
+                .cron_expression
+                .as_deref()
+                .map(str::trim)
+                .unwrap_or_default();
+            if expression.chars().count() > 255 || parse_cron(expression).is_err() {
+                return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                    "Hibás cron kifejezés!",
+                ));
+            }
+            Some(expression.to_string())
+        }
+        _ => {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Hibás ismétlődési szabály!",
+            ));
+        }
+    };
+    if payload
+        .end_date
+        .is_some_and(|end_date| end_date < payload.start_date)
+    {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            "A befejezés dátuma nem lehet korábbi a kezdő dátumnál!",
+        ));
+    }
+    let payment_term_days = payload
+        .payment_term_days
+        .unwrap_or(DEFAULT_PAYMENT_TERM_DAYS as i32);
+    if !(0..=365).contains(&payment_term_days) {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            "A fizetési határidő 0 és 365 nap között lehet!",
+        ));
+    }
+    if payload.lines.is_empty() {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            "A számlának legalább egy tételt tartalmaznia kell!",
+        ));
+    }
+    let mut net_total = Money::zero(&currency_code);
+    let mut lines = Vec::with_capacity(payload.lines.len());
+    for line in &payload.lines {
+        if line.service_id.is_some() == line.product_id.is_some() {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Minden tételnél pontosan egy szolgáltatást vagy terméket kell megadni!",
+            ));
+        }
+        if line.quantity <= BigDecimal::zero() {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "A mennyiségnek pozitív számnak kell lennie!",
+            ));
+        }
+        if line.unit_price < BigDecimal::zero() {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Az egységár nem lehet negatív!",
+            ));
+        }
+        net_total = net_total.checked_add(&Money::line_net(
+            &line.quantity,
+            &line.unit_price,
+            &currency_code,
+        ))?;
+        let mut line = line.clone();
+        line.description = line
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+        lines.push(line);
+    }
+    if !net_total.is_positive() {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            "Nulla végösszegű számla nem ütemezhető!",
+        ));
+    }
+    Ok(RecurringInvoiceInput {
+        title: title.to_string(),
+        currency_code,
+        cron_expression,
+        payment_term_days: Some(payment_term_days),
+        lines,
+        ..payload.clone()
+    })
+}
+
+async fn check_vat_treatments(
+    address_repo: &(dyn AddressRepository + Send + Sync),
+    recurring_invoice: &RecurringInvoiceInput,
+) -> RecurringInvoicesServiceResult<()> {
+    let jurisdiction = customer_jurisdiction(address_repo, recurring_invoice.customer_id).await?;
+    if recurring_invoice
+        .lines
+        .iter()
+        .any(|line| !jurisdiction.allows(line.vat_treatment))
+    {
+        return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+            TaxJurisdiction::MISMATCH,
+        ));
+    }
+    Ok(())
+}
+
+fn map_reference_error(e: RepositoryError) -> RecurringInvoicesServiceError {
+    if e.is_foreign_key_violation() {
+        RecurringInvoicesServiceError::UnprocessableEntry(
+            "A megadott ügyfél, szolgáltatás, termék, adó vagy pénznem nem létezik!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+fn map_issue_error(e: RepositoryError) -> RecurringInvoicesServiceError {
+    if e.is_unique_violation() {
+        RecurringInvoicesServiceError::UnprocessableEntry(
+            "A generált számlaszám már foglalt, kérjük próbálja újra!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+pub(crate) fn due_date(
+    recurring_invoice: &RecurringInvoice,
+    issue_date: NaiveDate,
+) -> RecurringInvoicesServiceResult<NaiveDate> {
+    issue_date
+        .checked_add_days(Days::new(
+            u64::try_from(recurring_invoice.payment_term_days).unwrap_or_default(),
+        ))
+        .ok_or(RecurringInvoicesServiceError::UnprocessableEntry(
+            "Hibás kiállítási dátum!",
+        ))
+}
+
+/// The credit limit overrun issuing the recurring invoice would cause, checked with the gross
+/// total of its lines as they would be issued
+pub(crate) async fn credit_limit_breach(
+    repo: &(dyn RecurringInvoicesRepository + Send + Sync),
+    customers_repo: &(dyn CustomersRepository + Send + Sync),
+    recurring_invoice: &RecurringInvoice,
+    acknowledged: bool,
+) -> RecurringInvoicesServiceResult<Option<CreditLimitBreach>> {
+    let lines = repo
+        .get_lines(
+            recurring_invoice.id,
+            CurrencyRules::of(&recurring_invoice.currency_code).scale,
+        )
+        .await?;
+    let amount = RecurringInvoiceDetails::new(recurring_invoice.clone(), lines).gross_total;
+    Ok(customers_repo
+        .get_credit_exposure(recurring_invoice.customer_id)
+        .await?
+        .check(&recurring_invoice.currency_code, &amount, acknowledged))
+}
+
+/// Sends the issued invoice to the customer, failures are only logged so the invoice stays
+/// issued and can be resent by hand.
+pub(crate) async fn send_invoice_email<M: RecurringInvoicesModuleInterface>(
+    module: &M,
+    repo: &(dyn RecurringInvoicesRepository + Send + Sync),
+    run: &RecurringInvoiceRun,
+    receivable: &Receivable,
+) {
+    let result: anyhow::Result<()> = async {
+        let recipient = repo.get_recipient(receivable.customer_id).await?;
+        let lines = repo
+            .get_lines(
+                run.recurring_invoice_id,
+                CurrencyRules::of(&receivable.currency_code).scale,
+            )
+            .await?;
+        let from = Mailbox::new(
+            Some(module.config().mail().default_from_name().to_owned()),
+            module.config().mail().default_from().parse()?,
+        );
+        let to = Mailbox::new(Some(recipient.name.clone()), recipient.email.parse()?);
+        let message = EmailTemplate::InvoiceIssued
+            .render(&json!({
+                "customer_name": recipient.name,
+                "document_number": receivable.document_number,
+                "issue_date": receivable.issue_date,
+                "due_date": receivable.due_date,
+                "currency_code": receivable.currency_code,
+                "amount": receivable.amount,
+                "lines": lines,
+            }))?
+            .into_message(from, to)?;
+        module.send(message).await?;
+        repo.mark_emailed(run.id).await?;
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        warn!(
+            "Could not email invoice {} to the customer: {}",
+            receivable.document_number, e
+        );
+    }
+}
+
+pub trait RecurringInvoicesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoiceDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<(PaginatorMeta, Vec<RecurringInvoice>)>>
+    + Send;
+    fn create(
+        &self,
+        payload: &RecurringInvoiceInput,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoice>> + Send;
+    fn update(
+        &self,
+        payload: &RecurringInvoiceInput,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoice>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = RecurringInvoicesServiceResult<()>> + Send;
+    fn pause(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoice>> + Send;
+    fn resume(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoice>> + Send;
+    fn skip_next(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoiceRun>> + Send;
+    fn get_runs(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<Vec<RecurringInvoiceRun>>> + Send;
+    fn issue_run(
+        &self,
+        payload: &IssueRunInput,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoiceRun>> + Send;
+    fn skip_run(
+        &self,
+        run_id: Uuid,
+    ) -> impl Future<Output = RecurringInvoicesServiceResult<RecurringInvoiceRun>> + Send;
+}
+
+impl<'a, T> RecurringInvoicesService for Service<'a, T>
+where
+    T: RecurringInvoicesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> RecurringInvoicesServiceResult<RecurringInvoiceDetails> {
+        let repo = self.module().recurring_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+        )?;
+        let recurring_invoice = repo.get_by_id(id).await?;
+        let lines = repo
+            .get_lines(
+                id,
+                CurrencyRules::of(&recurring_invoice.currency_code).scale,
+            )
+            .await?;
+        Ok(RecurringInvoiceDetails::new(recurring_invoice, lines))
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> RecurringInvoicesServiceResult<(PaginatorMeta, Vec<RecurringInvoice>)> {
+        Ok(self
+            .module()
+            .recurring_invoices_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &RecurringInvoiceInput,
+    ) -> RecurringInvoicesServiceResult<RecurringInvoice> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(RecurringInvoicesServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_recurring_invoice(payload, currency_code)?;
+        check_vat_treatments(&*self.module().address_repo(tenant_id)?, &input).await?;
+        let next_issue_date = next_issue_date(
+            &input.recurrence,
+            input.cron_expression.as_deref(),
+            input.start_date,
+            input.end_date,
+            Utc::now().date_naive(),
+        );
+        if next_issue_date.is_none() {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Az ütemezés alapján nem állítható ki több számla!",
+            ));
+        }
+        self.module()
+            .recurring_invoices_repo(tenant_id)?
+            .insert(&input, next_issue_date, self.claims()?.sub())
+            .await
+            .map_err(map_reference_error)
+    }
+
+    async fn update(
+        &self,
+        payload: &RecurringInvoiceInput,
+    ) -> RecurringInvoicesServiceResult<RecurringInvoice> {
+        let id = payload
+            .id
+            .ok_or(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(RecurringInvoicesServiceError::Unauthorized)?;
+        let currency_code = document_currency(
+            &*self.module().currencies_repo(tenant_id)?,
+            &payload.currency_code,
+        )
+        .await?;
+        let input = validate_recurring_invoice(payload, currency_code)?;
+        check_vat_treatments(&*self.module().address_repo(tenant_id)?, &input).await?;
+        let repo = self.module().recurring_invoices_repo(tenant_id)?;
+        let recurring_invoice = repo.get_by_id(id).await?;
+        // NOTE: periods already issued, drafted or skipped are not scheduled again
+        let from = recurring_invoice
+            .next_issue_date
+            .unwrap_or_default()
+            .max(Utc::now().date_naive());
+        let next_issue_date = next_issue_date(
+            &input.recurrence,
+            input.cron_expression.as_deref(),
+            input.start_date,
+            input.end_date,
+            from,
+        );
+        repo.update(id, &input, next_issue_date)
+            .await
+            .map_err(map_reference_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> RecurringInvoicesServiceResult<()> {
+        Ok(self
+            .module()
+            .recurring_invoices_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+            )?
+            .delete_by_id(id)
+            .await?)
+    }
+
+    async fn pause(&self, id: Uuid) -> RecurringInvoicesServiceResult<RecurringInvoice> {
+        let repo = self.module().recurring_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+        )?;
+        let recurring_invoice = repo.get_by_id(id).await?;
+        if recurring_invoice.status != STATUS_ACTIVE {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Csak aktív ismétlődő számla szüneteltethető!",
+            ));
+        }
+        Ok(repo
+            .set_schedule(id, STATUS_PAUSED, recurring_invoice.next_issue_date)
+            .await?)
+    }
+
+    async fn resume(&self, id: Uuid) -> RecurringInvoicesServiceResult<RecurringInvoice> {
+        let repo = self.module().recurring_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+        )?;
+        let recurring_invoice = repo.get_by_id(id).await?;
+        if recurring_invoice.status != STATUS_PAUSED {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Csak szüneteltetett ismétlődő számla folytatható!",
+            ));
+        }
+        // NOTE: periods missed while paused are not invoiced retroactively
+        let from = recurring_invoice
+            .next_issue_date
+            .unwrap_or_default()
+            .max(Utc::now().date_naive());
+        let next_issue_date = recurring_invoice.next_issue_date_from(from);
+        let status = if next_issue_date.is_some() {
+            STATUS_ACTIVE
+        } else {
+            STATUS_ENDED
+        };
+        Ok(repo.set_schedule(id, status, next_issue_date).await?)
+    }
+
+    async fn skip_next(&self, id: Uuid) -> RecurringInvoicesServiceResult<RecurringInvoiceRun> {
+        let repo = self.module().recurring_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+        )?;
+        let recurring_invoice = repo.get_by_id(id).await?;
+        let Some(skipped_date) = recurring_invoice.next_issue_date else {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Az ismétlődő számlának nincs következő esedékessége!",
+            ));
+        };
+        let next_issue_date = skipped_date
+            .succ_opt()
+            .and_then(|from| recurring_invoice.next_issue_date_from(from));
+        Ok(repo
+            .create_run(&recurring_invoice, RUN_STATUS_SKIPPED, next_issue_date)
+            .await?)
+    }
+
+    async fn get_runs(&self, id: Uuid) -> RecurringInvoicesServiceResult<Vec<RecurringInvoiceRun>> {
+        Ok(self
+            .module()
+            .recurring_invoices_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+            )?
+            .get_runs(id)
+            .await?)
+    }
+
+    async fn issue_run(
+        &self,
+        payload: &IssueRunInput,
+    ) -> RecurringInvoicesServiceResult<RecurringInvoiceRun> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(RecurringInvoicesServiceError::Unauthorized)?;
+        let repo = self.module().recurring_invoices_repo(tenant_id)?;
+        let run = repo.get_run(payload.uuid).await?;
+        if run.status != RUN_STATUS_DRAFT {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Csak piszkozat számla állítható ki!",
+            ));
+        }
+        let recurring_invoice = repo.get_by_id(run.recurring_invoice_id).await?;
+        if let Some(breach) = credit_limit_breach(
+            &*repo,
+            &*self.module().customers_repo(tenant_id)?,
+            &recurring_invoice,
+            payload.acknowledge_credit_limit,
+        )
+        .await?
+        {
+            return Err(RecurringInvoicesServiceError::CreditLimitExceeded(
+                Box::new(breach),
+            ));
+        }
+        let issue_date = Utc::now().date_naive();
+        let (run, receivable) = repo
+            .issue_draft(
+                &recurring_invoice,
+                run.id,
+                issue_date,
+                due_date(&recurring_invoice, issue_date)?,
+            )
+            .await
+            .map_err(map_issue_error)?;
+        if recurring_invoice.send_email {
+            send_invoice_email(self.module(), &*repo, &run, &receivable).await;
+        }
+        Ok(run)
+    }
+
+    async fn skip_run(&self, run_id: Uuid) -> RecurringInvoicesServiceResult<RecurringInvoiceRun> {
+        let repo = self.module().recurring_invoices_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(RecurringInvoicesServiceError::Unauthorized)?,
+        )?;
+        if repo.get_run(run_id).await?.status != RUN_STATUS_DRAFT {
+            return Err(RecurringInvoicesServiceError::UnprocessableEntry(
+                "Csak piszkozat számla hagyható ki!",
+            ));
+        }
+        Ok(repo.skip_draft(run_id).await?)
+    }
+}
@@ -18,4 +18,5 @@
  */
 
 pub mod print;
+pub mod rate;
 pub mod user_input;
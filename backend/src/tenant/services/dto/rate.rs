@@ -0,0 +1,127 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::value_object::ValueObjectRequired;
+use crate::tenant::currencies::types::CurrencyCode;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+pub const RATE_UNITS: [&str; 2] = ["hour", "unit"];
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ServiceRateInput {
+    pub service_id: Uuid,
+    pub customer_id: Uuid,
+    pub rate: BigDecimal,
+    pub unit: String,
+    pub currency_code: String,
+    pub valid_from: NaiveDate,
+    pub valid_until: Option<NaiveDate>,
+}
+
+impl ServiceRateInput {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.rate < BigDecimal::zero() {
+            return Err("Az ár nem lehet negatív!");
+        }
+        if !RATE_UNITS.contains(&self.unit.as_str()) {
+            return Err("Hibás elszámolási egység!");
+        }
+        if self
+            .currency_code
+            .parse::<ValueObjectRequired<CurrencyCode>>()
+            .is_err()
+        {
+            return Err("Hibás pénznem!");
+        }
+        if self
+            .valid_until
+            .is_some_and(|valid_until| valid_until < self.valid_from)
+        {
+            return Err("Az érvényesség vége nem lehet korábbi a kezdeténél!");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ResolveRateQuery {
+    pub service_id: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub date: Option<NaiveDate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn input() -> ServiceRateInput {
+        ServiceRateInput {
+            service_id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            rate: BigDecimal::from(12000),
+            unit: "hour".to_string(),
+            currency_code: "HUF".to_string(),
+            valid_from: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            valid_until: NaiveDate::from_ymd_opt(2026, 12, 31),
+        }
+    }
+
+    #[test]
+    fn test_validate_success() {
+        assert_eq!(input().validate(), Ok(()));
+        assert_eq!(
+            ServiceRateInput {
+                valid_until: None,
+                unit: "unit".to_string(),
+                ..input()
+            }
+            .validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_rates() {
+        let cases = vec![
+            ServiceRateInput {
+                rate: BigDecimal::from_str("-1").unwrap(),
+                ..input()
+            },
+            ServiceRateInput {
+                unit: "day".to_string(),
+                ..input()
+            },
+            ServiceRateInput {
+                currency_code: "HUFF".to_string(),
+                ..input()
+            },
+            ServiceRateInput {
+                valid_until: NaiveDate::from_ymd_opt(2025, 12, 31),
+                ..input()
+            },
+        ];
+        for case in cases {
+            assert!(case.validate().is_err());
+        }
+    }
+}
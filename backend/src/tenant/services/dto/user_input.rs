@@ -179,6 +179,7 @@ impl TryFrom<ServiceUserInputHelper> for ServiceUserInput {
 
 #[cfg(test)]
 mod tests {
+    use bigdecimal::BigDecimal;
     use uuid::Uuid;
 
     use super::*;
@@ -200,7 +201,10 @@ mod tests {
         assert_eq!(user_input.id.as_uuid(), None);
         assert_eq!(user_input.name.as_str().unwrap(), "Service Name");
         assert_eq!(user_input.description.as_str().unwrap(), "description");
-        assert_eq!(user_input.default_price.as_f64().unwrap(), 1000_f64);
+        assert_eq!(
+            user_input.default_price.as_decimal(),
+            Some(&BigDecimal::from(1000))
+        );
         assert_eq!(user_input.default_tax_id.as_uuid().unwrap(), default_tax_id);
         assert_eq!(user_input.currency_code.as_str().unwrap(), "HUF");
         assert_eq!(user_input.status.as_str().unwrap(), "active");
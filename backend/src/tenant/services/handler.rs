@@ -18,13 +18,14 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::services::ServicesModule;
 use crate::tenant::services::dto::print::ServicesResolvedPrint;
+use crate::tenant::services::dto::rate::{ResolveRateQuery, ServiceRateInput};
 use crate::tenant::services::dto::user_input::{ServiceUserInput, ServiceUserInputHelper};
 use crate::tenant::services::service::ServiceService;
 use crate::tenant::services::types::service::{ServiceFilterBy, ServiceOrderBy};
@@ -133,6 +134,93 @@ pub async fn delete<M: ServicesModule>(
     .into_response())
 }
 
+pub async fn rates<M: ServicesModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(services_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), services_module.clone());
+    let result = map_handler_err(
+        service.get_rates(payload.uuid).await,
+        services_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        services_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_rate<M: ServicesModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(services_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ServiceRateInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), services_module.clone());
+    let result =
+        map_handler_err(service.create_rate(&payload).await, services_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        services_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete_rate<M: ServicesModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(services_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), services_module.clone());
+    map_handler_err(
+        service.delete_rate(payload.uuid).await,
+        services_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az ügyfélár törlése sikeresen megtörtént",
+            ))
+            .build(),
+        services_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn resolve_rate<M: ServicesModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(services_module): State<Arc<M>>,
+    Query(payload): Query<ResolveRateQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), services_module.clone());
+    let result = map_handler_err(
+        service.resolve_rate(&payload).await,
+        services_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        services_module,
+    )
+    .await?
+    .into_response())
+}
+
 pub async fn list<M: ServicesModule>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(services_module): State<Arc<M>>,
@@ -229,7 +317,7 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::services::model::ServiceResolved;
+    use crate::tenant::services::model::{ResolvedServiceRate, ServiceResolved};
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::services::{
@@ -355,6 +443,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -395,6 +484,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -476,6 +566,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -591,6 +682,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -631,6 +723,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -712,6 +805,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -832,6 +926,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -870,6 +965,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -951,6 +1047,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -1131,6 +1228,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "CONFLICT",
                 "message": "A megadott névvel már létezik szolgáltatás a rendszerben!"
             }
         });
@@ -1188,6 +1286,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             }
@@ -1238,6 +1337,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1287,6 +1387,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1463,6 +1564,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message":  "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             }
         });
@@ -1514,6 +1616,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1565,6 +1668,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1740,6 +1844,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1780,6 +1885,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1950,6 +2056,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1991,6 +2098,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2025,4 +2133,131 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_create_rate_rejects_overlap() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut repo = MockServicesRepository::new();
+        repo.expect_count_overlapping_rates()
+            .times(1)
+            .returning(|_| Ok(1));
+        repo.expect_insert_rate().times(0);
+
+        let mut app_state = MockServicesModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_services_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let payload = json!({
+            "service_id": Uuid::new_v4(),
+            "customer_id": Uuid::new_v4(),
+            "rate": "12000",
+            "unit": "hour",
+            "currency_code": "HUF",
+            "valid_from": "2026-01-01",
+            "valid_until": null
+        });
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/services/create_rate")
+            .body(Body::from(payload.to_string()))
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(services::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let service_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let service_rate_id = Uuid::new_v4();
+        let mut repo = MockServicesRepository::new();
+        repo.expect_resolve_rate()
+            .times(1)
+            .with(
+                eq(service_id),
+                eq(Some(customer_id)),
+                eq(chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()),
+            )
+            .returning(move |_, _, _| {
+                Ok(ResolvedServiceRate {
+                    service_rate_id: Some(service_rate_id),
+                    price: Some(bigdecimal::BigDecimal::from(9000)),
+                    currency_code: Some("HUF".to_string()),
+                })
+            });
+
+        let mut app_state = MockServicesModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_services_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!(
+                "/api/services/resolve_rate?service_id={service_id}&customer_id={customer_id}&date=2026-03-01"
+            ))
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(services::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": {
+                "service_rate_id": service_rate_id,
+                "price": "9000",
+                "currency_code": "HUF"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
 }
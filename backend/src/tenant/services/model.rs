@@ -18,7 +18,7 @@
  */
 
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -54,3 +54,25 @@ pub struct ServiceResolved {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ServiceRate {
+    pub id: Uuid,
+    pub service_id: Uuid,
+    pub customer_id: Uuid,
+    pub customer: String,
+    pub rate: BigDecimal,
+    pub unit: String,
+    pub currency_code: String,
+    pub valid_from: NaiveDate,
+    pub valid_until: Option<NaiveDate>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ResolvedServiceRate {
+    pub service_rate_id: Option<Uuid>,
+    pub price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+}
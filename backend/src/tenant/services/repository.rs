@@ -21,10 +21,13 @@ use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Money;
+use crate::tenant::services::dto::rate::ServiceRateInput;
 use crate::tenant::services::dto::user_input::ServiceUserInput;
-use crate::tenant::services::model::{Service, ServiceResolved};
+use crate::tenant::services::model::{ResolvedServiceRate, Service, ServiceRate, ServiceResolved};
 use crate::tenant::services::types::service::{ServiceFilterBy, ServiceOrderBy};
 use async_trait::async_trait;
+use chrono::NaiveDate;
 #[cfg(test)]
 use mockall::automock;
 use sqlx::{AssertSqlSafe, PgPool};
@@ -43,8 +46,36 @@ pub trait ServicesRepository: Send + Sync + 'static {
     async fn insert(&self, service: &ServiceUserInput, sub: Uuid) -> RepositoryResult<Service>;
     async fn update(&self, service: &ServiceUserInput) -> RepositoryResult<Service>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_rates(&self, service_id: Uuid) -> RepositoryResult<Vec<ServiceRate>>;
+    async fn count_overlapping_rates(&self, rate: &ServiceRateInput) -> RepositoryResult<i64>;
+    async fn insert_rate(
+        &self,
+        rate: &ServiceRateInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ServiceRate>;
+    async fn delete_rate(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn resolve_rate(
+        &self,
+        service_id: Uuid,
+        customer_id: Option<Uuid>,
+        on: NaiveDate,
+    ) -> RepositoryResult<ResolvedServiceRate>;
 }
 
+const SERVICE_RATE_COLUMNS: &str = r#"
+    service_rates.id,
+    service_rates.service_id,
+    service_rates.customer_id,
+    customers.name as customer,
+    service_rates.rate,
+    service_rates.unit,
+    service_rates.currency_code,
+    service_rates.valid_from,
+    service_rates.valid_until,
+    service_rates.created_by_id,
+    service_rates.created_at
+"#;
+
 #[async_trait]
 impl ServicesRepository for PgPool {
     async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Service> {
@@ -236,7 +267,11 @@ impl ServicesRepository for PgPool {
         )
             .bind(input.name.as_str()?)
             .bind(input.description.as_str())
-            .bind(input.default_price.as_f64())
+            .bind(input.default_price.as_decimal().map(|price| {
+            Money::new(price.clone(), input.currency_code.as_str().unwrap_or_default())
+                .round()
+                .amount
+        }))
             .bind(input.default_tax_id.as_uuid())
             .bind(input.currency_code.as_str())
             .bind(input.status.as_str()?)
@@ -265,7 +300,14 @@ impl ServicesRepository for PgPool {
         )
         .bind(input.name.as_str()?)
         .bind(input.description.as_str())
-        .bind(input.default_price.as_f64())
+        .bind(input.default_price.as_decimal().map(|price| {
+            Money::new(
+                price.clone(),
+                input.currency_code.as_str().unwrap_or_default(),
+            )
+            .round()
+            .amount
+        }))
         .bind(input.default_tax_id.as_uuid())
         .bind(input.currency_code.as_str())
         .bind(input.status.as_str()?)
@@ -289,4 +331,101 @@ impl ServicesRepository for PgPool {
 
         Ok(())
     }
+
+    async fn get_rates(&self, service_id: Uuid) -> RepositoryResult<Vec<ServiceRate>> {
+        Ok(sqlx::query_as::<_, ServiceRate>(AssertSqlSafe(format!(
+            r#"
+            SELECT {SERVICE_RATE_COLUMNS}
+            FROM service_rates
+            JOIN customers ON service_rates.customer_id = customers.id
+            WHERE service_rates.service_id = $1
+                AND service_rates.deleted_at IS NULL
+            ORDER BY customers.name, service_rates.valid_from DESC
+            "#
+        )))
+        .bind(service_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn count_overlapping_rates(&self, rate: &ServiceRateInput) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM service_rates
+            WHERE service_id = $1
+                AND customer_id = $2
+                AND deleted_at IS NULL
+                AND (valid_until IS NULL OR valid_until >= $3)
+                AND ($4::date IS NULL OR valid_from <= $4)
+            "#,
+        )
+        .bind(rate.service_id)
+        .bind(rate.customer_id)
+        .bind(rate.valid_from)
+        .bind(rate.valid_until)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_rate(
+        &self,
+        rate: &ServiceRateInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ServiceRate> {
+        Ok(sqlx::query_as::<_, ServiceRate>(AssertSqlSafe(format!(
+            r#"
+            WITH inserted AS (
+                INSERT INTO service_rates (service_id, customer_id, rate, unit, currency_code,
+                                           valid_from, valid_until, created_by_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING *
+            )
+            SELECT {SERVICE_RATE_COLUMNS}
+            FROM inserted service_rates
+            JOIN customers ON service_rates.customer_id = customers.id
+            "#
+        )))
+        .bind(rate.service_id)
+        .bind(rate.customer_id)
+        .bind(&rate.rate)
+        .bind(&rate.unit)
+        .bind(&rate.currency_code)
+        .bind(rate.valid_from)
+        .bind(rate.valid_until)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_rate(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE service_rates
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn resolve_rate(
+        &self,
+        service_id: Uuid,
+        customer_id: Option<Uuid>,
+        on: NaiveDate,
+    ) -> RepositoryResult<ResolvedServiceRate> {
+        Ok(sqlx::query_as::<_, ResolvedServiceRate>(
+            "SELECT * FROM resolve_service_rate($1, $2, $3)",
+        )
+        .bind(service_id)
+        .bind(customer_id)
+        .bind(on)
+        .fetch_one(self)
+        .await?)
+    }
 }
@@ -37,6 +37,10 @@ pub fn routes<M: ServicesModule>(services_module: Arc<M>) -> Router {
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
             .route("/print", get(handler::print::<M>))
+            .route("/rates", get(handler::rates::<M>))
+            .route("/create_rate", post(handler::create_rate::<M>))
+            .route("/delete_rate", delete(handler::delete_rate::<M>))
+            .route("/resolve_rate", get(handler::resolve_rate::<M>))
             .layer(from_fn_with_state(services_module.clone(), require_auth))
             .with_state(services_module),
     )
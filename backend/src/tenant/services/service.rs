@@ -28,8 +28,11 @@ use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
 use crate::tenant::services::ServicesModule;
 use crate::tenant::services::dto::print::ServicesResolvedPrint;
+use crate::tenant::services::dto::rate::{ResolveRateQuery, ServiceRateInput};
 use crate::tenant::services::dto::user_input::ServiceUserInput;
-use crate::tenant::services::model::{Service as ServiceModel, ServiceResolved};
+use crate::tenant::services::model::{
+    ResolvedServiceRate, Service as ServiceModel, ServiceRate, ServiceResolved,
+};
 use crate::tenant::services::types::service::{ServiceFilterBy, ServiceOrderBy};
 use axum::http::StatusCode;
 use chrono::{DateTime, Utc};
@@ -58,6 +61,9 @@ pub enum ServicesServiceError {
     #[error("Hiba történt az adatok feldolgozása során: {0}")]
     UnprocessableEntry(&'static str),
 
+    #[error("Az ügyfélnek már van érvényes ára erre a szolgáltatásra a megadott időszakban!")]
+    RateOverlap,
+
     #[error("A lista nem létezik")]
     InvalidSelectList,
 
@@ -89,7 +95,7 @@ impl From<ServicesServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
-            ServicesServiceError::ServiceExists => Self::new(
+            ServicesServiceError::ServiceExists | ServicesServiceError::RateOverlap => Self::new(
                 Level::DEBUG,
                 StatusCode::CONFLICT,
                 file!(),
@@ -168,6 +174,19 @@ pub trait ServiceService {
         &self,
         get_query: &ResourceQuery<ServiceOrderBy, ServiceFilterBy>,
     ) -> impl Future<Output = ServicesServiceResult<(PaginatorMeta, Vec<ServiceResolved>)>> + Send;
+    fn get_rates(
+        &self,
+        service_id: Uuid,
+    ) -> impl Future<Output = ServicesServiceResult<Vec<ServiceRate>>> + Send;
+    fn create_rate(
+        &self,
+        payload: &ServiceRateInput,
+    ) -> impl Future<Output = ServicesServiceResult<ServiceRate>> + Send;
+    fn delete_rate(&self, id: Uuid) -> impl Future<Output = ServicesServiceResult<()>> + Send;
+    fn resolve_rate(
+        &self,
+        payload: &ResolveRateQuery,
+    ) -> impl Future<Output = ServicesServiceResult<ResolvedServiceRate>> + Send;
     fn print(
         &self,
         payload: &[ServicesResolvedPrint],
@@ -287,6 +306,74 @@ where
         }
     }
 
+    async fn get_rates(&self, service_id: Uuid) -> ServicesServiceResult<Vec<ServiceRate>> {
+        Ok(self
+            .module()
+            .services_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ServicesServiceError::Unauthorized)?,
+            )?
+            .get_rates(service_id)
+            .await?)
+    }
+
+    async fn create_rate(&self, payload: &ServiceRateInput) -> ServicesServiceResult<ServiceRate> {
+        payload
+            .validate()
+            .map_err(ServicesServiceError::UnprocessableEntry)?;
+        let repo = self.module().services_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ServicesServiceError::Unauthorized)?,
+        )?;
+        if repo.count_overlapping_rates(payload).await? > 0 {
+            return Err(ServicesServiceError::RateOverlap);
+        }
+        repo.insert_rate(payload, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_foreign_key_violation() {
+                    ServicesServiceError::UnprocessableEntry(
+                        "A megadott szolgáltatás, ügyfél vagy pénznem nem létezik!",
+                    )
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn delete_rate(&self, id: Uuid) -> ServicesServiceResult<()> {
+        Ok(self
+            .module()
+            .services_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ServicesServiceError::Unauthorized)?,
+            )?
+            .delete_rate(id)
+            .await?)
+    }
+
+    async fn resolve_rate(
+        &self,
+        payload: &ResolveRateQuery,
+    ) -> ServicesServiceResult<ResolvedServiceRate> {
+        Ok(self
+            .module()
+            .services_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ServicesServiceError::Unauthorized)?,
+            )?
+            .resolve_rate(
+                payload.service_id,
+                payload.customer_id,
+                payload.date.unwrap_or_else(|| Utc::now().date_naive()),
+            )
+            .await?)
+    }
+
     async fn print(&self, payload: &[ServicesResolvedPrint]) -> ServicesServiceResult<Vec<u8>> {
         Ok(PdfGenerator::gen_pdf_temporary(
             &PdfTemplates::ServiceView,
@@ -0,0 +1,383 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::decrypt_secret;
+use crate::tenant::shipments::carrier::{CarrierClient, CarrierError};
+use crate::tenant::shipments::model::{
+    CarrierAccount, CarrierBooking, STATUS_DELIVERED, STATUS_IN_TRANSIT, Shipment, TrackingEvent,
+};
+use async_trait::async_trait;
+use bigdecimal::ToPrimitive;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::time::Duration;
+
+// NOTE: MyGLS JSON API, see the "MyGLS API" documentation of GLS Hungary
+pub struct GlsClient {
+    account: CarrierAccount,
+    http: reqwest::Client,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GlsAddress<'a> {
+    name: &'a str,
+    street: &'a str,
+    house_number: &'a str,
+    city: &'a str,
+    zip_code: &'a str,
+    country_iso_code: &'a str,
+    contact_name: Option<&'a str>,
+    contact_phone: Option<&'a str>,
+    contact_email: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GlsParcel<'a> {
+    client_number: i64,
+    client_reference: &'a str,
+    count: i32,
+    #[serde(rename = "CODAmount")]
+    cod_amount: Option<f64>,
+    #[serde(rename = "CODReference")]
+    cod_reference: Option<&'a str>,
+    pickup_date: String,
+    pickup_address: GlsAddress<'a>,
+    delivery_address: GlsAddress<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct PrintLabelsRequest<'a> {
+    username: &'a str,
+    password: Vec<u8>,
+    parcel_list: Vec<GlsParcel<'a>>,
+    type_of_printer: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetParcelStatusesRequest<'a> {
+    username: &'a str,
+    password: Vec<u8>,
+    parcel_number: i64,
+    #[serde(rename = "ReturnPOD")]
+    return_pod: bool,
+    language_iso_code: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GlsError {
+    error_code: i32,
+    error_description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GlsParcelInfo {
+    parcel_id: i64,
+    parcel_number: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PrintLabelsResponse {
+    #[serde(default)]
+    labels: Option<Vec<u8>>,
+    #[serde(default)]
+    print_labels_error_list: Vec<GlsError>,
+    #[serde(default)]
+    print_labels_info_list: Vec<GlsParcelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GlsParcelStatus {
+    status_code: String,
+    status_date: String,
+    status_description: Option<String>,
+    depot_city: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetParcelStatusesResponse {
+    #[serde(default)]
+    parcel_status_list: Vec<GlsParcelStatus>,
+    #[serde(default)]
+    get_parcel_status_errors: Vec<GlsError>,
+}
+
+fn gls_date(date: DateTime<Utc>) -> String {
+    format!("/Date({})/", date.timestamp_millis())
+}
+
+fn parse_gls_date(value: &str) -> Option<DateTime<Utc>> {
+    let inner = value.strip_prefix("/Date(")?.strip_suffix(")/")?;
+    let sign_len = usize::from(inner.starts_with('-'));
+    let digits_len = inner[sign_len..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(inner.len() - sign_len);
+    DateTime::from_timestamp_millis(inner[..sign_len + digits_len].parse().ok()?)
+}
+
+fn map_status(status_code: &str) -> &'static str {
+    match status_code.trim_start_matches('0') {
+        "5" => STATUS_DELIVERED,
+        _ => STATUS_IN_TRANSIT,
+    }
+}
+
+fn errors_to_string(errors: &[GlsError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.error_code, e.error_description))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn booking_from_response(response: PrintLabelsResponse) -> Result<CarrierBooking, CarrierError> {
+    if !response.print_labels_error_list.is_empty() {
+        return Err(CarrierError::Rejected(errors_to_string(
+            &response.print_labels_error_list,
+        )));
+    }
+    let info = response
+        .print_labels_info_list
+        .first()
+        .ok_or_else(|| CarrierError::Rejected("missing parcel info".to_string()))?;
+    let label_pdf = response
+        .labels
+        .filter(|labels| !labels.is_empty())
+        .ok_or_else(|| CarrierError::Rejected("missing label".to_string()))?;
+    Ok(CarrierBooking {
+        carrier_parcel_id: info.parcel_id.to_string(),
+        tracking_number: info.parcel_number.to_string(),
+        label_pdf,
+    })
+}
+
+fn events_from_response(
+    response: GetParcelStatusesResponse,
+) -> Result<Vec<TrackingEvent>, CarrierError> {
+    if !response.get_parcel_status_errors.is_empty() {
+        return Err(CarrierError::Rejected(errors_to_string(
+            &response.get_parcel_status_errors,
+        )));
+    }
+    Ok(response
+        .parcel_status_list
+        .into_iter()
+        .filter_map(|status| {
+            Some(TrackingEvent {
+                status: map_status(&status.status_code),
+                occurred_at: parse_gls_date(&status.status_date)?,
+                code: status.status_code,
+                description: status.status_description,
+                location: status.depot_city,
+            })
+        })
+        .collect())
+}
+
+impl GlsClient {
+    pub fn new(account: CarrierAccount, timeout: Duration) -> Result<Self, CarrierError> {
+        Ok(Self {
+            account: CarrierAccount {
+                password: decrypt_secret(&account.password)?,
+                ..account
+            },
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+        })
+    }
+
+    fn endpoint(&self, method: &str) -> String {
+        format!(
+            "{}/ParcelService.svc/json/{}",
+            self.account.api_url.trim_end_matches('/'),
+            method
+        )
+    }
+
+    fn password_hash(&self) -> Vec<u8> {
+        Sha512::digest(self.account.password.as_bytes()).to_vec()
+    }
+
+    fn client_number(&self) -> Result<i64, CarrierError> {
+        self.account
+            .client_number
+            .trim()
+            .parse()
+            .map_err(|_| CarrierError::Rejected("invalid client number".to_string()))
+    }
+}
+
+#[async_trait]
+impl CarrierClient for GlsClient {
+    async fn book(&self, shipment: &Shipment) -> Result<CarrierBooking, CarrierError> {
+        let account = &self.account;
+        let reference = shipment
+            .reference
+            .clone()
+            .unwrap_or_else(|| shipment.id.to_string());
+        let request = PrintLabelsRequest {
+            username: &account.username,
+            password: self.password_hash(),
+            parcel_list: vec![GlsParcel {
+                client_number: self.client_number()?,
+                client_reference: &reference,
+                count: shipment.parcel_count,
+                cod_amount: shipment.cod_amount.as_ref().and_then(|v| v.to_f64()),
+                cod_reference: shipment.cod_amount.as_ref().map(|_| reference.as_str()),
+                pickup_date: gls_date(Utc::now()),
+                pickup_address: GlsAddress {
+                    name: &account.sender_name,
+                    street: &account.sender_street,
+                    house_number: &account.sender_house_number,
+                    city: &account.sender_city,
+                    zip_code: &account.sender_zip_code,
+                    country_iso_code: &account.sender_country_code,
+                    contact_name: account.sender_contact_name.as_deref(),
+                    contact_phone: account.sender_contact_phone.as_deref(),
+                    contact_email: account.sender_contact_email.as_deref(),
+                },
+                delivery_address: GlsAddress {
+                    name: &shipment.recipient_name,
+                    street: &shipment.recipient_street,
+                    house_number: &shipment.recipient_house_number,
+                    city: &shipment.recipient_city,
+                    zip_code: &shipment.recipient_zip_code,
+                    country_iso_code: &shipment.recipient_country_code,
+                    contact_name: Some(&shipment.recipient_name),
+                    contact_phone: shipment.recipient_phone.as_deref(),
+                    contact_email: shipment.recipient_email.as_deref(),
+                },
+            }],
+            type_of_printer: "A4_2x2",
+        };
+        let response = self
+            .http
+            .post(self.endpoint("PrintLabels"))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<PrintLabelsResponse>()
+            .await?;
+        booking_from_response(response)
+    }
+
+    async fn tracking(&self, shipment: &Shipment) -> Result<Vec<TrackingEvent>, CarrierError> {
+        let parcel_number = shipment
+            .tracking_number
+            .as_deref()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| CarrierError::Rejected("missing parcel number".to_string()))?;
+        let request = GetParcelStatusesRequest {
+            username: &self.account.username,
+            password: self.password_hash(),
+            parcel_number,
+            return_pod: false,
+            language_iso_code: "HU",
+        };
+        let response = self
+            .http
+            .post(self.endpoint("GetParcelStatuses"))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<GetParcelStatusesResponse>()
+            .await?;
+        events_from_response(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_gls_date() {
+        assert_eq!(
+            parse_gls_date("/Date(1760608800000+0200)/"),
+            DateTime::from_timestamp_millis(1760608800000)
+        );
+        assert_eq!(
+            parse_gls_date("/Date(1760608800000)/"),
+            DateTime::from_timestamp_millis(1760608800000)
+        );
+        assert_eq!(parse_gls_date("2025-10-16"), None);
+        let now = DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap();
+        assert_eq!(parse_gls_date(&gls_date(now)), Some(now));
+    }
+
+    #[test]
+    fn test_booking_from_response() {
+        let response: PrintLabelsResponse = serde_json::from_value(json!({
+            "Labels": [37, 80, 68, 70],
+            "PrintLabelsErrorList": [],
+            "PrintLabelsInfoList": [{"ClientReference": "R-1", "ParcelId": 123, "ParcelNumber": 50012345}]
+        }))
+        .unwrap();
+        assert_eq!(
+            booking_from_response(response).unwrap(),
+            CarrierBooking {
+                carrier_parcel_id: "123".to_string(),
+                tracking_number: "50012345".to_string(),
+                label_pdf: b"%PDF".to_vec(),
+            }
+        );
+
+        let rejected: PrintLabelsResponse = serde_json::from_value(json!({
+            "Labels": null,
+            "PrintLabelsErrorList": [{"ErrorCode": 14, "ErrorDescription": "Invalid zip code"}],
+            "PrintLabelsInfoList": []
+        }))
+        .unwrap();
+        assert!(matches!(
+            booking_from_response(rejected),
+            Err(CarrierError::Rejected(message)) if message == "14: Invalid zip code"
+        ));
+    }
+
+    #[test]
+    fn test_events_from_response() {
+        let response: GetParcelStatusesResponse = serde_json::from_value(json!({
+            "ParcelNumber": 50012345,
+            "ParcelStatusList": [
+                {"StatusCode": "1", "StatusDate": "/Date(1760608800000+0200)/", "StatusDescription": "Átadva a GLS-nek", "DepotCity": "Budapest"},
+                {"StatusCode": "05", "StatusDate": "/Date(1760695200000+0200)/", "StatusDescription": "Kézbesítve", "DepotCity": "Szeged"},
+                {"StatusCode": "2", "StatusDate": "invalid"}
+            ],
+            "GetParcelStatusErrors": []
+        }))
+        .unwrap();
+        let events = events_from_response(response).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].status, STATUS_IN_TRANSIT);
+        assert_eq!(events[0].location.as_deref(), Some("Budapest"));
+        assert_eq!(events[1].status, STATUS_DELIVERED);
+        assert_eq!(events[1].code, "05");
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::CryptoError;
+use crate::tenant::shipments::carrier::gls::GlsClient;
+use crate::tenant::shipments::model::{CarrierAccount, CarrierBooking, Shipment, TrackingEvent};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+pub mod gls;
+
+#[derive(Debug, Error)]
+pub enum CarrierError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("A futárszolgálat elutasította a kérést: {0}")]
+    Rejected(String),
+
+    #[error("Nem támogatott futárszolgálat: {0}")]
+    Unsupported(String),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CarrierClient: Send + Sync {
+    async fn book(&self, shipment: &Shipment) -> Result<CarrierBooking, CarrierError>;
+    async fn tracking(&self, shipment: &Shipment) -> Result<Vec<TrackingEvent>, CarrierError>;
+}
+
+pub fn carrier_client(
+    account: &CarrierAccount,
+    timeout: Duration,
+) -> Result<Arc<dyn CarrierClient + Send + Sync>, CarrierError> {
+    match account.carrier.as_str() {
+        "gls" => Ok(Arc::new(GlsClient::new(account.clone(), timeout)?)),
+        other => Err(CarrierError::Unsupported(other.to_string())),
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Clone, Deserialize, PartialEq)]
+pub struct CarrierAccountInput {
+    pub carrier: String,
+    pub name: String,
+    pub api_url: String,
+    pub username: String,
+    pub password: String,
+    pub client_number: String,
+    pub sender_name: String,
+    pub sender_street: String,
+    pub sender_house_number: String,
+    pub sender_city: String,
+    pub sender_zip_code: String,
+    pub sender_country_code: String,
+    pub sender_contact_name: Option<String>,
+    pub sender_contact_phone: Option<String>,
+    pub sender_contact_email: Option<String>,
+}
+
+impl fmt::Debug for CarrierAccountInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CarrierAccountInput")
+            .field("carrier", &self.carrier)
+            .field("name", &self.name)
+            .field("api_url", &self.api_url)
+            .field("username", &self.username)
+            .field("client_number", &self.client_number)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateShipment {
+    pub carrier_account_id: Uuid,
+    pub picking_list_id: Option<Uuid>,
+    pub package_type_id: Option<Uuid>,
+    pub reference: Option<String>,
+    pub recipient_name: String,
+    pub recipient_street: String,
+    pub recipient_house_number: String,
+    pub recipient_city: String,
+    pub recipient_zip_code: String,
+    pub recipient_country_code: String,
+    pub recipient_phone: Option<String>,
+    pub recipient_email: Option<String>,
+    #[serde(default = "default_parcel_count")]
+    pub parcel_count: i32,
+    pub weight_g: Option<i32>,
+    pub cod_amount: Option<BigDecimal>,
+}
+
+fn default_parcel_count() -> i32 {
+    1
+}
@@ -0,0 +1,475 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::shipments::ShipmentsModuleInterface;
+use crate::tenant::shipments::dto::{CarrierAccountInput, CreateShipment};
+use crate::tenant::shipments::service::ShipmentsService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result = map_handler_err(service.get(payload.uuid).await, shipments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        shipments_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        shipments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateShipment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result = map_handler_err(service.create(&payload).await, shipments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn book<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result =
+        map_handler_err(service.book(payload.uuid).await, shipments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn label<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let pdf = map_handler_err(service.label(payload.uuid).await, shipments_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"inline; filename="cimke_{}.pdf""#, payload.uuid)
+            .parse()
+            .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+pub async fn refresh_tracking<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result = map_handler_err(
+        service.refresh_tracking(payload.uuid).await,
+        shipments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn cancel<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result =
+        map_handler_err(service.cancel(payload.uuid).await, shipments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list_carrier_accounts<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result = map_handler_err(
+        service.get_carrier_accounts().await,
+        shipments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_carrier_account<M: ShipmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(shipments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CarrierAccountInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), shipments_module.clone());
+    let result = map_handler_err(
+        service.create_carrier_account(&payload).await,
+        shipments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        shipments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::shipments::carrier::MockCarrierClient;
+    use crate::tenant::shipments::model::{
+        CarrierAccount, CarrierBooking, STATUS_BOOKED, STATUS_DRAFT, Shipment,
+    };
+    use crate::tenant::shipments::{
+        self, repository::MockShipmentsRepository, tests::MockShipmentsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(repo: MockShipmentsRepository, active_tenant_id: Uuid) -> MockShipmentsModule {
+        let repo = Arc::new(repo);
+        let mut shipments_module = MockShipmentsModule::new();
+        shipments_module
+            .expect_shipments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        shipments_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        shipments_module
+    }
+
+    fn app(shipments_module: MockShipmentsModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(shipments::routes::routes(Arc::new(shipments_module))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn carrier_account() -> CarrierAccount {
+        CarrierAccount {
+            id: Uuid::new_v4(),
+            carrier: "gls".to_string(),
+            name: "GLS".to_string(),
+            api_url: "https://api.test.mygls.hu".to_string(),
+            username: "user@example.com".to_string(),
+            password: "secret".to_string(),
+            client_number: "100000001".to_string(),
+            sender_name: "Feladó Kft.".to_string(),
+            sender_street: "Fő utca".to_string(),
+            sender_house_number: "1".to_string(),
+            sender_city: "Budapest".to_string(),
+            sender_zip_code: "1011".to_string(),
+            sender_country_code: "HU".to_string(),
+            sender_contact_name: None,
+            sender_contact_phone: None,
+            sender_contact_email: None,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn shipment(carrier_account_id: Uuid, status: &str) -> Shipment {
+        Shipment {
+            id: Uuid::new_v4(),
+            carrier_account_id,
+            picking_list_id: None,
+            package_type_id: None,
+            reference: Some("R-1".to_string()),
+            recipient_name: "Címzett Bt.".to_string(),
+            recipient_street: "Kossuth utca".to_string(),
+            recipient_house_number: "2".to_string(),
+            recipient_city: "Szeged".to_string(),
+            recipient_zip_code: "6720".to_string(),
+            recipient_country_code: "HU".to_string(),
+            recipient_phone: None,
+            recipient_email: None,
+            parcel_count: 1,
+            weight_g: None,
+            cod_amount: None,
+            status: status.to_string(),
+            carrier_parcel_id: None,
+            tracking_number: None,
+            booked_at: None,
+            delivered_at: None,
+            tracking_checked_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_book_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let account = carrier_account();
+        let draft = shipment(account.id, STATUS_DRAFT);
+        let booked = Shipment {
+            status: STATUS_BOOKED.to_string(),
+            carrier_parcel_id: Some("42".to_string()),
+            tracking_number: Some("00012345".to_string()),
+            booked_at: Some(Utc::now()),
+            ..draft.clone()
+        };
+
+        let mut repo = MockShipmentsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(draft.id))
+            .times(1)
+            .returning({
+                let draft = draft.clone();
+                move |_| Ok(draft.clone())
+            });
+        repo.expect_get_carrier_account()
+            .with(eq(account.id))
+            .times(1)
+            .returning({
+                let account = account.clone();
+                move |_| Ok(account.clone())
+            });
+        repo.expect_mark_booked()
+            .times(1)
+            .withf(|_, booking| booking.tracking_number == "00012345")
+            .returning({
+                let booked = booked.clone();
+                move |_, _| Ok(booked.clone())
+            });
+
+        let mut client = MockCarrierClient::new();
+        client.expect_book().times(1).returning(|_| {
+            Ok(CarrierBooking {
+                carrier_parcel_id: "42".to_string(),
+                tracking_number: "00012345".to_string(),
+                label_pdf: b"%PDF".to_vec(),
+            })
+        });
+        let client = Arc::new(client);
+
+        let mut shipments_module = module(repo, active_tenant_id);
+        shipments_module
+            .expect_carrier_client()
+            .times(1)
+            .returning(move |_| Ok(client.clone()));
+
+        let response = app(shipments_module)
+            .oneshot(json_request(
+                "PUT",
+                "/api/shipments/book",
+                active_tenant_id,
+                json!({"uuid": draft.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": booked})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_book_rejects_booked_shipment() {
+        let active_tenant_id = Uuid::new_v4();
+        let booked = shipment(Uuid::new_v4(), STATUS_BOOKED);
+
+        let mut repo = MockShipmentsRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let booked = booked.clone();
+            move |_| Ok(booked.clone())
+        });
+        repo.expect_mark_booked().never();
+
+        let mut shipments_module = module(repo, active_tenant_id);
+        shipments_module.expect_carrier_client().never();
+
+        let response = app(shipments_module)
+            .oneshot(json_request(
+                "PUT",
+                "/api/shipments/book",
+                active_tenant_id,
+                json!({"uuid": booked.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_completed_picking_list() {
+        let active_tenant_id = Uuid::new_v4();
+        let account = carrier_account();
+        let picking_list_id = Uuid::new_v4();
+
+        let mut repo = MockShipmentsRepository::new();
+        repo.expect_get_carrier_account().times(1).returning({
+            let account = account.clone();
+            move |_| Ok(account.clone())
+        });
+        repo.expect_get_picking_list_status()
+            .with(eq(picking_list_id))
+            .times(1)
+            .returning(|_| Ok("in_progress".to_string()));
+        repo.expect_insert().never();
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(json_request(
+                "POST",
+                "/api/shipments/create",
+                active_tenant_id,
+                json!({
+                    "carrier_account_id": account.id,
+                    "picking_list_id": picking_list_id,
+                    "recipient_name": "Címzett Bt.",
+                    "recipient_street": "Kossuth utca",
+                    "recipient_house_number": "2",
+                    "recipient_city": "Szeged",
+                    "recipient_zip_code": "6720",
+                    "recipient_country_code": "hu"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::error::RepositoryResult;
+use crate::common::{AppState, BaseModule, ConfigProvider};
+use crate::tenant::shipments::carrier::{CarrierClient, CarrierError};
+use crate::tenant::shipments::model::CarrierAccount;
+use crate::tenant::shipments::repository::ShipmentsRepository;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub mod carrier;
+pub mod dto;
+pub(crate) mod handler;
+pub mod model;
+pub(crate) mod repository;
+pub(crate) mod routes;
+pub mod service;
+pub mod tracking;
+
+pub trait ShipmentsModuleInterface: BaseModule {
+    fn shipments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ShipmentsRepository + Send + Sync>>;
+    fn carrier_client(
+        &self,
+        account: &CarrierAccount,
+    ) -> Result<Arc<dyn CarrierClient + Send + Sync>, CarrierError>;
+}
+
+impl<P, T> ShipmentsModuleInterface for AppState<P, T>
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    fn shipments_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ShipmentsRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+
+    fn carrier_client(
+        &self,
+        account: &CarrierAccount,
+    ) -> Result<Arc<dyn CarrierClient + Send + Sync>, CarrierError> {
+        carrier::carrier_client(
+            account,
+            Duration::from_secs(self.config().carriers().request_timeout_secs()),
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::config::AppConfig;
+    use crate::common::error::RepositoryResult;
+    use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use lettre::{
+        Message,
+        transport::smtp::{Error, response::Response},
+    };
+    use mockall::mock;
+
+    mock!(
+        pub ShipmentsModule {}
+        impl ConfigProvider for ShipmentsModule {
+            type Cfg = AppConfig;
+            fn config(&self) -> &<Self as ConfigProvider>::Cfg;
+        }
+        impl MailTransporter for ShipmentsModule {
+            async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
+        }
+        impl BaseModule for ShipmentsModule {}
+        impl ShipmentsModuleInterface for ShipmentsModule {
+            fn shipments_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ShipmentsRepository + Send + Sync>>;
+            fn carrier_client(
+                &self,
+                account: &CarrierAccount,
+            ) -> Result<Arc<dyn CarrierClient + Send + Sync>, CarrierError>;
+        }
+    );
+}
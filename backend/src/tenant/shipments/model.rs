@@ -0,0 +1,223 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_BOOKED: &str = "booked";
+pub const STATUS_IN_TRANSIT: &str = "in_transit";
+pub const STATUS_DELIVERED: &str = "delivered";
+pub const STATUS_CANCELLED: &str = "cancelled";
+
+#[derive(Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct CarrierAccount {
+    pub id: Uuid,
+    pub carrier: String,
+    pub name: String,
+    pub api_url: String,
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+    pub client_number: String,
+    pub sender_name: String,
+    pub sender_street: String,
+    pub sender_house_number: String,
+    pub sender_city: String,
+    pub sender_zip_code: String,
+    pub sender_country_code: String,
+    pub sender_contact_name: Option<String>,
+    pub sender_contact_phone: Option<String>,
+    pub sender_contact_email: Option<String>,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+// NOTE: the password is left out so that the account can be logged safely
+impl fmt::Debug for CarrierAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CarrierAccount")
+            .field("id", &self.id)
+            .field("carrier", &self.carrier)
+            .field("name", &self.name)
+            .field("api_url", &self.api_url)
+            .field("username", &self.username)
+            .field("client_number", &self.client_number)
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Shipment {
+    pub id: Uuid,
+    pub carrier_account_id: Uuid,
+    pub picking_list_id: Option<Uuid>,
+    pub package_type_id: Option<Uuid>,
+    pub reference: Option<String>,
+    pub recipient_name: String,
+    pub recipient_street: String,
+    pub recipient_house_number: String,
+    pub recipient_city: String,
+    pub recipient_zip_code: String,
+    pub recipient_country_code: String,
+    pub recipient_phone: Option<String>,
+    pub recipient_email: Option<String>,
+    pub parcel_count: i32,
+    pub weight_g: Option<i32>,
+    pub cod_amount: Option<BigDecimal>,
+    pub status: String,
+    pub carrier_parcel_id: Option<String>,
+    pub tracking_number: Option<String>,
+    pub booked_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub tracking_checked_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ShipmentEvent {
+    pub id: Uuid,
+    pub shipment_id: Uuid,
+    pub status: String,
+    pub code: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ShipmentWithEvents {
+    #[serde(flatten)]
+    pub shipment: Shipment,
+    pub events: Vec<ShipmentEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarrierBooking {
+    pub carrier_parcel_id: String,
+    pub tracking_number: String,
+    pub label_pdf: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingEvent {
+    pub status: &'static str,
+    pub code: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+// NOTE: the timeline only moves forward, a late in-transit scan can not reopen a delivered shipment
+pub fn status_after_events(current: &str, events: &[TrackingEvent]) -> &'static str {
+    let rank = |status: &str| match status {
+        STATUS_BOOKED => 1,
+        STATUS_IN_TRANSIT => 2,
+        STATUS_DELIVERED => 3,
+        _ => 0,
+    };
+    let latest = events
+        .iter()
+        .map(|event| event.status)
+        .max_by_key(|status| rank(status))
+        .unwrap_or(STATUS_BOOKED);
+    match current {
+        STATUS_DELIVERED => STATUS_DELIVERED,
+        STATUS_IN_TRANSIT if rank(latest) < rank(STATUS_IN_TRANSIT) => STATUS_IN_TRANSIT,
+        _ => latest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(status: &'static str) -> TrackingEvent {
+        TrackingEvent {
+            status,
+            code: "1".to_string(),
+            description: None,
+            location: None,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_status_after_events() {
+        assert_eq!(status_after_events(STATUS_BOOKED, &[]), STATUS_BOOKED);
+        assert_eq!(
+            status_after_events(STATUS_BOOKED, &[event(STATUS_IN_TRANSIT)]),
+            STATUS_IN_TRANSIT
+        );
+        assert_eq!(
+            status_after_events(
+                STATUS_IN_TRANSIT,
+                &[event(STATUS_IN_TRANSIT), event(STATUS_DELIVERED)]
+            ),
+            STATUS_DELIVERED
+        );
+        assert_eq!(
+            status_after_events(STATUS_IN_TRANSIT, &[]),
+            STATUS_IN_TRANSIT
+        );
+        assert_eq!(
+            status_after_events(STATUS_DELIVERED, &[event(STATUS_IN_TRANSIT)]),
+            STATUS_DELIVERED
+        );
+    }
+
+    #[test]
+    fn test_carrier_account_debug_hides_password() {
+        let account = CarrierAccount {
+            id: Uuid::new_v4(),
+            carrier: "gls".to_string(),
+            name: "GLS".to_string(),
+            api_url: "https://api.test.mygls.hu".to_string(),
+            username: "user@example.com".to_string(),
+            password: "gls-password".to_string(),
+            client_number: "100000001".to_string(),
+            sender_name: "Feladó Kft.".to_string(),
+            sender_street: "Fő utca".to_string(),
+            sender_house_number: "1".to_string(),
+            sender_city: "Budapest".to_string(),
+            sender_zip_code: "1011".to_string(),
+            sender_country_code: "HU".to_string(),
+            sender_contact_name: None,
+            sender_contact_phone: None,
+            sender_contact_email: None,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        assert!(!format!("{account:?}").contains("gls-password"));
+    }
+}
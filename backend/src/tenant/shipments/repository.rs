@@ -0,0 +1,332 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::shipments::dto::{CarrierAccountInput, CreateShipment};
+use crate::tenant::shipments::model::{
+    CarrierAccount, CarrierBooking, STATUS_DELIVERED, Shipment, ShipmentEvent, TrackingEvent,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+// NOTE: label_pdf is left out on purpose, it is only loaded by get_label
+const SHIPMENT_COLUMNS: &str = r#"
+    id, carrier_account_id, picking_list_id, package_type_id, reference, recipient_name,
+    recipient_street, recipient_house_number, recipient_city, recipient_zip_code,
+    recipient_country_code, recipient_phone, recipient_email, parcel_count, weight_g, cod_amount,
+    status, carrier_parcel_id, tracking_number, booked_at, delivered_at, tracking_checked_at,
+    created_by_id, created_at, updated_at
+"#;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ShipmentsRepository: Send + Sync {
+    async fn get_carrier_accounts(&self) -> RepositoryResult<Vec<CarrierAccount>>;
+    async fn get_carrier_account(&self, id: Uuid) -> RepositoryResult<CarrierAccount>;
+    async fn insert_carrier_account(
+        &self,
+        input: &CarrierAccountInput,
+        sub: Uuid,
+    ) -> RepositoryResult<CarrierAccount>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Shipment>;
+    async fn get_events(&self, shipment_id: Uuid) -> RepositoryResult<Vec<ShipmentEvent>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Shipment>)>;
+    async fn get_picking_list_status(&self, picking_list_id: Uuid) -> RepositoryResult<String>;
+    async fn insert(&self, input: &CreateShipment, sub: Uuid) -> RepositoryResult<Shipment>;
+    async fn mark_booked(&self, id: Uuid, booking: &CarrierBooking) -> RepositoryResult<Shipment>;
+    async fn get_label(&self, id: Uuid) -> RepositoryResult<Option<Vec<u8>>>;
+    async fn get_trackable(&self) -> RepositoryResult<Vec<Shipment>>;
+    async fn record_tracking(
+        &self,
+        id: Uuid,
+        events: &[TrackingEvent],
+        status: &str,
+    ) -> RepositoryResult<Shipment>;
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Shipment>;
+}
+
+#[async_trait]
+impl ShipmentsRepository for PgPool {
+    async fn get_carrier_accounts(&self) -> RepositoryResult<Vec<CarrierAccount>> {
+        Ok(sqlx::query_as::<_, CarrierAccount>(
+            "SELECT * FROM carrier_accounts WHERE deleted_at IS NULL ORDER BY name",
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_carrier_account(&self, id: Uuid) -> RepositoryResult<CarrierAccount> {
+        Ok(sqlx::query_as::<_, CarrierAccount>(
+            "SELECT * FROM carrier_accounts WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_carrier_account(
+        &self,
+        input: &CarrierAccountInput,
+        sub: Uuid,
+    ) -> RepositoryResult<CarrierAccount> {
+        Ok(sqlx::query_as::<_, CarrierAccount>(
+            r#"
+            INSERT INTO carrier_accounts (carrier, name, api_url, username, password, client_number,
+                                          sender_name, sender_street, sender_house_number,
+                                          sender_city, sender_zip_code, sender_country_code,
+                                          sender_contact_name, sender_contact_phone,
+                                          sender_contact_email, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.carrier)
+        .bind(&input.name)
+        .bind(&input.api_url)
+        .bind(&input.username)
+        .bind(&input.password)
+        .bind(&input.client_number)
+        .bind(&input.sender_name)
+        .bind(&input.sender_street)
+        .bind(&input.sender_house_number)
+        .bind(&input.sender_city)
+        .bind(&input.sender_zip_code)
+        .bind(&input.sender_country_code)
+        .bind(&input.sender_contact_name)
+        .bind(&input.sender_contact_phone)
+        .bind(&input.sender_contact_email)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Shipment> {
+        Ok(sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            "SELECT {SHIPMENT_COLUMNS} FROM shipments WHERE id = $1" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_events(&self, shipment_id: Uuid) -> RepositoryResult<Vec<ShipmentEvent>> {
+        Ok(sqlx::query_as::<_, ShipmentEvent>(
+            "SELECT * FROM shipment_events WHERE shipment_id = $1 ORDER BY occurred_at",
+        )
+        .bind(shipment_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Shipment>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM shipments")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let shipments = sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            SELECT {SHIPMENT_COLUMNS}
+            FROM shipments
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "# // Security: constant
+        )))
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            shipments,
+        ))
+    }
+
+    async fn get_picking_list_status(&self, picking_list_id: Uuid) -> RepositoryResult<String> {
+        Ok(
+            sqlx::query_scalar::<_, String>("SELECT status FROM picking_lists WHERE id = $1")
+                .bind(picking_list_id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn insert(&self, input: &CreateShipment, sub: Uuid) -> RepositoryResult<Shipment> {
+        Ok(sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            INSERT INTO shipments (carrier_account_id, picking_list_id, package_type_id, reference,
+                                   recipient_name, recipient_street, recipient_house_number,
+                                   recipient_city, recipient_zip_code, recipient_country_code,
+                                   recipient_phone, recipient_email, parcel_count, weight_g,
+                                   cod_amount, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            RETURNING {SHIPMENT_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(input.carrier_account_id)
+        .bind(input.picking_list_id)
+        .bind(input.package_type_id)
+        .bind(&input.reference)
+        .bind(&input.recipient_name)
+        .bind(&input.recipient_street)
+        .bind(&input.recipient_house_number)
+        .bind(&input.recipient_city)
+        .bind(&input.recipient_zip_code)
+        .bind(&input.recipient_country_code)
+        .bind(&input.recipient_phone)
+        .bind(&input.recipient_email)
+        .bind(input.parcel_count)
+        .bind(input.weight_g)
+        .bind(&input.cod_amount)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn mark_booked(&self, id: Uuid, booking: &CarrierBooking) -> RepositoryResult<Shipment> {
+        Ok(sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            UPDATE shipments
+            SET status = 'booked',
+                carrier_parcel_id = $1,
+                tracking_number = $2,
+                label_pdf = $3,
+                booked_at = NOW()
+            WHERE id = $4
+                AND status = 'draft'
+            RETURNING {SHIPMENT_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(&booking.carrier_parcel_id)
+        .bind(&booking.tracking_number)
+        .bind(&booking.label_pdf)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_label(&self, id: Uuid) -> RepositoryResult<Option<Vec<u8>>> {
+        Ok(sqlx::query_scalar::<_, Option<Vec<u8>>>(
+            "SELECT label_pdf FROM shipments WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_trackable(&self) -> RepositoryResult<Vec<Shipment>> {
+        Ok(sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            SELECT {SHIPMENT_COLUMNS}
+            FROM shipments
+            WHERE status IN ('booked', 'in_transit')
+            ORDER BY tracking_checked_at NULLS FIRST
+            "# // Security: constant
+        )))
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn record_tracking(
+        &self,
+        id: Uuid,
+        events: &[TrackingEvent],
+        status: &str,
+    ) -> RepositoryResult<Shipment> {
+        let mut tx = self.begin().await?;
+
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO shipment_events (shipment_id, status, code, description, location,
+                                             occurred_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (shipment_id, code, occurred_at) DO NOTHING
+                "#,
+            )
+            .bind(id)
+            .bind(event.status)
+            .bind(&event.code)
+            .bind(&event.description)
+            .bind(&event.location)
+            .bind(event.occurred_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let delivered_at = events
+            .iter()
+            .filter(|event| event.status == STATUS_DELIVERED)
+            .map(|event| event.occurred_at)
+            .min();
+
+        let shipment = sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            UPDATE shipments
+            SET status = $1,
+                delivered_at = COALESCE(delivered_at, $2),
+                tracking_checked_at = NOW()
+            WHERE id = $3
+            RETURNING {SHIPMENT_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(status)
+        .bind(delivered_at)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(shipment)
+    }
+
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Shipment> {
+        Ok(sqlx::query_as::<_, Shipment>(AssertSqlSafe(format!(
+            r#"
+            UPDATE shipments
+            SET status = 'cancelled'
+            WHERE id = $1
+                AND status = 'draft'
+            RETURNING {SHIPMENT_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::ShipmentsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: ShipmentsModuleInterface>(shipments_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/shipments",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/list", get(handler::list::<M>))
+            .route("/create", post(handler::create::<M>))
+            .route("/book", put(handler::book::<M>))
+            .route("/label", get(handler::label::<M>))
+            .route("/refresh_tracking", put(handler::refresh_tracking::<M>))
+            .route("/cancel", put(handler::cancel::<M>))
+            .route(
+                "/carrier_accounts/list",
+                get(handler::list_carrier_accounts::<M>),
+            )
+            .route(
+                "/carrier_accounts/create",
+                post(handler::create_carrier_account::<M>),
+            )
+            .layer(from_fn_with_state(shipments_module.clone(), require_auth))
+            .with_state(shipments_module),
+    )
+}
@@ -0,0 +1,412 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::{CryptoError, encrypt_secret};
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::shipments::ShipmentsModuleInterface;
+use crate::tenant::shipments::carrier::CarrierError;
+use crate::tenant::shipments::dto::{CarrierAccountInput, CreateShipment};
+use crate::tenant::shipments::model::{
+    CarrierAccount, STATUS_DRAFT, Shipment, ShipmentWithEvents, status_after_events,
+};
+use crate::tenant::shipments::repository::ShipmentsRepository;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ShipmentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    Carrier(#[from] CarrierError),
+
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for ShipmentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => ShipmentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<ShipmentsServiceError> for AppError {
+    fn from(value: ShipmentsServiceError) -> Self {
+        match value {
+            ShipmentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ShipmentsServiceError::UnprocessableEntry(_)
+            | ShipmentsServiceError::Carrier(CarrierError::Rejected(_))
+            | ShipmentsServiceError::Carrier(CarrierError::Unsupported(_)) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            ShipmentsServiceError::Carrier(CarrierError::Http(_)) => Self::new(
+                Level::WARN,
+                StatusCode::BAD_GATEWAY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "A futárszolgálat jelenleg nem érhető el!"}),
+            ),
+            ShipmentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type ShipmentsServiceResult<T> = Result<T, ShipmentsServiceError>;
+
+fn required(value: &str, max_len: usize) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty() && value.chars().count() <= max_len).then(|| value.to_string())
+}
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn validate_carrier_account(
+    payload: &CarrierAccountInput,
+) -> ShipmentsServiceResult<CarrierAccountInput> {
+    if payload.carrier != "gls" {
+        return Err(ShipmentsServiceError::UnprocessableEntry(
+            "Nem támogatott futárszolgálat!",
+        ));
+    }
+    let invalid = || {
+        ShipmentsServiceError::UnprocessableEntry("A fiók és a feladó adatainak megadása kötelező!")
+    };
+    let api_url = required(&payload.api_url, 255).ok_or_else(invalid)?;
+    if !api_url.starts_with("https://") {
+        return Err(ShipmentsServiceError::UnprocessableEntry(
+            "Az API címnek https:// kezdetűnek kell lennie!",
+        ));
+    }
+    Ok(CarrierAccountInput {
+        carrier: payload.carrier.clone(),
+        name: required(&payload.name, 255).ok_or_else(invalid)?,
+        api_url,
+        username: required(&payload.username, 255).ok_or_else(invalid)?,
+        password: (!payload.password.is_empty())
+            .then(|| payload.password.clone())
+            .ok_or_else(invalid)?,
+        client_number: required(&payload.client_number, 50).ok_or_else(invalid)?,
+        sender_name: required(&payload.sender_name, 255).ok_or_else(invalid)?,
+        sender_street: required(&payload.sender_street, 255).ok_or_else(invalid)?,
+        sender_house_number: required(&payload.sender_house_number, 50).ok_or_else(invalid)?,
+        sender_city: required(&payload.sender_city, 255).ok_or_else(invalid)?,
+        sender_zip_code: required(&payload.sender_zip_code, 20).ok_or_else(invalid)?,
+        sender_country_code: required(&payload.sender_country_code, 2)
+            .map(|code| code.to_uppercase())
+            .ok_or_else(invalid)?,
+        sender_contact_name: optional(&payload.sender_contact_name),
+        sender_contact_phone: optional(&payload.sender_contact_phone),
+        sender_contact_email: optional(&payload.sender_contact_email),
+    })
+}
+
+fn validate_shipment(payload: &CreateShipment) -> ShipmentsServiceResult<CreateShipment> {
+    let invalid =
+        || ShipmentsServiceError::UnprocessableEntry("A címzett adatainak megadása kötelező!");
+    if payload.parcel_count < 1 || payload.parcel_count > 99 {
+        return Err(ShipmentsServiceError::UnprocessableEntry(
+            "A csomagok száma 1 és 99 között lehet!",
+        ));
+    }
+    if payload.weight_g.is_some_and(|weight| weight <= 0) {
+        return Err(ShipmentsServiceError::UnprocessableEntry(
+            "A súlynak pozitív számnak kell lennie!",
+        ));
+    }
+    if payload
+        .cod_amount
+        .as_ref()
+        .is_some_and(|amount| amount < &BigDecimal::zero())
+    {
+        return Err(ShipmentsServiceError::UnprocessableEntry(
+            "Az utánvét összege nem lehet negatív!",
+        ));
+    }
+    Ok(CreateShipment {
+        reference: optional(&payload.reference),
+        recipient_name: required(&payload.recipient_name, 255).ok_or_else(invalid)?,
+        recipient_street: required(&payload.recipient_street, 255).ok_or_else(invalid)?,
+        recipient_house_number: required(&payload.recipient_house_number, 50)
+            .ok_or_else(invalid)?,
+        recipient_city: required(&payload.recipient_city, 255).ok_or_else(invalid)?,
+        recipient_zip_code: required(&payload.recipient_zip_code, 20).ok_or_else(invalid)?,
+        recipient_country_code: required(&payload.recipient_country_code, 2)
+            .map(|code| code.to_uppercase())
+            .ok_or_else(invalid)?,
+        recipient_phone: optional(&payload.recipient_phone),
+        recipient_email: optional(&payload.recipient_email),
+        cod_amount: payload
+            .cod_amount
+            .clone()
+            .filter(|amount| !amount.is_zero()),
+        ..payload.clone()
+    })
+}
+
+pub(crate) async fn sync_tracking<M: ShipmentsModuleInterface>(
+    module: &M,
+    repo: &(dyn ShipmentsRepository + Send + Sync),
+    shipment: &Shipment,
+) -> ShipmentsServiceResult<Shipment> {
+    let account = repo
+        .get_carrier_account(shipment.carrier_account_id)
+        .await?;
+    let events = module.carrier_client(&account)?.tracking(shipment).await?;
+    let status = status_after_events(&shipment.status, &events);
+    Ok(repo.record_tracking(shipment.id, &events, status).await?)
+}
+
+pub trait ShipmentsService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ShipmentsServiceResult<ShipmentWithEvents>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = ShipmentsServiceResult<(PaginatorMeta, Vec<Shipment>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreateShipment,
+    ) -> impl Future<Output = ShipmentsServiceResult<Shipment>> + Send;
+    fn book(&self, id: Uuid) -> impl Future<Output = ShipmentsServiceResult<Shipment>> + Send;
+    fn label(&self, id: Uuid) -> impl Future<Output = ShipmentsServiceResult<Vec<u8>>> + Send;
+    fn refresh_tracking(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = ShipmentsServiceResult<ShipmentWithEvents>> + Send;
+    fn cancel(&self, id: Uuid) -> impl Future<Output = ShipmentsServiceResult<Shipment>> + Send;
+    fn get_carrier_accounts(
+        &self,
+    ) -> impl Future<Output = ShipmentsServiceResult<Vec<CarrierAccount>>> + Send;
+    fn create_carrier_account(
+        &self,
+        payload: &CarrierAccountInput,
+    ) -> impl Future<Output = ShipmentsServiceResult<CarrierAccount>> + Send;
+}
+
+impl<'a, T> ShipmentsService for Service<'a, T>
+where
+    T: ShipmentsModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> ShipmentsServiceResult<ShipmentWithEvents> {
+        let repo = self.module().shipments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ShipmentsServiceError::Unauthorized)?,
+        )?;
+        Ok(ShipmentWithEvents {
+            shipment: repo.get_by_id(id).await?,
+            events: repo.get_events(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> ShipmentsServiceResult<(PaginatorMeta, Vec<Shipment>)> {
+        Ok(self
+            .module()
+            .shipments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ShipmentsServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &CreateShipment) -> ShipmentsServiceResult<Shipment> {
+        let input = validate_shipment(payload)?;
+        let repo = self.module().shipments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ShipmentsServiceError::Unauthorized)?,
+        )?;
+        if repo
+            .get_carrier_account(input.carrier_account_id)
+            .await?
+            .status
+            != "active"
+        {
+            return Err(ShipmentsServiceError::UnprocessableEntry(
+                "A futárszolgálati fiók nem aktív!",
+            ));
+        }
+        if let Some(picking_list_id) = input.picking_list_id
+            && repo.get_picking_list_status(picking_list_id).await? != "completed"
+        {
+            return Err(ShipmentsServiceError::UnprocessableEntry(
+                "Csak lezárt szedési listához hozható létre szállítmány!",
+            ));
+        }
+        Ok(repo.insert(&input, self.claims()?.sub()).await?)
+    }
+
+    async fn book(&self, id: Uuid) -> ShipmentsServiceResult<Shipment> {
+        let repo = self.module().shipments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ShipmentsServiceError::Unauthorized)?,
+        )?;
+        let shipment = repo.get_by_id(id).await?;
+        if shipment.status != STATUS_DRAFT {
+            return Err(ShipmentsServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú szállítmány adható fel!",
+            ));
+        }
+        let account = repo
+            .get_carrier_account(shipment.carrier_account_id)
+            .await?;
+        let booking = self
+            .module()
+            .carrier_client(&account)?
+            .book(&shipment)
+            .await?;
+        Ok(repo.mark_booked(id, &booking).await?)
+    }
+
+    async fn label(&self, id: Uuid) -> ShipmentsServiceResult<Vec<u8>> {
+        self.module()
+            .shipments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ShipmentsServiceError::Unauthorized)?,
+            )?
+            .get_label(id)
+            .await?
+            .ok_or(ShipmentsServiceError::UnprocessableEntry(
+                "A szállítmányhoz még nem tartozik címke!",
+            ))
+    }
+
+    async fn refresh_tracking(&self, id: Uuid) -> ShipmentsServiceResult<ShipmentWithEvents> {
+        let repo = self.module().shipments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ShipmentsServiceError::Unauthorized)?,
+        )?;
+        let shipment = repo.get_by_id(id).await?;
+        if shipment.tracking_number.is_none() {
+            return Err(ShipmentsServiceError::UnprocessableEntry(
+                "A szállítmány még nincs feladva!",
+            ));
+        }
+        let shipment = sync_tracking(self.module(), &*repo, &shipment).await?;
+        Ok(ShipmentWithEvents {
+            events: repo.get_events(id).await?,
+            shipment,
+        })
+    }
+
+    async fn cancel(&self, id: Uuid) -> ShipmentsServiceResult<Shipment> {
+        let repo = self.module().shipments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(ShipmentsServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(id).await?.status != STATUS_DRAFT {
+            return Err(ShipmentsServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú szállítmány vonható vissza!",
+            ));
+        }
+        Ok(repo.cancel(id).await?)
+    }
+
+    async fn get_carrier_accounts(&self) -> ShipmentsServiceResult<Vec<CarrierAccount>> {
+        Ok(self
+            .module()
+            .shipments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ShipmentsServiceError::Unauthorized)?,
+            )?
+            .get_carrier_accounts()
+            .await?)
+    }
+
+    async fn create_carrier_account(
+        &self,
+        payload: &CarrierAccountInput,
+    ) -> ShipmentsServiceResult<CarrierAccount> {
+        let input = validate_carrier_account(payload)?;
+        let input = CarrierAccountInput {
+            password: encrypt_secret(&input.password)?,
+            ..input
+        };
+        Ok(self
+            .module()
+            .shipments_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(ShipmentsServiceError::Unauthorized)?,
+            )?
+            .insert_carrier_account(&input, self.claims()?.sub())
+            .await?)
+    }
+}
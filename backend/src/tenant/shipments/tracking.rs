@@ -0,0 +1,77 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::shipments::ShipmentsModuleInterface;
+use crate::tenant::shipments::service::sync_tracking;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+async fn poll_tenant<M: ShipmentsModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+) -> anyhow::Result<()> {
+    let repo = module.shipments_repo(tenant_id)?;
+    for shipment in repo.get_trackable().await? {
+        if let Err(e) = sync_tracking(module, &*repo, &shipment).await {
+            warn!("Shipment tracking failed: {} {}", shipment.id, e);
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_tracking_poller<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let interval_mins = app_state.config().carriers().tracking_poll_interval_mins();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_mins * 60));
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for shipment tracking: {}", e);
+                    continue;
+                }
+            };
+            for tenant in tenants {
+                if let Err(e) = poll_tenant(&*app_state, tenant.id).await {
+                    error!("Shipment tracking failed for tenant {}: {}", tenant.id, e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,141 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::stock_snapshots::StockSnapshotsModuleInterface;
+use crate::tenant::stock_snapshots::service::StockSnapshotsService;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: StockSnapshotsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_snapshots_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_snapshots_module.clone());
+    let result = map_handler_err(service.list().await, stock_snapshots_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_snapshots_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: StockSnapshotsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_snapshots_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_snapshots_module.clone());
+    let result = map_handler_err(service.create().await, stock_snapshots_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        stock_snapshots_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::stock_snapshots::model::StockSnapshot;
+    use crate::tenant::stock_snapshots::{
+        self, repository::MockStockSnapshotsRepository, tests::MockStockSnapshotsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_create_on_demand() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let snapshot = StockSnapshot {
+            id: Uuid::new_v4(),
+            taken_at: Utc::now(),
+            created_by_id: Some(sub),
+            created_at: Utc::now(),
+            line_count: 3,
+        };
+
+        let mut repo = MockStockSnapshotsRepository::new();
+        repo.expect_create()
+            .times(1)
+            .with(eq(Some(sub)))
+            .returning({
+                let snapshot = snapshot.clone();
+                move |_| Ok(snapshot.clone())
+            });
+
+        let mut app_state = MockStockSnapshotsModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_stock_snapshots_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .method("POST")
+            .uri("/api/stock_snapshots/create")
+            .body(Body::empty())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(stock_snapshots::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({ "meta": null, "data": snapshot })
+        );
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::stock_snapshots::model::StockSnapshot;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StockSnapshotsRepository: Send + Sync {
+    async fn list(&self) -> RepositoryResult<Vec<StockSnapshot>>;
+    async fn create(&self, sub: Option<Uuid>) -> RepositoryResult<StockSnapshot>;
+}
+
+#[async_trait]
+impl StockSnapshotsRepository for PgPool {
+    async fn list(&self) -> RepositoryResult<Vec<StockSnapshot>> {
+        Ok(sqlx::query_as::<_, StockSnapshot>(
+            r#"
+            SELECT stock_snapshots.*,
+                   (SELECT COUNT(*)
+                    FROM stock_snapshot_lines
+                    WHERE stock_snapshot_lines.snapshot_id = stock_snapshots.id) AS line_count
+            FROM stock_snapshots
+            ORDER BY taken_at DESC
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn create(&self, sub: Option<Uuid>) -> RepositoryResult<StockSnapshot> {
+        let mut tx = self.begin().await?;
+        // NOTE: waits for in-flight movements, so nothing dated before taken_at can be missed
+        sqlx::query("LOCK TABLE inventory_movements IN SHARE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let (id,) = sqlx::query_as::<_, (Uuid,)>(
+            r#"
+            INSERT INTO stock_snapshots (taken_at, created_by_id)
+            VALUES (clock_timestamp(), $1)
+            RETURNING id
+            "#,
+        )
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Rolls the previous snapshot forward instead of replaying the whole ledger
+        sqlx::query(
+            r#"
+            WITH current AS (
+                SELECT taken_at FROM stock_snapshots WHERE id = $1
+            ), base AS (
+                SELECT id, taken_at
+                FROM stock_snapshots
+                WHERE id <> $1
+                    AND taken_at <= (SELECT taken_at FROM current)
+                ORDER BY taken_at DESC
+                LIMIT 1
+            ), totals AS (
+                SELECT inventory_id, quantity, total_cost
+                FROM stock_snapshot_lines
+                WHERE snapshot_id = (SELECT id FROM base)
+                UNION ALL
+                SELECT inventory_id, quantity, COALESCE(total_cost, 0)
+                FROM inventory_movements
+                WHERE movement_date >= COALESCE((SELECT taken_at FROM base), '-infinity')
+                    AND movement_date < (SELECT taken_at FROM current)
+            )
+            INSERT INTO stock_snapshot_lines (snapshot_id, inventory_id, product_id, warehouse_id,
+                                              quantity, total_cost)
+            SELECT $1, inventory.id, inventory.product_id, inventory.warehouse_id,
+                   SUM(totals.quantity), SUM(totals.total_cost)
+            FROM totals
+            JOIN inventory ON totals.inventory_id = inventory.id
+            GROUP BY inventory.id
+            HAVING SUM(totals.quantity) <> 0 OR SUM(totals.total_cost) <> 0
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        let snapshot = sqlx::query_as::<_, StockSnapshot>(
+            r#"
+            SELECT stock_snapshots.*,
+                   (SELECT COUNT(*)
+                    FROM stock_snapshot_lines
+                    WHERE stock_snapshot_lines.snapshot_id = stock_snapshots.id) AS line_count
+            FROM stock_snapshots
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(snapshot)
+    }
+}
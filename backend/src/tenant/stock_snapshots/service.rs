@@ -0,0 +1,111 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::stock_snapshots::StockSnapshotsModuleInterface;
+use crate::tenant::stock_snapshots::model::StockSnapshot;
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+
+#[derive(Debug, Error)]
+pub enum StockSnapshotsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+}
+
+impl From<ServiceError> for StockSnapshotsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => StockSnapshotsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<StockSnapshotsServiceError> for AppError {
+    fn from(value: StockSnapshotsServiceError) -> Self {
+        match value {
+            StockSnapshotsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            StockSnapshotsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type StockSnapshotsServiceResult<T> = Result<T, StockSnapshotsServiceError>;
+
+pub trait StockSnapshotsService {
+    fn list(&self) -> impl Future<Output = StockSnapshotsServiceResult<Vec<StockSnapshot>>> + Send;
+    fn create(&self) -> impl Future<Output = StockSnapshotsServiceResult<StockSnapshot>> + Send;
+}
+
+impl<'a, T> StockSnapshotsService for Service<'a, T>
+where
+    T: StockSnapshotsModuleInterface,
+{
+    async fn list(&self) -> StockSnapshotsServiceResult<Vec<StockSnapshot>> {
+        Ok(self
+            .module()
+            .stock_snapshots_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockSnapshotsServiceError::Unauthorized)?,
+            )?
+            .list()
+            .await?)
+    }
+
+    async fn create(&self) -> StockSnapshotsServiceResult<StockSnapshot> {
+        Ok(self
+            .module()
+            .stock_snapshots_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockSnapshotsServiceError::Unauthorized)?,
+            )?
+            .create(Some(self.claims()?.sub()))
+            .await?)
+    }
+}
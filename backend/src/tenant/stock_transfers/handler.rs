@@ -0,0 +1,379 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::stock_transfers::StockTransfersModuleInterface;
+use crate::tenant::stock_transfers::dto::CreateStockTransfer;
+use crate::tenant::stock_transfers::service::StockTransfersService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn in_transit<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.get_in_transit().await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateStockTransfer>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn dispatch<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.dispatch(payload.uuid).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn receive<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.receive(payload.uuid).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn cancel<M: StockTransfersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stock_transfers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stock_transfers_module.clone());
+    let result = map_handler_err(
+        service.cancel(payload.uuid).await,
+        stock_transfers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stock_transfers_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::stock_transfers::model::{StockTransfer, StockTransferItem};
+    use crate::tenant::stock_transfers::{
+        self, repository::MockStockTransfersRepository, tests::MockStockTransfersModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockStockTransfersRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut stock_transfers_module = MockStockTransfersModule::new();
+        stock_transfers_module
+            .expect_stock_transfers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        stock_transfers_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(stock_transfers::routes::routes(Arc::new(
+                stock_transfers_module,
+            ))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn stock_transfer(status: &str) -> StockTransfer {
+        StockTransfer {
+            id: Uuid::new_v4(),
+            source_warehouse_id: Uuid::new_v4(),
+            destination_warehouse_id: Uuid::new_v4(),
+            status: status.to_string(),
+            note: None,
+            dispatched_at: None,
+            dispatched_by_id: None,
+            received_at: None,
+            received_by_id: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_same_warehouse() {
+        let active_tenant_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+        let mut repo = MockStockTransfersRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/stock_transfers/create",
+                active_tenant_id,
+                json!({
+                    "source_warehouse_id": warehouse_id,
+                    "destination_warehouse_id": warehouse_id,
+                    "note": null,
+                    "items": [{"product_id": Uuid::new_v4(), "quantity": "1"}]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_insufficient_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let stock_transfer = stock_transfer("draft");
+        let mut repo = MockStockTransfersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(stock_transfer.id))
+            .returning({
+                let stock_transfer = stock_transfer.clone();
+                move |_| Ok(stock_transfer.clone())
+            });
+        repo.expect_get_items()
+            .times(1)
+            .with(eq(stock_transfer.id))
+            .returning({
+                let stock_transfer_id = stock_transfer.id;
+                move |_| {
+                    Ok(vec![StockTransferItem {
+                        id: Uuid::new_v4(),
+                        stock_transfer_id,
+                        product_id: Uuid::new_v4(),
+                        product: "Csavar".to_string(),
+                        quantity: BigDecimal::from(5),
+                        source_quantity_available: Some(BigDecimal::from(3)),
+                    }])
+                }
+            });
+        repo.expect_dispatch().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/stock_transfers/dispatch",
+                active_tenant_id,
+                json!({"uuid": stock_transfer.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_receive_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let stock_transfer = stock_transfer("dispatched");
+        let received = StockTransfer {
+            status: "received".to_string(),
+            received_at: Some(Utc::now()),
+            ..stock_transfer.clone()
+        };
+        let mut repo = MockStockTransfersRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(stock_transfer.id))
+            .returning({
+                let stock_transfer = stock_transfer.clone();
+                move |_| Ok(stock_transfer.clone())
+            });
+        repo.expect_receive()
+            .times(1)
+            .withf({
+                let id = stock_transfer.id;
+                move |stock_transfer_id, _| *stock_transfer_id == id
+            })
+            .returning({
+                let received = received.clone();
+                move |_, _| Ok(Some(received.clone()))
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/stock_transfers/receive",
+                active_tenant_id,
+                json!({"uuid": stock_transfer.id}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_DISPATCHED: &str = "dispatched";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct StockTransfer {
+    pub id: Uuid,
+    pub source_warehouse_id: Uuid,
+    pub destination_warehouse_id: Uuid,
+    pub status: String,
+    pub note: Option<String>,
+    pub dispatched_at: Option<DateTime<Utc>>,
+    pub dispatched_by_id: Option<Uuid>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub received_by_id: Option<Uuid>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct StockTransferItem {
+    pub id: Uuid,
+    pub stock_transfer_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub quantity: BigDecimal,
+    pub source_quantity_available: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StockTransferDetails {
+    #[serde(flatten)]
+    pub transfer: StockTransfer,
+    pub items: Vec<StockTransferItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InTransitQuantity {
+    pub product_id: Uuid,
+    pub product: String,
+    pub destination_warehouse_id: Uuid,
+    pub destination_warehouse: String,
+    pub quantity: BigDecimal,
+}
@@ -0,0 +1,327 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::stock_transfers::dto::CreateStockTransfer;
+use crate::tenant::stock_transfers::model::{InTransitQuantity, StockTransfer, StockTransferItem};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StockTransfersRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<StockTransfer>;
+    async fn get_items(&self, stock_transfer_id: Uuid) -> RepositoryResult<Vec<StockTransferItem>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<StockTransfer>)>;
+    async fn get_in_transit(&self) -> RepositoryResult<Vec<InTransitQuantity>>;
+    async fn insert(
+        &self,
+        input: &CreateStockTransfer,
+        sub: Uuid,
+    ) -> RepositoryResult<StockTransfer>;
+    async fn dispatch(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<StockTransfer>>;
+    async fn receive(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<StockTransfer>>;
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Option<StockTransfer>>;
+}
+
+#[async_trait]
+impl StockTransfersRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<StockTransfer> {
+        Ok(
+            sqlx::query_as::<_, StockTransfer>("SELECT * FROM stock_transfers WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_items(&self, stock_transfer_id: Uuid) -> RepositoryResult<Vec<StockTransferItem>> {
+        Ok(sqlx::query_as::<_, StockTransferItem>(
+            r#"
+            SELECT stock_transfer_items.id,
+                   stock_transfer_items.stock_transfer_id,
+                   stock_transfer_items.product_id,
+                   products.name AS product,
+                   stock_transfer_items.quantity,
+                   inventory.quantity_available AS source_quantity_available
+            FROM stock_transfer_items
+            JOIN stock_transfers ON stock_transfer_items.stock_transfer_id = stock_transfers.id
+            JOIN products ON stock_transfer_items.product_id = products.id
+            LEFT JOIN inventory ON inventory.product_id = stock_transfer_items.product_id
+                AND inventory.warehouse_id = stock_transfers.source_warehouse_id
+                AND inventory.deleted_at IS NULL
+            WHERE stock_transfer_items.stock_transfer_id = $1
+            ORDER BY products.name
+            "#,
+        )
+        .bind(stock_transfer_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<StockTransfer>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM stock_transfers")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let stock_transfers = sqlx::query_as::<_, StockTransfer>(
+            r#"
+            SELECT *
+            FROM stock_transfers
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            stock_transfers,
+        ))
+    }
+
+    async fn get_in_transit(&self) -> RepositoryResult<Vec<InTransitQuantity>> {
+        Ok(sqlx::query_as::<_, InTransitQuantity>(
+            r#"
+            SELECT stock_transfer_items.product_id,
+                   products.name AS product,
+                   stock_transfers.destination_warehouse_id,
+                   warehouses.name AS destination_warehouse,
+                   SUM(stock_transfer_items.quantity) AS quantity
+            FROM stock_transfer_items
+            JOIN stock_transfers ON stock_transfer_items.stock_transfer_id = stock_transfers.id
+            JOIN products ON stock_transfer_items.product_id = products.id
+            JOIN warehouses ON stock_transfers.destination_warehouse_id = warehouses.id
+            WHERE stock_transfers.status = 'dispatched'
+            GROUP BY stock_transfer_items.product_id, products.name,
+                     stock_transfers.destination_warehouse_id, warehouses.name
+            ORDER BY warehouses.name, products.name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        input: &CreateStockTransfer,
+        sub: Uuid,
+    ) -> RepositoryResult<StockTransfer> {
+        let mut tx = self.begin().await?;
+        let stock_transfer = sqlx::query_as::<_, StockTransfer>(
+            r#"
+            INSERT INTO stock_transfers (source_warehouse_id, destination_warehouse_id, note, created_by_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(input.source_warehouse_id)
+        .bind(input.destination_warehouse_id)
+        .bind(&input.note)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        for item in &input.items {
+            sqlx::query(
+                "INSERT INTO stock_transfer_items (stock_transfer_id, product_id, quantity) VALUES ($1, $2, $3)",
+            )
+            .bind(stock_transfer.id)
+            .bind(item.product_id)
+            .bind(&item.quantity)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(stock_transfer)
+    }
+
+    async fn dispatch(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<StockTransfer>> {
+        let mut tx = self.begin().await?;
+        let Some(stock_transfer) = sqlx::query_as::<_, StockTransfer>(
+            r#"
+            UPDATE stock_transfers
+            SET status = 'dispatched',
+                dispatched_at = NOW(),
+                dispatched_by_id = $2
+            WHERE id = $1 AND status = 'draft'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE stock_transfer_items
+            SET source_inventory_id = inventory.id
+            FROM inventory
+            WHERE stock_transfer_items.stock_transfer_id = $1
+              AND inventory.product_id = stock_transfer_items.product_id
+              AND inventory.warehouse_id = $2
+              AND inventory.deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(stock_transfer.source_warehouse_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // NOTE: the inventory trigger and the quantity_available check reject the
+        // movement if a concurrent change left too little stock at the source.
+        sqlx::query(
+            r#"
+            INSERT INTO inventory_movements (
+                inventory_id, movement_type, quantity, reference_type, reference_id, created_by_id
+            )
+            SELECT source_inventory_id, 'transfer', -quantity, 'stock_transfers', $1, $2
+            FROM stock_transfer_items
+            WHERE stock_transfer_id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(stock_transfer))
+    }
+
+    async fn receive(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<StockTransfer>> {
+        let mut tx = self.begin().await?;
+        let Some(stock_transfer) = sqlx::query_as::<_, StockTransfer>(
+            r#"
+            UPDATE stock_transfers
+            SET status = 'received',
+                received_at = NOW(),
+                received_by_id = $2
+            WHERE id = $1 AND status = 'dispatched'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO inventory (product_id, warehouse_id, currency_code, created_by_id)
+            SELECT stock_transfer_items.product_id, $2, source.currency_code, $3
+            FROM stock_transfer_items
+            JOIN inventory source ON stock_transfer_items.source_inventory_id = source.id
+            WHERE stock_transfer_items.stock_transfer_id = $1
+              AND NOT EXISTS (
+                SELECT 1
+                FROM inventory destination
+                WHERE destination.product_id = stock_transfer_items.product_id
+                  AND destination.warehouse_id = $2
+                  AND destination.deleted_at IS NULL
+              )
+            "#,
+        )
+        .bind(id)
+        .bind(stock_transfer.destination_warehouse_id)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE stock_transfer_items
+            SET destination_inventory_id = inventory.id
+            FROM inventory
+            WHERE stock_transfer_items.stock_transfer_id = $1
+              AND inventory.product_id = stock_transfer_items.product_id
+              AND inventory.warehouse_id = $2
+              AND inventory.deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(stock_transfer.destination_warehouse_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // NOTE: the received stock keeps the cost it was dispatched at
+        sqlx::query(
+            r#"
+            INSERT INTO inventory_movements (
+                inventory_id, movement_type, quantity, reference_type, reference_id, created_by_id,
+                unit_cost
+            )
+            SELECT destination_inventory_id, 'transfer', quantity, 'stock_transfers', $1, $2,
+                   (SELECT inventory_movements.unit_cost
+                    FROM inventory_movements
+                    WHERE inventory_movements.reference_type = 'stock_transfers'
+                        AND inventory_movements.reference_id = $1
+                        AND inventory_movements.inventory_id = stock_transfer_items.source_inventory_id
+                        AND inventory_movements.quantity < 0
+                    LIMIT 1)
+            FROM stock_transfer_items
+            WHERE stock_transfer_id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(stock_transfer))
+    }
+
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Option<StockTransfer>> {
+        Ok(sqlx::query_as::<_, StockTransfer>(
+            "UPDATE stock_transfers SET status = 'cancelled' WHERE id = $1 AND status = 'draft' RETURNING *",
+        )
+        .bind(id)
+        .fetch_optional(self)
+        .await?)
+    }
+}
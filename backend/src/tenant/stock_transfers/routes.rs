@@ -0,0 +1,45 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::StockTransfersModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: StockTransfersModuleInterface>(stock_transfers_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/stock_transfers",
+        Router::new()
+            .route("/get", get(handler::get::<M>))
+            .route("/list", get(handler::list::<M>))
+            .route("/in_transit", get(handler::in_transit::<M>))
+            .route("/create", post(handler::create::<M>))
+            .route("/dispatch", put(handler::dispatch::<M>))
+            .route("/receive", put(handler::receive::<M>))
+            .route("/cancel", put(handler::cancel::<M>))
+            .layer(from_fn_with_state(
+                stock_transfers_module.clone(),
+                require_auth,
+            ))
+            .with_state(stock_transfers_module),
+    )
+}
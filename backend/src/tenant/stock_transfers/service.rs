@@ -0,0 +1,276 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::stock_transfers::StockTransfersModuleInterface;
+use crate::tenant::stock_transfers::dto::CreateStockTransfer;
+use crate::tenant::stock_transfers::model::{
+    InTransitQuantity, STATUS_DISPATCHED, STATUS_DRAFT, StockTransfer, StockTransferDetails,
+};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use std::collections::HashSet;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum StockTransfersServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for StockTransfersServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => StockTransfersServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<StockTransfersServiceError> for AppError {
+    fn from(value: StockTransfersServiceError) -> Self {
+        match value {
+            StockTransfersServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            StockTransfersServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            StockTransfersServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type StockTransfersServiceResult<T> = Result<T, StockTransfersServiceError>;
+
+fn validate(payload: &CreateStockTransfer) -> StockTransfersServiceResult<()> {
+    if payload.source_warehouse_id == payload.destination_warehouse_id {
+        return Err(StockTransfersServiceError::UnprocessableEntry(
+            "A forrás- és célraktár nem lehet azonos!",
+        ));
+    }
+    if payload.items.is_empty() {
+        return Err(StockTransfersServiceError::UnprocessableEntry(
+            "Legalább egy tétel megadása kötelező!",
+        ));
+    }
+    if payload
+        .items
+        .iter()
+        .any(|item| item.quantity <= BigDecimal::zero())
+    {
+        return Err(StockTransfersServiceError::UnprocessableEntry(
+            "A mennyiségnek pozitív számnak kell lennie!",
+        ));
+    }
+    let mut product_ids = HashSet::new();
+    if !payload
+        .items
+        .iter()
+        .all(|item| product_ids.insert(item.product_id))
+    {
+        return Err(StockTransfersServiceError::UnprocessableEntry(
+            "Egy termék csak egyszer szerepelhet az átmozgatásban!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait StockTransfersService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = StockTransfersServiceResult<StockTransferDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = StockTransfersServiceResult<(PaginatorMeta, Vec<StockTransfer>)>> + Send;
+    fn get_in_transit(
+        &self,
+    ) -> impl Future<Output = StockTransfersServiceResult<Vec<InTransitQuantity>>> + Send;
+    fn create(
+        &self,
+        payload: &CreateStockTransfer,
+    ) -> impl Future<Output = StockTransfersServiceResult<StockTransfer>> + Send;
+    fn dispatch(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = StockTransfersServiceResult<StockTransfer>> + Send;
+    fn receive(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = StockTransfersServiceResult<StockTransfer>> + Send;
+    fn cancel(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = StockTransfersServiceResult<StockTransfer>> + Send;
+}
+
+impl<'a, T> StockTransfersService for Service<'a, T>
+where
+    T: StockTransfersModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> StockTransfersServiceResult<StockTransferDetails> {
+        let repo = self.module().stock_transfers_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(StockTransfersServiceError::Unauthorized)?,
+        )?;
+        Ok(StockTransferDetails {
+            transfer: repo.get_by_id(id).await?,
+            items: repo.get_items(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> StockTransfersServiceResult<(PaginatorMeta, Vec<StockTransfer>)> {
+        Ok(self
+            .module()
+            .stock_transfers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockTransfersServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn get_in_transit(&self) -> StockTransfersServiceResult<Vec<InTransitQuantity>> {
+        Ok(self
+            .module()
+            .stock_transfers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockTransfersServiceError::Unauthorized)?,
+            )?
+            .get_in_transit()
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateStockTransfer,
+    ) -> StockTransfersServiceResult<StockTransfer> {
+        validate(payload)?;
+        Ok(self
+            .module()
+            .stock_transfers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockTransfersServiceError::Unauthorized)?,
+            )?
+            .insert(payload, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn dispatch(&self, id: Uuid) -> StockTransfersServiceResult<StockTransfer> {
+        let repo = self.module().stock_transfers_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(StockTransfersServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(id).await?.status != STATUS_DRAFT {
+            return Err(StockTransfersServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú átmozgatás indítható!",
+            ));
+        }
+        if repo.get_items(id).await?.iter().any(|item| {
+            item.source_quantity_available
+                .as_ref()
+                .is_none_or(|available| *available < item.quantity)
+        }) {
+            return Err(StockTransfersServiceError::UnprocessableEntry(
+                "Nincs elegendő készlet a forrásraktárban!",
+            ));
+        }
+        repo.dispatch(id, self.claims()?.sub()).await?.ok_or(
+            StockTransfersServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú átmozgatás indítható!",
+            ),
+        )
+    }
+
+    async fn receive(&self, id: Uuid) -> StockTransfersServiceResult<StockTransfer> {
+        let repo = self.module().stock_transfers_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(StockTransfersServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(id).await?.status != STATUS_DISPATCHED {
+            return Err(StockTransfersServiceError::UnprocessableEntry(
+                "Csak úton lévő átmozgatás vehető át!",
+            ));
+        }
+        repo.receive(id, self.claims()?.sub()).await?.ok_or(
+            StockTransfersServiceError::UnprocessableEntry("Csak úton lévő átmozgatás vehető át!"),
+        )
+    }
+
+    async fn cancel(&self, id: Uuid) -> StockTransfersServiceResult<StockTransfer> {
+        self.module()
+            .stock_transfers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StockTransfersServiceError::Unauthorized)?,
+            )?
+            .cancel(id)
+            .await?
+            .ok_or(StockTransfersServiceError::UnprocessableEntry(
+                "Csak piszkozat állapotú átmozgatás vonható vissza!",
+            ))
+    }
+}
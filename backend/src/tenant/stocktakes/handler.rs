@@ -0,0 +1,361 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::stocktakes::StocktakesModuleInterface;
+use crate::tenant::stocktakes::dto::{CreateStocktake, StocktakeCounts};
+use crate::tenant::stocktakes::service::StocktakesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let result =
+        map_handler_err(service.get(payload.uuid).await, stocktakes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        stocktakes_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        stocktakes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateStocktake>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let result = map_handler_err(service.create(&payload).await, stocktakes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn count<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<StocktakeCounts>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let result = map_handler_err(service.count(&payload).await, stocktakes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn approve<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let result = map_handler_err(
+        service.approve(payload.uuid).await,
+        stocktakes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn cancel<M: StocktakesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(stocktakes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), stocktakes_module.clone());
+    let result = map_handler_err(
+        service.cancel(payload.uuid).await,
+        stocktakes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        stocktakes_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::stocktakes::model::{Stocktake, StocktakeLine};
+    use crate::tenant::stocktakes::{
+        self, repository::MockStocktakesRepository, tests::MockStocktakesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockStocktakesRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let role = role.to_string();
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(Some(role.clone())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "inventory.adjust")
+            .returning(move |_, _| Ok(granted));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut stocktakes_module = MockStocktakesModule::new();
+        stocktakes_module
+            .expect_stocktakes_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        stocktakes_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        stocktakes_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        stocktakes_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(stocktakes::routes::routes(Arc::new(stocktakes_module))),
+        )
+    }
+
+    fn json_request(
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn stocktake(status: &str) -> Stocktake {
+        Stocktake {
+            id: Uuid::new_v4(),
+            warehouse_id: Uuid::new_v4(),
+            status: status.to_string(),
+            note: None,
+            approved_at: None,
+            approved_by_id: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn line(stocktake_id: Uuid, expected: i64, counted: i64, available: i64) -> StocktakeLine {
+        StocktakeLine {
+            id: Uuid::new_v4(),
+            stocktake_id,
+            inventory_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            product: "Csavar".to_string(),
+            expected_quantity: BigDecimal::from(expected),
+            counted_quantity: Some(BigDecimal::from(counted)),
+            variance: Some(BigDecimal::from(counted - expected)),
+            quantity_available: BigDecimal::from(available),
+            counted_at: Some(Utc::now()),
+            counted_by_id: Some(Uuid::new_v4()),
+            inventory_adjustment_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_approve_forbidden_without_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockStocktakesRepository::new();
+        repo.expect_approve().never();
+
+        let response = app(repo, "member", false, active_tenant_id)
+            .oneshot(json_request(
+                "/api/stocktakes/approve",
+                active_tenant_id,
+                json!({ "uuid": Uuid::new_v4() }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_approve_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let stocktake = stocktake("open");
+        let mut repo = MockStocktakesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(stocktake.id))
+            .returning({
+                let stocktake = stocktake.clone();
+                move |_| Ok(stocktake.clone())
+            });
+        repo.expect_get_lines()
+            .times(1)
+            .with(eq(stocktake.id))
+            .returning({
+                let stocktake_id = stocktake.id;
+                move |_| Ok(vec![line(stocktake_id, 10, 8, 10)])
+            });
+        repo.expect_approve().times(1).returning({
+            let stocktake = stocktake.clone();
+            move |_, _| {
+                Ok(Some(Stocktake {
+                    status: "approved".to_string(),
+                    ..stocktake.clone()
+                }))
+            }
+        });
+
+        let response = app(repo, "member", true, active_tenant_id)
+            .oneshot(json_request(
+                "/api/stocktakes/approve",
+                active_tenant_id,
+                json!({ "uuid": stocktake.id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_approve_insufficient_available_stock() {
+        let active_tenant_id = Uuid::new_v4();
+        let stocktake = stocktake("open");
+        let mut repo = MockStocktakesRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let stocktake = stocktake.clone();
+            move |_| Ok(stocktake.clone())
+        });
+        repo.expect_get_lines().times(1).returning({
+            let stocktake_id = stocktake.id;
+            move |_| Ok(vec![line(stocktake_id, 10, 4, 3)])
+        });
+        repo.expect_approve().never();
+
+        let response = app(repo, "owner", false, active_tenant_id)
+            .oneshot(json_request(
+                "/api/stocktakes/approve",
+                active_tenant_id,
+                json!({ "uuid": stocktake.id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
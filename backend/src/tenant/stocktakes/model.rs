@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_OPEN: &str = "open";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Stocktake {
+    pub id: Uuid,
+    pub warehouse_id: Uuid,
+    pub status: String,
+    pub note: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub approved_by_id: Option<Uuid>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct StocktakeLine {
+    pub id: Uuid,
+    pub stocktake_id: Uuid,
+    pub inventory_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub expected_quantity: BigDecimal,
+    pub counted_quantity: Option<BigDecimal>,
+    pub variance: Option<BigDecimal>,
+    pub quantity_available: BigDecimal,
+    pub counted_at: Option<DateTime<Utc>>,
+    pub counted_by_id: Option<Uuid>,
+    pub inventory_adjustment_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StocktakeDetails {
+    #[serde(flatten)]
+    pub stocktake: Stocktake,
+    pub lines: Vec<StocktakeLine>,
+}
@@ -0,0 +1,283 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::stocktakes::dto::{CreateStocktake, StocktakeCounts};
+use crate::tenant::stocktakes::model::{Stocktake, StocktakeLine};
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StocktakesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Stocktake>;
+    async fn get_lines(&self, stocktake_id: Uuid) -> RepositoryResult<Vec<StocktakeLine>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Stocktake>)>;
+    async fn insert(&self, input: &CreateStocktake, sub: Uuid) -> RepositoryResult<Stocktake>;
+    async fn count(&self, input: &StocktakeCounts, sub: Uuid) -> RepositoryResult<bool>;
+    async fn approve(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<Stocktake>>;
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Option<Stocktake>>;
+}
+
+#[async_trait]
+impl StocktakesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Stocktake> {
+        Ok(
+            sqlx::query_as::<_, Stocktake>("SELECT * FROM stocktakes WHERE id = $1")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn get_lines(&self, stocktake_id: Uuid) -> RepositoryResult<Vec<StocktakeLine>> {
+        Ok(sqlx::query_as::<_, StocktakeLine>(
+            r#"
+            SELECT stocktake_lines.id,
+                   stocktake_lines.stocktake_id,
+                   stocktake_lines.inventory_id,
+                   inventory.product_id,
+                   products.name AS product,
+                   stocktake_lines.expected_quantity,
+                   stocktake_lines.counted_quantity,
+                   stocktake_lines.counted_quantity - stocktake_lines.expected_quantity AS variance,
+                   inventory.quantity_available,
+                   stocktake_lines.counted_at,
+                   stocktake_lines.counted_by_id,
+                   stocktake_lines.inventory_adjustment_id
+            FROM stocktake_lines
+            JOIN inventory ON stocktake_lines.inventory_id = inventory.id
+            JOIN products ON inventory.product_id = products.id
+            WHERE stocktake_lines.stocktake_id = $1
+            ORDER BY products.name
+            "#,
+        )
+        .bind(stocktake_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Stocktake>)> {
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM stocktakes")
+            .fetch_one(self)
+            .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let stocktakes = sqlx::query_as::<_, Stocktake>(
+            r#"
+            SELECT *
+            FROM stocktakes
+            ORDER BY created_at DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            stocktakes,
+        ))
+    }
+
+    async fn insert(&self, input: &CreateStocktake, sub: Uuid) -> RepositoryResult<Stocktake> {
+        let mut tx = self.begin().await?;
+        let stocktake = sqlx::query_as::<_, Stocktake>(
+            r#"
+            INSERT INTO stocktakes (warehouse_id, note, created_by_id)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(input.warehouse_id)
+        .bind(&input.note)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO stocktake_lines (stocktake_id, inventory_id, expected_quantity)
+            SELECT $1, inventory.id, inventory.quantity_on_hand
+            FROM inventory
+            WHERE inventory.warehouse_id = $2
+              AND inventory.deleted_at IS NULL
+            "#,
+        )
+        .bind(stocktake.id)
+        .bind(input.warehouse_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(stocktake)
+    }
+
+    async fn count(&self, input: &StocktakeCounts, sub: Uuid) -> RepositoryResult<bool> {
+        let line_ids: Vec<Uuid> = input.counts.iter().map(|count| count.line_id).collect();
+        let quantities: Vec<BigDecimal> = input
+            .counts
+            .iter()
+            .map(|count| count.counted_quantity.clone())
+            .collect();
+
+        let mut tx = self.begin().await?;
+        let updated = sqlx::query(
+            r#"
+            UPDATE stocktake_lines
+            SET counted_quantity = counts.counted_quantity,
+                counted_at = now(),
+                counted_by_id = $4
+            FROM unnest($2::uuid[], $3::numeric[]) AS counts(line_id, counted_quantity),
+                 stocktakes
+            WHERE stocktake_lines.id = counts.line_id
+              AND stocktake_lines.stocktake_id = $1
+              AND stocktakes.id = stocktake_lines.stocktake_id
+              AND stocktakes.status = 'open'
+            "#,
+        )
+        .bind(input.id)
+        .bind(&line_ids)
+        .bind(quantities)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if updated != line_ids.len() as u64 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn approve(&self, id: Uuid, sub: Uuid) -> RepositoryResult<Option<Stocktake>> {
+        let mut tx = self.begin().await?;
+        let Some(stocktake) = sqlx::query_as::<_, Stocktake>(
+            r#"
+            UPDATE stocktakes
+            SET status = 'approved',
+                approved_at = now(),
+                approved_by_id = $2
+            WHERE id = $1 AND status = 'open'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(sub)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+
+        let variances = sqlx::query_as::<_, (Uuid, Uuid, BigDecimal)>(
+            r#"
+            SELECT id, inventory_id, counted_quantity - expected_quantity
+            FROM stocktake_lines
+            WHERE stocktake_id = $1
+              AND counted_quantity IS NOT NULL
+              AND counted_quantity <> expected_quantity
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (line_id, inventory_id, variance) in variances {
+            let inventory_adjustment_id = Uuid::new_v4();
+            let inventory_movement_id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO inventory_movements (
+                    inventory_id, movement_type, quantity, reference_type, reference_id, created_by_id
+                ) VALUES ($1, 'adjustment', $2, 'inventory_adjustments', $3, $4)
+                RETURNING id
+                "#,
+            )
+            .bind(inventory_id)
+            .bind(&variance)
+            .bind(inventory_adjustment_id)
+            .bind(sub)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO inventory_adjustments (
+                    id, inventory_id, inventory_movement_id, quantity, reason_code, note, created_by_id
+                ) VALUES ($1, $2, $3, $4, 'correction', $5, $6)
+                "#,
+            )
+            .bind(inventory_adjustment_id)
+            .bind(inventory_id)
+            .bind(inventory_movement_id)
+            .bind(&variance)
+            .bind(format!("Leltár: {id}"))
+            .bind(sub)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query("UPDATE stocktake_lines SET inventory_adjustment_id = $2 WHERE id = $1")
+                .bind(line_id)
+                .bind(inventory_adjustment_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(stocktake))
+    }
+
+    async fn cancel(&self, id: Uuid) -> RepositoryResult<Option<Stocktake>> {
+        Ok(sqlx::query_as::<_, Stocktake>(
+            r#"
+            UPDATE stocktakes
+            SET status = 'cancelled'
+            WHERE id = $1 AND status = 'open'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self)
+        .await?)
+    }
+}
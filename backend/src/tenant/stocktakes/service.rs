@@ -0,0 +1,277 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::permissions::model::INVENTORY_ADJUST;
+use crate::tenant::permissions::service::has_permission;
+use crate::tenant::stocktakes::StocktakesModuleInterface;
+use crate::tenant::stocktakes::dto::{CreateStocktake, StocktakeCounts};
+use crate::tenant::stocktakes::model::{STATUS_OPEN, Stocktake, StocktakeDetails};
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use std::collections::HashSet;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum StocktakesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for StocktakesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => StocktakesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<StocktakesServiceError> for AppError {
+    fn from(value: StocktakesServiceError) -> Self {
+        match value {
+            StocktakesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            StocktakesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            StocktakesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            StocktakesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type StocktakesServiceResult<T> = Result<T, StocktakesServiceError>;
+
+fn validate_counts(payload: &StocktakeCounts) -> StocktakesServiceResult<()> {
+    if payload.counts.is_empty() {
+        return Err(StocktakesServiceError::UnprocessableEntry(
+            "Legalább egy számolt tétel megadása kötelező!",
+        ));
+    }
+    if payload
+        .counts
+        .iter()
+        .any(|count| count.counted_quantity < BigDecimal::zero())
+    {
+        return Err(StocktakesServiceError::UnprocessableEntry(
+            "A számolt mennyiség nem lehet negatív!",
+        ));
+    }
+    let mut line_ids = HashSet::new();
+    if !payload
+        .counts
+        .iter()
+        .all(|count| line_ids.insert(count.line_id))
+    {
+        return Err(StocktakesServiceError::UnprocessableEntry(
+            "Egy tétel csak egyszer szerepelhet!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait StocktakesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = StocktakesServiceResult<StocktakeDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = StocktakesServiceResult<(PaginatorMeta, Vec<Stocktake>)>> + Send;
+    fn create(
+        &self,
+        payload: &CreateStocktake,
+    ) -> impl Future<Output = StocktakesServiceResult<Stocktake>> + Send;
+    fn count(
+        &self,
+        payload: &StocktakeCounts,
+    ) -> impl Future<Output = StocktakesServiceResult<StocktakeDetails>> + Send;
+    fn approve(&self, id: Uuid) -> impl Future<Output = StocktakesServiceResult<Stocktake>> + Send;
+    fn cancel(&self, id: Uuid) -> impl Future<Output = StocktakesServiceResult<Stocktake>> + Send;
+}
+
+impl<'a, T> StocktakesService for Service<'a, T>
+where
+    T: StocktakesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> StocktakesServiceResult<StocktakeDetails> {
+        let repo = self.module().stocktakes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(StocktakesServiceError::Unauthorized)?,
+        )?;
+        Ok(StocktakeDetails {
+            stocktake: repo.get_by_id(id).await?,
+            lines: repo.get_lines(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> StocktakesServiceResult<(PaginatorMeta, Vec<Stocktake>)> {
+        Ok(self
+            .module()
+            .stocktakes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StocktakesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(&self, payload: &CreateStocktake) -> StocktakesServiceResult<Stocktake> {
+        Ok(self
+            .module()
+            .stocktakes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StocktakesServiceError::Unauthorized)?,
+            )?
+            .insert(payload, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn count(&self, payload: &StocktakeCounts) -> StocktakesServiceResult<StocktakeDetails> {
+        validate_counts(payload)?;
+        let repo = self.module().stocktakes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(StocktakesServiceError::Unauthorized)?,
+        )?;
+        if repo.get_by_id(payload.id).await?.status != STATUS_OPEN {
+            return Err(StocktakesServiceError::UnprocessableEntry(
+                "Csak nyitott leltárban rögzíthető számolás!",
+            ));
+        }
+        if !repo.count(payload, self.claims()?.sub()).await? {
+            return Err(StocktakesServiceError::UnprocessableEntry(
+                "A megadott tételek nem tartoznak ehhez a leltárhoz!",
+            ));
+        }
+        Ok(StocktakeDetails {
+            stocktake: repo.get_by_id(payload.id).await?,
+            lines: repo.get_lines(payload.id).await?,
+        })
+    }
+
+    async fn approve(&self, id: Uuid) -> StocktakesServiceResult<Stocktake> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(StocktakesServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), INVENTORY_ADJUST).await? {
+            return Err(StocktakesServiceError::Forbidden);
+        }
+
+        let repo = self.module().stocktakes_repo(tenant_id)?;
+        if repo.get_by_id(id).await?.status != STATUS_OPEN {
+            return Err(StocktakesServiceError::UnprocessableEntry(
+                "Csak nyitott leltár hagyható jóvá!",
+            ));
+        }
+        let lines = repo.get_lines(id).await?;
+        if lines.iter().all(|line| line.counted_quantity.is_none()) {
+            return Err(StocktakesServiceError::UnprocessableEntry(
+                "A leltárban nincs számolt tétel!",
+            ));
+        }
+        if lines.iter().any(|line| {
+            line.variance
+                .as_ref()
+                .is_some_and(|variance| &line.quantity_available + variance < BigDecimal::zero())
+        }) {
+            return Err(StocktakesServiceError::UnprocessableEntry(
+                "A szabad készlet nem elegendő a csökkentéshez!",
+            ));
+        }
+        repo.approve(id, claims.sub())
+            .await?
+            .ok_or(StocktakesServiceError::UnprocessableEntry(
+                "Csak nyitott leltár hagyható jóvá!",
+            ))
+    }
+
+    async fn cancel(&self, id: Uuid) -> StocktakesServiceResult<Stocktake> {
+        self.module()
+            .stocktakes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(StocktakesServiceError::Unauthorized)?,
+            )?
+            .cancel(id)
+            .await?
+            .ok_or(StocktakesServiceError::UnprocessableEntry(
+                "Csak nyitott leltár vonható vissza!",
+            ))
+    }
+}
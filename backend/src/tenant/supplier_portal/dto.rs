@@ -0,0 +1,57 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateSupplierPortalToken {
+    pub supplier_id: Uuid,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub valid_days: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SupplierPortalTokenQuery {
+    pub supplier_id: Uuid,
+}
+
+/// The portal is reached without a session, the tenant is identified by the link given to
+/// the supplier and the supplier by the bearer token
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PortalQuery {
+    pub tenant_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PortalLineConfirmation {
+    pub purchase_order_line_id: Uuid,
+    pub expected_delivery_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PortalConfirmation {
+    pub purchase_order_id: Uuid,
+    pub supplier_reference: Option<String>,
+    pub expected_delivery_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub lines: Vec<PortalLineConfirmation>,
+}
@@ -0,0 +1,490 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::supplier_portal::SupplierPortalModuleInterface;
+use crate::tenant::supplier_portal::dto::{
+    CreateSupplierPortalToken, PortalConfirmation, PortalQuery, SupplierPortalTokenQuery,
+};
+use crate::tenant::supplier_portal::service::SupplierPortalService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum_extra::TypedHeader;
+use headers::{Authorization, authorization::Bearer};
+use std::sync::Arc;
+
+pub async fn list_tokens<M: SupplierPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(supplier_portal_module): State<Arc<M>>,
+    Query(payload): Query<SupplierPortalTokenQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), supplier_portal_module.clone());
+    let result = map_handler_err(
+        service.list_tokens(payload.supplier_id).await,
+        supplier_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        supplier_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_token<M: SupplierPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(supplier_portal_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateSupplierPortalToken>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), supplier_portal_module.clone());
+    let result = map_handler_err(
+        service.create_token(&payload).await,
+        supplier_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        supplier_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn revoke_token<M: SupplierPortalModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(supplier_portal_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), supplier_portal_module.clone());
+    let result = map_handler_err(
+        service.revoke_token(payload.uuid).await,
+        supplier_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        supplier_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn orders<M: SupplierPortalModuleInterface>(
+    State(supplier_portal_module): State<Arc<M>>,
+    Query(payload): Query<PortalQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+) -> HandlerResult {
+    let service = Service::new(None, supplier_portal_module.clone());
+    let result = map_handler_err(
+        service
+            .portal_orders(payload.tenant_id, bearer.token())
+            .await,
+        supplier_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        supplier_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn confirm<M: SupplierPortalModuleInterface>(
+    State(supplier_portal_module): State<Arc<M>>,
+    Query(query): Query<PortalQuery>,
+    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    ValidJson(payload): ValidJson<PortalConfirmation>,
+) -> HandlerResult {
+    let service = Service::new(None, supplier_portal_module.clone());
+    let result = map_handler_err(
+        service
+            .portal_confirm(query.tenant_id, bearer.token(), &payload)
+            .await,
+        supplier_portal_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        supplier_portal_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::crypto::hash_token;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::supplier_portal::model::{
+        IssuedSupplierPortalToken, PortalOrder, PortalOrderDetails, PortalOrderLine,
+        SCOPE_ORDERS_READ, SupplierPortalToken,
+    };
+    use crate::tenant::supplier_portal::{
+        self, repository::MockSupplierPortalRepository, tests::MockSupplierPortalModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, TimeDelta, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const SECRET: &str = "portal-secret";
+
+    fn module(repo: MockSupplierPortalRepository, tenant_id: Uuid) -> MockSupplierPortalModule {
+        let repo = Arc::new(repo);
+        let mut supplier_portal_module = MockSupplierPortalModule::new();
+        supplier_portal_module
+            .expect_supplier_portal_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        supplier_portal_module
+    }
+
+    fn app(supplier_portal_module: MockSupplierPortalModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(supplier_portal::routes::routes(Arc::new(
+                supplier_portal_module,
+            ))),
+        )
+    }
+
+    fn token(supplier_id: Uuid, scopes: &[&str]) -> SupplierPortalToken {
+        SupplierPortalToken {
+            id: Uuid::new_v4(),
+            supplier_id,
+            description: Some("Raktári rendelések".to_string()),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_at: Utc::now() + TimeDelta::days(30),
+            revoked_at: None,
+            last_used_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn order() -> PortalOrder {
+        PortalOrder {
+            id: Uuid::new_v4(),
+            order_number: "BR-2026-00012".to_string(),
+            warehouse: "Központi raktár".to_string(),
+            currency_code: "HUF".to_string(),
+            order_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            expected_delivery_date: None,
+            supplier_reference: None,
+            status: "sent".to_string(),
+            sent_at: Some(Utc::now()),
+            confirmed_at: None,
+        }
+    }
+
+    fn line(purchase_order_id: Uuid) -> PortalOrderLine {
+        PortalOrderLine {
+            id: Uuid::new_v4(),
+            purchase_order_id,
+            supplier_sku: Some("CS-12".to_string()),
+            description: "Csavar M6".to_string(),
+            quantity: BigDecimal::from(100),
+            unit_price: BigDecimal::from(25),
+            received_quantity: BigDecimal::from(0),
+            expected_delivery_date: None,
+            position: 1,
+        }
+    }
+
+    fn portal_request(method: &str, uri: String, body: Body) -> Request<Body> {
+        Request::builder()
+            .header("Authorization", format!("Bearer {SECRET}"))
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(body)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_token_returns_secret_and_stores_hash() {
+        let tenant_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+        let issued = token(supplier_id, &["orders.read", "orders.confirm"]);
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_insert_token()
+            .times(1)
+            .withf(move |input, token_hash, expires_at, _| {
+                input.supplier_id == supplier_id
+                    && input.scopes == vec!["orders.read", "orders.confirm"]
+                    && input.description.is_none()
+                    && token_hash.len() == 64
+                    && *expires_at > Utc::now() + TimeDelta::days(29)
+            })
+            .returning({
+                let issued = issued.clone();
+                move |_, _, _, _| Ok(issued.clone())
+            });
+
+        let mut supplier_portal_module = module(repo, tenant_id);
+        supplier_portal_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = app(supplier_portal_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("POST")
+                    .uri("/api/supplier_portal/tokens/create")
+                    .body(Body::from(
+                        json!({
+                            "supplier_id": supplier_id,
+                            "description": "  ",
+                            "scopes": ["orders.read", "orders.confirm", "orders.read"],
+                            "valid_days": 30
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: IssuedSupplierPortalToken =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body.token, issued);
+        assert_eq!(body.secret.len(), 48);
+    }
+
+    #[tokio::test]
+    async fn test_create_token_rejects_unknown_scope() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_insert_token().never();
+
+        let mut supplier_portal_module = module(repo, tenant_id);
+        supplier_portal_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = app(supplier_portal_module)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("POST")
+                    .uri("/api/supplier_portal/tokens/create")
+                    .body(Body::from(
+                        json!({
+                            "supplier_id": Uuid::new_v4(),
+                            "description": null,
+                            "scopes": ["orders.read", "invoices.read"],
+                            "valid_days": 30
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_portal_orders_lists_open_orders_of_token_supplier() {
+        let tenant_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+        let order = order();
+        let line = line(order.id);
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_authenticate()
+            .with(eq(hash_token(SECRET)))
+            .times(1)
+            .returning(move |_| Ok(Some(token(supplier_id, &[SCOPE_ORDERS_READ]))));
+        repo.expect_get_open_orders()
+            .with(eq(supplier_id))
+            .times(1)
+            .returning({
+                let order = order.clone();
+                move |_| Ok(vec![order.clone()])
+            });
+        repo.expect_get_order_lines()
+            .with(eq(vec![order.id]))
+            .times(1)
+            .returning({
+                let line = line.clone();
+                move |_| Ok(vec![line.clone()])
+            });
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(
+                "GET",
+                format!("/api/supplier_portal/orders?tenant_id={tenant_id}"),
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<PortalOrderDetails> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            body,
+            vec![PortalOrderDetails {
+                order,
+                lines: vec![line]
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_portal_rejects_invalid_token() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_authenticate().times(1).returning(|_| Ok(None));
+        repo.expect_get_open_orders().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(
+                "GET",
+                format!("/api/supplier_portal/orders?tenant_id={tenant_id}"),
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_portal_confirm_requires_confirm_scope() {
+        let tenant_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_authenticate()
+            .times(1)
+            .returning(move |_| Ok(Some(token(supplier_id, &[SCOPE_ORDERS_READ]))));
+        repo.expect_confirm().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(
+                "PUT",
+                format!("/api/supplier_portal/confirm?tenant_id={tenant_id}"),
+                Body::from(
+                    json!({
+                        "purchase_order_id": Uuid::new_v4(),
+                        "supplier_reference": "VR-881",
+                        "expected_delivery_date": "2026-10-20"
+                    })
+                    .to_string(),
+                ),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_portal_confirm_rejects_line_of_other_order() {
+        let tenant_id = Uuid::new_v4();
+        let supplier_id = Uuid::new_v4();
+        let order = order();
+        let line = line(order.id);
+
+        let mut repo = MockSupplierPortalRepository::new();
+        repo.expect_authenticate()
+            .times(1)
+            .returning(move |_| Ok(Some(token(supplier_id, &["orders.confirm"]))));
+        repo.expect_get_open_order()
+            .with(eq(supplier_id), eq(order.id))
+            .times(1)
+            .returning({
+                let order = order.clone();
+                move |_, _| Ok(order.clone())
+            });
+        repo.expect_get_order_lines()
+            .times(1)
+            .returning(move |_| Ok(vec![line.clone()]));
+        repo.expect_confirm().never();
+
+        let response = app(module(repo, tenant_id))
+            .oneshot(portal_request(
+                "PUT",
+                format!("/api/supplier_portal/confirm?tenant_id={tenant_id}"),
+                Body::from(
+                    json!({
+                        "purchase_order_id": order.id,
+                        "supplier_reference": null,
+                        "expected_delivery_date": "2026-10-20",
+                        "lines": [{
+                            "purchase_order_line_id": Uuid::new_v4(),
+                            "expected_delivery_date": "2026-10-22"
+                        }]
+                    })
+                    .to_string(),
+                ),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const SCOPE_ORDERS_READ: &str = "orders.read";
+pub const SCOPE_ORDERS_CONFIRM: &str = "orders.confirm";
+
+pub const SCOPES: [&str; 2] = [SCOPE_ORDERS_READ, SCOPE_ORDERS_CONFIRM];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SupplierPortalToken {
+    pub id: Uuid,
+    pub supplier_id: Uuid,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SupplierPortalToken {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
+/// Returned only when the token is issued, the secret itself is not stored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IssuedSupplierPortalToken {
+    #[serde(flatten)]
+    pub token: SupplierPortalToken,
+    pub secret: String,
+}
+
+/// Purchase order as shown to the supplier, without internal notes
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PortalOrder {
+    pub id: Uuid,
+    pub order_number: String,
+    pub warehouse: String,
+    pub currency_code: String,
+    pub order_date: NaiveDate,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub supplier_reference: Option<String>,
+    pub status: String,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct PortalOrderLine {
+    pub id: Uuid,
+    pub purchase_order_id: Uuid,
+    pub supplier_sku: Option<String>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub received_quantity: BigDecimal,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PortalOrderDetails {
+    #[serde(flatten)]
+    pub order: PortalOrder,
+    pub lines: Vec<PortalOrderLine>,
+}
@@ -0,0 +1,279 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::supplier_portal::dto::{CreateSupplierPortalToken, PortalConfirmation};
+use crate::tenant::supplier_portal::model::{PortalOrder, PortalOrderLine, SupplierPortalToken};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SupplierPortalRepository: Send + Sync {
+    async fn get_tokens(&self, supplier_id: Uuid) -> RepositoryResult<Vec<SupplierPortalToken>>;
+    async fn insert_token(
+        &self,
+        input: &CreateSupplierPortalToken,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        sub: Uuid,
+    ) -> RepositoryResult<SupplierPortalToken>;
+    async fn revoke_token(&self, id: Uuid) -> RepositoryResult<SupplierPortalToken>;
+    async fn authenticate(&self, token_hash: &str)
+    -> RepositoryResult<Option<SupplierPortalToken>>;
+    async fn get_open_orders(&self, supplier_id: Uuid) -> RepositoryResult<Vec<PortalOrder>>;
+    async fn get_open_order(&self, supplier_id: Uuid, id: Uuid) -> RepositoryResult<PortalOrder>;
+    async fn get_order_lines(
+        &self,
+        purchase_order_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<PortalOrderLine>>;
+    async fn confirm(
+        &self,
+        supplier_id: Uuid,
+        input: &PortalConfirmation,
+    ) -> RepositoryResult<PortalOrder>;
+}
+
+const TOKEN_COLUMNS: &str = r#"
+    id,
+    supplier_id,
+    description,
+    scopes,
+    expires_at,
+    revoked_at,
+    last_used_at,
+    created_by_id,
+    created_at
+"#;
+
+// NOTE: only sent orders that are still waiting for delivery are visible to the supplier
+const OPEN_ORDERS: &str = r#"
+    SELECT purchase_orders.id,
+           purchase_orders.order_number,
+           warehouses.name AS warehouse,
+           purchase_orders.currency_code,
+           purchase_orders.order_date,
+           purchase_orders.expected_delivery_date,
+           purchase_orders.supplier_reference,
+           purchase_orders.status,
+           purchase_orders.sent_at,
+           purchase_orders.confirmed_at
+    FROM purchase_orders
+    JOIN warehouses ON purchase_orders.warehouse_id = warehouses.id
+    WHERE purchase_orders.supplier_id = $1
+        AND purchase_orders.status IN ('sent', 'partially_received')
+        AND purchase_orders.deleted_at IS NULL
+"#;
+
+#[async_trait]
+impl SupplierPortalRepository for PgPool {
+    async fn get_tokens(&self, supplier_id: Uuid) -> RepositoryResult<Vec<SupplierPortalToken>> {
+        Ok(
+            sqlx::query_as::<_, SupplierPortalToken>(AssertSqlSafe(format!(
+                r#"
+            SELECT {TOKEN_COLUMNS}
+            FROM supplier_portal_tokens
+            WHERE supplier_id = $1
+            ORDER BY created_at DESC
+            "# // Security: constant
+            )))
+            .bind(supplier_id)
+            .fetch_all(self)
+            .await?,
+        )
+    }
+
+    async fn insert_token(
+        &self,
+        input: &CreateSupplierPortalToken,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        sub: Uuid,
+    ) -> RepositoryResult<SupplierPortalToken> {
+        Ok(
+            sqlx::query_as::<_, SupplierPortalToken>(AssertSqlSafe(format!(
+                r#"
+            INSERT INTO supplier_portal_tokens (supplier_id, token_hash, description, scopes,
+                                                expires_at, created_by_id)
+            SELECT id, $2, $3, $4, $5, $6
+            FROM suppliers
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(input.supplier_id)
+            .bind(token_hash)
+            .bind(&input.description)
+            .bind(&input.scopes)
+            .bind(expires_at)
+            .bind(sub)
+            .fetch_one(self)
+            .await?,
+        )
+    }
+
+    async fn revoke_token(&self, id: Uuid) -> RepositoryResult<SupplierPortalToken> {
+        Ok(
+            sqlx::query_as::<_, SupplierPortalToken>(AssertSqlSafe(format!(
+                r#"
+            UPDATE supplier_portal_tokens
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(id)
+            .fetch_one(self)
+            .await?,
+        )
+    }
+
+    async fn authenticate(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<SupplierPortalToken>> {
+        Ok(
+            sqlx::query_as::<_, SupplierPortalToken>(AssertSqlSafe(format!(
+                r#"
+            UPDATE supplier_portal_tokens
+            SET last_used_at = NOW()
+            WHERE token_hash = $1
+                AND revoked_at IS NULL
+                AND expires_at > NOW()
+                AND EXISTS (
+                    SELECT 1
+                    FROM suppliers
+                    WHERE suppliers.id = supplier_portal_tokens.supplier_id
+                        AND suppliers.status = 'active'
+                        AND suppliers.deleted_at IS NULL
+                )
+            RETURNING {TOKEN_COLUMNS}
+            "# // Security: constant
+            )))
+            .bind(token_hash)
+            .fetch_optional(self)
+            .await?,
+        )
+    }
+
+    async fn get_open_orders(&self, supplier_id: Uuid) -> RepositoryResult<Vec<PortalOrder>> {
+        Ok(sqlx::query_as::<_, PortalOrder>(AssertSqlSafe(format!(
+            "{OPEN_ORDERS} ORDER BY purchase_orders.order_date, purchase_orders.order_number" // Security: constant
+        )))
+        .bind(supplier_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_open_order(&self, supplier_id: Uuid, id: Uuid) -> RepositoryResult<PortalOrder> {
+        Ok(sqlx::query_as::<_, PortalOrder>(AssertSqlSafe(format!(
+            "{OPEN_ORDERS} AND purchase_orders.id = $2" // Security: constant
+        )))
+        .bind(supplier_id)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_order_lines(
+        &self,
+        purchase_order_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<PortalOrderLine>> {
+        Ok(sqlx::query_as::<_, PortalOrderLine>(
+            r#"
+            SELECT purchase_order_lines.id,
+                   purchase_order_lines.purchase_order_id,
+                   purchase_order_lines.supplier_sku,
+                   purchase_order_lines.description,
+                   purchase_order_lines.quantity,
+                   purchase_order_lines.unit_price,
+                   COALESCE(received.quantity, 0) AS received_quantity,
+                   purchase_order_lines.expected_delivery_date,
+                   purchase_order_lines.position
+            FROM purchase_order_lines
+            LEFT JOIN LATERAL (
+                SELECT SUM(goods_receipt_lines.quantity) AS quantity
+                FROM goods_receipt_lines
+                WHERE goods_receipt_lines.purchase_order_line_id = purchase_order_lines.id
+            ) AS received ON true
+            WHERE purchase_order_lines.purchase_order_id = ANY($1)
+            ORDER BY purchase_order_lines.purchase_order_id, purchase_order_lines.position
+            "#,
+        )
+        .bind(purchase_order_ids)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn confirm(
+        &self,
+        supplier_id: Uuid,
+        input: &PortalConfirmation,
+    ) -> RepositoryResult<PortalOrder> {
+        let mut tx = self.begin().await?;
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE purchase_orders
+            SET supplier_reference = COALESCE($1, supplier_reference),
+                expected_delivery_date = COALESCE($2, expected_delivery_date),
+                confirmed_at = NOW()
+            WHERE id = $3
+                AND supplier_id = $4
+                AND status IN ('sent', 'partially_received')
+                AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(&input.supplier_reference)
+        .bind(input.expected_delivery_date)
+        .bind(input.purchase_order_id)
+        .bind(supplier_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        for line in &input.lines {
+            sqlx::query(
+                r#"
+                UPDATE purchase_order_lines
+                SET expected_delivery_date = $1
+                WHERE id = $2
+                    AND purchase_order_id = $3
+                "#,
+            )
+            .bind(line.expected_delivery_date)
+            .bind(line.purchase_order_line_id)
+            .bind(input.purchase_order_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        let order = sqlx::query_as::<_, PortalOrder>(AssertSqlSafe(format!(
+            "{OPEN_ORDERS} AND purchase_orders.id = $2" // Security: constant
+        )))
+        .bind(supplier_id)
+        .bind(input.purchase_order_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(order)
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::SupplierPortalModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: SupplierPortalModuleInterface>(supplier_portal_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/supplier_portal",
+        Router::new()
+            .route("/tokens/list", get(handler::list_tokens::<M>))
+            .route("/tokens/create", post(handler::create_token::<M>))
+            .route("/tokens/revoke", put(handler::revoke_token::<M>))
+            .layer(from_fn_with_state(
+                supplier_portal_module.clone(),
+                require_auth,
+            ))
+            // NOTE: reached by suppliers with a portal token instead of a session
+            .route("/orders", get(handler::orders::<M>))
+            .route("/confirm", put(handler::confirm::<M>))
+            .with_state(supplier_portal_module),
+    )
+}
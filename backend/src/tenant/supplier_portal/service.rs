@@ -0,0 +1,365 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::crypto::hash_token;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::common::utils::generate_string_csprng;
+use crate::tenant::supplier_portal::SupplierPortalModuleInterface;
+use crate::tenant::supplier_portal::dto::{CreateSupplierPortalToken, PortalConfirmation};
+use crate::tenant::supplier_portal::model::{
+    IssuedSupplierPortalToken, PortalOrder, PortalOrderDetails, SCOPE_ORDERS_CONFIRM,
+    SCOPE_ORDERS_READ, SCOPES, SupplierPortalToken,
+};
+use crate::tenant::supplier_portal::repository::SupplierPortalRepository;
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const TOKEN_LENGTH: usize = 48;
+const MAX_VALID_DAYS: i64 = 365;
+
+#[derive(Debug, Error)]
+pub enum SupplierPortalServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Érvénytelen vagy lejárt hozzáférési kulcs!")]
+    InvalidToken,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("rng error")]
+    RngError,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for SupplierPortalServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => SupplierPortalServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<SupplierPortalServiceError> for AppError {
+    fn from(value: SupplierPortalServiceError) -> Self {
+        match value {
+            SupplierPortalServiceError::Unauthorized | SupplierPortalServiceError::InvalidToken => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNAUTHORIZED,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": value.to_string()}),
+                )
+            }
+            SupplierPortalServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            SupplierPortalServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            SupplierPortalServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type SupplierPortalServiceResult<T> = Result<T, SupplierPortalServiceError>;
+
+fn validate_token_request(
+    payload: &CreateSupplierPortalToken,
+) -> SupplierPortalServiceResult<CreateSupplierPortalToken> {
+    let description = payload
+        .description
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > 255)
+    {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "A megjegyzés legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let mut seen = HashSet::new();
+    let scopes: Vec<String> = payload
+        .scopes
+        .iter()
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| seen.insert(scope.clone()))
+        .collect();
+    if scopes.is_empty() || scopes.iter().any(|scope| !SCOPES.contains(&scope.as_str())) {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "Legalább egy érvényes jogosultságot meg kell adni!",
+        ));
+    }
+    if !(1..=MAX_VALID_DAYS).contains(&payload.valid_days) {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "Az érvényesség 1 és 365 nap között lehet!",
+        ));
+    }
+    Ok(CreateSupplierPortalToken {
+        supplier_id: payload.supplier_id,
+        description,
+        scopes,
+        valid_days: payload.valid_days,
+    })
+}
+
+fn validate_confirmation(
+    order: &PortalOrder,
+    line_ids: &[Uuid],
+    payload: &PortalConfirmation,
+) -> SupplierPortalServiceResult<PortalConfirmation> {
+    let supplier_reference = payload
+        .supplier_reference
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if supplier_reference
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > 100)
+    {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "A beszállítói hivatkozás legfeljebb 100 karakter lehet!",
+        ));
+    }
+    if payload
+        .expected_delivery_date
+        .into_iter()
+        .chain(payload.lines.iter().map(|line| line.expected_delivery_date))
+        .any(|date| date < order.order_date)
+    {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "A szállítási dátum nem lehet korábbi a rendelés dátumánál!",
+        ));
+    }
+    if payload
+        .lines
+        .iter()
+        .any(|line| !line_ids.contains(&line.purchase_order_line_id))
+    {
+        return Err(SupplierPortalServiceError::UnprocessableEntry(
+            "A tétel nem tartozik a rendeléshez!",
+        ));
+    }
+    Ok(PortalConfirmation {
+        supplier_reference,
+        ..payload.clone()
+    })
+}
+
+pub trait SupplierPortalService {
+    fn list_tokens(
+        &self,
+        supplier_id: Uuid,
+    ) -> impl Future<Output = SupplierPortalServiceResult<Vec<SupplierPortalToken>>> + Send;
+    fn create_token(
+        &self,
+        payload: &CreateSupplierPortalToken,
+    ) -> impl Future<Output = SupplierPortalServiceResult<IssuedSupplierPortalToken>> + Send;
+    fn revoke_token(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = SupplierPortalServiceResult<SupplierPortalToken>> + Send;
+    fn portal_orders(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> impl Future<Output = SupplierPortalServiceResult<Vec<PortalOrderDetails>>> + Send;
+    fn portal_confirm(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        payload: &PortalConfirmation,
+    ) -> impl Future<Output = SupplierPortalServiceResult<PortalOrderDetails>> + Send;
+    fn repo(&self) -> SupplierPortalServiceResult<Arc<dyn SupplierPortalRepository + Send + Sync>>;
+    fn authenticate(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        scope: &str,
+    ) -> impl Future<
+        Output = SupplierPortalServiceResult<(
+            Arc<dyn SupplierPortalRepository + Send + Sync>,
+            SupplierPortalToken,
+        )>,
+    > + Send;
+}
+
+impl<'a, T> SupplierPortalService for Service<'a, T>
+where
+    T: SupplierPortalModuleInterface,
+{
+    fn repo(&self) -> SupplierPortalServiceResult<Arc<dyn SupplierPortalRepository + Send + Sync>> {
+        Ok(self.module().supplier_portal_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(SupplierPortalServiceError::Unauthorized)?,
+        )?)
+    }
+
+    // NOTE: reached by the supplier without a session, an unknown tenant is reported the same
+    // way as an unknown token so the portal does not reveal which tenants exist
+    async fn authenticate(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        scope: &str,
+    ) -> SupplierPortalServiceResult<(
+        Arc<dyn SupplierPortalRepository + Send + Sync>,
+        SupplierPortalToken,
+    )> {
+        let repo = self
+            .module()
+            .supplier_portal_repo(tenant_id)
+            .map_err(|error| match error {
+                RepositoryError::TenantPoolNotFound => SupplierPortalServiceError::InvalidToken,
+                error => error.into(),
+            })?;
+        let token = repo
+            .authenticate(&hash_token(token))
+            .await?
+            .ok_or(SupplierPortalServiceError::InvalidToken)?;
+        if !token.has_scope(scope) {
+            return Err(SupplierPortalServiceError::Forbidden);
+        }
+        Ok((repo, token))
+    }
+
+    async fn list_tokens(
+        &self,
+        supplier_id: Uuid,
+    ) -> SupplierPortalServiceResult<Vec<SupplierPortalToken>> {
+        Ok(self.repo()?.get_tokens(supplier_id).await?)
+    }
+
+    async fn create_token(
+        &self,
+        payload: &CreateSupplierPortalToken,
+    ) -> SupplierPortalServiceResult<IssuedSupplierPortalToken> {
+        let input = validate_token_request(payload)?;
+        let secret = generate_string_csprng(TOKEN_LENGTH)
+            .map_err(|_| SupplierPortalServiceError::RngError)?;
+        let token = self
+            .repo()?
+            .insert_token(
+                &input,
+                &hash_token(&secret),
+                Utc::now() + Duration::days(input.valid_days),
+                self.claims()?.sub(),
+            )
+            .await?;
+        Ok(IssuedSupplierPortalToken { token, secret })
+    }
+
+    async fn revoke_token(&self, id: Uuid) -> SupplierPortalServiceResult<SupplierPortalToken> {
+        Ok(self.repo()?.revoke_token(id).await?)
+    }
+
+    async fn portal_orders(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> SupplierPortalServiceResult<Vec<PortalOrderDetails>> {
+        let (repo, token) = self
+            .authenticate(tenant_id, token, SCOPE_ORDERS_READ)
+            .await?;
+        let orders = repo.get_open_orders(token.supplier_id).await?;
+        let lines = repo
+            .get_order_lines(orders.iter().map(|order| order.id).collect())
+            .await?;
+        Ok(orders
+            .into_iter()
+            .map(|order| PortalOrderDetails {
+                lines: lines
+                    .iter()
+                    .filter(|line| line.purchase_order_id == order.id)
+                    .cloned()
+                    .collect(),
+                order,
+            })
+            .collect())
+    }
+
+    async fn portal_confirm(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+        payload: &PortalConfirmation,
+    ) -> SupplierPortalServiceResult<PortalOrderDetails> {
+        let (repo, token) = self
+            .authenticate(tenant_id, token, SCOPE_ORDERS_CONFIRM)
+            .await?;
+        let order = repo
+            .get_open_order(token.supplier_id, payload.purchase_order_id)
+            .await?;
+        let line_ids: Vec<Uuid> = repo
+            .get_order_lines(vec![order.id])
+            .await?
+            .iter()
+            .map(|line| line.id)
+            .collect();
+        let input = validate_confirmation(&order, &line_ids, payload)?;
+        let order = repo.confirm(token.supplier_id, &input).await?;
+        let lines = repo.get_order_lines(vec![order.id]).await?;
+        Ok(PortalOrderDetails { order, lines })
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SupplierInput {
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub tax_number: Option<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ProductSupplierInput {
+    pub product_id: Uuid,
+    pub supplier_id: Uuid,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+    #[serde(default)]
+    pub is_preferred: bool,
+}
+
+/// Either a single product, or (when `product_id` is omitted) every product on the
+/// reorder suggestion list computed with the same parameters as `/inventory/reorder_suggestions`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PriceComparisonQuery {
+    pub product_id: Option<Uuid>,
+    pub warehouse_id: Option<Uuid>,
+    pub window_days: Option<i64>,
+    pub cover_days: Option<i64>,
+}
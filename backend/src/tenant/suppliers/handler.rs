@@ -0,0 +1,572 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::suppliers::SuppliersModuleInterface;
+use crate::tenant::suppliers::dto::{PriceComparisonQuery, SupplierInput};
+use crate::tenant::suppliers::service::SuppliersService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let result = map_handler_err(service.get(payload.uuid).await, suppliers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        suppliers_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        suppliers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn select_list<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let result = map_handler_err(
+        service.get_select_list_items().await,
+        suppliers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SupplierInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let result = map_handler_err(service.create(&payload).await, suppliers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<SupplierInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let result = map_handler_err(service.update(&payload).await, suppliers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    map_handler_err(service.delete(payload.uuid).await, suppliers_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A beszállító törlése sikeresen megtörtént",
+            ))
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn price_comparison<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    Query(payload): Query<PriceComparisonQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let result = map_handler_err(
+        service.get_price_comparison(&payload).await,
+        suppliers_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        suppliers_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn export_price_comparison<M: SuppliersModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(suppliers_module): State<Arc<M>>,
+    Query(payload): Query<PriceComparisonQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), suppliers_module.clone());
+    let csv = map_handler_err(
+        service.export_price_comparison(&payload).await,
+        suppliers_module.clone(),
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        r#"attachment; filename="beszallitoi_arak.csv""#.parse().unwrap(),
+    );
+    Ok((StatusCode::OK, headers, csv).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::inventory::model::InventoryConsumption;
+    use crate::tenant::inventory::repository::MockInventoryRepository;
+    use crate::tenant::suppliers::model::{ProductPriceComparison, Supplier, SupplierOffer};
+    use crate::tenant::suppliers::{
+        self, repository::MockSuppliersRepository, tests::MockSuppliersModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockSuppliersRepository, active_tenant_id: Uuid) -> Router {
+        app_with_inventory(repo, MockInventoryRepository::new(), active_tenant_id)
+    }
+
+    fn app_with_inventory(
+        repo: MockSuppliersRepository,
+        inventory_repo: MockInventoryRepository,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let inventory_repo = Arc::new(inventory_repo);
+        let mut suppliers_module = MockSuppliersModule::new();
+        suppliers_module
+            .expect_suppliers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        suppliers_module
+            .expect_inventory_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(inventory_repo.clone()));
+        suppliers_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(suppliers::routes::routes(Arc::new(suppliers_module))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn get_request(uri: &str, active_tenant_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn offer(
+        product_id: Uuid,
+        supplier: &str,
+        purchase_price: Option<i32>,
+        lead_time_days: Option<i32>,
+    ) -> SupplierOffer {
+        SupplierOffer {
+            product_id,
+            product: "Csavar M8".to_string(),
+            supplier_id: Uuid::new_v4(),
+            supplier: supplier.to_string(),
+            supplier_sku: None,
+            purchase_price: purchase_price.map(BigDecimal::from),
+            currency_code: purchase_price.map(|_| "HUF".to_string()),
+            lead_time_days,
+            is_preferred: false,
+            order_count: 0,
+            last_order_number: None,
+            last_order_date: None,
+            last_unit_price: None,
+            last_currency_code: None,
+            last_quantity: None,
+            is_cheapest: false,
+            is_fastest: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let supplier = Supplier {
+            id: Uuid::new_v4(),
+            name: "Csavar Nagyker Kft.".to_string(),
+            contact_name: None,
+            email: Some("rendeles@csavar.hu".to_string()),
+            phone_number: None,
+            tax_number: Some("12345678-2-41".to_string()),
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(|input, _| {
+                input.name == "Csavar Nagyker Kft."
+                    && input.contact_name.is_none()
+                    && input.email.as_deref() == Some("rendeles@csavar.hu")
+            })
+            .returning({
+                let supplier = supplier.clone();
+                move |_, _| Ok(supplier.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/suppliers/create",
+                active_tenant_id,
+                json!({
+                    "name": " Csavar Nagyker Kft. ",
+                    "contact_name": " ",
+                    "email": "rendeles@csavar.hu",
+                    "phone_number": null,
+                    "tax_number": "12345678-2-41",
+                    "status": "active"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": supplier})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_invalid_email() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/suppliers/create",
+                active_tenant_id,
+                json!({
+                    "name": "Csavar Nagyker Kft.",
+                    "contact_name": null,
+                    "email": "rendeles",
+                    "phone_number": null,
+                    "tax_number": null,
+                    "status": "active"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_price_comparison_for_product() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_get_supplier_offers()
+            .times(1)
+            .with(eq(vec![product_id]))
+            .returning(move |_| {
+                let mut history_only = offer(product_id, "Fémáru Bt.", None, None);
+                history_only.order_count = 2;
+                history_only.last_order_number = Some("BR-2026-00004".to_string());
+                history_only.last_order_date = NaiveDate::from_ymd_opt(2026, 9, 1);
+                history_only.last_unit_price = Some(BigDecimal::from(110));
+                history_only.last_currency_code = Some("HUF".to_string());
+                history_only.last_quantity = Some(BigDecimal::from(500));
+                Ok(vec![
+                    offer(product_id, "Csavar Nagyker Kft.", Some(120), Some(2)),
+                    offer(product_id, "Import Kft.", Some(95), Some(20)),
+                    history_only,
+                ])
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(get_request(
+                &format!("/api/suppliers/price_comparison?product_id={product_id}"),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let comparisons: Vec<ProductPriceComparison> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].suggested_quantity, None);
+        let flags: Vec<_> = comparisons[0]
+            .offers
+            .iter()
+            .map(|offer| (offer.supplier.as_str(), offer.is_cheapest, offer.is_fastest))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("Csavar Nagyker Kft.", false, true),
+                ("Import Kft.", true, false),
+                ("Fémáru Bt.", false, false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_price_comparison_for_reorder_suggestions() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let row = move |warehouse: &str, available: i32| InventoryConsumption {
+            inventory_id: Uuid::new_v4(),
+            product_id,
+            product: "Csavar M8".to_string(),
+            warehouse_id: Uuid::new_v4(),
+            warehouse: warehouse.to_string(),
+            quantity_available: available.into(),
+            minimum_stock: Some(10.into()),
+            maximum_stock: None,
+            consumed: 60.into(),
+            supplier_id: None,
+            supplier: None,
+            supplier_sku: None,
+            purchase_price: None,
+            currency_code: None,
+            lead_time_days: None,
+        };
+        let mut inventory_repo = MockInventoryRepository::new();
+        inventory_repo
+            .expect_get_consumption()
+            .times(1)
+            .returning(move |_, _| Ok(vec![row("Központi", 8), row("Debrecen", 28)]));
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_get_supplier_offers()
+            .times(1)
+            .with(eq(vec![product_id]))
+            .returning(move |_| {
+                Ok(vec![offer(
+                    product_id,
+                    "Csavar Nagyker Kft.",
+                    Some(120),
+                    Some(2),
+                )])
+            });
+
+        let response = app_with_inventory(repo, inventory_repo, active_tenant_id)
+            .oneshot(get_request(
+                "/api/suppliers/price_comparison?window_days=30&cover_days=14",
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let comparisons: Vec<ProductPriceComparison> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(
+            comparisons[0].suggested_quantity,
+            Some(BigDecimal::from(40))
+        );
+        assert!(comparisons[0].offers[0].is_cheapest);
+        assert!(comparisons[0].offers[0].is_fastest);
+    }
+
+    #[tokio::test]
+    async fn test_price_comparison_export() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_get_supplier_offers()
+            .times(1)
+            .returning(move |_| {
+                Ok(vec![offer(
+                    product_id,
+                    "Csavar Nagyker Kft.",
+                    Some(120),
+                    Some(2),
+                )])
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(get_request(
+                &format!("/api/suppliers/price_comparison/export?product_id={product_id}"),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        let mut lines = csv.lines();
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("product,suggested_quantity,supplier,")
+        );
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("Csavar M8,,Csavar Nagyker Kft.,,120,HUF,2,false,true,true,0,")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_price_comparison_invalid_window() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockSuppliersRepository::new();
+        repo.expect_get_supplier_offers().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(get_request(
+                "/api/suppliers/price_comparison?window_days=0",
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
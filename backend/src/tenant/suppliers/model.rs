@@ -0,0 +1,135 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Supplier {
+    pub id: Uuid,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub tax_number: Option<String>,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ProductSupplier {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub supplier_id: Uuid,
+    pub supplier: String,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+    pub is_preferred: bool,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One supplier's offer for a product: the agreed price list entry (if any) together with
+/// the last non-draft purchase order line ordered from that supplier.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SupplierOffer {
+    pub product_id: Uuid,
+    pub product: String,
+    pub supplier_id: Uuid,
+    pub supplier: String,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+    pub is_preferred: bool,
+    pub order_count: i64,
+    pub last_order_number: Option<String>,
+    pub last_order_date: Option<NaiveDate>,
+    pub last_unit_price: Option<BigDecimal>,
+    pub last_currency_code: Option<String>,
+    pub last_quantity: Option<BigDecimal>,
+    #[sqlx(skip)]
+    pub is_cheapest: bool,
+    #[sqlx(skip)]
+    pub is_fastest: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductPriceComparison {
+    pub product_id: Uuid,
+    pub product: String,
+    pub suggested_quantity: Option<BigDecimal>,
+    pub offers: Vec<SupplierOffer>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PriceComparisonCsvRow {
+    pub product: String,
+    pub suggested_quantity: Option<BigDecimal>,
+    pub supplier: String,
+    pub supplier_sku: Option<String>,
+    pub purchase_price: Option<BigDecimal>,
+    pub currency_code: Option<String>,
+    pub lead_time_days: Option<i32>,
+    pub is_preferred: bool,
+    pub is_cheapest: bool,
+    pub is_fastest: bool,
+    pub order_count: i64,
+    pub last_order_number: Option<String>,
+    pub last_order_date: Option<NaiveDate>,
+    pub last_unit_price: Option<BigDecimal>,
+    pub last_currency_code: Option<String>,
+    pub last_quantity: Option<BigDecimal>,
+}
+
+impl ProductPriceComparison {
+    /// Flattens the comparison into one CSV row per offer.
+    pub fn csv_rows(&self) -> Vec<PriceComparisonCsvRow> {
+        self.offers
+            .iter()
+            .map(|offer| PriceComparisonCsvRow {
+                product: self.product.clone(),
+                suggested_quantity: self.suggested_quantity.clone(),
+                supplier: offer.supplier.clone(),
+                supplier_sku: offer.supplier_sku.clone(),
+                purchase_price: offer.purchase_price.clone(),
+                currency_code: offer.currency_code.clone(),
+                lead_time_days: offer.lead_time_days,
+                is_preferred: offer.is_preferred,
+                is_cheapest: offer.is_cheapest,
+                is_fastest: offer.is_fastest,
+                order_count: offer.order_count,
+                last_order_number: offer.last_order_number.clone(),
+                last_order_date: offer.last_order_date,
+                last_unit_price: offer.last_unit_price.clone(),
+                last_currency_code: offer.last_currency_code.clone(),
+                last_quantity: offer.last_quantity.clone(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,353 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::model::SelectOption;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::suppliers::dto::{ProductSupplierInput, SupplierInput};
+use crate::tenant::suppliers::model::{ProductSupplier, Supplier, SupplierOffer};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SuppliersRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Supplier>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Supplier>)>;
+    async fn get_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>>;
+    async fn insert(&self, input: &SupplierInput, sub: Uuid) -> RepositoryResult<Supplier>;
+    async fn update(&self, id: Uuid, input: &SupplierInput) -> RepositoryResult<Supplier>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_product_suppliers(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductSupplier>>;
+    async fn set_product_supplier(
+        &self,
+        input: &ProductSupplierInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ProductSupplier>;
+    async fn delete_product_supplier(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_supplier_offers(
+        &self,
+        product_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<SupplierOffer>>;
+}
+
+const PRODUCT_SUPPLIER_COLUMNS: &str = r#"
+    product_suppliers.id,
+    product_suppliers.product_id,
+    product_suppliers.supplier_id,
+    suppliers.name as supplier,
+    product_suppliers.supplier_sku,
+    product_suppliers.purchase_price,
+    product_suppliers.currency_code,
+    product_suppliers.lead_time_days,
+    product_suppliers.is_preferred,
+    product_suppliers.created_by_id,
+    product_suppliers.created_at,
+    product_suppliers.updated_at
+"#;
+
+#[async_trait]
+impl SuppliersRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Supplier> {
+        Ok(sqlx::query_as::<_, Supplier>(
+            "SELECT * FROM suppliers WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<Supplier>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM suppliers WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let suppliers = sqlx::query_as::<_, Supplier>(
+            r#"
+            SELECT *
+            FROM suppliers
+            WHERE deleted_at IS NULL
+            ORDER BY name
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            suppliers,
+        ))
+    }
+
+    async fn get_select_list_items(&self) -> RepositoryResult<Vec<SelectOption>> {
+        Ok(sqlx::query_as::<_, SelectOption>(
+            r#"
+            SELECT id::VARCHAR as value, name as title
+            FROM suppliers
+            WHERE status = 'active'
+                AND deleted_at IS NULL
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &SupplierInput, sub: Uuid) -> RepositoryResult<Supplier> {
+        Ok(sqlx::query_as::<_, Supplier>(
+            r#"
+            INSERT INTO suppliers (name, contact_name, email, phone_number, tax_number, status,
+                                   created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.contact_name)
+        .bind(&input.email)
+        .bind(&input.phone_number)
+        .bind(&input.tax_number)
+        .bind(&input.status)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(&self, id: Uuid, input: &SupplierInput) -> RepositoryResult<Supplier> {
+        Ok(sqlx::query_as::<_, Supplier>(
+            r#"
+            UPDATE suppliers
+            SET name = $1,
+                contact_name = $2,
+                email = $3,
+                phone_number = $4,
+                tax_number = $5,
+                status = $6
+            WHERE id = $7
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.contact_name)
+        .bind(&input.email)
+        .bind(&input.phone_number)
+        .bind(&input.tax_number)
+        .bind(&input.status)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE suppliers
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_product_suppliers(
+        &self,
+        product_id: Uuid,
+    ) -> RepositoryResult<Vec<ProductSupplier>> {
+        Ok(sqlx::query_as::<_, ProductSupplier>(AssertSqlSafe(format!(
+            r#"
+            SELECT {PRODUCT_SUPPLIER_COLUMNS}
+            FROM product_suppliers
+            JOIN suppliers ON product_suppliers.supplier_id = suppliers.id
+            WHERE product_suppliers.product_id = $1
+                AND suppliers.deleted_at IS NULL
+            ORDER BY product_suppliers.is_preferred DESC, suppliers.name
+            "#
+        )))
+        .bind(product_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_product_supplier(
+        &self,
+        input: &ProductSupplierInput,
+        sub: Uuid,
+    ) -> RepositoryResult<ProductSupplier> {
+        let mut tx = self.begin().await?;
+        if input.is_preferred {
+            sqlx::query(
+                r#"
+                UPDATE product_suppliers
+                SET is_preferred = false
+                WHERE product_id = $1
+                    AND supplier_id <> $2
+                    AND is_preferred
+                "#,
+            )
+            .bind(input.product_id)
+            .bind(input.supplier_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        let product_supplier = sqlx::query_as::<_, ProductSupplier>(AssertSqlSafe(format!(
+            r#"
+            WITH upserted AS (
+                INSERT INTO product_suppliers (product_id, supplier_id, supplier_sku, purchase_price,
+                                               currency_code, lead_time_days, is_preferred,
+                                               created_by_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (product_id, supplier_id) DO UPDATE
+                    SET supplier_sku = EXCLUDED.supplier_sku,
+                        purchase_price = EXCLUDED.purchase_price,
+                        currency_code = EXCLUDED.currency_code,
+                        lead_time_days = EXCLUDED.lead_time_days,
+                        is_preferred = EXCLUDED.is_preferred
+                RETURNING *
+            )
+            SELECT {PRODUCT_SUPPLIER_COLUMNS}
+            FROM upserted product_suppliers
+            JOIN suppliers ON product_suppliers.supplier_id = suppliers.id
+            "#
+        )))
+        .bind(input.product_id)
+        .bind(input.supplier_id)
+        .bind(&input.supplier_sku)
+        .bind(&input.purchase_price)
+        .bind(&input.currency_code)
+        .bind(input.lead_time_days)
+        .bind(input.is_preferred)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(product_supplier)
+    }
+
+    async fn delete_product_supplier(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM product_suppliers WHERE id = $1")
+            .bind(id)
+            .execute(self)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_supplier_offers(
+        &self,
+        product_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<SupplierOffer>> {
+        // Suppliers without a price list entry are still offered when the product has
+        // already been ordered from them.
+        Ok(sqlx::query_as::<_, SupplierOffer>(
+            r#"
+            WITH purchases AS (
+                SELECT purchase_order_lines.product_id,
+                       purchase_orders.supplier_id,
+                       purchase_orders.order_number,
+                       purchase_orders.order_date,
+                       purchase_orders.currency_code,
+                       purchase_orders.created_at,
+                       purchase_order_lines.unit_price,
+                       purchase_order_lines.quantity
+                FROM purchase_order_lines
+                JOIN purchase_orders ON purchase_order_lines.purchase_order_id = purchase_orders.id
+                WHERE purchase_order_lines.product_id = ANY($1)
+                    AND purchase_orders.status <> 'draft'
+                    AND purchase_orders.deleted_at IS NULL
+            ),
+            candidates AS (
+                SELECT product_id, supplier_id FROM product_suppliers WHERE product_id = ANY($1)
+                UNION
+                SELECT product_id, supplier_id FROM purchases
+            )
+            SELECT candidates.product_id,
+                   products.name as product,
+                   candidates.supplier_id,
+                   suppliers.name as supplier,
+                   product_suppliers.supplier_sku,
+                   product_suppliers.purchase_price,
+                   product_suppliers.currency_code,
+                   product_suppliers.lead_time_days,
+                   COALESCE(product_suppliers.is_preferred, false) as is_preferred,
+                   (SELECT COUNT(DISTINCT purchases.order_number)
+                    FROM purchases
+                    WHERE purchases.product_id = candidates.product_id
+                        AND purchases.supplier_id = candidates.supplier_id) as order_count,
+                   last_purchase.order_number as last_order_number,
+                   last_purchase.order_date as last_order_date,
+                   last_purchase.unit_price as last_unit_price,
+                   last_purchase.currency_code as last_currency_code,
+                   last_purchase.quantity as last_quantity
+            FROM candidates
+            JOIN products ON candidates.product_id = products.id
+            JOIN suppliers ON candidates.supplier_id = suppliers.id
+            LEFT JOIN product_suppliers ON product_suppliers.product_id = candidates.product_id
+                AND product_suppliers.supplier_id = candidates.supplier_id
+            LEFT JOIN LATERAL (
+                SELECT purchases.order_number,
+                       purchases.order_date,
+                       purchases.unit_price,
+                       purchases.currency_code,
+                       purchases.quantity
+                FROM purchases
+                WHERE purchases.product_id = candidates.product_id
+                    AND purchases.supplier_id = candidates.supplier_id
+                ORDER BY purchases.order_date DESC, purchases.created_at DESC
+                LIMIT 1
+            ) last_purchase ON true
+            WHERE suppliers.deleted_at IS NULL
+                AND suppliers.status = 'active'
+            ORDER BY products.name, candidates.product_id, is_preferred DESC, suppliers.name
+            "#,
+        )
+        .bind(product_ids)
+        .fetch_all(self)
+        .await?)
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::model::SelectOption;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::common::utils::to_csv;
+use crate::tenant::inventory::service::suggest_reorder;
+use crate::tenant::suppliers::SuppliersModuleInterface;
+use crate::tenant::suppliers::dto::{PriceComparisonQuery, SupplierInput};
+use crate::tenant::suppliers::model::{ProductPriceComparison, Supplier, SupplierOffer};
+use axum::http::StatusCode;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum SuppliersServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+impl From<ServiceError> for SuppliersServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => SuppliersServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<SuppliersServiceError> for AppError {
+    fn from(value: SuppliersServiceError) -> Self {
+        match value {
+            SuppliersServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            SuppliersServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            SuppliersServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type SuppliersServiceResult<T> = Result<T, SuppliersServiceError>;
+
+fn optional(value: &Option<String>, max_len: usize) -> Result<Option<String>, ()> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) if value.chars().count() > max_len => Err(()),
+        Some(value) => Ok(Some(value.to_string())),
+    }
+}
+
+fn validate(payload: &SupplierInput) -> SuppliersServiceResult<SupplierInput> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(SuppliersServiceError::UnprocessableEntry(
+            "A név megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    let invalid = |_| SuppliersServiceError::UnprocessableEntry("Túl hosszú kapcsolati adat!");
+    let email = optional(&payload.email, 255).map_err(invalid)?;
+    if email.as_ref().is_some_and(|email| !email.contains('@')) {
+        return Err(SuppliersServiceError::UnprocessableEntry(
+            "Hibás e-mail cím!",
+        ));
+    }
+    if !matches!(payload.status.as_str(), "active" | "inactive") {
+        return Err(SuppliersServiceError::UnprocessableEntry("Hibás státusz!"));
+    }
+    Ok(SupplierInput {
+        name: name.to_string(),
+        contact_name: optional(&payload.contact_name, 255).map_err(invalid)?,
+        email,
+        phone_number: optional(&payload.phone_number, 50).map_err(invalid)?,
+        tax_number: optional(&payload.tax_number, 50).map_err(invalid)?,
+        ..payload.clone()
+    })
+}
+
+/// The price an offer can be compared by: the price list entry, or the last purchase price
+/// when the supplier has no price list entry for the product.
+fn offer_price(offer: &SupplierOffer) -> Option<(&BigDecimal, &str)> {
+    match (&offer.purchase_price, &offer.currency_code) {
+        (Some(price), Some(currency_code)) => Some((price, currency_code)),
+        _ => offer
+            .last_unit_price
+            .as_ref()
+            .zip(offer.last_currency_code.as_deref()),
+    }
+}
+
+/// Flags the cheapest offers (per currency, as prices in different currencies are not
+/// comparable) and the ones with the shortest lead time.
+fn mark_best_offers(offers: &mut [SupplierOffer]) {
+    let fastest = offers.iter().filter_map(|offer| offer.lead_time_days).min();
+    let cheapest: Vec<bool> = offers
+        .iter()
+        .map(|offer| {
+            offer_price(offer).is_some_and(|(price, currency_code)| {
+                offers
+                    .iter()
+                    .filter_map(offer_price)
+                    .all(|(other, other_currency)| {
+                        other_currency != currency_code || other >= price
+                    })
+            })
+        })
+        .collect();
+    for (offer, is_cheapest) in offers.iter_mut().zip(cheapest) {
+        offer.is_cheapest = is_cheapest;
+        offer.is_fastest = fastest.is_some() && offer.lead_time_days == fastest;
+    }
+}
+
+pub trait SuppliersService {
+    fn get(&self, id: Uuid) -> impl Future<Output = SuppliersServiceResult<Supplier>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = SuppliersServiceResult<(PaginatorMeta, Vec<Supplier>)>> + Send;
+    fn get_select_list_items(
+        &self,
+    ) -> impl Future<Output = SuppliersServiceResult<Vec<SelectOption>>> + Send;
+    fn create(
+        &self,
+        payload: &SupplierInput,
+    ) -> impl Future<Output = SuppliersServiceResult<Supplier>> + Send;
+    fn update(
+        &self,
+        payload: &SupplierInput,
+    ) -> impl Future<Output = SuppliersServiceResult<Supplier>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = SuppliersServiceResult<()>> + Send;
+    fn get_price_comparison(
+        &self,
+        payload: &PriceComparisonQuery,
+    ) -> impl Future<Output = SuppliersServiceResult<Vec<ProductPriceComparison>>> + Send;
+    fn export_price_comparison(
+        &self,
+        payload: &PriceComparisonQuery,
+    ) -> impl Future<Output = SuppliersServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> SuppliersService for Service<'a, T>
+where
+    T: SuppliersModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> SuppliersServiceResult<Supplier> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .get_by_id(id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> SuppliersServiceResult<(PaginatorMeta, Vec<Supplier>)> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn get_select_list_items(&self) -> SuppliersServiceResult<Vec<SelectOption>> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .get_select_list_items()
+            .await?)
+    }
+
+    async fn create(&self, payload: &SupplierInput) -> SuppliersServiceResult<Supplier> {
+        let input = validate(payload)?;
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .insert(&input, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update(&self, payload: &SupplierInput) -> SuppliersServiceResult<Supplier> {
+        let id = payload.id.ok_or(SuppliersServiceError::UnprocessableEntry(
+            "Az azonosító megadása kötelező!",
+        ))?;
+        let input = validate(payload)?;
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .update(id, &input)
+            .await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> SuppliersServiceResult<()> {
+        Ok(self
+            .module()
+            .suppliers_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(SuppliersServiceError::Unauthorized)?,
+            )?
+            .delete_by_id(id)
+            .await?)
+    }
+
+    async fn get_price_comparison(
+        &self,
+        payload: &PriceComparisonQuery,
+    ) -> SuppliersServiceResult<Vec<ProductPriceComparison>> {
+        let window_days = payload.window_days.unwrap_or(30);
+        let cover_days = payload.cover_days.unwrap_or(14);
+        if !(1..=365).contains(&window_days) || !(1..=365).contains(&cover_days) {
+            return Err(SuppliersServiceError::UnprocessableEntry(
+                "Az időszaknak 1 és 365 nap között kell lennie!",
+            ));
+        }
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(SuppliersServiceError::Unauthorized)?;
+        let mut comparisons: Vec<ProductPriceComparison> = Vec::new();
+        let product_ids = match payload.product_id {
+            Some(product_id) => vec![product_id],
+            None => {
+                let suggestions = self
+                    .module()
+                    .inventory_repo(tenant_id)?
+                    .get_consumption(
+                        Utc::now() - Duration::days(window_days),
+                        payload.warehouse_id,
+                    )
+                    .await?
+                    .into_iter()
+                    .filter_map(|row| suggest_reorder(row, window_days, cover_days));
+                // NOTE: without a warehouse filter the same product may need restocking in
+                // several warehouses, buyers order the total quantity
+                for suggestion in suggestions {
+                    match comparisons
+                        .iter_mut()
+                        .find(|comparison| comparison.product_id == suggestion.product_id)
+                    {
+                        Some(comparison) => {
+                            comparison.suggested_quantity = comparison
+                                .suggested_quantity
+                                .take()
+                                .map(|quantity| quantity + &suggestion.suggested_quantity);
+                        }
+                        None => comparisons.push(ProductPriceComparison {
+                            product_id: suggestion.product_id,
+                            product: suggestion.product,
+                            suggested_quantity: Some(suggestion.suggested_quantity),
+                            offers: Vec::new(),
+                        }),
+                    }
+                }
+                if comparisons.is_empty() {
+                    return Ok(comparisons);
+                }
+                comparisons
+                    .iter()
+                    .map(|comparison| comparison.product_id)
+                    .collect()
+            }
+        };
+        let offers = self
+            .module()
+            .suppliers_repo(tenant_id)?
+            .get_supplier_offers(product_ids)
+            .await?;
+        for offer in offers {
+            match comparisons
+                .iter_mut()
+                .find(|comparison| comparison.product_id == offer.product_id)
+            {
+                Some(comparison) => comparison.offers.push(offer),
+                None => comparisons.push(ProductPriceComparison {
+                    product_id: offer.product_id,
+                    product: offer.product.clone(),
+                    suggested_quantity: None,
+                    offers: vec![offer],
+                }),
+            }
+        }
+        for comparison in comparisons.iter_mut() {
+            mark_best_offers(&mut comparison.offers);
+        }
+        Ok(comparisons)
+    }
+
+    async fn export_price_comparison(
+        &self,
+        payload: &PriceComparisonQuery,
+    ) -> SuppliersServiceResult<Vec<u8>> {
+        let rows: Vec<_> = self
+            .get_price_comparison(payload)
+            .await?
+            .iter()
+            .flat_map(ProductPriceComparison::csv_rows)
+            .collect();
+        Ok(to_csv(&rows)?)
+    }
+}
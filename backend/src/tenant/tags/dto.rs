@@ -0,0 +1,107 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::{Deserialize, Deserializer};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TagInput {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TagRenameInput {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// The taggings of `source_id` are moved to `target_id`, then the source tag is deleted
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TagMergeInput {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TaggedRecord {
+    pub taggable_type: String,
+    pub taggable_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TaggingInput {
+    pub tag_id: Uuid,
+    pub taggable_type: String,
+    pub taggable_id: Uuid,
+}
+
+/// `?tags=` filter of list endpoints, a comma separated list of tag ids. Only the records that
+/// carry every listed tag are kept.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct TagFilter {
+    #[serde(default, deserialize_with = "deserialize_tag_ids")]
+    pub tags: Option<Vec<Uuid>>,
+}
+
+fn deserialize_tag_ids<'de, D>(deserializer: D) -> Result<Option<Vec<Uuid>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tag_ids = String::deserialize(deserializer)?
+        .split(',')
+        .map(str::trim)
+        .filter(|tag_id| !tag_id.is_empty())
+        .map(Uuid::parse_str)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(serde::de::Error::custom)?;
+    Ok(Some(tag_ids).filter(|tag_ids| !tag_ids.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tag_filter_parses_comma_separated_ids() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let filter: TagFilter =
+            serde_json::from_value(json!({ "tags": format!("{first}, {second},") })).unwrap();
+
+        assert_eq!(filter.tags, Some(vec![first, second]));
+    }
+
+    #[test]
+    fn test_tag_filter_empty_and_invalid() {
+        assert_eq!(
+            serde_json::from_value::<TagFilter>(json!({})).unwrap().tags,
+            None
+        );
+        assert_eq!(
+            serde_json::from_value::<TagFilter>(json!({ "tags": "" }))
+                .unwrap()
+                .tags,
+            None
+        );
+        assert!(serde_json::from_value::<TagFilter>(json!({ "tags": "vip" })).is_err());
+    }
+}
@@ -0,0 +1,557 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::tags::TagsModuleInterface;
+use crate::tenant::tags::dto::{
+    TagInput, TagMergeInput, TagRenameInput, TaggedRecord, TaggingInput,
+};
+use crate::tenant::tags::service::TagsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.get_all().await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn usage<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.get_usage().await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TagInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.create(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    map_handler_err(service.delete(payload.uuid).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A címke törlése sikeresen megtörtént",
+            ))
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn rename<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TagRenameInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.rename(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn merge<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TagMergeInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.merge(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn record<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    Query(payload): Query<TaggedRecord>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result =
+        map_handler_err(service.get_by_record(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn attach<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaggingInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    let result = map_handler_err(service.attach(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn detach<M: TagsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tags_module): State<Arc<M>>,
+    Query(payload): Query<TaggingInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tags_module.clone());
+    map_handler_err(service.detach(&payload).await, tags_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A címke eltávolítása sikeresen megtörtént",
+            ))
+            .build(),
+        tags_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::tags::model::{Tag, TagMerge, TagUsage, Tagging};
+    use crate::tenant::tags::{self, repository::MockTagsRepository, tests::MockTagsModule};
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockTagsRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut tags_module = MockTagsModule::new();
+        tags_module
+            .expect_tags_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        tags_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(tags::routes::routes(Arc::new(tags_module))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_trims_name() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_name_exists()
+            .times(1)
+            .with(eq("VIP"), eq(None))
+            .returning(|_, _| Ok(false));
+        repo.expect_insert()
+            .times(1)
+            .with(
+                eq(TagInput {
+                    name: "VIP".to_string(),
+                    description: None,
+                }),
+                eq(sub),
+            )
+            .returning(move |input, sub| {
+                Ok(Tag {
+                    id: Uuid::new_v4(),
+                    name: input.name.clone(),
+                    description: None,
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                    deleted_at: None,
+                })
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/create",
+                sub,
+                active_tenant_id,
+                json!({ "name": "  VIP ", "description": " " }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            extract_json_response(response).await["data"]["name"],
+            json!("VIP")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attach_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let input = TaggingInput {
+            tag_id: Uuid::new_v4(),
+            taggable_type: "projects".to_string(),
+            taggable_id: Uuid::new_v4(),
+        };
+        let mut repo = MockTagsRepository::new();
+        repo.expect_record_exists()
+            .times(1)
+            .with(eq("projects"), eq(input.taggable_id))
+            .returning(|_, _| Ok(true));
+        repo.expect_attach()
+            .times(1)
+            .with(eq(input.clone()), eq(sub))
+            .returning(|input, sub| {
+                Ok(Tagging {
+                    id: Uuid::new_v4(),
+                    tag_id: input.tag_id,
+                    taggable_type: input.taggable_type.clone(),
+                    taggable_id: input.taggable_id,
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/attach",
+                sub,
+                active_tenant_id,
+                json!({
+                    "tag_id": input.tag_id,
+                    "taggable_type": input.taggable_type,
+                    "taggable_id": input.taggable_id
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_attach_invalid_type() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_record_exists().never();
+        repo.expect_attach().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/attach",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({
+                    "tag_id": Uuid::new_v4(),
+                    "taggable_type": "users; DROP TABLE taggings",
+                    "taggable_id": Uuid::new_v4()
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_attach_deleted_record() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_record_exists()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        repo.expect_attach().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/attach",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({
+                    "tag_id": Uuid::new_v4(),
+                    "taggable_type": "customers",
+                    "taggable_id": Uuid::new_v4()
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_rejects_taken_name() {
+        let active_tenant_id = Uuid::new_v4();
+        let tag_id = Uuid::new_v4();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_name_exists()
+            .times(1)
+            .with(eq("Törzsvásárló"), eq(Some(tag_id)))
+            .returning(|_, _| Ok(true));
+        repo.expect_rename().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/tags/rename",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({ "id": tag_id, "name": "Törzsvásárló " }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            extract_json_response(response).await["error"]["message"],
+            json!(
+                "Hiba történt az adatok feldolgozása során: Már létezik ilyen nevű címke, a két címkét összevonhatja!"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let target = tag("VIP");
+        let input = TagMergeInput {
+            source_id: Uuid::new_v4(),
+            target_id: target.id,
+        };
+        let mut repo = MockTagsRepository::new();
+        repo.expect_merge()
+            .times(1)
+            .with(eq(input.clone()))
+            .returning(move |_| {
+                Ok(TagMerge {
+                    tag: target.clone(),
+                    moved_count: 4,
+                    duplicate_count: 1,
+                })
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/merge",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({ "source_id": input.source_id, "target_id": input.target_id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["tag"]["name"], json!("VIP"));
+        assert_eq!(body["data"]["moved_count"], json!(4));
+        assert_eq!(body["data"]["duplicate_count"], json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_merge_rejects_same_tag() {
+        let active_tenant_id = Uuid::new_v4();
+        let tag_id = Uuid::new_v4();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_merge().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/tags/merge",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({ "source_id": tag_id, "target_id": tag_id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_usage_returns_counts_per_type() {
+        let active_tenant_id = Uuid::new_v4();
+        let last_used_at = Utc::now();
+        let mut repo = MockTagsRepository::new();
+        repo.expect_get_usage().times(1).returning(move || {
+            Ok(vec![
+                TagUsage {
+                    id: Uuid::new_v4(),
+                    name: "VIP".to_string(),
+                    description: None,
+                    customers_count: 12,
+                    products_count: 0,
+                    tasks_count: 3,
+                    projects_count: 1,
+                    worksheets_count: 0,
+                    quotes_count: 0,
+                    receivables_count: 0,
+                    total_count: 16,
+                    last_used_at: Some(last_used_at),
+                },
+                TagUsage {
+                    id: Uuid::new_v4(),
+                    name: "Régi".to_string(),
+                    description: None,
+                    customers_count: 0,
+                    products_count: 0,
+                    tasks_count: 0,
+                    projects_count: 0,
+                    worksheets_count: 0,
+                    quotes_count: 0,
+                    receivables_count: 0,
+                    total_count: 0,
+                    last_used_at: None,
+                },
+            ])
+        });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                "/api/tags/usage",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"][0]["customers_count"], json!(12));
+        assert_eq!(body["data"][0]["total_count"], json!(16));
+        assert_eq!(body["data"][1]["total_count"], json!(0));
+        assert_eq!(body["data"][1]["last_used_at"], json!(null));
+    }
+}
@@ -0,0 +1,80 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const TAGGABLE_TYPES: [&str; 7] = [
+    "customers",
+    "products",
+    "tasks",
+    "projects",
+    "worksheets",
+    "quotes",
+    "receivables",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Tag {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Tagging {
+    pub id: Uuid,
+    pub tag_id: Uuid,
+    pub taggable_type: String,
+    pub taggable_id: Uuid,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagMerge {
+    pub tag: Tag,
+    /// Taggings moved over from the source tag
+    pub moved_count: i64,
+    /// Taggings dropped because the record already carried the target tag
+    pub duplicate_count: i64,
+}
+
+/// Usage of a tag, only the taggings of records that are not deleted are counted
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TagUsage {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub customers_count: i64,
+    pub products_count: i64,
+    pub tasks_count: i64,
+    pub projects_count: i64,
+    pub worksheets_count: i64,
+    pub quotes_count: i64,
+    pub receivables_count: i64,
+    pub total_count: i64,
+    /// When the tag was last attached to a record, `None` for unused tags
+    pub last_used_at: Option<DateTime<Utc>>,
+}
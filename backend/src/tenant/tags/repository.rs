@@ -0,0 +1,330 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::tags::dto::{TagInput, TagMergeInput, TagRenameInput, TaggingInput};
+use crate::tenant::tags::model::{Tag, TagMerge, TagUsage, Tagging};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+/// Keeps the records that carry every tag of the `tags_param` UUID array, a NULL array keeps
+/// everything. `taggable_type` is always one of the `TAGGABLE_TYPES` constants.
+pub(crate) fn tagged_condition(
+    taggable_type: &str,
+    record_column: &str,
+    tags_param: usize,
+) -> String {
+    format!(
+        r#"(${tags_param}::UUID[] IS NULL
+            OR NOT EXISTS (SELECT 1
+                           FROM UNNEST(${tags_param}::UUID[]) AS wanted(tag_id)
+                           WHERE NOT EXISTS (SELECT 1
+                                             FROM taggings
+                                             WHERE taggings.tag_id = wanted.tag_id
+                                               AND taggings.taggable_type = '{taggable_type}'
+                                               AND taggings.taggable_id = {record_column})))"#
+    )
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TagsRepository: Send + Sync {
+    async fn get_all(&self) -> RepositoryResult<Vec<Tag>>;
+    async fn insert(&self, input: &TagInput, sub: Uuid) -> RepositoryResult<Tag>;
+    /// Most used tags first
+    async fn get_usage(&self) -> RepositoryResult<Vec<TagUsage>>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Tag>;
+    /// Case insensitive, `except_id` is left out of the check
+    async fn name_exists(&self, name: &str, except_id: Option<Uuid>) -> RepositoryResult<bool>;
+    async fn rename(&self, input: &TagRenameInput) -> RepositoryResult<Tag>;
+    /// Moves the taggings and the customer segment criteria of the source tag to the target
+    async fn merge(&self, input: &TagMergeInput) -> RepositoryResult<TagMerge>;
+    /// Removes the taggings of the tag as well
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn record_exists(&self, taggable_type: &str, taggable_id: Uuid)
+    -> RepositoryResult<bool>;
+    async fn get_by_record(
+        &self,
+        taggable_type: &str,
+        taggable_id: Uuid,
+    ) -> RepositoryResult<Vec<Tag>>;
+    async fn attach(&self, input: &TaggingInput, sub: Uuid) -> RepositoryResult<Tagging>;
+    async fn detach(&self, input: &TaggingInput) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl TagsRepository for PgPool {
+    async fn get_all(&self) -> RepositoryResult<Vec<Tag>> {
+        Ok(
+            sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE deleted_at IS NULL ORDER BY name")
+                .fetch_all(self)
+                .await?,
+        )
+    }
+
+    async fn insert(&self, input: &TagInput, sub: Uuid) -> RepositoryResult<Tag> {
+        Ok(sqlx::query_as::<_, Tag>(
+            r#"
+            INSERT INTO tags (name, description, created_by_id)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_usage(&self) -> RepositoryResult<Vec<TagUsage>> {
+        Ok(sqlx::query_as::<_, TagUsage>(
+            r#"
+            SELECT tags.id,
+                   tags.name,
+                   tags.description,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'customers')   AS customers_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'products')    AS products_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'tasks')       AS tasks_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'projects')    AS projects_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'worksheets')  AS worksheets_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'quotes')      AS quotes_count,
+                   COUNT(live.id) FILTER (WHERE live.taggable_type = 'receivables') AS receivables_count,
+                   COUNT(live.id)                                                   AS total_count,
+                   MAX(live.created_at)                                             AS last_used_at
+            FROM tags
+                     LEFT JOIN (SELECT taggings.*
+                                FROM taggings
+                                WHERE CASE taggings.taggable_type
+                                          WHEN 'customers' THEN EXISTS(SELECT 1 FROM customers
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'products' THEN EXISTS(SELECT 1 FROM products
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'tasks' THEN EXISTS(SELECT 1 FROM tasks
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'projects' THEN EXISTS(SELECT 1 FROM projects
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'worksheets' THEN EXISTS(SELECT 1 FROM worksheets
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'quotes' THEN EXISTS(SELECT 1 FROM quotes
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          WHEN 'receivables' THEN EXISTS(SELECT 1 FROM receivables
+                                              WHERE id = taggings.taggable_id AND deleted_at IS NULL)
+                                          ELSE false
+                                          END) AS live ON live.tag_id = tags.id
+            WHERE tags.deleted_at IS NULL
+            GROUP BY tags.id
+            ORDER BY total_count DESC, last_used_at DESC NULLS LAST, tags.name
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Tag> {
+        Ok(
+            sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_one(self)
+                .await?,
+        )
+    }
+
+    async fn name_exists(&self, name: &str, except_id: Option<Uuid>) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(SELECT 1
+                          FROM tags
+                          WHERE lower(name) = lower($1)
+                            AND ($2::UUID IS NULL OR id <> $2)
+                            AND deleted_at IS NULL)
+            "#,
+        )
+        .bind(name)
+        .bind(except_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn rename(&self, input: &TagRenameInput) -> RepositoryResult<Tag> {
+        Ok(sqlx::query_as::<_, Tag>(
+            "UPDATE tags SET name = $2 WHERE id = $1 AND deleted_at IS NULL RETURNING *",
+        )
+        .bind(input.id)
+        .bind(&input.name)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn merge(&self, input: &TagMergeInput) -> RepositoryResult<TagMerge> {
+        let mut tx = self.begin().await?;
+        let locked = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM tags WHERE id IN ($1, $2) AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(input.source_id)
+        .bind(input.target_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+
+        let duplicate_count = sqlx::query(
+            r#"
+            DELETE FROM taggings AS source
+            WHERE source.tag_id = $1
+              AND EXISTS (SELECT 1
+                          FROM taggings AS target
+                          WHERE target.tag_id = $2
+                            AND target.taggable_type = source.taggable_type
+                            AND target.taggable_id = source.taggable_id)
+            "#,
+        )
+        .bind(input.source_id)
+        .bind(input.target_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        let moved_count = sqlx::query("UPDATE taggings SET tag_id = $2 WHERE tag_id = $1")
+            .bind(input.source_id)
+            .bind(input.target_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        sqlx::query(
+            r#"
+            UPDATE customer_segments
+            SET tag_ids = ARRAY(SELECT DISTINCT UNNEST(array_replace(tag_ids, $1, $2)))
+            WHERE $1 = ANY (tag_ids)
+            "#,
+        )
+        .bind(input.source_id)
+        .bind(input.target_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE tags SET deleted_at = now() WHERE id = $1")
+            .bind(input.source_id)
+            .execute(&mut *tx)
+            .await?;
+        let tag = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1")
+            .bind(input.target_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(TagMerge {
+            tag,
+            moved_count: i64::try_from(moved_count)?,
+            duplicate_count: i64::try_from(duplicate_count)?,
+        })
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        let mut tx = self.begin().await?;
+        sqlx::query_scalar::<_, Uuid>(
+            "UPDATE tags SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL RETURNING id",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM taggings WHERE tag_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn record_exists(
+        &self,
+        taggable_type: &str,
+        taggable_id: Uuid,
+    ) -> RepositoryResult<bool> {
+        // NOTE: taggable_type is checked against TAGGABLE_TYPES by the service before it gets here
+        Ok(sqlx::query_scalar::<_, bool>(AssertSqlSafe(format!(
+            "SELECT EXISTS(SELECT 1 FROM {taggable_type} WHERE id = $1 AND deleted_at IS NULL)"
+        )))
+        .bind(taggable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_record(
+        &self,
+        taggable_type: &str,
+        taggable_id: Uuid,
+    ) -> RepositoryResult<Vec<Tag>> {
+        Ok(sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT tags.*
+            FROM taggings
+            JOIN tags ON taggings.tag_id = tags.id
+            WHERE taggings.taggable_type = $1
+              AND taggings.taggable_id = $2
+              AND tags.deleted_at IS NULL
+            ORDER BY tags.name
+            "#,
+        )
+        .bind(taggable_type)
+        .bind(taggable_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn attach(&self, input: &TaggingInput, sub: Uuid) -> RepositoryResult<Tagging> {
+        Ok(sqlx::query_as::<_, Tagging>(
+            r#"
+            INSERT INTO taggings (tag_id, taggable_type, taggable_id, created_by_id)
+            SELECT tags.id, $2, $3, $4
+            FROM tags
+            WHERE tags.id = $1
+              AND tags.deleted_at IS NULL
+            ON CONFLICT (tag_id, taggable_type, taggable_id)
+                DO UPDATE SET tag_id = EXCLUDED.tag_id
+            RETURNING *
+            "#,
+        )
+        .bind(input.tag_id)
+        .bind(&input.taggable_type)
+        .bind(input.taggable_id)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn detach(&self, input: &TaggingInput) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM taggings
+            WHERE tag_id = $1
+              AND taggable_type = $2
+              AND taggable_id = $3
+            "#,
+        )
+        .bind(input.tag_id)
+        .bind(&input.taggable_type)
+        .bind(input.taggable_id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+}
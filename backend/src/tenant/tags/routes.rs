@@ -0,0 +1,44 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::TagsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: TagsModuleInterface>(tags_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/tags",
+        Router::new()
+            .route("/list", get(handler::list::<M>))
+            .route("/usage", get(handler::usage::<M>))
+            .route("/create", post(handler::create::<M>))
+            .route("/delete", delete(handler::delete::<M>))
+            .route("/rename", put(handler::rename::<M>))
+            .route("/merge", post(handler::merge::<M>))
+            .route("/record", get(handler::record::<M>))
+            .route("/attach", post(handler::attach::<M>))
+            .route("/detach", delete(handler::detach::<M>))
+            .layer(from_fn_with_state(tags_module.clone(), require_auth))
+            .with_state(tags_module),
+    )
+}
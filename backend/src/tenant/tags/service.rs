@@ -0,0 +1,277 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::tags::TagsModuleInterface;
+use crate::tenant::tags::dto::{
+    TagInput, TagMergeInput, TagRenameInput, TaggedRecord, TaggingInput,
+};
+use crate::tenant::tags::model::{TAGGABLE_TYPES, Tag, TagMerge, TagUsage, Tagging};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TagsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TagsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TagsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TagsServiceError> for AppError {
+    fn from(value: TagsServiceError) -> Self {
+        match value {
+            TagsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TagsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TagsServiceError::Repository(RepositoryError::Database(sqlx::Error::RowNotFound)) => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::NOT_FOUND,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": "Nem található"}),
+                )
+            }
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TagsServiceResult<T> = Result<T, TagsServiceError>;
+
+const NAME_TAKEN: &str = "Már létezik ilyen nevű címke, a két címkét összevonhatja!";
+
+fn validate_name(name: &str) -> TagsServiceResult<String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(TagsServiceError::UnprocessableEntry(
+            "A címke neve kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_tag(payload: &TagInput) -> TagsServiceResult<TagInput> {
+    Ok(TagInput {
+        name: validate_name(&payload.name)?,
+        description: payload
+            .description
+            .as_ref()
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty()),
+    })
+}
+
+fn validate_taggable_type(taggable_type: &str) -> TagsServiceResult<()> {
+    if TAGGABLE_TYPES.contains(&taggable_type) {
+        Ok(())
+    } else {
+        Err(TagsServiceError::UnprocessableEntry(
+            "Hibás erőforrás típus",
+        ))
+    }
+}
+
+pub trait TagsService {
+    fn get_all(&self) -> impl Future<Output = TagsServiceResult<Vec<Tag>>> + Send;
+    fn get_usage(&self) -> impl Future<Output = TagsServiceResult<Vec<TagUsage>>> + Send;
+    fn create(&self, payload: &TagInput) -> impl Future<Output = TagsServiceResult<Tag>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = TagsServiceResult<()>> + Send;
+    fn rename(
+        &self,
+        payload: &TagRenameInput,
+    ) -> impl Future<Output = TagsServiceResult<Tag>> + Send;
+    fn merge(
+        &self,
+        payload: &TagMergeInput,
+    ) -> impl Future<Output = TagsServiceResult<TagMerge>> + Send;
+    fn get_by_record(
+        &self,
+        payload: &TaggedRecord,
+    ) -> impl Future<Output = TagsServiceResult<Vec<Tag>>> + Send;
+    fn attach(
+        &self,
+        payload: &TaggingInput,
+    ) -> impl Future<Output = TagsServiceResult<Tagging>> + Send;
+    fn detach(&self, payload: &TaggingInput) -> impl Future<Output = TagsServiceResult<()>> + Send;
+}
+
+impl<'a, T> TagsService for Service<'a, T>
+where
+    T: TagsModuleInterface,
+{
+    async fn get_all(&self) -> TagsServiceResult<Vec<Tag>> {
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .get_all()
+            .await?)
+    }
+
+    async fn get_usage(&self) -> TagsServiceResult<Vec<TagUsage>> {
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .get_usage()
+            .await?)
+    }
+
+    async fn create(&self, payload: &TagInput) -> TagsServiceResult<Tag> {
+        let input = validate_tag(payload)?;
+        let repo = self.module().tags_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TagsServiceError::Unauthorized)?,
+        )?;
+        if repo.name_exists(&input.name, None).await? {
+            return Err(TagsServiceError::UnprocessableEntry(NAME_TAKEN));
+        }
+        Ok(repo.insert(&input, self.claims()?.sub()).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> TagsServiceResult<()> {
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .delete_by_id(id)
+            .await?)
+    }
+
+    async fn rename(&self, payload: &TagRenameInput) -> TagsServiceResult<Tag> {
+        let input = TagRenameInput {
+            name: validate_name(&payload.name)?,
+            ..payload.clone()
+        };
+        let repo = self.module().tags_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TagsServiceError::Unauthorized)?,
+        )?;
+        if repo.name_exists(&input.name, Some(input.id)).await? {
+            return Err(TagsServiceError::UnprocessableEntry(NAME_TAKEN));
+        }
+        Ok(repo.rename(&input).await?)
+    }
+
+    async fn merge(&self, payload: &TagMergeInput) -> TagsServiceResult<TagMerge> {
+        if payload.source_id == payload.target_id {
+            return Err(TagsServiceError::UnprocessableEntry(
+                "Egy címke nem vonható össze önmagával!",
+            ));
+        }
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .merge(payload)
+            .await?)
+    }
+
+    async fn get_by_record(&self, payload: &TaggedRecord) -> TagsServiceResult<Vec<Tag>> {
+        validate_taggable_type(&payload.taggable_type)?;
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .get_by_record(&payload.taggable_type, payload.taggable_id)
+            .await?)
+    }
+
+    async fn attach(&self, payload: &TaggingInput) -> TagsServiceResult<Tagging> {
+        validate_taggable_type(&payload.taggable_type)?;
+        let repo = self.module().tags_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TagsServiceError::Unauthorized)?,
+        )?;
+        if !repo
+            .record_exists(&payload.taggable_type, payload.taggable_id)
+            .await?
+        {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound).into());
+        }
+        Ok(repo.attach(payload, self.claims()?.sub()).await?)
+    }
+
+    async fn detach(&self, payload: &TaggingInput) -> TagsServiceResult<()> {
+        validate_taggable_type(&payload.taggable_type)?;
+        Ok(self
+            .module()
+            .tags_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TagsServiceError::Unauthorized)?,
+            )?
+            .detach(payload)
+            .await?)
+    }
+}
@@ -0,0 +1,458 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::task_assignments::TaskAssignmentsModuleInterface;
+use crate::tenant::task_assignments::dto::{TaskAssignmentInput, TaskNotificationPreferencesInput};
+use crate::tenant::task_assignments::service::TaskAssignmentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    let result = map_handler_err(
+        service.list(payload.uuid).await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn assign<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskAssignmentInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    let result = map_handler_err(
+        service.assign(&payload).await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reassign<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskAssignmentInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    let result = map_handler_err(
+        service.reassign(&payload).await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn unassign<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskAssignmentInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    map_handler_err(
+        service.unassign(&payload).await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A hozzárendelés törlése sikeresen megtörtént",
+            ))
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn get_preferences<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    let result = map_handler_err(
+        service.get_preferences().await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update_preferences<M: TaskAssignmentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_assignments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskNotificationPreferencesInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_assignments_module.clone());
+    let result = map_handler_err(
+        service.update_preferences(&payload).await,
+        task_assignments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_assignments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::project_members::tests::project_access_repos;
+    use crate::tenant::task_assignments::model::{TaskAssignment, TaskNotification};
+    use crate::tenant::task_assignments::{
+        self, repository::MockTaskAssignmentsRepository, tests::MockTaskAssignmentsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockTaskAssignmentsRepository,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+    ) -> MockTaskAssignmentsModule {
+        module_with_project_role(repo, active_tenant_id, config_calls, Some("member"))
+    }
+
+    /// Every task belongs to a project, where the user holds `role`
+    fn module_with_project_role(
+        repo: MockTaskAssignmentsRepository,
+        active_tenant_id: Uuid,
+        config_calls: usize,
+        role: Option<&'static str>,
+    ) -> MockTaskAssignmentsModule {
+        let repo = Arc::new(repo);
+        let (project_members_repo, membership_repo, permissions_repo) = project_access_repos(role);
+        let mut task_assignments_module = MockTaskAssignmentsModule::new();
+        task_assignments_module
+            .expect_task_assignments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        task_assignments_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        task_assignments_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        task_assignments_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        task_assignments_module
+            .expect_config()
+            .times(config_calls)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        task_assignments_module
+    }
+
+    fn app(task_assignments_module: MockTaskAssignmentsModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(task_assignments::routes::routes(Arc::new(
+                task_assignments_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn assignment(task_id: Uuid, user_id: Uuid, created_by_id: Uuid) -> TaskAssignment {
+        TaskAssignment {
+            id: Uuid::new_v4(),
+            user_id,
+            task_id,
+            created_by_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn notification(assignment: &TaskAssignment) -> TaskNotification {
+        TaskNotification {
+            assignment_id: assignment.id,
+            user_id: assignment.user_id,
+            name: "Minta János".to_string(),
+            email: "janos@example.com".to_string(),
+            task_id: assignment.task_id,
+            task: "Klíma karbantartás".to_string(),
+            worksheet: "Irodaház üzemeltetés".to_string(),
+            description: None,
+            priority: Some("high".to_string()),
+            due_date: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assign_emails_assignee() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let assignment = assignment(Uuid::new_v4(), Uuid::new_v4(), sub);
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_assign()
+            .times(1)
+            .with(
+                eq(assignment.task_id),
+                eq(assignment.user_id),
+                eq(false),
+                eq(sub),
+            )
+            .returning({
+                let assignment = assignment.clone();
+                move |_, _, _, _| Ok(assignment.clone())
+            });
+        repo.expect_get_assignment_notification()
+            .times(1)
+            .with(eq(assignment.id))
+            .returning({
+                let notification = notification(&assignment);
+                move |_| Ok(Some(notification.clone()))
+            });
+        let mut task_assignments_module = module(repo, active_tenant_id, 3);
+        task_assignments_module
+            .expect_send()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let response = app(task_assignments_module)
+            .oneshot(request(
+                "POST",
+                "/api/task_assignments/assign",
+                sub,
+                active_tenant_id,
+                Some(json!({"task_id": assignment.task_id, "user_id": assignment.user_id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_reassign_respects_disabled_assignment_emails() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let assignment = assignment(Uuid::new_v4(), Uuid::new_v4(), sub);
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_assign()
+            .times(1)
+            .with(
+                eq(assignment.task_id),
+                eq(assignment.user_id),
+                eq(true),
+                eq(sub),
+            )
+            .returning({
+                let assignment = assignment.clone();
+                move |_, _, _, _| Ok(assignment.clone())
+            });
+        repo.expect_get_assignment_notification()
+            .times(1)
+            .with(eq(assignment.id))
+            .returning(|_| Ok(None));
+        let mut task_assignments_module = module(repo, active_tenant_id, 1);
+        task_assignments_module.expect_send().never();
+
+        let response = app(task_assignments_module)
+            .oneshot(request(
+                "PUT",
+                "/api/task_assignments/reassign",
+                sub,
+                active_tenant_id,
+                Some(json!({"task_id": assignment.task_id, "user_id": assignment.user_id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_self_assignment_is_not_emailed() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let assignment = assignment(Uuid::new_v4(), sub, sub);
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_assign().times(1).returning({
+            let assignment = assignment.clone();
+            move |_, _, _, _| Ok(assignment.clone())
+        });
+        repo.expect_get_assignment_notification().never();
+        let mut task_assignments_module = module(repo, active_tenant_id, 1);
+        task_assignments_module.expect_send().never();
+
+        let response = app(task_assignments_module)
+            .oneshot(request(
+                "POST",
+                "/api/task_assignments/assign",
+                sub,
+                active_tenant_id,
+                Some(json!({"task_id": assignment.task_id, "user_id": sub})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_unassign_not_assigned_user() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_unassign()
+            .times(1)
+            .returning(|_, _| Err(sqlx::Error::RowNotFound.into()));
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "PUT",
+                "/api/task_assignments/unassign",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({"task_id": Uuid::new_v4(), "user_id": Uuid::new_v4()})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_update_preferences_rejects_long_reminder_period() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_update_preferences().never();
+
+        let response = app(module(repo, active_tenant_id, 1))
+            .oneshot(request(
+                "PUT",
+                "/api/task_assignments/notification_preferences/update",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({
+                    "assignment_emails": true,
+                    "due_date_emails": true,
+                    "reminder_days_before": 31
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_assign_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_assign().never();
+        let mut task_assignments_module = module_with_project_role(repo, active_tenant_id, 1, None);
+        task_assignments_module.expect_send().never();
+
+        let response = app(task_assignments_module)
+            .oneshot(request(
+                "POST",
+                "/api/task_assignments/assign",
+                Uuid::new_v4(),
+                active_tenant_id,
+                Some(json!({"task_id": Uuid::new_v4(), "user_id": Uuid::new_v4()})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,115 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Europe::Budapest;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const DEFAULT_REMINDER_DAYS_BEFORE: i32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskAssignment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Uuid,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskAssignmentResolved {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user: String,
+    pub email: String,
+    pub task_id: Uuid,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskNotificationPreferences {
+    pub user_id: Uuid,
+    pub assignment_emails: bool,
+    pub due_date_emails: bool,
+    pub reminder_days_before: i32,
+}
+
+/// An assignment together with everything the notification emails of the assignee need
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskNotification {
+    pub assignment_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub task_id: Uuid,
+    pub task: String,
+    pub worksheet: String,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+impl TaskNotification {
+    pub fn email_data(&self, reassigned: bool) -> Value {
+        json!({
+            "name": self.name,
+            "task": self.task,
+            "worksheet": self.worksheet,
+            "description": self.description,
+            "priority": self.priority,
+            "due_date": self.due_date.map(|due_date| {
+                due_date
+                    .with_timezone(&Budapest)
+                    .format("%Y. %m. %d. %H:%M")
+                    .to_string()
+            }),
+            "reassigned": reassigned,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_data_formats_due_date_in_local_time() {
+        let notification = TaskNotification {
+            assignment_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Minta János".to_string(),
+            email: "janos@example.com".to_string(),
+            task_id: Uuid::new_v4(),
+            task: "Klíma karbantartás".to_string(),
+            worksheet: "Irodaház üzemeltetés".to_string(),
+            description: None,
+            priority: None,
+            due_date: Some("2026-07-01T14:30:00Z".parse().unwrap()),
+        };
+
+        let data = notification.email_data(true);
+
+        assert_eq!(data["due_date"], "2026. 07. 01. 16:30");
+        assert_eq!(data["reassigned"], true);
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::email_template::EmailTemplate;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::task_assignments::TaskAssignmentsModuleInterface;
+use crate::tenant::task_assignments::service::send_task_email;
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+async fn send_tenant_reminders<M: TaskAssignmentsModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+) -> anyhow::Result<()> {
+    let repo = module.task_assignments_repo(tenant_id)?;
+    for notification in repo.get_due_reminders().await? {
+        let Some(due_date) = notification.due_date else {
+            continue;
+        };
+        // NOTE: reminders that could not be sent are not marked, so they are retried on the
+        // next run
+        match send_task_email(module, EmailTemplate::TaskDueReminder, &notification, false).await {
+            Ok(()) => {
+                repo.mark_reminded(notification.assignment_id, due_date)
+                    .await?
+            }
+            Err(e) => warn!(
+                "Could not send due date reminder of task {} to {}: {}",
+                notification.task_id, notification.email, e
+            ),
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_task_due_reminders<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let interval_hours = app_state.config().tasks().due_reminder_interval_hours();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_hours * 3600));
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for task due date reminders: {}", e);
+                    continue;
+                }
+            };
+            for tenant in tenants {
+                if let Err(e) = send_tenant_reminders(&*app_state, tenant.id).await {
+                    error!(
+                        "Task due date reminders failed for tenant {}: {}",
+                        tenant.id, e
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::tenant::task_assignments::model::TaskNotification;
+    use crate::tenant::task_assignments::repository::MockTaskAssignmentsRepository;
+    use crate::tenant::task_assignments::tests::MockTaskAssignmentsModule;
+    use chrono::Days;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+
+    fn notification(email: &str) -> TaskNotification {
+        TaskNotification {
+            assignment_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Minta János".to_string(),
+            email: email.to_string(),
+            task_id: Uuid::new_v4(),
+            task: "Klíma karbantartás".to_string(),
+            worksheet: "Irodaház üzemeltetés".to_string(),
+            description: None,
+            priority: None,
+            due_date: Utc::now().checked_add_days(Days::new(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_sent_reminders_are_marked() {
+        let tenant_id = Uuid::new_v4();
+        let sent = notification("janos@example.com");
+        let invalid = notification("nem e-mail cím");
+        let mut repo = MockTaskAssignmentsRepository::new();
+        repo.expect_get_due_reminders().times(1).returning({
+            let notifications = vec![sent.clone(), invalid.clone()];
+            move || Ok(notifications.clone())
+        });
+        repo.expect_mark_reminded()
+            .times(1)
+            .with(eq(sent.assignment_id), eq(sent.due_date.unwrap()))
+            .returning(|_, _| Ok(()));
+        let repo = Arc::new(repo);
+        let mut module = MockTaskAssignmentsModule::new();
+        module
+            .expect_task_assignments_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        module
+            .expect_config()
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        module.expect_send().times(1).returning(|_| Ok(None));
+
+        send_tenant_reminders(&module, tenant_id).await.unwrap();
+    }
+}
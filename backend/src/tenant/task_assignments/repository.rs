@@ -0,0 +1,298 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::task_assignments::dto::TaskNotificationPreferencesInput;
+use crate::tenant::task_assignments::model::{
+    DEFAULT_REMINDER_DAYS_BEFORE, TaskAssignment, TaskAssignmentResolved, TaskNotification,
+    TaskNotificationPreferences,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+const NOTIFICATION_SELECT: &str = r#"
+    SELECT task_assignments.id AS assignment_id,
+           users.id AS user_id,
+           COALESCE(
+               NULLIF(TRIM(CONCAT(users.last_name, ' ', users.first_name)), ''),
+               users.email
+           ) AS name,
+           users.email,
+           tasks.id AS task_id,
+           services.name AS task,
+           worksheets.name AS worksheet,
+           tasks.description,
+           tasks.priority,
+           tasks.due_date
+    FROM task_assignments
+    JOIN users ON task_assignments.user_id = users.id
+    JOIN tasks ON task_assignments.task_id = tasks.id
+    JOIN services ON tasks.service_id = services.id
+    JOIN worksheets ON tasks.worksheet_id = worksheets.id
+    LEFT JOIN task_notification_preferences
+        ON task_notification_preferences.user_id = users.id
+    WHERE task_assignments.deleted_at IS NULL
+        AND tasks.deleted_at IS NULL
+        AND users.deleted_at IS NULL
+        AND users.status = 'active'
+"#;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TaskAssignmentsRepository: Send + Sync {
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskAssignmentResolved>>;
+    async fn assign(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+        replace: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskAssignment>;
+    async fn unassign(&self, task_id: Uuid, user_id: Uuid) -> RepositoryResult<()>;
+    async fn get_assignment_notification(
+        &self,
+        assignment_id: Uuid,
+    ) -> RepositoryResult<Option<TaskNotification>>;
+    async fn get_due_reminders(&self) -> RepositoryResult<Vec<TaskNotification>>;
+    async fn mark_reminded(
+        &self,
+        assignment_id: Uuid,
+        due_date: DateTime<Utc>,
+    ) -> RepositoryResult<()>;
+    async fn get_preferences(&self, user_id: Uuid)
+    -> RepositoryResult<TaskNotificationPreferences>;
+    async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        input: &TaskNotificationPreferencesInput,
+    ) -> RepositoryResult<TaskNotificationPreferences>;
+}
+
+#[async_trait]
+impl TaskAssignmentsRepository for PgPool {
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskAssignmentResolved>> {
+        Ok(sqlx::query_as::<_, TaskAssignmentResolved>(
+            r#"
+            SELECT task_assignments.id,
+                   task_assignments.user_id,
+                   COALESCE(
+                       NULLIF(TRIM(CONCAT(users.last_name, ' ', users.first_name)), ''),
+                       users.email
+                   ) AS user,
+                   users.email,
+                   task_assignments.task_id,
+                   task_assignments.created_by_id,
+                   task_assignments.created_at
+            FROM task_assignments
+            JOIN users ON task_assignments.user_id = users.id
+            WHERE task_assignments.task_id = $1
+                AND task_assignments.deleted_at IS NULL
+            ORDER BY task_assignments.created_at
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn assign(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+        replace: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskAssignment> {
+        let mut tx = self.begin().await?;
+        let task = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id
+            FROM tasks
+            WHERE id = $1
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(task_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if task.is_none() {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        if replace {
+            sqlx::query(
+                r#"
+                UPDATE task_assignments
+                SET deleted_at = now()
+                WHERE task_id = $1
+                    AND user_id <> $2
+                    AND deleted_at IS NULL
+                "#,
+            )
+            .bind(task_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        let assignment = sqlx::query_as::<_, TaskAssignment>(
+            r#"
+            INSERT INTO task_assignments (user_id, task_id, created_by_id)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, task_id, created_by_id, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(task_id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(assignment)
+    }
+
+    async fn unassign(&self, task_id: Uuid, user_id: Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE task_assignments
+            SET deleted_at = now()
+            WHERE task_id = $1
+                AND user_id = $2
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(task_id)
+        .bind(user_id)
+        .execute(self)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn get_assignment_notification(
+        &self,
+        assignment_id: Uuid,
+    ) -> RepositoryResult<Option<TaskNotification>> {
+        Ok(sqlx::query_as::<_, TaskNotification>(AssertSqlSafe(format!(
+            r#"
+            {NOTIFICATION_SELECT}
+                AND task_assignments.id = $1
+                AND COALESCE(task_notification_preferences.assignment_emails, true)
+            "# // Security: constant
+        )))
+        .bind(assignment_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    /// Assignments of unfinished tasks whose due date is within the reminder period of the
+    /// assignee and were not reminded of the current due date yet
+    async fn get_due_reminders(&self) -> RepositoryResult<Vec<TaskNotification>> {
+        Ok(sqlx::query_as::<_, TaskNotification>(AssertSqlSafe(format!(
+            r#"
+            {NOTIFICATION_SELECT}
+                AND tasks.status NOT IN ('done', 'inactive')
+                AND tasks.due_date > now()
+                AND tasks.due_date <= now() + make_interval(days => COALESCE(
+                    task_notification_preferences.reminder_days_before,
+                    $1
+                ))
+                AND COALESCE(task_notification_preferences.due_date_emails, true)
+                AND task_assignments.due_reminder_sent_for IS DISTINCT FROM tasks.due_date
+            ORDER BY tasks.due_date
+            "# // Security: constant
+        )))
+        .bind(DEFAULT_REMINDER_DAYS_BEFORE)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn mark_reminded(
+        &self,
+        assignment_id: Uuid,
+        due_date: DateTime<Utc>,
+    ) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE task_assignments
+            SET due_reminder_sent_for = $2
+            WHERE id = $1
+            "#,
+        )
+        .bind(assignment_id)
+        .bind(due_date)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> RepositoryResult<TaskNotificationPreferences> {
+        Ok(sqlx::query_as::<_, TaskNotificationPreferences>(
+            r#"
+            SELECT users.id AS user_id,
+                   COALESCE(task_notification_preferences.assignment_emails, true)
+                       AS assignment_emails,
+                   COALESCE(task_notification_preferences.due_date_emails, true)
+                       AS due_date_emails,
+                   COALESCE(task_notification_preferences.reminder_days_before, $2)
+                       AS reminder_days_before
+            FROM users
+            LEFT JOIN task_notification_preferences
+                ON task_notification_preferences.user_id = users.id
+            WHERE users.id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(DEFAULT_REMINDER_DAYS_BEFORE)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        input: &TaskNotificationPreferencesInput,
+    ) -> RepositoryResult<TaskNotificationPreferences> {
+        Ok(sqlx::query_as::<_, TaskNotificationPreferences>(
+            r#"
+            INSERT INTO task_notification_preferences
+                (user_id, assignment_emails, due_date_emails, reminder_days_before)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE
+                SET assignment_emails = EXCLUDED.assignment_emails,
+                    due_date_emails = EXCLUDED.due_date_emails,
+                    reminder_days_before = EXCLUDED.reminder_days_before
+            RETURNING user_id, assignment_emails, due_date_emails, reminder_days_before
+            "#,
+        )
+        .bind(user_id)
+        .bind(input.assignment_emails)
+        .bind(input.due_date_emails)
+        .bind(input.reminder_days_before)
+        .fetch_one(self)
+        .await?)
+    }
+}
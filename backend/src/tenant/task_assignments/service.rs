@@ -0,0 +1,321 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::email_template::EmailTemplate;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::ensure_task_access;
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::task_assignments::TaskAssignmentsModuleInterface;
+use crate::tenant::task_assignments::dto::{TaskAssignmentInput, TaskNotificationPreferencesInput};
+use crate::tenant::task_assignments::model::{
+    TaskAssignment, TaskAssignmentResolved, TaskNotification, TaskNotificationPreferences,
+};
+use crate::tenant::task_assignments::repository::TaskAssignmentsRepository;
+use axum::http::StatusCode;
+use lettre::message::Mailbox;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{Level, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TaskAssignmentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TaskAssignmentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TaskAssignmentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TaskAssignmentsServiceError> for AppError {
+    fn from(value: TaskAssignmentsServiceError) -> Self {
+        match value {
+            TaskAssignmentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskAssignmentsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            TaskAssignmentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskAssignmentsServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": TaskAssignmentsServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            TaskAssignmentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TaskAssignmentsServiceResult<T> = Result<T, TaskAssignmentsServiceError>;
+
+fn map_assign_error(e: RepositoryError) -> TaskAssignmentsServiceError {
+    if e.is_unique_violation() {
+        TaskAssignmentsServiceError::UnprocessableEntry(
+            "A felhasználó már hozzá van rendelve a feladathoz!",
+        )
+    } else if e.is_foreign_key_violation() {
+        TaskAssignmentsServiceError::UnprocessableEntry("A megadott felhasználó nem létezik!")
+    } else {
+        e.into()
+    }
+}
+
+/// Sends `template` to the assignee of the notification, failures are returned to the caller
+/// so scheduled reminders can be retried.
+pub(crate) async fn send_task_email<M: TaskAssignmentsModuleInterface>(
+    module: &M,
+    template: EmailTemplate,
+    notification: &TaskNotification,
+    reassigned: bool,
+) -> anyhow::Result<()> {
+    let from = Mailbox::new(
+        Some(module.config().mail().default_from_name().to_owned()),
+        module.config().mail().default_from().parse()?,
+    );
+    let to = Mailbox::new(Some(notification.name.clone()), notification.email.parse()?);
+    let message = template
+        .render(&notification.email_data(reassigned))?
+        .into_message(from, to)?;
+    module.send(message).await?;
+    Ok(())
+}
+
+/// Emails the new assignee unless they assigned the task to themselves or turned the
+/// assignment emails off, failures are only logged so the assignment stays in place.
+async fn notify_assignee<M: TaskAssignmentsModuleInterface>(
+    module: &M,
+    repo: &(dyn TaskAssignmentsRepository + Send + Sync),
+    assignment: &TaskAssignment,
+    reassigned: bool,
+) {
+    if assignment.user_id == assignment.created_by_id {
+        return;
+    }
+    let result: anyhow::Result<()> = async {
+        if let Some(notification) = repo.get_assignment_notification(assignment.id).await? {
+            send_task_email(
+                module,
+                EmailTemplate::TaskAssigned,
+                &notification,
+                reassigned,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        warn!(
+            "Could not email the assignment of task {} to user {}: {}",
+            assignment.task_id, assignment.user_id, e
+        );
+    }
+}
+
+pub trait TaskAssignmentsService {
+    fn list(
+        &self,
+        task_id: Uuid,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<Vec<TaskAssignmentResolved>>> + Send;
+    fn assign(
+        &self,
+        payload: &TaskAssignmentInput,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<TaskAssignment>> + Send;
+    fn reassign(
+        &self,
+        payload: &TaskAssignmentInput,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<TaskAssignment>> + Send;
+    fn unassign(
+        &self,
+        payload: &TaskAssignmentInput,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<()>> + Send;
+    fn get_preferences(
+        &self,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<TaskNotificationPreferences>> + Send;
+    fn update_preferences(
+        &self,
+        payload: &TaskNotificationPreferencesInput,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<TaskNotificationPreferences>> + Send;
+    fn repo(
+        &self,
+    ) -> TaskAssignmentsServiceResult<Arc<dyn TaskAssignmentsRepository + Send + Sync>>;
+    fn assign_with(
+        &self,
+        payload: &TaskAssignmentInput,
+        replace: bool,
+    ) -> impl Future<Output = TaskAssignmentsServiceResult<TaskAssignment>> + Send;
+}
+
+impl<'a, T> TaskAssignmentsService for Service<'a, T>
+where
+    T: TaskAssignmentsModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> TaskAssignmentsServiceResult<Arc<dyn TaskAssignmentsRepository + Send + Sync>> {
+        Ok(self.module().task_assignments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaskAssignmentsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn assign_with(
+        &self,
+        payload: &TaskAssignmentInput,
+        replace: bool,
+    ) -> TaskAssignmentsServiceResult<TaskAssignment> {
+        ensure_task_access(
+            self,
+            payload.task_id,
+            ProjectAccess::Write,
+            TaskAssignmentsServiceError::Forbidden,
+        )
+        .await?;
+        let repo = self.repo()?;
+        let assignment = repo
+            .assign(
+                payload.task_id,
+                payload.user_id,
+                replace,
+                self.claims()?.sub(),
+            )
+            .await
+            .map_err(map_assign_error)?;
+        notify_assignee(self.module(), &*repo, &assignment, replace).await;
+        Ok(assignment)
+    }
+
+    async fn list(
+        &self,
+        task_id: Uuid,
+    ) -> TaskAssignmentsServiceResult<Vec<TaskAssignmentResolved>> {
+        ensure_task_access(
+            self,
+            task_id,
+            ProjectAccess::Read,
+            TaskAssignmentsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(self.repo()?.get_by_task(task_id).await?)
+    }
+
+    async fn assign(
+        &self,
+        payload: &TaskAssignmentInput,
+    ) -> TaskAssignmentsServiceResult<TaskAssignment> {
+        self.assign_with(payload, false).await
+    }
+
+    async fn reassign(
+        &self,
+        payload: &TaskAssignmentInput,
+    ) -> TaskAssignmentsServiceResult<TaskAssignment> {
+        self.assign_with(payload, true).await
+    }
+
+    async fn unassign(&self, payload: &TaskAssignmentInput) -> TaskAssignmentsServiceResult<()> {
+        ensure_task_access(
+            self,
+            payload.task_id,
+            ProjectAccess::Write,
+            TaskAssignmentsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(self
+            .repo()?
+            .unassign(payload.task_id, payload.user_id)
+            .await?)
+    }
+
+    async fn get_preferences(&self) -> TaskAssignmentsServiceResult<TaskNotificationPreferences> {
+        Ok(self.repo()?.get_preferences(self.claims()?.sub()).await?)
+    }
+
+    async fn update_preferences(
+        &self,
+        payload: &TaskNotificationPreferencesInput,
+    ) -> TaskAssignmentsServiceResult<TaskNotificationPreferences> {
+        if !(0..=30).contains(&payload.reminder_days_before) {
+            return Err(TaskAssignmentsServiceError::UnprocessableEntry(
+                "Az emlékeztető a határidő előtt 0 és 30 nap között küldhető!",
+            ));
+        }
+        Ok(self
+            .repo()?
+            .update_preferences(self.claims()?.sub(), payload)
+            .await?)
+    }
+}
@@ -0,0 +1,532 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::task_checklist_items::TaskChecklistItemsModuleInterface;
+use crate::tenant::task_checklist_items::dto::{
+    CreateTaskChecklistItem, ReorderTaskChecklistItems, TaskChecklistItemsQuery,
+    UpdateTaskChecklistItem,
+};
+use crate::tenant::task_checklist_items::service::TaskChecklistItemsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: TaskChecklistItemsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_checklist_items_module): State<Arc<M>>,
+    Query(payload): Query<TaskChecklistItemsQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_checklist_items_module.clone());
+    let result = map_handler_err(
+        service.list(payload.task_id).await,
+        task_checklist_items_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_checklist_items_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: TaskChecklistItemsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_checklist_items_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateTaskChecklistItem>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_checklist_items_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        task_checklist_items_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        task_checklist_items_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: TaskChecklistItemsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_checklist_items_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateTaskChecklistItem>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_checklist_items_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        task_checklist_items_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_checklist_items_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reorder<M: TaskChecklistItemsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_checklist_items_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ReorderTaskChecklistItems>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_checklist_items_module.clone());
+    let result = map_handler_err(
+        service.reorder(&payload).await,
+        task_checklist_items_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_checklist_items_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: TaskChecklistItemsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_checklist_items_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_checklist_items_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        task_checklist_items_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az ellenőrzőlista elem törlése sikeresen megtörtént",
+            ))
+            .build(),
+        task_checklist_items_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::project_members::tests::project_access_repos;
+    use crate::tenant::task_checklist_items::model::{TaskChecklist, TaskChecklistItem};
+    use crate::tenant::task_checklist_items::{
+        self, repository::MockTaskChecklistItemsRepository, tests::MockTaskChecklistItemsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockTaskChecklistItemsRepository, active_tenant_id: Uuid) -> Router {
+        app_with_project_role(repo, active_tenant_id, Some("member"))
+    }
+
+    /// Every task belongs to a project, where the user holds `role`
+    fn app_with_project_role(
+        repo: MockTaskChecklistItemsRepository,
+        active_tenant_id: Uuid,
+        role: Option<&'static str>,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let (project_members_repo, membership_repo, permissions_repo) = project_access_repos(role);
+        let mut task_checklist_items_module = MockTaskChecklistItemsModule::new();
+        task_checklist_items_module
+            .expect_task_checklist_items_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        task_checklist_items_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        task_checklist_items_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        task_checklist_items_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        task_checklist_items_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(task_checklist_items::routes::routes(Arc::new(
+                task_checklist_items_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn checklist_item(task_id: Uuid, is_done: bool, position: f64) -> TaskChecklistItem {
+        TaskChecklistItem {
+            id: Uuid::new_v4(),
+            task_id,
+            title: "Alkatrész megrendelése".to_string(),
+            is_done,
+            position,
+            done_by_id: None,
+            done_at: None,
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_returns_completion() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let items = vec![
+            checklist_item(task_id, true, 1024.0),
+            checklist_item(task_id, false, 2048.0),
+        ];
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_task()
+            .with(eq(task_id))
+            .times(1)
+            .returning({
+                let items = items.clone();
+                move |_| Ok(items.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/task_checklist_items/list?task_id={task_id}"),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TaskChecklist =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body.items, items);
+        assert_eq!(body.done_count, 1);
+        assert_eq!(body.completion, Some(50));
+    }
+
+    #[tokio::test]
+    async fn test_create_trims_title() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let created = checklist_item(task_id, false, 1024.0);
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_task_exists()
+            .with(eq(task_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        repo.expect_insert()
+            .withf(move |id, title, sub| {
+                *id == task_id && title == "Alkatrész megrendelése" && *sub == user_id
+            })
+            .times(1)
+            .returning({
+                let created = created.clone();
+                move |_, _, _| Ok(created.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/task_checklist_items/create",
+                user_id,
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "title": "  Alkatrész megrendelése "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: TaskChecklistItem =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_empty_title() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_task_exists().never();
+        repo.expect_insert().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/task_checklist_items/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "title": "   "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_marks_item_done() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let updated = TaskChecklistItem {
+            done_by_id: Some(user_id),
+            done_at: Some(Utc::now()),
+            ..checklist_item(Uuid::new_v4(), true, 1024.0)
+        };
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(updated.id))
+            .times(1)
+            .returning({
+                let updated = updated.clone();
+                move |_| Ok(updated.clone())
+            });
+        repo.expect_update()
+            .withf({
+                let id = updated.id;
+                move |item_id, title, is_done, sub| {
+                    *item_id == id
+                        && title == "Alkatrész megrendelése"
+                        && *is_done
+                        && *sub == user_id
+                }
+            })
+            .times(1)
+            .returning({
+                let updated = updated.clone();
+                move |_, _, _, _| Ok(updated.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/task_checklist_items/update",
+                user_id,
+                tenant_id,
+                json!({
+                    "id": updated.id,
+                    "title": "Alkatrész megrendelése",
+                    "is_done": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TaskChecklistItem =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, updated);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_success() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let first = checklist_item(task_id, false, 1024.0);
+        let second = checklist_item(task_id, false, 2048.0);
+        let reordered = vec![
+            TaskChecklistItem {
+                position: 1024.0,
+                ..second.clone()
+            },
+            TaskChecklistItem {
+                position: 2048.0,
+                ..first.clone()
+            },
+        ];
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_task().times(1).returning({
+            let items = vec![first.clone(), second.clone()];
+            move |_| Ok(items.clone())
+        });
+        repo.expect_reorder()
+            .with(eq(task_id), eq(vec![second.id, first.id]))
+            .times(1)
+            .returning({
+                let reordered = reordered.clone();
+                move |_, _| Ok(reordered.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/task_checklist_items/reorder",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "item_ids": [second.id, first.id]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TaskChecklist =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body.items, reordered);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_rejects_incomplete_order() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let first = checklist_item(task_id, false, 1024.0);
+        let second = checklist_item(task_id, false, 2048.0);
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_task().times(1).returning({
+            let items = vec![first.clone(), second.clone()];
+            move |_| Ok(items.clone())
+        });
+        repo.expect_reorder().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/task_checklist_items/reorder",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "item_ids": [first.id, first.id]
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_is_forbidden_for_viewers() {
+        let tenant_id = Uuid::new_v4();
+        let item = checklist_item(Uuid::new_v4(), false, 1024.0);
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let item = item.clone();
+            move |_| Ok(item.clone())
+        });
+        repo.expect_update().never();
+
+        let response = app_with_project_role(repo, tenant_id, Some("viewer"))
+            .oneshot(request(
+                "PUT",
+                "/api/task_checklist_items/update",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "id": item.id,
+                    "title": "Alkatrész megrendelése",
+                    "is_done": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_list_is_forbidden_for_non_members() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTaskChecklistItemsRepository::new();
+        repo.expect_get_by_task().never();
+
+        let response = app_with_project_role(repo, tenant_id, None)
+            .oneshot(request(
+                "GET",
+                &format!("/api/task_checklist_items/list?task_id={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    pub is_done: bool,
+    pub position: f64,
+    pub done_by_id: Option<Uuid>,
+    pub done_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskChecklist {
+    pub items: Vec<TaskChecklistItem>,
+    pub done_count: i64,
+    /// Rounded down like the completion of the task lists, `None` for an empty checklist
+    pub completion: Option<i64>,
+}
+
+impl From<Vec<TaskChecklistItem>> for TaskChecklist {
+    fn from(items: Vec<TaskChecklistItem>) -> Self {
+        let total = items.len() as i64;
+        let done_count = items.iter().filter(|item| item.is_done).count() as i64;
+        TaskChecklist {
+            items,
+            done_count,
+            completion: (total > 0).then(|| done_count * 100 / total),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn item(is_done: bool) -> TaskChecklistItem {
+        TaskChecklistItem {
+            id: Uuid::new_v4(),
+            task_id: Uuid::nil(),
+            title: "Alkatrész megrendelése".to_string(),
+            is_done,
+            position: 1024.0,
+            done_by_id: None,
+            done_at: None,
+            created_by_id: Uuid::nil(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_completion() {
+        let checklist = TaskChecklist::from(vec![item(true), item(false), item(false)]);
+        assert_eq!(checklist.done_count, 1);
+        assert_eq!(checklist.completion, Some(33));
+
+        assert_eq!(TaskChecklist::from(vec![item(true)]).completion, Some(100));
+        assert_eq!(TaskChecklist::from(vec![]).completion, None);
+    }
+}
@@ -0,0 +1,199 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::task_checklist_items::model::TaskChecklistItem;
+use crate::tenant::tasks::model::POSITION_GAP;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TaskChecklistItemsRepository: Send + Sync {
+    async fn task_exists(&self, task_id: Uuid) -> RepositoryResult<bool>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskChecklistItem>;
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskChecklistItem>>;
+    async fn insert(
+        &self,
+        task_id: Uuid,
+        title: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskChecklistItem>;
+    async fn update(
+        &self,
+        id: Uuid,
+        title: &str,
+        is_done: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskChecklistItem>;
+    async fn reorder(
+        &self,
+        task_id: Uuid,
+        item_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<TaskChecklistItem>>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl TaskChecklistItemsRepository for PgPool {
+    async fn task_exists(&self, task_id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM tasks WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(task_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskChecklistItem> {
+        Ok(sqlx::query_as::<_, TaskChecklistItem>(
+            r#"
+            SELECT id, task_id, title, is_done, position, done_by_id, done_at, created_by_id,
+                   created_at, updated_at
+            FROM task_checklist_items
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskChecklistItem>> {
+        Ok(sqlx::query_as::<_, TaskChecklistItem>(
+            r#"
+            SELECT id, task_id, title, is_done, position, done_by_id, done_at, created_by_id,
+                   created_at, updated_at
+            FROM task_checklist_items
+            WHERE task_id = $1
+                AND deleted_at IS NULL
+            ORDER BY position, created_at
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        task_id: Uuid,
+        title: &str,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskChecklistItem> {
+        Ok(sqlx::query_as::<_, TaskChecklistItem>(
+            r#"
+            INSERT INTO task_checklist_items (task_id, title, position, created_by_id)
+            VALUES ($1, $2,
+                    COALESCE((SELECT MAX(position)
+                              FROM task_checklist_items
+                              WHERE task_id = $1
+                                  AND deleted_at IS NULL), 0) + $3,
+                    $4)
+            RETURNING id, task_id, title, is_done, position, done_by_id, done_at, created_by_id,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(task_id)
+        .bind(title)
+        .bind(POSITION_GAP)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    // NOTE: done_at and done_by_id are kept when a done item is saved again
+    async fn update(
+        &self,
+        id: Uuid,
+        title: &str,
+        is_done: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskChecklistItem> {
+        Ok(sqlx::query_as::<_, TaskChecklistItem>(
+            r#"
+            UPDATE task_checklist_items
+            SET title = $2,
+                is_done = $3,
+                done_at = CASE
+                    WHEN NOT $3 THEN NULL
+                    WHEN is_done THEN done_at
+                    ELSE NOW()
+                END,
+                done_by_id = CASE
+                    WHEN NOT $3 THEN NULL
+                    WHEN is_done THEN done_by_id
+                    ELSE $4
+                END
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING id, task_id, title, is_done, position, done_by_id, done_at, created_by_id,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(title)
+        .bind(is_done)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn reorder(
+        &self,
+        task_id: Uuid,
+        item_ids: Vec<Uuid>,
+    ) -> RepositoryResult<Vec<TaskChecklistItem>> {
+        sqlx::query(
+            r#"
+            UPDATE task_checklist_items
+            SET position = ordered.ordinality * $3
+            FROM unnest($2::UUID[]) WITH ORDINALITY AS ordered(id, ordinality)
+            WHERE task_checklist_items.id = ordered.id
+                AND task_checklist_items.task_id = $1
+                AND task_checklist_items.deleted_at IS NULL
+            "#,
+        )
+        .bind(task_id)
+        .bind(item_ids)
+        .bind(POSITION_GAP)
+        .execute(self)
+        .await?;
+        self.get_by_task(task_id).await
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE task_checklist_items
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+}
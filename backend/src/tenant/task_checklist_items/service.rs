@@ -0,0 +1,267 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::ensure_task_access;
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::task_checklist_items::TaskChecklistItemsModuleInterface;
+use crate::tenant::task_checklist_items::dto::{
+    CreateTaskChecklistItem, ReorderTaskChecklistItems, UpdateTaskChecklistItem,
+};
+use crate::tenant::task_checklist_items::model::{TaskChecklist, TaskChecklistItem};
+use crate::tenant::task_checklist_items::repository::TaskChecklistItemsRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const MAX_TITLE_LENGTH: usize = 255;
+
+#[derive(Debug, Error)]
+pub enum TaskChecklistItemsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TaskChecklistItemsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TaskChecklistItemsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TaskChecklistItemsServiceError> for AppError {
+    fn from(value: TaskChecklistItemsServiceError) -> Self {
+        match value {
+            TaskChecklistItemsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskChecklistItemsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            TaskChecklistItemsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskChecklistItemsServiceError::Repository(ref e)
+                if e.is_project_archived_violation() =>
+            {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": TaskChecklistItemsServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            TaskChecklistItemsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TaskChecklistItemsServiceResult<T> = Result<T, TaskChecklistItemsServiceError>;
+
+fn validate_title(title: &str) -> TaskChecklistItemsServiceResult<String> {
+    let title = title.trim();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(TaskChecklistItemsServiceError::UnprocessableEntry(
+            "Az ellenőrzőlista elem megnevezése nem lehet üres és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    Ok(title.to_string())
+}
+
+pub trait TaskChecklistItemsService {
+    fn list(
+        &self,
+        task_id: Uuid,
+    ) -> impl Future<Output = TaskChecklistItemsServiceResult<TaskChecklist>> + Send;
+    fn create(
+        &self,
+        payload: &CreateTaskChecklistItem,
+    ) -> impl Future<Output = TaskChecklistItemsServiceResult<TaskChecklistItem>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateTaskChecklistItem,
+    ) -> impl Future<Output = TaskChecklistItemsServiceResult<TaskChecklistItem>> + Send;
+    fn reorder(
+        &self,
+        payload: &ReorderTaskChecklistItems,
+    ) -> impl Future<Output = TaskChecklistItemsServiceResult<TaskChecklist>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = TaskChecklistItemsServiceResult<()>> + Send;
+    fn repo(
+        &self,
+    ) -> TaskChecklistItemsServiceResult<Arc<dyn TaskChecklistItemsRepository + Send + Sync>>;
+}
+
+impl<'a, T> TaskChecklistItemsService for Service<'a, T>
+where
+    T: TaskChecklistItemsModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> TaskChecklistItemsServiceResult<Arc<dyn TaskChecklistItemsRepository + Send + Sync>> {
+        Ok(self.module().task_checklist_items_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaskChecklistItemsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn list(&self, task_id: Uuid) -> TaskChecklistItemsServiceResult<TaskChecklist> {
+        ensure_task_access(
+            self,
+            task_id,
+            ProjectAccess::Read,
+            TaskChecklistItemsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(self.repo()?.get_by_task(task_id).await?.into())
+    }
+
+    async fn create(
+        &self,
+        payload: &CreateTaskChecklistItem,
+    ) -> TaskChecklistItemsServiceResult<TaskChecklistItem> {
+        let title = validate_title(&payload.title)?;
+        ensure_task_access(
+            self,
+            payload.task_id,
+            ProjectAccess::Write,
+            TaskChecklistItemsServiceError::Forbidden,
+        )
+        .await?;
+        let repo = self.repo()?;
+        if !repo.task_exists(payload.task_id).await? {
+            return Err(TaskChecklistItemsServiceError::UnprocessableEntry(
+                "A feladat nem található!",
+            ));
+        }
+        Ok(repo
+            .insert(payload.task_id, &title, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn update(
+        &self,
+        payload: &UpdateTaskChecklistItem,
+    ) -> TaskChecklistItemsServiceResult<TaskChecklistItem> {
+        let title = validate_title(&payload.title)?;
+        let repo = self.repo()?;
+        ensure_task_access(
+            self,
+            repo.get_by_id(payload.id).await?.task_id,
+            ProjectAccess::Write,
+            TaskChecklistItemsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(repo
+            .update(payload.id, &title, payload.is_done, self.claims()?.sub())
+            .await?)
+    }
+
+    // NOTE: a partial order would leave the positions ambiguous, so every item has to be listed
+    async fn reorder(
+        &self,
+        payload: &ReorderTaskChecklistItems,
+    ) -> TaskChecklistItemsServiceResult<TaskChecklist> {
+        ensure_task_access(
+            self,
+            payload.task_id,
+            ProjectAccess::Write,
+            TaskChecklistItemsServiceError::Forbidden,
+        )
+        .await?;
+        let repo = self.repo()?;
+        let current: HashSet<Uuid> = repo
+            .get_by_task(payload.task_id)
+            .await?
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        let requested: HashSet<Uuid> = payload.item_ids.iter().copied().collect();
+        if requested.len() != payload.item_ids.len() || requested != current {
+            return Err(TaskChecklistItemsServiceError::UnprocessableEntry(
+                "Az új sorrendnek a feladat összes ellenőrzőlista elemét pontosan egyszer kell tartalmaznia!",
+            ));
+        }
+        Ok(repo
+            .reorder(payload.task_id, payload.item_ids.clone())
+            .await?
+            .into())
+    }
+
+    async fn delete(&self, id: Uuid) -> TaskChecklistItemsServiceResult<()> {
+        let repo = self.repo()?;
+        ensure_task_access(
+            self,
+            repo.get_by_id(id).await?.task_id,
+            ProjectAccess::Write,
+            TaskChecklistItemsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(repo.delete_by_id(id).await?)
+    }
+}
@@ -0,0 +1,438 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::task_comments::TaskCommentsModuleInterface;
+use crate::tenant::task_comments::dto::{CreateTaskComment, TaskCommentsQuery, UpdateTaskComment};
+use crate::tenant::task_comments::service::TaskCommentsService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn list<M: TaskCommentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_comments_module): State<Arc<M>>,
+    Query(payload): Query<TaskCommentsQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_comments_module.clone());
+    let result = map_handler_err(
+        service.list(payload.task_id).await,
+        task_comments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_comments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: TaskCommentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_comments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateTaskComment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_comments_module.clone());
+    let result =
+        map_handler_err(service.create(&payload).await, task_comments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        task_comments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: TaskCommentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_comments_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateTaskComment>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_comments_module.clone());
+    let result =
+        map_handler_err(service.update(&payload).await, task_comments_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_comments_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: TaskCommentsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_comments_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_comments_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        task_comments_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A hozzászólás törlése sikeresen megtörtént",
+            ))
+            .build(),
+        task_comments_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::project_members::tests::project_access_repos;
+    use crate::tenant::task_comments::model::{TaskComment, TaskCommentThread};
+    use crate::tenant::task_comments::{
+        self, repository::MockTaskCommentsRepository, tests::MockTaskCommentsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockTaskCommentsRepository, active_tenant_id: Uuid) -> Router {
+        app_with_project_role(repo, active_tenant_id, Some("member"))
+    }
+
+    /// Every task belongs to a project, where the user holds `role`
+    fn app_with_project_role(
+        repo: MockTaskCommentsRepository,
+        active_tenant_id: Uuid,
+        role: Option<&'static str>,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let (project_members_repo, membership_repo, permissions_repo) = project_access_repos(role);
+        let mut task_comments_module = MockTaskCommentsModule::new();
+        task_comments_module
+            .expect_task_comments_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        task_comments_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        task_comments_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        task_comments_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        task_comments_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(task_comments::routes::routes(Arc::new(
+                task_comments_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn task_comment(task_id: Uuid, parent_id: Option<Uuid>, created_by_id: Uuid) -> TaskComment {
+        TaskComment {
+            id: Uuid::new_v4(),
+            task_id,
+            parent_id,
+            comment: Some("Megrendeltem az alkatrészt.".to_string()),
+            created_by_id,
+            created_by: "Kovács Anna".to_string(),
+            edited: false,
+            edited_at: None,
+            deleted: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_notifies_mentioned_users() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let created = task_comment(task_id, None, user_id);
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_task_exists()
+            .with(eq(task_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        repo.expect_insert()
+            .withf(move |input, mentions, sub| {
+                input.task_id == task_id
+                    && input.comment == "@Bela.Nagy @anna kérlek nézzétek meg"
+                    && *mentions == vec!["bela.nagy".to_string(), "anna".to_string()]
+                    && *sub == user_id
+            })
+            .times(1)
+            .returning({
+                let created = created.clone();
+                move |_, _, _| Ok(created.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/task_comments/create",
+                user_id,
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "parent_id": null,
+                    "comment": "  @Bela.Nagy @anna kérlek nézzétek meg "
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: TaskComment =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_reply_to_other_task() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let parent = task_comment(Uuid::new_v4(), None, Uuid::new_v4());
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_task_exists().times(1).returning(|_| Ok(true));
+        repo.expect_get_by_id()
+            .with(eq(parent.id))
+            .times(1)
+            .returning({
+                let parent = parent.clone();
+                move |_| Ok(parent.clone())
+            });
+        repo.expect_insert().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/task_comments/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "parent_id": parent.id,
+                    "comment": "Válasz"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_returns_threads() {
+        let tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let root = task_comment(task_id, None, Uuid::new_v4());
+        let reply = task_comment(task_id, Some(root.id), Uuid::new_v4());
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_get_by_task()
+            .with(eq(task_id))
+            .times(1)
+            .returning({
+                let comments = vec![root.clone(), reply.clone()];
+                move |_| Ok(comments.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/task_comments/list?task_id={task_id}"),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Vec<TaskCommentThread> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            body,
+            vec![TaskCommentThread {
+                comment: root,
+                replies: vec![TaskCommentThread {
+                    comment: reply,
+                    replies: vec![],
+                }],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_notifies_only_new_mentions() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let current = TaskComment {
+            comment: Some("@anna nézd meg".to_string()),
+            ..task_comment(Uuid::new_v4(), None, user_id)
+        };
+        let updated = TaskComment {
+            comment: Some("@anna és @bela nézzétek meg".to_string()),
+            edited: true,
+            edited_at: Some(Utc::now()),
+            ..current.clone()
+        };
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(current.id))
+            .times(1)
+            .returning({
+                let current = current.clone();
+                move |_| Ok(current.clone())
+            });
+        repo.expect_update()
+            .withf({
+                let id = current.id;
+                move |comment_id, comment, mentions, _| {
+                    *comment_id == id
+                        && comment == "@anna és @bela nézzétek meg"
+                        && *mentions == vec!["bela".to_string()]
+                }
+            })
+            .times(1)
+            .returning({
+                let updated = updated.clone();
+                move |_, _, _, _| Ok(updated.clone())
+            });
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/task_comments/update",
+                user_id,
+                tenant_id,
+                json!({
+                    "id": current.id,
+                    "comment": "@anna és @bela nézzétek meg"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TaskComment =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, updated);
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_other_user_is_forbidden() {
+        let tenant_id = Uuid::new_v4();
+        let current = task_comment(Uuid::new_v4(), None, Uuid::new_v4());
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_get_by_id().times(1).returning({
+            let current = current.clone();
+            move |_| Ok(current.clone())
+        });
+        repo.expect_delete_by_id().never();
+
+        let response = app(repo, tenant_id)
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/task_comments/delete?uuid={}", current.id),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_list_is_forbidden_for_non_members() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTaskCommentsRepository::new();
+        repo.expect_get_by_task().never();
+
+        let response = app_with_project_role(repo, tenant_id, None)
+            .oneshot(request(
+                "GET",
+                &format!("/api/task_comments/list?task_id={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const COMMENTABLE_TYPE: &str = "tasks";
+
+/// A deleted comment keeps its place in the thread without its text
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub comment: Option<String>,
+    pub created_by_id: Uuid,
+    pub created_by: String,
+    pub edited: bool,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskCommentThread {
+    #[serde(flatten)]
+    pub comment: TaskComment,
+    pub replies: Vec<TaskCommentThread>,
+}
+
+impl TaskCommentThread {
+    /// Builds the reply trees from the comments ordered by creation time, deleted comments are
+    /// only kept while they still have replies
+    pub fn build(comments: Vec<TaskComment>) -> Vec<TaskCommentThread> {
+        let mut children: HashMap<Option<Uuid>, Vec<TaskComment>> = HashMap::new();
+        for comment in comments {
+            children.entry(comment.parent_id).or_default().push(comment);
+        }
+        Self::children_of(None, &mut children)
+    }
+
+    fn children_of(
+        parent_id: Option<Uuid>,
+        children: &mut HashMap<Option<Uuid>, Vec<TaskComment>>,
+    ) -> Vec<TaskCommentThread> {
+        children
+            .remove(&parent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|comment| {
+                let replies = Self::children_of(Some(comment.id), children);
+                (!comment.deleted || !replies.is_empty())
+                    .then_some(TaskCommentThread { comment, replies })
+            })
+            .collect()
+    }
+}
+
+/// Collects the `@name` mentions of a comment, lowercased and without duplicates. The name is
+/// matched against the part of the user's email address before the `@`.
+pub fn parse_mentions(comment: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = comment.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let starts_mention = c == '@' && !previous.is_some_and(|p| p.is_alphanumeric());
+        previous = Some(c);
+        if !starts_mention {
+            continue;
+        }
+        let start = index + c.len_utf8();
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !(next.is_ascii_alphanumeric() || matches!(next, '.' | '_' | '-' | '+')) {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let name = comment[start..end].trim_end_matches('.').to_lowercase();
+        if !name.is_empty() && !mentions.contains(&name) {
+            mentions.push(name);
+        }
+    }
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn comment(id: Uuid, parent_id: Option<Uuid>, deleted: bool) -> TaskComment {
+        TaskComment {
+            id,
+            task_id: Uuid::nil(),
+            parent_id,
+            comment: (!deleted).then(|| "Rendben".to_string()),
+            created_by_id: Uuid::nil(),
+            created_by: "Teszt Elek".to_string(),
+            edited: false,
+            edited_at: None,
+            deleted,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_mentions() {
+        assert_eq!(
+            parse_mentions("@Kovacs.Anna kérlek nézd meg, @bela és @kovacs.anna. info@example.com"),
+            vec!["kovacs.anna".to_string(), "bela".to_string()]
+        );
+        assert!(parse_mentions("nincs említés @ itt").is_empty());
+    }
+
+    #[test]
+    fn test_build_keeps_deleted_comments_with_replies_only() {
+        let root = Uuid::new_v4();
+        let reply = Uuid::new_v4();
+        let deleted_leaf = Uuid::new_v4();
+        let threads = TaskCommentThread::build(vec![
+            comment(root, None, true),
+            comment(reply, Some(root), false),
+            comment(deleted_leaf, None, true),
+        ]);
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].comment.id, root);
+        assert_eq!(threads[0].replies.len(), 1);
+        assert_eq!(threads[0].replies[0].comment.id, reply);
+    }
+}
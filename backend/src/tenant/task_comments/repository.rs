@@ -0,0 +1,209 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::task_comments::dto::CreateTaskComment;
+use crate::tenant::task_comments::model::{COMMENTABLE_TYPE, TaskComment};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TaskCommentsRepository: Send + Sync {
+    async fn task_exists(&self, task_id: Uuid) -> RepositoryResult<bool>;
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskComment>>;
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskComment>;
+    async fn insert(
+        &self,
+        input: &CreateTaskComment,
+        mentions: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskComment>;
+    async fn update(
+        &self,
+        id: Uuid,
+        comment: &str,
+        mentions: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskComment>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+}
+
+const TASK_COMMENT_SELECT: &str = r#"
+    SELECT comments.id,
+           comments.commentable_id AS task_id,
+           comments.parent_id,
+           CASE WHEN comments.deleted_at IS NULL THEN comments.comment END AS comment,
+           comments.created_by_id,
+           users.last_name || ' ' || users.first_name AS created_by,
+           comments.edited_at IS NOT NULL AS edited,
+           comments.edited_at,
+           comments.deleted_at IS NOT NULL AS deleted,
+           comments.created_at
+    FROM comments
+    JOIN users ON comments.created_by_id = users.id
+    WHERE comments.commentable_type = 'tasks'
+"#;
+
+/// Mentioned users are notified through the watch notifications, the author is never notified
+/// about their own comment
+async fn notify_mentions(
+    tx: &mut Transaction<'_, Postgres>,
+    task_id: Uuid,
+    mentions: Vec<String>,
+    sub: Uuid,
+) -> RepositoryResult<()> {
+    if mentions.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+        SELECT users.id, $1, $2, 'mentioned'
+        FROM users
+        WHERE lower(split_part(users.email, '@', 1)) = ANY($3)
+            AND users.id <> $4
+            AND users.deleted_at IS NULL
+        "#,
+    )
+    .bind(COMMENTABLE_TYPE)
+    .bind(task_id)
+    .bind(mentions)
+    .bind(sub)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl TaskCommentsRepository for PgPool {
+    async fn task_exists(&self, task_id: Uuid) -> RepositoryResult<bool> {
+        Ok(sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM tasks WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(task_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_by_task(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskComment>> {
+        Ok(sqlx::query_as::<_, TaskComment>(AssertSqlSafe(format!(
+            "{TASK_COMMENT_SELECT} AND comments.commentable_id = $1 ORDER BY comments.created_at" // Security: constant
+        )))
+        .bind(task_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskComment> {
+        Ok(sqlx::query_as::<_, TaskComment>(AssertSqlSafe(format!(
+            "{TASK_COMMENT_SELECT} AND comments.id = $1 AND comments.deleted_at IS NULL" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert(
+        &self,
+        input: &CreateTaskComment,
+        mentions: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskComment> {
+        let mut tx = self.begin().await?;
+        let id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO comments (id, commentable_type, commentable_id, parent_id, comment,
+                                  created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(COMMENTABLE_TYPE)
+        .bind(input.task_id)
+        .bind(input.parent_id)
+        .bind(&input.comment)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        notify_mentions(&mut tx, input.task_id, mentions, sub).await?;
+        let task_comment = sqlx::query_as::<_, TaskComment>(AssertSqlSafe(format!(
+            "{TASK_COMMENT_SELECT} AND comments.id = $1" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(task_comment)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        comment: &str,
+        mentions: Vec<String>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskComment> {
+        let mut tx = self.begin().await?;
+        let task_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE comments
+            SET comment = $1,
+                edited_at = NOW()
+            WHERE id = $2
+                AND commentable_type = 'tasks'
+                AND deleted_at IS NULL
+            RETURNING commentable_id
+            "#,
+        )
+        .bind(comment)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        notify_mentions(&mut tx, task_id, mentions, sub).await?;
+        let task_comment = sqlx::query_as::<_, TaskComment>(AssertSqlSafe(format!(
+            "{TASK_COMMENT_SELECT} AND comments.id = $1" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(task_comment)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE comments
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND commentable_type = 'tasks'
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+}
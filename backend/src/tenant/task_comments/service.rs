@@ -0,0 +1,250 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::ensure_task_access;
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::task_comments::TaskCommentsModuleInterface;
+use crate::tenant::task_comments::dto::{CreateTaskComment, UpdateTaskComment};
+use crate::tenant::task_comments::model::{TaskComment, TaskCommentThread, parse_mentions};
+use crate::tenant::task_comments::repository::TaskCommentsRepository;
+use axum::http::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const MAX_COMMENT_LENGTH: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum TaskCommentsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TaskCommentsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TaskCommentsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TaskCommentsServiceError> for AppError {
+    fn from(value: TaskCommentsServiceError) -> Self {
+        match value {
+            TaskCommentsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskCommentsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            TaskCommentsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskCommentsServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": TaskCommentsServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            TaskCommentsServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TaskCommentsServiceResult<T> = Result<T, TaskCommentsServiceError>;
+
+fn validate_comment(comment: &str) -> TaskCommentsServiceResult<String> {
+    let comment = comment.trim();
+    if comment.is_empty() || comment.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(TaskCommentsServiceError::UnprocessableEntry(
+            "A hozzászólás nem lehet üres és legfeljebb 10 000 karakter lehet!",
+        ));
+    }
+    Ok(comment.to_string())
+}
+
+fn ensure_author(task_comment: &TaskComment, sub: Uuid) -> TaskCommentsServiceResult<()> {
+    if task_comment.created_by_id != sub {
+        return Err(TaskCommentsServiceError::Forbidden);
+    }
+    Ok(())
+}
+
+pub trait TaskCommentsService {
+    fn list(
+        &self,
+        task_id: Uuid,
+    ) -> impl Future<Output = TaskCommentsServiceResult<Vec<TaskCommentThread>>> + Send;
+    fn create(
+        &self,
+        payload: &CreateTaskComment,
+    ) -> impl Future<Output = TaskCommentsServiceResult<TaskComment>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateTaskComment,
+    ) -> impl Future<Output = TaskCommentsServiceResult<TaskComment>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = TaskCommentsServiceResult<()>> + Send;
+    fn repo(&self) -> TaskCommentsServiceResult<Arc<dyn TaskCommentsRepository + Send + Sync>>;
+}
+
+impl<'a, T> TaskCommentsService for Service<'a, T>
+where
+    T: TaskCommentsModuleInterface,
+{
+    fn repo(&self) -> TaskCommentsServiceResult<Arc<dyn TaskCommentsRepository + Send + Sync>> {
+        Ok(self.module().task_comments_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaskCommentsServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn list(&self, task_id: Uuid) -> TaskCommentsServiceResult<Vec<TaskCommentThread>> {
+        ensure_task_access(
+            self,
+            task_id,
+            ProjectAccess::Read,
+            TaskCommentsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(TaskCommentThread::build(
+            self.repo()?.get_by_task(task_id).await?,
+        ))
+    }
+
+    async fn create(&self, payload: &CreateTaskComment) -> TaskCommentsServiceResult<TaskComment> {
+        let comment = validate_comment(&payload.comment)?;
+        ensure_task_access(
+            self,
+            payload.task_id,
+            ProjectAccess::Write,
+            TaskCommentsServiceError::Forbidden,
+        )
+        .await?;
+        let repo = self.repo()?;
+        if !repo.task_exists(payload.task_id).await? {
+            return Err(TaskCommentsServiceError::UnprocessableEntry(
+                "A feladat nem található!",
+            ));
+        }
+        if let Some(parent_id) = payload.parent_id
+            && repo.get_by_id(parent_id).await?.task_id != payload.task_id
+        {
+            return Err(TaskCommentsServiceError::UnprocessableEntry(
+                "A megválaszolt hozzászólás egy másik feladathoz tartozik!",
+            ));
+        }
+        let mentions = parse_mentions(&comment);
+        Ok(repo
+            .insert(
+                &CreateTaskComment {
+                    comment,
+                    ..payload.clone()
+                },
+                mentions,
+                self.claims()?.sub(),
+            )
+            .await?)
+    }
+
+    // NOTE: only the users newly mentioned by the edit are notified
+    async fn update(&self, payload: &UpdateTaskComment) -> TaskCommentsServiceResult<TaskComment> {
+        let comment = validate_comment(&payload.comment)?;
+        let sub = self.claims()?.sub();
+        let repo = self.repo()?;
+        let current = repo.get_by_id(payload.id).await?;
+        ensure_author(&current, sub)?;
+        ensure_task_access(
+            self,
+            current.task_id,
+            ProjectAccess::Write,
+            TaskCommentsServiceError::Forbidden,
+        )
+        .await?;
+        let already_mentioned = parse_mentions(current.comment.as_deref().unwrap_or_default());
+        let mentions = parse_mentions(&comment)
+            .into_iter()
+            .filter(|mention| !already_mentioned.contains(mention))
+            .collect();
+        Ok(repo.update(current.id, &comment, mentions, sub).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> TaskCommentsServiceResult<()> {
+        let repo = self.repo()?;
+        let current = repo.get_by_id(id).await?;
+        ensure_author(&current, self.claims()?.sub())?;
+        ensure_task_access(
+            self,
+            current.task_id,
+            ProjectAccess::Write,
+            TaskCommentsServiceError::Forbidden,
+        )
+        .await?;
+        Ok(repo.delete_by_id(id).await?)
+    }
+}
@@ -0,0 +1,541 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::task_recurrences::TaskRecurrencesModuleInterface;
+use crate::tenant::task_recurrences::dto::TaskRecurrenceInput;
+use crate::tenant::task_recurrences::service::TaskRecurrencesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn get<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(service.list().await, task_recurrences_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskRecurrenceInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskRecurrenceInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "Az ismétlődés törlése sikeresen megtörtént",
+            ))
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn pause<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.pause(payload.uuid).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn resume<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.resume(payload.uuid).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn occurrences<M: TaskRecurrencesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(task_recurrences_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), task_recurrences_module.clone());
+    let result = map_handler_err(
+        service.occurrences(payload.uuid).await,
+        task_recurrences_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        task_recurrences_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::{MockUniqueViolation, generate_valid_jwt};
+    use crate::tenant::project_members::tests::project_access_repos;
+    use crate::tenant::task_recurrences::model::{TaskOccurrence, TaskRecurrence};
+    use crate::tenant::task_recurrences::{
+        self, repository::MockTaskRecurrencesRepository, tests::MockTaskRecurrencesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::{Datelike, Days, Months, NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use sqlx::error::DatabaseError;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn module(
+        repo: MockTaskRecurrencesRepository,
+        active_tenant_id: Uuid,
+    ) -> MockTaskRecurrencesModule {
+        module_with_project_role(repo, active_tenant_id, Some("member"))
+    }
+
+    /// Every template task belongs to a project, where the user holds `role`
+    fn module_with_project_role(
+        repo: MockTaskRecurrencesRepository,
+        active_tenant_id: Uuid,
+        role: Option<&'static str>,
+    ) -> MockTaskRecurrencesModule {
+        let repo = Arc::new(repo);
+        let (project_members_repo, membership_repo, permissions_repo) = project_access_repos(role);
+        let mut task_recurrences_module = MockTaskRecurrencesModule::new();
+        task_recurrences_module
+            .expect_task_recurrences_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        task_recurrences_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        task_recurrences_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        task_recurrences_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        task_recurrences_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        task_recurrences_module
+    }
+
+    fn app(task_recurrences_module: MockTaskRecurrencesModule) -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().merge(task_recurrences::routes::routes(Arc::new(
+                task_recurrences_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn recurrence(status: &str, next_occurrence_date: NaiveDate) -> TaskRecurrence {
+        TaskRecurrence {
+            id: Uuid::new_v4(),
+            template_task_id: Uuid::new_v4(),
+            rule: "FREQ=DAILY;INTERVAL=3".to_string(),
+            generate_on: "schedule".to_string(),
+            start_date: next_occurrence_date - Days::new(30),
+            next_occurrence_date: Some(next_occurrence_date),
+            occurrence_count: 10,
+            status: status.to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn payload(rule: &str, generate_on: &str, start_date: NaiveDate) -> serde_json::Value {
+        json!({
+            "id": null,
+            "template_task_id": Uuid::new_v4(),
+            "rule": rule,
+            "generate_on": generate_on,
+            "start_date": start_date,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_normalizes_rule_and_schedules_first_occurrence() {
+        let active_tenant_id = Uuid::new_v4();
+        // NOTE: the first day of a month is always an occurrence of the rule below
+        let start_date = (Utc::now().date_naive() + Months::new(1))
+            .with_day(1)
+            .unwrap();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_insert()
+            .times(1)
+            .withf(move |input, next_occurrence_date, _| {
+                input.rule == "FREQ=MONTHLY;BYMONTHDAY=1,15"
+                    && *next_occurrence_date == Some(start_date)
+            })
+            .returning(move |_, _, _| Ok(recurrence("active", start_date)));
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "POST",
+                "/api/task_recurrences/create",
+                active_tenant_id,
+                Some(payload(
+                    "RRULE:freq=monthly;bymonthday=15,1",
+                    "schedule",
+                    start_date,
+                )),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_rule() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "POST",
+                "/api/task_recurrences/create",
+                active_tenant_id,
+                Some(payload("FREQ=HOURLY", "schedule", Utc::now().date_naive())),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_second_recurrence_for_task() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_insert().times(1).returning(|_, _, _| {
+            Err(RepositoryError::Database(sqlx::Error::Database(
+                Box::new(MockUniqueViolation) as Box<dyn DatabaseError>,
+            )))
+        });
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "POST",
+                "/api/task_recurrences/create",
+                active_tenant_id,
+                Some(payload(
+                    "FREQ=WEEKLY",
+                    "completion",
+                    Utc::now().date_naive(),
+                )),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_pause_rejects_ended_recurrence() {
+        let active_tenant_id = Uuid::new_v4();
+        let recurrence = recurrence("ended", Utc::now().date_naive());
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurrence.id))
+            .returning({
+                let recurrence = recurrence.clone();
+                move |_| Ok(recurrence.clone())
+            });
+        repo.expect_set_schedule().never();
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "PUT",
+                "/api/task_recurrences/pause",
+                active_tenant_id,
+                Some(json!({"uuid": recurrence.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_resume_does_not_generate_missed_occurrences() {
+        let active_tenant_id = Uuid::new_v4();
+        let today = Utc::now().date_naive();
+        let recurrence = recurrence("paused", today - Days::new(9));
+        let expected = recurrence.next_occurrence_from(today);
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurrence.id))
+            .returning({
+                let recurrence = recurrence.clone();
+                move |_| Ok(recurrence.clone())
+            });
+        repo.expect_set_schedule()
+            .times(1)
+            .with(eq(recurrence.id), eq("active"), eq(expected))
+            .returning({
+                let recurrence = recurrence.clone();
+                move |_, _, _| Ok(recurrence.clone())
+            });
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "PUT",
+                "/api/task_recurrences/resume",
+                active_tenant_id,
+                Some(json!({"uuid": recurrence.id})),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(expected.unwrap() >= today);
+    }
+
+    #[tokio::test]
+    async fn test_occurrences_lists_generated_tasks() {
+        let active_tenant_id = Uuid::new_v4();
+        let recurrence = recurrence("active", Utc::now().date_naive());
+        let occurrence = TaskOccurrence {
+            task_id: Uuid::new_v4(),
+            occurrence_date: Utc::now().date_naive(),
+            status: "pending".to_string(),
+            created_at: Utc::now(),
+        };
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(recurrence.id))
+            .returning({
+                let recurrence = recurrence.clone();
+                move |_| Ok(recurrence.clone())
+            });
+        repo.expect_get_occurrences()
+            .times(1)
+            .with(eq(recurrence.id))
+            .returning({
+                let occurrence = occurrence.clone();
+                move |_| Ok(vec![occurrence.clone()])
+            });
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "GET",
+                &format!("/api/task_recurrences/occurrences?uuid={}", recurrence.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(module_with_project_role(repo, active_tenant_id, None))
+            .oneshot(request(
+                "POST",
+                "/api/task_recurrences/create",
+                active_tenant_id,
+                Some(payload("FREQ=DAILY", "schedule", Utc::now().date_naive())),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_list_is_restricted_to_member_projects() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_get_all()
+            .times(1)
+            .withf(|visible_to| visible_to.is_some())
+            .returning(|_| Ok(vec![]));
+
+        let response = app(module(repo, active_tenant_id))
+            .oneshot(request(
+                "GET",
+                "/api/task_recurrences/list",
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
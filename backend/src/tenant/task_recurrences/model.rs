@@ -0,0 +1,80 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::tenant::task_recurrences::rule::RecurrenceRule;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const GENERATE_ON_SCHEDULE: &str = "schedule";
+pub const GENERATE_ON_COMPLETION: &str = "completion";
+
+pub const STATUS_ACTIVE: &str = "active";
+pub const STATUS_PAUSED: &str = "paused";
+pub const STATUS_ENDED: &str = "ended";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskRecurrence {
+    pub id: Uuid,
+    pub template_task_id: Uuid,
+    pub rule: String,
+    pub generate_on: String,
+    pub start_date: NaiveDate,
+    pub next_occurrence_date: Option<NaiveDate>,
+    pub occurrence_count: i32,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl TaskRecurrence {
+    /// First occurrence on or after `from`, `None` once the rule is exhausted either by its end
+    /// date or by the number of occurrences already generated.
+    pub fn next_occurrence_from(&self, from: NaiveDate) -> Option<NaiveDate> {
+        let rule = self.rule.parse::<RecurrenceRule>().ok()?;
+        if rule
+            .count
+            .is_some_and(|count| i64::from(self.occurrence_count) >= i64::from(count))
+        {
+            return None;
+        }
+        rule.next_occurrence(self.start_date, from)
+    }
+
+    /// Occurrence following the one generated for `occurrence_date`, the generated one is
+    /// already taken into account for the number of occurrences.
+    pub fn following_occurrence(&self, occurrence_date: NaiveDate) -> Option<NaiveDate> {
+        TaskRecurrence {
+            occurrence_count: self.occurrence_count + 1,
+            ..self.clone()
+        }
+        .next_occurrence_from(occurrence_date.succ_opt()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskOccurrence {
+    pub task_id: Uuid,
+    pub occurrence_date: NaiveDate,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,298 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::copy_tags;
+use crate::common::error::RepositoryResult;
+use crate::tenant::project_members::repository::visible_project_condition;
+use crate::tenant::task_recurrences::dto::TaskRecurrenceInput;
+use crate::tenant::task_recurrences::model::{TaskOccurrence, TaskRecurrence};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TaskRecurrencesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskRecurrence>;
+    async fn get_all(&self, visible_to: Option<Uuid>) -> RepositoryResult<Vec<TaskRecurrence>>;
+    async fn insert(
+        &self,
+        input: &TaskRecurrenceInput,
+        next_occurrence_date: Option<NaiveDate>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskRecurrence>;
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &TaskRecurrenceInput,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskRecurrence>;
+    async fn set_schedule(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskRecurrence>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_occurrences(&self, id: Uuid) -> RepositoryResult<Vec<TaskOccurrence>>;
+    async fn get_due_scheduled(&self, today: NaiveDate) -> RepositoryResult<Vec<TaskRecurrence>>;
+    async fn get_completed(&self) -> RepositoryResult<Vec<TaskRecurrence>>;
+    async fn create_occurrence(
+        &self,
+        recurrence: &TaskRecurrence,
+        occurrence_date: NaiveDate,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskOccurrence>;
+}
+
+#[async_trait]
+impl TaskRecurrencesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TaskRecurrence> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            "SELECT * FROM task_recurrences WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_all(&self, visible_to: Option<Uuid>) -> RepositoryResult<Vec<TaskRecurrence>> {
+        let visible_condition = visible_project_condition("worksheets.project_id", 1);
+        Ok(sqlx::query_as::<_, TaskRecurrence>(AssertSqlSafe(format!(
+            r#"
+            SELECT task_recurrences.*
+            FROM task_recurrences
+            JOIN tasks ON task_recurrences.template_task_id = tasks.id
+            LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
+            WHERE task_recurrences.deleted_at IS NULL
+                AND {visible_condition}
+            ORDER BY task_recurrences.created_at
+            "# // Security: constant
+        )))
+        .bind(visible_to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    // NOTE: occurrences cannot serve as templates themselves, unknown templates give RowNotFound
+    async fn insert(
+        &self,
+        input: &TaskRecurrenceInput,
+        next_occurrence_date: Option<NaiveDate>,
+        sub: Uuid,
+    ) -> RepositoryResult<TaskRecurrence> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            r#"
+            INSERT INTO task_recurrences (template_task_id, rule, generate_on, start_date,
+                                          next_occurrence_date, created_by_id)
+            SELECT tasks.id, $2, $3, $4, $5, $6
+            FROM tasks
+            WHERE tasks.id = $1
+                AND tasks.recurrence_id IS NULL
+                AND tasks.deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(input.template_task_id)
+        .bind(&input.rule)
+        .bind(&input.generate_on)
+        .bind(input.start_date)
+        .bind(next_occurrence_date)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &TaskRecurrenceInput,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskRecurrence> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            r#"
+            UPDATE task_recurrences
+            SET rule = $2,
+                generate_on = $3,
+                start_date = $4,
+                next_occurrence_date = $5,
+                status = CASE
+                    WHEN $5::DATE IS NULL THEN 'ended'
+                    WHEN status = 'ended' THEN 'active'
+                    ELSE status
+                END
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&input.rule)
+        .bind(&input.generate_on)
+        .bind(input.start_date)
+        .bind(next_occurrence_date)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn set_schedule(
+        &self,
+        id: Uuid,
+        status: &str,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskRecurrence> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            r#"
+            UPDATE task_recurrences
+            SET status = $2,
+                next_occurrence_date = $3
+            WHERE id = $1
+                AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(next_occurrence_date)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            "UPDATE task_recurrences SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn get_occurrences(&self, id: Uuid) -> RepositoryResult<Vec<TaskOccurrence>> {
+        Ok(sqlx::query_as::<_, TaskOccurrence>(
+            r#"
+            SELECT id as task_id, occurrence_date, status, created_at
+            FROM tasks
+            WHERE recurrence_id = $1
+                AND deleted_at IS NULL
+            ORDER BY occurrence_date DESC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_due_scheduled(&self, today: NaiveDate) -> RepositoryResult<Vec<TaskRecurrence>> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            r#"
+            SELECT *
+            FROM task_recurrences
+            WHERE status = 'active'
+                AND generate_on = 'schedule'
+                AND next_occurrence_date <= $1
+                AND deleted_at IS NULL
+            ORDER BY next_occurrence_date
+            "#,
+        )
+        .bind(today)
+        .fetch_all(self)
+        .await?)
+    }
+
+    /// Recurrences generated on completion whose template and every occurrence are done
+    async fn get_completed(&self) -> RepositoryResult<Vec<TaskRecurrence>> {
+        Ok(sqlx::query_as::<_, TaskRecurrence>(
+            r#"
+            SELECT task_recurrences.*
+            FROM task_recurrences
+            WHERE task_recurrences.status = 'active'
+                AND task_recurrences.generate_on = 'completion'
+                AND task_recurrences.next_occurrence_date IS NOT NULL
+                AND task_recurrences.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM tasks
+                    WHERE (tasks.id = task_recurrences.template_task_id
+                            OR tasks.recurrence_id = task_recurrences.id)
+                        AND tasks.status <> 'done'
+                        AND tasks.deleted_at IS NULL
+                )
+            "#,
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn create_occurrence(
+        &self,
+        recurrence: &TaskRecurrence,
+        occurrence_date: NaiveDate,
+        next_occurrence_date: Option<NaiveDate>,
+    ) -> RepositoryResult<TaskOccurrence> {
+        let mut tx = self.begin().await?;
+        let occurrence = sqlx::query_as::<_, TaskOccurrence>(
+            r#"
+            INSERT INTO tasks (worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                               created_by_id, status, priority, due_date, description,
+                               recurrence_id, occurrence_date)
+            SELECT worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                   $2, 'pending', priority, $4::DATE, description, $3, $4
+            FROM tasks
+            WHERE id = $1
+            RETURNING id as task_id, occurrence_date, status, created_at
+            "#,
+        )
+        .bind(recurrence.template_task_id)
+        .bind(recurrence.created_by_id)
+        .bind(recurrence.id)
+        .bind(occurrence_date)
+        .fetch_one(&mut *tx)
+        .await?;
+        copy_tags(
+            &mut tx,
+            "tasks",
+            recurrence.template_task_id,
+            occurrence.task_id,
+            recurrence.created_by_id,
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE task_recurrences
+            SET next_occurrence_date = $2,
+                occurrence_count = occurrence_count + 1,
+                status = CASE WHEN $2::DATE IS NULL THEN 'ended' ELSE status END
+            WHERE id = $1
+            "#,
+        )
+        .bind(recurrence.id)
+        .bind(next_occurrence_date)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(occurrence)
+    }
+}
@@ -0,0 +1,315 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const MAX_INTERVAL: u32 = 99;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// The subset of the iCalendar RRULE used for recurring tasks: FREQ, INTERVAL, BYDAY (weekly
+/// rules only), BYMONTHDAY (monthly rules only) and either COUNT or UNTIL. Occurrences are
+/// counted from the start date of the recurrence, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Vec<u32>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    date.with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+fn months_between(from: NaiveDate, to: NaiveDate) -> i64 {
+    i64::from(to.year() - from.year()) * 12 + i64::from(to.month()) - i64::from(from.month())
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = vec![];
+        let mut by_month_day = vec![];
+        let mut count = None;
+        let mut until = None;
+        for part in s.split(';').filter(|part| !part.trim().is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or("Hibás ismétlődési szabály formátum!")?;
+            let value = value.trim().to_uppercase();
+            match key.trim().to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err("Az ismétlődés gyakorisága nem támogatott!"),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|interval| (1..=MAX_INTERVAL).contains(interval))
+                        .ok_or("Az ismétlődés köze 1 és 99 között lehet!")?
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(|day| parse_weekday(day.trim()))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or("Hibás nap megadás az ismétlődési szabályban!")?
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = value
+                        .split(',')
+                        .map(|day| {
+                            day.trim()
+                                .parse::<u32>()
+                                .ok()
+                                .filter(|day| (1..=31).contains(day))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or("A hónap napja 1 és 31 között lehet!")?
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or("Az előfordulások száma pozitív egész szám kell legyen!")?,
+                    )
+                }
+                "UNTIL" => {
+                    until = Some(
+                        value
+                            .get(..8)
+                            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+                            .ok_or("Hibás záró dátum az ismétlődési szabályban!")?,
+                    )
+                }
+                _ => return Err("Az ismétlődési szabály nem támogatott elemet tartalmaz!"),
+            }
+        }
+        let frequency = frequency.ok_or("Az ismétlődés gyakoriságának megadása kötelező!")?;
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err("Napok csak heti ismétlődésnél adhatók meg!");
+        }
+        if !by_month_day.is_empty() && frequency != Frequency::Monthly {
+            return Err("A hónap napjai csak havi ismétlődésnél adhatók meg!");
+        }
+        if count.is_some() && until.is_some() {
+            return Err("Az előfordulások száma és a záró dátum nem adható meg egyszerre!");
+        }
+        by_day.sort_by_key(Weekday::num_days_from_monday);
+        by_day.dedup();
+        by_month_day.sort_unstable();
+        by_month_day.dedup();
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            count,
+            until,
+        })
+    }
+}
+
+impl Display for RecurrenceRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FREQ={}", self.frequency.as_str())?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|day| weekday_code(*day)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(u32::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        Ok(())
+    }
+}
+
+impl RecurrenceRule {
+    fn matches(&self, start_date: NaiveDate, date: NaiveDate) -> bool {
+        let interval = i64::from(self.interval);
+        match self.frequency {
+            Frequency::Daily => (date - start_date).num_days() % interval == 0,
+            Frequency::Weekly => {
+                let weeks = (date.week(Weekday::Mon).first_day()
+                    - start_date.week(Weekday::Mon).first_day())
+                .num_days()
+                    / 7;
+                let weekday_matches = if self.by_day.is_empty() {
+                    date.weekday() == start_date.weekday()
+                } else {
+                    self.by_day.contains(&date.weekday())
+                };
+                weeks % interval == 0 && weekday_matches
+            }
+            // NOTE: without BYMONTHDAY a start on the 31st falls on the last day of shorter
+            // months, like the recurring invoice schedules
+            Frequency::Monthly => {
+                let day_matches = if self.by_month_day.is_empty() {
+                    date.day() == start_date.day().min(last_day_of_month(date))
+                } else {
+                    self.by_month_day.contains(&date.day())
+                };
+                months_between(start_date, date) % interval == 0 && day_matches
+            }
+            Frequency::Yearly => {
+                i64::from(date.year() - start_date.year()) % interval == 0
+                    && date.month() == start_date.month()
+                    && date.day() == start_date.day()
+            }
+        }
+    }
+
+    /// First occurrence on or after `from`, `None` when there is none until the end of the rule.
+    /// COUNT is not applied here, the caller knows how many occurrences were generated.
+    pub fn next_occurrence(&self, start_date: NaiveDate, from: NaiveDate) -> Option<NaiveDate> {
+        // NOTE: a yearly rule starting on the 29th of February only recurs in leap years
+        let horizon = 4 * 366 * u64::from(self.interval) + 1;
+        from.max(start_date)
+            .iter_days()
+            .take(usize::try_from(horizon).ok()?)
+            .take_while(|date| self.until.is_none_or(|until| *date <= until))
+            .find(|date| self.matches(start_date, *date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_normalizes_rule() {
+        let rule = "RRULE:freq=weekly;byday=th,mo,MO;interval=2"
+            .parse::<RecurrenceRule>()
+            .unwrap();
+
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH");
+        assert!("FREQ=DAILY;BYDAY=MO".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=HOURLY".parse::<RecurrenceRule>().is_err());
+        assert!(
+            "FREQ=DAILY;COUNT=3;UNTIL=20261231"
+                .parse::<RecurrenceRule>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_weekly_rule_with_interval_and_days() {
+        let rule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH"
+            .parse::<RecurrenceRule>()
+            .unwrap();
+        let start_date = date("2026-01-05");
+        let next = |from| rule.next_occurrence(start_date, from);
+
+        assert_eq!(next(date("2026-01-01")), Some(date("2026-01-05")));
+        assert_eq!(next(date("2026-01-06")), Some(date("2026-01-08")));
+        assert_eq!(next(date("2026-01-09")), Some(date("2026-01-19")));
+    }
+
+    #[test]
+    fn test_monthly_rule_clamps_to_month_end_and_respects_until() {
+        let rule = "FREQ=MONTHLY;UNTIL=20260415"
+            .parse::<RecurrenceRule>()
+            .unwrap();
+        let start_date = date("2026-01-31");
+        let next = |from| rule.next_occurrence(start_date, from);
+
+        assert_eq!(next(date("2026-02-01")), Some(date("2026-02-28")));
+        assert_eq!(next(date("2026-03-01")), Some(date("2026-03-31")));
+        assert_eq!(next(date("2026-04-01")), None);
+    }
+}
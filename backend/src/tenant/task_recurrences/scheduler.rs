@@ -0,0 +1,221 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::database::PoolManager;
+use crate::common::{AppState, ConfigProvider};
+use crate::manager::tenants::repository::TenantsRepository;
+use crate::tenant::task_recurrences::TaskRecurrencesModuleInterface;
+use crate::tenant::task_recurrences::model::{
+    GENERATE_ON_COMPLETION, GENERATE_ON_SCHEDULE, STATUS_ENDED, TaskRecurrence,
+};
+use crate::tenant::task_recurrences::repository::TaskRecurrencesRepository;
+use crate::tenant::task_recurrences::service::TaskRecurrencesServiceResult;
+use chrono::{NaiveDate, Utc};
+use lettre::{
+    AsyncTransport,
+    transport::smtp::{Error, response::Response},
+};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+async fn generate_scheduled(
+    repo: &(dyn TaskRecurrencesRepository + Send + Sync),
+    mut recurrence: TaskRecurrence,
+    today: NaiveDate,
+) -> TaskRecurrencesServiceResult<()> {
+    // NOTE: occurrences missed while the scheduler was not running are caught up one by one,
+    // each of them keeping its own occurrence date
+    while let Some(occurrence_date) = recurrence
+        .next_occurrence_date
+        .filter(|occurrence_date| *occurrence_date <= today)
+    {
+        let next_occurrence_date = recurrence.following_occurrence(occurrence_date);
+        repo.create_occurrence(&recurrence, occurrence_date, next_occurrence_date)
+            .await?;
+        recurrence.occurrence_count += 1;
+        recurrence.next_occurrence_date = next_occurrence_date;
+    }
+    Ok(())
+}
+
+async fn generate_on_completion(
+    repo: &(dyn TaskRecurrencesRepository + Send + Sync),
+    recurrence: TaskRecurrence,
+    today: NaiveDate,
+) -> TaskRecurrencesServiceResult<()> {
+    // NOTE: the next occurrence is only generated once the previous one is done, so a late
+    // completion moves the rest of the series instead of piling up overdue copies
+    let from = recurrence
+        .next_occurrence_date
+        .unwrap_or_default()
+        .max(today);
+    match recurrence.next_occurrence_from(from) {
+        Some(occurrence_date) => {
+            let next_occurrence_date = recurrence.following_occurrence(occurrence_date);
+            repo.create_occurrence(&recurrence, occurrence_date, next_occurrence_date)
+                .await?;
+        }
+        None => {
+            repo.set_schedule(recurrence.id, STATUS_ENDED, None).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn process_tenant<M: TaskRecurrencesModuleInterface>(
+    module: &M,
+    tenant_id: Uuid,
+    today: NaiveDate,
+) -> anyhow::Result<()> {
+    let repo = module.task_recurrences_repo(tenant_id)?;
+    let mut recurrences = repo.get_due_scheduled(today).await?;
+    recurrences.extend(repo.get_completed().await?);
+    for recurrence in recurrences {
+        let id = recurrence.id;
+        let result = match recurrence.generate_on.as_str() {
+            GENERATE_ON_SCHEDULE => generate_scheduled(&*repo, recurrence, today).await,
+            GENERATE_ON_COMPLETION => generate_on_completion(&*repo, recurrence, today).await,
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("Task recurrence {} could not be generated: {}", id, e);
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_task_recurrences<P, T>(app_state: Arc<AppState<P, T>>)
+where
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
+    T::Error: Debug,
+{
+    let interval_hours = app_state.config().tasks().recurrence_interval_hours();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_hours * 3600));
+        loop {
+            interval.tick().await;
+            let tenants = match TenantsRepository::get_all(
+                &*app_state.pool_manager().get_main_pool(),
+            )
+            .await
+            {
+                Ok(tenants) => tenants,
+                Err(e) => {
+                    error!("Could not list tenants for task recurrences: {}", e);
+                    continue;
+                }
+            };
+            let today = Utc::now().date_naive();
+            for tenant in tenants {
+                if let Err(e) = process_tenant(&*app_state, tenant.id, today).await {
+                    error!("Task recurrences failed for tenant {}: {}", tenant.id, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::task_recurrences::model::TaskOccurrence;
+    use crate::tenant::task_recurrences::repository::MockTaskRecurrencesRepository;
+    use mockall::Sequence;
+    use mockall::predicate::{always, eq};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn recurrence(generate_on: &str, rule: &str, next_occurrence_date: &str) -> TaskRecurrence {
+        TaskRecurrence {
+            id: Uuid::new_v4(),
+            template_task_id: Uuid::new_v4(),
+            rule: rule.to_string(),
+            generate_on: generate_on.to_string(),
+            start_date: date("2026-01-05"),
+            next_occurrence_date: Some(date(next_occurrence_date)),
+            occurrence_count: 0,
+            status: "active".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn occurrence(occurrence_date: NaiveDate) -> TaskOccurrence {
+        TaskOccurrence {
+            task_id: Uuid::new_v4(),
+            occurrence_date,
+            status: "pending".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_scheduled_catches_up_missed_occurrences() {
+        let mut sequence = Sequence::new();
+        let mut repo = MockTaskRecurrencesRepository::new();
+        for (occurrence_date, next_occurrence_date) in [
+            (date("2026-01-12"), Some(date("2026-01-19"))),
+            (date("2026-01-19"), None),
+        ] {
+            repo.expect_create_occurrence()
+                .times(1)
+                .in_sequence(&mut sequence)
+                .with(always(), eq(occurrence_date), eq(next_occurrence_date))
+                .returning(|_, occurrence_date, _| Ok(occurrence(occurrence_date)));
+        }
+
+        generate_scheduled(
+            &repo,
+            recurrence("schedule", "FREQ=WEEKLY;COUNT=2", "2026-01-12"),
+            date("2026-02-01"),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_generate_on_completion_continues_from_today() {
+        let mut repo = MockTaskRecurrencesRepository::new();
+        repo.expect_create_occurrence()
+            .times(1)
+            .with(
+                always(),
+                eq(date("2026-02-02")),
+                eq(Some(date("2026-02-09"))),
+            )
+            .returning(|_, occurrence_date, _| Ok(occurrence(occurrence_date)));
+        repo.expect_set_schedule().never();
+
+        generate_on_completion(
+            &repo,
+            recurrence("completion", "FREQ=WEEKLY", "2026-01-12"),
+            date("2026-02-01"),
+        )
+        .await
+        .unwrap();
+    }
+}
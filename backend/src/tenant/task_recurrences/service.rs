@@ -0,0 +1,362 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{ensure_task_access, visible_projects_of};
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::task_recurrences::TaskRecurrencesModuleInterface;
+use crate::tenant::task_recurrences::dto::TaskRecurrenceInput;
+use crate::tenant::task_recurrences::model::{
+    GENERATE_ON_COMPLETION, GENERATE_ON_SCHEDULE, STATUS_ACTIVE, STATUS_ENDED, STATUS_PAUSED,
+    TaskOccurrence, TaskRecurrence,
+};
+use crate::tenant::task_recurrences::repository::TaskRecurrencesRepository;
+use crate::tenant::task_recurrences::rule::RecurrenceRule;
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TaskRecurrencesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TaskRecurrencesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TaskRecurrencesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TaskRecurrencesServiceError> for AppError {
+    fn from(value: TaskRecurrencesServiceError) -> Self {
+        match value {
+            TaskRecurrencesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskRecurrencesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            TaskRecurrencesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TaskRecurrencesServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": TaskRecurrencesServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            TaskRecurrencesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TaskRecurrencesServiceResult<T> = Result<T, TaskRecurrencesServiceError>;
+
+/// Validates the input and stores the rule in its normalized form
+fn validate_recurrence(
+    payload: &TaskRecurrenceInput,
+) -> TaskRecurrencesServiceResult<TaskRecurrenceInput> {
+    let rule = payload
+        .rule
+        .trim()
+        .parse::<RecurrenceRule>()
+        .map_err(TaskRecurrencesServiceError::UnprocessableEntry)?;
+    if ![GENERATE_ON_SCHEDULE, GENERATE_ON_COMPLETION].contains(&payload.generate_on.as_str()) {
+        return Err(TaskRecurrencesServiceError::UnprocessableEntry(
+            "Az előfordulás ütemezés szerint vagy a feladat elkészültekor generálható!",
+        ));
+    }
+    Ok(TaskRecurrenceInput {
+        rule: rule.to_string(),
+        ..payload.clone()
+    })
+}
+
+pub trait TaskRecurrencesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<TaskRecurrence>> + Send;
+    fn list(
+        &self,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<Vec<TaskRecurrence>>> + Send;
+    fn create(
+        &self,
+        payload: &TaskRecurrenceInput,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<TaskRecurrence>> + Send;
+    fn update(
+        &self,
+        payload: &TaskRecurrenceInput,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<TaskRecurrence>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = TaskRecurrencesServiceResult<()>> + Send;
+    fn pause(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<TaskRecurrence>> + Send;
+    fn resume(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<TaskRecurrence>> + Send;
+    fn occurrences(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = TaskRecurrencesServiceResult<Vec<TaskOccurrence>>> + Send;
+    fn repo(
+        &self,
+    ) -> TaskRecurrencesServiceResult<Arc<dyn TaskRecurrencesRepository + Send + Sync>>;
+}
+
+impl<'a, T> TaskRecurrencesService for Service<'a, T>
+where
+    T: TaskRecurrencesModuleInterface,
+{
+    fn repo(
+        &self,
+    ) -> TaskRecurrencesServiceResult<Arc<dyn TaskRecurrencesRepository + Send + Sync>> {
+        Ok(self.module().task_recurrences_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaskRecurrencesServiceError::Unauthorized)?,
+        )?)
+    }
+
+    async fn get(&self, id: Uuid) -> TaskRecurrencesServiceResult<TaskRecurrence> {
+        let recurrence = self.repo()?.get_by_id(id).await?;
+        ensure_task_access(
+            self,
+            recurrence.template_task_id,
+            ProjectAccess::Read,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        Ok(recurrence)
+    }
+
+    async fn list(&self) -> TaskRecurrencesServiceResult<Vec<TaskRecurrence>> {
+        let visible_to = visible_projects_of(
+            self.module(),
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaskRecurrencesServiceError::Unauthorized)?,
+            self.claims()?.sub(),
+        )
+        .await?;
+        Ok(self.repo()?.get_all(visible_to).await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &TaskRecurrenceInput,
+    ) -> TaskRecurrencesServiceResult<TaskRecurrence> {
+        let input = validate_recurrence(payload)?;
+        ensure_task_access(
+            self,
+            input.template_task_id,
+            ProjectAccess::Write,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        let next_occurrence_date = input
+            .rule
+            .parse::<RecurrenceRule>()
+            .ok()
+            .and_then(|rule| rule.next_occurrence(input.start_date, Utc::now().date_naive()));
+        if next_occurrence_date.is_none() {
+            return Err(TaskRecurrencesServiceError::UnprocessableEntry(
+                "A szabály alapján nem keletkezik több előfordulás!",
+            ));
+        }
+        self.repo()?
+            .insert(&input, next_occurrence_date, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    TaskRecurrencesServiceError::UnprocessableEntry(
+                        "A feladathoz már tartozik ismétlődés!",
+                    )
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn update(
+        &self,
+        payload: &TaskRecurrenceInput,
+    ) -> TaskRecurrencesServiceResult<TaskRecurrence> {
+        let id = payload
+            .id
+            .ok_or(TaskRecurrencesServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let input = validate_recurrence(payload)?;
+        let repo = self.repo()?;
+        let recurrence = repo.get_by_id(id).await?;
+        ensure_task_access(
+            self,
+            recurrence.template_task_id,
+            ProjectAccess::Write,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        if recurrence.template_task_id != input.template_task_id {
+            return Err(TaskRecurrencesServiceError::UnprocessableEntry(
+                "Az ismétlődés sablon feladata nem módosítható!",
+            ));
+        }
+        // NOTE: dates already generated are not scheduled again
+        let from = recurrence
+            .next_occurrence_date
+            .unwrap_or_default()
+            .max(Utc::now().date_naive());
+        let next_occurrence_date = TaskRecurrence {
+            rule: input.rule.clone(),
+            start_date: input.start_date,
+            ..recurrence
+        }
+        .next_occurrence_from(from);
+        Ok(repo.update(id, &input, next_occurrence_date).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> TaskRecurrencesServiceResult<()> {
+        let repo = self.repo()?;
+        ensure_task_access(
+            self,
+            repo.get_by_id(id).await?.template_task_id,
+            ProjectAccess::Write,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn pause(&self, id: Uuid) -> TaskRecurrencesServiceResult<TaskRecurrence> {
+        let repo = self.repo()?;
+        let recurrence = repo.get_by_id(id).await?;
+        ensure_task_access(
+            self,
+            recurrence.template_task_id,
+            ProjectAccess::Write,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        if recurrence.status != STATUS_ACTIVE {
+            return Err(TaskRecurrencesServiceError::UnprocessableEntry(
+                "Csak aktív ismétlődés szüneteltethető!",
+            ));
+        }
+        Ok(repo
+            .set_schedule(id, STATUS_PAUSED, recurrence.next_occurrence_date)
+            .await?)
+    }
+
+    async fn resume(&self, id: Uuid) -> TaskRecurrencesServiceResult<TaskRecurrence> {
+        let repo = self.repo()?;
+        let recurrence = repo.get_by_id(id).await?;
+        ensure_task_access(
+            self,
+            recurrence.template_task_id,
+            ProjectAccess::Write,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        if recurrence.status != STATUS_PAUSED {
+            return Err(TaskRecurrencesServiceError::UnprocessableEntry(
+                "Csak szüneteltetett ismétlődés folytatható!",
+            ));
+        }
+        // NOTE: occurrences missed while paused are not generated retroactively
+        let from = recurrence
+            .next_occurrence_date
+            .unwrap_or_default()
+            .max(Utc::now().date_naive());
+        let next_occurrence_date = recurrence.next_occurrence_from(from);
+        let status = if next_occurrence_date.is_some() {
+            STATUS_ACTIVE
+        } else {
+            STATUS_ENDED
+        };
+        Ok(repo.set_schedule(id, status, next_occurrence_date).await?)
+    }
+
+    async fn occurrences(&self, id: Uuid) -> TaskRecurrencesServiceResult<Vec<TaskOccurrence>> {
+        let repo = self.repo()?;
+        ensure_task_access(
+            self,
+            repo.get_by_id(id).await?.template_task_id,
+            ProjectAccess::Read,
+            TaskRecurrencesServiceError::Forbidden,
+        )
+        .await?;
+        Ok(repo.get_occurrences(id).await?)
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TaskBoardQuery {
+    pub worksheet_id: Option<Uuid>,
+}
+
+/// A task dropped on the board: `previous_id` is the card above and `next_id` the card below
+/// the drop target in the `status` column, both empty when the column is empty
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TaskReorderInput {
+    pub task_id: Uuid,
+    pub status: String,
+    pub previous_id: Option<Uuid>,
+    pub next_id: Option<Uuid>,
+}
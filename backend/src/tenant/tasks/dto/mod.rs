@@ -17,5 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod board;
+pub mod dependency;
 pub mod print;
 pub mod user_input;
@@ -138,6 +138,13 @@ mod tests {
             updated_at: input_date,
             deleted_at: None,
             description: Some("Test description".to_string()),
+            position: 1024.0,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
         };
         let task_resolved_print = TaskResolvedPrint::from_task_resolved(task_resolved, tz);
         let task_resolved_print_expected = TaskResolvedPrint {
@@ -230,6 +230,7 @@ impl TryFrom<TaskUserInputHelper> for TaskUserInput {
 
 #[cfg(test)]
 mod tests {
+    use bigdecimal::BigDecimal;
     use chrono::{Days, Utc};
     use uuid::Uuid;
 
@@ -263,8 +264,11 @@ mod tests {
         assert_eq!(user_input.worksheet_id.as_uuid().unwrap(), worksheet_id);
         assert_eq!(user_input.service_id.as_uuid().unwrap(), service_id);
         assert_eq!(user_input.currency_code.as_str().unwrap(), "HUF");
-        assert_eq!(user_input.quantity.as_f64().unwrap(), 10_f64);
-        assert_eq!(user_input.price.as_f64().unwrap(), 1000_f64);
+        assert_eq!(
+            user_input.quantity.as_decimal(),
+            Some(&BigDecimal::from(10))
+        );
+        assert_eq!(user_input.price.as_decimal(), Some(&BigDecimal::from(1000)));
         assert_eq!(user_input.tax_id.as_uuid().unwrap(), tax_id);
         assert_eq!(user_input.status.as_str().unwrap(), "active");
         assert_eq!(user_input.priority.as_str().unwrap(), "normal");
@@ -17,13 +17,18 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::dto::{
+    DuplicateParams, EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam,
+};
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
-use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::query_parser::{ArchivableRawQuery, ResourceQuery};
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::tags::dto::TagFilter;
 use crate::tenant::tasks::TasksModule;
+use crate::tenant::tasks::dto::board::{TaskBoardQuery, TaskReorderInput};
+use crate::tenant::tasks::dto::dependency::TaskDependencyInput;
 use crate::tenant::tasks::dto::print::TaskResolvedPrint;
 use crate::tenant::tasks::dto::user_input::{TaskUserInput, TaskUserInputHelper};
 use crate::tenant::tasks::service::TaskService;
@@ -161,7 +166,8 @@ pub async fn select_list<M: TasksModule>(
 pub async fn list<M: TasksModule>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(tasks_module): State<Arc<M>>,
-    Query(payload): Query<CommonRawQuery>,
+    Query(payload): Query<ArchivableRawQuery>,
+    Query(tag_filter): Query<TagFilter>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), tasks_module.clone());
     let resource_query = map_handler_err(
@@ -170,7 +176,9 @@ pub async fn list<M: TasksModule>(
     )
     .await?;
     let (meta, data) = map_handler_err(
-        service.get_paged(&resource_query).await,
+        service
+            .get_paged(&resource_query, payload.include_archived(), tag_filter.tags)
+            .await,
         tasks_module.clone(),
     )
     .await?;
@@ -212,18 +220,144 @@ pub async fn print<M: TasksModule>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
+pub async fn duplicate<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DuplicateParams>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    let result = map_handler_err(service.duplicate(&payload).await, tasks_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn dependencies<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    let result = map_handler_err(
+        service.get_dependencies(payload.uuid).await,
+        tasks_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn add_dependency<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskDependencyInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    let result =
+        map_handler_err(service.add_dependency(&payload).await, tasks_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn remove_dependency<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    Query(payload): Query<TaskDependencyInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    map_handler_err(
+        service.remove_dependency(&payload).await,
+        tasks_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A függőség törlése sikeresen megtörtént",
+            ))
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn board<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    Query(payload): Query<TaskBoardQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    let result = map_handler_err(service.board(&payload).await, tasks_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn reorder<M: TasksModule>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(tasks_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<TaskReorderInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), tasks_module.clone());
+    let result = map_handler_err(service.reorder(&payload).await, tasks_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        tasks_module,
+    )
+    .await?
+    .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::dto::PaginatorMeta;
     use crate::common::error::RepositoryError;
     use crate::common::handler::tests::{
-        extract_json_response, generate_expired_jwt, generate_jwt_with_invalid_signature,
-        generate_valid_jwt,
+        MockProjectArchivedViolation, extract_json_response, generate_expired_jwt,
+        generate_jwt_with_invalid_signature, generate_valid_jwt,
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::tasks::model::TaskResolved;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::project_members::repository::MockProjectMembersRepository;
+    use crate::tenant::services::model::ResolvedServiceRate;
+    use crate::tenant::services::repository::MockServicesRepository;
+    use crate::tenant::tasks::model::{TaskDependency, TaskResolved};
+    use crate::tenant::worksheets::model::Worksheet;
+    use crate::tenant::worksheets::repository::MockWorksheetsRepository;
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::tasks::{
@@ -232,6 +366,7 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
     use chrono::{DateTime, Duration, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
@@ -242,6 +377,103 @@ mod tests {
     use tower::ServiceExt;
     use uuid::Uuid;
 
+    /// Every task and worksheet belongs to `project_id`, where the user holds `role`
+    fn expect_project_access(
+        app_state: &mut MockTasksModule,
+        project_id: Option<Uuid>,
+        role: Option<&'static str>,
+        access_all: bool,
+    ) {
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_task_project_id()
+            .returning(move |_| Ok(project_id));
+        project_members_repo
+            .expect_get_worksheet_project_id()
+            .returning(move |_| Ok(project_id));
+        project_members_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(role.map(str::to_string)));
+        let project_members_repo = Arc::new(project_members_repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "projects.access_all")
+            .returning(move |_, _| Ok(access_all));
+        let permissions_repo = Arc::new(permissions_repo);
+        app_state
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        app_state
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        app_state
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+    }
+
+    fn allow_project_access(app_state: &mut MockTasksModule) {
+        expect_project_access(app_state, None, None, true);
+    }
+
+    fn rate_app_state(
+        worksheet_id: Uuid,
+        service_id: Uuid,
+        rate: ResolvedServiceRate,
+    ) -> MockTasksModule {
+        let customer_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let mut worksheets_repo = MockWorksheetsRepository::new();
+        worksheets_repo
+            .expect_get_by_id()
+            .times(1)
+            .with(eq(worksheet_id))
+            .returning(move |_| {
+                Ok(Worksheet {
+                    id: worksheet_id,
+                    name: "Test Worksheet".to_string(),
+                    description: None,
+                    customer_id,
+                    project_id: None,
+                    created_by_id: Uuid::new_v4(),
+                    status: "active".to_string(),
+                    created_at: utc_now,
+                    updated_at: utc_now,
+                    deleted_at: None,
+                    worksheet_template_id: None,
+                    estimated_duration_minutes: None,
+                    billing_status: "not_billed".to_string(),
+                })
+            });
+        let mut services_repo = MockServicesRepository::new();
+        services_repo
+            .expect_resolve_rate()
+            .times(1)
+            .withf(move |service_id_inner, customer_id_inner, _| {
+                *service_id_inner == service_id && *customer_id_inner == Some(customer_id)
+            })
+            .returning(move |_, _, _| Ok(rate.clone()));
+
+        let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
+        let worksheets_repo = Arc::new(worksheets_repo);
+        let services_repo = Arc::new(services_repo);
+        app_state
+            .expect_worksheets_repo()
+            .times(1)
+            .returning(move |_| Ok(worksheets_repo.clone()));
+        app_state
+            .expect_services_repo()
+            .times(1)
+            .returning(move |_| Ok(services_repo.clone()));
+        app_state
+    }
+
     #[tokio::test]
     async fn test_get_success() {
         let active_tenant_id = Uuid::new_v4();
@@ -267,6 +499,7 @@ mod tests {
             updated_at: utc_now,
             deleted_at: None,
             description: None,
+            position: 1024.0,
         };
 
         let mut repo = MockTasksRepository::new();
@@ -279,6 +512,7 @@ mod tests {
             });
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -327,6 +561,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -355,6 +590,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -367,6 +603,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -395,6 +632,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -439,6 +677,7 @@ mod tests {
             .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -476,6 +715,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -512,6 +752,13 @@ mod tests {
             updated_at: utc_now,
             deleted_at: None,
             description: None,
+            position: 1024.0,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
         };
 
         let mut repo = MockTasksRepository::new();
@@ -524,6 +771,7 @@ mod tests {
             });
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -572,6 +820,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -600,6 +849,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -612,6 +862,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -640,6 +891,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -684,6 +936,7 @@ mod tests {
             .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -721,6 +974,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -762,20 +1016,33 @@ mod tests {
             updated_at: utc_now,
             deleted_at: None,
             description: None,
+            position: 1024.0,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
         };
 
         let mut repo = MockTasksRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<TaskOrderBy, TaskFilterBy>>()
-                .unwrap()))
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<TaskOrderBy, TaskFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(false),
+                eq(None),
+            )
             .returning({
                 let task_resolved = task_resolved.clone();
-                move |_| Ok((paginator_meta, vec![task_resolved.clone()]))
+                move |_, _, _, _| Ok((paginator_meta, vec![task_resolved.clone()]))
             });
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -822,6 +1089,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_unauthorized_expired() {
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -850,6 +1118,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -860,6 +1129,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_unauthorized_invalid_signature() {
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -888,6 +1158,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -926,12 +1197,18 @@ mod tests {
         let mut repo = MockTasksRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<TaskOrderBy, TaskFilterBy>>()
-                .unwrap()))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<TaskOrderBy, TaskFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(false),
+                eq(None),
+            )
+            .returning(|_, _, _, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -969,6 +1246,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -1000,14 +1278,19 @@ mod tests {
             due_date: "".to_string(),
             description: "".to_string(),
         };
-        let user_input = TaskUserInput::try_from(user_input_helper.clone()).unwrap();
+        let user_input = TaskUserInput::try_from(TaskUserInputHelper {
+            currency_code: "EUR".to_string(),
+            price: "45.5".to_string(),
+            ..user_input_helper.clone()
+        })
+        .unwrap();
         let task = Task {
             id: task_id,
             worksheet_id,
             service_id,
-            currency_code: "HUF".to_string(),
+            currency_code: "EUR".to_string(),
             quantity: None,
-            price: None,
+            price: Some(BigDecimal::from_str("45.5").unwrap()),
             tax_id,
             created_by_id,
             status: "active".to_string(),
@@ -1017,6 +1300,7 @@ mod tests {
             updated_at: utc_now,
             deleted_at: None,
             description: None,
+            position: 1024.0,
         };
 
         let mut repo = MockTasksRepository::new();
@@ -1043,7 +1327,15 @@ mod tests {
                 move |_, _| Ok(task.clone())
             });
 
-        let mut app_state = MockTasksModule::new();
+        let mut app_state = rate_app_state(
+            worksheet_id,
+            service_id,
+            ResolvedServiceRate {
+                service_rate_id: Some(Uuid::new_v4()),
+                price: Some(BigDecimal::from_str("45.50").unwrap()),
+                currency_code: Some("EUR".to_string()),
+            },
+        );
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1111,6 +1403,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1144,6 +1437,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error,
             }
@@ -1173,6 +1467,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1202,6 +1497,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1230,6 +1526,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1259,6 +1556,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1335,6 +1633,7 @@ mod tests {
             updated_at: utc_now,
             deleted_at: None,
             description: None,
+            position: 1024.0,
         };
 
         let user_input_helper = TaskUserInputHelper {
@@ -1361,7 +1660,15 @@ mod tests {
                 move |_| Ok(task.clone())
             });
 
-        let mut app_state = MockTasksModule::new();
+        let mut app_state = rate_app_state(
+            worksheet_id,
+            service_id,
+            ResolvedServiceRate {
+                service_rate_id: None,
+                price: None,
+                currency_code: None,
+            },
+        );
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1429,6 +1736,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1461,6 +1769,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             }
         });
@@ -1490,6 +1799,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1519,6 +1829,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1548,6 +1859,7 @@ mod tests {
         };
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1577,6 +1889,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1641,6 +1954,7 @@ mod tests {
             .returning(move |_| Ok(()));
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1692,6 +2006,7 @@ mod tests {
         let user_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1731,6 +2046,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1759,6 +2075,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1771,6 +2088,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1799,6 +2117,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1863,6 +2182,13 @@ mod tests {
             updated_at: test_time,
             deleted_at: None,
             description: None,
+            position: 1024.0,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
         };
 
         let mut repo = MockTasksRepository::new();
@@ -1875,6 +2201,7 @@ mod tests {
             });
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1949,6 +2276,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1978,6 +2306,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1990,6 +2319,7 @@ mod tests {
         let task_id = Uuid::new_v4();
 
         let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -2019,6 +2349,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -2051,4 +2382,685 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    fn task_with_status(id: Uuid, status: &str) -> Task {
+        let utc_now = Utc::now();
+        Task {
+            id,
+            worksheet_id: Uuid::new_v4(),
+            service_id: Uuid::new_v4(),
+            currency_code: "HUF".to_string(),
+            quantity: None,
+            price: None,
+            tax_id: Uuid::new_v4(),
+            created_by_id: Uuid::new_v4(),
+            status: status.to_string(),
+            priority: None,
+            due_date: None,
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+            description: None,
+            position: 1024.0,
+        }
+    }
+
+    fn dependency_app(repo: MockTasksRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
+        app_state
+            .expect_tasks_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn add_dependency_request(
+        active_tenant_id: Uuid,
+        task_id: Uuid,
+        blocked_by_id: Uuid,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/tasks/add_dependency")
+            .body(Body::from(
+                json!({"task_id": task_id, "blocked_by_id": blocked_by_id}).to_string(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let blocked_by_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(2)
+            .returning(|id| Ok(task_with_status(id, "pending")));
+        repo.expect_insert_dependency()
+            .times(1)
+            .with(eq(task_id), eq(blocked_by_id), mockall::predicate::always())
+            .returning(|task_id, blocked_by_id, sub| {
+                Ok(Some(TaskDependency {
+                    task_id,
+                    blocked_by_id,
+                    blocked_by_service: "Szerelés".to_string(),
+                    blocked_by_status: "pending".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                }))
+            });
+
+        let response = dependency_app(repo, active_tenant_id)
+            .oneshot(add_dependency_request(
+                active_tenant_id,
+                task_id,
+                blocked_by_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["blocked_by_service"], "Szerelés");
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_rejects_cycle() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(2)
+            .returning(|id| Ok(task_with_status(id, "pending")));
+        repo.expect_insert_dependency()
+            .times(1)
+            .returning(|_, _, _| Ok(None));
+
+        let response = dependency_app(repo, active_tenant_id)
+            .oneshot(add_dependency_request(
+                active_tenant_id,
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_in_archived_project_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(2)
+            .returning(|id| Ok(task_with_status(id, "pending")));
+        repo.expect_insert_dependency()
+            .times(1)
+            .returning(|_, _, _| {
+                Err(RepositoryError::Database(sqlx::Error::Database(Box::new(
+                    MockProjectArchivedViolation,
+                ))))
+            });
+
+        let response = dependency_app(repo, active_tenant_id)
+            .oneshot(add_dependency_request(
+                active_tenant_id,
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_on_itself_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
+        app_state.expect_tasks_repo().never();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app
+            .oneshot(add_dependency_request(active_tenant_id, task_id, task_id))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_update_to_in_progress_blocked_by_unfinished_task() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let service_id = Uuid::new_v4();
+        let user_input_helper = TaskUserInputHelper {
+            id: Some(task_id.to_string()),
+            worksheet_id: worksheet_id.to_string(),
+            service_id: service_id.to_string(),
+            currency_code: "HUF".to_string(),
+            quantity: "".to_string(),
+            price: "".to_string(),
+            tax_id: Uuid::new_v4().to_string(),
+            status: "in_progress".to_string(),
+            priority: "".to_string(),
+            due_date: "".to_string(),
+            description: "".to_string(),
+        };
+
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|id| Ok(task_with_status(id, "pending")));
+        repo.expect_count_unfinished_blockers()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|_| Ok(1));
+        repo.expect_update().never();
+
+        let mut app_state = rate_app_state(
+            worksheet_id,
+            service_id,
+            ResolvedServiceRate {
+                service_rate_id: None,
+                price: None,
+                currency_code: None,
+            },
+        );
+        let repo = Arc::new(repo);
+        app_state
+            .expect_tasks_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/tasks/update")
+            .body(Body::from(
+                serde_json::to_string(&user_input_helper).unwrap(),
+            ))
+            .unwrap();
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn reorder_request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/tasks/reorder")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reorder_moves_task_between_columns() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let previous_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|id| Ok(task_with_status(id, "pending")));
+        repo.expect_count_unfinished_blockers()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|_| Ok(0));
+        repo.expect_reorder()
+            .times(1)
+            .withf(move |input| {
+                input.task_id == task_id
+                    && input.status == "in_progress"
+                    && input.previous_id == Some(previous_id)
+                    && input.next_id.is_none()
+            })
+            .returning(|input| {
+                let mut task = task_with_status(input.task_id, &input.status);
+                task.position = 3072.0;
+                Ok(task)
+            });
+
+        let response = dependency_app(repo, active_tenant_id)
+            .oneshot(reorder_request(
+                active_tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "status": "in_progress",
+                    "previous_id": previous_id,
+                    "next_id": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["status"], "in_progress");
+        assert_eq!(response_body["data"]["position"], 3072.0);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_into_done_blocked_by_unfinished_task() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|id| Ok(task_with_status(id, "in_progress")));
+        repo.expect_count_unfinished_blockers()
+            .times(1)
+            .returning(|_| Ok(2));
+        repo.expect_reorder().never();
+
+        let response = dependency_app(repo, active_tenant_id)
+            .oneshot(reorder_request(
+                active_tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "status": "done",
+                    "previous_id": null,
+                    "next_id": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_next_to_itself_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut app_state = MockTasksModule::new();
+        allow_project_access(&mut app_state);
+        app_state.expect_tasks_repo().never();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app
+            .oneshot(reorder_request(
+                active_tenant_id,
+                json!({
+                    "task_id": task_id,
+                    "status": "pending",
+                    "previous_id": task_id,
+                    "next_id": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn access_app(
+        repo: MockTasksRepository,
+        active_tenant_id: Uuid,
+        project_id: Option<Uuid>,
+        role: Option<&'static str>,
+        access_all: bool,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let mut app_state = MockTasksModule::new();
+        expect_project_access(&mut app_state, project_id, role, access_all);
+        app_state
+            .expect_tasks_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn access_request(method: &str, uri: &str, sub: Uuid, active_tenant_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_is_allowed_for_project_viewers() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(task_id))
+            .returning(move |_| Ok(task_with_status(task_id, "active")));
+
+        let response = access_app(
+            repo,
+            active_tenant_id,
+            Some(Uuid::new_v4()),
+            Some("viewer"),
+            false,
+        )
+        .oneshot(access_request(
+            "GET",
+            &format!("/api/tasks/get?uuid={task_id}"),
+            Uuid::new_v4(),
+            active_tenant_id,
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_forbidden_for_project_viewers() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_delete_by_id().never();
+
+        let response = access_app(
+            repo,
+            active_tenant_id,
+            Some(Uuid::new_v4()),
+            Some("viewer"),
+            false,
+        )
+        .oneshot(access_request(
+            "DELETE",
+            &format!("/api/tasks/delete?uuid={}", Uuid::new_v4()),
+            Uuid::new_v4(),
+            active_tenant_id,
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_by_id().never();
+
+        let response = access_app(repo, active_tenant_id, Some(Uuid::new_v4()), None, false)
+            .oneshot(access_request(
+                "GET",
+                &format!("/api/tasks/get?uuid={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_dependencies_are_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_dependencies().never();
+
+        let response = access_app(repo, active_tenant_id, Some(Uuid::new_v4()), None, false)
+            .oneshot(access_request(
+                "GET",
+                &format!("/api/tasks/dependencies?uuid={}", Uuid::new_v4()),
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_remove_dependency_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_delete_dependency().never();
+
+        let response = access_app(repo, active_tenant_id, Some(Uuid::new_v4()), None, false)
+            .oneshot(access_request(
+                "DELETE",
+                &format!(
+                    "/api/tasks/remove_dependency?task_id={}&blocked_by_id={}",
+                    Uuid::new_v4(),
+                    Uuid::new_v4()
+                ),
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_add_dependency_on_task_of_foreign_project_is_forbidden() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let own_project_id = Uuid::new_v4();
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_task_project_id()
+            .returning(move |id| {
+                Ok(Some(if id == task_id {
+                    own_project_id
+                } else {
+                    Uuid::new_v4()
+                }))
+            });
+        project_members_repo
+            .expect_get_role()
+            .returning(move |project_id, _| {
+                Ok((project_id == own_project_id).then(|| "member".to_string()))
+            });
+        let project_members_repo = Arc::new(project_members_repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .returning(|_, _| Ok(false));
+        let permissions_repo = Arc::new(permissions_repo);
+        let mut app_state = MockTasksModule::new();
+        app_state
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        app_state
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        app_state
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        app_state.expect_tasks_repo().never();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(tasks::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app
+            .oneshot(add_dependency_request(
+                active_tenant_id,
+                task_id,
+                Uuid::new_v4(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_board_is_restricted_to_member_projects() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_board()
+            .times(1)
+            .withf(move |worksheet_id, visible_to| {
+                worksheet_id.is_none() && *visible_to == Some(sub)
+            })
+            .returning(|_, _| Ok(vec![]));
+
+        let response = access_app(repo, active_tenant_id, None, None, false)
+            .oneshot(access_request(
+                "GET",
+                "/api/tasks/board",
+                sub,
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_delete_in_archived_project_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_delete_by_id()
+            .times(1)
+            .with(eq(task_id))
+            .returning(|_| {
+                Err(RepositoryError::Database(sqlx::Error::Database(Box::new(
+                    MockProjectArchivedViolation,
+                ))))
+            });
+
+        let response = access_app(
+            repo,
+            active_tenant_id,
+            Some(Uuid::new_v4()),
+            Some("member"),
+            false,
+        )
+        .oneshot(access_request(
+            "DELETE",
+            &format!("/api/tasks/delete?uuid={task_id}"),
+            Uuid::new_v4(),
+            active_tenant_id,
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_list_includes_archived_projects_on_request() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockTasksRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .withf(|_, visible_to, include_archived, _| visible_to.is_none() && *include_archived)
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = access_app(repo, active_tenant_id, None, None, true)
+            .oneshot(access_request(
+                "GET",
+                "/api/tasks/list?include_archived=true",
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
@@ -17,10 +17,11 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::common::AppState;
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
-use crate::common::{AppState, BaseModule};
 use crate::tenant::currencies::repository::CurrenciesRepository;
+use crate::tenant::project_members::ProjectMembersModuleInterface;
 use crate::tenant::services::repository::ServicesRepository;
 use crate::tenant::tasks::repository::TasksRepository;
 use crate::tenant::taxes::repository::TaxesRepository;
@@ -41,7 +42,7 @@ pub(crate) mod routes;
 pub mod service;
 pub(crate) mod types;
 
-pub trait TasksModule: BaseModule {
+pub trait TasksModule: ProjectMembersModuleInterface {
     fn tasks_repo(
         &self,
         tenant_id: Uuid,
@@ -108,6 +109,10 @@ pub mod tests {
     use crate::common::config::AppConfig;
     use crate::common::error::RepositoryResult;
     use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::TenantsRepository;
+    use crate::tenant::permissions::PermissionsModuleInterface;
+    use crate::tenant::permissions::repository::PermissionsRepository;
+    use crate::tenant::project_members::repository::ProjectMembersRepository;
     use lettre::{
         Message,
         transport::smtp::{Error, response::Response},
@@ -124,6 +129,19 @@ pub mod tests {
             async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
         }
         impl BaseModule for TasksModule {}
+        impl PermissionsModuleInterface for TasksModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
+        impl ProjectMembersModuleInterface for TasksModule {
+            fn project_members_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>>;
+        }
         impl TasksModule for TasksModule {
             fn tasks_repo(
                 &self,
@@ -22,6 +22,40 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+pub const STATUS_IN_PROGRESS: &str = "in_progress";
+pub const STATUS_DONE: &str = "done";
+
+/// A task can only enter these statuses once every task blocking it is done
+pub const BLOCKED_STATUSES: &[&str] = &[STATUS_IN_PROGRESS, STATUS_DONE];
+
+/// Columns of the kanban board in workflow order
+pub const BOARD_STATUSES: &[&str] = &[
+    "pending",
+    "active",
+    STATUS_IN_PROGRESS,
+    STATUS_DONE,
+    "inactive",
+];
+
+pub const POSITION_GAP: f64 = 1024.0;
+
+/// Neighbours closer than this are renumbered before a task is placed between them
+const MIN_POSITION_GAP: f64 = 1e-6;
+
+/// Position between the neighbours of the drop target, `None` when the gap between them is
+/// used up and the column has to be renumbered first
+pub fn position_between(previous: Option<f64>, next: Option<f64>) -> Option<f64> {
+    match (previous, next) {
+        (None, None) => Some(POSITION_GAP),
+        (Some(previous), None) => Some(previous + POSITION_GAP),
+        (None, Some(next)) => Some(next - POSITION_GAP),
+        (Some(previous), Some(next)) if next - previous > MIN_POSITION_GAP => {
+            Some(previous + (next - previous) / 2.0)
+        }
+        (Some(_), Some(_)) => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Task {
     pub id: Uuid,
@@ -39,6 +73,7 @@ pub struct Task {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub description: Option<String>,
+    pub position: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -62,4 +97,114 @@ pub struct TaskResolved {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub description: Option<String>,
+    pub position: f64,
+    pub checklist_item_count: i64,
+    /// Percentage of the done checklist items, `None` when the task has no checklist
+    pub checklist_completion: Option<i64>,
+    pub comment_count: i64,
+    pub latest_comment_at: Option<DateTime<Utc>>,
+    pub latest_comment_by_id: Option<Uuid>,
+    pub latest_comment_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocked_by_id: Uuid,
+    pub blocked_by_service: String,
+    pub blocked_by_status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskBoardColumn {
+    pub status: String,
+    pub tasks: Vec<TaskResolved>,
+}
+
+impl TaskBoardColumn {
+    /// Groups tasks already ordered by position into the board columns, tasks of statuses
+    /// without a column are left out
+    pub fn group(tasks: Vec<TaskResolved>) -> Vec<TaskBoardColumn> {
+        let mut columns: Vec<TaskBoardColumn> = BOARD_STATUSES
+            .iter()
+            .map(|status| TaskBoardColumn {
+                status: status.to_string(),
+                tasks: vec![],
+            })
+            .collect();
+        for task in tasks {
+            if let Some(column) = columns
+                .iter_mut()
+                .find(|column| column.status == task.status)
+            {
+                column.tasks.push(task);
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_between_neighbours() {
+        assert_eq!(position_between(None, None), Some(1024.0));
+        assert_eq!(position_between(Some(2048.0), None), Some(3072.0));
+        assert_eq!(position_between(None, Some(1024.0)), Some(0.0));
+        assert_eq!(position_between(Some(1024.0), Some(2048.0)), Some(1536.0));
+        assert_eq!(position_between(Some(1.0), Some(1.0 + 1e-9)), None);
+    }
+
+    #[test]
+    fn test_board_groups_tasks_by_status_keeping_order() {
+        let task = |status: &str, position: f64| TaskResolved {
+            id: Uuid::new_v4(),
+            worksheet_id: Uuid::new_v4(),
+            worksheet: "Test worksheet".to_string(),
+            service_id: Uuid::new_v4(),
+            service: "Test service".to_string(),
+            currency_code: "HUF".to_string(),
+            quantity: None,
+            price: None,
+            tax_id: Uuid::new_v4(),
+            tax: "Test tax".to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_by: "Test User".to_string(),
+            status: status.to_string(),
+            priority: None,
+            due_date: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            description: None,
+            position,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
+        };
+
+        let columns = TaskBoardColumn::group(vec![
+            task("done", 1024.0),
+            task("pending", 512.0),
+            task("pending", 2048.0),
+            task("archived", 1024.0),
+        ]);
+
+        let statuses: Vec<&str> = columns.iter().map(|c| c.status.as_str()).collect();
+        assert_eq!(
+            statuses,
+            vec!["pending", "active", "in_progress", "done", "inactive"]
+        );
+        let positions: Vec<f64> = columns[0].tasks.iter().map(|t| t.position).collect();
+        assert_eq!(positions, vec![512.0, 2048.0]);
+        assert_eq!(columns[3].tasks.len(), 1);
+        assert_eq!(columns.iter().map(|c| c.tasks.len()).sum::<usize>(), 3);
+    }
 }
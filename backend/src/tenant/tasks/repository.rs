@@ -17,16 +17,24 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::database::copy_tags;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Money;
+use crate::tenant::project_members::repository::visible_project_condition;
+use crate::tenant::projects::repository::archived_project_condition;
+use crate::tenant::tags::repository::tagged_condition;
+use crate::tenant::tasks::dto::board::TaskReorderInput;
 use crate::tenant::tasks::dto::user_input::TaskUserInput;
-use crate::tenant::tasks::model::{Task, TaskResolved};
+use crate::tenant::tasks::model::{
+    POSITION_GAP, Task, TaskDependency, TaskResolved, position_between,
+};
 use crate::tenant::tasks::types::task::{TaskFilterBy, TaskOrderBy};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
-use sqlx::{AssertSqlSafe, PgPool};
+use sqlx::{AssertSqlSafe, PgConnection, PgPool};
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
@@ -37,10 +45,102 @@ pub trait TasksRepository: Send + Sync {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<TaskOrderBy, TaskFilterBy>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<TaskResolved>)>;
     async fn insert(&self, task: &TaskUserInput, sub: Uuid) -> RepositoryResult<Task>;
     async fn update(&self, task: &TaskUserInput) -> RepositoryResult<Task>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Task>;
+    async fn get_dependencies(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskDependency>>;
+    async fn insert_dependency(
+        &self,
+        task_id: Uuid,
+        blocked_by_id: Uuid,
+        sub: Uuid,
+    ) -> RepositoryResult<Option<TaskDependency>>;
+    async fn delete_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> RepositoryResult<()>;
+    async fn count_unfinished_blockers(&self, task_id: Uuid) -> RepositoryResult<i64>;
+    async fn get_board(
+        &self,
+        worksheet_id: Option<Uuid>,
+        visible_to: Option<Uuid>,
+    ) -> RepositoryResult<Vec<TaskResolved>>;
+    async fn reorder(&self, input: &TaskReorderInput) -> RepositoryResult<Task>;
+}
+
+/// Blockers that are not done yet, deleted tasks no longer block anything
+const UNFINISHED_BLOCKERS: &str = r#"
+    SELECT 1
+    FROM task_dependencies
+    JOIN tasks blockers ON task_dependencies.blocked_by_id = blockers.id
+    WHERE task_dependencies.task_id = tasks.id
+        AND blockers.deleted_at IS NULL
+        AND blockers.status <> 'done'
+"#;
+
+const TASK_DEPENDENCY_SELECT: &str = r#"
+    SELECT task_dependencies.task_id,
+           task_dependencies.blocked_by_id,
+           services.name as blocked_by_service,
+           blockers.status as blocked_by_status,
+           task_dependencies.created_by_id,
+           task_dependencies.created_at
+    FROM task_dependencies
+    JOIN tasks blockers ON task_dependencies.blocked_by_id = blockers.id
+    LEFT JOIN services ON blockers.service_id = services.id
+    WHERE blockers.deleted_at IS NULL
+"#;
+
+/// Percentage of the done checklist items, NULL when the task has no checklist
+const CHECKLIST_COMPLETION: &str = r#"
+    SELECT COUNT(*) FILTER (WHERE task_checklist_items.is_done) * 100 / NULLIF(COUNT(*), 0)
+    FROM task_checklist_items
+    WHERE task_checklist_items.task_id = tasks.id
+        AND task_checklist_items.deleted_at IS NULL
+"#;
+
+// NOTE: ready_to_start expects "true" or "false", tasks already started or done are never ready.
+// checklist_completion expects a percentage between 0 and 100 and matches the tasks with at least
+// that much of their checklist done, tasks without a checklist never match.
+fn filter_condition(filter_by: &str, value: &str) -> RepositoryResult<String> {
+    match (filter_by, value) {
+        ("name", _) => Ok("services.name::TEXT ILIKE '%' || $1 || '%'".to_string()),
+        ("ready_to_start", "true" | "false") => Ok(format!(
+            "(tasks.status NOT IN ('in_progress', 'done') AND NOT EXISTS ({UNFINISHED_BLOCKERS})) = $1::BOOLEAN"
+        )),
+        ("checklist_completion", value) if value.parse::<u8>().is_ok_and(|value| value <= 100) => {
+            Ok(format!("({CHECKLIST_COMPLETION}) >= $1::BIGINT"))
+        }
+        _ => Err(RepositoryError::InvalidInput("filter_by".to_string())),
+    }
+}
+
+/// Position of a board neighbour, it has to be in the target column already
+async fn neighbour_position(
+    conn: &mut PgConnection,
+    id: Option<Uuid>,
+    status: &str,
+) -> RepositoryResult<Option<f64>> {
+    let Some(id) = id else {
+        return Ok(None);
+    };
+    Ok(Some(
+        sqlx::query_scalar::<_, f64>(
+            r#"
+            SELECT position
+            FROM tasks
+            WHERE id = $1
+                AND status = $2
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(conn)
+        .await?,
+    ))
 }
 
 #[async_trait]
@@ -81,12 +181,39 @@ impl TasksRepository for PgPool {
                 tasks.created_at as created_at,
                 tasks.updated_at as updated_at,
                 tasks.deleted_at as deleted_at,
-                tasks.description as description
+                tasks.description as description,
+                tasks.position as position,
+                (SELECT COUNT(*) FROM task_checklist_items
+                    WHERE task_checklist_items.task_id = tasks.id
+                        AND task_checklist_items.deleted_at IS NULL) as checklist_item_count,
+                (SELECT COUNT(*) FILTER (WHERE task_checklist_items.is_done) * 100 / NULLIF(COUNT(*), 0)
+                    FROM task_checklist_items
+                    WHERE task_checklist_items.task_id = tasks.id
+                        AND task_checklist_items.deleted_at IS NULL) as checklist_completion,
+                (SELECT COUNT(*) FROM comments
+                    WHERE comments.commentable_type = 'tasks'
+                        AND comments.commentable_id = tasks.id
+                        AND comments.deleted_at IS NULL) as comment_count,
+                latest_comment.created_at as latest_comment_at,
+                latest_comment.created_by_id as latest_comment_by_id,
+                latest_comment.created_by as latest_comment_by
             FROM tasks
             LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
             LEFT JOIN services ON tasks.service_id = services.id
             LEFT JOIN taxes ON tasks.tax_id = taxes.id
             LEFT JOIN users ON tasks.created_by_id = users.id
+            LEFT JOIN LATERAL (
+                SELECT comments.created_at,
+                       comments.created_by_id,
+                       commenters.last_name || ' ' || commenters.first_name as created_by
+                FROM comments
+                LEFT JOIN users commenters ON comments.created_by_id = commenters.id
+                WHERE comments.commentable_type = 'tasks'
+                    AND comments.commentable_id = tasks.id
+                    AND comments.deleted_at IS NULL
+                ORDER BY comments.created_at DESC
+                LIMIT 1
+            ) latest_comment ON true
             WHERE tasks.deleted_at IS NULL
                 AND tasks.id = $1
             "#,
@@ -98,30 +225,51 @@ impl TasksRepository for PgPool {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<TaskOrderBy, TaskFilterBy>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<TaskResolved>)> {
+        let archived_condition =
+            archived_project_condition("worksheets.project_id", include_archived);
         let total: (i64,) = match (
             query_params.filtering().filter_by(), // Security: ValueObject
             query_params.filtering().value_unchecked(), // Security: bind
         ) {
             (Some(filter_by), Some(value_unchecked)) => {
-                let filter_by = match filter_by {
-                    "name" => "services.name",
-                    _ => return Err(RepositoryError::InvalidInput("filter_by".to_string())),
-                };
+                let filter_condition = filter_condition(filter_by, value_unchecked)?;
+                let visible_condition = visible_project_condition("worksheets.project_id", 2);
                 sqlx::query_as(AssertSqlSafe(format!(
                     r#"SELECT COUNT(*) FROM tasks
                         LEFT JOIN services ON tasks.service_id = services.id
+                        LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
                         WHERE tasks.deleted_at IS NULL
-                            AND ($1::TEXT IS NULL OR {filter_by}::TEXT ILIKE '%' || $1 || '%')"#
+                            AND ($1::TEXT IS NULL OR {filter_condition})
+                            AND {visible_condition}
+                            AND {archived_condition}
+                            AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("tasks", "tasks.id", 3)
                 )))
                 .bind(value_unchecked)
+                .bind(visible_to)
+                .bind(&tags)
                 .fetch_one(self)
                 .await?
             }
             (_, _) => {
-                sqlx::query_as("SELECT COUNT(*) FROM tasks WHERE deleted_at IS NULL")
-                    .fetch_one(self)
-                    .await?
+                let visible_condition = visible_project_condition("worksheets.project_id", 1);
+                sqlx::query_as(AssertSqlSafe(format!(
+                    r#"SELECT COUNT(*) FROM tasks
+                        LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
+                        WHERE tasks.deleted_at IS NULL
+                            AND {visible_condition}
+                            AND {archived_condition}
+                            AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("tasks", "tasks.id", 2)
+                )))
+                .bind(visible_to)
+                .bind(&tags)
+                .fetch_one(self)
+                .await?
             }
         };
 
@@ -140,10 +288,7 @@ impl TasksRepository for PgPool {
             query_params.filtering().value_unchecked(), // Security: bind
         ) {
             (Some(filter_by), Some(value_unchecked)) => {
-                let filter_by = match filter_by {
-                    "name" => "services.name",
-                    _ => return Err(RepositoryError::InvalidInput("filter_by".to_string())),
-                };
+                let filter_condition = filter_condition(filter_by, value_unchecked)?;
                 let sql = format!(
                     r#"
                     SELECT
@@ -165,24 +310,58 @@ impl TasksRepository for PgPool {
                         tasks.created_at as created_at,
                         tasks.updated_at as updated_at,
                         tasks.deleted_at as deleted_at,
-                        tasks.description as description
+                        tasks.description as description,
+                        tasks.position as position,
+                        (SELECT COUNT(*) FROM task_checklist_items
+                            WHERE task_checklist_items.task_id = tasks.id
+                                AND task_checklist_items.deleted_at IS NULL) as checklist_item_count,
+                        (SELECT COUNT(*) FILTER (WHERE task_checklist_items.is_done) * 100 / NULLIF(COUNT(*), 0)
+                            FROM task_checklist_items
+                            WHERE task_checklist_items.task_id = tasks.id
+                                AND task_checklist_items.deleted_at IS NULL) as checklist_completion,
+                        (SELECT COUNT(*) FROM comments
+                            WHERE comments.commentable_type = 'tasks'
+                                AND comments.commentable_id = tasks.id
+                                AND comments.deleted_at IS NULL) as comment_count,
+                        latest_comment.created_at as latest_comment_at,
+                        latest_comment.created_by_id as latest_comment_by_id,
+                        latest_comment.created_by as latest_comment_by
                     FROM tasks
                     LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
                     LEFT JOIN services ON tasks.service_id = services.id
                     LEFT JOIN taxes ON tasks.tax_id = taxes.id
                     LEFT JOIN users ON tasks.created_by_id = users.id
+                    LEFT JOIN LATERAL (
+                        SELECT comments.created_at,
+                               comments.created_by_id,
+                               commenters.last_name || ' ' || commenters.first_name as created_by
+                        FROM comments
+                        LEFT JOIN users commenters ON comments.created_by_id = commenters.id
+                        WHERE comments.commentable_type = 'tasks'
+                            AND comments.commentable_id = tasks.id
+                            AND comments.deleted_at IS NULL
+                        ORDER BY comments.created_at DESC
+                        LIMIT 1
+                    ) latest_comment ON true
                     WHERE tasks.deleted_at IS NULL
-                        AND ($1::TEXT IS NULL OR {filter_by}::TEXT ILIKE '%' || $1 || '%')
+                        AND ($1::TEXT IS NULL OR {filter_condition})
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}
                     {order_by_clause}
                     LIMIT $2
                     OFFSET $3
-                    "#
+                    "#,
+                    tagged_condition = tagged_condition("tasks", "tasks.id", 5),
+                    visible_condition = visible_project_condition("worksheets.project_id", 4)
                 );
 
                 sqlx::query_as::<_, TaskResolved>(AssertSqlSafe(sql))
                     .bind(value_unchecked)
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(visible_to)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -208,22 +387,56 @@ impl TasksRepository for PgPool {
                         tasks.created_at as created_at,
                         tasks.updated_at as updated_at,
                         tasks.deleted_at as deleted_at,
-                        tasks.description as description
+                        tasks.description as description,
+                        tasks.position as position,
+                        (SELECT COUNT(*) FROM task_checklist_items
+                            WHERE task_checklist_items.task_id = tasks.id
+                                AND task_checklist_items.deleted_at IS NULL) as checklist_item_count,
+                        (SELECT COUNT(*) FILTER (WHERE task_checklist_items.is_done) * 100 / NULLIF(COUNT(*), 0)
+                            FROM task_checklist_items
+                            WHERE task_checklist_items.task_id = tasks.id
+                                AND task_checklist_items.deleted_at IS NULL) as checklist_completion,
+                        (SELECT COUNT(*) FROM comments
+                            WHERE comments.commentable_type = 'tasks'
+                                AND comments.commentable_id = tasks.id
+                                AND comments.deleted_at IS NULL) as comment_count,
+                        latest_comment.created_at as latest_comment_at,
+                        latest_comment.created_by_id as latest_comment_by_id,
+                        latest_comment.created_by as latest_comment_by
                     FROM tasks
                     LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
                     LEFT JOIN services ON tasks.service_id = services.id
                     LEFT JOIN taxes ON tasks.tax_id = taxes.id
                     LEFT JOIN users ON tasks.created_by_id = users.id
+                    LEFT JOIN LATERAL (
+                        SELECT comments.created_at,
+                               comments.created_by_id,
+                               commenters.last_name || ' ' || commenters.first_name as created_by
+                        FROM comments
+                        LEFT JOIN users commenters ON comments.created_by_id = commenters.id
+                        WHERE comments.commentable_type = 'tasks'
+                            AND comments.commentable_id = tasks.id
+                            AND comments.deleted_at IS NULL
+                        ORDER BY comments.created_at DESC
+                        LIMIT 1
+                    ) latest_comment ON true
                     WHERE tasks.deleted_at IS NULL
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}
                     {order_by_clause}
                     LIMIT $1
                     OFFSET $2
-                    "#
+                    "#,
+                    tagged_condition = tagged_condition("tasks", "tasks.id", 4),
+                    visible_condition = visible_project_condition("worksheets.project_id", 3)
                 );
 
                 sqlx::query_as::<_, TaskResolved>(AssertSqlSafe(sql))
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(visible_to)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -246,8 +459,12 @@ impl TasksRepository for PgPool {
             .bind(task.worksheet_id.as_uuid()?)
             .bind(task.service_id.as_uuid()?)
             .bind(task.currency_code.as_str()?)
-            .bind(task.quantity.as_f64())
-            .bind(task.price.as_f64())
+            .bind(task.quantity.as_decimal())
+            .bind(task.price.as_decimal().map(|price| {
+            Money::new(price.clone(), task.currency_code.as_str().unwrap_or_default())
+                .round()
+                .amount
+        }))
             .bind(task.tax_id.as_uuid()?)
             .bind(sub)
             .bind(task.status.as_str()? )
@@ -285,8 +502,15 @@ impl TasksRepository for PgPool {
         .bind(task.worksheet_id.as_uuid()?)
         .bind(task.service_id.as_uuid()?)
         .bind(task.currency_code.as_str()?)
-        .bind(task.quantity.as_f64())
-        .bind(task.price.as_f64())
+        .bind(task.quantity.as_decimal())
+        .bind(task.price.as_decimal().map(|price| {
+            Money::new(
+                price.clone(),
+                task.currency_code.as_str().unwrap_or_default(),
+            )
+            .round()
+            .amount
+        }))
         .bind(task.tax_id.as_uuid()?)
         .bind(task.status.as_str()?)
         .bind(task.priority.as_str())
@@ -312,4 +536,254 @@ impl TasksRepository for PgPool {
 
         Ok(())
     }
+
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Task> {
+        let mut tx = self.begin().await?;
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            INSERT INTO tasks (worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                               created_by_id, status, priority, due_date, description)
+            SELECT worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                   $2, status, priority, due_date, description
+            FROM tasks
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(params.id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        if params.include_tags {
+            copy_tags(&mut tx, "tasks", params.id, task.id, sub).await?;
+        }
+        tx.commit().await?;
+        Ok(task)
+    }
+    async fn get_dependencies(&self, task_id: Uuid) -> RepositoryResult<Vec<TaskDependency>> {
+        Ok(sqlx::query_as::<_, TaskDependency>(AssertSqlSafe(format!(
+            "{TASK_DEPENDENCY_SELECT} AND task_dependencies.task_id = $1 ORDER BY task_dependencies.created_at" // Security: constant
+        )))
+        .bind(task_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_dependency(
+        &self,
+        task_id: Uuid,
+        blocked_by_id: Uuid,
+        sub: Uuid,
+    ) -> RepositoryResult<Option<TaskDependency>> {
+        let mut tx = self.begin().await?;
+        // NOTE: a cycle can close over any path of the dependency graph, so concurrent
+        // inserts are serialized with a transaction level lock instead of row locks
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('task_dependencies'))")
+            .execute(&mut *tx)
+            .await?;
+        let creates_cycle = sqlx::query_scalar::<_, bool>(
+            r#"
+            WITH RECURSIVE blockers AS (
+                SELECT blocked_by_id FROM task_dependencies WHERE task_id = $1
+                UNION
+                SELECT task_dependencies.blocked_by_id
+                FROM task_dependencies
+                JOIN blockers ON task_dependencies.task_id = blockers.blocked_by_id
+            )
+            SELECT EXISTS(SELECT 1 FROM blockers WHERE blocked_by_id = $2)
+            "#,
+        )
+        .bind(blocked_by_id)
+        .bind(task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if creates_cycle {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+        sqlx::query(
+            "INSERT INTO task_dependencies (task_id, blocked_by_id, created_by_id) VALUES ($1, $2, $3)",
+        )
+        .bind(task_id)
+        .bind(blocked_by_id)
+        .bind(sub)
+        .execute(&mut *tx)
+        .await?;
+        let dependency = sqlx::query_as::<_, TaskDependency>(AssertSqlSafe(format!(
+            "{TASK_DEPENDENCY_SELECT} AND task_dependencies.task_id = $1 AND task_dependencies.blocked_by_id = $2" // Security: constant
+        )))
+        .bind(task_id)
+        .bind(blocked_by_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(dependency))
+    }
+
+    async fn delete_dependency(&self, task_id: Uuid, blocked_by_id: Uuid) -> RepositoryResult<()> {
+        let result =
+            sqlx::query("DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_id = $2")
+                .bind(task_id)
+                .bind(blocked_by_id)
+                .execute(self)
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn count_unfinished_blockers(&self, task_id: Uuid) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM task_dependencies
+            JOIN tasks blockers ON task_dependencies.blocked_by_id = blockers.id
+            WHERE task_dependencies.task_id = $1
+                AND blockers.deleted_at IS NULL
+                AND blockers.status <> 'done'
+            "#,
+        )
+        .bind(task_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_board(
+        &self,
+        worksheet_id: Option<Uuid>,
+        visible_to: Option<Uuid>,
+    ) -> RepositoryResult<Vec<TaskResolved>> {
+        Ok(sqlx::query_as::<_, TaskResolved>(AssertSqlSafe(format!(
+            r#"
+            SELECT
+                tasks.id as id,
+                tasks.worksheet_id as worksheet_id,
+                worksheets.name as worksheet,
+                tasks.service_id as service_id,
+                services.name as service,
+                tasks.currency_code as currency_code,
+                tasks.quantity as quantity,
+                tasks.price as price,
+                tasks.tax_id as tax_id,
+                taxes.description as tax,
+                tasks.created_by_id as created_by_id,
+                users.last_name || ' ' || users.first_name as created_by,
+                tasks.status as status,
+                tasks.priority as priority,
+                tasks.due_date as due_date,
+                tasks.created_at as created_at,
+                tasks.updated_at as updated_at,
+                tasks.deleted_at as deleted_at,
+                tasks.description as description,
+                tasks.position as position,
+                (SELECT COUNT(*) FROM task_checklist_items
+                    WHERE task_checklist_items.task_id = tasks.id
+                        AND task_checklist_items.deleted_at IS NULL) as checklist_item_count,
+                (SELECT COUNT(*) FILTER (WHERE task_checklist_items.is_done) * 100 / NULLIF(COUNT(*), 0)
+                    FROM task_checklist_items
+                    WHERE task_checklist_items.task_id = tasks.id
+                        AND task_checklist_items.deleted_at IS NULL) as checklist_completion,
+                (SELECT COUNT(*) FROM comments
+                    WHERE comments.commentable_type = 'tasks'
+                        AND comments.commentable_id = tasks.id
+                        AND comments.deleted_at IS NULL) as comment_count,
+                latest_comment.created_at as latest_comment_at,
+                latest_comment.created_by_id as latest_comment_by_id,
+                latest_comment.created_by as latest_comment_by
+            FROM tasks
+            LEFT JOIN worksheets ON tasks.worksheet_id = worksheets.id
+            LEFT JOIN services ON tasks.service_id = services.id
+            LEFT JOIN taxes ON tasks.tax_id = taxes.id
+            LEFT JOIN users ON tasks.created_by_id = users.id
+            LEFT JOIN LATERAL (
+                SELECT comments.created_at,
+                       comments.created_by_id,
+                       commenters.last_name || ' ' || commenters.first_name as created_by
+                FROM comments
+                LEFT JOIN users commenters ON comments.created_by_id = commenters.id
+                WHERE comments.commentable_type = 'tasks'
+                    AND comments.commentable_id = tasks.id
+                    AND comments.deleted_at IS NULL
+                ORDER BY comments.created_at DESC
+                LIMIT 1
+            ) latest_comment ON true
+            WHERE tasks.deleted_at IS NULL
+                AND ($1::UUID IS NULL OR tasks.worksheet_id = $1)
+                AND {visible_condition}
+                AND {archived_condition}
+            ORDER BY tasks.status, tasks.position, tasks.created_at
+            "#,
+            visible_condition = visible_project_condition("worksheets.project_id", 2),
+            // NOTE: a board opened for a specific worksheet shows it even if its project is archived
+            archived_condition =
+                archived_project_condition("worksheets.project_id", worksheet_id.is_some())
+        )))
+        .bind(worksheet_id)
+        .bind(visible_to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn reorder(&self, input: &TaskReorderInput) -> RepositoryResult<Task> {
+        let mut tx = self.begin().await?;
+        sqlx::query(
+            r#"
+            SELECT id
+            FROM tasks
+            WHERE id = $1
+                AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(input.task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let previous = neighbour_position(&mut tx, input.previous_id, &input.status).await?;
+        let next = neighbour_position(&mut tx, input.next_id, &input.status).await?;
+        let position = match position_between(previous, next) {
+            Some(position) => position,
+            None => {
+                // NOTE: the gap is used up after many drops to the same place, so the column
+                // is spread out again keeping its order
+                sqlx::query(
+                    r#"
+                    UPDATE tasks
+                    SET position = ranked.row_number * $2
+                    FROM (SELECT id,
+                                 row_number() OVER (ORDER BY position, created_at, id) AS row_number
+                          FROM tasks
+                          WHERE status = $1
+                              AND deleted_at IS NULL) ranked
+                    WHERE tasks.id = ranked.id
+                    "#,
+                )
+                .bind(&input.status)
+                .bind(POSITION_GAP)
+                .execute(&mut *tx)
+                .await?;
+                let previous =
+                    neighbour_position(&mut tx, input.previous_id, &input.status).await?;
+                let next = neighbour_position(&mut tx, input.next_id, &input.status).await?;
+                position_between(previous, next)
+                    .ok_or_else(|| RepositoryError::InvalidInput("position".to_string()))?
+            }
+        };
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET status = $2,
+                position = $3
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(input.task_id)
+        .bind(&input.status)
+        .bind(position)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(task)
+    }
 }
@@ -36,7 +36,16 @@ pub fn routes<M: TasksModule>(tasks_module: Arc<M>) -> Router {
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
+            .route("/duplicate", post(handler::duplicate::<M>))
             .route("/print", get(handler::print::<M>))
+            .route("/dependencies", get(handler::dependencies::<M>))
+            .route("/add_dependency", post(handler::add_dependency::<M>))
+            .route(
+                "/remove_dependency",
+                delete(handler::remove_dependency::<M>),
+            )
+            .route("/board", get(handler::board::<M>))
+            .route("/reorder", put(handler::reorder::<M>))
             .layer(from_fn_with_state(tasks_module.clone(), require_auth))
             .with_state(tasks_module),
     )
@@ -17,20 +17,31 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
 use crate::common::model::SelectOption;
 #[double]
 use crate::common::pdf::PdfGenerator;
 use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{
+    can_access_task, ensure_worksheet_access, visible_projects_of,
+};
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
 use crate::tenant::tasks::TasksModule;
+use crate::tenant::tasks::dto::board::{TaskBoardQuery, TaskReorderInput};
+use crate::tenant::tasks::dto::dependency::TaskDependencyInput;
 use crate::tenant::tasks::dto::print::TaskResolvedPrint;
 use crate::tenant::tasks::dto::user_input::TaskUserInput;
-use crate::tenant::tasks::model::{Task, TaskResolved};
-use crate::tenant::tasks::types::task::{TaskFilterBy, TaskOrderBy};
+use crate::tenant::tasks::model::{
+    BLOCKED_STATUSES, BOARD_STATUSES, Task, TaskBoardColumn, TaskDependency, TaskResolved,
+};
+use crate::tenant::tasks::repository::TasksRepository;
+use crate::tenant::tasks::types::task::{TaskFilterBy, TaskOrderBy, TaskPrice};
 use axum::http::StatusCode;
 use chrono::{DateTime, Duration, Utc};
 use chrono_tz::Tz;
@@ -52,6 +63,9 @@ pub enum TasksServiceError {
     #[error("Hozzáférés megtagadva!")]
     Unauthorized,
 
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
     #[error("Hiba történt az adatok feldolgozása során: {0}")]
     UnprocessableEntry(&'static str),
 
@@ -86,6 +100,16 @@ impl From<TasksServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            TasksServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
             TasksServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -93,6 +117,13 @@ impl From<TasksServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            TasksServiceError::Repository(ref e) if e.is_project_archived_violation() => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": TasksServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+            ),
             TasksServiceError::Repository(RepositoryError::Database(sqlx::Error::RowNotFound)) => {
                 Self::new(
                     Level::DEBUG,
@@ -155,30 +186,143 @@ pub trait TaskService {
         payload: &TaskUserInput,
     ) -> impl Future<Output = TasksServiceResult<Task>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = TasksServiceResult<()>> + Send;
+    fn duplicate(
+        &self,
+        payload: &DuplicateParams,
+    ) -> impl Future<Output = TasksServiceResult<Task>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<TaskOrderBy, TaskFilterBy>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> impl Future<Output = TasksServiceResult<(PaginatorMeta, Vec<TaskResolved>)>> + Send;
     fn print(
         &self,
         payload: &[TaskResolvedPrint],
     ) -> impl Future<Output = TasksServiceResult<Vec<u8>>> + Send;
     fn print_snapshot(&self, path: &Path) -> impl Future<Output = TasksServiceResult<()>> + Sync;
+    fn get_dependencies(
+        &self,
+        task_id: Uuid,
+    ) -> impl Future<Output = TasksServiceResult<Vec<TaskDependency>>> + Send;
+    fn add_dependency(
+        &self,
+        payload: &TaskDependencyInput,
+    ) -> impl Future<Output = TasksServiceResult<TaskDependency>> + Send;
+    fn remove_dependency(
+        &self,
+        payload: &TaskDependencyInput,
+    ) -> impl Future<Output = TasksServiceResult<()>> + Send;
+    fn board(
+        &self,
+        payload: &TaskBoardQuery,
+    ) -> impl Future<Output = TasksServiceResult<Vec<TaskBoardColumn>>> + Send;
+    fn reorder(
+        &self,
+        payload: &TaskReorderInput,
+    ) -> impl Future<Output = TasksServiceResult<Task>> + Send;
+    fn ensure_task_access(
+        &self,
+        task_id: Uuid,
+        access: ProjectAccess,
+    ) -> impl Future<Output = TasksServiceResult<()>> + Send;
+    fn visible_to(&self) -> impl Future<Output = TasksServiceResult<Option<Uuid>>> + Send;
+}
+
+// NOTE: only entering a blocked status is checked, so a task already in progress stays editable
+async fn ensure_not_blocked(
+    repo: &(dyn TasksRepository + Send + Sync),
+    id: Uuid,
+    status: &str,
+) -> TasksServiceResult<()> {
+    if !BLOCKED_STATUSES.contains(&status) || repo.get_by_id(id).await?.status == status {
+        return Ok(());
+    }
+    if repo.count_unfinished_blockers(id).await? > 0 {
+        return Err(TasksServiceError::UnprocessableEntry(
+            "A feladat nem kezdhető meg és nem zárható le, amíg az előfeltételei nincsenek készen!",
+        ));
+    }
+    Ok(())
+}
+
+// NOTE: tasks without an explicit price are billed at the rate resolved for the worksheet's customer
+async fn with_resolved_rate<T: TasksModule>(
+    module: &T,
+    tenant_id: Uuid,
+    payload: &TaskUserInput,
+) -> TasksServiceResult<TaskUserInput> {
+    let mut task = payload.clone();
+    if task.price.is_present() {
+        return Ok(task);
+    }
+    let invalid = |_| TasksServiceError::UnprocessableEntry(TaskPrice::PARSE_ERROR);
+    let worksheet = module
+        .worksheets_repo(tenant_id)?
+        .get_by_id(task.worksheet_id.as_uuid().map_err(invalid)?)
+        .await?;
+    let rate = module
+        .services_repo(tenant_id)?
+        .resolve_rate(
+            task.service_id.as_uuid().map_err(invalid)?,
+            Some(worksheet.customer_id),
+            Utc::now().date_naive(),
+        )
+        .await?;
+    if let (Some(price), Some(currency_code)) = (rate.price, rate.currency_code) {
+        task.price = price.to_string().parse().map_err(invalid)?;
+        task.currency_code = currency_code.parse().map_err(invalid)?;
+    }
+    Ok(task)
 }
 
 impl<'a, T> TaskService for Service<'a, T>
 where
     T: TasksModule,
 {
+    async fn ensure_task_access(
+        &self,
+        task_id: Uuid,
+        access: ProjectAccess,
+    ) -> TasksServiceResult<()> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(TasksServiceError::Unauthorized)?;
+        if !can_access_task(self.module(), tenant_id, claims.sub(), task_id, access).await? {
+            return Err(TasksServiceError::Forbidden);
+        }
+        Ok(())
+    }
+
+    async fn visible_to(&self) -> TasksServiceResult<Option<Uuid>> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(TasksServiceError::Unauthorized)?;
+        Ok(visible_projects_of(self.module(), tenant_id, claims.sub()).await?)
+    }
+
     async fn insert(&self, payload: &TaskUserInput) -> TasksServiceResult<Task> {
+        let active_tenant = self
+            .claims()?
+            .active_tenant()
+            .ok_or(TasksServiceError::Unauthorized)?;
+        // Tasks can only be added to or moved onto the worksheets of projects the user can edit
+        if let Ok(worksheet_id) = payload.worksheet_id.as_uuid() {
+            ensure_worksheet_access(
+                self,
+                worksheet_id,
+                ProjectAccess::Write,
+                TasksServiceError::Forbidden,
+            )
+            .await?;
+        }
+        let task = with_resolved_rate(self.module(), active_tenant, payload).await?;
         Ok(self
             .module()
-            .tasks_repo(
-                self.claims()?
-                    .active_tenant()
-                    .ok_or(TasksServiceError::Unauthorized)?,
-            )?
-            .insert(payload, self.claims()?.sub())
+            .tasks_repo(active_tenant)?
+            .insert(&task, self.claims()?.sub())
             .await?)
     }
     async fn get_select_list_items(
@@ -217,6 +361,8 @@ where
         })
     }
     async fn get_resolved(&self, payload: Uuid) -> TasksServiceResult<TaskResolved> {
+        self.ensure_task_access(payload, ProjectAccess::Read)
+            .await?;
         Ok(self
             .module()
             .tasks_repo(
@@ -229,6 +375,8 @@ where
     }
 
     async fn get(&self, payload: Uuid) -> TasksServiceResult<Task> {
+        self.ensure_task_access(payload, ProjectAccess::Read)
+            .await?;
         Ok(self
             .module()
             .tasks_repo(
@@ -245,6 +393,33 @@ where
                 "Az azonosító megadása kötelező!",
             ));
         }
+        let active_tenant = self
+            .claims()?
+            .active_tenant()
+            .ok_or(TasksServiceError::Unauthorized)?;
+        if let Some(id) = payload.id.as_uuid() {
+            self.ensure_task_access(id, ProjectAccess::Write).await?;
+        }
+        // Tasks can only be added to or moved onto the worksheets of projects the user can edit
+        if let Ok(worksheet_id) = payload.worksheet_id.as_uuid() {
+            ensure_worksheet_access(
+                self,
+                worksheet_id,
+                ProjectAccess::Write,
+                TasksServiceError::Forbidden,
+            )
+            .await?;
+        }
+        let task = with_resolved_rate(self.module(), active_tenant, payload).await?;
+        let repo = self.module().tasks_repo(active_tenant)?;
+        if let (Some(id), Ok(status)) = (task.id.as_uuid(), task.status.as_str()) {
+            ensure_not_blocked(&*repo, id, status).await?;
+        }
+        Ok(repo.update(&task).await?)
+    }
+    async fn delete(&self, payload: Uuid) -> TasksServiceResult<()> {
+        self.ensure_task_access(payload, ProjectAccess::Write)
+            .await?;
         Ok(self
             .module()
             .tasks_repo(
@@ -252,10 +427,12 @@ where
                     .active_tenant()
                     .ok_or(TasksServiceError::Unauthorized)?,
             )?
-            .update(payload)
+            .delete_by_id(payload)
             .await?)
     }
-    async fn delete(&self, payload: Uuid) -> TasksServiceResult<()> {
+    async fn duplicate(&self, payload: &DuplicateParams) -> TasksServiceResult<Task> {
+        self.ensure_task_access(payload.id, ProjectAccess::Write)
+            .await?;
         Ok(self
             .module()
             .tasks_repo(
@@ -263,13 +440,16 @@ where
                     .active_tenant()
                     .ok_or(TasksServiceError::Unauthorized)?,
             )?
-            .delete_by_id(payload)
+            .duplicate(payload, self.claims()?.sub())
             .await?)
     }
     async fn get_paged(
         &self,
         get_query: &ResourceQuery<TaskOrderBy, TaskFilterBy>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> TasksServiceResult<(PaginatorMeta, Vec<TaskResolved>)> {
+        let visible_to = self.visible_to().await?;
         Ok(self
             .module()
             .tasks_repo(
@@ -277,7 +457,7 @@ where
                     .active_tenant()
                     .ok_or(TasksServiceError::Unauthorized)?,
             )?
-            .get_paged(get_query)
+            .get_paged(get_query, visible_to, include_archived, tags)
             .await?)
     }
 
@@ -330,6 +510,13 @@ where
             updated_at: test_time,
             deleted_at: None,
             description: None,
+            position: 1024.0,
+            checklist_item_count: 0,
+            checklist_completion: None,
+            comment_count: 0,
+            latest_comment_at: None,
+            latest_comment_by_id: None,
+            latest_comment_by: None,
         };
         let task_resolved_print = TaskResolvedPrint::from_task_resolved(task_resolved, tz);
         let pdf = self.print(&[task_resolved_print]).await?;
@@ -337,4 +524,101 @@ where
         file.write_all(&pdf)?;
         Ok(())
     }
+
+    async fn get_dependencies(&self, task_id: Uuid) -> TasksServiceResult<Vec<TaskDependency>> {
+        self.ensure_task_access(task_id, ProjectAccess::Read)
+            .await?;
+        Ok(self
+            .module()
+            .tasks_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TasksServiceError::Unauthorized)?,
+            )?
+            .get_dependencies(task_id)
+            .await?)
+    }
+
+    async fn add_dependency(
+        &self,
+        payload: &TaskDependencyInput,
+    ) -> TasksServiceResult<TaskDependency> {
+        if payload.task_id == payload.blocked_by_id {
+            return Err(TasksServiceError::UnprocessableEntry(
+                "A feladat nem függhet saját magától!",
+            ));
+        }
+        self.ensure_task_access(payload.task_id, ProjectAccess::Write)
+            .await?;
+        self.ensure_task_access(payload.blocked_by_id, ProjectAccess::Read)
+            .await?;
+        let repo = self.module().tasks_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TasksServiceError::Unauthorized)?,
+        )?;
+        repo.get_by_id(payload.task_id).await?;
+        repo.get_by_id(payload.blocked_by_id).await?;
+        repo.insert_dependency(payload.task_id, payload.blocked_by_id, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    TasksServiceError::UnprocessableEntry("A függőség már létezik!")
+                } else {
+                    e.into()
+                }
+            })?
+            .ok_or(TasksServiceError::UnprocessableEntry(
+                "A függőség körkörös hivatkozást hozna létre!",
+            ))
+    }
+
+    async fn remove_dependency(&self, payload: &TaskDependencyInput) -> TasksServiceResult<()> {
+        self.ensure_task_access(payload.task_id, ProjectAccess::Write)
+            .await?;
+        Ok(self
+            .module()
+            .tasks_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TasksServiceError::Unauthorized)?,
+            )?
+            .delete_dependency(payload.task_id, payload.blocked_by_id)
+            .await?)
+    }
+
+    async fn board(&self, payload: &TaskBoardQuery) -> TasksServiceResult<Vec<TaskBoardColumn>> {
+        let visible_to = self.visible_to().await?;
+        let tasks = self
+            .module()
+            .tasks_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TasksServiceError::Unauthorized)?,
+            )?
+            .get_board(payload.worksheet_id, visible_to)
+            .await?;
+        Ok(TaskBoardColumn::group(tasks))
+    }
+
+    async fn reorder(&self, payload: &TaskReorderInput) -> TasksServiceResult<Task> {
+        if !BOARD_STATUSES.contains(&payload.status.as_str()) {
+            return Err(TasksServiceError::UnprocessableEntry("Hibás státusz!"));
+        }
+        if payload.previous_id == Some(payload.task_id)
+            || payload.next_id == Some(payload.task_id)
+            || (payload.previous_id.is_some() && payload.previous_id == payload.next_id)
+        {
+            return Err(TasksServiceError::UnprocessableEntry("Hibás célpozíció!"));
+        }
+        self.ensure_task_access(payload.task_id, ProjectAccess::Write)
+            .await?;
+        let repo = self.module().tasks_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TasksServiceError::Unauthorized)?,
+        )?;
+        ensure_not_blocked(&*repo, payload.task_id, &payload.status).await?;
+        Ok(repo.reorder(payload).await?)
+    }
 }
@@ -37,6 +37,8 @@ impl ValueObjectData for FilterBy {
     fn validate(&self) -> Result<(), ValueObjectError> {
         match self.0.as_str() {
             "name" => Ok(()),
+            "ready_to_start" => Ok(()),
+            "checklist_completion" => Ok(()),
             _ => Err(ValueObjectError::InvalidInput("Hibás sorrend formátum")),
         }
     }
@@ -18,23 +18,26 @@
  */
 
 use crate::common::value_object::*;
+use bigdecimal::BigDecimal;
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct Price(f64);
+pub struct Price(BigDecimal);
 
 impl Price {
     pub const PARSE_ERROR: &'static str = "Hibás fogyasztói ár formátum!";
 }
 
 impl ValueObjectData for Price {
-    type DataType = f64;
+    type DataType = BigDecimal;
 
     fn new(data: &str) -> ValueObjectResult<Option<Self>> {
         if !data.trim().is_empty() {
-            Ok(Some(Self(data.replace(",", ".").parse::<f64>().map_err(
-                |_| ValueObjectError::InvalidInput(Self::PARSE_ERROR),
-            )?)))
+            Ok(Some(Self(
+                BigDecimal::from_str(&data.trim().replace(",", "."))
+                    .map_err(|_| ValueObjectError::InvalidInput(Self::PARSE_ERROR))?,
+            )))
         } else {
             Ok(None)
         }
@@ -61,10 +64,16 @@ mod tests {
     #[test]
     fn test_valid_price() {
         let price = "123.45".parse::<ValueObjectRequired<Price>>().unwrap();
-        assert_eq!(price.as_f64().unwrap(), 123.45_f64);
+        assert_eq!(
+            price.as_decimal().unwrap(),
+            &BigDecimal::from_str("123.45").unwrap()
+        );
 
         let price = "123,45".parse::<ValueObjectRequired<Price>>().unwrap();
-        assert_eq!(price.as_f64().unwrap(), 123.45_f64);
+        assert_eq!(
+            price.as_decimal().unwrap(),
+            &BigDecimal::from_str("123.45").unwrap()
+        );
     }
 
     #[test]
@@ -42,6 +42,9 @@ impl ValueObjectData for Status {
         match self.0.as_str() {
             "active" => Ok(()),
             "inactive" => Ok(()),
+            "pending" => Ok(()),
+            "in_progress" => Ok(()),
+            "done" => Ok(()),
             _ => Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR)),
         }
     }
@@ -67,6 +70,14 @@ mod tests {
         assert_eq!(status.as_str().unwrap(), "active");
     }
 
+    #[test]
+    fn test_valid_workflow_statuses() {
+        for value in ["pending", "in_progress", "done"] {
+            let status = value.parse::<ValueObjectRequired<Status>>().unwrap();
+            assert_eq!(status.as_str().unwrap(), value);
+        }
+    }
+
     #[test]
     fn test_invalid_status() {
         let status = "invalid".parse::<ValueObjectRequired<Status>>();
@@ -19,3 +19,4 @@
 
 pub mod print;
 pub mod user_input;
+pub mod version;
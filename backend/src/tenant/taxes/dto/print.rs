@@ -121,6 +121,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: input_date,
@@ -25,6 +25,7 @@ use crate::tenant::taxes::types::legal_text::LegalText;
 use crate::tenant::taxes::types::reporting_code::ReportingCode;
 use crate::tenant::taxes::types::{
     TaxCategory, TaxDescription, TaxLegalText, TaxRate, TaxReportingCode, TaxStatus,
+    TaxValidityDate,
 };
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
@@ -44,6 +45,10 @@ pub struct TaxUserInputHelper {
     pub reporting_code: String,
     pub is_default: bool,
     pub status: String,
+    #[serde(default)]
+    pub valid_from: String,
+    #[serde(default)]
+    pub valid_to: String,
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -58,6 +63,8 @@ pub struct TaxUserInputError {
     pub reporting_code: Option<String>,
     pub is_default: Option<String>,
     pub status: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_to: Option<String>,
 }
 
 impl TaxUserInputError {
@@ -72,6 +79,8 @@ impl TaxUserInputError {
             && self.reporting_code.is_none()
             && self.is_default.is_none()
             && self.status.is_none()
+            && self.valid_from.is_none()
+            && self.valid_to.is_none()
     }
 }
 
@@ -117,6 +126,8 @@ pub struct TaxUserInput {
     pub reporting_code: ValueObjectOptional<TaxReportingCode>,
     pub is_default: bool,
     pub status: ValueObjectRequired<TaxStatus>,
+    pub valid_from: ValueObjectOptional<TaxValidityDate>,
+    pub valid_to: ValueObjectOptional<TaxValidityDate>,
 }
 
 impl TryFrom<TaxUserInputHelper> for TaxUserInput {
@@ -191,6 +202,28 @@ impl TryFrom<TaxUserInputHelper> for TaxUserInput {
                 error.status = Some(e.to_string());
             });
 
+        let valid_from = value
+            .valid_from
+            .parse::<ValueObjectOptional<TaxValidityDate>>()
+            .inspect_err(|e| {
+                error.valid_from = Some(e.to_string());
+            });
+
+        let valid_to = value
+            .valid_to
+            .parse::<ValueObjectOptional<TaxValidityDate>>()
+            .inspect_err(|e| {
+                error.valid_to = Some(e.to_string());
+            });
+
+        if let (Ok(valid_from), Ok(valid_to)) = (&valid_from, &valid_to)
+            && let (Some(valid_from), Some(valid_to)) =
+                (valid_from.as_date_naive(), valid_to.as_date_naive())
+            && valid_to < valid_from
+        {
+            error.valid_to = Some(TaxValidityDate::PERIOD_ERROR.to_string());
+        }
+
         if error.is_empty() {
             Ok(TaxUserInput {
                 id: id?,
@@ -203,6 +236,8 @@ impl TryFrom<TaxUserInputHelper> for TaxUserInput {
                 reporting_code: reporting_code?,
                 is_default: value.is_default,
                 status: status?,
+                valid_from: valid_from?,
+                valid_to: valid_to?,
             })
         } else {
             Err(error)
@@ -227,6 +262,8 @@ mod tests {
             reporting_code: String::from("reporting code"),
             is_default: false,
             status: String::from("active"),
+            valid_from: String::from("2026-01-01"),
+            valid_to: String::new(),
         })
         .unwrap();
 
@@ -242,6 +279,11 @@ mod tests {
         );
         assert!(!user_input.is_default);
         assert_eq!(user_input.status.as_str().unwrap(), "active");
+        assert_eq!(
+            user_input.valid_from.as_date_naive().unwrap().to_string(),
+            "2026-01-01"
+        );
+        assert!(user_input.valid_to.as_date_naive().is_none());
     }
 
     #[test]
@@ -259,6 +301,8 @@ mod tests {
             reporting_code: invalid_reporting_code,
             is_default: false,
             status: String::from("activee"),
+            valid_from: String::from("2026-01-01"),
+            valid_to: String::from("2025-12-31"),
         })
         .unwrap_err();
 
@@ -283,5 +327,6 @@ mod tests {
         );
         assert_eq!(user_input.is_default, None);
         assert_eq!(user_input.status.unwrap(), TaxStatus::VALIDATION_ERROR);
+        assert_eq!(user_input.valid_to.unwrap(), TaxValidityDate::PERIOD_ERROR);
     }
 }
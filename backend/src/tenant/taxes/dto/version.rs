@@ -0,0 +1,38 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Supersedes `tax_id` with a new rate from `valid_from`, closing the old period the day before
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct NewTaxVersion {
+    pub tax_id: Uuid,
+    pub rate: BigDecimal,
+    pub valid_from: NaiveDate,
+}
+
+/// `date` defaults to today
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TaxEffectiveQuery {
+    pub tax_id: Uuid,
+    pub date: Option<NaiveDate>,
+}
@@ -18,7 +18,7 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
 use crate::common::service::Service;
@@ -26,6 +26,7 @@ use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::taxes::TaxesModuleInterface;
 use crate::tenant::taxes::dto::print::TaxResolvedPrint;
 use crate::tenant::taxes::dto::user_input::{TaxUserInput, TaxUserInputHelper};
+use crate::tenant::taxes::dto::version::{NewTaxVersion, TaxEffectiveQuery};
 use crate::tenant::taxes::service::TaxService;
 use crate::tenant::taxes::types::{TaxFilterBy, TaxOrderBy};
 use axum::extract::{Query, State};
@@ -212,6 +213,64 @@ pub async fn print<M: TaxesModuleInterface>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
+pub async fn effective<M: TaxesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(taxes_module): State<Arc<M>>,
+    Query(payload): Query<TaxEffectiveQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), taxes_module.clone());
+    let result = map_handler_err(service.effective(&payload).await, taxes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        taxes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn customer_jurisdiction<M: TaxesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(taxes_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), taxes_module.clone());
+    let result = map_handler_err(
+        service.customer_jurisdiction(payload.uuid).await,
+        taxes_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        taxes_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn new_version<M: TaxesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(taxes_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<NewTaxVersion>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), taxes_module.clone());
+    let result = map_handler_err(service.new_version(&payload).await, taxes_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        taxes_module,
+    )
+    .await?
+    .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +282,8 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
+    use crate::tenant::address::model::BillingAddress;
+    use crate::tenant::address::repository::MockAddressRepository;
     use crate::tenant::taxes::model::TaxResolved;
     use crate::{
         common::config::tests::AppConfigBuilder,
@@ -232,7 +293,8 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
-    use chrono::{DateTime, Utc};
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, NaiveDate, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
     use serde_json::json;
@@ -259,6 +321,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_at: utc_now,
             updated_at: utc_now,
@@ -351,6 +416,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -391,6 +457,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -472,6 +539,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -497,6 +565,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: utc_now,
@@ -590,6 +661,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -630,6 +702,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -711,6 +784,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -741,6 +815,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: utc_now,
@@ -834,6 +911,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -872,6 +950,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -953,6 +1032,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -978,6 +1058,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_at: utc_now,
             updated_at: utc_now,
@@ -995,6 +1078,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
         let user_input = TaxUserInput::try_from(user_input_helper.clone()).unwrap();
 
@@ -1082,6 +1167,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "activee".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1119,6 +1206,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             }
@@ -1140,6 +1228,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1172,6 +1262,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1192,6 +1283,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1224,6 +1317,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1244,6 +1338,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let app_state = MockTaxesModule::new();
@@ -1288,6 +1384,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
         let user_input = TaxUserInput::try_from(user_input_helper.clone()).unwrap();
         let tax = Tax {
@@ -1301,6 +1399,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_at: utc_now,
             updated_at: utc_now,
@@ -1377,6 +1478,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1412,6 +1515,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             }
         });
@@ -1434,6 +1538,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1466,6 +1572,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1488,6 +1595,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let mut app_state = MockTaxesModule::new();
@@ -1520,6 +1629,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1541,6 +1651,8 @@ mod tests {
             reporting_code: "".to_string(),
             is_default: true,
             status: "active".to_string(),
+            valid_from: String::new(),
+            valid_to: String::new(),
         };
 
         let app_state = MockTaxesModule::new();
@@ -1697,6 +1809,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1737,6 +1850,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1790,6 +1904,9 @@ mod tests {
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: test_time,
@@ -1910,6 +2027,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1951,6 +2069,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1983,4 +2102,319 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    fn versioned_tax(
+        id: Uuid,
+        rate: &str,
+        valid_from: Option<NaiveDate>,
+        previous_version_id: Option<Uuid>,
+    ) -> Tax {
+        let utc_now = Utc::now();
+        Tax {
+            id,
+            rate: Some(rate.parse().unwrap()),
+            description: "ÁFA".to_string(),
+            country_code: "HU".to_string(),
+            tax_category: "standard".to_string(),
+            is_rate_applicable: true,
+            legal_text: None,
+            reporting_code: None,
+            is_default: true,
+            status: "active".to_string(),
+            valid_from,
+            valid_to: None,
+            previous_version_id,
+            created_by_id: Uuid::new_v4(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_effective_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let tax_id = Uuid::new_v4();
+        let on = NaiveDate::from_ymd_opt(2027, 3, 1).unwrap();
+        let tax = versioned_tax(
+            Uuid::new_v4(),
+            "25",
+            NaiveDate::from_ymd_opt(2027, 1, 1),
+            Some(tax_id),
+        );
+
+        let mut repo = MockTaxesRepository::new();
+        repo.expect_get_effective()
+            .times(1)
+            .with(eq(tax_id), eq(on))
+            .returning({
+                let tax = tax.clone();
+                move |_, _| Ok(tax.clone())
+            });
+
+        let mut app_state = MockTaxesModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_taxes_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("GET")
+            .uri(format!(
+                "/api/taxes/effective?tax_id={tax_id}&date=2027-03-01"
+            ))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(taxes::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": tax
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_new_version_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let previous = versioned_tax(Uuid::new_v4(), "27", None, None);
+        let valid_from = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let created = versioned_tax(Uuid::new_v4(), "25", Some(valid_from), Some(previous.id));
+
+        let mut repo = MockTaxesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(previous.id))
+            .returning({
+                let previous = previous.clone();
+                move |_| Ok(previous.clone())
+            });
+        repo.expect_insert_version()
+            .times(1)
+            .withf({
+                let previous_id = previous.id;
+                move |previous, version, sub| {
+                    previous.id == previous_id
+                        && version.rate == "25".parse::<BigDecimal>().unwrap()
+                        && version.valid_from == valid_from
+                        && *sub == user_id
+                }
+            })
+            .returning({
+                let created = created.clone();
+                move |_, _, _| Ok(created.clone())
+            });
+
+        let mut app_state = MockTaxesModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_taxes_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(user_id), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/taxes/new_version")
+            .body(
+                json!({
+                    "tax_id": previous.id,
+                    "rate": "25",
+                    "valid_from": "2027-01-01"
+                })
+                .to_string(),
+            )
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(taxes::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": created
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_new_version_must_start_after_previous() {
+        let active_tenant_id = Uuid::new_v4();
+        let previous = versioned_tax(
+            Uuid::new_v4(),
+            "27",
+            NaiveDate::from_ymd_opt(2027, 1, 1),
+            None,
+        );
+
+        let mut repo = MockTaxesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(previous.id))
+            .returning({
+                let previous = previous.clone();
+                move |_| Ok(previous.clone())
+            });
+        repo.expect_insert_version().never();
+
+        let mut app_state = MockTaxesModule::new();
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_taxes_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/taxes/new_version")
+            .body(
+                json!({
+                    "tax_id": previous.id,
+                    "rate": "25",
+                    "valid_from": "2026-12-31"
+                })
+                .to_string(),
+            )
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(taxes::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "error": {
+                "code": "UNPROCESSABLE_ENTRY",
+                "message": "Hiba történt az adatok feldolgozása során: Az új adókulcs érvényessége az előző kezdete után kell induljon!"
+            }
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    #[tokio::test]
+    async fn test_customer_jurisdiction_suggests_eu_supply_for_eu_business() {
+        let active_tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+
+        let mut address_repo = MockAddressRepository::new();
+        address_repo
+            .expect_get_customer_billing_address()
+            .times(1)
+            .with(eq(customer_id))
+            .returning(|_| {
+                Ok(Some(BillingAddress {
+                    country_code: "AT".to_string(),
+                    region: Some("Wien".to_string()),
+                    is_business: true,
+                }))
+            });
+
+        let mut app_state = MockTaxesModule::new();
+        let address_repo = Arc::new(address_repo);
+        app_state
+            .expect_address_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(address_repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!(
+                "/api/taxes/customer_jurisdiction?uuid={customer_id}"
+            ))
+            .body("".to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(taxes::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["jurisdiction"], json!("eu_b2b"));
+        assert_eq!(
+            response_body["data"]["suggested_vat_treatment"],
+            json!("eu_supply")
+        );
+        assert_eq!(
+            response_body["data"]["allowed_vat_treatments"],
+            json!(["domestic", "aam", "eu_supply"])
+        );
+    }
 }
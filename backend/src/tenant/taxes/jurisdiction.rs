@@ -0,0 +1,243 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::address::model::BillingAddress;
+use crate::tenant::address::repository::AddressRepository;
+use crate::tenant::taxes::vat_treatment::VatTreatment;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const HOME_COUNTRY_CODE: &str = "HU";
+
+// NOTE: ISO 3166 codes of the EU member states, Greece is GR here, not EL as in VAT numbers
+const EU_COUNTRY_CODES: [&str; 27] = [
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+// NOTE: territories of member states outside the EU VAT area, matched by the region name of the
+// address as it is typed in, so the local and the Hungarian names are both listed
+const NON_VAT_REGIONS: [(&str, &[&str]); 6] = [
+    (
+        "ES",
+        &[
+            "canarias",
+            "islas canarias",
+            "kanári-szigetek",
+            "canary islands",
+            "ceuta",
+            "melilla",
+        ],
+    ),
+    ("GR", &["agion oros", "athosz", "mount athos"]),
+    ("FI", &["åland", "aland", "ahvenanmaa", "ålandszigetek"]),
+    (
+        "FR",
+        &[
+            "guadeloupe",
+            "martinique",
+            "réunion",
+            "reunion",
+            "guyane",
+            "francia guyana",
+            "mayotte",
+        ],
+    ),
+    ("DE", &["helgoland", "büsingen", "büsingen am hochrhein"]),
+    ("IT", &["livigno", "campione d'italia"]),
+];
+
+/// Where the supplies of a document are taxed, decided by the billing address of the customer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxJurisdiction {
+    Domestic,
+    EuB2b,
+    EuB2c,
+    Export,
+}
+
+impl TaxJurisdiction {
+    pub const MISMATCH: &'static str =
+        "A tétel áfakezelése nem felel meg az ügyfél számlázási címének!";
+
+    /// Domestic when the customer has no address yet
+    pub fn of(address: Option<&BillingAddress>) -> Self {
+        let Some(address) = address else {
+            return TaxJurisdiction::Domestic;
+        };
+        let country_code = address.country_code.trim().to_uppercase();
+        if country_code == HOME_COUNTRY_CODE {
+            TaxJurisdiction::Domestic
+        } else if !EU_COUNTRY_CODES.contains(&country_code.as_str())
+            || is_non_vat_region(&country_code, address.region.as_deref())
+        {
+            TaxJurisdiction::Export
+        } else if address.is_business {
+            TaxJurisdiction::EuB2b
+        } else {
+            TaxJurisdiction::EuB2c
+        }
+    }
+
+    /// The treatment offered for the new lines of a document
+    pub fn suggested_treatment(&self) -> VatTreatment {
+        match self {
+            TaxJurisdiction::Domestic | TaxJurisdiction::EuB2c => VatTreatment::Domestic,
+            TaxJurisdiction::EuB2b => VatTreatment::EuSupply,
+            TaxJurisdiction::Export => VatTreatment::Export,
+        }
+    }
+
+    /// Domestic VAT and subject exemption can be invoiced to anyone, the other treatments only
+    /// to a customer whose address matches them
+    pub fn allows(&self, treatment: VatTreatment) -> bool {
+        match treatment {
+            VatTreatment::Domestic | VatTreatment::Aam => true,
+            VatTreatment::ReverseCharge => *self == TaxJurisdiction::Domestic,
+            VatTreatment::EuSupply => *self == TaxJurisdiction::EuB2b,
+            VatTreatment::Export => *self == TaxJurisdiction::Export,
+        }
+    }
+
+    pub fn allowed_treatments(&self) -> Vec<VatTreatment> {
+        [
+            VatTreatment::Domestic,
+            VatTreatment::ReverseCharge,
+            VatTreatment::Aam,
+            VatTreatment::EuSupply,
+            VatTreatment::Export,
+        ]
+        .into_iter()
+        .filter(|treatment| self.allows(*treatment))
+        .collect()
+    }
+}
+
+/// Resolves the jurisdiction of a customer from its billing address
+pub(crate) async fn customer_jurisdiction(
+    address_repo: &(dyn AddressRepository + Send + Sync),
+    customer_id: Uuid,
+) -> RepositoryResult<TaxJurisdiction> {
+    Ok(TaxJurisdiction::of(
+        address_repo
+            .get_customer_billing_address(customer_id)
+            .await?
+            .as_ref(),
+    ))
+}
+
+fn is_non_vat_region(country_code: &str, region: Option<&str>) -> bool {
+    let Some(region) = region.map(|region| region.trim().to_lowercase()) else {
+        return false;
+    };
+    NON_VAT_REGIONS
+        .iter()
+        .any(|(code, regions)| *code == country_code && regions.contains(&region.as_str()))
+}
+
+/// The tax jurisdiction of a customer with the VAT treatments its documents can carry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerTaxJurisdiction {
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+    pub jurisdiction: TaxJurisdiction,
+    pub suggested_vat_treatment: VatTreatment,
+    pub allowed_vat_treatments: Vec<VatTreatment>,
+}
+
+impl CustomerTaxJurisdiction {
+    pub fn new(address: Option<BillingAddress>) -> Self {
+        let jurisdiction = TaxJurisdiction::of(address.as_ref());
+        Self {
+            country_code: address.as_ref().map(|address| address.country_code.clone()),
+            region: address.and_then(|address| address.region),
+            jurisdiction,
+            suggested_vat_treatment: jurisdiction.suggested_treatment(),
+            allowed_vat_treatments: jurisdiction.allowed_treatments(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(country_code: &str, region: Option<&str>, is_business: bool) -> BillingAddress {
+        BillingAddress {
+            country_code: country_code.to_string(),
+            region: region.map(str::to_string),
+            is_business,
+        }
+    }
+
+    #[test]
+    fn test_jurisdiction_of_address() {
+        assert_eq!(TaxJurisdiction::of(None), TaxJurisdiction::Domestic);
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("HU", Some("Pest"), true))),
+            TaxJurisdiction::Domestic
+        );
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("at", None, true))),
+            TaxJurisdiction::EuB2b
+        );
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("AT", None, false))),
+            TaxJurisdiction::EuB2c
+        );
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("CH", None, true))),
+            TaxJurisdiction::Export
+        );
+    }
+
+    #[test]
+    fn test_region_outside_vat_area_is_export() {
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("ES", Some(" Islas Canarias "), true))),
+            TaxJurisdiction::Export
+        );
+        assert_eq!(
+            TaxJurisdiction::of(Some(&address("ES", Some("Madrid"), true))),
+            TaxJurisdiction::EuB2b
+        );
+    }
+
+    #[test]
+    fn test_allowed_treatments() {
+        assert_eq!(
+            TaxJurisdiction::Domestic.allowed_treatments(),
+            vec![
+                VatTreatment::Domestic,
+                VatTreatment::ReverseCharge,
+                VatTreatment::Aam
+            ]
+        );
+        assert!(TaxJurisdiction::EuB2b.allows(VatTreatment::EuSupply));
+        assert!(!TaxJurisdiction::EuB2c.allows(VatTreatment::EuSupply));
+        assert!(!TaxJurisdiction::Domestic.allows(VatTreatment::Export));
+        assert!(!TaxJurisdiction::Export.allows(VatTreatment::ReverseCharge));
+        assert_eq!(
+            TaxJurisdiction::Export.suggested_treatment(),
+            VatTreatment::Export
+        );
+    }
+}
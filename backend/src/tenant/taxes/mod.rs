@@ -32,11 +32,13 @@ use uuid::Uuid;
 
 pub mod dto;
 mod handler;
+pub mod jurisdiction;
 pub mod model;
 pub(crate) mod repository;
 pub(crate) mod routes;
 pub mod service;
 pub(crate) mod types;
+pub mod vat_treatment;
 
 pub trait TaxesModuleInterface: BaseModule {
     fn taxes_repo(
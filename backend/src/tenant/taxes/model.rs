@@ -18,7 +18,7 @@
  */
 
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -35,6 +35,10 @@ pub struct Tax {
     pub reporting_code: Option<String>,
     pub is_default: bool,
     pub status: String,
+    /// Validity period of the rate, open ended on a missing side
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    pub previous_version_id: Option<Uuid>,
     pub created_by_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -54,6 +58,10 @@ pub struct TaxResolved {
     pub reporting_code: Option<String>,
     pub is_default: bool,
     pub status: String,
+    /// Validity period of the rate, open ended on a missing side
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+    pub previous_version_id: Option<Uuid>,
     pub created_by_id: Uuid,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
@@ -22,9 +22,11 @@ use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
 use crate::tenant::taxes::dto::user_input::TaxUserInput;
+use crate::tenant::taxes::dto::version::NewTaxVersion;
 use crate::tenant::taxes::model::{Tax, TaxResolved};
 use crate::tenant::taxes::types::{TaxFilterBy, TaxOrderBy};
 use async_trait::async_trait;
+use chrono::{Days, NaiveDate};
 #[cfg(test)]
 use mockall::automock;
 use sqlx::{AssertSqlSafe, PgPool};
@@ -43,6 +45,13 @@ pub trait TaxesRepository: Send + Sync {
     async fn insert(&self, tax: &TaxUserInput, sub: Uuid) -> RepositoryResult<Tax>;
     async fn update(&self, tax: &TaxUserInput) -> RepositoryResult<Tax>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_effective(&self, tax_id: Uuid, on: NaiveDate) -> RepositoryResult<Tax>;
+    async fn insert_version(
+        &self,
+        previous: &Tax,
+        version: &NewTaxVersion,
+        sub: Uuid,
+    ) -> RepositoryResult<Tax>;
 }
 
 #[async_trait]
@@ -75,6 +84,9 @@ impl TaxesRepository for PgPool {
                 taxes.reporting_code as reporting_code,
                 taxes.is_default as is_default,
                 taxes.status as status,
+                taxes.valid_from as valid_from,
+                taxes.valid_to as valid_to,
+                taxes.previous_version_id as previous_version_id,
                 taxes.created_by_id as created_by_id,
                 users.last_name || ' ' || users.first_name as created_by,
                 taxes.created_at as created_at,
@@ -99,6 +111,8 @@ impl TaxesRepository for PgPool {
                 taxes.description as title
                 FROM taxes
                 WHERE deleted_at IS NULL
+                    AND (valid_from IS NULL OR valid_from <= CURRENT_DATE)
+                    AND (valid_to IS NULL OR valid_to >= CURRENT_DATE)
                 ORDER BY taxes.description
                 "#,
         )
@@ -159,6 +173,9 @@ impl TaxesRepository for PgPool {
                         taxes.reporting_code as reporting_code,
                         taxes.is_default as is_default,
                         taxes.status as status,
+                        taxes.valid_from as valid_from,
+                        taxes.valid_to as valid_to,
+                        taxes.previous_version_id as previous_version_id,
                         taxes.created_by_id as created_by_id,
                         users.last_name || ' ' || users.first_name as created_by,
                         taxes.created_at as created_at,
@@ -197,6 +214,9 @@ impl TaxesRepository for PgPool {
                         taxes.reporting_code as reporting_code,
                         taxes.is_default as is_default,
                         taxes.status as status,
+                        taxes.valid_from as valid_from,
+                        taxes.valid_to as valid_to,
+                        taxes.previous_version_id as previous_version_id,
                         taxes.created_by_id as created_by_id,
                         users.last_name || ' ' || users.first_name as created_by,
                         taxes.created_at as created_at,
@@ -245,9 +265,11 @@ impl TaxesRepository for PgPool {
                 reporting_code,
                 is_default,
                 status,
+                valid_from,
+                valid_to,
                 created_by_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *",
         )
         .bind(rate)
         .bind(tax.description.as_str()?)
@@ -258,6 +280,8 @@ impl TaxesRepository for PgPool {
         .bind(tax.reporting_code.as_str())
         .bind(tax.is_default)
         .bind(tax.status.as_str()?)
+        .bind(tax.valid_from.as_date_naive())
+        .bind(tax.valid_to.as_date_naive())
         .bind(sub)
         .fetch_one(self)
         .await?)
@@ -283,8 +307,10 @@ impl TaxesRepository for PgPool {
                 legal_text = $6,
                 reporting_code = $7,
                 is_default = $8,
-                status = $9
-            WHERE id = $10
+                status = $9,
+                valid_from = $10,
+                valid_to = $11
+            WHERE id = $12
                 AND deleted_at IS NULL
             RETURNING *
             "#,
@@ -298,6 +324,8 @@ impl TaxesRepository for PgPool {
         .bind(tax.reporting_code.as_str())
         .bind(tax.is_default)
         .bind(tax.status.as_str()?)
+        .bind(tax.valid_from.as_date_naive())
+        .bind(tax.valid_to.as_date_naive())
         .bind(id)
         .fetch_one(self)
         .await?)
@@ -318,4 +346,81 @@ impl TaxesRepository for PgPool {
 
         Ok(())
     }
+
+    async fn get_effective(&self, tax_id: Uuid, on: NaiveDate) -> RepositoryResult<Tax> {
+        Ok(sqlx::query_as::<_, Tax>(
+            r#"
+            SELECT *
+            FROM taxes
+            WHERE taxes.deleted_at IS NULL
+                AND taxes.id = effective_tax_id($1, $2)
+            "#,
+        )
+        .bind(tax_id)
+        .bind(on)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn insert_version(
+        &self,
+        previous: &Tax,
+        version: &NewTaxVersion,
+        sub: Uuid,
+    ) -> RepositoryResult<Tax> {
+        let previous_valid_to = version
+            .valid_from
+            .checked_sub_days(Days::new(1))
+            .ok_or_else(|| RepositoryError::InvalidInput("valid_from".to_string()))?;
+        let mut tx = self.begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE taxes
+            SET valid_to = $1,
+                is_default = false
+            WHERE id = $2
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(previous_valid_to)
+        .bind(previous.id)
+        .execute(&mut *tx)
+        .await?;
+
+        let tax = sqlx::query_as::<_, Tax>(
+            r#"
+            INSERT INTO taxes (
+                rate,
+                description,
+                country_code,
+                tax_category,
+                is_rate_applicable,
+                legal_text,
+                reporting_code,
+                is_default,
+                status,
+                valid_from,
+                valid_to,
+                previous_version_id,
+                created_by_id
+            )
+            SELECT $1, description, country_code, tax_category, is_rate_applicable,
+                legal_text, reporting_code, $4, status, $2, NULL, id, $3
+            FROM taxes
+            WHERE id = $5
+            RETURNING *
+            "#,
+        )
+        .bind(&version.rate)
+        .bind(version.valid_from)
+        .bind(sub)
+        .bind(previous.is_default)
+        .bind(previous.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(tax)
+    }
 }
@@ -37,6 +37,12 @@ pub fn routes<M: TaxesModuleInterface>(taxes_module: Arc<M>) -> Router {
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
             .route("/print", get(handler::print::<M>))
+            .route("/effective", get(handler::effective::<M>))
+            .route("/new_version", post(handler::new_version::<M>))
+            .route(
+                "/customer_jurisdiction",
+                get(handler::customer_jurisdiction::<M>),
+            )
             .layer(from_fn_with_state(taxes_module.clone(), require_auth))
             .with_state(taxes_module),
     )
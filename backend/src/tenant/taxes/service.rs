@@ -29,6 +29,8 @@ use crate::common::service::{Service, ServiceError};
 use crate::tenant::taxes::TaxesModuleInterface;
 use crate::tenant::taxes::dto::print::TaxResolvedPrint;
 use crate::tenant::taxes::dto::user_input::TaxUserInput;
+use crate::tenant::taxes::dto::version::{NewTaxVersion, TaxEffectiveQuery};
+use crate::tenant::taxes::jurisdiction::CustomerTaxJurisdiction;
 use crate::tenant::taxes::model::{Tax, TaxResolved};
 use crate::tenant::taxes::types::{TaxFilterBy, TaxOrderBy};
 use axum::http::StatusCode;
@@ -169,6 +171,18 @@ pub trait TaxService {
         payload: &[TaxResolvedPrint],
     ) -> impl Future<Output = TaxesServiceResult<Vec<u8>>> + Send;
     fn print_snapshot(&self, path: &Path) -> impl Future<Output = TaxesServiceResult<()>> + Sync;
+    fn effective(
+        &self,
+        payload: &TaxEffectiveQuery,
+    ) -> impl Future<Output = TaxesServiceResult<Tax>> + Send;
+    fn new_version(
+        &self,
+        payload: &NewTaxVersion,
+    ) -> impl Future<Output = TaxesServiceResult<Tax>> + Send;
+    fn customer_jurisdiction(
+        &self,
+        customer_id: Uuid,
+    ) -> impl Future<Output = TaxesServiceResult<CustomerTaxJurisdiction>> + Send;
 }
 
 impl<'a, T> TaxService for Service<'a, T>
@@ -308,6 +322,9 @@ where
             reporting_code: None,
             is_default: true,
             status: "active".to_string(),
+            valid_from: None,
+            valid_to: None,
+            previous_version_id: None,
             created_by_id,
             created_by: "Test User".to_string(),
             created_at: test_time,
@@ -320,4 +337,71 @@ where
         file.write_all(&pdf)?;
         Ok(())
     }
+
+    async fn effective(&self, payload: &TaxEffectiveQuery) -> TaxesServiceResult<Tax> {
+        Ok(self
+            .module()
+            .taxes_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TaxesServiceError::Unauthorized)?,
+            )?
+            .get_effective(
+                payload.tax_id,
+                payload.date.unwrap_or_else(|| Utc::now().date_naive()),
+            )
+            .await?)
+    }
+
+    async fn new_version(&self, payload: &NewTaxVersion) -> TaxesServiceResult<Tax> {
+        let repo = self.module().taxes_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TaxesServiceError::Unauthorized)?,
+        )?;
+        let previous = repo.get_by_id(payload.tax_id).await?;
+        if !previous.is_rate_applicable {
+            return Err(TaxesServiceError::UnprocessableEntry(
+                "Kulcs nélküli adóhoz nem hozható létre új adókulcs!",
+            ));
+        }
+        if payload.rate < 0 || payload.rate > 100 {
+            return Err(TaxesServiceError::UnprocessableEntry(
+                "Az adókulcsnak 0 és 100 között kell lennie!",
+            ));
+        }
+        if previous
+            .valid_from
+            .is_some_and(|valid_from| payload.valid_from <= valid_from)
+        {
+            return Err(TaxesServiceError::UnprocessableEntry(
+                "Az új adókulcs érvényessége az előző kezdete után kell induljon!",
+            ));
+        }
+        repo.insert_version(&previous, payload, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    TaxesServiceError::TaxExists
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn customer_jurisdiction(
+        &self,
+        customer_id: Uuid,
+    ) -> TaxesServiceResult<CustomerTaxJurisdiction> {
+        let address = self
+            .module()
+            .address_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(TaxesServiceError::Unauthorized)?,
+            )?
+            .get_customer_billing_address(customer_id)
+            .await?;
+        Ok(CustomerTaxJurisdiction::new(address))
+    }
 }
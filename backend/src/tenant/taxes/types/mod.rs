@@ -25,6 +25,7 @@ pub(crate) mod rate;
 pub(crate) mod reporting_code;
 pub(crate) mod status;
 pub(crate) mod tax_category;
+pub(crate) mod validity_date;
 
 pub(crate) use description::Description as TaxDescription;
 pub(crate) use filter_by::FilterBy as TaxFilterBy;
@@ -34,3 +35,4 @@ pub(crate) use rate::Rate as TaxRate;
 pub(crate) use reporting_code::ReportingCode as TaxReportingCode;
 pub(crate) use status::Status as TaxStatus;
 pub(crate) use tax_category::TaxCategory;
+pub(crate) use validity_date::ValidityDate as TaxValidityDate;
@@ -0,0 +1,88 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::value_object::*;
+use chrono::NaiveDate;
+use std::fmt::Display;
+
+/// First or last day of the validity period of a tax
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidityDate(NaiveDate);
+
+impl ValidityDate {
+    pub const PARSE_ERROR: &'static str = "Hibás dátum formátum!";
+    pub const PERIOD_ERROR: &'static str =
+        "Az érvényesség vége nem lehet korábbi az érvényesség kezdeténél!";
+}
+
+impl ValueObjectData for ValidityDate {
+    type DataType = NaiveDate;
+
+    fn new(data: &str) -> ValueObjectResult<Option<Self>> {
+        let data_trim = data.trim();
+        if !data_trim.is_empty() {
+            Ok(Some(Self(data_trim.parse().map_err(|_| {
+                ValueObjectError::InvalidInput(Self::PARSE_ERROR)
+            })?)))
+        } else {
+            Ok(None)
+        }
+    }
+    fn validate(&self) -> Result<(), ValueObjectError> {
+        Ok(())
+    }
+
+    fn get_data(&self) -> &Self::DataType {
+        &self.0
+    }
+}
+
+impl Display for ValidityDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid() {
+        let date = "2026-01-01"
+            .parse::<ValueObjectOptional<ValidityDate>>()
+            .unwrap();
+        assert_eq!(
+            date.as_date_naive(),
+            Some(&NaiveDate::from_ymd_opt(2026, 1, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let date = " ".parse::<ValueObjectOptional<ValidityDate>>().unwrap();
+        assert!(date.as_date_naive().is_none());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let date = "2026.01.01".parse::<ValueObjectOptional<ValidityDate>>();
+        assert!(date.is_err());
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Special VAT treatment of a document line, overriding the rate of its tax
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VatTreatment {
+    #[default]
+    Domestic,
+    ReverseCharge,
+    Aam,
+    EuSupply,
+    Export,
+}
+
+impl VatTreatment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VatTreatment::Domestic => "domestic",
+            VatTreatment::ReverseCharge => "reverse_charge",
+            VatTreatment::Aam => "aam",
+            VatTreatment::EuSupply => "eu_supply",
+            VatTreatment::Export => "export",
+        }
+    }
+
+    /// Only domestic lines carry the VAT of their tax, the others are invoiced without VAT
+    pub fn is_taxed(&self) -> bool {
+        matches!(self, VatTreatment::Domestic)
+    }
+
+    /// Printed in the VAT column of the line instead of the rate
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            VatTreatment::Domestic => None,
+            VatTreatment::ReverseCharge => Some("FAD"),
+            VatTreatment::Aam => Some("AAM"),
+            VatTreatment::EuSupply => Some("KBAET"),
+            VatTreatment::Export => Some("EAM"),
+        }
+    }
+
+    /// `vatExemption/case` of the NAV invoice data, reverse charge is reported on its own
+    pub fn nav_exemption_case(&self) -> Option<&'static str> {
+        match self {
+            VatTreatment::Aam => Some("AAM"),
+            VatTreatment::EuSupply => Some("KBAET"),
+            VatTreatment::Export => Some("EAM"),
+            VatTreatment::Domestic | VatTreatment::ReverseCharge => None,
+        }
+    }
+
+    fn statutory_text(&self) -> Option<&'static str> {
+        match self {
+            VatTreatment::Domestic => None,
+            VatTreatment::ReverseCharge => Some(
+                "Fordított adózás: az adót a termék beszerzője, a szolgáltatás igénybevevője fizeti (Áfa tv. 142. §).",
+            ),
+            VatTreatment::Aam => Some("Alanyi adómentes (Áfa tv. 187. §)."),
+            VatTreatment::EuSupply => {
+                Some("Adómentes Közösségen belüli termékértékesítés (Áfa tv. 89. §).")
+            }
+            VatTreatment::Export => Some("Adómentes termékexport (Áfa tv. 98. §)."),
+        }
+    }
+}
+
+impl FromStr for VatTreatment {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "domestic" => Ok(VatTreatment::Domestic),
+            "reverse_charge" => Ok(VatTreatment::ReverseCharge),
+            "aam" => Ok(VatTreatment::Aam),
+            "eu_supply" => Ok(VatTreatment::EuSupply),
+            "export" => Ok(VatTreatment::Export),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Legal text an invoice has to carry for the VAT treatment of a line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaxLegalText(String);
+
+impl TaxLegalText {
+    /// The statutory text of a special treatment, the legal text of the tax otherwise
+    pub fn of(treatment: VatTreatment, tax_legal_text: Option<&str>) -> Option<Self> {
+        treatment
+            .statutory_text()
+            .or(tax_legal_text.filter(|text| !text.trim().is_empty()))
+            .map(|text| Self(text.to_string()))
+    }
+
+    /// Legal texts of the lines of a document, each once, in line order
+    pub fn collect<'a>(
+        lines: impl IntoIterator<Item = (VatTreatment, Option<&'a str>)>,
+    ) -> Vec<Self> {
+        let mut texts: Vec<Self> = Vec::new();
+        for (treatment, tax_legal_text) in lines {
+            if let Some(text) = Self::of(treatment, tax_legal_text)
+                && !texts.contains(&text)
+            {
+                texts.push(text);
+            }
+        }
+        texts
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_domestic_is_taxed() {
+        assert!(VatTreatment::Domestic.is_taxed());
+        assert!(!VatTreatment::ReverseCharge.is_taxed());
+        assert!(!VatTreatment::Aam.is_taxed());
+        assert!(!VatTreatment::EuSupply.is_taxed());
+        assert!(!VatTreatment::Export.is_taxed());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for treatment in [
+            VatTreatment::Domestic,
+            VatTreatment::ReverseCharge,
+            VatTreatment::Aam,
+            VatTreatment::EuSupply,
+            VatTreatment::Export,
+        ] {
+            assert_eq!(treatment.as_str().parse::<VatTreatment>(), Ok(treatment));
+        }
+        assert!("outside_scope".parse::<VatTreatment>().is_err());
+    }
+
+    #[test]
+    fn test_statutory_text_wins_over_tax_legal_text() {
+        let text = TaxLegalText::of(VatTreatment::EuSupply, Some("Tárgyi adómentes")).unwrap();
+        assert_eq!(
+            text.as_str(),
+            "Adómentes Közösségen belüli termékértékesítés (Áfa tv. 89. §)."
+        );
+        assert_eq!(
+            TaxLegalText::of(VatTreatment::Domestic, Some("Tárgyi adómentes"))
+                .unwrap()
+                .as_str(),
+            "Tárgyi adómentes"
+        );
+        assert_eq!(TaxLegalText::of(VatTreatment::Domestic, None), None);
+    }
+
+    #[test]
+    fn test_collect_deduplicates_in_line_order() {
+        let texts = TaxLegalText::collect([
+            (VatTreatment::Aam, None),
+            (VatTreatment::Domestic, None),
+            (VatTreatment::ReverseCharge, None),
+            (VatTreatment::Aam, None),
+        ]);
+        assert_eq!(
+            texts,
+            vec![
+                TaxLegalText::of(VatTreatment::Aam, None).unwrap(),
+                TaxLegalText::of(VatTreatment::ReverseCharge, None).unwrap(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct StartTimer {
+    pub task_id: Option<Uuid>,
+    pub worksheet_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub billable: bool,
+}
+
+/// Manual entry, recorded for the current user unless `user_id` is given
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateTimeEntry {
+    pub user_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub worksheet_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub billable: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UpdateTimeEntry {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub worksheet_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub billable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTimeEntry {
+    pub user_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub worksheet_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub billable: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TimeEntriesQuery {
+    pub user_id: Option<Uuid>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TimeSummaryQuery {
+    pub user_id: Option<Uuid>,
+    pub period: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ApproveTimeEntries {
+    pub ids: Vec<Uuid>,
+}
@@ -0,0 +1,621 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::time_entries::TimeEntriesModuleInterface;
+use crate::tenant::time_entries::dto::{
+    ApproveTimeEntries, CreateTimeEntry, StartTimer, TimeEntriesQuery, TimeSummaryQuery,
+    UpdateTimeEntry,
+};
+use crate::tenant::time_entries::service::TimeEntriesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn start<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<StartTimer>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result =
+        map_handler_err(service.start(&payload).await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn stop<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result = map_handler_err(service.stop().await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn running<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result = map_handler_err(service.running().await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    Query(payload): Query<TimeEntriesQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let tz = map_handler_err(claims.tz(), time_entries_module.clone()).await?;
+    let result = map_handler_err(
+        service.list(&payload, tz).await,
+        time_entries_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn summary<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    Query(payload): Query<TimeSummaryQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let tz = map_handler_err(claims.tz(), time_entries_module.clone()).await?;
+    let result = map_handler_err(
+        service.summary(&payload, tz).await,
+        time_entries_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateTimeEntry>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result =
+        map_handler_err(service.create(&payload).await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UpdateTimeEntry>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result =
+        map_handler_err(service.update(&payload).await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        time_entries_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A munkaidő bejegyzés törlése sikeresen megtörtént",
+            ))
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn approve<M: TimeEntriesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(time_entries_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ApproveTimeEntries>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), time_entries_module.clone());
+    let result =
+        map_handler_err(service.approve(&payload).await, time_entries_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        time_entries_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::project_members::tests::project_access_repos;
+    use crate::tenant::time_entries::model::{TimeEntry, TimeSummary, TimeSummaryRow};
+    use crate::tenant::time_entries::{
+        self, repository::MockTimeEntriesRepository, tests::MockTimeEntriesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::{NaiveDate, TimeDelta, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockTimeEntriesRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        app_with_project_role(repo, role, granted, active_tenant_id, Some("member"))
+    }
+
+    /// Every task belongs to a project, where the user holds `project_role`
+    fn app_with_project_role(
+        repo: MockTimeEntriesRepository,
+        role: &str,
+        granted: bool,
+        active_tenant_id: Uuid,
+        project_role: Option<&'static str>,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let (project_members_repo, _, _) = project_access_repos(project_role);
+        let role = role.to_string();
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(Some(role.clone())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .returning(move |_, permission| Ok(granted && permission == "time_entries.approve"));
+        let permissions_repo = Arc::new(permissions_repo);
+
+        let mut time_entries_module = MockTimeEntriesModule::new();
+        time_entries_module
+            .expect_time_entries_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        time_entries_module
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        time_entries_module
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        time_entries_module
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+        time_entries_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(time_entries::routes::routes(Arc::new(time_entries_module))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn time_entry(user_id: Uuid, running: bool) -> TimeEntry {
+        let started_at = Utc::now() - TimeDelta::hours(2);
+        TimeEntry {
+            id: Uuid::new_v4(),
+            user_id,
+            task_id: Some(Uuid::new_v4()),
+            worksheet_id: None,
+            project_id: None,
+            description: Some("Hibaelhárítás".to_string()),
+            started_at,
+            ended_at: (!running).then(|| started_at + TimeDelta::minutes(90)),
+            duration_minutes: (!running).then_some(90),
+            billable: true,
+            approved_by_id: None,
+            approved_at: None,
+            created_by_id: user_id,
+            created_at: started_at,
+            updated_at: started_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_rejects_second_running_timer() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_running()
+            .with(eq(user_id))
+            .times(1)
+            .returning(move |_| Ok(Some(time_entry(user_id, true))));
+        repo.expect_insert().never();
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/time_entries/start",
+                user_id,
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "worksheet_id": null,
+                    "project_id": null,
+                    "description": null,
+                    "billable": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_exactly_one_target() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/time_entries/create",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({
+                    "user_id": null,
+                    "task_id": Uuid::new_v4(),
+                    "worksheet_id": Uuid::new_v4(),
+                    "project_id": null,
+                    "description": null,
+                    "started_at": Utc::now() - TimeDelta::hours(3),
+                    "ended_at": Utc::now() - TimeDelta::hours(1),
+                    "billable": false
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_stop_closes_running_timer() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let running = time_entry(user_id, true);
+        let stopped = TimeEntry {
+            ended_at: Some(Utc::now()),
+            duration_minutes: Some(120),
+            ..running.clone()
+        };
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_running()
+            .with(eq(user_id))
+            .times(1)
+            .returning(move |_| Ok(Some(running.clone())));
+        repo.expect_stop()
+            .withf({
+                let id = stopped.id;
+                move |entry_id, _| *entry_id == id
+            })
+            .times(1)
+            .returning({
+                let stopped = stopped.clone();
+                move |_, _| Ok(stopped.clone())
+            });
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/time_entries/stop",
+                user_id,
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TimeEntry =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(body, stopped);
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_approved_entry() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let approved = TimeEntry {
+            approved_by_id: Some(Uuid::new_v4()),
+            approved_at: Some(Utc::now()),
+            ..time_entry(user_id, false)
+        };
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(approved.id))
+            .times(1)
+            .returning({
+                let approved = approved.clone();
+                move |_| Ok(approved.clone())
+            });
+        repo.expect_update().never();
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/time_entries/update",
+                user_id,
+                tenant_id,
+                json!({
+                    "id": approved.id,
+                    "task_id": approved.task_id,
+                    "worksheet_id": null,
+                    "project_id": null,
+                    "description": "Javított leírás",
+                    "started_at": approved.started_at,
+                    "ended_at": approved.ended_at,
+                    "billable": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_summary_of_other_user_requires_permission() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_summary().never();
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!(
+                    "/api/time_entries/summary?user_id={}&period=weekly&from=2026-10-01&to=2026-10-31",
+                    Uuid::new_v4()
+                ),
+                Uuid::new_v4(),
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_summary_totals_weekly_rows() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let from = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let rows = vec![
+            TimeSummaryRow {
+                period_start: from,
+                entry_count: 6,
+                total_minutes: 1200,
+                billable_minutes: 900,
+            },
+            TimeSummaryRow {
+                period_start: NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+                entry_count: 4,
+                total_minutes: 600,
+                billable_minutes: 600,
+            },
+        ];
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_summary()
+            .with(
+                eq(user_id),
+                eq("Europe/Budapest"),
+                eq("week"),
+                eq(from),
+                eq(to),
+            )
+            .times(1)
+            .returning({
+                let rows = rows.clone();
+                move |_, _, _, _, _| Ok(rows.clone())
+            });
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "GET",
+                "/api/time_entries/summary?period=weekly&from=2026-10-05&to=2026-10-18",
+                user_id,
+                tenant_id,
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: TimeSummary =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(
+            body,
+            TimeSummary {
+                user_id,
+                period: "weekly".to_string(),
+                from,
+                to,
+                total_minutes: 1800,
+                billable_minutes: 1500,
+                rows,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approve_requires_permission() {
+        let tenant_id = Uuid::new_v4();
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_approve().never();
+
+        let response = app(repo, "member", false, tenant_id)
+            .oneshot(request(
+                "PUT",
+                "/api/time_entries/approve",
+                Uuid::new_v4(),
+                tenant_id,
+                json!({"ids": [Uuid::new_v4()]}),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_start_is_forbidden_for_non_members() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let mut repo = MockTimeEntriesRepository::new();
+        repo.expect_get_running().never();
+        repo.expect_insert().never();
+
+        let response = app_with_project_role(repo, "member", false, tenant_id, None)
+            .oneshot(request(
+                "POST",
+                "/api/time_entries/start",
+                user_id,
+                tenant_id,
+                json!({
+                    "task_id": Uuid::new_v4(),
+                    "worksheet_id": null,
+                    "project_id": null,
+                    "description": null,
+                    "billable": true
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
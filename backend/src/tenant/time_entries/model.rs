@@ -0,0 +1,95 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const PERIOD_DAILY: &str = "daily";
+pub const PERIOD_WEEKLY: &str = "weekly";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub worksheet_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_minutes: Option<i64>,
+    pub billable: bool,
+    pub approved_by_id: Option<Uuid>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TimeEntry {
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Approved entries are locked, they can no longer be modified or deleted
+    pub fn is_locked(&self) -> bool {
+        self.approved_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TimeSummaryRow {
+    pub period_start: NaiveDate,
+    pub entry_count: i64,
+    pub total_minutes: i64,
+    pub billable_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimeSummary {
+    pub user_id: Uuid,
+    pub period: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_minutes: i64,
+    pub billable_minutes: i64,
+    pub rows: Vec<TimeSummaryRow>,
+}
+
+impl TimeSummary {
+    pub fn new(
+        user_id: Uuid,
+        period: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        rows: Vec<TimeSummaryRow>,
+    ) -> Self {
+        Self {
+            user_id,
+            period: period.to_string(),
+            from,
+            to,
+            total_minutes: rows.iter().map(|row| row.total_minutes).sum(),
+            billable_minutes: rows.iter().map(|row| row.billable_minutes).sum(),
+            rows,
+        }
+    }
+}
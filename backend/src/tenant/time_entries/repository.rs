@@ -0,0 +1,263 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::time_entries::dto::NewTimeEntry;
+use crate::tenant::time_entries::model::{TimeEntry, TimeSummaryRow};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TimeEntriesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TimeEntry>;
+    async fn get_running(&self, user_id: Uuid) -> RepositoryResult<Option<TimeEntry>>;
+    async fn get_range(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<TimeEntry>>;
+    async fn get_summary(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        date_part: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<TimeSummaryRow>>;
+    async fn insert(&self, input: &NewTimeEntry, sub: Uuid) -> RepositoryResult<TimeEntry>;
+    async fn update(&self, id: Uuid, input: &NewTimeEntry) -> RepositoryResult<TimeEntry>;
+    async fn stop(&self, id: Uuid, ended_at: DateTime<Utc>) -> RepositoryResult<TimeEntry>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn approve(&self, ids: Vec<Uuid>, sub: Uuid) -> RepositoryResult<Vec<TimeEntry>>;
+}
+
+const TIME_ENTRY_COLUMNS: &str = r#"
+    id,
+    user_id,
+    task_id,
+    worksheet_id,
+    project_id,
+    description,
+    started_at,
+    ended_at,
+    (EXTRACT(EPOCH FROM (ended_at - started_at)) / 60)::bigint AS duration_minutes,
+    billable,
+    approved_by_id,
+    approved_at,
+    created_by_id,
+    created_at,
+    updated_at
+"#;
+
+#[async_trait]
+impl TimeEntriesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<TimeEntry> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            "SELECT {TIME_ENTRY_COLUMNS} FROM time_entries WHERE id = $1 AND deleted_at IS NULL" // Security: constant
+        )))
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_running(&self, user_id: Uuid) -> RepositoryResult<Option<TimeEntry>> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            SELECT {TIME_ENTRY_COLUMNS}
+            FROM time_entries
+            WHERE user_id = $1
+                AND ended_at IS NULL
+                AND deleted_at IS NULL
+            "# // Security: constant
+        )))
+        .bind(user_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn get_range(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<TimeEntry>> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            SELECT {TIME_ENTRY_COLUMNS}
+            FROM time_entries
+            WHERE user_id = $1
+                AND (started_at AT TIME ZONE $2)::date BETWEEN $3 AND $4
+                AND deleted_at IS NULL
+            ORDER BY started_at
+            "# // Security: constant
+        )))
+        .bind(user_id)
+        .bind(timezone)
+        .bind(from)
+        .bind(to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    // NOTE: days and weeks are cut at the user's local midnight, weeks start on Monday
+    async fn get_summary(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        date_part: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<TimeSummaryRow>> {
+        Ok(sqlx::query_as::<_, TimeSummaryRow>(
+            r#"
+            SELECT date_trunc($3, started_at AT TIME ZONE $2)::date AS period_start,
+                   COUNT(*) AS entry_count,
+                   (COALESCE(SUM(EXTRACT(EPOCH FROM (ended_at - started_at))), 0) / 60)::bigint
+                       AS total_minutes,
+                   (COALESCE(SUM(EXTRACT(EPOCH FROM (ended_at - started_at)))
+                       FILTER (WHERE billable), 0) / 60)::bigint AS billable_minutes
+            FROM time_entries
+            WHERE user_id = $1
+                AND ended_at IS NOT NULL
+                AND (started_at AT TIME ZONE $2)::date BETWEEN $4 AND $5
+                AND deleted_at IS NULL
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(timezone)
+        .bind(date_part)
+        .bind(from)
+        .bind(to)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, input: &NewTimeEntry, sub: Uuid) -> RepositoryResult<TimeEntry> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            INSERT INTO time_entries (user_id, task_id, worksheet_id, project_id, description,
+                                      started_at, ended_at, billable, created_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(input.user_id)
+        .bind(input.task_id)
+        .bind(input.worksheet_id)
+        .bind(input.project_id)
+        .bind(&input.description)
+        .bind(input.started_at)
+        .bind(input.ended_at)
+        .bind(input.billable)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(&self, id: Uuid, input: &NewTimeEntry) -> RepositoryResult<TimeEntry> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            UPDATE time_entries
+            SET task_id = $1,
+                worksheet_id = $2,
+                project_id = $3,
+                description = $4,
+                started_at = $5,
+                ended_at = $6,
+                billable = $7
+            WHERE id = $8
+                AND approved_at IS NULL
+                AND deleted_at IS NULL
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(input.task_id)
+        .bind(input.worksheet_id)
+        .bind(input.project_id)
+        .bind(&input.description)
+        .bind(input.started_at)
+        .bind(input.ended_at)
+        .bind(input.billable)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn stop(&self, id: Uuid, ended_at: DateTime<Utc>) -> RepositoryResult<TimeEntry> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            UPDATE time_entries
+            SET ended_at = $1
+            WHERE id = $2
+                AND ended_at IS NULL
+                AND deleted_at IS NULL
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(ended_at)
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE time_entries
+            SET deleted_at = NOW()
+            WHERE id = $1
+                AND approved_at IS NULL
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn approve(&self, ids: Vec<Uuid>, sub: Uuid) -> RepositoryResult<Vec<TimeEntry>> {
+        Ok(sqlx::query_as::<_, TimeEntry>(AssertSqlSafe(format!(
+            r#"
+            UPDATE time_entries
+            SET approved_by_id = $1,
+                approved_at = NOW()
+            WHERE id = ANY($2)
+                AND ended_at IS NOT NULL
+                AND approved_at IS NULL
+                AND deleted_at IS NULL
+            RETURNING {TIME_ENTRY_COLUMNS}
+            "# // Security: constant
+        )))
+        .bind(sub)
+        .bind(ids)
+        .fetch_all(self)
+        .await?)
+    }
+}
@@ -0,0 +1,507 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
+use crate::common::service::{Service, ServiceError};
+use crate::tenant::permissions::model::TIME_ENTRIES_APPROVE;
+use crate::tenant::permissions::service::has_permission;
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{
+    can_access_project, ensure_task_access, ensure_worksheet_access,
+};
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::time_entries::TimeEntriesModuleInterface;
+use crate::tenant::time_entries::dto::{
+    ApproveTimeEntries, CreateTimeEntry, NewTimeEntry, StartTimer, TimeEntriesQuery,
+    TimeSummaryQuery, UpdateTimeEntry,
+};
+use crate::tenant::time_entries::model::{PERIOD_DAILY, PERIOD_WEEKLY, TimeEntry, TimeSummary};
+use crate::tenant::time_entries::repository::TimeEntriesRepository;
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+const MAX_ENTRY_HOURS: i64 = 24;
+const MAX_RANGE_DAYS: i64 = 366;
+
+#[derive(Debug, Error)]
+pub enum TimeEntriesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for TimeEntriesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => TimeEntriesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<TimeEntriesServiceError> for AppError {
+    fn from(value: TimeEntriesServiceError) -> Self {
+        match value {
+            TimeEntriesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TimeEntriesServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            TimeEntriesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            TimeEntriesServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": TimeEntriesServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
+            TimeEntriesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type TimeEntriesServiceResult<T> = Result<T, TimeEntriesServiceError>;
+
+fn map_write_error(e: RepositoryError) -> TimeEntriesServiceError {
+    if e.is_unique_violation() {
+        TimeEntriesServiceError::UnprocessableEntry("Már fut egy időmérés, előbb állítsa le!")
+    } else if e.is_foreign_key_violation() {
+        TimeEntriesServiceError::UnprocessableEntry(
+            "A megadott feladat, munkalap vagy projekt nem létezik!",
+        )
+    } else {
+        e.into()
+    }
+}
+
+fn validate_target(
+    task_id: Option<Uuid>,
+    worksheet_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+) -> TimeEntriesServiceResult<()> {
+    if [task_id, worksheet_id, project_id]
+        .iter()
+        .filter(|id| id.is_some())
+        .count()
+        != 1
+    {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "Pontosan egy feladatot, munkalapot vagy projektet kell megadni!",
+        ));
+    }
+    Ok(())
+}
+
+fn optional_text(value: &Option<String>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn validate_period(
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> TimeEntriesServiceResult<()> {
+    if ended_at <= started_at {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "A befejezés időpontjának a kezdés után kell lennie!",
+        ));
+    }
+    if ended_at - started_at > TimeDelta::hours(MAX_ENTRY_HOURS) {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "Egy bejegyzés legfeljebb 24 órát fedhet le!",
+        ));
+    }
+    if ended_at > now {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "Jövőbeli időszakra nem rögzíthető munkaidő!",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_range(from: NaiveDate, to: NaiveDate) -> TimeEntriesServiceResult<()> {
+    if to < from || (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "Az időszak legfeljebb 366 nap lehet, és a vége nem lehet korábbi a kezdeténél!",
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_unlocked(time_entry: &TimeEntry) -> TimeEntriesServiceResult<()> {
+    if time_entry.is_locked() {
+        return Err(TimeEntriesServiceError::UnprocessableEntry(
+            "Jóváhagyott bejegyzés nem módosítható!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait TimeEntriesService {
+    fn start(
+        &self,
+        payload: &StartTimer,
+    ) -> impl Future<Output = TimeEntriesServiceResult<TimeEntry>> + Send;
+    fn stop(&self) -> impl Future<Output = TimeEntriesServiceResult<TimeEntry>> + Send;
+    fn running(&self) -> impl Future<Output = TimeEntriesServiceResult<Option<TimeEntry>>> + Send;
+    fn list(
+        &self,
+        query: &TimeEntriesQuery,
+        tz: Tz,
+    ) -> impl Future<Output = TimeEntriesServiceResult<Vec<TimeEntry>>> + Send;
+    fn summary(
+        &self,
+        query: &TimeSummaryQuery,
+        tz: Tz,
+    ) -> impl Future<Output = TimeEntriesServiceResult<TimeSummary>> + Send;
+    fn create(
+        &self,
+        payload: &CreateTimeEntry,
+    ) -> impl Future<Output = TimeEntriesServiceResult<TimeEntry>> + Send;
+    fn update(
+        &self,
+        payload: &UpdateTimeEntry,
+    ) -> impl Future<Output = TimeEntriesServiceResult<TimeEntry>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = TimeEntriesServiceResult<()>> + Send;
+    fn approve(
+        &self,
+        payload: &ApproveTimeEntries,
+    ) -> impl Future<Output = TimeEntriesServiceResult<Vec<TimeEntry>>> + Send;
+    fn repo(&self) -> TimeEntriesServiceResult<Arc<dyn TimeEntriesRepository + Send + Sync>>;
+    fn ensure_access(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = TimeEntriesServiceResult<()>> + Send;
+    fn ensure_approver(&self) -> impl Future<Output = TimeEntriesServiceResult<()>> + Send;
+    fn ensure_target_access(
+        &self,
+        task_id: Option<Uuid>,
+        worksheet_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> impl Future<Output = TimeEntriesServiceResult<()>> + Send;
+}
+
+impl<'a, T> TimeEntriesService for Service<'a, T>
+where
+    T: TimeEntriesModuleInterface,
+{
+    fn repo(&self) -> TimeEntriesServiceResult<Arc<dyn TimeEntriesRepository + Send + Sync>> {
+        Ok(self.module().time_entries_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(TimeEntriesServiceError::Unauthorized)?,
+        )?)
+    }
+
+    /// Everyone manages their own entries, the entries of others are reserved for users who
+    /// can approve them
+    async fn ensure_access(&self, user_id: Uuid) -> TimeEntriesServiceResult<()> {
+        let claims = self.claims()?;
+        if user_id == claims.sub() {
+            return Ok(());
+        }
+        self.ensure_approver().await
+    }
+
+    async fn ensure_approver(&self) -> TimeEntriesServiceResult<()> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(TimeEntriesServiceError::Unauthorized)?;
+        if !has_permission(self.module(), tenant_id, claims.sub(), TIME_ENTRIES_APPROVE).await? {
+            return Err(TimeEntriesServiceError::Forbidden);
+        }
+        Ok(())
+    }
+
+    /// Time can only be booked on the tasks, worksheets and projects the user may work on
+    async fn ensure_target_access(
+        &self,
+        task_id: Option<Uuid>,
+        worksheet_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> TimeEntriesServiceResult<()> {
+        if let Some(task_id) = task_id {
+            ensure_task_access(
+                self,
+                task_id,
+                ProjectAccess::Write,
+                TimeEntriesServiceError::Forbidden,
+            )
+            .await?;
+        }
+        if let Some(worksheet_id) = worksheet_id {
+            ensure_worksheet_access(
+                self,
+                worksheet_id,
+                ProjectAccess::Write,
+                TimeEntriesServiceError::Forbidden,
+            )
+            .await?;
+        }
+        if project_id.is_some() {
+            let claims = self.claims()?;
+            let tenant_id = claims
+                .active_tenant()
+                .ok_or(TimeEntriesServiceError::Unauthorized)?;
+            if !can_access_project(
+                self.module(),
+                tenant_id,
+                claims.sub(),
+                project_id,
+                ProjectAccess::Write,
+            )
+            .await?
+            {
+                return Err(TimeEntriesServiceError::Forbidden);
+            }
+        }
+        Ok(())
+    }
+
+    async fn start(&self, payload: &StartTimer) -> TimeEntriesServiceResult<TimeEntry> {
+        validate_target(payload.task_id, payload.worksheet_id, payload.project_id)?;
+        self.ensure_target_access(payload.task_id, payload.worksheet_id, payload.project_id)
+            .await?;
+        let sub = self.claims()?.sub();
+        let repo = self.repo()?;
+        if repo.get_running(sub).await?.is_some() {
+            return Err(TimeEntriesServiceError::UnprocessableEntry(
+                "Már fut egy időmérés, előbb állítsa le!",
+            ));
+        }
+        repo.insert(
+            &NewTimeEntry {
+                user_id: sub,
+                task_id: payload.task_id,
+                worksheet_id: payload.worksheet_id,
+                project_id: payload.project_id,
+                description: optional_text(&payload.description),
+                started_at: Utc::now(),
+                ended_at: None,
+                billable: payload.billable,
+            },
+            sub,
+        )
+        .await
+        .map_err(map_write_error)
+    }
+
+    async fn stop(&self) -> TimeEntriesServiceResult<TimeEntry> {
+        let repo = self.repo()?;
+        let running = repo.get_running(self.claims()?.sub()).await?.ok_or(
+            TimeEntriesServiceError::UnprocessableEntry("Nincs futó időmérés!"),
+        )?;
+        Ok(repo.stop(running.id, Utc::now()).await?)
+    }
+
+    async fn running(&self) -> TimeEntriesServiceResult<Option<TimeEntry>> {
+        Ok(self.repo()?.get_running(self.claims()?.sub()).await?)
+    }
+
+    async fn list(
+        &self,
+        query: &TimeEntriesQuery,
+        tz: Tz,
+    ) -> TimeEntriesServiceResult<Vec<TimeEntry>> {
+        validate_range(query.from, query.to)?;
+        let user_id = query.user_id.unwrap_or(self.claims()?.sub());
+        self.ensure_access(user_id).await?;
+        Ok(self
+            .repo()?
+            .get_range(user_id, tz.name(), query.from, query.to)
+            .await?)
+    }
+
+    async fn summary(
+        &self,
+        query: &TimeSummaryQuery,
+        tz: Tz,
+    ) -> TimeEntriesServiceResult<TimeSummary> {
+        let date_part = match query.period.as_str() {
+            PERIOD_DAILY => "day",
+            PERIOD_WEEKLY => "week",
+            _ => {
+                return Err(TimeEntriesServiceError::UnprocessableEntry(
+                    "Az összesítés napi vagy heti lehet!",
+                ));
+            }
+        };
+        validate_range(query.from, query.to)?;
+        let user_id = query.user_id.unwrap_or(self.claims()?.sub());
+        self.ensure_access(user_id).await?;
+        let rows = self
+            .repo()?
+            .get_summary(user_id, tz.name(), date_part, query.from, query.to)
+            .await?;
+        Ok(TimeSummary::new(
+            user_id,
+            &query.period,
+            query.from,
+            query.to,
+            rows,
+        ))
+    }
+
+    async fn create(&self, payload: &CreateTimeEntry) -> TimeEntriesServiceResult<TimeEntry> {
+        validate_target(payload.task_id, payload.worksheet_id, payload.project_id)?;
+        validate_period(payload.started_at, payload.ended_at, Utc::now())?;
+        let sub = self.claims()?.sub();
+        let user_id = payload.user_id.unwrap_or(sub);
+        self.ensure_access(user_id).await?;
+        self.ensure_target_access(payload.task_id, payload.worksheet_id, payload.project_id)
+            .await?;
+        self.repo()?
+            .insert(
+                &NewTimeEntry {
+                    user_id,
+                    task_id: payload.task_id,
+                    worksheet_id: payload.worksheet_id,
+                    project_id: payload.project_id,
+                    description: optional_text(&payload.description),
+                    started_at: payload.started_at,
+                    ended_at: Some(payload.ended_at),
+                    billable: payload.billable,
+                },
+                sub,
+            )
+            .await
+            .map_err(map_write_error)
+    }
+
+    async fn update(&self, payload: &UpdateTimeEntry) -> TimeEntriesServiceResult<TimeEntry> {
+        validate_target(payload.task_id, payload.worksheet_id, payload.project_id)?;
+        validate_period(payload.started_at, payload.ended_at, Utc::now())?;
+        let repo = self.repo()?;
+        let time_entry = repo.get_by_id(payload.id).await?;
+        self.ensure_access(time_entry.user_id).await?;
+        self.ensure_target_access(
+            time_entry.task_id,
+            time_entry.worksheet_id,
+            time_entry.project_id,
+        )
+        .await?;
+        self.ensure_target_access(payload.task_id, payload.worksheet_id, payload.project_id)
+            .await?;
+        ensure_unlocked(&time_entry)?;
+        if time_entry.is_running() {
+            return Err(TimeEntriesServiceError::UnprocessableEntry(
+                "Futó időmérés nem módosítható, előbb állítsa le!",
+            ));
+        }
+        repo.update(
+            time_entry.id,
+            &NewTimeEntry {
+                user_id: time_entry.user_id,
+                task_id: payload.task_id,
+                worksheet_id: payload.worksheet_id,
+                project_id: payload.project_id,
+                description: optional_text(&payload.description),
+                started_at: payload.started_at,
+                ended_at: Some(payload.ended_at),
+                billable: payload.billable,
+            },
+        )
+        .await
+        .map_err(map_write_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> TimeEntriesServiceResult<()> {
+        let repo = self.repo()?;
+        let time_entry = repo.get_by_id(id).await?;
+        self.ensure_access(time_entry.user_id).await?;
+        self.ensure_target_access(
+            time_entry.task_id,
+            time_entry.worksheet_id,
+            time_entry.project_id,
+        )
+        .await?;
+        ensure_unlocked(&time_entry)?;
+        Ok(repo.delete_by_id(id).await?)
+    }
+
+    async fn approve(
+        &self,
+        payload: &ApproveTimeEntries,
+    ) -> TimeEntriesServiceResult<Vec<TimeEntry>> {
+        if payload.ids.is_empty() {
+            return Err(TimeEntriesServiceError::UnprocessableEntry(
+                "Legalább egy bejegyzést ki kell választani!",
+            ));
+        }
+        self.ensure_approver().await?;
+        Ok(self
+            .repo()?
+            .approve(payload.ids.clone(), self.claims()?.sub())
+            .await?)
+    }
+}
@@ -23,11 +23,13 @@ use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait UsersRepository: Send + Sync {
     async fn insert_from_manager(&self, user: User) -> RepositoryResult<User>;
+    async fn get_all_ids(&self) -> RepositoryResult<Vec<Uuid>>;
 }
 
 #[async_trait]
@@ -60,4 +62,12 @@ impl UsersRepository for PgPool {
         .fetch_one(self)
         .await?)
     }
+
+    async fn get_all_ids(&self) -> RepositoryResult<Vec<Uuid>> {
+        Ok(
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE deleted_at IS NULL")
+                .fetch_all(self)
+                .await?,
+        )
+    }
 }
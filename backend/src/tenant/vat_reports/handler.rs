@@ -0,0 +1,286 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SuccessResponseBuilder};
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::service::Service;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::vat_reports::VatReportsModuleInterface;
+use crate::tenant::vat_reports::dto::VatSummaryQuery;
+use crate::tenant::vat_reports::service::VatReportsService;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+pub async fn summary<M: VatReportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(vat_reports_module): State<Arc<M>>,
+    Query(payload): Query<VatSummaryQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), vat_reports_module.clone());
+    let result =
+        map_handler_err(service.summary(&payload).await, vat_reports_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        vat_reports_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn export_summary<M: VatReportsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(vat_reports_module): State<Arc<M>>,
+    Query(payload): Query<VatSummaryQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), vat_reports_module.clone());
+    let csv = map_handler_err(
+        service.export_summary(&payload).await,
+        vat_reports_module.clone(),
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        "text/csv; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(
+            r#"attachment; filename="afa_osszesito_{}_{}.csv""#,
+            payload.from, payload.to
+        )
+        .parse()
+        .unwrap(),
+    );
+    Ok((StatusCode::OK, headers, csv).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::common::types::Money;
+    use crate::tenant::exchange_rates::model::ExchangeRate;
+    use crate::tenant::exchange_rates::repository::MockExchangeRatesRepository;
+    use crate::tenant::vat_reports::model::{VatSummary, VatSummarySource};
+    use crate::tenant::vat_reports::{
+        self, repository::MockVatReportsRepository, tests::MockVatReportsModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, Utc};
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(
+        repo: MockVatReportsRepository,
+        exchange_rates_repo: MockExchangeRatesRepository,
+        tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let exchange_rates_repo = Arc::new(exchange_rates_repo);
+        let mut vat_reports_module = MockVatReportsModule::new();
+        vat_reports_module
+            .expect_vat_reports_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        vat_reports_module
+            .expect_exchange_rates_repo()
+            .with(eq(tenant_id))
+            .returning(move |_| Ok(exchange_rates_repo.clone()));
+        vat_reports_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(vat_reports::routes::routes(Arc::new(vat_reports_module))),
+        )
+    }
+
+    fn request(uri: &str, tenant_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!("Bearer {}", generate_valid_jwt(None, Some(tenant_id))),
+            )
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn source(
+        currency_code: &str,
+        issue_date: NaiveDate,
+        tax_rate: &str,
+        vat_treatment: &str,
+        net: &str,
+        tax: &str,
+    ) -> VatSummarySource {
+        VatSummarySource {
+            currency_code: currency_code.to_string(),
+            issue_date,
+            tax_rate: BigDecimal::from_str(tax_rate).unwrap(),
+            reporting_code: None,
+            vat_treatment: vat_treatment.to_string(),
+            document_count: 1,
+            net_amount: BigDecimal::from_str(net).unwrap(),
+            tax_amount: BigDecimal::from_str(tax).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_converts_foreign_currency_at_issue_date_rate() {
+        let tenant_id = Uuid::new_v4();
+        let from = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 10, 31).unwrap();
+        let eur_date = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+
+        let mut repo = MockVatReportsRepository::new();
+        repo.expect_get_summary_sources()
+            .with(eq(from), eq(to))
+            .times(1)
+            .returning(move |_, _| {
+                Ok(vec![
+                    source("HUF", from, "27", "domestic", "10000", "2700"),
+                    source("EUR", eur_date, "27", "domestic", "100", "27"),
+                    source("HUF", eur_date, "0", "aam", "5000", "0"),
+                ])
+            });
+        let mut exchange_rates_repo = MockExchangeRatesRepository::new();
+        exchange_rates_repo
+            .expect_get_rate_as_of()
+            .withf(move |currency_code, as_of| currency_code == "EUR" && *as_of == eur_date)
+            .times(1)
+            .returning(move |_, _| {
+                Ok(Some(ExchangeRate {
+                    id: Uuid::new_v4(),
+                    currency_code: "EUR".to_string(),
+                    rate_date: eur_date,
+                    rate: BigDecimal::from(400),
+                    source: "mnb".to_string(),
+                    created_by_id: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }))
+            });
+
+        let response = app(repo, exchange_rates_repo, tenant_id)
+            .oneshot(request(
+                "/api/vat_reports/summary?from=2026-10-01&to=2026-10-31",
+                tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = extract_json_response(response).await;
+        let summary: VatSummary = serde_json::from_value(body["data"].clone()).unwrap();
+        assert_eq!(summary.currency_code, "HUF");
+        assert_eq!(summary.rows.len(), 2);
+        assert_eq!(summary.rows[0].vat_treatment, "aam");
+        assert_eq!(summary.rows[0].tax_amount, BigDecimal::from(0));
+        assert_eq!(summary.rows[1].document_count, 2);
+        assert_eq!(summary.rows[1].net_amount, BigDecimal::from(50000));
+        assert_eq!(summary.rows[1].tax_amount, BigDecimal::from(13500));
+        assert_eq!(
+            summary.tax_total,
+            Money::new(BigDecimal::from(13500), "HUF")
+        );
+        assert_eq!(
+            summary.gross_total,
+            Money::new(BigDecimal::from(68500), "HUF")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summary_rejects_reversed_period() {
+        let tenant_id = Uuid::new_v4();
+        let mut repo = MockVatReportsRepository::new();
+        repo.expect_get_summary_sources().never();
+
+        let response = app(repo, MockExchangeRatesRepository::new(), tenant_id)
+            .oneshot(request(
+                "/api/vat_reports/summary?from=2026-10-31&to=2026-10-01",
+                tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({
+                "error": {
+                    "code": "UNPROCESSABLE_ENTRY",
+                    "message": "Hiba történt az adatok feldolgozása során: Az időszak vége nem lehet korábbi a kezdeténél!"
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_summary_downloads_csv() {
+        let tenant_id = Uuid::new_v4();
+        let from = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut repo = MockVatReportsRepository::new();
+        repo.expect_get_summary_sources()
+            .times(1)
+            .returning(move |_, _| {
+                Ok(vec![VatSummarySource {
+                    reporting_code: Some("27".to_string()),
+                    document_count: 3,
+                    ..source("HUF", from, "27", "domestic", "10000", "2700")
+                }])
+            });
+
+        let response = app(repo, MockExchangeRatesRepository::new(), tenant_id)
+            .oneshot(request(
+                "/api/vat_reports/summary/export?from=2026-10-01&to=2026-10-31",
+                tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+            r#"attachment; filename="afa_osszesito_2026-10-01_2026-10-31.csv""#
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            "tax_rate,reporting_code,vat_treatment,document_count,currency_code,net_amount,tax_amount,gross_amount\n27,27,domestic,3,HUF,10000.00,2700.00,12700.00\n"
+        );
+    }
+}
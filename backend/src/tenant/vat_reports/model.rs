@@ -0,0 +1,62 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::types::Money;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Invoice and credit note lines of a period summed per document currency and issue date, so
+/// each group is converted at the exchange rate of its own day
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct VatSummarySource {
+    pub currency_code: String,
+    pub issue_date: NaiveDate,
+    pub tax_rate: BigDecimal,
+    pub reporting_code: Option<String>,
+    pub vat_treatment: String,
+    pub document_count: i64,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+}
+
+/// One line of the VAT return: the issued documents of a rate, reporting code and treatment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VatSummaryRow {
+    pub tax_rate: BigDecimal,
+    pub reporting_code: Option<String>,
+    pub vat_treatment: String,
+    pub document_count: i64,
+    pub currency_code: String,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub gross_amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VatSummary {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub currency_code: String,
+    pub rows: Vec<VatSummaryRow>,
+    pub net_total: Money,
+    pub tax_total: Money,
+    pub gross_total: Money,
+}
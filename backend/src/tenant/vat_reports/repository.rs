@@ -0,0 +1,94 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryResult;
+use crate::tenant::vat_reports::model::VatSummarySource;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::PgPool;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait VatReportsRepository: Send + Sync {
+    async fn get_summary_sources(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<VatSummarySource>>;
+}
+
+#[async_trait]
+impl VatReportsRepository for PgPool {
+    // NOTE: credit notes carry negative amounts, so they reduce the VAT of their own period
+    async fn get_summary_sources(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> RepositoryResult<Vec<VatSummarySource>> {
+        Ok(sqlx::query_as::<_, VatSummarySource>(
+            r#"
+            SELECT lines.currency_code,
+                   lines.issue_date,
+                   lines.tax_rate,
+                   lines.reporting_code,
+                   lines.vat_treatment,
+                   COUNT(DISTINCT lines.document_id) AS document_count,
+                   SUM(lines.net_amount)             AS net_amount,
+                   SUM(lines.tax_amount)             AS tax_amount
+            FROM (SELECT receivables.id AS document_id,
+                         receivables.currency_code,
+                         receivables.issue_date,
+                         receivable_lines.tax_rate,
+                         taxes.reporting_code,
+                         receivable_lines.vat_treatment,
+                         receivable_lines.net_amount,
+                         receivable_lines.tax_amount
+                  FROM receivable_lines
+                  JOIN receivables ON receivable_lines.receivable_id = receivables.id
+                  JOIN taxes ON receivable_lines.tax_id = taxes.id
+                  WHERE receivables.deleted_at IS NULL
+                      AND receivables.issue_date BETWEEN $1 AND $2
+                  UNION ALL
+                  SELECT credit_notes.id,
+                         credit_notes.currency_code,
+                         credit_notes.issue_date,
+                         credit_note_lines.tax_rate,
+                         taxes.reporting_code,
+                         receivable_lines.vat_treatment,
+                         credit_note_lines.net_amount,
+                         credit_note_lines.tax_amount
+                  FROM credit_note_lines
+                  JOIN credit_notes ON credit_note_lines.credit_note_id = credit_notes.id
+                  JOIN receivable_lines
+                      ON credit_note_lines.receivable_line_id = receivable_lines.id
+                  JOIN taxes ON credit_note_lines.tax_id = taxes.id
+                  WHERE credit_notes.issue_date BETWEEN $1 AND $2) AS lines
+            GROUP BY lines.currency_code, lines.issue_date, lines.tax_rate,
+                     lines.reporting_code, lines.vat_treatment
+            ORDER BY lines.issue_date, lines.currency_code
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(self)
+        .await?)
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::VatReportsModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn routes<M: VatReportsModuleInterface>(vat_reports_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/vat_reports",
+        Router::new()
+            .route("/summary", get(handler::summary::<M>))
+            .route("/summary/export", get(handler::export_summary::<M>))
+            .layer(from_fn_with_state(vat_reports_module.clone(), require_auth))
+            .with_state(vat_reports_module),
+    )
+}
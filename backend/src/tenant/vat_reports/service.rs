@@ -0,0 +1,225 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::{Money, MoneyError};
+use crate::common::utils::to_csv;
+use crate::common::value_object::ValueObjectRequired;
+use crate::tenant::currencies::types::CurrencyCode;
+use crate::tenant::exchange_rates::model::BASE_CURRENCY_CODE;
+use crate::tenant::exchange_rates::service::{ExchangeRatesServiceError, exchange_rate};
+use crate::tenant::vat_reports::VatReportsModuleInterface;
+use crate::tenant::vat_reports::dto::VatSummaryQuery;
+use crate::tenant::vat_reports::model::{VatSummary, VatSummaryRow};
+use axum::http::StatusCode;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde_json::json;
+use std::collections::HashMap;
+use thiserror::Error;
+use tracing::Level;
+
+#[derive(Debug, Error)]
+pub enum VatReportsServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("{0}")]
+    ExchangeRates(#[from] ExchangeRatesServiceError),
+
+    #[error("{0}")]
+    Money(#[from] MoneyError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+impl From<ServiceError> for VatReportsServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => VatReportsServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<VatReportsServiceError> for AppError {
+    fn from(value: VatReportsServiceError) -> Self {
+        match value {
+            VatReportsServiceError::ExchangeRates(error) => error.into(),
+            VatReportsServiceError::Money(error) => error.into(),
+            VatReportsServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            VatReportsServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+type VatReportsServiceResult<T> = Result<T, VatReportsServiceError>;
+
+pub trait VatReportsService {
+    fn summary(
+        &self,
+        query: &VatSummaryQuery,
+    ) -> impl Future<Output = VatReportsServiceResult<VatSummary>> + Send;
+    fn export_summary(
+        &self,
+        query: &VatSummaryQuery,
+    ) -> impl Future<Output = VatReportsServiceResult<Vec<u8>>> + Send;
+}
+
+impl<'a, T> VatReportsService for Service<'a, T>
+where
+    T: VatReportsModuleInterface,
+{
+    // NOTE: every group is converted and rounded on its own, the same way the amounts of a
+    // foreign currency invoice are reported to NAV
+    async fn summary(&self, query: &VatSummaryQuery) -> VatReportsServiceResult<VatSummary> {
+        if query.to < query.from {
+            return Err(VatReportsServiceError::UnprocessableEntry(
+                "Az időszak vége nem lehet korábbi a kezdeténél!",
+            ));
+        }
+        let currency_code = query
+            .currency_code
+            .as_deref()
+            .map(|code| code.trim().to_uppercase())
+            .unwrap_or_else(|| BASE_CURRENCY_CODE.to_string());
+        currency_code
+            .parse::<ValueObjectRequired<CurrencyCode>>()
+            .map_err(|_| {
+                VatReportsServiceError::UnprocessableEntry(CurrencyCode::VALIDATION_ERROR)
+            })?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(VatReportsServiceError::Unauthorized)?;
+        let sources = self
+            .module()
+            .vat_reports_repo(tenant_id)?
+            .get_summary_sources(query.from, query.to)
+            .await?;
+        let exchange_rates_repo = self.module().exchange_rates_repo(tenant_id)?;
+
+        let mut rates: HashMap<(String, NaiveDate), BigDecimal> = HashMap::new();
+        let mut rows: Vec<VatSummaryRow> = Vec::new();
+        for source in sources {
+            let key = (source.currency_code.clone(), source.issue_date);
+            let rate = match rates.get(&key) {
+                Some(rate) => rate.clone(),
+                None => {
+                    let rate = exchange_rate(
+                        &*exchange_rates_repo,
+                        &source.currency_code,
+                        &currency_code,
+                        source.issue_date,
+                    )
+                    .await?
+                    .rate;
+                    rates.insert(key, rate.clone());
+                    rate
+                }
+            };
+            let net_amount = Money::new(source.net_amount, &source.currency_code)
+                .convert(&rate, &currency_code)
+                .amount;
+            let tax_amount = Money::new(source.tax_amount, &source.currency_code)
+                .convert(&rate, &currency_code)
+                .amount;
+            match rows.iter_mut().find(|row| {
+                row.tax_rate == source.tax_rate
+                    && row.reporting_code == source.reporting_code
+                    && row.vat_treatment == source.vat_treatment
+            }) {
+                Some(row) => {
+                    row.document_count += source.document_count;
+                    row.gross_amount += &net_amount + &tax_amount;
+                    row.net_amount += net_amount;
+                    row.tax_amount += tax_amount;
+                }
+                None => rows.push(VatSummaryRow {
+                    tax_rate: source.tax_rate,
+                    reporting_code: source.reporting_code,
+                    vat_treatment: source.vat_treatment,
+                    document_count: source.document_count,
+                    currency_code: currency_code.clone(),
+                    gross_amount: &net_amount + &tax_amount,
+                    net_amount,
+                    tax_amount,
+                }),
+            }
+        }
+        rows.sort_by(|a, b| {
+            a.vat_treatment
+                .cmp(&b.vat_treatment)
+                .then(b.tax_rate.cmp(&a.tax_rate))
+                .then(a.reporting_code.cmp(&b.reporting_code))
+        });
+
+        let mut net_total = Money::zero(&currency_code);
+        let mut tax_total = Money::zero(&currency_code);
+        let mut gross_total = Money::zero(&currency_code);
+        for row in &rows {
+            net_total =
+                net_total.checked_add(&Money::new(row.net_amount.clone(), &row.currency_code))?;
+            tax_total =
+                tax_total.checked_add(&Money::new(row.tax_amount.clone(), &row.currency_code))?;
+            gross_total = gross_total
+                .checked_add(&Money::new(row.gross_amount.clone(), &row.currency_code))?;
+        }
+        Ok(VatSummary {
+            from: query.from,
+            to: query.to,
+            currency_code,
+            rows,
+            net_total,
+            tax_total,
+            gross_total,
+        })
+    }
+
+    async fn export_summary(&self, query: &VatSummaryQuery) -> VatReportsServiceResult<Vec<u8>> {
+        Ok(to_csv(&self.summary(query).await?.rows)?)
+    }
+}
@@ -18,12 +18,13 @@
  */
 
 use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
 use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
 use crate::common::service::Service;
 use crate::manager::auth::middleware::AuthenticatedUser;
 use crate::tenant::warehouses::WarehousesModuleInterface;
+use crate::tenant::warehouses::dto::capacity::WarehouseCapacityInput;
 use crate::tenant::warehouses::dto::print::WarehouseResolvedPrint;
 use crate::tenant::warehouses::dto::user_input::{WarehouseUserInput, WarehouseUserInputHelper};
 use crate::tenant::warehouses::service::WarehouseService;
@@ -196,6 +197,71 @@ pub async fn print<M: WarehousesModuleInterface>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
+pub async fn capacity<M: WarehousesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(warehouses_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), warehouses_module.clone());
+    let result = map_handler_err(
+        service.get_capacities(Some(payload.uuid)).await,
+        warehouses_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        warehouses_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn utilization<M: WarehousesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(warehouses_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), warehouses_module.clone());
+    let result = map_handler_err(
+        service.get_capacities(None).await,
+        warehouses_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        warehouses_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn set_capacity<M: WarehousesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(warehouses_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<WarehouseCapacityInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), warehouses_module.clone());
+    let result = map_handler_err(
+        service.set_capacity(&payload).await,
+        warehouses_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        warehouses_module,
+    )
+    .await?
+    .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,7 +273,7 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::warehouses::model::WarehouseResolved;
+    use crate::tenant::warehouses::model::{WarehouseCapacity, WarehouseResolved};
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::warehouses::{
@@ -331,6 +397,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -371,6 +438,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -453,6 +521,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -565,6 +634,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -605,6 +675,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -686,6 +757,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -803,6 +875,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -841,6 +914,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -922,6 +996,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -1068,6 +1143,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error,
             }
@@ -1116,6 +1192,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1163,6 +1240,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1331,6 +1409,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             }
         });
@@ -1380,6 +1459,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1429,6 +1509,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1602,6 +1683,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1642,6 +1724,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1812,6 +1895,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1853,6 +1937,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1887,4 +1972,120 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    fn capacity_request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("PUT")
+            .uri("/api/warehouses/set_capacity")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_set_capacity_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let warehouse_id = Uuid::new_v4();
+        let capacity = WarehouseCapacity {
+            id: Uuid::new_v4(),
+            warehouse_id,
+            warehouse: "Központi".to_string(),
+            zone: Some("A".to_string()),
+            capacity_m3: "12.5".parse().unwrap(),
+            alert_threshold_percent: 80.into(),
+            used_m3: "11".parse().unwrap(),
+            utilization_percent: 88.into(),
+            unmeasured_items: 2,
+            above_threshold: true,
+        };
+
+        let mut repo = MockWarehousesRepository::new();
+        repo.expect_set_capacity()
+            .times(1)
+            .withf(move |input| {
+                input.warehouse_id == warehouse_id && input.zone.as_deref() == Some("A")
+            })
+            .returning(|_| Ok(()));
+        repo.expect_update_capacity_alerts()
+            .times(1)
+            .returning(|| Ok(1));
+        repo.expect_get_capacities()
+            .times(1)
+            .with(eq(Some(warehouse_id)))
+            .returning({
+                let capacity = capacity.clone();
+                move |_| Ok(vec![capacity.clone()])
+            });
+
+        let mut app_state = MockWarehousesModule::new();
+        let repo = Arc::new(repo);
+        app_state
+            .expect_warehouses_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(warehouses::routes::routes(Arc::new(app_state))),
+        );
+        let response = app
+            .oneshot(capacity_request(
+                active_tenant_id,
+                json!({
+                    "warehouse_id": warehouse_id,
+                    "zone": " A ",
+                    "capacity_m3": "12.5",
+                    "alert_threshold_percent": "80"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: Vec<WarehouseCapacity> =
+            serde_json::from_value(extract_json_response(response).await["data"].clone()).unwrap();
+        assert_eq!(result, vec![capacity]);
+    }
+
+    #[tokio::test]
+    async fn test_set_capacity_invalid_threshold() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut app_state = MockWarehousesModule::new();
+        app_state.expect_warehouses_repo().never();
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(warehouses::routes::routes(Arc::new(app_state))),
+        );
+        let response = app
+            .oneshot(capacity_request(
+                active_tenant_id,
+                json!({
+                    "warehouse_id": Uuid::new_v4(),
+                    "zone": null,
+                    "capacity_m3": "10",
+                    "alert_threshold_percent": "120"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
@@ -29,6 +29,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use uuid::Uuid;
 
+pub mod capacity;
 pub mod dto;
 mod handler;
 pub mod model;
@@ -17,6 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -48,3 +49,17 @@ pub struct WarehouseResolved {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WarehouseCapacity {
+    pub id: Uuid,
+    pub warehouse_id: Uuid,
+    pub warehouse: String,
+    pub zone: Option<String>,
+    pub capacity_m3: BigDecimal,
+    pub alert_threshold_percent: BigDecimal,
+    pub used_m3: BigDecimal,
+    pub utilization_percent: BigDecimal,
+    pub unmeasured_items: i64,
+    pub above_threshold: bool,
+}
@@ -21,8 +21,9 @@ use crate::common::dto::PaginatorMeta;
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::tenant::warehouses::dto::capacity::WarehouseCapacityInput;
 use crate::tenant::warehouses::dto::user_input::WarehouseUserInput;
-use crate::tenant::warehouses::model::{Warehouse, WarehouseResolved};
+use crate::tenant::warehouses::model::{Warehouse, WarehouseCapacity, WarehouseResolved};
 use crate::tenant::warehouses::types::warehouse::{WarehouseFilterBy, WarehouseOrderBy};
 use async_trait::async_trait;
 #[cfg(test)]
@@ -44,6 +45,12 @@ pub trait WarehousesRepository: Send + Sync {
     -> RepositoryResult<Warehouse>;
     async fn update(&self, warehouse: WarehouseUserInput) -> RepositoryResult<Warehouse>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn get_capacities(
+        &self,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<WarehouseCapacity>>;
+    async fn set_capacity(&self, input: &WarehouseCapacityInput) -> RepositoryResult<()>;
+    async fn update_capacity_alerts(&self) -> RepositoryResult<u64>;
 }
 
 #[async_trait]
@@ -264,4 +271,105 @@ impl WarehousesRepository for PgPool {
 
         Ok(())
     }
+
+    async fn get_capacities(
+        &self,
+        warehouse_id: Option<Uuid>,
+    ) -> RepositoryResult<Vec<WarehouseCapacity>> {
+        Ok(sqlx::query_as::<_, WarehouseCapacity>(
+            r#"
+            SELECT warehouse_capacities.id,
+                   warehouse_capacities.warehouse_id,
+                   warehouses.name AS warehouse,
+                   warehouse_capacities.zone,
+                   warehouse_capacities.capacity_m3,
+                   warehouse_capacities.alert_threshold_percent,
+                   warehouse_capacity_utilization.used_m3,
+                   round(warehouse_capacity_utilization.used_m3 * 100
+                       / warehouse_capacities.capacity_m3, 2) AS utilization_percent,
+                   warehouse_capacity_utilization.unmeasured_items,
+                   warehouse_capacities.above_threshold
+            FROM warehouse_capacities
+            JOIN warehouse_capacity_utilization
+                ON warehouse_capacity_utilization.id = warehouse_capacities.id
+            JOIN warehouses ON warehouse_capacities.warehouse_id = warehouses.id
+            WHERE warehouses.deleted_at IS NULL
+                AND ($1::uuid IS NULL OR warehouse_capacities.warehouse_id = $1)
+            ORDER BY warehouses.name, warehouse_capacities.zone NULLS FIRST
+            "#,
+        )
+        .bind(warehouse_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_capacity(&self, input: &WarehouseCapacityInput) -> RepositoryResult<()> {
+        let affected = match &input.capacity_m3 {
+            Some(capacity_m3) => sqlx::query(
+                r#"
+                INSERT INTO warehouse_capacities (warehouse_id, zone, capacity_m3, alert_threshold_percent)
+                SELECT id, $2, $3, COALESCE($4::numeric, 90)
+                FROM warehouses
+                WHERE id = $1 AND deleted_at IS NULL
+                ON CONFLICT (warehouse_id, (COALESCE(zone, ''))) DO UPDATE
+                    SET capacity_m3             = EXCLUDED.capacity_m3,
+                        alert_threshold_percent = COALESCE($4, warehouse_capacities.alert_threshold_percent),
+                        updated_at              = now()
+                "#,
+            )
+            .bind(input.warehouse_id)
+            .bind(&input.zone)
+            .bind(capacity_m3)
+            .bind(&input.alert_threshold_percent)
+            .execute(self)
+            .await?
+            .rows_affected(),
+            None => sqlx::query(
+                r#"
+                DELETE FROM warehouse_capacities
+                WHERE warehouse_id = $1 AND zone IS NOT DISTINCT FROM $2
+                "#,
+            )
+            .bind(input.warehouse_id)
+            .bind(&input.zone)
+            .execute(self)
+            .await?
+            .rows_affected(),
+        };
+        if affected == 0 {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound));
+        }
+        Ok(())
+    }
+
+    async fn update_capacity_alerts(&self) -> RepositoryResult<u64> {
+        // NOTE: watchers are only notified when a capacity goes above its threshold,
+        // not on every run while it stays there
+        Ok(sqlx::query(
+            r#"
+            WITH changed AS (
+                UPDATE warehouse_capacities
+                SET above_threshold = warehouse_capacity_utilization.used_m3 * 100
+                        / warehouse_capacities.capacity_m3 >= warehouse_capacities.alert_threshold_percent,
+                    updated_at = now()
+                FROM warehouse_capacity_utilization
+                WHERE warehouse_capacity_utilization.id = warehouse_capacities.id
+                    AND warehouse_capacities.above_threshold <> (
+                        warehouse_capacity_utilization.used_m3 * 100
+                            / warehouse_capacities.capacity_m3 >= warehouse_capacities.alert_threshold_percent
+                    )
+                RETURNING warehouse_capacities.warehouse_id, warehouse_capacities.above_threshold
+            )
+            INSERT INTO watch_notifications (user_id, watchable_type, watchable_id, event)
+            SELECT DISTINCT watches.user_id, 'warehouses', changed.warehouse_id, 'capacity'
+            FROM changed
+            JOIN watches ON watches.watchable_type = 'warehouses'
+                AND watches.watchable_id = changed.warehouse_id
+            WHERE changed.above_threshold
+            "#,
+        )
+        .execute(self)
+        .await?
+        .rows_affected())
+    }
 }
@@ -35,6 +35,9 @@ pub fn routes<M: WarehousesModuleInterface>(warehouses_module: Arc<M>) -> Router
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
+            .route("/capacity", get(handler::capacity::<M>))
+            .route("/utilization", get(handler::utilization::<M>))
+            .route("/set_capacity", put(handler::set_capacity::<M>))
             .route("/print", get(handler::print::<M>))
             .layer(from_fn_with_state(warehouses_module.clone(), require_auth))
             .with_state(warehouses_module),
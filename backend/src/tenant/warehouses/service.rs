@@ -26,11 +26,13 @@ use crate::common::pdf::{PdfGenError, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
 use crate::tenant::warehouses::WarehousesModuleInterface;
+use crate::tenant::warehouses::dto::capacity::WarehouseCapacityInput;
 use crate::tenant::warehouses::dto::print::WarehouseResolvedPrint;
 use crate::tenant::warehouses::dto::user_input::WarehouseUserInput;
-use crate::tenant::warehouses::model::{Warehouse, WarehouseResolved};
+use crate::tenant::warehouses::model::{Warehouse, WarehouseCapacity, WarehouseResolved};
 use crate::tenant::warehouses::types::warehouse::{WarehouseFilterBy, WarehouseOrderBy};
 use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
@@ -126,6 +128,14 @@ pub trait WarehouseService {
         payload: &WarehouseUserInput,
     ) -> impl Future<Output = WarehousesServiceResult<Warehouse>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = WarehousesServiceResult<()>> + Send;
+    fn get_capacities(
+        &self,
+        warehouse_id: Option<Uuid>,
+    ) -> impl Future<Output = WarehousesServiceResult<Vec<WarehouseCapacity>>> + Send;
+    fn set_capacity(
+        &self,
+        payload: &WarehouseCapacityInput,
+    ) -> impl Future<Output = WarehousesServiceResult<Vec<WarehouseCapacity>>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<WarehouseOrderBy, WarehouseFilterBy>,
@@ -205,6 +215,66 @@ where
             .delete_by_id(payload)
             .await?)
     }
+    async fn get_capacities(
+        &self,
+        warehouse_id: Option<Uuid>,
+    ) -> WarehousesServiceResult<Vec<WarehouseCapacity>> {
+        Ok(self
+            .module()
+            .warehouses_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WarehousesServiceError::Unauthorized)?,
+            )?
+            .get_capacities(warehouse_id)
+            .await?)
+    }
+    async fn set_capacity(
+        &self,
+        payload: &WarehouseCapacityInput,
+    ) -> WarehousesServiceResult<Vec<WarehouseCapacity>> {
+        if payload
+            .capacity_m3
+            .as_ref()
+            .is_some_and(|capacity| *capacity <= BigDecimal::zero())
+        {
+            return Err(WarehousesServiceError::UnprocessableEntry(
+                "A kapacitásnak pozitív számnak kell lennie!",
+            ));
+        }
+        let hundred = BigDecimal::from(100);
+        if payload
+            .alert_threshold_percent
+            .as_ref()
+            .is_some_and(|threshold| *threshold <= BigDecimal::zero() || *threshold > hundred)
+        {
+            return Err(WarehousesServiceError::UnprocessableEntry(
+                "A riasztási küszöbnek 0 és 100 százalék közé kell esnie!",
+            ));
+        }
+        let zone = payload
+            .zone
+            .as_deref()
+            .map(str::trim)
+            .filter(|zone| !zone.is_empty());
+        if zone.is_some_and(|zone| zone.chars().count() > 50) {
+            return Err(WarehousesServiceError::UnprocessableEntry(
+                "A zóna legfeljebb 50 karakter lehet!",
+            ));
+        }
+        let repo = self.module().warehouses_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WarehousesServiceError::Unauthorized)?,
+        )?;
+        repo.set_capacity(&WarehouseCapacityInput {
+            zone: zone.map(str::to_owned),
+            ..payload.clone()
+        })
+        .await?;
+        repo.update_capacity_alerts().await?;
+        Ok(repo.get_capacities(Some(payload.warehouse_id)).await?)
+    }
     async fn get_paged(
         &self,
         get_query: &ResourceQuery<WarehouseOrderBy, WarehouseFilterBy>,
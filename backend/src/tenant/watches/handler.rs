@@ -0,0 +1,358 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::watches::WatchesModuleInterface;
+use crate::tenant::watches::dto::WatchInput;
+use crate::tenant::watches::service::WatchesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn list<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    let result = map_handler_err(service.get_all().await, watches_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn summary<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    let result = map_handler_err(service.get_summary().await, watches_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn watch<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<WatchInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    let result = map_handler_err(service.watch(&payload).await, watches_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn unwatch<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+    Query(payload): Query<WatchInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    map_handler_err(service.unwatch(&payload).await, watches_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A követés megszüntetése sikeresen megtörtént",
+            ))
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn notifications<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        watches_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_notifications_paged(&resource_query).await,
+        watches_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn mark_read<M: WatchesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(watches_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), watches_module.clone());
+    let result = map_handler_err(
+        service.mark_read(payload.uuid).await,
+        watches_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        watches_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::error::RepositoryError;
+    use crate::common::handler::tests::generate_valid_jwt;
+    use crate::tenant::watches::model::{Watch, WatchSummary};
+    use crate::tenant::watches::{
+        self, repository::MockWatchesRepository, tests::MockWatchesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockWatchesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut watches_module = MockWatchesModule::new();
+        watches_module
+            .expect_watches_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        watches_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(watches::routes::routes(Arc::new(watches_module))),
+        )
+    }
+
+    fn json_request(
+        method: &str,
+        uri: &str,
+        sub: Uuid,
+        active_tenant_id: Uuid,
+        payload: serde_json::Value,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_watch_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let input = WatchInput {
+            watchable_type: "customers".to_string(),
+            watchable_id: Uuid::new_v4(),
+        };
+        let watch = Watch {
+            id: Uuid::new_v4(),
+            user_id: sub,
+            watchable_type: input.watchable_type.clone(),
+            watchable_id: input.watchable_id,
+            created_at: Utc::now(),
+        };
+        let mut repo = MockWatchesRepository::new();
+        repo.expect_record_exists()
+            .times(1)
+            .with(eq("customers"), eq(input.watchable_id))
+            .returning(|_, _| Ok(true));
+        repo.expect_watch()
+            .times(1)
+            .with(eq(input.clone()), eq(sub))
+            .returning({
+                let watch = watch.clone();
+                move |_, _| Ok(watch.clone())
+            });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/watches/watch",
+                sub,
+                active_tenant_id,
+                json!({
+                    "watchable_type": input.watchable_type,
+                    "watchable_id": input.watchable_id
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_watch_invalid_type() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockWatchesRepository::new();
+        repo.expect_record_exists().never();
+        repo.expect_watch().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "POST",
+                "/api/watches/watch",
+                Uuid::new_v4(),
+                active_tenant_id,
+                json!({
+                    "watchable_type": "users; DROP TABLE watches",
+                    "watchable_id": Uuid::new_v4()
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_summary_returns_unread_counts() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let summary = vec![
+            WatchSummary {
+                watchable_type: "tasks".to_string(),
+                watchable_id: Uuid::new_v4(),
+                name: Some("Kazán karbantartás".to_string()),
+                watched_since: Utc::now(),
+                unread_count: 3,
+                last_activity_at: Some(Utc::now()),
+            },
+            WatchSummary {
+                watchable_type: "projects".to_string(),
+                watchable_id: Uuid::new_v4(),
+                name: None,
+                watched_since: Utc::now(),
+                unread_count: 0,
+                last_activity_at: None,
+            },
+        ];
+        let mut repo = MockWatchesRepository::new();
+        repo.expect_get_summary().times(1).with(eq(sub)).returning({
+            let summary = summary.clone();
+            move |_| Ok(summary.clone())
+        });
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "GET",
+                "/api/watches/summary",
+                sub,
+                active_tenant_id,
+                json!(null),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"][0]["unread_count"], json!(3));
+        assert_eq!(body["data"][0]["name"], json!("Kazán karbantartás"));
+        assert_eq!(body["data"][1]["name"], json!(null));
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_other_users_notification() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let notification_id = Uuid::new_v4();
+        let mut repo = MockWatchesRepository::new();
+        repo.expect_mark_read()
+            .times(1)
+            .with(eq(notification_id), eq(sub))
+            .returning(|_, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(json_request(
+                "PUT",
+                "/api/watches/notifications/read",
+                sub,
+                active_tenant_id,
+                json!({ "uuid": notification_id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
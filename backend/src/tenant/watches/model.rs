@@ -0,0 +1,65 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const WATCHABLE_TYPES: [&str; 7] = [
+    "customers",
+    "projects",
+    "worksheets",
+    "tasks",
+    "products",
+    "warehouses",
+    "services",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Watch {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub watchable_type: String,
+    pub watchable_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WatchNotification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub watchable_type: String,
+    pub watchable_id: Uuid,
+    pub event: String,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+/// A watched record with the activity the user has not read yet
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WatchSummary {
+    pub watchable_type: String,
+    pub watchable_id: Uuid,
+    /// Name of the record, the service name for tasks, `None` once it is deleted
+    pub name: Option<String>,
+    pub watched_since: DateTime<Utc>,
+    pub unread_count: i64,
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,210 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::watches::dto::WatchInput;
+use crate::tenant::watches::model::{Watch, WatchNotification, WatchSummary};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{AssertSqlSafe, PgPool};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WatchesRepository: Send + Sync {
+    async fn get_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Watch>>;
+    async fn get_summary(&self, user_id: Uuid) -> RepositoryResult<Vec<WatchSummary>>;
+    async fn record_exists(
+        &self,
+        watchable_type: &str,
+        watchable_id: Uuid,
+    ) -> RepositoryResult<bool>;
+    async fn watch(&self, input: &WatchInput, user_id: Uuid) -> RepositoryResult<Watch>;
+    async fn unwatch(&self, input: &WatchInput, user_id: Uuid) -> RepositoryResult<()>;
+    async fn get_notifications_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+        user_id: Uuid,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<WatchNotification>)>;
+    async fn mark_read(&self, id: Uuid, user_id: Uuid) -> RepositoryResult<WatchNotification>;
+}
+
+#[async_trait]
+impl WatchesRepository for PgPool {
+    async fn get_by_user(&self, user_id: Uuid) -> RepositoryResult<Vec<Watch>> {
+        Ok(sqlx::query_as::<_, Watch>(
+            "SELECT * FROM watches WHERE user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_summary(&self, user_id: Uuid) -> RepositoryResult<Vec<WatchSummary>> {
+        Ok(sqlx::query_as::<_, WatchSummary>(
+            r#"
+            SELECT watches.watchable_type,
+                   watches.watchable_id,
+                   COALESCE(customers.name, projects.name, worksheets.name, task_services.name,
+                            products.name, warehouses.name, services.name) AS name,
+                   watches.created_at AS watched_since,
+                   activity.unread_count,
+                   activity.last_activity_at
+            FROM watches
+                     LEFT JOIN customers ON watches.watchable_type = 'customers'
+                AND customers.id = watches.watchable_id AND customers.deleted_at IS NULL
+                     LEFT JOIN projects ON watches.watchable_type = 'projects'
+                AND projects.id = watches.watchable_id AND projects.deleted_at IS NULL
+                     LEFT JOIN worksheets ON watches.watchable_type = 'worksheets'
+                AND worksheets.id = watches.watchable_id AND worksheets.deleted_at IS NULL
+                     LEFT JOIN tasks ON watches.watchable_type = 'tasks'
+                AND tasks.id = watches.watchable_id AND tasks.deleted_at IS NULL
+                     LEFT JOIN services task_services ON tasks.service_id = task_services.id
+                     LEFT JOIN products ON watches.watchable_type = 'products'
+                AND products.id = watches.watchable_id AND products.deleted_at IS NULL
+                     LEFT JOIN warehouses ON watches.watchable_type = 'warehouses'
+                AND warehouses.id = watches.watchable_id AND warehouses.deleted_at IS NULL
+                     LEFT JOIN services ON watches.watchable_type = 'services'
+                AND services.id = watches.watchable_id AND services.deleted_at IS NULL
+                     CROSS JOIN LATERAL (
+                SELECT COUNT(*) FILTER (WHERE watch_notifications.read_at IS NULL) AS unread_count,
+                       MAX(watch_notifications.created_at)                        AS last_activity_at
+                FROM watch_notifications
+                WHERE watch_notifications.user_id = watches.user_id
+                  AND watch_notifications.watchable_type = watches.watchable_type
+                  AND watch_notifications.watchable_id = watches.watchable_id
+                ) activity
+            WHERE watches.user_id = $1
+            ORDER BY activity.unread_count > 0 DESC,
+                     activity.last_activity_at DESC NULLS LAST,
+                     watches.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn record_exists(
+        &self,
+        watchable_type: &str,
+        watchable_id: Uuid,
+    ) -> RepositoryResult<bool> {
+        // NOTE: watchable_type is checked against WATCHABLE_TYPES by the service before it gets here
+        Ok(sqlx::query_scalar::<_, bool>(AssertSqlSafe(format!(
+            "SELECT EXISTS(SELECT 1 FROM {watchable_type} WHERE id = $1 AND deleted_at IS NULL)"
+        )))
+        .bind(watchable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn watch(&self, input: &WatchInput, user_id: Uuid) -> RepositoryResult<Watch> {
+        Ok(sqlx::query_as::<_, Watch>(
+            r#"
+            INSERT INTO watches (user_id, watchable_type, watchable_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, watchable_type, watchable_id)
+                DO UPDATE SET user_id = EXCLUDED.user_id
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(&input.watchable_type)
+        .bind(input.watchable_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn unwatch(&self, input: &WatchInput, user_id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM watches
+            WHERE user_id = $1
+              AND watchable_type = $2
+              AND watchable_id = $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(&input.watchable_type)
+        .bind(input.watchable_id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_notifications_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+        user_id: Uuid,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<WatchNotification>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM watch_notifications WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let notifications = sqlx::query_as::<_, WatchNotification>(
+            r#"
+            SELECT *
+            FROM watch_notifications
+            WHERE user_id = $1
+            ORDER BY read_at IS NOT NULL, created_at DESC
+            LIMIT $2
+            OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            notifications,
+        ))
+    }
+
+    async fn mark_read(&self, id: Uuid, user_id: Uuid) -> RepositoryResult<WatchNotification> {
+        Ok(sqlx::query_as::<_, WatchNotification>(
+            r#"
+            UPDATE watch_notifications
+            SET read_at = COALESCE(read_at, now())
+            WHERE id = $1
+              AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(self)
+        .await?)
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::WatchesModuleInterface;
+use super::handler;
+use crate::manager::auth::middleware::require_auth;
+use axum::Router;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, post, put};
+use std::sync::Arc;
+
+pub fn routes<M: WatchesModuleInterface>(watches_module: Arc<M>) -> Router {
+    Router::new().nest(
+        "/watches",
+        Router::new()
+            .route("/list", get(handler::list::<M>))
+            .route("/summary", get(handler::summary::<M>))
+            .route("/watch", post(handler::watch::<M>))
+            .route("/unwatch", delete(handler::unwatch::<M>))
+            .route("/notifications", get(handler::notifications::<M>))
+            .route("/notifications/read", put(handler::mark_read::<M>))
+            .layer(from_fn_with_state(watches_module.clone(), require_auth))
+            .with_state(watches_module),
+    )
+}
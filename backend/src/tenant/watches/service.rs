@@ -0,0 +1,208 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::watches::WatchesModuleInterface;
+use crate::tenant::watches::dto::WatchInput;
+use crate::tenant::watches::model::{WATCHABLE_TYPES, Watch, WatchNotification, WatchSummary};
+use axum::http::StatusCode;
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum WatchesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for WatchesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => WatchesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<WatchesServiceError> for AppError {
+    fn from(value: WatchesServiceError) -> Self {
+        match value {
+            WatchesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            WatchesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            WatchesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type WatchesServiceResult<T> = Result<T, WatchesServiceError>;
+
+fn validate(payload: &WatchInput) -> WatchesServiceResult<()> {
+    if WATCHABLE_TYPES.contains(&payload.watchable_type.as_str()) {
+        Ok(())
+    } else {
+        Err(WatchesServiceError::UnprocessableEntry(
+            "Hibás erőforrás típus",
+        ))
+    }
+}
+
+pub trait WatchesService {
+    fn get_all(&self) -> impl Future<Output = WatchesServiceResult<Vec<Watch>>> + Send;
+    fn get_summary(&self) -> impl Future<Output = WatchesServiceResult<Vec<WatchSummary>>> + Send;
+    fn watch(
+        &self,
+        payload: &WatchInput,
+    ) -> impl Future<Output = WatchesServiceResult<Watch>> + Send;
+    fn unwatch(
+        &self,
+        payload: &WatchInput,
+    ) -> impl Future<Output = WatchesServiceResult<()>> + Send;
+    fn get_notifications_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<Output = WatchesServiceResult<(PaginatorMeta, Vec<WatchNotification>)>> + Send;
+    fn mark_read(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = WatchesServiceResult<WatchNotification>> + Send;
+}
+
+impl<'a, T> WatchesService for Service<'a, T>
+where
+    T: WatchesModuleInterface,
+{
+    async fn get_all(&self) -> WatchesServiceResult<Vec<Watch>> {
+        Ok(self
+            .module()
+            .watches_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WatchesServiceError::Unauthorized)?,
+            )?
+            .get_by_user(self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get_summary(&self) -> WatchesServiceResult<Vec<WatchSummary>> {
+        Ok(self
+            .module()
+            .watches_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WatchesServiceError::Unauthorized)?,
+            )?
+            .get_summary(self.claims()?.sub())
+            .await?)
+    }
+
+    async fn watch(&self, payload: &WatchInput) -> WatchesServiceResult<Watch> {
+        validate(payload)?;
+        let repo = self.module().watches_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WatchesServiceError::Unauthorized)?,
+        )?;
+        if !repo
+            .record_exists(&payload.watchable_type, payload.watchable_id)
+            .await?
+        {
+            return Err(RepositoryError::Database(sqlx::Error::RowNotFound).into());
+        }
+        Ok(repo.watch(payload, self.claims()?.sub()).await?)
+    }
+
+    async fn unwatch(&self, payload: &WatchInput) -> WatchesServiceResult<()> {
+        validate(payload)?;
+        Ok(self
+            .module()
+            .watches_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WatchesServiceError::Unauthorized)?,
+            )?
+            .unwatch(payload, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get_notifications_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> WatchesServiceResult<(PaginatorMeta, Vec<WatchNotification>)> {
+        Ok(self
+            .module()
+            .watches_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WatchesServiceError::Unauthorized)?,
+            )?
+            .get_notifications_paged(get_query, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn mark_read(&self, id: Uuid) -> WatchesServiceResult<WatchNotification> {
+        Ok(self
+            .module()
+            .watches_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WatchesServiceError::Unauthorized)?,
+            )?
+            .mark_read(id, self.claims()?.sub())
+            .await?)
+    }
+}
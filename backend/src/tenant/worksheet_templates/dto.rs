@@ -0,0 +1,58 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TemplateServiceInput {
+    pub service_id: Uuid,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TemplateMaterialInput {
+    pub product_id: Uuid,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct WorksheetTemplateInput {
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub estimated_duration_minutes: Option<i32>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+    #[serde(default)]
+    pub services: Vec<TemplateServiceInput>,
+    #[serde(default)]
+    pub materials: Vec<TemplateMaterialInput>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CreateWorksheetFromTemplate {
+    pub worksheet_template_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub customer_id: Uuid,
+    pub project_id: Option<Uuid>,
+}
@@ -0,0 +1,400 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
+use crate::common::extractors::ValidJson;
+use crate::common::handler::{HandlerResult, map_handler_err};
+use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::service::Service;
+use crate::common::types::Empty;
+use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::worksheet_templates::WorksheetTemplatesModuleInterface;
+use crate::tenant::worksheet_templates::dto::{
+    CreateWorksheetFromTemplate, WorksheetTemplateInput,
+};
+use crate::tenant::worksheet_templates::service::WorksheetTemplatesService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub async fn get<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    let result = map_handler_err(
+        service.get(payload.uuid).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn list<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    Query(payload): Query<CommonRawQuery>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    let resource_query = map_handler_err(
+        ResourceQuery::<Empty, Empty>::from_str(payload.q()),
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    let (meta, data) = map_handler_err(
+        service.get_paged(&resource_query).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::new()
+            .status_code(StatusCode::OK)
+            .meta(meta)
+            .data(data)
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<WorksheetTemplateInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    let result = map_handler_err(
+        service.create(&payload).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn update<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<WorksheetTemplateInput>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    let result = map_handler_err(
+        service.update(&payload).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn delete<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    map_handler_err(
+        service.delete(payload.uuid).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(SimpleMessageResponse::new(
+                "A munkalap sablon törlése sikeresen megtörtént",
+            ))
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn create_worksheet<M: WorksheetTemplatesModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheet_templates_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<CreateWorksheetFromTemplate>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheet_templates_module.clone());
+    let result = map_handler_err(
+        service.create_worksheet(&payload).await,
+        worksheet_templates_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        worksheet_templates_module,
+    )
+    .await?
+    .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::tests::AppConfigBuilder;
+    use crate::common::handler::tests::{extract_json_response, generate_valid_jwt};
+    use crate::tenant::worksheet_templates::model::{
+        WorksheetTemplate, WorksheetTemplateDetails, WorksheetTemplateService,
+    };
+    use crate::tenant::worksheet_templates::{
+        self, repository::MockWorksheetTemplatesRepository, tests::MockWorksheetTemplatesModule,
+    };
+    use axum::body::Body;
+    use axum::{Router, http::Request};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: MockWorksheetTemplatesRepository, active_tenant_id: Uuid) -> Router {
+        let repo = Arc::new(repo);
+        let mut worksheet_templates_module = MockWorksheetTemplatesModule::new();
+        worksheet_templates_module
+            .expect_worksheet_templates_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        worksheet_templates_module
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(worksheet_templates::routes::routes(Arc::new(
+                worksheet_templates_module,
+            ))),
+        )
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        active_tenant_id: Uuid,
+        payload: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+            .unwrap()
+    }
+
+    fn template(status: &str) -> WorksheetTemplate {
+        WorksheetTemplate {
+            id: Uuid::new_v4(),
+            name: "Klíma karbantartás".to_string(),
+            description: None,
+            estimated_duration_minutes: Some(90),
+            checklist: vec!["Szűrő tisztítása".to_string(), "Nyomáspróba".to_string()],
+            status: status.to_string(),
+            created_by_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_services_and_materials() {
+        let active_tenant_id = Uuid::new_v4();
+        let template = template("active");
+        let service_line = WorksheetTemplateService {
+            id: Uuid::new_v4(),
+            worksheet_template_id: template.id,
+            service_id: Uuid::new_v4(),
+            service: "Kiszállás".to_string(),
+            quantity: BigDecimal::from(1),
+            position: 0,
+        };
+        let mut repo = MockWorksheetTemplatesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(template.id))
+            .returning({
+                let template = template.clone();
+                move |_| Ok(template.clone())
+            });
+        repo.expect_get_services()
+            .times(1)
+            .with(eq(template.id))
+            .returning({
+                let service_line = service_line.clone();
+                move |_| Ok(vec![service_line.clone()])
+            });
+        repo.expect_get_materials()
+            .times(1)
+            .with(eq(template.id))
+            .returning(|_| Ok(vec![]));
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "GET",
+                &format!("/api/worksheet_templates/get?uuid={}", template.id),
+                active_tenant_id,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            extract_json_response(response).await,
+            json!({"meta": null, "data": WorksheetTemplateDetails {
+                template,
+                services: vec![service_line],
+                materials: vec![],
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_empty_checklist_item() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockWorksheetTemplatesRepository::new();
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/worksheet_templates/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "name": "Klíma karbantartás",
+                    "description": null,
+                    "estimated_duration_minutes": 90,
+                    "checklist": ["Szűrő tisztítása", "  "],
+                    "services": [],
+                    "materials": [],
+                    "status": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_worksheet_from_inactive_template() {
+        let active_tenant_id = Uuid::new_v4();
+        let template = template("inactive");
+        let mut repo = MockWorksheetTemplatesRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(template.id))
+            .returning({
+                let template = template.clone();
+                move |_| Ok(template.clone())
+            });
+        repo.expect_create_worksheet().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/worksheet_templates/create_worksheet",
+                active_tenant_id,
+                Some(json!({
+                    "worksheet_template_id": template.id,
+                    "name": "Éves karbantartás",
+                    "description": null,
+                    "customer_id": Uuid::new_v4(),
+                    "project_id": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_discontinued_material() {
+        let active_tenant_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let mut repo = MockWorksheetTemplatesRepository::new();
+        repo.expect_count_unorderable_products()
+            .times(1)
+            .withf(move |ids| ids == [product_id])
+            .returning(|_| Ok(1));
+        repo.expect_insert().never();
+
+        let response = app(repo, active_tenant_id)
+            .oneshot(request(
+                "POST",
+                "/api/worksheet_templates/create",
+                active_tenant_id,
+                Some(json!({
+                    "id": null,
+                    "name": "Klíma karbantartás",
+                    "description": null,
+                    "estimated_duration_minutes": 90,
+                    "checklist": [],
+                    "services": [],
+                    "materials": [{"product_id": product_id, "quantity": "2"}],
+                    "status": null
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_ACTIVE: &str = "active";
+pub const STATUS_INACTIVE: &str = "inactive";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub estimated_duration_minutes: Option<i32>,
+    pub checklist: Vec<String>,
+    pub status: String,
+    pub created_by_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetTemplateService {
+    pub id: Uuid,
+    pub worksheet_template_id: Uuid,
+    pub service_id: Uuid,
+    pub service: String,
+    pub quantity: BigDecimal,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetTemplateMaterial {
+    pub id: Uuid,
+    pub worksheet_template_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub quantity: BigDecimal,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorksheetTemplateDetails {
+    #[serde(flatten)]
+    pub template: WorksheetTemplate,
+    pub services: Vec<WorksheetTemplateService>,
+    pub materials: Vec<WorksheetTemplateMaterial>,
+}
@@ -0,0 +1,388 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryResult;
+use crate::common::query_parser::ResourceQuery;
+use crate::common::types::Empty;
+use crate::tenant::worksheet_templates::dto::{
+    CreateWorksheetFromTemplate, WorksheetTemplateInput,
+};
+use crate::tenant::worksheet_templates::model::{
+    WorksheetTemplate, WorksheetTemplateMaterial, WorksheetTemplateService,
+};
+use crate::tenant::worksheets::model::Worksheet;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WorksheetTemplatesRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<WorksheetTemplate>;
+    async fn get_services(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetTemplateService>>;
+    async fn get_materials(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetTemplateMaterial>>;
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<WorksheetTemplate>)>;
+    async fn insert(
+        &self,
+        input: &WorksheetTemplateInput,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetTemplate>;
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &WorksheetTemplateInput,
+    ) -> RepositoryResult<WorksheetTemplate>;
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn count_services_without_defaults(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<i64>;
+    async fn create_worksheet(
+        &self,
+        template: &WorksheetTemplate,
+        input: &CreateWorksheetFromTemplate,
+        sub: Uuid,
+    ) -> RepositoryResult<Worksheet>;
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64>;
+}
+
+async fn insert_lines(
+    tx: &mut Transaction<'_, Postgres>,
+    worksheet_template_id: Uuid,
+    input: &WorksheetTemplateInput,
+) -> RepositoryResult<()> {
+    for (position, line) in input.services.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO worksheet_template_services (worksheet_template_id, service_id, quantity, position)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(worksheet_template_id)
+        .bind(line.service_id)
+        .bind(&line.quantity)
+        .bind(i32::try_from(position)?)
+        .execute(&mut **tx)
+        .await?;
+    }
+    for (position, line) in input.materials.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO worksheet_template_materials (worksheet_template_id, product_id, quantity, position)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(worksheet_template_id)
+        .bind(line.product_id)
+        .bind(&line.quantity)
+        .bind(i32::try_from(position)?)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl WorksheetTemplatesRepository for PgPool {
+    async fn get_by_id(&self, id: Uuid) -> RepositoryResult<WorksheetTemplate> {
+        Ok(sqlx::query_as::<_, WorksheetTemplate>(
+            "SELECT * FROM worksheet_templates WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_services(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetTemplateService>> {
+        Ok(sqlx::query_as::<_, WorksheetTemplateService>(
+            r#"
+            SELECT worksheet_template_services.id,
+                   worksheet_template_services.worksheet_template_id,
+                   worksheet_template_services.service_id,
+                   services.name AS service,
+                   worksheet_template_services.quantity,
+                   worksheet_template_services.position
+            FROM worksheet_template_services
+            JOIN services ON worksheet_template_services.service_id = services.id
+            WHERE worksheet_template_services.worksheet_template_id = $1
+            ORDER BY worksheet_template_services.position
+            "#,
+        )
+        .bind(worksheet_template_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_materials(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetTemplateMaterial>> {
+        Ok(sqlx::query_as::<_, WorksheetTemplateMaterial>(
+            r#"
+            SELECT worksheet_template_materials.id,
+                   worksheet_template_materials.worksheet_template_id,
+                   worksheet_template_materials.product_id,
+                   products.name AS product,
+                   worksheet_template_materials.quantity,
+                   worksheet_template_materials.position
+            FROM worksheet_template_materials
+            JOIN products ON worksheet_template_materials.product_id = products.id
+            WHERE worksheet_template_materials.worksheet_template_id = $1
+            ORDER BY worksheet_template_materials.position
+            "#,
+        )
+        .bind(worksheet_template_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        query_params: &ResourceQuery<Empty, Empty>,
+    ) -> RepositoryResult<(PaginatorMeta, Vec<WorksheetTemplate>)> {
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM worksheet_templates WHERE deleted_at IS NULL")
+                .fetch_one(self)
+                .await?;
+
+        let limit = i32::try_from(query_params.paging().limit().unwrap_or(25))?;
+
+        let templates = sqlx::query_as::<_, WorksheetTemplate>(
+            r#"
+            SELECT *
+            FROM worksheet_templates
+            WHERE deleted_at IS NULL
+            ORDER BY name
+            LIMIT $1
+            OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+        .fetch_all(self)
+        .await?;
+
+        Ok((
+            PaginatorMeta {
+                page: query_params.paging().page().unwrap_or(1).try_into()?,
+                limit,
+                total: total.0,
+            },
+            templates,
+        ))
+    }
+
+    async fn insert(
+        &self,
+        input: &WorksheetTemplateInput,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetTemplate> {
+        let mut tx = self.begin().await?;
+        let template = sqlx::query_as::<_, WorksheetTemplate>(
+            r#"
+            INSERT INTO worksheet_templates (name, description, estimated_duration_minutes, checklist, created_by_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(input.estimated_duration_minutes)
+        .bind(&input.checklist)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_lines(&mut tx, template.id, input).await?;
+        tx.commit().await?;
+        Ok(template)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: &WorksheetTemplateInput,
+    ) -> RepositoryResult<WorksheetTemplate> {
+        let mut tx = self.begin().await?;
+        let template = sqlx::query_as::<_, WorksheetTemplate>(
+            r#"
+            UPDATE worksheet_templates
+            SET name = $1,
+                description = $2,
+                estimated_duration_minutes = $3,
+                checklist = $4,
+                status = $5
+            WHERE id = $6 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(input.estimated_duration_minutes)
+        .bind(&input.checklist)
+        .bind(&input.status)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM worksheet_template_services WHERE worksheet_template_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM worksheet_template_materials WHERE worksheet_template_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        insert_lines(&mut tx, id, input).await?;
+        tx.commit().await?;
+        Ok(template)
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
+        sqlx::query(
+            "UPDATE worksheet_templates SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .execute(self)
+        .await?;
+        Ok(())
+    }
+
+    async fn count_services_without_defaults(
+        &self,
+        worksheet_template_id: Uuid,
+    ) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM worksheet_template_services
+            JOIN services ON worksheet_template_services.service_id = services.id
+            WHERE worksheet_template_services.worksheet_template_id = $1
+              AND (services.default_tax_id IS NULL
+                OR services.currency_code IS NULL
+                OR services.deleted_at IS NOT NULL)
+            "#,
+        )
+        .bind(worksheet_template_id)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn create_worksheet(
+        &self,
+        template: &WorksheetTemplate,
+        input: &CreateWorksheetFromTemplate,
+        sub: Uuid,
+    ) -> RepositoryResult<Worksheet> {
+        let mut tx = self.begin().await?;
+        let worksheet = sqlx::query_as::<_, Worksheet>(
+            r#"
+            INSERT INTO worksheets (name, description, customer_id, project_id, created_by_id, status,
+                                    worksheet_template_id, estimated_duration_minutes)
+            VALUES ($1, $2, $3, $4, $5, 'active', $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&input.name)
+        .bind(input.description.as_ref().or(template.description.as_ref()))
+        .bind(input.customer_id)
+        .bind(input.project_id)
+        .bind(sub)
+        .bind(template.id)
+        .bind(template.estimated_duration_minutes)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO worksheet_checklist_items (worksheet_id, position, title)
+            SELECT $1, (item.position - 1)::integer, item.title
+            FROM unnest($2::text[]) WITH ORDINALITY AS item(title, position)
+            "#,
+        )
+        .bind(worksheet.id)
+        .bind(&template.checklist)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO tasks (worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                               created_by_id, description)
+            SELECT $1,
+                   services.id,
+                   rate.currency_code,
+                   worksheet_template_services.quantity,
+                   rate.price,
+                   services.default_tax_id,
+                   $2,
+                   services.description
+            FROM worksheet_template_services
+            JOIN services ON worksheet_template_services.service_id = services.id
+            CROSS JOIN LATERAL resolve_service_rate(services.id, $4, CURRENT_DATE) AS rate
+            WHERE worksheet_template_services.worksheet_template_id = $3
+            ORDER BY worksheet_template_services.position
+            "#,
+        )
+        .bind(worksheet.id)
+        .bind(sub)
+        .bind(template.id)
+        .bind(input.customer_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO worksheet_planned_materials (worksheet_id, product_id, quantity)
+            SELECT $1, product_id, quantity
+            FROM worksheet_template_materials
+            WHERE worksheet_template_id = $2
+            "#,
+        )
+        .bind(worksheet.id)
+        .bind(template.id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(worksheet)
+    }
+
+    async fn count_unorderable_products(&self, product_ids: &[Uuid]) -> RepositoryResult<i64> {
+        Ok(sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM products WHERE id = ANY($1) AND status <> 'active'",
+        )
+        .bind(product_ids)
+        .fetch_one(self)
+        .await?)
+    }
+}
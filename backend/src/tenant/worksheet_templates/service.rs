@@ -0,0 +1,324 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::common::dto::PaginatorMeta;
+use crate::common::error::RepositoryError;
+use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::query_parser::ResourceQuery;
+use crate::common::service::{Service, ServiceError};
+use crate::common::types::Empty;
+use crate::tenant::worksheet_templates::WorksheetTemplatesModuleInterface;
+use crate::tenant::worksheet_templates::dto::{
+    CreateWorksheetFromTemplate, WorksheetTemplateInput,
+};
+use crate::tenant::worksheet_templates::model::{
+    STATUS_ACTIVE, STATUS_INACTIVE, WorksheetTemplate, WorksheetTemplateDetails,
+};
+use crate::tenant::worksheet_templates::repository::WorksheetTemplatesRepository;
+use crate::tenant::worksheets::model::Worksheet;
+use axum::http::StatusCode;
+use bigdecimal::{BigDecimal, Zero};
+use serde_json::json;
+use thiserror::Error;
+use tracing::Level;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum WorksheetTemplatesServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Hozzáférés megtagadva!")]
+    Unauthorized,
+
+    #[error("Hiba történt az adatok feldolgozása során: {0}")]
+    UnprocessableEntry(&'static str),
+}
+
+impl From<ServiceError> for WorksheetTemplatesServiceError {
+    fn from(value: ServiceError) -> Self {
+        match value {
+            ServiceError::Unauthorized => WorksheetTemplatesServiceError::Unauthorized,
+        }
+    }
+}
+
+impl From<WorksheetTemplatesServiceError> for AppError {
+    fn from(value: WorksheetTemplatesServiceError) -> Self {
+        match value {
+            WorksheetTemplatesServiceError::Unauthorized => Self::new(
+                Level::DEBUG,
+                StatusCode::UNAUTHORIZED,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            WorksheetTemplatesServiceError::UnprocessableEntry(_) => Self::new(
+                Level::DEBUG,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": value.to_string()}),
+            ),
+            WorksheetTemplatesServiceError::Repository(RepositoryError::Database(
+                sqlx::Error::RowNotFound,
+            )) => Self::new(
+                Level::DEBUG,
+                StatusCode::NOT_FOUND,
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({"message": "Nem található"}),
+            ),
+            _ => Self::new(
+                Level::ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                file!(),
+                AppErrorVisibility::Internal,
+                json!({"message": value.to_string()}),
+            ),
+        }
+    }
+}
+
+pub type WorksheetTemplatesServiceResult<T> = Result<T, WorksheetTemplatesServiceError>;
+
+fn validate_template(
+    payload: &WorksheetTemplateInput,
+) -> WorksheetTemplatesServiceResult<WorksheetTemplateInput> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > 255 {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "A név megadása kötelező és legfeljebb 255 karakter lehet!",
+        ));
+    }
+    if payload
+        .estimated_duration_minutes
+        .is_some_and(|minutes| minutes <= 0)
+    {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "A becsült időtartamnak pozitív számnak kell lennie!",
+        ));
+    }
+    let checklist = payload
+        .checklist
+        .iter()
+        .map(|item| item.trim().to_string())
+        .collect::<Vec<_>>();
+    if checklist.iter().any(|item| item.is_empty()) {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "Az ellenőrzőlista elemei nem lehetnek üresek!",
+        ));
+    }
+    if payload
+        .services
+        .iter()
+        .any(|line| line.quantity <= BigDecimal::zero())
+        || payload
+            .materials
+            .iter()
+            .any(|line| line.quantity <= BigDecimal::zero())
+    {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "A mennyiségnek pozitív számnak kell lennie!",
+        ));
+    }
+    let status = payload
+        .status
+        .clone()
+        .unwrap_or_else(|| STATUS_ACTIVE.to_string());
+    if status != STATUS_ACTIVE && status != STATUS_INACTIVE {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "Hibás státusz!",
+        ));
+    }
+    Ok(WorksheetTemplateInput {
+        name: name.to_string(),
+        description: payload
+            .description
+            .as_ref()
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty()),
+        checklist,
+        status: Some(status),
+        ..payload.clone()
+    })
+}
+
+async fn validate_materials(
+    repo: &(dyn WorksheetTemplatesRepository + Send + Sync),
+    input: &WorksheetTemplateInput,
+) -> WorksheetTemplatesServiceResult<()> {
+    let product_ids: Vec<Uuid> = input.materials.iter().map(|line| line.product_id).collect();
+    if !product_ids.is_empty() && repo.count_unorderable_products(&product_ids).await? > 0 {
+        return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+            "Csak aktív termék adható a sablonhoz!",
+        ));
+    }
+    Ok(())
+}
+
+pub trait WorksheetTemplatesService {
+    fn get(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = WorksheetTemplatesServiceResult<WorksheetTemplateDetails>> + Send;
+    fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> impl Future<
+        Output = WorksheetTemplatesServiceResult<(PaginatorMeta, Vec<WorksheetTemplate>)>,
+    > + Send;
+    fn create(
+        &self,
+        payload: &WorksheetTemplateInput,
+    ) -> impl Future<Output = WorksheetTemplatesServiceResult<WorksheetTemplate>> + Send;
+    fn update(
+        &self,
+        payload: &WorksheetTemplateInput,
+    ) -> impl Future<Output = WorksheetTemplatesServiceResult<WorksheetTemplate>> + Send;
+    fn delete(&self, id: Uuid) -> impl Future<Output = WorksheetTemplatesServiceResult<()>> + Send;
+    fn create_worksheet(
+        &self,
+        payload: &CreateWorksheetFromTemplate,
+    ) -> impl Future<Output = WorksheetTemplatesServiceResult<Worksheet>> + Send;
+}
+
+impl<'a, T> WorksheetTemplatesService for Service<'a, T>
+where
+    T: WorksheetTemplatesModuleInterface,
+{
+    async fn get(&self, id: Uuid) -> WorksheetTemplatesServiceResult<WorksheetTemplateDetails> {
+        let repo = self.module().worksheet_templates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+        )?;
+        Ok(WorksheetTemplateDetails {
+            template: repo.get_by_id(id).await?,
+            services: repo.get_services(id).await?,
+            materials: repo.get_materials(id).await?,
+        })
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<Empty, Empty>,
+    ) -> WorksheetTemplatesServiceResult<(PaginatorMeta, Vec<WorksheetTemplate>)> {
+        Ok(self
+            .module()
+            .worksheet_templates_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+            )?
+            .get_paged(get_query)
+            .await?)
+    }
+
+    async fn create(
+        &self,
+        payload: &WorksheetTemplateInput,
+    ) -> WorksheetTemplatesServiceResult<WorksheetTemplate> {
+        let input = validate_template(payload)?;
+        let repo = self.module().worksheet_templates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+        )?;
+        validate_materials(&*repo, &input).await?;
+        Ok(repo.insert(&input, self.claims()?.sub()).await?)
+    }
+
+    async fn update(
+        &self,
+        payload: &WorksheetTemplateInput,
+    ) -> WorksheetTemplatesServiceResult<WorksheetTemplate> {
+        let id = payload
+            .id
+            .ok_or(WorksheetTemplatesServiceError::UnprocessableEntry(
+                "Az azonosító megadása kötelező!",
+            ))?;
+        let input = validate_template(payload)?;
+        let repo = self.module().worksheet_templates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+        )?;
+        validate_materials(&*repo, &input).await?;
+        Ok(repo.update(id, &input).await?)
+    }
+
+    async fn delete(&self, id: Uuid) -> WorksheetTemplatesServiceResult<()> {
+        Ok(self
+            .module()
+            .worksheet_templates_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+            )?
+            .delete_by_id(id)
+            .await?)
+    }
+
+    async fn create_worksheet(
+        &self,
+        payload: &CreateWorksheetFromTemplate,
+    ) -> WorksheetTemplatesServiceResult<Worksheet> {
+        let name = payload.name.trim();
+        if name.is_empty() || name.chars().count() > 255 {
+            return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+                "A név megadása kötelező és legfeljebb 255 karakter lehet!",
+            ));
+        }
+        let repo = self.module().worksheet_templates_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetTemplatesServiceError::Unauthorized)?,
+        )?;
+        let template = repo.get_by_id(payload.worksheet_template_id).await?;
+        if template.status != STATUS_ACTIVE {
+            return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+                "Inaktív sablonból nem hozható létre munkalap!",
+            ));
+        }
+        let product_ids: Vec<Uuid> = repo
+            .get_materials(template.id)
+            .await?
+            .iter()
+            .map(|material| material.product_id)
+            .collect();
+        if repo.count_unorderable_products(&product_ids).await? > 0 {
+            return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+                "A sablon nem aktív (pl. kifutó) terméket tartalmaz, ebből nem hozható létre munkalap!",
+            ));
+        }
+        if repo.count_services_without_defaults(template.id).await? > 0 {
+            return Err(WorksheetTemplatesServiceError::UnprocessableEntry(
+                "A sablon szolgáltatásainál az alapértelmezett adó és pénznem megadása kötelező!",
+            ));
+        }
+        let input = CreateWorksheetFromTemplate {
+            name: name.to_string(),
+            ..payload.clone()
+        };
+        Ok(repo
+            .create_worksheet(&template, &input, self.claims()?.sub())
+            .await?)
+    }
+}
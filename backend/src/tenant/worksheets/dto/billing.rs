@@ -0,0 +1,65 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::tenant::worksheets::model::WorksheetBillableItem;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct BillWorksheet {
+    pub worksheet_id: Uuid,
+    /// Time booked directly on the worksheet is billed as this service, left out when missing
+    pub time_entry_service_id: Option<Uuid>,
+    /// Defaults to the currency of the first billable item, items in other currencies stay unbilled
+    /// unless `convert_currencies` is set
+    pub currency_code: Option<String>,
+    /// Converts items in other currencies at the stored exchange rate of the issue date
+    #[serde(default)]
+    pub convert_currencies: bool,
+    pub document_number: Option<String>,
+    pub issue_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    /// Confirms an overrun of the customer credit limit, has no effect in block mode
+    #[serde(default)]
+    pub acknowledge_credit_limit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewWorksheetInvoice {
+    pub worksheet_id: Uuid,
+    pub customer_id: Uuid,
+    /// Generated from the invoice number sequence when missing
+    pub document_number: Option<String>,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub currency_code: String,
+    pub items: Vec<WorksheetBillableItem>,
+}
+
+impl NewWorksheetInvoice {
+    pub fn amount(&self) -> BigDecimal {
+        self.items
+            .iter()
+            .map(|item| &item.net_amount + &item.tax_amount)
+            .sum()
+    }
+}
@@ -17,5 +17,8 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod billing;
+pub mod checklist;
 pub mod print;
+pub mod signature;
 pub mod user_input;
@@ -20,9 +20,16 @@
 use bigdecimal::BigDecimal;
 use chrono_tz::Tz;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
-use crate::tenant::worksheets::model::WorksheetResolved;
+use crate::common::pdf::{format_date, format_money, format_number, format_quantity};
+use crate::tenant::document_settings::dto::Letterhead;
+use crate::tenant::document_settings::model::DocumentRecipient;
+use crate::tenant::worksheets::dto::signature::WorksheetSignaturePrint;
+use crate::tenant::worksheets::model::{
+    WorksheetDocumentLine, WorksheetDocumentTimeEntry, WorksheetResolved,
+};
 
 #[derive(Clone, Serialize, PartialEq, Debug)]
 pub struct WorksheetResolvedPrint {
@@ -82,12 +89,210 @@ impl WorksheetResolvedPrint {
         match status {
             "active" => "Aktív",
             "inactive" => "Inaktív",
+            "completed" => "Befejezett",
             _ => "Ismeretlen státusz!",
         }
         .to_string()
     }
 }
 
+/// The lines listed by the detailed worksheet document
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct WorksheetDocumentContent {
+    pub tasks: Vec<WorksheetDocumentLine>,
+    pub materials: Vec<WorksheetDocumentLine>,
+    pub time_entries: Vec<WorksheetDocumentTimeEntry>,
+}
+
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WorksheetDocumentLinePrint {
+    pub name: String,
+    pub description: Option<String>,
+    pub quantity: String,
+    pub unit_price: String,
+    pub tax_rate: String,
+    pub net_amount: String,
+    pub gross_amount: String,
+}
+
+impl WorksheetDocumentLinePrint {
+    fn from_line(line: &WorksheetDocumentLine) -> Self {
+        let quantity = format_quantity(&line.quantity.abs());
+        Self {
+            name: line.name.clone(),
+            description: line.description.clone(),
+            quantity: match &line.unit {
+                Some(unit) => format!("{quantity}\u{a0}{unit}"),
+                None => quantity,
+            },
+            unit_price: line
+                .unit_price
+                .as_ref()
+                .map(|price| format_money(price, &line.currency_code))
+                .unwrap_or_else(|| "-".to_string()),
+            tax_rate: format!("{}%", format_number(&line.tax_rate, 0)),
+            net_amount: format_money(&line.net_amount(), &line.currency_code),
+            gross_amount: format_money(&line.gross_amount(), &line.currency_code),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WorksheetTimeEntryPrint {
+    pub user_name: String,
+    pub task: String,
+    pub description: Option<String>,
+    pub started_at: String,
+    pub duration: String,
+    pub billable: String,
+}
+
+impl WorksheetTimeEntryPrint {
+    fn from_time_entry(time_entry: WorksheetDocumentTimeEntry, tz: Tz) -> Self {
+        Self {
+            user_name: time_entry.user_name,
+            task: time_entry.task.unwrap_or_else(|| "-".to_string()),
+            description: time_entry.description,
+            started_at: time_entry
+                .started_at
+                .with_timezone(&tz)
+                .format("%Y. %m. %d. %H:%M")
+                .to_string(),
+            duration: format_duration((time_entry.ended_at - time_entry.started_at).num_minutes()),
+            billable: if time_entry.billable { "Igen" } else { "Nem" }.to_string(),
+        }
+    }
+}
+
+/// Net, tax and gross amounts of the task and material lines per tax and currency
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WorksheetTaxSummaryPrint {
+    pub tax: String,
+    pub net_amount: String,
+    pub tax_amount: String,
+    pub gross_amount: String,
+}
+
+impl WorksheetTaxSummaryPrint {
+    fn summarize<'a>(lines: impl Iterator<Item = &'a WorksheetDocumentLine>) -> Vec<Self> {
+        let mut totals: BTreeMap<(&str, &str), (BigDecimal, BigDecimal)> = BTreeMap::new();
+        for line in lines {
+            let (net, gross) = totals
+                .entry((line.tax.as_str(), line.currency_code.as_str()))
+                .or_default();
+            *net += line.net_amount();
+            *gross += line.gross_amount();
+        }
+        totals
+            .into_iter()
+            .map(|((tax, currency_code), (net, gross))| Self {
+                tax: tax.to_string(),
+                net_amount: format_money(&net, currency_code),
+                tax_amount: format_money(&(&gross - &net), currency_code),
+                gross_amount: format_money(&gross, currency_code),
+            })
+            .collect()
+    }
+}
+
+fn format_duration(minutes: i64) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WorksheetDocumentPrint {
+    pub letterhead: Letterhead,
+    pub recipient: DocumentRecipient,
+    pub name: String,
+    pub description: Option<String>,
+    pub project: String,
+    pub status: String,
+    pub created_at: String,
+    pub net_material_cost: String,
+    pub gross_material_cost: String,
+    pub net_work_cost: String,
+    pub gross_work_cost: String,
+    pub net_total: String,
+    pub tax_total: String,
+    pub gross_total: String,
+    /// `false` for the summary layout, which only prints the totals
+    pub detailed: bool,
+    pub tasks: Vec<WorksheetDocumentLinePrint>,
+    pub materials: Vec<WorksheetDocumentLinePrint>,
+    pub time_entries: Vec<WorksheetTimeEntryPrint>,
+    pub total_time: String,
+    pub tax_summary: Vec<WorksheetTaxSummaryPrint>,
+    pub signature: Option<WorksheetSignaturePrint>,
+}
+
+impl WorksheetDocumentPrint {
+    pub fn new(
+        worksheet_resolved: WorksheetResolved,
+        content: Option<WorksheetDocumentContent>,
+        letterhead: Letterhead,
+        recipient: DocumentRecipient,
+        signature: Option<WorksheetSignaturePrint>,
+        tz: Tz,
+    ) -> Self {
+        // NOTE: the cost totals are summed across task currencies, so no currency is printed
+        let money = |value: &BigDecimal| format_number(value, 2);
+        let net_total = &worksheet_resolved.net_material_cost + &worksheet_resolved.net_work_cost;
+        let gross_total =
+            &worksheet_resolved.gross_material_cost + &worksheet_resolved.gross_work_cost;
+        let detailed = content.is_some();
+        let content = content.unwrap_or_default();
+        let total_minutes = content
+            .time_entries
+            .iter()
+            .map(|time_entry| (time_entry.ended_at - time_entry.started_at).num_minutes())
+            .sum();
+        Self {
+            letterhead,
+            recipient,
+            name: worksheet_resolved.name,
+            description: worksheet_resolved.description,
+            project: worksheet_resolved
+                .project
+                .unwrap_or_else(|| "-".to_string()),
+            status: WorksheetResolvedPrint::map_status(&worksheet_resolved.status),
+            created_at: format_date(
+                worksheet_resolved
+                    .created_at
+                    .with_timezone(&tz)
+                    .date_naive(),
+            ),
+            net_material_cost: money(&worksheet_resolved.net_material_cost),
+            gross_material_cost: money(&worksheet_resolved.gross_material_cost),
+            net_work_cost: money(&worksheet_resolved.net_work_cost),
+            gross_work_cost: money(&worksheet_resolved.gross_work_cost),
+            tax_total: money(&(&gross_total - &net_total)),
+            net_total: money(&net_total),
+            gross_total: money(&gross_total),
+            detailed,
+            tasks: content
+                .tasks
+                .iter()
+                .map(WorksheetDocumentLinePrint::from_line)
+                .collect(),
+            materials: content
+                .materials
+                .iter()
+                .map(WorksheetDocumentLinePrint::from_line)
+                .collect(),
+            tax_summary: WorksheetTaxSummaryPrint::summarize(
+                content.tasks.iter().chain(content.materials.iter()),
+            ),
+            total_time: format_duration(total_minutes),
+            time_entries: content
+                .time_entries
+                .into_iter()
+                .map(|time_entry| WorksheetTimeEntryPrint::from_time_entry(time_entry, tz))
+                .collect(),
+            signature,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +350,106 @@ mod tests {
         };
         assert_eq!(worksheet_resolved_print, worksheet_resolved_print_expected);
     }
+
+    #[test]
+    fn test_document_summarizes_taxes_per_currency() {
+        let line = |name: &str,
+                    quantity: &str,
+                    unit_price: &str,
+                    currency_code: &str,
+                    tax: &str,
+                    tax_rate: &str| {
+            WorksheetDocumentLine {
+                name: name.to_string(),
+                description: None,
+                quantity: quantity.parse().unwrap(),
+                unit: None,
+                unit_price: Some(unit_price.parse().unwrap()),
+                currency_code: currency_code.to_string(),
+                tax: tax.to_string(),
+                tax_rate: tax_rate.parse().unwrap(),
+            }
+        };
+        let started_at: DateTime<Utc> = "2026-01-01T08:00:00Z".parse().unwrap();
+        let content = WorksheetDocumentContent {
+            tasks: vec![
+                line("Szerelés", "2", "10000", "HUF", "27%", "27"),
+                line("Kiszállás", "1", "5000", "HUF", "27%", "27"),
+            ],
+            materials: vec![
+                WorksheetDocumentLine {
+                    unit: Some("db".to_string()),
+                    ..line("Csavar", "-10", "100", "HUF", "AAM", "0")
+                },
+                line("Kábel", "1", "20", "EUR", "27%", "27"),
+            ],
+            time_entries: vec![WorksheetDocumentTimeEntry {
+                user_name: "Teszt Elek".to_string(),
+                task: None,
+                description: None,
+                started_at,
+                ended_at: started_at + chrono::Duration::minutes(95),
+                billable: true,
+            }],
+        };
+        let worksheet_resolved = WorksheetResolved {
+            id: Uuid::new_v4(),
+            name: "Test worksheet".to_string(),
+            description: None,
+            customer_id: Uuid::new_v4(),
+            customer: "Test customer".to_string(),
+            project_id: None,
+            project: None,
+            created_by_id: Uuid::new_v4(),
+            created_by: "Test user".to_string(),
+            status: "completed".to_string(),
+            created_at: started_at,
+            updated_at: started_at,
+            deleted_at: None,
+            net_material_cost: "1020".parse().unwrap(),
+            gross_material_cost: "1025.4".parse().unwrap(),
+            net_work_cost: "25000".parse().unwrap(),
+            gross_work_cost: "31750".parse().unwrap(),
+        };
+        let document = WorksheetDocumentPrint::new(
+            worksheet_resolved,
+            Some(content),
+            Letterhead::default(),
+            DocumentRecipient {
+                name: "Teszt Ügyfél".to_string(),
+                email: "ugyfel@example.com".to_string(),
+                phone_number: None,
+            },
+            None,
+            "Europe/Budapest".parse().unwrap(),
+        );
+
+        assert!(document.detailed);
+        assert_eq!(document.materials[0].quantity, "10\u{a0}db");
+        assert_eq!(document.total_time, "1:35");
+        assert_eq!(document.time_entries[0].started_at, "2026. 01. 01. 09:00");
+        assert_eq!(
+            document.tax_summary,
+            vec![
+                WorksheetTaxSummaryPrint {
+                    tax: "27%".to_string(),
+                    net_amount: "20,00\u{a0}EUR".to_string(),
+                    tax_amount: "5,40\u{a0}EUR".to_string(),
+                    gross_amount: "25,40\u{a0}EUR".to_string(),
+                },
+                WorksheetTaxSummaryPrint {
+                    tax: "27%".to_string(),
+                    net_amount: "25\u{a0}000\u{a0}Ft".to_string(),
+                    tax_amount: "6\u{a0}750\u{a0}Ft".to_string(),
+                    gross_amount: "31\u{a0}750\u{a0}Ft".to_string(),
+                },
+                WorksheetTaxSummaryPrint {
+                    tax: "AAM".to_string(),
+                    net_amount: "1\u{a0}000\u{a0}Ft".to_string(),
+                    tax_amount: "0\u{a0}Ft".to_string(),
+                    gross_amount: "1\u{a0}000\u{a0}Ft".to_string(),
+                },
+            ]
+        );
+    }
 }
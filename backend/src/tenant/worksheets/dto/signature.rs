@@ -0,0 +1,59 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use image::ImageFormat;
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const MAX_SIGNATURE_SIZE: usize = 512 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorksheetSignatureUpload {
+    pub worksheet_id: Uuid,
+    pub signer_name: String,
+    pub data: Vec<u8>,
+}
+
+impl WorksheetSignatureUpload {
+    // NOTE: same as the document logo, typst can only embed PNG and JPEG
+    pub fn format(&self) -> Option<(&'static str, &'static str)> {
+        match image::guess_format(&self.data) {
+            Ok(ImageFormat::Png) => Some(("image/png", "png")),
+            Ok(ImageFormat::Jpeg) => Some(("image/jpeg", "jpg")),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewWorksheetSignature {
+    pub worksheet_id: Uuid,
+    pub signer_name: String,
+    pub image_storage_key: String,
+    pub image_content_type: String,
+    pub content_hash: String,
+}
+
+/// The signature block of the worksheet document, the image is passed to typst separately
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WorksheetSignaturePrint {
+    pub signer_name: String,
+    pub signed_at: String,
+    pub content_hash: String,
+}
@@ -17,23 +17,31 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::{EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam};
-use crate::common::extractors::UserInput;
+use crate::common::dto::{
+    DuplicateParams, EmptyType, SimpleMessageResponse, SuccessResponseBuilder, UuidParam,
+};
+use crate::common::extractors::{UserInput, ValidJson};
 use crate::common::handler::{HandlerResult, map_handler_err};
-use crate::common::query_parser::{CommonRawQuery, ResourceQuery};
+use crate::common::query_parser::{ArchivableRawQuery, ResourceQuery};
 use crate::common::service::Service;
+use crate::manager::auth::dto::claims::Claims;
 use crate::manager::auth::middleware::AuthenticatedUser;
+use crate::tenant::tags::dto::TagFilter;
 use crate::tenant::worksheets::WorksheetsModuleInterface;
+use crate::tenant::worksheets::dto::billing::BillWorksheet;
+use crate::tenant::worksheets::dto::checklist::ChecklistItemCompletion;
 use crate::tenant::worksheets::dto::print::WorksheetResolvedPrint;
+use crate::tenant::worksheets::dto::signature::WorksheetSignatureUpload;
 use crate::tenant::worksheets::dto::user_input::{WorksheetUserInput, WorksheetUserInputHelper};
-use crate::tenant::worksheets::service::WorksheetService;
+use crate::tenant::worksheets::service::{WorksheetService, WorksheetsServiceError};
 use crate::tenant::worksheets::types::worksheet::{WorksheetFilterBy, WorksheetOrderBy};
-use axum::extract::{Query, State};
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub async fn get_resolved<M: WorksheetsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
@@ -168,7 +176,8 @@ pub async fn select_list<M: WorksheetsModuleInterface>(
 pub async fn list<M: WorksheetsModuleInterface>(
     AuthenticatedUser(claims): AuthenticatedUser,
     State(worksheets_module): State<Arc<M>>,
-    Query(payload): Query<CommonRawQuery>,
+    Query(payload): Query<ArchivableRawQuery>,
+    Query(tag_filter): Query<TagFilter>,
 ) -> HandlerResult {
     let service = Service::new(Some(&claims), worksheets_module.clone());
     let resource_query = map_handler_err(
@@ -177,7 +186,9 @@ pub async fn list<M: WorksheetsModuleInterface>(
     )
     .await?;
     let (meta, data) = map_handler_err(
-        service.get_paged(&resource_query).await,
+        service
+            .get_paged(&resource_query, payload.include_archived(), tag_filter.tags)
+            .await,
         worksheets_module.clone(),
     )
     .await?;
@@ -223,6 +234,262 @@ pub async fn print<M: WorksheetsModuleInterface>(
     Ok((StatusCode::OK, headers, pdf).into_response())
 }
 
+pub async fn pdf<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    pdf_response(&claims, worksheets_module, payload.uuid).await
+}
+
+pub async fn pdf_by_id<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Path(id): Path<Uuid>,
+) -> HandlerResult {
+    pdf_response(&claims, worksheets_module, id).await
+}
+
+async fn pdf_response<M: WorksheetsModuleInterface>(
+    claims: &Claims,
+    worksheets_module: Arc<M>,
+    id: Uuid,
+) -> HandlerResult {
+    let service = Service::new(Some(claims), worksheets_module.clone());
+    let tz = map_handler_err(claims.tz(), worksheets_module.clone()).await?;
+    let pdf = map_handler_err(service.print_document(id, tz).await, worksheets_module).await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "application/pdf".parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(r#"inline; filename="{id}""#).parse().unwrap(),
+    );
+    Ok((StatusCode::OK, headers, pdf).into_response())
+}
+
+pub async fn bill<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<BillWorksheet>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(service.bill(&payload).await, worksheets_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn invoices<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(
+        service.get_invoices(payload.uuid).await,
+        worksheets_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+async fn read_signature(
+    mut multipart: Multipart,
+) -> Result<WorksheetSignatureUpload, WorksheetsServiceError> {
+    let invalid = |_| WorksheetsServiceError::UnprocessableEntry("Hibás feltöltési kérés!");
+    let mut worksheet_id = None;
+    let mut signer_name = None;
+    let mut data = None;
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        let name = field.name().map(str::to_owned);
+        match name.as_deref() {
+            Some("worksheet_id") => {
+                worksheet_id = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(invalid)?
+                        .trim()
+                        .parse::<uuid::Uuid>()
+                        .map_err(|_| {
+                            WorksheetsServiceError::UnprocessableEntry("Hibás munkalap azonosító!")
+                        })?,
+                )
+            }
+            Some("signer_name") => signer_name = Some(field.text().await.map_err(invalid)?),
+            Some("file") => data = Some(field.bytes().await.map_err(invalid)?.to_vec()),
+            _ => {}
+        }
+    }
+    match (worksheet_id, signer_name, data) {
+        (Some(worksheet_id), Some(signer_name), Some(data)) => Ok(WorksheetSignatureUpload {
+            worksheet_id,
+            signer_name,
+            data,
+        }),
+        _ => Err(WorksheetsServiceError::UnprocessableEntry(
+            "A munkalap, az aláíró neve és az aláírás képe kötelező!",
+        )),
+    }
+}
+
+pub async fn sign<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    multipart: Multipart,
+) -> HandlerResult {
+    let payload =
+        map_handler_err(read_signature(multipart).await, worksheets_module.clone()).await?;
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(service.sign(payload).await, worksheets_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn signature<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(
+        service.get_signature(payload.uuid).await,
+        worksheets_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn signature_image<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let (content_type, data) = map_handler_err(
+        service.get_signature_image(payload.uuid).await,
+        worksheets_module,
+    )
+    .await?;
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+    Ok((StatusCode::OK, headers, data).into_response())
+}
+
+pub async fn checklist<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(
+        service.get_checklist(payload.uuid).await,
+        worksheets_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn complete_checklist_item<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<ChecklistItemCompletion>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(
+        service.complete_checklist_item(&payload).await,
+        worksheets_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn planned_materials<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    Query(payload): Query<UuidParam>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result = map_handler_err(
+        service.get_planned_materials(payload.uuid).await,
+        worksheets_module.clone(),
+    )
+    .await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::OK)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
+pub async fn duplicate<M: WorksheetsModuleInterface>(
+    AuthenticatedUser(claims): AuthenticatedUser,
+    State(worksheets_module): State<Arc<M>>,
+    ValidJson(payload): ValidJson<DuplicateParams>,
+) -> HandlerResult {
+    let service = Service::new(Some(&claims), worksheets_module.clone());
+    let result =
+        map_handler_err(service.duplicate(&payload).await, worksheets_module.clone()).await?;
+    Ok(map_handler_err(
+        SuccessResponseBuilder::<EmptyType, _>::new()
+            .status_code(StatusCode::CREATED)
+            .data(result)
+            .build(),
+        worksheets_module,
+    )
+    .await?
+    .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,7 +501,23 @@ mod tests {
     };
     use crate::common::pdf::tests::{PDF_GENERATOR_TEST_SYNC, extract_pdf_text};
     use crate::common::pdf::{MockPdfGenerator, PdfGenerator, PdfTemplates};
-    use crate::tenant::worksheets::model::WorksheetResolved;
+    use crate::common::storage::MockFileStorage;
+    use crate::manager::tenants::repository::MockTenantsRepository;
+    use crate::tenant::customers::model::CustomerCreditExposure;
+    use crate::tenant::customers::repository::MockCustomersRepository;
+    use crate::tenant::document_settings::model::{DocumentRecipient, DocumentSettings};
+    use crate::tenant::document_settings::repository::MockDocumentSettingsRepository;
+    use crate::tenant::exchange_rates::model::ExchangeRate;
+    use crate::tenant::exchange_rates::repository::MockExchangeRatesRepository;
+    use crate::tenant::permissions::repository::MockPermissionsRepository;
+    use crate::tenant::products::dto::attachment::tests::png;
+    use crate::tenant::project_members::repository::MockProjectMembersRepository;
+    use crate::tenant::receivables::model::Receivable;
+    use crate::tenant::worksheets::dto::print::WorksheetDocumentPrint;
+    use crate::tenant::worksheets::model::{
+        WorksheetBillableItem, WorksheetDocumentLine, WorksheetDocumentTimeEntry,
+        WorksheetResolved, WorksheetSignature,
+    };
     use crate::{
         common::config::tests::AppConfigBuilder,
         tenant::worksheets::{
@@ -244,7 +527,8 @@ mod tests {
     };
     use axum::body::Body;
     use axum::{Router, http::Request};
-    use chrono::{DateTime, Utc};
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, NaiveDate, Utc};
     use mockall::predicate::eq;
     use pretty_assertions::assert_eq;
     use serde_json::json;
@@ -254,6 +538,47 @@ mod tests {
     use tower::ServiceExt;
     use uuid::Uuid;
 
+    /// Every worksheet belongs to `project_id`, where the user holds `role`
+    fn expect_project_access(
+        app_state: &mut MockWorksheetsModule,
+        project_id: Option<Uuid>,
+        role: Option<&'static str>,
+        access_all: bool,
+    ) {
+        let mut project_members_repo = MockProjectMembersRepository::new();
+        project_members_repo
+            .expect_get_worksheet_project_id()
+            .returning(move |_| Ok(project_id));
+        project_members_repo
+            .expect_get_role()
+            .returning(move |_, _| Ok(role.map(str::to_string)));
+        let project_members_repo = Arc::new(project_members_repo);
+        let mut membership_repo = MockTenantsRepository::new();
+        membership_repo
+            .expect_get_role()
+            .returning(|_, _| Ok(Some("member".to_string())));
+        let membership_repo = Arc::new(membership_repo);
+        let mut permissions_repo = MockPermissionsRepository::new();
+        permissions_repo
+            .expect_has_permission()
+            .withf(|_, permission| permission == "projects.access_all")
+            .returning(move |_, _| Ok(access_all));
+        let permissions_repo = Arc::new(permissions_repo);
+        app_state
+            .expect_project_members_repo()
+            .returning(move |_| Ok(project_members_repo.clone()));
+        app_state
+            .expect_membership_repo()
+            .returning(move || membership_repo.clone());
+        app_state
+            .expect_permissions_repo()
+            .returning(move |_| Ok(permissions_repo.clone()));
+    }
+
+    fn allow_project_access(app_state: &mut MockWorksheetsModule) {
+        expect_project_access(app_state, None, None, true);
+    }
+
     #[tokio::test]
     async fn test_get_success() {
         let active_tenant_id = Uuid::new_v4();
@@ -272,6 +597,9 @@ mod tests {
             created_at: utc_now,
             updated_at: utc_now,
             deleted_at: None,
+            worksheet_template_id: None,
+            estimated_duration_minutes: None,
+            billing_status: "not_billed".to_string(),
         };
 
         let mut repo = MockWorksheetsRepository::new();
@@ -284,6 +612,7 @@ mod tests {
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -332,6 +661,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -360,6 +690,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -372,6 +703,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -400,6 +732,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -444,6 +777,7 @@ mod tests {
             .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -481,6 +815,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -525,6 +860,7 @@ mod tests {
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -573,6 +909,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -601,6 +938,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -613,6 +951,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -641,6 +980,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -685,6 +1025,7 @@ mod tests {
             .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -722,6 +1063,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -764,15 +1106,21 @@ mod tests {
         let mut repo = MockWorksheetsRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>>()
-                .unwrap()))
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(false),
+                eq(None),
+            )
             .returning({
                 let worksheet_resolved = worksheet_resolved.clone();
-                move |_| Ok((paginator_meta, vec![worksheet_resolved.clone()]))
+                move |_, _, _, _| Ok((paginator_meta, vec![worksheet_resolved.clone()]))
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -819,6 +1167,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_unauthorized_expired() {
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -847,6 +1196,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -857,6 +1207,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_unauthorized_invalid_signature() {
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -885,6 +1236,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -923,12 +1275,18 @@ mod tests {
         let mut repo = MockWorksheetsRepository::new();
         repo.expect_get_paged()
             .times(1)
-            .with(eq(""
-                .parse::<ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>>()
-                .unwrap()))
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .with(
+                eq(""
+                    .parse::<ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>>()
+                    .unwrap()),
+                eq(None),
+                eq(false),
+                eq(None),
+            )
+            .returning(|_, _, _, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -966,6 +1324,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "NOT_FOUND",
                 "message": "Nem található"
             }
         });
@@ -991,6 +1350,9 @@ mod tests {
             created_at: utc_now,
             updated_at: utc_now,
             deleted_at: None,
+            worksheet_template_id: None,
+            estimated_duration_minutes: None,
+            billing_status: "not_billed".to_string(),
         };
 
         let user_input_helper = WorksheetUserInputHelper {
@@ -1023,6 +1385,7 @@ mod tests {
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1083,6 +1446,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1117,6 +1481,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "VALIDATION_FAILED",
                 "message": "Kérjük ellenőrizze a hibás mezőket!",
                 "fields": user_input_error
             }
@@ -1137,6 +1502,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1166,6 +1532,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1185,6 +1552,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1214,6 +1582,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1274,6 +1643,9 @@ mod tests {
             created_at: utc_now,
             updated_at: utc_now,
             deleted_at: None,
+            worksheet_template_id: None,
+            estimated_duration_minutes: None,
+            billing_status: "not_billed".to_string(),
         };
 
         let user_input_helper = WorksheetUserInputHelper {
@@ -1287,6 +1659,10 @@ mod tests {
         let user_input = WorksheetUserInput::try_from(user_input_helper.clone()).unwrap();
 
         let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_signature()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(|_| Ok(None));
         repo.expect_update()
             .times(1)
             .with(eq(user_input))
@@ -1296,6 +1672,7 @@ mod tests {
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1355,6 +1732,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1387,6 +1765,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNPROCESSABLE_ENTRY",
                 "message": "Hiba történt az adatok feldolgozása során: Az azonosító megadása kötelező!"
             }
         });
@@ -1406,6 +1785,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1435,6 +1815,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1455,6 +1836,7 @@ mod tests {
         };
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1484,6 +1866,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1533,12 +1916,17 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
         let mut repo = MockWorksheetsRepository::new();
 
+        repo.expect_get_signature()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(|_| Ok(None));
         repo.expect_delete_by_id()
             .times(1)
             .with(eq(worksheet_id))
             .returning(move |_| Ok(()));
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1590,6 +1978,7 @@ mod tests {
         let user_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1629,6 +2018,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1657,6 +2047,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1669,6 +2060,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1697,6 +2089,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1769,6 +2162,7 @@ mod tests {
             });
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let repo = Arc::new(repo);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
@@ -1846,6 +2240,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1875,6 +2270,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1887,6 +2283,7 @@ mod tests {
         let worksheet_id = Uuid::new_v4();
 
         let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
         let test_config = AppConfigBuilder::default().build().unwrap();
         app_state
             .expect_config()
@@ -1916,6 +2313,7 @@ mod tests {
         let response_body = extract_json_response(response).await;
         let expected_body = json!({
             "error": {
+                "code": "UNAUTHORIZED",
                 "message": "Hozzáférés megtagadva!"
             }
         });
@@ -1950,4 +2348,1143 @@ mod tests {
 
         assert_eq!(response_body, expected_body);
     }
+
+    #[tokio::test]
+    async fn test_duplicate_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let source_id = Uuid::new_v4();
+        let utc_now = Utc::now();
+        let worksheet = Worksheet {
+            id: Uuid::new_v4(),
+            name: "Test worksheet (másolat)".to_string(),
+            description: None,
+            customer_id: Uuid::new_v4(),
+            project_id: None,
+            created_by_id: Uuid::new_v4(),
+            status: "active".to_string(),
+            created_at: utc_now,
+            updated_at: utc_now,
+            deleted_at: None,
+            worksheet_template_id: None,
+            estimated_duration_minutes: None,
+            billing_status: "not_billed".to_string(),
+        };
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_duplicate()
+            .times(1)
+            .withf(move |params, _| {
+                *params
+                    == DuplicateParams {
+                        id: source_id,
+                        include_lines: true,
+                        include_tags: false,
+                    }
+            })
+            .returning({
+                let worksheet = worksheet.clone();
+                move |_, _| Ok(worksheet.clone())
+            });
+
+        let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
+        let repo = Arc::new(repo);
+        let test_config = AppConfigBuilder::default().build().unwrap();
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(test_config.clone());
+        let request = Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(None, Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/worksheets/duplicate")
+            .body(json!({"id": source_id, "include_lines": true}).to_string())
+            .unwrap();
+
+        let app = Router::new().nest(
+            "/api",
+            Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+        );
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response_body = extract_json_response(response).await;
+        let expected_body = json!({
+            "meta": null,
+            "data": worksheet
+        });
+
+        assert_eq!(response_body, expected_body);
+    }
+
+    fn worksheet_resolved(worksheet_id: Uuid, status: &str) -> WorksheetResolved {
+        WorksheetResolved {
+            id: worksheet_id,
+            name: "Test worksheet".to_string(),
+            description: None,
+            customer_id: Uuid::new_v4(),
+            customer: "Test customer".to_string(),
+            project_id: None,
+            project: None,
+            created_by_id: Uuid::new_v4(),
+            created_by: "Test user".to_string(),
+            status: status.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            net_material_cost: "10".parse().unwrap(),
+            gross_material_cost: "20".parse().unwrap(),
+            net_work_cost: "30".parse().unwrap(),
+            gross_work_cost: "40".parse().unwrap(),
+        }
+    }
+
+    fn signature(worksheet_id: Uuid, content_hash: String) -> WorksheetSignature {
+        WorksheetSignature {
+            worksheet_id,
+            signer_name: "Kovács Anna".to_string(),
+            image_storage_key: format!("tenant/worksheets/{worksheet_id}/signature.png"),
+            image_content_type: "image/png".to_string(),
+            content_hash,
+            signed_by_id: Uuid::new_v4(),
+            signed_at: Utc::now(),
+        }
+    }
+
+    fn signature_app(
+        repo: MockWorksheetsRepository,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let storage = Arc::new(storage);
+        let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn sign_request(active_tenant_id: Uuid, worksheet_id: Uuid, data: &[u8]) -> Request<Body> {
+        let boundary = "obvia-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"worksheet_id\"\r\n\r\n{worksheet_id}\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"signer_name\"\r\n\r\n Kovács Anna \r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"signature.png\"\r\n\
+             Content-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                ),
+            )
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .method("POST")
+            .uri("/api/worksheets/sign")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sign_success() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let worksheet = worksheet_resolved(worksheet_id, "completed");
+        let content_hash = worksheet.content_hash(&[]);
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(move |_| Ok(worksheet.clone()));
+        repo.expect_get_signature().times(1).returning(|_| Ok(None));
+        repo.expect_get_content_tasks()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        repo.expect_insert_signature()
+            .withf({
+                let content_hash = content_hash.clone();
+                move |signature, _| {
+                    signature.worksheet_id == worksheet_id
+                        && signature.signer_name == "Kovács Anna"
+                        && signature.image_content_type == "image/png"
+                        && signature.content_hash == content_hash
+                }
+            })
+            .times(1)
+            .returning(move |_, _| Ok(signature(worksheet_id, content_hash.clone())));
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_put()
+            .times(1)
+            .withf(move |key, content_type, _| {
+                key.starts_with(&format!("{active_tenant_id}/worksheets/{worksheet_id}/"))
+                    && content_type == "image/png"
+            })
+            .returning(|_, _, _| Ok(()));
+        storage.expect_delete().never();
+
+        let response = signature_app(repo, storage, active_tenant_id)
+            .oneshot(sign_request(active_tenant_id, worksheet_id, &png(120, 40)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_sign_rejects_worksheet_not_completed() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet_resolved(worksheet_id, "active")));
+        repo.expect_insert_signature().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_put().never();
+
+        let response = signature_app(repo, storage, active_tenant_id)
+            .oneshot(sign_request(active_tenant_id, worksheet_id, &png(120, 40)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_signature_detects_changed_content() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let worksheet = worksheet_resolved(worksheet_id, "completed");
+        let signed = signature(worksheet_id, worksheet.content_hash(&[]));
+        let changed = WorksheetResolved {
+            net_work_cost: "35".parse().unwrap(),
+            ..worksheet
+        };
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_signature().times(1).returning({
+            let signed = signed.clone();
+            move |_| Ok(Some(signed.clone()))
+        });
+        repo.expect_get_resolved_by_id()
+            .times(1)
+            .returning(move |_| Ok(changed.clone()));
+        repo.expect_get_content_tasks()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let response = signature_app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                        ),
+                    )
+                    .method("GET")
+                    .uri(format!("/api/worksheets/signature?uuid={worksheet_id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let response_body = extract_json_response(response).await;
+        assert_eq!(response_body["data"]["content_unchanged"], json!(false));
+        assert_eq!(
+            response_body["data"]["content_hash"],
+            json!(signed.content_hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_signed_worksheet_is_rejected() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_signature()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(move |_| Ok(Some(signature(worksheet_id, "hash".to_string()))));
+        repo.expect_update().never();
+
+        let response = signature_app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(
+                Request::builder()
+                    .header(
+                        "Authorization",
+                        format!(
+                            "Bearer {}",
+                            generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                        ),
+                    )
+                    .header("Content-Type", "application/json")
+                    .method("PUT")
+                    .uri("/api/worksheets/update")
+                    .body(Body::from(
+                        json!({
+                            "id": worksheet_id.to_string(),
+                            "name": "Test worksheet",
+                            "description": "",
+                            "customer_id": Uuid::new_v4().to_string(),
+                            "project_id": "",
+                            "status": "completed"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn document_settings(worksheet_layout: &str, worksheet_hide_logo: bool) -> DocumentSettings {
+        DocumentSettings {
+            company_name: Some("Obvia Kft.".to_string()),
+            logo_storage_key: Some("tenant/document_settings/logo.png".to_string()),
+            logo_content_type: Some("image/png".to_string()),
+            worksheet_layout: worksheet_layout.to_string(),
+            worksheet_hide_logo,
+            updated_at: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    fn pdf_app(
+        repo: MockWorksheetsRepository,
+        settings: DocumentSettings,
+        storage: MockFileStorage,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let mut document_settings_repo = MockDocumentSettingsRepository::new();
+        document_settings_repo
+            .expect_get()
+            .times(1)
+            .returning(move || Ok(settings.clone()));
+        document_settings_repo
+            .expect_get_recipient()
+            .times(1)
+            .returning(|_| {
+                Ok(DocumentRecipient {
+                    name: "Teszt Ügyfél".to_string(),
+                    email: "ugyfel@example.com".to_string(),
+                    phone_number: None,
+                })
+            });
+        let document_settings_repo = Arc::new(document_settings_repo);
+        let repo = Arc::new(repo);
+        let storage = Arc::new(storage);
+        let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_document_settings_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(document_settings_repo.clone()));
+        app_state
+            .expect_file_storage()
+            .returning(move || storage.clone());
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn pdf_request(active_tenant_id: Uuid, worksheet_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                ),
+            )
+            .method("GET")
+            .uri(format!("/api/worksheets/{worksheet_id}/pdf"))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pdf_renders_detailed_document() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let started_at: DateTime<Utc> = "2026-01-01T08:00:00Z".parse().unwrap();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(move |_| Ok(worksheet_resolved(worksheet_id, "completed")));
+        repo.expect_get_signature().times(1).returning(|_| Ok(None));
+        repo.expect_get_document_tasks()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(|_| {
+                Ok(vec![WorksheetDocumentLine {
+                    name: "Szerelés".to_string(),
+                    description: Some("Kazán csere".to_string()),
+                    quantity: "2".parse().unwrap(),
+                    unit: None,
+                    unit_price: Some("15".parse().unwrap()),
+                    currency_code: "HUF".to_string(),
+                    tax: "27%".to_string(),
+                    tax_rate: "27".parse().unwrap(),
+                }])
+            });
+        repo.expect_get_document_materials()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        repo.expect_get_document_time_entries()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(move |_| {
+                Ok(vec![WorksheetDocumentTimeEntry {
+                    user_name: "Teszt Elek".to_string(),
+                    task: Some("Szerelés".to_string()),
+                    description: None,
+                    started_at,
+                    ended_at: started_at + chrono::Duration::minutes(150),
+                    billable: true,
+                }])
+            });
+        let mut storage = MockFileStorage::new();
+        storage
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(b"logo".to_vec()));
+
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_document_with_images_context();
+        pdf_gen
+            .expect::<Vec<WorksheetDocumentPrint>>()
+            .times(1)
+            .withf(|template, payload, images| {
+                *template == PdfTemplates::WorksheetDocument
+                    && payload[0].detailed
+                    && payload[0].tasks[0].gross_amount == "38\u{a0}Ft"
+                    && payload[0].tax_summary[0].tax_amount == "8\u{a0}Ft"
+                    && payload[0].total_time == "2:30"
+                    && payload[0].time_entries[0].task == "Szerelés"
+                    && images.len() == 1
+                    && images[0].0 == "logo"
+            })
+            .returning(|_, _, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = pdf_app(
+            repo,
+            document_settings("detailed", false),
+            storage,
+            active_tenant_id,
+        )
+        .oneshot(pdf_request(active_tenant_id, worksheet_id))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pdf_summary_layout_without_logo() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_resolved_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet_resolved(worksheet_id, "active")));
+        repo.expect_get_signature().times(1).returning(|_| Ok(None));
+        repo.expect_get_document_tasks().never();
+        repo.expect_get_document_materials().never();
+        repo.expect_get_document_time_entries().never();
+        let mut storage = MockFileStorage::new();
+        storage.expect_get().never();
+
+        let _m = PDF_GENERATOR_TEST_SYNC.lock();
+        let pdf_gen = MockPdfGenerator::gen_pdf_document_with_images_context();
+        pdf_gen
+            .expect::<Vec<WorksheetDocumentPrint>>()
+            .times(1)
+            .withf(|_, payload, images| {
+                !payload[0].detailed
+                    && payload[0].tasks.is_empty()
+                    && payload[0].gross_total == "60,00"
+                    && images.is_empty()
+            })
+            .returning(|_, _, _| Ok(b"%PDF-1.7".to_vec()));
+
+        let response = pdf_app(
+            repo,
+            document_settings("summary", true),
+            storage,
+            active_tenant_id,
+        )
+        .oneshot(pdf_request(active_tenant_id, worksheet_id))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn billable_item(
+        source: &str,
+        currency_code: &str,
+        net: &str,
+        tax: &str,
+    ) -> WorksheetBillableItem {
+        WorksheetBillableItem {
+            source: source.to_string(),
+            source_id: Uuid::new_v4(),
+            service_id: Some(Uuid::new_v4()),
+            product_id: None,
+            description: "Szerelés".to_string(),
+            quantity: "1".parse().unwrap(),
+            unit_price: net.parse().unwrap(),
+            currency_code: Some(currency_code.to_string()),
+            tax_id: Some(Uuid::new_v4()),
+            tax_rate: "27".parse().unwrap(),
+            net_amount: net.parse().unwrap(),
+            tax_amount: tax.parse().unwrap(),
+        }
+    }
+
+    fn billing_app(
+        repo: MockWorksheetsRepository,
+        customers_repo: MockCustomersRepository,
+        active_tenant_id: Uuid,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let customers_repo = Arc::new(customers_repo);
+        let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(customers_repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn bill_request(active_tenant_id: Uuid, payload: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(Uuid::new_v4()), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method("POST")
+            .uri("/api/worksheets/bill")
+            .body(Body::from(payload.to_string()))
+            .unwrap()
+    }
+
+    fn worksheet(worksheet_id: Uuid) -> Worksheet {
+        Worksheet {
+            id: worksheet_id,
+            name: "Test worksheet".to_string(),
+            description: None,
+            customer_id: Uuid::new_v4(),
+            project_id: None,
+            created_by_id: Uuid::new_v4(),
+            status: "completed".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            worksheet_template_id: None,
+            estimated_duration_minutes: None,
+            billing_status: "not_billed".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bill_invoices_items_in_first_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let time_entry_service_id = Uuid::new_v4();
+        let worksheet = worksheet(worksheet_id);
+        let customer_id = worksheet.customer_id;
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .with(eq(worksheet_id))
+            .times(1)
+            .returning(move |_| Ok(worksheet.clone()));
+        repo.expect_get_billable_items()
+            .with(
+                eq(worksheet_id),
+                eq(Some(time_entry_service_id)),
+                eq(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()),
+            )
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    billable_item("tasks", "HUF", "10000", "2700"),
+                    billable_item("inventory_movements", "EUR", "20", "5.40"),
+                    billable_item("time_entries", "HUF", "5000", "1350"),
+                ])
+            });
+        repo.expect_bill()
+            .withf(move |invoice, _| {
+                invoice.worksheet_id == worksheet_id
+                    && invoice.customer_id == customer_id
+                    && invoice.currency_code == "HUF"
+                    && invoice.document_number.is_none()
+                    && invoice.items.len() == 2
+                    && invoice.amount() == "19050".parse::<BigDecimal>().unwrap()
+            })
+            .times(1)
+            .returning(move |invoice, sub| {
+                Ok(Receivable {
+                    id: Uuid::new_v4(),
+                    customer_id: invoice.customer_id,
+                    document_number: "SZ-2026-00001".to_string(),
+                    issue_date: invoice.issue_date,
+                    due_date: invoice.due_date,
+                    currency_code: invoice.currency_code.clone(),
+                    amount: invoice.amount(),
+                    paid_amount: BigDecimal::from(0),
+                    credited_amount: BigDecimal::from(0),
+                    status: "open".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                })
+            });
+
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .with(eq(customer_id))
+            .times(1)
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: Some(BigDecimal::from(50000)),
+                    currency_code: Some("HUF".to_string()),
+                    open_balance: BigDecimal::from(30950),
+                    mode: "block".to_string(),
+                })
+            });
+
+        let response = billing_app(repo, customers_repo, active_tenant_id)
+            .oneshot(bill_request(
+                active_tenant_id,
+                json!({
+                    "worksheet_id": worksheet_id,
+                    "time_entry_service_id": time_entry_service_id,
+                    "currency_code": null,
+                    "document_number": " ",
+                    "issue_date": "2026-03-01",
+                    "due_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = extract_json_response(response).await;
+        assert_eq!(body["data"]["due_date"], json!("2026-03-09"));
+    }
+
+    #[tokio::test]
+    async fn test_bill_converts_items_in_other_currencies() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let worksheet = worksheet(worksheet_id);
+        let customer_id = worksheet.customer_id;
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet.clone()));
+        repo.expect_get_billable_items()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    billable_item("tasks", "HUF", "10000", "2700"),
+                    billable_item("inventory_movements", "EUR", "20", "5.40"),
+                ])
+            });
+        repo.expect_bill()
+            .withf(|invoice, _| {
+                invoice.currency_code == "HUF"
+                    && invoice.items.len() == 2
+                    && invoice.items[1].currency_code.as_deref() == Some("HUF")
+                    && invoice.items[1].unit_price == "7800".parse::<BigDecimal>().unwrap()
+                    && invoice.items[1].tax_amount == "2106".parse::<BigDecimal>().unwrap()
+                    && invoice.amount() == "22606".parse::<BigDecimal>().unwrap()
+            })
+            .times(1)
+            .returning(move |invoice, sub| {
+                Ok(Receivable {
+                    id: Uuid::new_v4(),
+                    customer_id: invoice.customer_id,
+                    document_number: "SZ-2026-00002".to_string(),
+                    issue_date: invoice.issue_date,
+                    due_date: invoice.due_date,
+                    currency_code: invoice.currency_code.clone(),
+                    amount: invoice.amount(),
+                    paid_amount: BigDecimal::from(0),
+                    credited_amount: BigDecimal::from(0),
+                    status: "open".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                })
+            });
+
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .with(eq(customer_id))
+            .times(1)
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: None,
+                    currency_code: None,
+                    open_balance: BigDecimal::from(0),
+                    mode: "warn".to_string(),
+                })
+            });
+
+        let mut exchange_rates_repo = MockExchangeRatesRepository::new();
+        exchange_rates_repo
+            .expect_get_rate_as_of()
+            .withf(|currency_code, date| {
+                currency_code == "EUR" && *date == NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(Some(ExchangeRate {
+                    id: Uuid::new_v4(),
+                    currency_code: "EUR".to_string(),
+                    rate_date: NaiveDate::from_ymd_opt(2026, 2, 27).unwrap(),
+                    rate: BigDecimal::from(390),
+                    source: "mnb".to_string(),
+                    created_by_id: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }))
+            });
+
+        let repo = Arc::new(repo);
+        let customers_repo = Arc::new(customers_repo);
+        let exchange_rates_repo = Arc::new(exchange_rates_repo);
+        let mut app_state = MockWorksheetsModule::new();
+        allow_project_access(&mut app_state);
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_customers_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(customers_repo.clone()));
+        app_state
+            .expect_exchange_rates_repo()
+            .with(eq(active_tenant_id))
+            .times(1)
+            .returning(move |_| Ok(exchange_rates_repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+
+        let response = Router::new()
+            .nest(
+                "/api",
+                Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+            )
+            .oneshot(bill_request(
+                active_tenant_id,
+                json!({
+                    "worksheet_id": worksheet_id,
+                    "time_entry_service_id": null,
+                    "currency_code": "HUF",
+                    "convert_currencies": true,
+                    "document_number": null,
+                    "issue_date": "2026-03-01",
+                    "due_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_bill_rounds_items_to_the_invoice_currency() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let worksheet = worksheet(worksheet_id);
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet.clone()));
+        repo.expect_get_billable_items()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![WorksheetBillableItem {
+                    quantity: "0.75".parse().unwrap(),
+                    unit_price: "3333.33".parse().unwrap(),
+                    net_amount: "2499.9975".parse().unwrap(),
+                    tax_amount: "674.999325".parse().unwrap(),
+                    ..billable_item("time_entries", "HUF", "0", "0")
+                }])
+            });
+        repo.expect_bill()
+            .withf(|invoice, _| {
+                invoice.items[0].net_amount == "2500.00".parse::<BigDecimal>().unwrap()
+                    && invoice.items[0].tax_amount == "675.00".parse::<BigDecimal>().unwrap()
+                    && invoice.amount() == "3175".parse::<BigDecimal>().unwrap()
+            })
+            .times(1)
+            .returning(move |invoice, sub| {
+                Ok(Receivable {
+                    id: Uuid::new_v4(),
+                    customer_id: invoice.customer_id,
+                    document_number: "SZ-2026-00003".to_string(),
+                    issue_date: invoice.issue_date,
+                    due_date: invoice.due_date,
+                    currency_code: invoice.currency_code.clone(),
+                    amount: invoice.amount(),
+                    paid_amount: BigDecimal::from(0),
+                    credited_amount: BigDecimal::from(0),
+                    status: "open".to_string(),
+                    created_by_id: sub,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                })
+            });
+
+        let mut customers_repo = MockCustomersRepository::new();
+        customers_repo
+            .expect_get_credit_exposure()
+            .times(1)
+            .returning(|customer_id| {
+                Ok(CustomerCreditExposure {
+                    customer_id,
+                    credit_limit: None,
+                    currency_code: None,
+                    open_balance: BigDecimal::from(0),
+                    mode: "warn".to_string(),
+                })
+            });
+
+        let response = billing_app(repo, customers_repo, active_tenant_id)
+            .oneshot(bill_request(
+                active_tenant_id,
+                json!({
+                    "worksheet_id": worksheet_id,
+                    "time_entry_service_id": null,
+                    "currency_code": null,
+                    "document_number": null,
+                    "issue_date": "2026-03-01",
+                    "due_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_bill_rejects_worksheet_without_billable_items() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet(worksheet_id)));
+        repo.expect_get_billable_items()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+        repo.expect_bill().never();
+
+        let response = signature_app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(bill_request(
+                active_tenant_id,
+                json!({
+                    "worksheet_id": worksheet_id,
+                    "time_entry_service_id": null,
+                    "currency_code": null,
+                    "document_number": null,
+                    "issue_date": null,
+                    "due_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_bill_rejects_time_entry_service_without_tax() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .returning(move |_| Ok(worksheet(worksheet_id)));
+        repo.expect_get_billable_items()
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![WorksheetBillableItem {
+                    tax_id: None,
+                    ..billable_item("time_entries", "HUF", "5000", "0")
+                }])
+            });
+        repo.expect_bill().never();
+
+        let response = signature_app(repo, MockFileStorage::new(), active_tenant_id)
+            .oneshot(bill_request(
+                active_tenant_id,
+                json!({
+                    "worksheet_id": worksheet_id,
+                    "time_entry_service_id": Uuid::new_v4(),
+                    "currency_code": "HUF",
+                    "document_number": null,
+                    "issue_date": null,
+                    "due_date": null
+                }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn access_app(
+        repo: MockWorksheetsRepository,
+        active_tenant_id: Uuid,
+        project_id: Option<Uuid>,
+        role: Option<&'static str>,
+        access_all: bool,
+    ) -> Router {
+        let repo = Arc::new(repo);
+        let mut app_state = MockWorksheetsModule::new();
+        expect_project_access(&mut app_state, project_id, role, access_all);
+        app_state
+            .expect_worksheets_repo()
+            .with(eq(active_tenant_id))
+            .returning(move |_| Ok(repo.clone()));
+        app_state
+            .expect_config()
+            .times(1)
+            .return_const(AppConfigBuilder::default().build().unwrap());
+        Router::new().nest(
+            "/api",
+            Router::new().merge(worksheets::routes::routes(Arc::new(app_state))),
+        )
+    }
+
+    fn access_request(method: &str, uri: &str, sub: Uuid, active_tenant_id: Uuid) -> Request<Body> {
+        Request::builder()
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    generate_valid_jwt(Some(sub), Some(active_tenant_id))
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_is_forbidden_for_non_members() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id()
+            .times(1)
+            .with(eq(worksheet_id))
+            .returning(move |_| {
+                Ok(Worksheet {
+                    project_id: Some(project_id),
+                    ..worksheet(worksheet_id)
+                })
+            });
+
+        let response = access_app(repo, active_tenant_id, Some(project_id), None, false)
+            .oneshot(access_request(
+                "GET",
+                &format!("/api/worksheets/get?uuid={worksheet_id}"),
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_with_access_all_permission() {
+        let active_tenant_id = Uuid::new_v4();
+        let worksheet_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_by_id().times(1).returning(move |_| {
+            Ok(Worksheet {
+                project_id: Some(project_id),
+                ..worksheet(worksheet_id)
+            })
+        });
+
+        let response = access_app(repo, active_tenant_id, Some(project_id), None, true)
+            .oneshot(access_request(
+                "GET",
+                &format!("/api/worksheets/get?uuid={worksheet_id}"),
+                Uuid::new_v4(),
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_forbidden_for_project_viewers() {
+        let active_tenant_id = Uuid::new_v4();
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_signature().never();
+        repo.expect_delete_by_id().never();
+
+        let response = access_app(
+            repo,
+            active_tenant_id,
+            Some(Uuid::new_v4()),
+            Some("viewer"),
+            false,
+        )
+        .oneshot(access_request(
+            "DELETE",
+            &format!("/api/worksheets/delete?uuid={}", Uuid::new_v4()),
+            Uuid::new_v4(),
+            active_tenant_id,
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_list_is_restricted_to_member_projects() {
+        let active_tenant_id = Uuid::new_v4();
+        let sub = Uuid::new_v4();
+        let mut repo = MockWorksheetsRepository::new();
+        repo.expect_get_paged()
+            .times(1)
+            .withf(move |_, visible_to, include_archived, _| {
+                *visible_to == Some(sub) && !include_archived
+            })
+            .returning(|_, _, _, _| {
+                Ok((
+                    PaginatorMeta {
+                        page: 1,
+                        limit: 25,
+                        total: 0,
+                    },
+                    vec![],
+                ))
+            });
+
+        let response = access_app(repo, active_tenant_id, None, None, false)
+            .oneshot(access_request(
+                "GET",
+                "/api/worksheets/list",
+                sub,
+                active_tenant_id,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
@@ -19,8 +19,12 @@
 
 use crate::common::database::PoolManager;
 use crate::common::error::RepositoryResult;
-use crate::common::{AppState, BaseModule};
+use crate::common::storage::{FileStorage, file_storage};
+use crate::common::{AppState, ConfigProvider};
 use crate::tenant::customers::repository::CustomersRepository;
+use crate::tenant::document_settings::repository::DocumentSettingsRepository;
+use crate::tenant::exchange_rates::repository::ExchangeRatesRepository;
+use crate::tenant::project_members::ProjectMembersModuleInterface;
 use crate::tenant::worksheets::repository::WorksheetsRepository;
 use lettre::{
     AsyncTransport,
@@ -38,7 +42,7 @@ pub(crate) mod routes;
 pub mod service;
 pub(crate) mod types;
 
-pub trait WorksheetsModuleInterface: BaseModule {
+pub trait WorksheetsModuleInterface: ProjectMembersModuleInterface {
     fn worksheets_repo(
         &self,
         tenant_id: Uuid,
@@ -47,12 +51,21 @@ pub trait WorksheetsModuleInterface: BaseModule {
         &self,
         tenant_id: Uuid,
     ) -> RepositoryResult<Arc<dyn CustomersRepository + Send + Sync>>;
+    fn document_settings_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn DocumentSettingsRepository + Send + Sync>>;
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
 }
 
 impl<P, T> WorksheetsModuleInterface for AppState<P, T>
 where
-    P: PoolManager + Send + Sync,
-    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync,
+    P: PoolManager + Send + Sync + 'static,
+    T: AsyncTransport<Ok = Response, Error = Error> + Send + Sync + 'static,
     T::Error: Debug,
 {
     fn worksheets_repo(
@@ -67,6 +80,21 @@ where
     ) -> RepositoryResult<Arc<dyn CustomersRepository + Send + Sync>> {
         Ok(self.get_tenant_pool(tenant_id)?)
     }
+    fn document_settings_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn DocumentSettingsRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn exchange_rates_repo(
+        &self,
+        tenant_id: Uuid,
+    ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>> {
+        Ok(self.get_tenant_pool(tenant_id)?)
+    }
+    fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync> {
+        file_storage(self.config().storage())
+    }
 }
 
 #[cfg(test)]
@@ -75,6 +103,10 @@ pub mod tests {
     use crate::common::config::AppConfig;
     use crate::common::error::RepositoryResult;
     use crate::common::{BaseModule, ConfigProvider, MailTransporter};
+    use crate::manager::tenants::repository::TenantsRepository;
+    use crate::tenant::permissions::PermissionsModuleInterface;
+    use crate::tenant::permissions::repository::PermissionsRepository;
+    use crate::tenant::project_members::repository::ProjectMembersRepository;
     use lettre::{
         Message,
         transport::smtp::{Error, response::Response},
@@ -91,6 +123,19 @@ pub mod tests {
             async fn send(&self, message: Message) -> Result<Option<Response>, Error>;
         }
         impl BaseModule for WorksheetsModule {}
+        impl PermissionsModuleInterface for WorksheetsModule {
+            fn permissions_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn PermissionsRepository + Send + Sync>>;
+            fn membership_repo(&self) -> Arc<dyn TenantsRepository + Send + Sync>;
+        }
+        impl ProjectMembersModuleInterface for WorksheetsModule {
+            fn project_members_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ProjectMembersRepository + Send + Sync>>;
+        }
         impl WorksheetsModuleInterface for WorksheetsModule {
             fn worksheets_repo(
                 &self,
@@ -100,6 +145,15 @@ pub mod tests {
                 &self,
                 tenant_id: Uuid,
             ) -> RepositoryResult<Arc<dyn CustomersRepository + Send + Sync>>;
+            fn document_settings_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn DocumentSettingsRepository + Send + Sync>>;
+            fn exchange_rates_repo(
+                &self,
+                tenant_id: Uuid,
+            ) -> RepositoryResult<Arc<dyn ExchangeRatesRepository + Send + Sync>>;
+            fn file_storage(&self) -> Arc<dyn FileStorage + Send + Sync>;
         }
     );
 }
@@ -19,6 +19,7 @@
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -34,6 +35,10 @@ pub struct Worksheet {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub worksheet_template_id: Option<Uuid>,
+    pub estimated_duration_minutes: Option<i32>,
+    /// `not_billed`, `partially_billed` or `billed`, kept up to date by the database
+    pub billing_status: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -56,3 +61,202 @@ pub struct WorksheetResolved {
     pub net_work_cost: BigDecimal,
     pub gross_work_cost: BigDecimal,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetChecklistItem {
+    pub id: Uuid,
+    pub worksheet_id: Uuid,
+    pub position: i32,
+    pub title: String,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetPlannedMaterial {
+    pub id: Uuid,
+    pub worksheet_id: Uuid,
+    pub product_id: Uuid,
+    pub product: String,
+    pub quantity: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetSignature {
+    pub worksheet_id: Uuid,
+    pub signer_name: String,
+    pub image_storage_key: String,
+    pub image_content_type: String,
+    pub content_hash: String,
+    pub signed_by_id: Uuid,
+    pub signed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorksheetSignatureStatus {
+    #[serde(flatten)]
+    pub signature: WorksheetSignature,
+    /// `false` once the worksheet or its tasks changed after signing
+    pub content_unchanged: bool,
+}
+
+/// A task line as it is covered by the signature hash
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetContentTask {
+    pub id: Uuid,
+    pub service: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub currency_code: String,
+    pub quantity: Option<BigDecimal>,
+    pub price: Option<BigDecimal>,
+    pub tax: String,
+}
+
+/// A task or material line of the worksheet document
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetDocumentLine {
+    pub name: String,
+    pub description: Option<String>,
+    pub quantity: BigDecimal,
+    pub unit: Option<String>,
+    pub unit_price: Option<BigDecimal>,
+    pub currency_code: String,
+    pub tax: String,
+    /// 0 for taxes without an applicable rate
+    pub tax_rate: BigDecimal,
+}
+
+impl WorksheetDocumentLine {
+    pub fn net_amount(&self) -> BigDecimal {
+        self.quantity.abs() * self.unit_price.clone().unwrap_or_default()
+    }
+    pub fn gross_amount(&self) -> BigDecimal {
+        self.net_amount() * (&self.tax_rate / BigDecimal::from(100) + BigDecimal::from(1))
+    }
+}
+
+/// A finished time entry booked on the worksheet or one of its tasks
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetDocumentTimeEntry {
+    pub user_name: String,
+    pub task: Option<String>,
+    pub description: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub billable: bool,
+}
+
+/// A not yet billed task, worksheet time entry or consumed material as an invoice line
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorksheetBillableItem {
+    /// The table of the billed item: `tasks`, `time_entries` or `inventory_movements`
+    pub source: String,
+    pub source_id: Uuid,
+    pub service_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub description: String,
+    pub quantity: BigDecimal,
+    pub unit_price: BigDecimal,
+    pub currency_code: Option<String>,
+    pub tax_id: Option<Uuid>,
+    pub tax_rate: BigDecimal,
+    pub net_amount: BigDecimal,
+    pub tax_amount: BigDecimal,
+}
+
+#[derive(Serialize)]
+struct WorksheetContent<'a> {
+    id: Uuid,
+    name: &'a str,
+    description: Option<&'a str>,
+    customer_id: Uuid,
+    project_id: Option<Uuid>,
+    status: &'a str,
+    net_material_cost: &'a BigDecimal,
+    gross_material_cost: &'a BigDecimal,
+    net_work_cost: &'a BigDecimal,
+    gross_work_cost: &'a BigDecimal,
+    tasks: &'a [WorksheetContentTask],
+}
+
+impl WorksheetResolved {
+    /// SHA-256 of the signed content in hex, timestamps are left out so only real changes count
+    pub fn content_hash(&self, tasks: &[WorksheetContentTask]) -> String {
+        let content = WorksheetContent {
+            id: self.id,
+            name: &self.name,
+            description: self.description.as_deref(),
+            customer_id: self.customer_id,
+            project_id: self.project_id,
+            status: &self.status,
+            net_material_cost: &self.net_material_cost,
+            gross_material_cost: &self.gross_material_cost,
+            net_work_cost: &self.net_work_cost,
+            gross_work_cost: &self.gross_work_cost,
+            tasks,
+        };
+        Sha256::digest(serde_json::to_vec(&content).unwrap_or_default())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn worksheet() -> WorksheetResolved {
+        WorksheetResolved {
+            id: Uuid::new_v4(),
+            name: "Kazán karbantartás".to_string(),
+            description: None,
+            customer_id: Uuid::new_v4(),
+            customer: "Teszt Kft.".to_string(),
+            project_id: None,
+            project: None,
+            created_by_id: Uuid::new_v4(),
+            created_by: "Teszt Elek".to_string(),
+            status: "completed".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            net_material_cost: "1000".parse().unwrap(),
+            gross_material_cost: "1270".parse().unwrap(),
+            net_work_cost: "5000".parse().unwrap(),
+            gross_work_cost: "6350".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_ignores_timestamps_only() {
+        let worksheet = worksheet();
+        let task = WorksheetContentTask {
+            id: Uuid::new_v4(),
+            service: "Szerelés".to_string(),
+            description: None,
+            status: "done".to_string(),
+            currency_code: "HUF".to_string(),
+            quantity: Some("2".parse().unwrap()),
+            price: Some("2500".parse().unwrap()),
+            tax: "27%".to_string(),
+        };
+        let hash = worksheet.content_hash(std::slice::from_ref(&task));
+        assert_eq!(hash.len(), 64);
+
+        let touched = WorksheetResolved {
+            updated_at: Utc::now() + chrono::Duration::hours(1),
+            ..worksheet.clone()
+        };
+        assert_eq!(touched.content_hash(std::slice::from_ref(&task)), hash);
+
+        let repriced = WorksheetContentTask {
+            price: Some("3000".parse().unwrap()),
+            ..task
+        };
+        assert_ne!(worksheet.content_hash(&[repriced]), hash);
+        assert_ne!(worksheet.content_hash(&[]), hash);
+    }
+}
@@ -17,14 +17,26 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::database::copy_tags;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::{RepositoryError, RepositoryResult};
 use crate::common::model::SelectOption;
 use crate::common::query_parser::ResourceQuery;
+use crate::tenant::project_members::repository::visible_project_condition;
+use crate::tenant::projects::repository::archived_project_condition;
+use crate::tenant::receivables::model::Receivable;
+use crate::tenant::tags::repository::tagged_condition;
+use crate::tenant::worksheets::dto::billing::NewWorksheetInvoice;
+use crate::tenant::worksheets::dto::signature::NewWorksheetSignature;
 use crate::tenant::worksheets::dto::user_input::WorksheetUserInput;
-use crate::tenant::worksheets::model::{Worksheet, WorksheetResolved};
+use crate::tenant::worksheets::model::{
+    Worksheet, WorksheetBillableItem, WorksheetChecklistItem, WorksheetContentTask,
+    WorksheetDocumentLine, WorksheetDocumentTimeEntry, WorksheetPlannedMaterial, WorksheetResolved,
+    WorksheetSignature,
+};
 use crate::tenant::worksheets::types::worksheet::{WorksheetFilterBy, WorksheetOrderBy};
 use async_trait::async_trait;
+use chrono::NaiveDate;
 #[cfg(test)]
 use mockall::automock;
 use sqlx::{AssertSqlSafe, PgPool};
@@ -39,13 +51,73 @@ pub trait WorksheetsRepository: Send + Sync {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<WorksheetResolved>)>;
     async fn insert(&self, worksheet: WorksheetUserInput, sub: Uuid)
     -> RepositoryResult<Worksheet>;
     async fn update(&self, worksheet: WorksheetUserInput) -> RepositoryResult<Worksheet>;
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()>;
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Worksheet>;
+    async fn get_checklist(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetChecklistItem>>;
+    async fn set_checklist_item_completed(
+        &self,
+        id: Uuid,
+        completed: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetChecklistItem>;
+    async fn get_planned_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetPlannedMaterial>>;
+    async fn get_signature(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Option<WorksheetSignature>>;
+    async fn get_content_tasks(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetContentTask>>;
+    async fn get_document_tasks(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentLine>>;
+    async fn get_document_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentLine>>;
+    async fn get_document_time_entries(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentTimeEntry>>;
+    async fn insert_signature(
+        &self,
+        signature: &NewWorksheetSignature,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetSignature>;
+    /// Unbilled items with unrounded amounts, the service rounds them to the invoice currency
+    async fn get_billable_items(
+        &self,
+        worksheet_id: Uuid,
+        time_entry_service_id: Option<Uuid>,
+        issue_date: NaiveDate,
+    ) -> RepositoryResult<Vec<WorksheetBillableItem>>;
+    async fn bill(&self, invoice: &NewWorksheetInvoice, sub: Uuid) -> RepositoryResult<Receivable>;
+    async fn get_invoices(&self, worksheet_id: Uuid) -> RepositoryResult<Vec<Receivable>>;
 }
 
+/// Taxes without an applicable rate are printed with 0%
+const DOCUMENT_TAX_RATE: &str =
+    "(CASE WHEN taxes.is_rate_applicable THEN COALESCE(taxes.rate, 0) ELSE 0 END)";
+
+/// Signed worksheets are locked
+const NOT_SIGNED: &str =
+    "NOT EXISTS (SELECT 1 FROM worksheet_signatures WHERE worksheet_id = worksheets.id)";
+
 #[async_trait]
 impl WorksheetsRepository for PgPool {
     async fn get_by_id(&self, id: Uuid) -> RepositoryResult<Worksheet> {
@@ -130,25 +202,47 @@ impl WorksheetsRepository for PgPool {
     async fn get_paged(
         &self,
         query_params: &ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>,
+        visible_to: Option<Uuid>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> RepositoryResult<(PaginatorMeta, Vec<WorksheetResolved>)> {
+        let archived_condition =
+            archived_project_condition("worksheets.project_id", include_archived);
         let total: (i64,) = match (
             query_params.filtering().filter_by(), // Security: ValueObject
             query_params.filtering().value_unchecked(), // Security: bind
         ) {
             (Some(filter_by), Some(value_unchecked)) => {
+                let visible_condition = visible_project_condition("worksheets.project_id", 2);
                 sqlx::query_as(AssertSqlSafe(format!(
                     r#"SELECT COUNT(*) FROM worksheets
                     WHERE deleted_at IS NULL
-                        AND ($1::TEXT IS NULL OR worksheets.{filter_by}::TEXT ILIKE '%' || $1 || '%')"#
+                        AND ($1::TEXT IS NULL OR worksheets.{filter_by}::TEXT ILIKE '%' || $1 || '%')
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("worksheets", "worksheets.id", 3)
                 )))
                 .bind(value_unchecked)
+                .bind(visible_to)
+                .bind(&tags)
                 .fetch_one(self)
                 .await?
             }
             (_, _) => {
-                sqlx::query_as("SELECT COUNT(*) FROM worksheets WHERE deleted_at IS NULL")
-                    .fetch_one(self)
-                    .await?
+                let visible_condition = visible_project_condition("worksheets.project_id", 1);
+                sqlx::query_as(AssertSqlSafe(format!(
+                    r#"SELECT COUNT(*) FROM worksheets
+                    WHERE deleted_at IS NULL
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}"#,
+                    tagged_condition = tagged_condition("worksheets", "worksheets.id", 2)
+                )))
+                .bind(visible_to)
+                .bind(&tags)
+                .fetch_one(self)
+                .await?
             }
         };
 
@@ -217,16 +311,23 @@ impl WorksheetsRepository for PgPool {
                     LEFT JOIN work_costs wc ON wc.worksheet_id = worksheets.id
                     WHERE worksheets.deleted_at IS NULL
                         AND ($1::TEXT IS NULL OR worksheets.{filter_by}::TEXT ILIKE '%' || $1 || '%')
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}
                     {order_by_clause}
                     LIMIT $2
                     OFFSET $3
-                    "#
+                    "#,
+                    tagged_condition = tagged_condition("worksheets", "worksheets.id", 5),
+                    visible_condition = visible_project_condition("worksheets.project_id", 4)
                 );
 
                 sqlx::query_as::<_, WorksheetResolved>(AssertSqlSafe(sql))
                     .bind(value_unchecked)
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(visible_to)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -280,15 +381,22 @@ impl WorksheetsRepository for PgPool {
                     LEFT JOIN material_costs mc ON mc.worksheet_id = worksheets.id
                     LEFT JOIN work_costs wc ON wc.worksheet_id = worksheets.id
                     WHERE worksheets.deleted_at IS NULL
+                        AND {visible_condition}
+                        AND {archived_condition}
+                        AND {tagged_condition}
                     {order_by_clause}
                     LIMIT $1
                     OFFSET $2
-                    "#
+                    "#,
+                    tagged_condition = tagged_condition("worksheets", "worksheets.id", 4),
+                    visible_condition = visible_project_condition("worksheets.project_id", 3)
                 );
 
                 sqlx::query_as::<_, WorksheetResolved>(AssertSqlSafe(sql))
                     .bind(limit)
                     .bind(i32::try_from(query_params.paging().offset().unwrap_or(0))?)
+                    .bind(visible_to)
+                    .bind(&tags)
                     .fetch_all(self)
                     .await?
             }
@@ -331,7 +439,7 @@ impl WorksheetsRepository for PgPool {
             .id
             .as_uuid()
             .ok_or_else(|| RepositoryError::InvalidInput("id".to_string()))?;
-        Ok(sqlx::query_as::<_, Worksheet>(
+        Ok(sqlx::query_as::<_, Worksheet>(AssertSqlSafe(format!(
             r#"
             UPDATE worksheets
             SET name = $1,
@@ -341,9 +449,10 @@ impl WorksheetsRepository for PgPool {
                 status = $5
             WHERE id = $6
                 AND deleted_at IS NULL
+                AND {NOT_SIGNED}
             RETURNING *
-            "#,
-        )
+            "# // Security: constant
+        )))
         .bind(worksheet.name.as_str()?)
         .bind(worksheet.description.as_str())
         .bind(worksheet.customer_id.as_uuid()?)
@@ -355,18 +464,491 @@ impl WorksheetsRepository for PgPool {
     }
 
     async fn delete_by_id(&self, id: Uuid) -> RepositoryResult<()> {
-        sqlx::query(
+        sqlx::query(AssertSqlSafe(format!(
             r#"
             UPDATE worksheets
             SET deleted_at = NOW()
             WHERE id = $1
                 AND deleted_at IS NULL
-            "#,
-        )
+                AND {NOT_SIGNED}
+            "# // Security: constant
+        )))
         .bind(id)
         .execute(self)
         .await?;
 
         Ok(())
     }
+
+    async fn get_checklist(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetChecklistItem>> {
+        Ok(sqlx::query_as::<_, WorksheetChecklistItem>(
+            "SELECT * FROM worksheet_checklist_items WHERE worksheet_id = $1 ORDER BY position",
+        )
+        .bind(worksheet_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn set_checklist_item_completed(
+        &self,
+        id: Uuid,
+        completed: bool,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetChecklistItem> {
+        Ok(sqlx::query_as::<_, WorksheetChecklistItem>(
+            r#"
+            UPDATE worksheet_checklist_items
+            SET completed_at = CASE WHEN $2 THEN NOW() END,
+                completed_by_id = CASE WHEN $2 THEN $3 END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(completed)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_planned_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetPlannedMaterial>> {
+        Ok(sqlx::query_as::<_, WorksheetPlannedMaterial>(
+            r#"
+            SELECT worksheet_planned_materials.id,
+                   worksheet_planned_materials.worksheet_id,
+                   worksheet_planned_materials.product_id,
+                   products.name AS product,
+                   worksheet_planned_materials.quantity
+            FROM worksheet_planned_materials
+            JOIN products ON worksheet_planned_materials.product_id = products.id
+            WHERE worksheet_planned_materials.worksheet_id = $1
+            ORDER BY products.name
+            "#,
+        )
+        .bind(worksheet_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_signature(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Option<WorksheetSignature>> {
+        Ok(sqlx::query_as::<_, WorksheetSignature>(
+            "SELECT * FROM worksheet_signatures WHERE worksheet_id = $1",
+        )
+        .bind(worksheet_id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn get_content_tasks(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetContentTask>> {
+        Ok(sqlx::query_as::<_, WorksheetContentTask>(
+            r#"
+            SELECT tasks.id,
+                   services.name as service,
+                   tasks.description,
+                   tasks.status,
+                   tasks.currency_code,
+                   tasks.quantity,
+                   tasks.price,
+                   taxes.description as tax
+            FROM tasks
+            LEFT JOIN services ON tasks.service_id = services.id
+            LEFT JOIN taxes ON tasks.tax_id = taxes.id
+            WHERE tasks.worksheet_id = $1
+                AND tasks.deleted_at IS NULL
+            ORDER BY tasks.id
+            "#,
+        )
+        .bind(worksheet_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn get_document_tasks(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentLine>> {
+        Ok(
+            sqlx::query_as::<_, WorksheetDocumentLine>(AssertSqlSafe(format!(
+                r#"
+            SELECT services.name as name,
+                   tasks.description,
+                   tasks.quantity,
+                   NULL::text as unit,
+                   tasks.price as unit_price,
+                   tasks.currency_code,
+                   taxes.description as tax,
+                   {DOCUMENT_TAX_RATE} as tax_rate
+            FROM tasks
+            LEFT JOIN services ON tasks.service_id = services.id
+            LEFT JOIN taxes ON tasks.tax_id = taxes.id
+            WHERE tasks.worksheet_id = $1
+                AND tasks.deleted_at IS NULL
+            ORDER BY tasks.created_at
+            "# // Security: constant
+            )))
+            .bind(worksheet_id)
+            .fetch_all(self)
+            .await?,
+        )
+    }
+
+    async fn get_document_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentLine>> {
+        Ok(
+            sqlx::query_as::<_, WorksheetDocumentLine>(AssertSqlSafe(format!(
+                r#"
+            SELECT products.name as name,
+                   NULL::text as description,
+                   abs(inventory_movements.quantity) as quantity,
+                   units_of_measure.unit_of_measure as unit,
+                   inventory_movements.unit_price,
+                   inventory.currency_code,
+                   taxes.description as tax,
+                   {DOCUMENT_TAX_RATE} as tax_rate
+            FROM inventory_movements
+            LEFT JOIN inventory ON inventory_movements.inventory_id = inventory.id
+            LEFT JOIN products ON inventory.product_id = products.id
+            LEFT JOIN units_of_measure ON products.unit_of_measure_id = units_of_measure.id
+            LEFT JOIN taxes ON inventory_movements.tax_id = taxes.id
+            WHERE inventory_movements.reference_type = 'worksheets'
+                AND inventory_movements.reference_id = $1
+                AND inventory_movements.movement_type = 'out'
+            ORDER BY inventory_movements.movement_date
+            "# // Security: constant
+            )))
+            .bind(worksheet_id)
+            .fetch_all(self)
+            .await?,
+        )
+    }
+
+    async fn get_document_time_entries(
+        &self,
+        worksheet_id: Uuid,
+    ) -> RepositoryResult<Vec<WorksheetDocumentTimeEntry>> {
+        Ok(sqlx::query_as::<_, WorksheetDocumentTimeEntry>(
+            r#"
+            SELECT users.last_name || ' ' || users.first_name as user_name,
+                   services.name as task,
+                   time_entries.description,
+                   time_entries.started_at,
+                   time_entries.ended_at,
+                   time_entries.billable
+            FROM time_entries
+            LEFT JOIN users ON time_entries.user_id = users.id
+            LEFT JOIN tasks ON time_entries.task_id = tasks.id
+            LEFT JOIN services ON tasks.service_id = services.id
+            WHERE time_entries.deleted_at IS NULL
+                AND time_entries.ended_at IS NOT NULL
+                AND (time_entries.worksheet_id = $1
+                    OR (tasks.worksheet_id = $1 AND tasks.deleted_at IS NULL))
+            ORDER BY time_entries.started_at
+            "#,
+        )
+        .bind(worksheet_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert_signature(
+        &self,
+        signature: &NewWorksheetSignature,
+        sub: Uuid,
+    ) -> RepositoryResult<WorksheetSignature> {
+        Ok(sqlx::query_as::<_, WorksheetSignature>(
+            r#"
+            INSERT INTO worksheet_signatures (worksheet_id, signer_name, image_storage_key,
+                                              image_content_type, content_hash, signed_by_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(signature.worksheet_id)
+        .bind(&signature.signer_name)
+        .bind(&signature.image_storage_key)
+        .bind(&signature.image_content_type)
+        .bind(&signature.content_hash)
+        .bind(sub)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn get_billable_items(
+        &self,
+        worksheet_id: Uuid,
+        time_entry_service_id: Option<Uuid>,
+        issue_date: NaiveDate,
+    ) -> RepositoryResult<Vec<WorksheetBillableItem>> {
+        Ok(sqlx::query_as::<_, WorksheetBillableItem>(
+            r#"
+            WITH items AS (SELECT 'tasks'                                                    AS source,
+                                  tasks.id                                                   AS source_id,
+                                  tasks.service_id                                           AS service_id,
+                                  NULL::uuid                                                 AS product_id,
+                                  services.name || COALESCE(' - ' || tasks.description, '') AS description,
+                                  tasks.quantity                                             AS quantity,
+                                  COALESCE(tasks.price, 0)                                   AS unit_price,
+                                  tasks.currency_code::varchar                               AS currency_code,
+                                  tasks.tax_id                                               AS tax_id,
+                                  tasks.created_at                                           AS sort_key
+                           FROM tasks
+                           JOIN services ON tasks.service_id = services.id
+                           WHERE tasks.worksheet_id = $1
+                             AND tasks.deleted_at IS NULL
+                             AND tasks.receivable_id IS NULL
+                           UNION ALL
+                           SELECT 'time_entries',
+                                  time_entries.id,
+                                  services.id,
+                                  NULL::uuid,
+                                  services.name || ' (' || to_char(time_entries.started_at, 'YYYY.MM.DD') || ')'
+                                      || COALESCE(' - ' || time_entries.description, ''),
+                                  round(extract(epoch FROM time_entries.ended_at - time_entries.started_at) / 3600, 2),
+                                  COALESCE(rate.price, 0),
+                                  rate.currency_code,
+                                  services.default_tax_id,
+                                  time_entries.started_at
+                           FROM time_entries
+                           JOIN worksheets ON time_entries.worksheet_id = worksheets.id
+                           JOIN services ON services.id = $2
+                           LEFT JOIN LATERAL resolve_service_rate(services.id, worksheets.customer_id,
+                                                                  time_entries.started_at::date) AS rate ON true
+                           WHERE time_entries.worksheet_id = $1
+                             AND time_entries.deleted_at IS NULL
+                             AND time_entries.billable
+                             AND time_entries.ended_at IS NOT NULL
+                             AND time_entries.receivable_id IS NULL
+                           UNION ALL
+                           SELECT 'inventory_movements',
+                                  inventory_movements.id,
+                                  NULL::uuid,
+                                  inventory.product_id,
+                                  products.name,
+                                  abs(inventory_movements.quantity),
+                                  COALESCE(inventory_movements.unit_price, 0),
+                                  inventory.currency_code,
+                                  inventory_movements.tax_id,
+                                  inventory_movements.movement_date
+                           FROM inventory_movements
+                           JOIN inventory ON inventory_movements.inventory_id = inventory.id
+                           JOIN products ON inventory.product_id = products.id
+                           WHERE inventory_movements.reference_type = 'worksheets'
+                             AND inventory_movements.reference_id = $1
+                             AND inventory_movements.movement_type = 'out'
+                             AND inventory_movements.receivable_id IS NULL)
+            SELECT items.source,
+                   items.source_id,
+                   items.service_id,
+                   items.product_id,
+                   items.description,
+                   items.quantity,
+                   items.unit_price,
+                   items.currency_code,
+                   COALESCE(taxes.id, items.tax_id)            AS tax_id,
+                   COALESCE(taxes.rate, 0)                    AS tax_rate,
+                   items.quantity * items.unit_price           AS net_amount,
+                   CASE
+                       WHEN taxes.is_rate_applicable
+                           THEN items.quantity * items.unit_price * COALESCE(taxes.rate, 0) / 100
+                       ELSE 0
+                   END                                         AS tax_amount
+            FROM items
+            LEFT JOIN taxes ON taxes.id = effective_tax_id(items.tax_id, $3)
+            ORDER BY items.source, items.sort_key
+            "#,
+        )
+        .bind(worksheet_id)
+        .bind(time_entry_service_id)
+        .bind(issue_date)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn bill(&self, invoice: &NewWorksheetInvoice, sub: Uuid) -> RepositoryResult<Receivable> {
+        let mut tx = self.begin().await?;
+        // NOTE: concurrent billing of the same worksheet waits here instead of billing items twice
+        sqlx::query("SELECT id FROM worksheets WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(invoice.worksheet_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let receivable = sqlx::query_as::<_, Receivable>(
+            r#"
+            INSERT INTO receivables (customer_id, document_number, issue_date, due_date,
+                                     currency_code, amount, created_by_id)
+            VALUES ($1,
+                    COALESCE($2, 'SZ-' || to_char($3::date, 'YYYY') || '-'
+                        || lpad(nextval('invoice_number_seq')::text, 5, '0')),
+                    $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(invoice.customer_id)
+        .bind(&invoice.document_number)
+        .bind(invoice.issue_date)
+        .bind(invoice.due_date)
+        .bind(&invoice.currency_code)
+        .bind(invoice.amount())
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (position, item) in invoice.items.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO receivable_lines (receivable_id, service_id, product_id, description,
+                                              quantity, unit_price, tax_id, tax_rate, net_amount,
+                                              tax_amount, position)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(receivable.id)
+            .bind(item.service_id)
+            .bind(item.product_id)
+            .bind(&item.description)
+            .bind(&item.quantity)
+            .bind(&item.unit_price)
+            .bind(item.tax_id)
+            .bind(&item.tax_rate)
+            .bind(&item.net_amount)
+            .bind(&item.tax_amount)
+            .bind(position as i32 + 1)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for table in ["tasks", "time_entries", "inventory_movements"] {
+            let ids: Vec<Uuid> = invoice
+                .items
+                .iter()
+                .filter(|item| item.source == table)
+                .map(|item| item.source_id)
+                .collect();
+            if ids.is_empty() {
+                continue;
+            }
+            let marked = sqlx::query(AssertSqlSafe(format!(
+                r#"
+                UPDATE {table}
+                SET receivable_id = $1
+                WHERE id = ANY($2)
+                    AND receivable_id IS NULL
+                "# // Security: constant
+            )))
+            .bind(receivable.id)
+            .bind(&ids)
+            .execute(&mut *tx)
+            .await?;
+            if marked.rows_affected() != ids.len() as u64 {
+                return Err(sqlx::Error::RowNotFound.into());
+            }
+        }
+
+        sqlx::query(
+            "INSERT INTO worksheet_receivables (worksheet_id, receivable_id) VALUES ($1, $2)",
+        )
+        .bind(invoice.worksheet_id)
+        .bind(receivable.id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(receivable)
+    }
+
+    async fn get_invoices(&self, worksheet_id: Uuid) -> RepositoryResult<Vec<Receivable>> {
+        Ok(sqlx::query_as::<_, Receivable>(
+            r#"
+            SELECT receivables.*
+            FROM worksheet_receivables
+            JOIN receivables ON worksheet_receivables.receivable_id = receivables.id
+            WHERE worksheet_receivables.worksheet_id = $1
+                AND receivables.deleted_at IS NULL
+            ORDER BY receivables.issue_date, receivables.document_number
+            "#,
+        )
+        .bind(worksheet_id)
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn duplicate(&self, params: &DuplicateParams, sub: Uuid) -> RepositoryResult<Worksheet> {
+        let mut tx = self.begin().await?;
+        let worksheet = sqlx::query_as::<_, Worksheet>(
+            r#"
+            INSERT INTO worksheets (name, description, customer_id, project_id, created_by_id, status,
+                                    worksheet_template_id, estimated_duration_minutes)
+            SELECT left(name || ' (másolat)', 255), description, customer_id, project_id, $2, status,
+                   worksheet_template_id, estimated_duration_minutes
+            FROM worksheets
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(params.id)
+        .bind(sub)
+        .fetch_one(&mut *tx)
+        .await?;
+        if params.include_lines {
+            sqlx::query(
+                r#"
+                INSERT INTO tasks (worksheet_id, service_id, currency_code, quantity, price, tax_id,
+                                   created_by_id, status, priority, due_date, description)
+                SELECT $1, service_id, currency_code, quantity, price, tax_id,
+                       $2, status, priority, due_date, description
+                FROM tasks
+                WHERE worksheet_id = $3 AND deleted_at IS NULL
+                ORDER BY created_at
+                "#,
+            )
+            .bind(worksheet.id)
+            .bind(sub)
+            .bind(params.id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO worksheet_checklist_items (worksheet_id, position, title)
+                SELECT $1, position, title
+                FROM worksheet_checklist_items
+                WHERE worksheet_id = $2
+                "#,
+            )
+            .bind(worksheet.id)
+            .bind(params.id)
+            .execute(&mut *tx)
+            .await?;
+            // NOTE: materials of products that can no longer be ordered are not carried over
+            sqlx::query(
+                r#"
+                INSERT INTO worksheet_planned_materials (worksheet_id, product_id, quantity)
+                SELECT $1, worksheet_planned_materials.product_id, worksheet_planned_materials.quantity
+                FROM worksheet_planned_materials
+                JOIN products ON worksheet_planned_materials.product_id = products.id
+                WHERE worksheet_planned_materials.worksheet_id = $2 AND products.status = 'active'
+                "#,
+            )
+            .bind(worksheet.id)
+            .bind(params.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        if params.include_tags {
+            copy_tags(&mut tx, "worksheets", params.id, worksheet.id, sub).await?;
+        }
+        tx.commit().await?;
+        Ok(worksheet)
+    }
 }
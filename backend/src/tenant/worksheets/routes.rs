@@ -36,7 +36,21 @@ pub fn routes<M: WorksheetsModuleInterface>(worksheets_module: Arc<M>) -> Router
             .route("/create", post(handler::create::<M>))
             .route("/update", put(handler::update::<M>))
             .route("/delete", delete(handler::delete::<M>))
+            .route("/duplicate", post(handler::duplicate::<M>))
             .route("/print", get(handler::print::<M>))
+            .route("/pdf", get(handler::pdf::<M>))
+            .route("/{id}/pdf", get(handler::pdf_by_id::<M>))
+            .route("/sign", post(handler::sign::<M>))
+            .route("/signature", get(handler::signature::<M>))
+            .route("/signature/image", get(handler::signature_image::<M>))
+            .route("/bill", post(handler::bill::<M>))
+            .route("/invoices", get(handler::invoices::<M>))
+            .route("/checklist", get(handler::checklist::<M>))
+            .route(
+                "/checklist/complete",
+                put(handler::complete_checklist_item::<M>),
+            )
+            .route("/planned_materials", get(handler::planned_materials::<M>))
             .layer(from_fn_with_state(worksheets_module.clone(), require_auth))
             .with_state(worksheets_module),
     )
@@ -17,31 +17,59 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::common::dto::PaginatorMeta;
+use crate::common::dto::{DuplicateParams, PaginatorMeta};
 use crate::common::error::RepositoryError;
 use crate::common::error::v2::{AppError, AppErrorVisibility};
+use crate::common::error_code::ErrorCode;
 use crate::common::model::SelectOption;
 #[double]
 use crate::common::pdf::PdfGenerator;
-use crate::common::pdf::{PdfGenError, PdfTemplates};
+use crate::common::pdf::{PdfGenError, PdfLogo, PdfTemplates};
 use crate::common::query_parser::ResourceQuery;
 use crate::common::service::{Service, ServiceError};
+use crate::common::storage::{FileStorage, StorageError};
+use crate::common::types::Money;
+use crate::tenant::customers::model::CreditLimitBreach;
+use crate::tenant::document_settings::dto::logo_extension;
+use crate::tenant::document_settings::repository::DocumentSettingsRepository;
+use crate::tenant::document_settings::service::letterhead_from_settings;
+use crate::tenant::exchange_rates::service::{ExchangeRatesServiceError, exchange_rate};
+use crate::tenant::project_members::model::ProjectAccess;
+use crate::tenant::project_members::service::{
+    can_access_project, ensure_worksheet_access, visible_projects_of,
+};
+use crate::tenant::projects::model::ARCHIVED_PROJECT_LOCKED;
+use crate::tenant::quotes::dto::DEFAULT_PAYMENT_TERM_DAYS;
+use crate::tenant::receivables::model::Receivable;
 use crate::tenant::worksheets::WorksheetsModuleInterface;
-use crate::tenant::worksheets::dto::print::WorksheetResolvedPrint;
+use crate::tenant::worksheets::dto::billing::{BillWorksheet, NewWorksheetInvoice};
+use crate::tenant::worksheets::dto::checklist::ChecklistItemCompletion;
+use crate::tenant::worksheets::dto::print::{
+    WorksheetDocumentContent, WorksheetDocumentPrint, WorksheetResolvedPrint,
+};
+use crate::tenant::worksheets::dto::signature::{
+    MAX_SIGNATURE_SIZE, NewWorksheetSignature, WorksheetSignaturePrint, WorksheetSignatureUpload,
+};
 use crate::tenant::worksheets::dto::user_input::WorksheetUserInput;
-use crate::tenant::worksheets::model::{Worksheet, WorksheetResolved};
+use crate::tenant::worksheets::model::{
+    Worksheet, WorksheetBillableItem, WorksheetChecklistItem, WorksheetPlannedMaterial,
+    WorksheetResolved, WorksheetSignature, WorksheetSignatureStatus,
+};
+use crate::tenant::worksheets::repository::WorksheetsRepository;
 use crate::tenant::worksheets::types::worksheet::{WorksheetFilterBy, WorksheetOrderBy};
 use axum::http::StatusCode;
-use chrono::{DateTime, Utc};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Days, Utc};
 use chrono_tz::Tz;
 use mockall_double::double;
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
-use tracing::Level;
+use tracing::{Level, error};
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -52,6 +80,9 @@ pub enum WorksheetsServiceError {
     #[error("Hozzáférés megtagadva!")]
     Unauthorized,
 
+    #[error("A művelet nem engedélyezett.")]
+    Forbidden,
+
     #[error("Hiba történt az adatok feldolgozása során: {0}")]
     UnprocessableEntry(&'static str),
 
@@ -66,6 +97,15 @@ pub enum WorksheetsServiceError {
 
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("{}", .0.message())]
+    CreditLimitExceeded(Box<CreditLimitBreach>),
+
+    #[error("{0}")]
+    ExchangeRates(#[from] ExchangeRatesServiceError),
 }
 
 impl From<ServiceError> for WorksheetsServiceError {
@@ -79,6 +119,7 @@ impl From<ServiceError> for WorksheetsServiceError {
 impl From<WorksheetsServiceError> for AppError {
     fn from(value: WorksheetsServiceError) -> Self {
         match value {
+            WorksheetsServiceError::ExchangeRates(error) => error.into(),
             WorksheetsServiceError::Unauthorized => Self::new(
                 Level::DEBUG,
                 StatusCode::UNAUTHORIZED,
@@ -86,6 +127,27 @@ impl From<WorksheetsServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            WorksheetsServiceError::Forbidden => Self::new(
+                Level::DEBUG,
+                ErrorCode::Forbidden.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::Forbidden.code(),
+                    "message": ErrorCode::Forbidden.description().hu
+                }),
+            ),
+            WorksheetsServiceError::CreditLimitExceeded(ref breach) => Self::new(
+                Level::DEBUG,
+                ErrorCode::CreditLimitExceeded.http_status(),
+                file!(),
+                AppErrorVisibility::UserFacing,
+                json!({
+                    "code": ErrorCode::CreditLimitExceeded.code(),
+                    "message": value.to_string(),
+                    "credit_limit": breach
+                }),
+            ),
             WorksheetsServiceError::UnprocessableEntry(_) => Self::new(
                 Level::DEBUG,
                 StatusCode::UNPROCESSABLE_ENTITY,
@@ -93,6 +155,15 @@ impl From<WorksheetsServiceError> for AppError {
                 AppErrorVisibility::UserFacing,
                 json!({"message": value.to_string()}),
             ),
+            WorksheetsServiceError::Repository(ref e) if e.is_project_archived_violation() => {
+                Self::new(
+                    Level::DEBUG,
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    file!(),
+                    AppErrorVisibility::UserFacing,
+                    json!({"message": WorksheetsServiceError::UnprocessableEntry(ARCHIVED_PROJECT_LOCKED).to_string()}),
+                )
+            }
             WorksheetsServiceError::Repository(RepositoryError::Database(
                 sqlx::Error::RowNotFound,
             )) => Self::new(
@@ -115,6 +186,123 @@ impl From<WorksheetsServiceError> for AppError {
 
 type WorksheetsServiceResult<T> = Result<T, WorksheetsServiceError>;
 
+const SIGNED_WORKSHEET_LOCKED: &str = "Az aláírt munkalap nem módosítható!";
+
+/// The item priced in `currency_code` at `rate`, with the net and tax amounts recalculated from
+/// the converted unit price
+/// The item with its net and tax amounts rounded to `currency_code`
+fn priced_item(item: WorksheetBillableItem, currency_code: &str) -> WorksheetBillableItem {
+    let net_amount = Money::line_net(&item.quantity, &item.unit_price, currency_code);
+    let tax_amount = if item.tax_amount.is_zero() {
+        Money::zero(currency_code)
+    } else {
+        net_amount.tax(&item.tax_rate)
+    };
+    WorksheetBillableItem {
+        currency_code: Some(currency_code.to_string()),
+        net_amount: net_amount.amount,
+        tax_amount: tax_amount.amount,
+        ..item
+    }
+}
+
+fn converted_item(
+    item: WorksheetBillableItem,
+    rate: &BigDecimal,
+    currency_code: &str,
+) -> WorksheetBillableItem {
+    let unit_price = Money::new(
+        item.unit_price.clone(),
+        item.currency_code.as_deref().unwrap_or_default(),
+    )
+    .convert(rate, currency_code);
+    priced_item(
+        WorksheetBillableItem {
+            unit_price: unit_price.amount,
+            ..item
+        },
+        currency_code,
+    )
+}
+
+async fn ensure_not_signed(
+    repo: &dyn WorksheetsRepository,
+    worksheet_id: Uuid,
+) -> WorksheetsServiceResult<()> {
+    if repo.get_signature(worksheet_id).await?.is_some() {
+        return Err(WorksheetsServiceError::UnprocessableEntry(
+            SIGNED_WORKSHEET_LOCKED,
+        ));
+    }
+    Ok(())
+}
+
+/// Renders the worksheet document with the letterhead, recipient and signature, access to the
+/// worksheet is checked by the caller
+pub(crate) async fn render_worksheet_document(
+    worksheets_repo: &(dyn WorksheetsRepository + Send + Sync),
+    document_settings_repo: &(dyn DocumentSettingsRepository + Send + Sync),
+    storage: &(dyn FileStorage + Send + Sync),
+    worksheet_resolved: WorksheetResolved,
+    tz: Tz,
+) -> Result<Vec<u8>, WorksheetsServiceError> {
+    let id = worksheet_resolved.id;
+    let signature = worksheets_repo.get_signature(id).await?;
+    let mut settings = document_settings_repo.get().await?;
+    let content = match settings.worksheet_layout.as_str() {
+        "summary" => None,
+        _ => Some(WorksheetDocumentContent {
+            tasks: worksheets_repo.get_document_tasks(id).await?,
+            materials: worksheets_repo.get_document_materials(id).await?,
+            time_entries: worksheets_repo.get_document_time_entries(id).await?,
+        }),
+    };
+    if settings.worksheet_hide_logo {
+        settings.logo_storage_key = None;
+    }
+    let mut letterhead = letterhead_from_settings(settings, storage).await;
+    let recipient = document_settings_repo
+        .get_recipient(worksheet_resolved.customer_id)
+        .await?;
+    let mut images = Vec::new();
+    if let Some(logo) = letterhead.logo.take() {
+        images.push(("logo", logo));
+    }
+    let signature_print = match signature {
+        Some(signature) => {
+            images.push((
+                "signature",
+                PdfLogo {
+                    data: storage.get(&signature.image_storage_key).await?,
+                    extension: logo_extension(&signature.image_content_type),
+                },
+            ));
+            Some(WorksheetSignaturePrint {
+                signer_name: signature.signer_name,
+                signed_at: signature
+                    .signed_at
+                    .with_timezone(&tz)
+                    .format("%Y. %m. %d. %H:%M")
+                    .to_string(),
+                content_hash: signature.content_hash,
+            })
+        }
+        None => None,
+    };
+    Ok(PdfGenerator::gen_pdf_document_with_images(
+        &PdfTemplates::WorksheetDocument,
+        vec![WorksheetDocumentPrint::new(
+            worksheet_resolved,
+            content,
+            letterhead,
+            recipient,
+            signature_print,
+            tz,
+        )],
+        images,
+    )?)
+}
+
 pub enum WorksheetsSelectLists {
     Customers,
 }
@@ -150,9 +338,27 @@ pub trait WorksheetService {
         payload: &WorksheetUserInput,
     ) -> impl Future<Output = WorksheetsServiceResult<Worksheet>> + Send;
     fn delete(&self, payload: Uuid) -> impl Future<Output = WorksheetsServiceResult<()>> + Send;
+    fn duplicate(
+        &self,
+        payload: &DuplicateParams,
+    ) -> impl Future<Output = WorksheetsServiceResult<Worksheet>> + Send;
+    fn get_checklist(
+        &self,
+        worksheet_id: Uuid,
+    ) -> impl Future<Output = WorksheetsServiceResult<Vec<WorksheetChecklistItem>>> + Send;
+    fn complete_checklist_item(
+        &self,
+        payload: &ChecklistItemCompletion,
+    ) -> impl Future<Output = WorksheetsServiceResult<WorksheetChecklistItem>> + Send;
+    fn get_planned_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> impl Future<Output = WorksheetsServiceResult<Vec<WorksheetPlannedMaterial>>> + Send;
     fn get_paged(
         &self,
         get_query: &ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
     ) -> impl Future<Output = WorksheetsServiceResult<(PaginatorMeta, Vec<WorksheetResolved>)>> + Send;
     fn print(
         &self,
@@ -162,13 +368,60 @@ pub trait WorksheetService {
         &self,
         path: &Path,
     ) -> impl Future<Output = WorksheetsServiceResult<()>> + Sync;
+    fn print_document(
+        &self,
+        id: Uuid,
+        tz: Tz,
+    ) -> impl Future<Output = WorksheetsServiceResult<Vec<u8>>> + Send;
+    fn sign(
+        &self,
+        payload: WorksheetSignatureUpload,
+    ) -> impl Future<Output = WorksheetsServiceResult<WorksheetSignature>> + Send;
+    fn get_signature(
+        &self,
+        worksheet_id: Uuid,
+    ) -> impl Future<Output = WorksheetsServiceResult<WorksheetSignatureStatus>> + Send;
+    fn get_signature_image(
+        &self,
+        worksheet_id: Uuid,
+    ) -> impl Future<Output = WorksheetsServiceResult<(String, Vec<u8>)>> + Send;
+    fn bill(
+        &self,
+        payload: &BillWorksheet,
+    ) -> impl Future<Output = WorksheetsServiceResult<Receivable>> + Send;
+    fn get_invoices(
+        &self,
+        worksheet_id: Uuid,
+    ) -> impl Future<Output = WorksheetsServiceResult<Vec<Receivable>>> + Send;
+    fn ensure_project_access(
+        &self,
+        project_id: Option<Uuid>,
+        access: ProjectAccess,
+    ) -> impl Future<Output = WorksheetsServiceResult<()>> + Send;
 }
 
 impl<'a, T> WorksheetService for Service<'a, T>
 where
     T: WorksheetsModuleInterface,
 {
+    async fn ensure_project_access(
+        &self,
+        project_id: Option<Uuid>,
+        access: ProjectAccess,
+    ) -> WorksheetsServiceResult<()> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(WorksheetsServiceError::Unauthorized)?;
+        if !can_access_project(self.module(), tenant_id, claims.sub(), project_id, access).await? {
+            return Err(WorksheetsServiceError::Forbidden);
+        }
+        Ok(())
+    }
+
     async fn insert(&self, payload: &WorksheetUserInput) -> WorksheetsServiceResult<Worksheet> {
+        self.ensure_project_access(payload.project_id.as_uuid(), ProjectAccess::Write)
+            .await?;
         Ok(self
             .module()
             .worksheets_repo(
@@ -198,7 +451,7 @@ where
         })
     }
     async fn get_resolved(&self, payload: Uuid) -> WorksheetsServiceResult<WorksheetResolved> {
-        Ok(self
+        let worksheet = self
             .module()
             .worksheets_repo(
                 self.claims()?
@@ -206,11 +459,14 @@ where
                     .ok_or(WorksheetsServiceError::Unauthorized)?,
             )?
             .get_resolved_by_id(payload)
-            .await?)
+            .await?;
+        self.ensure_project_access(worksheet.project_id, ProjectAccess::Read)
+            .await?;
+        Ok(worksheet)
     }
 
     async fn get(&self, payload: Uuid) -> WorksheetsServiceResult<Worksheet> {
-        Ok(self
+        let worksheet = self
             .module()
             .worksheets_repo(
                 self.claims()?
@@ -218,7 +474,10 @@ where
                     .ok_or(WorksheetsServiceError::Unauthorized)?,
             )?
             .get_by_id(payload)
-            .await?)
+            .await?;
+        self.ensure_project_access(worksheet.project_id, ProjectAccess::Read)
+            .await?;
+        Ok(worksheet)
     }
 
     async fn update(&self, payload: &WorksheetUserInput) -> WorksheetsServiceResult<Worksheet> {
@@ -227,6 +486,50 @@ where
                 "Az azonosító megadása kötelező!",
             ));
         }
+        let repo = self.module().worksheets_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetsServiceError::Unauthorized)?,
+        )?;
+        if let Some(id) = payload.id.as_uuid() {
+            ensure_worksheet_access(
+                self,
+                id,
+                ProjectAccess::Write,
+                WorksheetsServiceError::Forbidden,
+            )
+            .await?;
+            ensure_not_signed(&*repo, id).await?;
+        }
+        self.ensure_project_access(payload.project_id.as_uuid(), ProjectAccess::Write)
+            .await?;
+        Ok(repo.update(payload.clone()).await?)
+    }
+    async fn delete(&self, payload: Uuid) -> WorksheetsServiceResult<()> {
+        let repo = self.module().worksheets_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetsServiceError::Unauthorized)?,
+        )?;
+        ensure_worksheet_access(
+            self,
+            payload,
+            ProjectAccess::Write,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
+        ensure_not_signed(&*repo, payload).await?;
+        Ok(repo.delete_by_id(payload).await?)
+    }
+
+    async fn duplicate(&self, payload: &DuplicateParams) -> WorksheetsServiceResult<Worksheet> {
+        ensure_worksheet_access(
+            self,
+            payload.id,
+            ProjectAccess::Write,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
         Ok(self
             .module()
             .worksheets_repo(
@@ -234,10 +537,21 @@ where
                     .active_tenant()
                     .ok_or(WorksheetsServiceError::Unauthorized)?,
             )?
-            .update(payload.clone())
+            .duplicate(payload, self.claims()?.sub())
             .await?)
     }
-    async fn delete(&self, payload: Uuid) -> WorksheetsServiceResult<()> {
+
+    async fn get_checklist(
+        &self,
+        worksheet_id: Uuid,
+    ) -> WorksheetsServiceResult<Vec<WorksheetChecklistItem>> {
+        ensure_worksheet_access(
+            self,
+            worksheet_id,
+            ProjectAccess::Read,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
         Ok(self
             .module()
             .worksheets_repo(
@@ -245,14 +559,36 @@ where
                     .active_tenant()
                     .ok_or(WorksheetsServiceError::Unauthorized)?,
             )?
-            .delete_by_id(payload)
+            .get_checklist(worksheet_id)
             .await?)
     }
 
-    async fn get_paged(
+    async fn complete_checklist_item(
         &self,
-        get_query: &ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>,
-    ) -> WorksheetsServiceResult<(PaginatorMeta, Vec<WorksheetResolved>)> {
+        payload: &ChecklistItemCompletion,
+    ) -> WorksheetsServiceResult<WorksheetChecklistItem> {
+        Ok(self
+            .module()
+            .worksheets_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WorksheetsServiceError::Unauthorized)?,
+            )?
+            .set_checklist_item_completed(payload.id, payload.completed, self.claims()?.sub())
+            .await?)
+    }
+
+    async fn get_planned_materials(
+        &self,
+        worksheet_id: Uuid,
+    ) -> WorksheetsServiceResult<Vec<WorksheetPlannedMaterial>> {
+        ensure_worksheet_access(
+            self,
+            worksheet_id,
+            ProjectAccess::Read,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
         Ok(self
             .module()
             .worksheets_repo(
@@ -260,7 +596,25 @@ where
                     .active_tenant()
                     .ok_or(WorksheetsServiceError::Unauthorized)?,
             )?
-            .get_paged(get_query)
+            .get_planned_materials(worksheet_id)
+            .await?)
+    }
+
+    async fn get_paged(
+        &self,
+        get_query: &ResourceQuery<WorksheetOrderBy, WorksheetFilterBy>,
+        include_archived: bool,
+        tags: Option<Vec<Uuid>>,
+    ) -> WorksheetsServiceResult<(PaginatorMeta, Vec<WorksheetResolved>)> {
+        let claims = self.claims()?;
+        let tenant_id = claims
+            .active_tenant()
+            .ok_or(WorksheetsServiceError::Unauthorized)?;
+        let visible_to = visible_projects_of(self.module(), tenant_id, claims.sub()).await?;
+        Ok(self
+            .module()
+            .worksheets_repo(tenant_id)?
+            .get_paged(get_query, visible_to, include_archived, tags)
             .await?)
     }
 
@@ -270,6 +624,296 @@ where
             payload.to_vec(),
         )?)
     }
+    async fn print_document(&self, id: Uuid, tz: Tz) -> WorksheetsServiceResult<Vec<u8>> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(WorksheetsServiceError::Unauthorized)?;
+        let worksheets_repo = self.module().worksheets_repo(tenant_id)?;
+        let worksheet_resolved = worksheets_repo.get_resolved_by_id(id).await?;
+        self.ensure_project_access(worksheet_resolved.project_id, ProjectAccess::Read)
+            .await?;
+        render_worksheet_document(
+            &*worksheets_repo,
+            &*self.module().document_settings_repo(tenant_id)?,
+            &*self.module().file_storage(),
+            worksheet_resolved,
+            tz,
+        )
+        .await
+    }
+    async fn sign(
+        &self,
+        payload: WorksheetSignatureUpload,
+    ) -> WorksheetsServiceResult<WorksheetSignature> {
+        let signer_name = payload.signer_name.trim().to_string();
+        if signer_name.is_empty() || signer_name.chars().count() > 255 {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "Az aláíró neve nem lehet üres és legfeljebb 255 karakter lehet!",
+            ));
+        }
+        if payload.data.len() > MAX_SIGNATURE_SIZE {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "Az aláírás képe legfeljebb 512 KB lehet!",
+            ));
+        }
+        let (content_type, extension) =
+            payload
+                .format()
+                .ok_or(WorksheetsServiceError::UnprocessableEntry(
+                    "Az aláírás csak PNG vagy JPEG kép lehet!",
+                ))?;
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(WorksheetsServiceError::Unauthorized)?;
+        let repo = self.module().worksheets_repo(tenant_id)?;
+        let worksheet = repo.get_resolved_by_id(payload.worksheet_id).await?;
+        self.ensure_project_access(worksheet.project_id, ProjectAccess::Write)
+            .await?;
+        if worksheet.status != "completed" {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "Csak befejezett munkalap írható alá!",
+            ));
+        }
+        ensure_not_signed(&*repo, worksheet.id).await?;
+        let content_hash = worksheet.content_hash(&repo.get_content_tasks(worksheet.id).await?);
+        let storage_key = format!(
+            "{tenant_id}/worksheets/{}/signature_{}.{extension}",
+            worksheet.id,
+            Uuid::new_v4()
+        );
+        let storage = self.module().file_storage();
+        storage
+            .put(&storage_key, content_type, payload.data)
+            .await?;
+        match repo
+            .insert_signature(
+                &NewWorksheetSignature {
+                    worksheet_id: worksheet.id,
+                    signer_name,
+                    image_storage_key: storage_key.clone(),
+                    image_content_type: content_type.to_string(),
+                    content_hash,
+                },
+                self.claims()?.sub(),
+            )
+            .await
+        {
+            Ok(signature) => Ok(signature),
+            Err(e) => {
+                if let Err(e) = storage.delete(&storage_key).await {
+                    error!("Could not remove signature file {}: {}", storage_key, e);
+                }
+                if e.is_unique_violation() {
+                    return Err(WorksheetsServiceError::UnprocessableEntry(
+                        SIGNED_WORKSHEET_LOCKED,
+                    ));
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn bill(&self, payload: &BillWorksheet) -> WorksheetsServiceResult<Receivable> {
+        let tenant_id = self
+            .claims()?
+            .active_tenant()
+            .ok_or(WorksheetsServiceError::Unauthorized)?;
+        let repo = self.module().worksheets_repo(tenant_id)?;
+        let worksheet = repo.get_by_id(payload.worksheet_id).await?;
+        self.ensure_project_access(worksheet.project_id, ProjectAccess::Write)
+            .await?;
+        let document_number = match payload.document_number.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(number) if number.chars().count() > 100 => {
+                return Err(WorksheetsServiceError::UnprocessableEntry(
+                    "A bizonylatszám legfeljebb 100 karakter lehet!",
+                ));
+            }
+            Some(number) => Some(number.to_string()),
+        };
+        let issue_date = payload
+            .issue_date
+            .unwrap_or_else(|| Utc::now().date_naive());
+        let due_date = match payload.due_date {
+            Some(due_date) => due_date,
+            None => issue_date
+                .checked_add_days(Days::new(DEFAULT_PAYMENT_TERM_DAYS))
+                .ok_or(WorksheetsServiceError::UnprocessableEntry(
+                    "Hibás kiállítási dátum!",
+                ))?,
+        };
+        if due_date < issue_date {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "A fizetési határidő nem lehet korábbi a kiállítás dátumánál!",
+            ));
+        }
+        let items = repo
+            .get_billable_items(worksheet.id, payload.time_entry_service_id, issue_date)
+            .await?;
+        let Some(currency_code) = payload
+            .currency_code
+            .clone()
+            .or_else(|| items.iter().find_map(|item| item.currency_code.clone()))
+        else {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "A munkalapon nincs számlázható tétel!",
+            ));
+        };
+        let items: Vec<_> = if payload.convert_currencies {
+            let exchange_rates_repo = self.module().exchange_rates_repo(tenant_id)?;
+            let mut rates: HashMap<String, BigDecimal> = HashMap::new();
+            let mut converted = Vec::with_capacity(items.len());
+            for item in items {
+                match item.currency_code.clone() {
+                    Some(item_currency_code) if item_currency_code == currency_code => {
+                        converted.push(priced_item(item, &currency_code))
+                    }
+                    Some(item_currency_code) => {
+                        if !rates.contains_key(&item_currency_code) {
+                            let lookup = exchange_rate(
+                                &*exchange_rates_repo,
+                                &item_currency_code,
+                                &currency_code,
+                                issue_date,
+                            )
+                            .await?;
+                            rates.insert(item_currency_code.clone(), lookup.rate);
+                        }
+                        converted.push(converted_item(
+                            item,
+                            &rates[&item_currency_code],
+                            &currency_code,
+                        ));
+                    }
+                    None => {}
+                }
+            }
+            converted
+        } else {
+            items
+                .into_iter()
+                .filter(|item| item.currency_code.as_deref() == Some(currency_code.as_str()))
+                .map(|item| priced_item(item, &currency_code))
+                .collect()
+        };
+        if items.iter().any(|item| item.tax_id.is_none()) {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "A munkaidő szolgáltatásához nincs alapértelmezett adó megadva!",
+            ));
+        }
+        let invoice = NewWorksheetInvoice {
+            worksheet_id: worksheet.id,
+            customer_id: worksheet.customer_id,
+            document_number,
+            issue_date,
+            due_date,
+            currency_code,
+            items,
+        };
+        if invoice.amount() <= BigDecimal::zero() {
+            return Err(WorksheetsServiceError::UnprocessableEntry(
+                "A munkalapon nincs számlázható tétel!",
+            ));
+        }
+        if let Some(breach) = self
+            .module()
+            .customers_repo(tenant_id)?
+            .get_credit_exposure(invoice.customer_id)
+            .await?
+            .check(
+                &invoice.currency_code,
+                &invoice.amount(),
+                payload.acknowledge_credit_limit,
+            )
+        {
+            return Err(WorksheetsServiceError::CreditLimitExceeded(Box::new(
+                breach,
+            )));
+        }
+        repo.bill(&invoice, self.claims()?.sub())
+            .await
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    WorksheetsServiceError::UnprocessableEntry(
+                        "A megadott bizonylatszám már foglalt!",
+                    )
+                } else {
+                    e.into()
+                }
+            })
+    }
+
+    async fn get_invoices(&self, worksheet_id: Uuid) -> WorksheetsServiceResult<Vec<Receivable>> {
+        let repo = self.module().worksheets_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetsServiceError::Unauthorized)?,
+        )?;
+        let worksheet = repo.get_by_id(worksheet_id).await?;
+        self.ensure_project_access(worksheet.project_id, ProjectAccess::Read)
+            .await?;
+        Ok(repo.get_invoices(worksheet_id).await?)
+    }
+
+    async fn get_signature(
+        &self,
+        worksheet_id: Uuid,
+    ) -> WorksheetsServiceResult<WorksheetSignatureStatus> {
+        ensure_worksheet_access(
+            self,
+            worksheet_id,
+            ProjectAccess::Read,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
+        let repo = self.module().worksheets_repo(
+            self.claims()?
+                .active_tenant()
+                .ok_or(WorksheetsServiceError::Unauthorized)?,
+        )?;
+        let signature = repo
+            .get_signature(worksheet_id)
+            .await?
+            .ok_or(RepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let worksheet = repo.get_resolved_by_id(worksheet_id).await?;
+        let content_hash = worksheet.content_hash(&repo.get_content_tasks(worksheet_id).await?);
+        Ok(WorksheetSignatureStatus {
+            content_unchanged: content_hash == signature.content_hash,
+            signature,
+        })
+    }
+
+    async fn get_signature_image(
+        &self,
+        worksheet_id: Uuid,
+    ) -> WorksheetsServiceResult<(String, Vec<u8>)> {
+        ensure_worksheet_access(
+            self,
+            worksheet_id,
+            ProjectAccess::Read,
+            WorksheetsServiceError::Forbidden,
+        )
+        .await?;
+        let signature = self
+            .module()
+            .worksheets_repo(
+                self.claims()?
+                    .active_tenant()
+                    .ok_or(WorksheetsServiceError::Unauthorized)?,
+            )?
+            .get_signature(worksheet_id)
+            .await?
+            .ok_or(RepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let data = self
+            .module()
+            .file_storage()
+            .get(&signature.image_storage_key)
+            .await?;
+        Ok((signature.image_content_type, data))
+    }
+
     async fn print_snapshot(&self, path: &Path) -> WorksheetsServiceResult<()> {
         let test_time: DateTime<Utc> = "2026-01-02T11:11:11Z"
             .parse()
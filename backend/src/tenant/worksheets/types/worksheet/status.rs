@@ -42,6 +42,7 @@ impl ValueObjectData for Status {
         match self.0.as_str() {
             "active" => Ok(()),
             "inactive" => Ok(()),
+            "completed" => Ok(()),
             _ => Err(ValueObjectError::InvalidInput(Self::VALIDATION_ERROR)),
         }
     }
@@ -0,0 +1,84 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+
+#import "document_base.typ": document_header, footer_note, info_table, recipient_block, totals_table
+
+#set page(numbering: "1/1", margin: (x: 1.8cm, y: 2cm))
+#set text(size: 10pt, lang: "hu")
+
+#let statements = json(bytes(sys.inputs.at("payload", default: "[]")))
+
+#for statement in statements [
+  #document_header(statement.letterhead, "Folyószámla-kivonat", statement.period)
+
+  #v(0.6cm)
+
+  #grid(
+    columns: (1fr, 1fr),
+    gutter: 1cm,
+    recipient_block(statement.recipient),
+    info_table((
+      ("Időszak", statement.period),
+      ("Egyenleg dátuma", statement.as_of),
+    )),
+  )
+
+  #if statement.currencies.len() == 0 [
+    #v(0.8cm)
+    A megadott időszakban nincs kiállított számla és nyitott tétel.
+  ]
+
+  #for currency in statement.currencies [
+    #v(0.8cm)
+
+    #text(size: 13pt, weight: "bold")[#currency.currency_code]
+
+    #table(
+      columns: (1fr, auto, auto, auto, auto, auto, auto),
+      align: (left, right, right, right, right, right, right),
+      fill: (_, y) => if y == 0 { rgb("E6E6E6") } else if calc.even(y) { rgb("F7F7F7") },
+      stroke: none,
+      inset: 6pt,
+      table.header(
+        [*Sorszám*], [*Kelt*], [*Határidő*], [*Összeg*], [*Kiegyenlítve*], [*Nyitott*], [*Késés (nap)*],
+      ),
+      ..currency.items.map(item => (
+        item.document_number,
+        item.issue_date,
+        item.due_date,
+        item.amount,
+        item.settled_amount,
+        item.open_balance,
+        item.days_overdue,
+      )).flatten(),
+    )
+
+    #totals_table((
+      ("Korábbi nyitott tételek", currency.earlier_balance),
+      ("Időszakban számlázva", currency.invoiced_total),
+      ("Időszak nyitott tételei", currency.period_balance),
+      ([*Fizetendő egyenleg*], [*#currency.closing_balance*]),
+    ))
+  ]
+
+  #footer_note(statement.letterhead)
+
+  #pagebreak(weak: true)
+]
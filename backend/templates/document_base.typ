@@ -0,0 +1,96 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+// Shared building blocks of the customer facing documents. The optional `logo`
+// input holds an absolute path, the generator sets the typst root to "/" for it.
+
+#let value_or(obj, key, default: "") = {
+  let value = obj.at(key, default: none)
+  if value == none or value == "" { default } else { value }
+}
+
+#let issuer_block(issuer) = {
+  let logo = sys.inputs.at("logo", default: none)
+  if logo != none {
+    image(logo, height: 1.6cm)
+    v(0.2cm)
+  }
+  text(size: 12pt, weight: "bold")[#value_or(issuer, "company_name")]
+  linebreak()
+  for (label, key) in (
+    ("", "address"),
+    ("Adószám: ", "tax_number"),
+    ("Bankszámla: ", "bank_account"),
+    ("E-mail: ", "email"),
+    ("Telefon: ", "phone_number"),
+  ) {
+    let value = value_or(issuer, key)
+    if value != "" {
+      [#label#value]
+      linebreak()
+    }
+  }
+}
+
+#let document_header(issuer, title, number) = grid(
+  columns: (1fr, auto),
+  gutter: 1cm,
+  issuer_block(issuer),
+  align(right)[
+    #text(size: 18pt, weight: "bold")[#title] \
+    #text(size: 11pt)[#number]
+  ],
+)
+
+#let recipient_block(recipient) = block(
+  width: 100%,
+  inset: 8pt,
+  stroke: 0.5pt + rgb("BFBFBF"),
+  [
+    *Vevő* \
+    #value_or(recipient, "name") \
+    #value_or(recipient, "email") \
+    #value_or(recipient, "phone_number")
+  ],
+)
+
+#let info_table(rows) = table(
+  columns: (auto, 1fr),
+  stroke: none,
+  inset: 4pt,
+  ..rows.map(((label, value)) => ([*#label*], [#value])).flatten(),
+)
+
+#let totals_table(rows) = align(right, table(
+  columns: (auto, auto),
+  align: (left, right),
+  stroke: none,
+  inset: 4pt,
+  ..rows.map(((label, value)) => ([#label], [#value])).flatten(),
+))
+
+#let footer_note(issuer) = {
+  let note = value_or(issuer, "footer_note")
+  if note != "" {
+    v(1fr)
+    line(length: 100%, stroke: 0.5pt + rgb("BFBFBF"))
+    text(size: 8pt)[#note]
+  }
+}
+
@@ -0,0 +1,66 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#import "document_base.typ": document_header, footer_note, info_table, recipient_block, totals_table, value_or
+
+#set page(numbering: "1/1", margin: (x: 1.8cm, y: 2cm))
+#set text(size: 10pt, lang: "hu")
+
+#let invoices = json(bytes(sys.inputs.at("payload", default: "[]")))
+
+#for invoice in invoices [
+  #document_header(invoice.letterhead, "Számla", invoice.document_number)
+
+  #v(0.6cm)
+
+  #grid(
+    columns: (1fr, 1fr),
+    gutter: 1cm,
+    recipient_block(invoice.recipient),
+    info_table((
+      ("Kelt", invoice.issue_date),
+      ("Fizetési határidő", invoice.due_date),
+      ("Pénznem", invoice.currency_code),
+      ("Állapot", invoice.status),
+    )),
+  )
+
+  #v(0.8cm)
+
+  #totals_table((
+    ([*Végösszeg*], [*#invoice.amount*]),
+    ("Kiegyenlítve", invoice.paid_amount),
+    ([*Fizetendő*], [*#invoice.open_balance*]),
+  ))
+
+  #for legal_text in invoice.legal_texts [
+    #text(size: 9pt)[#legal_text]
+  ]
+
+  #v(0.6cm)
+
+  #if value_or(invoice.letterhead, "bank_account") != "" [
+    Kérjük, az összeget a #value_or(invoice.letterhead, "bank_account") bankszámlaszámra
+    utalja, a közleményben a számla sorszámának (#invoice.document_number) feltüntetésével.
+  ]
+
+  #footer_note(invoice.letterhead)
+
+  #pagebreak(weak: true)
+]
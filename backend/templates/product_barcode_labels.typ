@@ -0,0 +1,55 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#set page(paper: "a4", margin: (x: 8mm, y: 10mm))
+#set text(size: 8pt)
+
+#let labels = json(bytes(sys.inputs.at("payload", default: "[]")))
+
+// NOTE: long Code128 values are narrowed to fit the label instead of overflowing it
+#let bars(modules) = layout(size => {
+  let width = calc.min(0.33mm, size.width / modules.len())
+  box(height: 14mm, {
+    for module in modules.clusters() {
+      box(width: width, height: 100%, fill: if module == "1" { black } else { none })
+    }
+  })
+})
+
+#let label(item) = block(
+  width: 100%,
+  height: 36mm,
+  inset: 3mm,
+  stroke: 0.2pt + luma(200),
+  breakable: false,
+  align(center)[
+    #text(weight: "bold")[#item.name]
+    #if item.at("sku", default: none) != none [ \ #item.sku ]
+    #v(1mm)
+    #bars(item.modules)
+    #v(0.5mm)
+    #text(font: "DejaVu Sans Mono")[#item.barcode]
+  ],
+)
+
+#grid(
+  columns: (1fr, 1fr, 1fr),
+  gutter: 0mm,
+  ..labels.map(label),
+)
@@ -0,0 +1,83 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#import "document_base.typ": document_header, footer_note, info_table, recipient_block, totals_table
+
+#set page(numbering: "1/1", margin: (x: 1.8cm, y: 2cm))
+#set text(size: 10pt, lang: "hu")
+
+#let quotes = json(bytes(sys.inputs.at("payload", default: "[]")))
+
+#for quote in quotes [
+  #document_header(quote.letterhead, "Árajánlat", quote.quote_number)
+
+  #v(0.6cm)
+
+  #grid(
+    columns: (1fr, 1fr),
+    gutter: 1cm,
+    recipient_block(quote.recipient),
+    info_table((
+      ("Kelt", quote.issue_date),
+      ("Érvényes", quote.valid_until),
+      ("Pénznem", quote.currency_code),
+    )),
+  )
+
+  #v(0.6cm)
+
+  #text(size: 13pt, weight: "bold")[#quote.title]
+
+  #if quote.notes != none [
+    #quote.notes
+  ]
+
+  #v(0.4cm)
+
+  #table(
+    columns: (1fr, auto, auto, auto, auto, auto),
+    align: (left, right, right, right, right, right),
+    fill: (_, y) => if y == 0 { rgb("E6E6E6") } else if calc.even(y) { rgb("F7F7F7") },
+    stroke: none,
+    inset: 6pt,
+    table.header([*Megnevezés*], [*Mennyiség*], [*Egységár*], [*ÁFA*], [*Nettó*], [*Bruttó*]),
+    ..quote.lines.map(item => (
+      item.description,
+      item.quantity,
+      item.unit_price,
+      item.tax_rate,
+      item.net_amount,
+      item.gross_amount,
+    )).flatten(),
+  )
+
+  #totals_table((
+    ("Nettó összesen", quote.net_total),
+    ("ÁFA összesen", quote.tax_total),
+    ([*Bruttó összesen*], [*#quote.gross_total*]),
+  ))
+
+  #for legal_text in quote.legal_texts [
+    #text(size: 9pt)[#legal_text]
+  ]
+
+  #footer_note(quote.letterhead)
+
+  #pagebreak(weak: true)
+]
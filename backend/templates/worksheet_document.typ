@@ -0,0 +1,161 @@
+/*
+ * This file is part of the Obvia ERP.
+ *
+ * Copyright (C) 2026 Kovács Dávid <kapcsolat@kovacsdavid.dev>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#import "document_base.typ": document_header, footer_note, info_table, recipient_block, totals_table
+
+#set page(numbering: "1/1", margin: (x: 1.8cm, y: 2cm))
+#set text(size: 10pt, lang: "hu")
+
+#let worksheets = json(bytes(sys.inputs.at("payload", default: "[]")))
+
+#let line_table(title, lines) = if lines.len() > 0 [
+  #v(0.6cm)
+  #text(size: 11pt, weight: "bold")[#title]
+  #table(
+    columns: (1fr, auto, auto, auto, auto, auto),
+    align: (left, right, right, right, right, right),
+    fill: (_, y) => if y == 0 { rgb("E6E6E6") } else if calc.even(y) { rgb("F7F7F7") },
+    stroke: none,
+    inset: 6pt,
+    table.header([*Megnevezés*], [*Mennyiség*], [*Egységár*], [*ÁFA*], [*Nettó*], [*Bruttó*]),
+    ..lines.map(item => (
+      if item.description != none [#item.name \ #text(size: 8pt)[#item.description]] else [#item.name],
+      item.quantity,
+      item.unit_price,
+      item.tax_rate,
+      item.net_amount,
+      item.gross_amount,
+    )).flatten(),
+  )
+]
+
+#for worksheet in worksheets [
+  #document_header(worksheet.letterhead, "Munkalap", worksheet.name)
+
+  #v(0.6cm)
+
+  #grid(
+    columns: (1fr, 1fr),
+    gutter: 1cm,
+    recipient_block(worksheet.recipient),
+    info_table((
+      ("Kelt", worksheet.created_at),
+      ("Projekt", worksheet.project),
+      ("Állapot", worksheet.status),
+    )),
+  )
+
+  #if worksheet.description != none [
+    #v(0.4cm)
+    #worksheet.description
+  ]
+
+  #if worksheet.detailed [
+    #line_table("Elvégzett munkák", worksheet.tasks)
+    #line_table("Felhasznált anyagok", worksheet.materials)
+
+    #if worksheet.time_entries.len() > 0 [
+      #v(0.6cm)
+      #text(size: 11pt, weight: "bold")[Munkaidő]
+      #table(
+        columns: (auto, auto, 1fr, auto, auto),
+        align: (left, left, left, right, center),
+        fill: (_, y) => if y == 0 { rgb("E6E6E6") } else if calc.even(y) { rgb("F7F7F7") },
+        stroke: none,
+        inset: 6pt,
+        table.header([*Kezdés*], [*Munkatárs*], [*Feladat*], [*Időtartam*], [*Számlázható*]),
+        ..worksheet.time_entries.map(entry => (
+          entry.started_at,
+          entry.user_name,
+          if entry.description != none [#entry.task \ #text(size: 8pt)[#entry.description]] else [#entry.task],
+          entry.duration,
+          entry.billable,
+        )).flatten(),
+        table.cell(colspan: 3, align: right)[*Összesen*], [*#worksheet.total_time*], [],
+      )
+    ]
+
+    #if worksheet.tax_summary.len() > 0 [
+      #v(0.6cm)
+      #text(size: 11pt, weight: "bold")[ÁFA összesítő]
+      #table(
+        columns: (1fr, auto, auto, auto),
+        align: (left, right, right, right),
+        fill: (_, y) => if y == 0 { rgb("E6E6E6") },
+        stroke: none,
+        inset: 6pt,
+        table.header([*Adó*], [*Nettó*], [*ÁFA*], [*Bruttó*]),
+        ..worksheet.tax_summary.map(item => (
+          item.tax,
+          item.net_amount,
+          item.tax_amount,
+          item.gross_amount,
+        )).flatten(),
+      )
+    ]
+  ]
+
+  #v(0.6cm)
+
+  #table(
+    columns: (1fr, auto, auto),
+    align: (left, right, right),
+    fill: (_, y) => if y == 0 { rgb("E6E6E6") },
+    stroke: none,
+    inset: 6pt,
+    table.header([*Tétel*], [*Nettó*], [*Bruttó*]),
+    [Anyagköltség], worksheet.net_material_cost, worksheet.gross_material_cost,
+    [Munkadíj], worksheet.net_work_cost, worksheet.gross_work_cost,
+  )
+
+  #totals_table((
+    ("Nettó összesen", worksheet.net_total),
+    ("ÁFA összesen", worksheet.tax_total),
+    ([*Bruttó összesen*], [*#worksheet.gross_total*]),
+  ))
+
+  #v(1.5cm)
+
+  // NOTE: the signature image is only passed for a signed worksheet
+  #let signature_image = sys.inputs.at("signature", default: none)
+
+  #grid(
+    columns: (1fr, 1fr),
+    gutter: 2cm,
+    align(center + bottom)[#line(length: 100%) Munkát végző],
+    align(center + bottom)[
+      #if signature_image != none [
+        #image(signature_image, height: 1.5cm)
+      ]
+      #line(length: 100%) Megrendelő
+      #if worksheet.signature != none [
+        \ #worksheet.signature.signer_name, #worksheet.signature.signed_at
+      ]
+    ],
+  )
+
+  #if worksheet.signature != none [
+    #v(0.3cm)
+    #text(size: 7pt, fill: rgb("666666"))[Aláírt tartalom ellenőrző kódja (SHA-256): #worksheet.signature.content_hash]
+  ]
+
+  #footer_note(worksheet.letterhead)
+
+  #pagebreak(weak: true)
+]